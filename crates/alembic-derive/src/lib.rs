@@ -0,0 +1,119 @@
+//! `#[derive(AbcStruct)]` for the `alembic` crate.
+//!
+//! Maps each named field of a struct to a property of the same name in a
+//! compound (`.arbGeomParams`, `.userProperties`, or any nested compound),
+//! generating `alembic::geom::AbcStruct` (read/write the fields) and
+//! `alembic::geom::AbcProperty` (so the struct nests as a sub-compound).
+//!
+//! Field attributes:
+//! - `#[abc(rename = "name")]` - property name (default: the field name)
+//! - `#[abc(skip)]` - not stored; filled with `Default::default()` on read
+//! - `#[abc(scope = "vtx")]` - `geoScope` written into the property metadata
+//!   (`con`, `uni`, `var`, `vtx`, `fvr`), for arbitrary geometry params
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+#[proc_macro_derive(AbcStruct, attributes(abc))]
+pub fn derive_abc_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Parsed `#[abc(...)]` options of one field.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    scope: Option<String>,
+}
+
+const SCOPES: &[&str] = &["con", "uni", "var", "vtx", "fvr"];
+
+fn field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("abc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                attrs.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+            } else if meta.path.is_ident("scope") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                if !SCOPES.contains(&lit.value().as_str()) {
+                    return Err(syn::Error::new(lit.span(), "expected one of: con, uni, var, vtx, fvr"));
+                }
+                attrs.scope = Some(lit.value());
+            } else {
+                return Err(meta.error("unknown abc attribute (expected rename, skip or scope)"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "AbcStruct requires named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "AbcStruct can only be derived for structs")),
+    };
+
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let attrs = field_attrs(field)?;
+        if attrs.skip {
+            reads.push(quote! { #ident: ::core::default::Default::default() });
+            continue;
+        }
+        let name = attrs.rename.unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+        reads.push(quote! {
+            #ident: <#ty as ::alembic::geom::AbcProperty>::read_property(compound, #name, index)?
+        });
+        writes.push(quote! {
+            ::alembic::geom::AbcProperty::write_property(&self.#ident, compound, #name);
+        });
+        if let Some(scope) = attrs.scope {
+            writes.push(quote! { ::alembic::geom::typed::set_geo_scope(compound, #name, #scope); });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::alembic::geom::AbcStruct for #ident #ty_generics #where_clause {
+            fn read_compound(
+                compound: &::alembic::abc::ICompoundProperty<'_>,
+                index: usize,
+            ) -> ::alembic::Result<Self> {
+                ::core::result::Result::Ok(Self { #(#reads,)* })
+            }
+
+            fn write_compound(&self, compound: &mut ::alembic::ogawa::writer::OProperty) {
+                #(#writes)*
+            }
+        }
+
+        impl #impl_generics ::alembic::geom::AbcProperty for #ident #ty_generics #where_clause {
+            fn read_property(
+                compound: &::alembic::abc::ICompoundProperty<'_>,
+                name: &str,
+                index: usize,
+            ) -> ::alembic::Result<Self> {
+                ::alembic::geom::typed::read_nested(compound, name, index)
+            }
+
+            fn write_property(&self, compound: &mut ::alembic::ogawa::writer::OProperty, name: &str) {
+                ::alembic::geom::typed::write_nested(self, compound, name)
+            }
+        }
+    })
+}
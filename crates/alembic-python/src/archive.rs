@@ -6,7 +6,7 @@ use pyo3::prelude::*;
 use pyo3::exceptions::{PyIOError, PyValueError};
 use std::sync::Arc;
 
-use crate::abc::IArchive;
+use alembic::abc::IArchive;
 use super::time_sampling::PyTimeSampling;
 
 /// Python wrapper for IArchive (read-only archive).
@@ -0,0 +1,143 @@
+//! Array arguments of the write API.
+//!
+//! Sample data (positions, normals, counts, indices, ids) is accepted as
+//! numpy arrays as well as plain Python sequences. Anything exposing the
+//! buffer protocol (numpy, `array.array`, `memoryview`) is copied straight
+//! out of its buffer, without creating a Python object per element;
+//! float32/float64 and int32/int64/uint32 buffers are converted as
+//! needed. Vector data may be `(N, 3)` or flat with a multiple of 3 values.
+//! Other objects fall back to the regular sequence conversion.
+//!
+//! ```python
+//! P = np.asarray(points, dtype=np.float32)       # (N, 3)
+//! mesh.addSample(P, counts, indices)             # counts/indices: int32 or int64
+//! ```
+
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Flat `f32` contents and shape of a float32/float64 buffer.
+fn float_buffer(obj: &Bound<'_, PyAny>) -> PyResult<Option<(Vec<f32>, Vec<usize>)>> {
+    let py = obj.py();
+    if let Ok(buf) = PyBuffer::<f32>::get(obj) {
+        return Ok(Some((buf.to_vec(py)?, buf.shape().to_vec())));
+    }
+    if let Ok(buf) = PyBuffer::<f64>::get(obj) {
+        let values = buf.to_vec(py)?.into_iter().map(|v| v as f32).collect();
+        return Ok(Some((values, buf.shape().to_vec())));
+    }
+    Ok(None)
+}
+
+/// Flat `i64` contents of an integer buffer.
+fn int_buffer(obj: &Bound<'_, PyAny>) -> PyResult<Option<Vec<i64>>> {
+    let py = obj.py();
+    if let Ok(buf) = PyBuffer::<i32>::get(obj) {
+        return Ok(Some(buf.to_vec(py)?.into_iter().map(i64::from).collect()));
+    }
+    if let Ok(buf) = PyBuffer::<i64>::get(obj) {
+        return Ok(Some(buf.to_vec(py)?));
+    }
+    if let Ok(buf) = PyBuffer::<u32>::get(obj) {
+        return Ok(Some(buf.to_vec(py)?.into_iter().map(i64::from).collect()));
+    }
+    Ok(None)
+}
+
+/// Split flat floats into `N`-vectors, checking an `(M, N)` or flat shape.
+fn chunked<const N: usize>(values: Vec<f32>, shape: &[usize]) -> PyResult<Vec<[f32; N]>> {
+    let shape_ok = match shape {
+        [_] => values.len().is_multiple_of(N),
+        [_, n] => *n == N,
+        _ => false,
+    };
+    if !shape_ok {
+        return Err(PyValueError::new_err(format!(
+            "expected an array of shape (n, {}) or a flat array of multiples of {}, got shape {:?}",
+            N, N, shape
+        )));
+    }
+    Ok(values.chunks_exact(N).map(|c| std::array::from_fn(|i| c[i])).collect())
+}
+
+/// `(N, 3)` float data: positions, normals, velocities.
+pub struct Vec3Array(pub Vec<glam::Vec3>);
+
+impl FromPyObject<'_, '_> for Vec3Array {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let vecs: Vec<[f32; 3]> = match float_buffer(&obj)? {
+            Some((values, shape)) => chunked(values, &shape)?,
+            None => obj.extract()?,
+        };
+        Ok(Self(vecs.into_iter().map(glam::Vec3::from).collect()))
+    }
+}
+
+/// `(N, 2)` float data: uvs.
+pub struct Vec2Array(pub Vec<glam::Vec2>);
+
+impl FromPyObject<'_, '_> for Vec2Array {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let vecs: Vec<[f32; 2]> = match float_buffer(&obj)? {
+            Some((values, shape)) => chunked(values, &shape)?,
+            None => obj.extract()?,
+        };
+        Ok(Self(vecs.into_iter().map(glam::Vec2::from).collect()))
+    }
+}
+
+/// Scalar float data: widths.
+pub struct F32Array(pub Vec<f32>);
+
+impl FromPyObject<'_, '_> for F32Array {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        match float_buffer(&obj)? {
+            Some((values, _)) => Ok(Self(values)),
+            None => Ok(Self(obj.extract()?)),
+        }
+    }
+}
+
+/// `int32` data: face counts and indices. Wider integer arrays are accepted
+/// if every value fits.
+pub struct I32Array(pub Vec<i32>);
+
+impl FromPyObject<'_, '_> for I32Array {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let Some(values) = int_buffer(&obj)? else {
+            return Ok(Self(obj.extract()?));
+        };
+        values.into_iter()
+            .map(|v| i32::try_from(v).map_err(|_| PyValueError::new_err(format!("value {} does not fit in int32", v))))
+            .collect::<PyResult<_>>()
+            .map(Self)
+    }
+}
+
+/// Point ids (stored as signed 64-bit).
+pub struct IdArray(pub Vec<i64>);
+
+impl FromPyObject<'_, '_> for IdArray {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        if let Some(values) = int_buffer(&obj)? {
+            return Ok(Self(values));
+        }
+        if let Ok(buf) = PyBuffer::<u64>::get(&obj) {
+            return Ok(Self(buf.to_vec(obj.py())?.into_iter().map(|id| id as i64).collect()));
+        }
+        // Alembic ids are signed; unsigned ids are reinterpreted
+        let ids: Vec<u64> = obj.extract()?;
+        Ok(Self(ids.into_iter().map(|id| id as i64).collect()))
+    }
+}
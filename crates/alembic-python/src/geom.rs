@@ -4,8 +4,8 @@
 
 use pyo3::prelude::*;
 
-use crate::geom::{
-    PolyMeshSample, SubDSample, CurvesSample, PointsSample, CameraSample,
+use alembic::geom::{
+    PolyMeshSample, SubDSample, CurvesSample, PointsSample, CameraSample, FilmBackXformOpType,
     XformSample, LightSample, NuPatchSample, FaceSetSample, GeomParamSample,
     IFaceSet, IGeomParam,
     visibility::{ObjectVisibility, OVisibilityProperty},
@@ -168,7 +168,7 @@ impl From<FaceSetSample> for PyFaceSetSample {
 /// Python wrapper for IFaceSet schema reader.
 #[pyclass(name = "IFaceSet")]
 pub struct PyIFaceSet {
-    archive: Arc<crate::abc::IArchive>,
+    archive: Arc<alembic::abc::IArchive>,
     path: String,
 }
 
@@ -217,7 +217,7 @@ impl PyIFaceSet {
 
 impl PyIFaceSet {
     /// Create from archive and path.
-    pub fn new(archive: Arc<crate::abc::IArchive>, path: String) -> Self {
+    pub fn new(archive: Arc<alembic::abc::IArchive>, path: String) -> Self {
         Self { archive, path }
     }
     
@@ -230,7 +230,7 @@ impl PyIFaceSet {
         let parts: Vec<&str> = self.path.trim_start_matches('/').split('/').filter(|s| !s.is_empty()).collect();
         
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[&str],
             f: impl FnOnce(&IFaceSet<'_>) -> T,
         ) -> Option<T> {
@@ -328,7 +328,7 @@ impl From<GeomParamSample> for PyGeomParamSample {
 /// Python wrapper for IGeomParam schema reader.
 #[pyclass(name = "IGeomParam")]
 pub struct PyIGeomParam {
-    archive: Arc<crate::abc::IArchive>,
+    archive: Arc<alembic::abc::IArchive>,
     object_path: String,
     param_name: String,
 }
@@ -418,7 +418,7 @@ impl PyIGeomParam {
 
 impl PyIGeomParam {
     /// Create from archive, object path and param name.
-    pub fn new(archive: Arc<crate::abc::IArchive>, object_path: String, param_name: String) -> Self {
+    pub fn new(archive: Arc<alembic::abc::IArchive>, object_path: String, param_name: String) -> Self {
         Self { archive, object_path, param_name }
     }
     
@@ -432,7 +432,7 @@ impl PyIGeomParam {
         let param_name = self.param_name.clone();
         
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[&str],
             param_name: &str,
             f: impl FnOnce(&IGeomParam<'_>) -> T,
@@ -601,6 +601,20 @@ impl PyPointsSample {
     #[getter]
     pub fn widths(&self) -> Vec<f32> { self.widths.clone() }
     
+    /// Index of the point with the given id, or None.
+    fn indexOfId(&self, id: u64) -> Option<usize> {
+        self.ids.iter().position(|&i| i == id)
+    }
+    
+    /// Map of id -> point index (first index wins on duplicates).
+    fn idToIndex(&self) -> std::collections::HashMap<u64, usize> {
+        let mut map = std::collections::HashMap::with_capacity(self.ids.len());
+        for (index, &id) in self.ids.iter().enumerate() {
+            map.entry(id).or_insert(index);
+        }
+        map
+    }
+    
     fn __len__(&self) -> usize { self.positions.len() }
     
     fn __repr__(&self) -> String {
@@ -638,6 +652,8 @@ pub struct PyCameraSample {
     pub focus_distance: f64,
     pub shutter_open: f64,
     pub shutter_close: f64,
+    /// Full sample (overscan, film back ops) for the derived queries.
+    pub sample: CameraSample,
 }
 
 #[pymethods]
@@ -667,6 +683,39 @@ impl PyCameraSample {
     #[getter]
     pub fn shutterClose(&self) -> f64 { self.shutter_close }
     
+    /// Overscan as (left, right, top, bottom).
+    #[getter]
+    pub fn overscan(&self) -> (f64, f64, f64, f64) {
+        let s = &self.sample;
+        (s.overscan_left, s.overscan_right, s.overscan_top, s.overscan_bottom)
+    }
+
+    /// Film back ops as (type, hint, channels), type is "translate", "scale" or "matrix".
+    #[getter]
+    pub fn filmBackOps(&self) -> Vec<(String, String, Vec<f64>)> {
+        self.sample.film_back_xform_ops.iter()
+            .map(|op| {
+                let kind = match op.op_type() {
+                    FilmBackXformOpType::Translate => "translate",
+                    FilmBackXformOpType::Scale => "scale",
+                    FilmBackXformOpType::Matrix => "matrix",
+                };
+                let channels = (0..op.num_channels()).map(|c| op.channel_value(c)).collect();
+                (kind.to_string(), op.hint().to_string(), channels)
+            })
+            .collect()
+    }
+
+    /// Horizontal field of view in degrees (Alembic `getFieldOfView()`).
+    pub fn getFieldOfView(&self) -> f64 {
+        self.sample.field_of_view()
+    }
+
+    /// Screen window as (top, bottom, left, right).
+    pub fn getScreenWindow(&self) -> (f64, f64, f64, f64) {
+        self.sample.screen_window()
+    }
+
     /// Get horizontal field of view in degrees.
     pub fn getFovHorizontal(&self) -> f64 {
         2.0 * (self.horizontal_aperture / (2.0 * self.focal_length)).atan().to_degrees()
@@ -697,6 +746,7 @@ impl From<CameraSample> for PyCameraSample {
             focus_distance: s.focus_distance,
             shutter_open: s.shutter_open,
             shutter_close: s.shutter_close,
+            sample: s,
         }
     }
 }
@@ -868,6 +918,10 @@ pub struct PyLightSample {
     pub camera: PyCameraSample,
     /// Child bounds (optional).
     pub child_bounds: Option<([f64; 3], [f64; 3])>,
+    pub light_type: &'static str,
+    pub intensity: Option<f32>,
+    pub color: Option<[f32; 3]>,
+    pub cone_angle: Option<f32>,
 }
 
 #[pymethods]
@@ -888,6 +942,7 @@ impl PyLightSample {
             focus_distance: self.camera.focus_distance,
             shutter_open: self.camera.shutter_open,
             shutter_close: self.camera.shutter_close,
+            sample: self.camera.sample.clone(),
         }
     }
     
@@ -897,8 +952,32 @@ impl PyLightSample {
         self.child_bounds
     }
     
+    /// Light type: "point", "distant", "spot", "area", "dome" or "unknown".
+    #[getter]
+    pub fn lightType(&self) -> &'static str {
+        self.light_type
+    }
+    
+    /// Intensity (None if not stored).
+    #[getter]
+    pub fn intensity(&self) -> Option<f32> {
+        self.intensity
+    }
+    
+    /// Linear RGB color (None if not stored).
+    #[getter]
+    pub fn color(&self) -> Option<[f32; 3]> {
+        self.color
+    }
+    
+    /// Full spot cone angle in degrees (None if not stored).
+    #[getter]
+    pub fn coneAngle(&self) -> Option<f32> {
+        self.cone_angle
+    }
+    
     fn __repr__(&self) -> String {
-        "<LightSample>".to_string()
+        format!("<LightSample {}>", self.light_type)
     }
 }
 
@@ -910,6 +989,10 @@ impl From<LightSample> for PyLightSample {
                 [b.min.x, b.min.y, b.min.z],
                 [b.max.x, b.max.y, b.max.z]
             )),
+            light_type: s.light_type.name(),
+            intensity: s.intensity,
+            color: s.color.map(|c| c.to_array()),
+            cone_angle: s.cone_angle,
         }
     }
 }
@@ -1126,7 +1209,7 @@ impl PyOVisibilityProperty {
     }
     
     /// Get the underlying OProperty for adding to objects.
-    pub fn into_property(self) -> crate::ogawa::writer::OProperty {
+    pub fn into_property(self) -> alembic::ogawa::writer::OProperty {
         self.inner.into_property()
     }
 }
@@ -29,6 +29,7 @@ mod time_sampling;
 mod geom;
 mod properties;
 mod write;
+mod arrays;
 mod materials;
 mod schemas;
 
@@ -52,7 +53,7 @@ pub use schemas::*;
 #[pyfunction]
 fn view(path: &str) -> PyResult<()> {
     let file = std::path::PathBuf::from(path);
-    crate::viewer::run(Some(file))
+    alembic_viewer::run(Some(file))
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Viewer error: {}", e)))
 }
 
@@ -204,6 +205,10 @@ fn alembic_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<write::PyOArchive>()?;
     m.add_class::<write::PyOPolyMesh>()?;
     m.add_class::<write::PyOXform>()?;
+    m.add_class::<write::PyOObject>()?;
+    m.add_class::<write::PyOPoints>()?;
+    m.add_class::<write::PyOSubD>()?;
+    m.add_class::<write::PyOCurves>()?;
     // Schema readers at top level
     m.add_class::<schemas::PyIPolyMesh>()?;
     m.add_class::<schemas::PyIXform>()?;
@@ -6,9 +6,9 @@ use pyo3::prelude::*;
 use std::sync::Arc;
 use std::collections::HashMap;
 
-use crate::abc::IArchive;
-use crate::material::IMaterial;
-use crate::collection::{ICollections, Collection};
+use alembic::abc::IArchive;
+use alembic::material::IMaterial;
+use alembic::collection::{ICollections, Collection};
 
 // ============================================================================
 // Collection bindings
@@ -109,7 +109,7 @@ impl PyICollections {
         let root = self.archive.getTop();
         
         fn traverse_and_execute<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
             f: impl FnOnce(&ICollections<'_>) -> Option<T>,
         ) -> Option<T> {
@@ -186,7 +186,7 @@ impl PyIMaterial {
         let root = self.archive.getTop();
         
         fn traverse_and_execute<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
             f: impl FnOnce(&IMaterial<'_>) -> Option<T>,
         ) -> Option<T> {
@@ -268,8 +268,8 @@ impl PyIMaterial {
 }
 
 /// Convert ShaderParamValue to Py<PyAny>.
-fn param_to_pyobject(py: Python<'_>, value: &crate::material::ShaderParamValue) -> Py<PyAny> {
-    use crate::material::ShaderParamValue;
+fn param_to_pyobject(py: Python<'_>, value: &alembic::material::ShaderParamValue) -> Py<PyAny> {
+    use alembic::material::ShaderParamValue;
     
     match value {
         ShaderParamValue::Bool(v) => v.into_pyobject(py).unwrap().to_owned().unbind().into_any(),
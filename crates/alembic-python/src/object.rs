@@ -6,15 +6,17 @@ use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use std::sync::Arc;
 
-use crate::abc::IArchive;
-use crate::geom::{IPolyMesh, ISubD, ICurves, IPoints, ICamera, ILight, IXform, INuPatch, IFaceSet, IGeomParam};
-use crate::geom::visibility::{get_visibility, is_visible};
+use alembic::abc::IArchive;
+use alembic::geom::{IPolyMesh, ISubD, ICurves, IPoints, ICamera, ILight, IXform, INuPatch, IFaceSet, IGeomParam};
+use alembic::geom::visibility::{get_visibility, is_visible};
 use super::geom::{
     PyPolyMeshSample, PySubDSample, PyCurvesSample, PyPointsSample,
     PyCameraSample, PyXformSample, PyLightSample, PyNuPatchSample,
     PyFaceSetSample, PyIFaceSet, PyIGeomParam, PyObjectVisibility,
 };
 use super::properties::PyICompoundProperty;
+use super::time_sampling::{PyTimeSampling, resolve_selector, selector_for_time};
+use alembic::core::{SampleSelector, TimeSampling};
 
 /// Python wrapper for IObject.
 /// 
@@ -37,7 +39,7 @@ impl PyIObject {
     /// Execute closure with resolved object (recursive traversal).
     fn with_object<T, F>(&self, f: F) -> Option<T>
     where
-        F: FnOnce(&crate::abc::IObject) -> Option<T>,
+        F: FnOnce(&alembic::abc::IObject) -> Option<T>,
     {
         let root = self.archive.getTop();
         
@@ -47,9 +49,9 @@ impl PyIObject {
         
         // Use recursive helper to avoid borrow checker issues
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
-            f: impl FnOnce(&crate::abc::IObject) -> Option<T>,
+            f: impl FnOnce(&alembic::abc::IObject) -> Option<T>,
         ) -> Option<T> {
             if path.is_empty() {
                 f(&obj)
@@ -61,6 +63,38 @@ impl PyIObject {
         
         traverse(root, &self.path, f)
     }
+    
+    /// Accumulate Xform matrices from the root down to this object.
+    fn world_matrix(&self, sel: &SampleSelector) -> Option<glam::Mat4> {
+        fn traverse(
+            archive: &IArchive,
+            obj: &alembic::abc::IObject<'_>,
+            path: &[String],
+            sel: &SampleSelector,
+            parent: glam::Mat4,
+        ) -> Option<glam::Mat4> {
+            let world = match IXform::new(obj) {
+                Some(xform) if xform.getNumSamples() > 0 => {
+                    let ts = archive.getTimeSampling(xform.getTimeSamplingIndex() as usize);
+                    let idx = resolve_selector(sel, ts, xform.getNumSamples());
+                    match xform.getSample(idx) {
+                        Ok(s) if s.inherits => parent * s.matrix(),
+                        Ok(s) => s.matrix(),
+                        Err(_) => parent,
+                    }
+                }
+                _ => parent,
+            };
+            if path.is_empty() {
+                return Some(world);
+            }
+            let child = obj.getChildByName(&path[0])?;
+            traverse(archive, &child, &path[1..], sel, world)
+        }
+        
+        let root = self.archive.getTop();
+        traverse(&self.archive, &root, &self.path, sel, glam::Mat4::IDENTITY)
+    }
 }
 
 #[pymethods]
@@ -387,7 +421,7 @@ impl PyIObject {
     fn getVisibility(&self, index: usize) -> PyObjectVisibility {
         self.with_object(|obj| {
             Some(get_visibility(obj, index).into())
-        }).unwrap_or_else(|| PyObjectVisibility::from(crate::geom::visibility::ObjectVisibility::Deferred))
+        }).unwrap_or_else(|| PyObjectVisibility::from(alembic::geom::visibility::ObjectVisibility::Deferred))
     }
     
     /// Check if object is visible at sample index.
@@ -434,6 +468,57 @@ impl PyIObject {
         }).unwrap_or(0)
     }
     
+    /// Get the time sampling for this object's primary data.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        self.archive.getTimeSampling(self.getTimeSamplingIndex() as usize)
+            .cloned()
+            .unwrap_or_else(TimeSampling::identity)
+            .into()
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
+    /// Resolve a time to a sample index (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn getSampleIndexAtTime(&self, time: f64, mode: &str) -> PyResult<usize> {
+        let sel = selector_for_time(time, mode)?;
+        let ts = self.archive.getTimeSampling(self.getTimeSamplingIndex() as usize);
+        Ok(resolve_selector(&sel, ts, self.getNumSamples()))
+    }
+    
+    // ========================================================================
+    // World transforms
+    // ========================================================================
+    
+    /// Get the 4x4 local-to-world matrix (column-major).
+    ///
+    /// Concatenates all ancestor Xforms (including this object if it is an
+    /// Xform), honouring `inheritsXforms`. If `time` is given each Xform is
+    /// sampled by its own time sampling using `mode`; otherwise `index` is used
+    /// (clamped per Xform).
+    #[pyo3(signature = (index=0, time=None, mode="near"))]
+    fn getWorldMatrix(&self, index: usize, time: Option<f64>, mode: &str) -> PyResult<[[f64; 4]; 4]> {
+        let sel = match time {
+            Some(t) => selector_for_time(t, mode)?,
+            None => SampleSelector::index(index),
+        };
+        let world = self.world_matrix(&sel)
+            .ok_or_else(|| PyValueError::new_err("Object not found"))?;
+        Ok(world.to_cols_array_2d().map(|c| c.map(|v| v as f64)))
+    }
+    
+    /// Get the world-space position of this object's origin.
+    #[pyo3(signature = (index=0, time=None, mode="near"))]
+    fn getWorldTranslation(&self, index: usize, time: Option<f64>, mode: &str) -> PyResult<[f64; 3]> {
+        let m = self.getWorldMatrix(index, time, mode)?;
+        Ok([m[3][0], m[3][1], m[3][2]])
+    }
+    
     // ========================================================================
     // Bounds
     // ========================================================================
@@ -450,7 +535,7 @@ impl PyIObject {
             let scalar = bnds_prop.asScalar()?;
             
             let mut buf = [0u8; 48]; // 6 x f64
-            scalar.getSample(index, &mut buf).ok()?;
+            scalar.getSample(scalar.getSampleIndex(index.into()), &mut buf).ok()?;
             let doubles: &[f64] = bytemuck::try_cast_slice(&buf).ok()?;
             
             if doubles.len() >= 6 {
@@ -476,7 +561,7 @@ impl PyIObject {
             let scalar = bnds_prop.asScalar()?;
             
             let mut buf = [0u8; 48]; // 6 x f64
-            scalar.getSample(index, &mut buf).ok()?;
+            scalar.getSample(scalar.getSampleIndex(index.into()), &mut buf).ok()?;
             let doubles: &[f64] = bytemuck::try_cast_slice(&buf).ok()?;
             
             if doubles.len() >= 6 {
@@ -6,9 +6,9 @@ use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use std::sync::Arc;
 
-use crate::abc::IArchive;
-use crate::util::{DataType, PlainOldDataType};
-use crate::core::ScalarPropertyReader;
+use alembic::abc::IArchive;
+use alembic::util::{DataType, PlainOldDataType};
+use alembic::core::ScalarPropertyReader;
 
 /// Property info returned to Python (owns the data).
 #[pyclass(name = "PropertyInfo")]
@@ -64,16 +64,16 @@ impl PyICompoundProperty {
     /// Traverse to object, then to compound, execute closure.
     fn with_compound<T, F>(&self, f: F) -> Option<T>
     where
-        F: FnOnce(&crate::abc::ICompoundProperty<'_>) -> Option<T>,
+        F: FnOnce(&alembic::abc::ICompoundProperty<'_>) -> Option<T>,
     {
         let root = self.archive.getTop();
         
         // Recursive object traversal with closure
         fn traverse_obj<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             obj_path: &[String],
             prop_path: &[String],
-            f: impl FnOnce(&crate::abc::ICompoundProperty<'_>) -> Option<T>,
+            f: impl FnOnce(&alembic::abc::ICompoundProperty<'_>) -> Option<T>,
         ) -> Option<T> {
             if obj_path.is_empty() {
                 // Reached target object, now traverse properties
@@ -87,9 +87,9 @@ impl PyICompoundProperty {
         
         // Recursive property traversal with closure
         fn traverse_prop<'a, T>(
-            compound: crate::abc::ICompoundProperty<'a>,
+            compound: alembic::abc::ICompoundProperty<'a>,
             path: &[String],
-            f: impl FnOnce(&crate::abc::ICompoundProperty<'_>) -> Option<T>,
+            f: impl FnOnce(&alembic::abc::ICompoundProperty<'_>) -> Option<T>,
         ) -> Option<T> {
             if path.is_empty() {
                 f(&compound)
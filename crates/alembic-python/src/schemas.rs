@@ -13,15 +13,44 @@ use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use std::sync::Arc;
 
-use crate::abc::IArchive;
-use crate::geom::{IPolyMesh, ISubD, ICurves, IPoints, ICamera, ILight, IXform, INuPatch, IFaceSet};
+use alembic::abc::IArchive;
+use alembic::geom::{IPolyMesh, ISubD, ICurves, IPoints, ICamera, ILight, IXform, INuPatch, IFaceSet};
 use super::geom::PyIFaceSet;
 use super::geom::{
     PyPolyMeshSample, PySubDSample, PyCurvesSample, PyPointsSample,
     PyCameraSample, PyXformSample, PyLightSample, PyNuPatchSample, PyFaceSetSample,
 };
 use super::object::PyIObject;
-use super::time_sampling::PyISampleSelector;
+use super::time_sampling::{PyISampleSelector, PyTimeSampling, resolve_selector, selector_for_time};
+use alembic::core::TimeSampling;
+
+/// Resolve an explicit index or an ISampleSelector against a schema's time sampling.
+fn resolve_index(
+    archive: &IArchive,
+    ts_index: u32,
+    num_samples: usize,
+    index: usize,
+    selector: Option<&PyISampleSelector>,
+) -> usize {
+    match selector {
+        Some(sel) => resolve_selector(&sel.inner, archive.getTimeSampling(ts_index as usize), num_samples),
+        None => index,
+    }
+}
+
+/// Resolve a time and mode string to a sample index.
+fn time_index(archive: &IArchive, ts_index: u32, num_samples: usize, time: f64, mode: &str) -> PyResult<usize> {
+    let sel = selector_for_time(time, mode)?;
+    Ok(resolve_selector(&sel, archive.getTimeSampling(ts_index as usize), num_samples))
+}
+
+/// Look up a schema's time sampling (identity if the index is out of range).
+fn schema_time_sampling(archive: &IArchive, ts_index: u32) -> PyTimeSampling {
+    archive.getTimeSampling(ts_index as usize)
+        .cloned()
+        .unwrap_or_else(TimeSampling::identity)
+        .into()
+}
 
 // ============================================================================
 // IPolyMesh
@@ -57,6 +86,12 @@ impl PyIPolyMesh {
         }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyPolyMeshSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     /// Check if valid.
     fn valid(&self) -> bool {
         self.with_mesh(|_| Some(true)).unwrap_or(false)
@@ -89,7 +124,7 @@ impl PyIPolyMesh {
         let root = self.archive.getTop();
         
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
             f: impl FnOnce(&IPolyMesh<'_>) -> Option<T>,
         ) -> Option<T> {
@@ -128,11 +163,30 @@ impl PyIPolyMeshSchema {
     /// Get sample at index or using ISampleSelector.
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyPolyMeshSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_mesh(|m| m.getSample(idx).ok().map(|s| s.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyPolyMeshSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     /// Get time sampling index.
     fn getTimeSamplingIndex(&self) -> u32 {
         self.with_mesh(|m| Some(m.getTimeSamplingIndex())).unwrap_or(0)
@@ -143,9 +197,9 @@ impl PyIPolyMeshSchema {
         self.with_mesh(|m| {
             let tv = m.topology_variance();
             Some(match tv {
-                crate::core::TopologyVariance::Heterogeneous => 0,
-                crate::core::TopologyVariance::Homogeneous => 1,
-                crate::core::TopologyVariance::Static => 2,
+                alembic::core::TopologyVariance::Heterogeneous => 0,
+                alembic::core::TopologyVariance::Homogeneous => 1,
+                alembic::core::TopologyVariance::Static => 2,
             })
         }).unwrap_or(0)
     }
@@ -223,7 +277,7 @@ impl PyIPolyMeshSchema {
         let root = self.archive.getTop();
         
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
             f: impl FnOnce(&IPolyMesh<'_>) -> Option<T>,
         ) -> Option<T> {
@@ -271,6 +325,12 @@ impl PyIXform {
         }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyXformSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool {
         self.with_xform(|_| Some(true)).unwrap_or(false)
     }
@@ -296,7 +356,7 @@ impl PyIXform {
     {
         let root = self.archive.getTop();
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
             f: impl FnOnce(&IXform<'_>) -> Option<T>,
         ) -> Option<T> {
@@ -330,11 +390,30 @@ impl PyIXformSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyXformSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_xform(|x| x.getSample(idx).ok().map(|s| s.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyXformSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 {
         self.with_xform(|x| Some(x.getTimeSamplingIndex())).unwrap_or(0)
     }
@@ -357,7 +436,7 @@ impl PyIXformSchema {
     {
         let root = self.archive.getTop();
         fn traverse<'a, T>(
-            obj: crate::abc::IObject<'a>,
+            obj: alembic::abc::IObject<'a>,
             path: &[String],
             f: impl FnOnce(&IXform<'_>) -> Option<T>,
         ) -> Option<T> {
@@ -397,6 +476,12 @@ impl PyISubD {
         PyISubDSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PySubDSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_subd(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -410,7 +495,7 @@ impl PyISubD {
     fn with_subd<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ISubD<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ISubD<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ISubD<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ISubD::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -431,11 +516,30 @@ impl PyISubDSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PySubDSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_subd(|s| s.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PySubDSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_subd(|s| Some(s.getTimeSamplingIndex())).unwrap_or(0) }
     
     /// Get subdivision scheme (kCatmullClark=0, kLoop=1, kBilinear=2).
@@ -443,33 +547,33 @@ impl PyISubDSchema {
         self.with_subd(|s| {
             s.getSample(0).ok().map(|sample| {
                 match sample.scheme {
-                    crate::geom::SubDScheme::CatmullClark => 0,
-                    crate::geom::SubDScheme::Loop => 1,
-                    crate::geom::SubDScheme::Bilinear => 2,
+                    alembic::geom::SubDScheme::CatmullClark => 0,
+                    alembic::geom::SubDScheme::Loop => 1,
+                    alembic::geom::SubDScheme::Bilinear => 2,
                 }
             })
-        }).flatten().unwrap_or(0)
+        }).unwrap_or(0)
     }
     
     /// Get face-varying interpolate boundary value.
     fn getFaceVaryingInterpolateBoundary(&self) -> i32 {
         self.with_subd(|s| {
-            s.getSample(0).ok().map(|sample| sample.fv_interp_boundary)
-        }).flatten().unwrap_or(0)
+            s.getSample(0).ok().map(|sample| sample.fv_interp_boundary.as_i32())
+        }).unwrap_or(0)
     }
     
     /// Get face-varying propagate corners flag.
     fn getFaceVaryingPropagateCornersFlag(&self) -> i32 {
         self.with_subd(|s| {
             s.getSample(0).ok().map(|sample| sample.fv_propagate_corners)
-        }).flatten().unwrap_or(0)
+        }).unwrap_or(0)
     }
     
     /// Get interpolate boundary value.
     fn getInterpolateBoundary(&self) -> i32 {
         self.with_subd(|s| {
-            s.getSample(0).ok().map(|sample| sample.interp_boundary)
-        }).flatten().unwrap_or(0)
+            s.getSample(0).ok().map(|sample| sample.interp_boundary.as_i32())
+        }).unwrap_or(0)
     }
     
     fn __repr__(&self) -> String { format!("<ISubDSchema {} samples>", self.getNumSamples()) }
@@ -479,7 +583,7 @@ impl PyISubDSchema {
     fn with_subd<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ISubD<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ISubD<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ISubD<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ISubD::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -511,6 +615,12 @@ impl PyICurves {
         PyICurvesSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyCurvesSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_curves(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -524,7 +634,7 @@ impl PyICurves {
     fn with_curves<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ICurves<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICurves<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICurves<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ICurves::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -545,32 +655,51 @@ impl PyICurvesSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyCurvesSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_curves(|c| c.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyCurvesSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_curves(|c| Some(c.getTimeSamplingIndex())).unwrap_or(0) }
     
     /// Get curve type (kCubic=0, kLinear=1, kVariableOrder=2).
     fn getCurveType(&self) -> u8 {
         self.with_curves(|c| {
             c.getSample(0).ok().map(|sample| sample.curve_type.to_u8())
-        }).flatten().unwrap_or(0)
+        }).unwrap_or(0)
     }
     
     /// Get curve periodicity (kNonPeriodic=0, kPeriodic=1).
     fn getCurvePeriodicity(&self) -> u8 {
         self.with_curves(|c| {
-            c.getSample(0).ok().map(|sample| sample.periodicity.to_u8())
-        }).flatten().unwrap_or(0)
+            c.getSample(0).ok().map(|sample| sample.wrap.to_u8())
+        }).unwrap_or(0)
     }
     
     /// Get basis type (kNoBasis=0, kBezierBasis=1, kBsplineBasis=2, kCatmullRomBasis=3, kHermiteBasis=4, kPowerBasis=5).
     fn getBasis(&self) -> u8 {
         self.with_curves(|c| {
             c.getSample(0).ok().map(|sample| sample.basis.to_u8())
-        }).flatten().unwrap_or(0)
+        }).unwrap_or(0)
     }
     
     fn __repr__(&self) -> String { format!("<ICurvesSchema {} samples>", self.getNumSamples()) }
@@ -580,7 +709,7 @@ impl PyICurvesSchema {
     fn with_curves<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ICurves<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICurves<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICurves<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ICurves::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -612,6 +741,12 @@ impl PyIPoints {
         PyIPointsSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyPointsSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_points(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -625,7 +760,7 @@ impl PyIPoints {
     fn with_points<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&IPoints<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IPoints<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IPoints<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&IPoints::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -646,11 +781,30 @@ impl PyIPointsSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyPointsSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_points(|p| p.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyPointsSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_points(|p| Some(p.getTimeSamplingIndex())).unwrap_or(0) }
     fn __repr__(&self) -> String { format!("<IPointsSchema {} samples>", self.getNumSamples()) }
 }
@@ -659,7 +813,7 @@ impl PyIPointsSchema {
     fn with_points<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&IPoints<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IPoints<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IPoints<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&IPoints::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -691,6 +845,12 @@ impl PyICamera {
         PyICameraSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyCameraSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_camera(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -704,7 +864,7 @@ impl PyICamera {
     fn with_camera<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ICamera<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICamera<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICamera<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ICamera::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -725,11 +885,30 @@ impl PyICameraSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyCameraSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_camera(|c| c.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyCameraSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_camera(|c| Some(c.getTimeSamplingIndex())).unwrap_or(0) }
     fn __repr__(&self) -> String { format!("<ICameraSchema {} samples>", self.getNumSamples()) }
 }
@@ -738,7 +917,7 @@ impl PyICameraSchema {
     fn with_camera<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ICamera<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICamera<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ICamera<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ICamera::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -770,6 +949,12 @@ impl PyILight {
         PyILightSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyLightSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_light(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -783,7 +968,7 @@ impl PyILight {
     fn with_light<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ILight<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ILight<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ILight<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ILight::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -804,11 +989,30 @@ impl PyILightSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyLightSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_light(|l| l.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyLightSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_light(|l| Some(l.getTimeSamplingIndex())).unwrap_or(0) }
     fn __repr__(&self) -> String { format!("<ILightSchema {} samples>", self.getNumSamples()) }
 }
@@ -817,7 +1021,7 @@ impl PyILightSchema {
     fn with_light<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&ILight<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ILight<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&ILight<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&ILight::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -849,6 +1053,12 @@ impl PyINuPatch {
         PyINuPatchSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyNuPatchSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_nupatch(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -862,7 +1072,7 @@ impl PyINuPatch {
     fn with_nupatch<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&INuPatch<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&INuPatch<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&INuPatch<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&INuPatch::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -883,11 +1093,30 @@ impl PyINuPatchSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyNuPatchSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_nupatch(|n| n.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyNuPatchSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_nupatch(|n| Some(n.getTimeSamplingIndex())).unwrap_or(0) }
     fn __repr__(&self) -> String { format!("<INuPatchSchema {} samples>", self.getNumSamples()) }
 }
@@ -896,7 +1125,7 @@ impl PyINuPatchSchema {
     fn with_nupatch<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&INuPatch<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&INuPatch<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&INuPatch<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&INuPatch::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -921,11 +1150,30 @@ impl PyIFaceSetSchema {
     
     #[pyo3(signature = (index=0, selector=None))]
     fn getValue(&self, index: usize, selector: Option<&PyISampleSelector>) -> PyResult<PyFaceSetSample> {
-        let idx = selector.map(|s| s.getRequestedIndex()).unwrap_or(index);
+        let idx = resolve_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), index, selector);
         self.with_faceset(|f| f.getSample(idx).ok().map(|v| v.into()))
             .ok_or_else(|| PyValueError::new_err("Failed to get sample"))
     }
     
+    /// Get sample at time (mode: "near", "floor" or "ceil").
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyFaceSetSample> {
+        let idx = time_index(&self.archive, self.getTimeSamplingIndex(), self.getNumSamples(), time, mode)?;
+        self.getValue(idx, None)
+    }
+    
+    /// Get the time sampling used by this schema.
+    fn getTimeSampling(&self) -> PyTimeSampling {
+        schema_time_sampling(&self.archive, self.getTimeSamplingIndex())
+    }
+    
+    /// Get the time of every sample.
+    fn getSampleTimes(&self) -> Vec<f64> {
+        let ts = self.getTimeSampling().inner;
+        let n = self.getNumSamples();
+        (0..n).map(|i| ts.sample_time(i, n)).collect()
+    }
+    
     fn getTimeSamplingIndex(&self) -> u32 { self.with_faceset(|f| Some(f.getTimeSamplingIndex())).unwrap_or(0) }
     
     /// Get face exclusivity setting.
@@ -941,7 +1189,7 @@ impl PyIFaceSetSchema {
     fn with_faceset<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&IFaceSet<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IFaceSet<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IFaceSet<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&IFaceSet::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
@@ -970,6 +1218,12 @@ impl PyIFaceSetTyped {
         PyIFaceSetSchema { archive: self.archive.clone(), path: self.path.clone() }
     }
     
+    /// Get sample at time (shortcut for `getSchema().sample_at_time()`).
+    #[pyo3(signature = (time, mode="near"))]
+    fn sample_at_time(&self, time: f64, mode: &str) -> PyResult<PyFaceSetSample> {
+        self.getSchema().sample_at_time(time, mode)
+    }
+    
     fn valid(&self) -> bool { self.with_faceset(|_| Some(true)).unwrap_or(false) }
     fn getName(&self) -> String { self.path.last().cloned().unwrap_or_default() }
     fn getFullName(&self) -> String {
@@ -983,7 +1237,7 @@ impl PyIFaceSetTyped {
     fn with_faceset<T, F>(&self, f: F) -> Option<T>
     where F: FnOnce(&IFaceSet<'_>) -> Option<T> {
         let root = self.archive.getTop();
-        fn traverse<'a, T>(obj: crate::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IFaceSet<'_>) -> Option<T>) -> Option<T> {
+        fn traverse<'a, T>(obj: alembic::abc::IObject<'a>, path: &[String], f: impl FnOnce(&IFaceSet<'_>) -> Option<T>) -> Option<T> {
             if path.is_empty() { f(&IFaceSet::new(&obj)?) }
             else { traverse(obj.getChildByName(&path[0])?, &path[1..], f) }
         }
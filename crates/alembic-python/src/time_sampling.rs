@@ -3,7 +3,8 @@
 #![allow(non_snake_case)]
 
 use pyo3::prelude::*;
-use crate::core::{TimeSampling, TimeSamplingType};
+use pyo3::exceptions::PyValueError;
+use alembic::core::{TimeSampling, TimeSamplingType};
 
 /// Python wrapper for TimeSampling.
 #[pyclass(name = "TimeSampling")]
@@ -30,6 +31,28 @@ impl PyTimeSampling {
         Self { inner: TimeSampling::uniform(time_per_cycle, start_time) }
     }
     
+    /// Create uniform time sampling from a frame rate.
+    /// 
+    /// Args:
+    ///     fps: Frames per second
+    ///     start_frame: Frame of the first sample (time = frame / fps)
+    #[staticmethod]
+    #[pyo3(signature = (fps, start_frame=0.0))]
+    fn uniform_fps(fps: f64, start_frame: f64) -> PyResult<Self> {
+        if !(fps.is_finite() && fps > 0.0) {
+            return Err(PyValueError::new_err("fps must be positive"));
+        }
+        Ok(Self { inner: TimeSampling::uniform_fps(fps, start_frame) })
+    }
+    
+    /// Create cyclic time sampling with the same sub-frame offsets every frame.
+    #[staticmethod]
+    fn cyclic_fps(fps: f64, start_frame: f64, subframes: Vec<f64>) -> PyResult<Self> {
+        let inner = TimeSampling::cyclic_fps(fps, start_frame, &subframes);
+        inner.validate().map_err(PyValueError::new_err)?;
+        Ok(Self { inner })
+    }
+    
     /// Create acyclic time sampling with explicit times.
     #[staticmethod]
     fn acyclic(times: Vec<f64>) -> Self {
@@ -118,6 +141,42 @@ impl PyTimeSampling {
         self.inner.time_range(num_samples)
     }
     
+    /// Get the times of all `num_samples` samples.
+    fn getSampleTimes(&self, num_samples: usize) -> Vec<f64> {
+        (0..num_samples).map(|i| self.inner.sample_time(i, num_samples)).collect()
+    }
+    
+    /// Get frames per second for uniform sampling, None otherwise.
+    fn getFps(&self) -> Option<f64> {
+        match &self.inner.sampling_type {
+            TimeSamplingType::Uniform { time_per_cycle, .. } if *time_per_cycle > 0.0 => {
+                Some(1.0 / time_per_cycle)
+            }
+            _ => None,
+        }
+    }
+    
+    /// Get sampling type name ("identity", "uniform", "cyclic" or "acyclic").
+    fn getTypeName(&self) -> &'static str {
+        match &self.inner.sampling_type {
+            TimeSamplingType::Identity => "identity",
+            TimeSamplingType::Uniform { .. } => "uniform",
+            TimeSamplingType::Cyclic { .. } => "cyclic",
+            TimeSamplingType::Acyclic { .. } => "acyclic",
+        }
+    }
+    
+    /// Resolve a time to a sample index.
+    ///
+    /// Args:
+    ///     time: Time in seconds
+    ///     num_samples: Number of samples of the property
+    ///     mode: "near" (default), "floor" or "ceil"
+    #[pyo3(signature = (time, num_samples, mode="near"))]
+    fn getIndexAtTime(&self, time: f64, num_samples: usize, mode: &str) -> PyResult<usize> {
+        Ok(selector_for_time(time, mode)?.get_index(&self.inner, num_samples))
+    }
+    
     fn __repr__(&self) -> String {
         let type_str = match &self.inner.sampling_type {
             TimeSamplingType::Identity => "Identity".to_string(),
@@ -151,7 +210,31 @@ impl From<&TimeSampling> for PyTimeSampling {
 // ISampleSelector — Python wrapper for SampleSelector
 // ============================================================================
 
-use crate::core::SampleSelector;
+use alembic::core::SampleSelector;
+
+/// Build a time-based selector from a mode string ("near", "floor", "ceil").
+pub(crate) fn selector_for_time(time: f64, mode: &str) -> PyResult<SampleSelector> {
+    match mode {
+        "near" | "nearest" => Ok(SampleSelector::time_near(time)),
+        "floor" => Ok(SampleSelector::time_floor(time)),
+        "ceil" => Ok(SampleSelector::time_ceil(time)),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown sampling mode '{}' (expected 'near', 'floor' or 'ceil')", mode
+        ))),
+    }
+}
+
+/// Resolve a selector against optional time sampling (identity if missing).
+pub(crate) fn resolve_selector(
+    selector: &SampleSelector,
+    ts: Option<&TimeSampling>,
+    num_samples: usize,
+) -> usize {
+    match ts {
+        Some(ts) => selector.get_index(ts, num_samples),
+        None => selector.get_index(&TimeSampling::identity(), num_samples),
+    }
+}
 
 /// Python wrapper for ISampleSelector.
 ///
@@ -222,7 +305,7 @@ impl PyISampleSelector {
 
     /// Resolve to actual sample index given time sampling and num samples.
     fn getIndex(&self, ts: &PyTimeSampling, num_samples: usize) -> usize {
-        self.inner.resolve(&ts.inner, num_samples)
+        self.inner.get_index(&ts.inner, num_samples)
     }
 
     /// Get the requested index (for Index selectors).
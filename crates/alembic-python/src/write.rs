@@ -7,16 +7,19 @@ use pyo3::exceptions::{PyValueError, PyIOError};
 use std::sync::{Arc, Mutex};
 
 
-use crate::ogawa::writer::{
+use alembic::ogawa::writer::{
     OArchive, OObject, OPolyMesh, OXform, OPolyMeshSample, OXformSample,
-    OCurves, OCurvesSample, OPoints, OPointsSample, OSubD, OSubDSample,
+    OCurves, OCurvesSample, OPoints, OPointsSample, PointIdMode, OSubD, OSubDSample,
     OCamera, ONuPatch, ONuPatchSample, OLight, OFaceSet, OFaceSetSample,
     OMaterial, OMaterialSample, OCollections, OProperty,
 };
-use crate::util::DataType;
-use crate::core::TimeSampling;
-use crate::geom::{CurveType, CurvePeriodicity, BasisType, CameraSample};
-use crate::material::{ShaderParam, ShaderParamValue};
+use alembic::util::DataType;
+use alembic::core::TimeSampling;
+use alembic::geom::{CurveType, CurvePeriodicity, BasisType, CameraSample, FaceVaryingInterpolation, InterpolateBoundary};
+use alembic::material::{ShaderParam, ShaderParamValue};
+
+use crate::arrays::{F32Array, I32Array, IdArray, Vec2Array, Vec3Array};
+use crate::time_sampling::PyTimeSampling;
 
 // ============================================================================
 // OArchive wrapper
@@ -104,6 +107,14 @@ impl PyOArchive {
         Ok(())
     }
     
+    /// Add a `TimeSampling` (see `Abc.TimeSampling`). Returns its index, to
+    /// pass to the schemas' `setTimeSamplingIndex`.
+    fn addTimeSampling(&self, ts: &PyTimeSampling) -> PyResult<u32> {
+        let mut guard = self.archive.lock().map_err(|e| PyValueError::new_err(format!("Archive lock poisoned in addTimeSampling(): {}", e)))?;
+        let archive = guard.as_mut().ok_or_else(|| PyValueError::new_err("Archive already closed"))?;
+        Ok(archive.addTimeSampling(ts.inner.clone()))
+    }
+    
     /// Add uniform time sampling (fps-based). Returns time sampling index.
     #[pyo3(signature = (fps, start_time=0.0))]
     fn addUniformTimeSampling(&self, fps: f64, start_time: f64) -> PyResult<u32> {
@@ -177,6 +188,19 @@ impl PyOArchive {
         }
         Ok(())
     }
+
+    /// Discard an unfinished archive and delete its temp file.
+    ///
+    /// An archive that is garbage-collected without writeArchive()/close()
+    /// is discarded the same way. An existing file at the path is left
+    /// untouched; once the archive is written this does nothing.
+    fn abort(&self) -> PyResult<()> {
+        let mut guard = self.archive.lock().map_err(|e| PyValueError::new_err(format!("Archive lock poisoned in abort(): {}", e)))?;
+        if let Some(archive) = guard.take() {
+            archive.abort();
+        }
+        Ok(())
+    }
     
     /// Context manager enter.
     fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
@@ -184,14 +208,22 @@ impl PyOArchive {
     }
     
     /// Context manager exit - close archive.
-    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    ///
+    /// If the block raised, the archive is dropped instead: an unwritten
+    /// archive is deleted rather than finalized as an empty file.
+    #[pyo3(signature = (exc_type=None, _exc_val=None, _exc_tb=None))]
     fn __exit__(
         &self,
-        _exc_type: Option<&Bound<'_, PyAny>>,
+        exc_type: Option<&Bound<'_, PyAny>>,
         _exc_val: Option<&Bound<'_, PyAny>>,
         _exc_tb: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<bool> {
-        self.close()?;
+        if exc_type.is_some_and(|t| !t.is_none()) {
+            let mut guard = self.archive.lock().map_err(|e| PyValueError::new_err(format!("Archive lock poisoned in __exit__(): {}", e)))?;
+            drop(guard.take());
+        } else {
+            self.close()?;
+        }
         Ok(false)
     }
     
@@ -391,35 +423,32 @@ impl PyOPolyMesh {
         self.inner.is_none()
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a sample with positions, face counts, and face indices.
-    #[pyo3(signature = (positions, face_counts, face_indices, normals=None, uvs=None))]
+    ///
+    /// Arrays may be numpy arrays (read from their buffer) or sequences.
+    /// keep_topology: hold the previous sample's faces; face_counts and face_indices are ignored
+    #[pyo3(signature = (positions, face_counts, face_indices, normals=None, uvs=None, keep_topology=false))]
     fn addSample(
         &mut self,
-        positions: Vec<[f32; 3]>,
-        face_counts: Vec<i32>,
-        face_indices: Vec<i32>,
-        normals: Option<Vec<[f32; 3]>>,
-        uvs: Option<Vec<[f32; 2]>>,
+        positions: Vec3Array,
+        face_counts: I32Array,
+        face_indices: I32Array,
+        normals: Option<Vec3Array>,
+        uvs: Option<Vec2Array>,
+        keep_topology: bool,
     ) -> PyResult<()> {
         let inner = self.inner_mut()?;
         
-        let pos: Vec<glam::Vec3> = positions.iter()
-            .map(|p| glam::Vec3::new(p[0], p[1], p[2]))
-            .collect();
-        
-        let mut sample = OPolyMeshSample::new(pos, face_counts, face_indices);
-        
-        if let Some(norms) = normals {
-            sample.normals = Some(norms.iter()
-                .map(|n| glam::Vec3::new(n[0], n[1], n[2]))
-                .collect());
-        }
-        
-        if let Some(uv_data) = uvs {
-            sample.uvs = Some(uv_data.iter()
-                .map(|u| glam::Vec2::new(u[0], u[1]))
-                .collect());
-        }
+        let mut sample = OPolyMeshSample::new(positions.0, face_counts.0, face_indices.0);
+        sample.keep_topology = keep_topology;
+        sample.normals = normals.map(|n| n.0);
+        sample.uvs = uvs.map(|u| u.0);
         
         inner.add_sample(&sample);
         Ok(())
@@ -472,6 +501,12 @@ impl PyOXform {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Check if this object has been consumed (added to a parent).
     fn isConsumed(&self) -> bool {
         self.inner.is_none()
@@ -617,6 +652,12 @@ impl PyOCurves {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a sample.
     /// curve_type: "linear", "cubic", "bezier", "bspline", "catmullrom", "hermite"
     /// wrap: "nonperiodic", "periodic"
@@ -629,22 +670,18 @@ impl PyOCurves {
     ))]
     fn addSample(
         &mut self,
-        positions: Vec<[f32; 3]>,
-        num_vertices: Vec<i32>,
+        positions: Vec3Array,
+        num_vertices: I32Array,
         curve_type: &str,
         wrap: &str,
         basis: &str,
-        velocities: Option<Vec<[f32; 3]>>,
-        widths: Option<Vec<f32>>,
-        normals: Option<Vec<[f32; 3]>>,
-        uvs: Option<Vec<[f32; 2]>>,
-        knots: Option<Vec<f32>>,
-        orders: Option<Vec<i32>>,
+        velocities: Option<Vec3Array>,
+        widths: Option<F32Array>,
+        normals: Option<Vec3Array>,
+        uvs: Option<Vec2Array>,
+        knots: Option<F32Array>,
+        orders: Option<I32Array>,
     ) -> PyResult<()> {
-        let pos: Vec<glam::Vec3> = positions.iter()
-            .map(|p| glam::Vec3::new(p[0], p[1], p[2]))
-            .collect();
-        
         let ct = match curve_type.to_lowercase().as_str() {
             "cubic" | "bezier" | "bspline" | "catmullrom" | "hermite" => CurveType::Cubic,
             "linear" => CurveType::Linear,
@@ -666,33 +703,17 @@ impl PyOCurves {
             _ => BasisType::NoBasis,
         };
         
-        let mut sample = OCurvesSample::new(pos, num_vertices)
+        let mut sample = OCurvesSample::new(positions.0, num_vertices.0)
             .with_curve_type(ct)
             .with_wrap(w)
             .with_basis(b);
         
-        if let Some(vels) = velocities {
-            sample.velocities = Some(vels.iter()
-                .map(|v| glam::Vec3::new(v[0], v[1], v[2]))
-                .collect());
-        }
-        
-        sample.widths = widths;
-        
-        if let Some(norms) = normals {
-            sample.normals = Some(norms.iter()
-                .map(|n| glam::Vec3::new(n[0], n[1], n[2]))
-                .collect());
-        }
-        
-        if let Some(uv_data) = uvs {
-            sample.uvs = Some(uv_data.iter()
-                .map(|u| glam::Vec2::new(u[0], u[1]))
-                .collect());
-        }
-        
-        sample.knots = knots;
-        sample.orders = orders;
+        sample.velocities = velocities.map(|v| v.0);
+        sample.widths = widths.map(|w| w.0);
+        sample.normals = normals.map(|n| n.0);
+        sample.uvs = uvs.map(|u| u.0);
+        sample.knots = knots.map(|k| k.0);
+        sample.orders = orders.map(|o| o.0);
         
         self.inner_mut()?.add_sample(&sample);
         Ok(())
@@ -744,32 +765,39 @@ impl PyOPoints {
         &self.name
     }
     
-    /// Add a sample.
-    #[pyo3(signature = (positions, ids, velocities=None, widths=None))]
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
+    /// Set id generation for samples without ids ("sequential" or "hashed").
+    fn setIdMode(&mut self, mode: &str) -> PyResult<()> {
+        let mode = match mode {
+            "sequential" => PointIdMode::Sequential,
+            "hashed" => PointIdMode::Hashed,
+            _ => return Err(PyValueError::new_err(format!("Unknown id mode '{}'", mode))),
+        };
+        self.inner_mut()?.set_id_mode(mode);
+        Ok(())
+    }
+    
+    /// Add a sample. Ids are generated when omitted and must be unique otherwise.
+    #[pyo3(signature = (positions, ids=None, velocities=None, widths=None))]
     fn addSample(
         &mut self,
-        positions: Vec<[f32; 3]>,
-        ids: Vec<u64>,
-        velocities: Option<Vec<[f32; 3]>>,
-        widths: Option<Vec<f32>>,
+        positions: Vec3Array,
+        ids: Option<IdArray>,
+        velocities: Option<Vec3Array>,
+        widths: Option<F32Array>,
     ) -> PyResult<()> {
-        let pos: Vec<glam::Vec3> = positions.iter()
-            .map(|p| glam::Vec3::new(p[0], p[1], p[2]))
-            .collect();
+        // Alembic uses signed ids internally
+        let mut sample = OPointsSample::new(positions.0, ids.map(|i| i.0).unwrap_or_default());
+        sample.velocities = velocities.map(|v| v.0);
+        sample.widths = widths.map(|w| w.0);
         
-        // Convert u64 to i64 (Alembic uses signed IDs internally)
-        let ids_i64: Vec<i64> = ids.iter().map(|&id| id as i64).collect();
-        let mut sample = OPointsSample::new(pos, ids_i64);
-        
-        if let Some(vels) = velocities {
-            sample.velocities = Some(vels.iter()
-                .map(|v| glam::Vec3::new(v[0], v[1], v[2]))
-                .collect());
-        }
-        
-        sample.widths = widths;
-        
-        self.inner_mut()?.add_sample(&sample);
+        self.inner_mut()?.try_add_sample(&sample)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
         Ok(())
     }
     
@@ -819,57 +847,64 @@ impl PyOSubD {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a sample.
     /// scheme: "catmullClark", "loop", "bilinear"
+    /// interpolate_boundary: 0 none, 1 edge and corner, 2 edge only
+    /// fv_interpolate_boundary: 0 all (linear), 1 corners plus 1, 2 none (smooth), 3 boundaries
+    /// keep_topology: hold the previous sample's faces; face_counts and face_indices are ignored
     #[allow(clippy::too_many_arguments)] // Python API requires many params for full SubD sample
     #[pyo3(signature = (
         positions, face_counts, face_indices, scheme="catmullClark",
         velocities=None, crease_indices=None, crease_lengths=None, crease_sharpnesses=None,
-        corner_indices=None, corner_sharpnesses=None, holes=None, uvs=None, uv_indices=None
+        corner_indices=None, corner_sharpnesses=None, holes=None, uvs=None, uv_indices=None,
+        interpolate_boundary=None, fv_interpolate_boundary=None, keep_topology=false
     ))]
     fn addSample(
         &mut self,
-        positions: Vec<[f32; 3]>,
-        face_counts: Vec<i32>,
-        face_indices: Vec<i32>,
+        positions: Vec3Array,
+        face_counts: I32Array,
+        face_indices: I32Array,
         scheme: &str,
-        velocities: Option<Vec<[f32; 3]>>,
+        velocities: Option<Vec3Array>,
         crease_indices: Option<Vec<i32>>,
         crease_lengths: Option<Vec<i32>>,
         crease_sharpnesses: Option<Vec<f32>>,
         corner_indices: Option<Vec<i32>>,
         corner_sharpnesses: Option<Vec<f32>>,
         holes: Option<Vec<i32>>,
-        uvs: Option<Vec<[f32; 2]>>,
-        uv_indices: Option<Vec<i32>>,
+        uvs: Option<Vec2Array>,
+        uv_indices: Option<I32Array>,
+        interpolate_boundary: Option<i32>,
+        fv_interpolate_boundary: Option<i32>,
+        keep_topology: bool,
     ) -> PyResult<()> {
-        let pos: Vec<glam::Vec3> = positions.iter()
-            .map(|p| glam::Vec3::new(p[0], p[1], p[2]))
-            .collect();
-        
-        let mut sample = OSubDSample::new(pos, face_counts, face_indices)
+        let mut sample = OSubDSample::new(positions.0, face_counts.0, face_indices.0)
             .with_scheme(scheme);
-        
-        if let Some(vels) = velocities {
-            sample.velocities = Some(vels.iter()
-                .map(|v| glam::Vec3::new(v[0], v[1], v[2]))
-                .collect());
+        sample.keep_topology = keep_topology;
+        if let Some(v) = interpolate_boundary {
+            sample.interp_boundary = Some(InterpolateBoundary::from_i32(v)
+                .ok_or_else(|| PyValueError::new_err(format!("invalid interpolate_boundary: {}", v)))?);
+        }
+        if let Some(v) = fv_interpolate_boundary {
+            sample.fv_interp_boundary = Some(FaceVaryingInterpolation::from_i32(v)
+                .ok_or_else(|| PyValueError::new_err(format!("invalid fv_interpolate_boundary: {}", v)))?);
         }
         
+        sample.velocities = velocities.map(|v| v.0);
         sample.crease_indices = crease_indices;
         sample.crease_lengths = crease_lengths;
         sample.crease_sharpnesses = crease_sharpnesses;
         sample.corner_indices = corner_indices;
         sample.corner_sharpnesses = corner_sharpnesses;
         sample.holes = holes;
-        
-        if let Some(uv_data) = uvs {
-            sample.uvs = Some(uv_data.iter()
-                .map(|u| glam::Vec2::new(u[0], u[1]))
-                .collect());
-        }
-        
-        sample.uv_indices = uv_indices;
+        sample.uvs = uvs.map(|u| u.0);
+        sample.uv_indices = uv_indices.map(|i| i.0);
         
         self.inner_mut()?.add_sample(&sample);
         Ok(())
@@ -920,6 +955,12 @@ impl PyOCamera {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a sample with camera parameters.
     #[allow(clippy::too_many_arguments)] // Camera has 16 standard params per Alembic spec
     #[pyo3(signature = (
@@ -1016,6 +1057,12 @@ impl PyONuPatch {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a sample.
     #[allow(clippy::too_many_arguments)] // NuPatch requires many params per Alembic spec
     #[pyo3(signature = (
@@ -1111,6 +1158,12 @@ impl PyOLight {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a camera sample (light uses camera schema for parameters).
     #[allow(clippy::too_many_arguments)] // Camera has 16 standard params per Alembic spec
     #[pyo3(signature = (
@@ -1207,6 +1260,12 @@ impl PyOFaceSet {
         &self.name
     }
     
+    /// Use a time sampling added with `OArchive.addTimeSampling` (index).
+    fn setTimeSamplingIndex(&mut self, index: u32) -> PyResult<()> {
+        self.inner_mut()?.set_time_sampling(index);
+        Ok(())
+    }
+    
     /// Add a sample with face indices.
     fn addSample(&mut self, faces: Vec<i32>) -> PyResult<()> {
         let sample = OFaceSetSample::new(faces);
@@ -0,0 +1,90 @@
+//! Example schema plugin for `alembic-cli --plugin`.
+//!
+//! Summarizes a made-up crowd schema, `acme_Crowd_v1`, whose objects carry
+//! a `.crowd` compound with an `agentType` string and a `P` array of agent
+//! positions:
+//!
+//! ```text
+//! $ cargo build -p alembic-tools --example schema_plugin
+//! $ alembic-cli --plugin target/debug/examples/libschema_plugin.so tree crowd.abc
+//! /
+//!   crowd [acme_Crowd_v1] - 250 soldier agents, 48 samples
+//! ```
+//!
+//! Only the C ABI of `include/alembic_cli_plugin.h` is used, the way a plugin
+//! written in C or C++ would; nothing here links against alembic-cli.
+
+use std::ffi::{c_char, c_void, CStr};
+
+const ABI_VERSION: u32 = 1;
+
+#[repr(C)]
+pub struct AbcPluginObject {
+    path: *const c_char,
+    name: *const c_char,
+    schema: *const c_char,
+    metadata: *const c_char,
+    num_children: u64,
+    handle: *const c_void,
+    num_samples: extern "C" fn(*const c_void, *const c_char) -> i64,
+    read_sample: extern "C" fn(*const c_void, *const c_char, u64, *mut c_void, usize) -> i64,
+}
+
+#[repr(C)]
+pub struct AbcSchemaPlugin {
+    abi_version: u32,
+    name: *const c_char,
+    schemas: *const *const c_char,
+    summarize: extern "C" fn(*const AbcPluginObject, *mut c_char, usize) -> i32,
+}
+
+/// The descriptor holds raw pointers to static data only.
+struct Descriptor(AbcSchemaPlugin);
+unsafe impl Sync for Descriptor {}
+
+struct Schemas([*const c_char; 2]);
+unsafe impl Sync for Schemas {}
+
+static SCHEMAS: Schemas = Schemas([c"acme_Crowd_v1".as_ptr(), std::ptr::null()]);
+
+static PLUGIN: Descriptor = Descriptor(AbcSchemaPlugin {
+    abi_version: ABI_VERSION,
+    name: c"acme-crowd".as_ptr(),
+    schemas: SCHEMAS.0.as_ptr(),
+    summarize,
+});
+
+#[no_mangle]
+pub extern "C" fn alembic_cli_schema_plugin() -> *const AbcSchemaPlugin {
+    &PLUGIN.0
+}
+
+/// Sample `index` of the property at `path`, as bytes.
+fn read(obj: &AbcPluginObject, path: &CStr, index: u64) -> Option<Vec<u8>> {
+    let len = (obj.read_sample)(obj.handle, path.as_ptr(), index, std::ptr::null_mut(), 0);
+    let mut buf = vec![0u8; usize::try_from(len).ok()?];
+    let read = (obj.read_sample)(obj.handle, path.as_ptr(), index, buf.as_mut_ptr() as *mut c_void, buf.len());
+    (read == len).then_some(buf)
+}
+
+extern "C" fn summarize(obj: *const AbcPluginObject, out: *mut c_char, out_len: usize) -> i32 {
+    // SAFETY: the host passes a valid object for the duration of the call
+    let Some(obj) = (unsafe { obj.as_ref() }) else { return 1 };
+    let samples = (obj.num_samples)(obj.handle, c".crowd/P".as_ptr());
+    if samples < 0 {
+        return 1;
+    }
+    let agents = read(obj, c".crowd/P", 0).map_or(0, |p| p.len() / 12);
+    let agent_type = read(obj, c".crowd/agentType", 0)
+        .map(|b| String::from_utf8_lossy(&b).into_owned())
+        .unwrap_or_else(|| "unknown".to_string());
+    let text = format!("{} {} agents, {} samples", agents, agent_type, samples);
+
+    let n = text.len().min(out_len.saturating_sub(1));
+    // SAFETY: the host passes a buffer of `out_len` bytes
+    unsafe {
+        std::ptr::copy_nonoverlapping(text.as_ptr(), out as *mut u8, n);
+        *out.add(n) = 0;
+    }
+    0
+}
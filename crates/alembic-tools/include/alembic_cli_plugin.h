@@ -0,0 +1,89 @@
+/*
+ * alembic_cli_plugin.h - schema plugin ABI for alembic-cli, version 1.
+ *
+ * A schema plugin is a shared library (.so / .dylib / .dll) that gives
+ * studio-specific schemas a one-line summary in `alembic-cli tree`, `stats`
+ * and `meta`, without rebuilding the CLI. Load plugins with
+ *
+ *     alembic-cli --plugin ./libmy_schemas.so tree shot.abc
+ *     ALEMBIC_CLI_PLUGINS=/opt/abc/plugins/libmy_schemas.so alembic-cli stats shot.abc
+ *
+ * (ALEMBIC_CLI_PLUGINS is a PATH-style list). The CLI must be built with the
+ * `plugins` feature.
+ *
+ * Stability: this ABI only grows. Structs gain fields at the end, never
+ * change or drop existing ones, and every change that a version-1 plugin
+ * could notice bumps ALEMBIC_CLI_PLUGIN_ABI_VERSION. The CLI refuses plugins
+ * reporting a version it does not know.
+ *
+ * Threading: all calls happen on one thread. Pointers handed to the plugin
+ * are only valid for the duration of the call.
+ */
+
+#ifndef ALEMBIC_CLI_PLUGIN_H
+#define ALEMBIC_CLI_PLUGIN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define ALEMBIC_CLI_PLUGIN_ABI_VERSION 1
+
+/* The object being summarized. Strings are UTF-8 and NUL-terminated. */
+typedef struct AbcPluginObject {
+    const char *path;         /* full path, e.g. "/shot/crowd" */
+    const char *name;
+    const char *schema;       /* "schema" metadata value, "" if none */
+    const char *metadata;     /* all metadata, serialized as "key=value;key=value" */
+    uint64_t num_children;
+
+    /* Passed back to the callbacks below. */
+    const void *handle;
+
+    /*
+     * Number of samples of the property at `prop_path`, with compounds
+     * separated by '/' (e.g. ".geom/P", ".arbGeomParams/agentType"), or -1
+     * if there is no such scalar/array property.
+     */
+    int64_t (*num_samples)(const void *handle, const char *prop_path);
+
+    /*
+     * Read sample `index` of the property at `prop_path` into `buf`:
+     * the raw little-endian values (all elements for arrays, UTF-8 bytes
+     * without a terminator for strings). Returns the size in bytes and
+     * copies only if it fits in `buf_len`, so call with buf_len = 0 to size
+     * the buffer. Returns -1 if the property or sample cannot be read.
+     */
+    int64_t (*read_sample)(const void *handle, const char *prop_path, uint64_t index,
+                           void *buf, size_t buf_len);
+} AbcPluginObject;
+
+typedef struct AbcSchemaPlugin {
+    uint32_t abi_version;     /* ALEMBIC_CLI_PLUGIN_ABI_VERSION */
+    const char *name;         /* shown by `alembic-cli -v` when loaded */
+
+    /* NULL-terminated list of "schema" metadata values this plugin handles. */
+    const char *const *schemas;
+
+    /*
+     * Write a one-line summary of `obj` (no newline) into `out`, NUL-
+     * terminated and at most `out_len` bytes including the terminator.
+     * Return 0 on success; anything else falls back to the built-in output.
+     */
+    int32_t (*summarize)(const AbcPluginObject *obj, char *out, size_t out_len);
+} AbcSchemaPlugin;
+
+/*
+ * The one symbol a plugin exports. The returned struct (and the strings it
+ * points to) must stay valid while the library is loaded.
+ */
+const AbcSchemaPlugin *alembic_cli_schema_plugin(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ALEMBIC_CLI_PLUGIN_H */
@@ -0,0 +1,74 @@
+//! `alembic check <file.abc> [--json] [--strict] [--max n]` - lint an archive.
+//!
+//! Thin CLI wrapper around [`alembic::validate`]: prints one line per
+//! diagnostic (or a JSON array with `--json`) and reports how many errors
+//! were found. Warnings only fail the check with `--strict`.
+
+use alembic::validate::{validate_archive, Diagnostic, Severity};
+use serde_json::json;
+
+/// Options for a check run.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Print diagnostics as a JSON array instead of text.
+    pub json: bool,
+    /// Count warnings as failures.
+    pub strict: bool,
+    /// Print at most this many diagnostics (the counts are always complete).
+    pub max: Option<usize>,
+}
+
+/// Parse the flags following `check <file>`.
+pub fn parse_args(args: &[&str]) -> Result<CheckOptions, String> {
+    let mut opts = CheckOptions::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--json" => opts.json = true,
+            "--strict" => opts.strict = true,
+            "--max" => {
+                let v = iter.next().ok_or("--max requires a number")?;
+                opts.max = Some(v.parse().map_err(|_| format!("invalid --max: {}", v))?);
+            }
+            _ => return Err(format!("unknown check option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// Validate `path` and print the findings. Returns the number of failures
+/// (errors, plus warnings with `--strict`).
+pub fn run(path: &str, opts: &CheckOptions) -> Result<usize, String> {
+    let diagnostics = validate_archive(path);
+    let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+    let warnings = diagnostics.len() - errors;
+    let shown = &diagnostics[..opts.max.unwrap_or(usize::MAX).min(diagnostics.len())];
+
+    if opts.json {
+        let list: Vec<_> = shown.iter().map(to_json).collect();
+        let out = serde_json::to_string_pretty(&list).map_err(|e| e.to_string())?;
+        println!("{}", out);
+    } else {
+        for d in shown {
+            println!("{}", d);
+        }
+        if shown.len() < diagnostics.len() {
+            println!("... {} more", diagnostics.len() - shown.len());
+        }
+        println!("{}: {} errors, {} warnings", path, errors, warnings);
+    }
+    Ok(if opts.strict { errors + warnings } else { errors })
+}
+
+fn to_json(d: &Diagnostic) -> serde_json::Value {
+    json!({
+        "severity": match d.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        },
+        "check": d.check.as_str(),
+        "object": d.object,
+        "sample": d.sample,
+        "message": d.message,
+    })
+}
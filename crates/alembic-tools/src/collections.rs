@@ -0,0 +1,119 @@
+//! `alembic collections <file.abc> [--time <sec>] [--all-samples] [--json]` - list collections.
+//!
+//! Lists every Collections object in an archive (selection sets and render
+//! layers exported from Maya, ...) with the members of each collection.
+//! Membership can be animated; `--time` picks the sample held at a time and
+//! `--all-samples` prints every sample.
+
+use alembic::abc::{IArchive as AbcIArchive, IObject};
+use alembic::collection::ICollections;
+use alembic::core::SampleSelector;
+use serde_json::json;
+
+/// Output options for `collections`.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionsOptions {
+    /// Time in seconds; members are those of the sample held at it.
+    pub time: Option<f64>,
+    pub all_samples: bool,
+    pub json: bool,
+}
+
+/// Parse the flags following `collections <file>`.
+pub fn parse_args(args: &[&str]) -> Result<CollectionsOptions, String> {
+    let mut opts = CollectionsOptions::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--time" | "-t" => {
+                let v = iter.next().ok_or("--time requires seconds")?;
+                opts.time = Some(v.parse().map_err(|_| format!("invalid --time: {}", v))?);
+            }
+            "--all-samples" => opts.all_samples = true,
+            "--json" => opts.json = true,
+            _ => return Err(format!("unknown collections option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// One collection of one Collections object.
+struct Row {
+    object: String,
+    name: String,
+    num_samples: usize,
+    members: Vec<String>,
+    /// (time, members) per sample, with `--all-samples`.
+    samples: Vec<(f64, Vec<String>)>,
+}
+
+/// List the collections of `input`.
+pub fn run(input: &str, opts: &CollectionsOptions) -> Result<(), String> {
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let mut rows = Vec::new();
+    collect_object(&archive, &archive.getTop(), opts, &mut rows);
+
+    if opts.json {
+        let list: Vec<_> = rows.iter().map(|r| {
+            let mut entry = json!({
+                "object": r.object,
+                "name": r.name,
+                "samples": r.num_samples,
+                "members": r.members,
+            });
+            if opts.all_samples {
+                entry["membership"] = r.samples.iter()
+                    .map(|(time, members)| json!({ "time": time, "members": members }))
+                    .collect();
+            }
+            entry
+        }).collect();
+        println!("{}", serde_json::to_string_pretty(&list).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+
+    let mut object = "";
+    for r in &rows {
+        if r.object != object {
+            object = &r.object;
+            println!("{}", object);
+        }
+        let animated = if r.num_samples > 1 { format!(", animated ({} samples)", r.num_samples) } else { String::new() };
+        println!("  {}  {} members{}", r.name, r.members.len(), animated);
+        if opts.all_samples && r.num_samples > 1 {
+            for (time, members) in &r.samples {
+                println!("    @{:.4}s  {}", time, members.join(" "));
+            }
+        } else {
+            for member in &r.members {
+                println!("    {}", member);
+            }
+        }
+    }
+    let objects = rows.iter().map(|r| &r.object).collect::<std::collections::HashSet<_>>().len();
+    println!("{} collections in {} objects", rows.len(), objects);
+    Ok(())
+}
+
+fn collect_object(archive: &AbcIArchive, obj: &IObject, opts: &CollectionsOptions, rows: &mut Vec<Row>) {
+    if let Some(collections) = ICollections::new(obj) {
+        let sel = opts.time.map_or(SampleSelector::first(), SampleSelector::time_floor);
+        for name in collections.collection_names() {
+            let num_samples = collections.num_samples(&name);
+            let members = collections.get_sample(&name, sel).map(|c| c.paths).unwrap_or_default();
+            let mut samples = Vec::new();
+            if opts.all_samples {
+                let ts = collections.time_sampling_index(&name).and_then(|i| archive.getTimeSampling(i as usize));
+                for i in 0..num_samples {
+                    let time = ts.map_or(0.0, |ts| ts.sample_time(i, num_samples));
+                    let paths = collections.get_sample(&name, i).map(|c| c.paths).unwrap_or_default();
+                    samples.push((time, paths));
+                }
+            }
+            rows.push(Row { object: obj.getFullName().to_string(), name, num_samples, members, samples });
+        }
+    }
+    for child in obj.getChildren() {
+        collect_object(archive, &child, opts, rows);
+    }
+}
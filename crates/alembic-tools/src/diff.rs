@@ -0,0 +1,77 @@
+//! `alembic diff <a.abc> <b.abc>` - structural and numeric archive diff.
+//!
+//! Thin CLI wrapper around [`alembic::compare`]: prints added/removed objects
+//! and properties, schema/type mismatches, sample count and time differences
+//! and per-sample numeric deltas above the tolerances. Exits with status 2
+//! when the archives differ, so it can gate writer regression checks against
+//! files produced by the C++ library.
+
+use alembic::abc::IArchive as AbcIArchive;
+use alembic::compare::{compare_archives, CompareOptions};
+use tracing::info;
+
+/// Maximum number of differences printed unless `--max` is given.
+const DEFAULT_MAX_REPORTED: usize = 50;
+
+/// Options for `diff`.
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    pub compare: CompareOptions,
+    /// Maximum number of differences to print (0 = all).
+    pub max_reported: usize,
+}
+
+/// Parse the flags following `diff <a> <b>`.
+pub fn parse_args(args: &[&str]) -> Result<DiffOptions, String> {
+    let mut opts = DiffOptions { compare: CompareOptions::default(), max_reported: DEFAULT_MAX_REPORTED };
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        let mut value = |flag: &str| -> Result<f64, String> {
+            let v = iter.next().ok_or_else(|| format!("{} requires a number", flag))?;
+            v.parse::<f64>()
+                .ok()
+                .filter(|v| *v >= 0.0)
+                .ok_or_else(|| format!("invalid {} value: {}", flag, v))
+        };
+        match arg {
+            "--tol" | "--abs-tol" => opts.compare.abs_tolerance = value(arg)?,
+            "--rel-tol" => opts.compare.rel_tolerance = value(arg)?,
+            "--time-tol" => opts.compare.time_tolerance = value(arg)?,
+            "--max" => opts.max_reported = value(arg)? as usize,
+            "--metadata" => opts.compare.compare_metadata = true,
+            _ => return Err(format!("unknown diff option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// Compare `a` against `b` and print the differences.
+/// Returns the number of differences found.
+pub fn run(a: &str, b: &str, opts: &DiffOptions) -> Result<usize, String> {
+    info!("Diff {} {} ({:?})", a, b, opts);
+    let archive_a = AbcIArchive::open(a).map_err(|e| format!("failed to open {}: {}", a, e))?;
+    let archive_b = AbcIArchive::open(b).map_err(|e| format!("failed to open {}: {}", b, e))?;
+    let report = compare_archives(&archive_a, &archive_b, &opts.compare).map_err(|e| format!("compare failed: {}", e))?;
+
+    let diffs = &report.differences;
+    if diffs.is_empty() {
+        println!("Identical: {} and {}", a, b);
+    } else {
+        println!("--- {}", a);
+        println!("+++ {}", b);
+        let limit = if opts.max_reported == 0 { diffs.len() } else { opts.max_reported };
+        for d in diffs.iter().take(limit) {
+            println!("{}", d);
+        }
+        if diffs.len() > limit {
+            println!("... {} more", diffs.len() - limit);
+        }
+        println!();
+    }
+    println!("  Objects:     {}", report.objects_compared);
+    println!("  Properties:  {}", report.properties_compared);
+    println!("  Samples:     {}", report.samples_compared);
+    println!("  Max delta:   {:e}", report.max_delta);
+    println!("  Differences: {}", diffs.len());
+    Ok(diffs.len())
+}
@@ -0,0 +1,276 @@
+//! `alembic echo <file.abc> </obj.prop> [--sample n] [--format hex|floats] [--compare other.abc]` - raw samples.
+//!
+//! In the spirit of the C++ `abcecho`: dumps the stored payload of a
+//! property's samples, as a hex dump or as decoded values, with each
+//! sample's dimensions and stored key. `--compare` reads the same property
+//! from a second archive and reports the bytes that differ, for tracking
+//! down byte-level differences against files written by the C++ library;
+//! the command then exits 2 if any sample differs.
+
+use alembic::abc::{IArchive as AbcIArchive, IProperty};
+use alembic::compare::pod_values;
+use alembic::util::PlainOldDataType;
+
+use crate::histo::find_property;
+
+/// How sample payloads are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EchoFormat {
+    /// `hexdump -C` style rows.
+    #[default]
+    Hex,
+    /// Values decoded by POD type, one element per line.
+    Floats,
+}
+
+/// Options for `echo`.
+#[derive(Debug, Clone, Default)]
+pub struct EchoOptions {
+    /// Only this sample; all samples otherwise.
+    pub sample: Option<usize>,
+    pub format: EchoFormat,
+    /// Archive to diff the payload bytes against.
+    pub compare: Option<String>,
+}
+
+/// Differing 16-byte rows printed per sample with `--compare`.
+const MAX_DIFF_ROWS: usize = 16;
+
+/// Parse the flags following `echo <file> </obj.prop>`.
+pub fn parse_args(args: &[&str]) -> Result<EchoOptions, String> {
+    let mut opts = EchoOptions::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--sample" | "-s" => {
+                let v = iter.next().ok_or("--sample requires an index")?;
+                opts.sample = Some(v.parse().map_err(|_| format!("invalid --sample: {}", v))?);
+            }
+            "--format" | "-f" => {
+                opts.format = match *iter.next().ok_or("--format requires hex|floats")? {
+                    "hex" => EchoFormat::Hex,
+                    "floats" => EchoFormat::Floats,
+                    other => return Err(format!("unknown format: {} (expected hex|floats)", other)),
+                };
+            }
+            "--compare" => opts.compare = Some(iter.next().ok_or("--compare requires a file")?.to_string()),
+            _ => return Err(format!("unknown echo option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// One stored sample.
+struct Sample {
+    index: usize,
+    dims: Vec<usize>,
+    key: [u8; 16],
+    payload: Vec<u8>,
+}
+
+/// One property's samples, read up front.
+struct Samples {
+    object: String,
+    property: String,
+    pod: PlainOldDataType,
+    extent: usize,
+    is_array: bool,
+    num_samples: usize,
+    /// The selected samples.
+    data: Vec<Sample>,
+}
+
+/// Dump `path` from `input`. Returns the number of differences from the
+/// `--compare` archive (0 without it).
+pub fn run(input: &str, path: &str, opts: &EchoOptions) -> Result<usize, String> {
+    let ours = read(input, path, opts.sample)?;
+    let Some(other) = &opts.compare else {
+        print_samples(&ours, opts.format);
+        return Ok(0);
+    };
+    let theirs = read(other, path, opts.sample)?;
+    Ok(print_comparison(&ours, &theirs, input, other))
+}
+
+fn read(input: &str, path: &str, sample: Option<usize>) -> Result<Samples, String> {
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    find_property(&archive, path, &mut |object, property, prop| read_samples(object, property, prop, sample))
+        .unwrap_or_else(|| Err(format!("property not found in {}: {} (expected /object/path.property)", input, path)))
+}
+
+fn read_samples(object: &str, property: &str, prop: &IProperty, sample: Option<usize>) -> Result<Samples, String> {
+    let raw = prop.asRaw().ok_or_else(|| format!("{} is a compound property", property))?;
+    let num_samples = raw.getNumSamples();
+    let key = |i: usize| -> Result<[u8; 16], String> {
+        let key = match (prop.asArray(), prop.asScalar()) {
+            (Some(array), _) => array.getKey(i),
+            (_, Some(scalar)) => scalar.getKey(i),
+            _ => unreachable!("raw properties are scalar or array"),
+        };
+        key.map_err(|e| e.to_string())
+    };
+    let indices = match sample {
+        Some(i) if i >= num_samples => {
+            return Err(format!("--sample {} out of range: {} has {} samples", i, property, num_samples));
+        }
+        Some(i) => i..i + 1,
+        None => 0..num_samples,
+    };
+    let mut data = Vec::with_capacity(indices.len());
+    for index in indices {
+        data.push(Sample {
+            index,
+            dims: raw.getDimensions(index).map_err(|e| e.to_string())?,
+            key: key(index)?,
+            payload: raw.getSample(index).map_err(|e| e.to_string())?,
+        });
+    }
+    let dt = prop.getHeader().data_type;
+    Ok(Samples {
+        object: object.to_string(),
+        property: property.to_string(),
+        pod: dt.pod,
+        extent: dt.extent.max(1) as usize,
+        is_array: raw.isArray(),
+        num_samples,
+        data,
+    })
+}
+
+fn header(samples: &Samples) -> String {
+    format!(
+        "{} {}  {} {}[{}]  {} samples",
+        samples.object,
+        samples.property,
+        if samples.is_array { "array" } else { "scalar" },
+        samples.pod.name(),
+        samples.extent,
+        samples.num_samples
+    )
+}
+
+fn print_samples(samples: &Samples, format: EchoFormat) {
+    println!("{}", header(samples));
+    for Sample { index, dims, key, payload } in &samples.data {
+        println!("sample {}  dims {:?}  {} bytes  key {}", index, dims, payload.len(), hex(key));
+        match format {
+            EchoFormat::Hex => {
+                for (row, chunk) in payload.chunks(16).enumerate() {
+                    println!("  {}", hex_row(row * 16, chunk));
+                }
+            }
+            EchoFormat::Floats => print_values(samples, payload),
+        }
+    }
+}
+
+/// Decoded values, one element per line; strings split at their NULs.
+fn print_values(samples: &Samples, payload: &[u8]) {
+    if samples.pod.is_string() {
+        for (i, s) in payload.split(|&b| b == 0).filter(|s| !s.is_empty()).enumerate() {
+            println!("  [{}] {:?}", i, String::from_utf8_lossy(s));
+        }
+        return;
+    }
+    let Some(values) = pod_values(samples.pod, payload) else {
+        println!("  ({} can't be decoded, use --format hex)", samples.pod.name());
+        return;
+    };
+    for (i, element) in values.chunks(samples.extent).enumerate() {
+        let parts: Vec<String> = element.iter().map(|v| v.to_string()).collect();
+        match parts.as_slice() {
+            [single] => println!("  [{}] {}", i, single),
+            _ => println!("  [{}] ({})", i, parts.join(", ")),
+        }
+    }
+}
+
+/// Print where `ours` and `theirs` differ; returns the number of differing
+/// samples (a data type or sample count mismatch counts as one).
+fn print_comparison(ours: &Samples, theirs: &Samples, a: &str, b: &str) -> usize {
+    println!("--- {}  {}", a, header(ours));
+    println!("+++ {}  {}", b, header(theirs));
+    let mut differing = 0;
+    if (ours.pod, ours.extent, ours.is_array) != (theirs.pod, theirs.extent, theirs.is_array) {
+        println!("data types differ");
+        differing += 1;
+    }
+    if ours.num_samples != theirs.num_samples {
+        println!("sample counts differ: {} vs {}", ours.num_samples, theirs.num_samples);
+        differing += 1;
+    }
+    for (ours, theirs) in ours.data.iter().zip(&theirs.data) {
+        let Sample { index, dims, key, payload } = ours;
+        let Sample { dims: other_dims, key: other_key, payload: other_payload, .. } = theirs;
+        if payload == other_payload && dims == other_dims {
+            let keys = if key == other_key { "" } else { " (stored keys differ)" };
+            println!("sample {}: identical, {} bytes{}", index, payload.len(), keys);
+            continue;
+        }
+        differing += 1;
+        let bytes = payload.iter().zip(other_payload).filter(|(x, y)| x != y).count()
+            + payload.len().abs_diff(other_payload.len());
+        if payload.len() == other_payload.len() && dims == other_dims {
+            println!("sample {}: {} of {} bytes differ", index, bytes, payload.len());
+        } else {
+            println!(
+                "sample {}: {} bytes differ ({} vs {} bytes, dims {:?} vs {:?})",
+                index, bytes, payload.len(), other_payload.len(), dims, other_dims
+            );
+        }
+        let rows = payload.len().max(other_payload.len()).div_ceil(16);
+        let row_bytes = |data: &[u8], row: usize| data.get(row * 16..data.len().min(row * 16 + 16)).unwrap_or(&[]).to_vec();
+        let mut shown = 0;
+        for row in 0..rows {
+            let (x, y) = (row_bytes(payload, row), row_bytes(other_payload, row));
+            if x == y {
+                continue;
+            }
+            if shown == MAX_DIFF_ROWS {
+                println!("  ... more differing rows");
+                break;
+            }
+            println!("- {}", hex_row(row * 16, &x));
+            println!("+ {}", hex_row(row * 16, &y));
+            shown += 1;
+        }
+    }
+    differing
+}
+
+/// `00000010  00 00 80 3f ...  |...?|`
+fn hex_row(offset: usize, bytes: &[u8]) -> String {
+    let mut hex_part = String::with_capacity(49);
+    for (i, b) in bytes.iter().enumerate() {
+        if i == 8 {
+            hex_part.push(' ');
+        }
+        hex_part.push_str(&format!("{:02x} ", b));
+    }
+    let ascii: String = bytes.iter().map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }).collect();
+    format!("{:08x}  {:<49} |{}|", offset, hex_part, ascii)
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_row() {
+        let row = hex_row(16, &[0x00, 0x00, 0x80, 0x3f, b'a', b'b']);
+        assert_eq!(row, format!("00000010  {:<49} |...?ab|", "00 00 80 3f 61 62 "));
+        let full: Vec<u8> = (0..16).collect();
+        assert!(hex_row(0, &full).contains("07  08"));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let opts = parse_args(&["--sample", "12", "--format", "floats", "--compare", "cpp.abc"]).unwrap();
+        assert_eq!((opts.sample, opts.format, opts.compare.as_deref()), (Some(12), EchoFormat::Floats, Some("cpp.abc")));
+        assert!(parse_args(&["--format", "octal"]).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! `alembic extract <in> </path/to/subtree> <out> [--bake]` - split out a sub-hierarchy.
+//!
+//! Heavy set caches are often handed to layout in pieces. `extract` writes a
+//! new archive holding one object and everything below it. By default the
+//! ancestors are kept (with their transforms, but without their other
+//! children) so the subtree stays where it was in world space; with `--bake`
+//! the ancestors are left out and their transforms are multiplied into the
+//! subtree root instead.
+//!
+//! The path may also be a hierarchy query (`/set/building_*`,
+//! `/set/**[PolyMesh]`, see `alembic::query`). Without `--bake` every match
+//! is extracted, sharing their ancestors; `--bake` needs exactly one match.
+//!
+//! Instances whose source lies inside the subtree stay instances (with the
+//! path rewritten when baking); the others are expanded into copies.
+
+use std::collections::HashMap;
+
+use alembic::abc::IArchive as AbcIArchive;
+use alembic::core::TimeSampling;
+use alembic::ogawa::writer::{OArchive, OObject, OProperty, OPropertyData, OXform, OXformSample};
+use alembic::prelude::{IObject, IXform};
+use alembic::query::Query;
+use alembic::util::Chrono;
+use tracing::{debug, info};
+
+/// Options for an extract.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    /// Drop the ancestors and bake their transforms into the subtree root.
+    pub bake: bool,
+}
+
+/// Parse the flags following `extract <in> <path> <out>`.
+pub fn parse_args(args: &[&str]) -> Result<ExtractOptions, String> {
+    let mut opts = ExtractOptions::default();
+    for &arg in args {
+        match arg {
+            "--bake" | "--bake-xforms" => opts.bake = true,
+            "--preserve" | "--keep-ancestors" => opts.bake = false,
+            _ => return Err(format!("unknown extract option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// Shared state for one extract.
+struct Extractor {
+    ts_map: HashMap<u32, u32>,
+    /// Full paths of the extracted objects in the input.
+    sources: Vec<String>,
+    /// Instance source paths starting with `from` are rewritten to start with `to`.
+    from: String,
+    to: String,
+    objects: usize,
+    ancestors: usize,
+    instances_kept: usize,
+    instances_expanded: usize,
+}
+
+/// Write the objects at `path` (a path or a query) in `input`, with everything
+/// below them, to `output`.
+pub fn extract(input: &str, path: &str, output: &str, opts: &ExtractOptions) -> Result<(), String> {
+    info!("Extract {} from {} -> {} (bake: {})", path, input, output, opts.bake);
+
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let targets = resolve_targets(&archive, path, input)?;
+    if opts.bake && targets.len() > 1 {
+        return Err(format!(
+            "--bake needs a single object, but {} matches {}: {}",
+            path,
+            targets.len(),
+            targets.join(", ")
+        ));
+    }
+
+    let mut out_archive = OArchive::create(output).map_err(|e| format!("failed to create {}: {}", output, e))?;
+    out_archive.set_archive_metadata(archive.getArchiveMetaData().clone());
+    out_archive.set_library_version(archive.getArchiveVersion());
+
+    let mut ts_map = HashMap::new();
+    ts_map.insert(0, 0);
+    for i in 1..archive.getNumTimeSamplings() {
+        if let Some(ts) = archive.getTimeSampling(i) {
+            ts_map.insert(i as u32, out_archive.addTimeSampling(ts.clone()));
+        }
+    }
+
+    // Instance paths stay as they are unless baking moves the subtree
+    let mut e = Extractor {
+        ts_map,
+        sources: targets.clone(),
+        from: String::new(),
+        to: String::new(),
+        objects: 0,
+        ancestors: 0,
+        instances_kept: 0,
+        instances_expanded: 0,
+    };
+
+    let mut out_root = OObject::new("");
+    let summary = if opts.bake {
+        let path = &targets[0];
+        let target = archive.findObject(path).ok_or_else(|| format!("no object at {} in {}", path, input))?;
+        // Every proper prefix of the path, outermost first
+        let ancestors: Vec<IObject> = path
+            .match_indices('/')
+            .skip(1)
+            .map(|(i, _)| archive.findObject(&path[..i]).ok_or_else(|| format!("no object at {}", &path[..i])))
+            .collect::<Result<_, _>>()?;
+        e.from = path[..path.rfind('/').unwrap_or(0)].to_string();
+        let (top, summary) = e.bake(&archive, &mut out_archive, &target, &ancestors)?;
+        out_root.add_child(top);
+        summary
+    } else {
+        for child in archive.getTop().getChildren() {
+            if let Some(node) = e.copy_towards(&child)? {
+                out_root.add_child(node);
+            }
+        }
+        format!("{} kept", e.ancestors)
+    };
+
+    out_archive.write_archive(&out_root).map_err(|e| format!("failed to write archive: {}", e))?;
+
+    if let [target] = targets.as_slice() {
+        println!("Extracted {} from {} -> {}", target, input, output);
+    } else {
+        println!("Extracted {} subtrees matching {} from {} -> {}", targets.len(), path, input, output);
+    }
+    println!("  Objects:   {}", e.objects);
+    println!("  Ancestors: {}", summary);
+    if e.instances_kept + e.instances_expanded > 0 {
+        println!("  Instances: {} kept, {} expanded (source outside the subtree)", e.instances_kept, e.instances_expanded);
+    }
+    Ok(())
+}
+
+/// Paths selected by `pattern`: a plain path, or every match of a query that
+/// is not already below another match.
+fn resolve_targets(archive: &AbcIArchive, pattern: &str, input: &str) -> Result<Vec<String>, String> {
+    if !pattern.contains(['*', '?', '[', '{', '\\']) {
+        let path = format!("/{}", pattern.trim_matches('/'));
+        if path == "/" {
+            return Err("extract needs an object path below the root, e.g. /set/building_01".to_string());
+        }
+        archive.findObject(&path).ok_or_else(|| format!("no object at {} in {}", path, input))?;
+        return Ok(vec![path]);
+    }
+    let query = Query::parse(pattern).map_err(|e| e.to_string())?;
+    let mut targets: Vec<String> = Vec::new();
+    // Matches come in hierarchy order, so a nested match follows its ancestor
+    for path in query.select_paths(&archive.getTop()) {
+        if !targets.iter().any(|t| path.starts_with(t.as_str()) && path[t.len()..].starts_with('/')) {
+            targets.push(path);
+        }
+    }
+    if targets.is_empty() {
+        return Err(format!("no objects match {} in {}", pattern, input));
+    }
+    Ok(targets)
+}
+
+impl Extractor {
+    /// Whether `path` is one of the extracted objects or inside one.
+    fn in_sources(&self, path: &str) -> bool {
+        self.sources.iter().any(|s| path.starts_with(s.as_str()) && (path.len() == s.len() || path[s.len()..].starts_with('/')))
+    }
+
+    /// Copy `obj` if it is a target, or the part of it leading to targets
+    /// (its own data and the children on the way); `None` if no target is below.
+    fn copy_towards(&mut self, obj: &IObject) -> Result<Option<OObject>, String> {
+        let path = obj.getFullName();
+        if self.sources.iter().any(|s| s == path) {
+            return self.copy_object(obj).map(Some);
+        }
+        let prefix = format!("{}/", path);
+        if !self.sources.iter().any(|s| s.starts_with(&prefix)) {
+            return Ok(None);
+        }
+        self.ancestors += 1;
+        let mut out = self.copy_own(obj);
+        drop_child_bounds(&mut out);
+        for child in obj.getChildren() {
+            if let Some(node) = self.copy_towards(&child)? {
+                out.add_child(node);
+            }
+        }
+        Ok(Some(out))
+    }
+
+    /// Copy `obj` and its descendants.
+    fn copy_object(&mut self, obj: &IObject) -> Result<OObject, String> {
+        debug!("extract: {}", obj.getFullName());
+        self.objects += 1;
+        if let Some(source) = obj.instance_source_path() {
+            if self.in_sources(source) {
+                self.instances_kept += 1;
+                let rewritten = format!("{}{}", self.to, &source[self.from.len()..]);
+                let mut out = OObject::new("");
+                out.add_instance_named(obj.getName(), &rewritten);
+                return Ok(out.children.remove(0));
+            }
+            // The source won't be in the output: copy what the instance resolves to
+            self.instances_expanded += 1;
+        }
+        let mut out = self.copy_own(obj);
+        for child in obj.getChildren() {
+            out.add_child(self.copy_object(&child)?);
+        }
+        Ok(out)
+    }
+
+    /// Copy the metadata and properties of `obj`, without children.
+    fn copy_own(&self, obj: &IObject) -> OObject {
+        let mut out = OObject::new(obj.getName());
+        out.meta_data = obj.getMetaData().clone();
+        super::copy_properties_from(&obj.getProperties(), &mut out.properties, &self.ts_map);
+        out
+    }
+
+    /// Top-level object for `--bake`, and a summary line.
+    fn bake(
+        &mut self,
+        archive: &AbcIArchive,
+        out_archive: &mut OArchive,
+        target: &IObject,
+        ancestors: &[IObject],
+    ) -> Result<(OObject, String), String> {
+        let chain: Vec<(IXform, TimeSampling)> = ancestors
+            .iter()
+            .filter_map(IXform::new)
+            .map(|x| {
+                let ts = archive.getTimeSampling(x.getTimeSamplingIndex() as usize).cloned().unwrap_or_default();
+                (x, ts)
+            })
+            .collect();
+        let own = IXform::new(target).map(|x| {
+            let ts = archive.getTimeSampling(x.getTimeSamplingIndex() as usize).cloned().unwrap_or_default();
+            (x, ts)
+        });
+
+        // Bake at every time any of the involved transforms has a sample
+        let mut times: Vec<Chrono> = Vec::new();
+        for (xform, ts) in chain.iter().chain(own.iter()) {
+            let n = xform.getNumSamples();
+            if n > 1 {
+                times.extend((0..n).map(|i| ts.sample_time(i, n)));
+            }
+        }
+        times.sort_by(|a, b| a.total_cmp(b));
+        times.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+        let animated = times.len() > 1;
+        if times.is_empty() {
+            times.push(0.0);
+        }
+
+        let parent_world = |t: Chrono| -> Result<glam::Mat4, String> {
+            let mut world = glam::Mat4::IDENTITY;
+            for (xform, ts) in &chain {
+                let m = sample_at(xform, ts, t)?;
+                world = if m.inherits { world * m.matrix() } else { m.matrix() };
+            }
+            Ok(world)
+        };
+        let worlds: Vec<glam::Mat4> = times.iter().map(|&t| parent_world(t)).collect::<Result<_, _>>()?;
+
+        let ts_index = if !animated {
+            0
+        } else if let Some((xform, _)) = own.as_ref().filter(|(x, ts)| same_times(ts, x.getNumSamples(), &times)) {
+            // The subtree root's own sampling covers every time
+            debug!("extract: baking on the time sampling of {}", xform.getFullName());
+            super::map_ts(&self.ts_map, xform.getTimeSamplingIndex())
+        } else {
+            out_archive.addTimeSampling(TimeSampling::acyclic(times.clone()))
+        };
+
+        let mut out_xform;
+        let wrapper = own.is_none();
+        if let Some((xform, ts)) = &own {
+            self.to = String::new();
+            out_xform = OXform::new(target.getName());
+            for (&t, world) in times.iter().zip(&worlds) {
+                let local = sample_at(xform, ts, t)?;
+                let m = if local.inherits { *world * local.matrix() } else { local.matrix() };
+                out_xform.add_sample(OXformSample::from_matrix(m, local.inherits));
+            }
+        } else if animated || worlds[0] != glam::Mat4::IDENTITY {
+            // No transform of its own: wrap it in one named after its parent
+            let parent = ancestors.last().map(|a| a.getName().to_string()).unwrap_or_default();
+            self.to = format!("/{}", parent);
+            out_xform = OXform::new(&parent);
+            for world in &worlds {
+                out_xform.add_sample(OXformSample::from_matrix(*world, true));
+            }
+        } else {
+            self.to = String::new();
+            return Ok((self.copy_object(target)?, format!("{} dropped (identity)", ancestors.len())));
+        }
+        out_xform.set_time_sampling(ts_index);
+
+        let out = if wrapper {
+            out_xform.add_child(self.copy_object(target)?);
+            out_xform.build()
+        } else {
+            // Children first, so `.childBnds` is computed from them
+            self.objects += 1;
+            for child in target.getChildren() {
+                out_xform.add_child(self.copy_object(&child)?);
+            }
+            let mut out = out_xform.build();
+            out.meta_data = target.getMetaData().clone();
+            let props = target.getProperties();
+            for i in 0..props.getNumProperties() {
+                let Some(prop) = props.getProperty(i) else { continue };
+                if prop.getHeader().name == ".xform" {
+                    continue;
+                }
+                if let Some(copy) = super::copy_property_recursive(&prop, &self.ts_map) {
+                    super::merge_property(&mut out.properties, copy);
+                }
+            }
+            out
+        };
+        let into = if wrapper { format!("a new /{}", out.name) } else { format!("/{}", out.name) };
+        Ok((out, format!("{} baked into {} ({} samples)", ancestors.len(), into, times.len())))
+    }
+}
+
+/// Transform of `xform` at `time`, interpolated between samples.
+fn sample_at(xform: &IXform, ts: &TimeSampling, time: Chrono) -> Result<alembic::geom::XformSample, String> {
+    let result = if xform.getNumSamples() > 1 {
+        xform.get_interpolated_sample(time, ts)
+    } else {
+        xform.getSample(0)
+    };
+    result.map_err(|e| format!("failed to read {} at {}: {}", xform.getFullName(), time, e))
+}
+
+/// Whether `ts` with `num_samples` samples puts them exactly at `times`.
+fn same_times(ts: &TimeSampling, num_samples: usize, times: &[Chrono]) -> bool {
+    num_samples == times.len() && times.iter().enumerate().all(|(i, &t)| (ts.sample_time(i, num_samples) - t).abs() < 1e-9)
+}
+
+/// Remove `.xform/.childBnds`: an ancestor's bounds still cover the siblings left out.
+pub(crate) fn drop_child_bounds(obj: &mut OObject) {
+    if let Some(OProperty { data: OPropertyData::Compound(children), .. }) = obj.properties.iter_mut().find(|p| p.name == ".xform") {
+        children.retain(|c| c.name != ".childBnds");
+    }
+}
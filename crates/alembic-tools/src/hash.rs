@@ -0,0 +1,157 @@
+//! `alembic hash` - content digest tree for dedup and cache invalidation.
+//!
+//! Prints, for every object, the SpookyHash digests Ogawa writers store in
+//! the object headers: the properties hash and the children hash (C++
+//! `getPropertiesHash` / `getChildrenHash`). No sample data is read, so
+//! hashing is cheap even for large caches. `--properties` adds one digest
+//! per property, built from the stored sample keys.
+//!
+//! ```text
+//! alembic hash shot.abc                 # archive digest + one line per object
+//! alembic hash shot.abc --properties    # plus one line per property
+//! alembic hash shot.abc --json
+//! ```
+//!
+//! Two archives with the same archive digest hold the same hierarchy,
+//! metadata and samples; an object whose children hash is unchanged can
+//! keep everything cached below it.
+
+use alembic::abc::{IArchive as AbcIArchive, ICompoundProperty};
+use alembic::core::compute_digest;
+use alembic::prelude::IObject;
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+/// Placeholder for digests the archive does not store.
+const MISSING: &str = "--------------------------------";
+
+/// Output options for `alembic hash`.
+#[derive(Debug, Clone, Default)]
+pub struct HashOptions {
+    /// Also print a digest per property.
+    pub properties: bool,
+    /// Print JSON instead of text.
+    pub json: bool,
+}
+
+/// Parse the flags following `hash <file>`.
+pub fn parse_args(args: &[&str]) -> Result<HashOptions, String> {
+    let mut opts = HashOptions::default();
+    for &arg in args {
+        match arg {
+            "--properties" | "-p" => opts.properties = true,
+            "--json" | "-j" => opts.json = true,
+            _ => return Err(format!("unknown hash option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// Digests of one object.
+struct ObjectDigest {
+    path: String,
+    properties_hash: Option<[u8; 16]>,
+    children_hash: Option<[u8; 16]>,
+    /// Property path (compounds joined with '/') -> digest.
+    properties: Vec<(String, [u8; 16])>,
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_or_missing(digest: Option<[u8; 16]>) -> String {
+    digest.map_or_else(|| MISSING.to_string(), |d| hex(&d))
+}
+
+/// Whole-archive digest: the top object's properties and children hashes
+/// combined.
+fn archive_digest(top: &ObjectDigest) -> Option<[u8; 16]> {
+    let mut bytes = top.properties_hash?.to_vec();
+    bytes.extend_from_slice(&top.children_hash?);
+    Some(compute_digest(&bytes, None, None))
+}
+
+fn add_properties(
+    props: &ICompoundProperty<'_>,
+    prefix: &str,
+    out: &mut Vec<(String, [u8; 16])>,
+) -> alembic::util::Result<()> {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        let name = format!("{}{}", prefix, prop.getName());
+        out.push((name.clone(), prop.digest()?));
+        if let Some(compound) = prop.asCompound() {
+            add_properties(&compound, &format!("{}/", name), out)?;
+        }
+    }
+    Ok(())
+}
+
+fn collect(obj: &IObject, with_properties: bool, out: &mut Vec<ObjectDigest>) -> Result<(), String> {
+    let path = if obj.getFullName().is_empty() { "/" } else { obj.getFullName() };
+    debug!("hash: {}", path);
+    let mut properties = Vec::new();
+    if with_properties {
+        add_properties(&obj.getProperties(), "", &mut properties).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    out.push(ObjectDigest {
+        path: path.to_string(),
+        properties_hash: obj.properties_hash(),
+        children_hash: obj.children_hash(),
+        properties,
+    });
+    for child in obj.getChildren() {
+        collect(&child, with_properties, out)?;
+    }
+    Ok(())
+}
+
+fn to_json(source: &str, objects: &[ObjectDigest]) -> Value {
+    let entries: Vec<Value> = objects
+        .iter()
+        .map(|o| {
+            let mut v = json!({
+                "path": o.path,
+                "properties_hash": o.properties_hash.map(|d| hex(&d)),
+                "children_hash": o.children_hash.map(|d| hex(&d)),
+            });
+            if !o.properties.is_empty() {
+                let props: serde_json::Map<String, Value> =
+                    o.properties.iter().map(|(name, d)| (name.clone(), json!(hex(d)))).collect();
+                v["properties"] = Value::Object(props);
+            }
+            v
+        })
+        .collect();
+    json!({
+        "source": source,
+        "digest": objects.first().and_then(archive_digest).map(|d| hex(&d)),
+        "objects": entries,
+    })
+}
+
+/// Print the digest tree of `input`.
+pub fn run(input: &str, opts: &HashOptions) -> Result<(), String> {
+    info!("Hash {} ({:?})", input, opts);
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let mut objects = Vec::new();
+    collect(&archive.getTop(), opts.properties, &mut objects)?;
+
+    if opts.json {
+        let source = std::path::Path::new(input).file_name().and_then(|n| n.to_str()).unwrap_or(input);
+        println!("{}", serde_json::to_string_pretty(&to_json(source, &objects)).unwrap_or_default());
+        return Ok(());
+    }
+
+    println!("{}  {}", hex_or_missing(objects.first().and_then(archive_digest)), input);
+    println!();
+    println!("{:<32} {:<32} path", "properties", "children");
+    for o in &objects {
+        println!("{} {} {}", hex_or_missing(o.properties_hash), hex_or_missing(o.children_hash), o.path);
+        for (name, digest) in &o.properties {
+            println!("{} {:<32}   {}", hex(digest), "", name);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,484 @@
+//! `alembic histo <file.abc> </obj.prop> [--bins 32] [--below x] [--above x] [--json]` - value distribution.
+//!
+//! Range statistics and a histogram over every sample of one numeric
+//! property, for QC of simulation output: point widths under the renderer's
+//! minimum, exploding velocities, stray NaNs. Vector elements are reduced to
+//! their magnitude (or one component with `--component`). `--below` and
+//! `--above` are thresholds; values outside them are reported with the
+//! samples they occur in and make the command exit 2.
+
+use alembic::abc::{IArchive as AbcIArchive, ICompoundProperty, IProperty};
+use alembic::compare::pod_values;
+use alembic::util::DataType;
+use serde_json::json;
+
+/// Width of the longest bar in the text histogram.
+const BAR_WIDTH: usize = 40;
+
+/// Options for `histo`.
+#[derive(Debug, Clone)]
+pub struct HistoOptions {
+    pub bins: usize,
+    /// Histogram range; defaults to the finite min/max of the data.
+    pub range: Option<(f64, f64)>,
+    /// Histogram one component of vector elements instead of the magnitude.
+    pub component: Option<usize>,
+    /// QC threshold: values below it fail.
+    pub below: Option<f64>,
+    /// QC threshold: values above it fail.
+    pub above: Option<f64>,
+    /// Also print min/max/mean of every sample.
+    pub per_sample: bool,
+    pub json: bool,
+}
+
+impl Default for HistoOptions {
+    fn default() -> Self {
+        Self { bins: 32, range: None, component: None, below: None, above: None, per_sample: false, json: false }
+    }
+}
+
+/// Parse the flags following `histo <file> <property>`.
+pub fn parse_args(args: &[&str]) -> Result<HistoOptions, String> {
+    fn number(v: Option<&&str>, flag: &str) -> Result<f64, String> {
+        let v = v.ok_or_else(|| format!("{} requires a number", flag))?;
+        v.parse().map_err(|_| format!("invalid {}: {}", flag, v))
+    }
+    let mut opts = HistoOptions::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--bins" | "-b" => {
+                let v = iter.next().ok_or("--bins requires a number")?;
+                opts.bins = v.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("invalid --bins: {}", v))?;
+            }
+            "--range" => {
+                let (lo, hi) = (number(iter.next(), "--range")?, number(iter.next(), "--range")?);
+                if lo.partial_cmp(&hi) != Some(std::cmp::Ordering::Less) {
+                    return Err(format!("invalid --range: {} {}", lo, hi));
+                }
+                opts.range = Some((lo, hi));
+            }
+            "--component" | "-c" => {
+                let v = iter.next().ok_or("--component requires an index")?;
+                opts.component = Some(v.parse().map_err(|_| format!("invalid --component: {}", v))?);
+            }
+            "--below" => opts.below = Some(number(iter.next(), "--below")?),
+            "--above" => opts.above = Some(number(iter.next(), "--above")?),
+            "--per-sample" => opts.per_sample = true,
+            "--json" => opts.json = true,
+            _ => return Err(format!("unknown histo option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// Running min/max/mean/variance (Welford) of the finite values.
+#[derive(Debug, Clone, Copy, Default)]
+struct RangeStats {
+    count: usize,
+    non_finite: usize,
+    min: f64,
+    max: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl RangeStats {
+    fn add(&mut self, v: f64) {
+        if !v.is_finite() {
+            self.non_finite += 1;
+            return;
+        }
+        if self.count == 0 {
+            self.min = v;
+            self.max = v;
+        } else {
+            self.min = self.min.min(v);
+            self.max = self.max.max(v);
+        }
+        self.count += 1;
+        let delta = v - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (v - self.mean);
+    }
+
+    fn stddev(&self) -> f64 {
+        if self.count > 1 { (self.m2 / self.count as f64).sqrt() } else { 0.0 }
+    }
+
+    fn to_json(self) -> serde_json::Value {
+        let finite = self.count > 0;
+        json!({
+            "count": self.count,
+            "non_finite": self.non_finite,
+            "min": finite.then_some(self.min),
+            "max": finite.then_some(self.max),
+            "mean": finite.then_some(self.mean),
+            "stddev": finite.then_some(self.stddev()),
+        })
+    }
+}
+
+/// Fixed-width bins over `[lo, hi]`; the last bin is closed.
+#[derive(Debug, Clone)]
+struct Histogram {
+    lo: f64,
+    hi: f64,
+    counts: Vec<usize>,
+    /// Finite values outside `[lo, hi]`.
+    outside: usize,
+}
+
+impl Histogram {
+    fn new(lo: f64, hi: f64, bins: usize) -> Self {
+        Self { lo, hi, counts: vec![0; bins], outside: 0 }
+    }
+
+    fn add(&mut self, v: f64) {
+        if !v.is_finite() {
+            return;
+        }
+        if v < self.lo || v > self.hi {
+            self.outside += 1;
+            return;
+        }
+        let n = self.counts.len();
+        let bin = if self.hi > self.lo {
+            (((v - self.lo) / (self.hi - self.lo)) * n as f64) as usize
+        } else {
+            0
+        };
+        self.counts[bin.min(n - 1)] += 1;
+    }
+
+    fn edges(&self, bin: usize) -> (f64, f64) {
+        let width = (self.hi - self.lo) / self.counts.len() as f64;
+        (self.lo + width * bin as f64, self.lo + width * (bin + 1) as f64)
+    }
+}
+
+/// Values outside one QC threshold.
+#[derive(Debug, Clone)]
+struct Violations {
+    threshold: f64,
+    count: usize,
+    worst: Option<f64>,
+    /// Sample indices with at least one violation.
+    samples: Vec<usize>,
+}
+
+impl Violations {
+    fn new(threshold: f64) -> Self {
+        Self { threshold, count: 0, worst: None, samples: Vec::new() }
+    }
+
+    fn add(&mut self, v: f64, sample: usize, below: bool) {
+        self.count += 1;
+        self.worst = Some(match self.worst {
+            Some(w) if below => w.min(v),
+            Some(w) => w.max(v),
+            None => v,
+        });
+        if self.samples.last() != Some(&sample) {
+            self.samples.push(sample);
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "threshold": self.threshold,
+            "count": self.count,
+            "worst": self.worst,
+            "samples": self.samples,
+        })
+    }
+}
+
+/// Read `path` and print its value distribution. Returns the number of
+/// threshold violations.
+pub fn run(input: &str, path: &str, opts: &HistoOptions) -> Result<usize, String> {
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    find_property(&archive, path, &mut |object, property, prop| report(object, property, prop, opts))
+        .unwrap_or_else(|| Err(format!("property not found: {} (expected /object/path.property)", path)))
+}
+
+/// Resolve `/object/path.property` in `archive` and call `f` with the
+/// object's full name, the property's path below it and the property.
+pub(crate) fn find_property<T>(
+    archive: &AbcIArchive,
+    path: &str,
+    f: &mut dyn FnMut(&str, &str, &IProperty) -> T,
+) -> Option<T> {
+    for (object_path, prop_spec) in split_candidates(path) {
+        let Some(object) = archive.findObject(object_path) else { continue };
+        let props = object.getProperties();
+        let Some(names) = resolve_property(&props, prop_spec) else { continue };
+        return with_property(&props, &names, &mut |prop| f(object.getFullName(), &names.join("/"), prop));
+    }
+    None
+}
+
+/// `/geo.001.widths` → `("/geo", "001.widths")`, `("/geo.001", "widths")`:
+/// every split at a `.`, since object names may contain dots too.
+fn split_candidates(path: &str) -> Vec<(&str, &str)> {
+    path.match_indices('.')
+        .map(|(i, _)| i)
+        .filter(|&i| i > 0 && i + 1 < path.len() && !path[..i].ends_with('/'))
+        .map(|i| (&path[..i], &path[i + 1..]))
+        .collect()
+}
+
+/// Property names from `props` down to the property `spec` names: an exact
+/// `/`-separated path (the leading `.` of names like `.geom` optional), else
+/// the first property named like the last segment, depth first. Indexed
+/// GeomParam compounds resolve to their `.vals`.
+fn resolve_property(props: &ICompoundProperty, spec: &str) -> Option<Vec<String>> {
+    let mut names = exact_path(props, &spec.split('/').collect::<Vec<_>>())
+        .or_else(|| find_by_name(props, spec.rsplit('/').next().unwrap_or(spec)))?;
+    let indexed = with_property(props, &names, &mut |prop| {
+        prop.asCompound().is_some_and(|c| c.getPropertyByName(".vals").is_some())
+    });
+    if indexed == Some(true) {
+        names.push(".vals".into());
+    }
+    Some(names)
+}
+
+fn exact_path(props: &ICompoundProperty, segments: &[&str]) -> Option<Vec<String>> {
+    let (first, rest) = segments.split_first()?;
+    let name = [first.to_string(), format!(".{}", first)]
+        .into_iter()
+        .find(|n| props.getPropertyByName(n).is_some())?;
+    if rest.is_empty() {
+        return Some(vec![name]);
+    }
+    let prop = props.getPropertyByName(&name)?;
+    let mut names = exact_path(&prop.asCompound()?, rest)?;
+    names.insert(0, name);
+    Some(names)
+}
+
+fn find_by_name(props: &ICompoundProperty, name: &str) -> Option<Vec<String>> {
+    let dotted = format!(".{}", name);
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        let own = prop.getName().to_string();
+        if own == name || own == dotted {
+            return Some(vec![own]);
+        }
+        if let Some(mut names) = prop.asCompound().and_then(|c| find_by_name(&c, name)) {
+            names.insert(0, own);
+            return Some(names);
+        }
+    }
+    None
+}
+
+/// Call `f` with the property at `names` below `props`.
+fn with_property<T>(props: &ICompoundProperty, names: &[String], f: &mut dyn FnMut(&IProperty) -> T) -> Option<T> {
+    let (first, rest) = names.split_first()?;
+    let prop = props.getPropertyByName(first)?;
+    if rest.is_empty() {
+        return Some(f(&prop));
+    }
+    with_property(&prop.asCompound()?, rest, f)
+}
+
+/// Raw bytes of one sample of a scalar or array property.
+type SampleReader<'a> = Box<dyn Fn(usize) -> Result<Vec<u8>, String> + 'a>;
+
+fn report(object: &str, property: &str, prop: &IProperty, opts: &HistoOptions) -> Result<usize, String> {
+    let dt = prop.getHeader().data_type;
+    let extent = dt.extent.max(1) as usize;
+    if let Some(c) = opts.component.filter(|&c| c >= extent) {
+        return Err(format!("--component {} out of range for {} (extent {})", c, property, extent));
+    }
+    let (num_samples, read): (usize, SampleReader) =
+        if let Some(array) = prop.asArray() {
+            (array.getNumSamples(), Box::new(move |s| array.getSampleVec(s).map_err(|e| e.to_string())))
+        } else if let Some(scalar) = prop.asScalar() {
+            (scalar.getNumSamples(), Box::new(move |s| scalar.getSampleVec(s).map_err(|e| e.to_string())))
+        } else {
+            return Err(format!("{} is a compound property", property));
+        };
+    // One pass for the range and thresholds, a second to bin against it
+    // unless --range fixed it up front; keeps memory flat for big caches.
+    let for_each_value = |f: &mut dyn FnMut(usize, f64)| -> Result<(), String> {
+        for s in 0..num_samples {
+            let data = read(s)?;
+            let values = pod_values(dt.pod, &data)
+                .ok_or_else(|| format!("{} is not numeric ({})", property, dt.pod.name()))?;
+            for element in values.chunks_exact(extent) {
+                f(s, element_value(element, opts.component));
+            }
+        }
+        Ok(())
+    };
+
+    let mut total = RangeStats::default();
+    let mut per_sample = vec![RangeStats::default(); if opts.per_sample { num_samples } else { 0 }];
+    let mut below = opts.below.map(Violations::new);
+    let mut above = opts.above.map(Violations::new);
+    let mut histogram = opts.range.map(|(lo, hi)| Histogram::new(lo, hi, opts.bins));
+    for_each_value(&mut |s, v| {
+        total.add(v);
+        if let Some(stats) = per_sample.get_mut(s) {
+            stats.add(v);
+        }
+        if let Some(h) = histogram.as_mut() {
+            h.add(v);
+        }
+        // NaN passes every comparison, so it counts against whichever threshold is set
+        if let Some(b) = below.as_mut().filter(|b| v < b.threshold || v.is_nan()) {
+            b.add(v, s, true);
+        } else if let Some(a) = above.as_mut().filter(|a| v > a.threshold || v.is_nan()) {
+            a.add(v, s, false);
+        }
+    })?;
+    let histogram = match histogram {
+        Some(h) => h,
+        None => {
+            let mut h = Histogram::new(total.min, total.max, opts.bins);
+            if total.count > 0 {
+                for_each_value(&mut |_, v| h.add(v))?;
+            }
+            h
+        }
+    };
+    let violations = below.as_ref().map_or(0, |v| v.count) + above.as_ref().map_or(0, |v| v.count);
+    let measure = match opts.component {
+        Some(c) => format!("component {}", c),
+        None if extent > 1 => "magnitude".to_string(),
+        None => "value".to_string(),
+    };
+
+    if opts.json {
+        let bins: Vec<_> = (0..histogram.counts.len()).map(|i| {
+            let (lo, hi) = histogram.edges(i);
+            json!({"lo": lo, "hi": hi, "count": histogram.counts[i]})
+        }).collect();
+        let mut out = json!({
+            "object": object,
+            "property": property,
+            "dtype": dtype_label(dt),
+            "measure": measure,
+            "samples": num_samples,
+            "stats": total.to_json(),
+            "bins": bins,
+            "outside_range": histogram.outside,
+            "below": below.as_ref().map(Violations::to_json),
+            "above": above.as_ref().map(Violations::to_json),
+        });
+        if opts.per_sample {
+            out["per_sample"] = per_sample.iter().map(|s| s.to_json()).collect();
+        }
+        println!("{}", serde_json::to_string_pretty(&out).map_err(|e| e.to_string())?);
+        return Ok(violations);
+    }
+
+    println!("{} {}  {} ({}), {} samples", object, property, dtype_label(dt), measure, num_samples);
+    if total.count == 0 {
+        println!("  no finite values ({} non-finite)", total.non_finite);
+    } else {
+        println!(
+            "  {} values  min {}  max {}  mean {}  stddev {}",
+            total.count, fmt(total.min), fmt(total.max), fmt(total.mean), fmt(total.stddev())
+        );
+        if total.non_finite > 0 {
+            println!("  {} non-finite values (NaN/inf)", total.non_finite);
+        }
+        println!();
+        let peak = histogram.counts.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in histogram.counts.iter().enumerate() {
+            let (lo, hi) = histogram.edges(i);
+            let bar = "#".repeat((count * BAR_WIDTH).div_ceil(peak));
+            println!("  [{:>12}, {:>12}{} {:>10}  {}", fmt(lo), fmt(hi), if i + 1 == histogram.counts.len() { "]" } else { ")" }, count, bar);
+        }
+        if histogram.outside > 0 {
+            println!("  {} values outside the range", histogram.outside);
+        }
+    }
+    if opts.per_sample {
+        println!();
+        println!("  {:>7} {:>10} {:>12} {:>12} {:>12}", "SAMPLE", "VALUES", "MIN", "MAX", "MEAN");
+        for (i, s) in per_sample.iter().enumerate() {
+            let cell = |v: f64| if s.count > 0 { fmt(v) } else { "-".into() };
+            println!("  {:>7} {:>10} {:>12} {:>12} {:>12}", i, s.count, cell(s.min), cell(s.max), cell(s.mean));
+        }
+    }
+    for (label, v) in [("below", &below), ("above", &above)] {
+        let Some(v) = v else { continue };
+        println!();
+        if v.count == 0 {
+            println!("  OK: no values {} {}", label, fmt(v.threshold));
+        } else {
+            println!(
+                "  FAIL: {} values {} {} (worst {}) in samples {}",
+                v.count, label, fmt(v.threshold), v.worst.map_or("-".into(), fmt), sample_list(&v.samples)
+            );
+        }
+    }
+    Ok(violations)
+}
+
+/// Magnitude of `element`, or one of its components.
+fn element_value(element: &[f64], component: Option<usize>) -> f64 {
+    match (component, element) {
+        (Some(c), _) => element[c],
+        (None, [v]) => *v,
+        (None, _) => element.iter().map(|v| v * v).sum::<f64>().sqrt(),
+    }
+}
+
+fn dtype_label(dt: DataType) -> String {
+    if dt.extent > 1 { format!("{}[{}]", dt.pod.name(), dt.extent) } else { dt.pod.name().to_string() }
+}
+
+fn fmt(v: f64) -> String {
+    if v != 0.0 && (v.abs() < 1e-3 || v.abs() >= 1e6) { format!("{:.4e}", v) } else { format!("{:.4}", v) }
+}
+
+/// `0, 2, 5-9, ...` with at most a few ranges.
+fn sample_list(samples: &[usize]) -> String {
+    const MAX_RANGES: usize = 8;
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &s in samples {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == s => *end = s,
+            _ => ranges.push((s, s)),
+        }
+    }
+    let mut parts: Vec<String> = ranges.iter().take(MAX_RANGES)
+        .map(|&(a, b)| if a == b { a.to_string() } else { format!("{}-{}", a, b) })
+        .collect();
+    if ranges.len() > MAX_RANGES {
+        parts.push("...".into());
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_and_stats() {
+        let mut h = Histogram::new(0.0, 1.0, 4);
+        let mut s = RangeStats::default();
+        for v in [0.0, 0.1, 0.5, 1.0, 1.5, f64::NAN] {
+            h.add(v);
+            s.add(v);
+        }
+        assert_eq!(h.counts, vec![2, 0, 1, 1]);
+        assert_eq!(h.outside, 1);
+        assert_eq!((s.count, s.non_finite, s.min, s.max), (5, 1, 0.0, 1.5));
+        assert!((s.mean - 0.62).abs() < 1e-12);
+        assert_eq!(element_value(&[3.0, 4.0], None), 5.0);
+        assert_eq!(element_value(&[3.0, 4.0], Some(0)), 3.0);
+        assert_eq!(sample_list(&[0, 2, 3, 4, 7]), "0, 2-4, 7");
+        assert_eq!(split_candidates("/pts.geom/.widths"), vec![("/pts", "geom/.widths")]);
+        assert_eq!(split_candidates("/pts..widths"), vec![("/pts", ".widths"), ("/pts.", "widths")]);
+        assert_eq!(split_candidates("/geo.001.widths"), vec![("/geo", "001.widths"), ("/geo.001", "widths")]);
+    }
+}
@@ -3,24 +3,51 @@
 use alembic::prelude::{IObject, IPolyMesh, ISubD, ICurves, IPoints, ICamera, IXform, INuPatch, ILight, IFaceSet};
 use alembic::abc::{ICompoundProperty, IProperty};
 use alembic::abc::IArchive as AbcIArchive;
+use alembic::copy::CopyTransform;
 use alembic::ogawa::writer::{
     OArchive, OObject, OPolyMesh, OPolyMeshSample, OXform, OXformSample,
     OSubD, OSubDSample, OCurves, OCurvesSample, OPoints, OPointsSample,
     OCamera, ONuPatch, ONuPatchSample, OLight, OFaceSet, OFaceSetSample,
-    OProperty, OPropertyData, OMaterial, OMaterialSample,
+    OProperty, OPropertyData, OMaterial, OMaterialSample, add_material_assignment,
 };
 use alembic::material::{ShaderParam, ShaderParamValue};
-use alembic::util::PlainOldDataType;
+use alembic::query::Query;
+use alembic::resample::ResampleMode;
+use std::collections::HashSet;
 use std::env;
 use std::path::{Path, PathBuf};
 
-use tracing::{info, debug, trace};
-
-/// Parse CLI flags: -v/-vv/-vvv for verbosity, -l/--log for file output.
-/// Returns (verbosity: 0-3, log_file: Option, remaining args).
-fn parse_global_flags(args: &[String]) -> (u8, Option<PathBuf>, Vec<&str>) {
+use tracing::{info, debug, trace, warn};
+
+#[cfg(feature = "serve")]
+mod serve;
+mod check;
+mod collections;
+mod diff;
+mod echo;
+mod extract;
+mod hash;
+mod histo;
+mod manifest;
+mod merge;
+#[cfg(feature = "compress")]
+mod pack;
+mod patch;
+mod plugin;
+mod props;
+#[cfg(feature = "viewer")]
+mod render;
+mod resample;
+mod strip;
+mod watch;
+
+/// Parse CLI flags: -v/-vv/-vvv for verbosity, -l/--log for file output,
+/// --plugin <lib> for schema plugins.
+/// Returns (verbosity: 0-3, log_file: Option, plugins, remaining args).
+fn parse_global_flags(args: &[String]) -> (u8, Option<PathBuf>, Vec<PathBuf>, Vec<&str>) {
     let mut verbosity: u8 = 0;
     let mut log_file: Option<PathBuf> = None;
+    let mut plugins = Vec::new();
     let mut filtered = Vec::new();
     let mut skip_next = false;
 
@@ -44,10 +71,16 @@ fn parse_global_flags(args: &[String]) -> (u8, Option<PathBuf>, Vec<&str>) {
                     log_file = Some(PathBuf::from("alembic-cli.log"));
                 }
             }
+            "--plugin" => {
+                if let Some(next) = args.get(i + 1) {
+                    plugins.push(PathBuf::from(next));
+                    skip_next = true;
+                }
+            }
             _ => filtered.push(arg.as_str()),
         }
     }
-    (verbosity, log_file, filtered)
+    (verbosity, log_file, plugins, filtered)
 }
 
 /// Initialize tracing subscriber for CLI (no chrome profiler).
@@ -77,7 +110,7 @@ fn init_cli_tracing(verbosity: u8, log_file: Option<&Path>) {
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let (verbosity, log_file, filtered_args) = parse_global_flags(&args[1..]);
+    let (verbosity, log_file, plugins, filtered_args) = parse_global_flags(&args[1..]);
 
     if filtered_args.is_empty() {
         print_help();
@@ -90,25 +123,51 @@ fn main() {
     if !is_viewer {
         init_cli_tracing(verbosity, log_file.as_deref());
     }
+    plugin::load(&plugins);
 
     match filtered_args[0] {
         // View command - launch 3D viewer
         "view" | "v" => {
             #[cfg(feature = "viewer")]
             {
-                let file = filtered_args.get(1).map(|s| PathBuf::from(*s));
-                if let Err(e) = alembic::viewer::run(file, verbosity, log_file) {
+                let (file, launch) = match parse_view_args(&filtered_args[1..]) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        eprintln!("Usage: alembic view [file.abc] [--camera <path>] [--frame <n>] [--env <file.hdr>] [--bg r,g,b] [--no-grid] [--pt] [--pt-preset <name>]");
+                        std::process::exit(1);
+                    }
+                };
+                if let Err(e) = alembic_viewer::run_with_options(file, launch, verbosity, log_file) {
                     eprintln!("Viewer error: {}", e);
                     std::process::exit(1);
                 }
             }
             #[cfg(not(feature = "viewer"))]
             {
-                eprintln!("Viewer not available. Rebuild with: cargo build --features viewer");
+                eprintln!("Viewer not available. Rebuild with: cargo build -p alembic-tools --features viewer");
                 std::process::exit(1);
             }
         }
         
+        // Render command - headless path-traced frames
+        "render" => {
+            #[cfg(feature = "viewer")]
+            {
+                let result = render::parse_args(&filtered_args[1..]).and_then(|opts| render::render(&opts));
+                if let Err(e) = result {
+                    eprintln!("Error: {}", e);
+                    eprintln!("Usage: alembic render <file.abc> -o out.####.exr [--frames a-b] [--step n] [--spp n] [--size WxH] [--camera <path>] [--bounces n] [--jobs n] [--vram-mb n]");
+                    std::process::exit(1);
+                }
+            }
+            #[cfg(not(feature = "viewer"))]
+            {
+                eprintln!("Render not available. Rebuild with: cargo build -p alembic-tools --features viewer");
+                std::process::exit(1);
+            }
+        }
+
         // Info command - show archive summary
         "info" | "i" => {
             if filtered_args.len() < 2 {
@@ -123,10 +182,10 @@ fn main() {
         "tree" | "t" => {
             if filtered_args.len() < 2 {
                 eprintln!("Error: missing file argument");
-                eprintln!("Usage: alembic tree <file.abc>");
+                eprintln!("Usage: alembic tree <file.abc> [pattern]");
                 std::process::exit(1);
             }
-            cmd_tree(filtered_args[1]);
+            cmd_tree(filtered_args[1], filtered_args.get(2).copied());
         }
         
         // Stats command - detailed statistics
@@ -169,20 +228,103 @@ fn main() {
         "copy" | "c" => {
             if filtered_args.len() < 3 {
                 eprintln!("Error: missing arguments");
-                eprintln!("Usage: alembic copy <input.abc> <output.abc>");
+                eprintln!("Usage: alembic copy <input.abc> <output.abc> [--resample-uniform <fps>] [--frames <a-b>] [--interp nearest|linear]");
+                std::process::exit(1);
+            }
+            match resample::parse_args(&filtered_args[3..], ResampleMode::Nearest) {
+                Ok(None) => cmd_copy(filtered_args[1], filtered_args[2]),
+                Ok(Some(opts)) => {
+                    if let Err(e) = resample::copy_resampled(filtered_args[1], filtered_args[2], &opts) {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        
+        // Strip command - drop shading attributes to shrink caches
+        "strip" => {
+            if filtered_args.len() < 3 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic strip <input.abc> <output.abc> [--drop N,uv,...] [--recompute-normals] [--fix-winding]");
+                std::process::exit(1);
+            }
+            let result = strip::parse_args(&filtered_args[3..])
+                .and_then(|opts| strip::strip(filtered_args[1], filtered_args[2], &opts));
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        
+        // Extract command - write one object subtree to a new archive
+        "extract" => {
+            if filtered_args.len() < 4 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic extract <input.abc> </path/to/subtree|pattern> <output.abc> [--bake]");
+                std::process::exit(1);
+            }
+            let result = extract::parse_args(&filtered_args[4..])
+                .and_then(|opts| extract::extract(filtered_args[1], filtered_args[2], filtered_args[3], &opts));
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        
+        // Merge command - combine the hierarchies of several archives
+        "merge" => {
+            if filtered_args.len() < 2 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic merge <a.abc> <b.abc> ... -o <output.abc> [--on-collision error|rename|replace]");
+                std::process::exit(1);
+            }
+            if let Err(e) = merge::parse_args(&filtered_args[1..]).and_then(|opts| merge::merge(&opts)) {
+                eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
-            cmd_copy(filtered_args[1], filtered_args[2]);
         }
         
         // Copy2 command - full re-write using our writer (ALL schema types)
         "copy2" | "c2" => {
             if filtered_args.len() < 3 {
                 eprintln!("Error: missing arguments");
-                eprintln!("Usage: alembic copy2 <input.abc> <output.abc>");
+                eprintln!("Usage: alembic copy2 <input.abc> <output.abc> [--fps <n>] [--frames <a-b>] [--interp nearest|linear]");
+                std::process::exit(1);
+            }
+            // Retiming goes through the property-level resampler (linear by default)
+            match resample::parse_args(&filtered_args[3..], ResampleMode::Linear) {
+                Ok(None) => cmd_copy2(filtered_args[1], filtered_args[2]),
+                Ok(Some(opts)) => {
+                    if let Err(e) = resample::copy_resampled(filtered_args[1], filtered_args[2], &opts) {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        
+        // Export command - convert to another interchange format
+        "export" | "x" => {
+            if filtered_args.len() < 3 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic export <input.abc> <output.glb|.gltf|.obj|.usda|.pbrt|.xml> [options] (see help)");
+                eprintln!("       alembic export <input.abc> <output.####.obj> [--frames <a-b>] [--local] [--no-subd] [--include-hidden]");
+                eprintln!("       alembic export <input.abc> <output.usda> [--fps <n>]");
+                std::process::exit(1);
+            }
+            if let Err(e) = cmd_export(filtered_args[1], filtered_args[2], &filtered_args[3..]) {
+                eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
-            cmd_copy2(filtered_args[1], filtered_args[2]);
         }
         
         // Materialize command - add materials to meshes missing them
@@ -196,6 +338,259 @@ fn main() {
             cmd_materialize(filtered_args[1]);
         }
         
+        // Diff command - compare two archives with numeric tolerances
+        "diff" => {
+            if filtered_args.len() < 3 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic diff <a.abc> <b.abc> [--tol <abs>] [--rel-tol <rel>] [--time-tol <sec>] [--metadata] [--max <n>]");
+                std::process::exit(1);
+            }
+            let result = diff::parse_args(&filtered_args[3..])
+                .and_then(|opts| diff::run(filtered_args[1], filtered_args[2], &opts));
+            match result {
+                Ok(0) => {}
+                Ok(_) => std::process::exit(2),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        // Patch commands - distribute cache updates as sample-level patches
+        "make-patch" => {
+            if filtered_args.len() < 4 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic make-patch <base.abc> <target.abc> <out.abcpatch>");
+                std::process::exit(1);
+            }
+            if let Err(e) = patch::make(filtered_args[1], filtered_args[2], filtered_args[3]) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "apply-patch" => {
+            if filtered_args.len() < 4 {
+                eprintln!("Error: missing arguments");
+                eprintln!("Usage: alembic apply-patch <base.abc> <patch.abcpatch> <out.abc>");
+                std::process::exit(1);
+            }
+            if let Err(e) = patch::apply(filtered_args[1], filtered_args[2], filtered_args[3]) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+
+        // Pack commands - non-standard compressed caches
+        "pack" => {
+            #[cfg(feature = "compress")]
+            {
+                if filtered_args.len() < 3 {
+                    eprintln!("Error: missing arguments");
+                    eprintln!("Usage: alembic pack <in.abc> <out.abc> [--codec lz4|zlib]");
+                    std::process::exit(1);
+                }
+                let result = pack::parse_args(&filtered_args[3..])
+                    .and_then(|codec| pack::pack(filtered_args[1], filtered_args[2], codec));
+                if let Err(e) = result {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            #[cfg(not(feature = "compress"))]
+            {
+                eprintln!("Pack not available. Rebuild with: cargo build -p alembic-tools --features compress");
+                std::process::exit(1);
+            }
+        }
+        "unpack" => {
+            #[cfg(feature = "compress")]
+            {
+                if filtered_args.len() < 3 {
+                    eprintln!("Error: missing arguments");
+                    eprintln!("Usage: alembic unpack <in.abc> <out.abc>");
+                    std::process::exit(1);
+                }
+                if let Err(e) = pack::unpack(filtered_args[1], filtered_args[2]) {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            #[cfg(not(feature = "compress"))]
+            {
+                eprintln!("Unpack not available. Rebuild with: cargo build -p alembic-tools --features compress");
+                std::process::exit(1);
+            }
+        }
+
+        // Manifest command - per-sample golden hashes
+        "manifest" => {
+            let opts = match filtered_args.get(1).map(|_| manifest::parse_args(&filtered_args[2..])) {
+                Some(Ok(o)) => o,
+                Some(Err(e)) => {
+                    eprintln!("Error: {}", e);
+                    eprintln!("Usage: alembic manifest <file.abc> [-o manifest.json | --verify manifest.json]");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("Error: missing file argument");
+                    eprintln!("Usage: alembic manifest <file.abc> [-o manifest.json | --verify manifest.json]");
+                    std::process::exit(1);
+                }
+            };
+            match manifest::run(filtered_args[1], &opts) {
+                Ok(0) => {}
+                Ok(_) => std::process::exit(2),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        // Hash command - stored object digests (dedup / cache invalidation)
+        "hash" => {
+            let opts = match filtered_args.get(1).map(|_| hash::parse_args(&filtered_args[2..])) {
+                Some(Ok(o)) => o,
+                Some(Err(e)) => {
+                    eprintln!("Error: {}", e);
+                    eprintln!("Usage: alembic hash <file.abc> [--properties] [--json]");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("Error: missing file argument");
+                    eprintln!("Usage: alembic hash <file.abc> [--properties] [--json]");
+                    std::process::exit(1);
+                }
+            };
+            if let Err(e) = hash::run(filtered_args[1], &opts) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        // Check command - lint topology, NaNs, GeomParams and metadata
+        "check" => {
+            if filtered_args.len() < 2 {
+                eprintln!("Error: missing file argument");
+                eprintln!("Usage: alembic check <file.abc> [--json] [--strict] [--max <n>]");
+                std::process::exit(1);
+            }
+            let result = check::parse_args(&filtered_args[2..])
+                .and_then(|opts| check::run(filtered_args[1], &opts));
+            match result {
+                Ok(0) => {}
+                Ok(_) => std::process::exit(2),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        // Props command - property-centric listing for data audits
+        "props" => {
+            if filtered_args.len() < 2 {
+                eprintln!("Error: missing file argument");
+                eprintln!("Usage: alembic props <file.abc> [--type scalar|array|compound] [--min-samples <n>] [--dtype <type>] [--name <substr>] [--sort path|bytes|samples] [--json]");
+                std::process::exit(1);
+            }
+            let result = props::parse_args(&filtered_args[2..])
+                .and_then(|opts| props::run(filtered_args[1], &opts));
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        // Collections command - named object sets, with animated membership
+        "collections" => {
+            if filtered_args.len() < 2 {
+                eprintln!("Error: missing file argument");
+                eprintln!("Usage: alembic collections <file.abc> [--time <sec>] [--all-samples] [--json]");
+                std::process::exit(1);
+            }
+            let result = collections::parse_args(&filtered_args[2..])
+                .and_then(|opts| collections::run(filtered_args[1], &opts));
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        // Echo command - raw sample payloads, optionally diffed against another archive
+        "echo" => {
+            if filtered_args.len() < 3 {
+                eprintln!("Error: missing file or property argument");
+                eprintln!("Usage: alembic echo <file.abc> </object.property> [--sample <n>] [--format hex|floats] [--compare <other.abc>]");
+                std::process::exit(1);
+            }
+            let result = echo::parse_args(&filtered_args[3..])
+                .and_then(|opts| echo::run(filtered_args[1], filtered_args[2], &opts));
+            match result {
+                Ok(0) => {}
+                Ok(_) => std::process::exit(2),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        // Histo command - value distribution of one property, with QC thresholds
+        "histo" => {
+            if filtered_args.len() < 3 {
+                eprintln!("Error: missing file or property argument");
+                eprintln!("Usage: alembic histo <file.abc> </object.property> [--bins <n>] [--range <min> <max>] [--component <i>] [--below <x>] [--above <x>] [--per-sample] [--json]");
+                std::process::exit(1);
+            }
+            let result = histo::parse_args(&filtered_args[3..])
+                .and_then(|opts| histo::run(filtered_args[1], filtered_args[2], &opts));
+            match result {
+                Ok(0) => {}
+                Ok(_) => std::process::exit(2),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        "watch" => {
+            let opts = match watch::parse_args(&filtered_args[1..]) {
+                Ok(o) => o,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    eprintln!("Usage: alembic watch <dir> [--rules rules.toml] [--once]");
+                    std::process::exit(1);
+                }
+            };
+            match watch::run(opts) {
+                Ok(0) => {}
+                Ok(_) => std::process::exit(2),
+                Err(e) => {
+                    eprintln!("Watch error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        // Serve command - read-only HTTP service
+        "serve" => {
+            #[cfg(feature = "serve")]
+            {
+                let opts = match serve::parse_args(&filtered_args[1..]) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        eprintln!("Usage: alembic serve <file.abc|dir>... [--bind addr:port] [--threads n] [--max-pending n]");
+                        std::process::exit(1);
+                    }
+                };
+                if let Err(e) = serve::run(opts) {
+                    eprintln!("Serve error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            #[cfg(not(feature = "serve"))]
+            {
+                eprintln!("Server not available. Rebuild with: cargo build -p alembic-tools --features serve");
+                std::process::exit(1);
+            }
+        }
+        
         // Help
         "help" | "h" | "-h" | "--help" => print_help(),
         
@@ -213,6 +608,50 @@ fn main() {
     }
 }
 
+/// Parse `view` arguments: optional file plus startup overrides.
+#[cfg(feature = "viewer")]
+fn parse_view_args(args: &[&str]) -> Result<(Option<PathBuf>, alembic_viewer::LaunchOptions), String> {
+    let mut file = None;
+    let mut launch = alembic_viewer::LaunchOptions::default();
+    let mut iter = args.iter();
+
+    while let Some(&arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().copied()
+            .ok_or_else(|| format!("{} requires a value", flag));
+        match arg {
+            "--camera" | "--cam" => launch.camera = Some(value(arg)?.to_string()),
+            "--frame" | "-f" => {
+                let v = value(arg)?;
+                launch.frame = Some(v.parse().map_err(|_| format!("invalid frame: {}", v))?);
+            }
+            "--env" | "--hdr" => launch.env = Some(PathBuf::from(value(arg)?)),
+            "--bg" | "--background" => {
+                let v = value(arg)?;
+                launch.background = Some(parse_rgb(v).ok_or_else(|| format!("invalid color: {} (expected r,g,b)", v))?);
+            }
+            "--grid" => launch.grid = Some(true),
+            "--no-grid" => launch.grid = Some(false),
+            "--pt" | "--path-tracer" => launch.path_tracing = Some(true),
+            "--raster" | "--no-pt" => launch.path_tracing = Some(false),
+            "--pt-preset" => launch.pt_preset = Some(value(arg)?.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("unknown view option: {}", arg)),
+            _ if file.is_none() => file = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    Ok((file, launch))
+}
+
+/// Parse "r,g,b" (0..1 floats) or a single gray value.
+fn parse_rgb(s: &str) -> Option<[f32; 3]> {
+    let parts: Vec<f32> = s.split(',').map(|p| p.trim().parse().ok()).collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [v] => Some([*v; 3]),
+        [r, g, b] => Some([*r, *g, *b]),
+        _ => None,
+    }
+}
+
 fn print_help() {
     println!("alembic - Alembic file toolkit");
     println!();
@@ -221,33 +660,111 @@ fn print_help() {
     println!();
     println!("COMMANDS:");
     println!("    v, view   <file>              Open file in 3D viewer (Esc to exit)");
+    println!("              [--camera <path>] [--frame <n>] [--env <hdr>] [--bg r,g,b] [--no-grid] [--pt] [--pt-preset <name>]");
+    println!("    render <file> -o <out.####.exr>  Headless path-traced EXR frames for QC (several frames in flight)");
+    println!("              [--frames <a-b>] [--step n] [--spp n] [--size WxH] [--camera <path>] [--bounces n] [--jobs n] [--vram-mb n]");
     println!("    i, info   <file>              Show archive info and object counts");
-    println!("    t, tree   <file>              Show full object hierarchy");
+    println!("    t, tree   <file> [pattern]    Show full object hierarchy (or the matches and their parents)");
     println!("    s, stats  <file>              Show detailed statistics with timing info");
     println!("    d, dump   <file> [pattern]    Dump xform transforms (filter by pattern)");
     println!("    m, meta   <file> [pattern]    Show object/property metadata");
     println!("    c, copy   <in> <out>          Copy archive (Xform + PolyMesh only)");
+    println!("              [--resample-uniform <fps>] [--frames <a-b>] [--interp nearest|linear]  Resample ALL animated properties");
     println!("    c2, copy2 <in> <out>          Full re-write using our writer (ALL types)");
+    println!("              [--fps <n>] [--frames <a-b>] [--interp linear|nearest]  Retime/trim (interpolates by default)");
+    println!("    strip <in> <out>              Drop shading attributes, report bytes saved");
+    println!("              [--drop N,uv,velocities,<arbGeomParam>] [--recompute-normals] [--fix-winding]");
+    println!("    extract <in> <path> <out>     Write objects matching <path> and everything below them to a new archive");
+    println!("              [--bake]  Drop the ancestors, baking their transforms into the subtree root");
+    println!("    merge <a> <b> ... -o <out>    Combine archives (--on-collision error|rename|replace)");
+    println!("    x, export <in> <out>          Convert to glTF 2.0 (.glb or .gltf + .bin), an OBJ sequence (.obj), USD (.usda),");
+    println!("                                  or a PBRT v4 (.pbrt) / Mitsuba 3 (.xml) scene with PLY meshes");
+    println!("              [--time <sec> | --frame <n>] (glTF, PBRT, Mitsuba) [--frames <a-b>] [--local] (OBJ) [--fps <n>] (USD)");
+    println!("              [--camera <path>] [--size WxH] [--spp n] [--bounces n] [--env <map>] [--env-color r,g,b] (PBRT, Mitsuba)");
+    println!("              [--no-subd] [--include-hidden] (all but USD)");
     println!("    mat, materialize <file>       Add materials to meshes (outputs <file>_mat.abc)");
+    println!("    serve <file|dir>...           Read-only HTTP service (manifest, objects, samples, thumbnails)");
+    println!("              [--bind addr:port] [--threads n] [--max-pending n]");
+    println!("    diff <a> <b>                  Compare archives: objects, schemas, sample counts, value deltas");
+    println!("              [--tol <abs>] [--rel-tol <rel>] [--time-tol <sec>] [--metadata] [--max <n>]");
+    println!("    make-patch <base> <target> <out.abcpatch>  Binary patch with the samples added/changed since <base>");
+    println!("    apply-patch <base> <patch> <out>           Rebuild the target from <base> and a patch (verified)");
+    println!("    pack <in> <out>               Compressed copy for internal storage (non-standard, --features compress)");
+    println!("              [--codec lz4|zlib]");
+    println!("    unpack <in> <out>             Standard archive from a packed one");
+    println!("    manifest <file>               Per-sample hash manifest (JSON) for QC");
+    println!("              [-o manifest.json] [--verify manifest.json]");
+    println!("    hash <file>                   Stored object digests (properties/children) for dedup and caching");
+    println!("              [--properties] [--json]");
+    println!("    check <file>                  Lint: bad face indices, NaNs, GeomParam lengths, times, schemas");
+    println!("              [--json] [--strict] [--max <n>]");
+    println!("    props <file>                  List properties across the archive with sample counts and sizes");
+    println!("              [--type scalar|array|compound] [--min-samples <n>] [--dtype float32x3] [--name <substr>]");
+    println!("              [--sort path|bytes|samples] [--json]");
+    println!("    collections <file>            List collections (selection sets, render layers) and their members");
+    println!("              [--time <sec>] [--all-samples] [--json]");
+    println!("    echo <file> </obj.prop>       Raw sample payloads (abcecho-style), or their byte diff against another file");
+    println!("              [--sample <n>] [--format hex|floats] [--compare <other.abc>]");
+    println!("    histo <file> </obj.prop>      Value range and histogram of a property over all samples");
+    println!("              [--bins <n>] [--range <min> <max>] [--component <i>] [--below <x>] [--above <x>]");
+    println!("              [--per-sample] [--json]");
+    println!("    watch <dir>                   Validate .abc files as they land, write pass/fail reports");
+    println!("              [--rules rules.toml] [--once]");
     println!("    h, help                       Show this help");
     println!();
     println!("OPTIONS:");
     println!("    -v, --verbose    Show debug output");
     println!("    -vv, --trace     Show trace output (very verbose)");
     println!("    -q, --quiet      Suppress all output");
+    println!("    --plugin <lib>   Load a schema plugin for tree/stats/meta (repeatable; also ALEMBIC_CLI_PLUGINS)");
     println!();
     println!("EXAMPLES:");
     println!("    alembic view model.abc                # Open in 3D viewer");
+    println!("    alembic view shot.abc --camera /shotCam --frame 1015 --pt");
+    println!("    alembic render shot.abc -o qc/shot.####.exr --frames 1001-1100 --spp 64");
     println!("    alembic info scene.abc                # Quick overview");
     println!("    alembic tree character.abc            # See hierarchy");
     println!("    alembic dump scene.abc wheel          # Dump transforms matching 'wheel'");
     println!("    alembic dump scene.abc --json         # Export all transforms as JSON");
+    println!("    alembic tree shot.abc '/root/char*/geo/**[PolyMesh]'   # Hierarchy query");
     println!("    alembic copy input.abc output.abc     # Test round-trip");
+    println!("    alembic copy2 shot.abc out.abc --fps 30 --frames 1001-1100  # Retime and trim");
+    println!("    alembic strip sim.abc sim_lite.abc --drop N,uv --recompute-normals");
+    println!("    alembic strip export.abc fixed.abc --fix-winding   # Rewind faces flipped against their neighbours");
+    println!("    alembic extract set.abc /set/building_01 building_01.abc --bake");
+    println!("    alembic extract set.abc '/set/building_*' buildings.abc");
+    println!("    alembic merge layout.abc anim.abc fx.abc -o shot.abc --on-collision rename");
+    println!("    alembic export scene.abc scene.glb    # Meshes, xforms, cameras and UV sets to glTF");
+    println!("    alembic export shot.abc out/shot.####.obj --frames 1001-1010  # One OBJ per frame");
+    println!("    alembic export shot.abc shot.usda     # USD layer with all time samples");
+    println!("    alembic export shot.abc qc/shot.xml --frame 1015 --camera /shotCam --env studio.exr  # Cross-check in Mitsuba");
     println!("    alembic -v info large.abc             # Verbose info");
+    println!("    alembic serve /shots --bind 0.0.0.0:8080 --threads 8");
+    println!("    alembic diff cpp_out.abc rust_out.abc --tol 1e-6   # exit 2 if they differ");
+    println!("    alembic make-patch shot_v1.abc shot_v2.abc v1_v2.abcpatch   # ship only what changed");
+    println!("    alembic apply-patch shot_v1.abc v1_v2.abcpatch shot_v2.abc");
+    println!("    alembic pack sim.abc sim_packed.abc --codec zlib   # farm storage; unpack before handing to DCCs");
+    println!("    alembic manifest shot.abc -o shot.manifest.json");
+    println!("    alembic manifest delivery.abc --verify shot.manifest.json   # exit 2 on mismatch");
+    println!("    alembic check shot.abc                # exit 2 on errors (--strict: on warnings too)");
+    println!("    alembic props shot.abc --type array --min-samples 2 --dtype float32x3 --sort bytes");
+    println!("    alembic collections layout.abc --all-samples   # membership per sample of animated sets");
+    println!("    alembic echo rust_out.abc /geo/mesh.P --sample 12 --compare cpp_out.abc   # exit 2 if bytes differ");
+    println!("    alembic histo fur.abc /groom/curves.widths --below 0.0005   # exit 2 on sub-pixel widths");
+    println!("    alembic histo sim.abc /fluid/particles.velocities --bins 64 --per-sample");
+    println!("    alembic watch /publish --rules rules.toml");
+    println!("    alembic watch /publish --rules rules.toml --once   # CI gate, exit 2 on failure");
     println!();
     println!("NOTES:");
     println!("    - Passing a .abc file directly is equivalent to 'info'");
+    println!("    - Patterns: * ? {{a,b}} within a name, ** for any depth, [Type] on the last segment;");
+    println!("      a bare word matches any path containing it ('wheel' = '**/*wheel*/**')");
+    println!("    - --frame/--frames take scene frame numbers, mapped with the archive fps:");
+    println!("      time = frame / fps, or (frame - 1) / fps for archives written by Houdini");
+    println!("    - histo reduces vector values to their magnitude; '.widths' and '.geom/.widths' both resolve");
     println!("    - Viewer requires --features viewer (enabled by default)");
+    println!("    - serve requires --features serve");
+    println!("    - pack/unpack require --features compress; packed archives only open with it");
     println!("    - Press Esc to close the viewer");
 }
 
@@ -267,6 +784,8 @@ fn cmd_info(path: &str) {
     println!("Archive: {}", path);
     println!("Version: {}", archive.getArchiveVersion());
     println!("Time samplings: {}", archive.getNumTimeSamplings());
+    let frames = archive.frame_mapping();
+    println!("Frame mapping: {} fps, {:?} convention", frames.fps, frames.convention);
     println!();
     
     // Count objects by type
@@ -293,9 +812,10 @@ fn cmd_info(path: &str) {
     println!("Total objects: {}", counts.total());
 }
 
-fn cmd_tree(path: &str) {
+fn cmd_tree(path: &str, pattern: Option<&str>) {
     info!("Opening archive: {}", path);
     
+    let query = pattern.map(parse_filter_or_exit);
     let archive = match AbcIArchive::open(path) {
         Ok(a) => a,
         Err(e) => {
@@ -308,7 +828,18 @@ fn cmd_tree(path: &str) {
     println!();
     
     let root = archive.getTop();
-    print_tree(&root, 0);
+    // With a pattern, show the matches and the objects leading to them
+    let shown = query.as_ref().map(|q| {
+        let mut shown = HashSet::new();
+        for path in q.select_paths(&root) {
+            let mut end = path.len();
+            while end > 0 && shown.insert(path[..end].to_string()) {
+                end = path[..end].rfind('/').unwrap_or(0);
+            }
+        }
+        shown
+    });
+    print_tree(&root, 0, shown.as_ref());
 }
 
 fn cmd_stats(path: &str) {
@@ -416,20 +947,24 @@ fn count_objects(obj: &IObject, counts: &mut ObjectCounts) {
     }
 }
 
-fn print_tree(obj: &IObject, depth: usize) {
+fn print_tree(obj: &IObject, depth: usize, shown: Option<&HashSet<String>>) {
     let indent = "  ".repeat(depth);
     let schema = obj.getMetaData().get("schema").unwrap_or_default();
     let type_str = schema_to_type(schema);
     
     if depth == 0 {
         println!("{}/", obj.getName());
+    } else if let Some(summary) = plugin::summary(obj) {
+        println!("{}{} [{}] - {}", indent, obj.getName(), type_str, summary);
     } else {
         println!("{}{} [{}]", indent, obj.getName(), type_str);
     }
     
     for i in 0..obj.getNumChildren() {
         if let Some(child) = obj.getChild(i) {
-            print_tree(&child, depth + 1);
+            if shown.is_none_or(|s| s.contains(child.getFullName())) {
+                print_tree(&child, depth + 1, shown);
+            }
         }
     }
 }
@@ -439,8 +974,10 @@ fn print_stats_tree(obj: &IObject, depth: usize) {
     let schema = obj.getMetaData().get("schema").unwrap_or_default();
     let type_str = schema_to_type(schema);
     
-    // Get additional info based on type
-    let extra_info = get_object_info(obj, schema);
+    // Get additional info based on type (plugins first)
+    let extra_info = plugin::summary(obj)
+        .map(|summary| format!("- {}", summary))
+        .unwrap_or_else(|| get_object_info(obj, schema));
     
     if depth == 0 {
         println!("{}/", obj.getName());
@@ -475,6 +1012,7 @@ fn schema_to_type(schema: &str) -> &str {
 fn cmd_meta(path: &str, pattern: Option<&str>) {
     info!("Opening archive: {}", path);
     
+    let query = pattern.map(parse_filter_or_exit);
     let archive = match AbcIArchive::open(path) {
         Ok(a) => a,
         Err(e) => {
@@ -503,15 +1041,14 @@ fn cmd_meta(path: &str, pattern: Option<&str>) {
     
     println!("\nObject Metadata{}", if let Some(p) = pattern { format!(" (filter: {})", p) } else { String::new() });
     let root = archive.getTop();
-    dump_object_meta(&root, 0, pattern);
+    dump_object_meta(&root, 0, query.as_ref());
 }
 
-fn dump_object_meta(obj: &IObject, depth: usize, pattern: Option<&str>) {
+fn dump_object_meta(obj: &IObject, depth: usize, query: Option<&Query>) {
     let indent = "  ".repeat(depth);
     let name = obj.getName();
-    let full_name = obj.getFullName();
     
-    let matches = pattern.map(|p| full_name.contains(p) || name.contains(p)).unwrap_or(true);
+    let matches = query.is_none_or(|q| q.matches(obj));
     
     if matches {
         let meta = obj.getMetaData();
@@ -519,6 +1056,9 @@ fn dump_object_meta(obj: &IObject, depth: usize, pattern: Option<&str>) {
         let type_str = schema_to_type(schema);
         
         println!("{}[{}] {}", indent, type_str, name);
+        if let Some(summary) = plugin::summary(obj) {
+            println!("{}  summary: {}", indent, summary);
+        }
         
         // Print object metadata
         if !meta.is_empty() {
@@ -539,7 +1079,7 @@ fn dump_object_meta(obj: &IObject, depth: usize, pattern: Option<&str>) {
     }
     
     for child in obj.getChildren() {
-        dump_object_meta(&child, depth + 1, pattern);
+        dump_object_meta(&child, depth + 1, query);
     }
 }
 
@@ -577,6 +1117,7 @@ fn dump_properties(props: &ICompoundProperty, depth: usize) {
 fn cmd_dump(path: &str, pattern: Option<&str>, json_mode: bool) {
     info!("Opening archive: {}", path);
     
+    let query = pattern.map(parse_filter_or_exit);
     let archive = match AbcIArchive::open(path) {
         Ok(a) => a,
         Err(e) => {
@@ -588,7 +1129,7 @@ fn cmd_dump(path: &str, pattern: Option<&str>, json_mode: bool) {
     if json_mode {
         let root = archive.getTop();
         let mut objects = Vec::new();
-        collect_dump_json(&root, glam::Mat4::IDENTITY, pattern, &mut objects);
+        collect_dump_json(&root, glam::Mat4::IDENTITY, query.as_ref(), &mut objects);
         println!("{}", serde_json::to_string_pretty(&serde_json::json!({
             "archive": path,
             "objects": objects
@@ -598,17 +1139,16 @@ fn cmd_dump(path: &str, pattern: Option<&str>, json_mode: bool) {
         println!("Xform Dump{}", if let Some(p) = pattern { format!(" (filter: {})", p) } else { String::new() });
         println!();
         let root = archive.getTop();
-        dump_xforms(&root, 0, glam::Mat4::IDENTITY, pattern);
+        dump_xforms(&root, 0, glam::Mat4::IDENTITY, query.as_ref());
     }
 }
 
-fn dump_xforms(obj: &IObject, depth: usize, parent_world: glam::Mat4, pattern: Option<&str>) {
+fn dump_xforms(obj: &IObject, depth: usize, parent_world: glam::Mat4, query: Option<&Query>) {
     let indent = "  ".repeat(depth);
     let name = obj.getName();
-    let full_name = obj.getFullName();
     
     // Check pattern filter
-    let matches_pattern = pattern.map(|p| full_name.contains(p) || name.contains(p)).unwrap_or(true);
+    let matches_pattern = query.is_none_or(|q| q.matches(obj));
     
     // Get local transform
     let local_matrix = if let Some(xform) = IXform::new(obj) {
@@ -658,7 +1198,7 @@ fn dump_xforms(obj: &IObject, depth: usize, parent_world: glam::Mat4, pattern: O
     
     // Recurse into children
     for child in obj.getChildren() {
-        dump_xforms(&child, depth + 1, local_matrix, pattern);
+        dump_xforms(&child, depth + 1, local_matrix, query);
     }
 }
 
@@ -674,12 +1214,12 @@ fn print_matrix(m: &glam::Mat4, indent: &str) {
 fn collect_dump_json(
     obj: &IObject,
     parent_world: glam::Mat4,
-    pattern: Option<&str>,
+    query: Option<&Query>,
     out: &mut Vec<serde_json::Value>,
 ) -> glam::Mat4 {
     let name = obj.getName();
     let full_name = obj.getFullName();
-    let matches = pattern.map(|p| full_name.contains(p) || name.contains(p)).unwrap_or(true);
+    let matches = query.is_none_or(|q| q.matches(obj));
     
     let local_matrix = if let Some(xform) = IXform::new(obj) {
         if let Ok(sample) = xform.getSample(0) {
@@ -729,7 +1269,7 @@ fn collect_dump_json(
     };
     
     for child in obj.getChildren() {
-        collect_dump_json(&child, local_matrix, pattern, out);
+        collect_dump_json(&child, local_matrix, query, out);
     }
     local_matrix
 }
@@ -738,6 +1278,162 @@ fn mat4_to_array(m: &glam::Mat4) -> [[f32; 4]; 4] {
     m.to_cols_array_2d()
 }
 
+fn cmd_export(input: &str, output: &str, args: &[&str]) -> Result<(), String> {
+    use alembic::export::{
+        export_gltf, export_mitsuba, export_obj_sequence, export_pbrt, export_usda, GltfOptions, ObjOptions,
+        RenderExportOptions, UsdOptions,
+    };
+
+    let ext = Path::new(output).extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    if !matches!(ext.as_str(), "glb" | "gltf" | "obj" | "usda" | "usd" | "pbrt" | "xml") {
+        return Err(format!("unsupported export format: {} (expected .glb, .gltf, .obj, .usda, .pbrt or .xml)", output));
+    }
+    let is_obj = ext == "obj";
+    let is_usd = matches!(ext.as_str(), "usda" | "usd");
+    // PBRT / Mitsuba scenes for offline renderers
+    let is_render = matches!(ext.as_str(), "pbrt" | "xml");
+    let is_gltf = !is_obj && !is_usd && !is_render;
+
+    let mut gltf = GltfOptions::default();
+    let mut obj = ObjOptions::default();
+    let mut usd = UsdOptions::default();
+    let mut render = RenderExportOptions::default();
+    let mut frame = None;
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--time" | "-t" if is_gltf || is_render => {
+                let v = iter.next().ok_or("--time requires a value")?;
+                gltf.time = v.parse().map_err(|_| format!("invalid time: {}", v))?;
+                render.time = gltf.time;
+            }
+            "--frame" if is_gltf || is_render => {
+                let v = iter.next().ok_or("--frame requires a value")?;
+                frame = Some(v.parse::<f64>().map_err(|_| format!("invalid frame: {}", v))?);
+            }
+            "--camera" | "--cam" if is_render => {
+                render.camera = Some(iter.next().ok_or("--camera requires a value")?.to_string());
+            }
+            "--size" if is_render => {
+                let v = iter.next().ok_or("--size requires a value")?;
+                let size = v.split_once('x').and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+                render.resolution = Some(size.filter(|&(w, h)| w > 0 && h > 0).ok_or_else(|| format!("invalid size: {} (expected WxH)", v))?);
+            }
+            "--spp" if is_render => {
+                let v = iter.next().ok_or("--spp requires a value")?;
+                render.spp = v.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("invalid spp: {}", v))?;
+            }
+            "--bounces" if is_render => {
+                let v = iter.next().ok_or("--bounces requires a value")?;
+                render.max_depth = v.parse().map_err(|_| format!("invalid bounces: {}", v))?;
+            }
+            "--env" | "--hdr" if is_render => {
+                render.env = Some(PathBuf::from(iter.next().ok_or("--env requires a value")?));
+            }
+            "--env-color" if is_render => {
+                let v = iter.next().ok_or("--env-color requires a value")?;
+                render.env_color = parse_rgb(v).ok_or_else(|| format!("invalid color: {} (expected r,g,b)", v))?;
+            }
+            "--frames" | "-f" if is_obj => {
+                let v = iter.next().ok_or("--frames requires a value")?;
+                obj.frames = Some(parse_frame_range(v).ok_or_else(|| format!("invalid frame range: {} (expected a-b or n)", v))?);
+            }
+            "--local" if is_obj => obj.world_space = false,
+            "--fps" if is_usd => {
+                let v = iter.next().ok_or("--fps requires a value")?;
+                usd.fps = Some(v.parse().ok().filter(|f: &f64| *f > 0.0).ok_or_else(|| format!("invalid fps: {}", v))?);
+            }
+            "--no-subd" if !is_usd => {
+                gltf.include_subd = false;
+                obj.include_subd = false;
+                render.include_subd = false;
+            }
+            "--include-hidden" if !is_usd => {
+                gltf.skip_hidden = false;
+                obj.skip_hidden = false;
+                render.skip_hidden = false;
+            }
+            _ => return Err(format!("unknown {} export option: {}", ext, arg)),
+        }
+    }
+
+    let archive = AbcIArchive::open(input).map_err(|e| format!("Failed to open {}: {}", input, e))?;
+    if let Some(frame) = frame {
+        // Scene frame -> archive time via the archive's fps and frame convention
+        gltf.time = archive.frame_mapping().frame_to_time(frame);
+        render.time = gltf.time;
+    }
+    if is_render {
+        let stats = if ext == "pbrt" { export_pbrt(&archive, output, &render) } else { export_mitsuba(&archive, output, &render) }
+            .map_err(|e| format!("Failed to write {}: {}", output, e))?;
+        info!("Exported {} -> {} ({} files)", input, output, stats.files.len());
+        println!(
+            "Exported {} meshes ({} triangles), {} materials, camera {} to {}",
+            stats.meshes,
+            stats.triangles,
+            stats.materials,
+            stats.camera.as_deref().unwrap_or("(framing the scene)"),
+            output
+        );
+        return Ok(());
+    }
+    if is_usd {
+        let stats = export_usda(&archive, output, &usd).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+        info!("Exported {} -> {}", input, output);
+        println!(
+            "Exported {} prims ({} xforms, {} meshes, {} cameras, {} points) at {} fps to {}",
+            stats.prims, stats.xforms, stats.meshes, stats.cameras, stats.points, stats.fps, output
+        );
+        return Ok(());
+    }
+    if is_obj {
+        let stats = export_obj_sequence(&archive, output, &obj).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+        info!("Exported {} -> {} file(s)", input, stats.files.len());
+        match (stats.files.first(), stats.files.last()) {
+            (Some(first), Some(last)) if stats.files.len() > 1 => println!(
+                "Exported {} OBJ files ({} .. {})", stats.files.len(), first.display(), last.display()
+            ),
+            (Some(first), _) => println!("Exported {} meshes to {}", stats.meshes, first.display()),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    let stats = export_gltf(&archive, output, &gltf).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    info!("Exported {} -> {}", input, output);
+    println!(
+        "Exported {} nodes, {} meshes ({} triangles), {} cameras to {}",
+        stats.nodes, stats.meshes, stats.triangles, stats.cameras, output
+    );
+    Ok(())
+}
+
+/// Object filter for tree/dump/meta: a hierarchy query (see `alembic::query`),
+/// where a bare word like `wheel` keeps meaning "any object with it in its path"
+/// (objects named `*wheel*` and everything below them).
+fn parse_filter(pattern: &str) -> Result<Query, String> {
+    let is_word = !pattern.contains(['/', '*', '?', '[', '{', '\\']);
+    let query = if is_word { format!("**/*{}*/**", pattern) } else { pattern.to_string() };
+    Query::parse(&query).map_err(|e| e.to_string())
+}
+
+fn parse_filter_or_exit(pattern: &str) -> Query {
+    parse_filter(pattern).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+/// Parse "a-b" (inclusive) or a single frame "n"; negative frames are allowed ("-5--1").
+fn parse_frame_range(s: &str) -> Option<std::ops::RangeInclusive<i64>> {
+    if let Ok(frame) = s.parse::<i64>() {
+        return Some(frame..=frame);
+    }
+    let split = s.char_indices().skip(1).find(|&(_, c)| c == '-')?.0;
+    let (a, b) = (s[..split].parse().ok()?, s[split + 1..].parse().ok()?);
+    (a <= b).then_some(a..=b)
+}
+
 fn cmd_copy(input: &str, output: &str) {
     info!("Copying {} -> {}", input, output);
     
@@ -921,70 +1617,25 @@ fn merge_property(target: &mut Vec<OProperty>, prop: OProperty) {
     target.push(prop);
 }
 
-fn copy_property_recursive(
-    prop: &IProperty<'_>,
-    ts_map: &std::collections::HashMap<u32, u32>,
-) -> Option<OProperty> {
-    let header = prop.getHeader();
-    let name = &header.name;
-    let data_type = header.data_type;
-    let ts_idx = map_ts(ts_map, header.time_sampling_index);
-    let meta = header.meta_data.clone();
-
-    if let Some(compound) = prop.asCompound() {
-        let mut out = OProperty::compound(name);
-        out.meta_data = meta;
-        out.time_sampling_index = ts_idx;
-        let num = compound.getNumProperties();
-        for i in 0..num {
-            if let Some(child) = compound.getProperty(i) {
-                if let Some(out_child) = copy_property_recursive(&child, ts_map) {
-                    out.add_child(out_child);
-                }
-            }
-        }
-        return Some(out);
-    }
-
-    if let Some(scalar) = prop.asScalar() {
-        let mut out = OProperty::scalar(name, data_type);
-        out.meta_data = meta;
-        out.time_sampling_index = ts_idx;
-        let num_samples = scalar.getNumSamples();
-        let is_string = matches!(
-            data_type.pod,
-            PlainOldDataType::String | PlainOldDataType::Wstring
-        );
-        for i in 0..num_samples {
-            if is_string {
-                if let Ok(buf) = scalar.getSampleVec(i) {
-                    out.add_scalar_sample(&buf);
-                }
-            } else {
-                let sample_size = data_type.num_bytes();
-                let mut buf = vec![0u8; sample_size];
-                if scalar.getSample(i, &mut buf).is_ok() {
-                    out.add_scalar_sample(&buf);
-                }
-            }
-        }
-        return Some(out);
-    }
+/// Copies samples unchanged onto the output archive's time samplings.
+/// Unreadable samples end the property's copy with a warning.
+struct MapTimeSampling<'a>(&'a std::collections::HashMap<u32, u32>);
 
-    if let Some(array) = prop.asArray() {
-        let mut out = OProperty::array(name, data_type);
-        out.meta_data = meta;
-        out.time_sampling_index = ts_idx;
-        let num_samples = array.getNumSamples();
-        for i in 0..num_samples {
-            if let (Ok(data), Ok(dims)) = (array.getSampleVec(i), array.getDimensions(i)) {
-                out.add_array_sample(&data, &dims);
-            }
+impl CopyTransform for MapTimeSampling<'_> {
+    fn copy_samples(&mut self, prop: &IProperty<'_>, out: &mut OProperty) -> alembic::Result<bool> {
+        out.time_sampling_index = map_ts(self.0, out.time_sampling_index);
+        if let Err(e) = alembic::copy::copy_samples(prop, out) {
+            warn!("{}: {}", out.name, e);
         }
-        return Some(out);
+        Ok(true)
     }
+}
 
-    None
+fn copy_property_recursive(
+    prop: &IProperty<'_>,
+    ts_map: &std::collections::HashMap<u32, u32>,
+) -> Option<OProperty> {
+    alembic::copy::copy_property(prop, &mut MapTimeSampling(ts_map)).ok().flatten()
 }
 
 fn copy_properties_from(
@@ -1166,7 +1817,7 @@ fn copy2_xform(
         for i in 0..xform.getNumSamples() {
             if let Ok(sample) = xform.getSample(i) {
                 // Preserve original ops for binary parity
-                out_xform.add_sample(OXformSample::from_ops(sample.ops.clone(), sample.inherits));
+                out_xform.add_sample(OXformSample::from_sample(&sample));
             }
         }
         
@@ -1281,6 +1932,10 @@ fn copy2_subd(
                     sample.face_counts.clone(),
                     sample.face_indices.clone(),
                 );
+                out_sample.subdivision_scheme = sample.scheme.as_str().to_string();
+                out_sample.interp_boundary = Some(sample.interp_boundary);
+                out_sample.fv_interp_boundary = Some(sample.fv_interp_boundary);
+                out_sample.fv_propagate_corners = Some(sample.fv_propagate_corners);
                 // Copy optional fields (velocities already Option, others are plain Vec)
                 out_sample.velocities = sample.velocities.clone();
                 if !sample.crease_indices.is_empty() {
@@ -1925,7 +2580,7 @@ fn materialize_object(
             
             for i in 0..xform.getNumSamples() {
                 if let Ok(sample) = xform.getSample(i) {
-                    out_xform.add_sample(OXformSample::from_ops(sample.ops.clone(), sample.inherits));
+                    out_xform.add_sample(OXformSample::from_sample(&sample));
                 }
             }
             
@@ -1956,27 +2611,6 @@ fn materialize_object(
     Some(out_obj)
 }
 
-/// Add material assignment property to an object
-fn add_material_assignment(obj: &mut OObject, material_path: &str) {
-    // Create .material compound property
-    let mut mat_compound = OProperty::compound(".material");
-    
-    // Add "assign" scalar property with the material path
-    let mut assign_prop = OProperty::scalar(
-        "assign",
-        alembic::util::DataType::new(PlainOldDataType::String, 1),
-    );
-    let mut path_bytes = material_path.as_bytes().to_vec();
-    path_bytes.push(0); // null terminator
-    assign_prop.add_scalar_sample(&path_bytes);
-    
-    if let OPropertyData::Compound(children) = &mut mat_compound.data {
-        children.push(assign_prop);
-    }
-    
-    obj.properties.push(mat_compound);
-}
-
 fn cmd_materialize(input: &str) {
     // Generate output filename
     let input_path = Path::new(input);
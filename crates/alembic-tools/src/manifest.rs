@@ -0,0 +1,329 @@
+//! `alembic manifest` - golden hash manifests for archive QC.
+//!
+//! A manifest records, for every object, its schema and for every leaf
+//! property its data type, sample time range and one hash per sample. It is
+//! written as JSON and can later be checked against any archive that should
+//! hold the same data (a re-export, a copy, a client delivery):
+//!
+//! ```text
+//! alembic manifest shot.abc -o shot.manifest.json     # generate
+//! alembic manifest delivery.abc --verify shot.manifest.json
+//! ```
+//!
+//! Hashes are MurmurHash3 x64_128 of the decoded sample bytes (plus array
+//! dimensions), so they do not depend on how the file was laid out or
+//! compressed. Verification exits with status 2 when anything differs.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use alembic::abc::{IArchive as AbcIArchive, ICompoundProperty, IProperty};
+use alembic::core::compute_digest;
+use alembic::prelude::IObject;
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+/// Manifest format version written to and required from manifest files.
+const MANIFEST_VERSION: u64 = 1;
+
+/// Maximum number of differences printed by `--verify`.
+const MAX_REPORTED: usize = 50;
+
+/// What to do with the archive.
+#[derive(Debug, Clone)]
+pub enum ManifestOptions {
+    /// Write a manifest to a file, or stdout when `None`.
+    Generate { output: Option<PathBuf> },
+    /// Compare the archive against a stored manifest.
+    Verify { manifest: PathBuf },
+}
+
+/// Parse the flags following `manifest <file>`.
+pub fn parse_args(args: &[&str]) -> Result<ManifestOptions, String> {
+    let mut output = None;
+    let mut verify = None;
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "-o" | "--output" => {
+                output = Some(PathBuf::from(iter.next().ok_or("--output requires a path")?));
+            }
+            "--verify" => {
+                verify = Some(PathBuf::from(iter.next().ok_or("--verify requires a manifest path")?));
+            }
+            _ => return Err(format!("unknown manifest option: {}", arg)),
+        }
+    }
+    match (output, verify) {
+        (Some(_), Some(_)) => Err("--output and --verify are mutually exclusive".to_string()),
+        (_, Some(manifest)) => Ok(ManifestOptions::Verify { manifest }),
+        (output, None) => Ok(ManifestOptions::Generate { output }),
+    }
+}
+
+/// Hashes of one leaf property.
+#[derive(Debug, Clone, PartialEq)]
+struct PropertyEntry {
+    kind: &'static str,
+    data_type: String,
+    /// First and last sample time (absent for properties without samples).
+    times: Option<(f64, f64)>,
+    samples: Vec<String>,
+}
+
+/// One object: schema plus its leaf properties keyed by compound path.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ObjectEntry {
+    schema: String,
+    properties: BTreeMap<String, PropertyEntry>,
+}
+
+/// Manifest of a whole archive, keyed by object full name.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Manifest {
+    objects: BTreeMap<String, ObjectEntry>,
+}
+
+impl Manifest {
+    fn build(archive: &AbcIArchive) -> Result<Self, String> {
+        let mut manifest = Manifest::default();
+        let root = archive.getTop();
+        manifest.add_object(archive, &root)?;
+        Ok(manifest)
+    }
+
+    fn add_object(&mut self, archive: &AbcIArchive, obj: &IObject) -> Result<(), String> {
+        debug!("manifest: {}", obj.getFullName());
+        let mut entry = ObjectEntry {
+            schema: obj.getMetaData().get("schema").unwrap_or_default().to_string(),
+            ..Default::default()
+        };
+        let props = obj.getProperties();
+        add_compound(archive, &props, "", &mut entry.properties)
+            .map_err(|e| format!("{}: {}", obj.getFullName(), e))?;
+        let path = if obj.getFullName().is_empty() { "/" } else { obj.getFullName() };
+        self.objects.insert(path.to_string(), entry);
+        for child in obj.getChildren() {
+            self.add_object(archive, &child)?;
+        }
+        Ok(())
+    }
+
+    fn num_properties(&self) -> usize {
+        self.objects.values().map(|o| o.properties.len()).sum()
+    }
+
+    fn num_samples(&self) -> usize {
+        self.objects.values().flat_map(|o| o.properties.values()).map(|p| p.samples.len()).sum()
+    }
+
+    fn to_json(&self, source: &str) -> Value {
+        let objects: serde_json::Map<String, Value> = self
+            .objects
+            .iter()
+            .map(|(path, obj)| {
+                let props: serde_json::Map<String, Value> = obj
+                    .properties
+                    .iter()
+                    .map(|(name, p)| {
+                        let mut v = json!({ "kind": p.kind, "type": p.data_type, "samples": p.samples });
+                        if let Some((a, b)) = p.times {
+                            v["times"] = json!([a, b]);
+                        }
+                        (name.clone(), v)
+                    })
+                    .collect();
+                (path.clone(), json!({ "schema": obj.schema, "properties": props }))
+            })
+            .collect();
+        json!({
+            "format": "alembic-manifest",
+            "version": MANIFEST_VERSION,
+            "source": source,
+            "objects": objects,
+        })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, String> {
+        if value["format"] != "alembic-manifest" {
+            return Err("not an alembic manifest".to_string());
+        }
+        let version = value["version"].as_u64().unwrap_or(0);
+        if version != MANIFEST_VERSION {
+            return Err(format!("unsupported manifest version {}", version));
+        }
+        let objects = value["objects"].as_object().ok_or("manifest has no objects")?;
+        let mut manifest = Manifest::default();
+        for (path, obj) in objects {
+            let mut entry = ObjectEntry {
+                schema: obj["schema"].as_str().unwrap_or_default().to_string(),
+                ..Default::default()
+            };
+            for (name, p) in obj["properties"].as_object().into_iter().flatten() {
+                let times = p["times"].as_array().and_then(|t| Some((t.first()?.as_f64()?, t.get(1)?.as_f64()?)));
+                let samples = p["samples"]
+                    .as_array()
+                    .ok_or_else(|| format!("{} {}: missing samples", path, name))?
+                    .iter()
+                    .map(|s| s.as_str().unwrap_or_default().to_string())
+                    .collect();
+                let kind = match p["kind"].as_str() {
+                    Some("scalar") => "scalar",
+                    _ => "array",
+                };
+                entry.properties.insert(name.clone(), PropertyEntry {
+                    kind,
+                    data_type: p["type"].as_str().unwrap_or_default().to_string(),
+                    times,
+                    samples,
+                });
+            }
+            manifest.objects.insert(path.clone(), entry);
+        }
+        Ok(manifest)
+    }
+
+    /// Human-readable differences between `self` (expected) and `actual`.
+    fn diff(&self, actual: &Manifest) -> Vec<String> {
+        let mut out = Vec::new();
+        for (path, expected) in &self.objects {
+            let Some(found) = actual.objects.get(path) else {
+                out.push(format!("{}: missing object", path));
+                continue;
+            };
+            if expected.schema != found.schema {
+                out.push(format!("{}: schema {:?}, expected {:?}", path, found.schema, expected.schema));
+            }
+            for (name, e) in &expected.properties {
+                let Some(f) = found.properties.get(name) else {
+                    out.push(format!("{} {}: missing property", path, name));
+                    continue;
+                };
+                if e.kind != f.kind || e.data_type != f.data_type {
+                    out.push(format!("{} {}: {} {}, expected {} {}", path, name, f.kind, f.data_type, e.kind, e.data_type));
+                    continue;
+                }
+                if e.samples.len() != f.samples.len() {
+                    out.push(format!("{} {}: {} samples, expected {}", path, name, f.samples.len(), e.samples.len()));
+                    continue;
+                }
+                let times_match = match (e.times, f.times) {
+                    (Some((a0, a1)), Some((b0, b1))) => (a0 - b0).abs() < 1e-9 && (a1 - b1).abs() < 1e-9,
+                    (a, b) => a == b,
+                };
+                if !times_match {
+                    out.push(format!("{} {}: sample times {:?}, expected {:?}", path, name, f.times, e.times));
+                }
+                for (i, (a, b)) in e.samples.iter().zip(&f.samples).enumerate() {
+                    if a != b {
+                        out.push(format!("{} {}: sample {} differs", path, name, i));
+                    }
+                }
+            }
+            for name in found.properties.keys().filter(|n| !expected.properties.contains_key(*n)) {
+                out.push(format!("{} {}: unexpected property", path, name));
+            }
+        }
+        for path in actual.objects.keys().filter(|p| !self.objects.contains_key(*p)) {
+            out.push(format!("{}: unexpected object", path));
+        }
+        out
+    }
+}
+
+fn add_compound(
+    archive: &AbcIArchive,
+    props: &ICompoundProperty<'_>,
+    prefix: &str,
+    out: &mut BTreeMap<String, PropertyEntry>,
+) -> alembic::util::Result<()> {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        let name = format!("{}{}", prefix, prop.getName());
+        if let Some(compound) = prop.asCompound() {
+            add_compound(archive, &compound, &format!("{}/", name), out)?;
+        } else if let Some(entry) = property_entry(archive, &prop)? {
+            out.insert(name, entry);
+        }
+    }
+    Ok(())
+}
+
+fn property_entry(archive: &AbcIArchive, prop: &IProperty<'_>) -> alembic::util::Result<Option<PropertyEntry>> {
+    let header = prop.getHeader();
+    let pod_size = Some(header.data_type.pod.num_bytes() as u32);
+    let mut samples = Vec::new();
+    let kind = if let Some(scalar) = prop.asScalar() {
+        for i in 0..scalar.getNumSamples() {
+            samples.push(hex(&compute_digest(&scalar.getSampleVec(i)?, None, pod_size)));
+        }
+        "scalar"
+    } else if let Some(array) = prop.asArray() {
+        for i in 0..array.getNumSamples() {
+            let mut data = array.getSampleVec(i)?;
+            for d in array.getDimensions(i)? {
+                data.extend_from_slice(&(d as u64).to_le_bytes());
+            }
+            samples.push(hex(&compute_digest(&data, None, None)));
+        }
+        "array"
+    } else {
+        return Ok(None);
+    };
+    let times = match (samples.len(), archive.getTimeSampling(header.time_sampling_index as usize)) {
+        (0, _) | (_, None) => None,
+        (n, Some(ts)) => Some(ts.time_range(n)),
+    };
+    Ok(Some(PropertyEntry { kind, data_type: header.data_type.to_string(), times, samples }))
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Generate or verify a manifest for `input`.
+/// Returns the number of differences found (always 0 when generating).
+pub fn run(input: &str, opts: &ManifestOptions) -> Result<usize, String> {
+    info!("Manifest {} ({:?})", input, opts);
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let manifest = Manifest::build(&archive)?;
+
+    match opts {
+        ManifestOptions::Generate { output } => {
+            let source = std::path::Path::new(input).file_name().and_then(|n| n.to_str()).unwrap_or(input);
+            let text = serde_json::to_string_pretty(&manifest.to_json(source)).unwrap_or_default();
+            match output {
+                Some(path) => {
+                    std::fs::write(path, text + "\n").map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+                    println!("Manifest {} -> {}", input, path.display());
+                    println!("  Objects:    {}", manifest.objects.len());
+                    println!("  Properties: {}", manifest.num_properties());
+                    println!("  Samples:    {}", manifest.num_samples());
+                }
+                None => println!("{}", text),
+            }
+            Ok(0)
+        }
+        ManifestOptions::Verify { manifest: path } => {
+            let text = std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+            let value: Value = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let expected = Manifest::from_json(&value).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let diffs = expected.diff(&manifest);
+            if diffs.is_empty() {
+                println!("OK {} matches {}", input, path.display());
+                println!("  Objects:    {}", manifest.objects.len());
+                println!("  Properties: {}", manifest.num_properties());
+                println!("  Samples:    {}", manifest.num_samples());
+            } else {
+                println!("FAILED {} does not match {} ({} differences)", input, path.display(), diffs.len());
+                for d in diffs.iter().take(MAX_REPORTED) {
+                    println!("  - {}", d);
+                }
+                if diffs.len() > MAX_REPORTED {
+                    println!("  ... {} more", diffs.len() - MAX_REPORTED);
+                }
+            }
+            Ok(diffs.len())
+        }
+    }
+}
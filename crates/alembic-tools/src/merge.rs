@@ -0,0 +1,321 @@
+//! `alembic merge a.abc b.abc ... -o out.abc` - combine several archives.
+//!
+//! Shots are assembled from per-department caches (layout, anim, fx, ...).
+//! `merge` writes the hierarchies of all inputs, in the order given, into one
+//! archive. An object found again in a later input (same path, metadata and
+//! property samples, typically a shared group like `/shot/char`) is written
+//! once and the children of both are combined below it. Any other object
+//! whose path is already taken is a collision, resolved by `--on-collision`:
+//!
+//! - `error` (default): stop and report the path;
+//! - `rename`: the later object is added as `name_1`, `name_2`, ...;
+//! - `replace`: the later object replaces the earlier one.
+//!
+//! Time samplings are merged: equivalent samplings from different inputs
+//! become one. Instances follow renamed sources; an instance whose source was
+//! replaced by an object without that path is dropped.
+
+use std::collections::{HashMap, HashSet};
+
+use alembic::abc::{IArchive as AbcIArchive, ICompoundProperty};
+use alembic::ogawa::writer::{OArchive, OObject};
+use alembic::prelude::IObject;
+use tracing::{debug, info, warn};
+
+/// What to do when a later input has an object at a path already taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Collision {
+    /// Stop with an error naming the path.
+    #[default]
+    Error,
+    /// Keep both; the later object gets a `_N` suffix.
+    Rename,
+    /// Keep the later object only.
+    Replace,
+}
+
+impl Collision {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "error" => Ok(Self::Error),
+            "rename" => Ok(Self::Rename),
+            "replace" => Ok(Self::Replace),
+            _ => Err(format!("unknown collision policy: {} (expected error, rename or replace)", s)),
+        }
+    }
+}
+
+/// Options for a merge.
+#[derive(Debug, Clone, Default)]
+pub struct MergeOptions {
+    /// Input archives, earliest first.
+    pub inputs: Vec<String>,
+    pub output: String,
+    pub on_collision: Collision,
+}
+
+/// Parse the arguments following `merge`: input files, `-o <out>` and flags.
+pub fn parse_args(args: &[&str]) -> Result<MergeOptions, String> {
+    let mut opts = MergeOptions::default();
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "-o" | "--output" => output = Some(iter.next().ok_or("-o requires an output file")?.to_string()),
+            "--on-collision" => {
+                let v = iter.next().ok_or("--on-collision requires error, rename or replace")?;
+                opts.on_collision = Collision::parse(v)?;
+            }
+            "--rename" => opts.on_collision = Collision::Rename,
+            "--replace" => opts.on_collision = Collision::Replace,
+            _ if arg.starts_with('-') => return Err(format!("unknown merge option: {}", arg)),
+            _ => opts.inputs.push(arg.to_string()),
+        }
+    }
+    opts.output = output.ok_or("merge needs an output file: -o <out.abc>")?;
+    if opts.inputs.is_empty() {
+        return Err("merge needs at least one input archive".to_string());
+    }
+    Ok(opts)
+}
+
+/// What makes two objects from different inputs the same object: metadata
+/// and the digest of every property except child bounds, which depend on the
+/// children each input has.
+#[derive(PartialEq, Eq)]
+struct Identity {
+    meta_data: String,
+    properties: Vec<(String, [u8; 16])>,
+}
+
+fn identity(obj: &IObject) -> Option<Identity> {
+    if obj.is_instance() {
+        return None;
+    }
+    let mut properties = Vec::new();
+    add_digests(&obj.getProperties(), "", &mut properties).ok()?;
+    Some(Identity { meta_data: obj.getMetaData().serialize(), properties })
+}
+
+fn add_digests(props: &ICompoundProperty<'_>, prefix: &str, out: &mut Vec<(String, [u8; 16])>) -> alembic::util::Result<()> {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        let name = format!("{}{}", prefix, prop.getName());
+        if let Some(compound) = prop.asCompound() {
+            add_digests(&compound, &format!("{}/", name), out)?;
+        } else if prop.getName() != ".childBnds" {
+            out.push((name, prop.digest()?));
+        }
+    }
+    Ok(())
+}
+
+/// Shared state for one merge.
+#[derive(Default)]
+struct Merger {
+    on_collision: Collision,
+    /// Output path -> identity of the object written there.
+    identities: HashMap<String, Identity>,
+    /// Instances written so far: child indices from the root, source path.
+    instances: Vec<(Vec<usize>, String)>,
+    // Per input
+    ts_map: HashMap<u32, u32>,
+    /// Renamed objects of the current input: (input path, output path).
+    renames: Vec<(String, String)>,
+    /// Instances of the current input, source path not yet rewritten.
+    pending: Vec<(Vec<usize>, String)>,
+    // Totals
+    objects: usize,
+    shared: usize,
+    renamed: usize,
+    replaced: usize,
+}
+
+/// Merge `opts.inputs` into `opts.output`.
+pub fn merge(opts: &MergeOptions) -> Result<(), String> {
+    info!("Merge {} archives -> {} (collisions: {:?})", opts.inputs.len(), opts.output, opts.on_collision);
+    let archives: Vec<AbcIArchive> = opts.inputs
+        .iter()
+        .map(|input| AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e)))
+        .collect::<Result<_, _>>()?;
+
+    let mut out_archive = OArchive::create(&opts.output).map_err(|e| format!("failed to create {}: {}", opts.output, e))?;
+    out_archive.set_archive_metadata(archives[0].getArchiveMetaData().clone());
+    out_archive.set_library_version(archives[0].getArchiveVersion());
+
+    let mut m = Merger { on_collision: opts.on_collision, ..Default::default() };
+    let mut out_root = OObject::new("");
+    for (archive, input) in archives.iter().zip(&opts.inputs) {
+        debug!("merge: {}", input);
+        m.ts_map = HashMap::from([(0, 0)]);
+        for i in 1..archive.getNumTimeSamplings() {
+            if let Some(ts) = archive.getTimeSampling(i) {
+                m.ts_map.insert(i as u32, out_archive.addTimeSampling(ts.clone()));
+            }
+        }
+        m.merge_children(&mut out_root, "", &mut Vec::new(), &archive.getTop(), input)?;
+        m.finish_input(&mut out_root);
+    }
+    let dropped = m.drop_dangling_instances(&mut out_root);
+
+    out_archive.write_archive(&out_root).map_err(|e| format!("failed to write archive: {}", e))?;
+
+    println!("Merged {} archives -> {}", opts.inputs.len(), opts.output);
+    println!("  Objects:         {}", m.objects);
+    println!("  Shared:          {}", m.shared);
+    println!("  Collisions:      {} renamed, {} replaced", m.renamed, m.replaced);
+    println!("  Time samplings:  {}", out_archive.getNumTimeSamplings());
+    if dropped > 0 {
+        println!("  Instances:       {} dropped (source replaced)", dropped);
+    }
+    Ok(())
+}
+
+impl Merger {
+    /// Add the children of `obj` (from `input`) below `out`, which sits at
+    /// `out_path` / child indices `index` in the output.
+    fn merge_children(
+        &mut self,
+        out: &mut OObject,
+        out_path: &str,
+        index: &mut Vec<usize>,
+        obj: &IObject,
+        input: &str,
+    ) -> Result<(), String> {
+        for child in obj.getChildren() {
+            let name = child.getName();
+            let path = format!("{}/{}", out_path, name);
+            let Some(i) = out.children.iter().position(|c| c.name == name) else {
+                index.push(out.children.len());
+                let node = self.copy(&child, &path, index)?;
+                out.children.push(node);
+                index.pop();
+                continue;
+            };
+            let same = identity(&child).is_some_and(|id| self.identities.get(&path) == Some(&id));
+            if same {
+                self.shared += 1;
+                index.push(i);
+                let existing = &mut out.children[i];
+                let objects = self.objects;
+                self.merge_children(existing, &path, index, &child, input)?;
+                if self.objects != objects {
+                    // Its stored bounds only cover the children of the first input
+                    super::extract::drop_child_bounds(existing);
+                }
+                index.pop();
+                continue;
+            }
+            match self.on_collision {
+                Collision::Error => {
+                    return Err(format!(
+                        "{} in {} collides with an object from an earlier input (use --on-collision rename|replace)",
+                        path, input
+                    ));
+                }
+                Collision::Rename => {
+                    let new_name = (1..)
+                        .map(|n| format!("{}_{}", name, n))
+                        .find(|n| !out.children.iter().any(|c| &c.name == n) && obj.getChildByName(n).is_none())
+                        .expect("unbounded suffixes");
+                    let new_path = format!("{}/{}", out_path, new_name);
+                    debug!("merge: {} -> {}", path, new_path);
+                    self.renamed += 1;
+                    self.renames.push((child.getFullName().to_string(), new_path.clone()));
+                    index.push(out.children.len());
+                    let mut node = self.copy(&child, &new_path, index)?;
+                    node.name = new_name;
+                    out.children.push(node);
+                    index.pop();
+                }
+                Collision::Replace => {
+                    debug!("merge: {} replaced", path);
+                    self.replaced += 1;
+                    index.push(i);
+                    self.forget(&path, index);
+                    out.children[i] = self.copy(&child, &path, index)?;
+                    index.pop();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy `obj` and its descendants to `out_path`. Instances get their
+    /// source once the whole input is placed (see `finish_input`).
+    fn copy(&mut self, obj: &IObject, out_path: &str, index: &mut Vec<usize>) -> Result<OObject, String> {
+        self.objects += 1;
+        if let Some(source) = obj.instance_source_path() {
+            self.pending.push((index.clone(), source.to_string()));
+            return Ok(OObject::new(obj.getName()));
+        }
+        if let Some(id) = identity(obj) {
+            self.identities.insert(out_path.to_string(), id);
+        }
+        let mut out = OObject::new(obj.getName());
+        out.meta_data = obj.getMetaData().clone();
+        super::copy_properties_from(&obj.getProperties(), &mut out.properties, &self.ts_map);
+        for (i, child) in obj.getChildren().enumerate() {
+            index.push(i);
+            let path = format!("{}/{}", out_path, child.getName());
+            out.add_child(self.copy(&child, &path, index)?);
+            index.pop();
+        }
+        Ok(out)
+    }
+
+    /// Forget what was written at `path` (child indices `index`) before it is replaced.
+    fn forget(&mut self, path: &str, index: &[usize]) {
+        let prefix = format!("{}/", path);
+        self.identities.retain(|p, _| p != path && !p.starts_with(&prefix));
+        self.instances.retain(|(i, _)| !i.starts_with(index));
+    }
+
+    /// Write the instances of the current input, following its renames.
+    fn finish_input(&mut self, root: &mut OObject) {
+        for (index, source) in std::mem::take(&mut self.pending) {
+            // Longest renamed prefix wins
+            let source = self.renames
+                .iter()
+                .filter(|(from, _)| source == *from || source.starts_with(&format!("{}/", from)))
+                .max_by_key(|(from, _)| from.len())
+                .map_or(source.clone(), |(from, to)| format!("{}{}", to, &source[from.len()..]));
+            let node = node_at(root, &index);
+            let mut holder = OObject::new("");
+            holder.add_instance_named(&node.name, &source);
+            *node = holder.children.remove(0);
+            self.instances.push((index, source));
+        }
+        self.renames.clear();
+    }
+
+    /// Remove instances whose source is no longer in the output. Returns how many.
+    fn drop_dangling_instances(&mut self, root: &mut OObject) -> usize {
+        let mut paths = HashSet::new();
+        collect_paths(root, "", &mut paths);
+        let mut dangling: Vec<_> = self.instances.iter().filter(|(_, source)| !paths.contains(source)).collect();
+        // Deepest/last first, so removals don't shift the indices still to visit
+        dangling.sort_by(|a, b| b.0.cmp(&a.0));
+        for (index, source) in &dangling {
+            warn!("merge: dropping instance of {} (source replaced)", source);
+            let (last, parent) = index.split_last().expect("instances are below the root");
+            node_at(root, parent).children.remove(*last);
+        }
+        dangling.len()
+    }
+}
+
+fn node_at<'a>(root: &'a mut OObject, index: &[usize]) -> &'a mut OObject {
+    index.iter().fold(root, |node, &i| &mut node.children[i])
+}
+
+/// Paths of every non-instance object below `obj`.
+fn collect_paths(obj: &OObject, path: &str, out: &mut HashSet<String>) {
+    for child in &obj.children {
+        if !child.is_instance() {
+            let child_path = format!("{}/{}", path, child.name);
+            collect_paths(child, &child_path, out);
+            out.insert(child_path);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! `alembic pack` / `unpack` - compact compressed caches for farm storage.
+//!
+//! Thin CLI wrapper around [`alembic::pack`]: `pack` rewrites an archive
+//! with LZ4- or zlib-compressed sample blocks (readable only by this
+//! library with the `compress` feature), `unpack` turns it back into a
+//! standard archive for DCCs and stock Alembic. Both verify the output
+//! against the input's content fingerprint.
+
+use alembic::abc::IArchive as AbcIArchive;
+use alembic::ogawa::BlockCodec;
+use alembic::pack::{pack_archive, unpack_archive, PackStats};
+use tracing::info;
+
+/// Parse the flags following `pack <in> <out>`.
+pub fn parse_args(args: &[&str]) -> Result<BlockCodec, String> {
+    let mut codec = BlockCodec::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--codec" => {
+                let v = iter.next().ok_or("--codec requires lz4 or zlib")?;
+                codec = BlockCodec::parse(v).ok_or_else(|| format!("unknown codec: {} (expected lz4 or zlib)", v))?;
+            }
+            _ => return Err(format!("unknown pack option: {}", arg)),
+        }
+    }
+    Ok(codec)
+}
+
+/// `pack <in.abc> <out.abc> [--codec lz4|zlib]`
+pub fn pack(input: &str, output: &str, codec: BlockCodec) -> Result<(), String> {
+    info!("Pack {} -> {} ({})", input, output, codec.name());
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let stats = pack_archive(&archive, output, codec).map_err(|e| format!("failed to pack {}: {}", input, e))?;
+    println!("Packed {} -> {} ({})", input, output, codec.name());
+    print_sizes(&stats);
+    Ok(())
+}
+
+/// `unpack <in.abc> <out.abc>`
+pub fn unpack(input: &str, output: &str) -> Result<(), String> {
+    info!("Unpack {} -> {}", input, output);
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let stats = unpack_archive(&archive, output).map_err(|e| format!("failed to unpack {}: {}", input, e))?;
+    println!("Unpacked {} -> {}", input, output);
+    print_sizes(&stats);
+    Ok(())
+}
+
+fn print_sizes(stats: &PackStats) {
+    let percent = if stats.input_bytes > 0 { stats.output_bytes as f64 * 100.0 / stats.input_bytes as f64 } else { 0.0 };
+    println!("  Samples: {}", stats.samples);
+    println!("  Input:   {} bytes", stats.input_bytes);
+    println!("  Output:  {} bytes ({:.1}%)", stats.output_bytes, percent);
+    println!("  Verified against the input fingerprint");
+}
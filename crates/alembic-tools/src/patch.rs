@@ -0,0 +1,46 @@
+//! `alembic make-patch` / `apply-patch` - ship cache updates as binary patches.
+//!
+//! Thin CLI wrapper around [`alembic::patch`]: `make-patch` records the
+//! samples added or changed between two versions of an archive (unchanged
+//! data is referenced, not copied), `apply-patch` rebuilds the new version
+//! from the old one at the receiving site and verifies the result.
+
+use alembic::abc::IArchive as AbcIArchive;
+use alembic::patch::{apply_patch, make_patch, ArchivePatch};
+use tracing::info;
+
+/// `make-patch <base.abc> <target.abc> <out.abcpatch>`
+pub fn make(base: &str, target: &str, output: &str) -> Result<(), String> {
+    info!("Make patch {} -> {} into {}", base, target, output);
+    let base_archive = AbcIArchive::open(base).map_err(|e| format!("failed to open {}: {}", base, e))?;
+    let target_archive = AbcIArchive::open(target).map_err(|e| format!("failed to open {}: {}", target, e))?;
+    let patch = make_patch(&base_archive, &target_archive).map_err(|e| format!("failed to diff archives: {}", e))?;
+    patch.write(output).map_err(|e| format!("failed to write {}: {}", output, e))?;
+
+    let patch_size = std::fs::metadata(output).map(|m| m.len()).unwrap_or(0);
+    let target_size = std::fs::metadata(target).map(|m| m.len()).unwrap_or(0);
+    let percent = if target_size > 0 { patch_size as f64 * 100.0 / target_size as f64 } else { 0.0 };
+    let s = &patch.stats;
+    println!("Patch {} -> {}", base, target);
+    println!("  Unchanged:   {} samples", s.unchanged);
+    println!("  Changed:     {} samples", s.changed);
+    println!("  Added:       {} samples", s.added);
+    println!("  Removed:     {} samples", s.removed);
+    println!("  From base:   {} samples", s.reused);
+    println!("  Inline:      {} samples, {} bytes", s.inline_samples, s.inline_bytes);
+    println!("  Patch size:  {} bytes ({:.1}% of {})", patch_size, percent, target);
+    Ok(())
+}
+
+/// `apply-patch <base.abc> <patch.abcpatch> <out.abc>`
+pub fn apply(base: &str, patch_path: &str, output: &str) -> Result<(), String> {
+    info!("Apply patch {} to {} into {}", patch_path, base, output);
+    let patch = ArchivePatch::read(patch_path).map_err(|e| format!("failed to read {}: {}", patch_path, e))?;
+    let base_archive = AbcIArchive::open(base).map_err(|e| format!("failed to open {}: {}", base, e))?;
+    apply_patch(&base_archive, &patch, output).map_err(|e| format!("failed to apply {}: {}", patch_path, e))?;
+    let s = &patch.stats;
+    println!("Patched {} -> {}", base, output);
+    println!("  Changed: {}  Added: {}  Removed: {} samples", s.changed, s.added, s.removed);
+    println!("  Verified against the target fingerprint");
+    Ok(())
+}
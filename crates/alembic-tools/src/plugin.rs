@@ -0,0 +1,255 @@
+//! Schema plugins: studio schemas summarized by shared libraries.
+//!
+//! `tree`, `stats` and `meta` print one line per object. For schemas the CLI
+//! does not know, a plugin loaded with `--plugin <lib>` (or listed in
+//! `ALEMBIC_CLI_PLUGINS`) can supply that line. Plugins implement the C ABI
+//! in `include/alembic_cli_plugin.h`: they export
+//! `alembic_cli_schema_plugin()`, returning the schemas they handle and a
+//! `summarize` callback that reads properties back through the host.
+//!
+//! Loading needs the `plugins` feature; without it, requested plugins are
+//! reported and ignored.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use alembic::prelude::IObject;
+
+/// Environment variable with a PATH-style list of plugin libraries.
+pub const PLUGINS_ENV: &str = "ALEMBIC_CLI_PLUGINS";
+
+/// Version of `include/alembic_cli_plugin.h` this build implements.
+#[cfg_attr(not(feature = "plugins"), allow(dead_code))]
+pub const ABI_VERSION: u32 = 1;
+
+static PLUGINS: OnceLock<Vec<imp::SchemaPlugin>> = OnceLock::new();
+
+/// Load the plugins given on the command line and in `ALEMBIC_CLI_PLUGINS`.
+///
+/// Libraries that fail to load are reported on stderr and skipped.
+pub fn load(paths: &[PathBuf]) {
+    let mut all = paths.to_vec();
+    if let Some(list) = std::env::var_os(PLUGINS_ENV) {
+        all.extend(std::env::split_paths(&list).filter(|p| !p.as_os_str().is_empty()));
+    }
+    let plugins = all
+        .iter()
+        .filter_map(|path| match imp::SchemaPlugin::load(path) {
+            Ok(plugin) => Some(plugin),
+            Err(e) => {
+                eprintln!("Warning: plugin {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect();
+    let _ = PLUGINS.set(plugins);
+}
+
+/// One-line summary of `obj` from the first plugin handling its schema.
+pub fn summary(obj: &IObject) -> Option<String> {
+    let schema = obj.getMetaData().get("schema")?;
+    PLUGINS.get()?.iter().find(|p| p.handles(schema))?.summarize(obj)
+}
+
+#[cfg(feature = "plugins")]
+mod imp {
+    use std::ffi::{c_char, c_void, CStr, CString};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::path::Path;
+
+    use alembic::abc::{ICompoundProperty, IProperty};
+    use alembic::prelude::IObject;
+    use alembic::util::PlainOldDataType;
+    use tracing::{debug, info};
+
+    /// `AbcPluginObject` in the header.
+    #[repr(C)]
+    struct PluginObject {
+        path: *const c_char,
+        name: *const c_char,
+        schema: *const c_char,
+        metadata: *const c_char,
+        num_children: u64,
+        handle: *const c_void,
+        num_samples: extern "C" fn(*const c_void, *const c_char) -> i64,
+        read_sample: extern "C" fn(*const c_void, *const c_char, u64, *mut c_void, usize) -> i64,
+    }
+
+    /// `AbcSchemaPlugin` in the header.
+    #[repr(C)]
+    struct PluginDesc {
+        abi_version: u32,
+        name: *const c_char,
+        schemas: *const *const c_char,
+        summarize: Option<extern "C" fn(*const PluginObject, *mut c_char, usize) -> i32>,
+    }
+
+    /// Longest summary a plugin can write, terminator included.
+    const SUMMARY_LEN: usize = 1024;
+
+    pub struct SchemaPlugin {
+        name: String,
+        schemas: Vec<String>,
+        summarize: extern "C" fn(*const PluginObject, *mut c_char, usize) -> i32,
+        // Keeps the code behind `summarize` mapped
+        _library: libloading::Library,
+    }
+
+    impl SchemaPlugin {
+        pub fn load(path: &Path) -> Result<Self, String> {
+            // SAFETY: loading runs the library's initializers; plugins are
+            // trusted code the user asked for.
+            let library = unsafe { libloading::Library::new(path) }.map_err(|e| e.to_string())?;
+            // SAFETY: the symbol has the signature declared in the header.
+            let entry = unsafe { library.get::<extern "C" fn() -> *const PluginDesc>(b"alembic_cli_schema_plugin\0") }
+                .map_err(|e| e.to_string())?;
+            // SAFETY: the plugin returns a descriptor valid while it is loaded (or null)
+            let desc = unsafe { entry().as_ref() }.ok_or("alembic_cli_schema_plugin() returned null")?;
+            if desc.abi_version != super::ABI_VERSION {
+                return Err(format!("plugin ABI version {}, expected {}", desc.abi_version, super::ABI_VERSION));
+            }
+            let summarize = desc.summarize.ok_or("no summarize callback")?;
+            let name = unsafe { c_string(desc.name) }.unwrap_or_else(|| path.display().to_string());
+            let mut schemas = Vec::new();
+            let mut cursor = desc.schemas;
+            // SAFETY: a NULL-terminated array of C strings, per the header
+            while let Some(schema) = (!cursor.is_null()).then(|| unsafe { *cursor }).filter(|s| !s.is_null()) {
+                schemas.extend(unsafe { c_string(schema) });
+                cursor = unsafe { cursor.add(1) };
+            }
+            info!("Loaded schema plugin {} ({}): {}", name, path.display(), schemas.join(", "));
+            Ok(Self { name, schemas, summarize, _library: library })
+        }
+
+        pub fn handles(&self, schema: &str) -> bool {
+            self.schemas.iter().any(|s| s == schema)
+        }
+
+        pub fn summarize(&self, obj: &IObject) -> Option<String> {
+            let cstr = |s: &str| CString::new(s.replace('\0', "")).unwrap_or_default();
+            let path = cstr(obj.getFullName());
+            let name = cstr(obj.getName());
+            let schema = cstr(obj.getMetaData().get("schema").unwrap_or_default());
+            let metadata = cstr(&obj.getMetaData().serialize());
+            let view = PluginObject {
+                path: path.as_ptr(),
+                name: name.as_ptr(),
+                schema: schema.as_ptr(),
+                metadata: metadata.as_ptr(),
+                num_children: obj.getNumChildren() as u64,
+                handle: obj as *const IObject as *const c_void,
+                num_samples: host_num_samples,
+                read_sample: host_read_sample,
+            };
+            let mut out = vec![0u8; SUMMARY_LEN];
+            let status = (self.summarize)(&view, out.as_mut_ptr() as *mut c_char, out.len());
+            if status != 0 {
+                debug!("plugin {}: no summary for {} ({})", self.name, obj.getFullName(), status);
+                return None;
+            }
+            // Force termination in case the plugin filled the whole buffer
+            *out.last_mut()? = 0;
+            let text = CStr::from_bytes_until_nul(&out).ok()?.to_string_lossy();
+            Some(text.lines().next().unwrap_or_default().to_string())
+        }
+    }
+
+    /// SAFETY: `ptr` is null or a NUL-terminated string.
+    unsafe fn c_string(ptr: *const c_char) -> Option<String> {
+        (!ptr.is_null()).then(|| unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+    }
+
+    /// Call `f` with the scalar/array property at `parts` below `props`.
+    fn with_property<R>(props: &ICompoundProperty<'_>, parts: &[&str], f: &mut dyn FnMut(&IProperty<'_>) -> Option<R>) -> Option<R> {
+        let (first, rest) = parts.split_first()?;
+        let prop = props.getPropertyByName(first)?;
+        if rest.is_empty() {
+            return f(&prop);
+        }
+        with_property(&prop.asCompound()?, rest, f)
+    }
+
+    /// Resolve the host callback arguments and run `f` on the property,
+    /// returning -1 for anything missing and on panics.
+    fn with_host_property(
+        handle: *const c_void,
+        prop_path: *const c_char,
+        mut f: impl FnMut(&IProperty<'_>) -> Option<i64>,
+    ) -> i64 {
+        if handle.is_null() || prop_path.is_null() {
+            return -1;
+        }
+        catch_unwind(AssertUnwindSafe(|| {
+            // SAFETY: `handle` is the IObject passed to `summarize`, alive for the call
+            let obj = unsafe { &*(handle as *const IObject) };
+            let path = unsafe { CStr::from_ptr(prop_path) }.to_str().ok()?;
+            let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+            with_property(&obj.getProperties(), &parts, &mut f)
+        }))
+        .ok()
+        .flatten()
+        .unwrap_or(-1)
+    }
+
+    extern "C" fn host_num_samples(handle: *const c_void, prop_path: *const c_char) -> i64 {
+        with_host_property(handle, prop_path, |prop| {
+            if let Some(scalar) = prop.asScalar() {
+                Some(scalar.getNumSamples() as i64)
+            } else {
+                prop.asArray().map(|array| array.getNumSamples() as i64)
+            }
+        })
+    }
+
+    extern "C" fn host_read_sample(handle: *const c_void, prop_path: *const c_char, index: u64, buf: *mut c_void, buf_len: usize) -> i64 {
+        with_host_property(handle, prop_path, |prop| {
+            let index = index as usize;
+            let bytes = if let Some(scalar) = prop.asScalar() {
+                let data_type = prop.getHeader().data_type;
+                if matches!(data_type.pod, PlainOldDataType::String | PlainOldDataType::Wstring) {
+                    let mut bytes = scalar.getSampleVec(index).ok()?;
+                    // Strings are stored NUL-terminated
+                    while bytes.last() == Some(&0) {
+                        bytes.pop();
+                    }
+                    bytes
+                } else {
+                    let mut bytes = vec![0u8; data_type.num_bytes()];
+                    scalar.getSample(index, &mut bytes).ok()?;
+                    bytes
+                }
+            } else {
+                prop.asArray()?.getSampleVec(index).ok()?
+            };
+            if !buf.is_null() && bytes.len() <= buf_len {
+                // SAFETY: the plugin passed a buffer of `buf_len` bytes
+                unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf as *mut u8, bytes.len()) };
+            }
+            Some(bytes.len() as i64)
+        })
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+mod imp {
+    use std::path::Path;
+
+    use alembic::prelude::IObject;
+
+    /// Stand-in when built without `plugins`: nothing ever loads.
+    pub enum SchemaPlugin {}
+
+    impl SchemaPlugin {
+        pub fn load(_path: &Path) -> Result<Self, String> {
+            Err("alembic-cli was built without the `plugins` feature".to_string())
+        }
+
+        pub fn handles(&self, _schema: &str) -> bool {
+            match *self {}
+        }
+
+        pub fn summarize(&self, _obj: &IObject) -> Option<String> {
+            match *self {}
+        }
+    }
+}
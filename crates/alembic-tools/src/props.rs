@@ -0,0 +1,252 @@
+//! `alembic props <file.abc> [--type array] [--min-samples 2] [--dtype float32x3]` - list properties.
+//!
+//! Property-centric view of an archive for data audits: every property
+//! matching the filters, with its sample count, element count and the
+//! bytes its samples hold (before deduplication). Complements the
+//! object-centric `tree` and `meta` commands.
+
+use alembic::abc::{IArchive as AbcIArchive, ICompoundProperty, IObject};
+use alembic::util::{DataType, PlainOldDataType};
+use serde_json::json;
+
+/// Property kind filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropKind {
+    Scalar,
+    Array,
+    Compound,
+}
+
+impl PropKind {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "scalar" => Some(Self::Scalar),
+            "array" => Some(Self::Array),
+            "compound" => Some(Self::Compound),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Scalar => "scalar",
+            Self::Array => "array",
+            Self::Compound => "compound",
+        }
+    }
+}
+
+/// Sort order of the listing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    /// Hierarchy order.
+    #[default]
+    Path,
+    /// Largest first.
+    Bytes,
+    /// Most samples first.
+    Samples,
+}
+
+/// Filters and output options for `props`.
+#[derive(Debug, Clone, Default)]
+pub struct PropsOptions {
+    pub kind: Option<PropKind>,
+    pub min_samples: usize,
+    pub dtype: Option<DataType>,
+    /// Substring of the property name or path.
+    pub name: Option<String>,
+    pub sort: SortBy,
+    pub json: bool,
+}
+
+/// Parse the flags following `props <file>`.
+pub fn parse_args(args: &[&str]) -> Result<PropsOptions, String> {
+    let mut opts = PropsOptions::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--type" | "-t" => {
+                let v = iter.next().ok_or("--type requires scalar|array|compound")?;
+                opts.kind = Some(PropKind::parse(v).ok_or_else(|| format!("unknown property type: {}", v))?);
+            }
+            "--min-samples" => {
+                let v = iter.next().ok_or("--min-samples requires a number")?;
+                opts.min_samples = v.parse().map_err(|_| format!("invalid --min-samples: {}", v))?;
+            }
+            "--dtype" => {
+                let v = iter.next().ok_or("--dtype requires a data type, e.g. float32x3")?;
+                opts.dtype = Some(parse_dtype(v).ok_or_else(|| format!("unknown data type: {} (expected e.g. float32, int32x2)", v))?);
+            }
+            "--name" => opts.name = Some(iter.next().ok_or("--name requires a substring")?.to_string()),
+            "--sort" => {
+                opts.sort = match *iter.next().ok_or("--sort requires path|bytes|samples")? {
+                    "path" => SortBy::Path,
+                    "bytes" => SortBy::Bytes,
+                    "samples" => SortBy::Samples,
+                    other => return Err(format!("unknown sort order: {}", other)),
+                };
+            }
+            "--json" => opts.json = true,
+            _ => return Err(format!("unknown props option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+/// Parse `float32x3`, `int32`, `float64_t`, `bool` ...
+fn parse_dtype(s: &str) -> Option<DataType> {
+    let (pod, extent) = match s.rsplit_once('x') {
+        Some((pod, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => (pod, n.parse().ok()?),
+        _ => (s, 1),
+    };
+    let pod = match pod {
+        "string" | "wstring" => PlainOldDataType::from_name(pod),
+        _ => PlainOldDataType::from_name(&format!("{}_t", pod.trim_end_matches("_t"))),
+    };
+    (pod != PlainOldDataType::Unknown && extent > 0).then_some(DataType::new(pod, extent))
+}
+
+/// `float32x3` style name, the inverse of [`parse_dtype`].
+fn dtype_name(dt: DataType) -> String {
+    let pod = dt.pod.name().trim_end_matches("_t");
+    if dt.extent == 1 {
+        pod.to_string()
+    } else {
+        format!("{}x{}", pod, dt.extent)
+    }
+}
+
+/// One listed property.
+struct PropRow {
+    object: String,
+    path: String,
+    kind: PropKind,
+    dtype: Option<DataType>,
+    samples: usize,
+    elements: usize,
+    bytes: usize,
+}
+
+/// List the properties of `input` matching `opts`.
+pub fn run(input: &str, opts: &PropsOptions) -> Result<(), String> {
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let mut rows = Vec::new();
+    collect_object(&archive.getTop(), opts, &mut rows);
+    match opts.sort {
+        SortBy::Path => {}
+        SortBy::Bytes => rows.sort_by_key(|r| std::cmp::Reverse(r.bytes)),
+        SortBy::Samples => rows.sort_by_key(|r| std::cmp::Reverse(r.samples)),
+    }
+
+    if opts.json {
+        let list: Vec<_> = rows.iter().map(|r| json!({
+            "object": r.object,
+            "property": r.path,
+            "type": r.kind.as_str(),
+            "dtype": r.dtype.map(dtype_name),
+            "samples": r.samples,
+            "elements": r.elements,
+            "bytes": r.bytes,
+        })).collect();
+        println!("{}", serde_json::to_string_pretty(&list).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+
+    println!("{:>8} {:>10} {:>12}  {:<8} {:<10} PROPERTY", "SAMPLES", "ELEMENTS", "BYTES", "TYPE", "DTYPE");
+    for r in &rows {
+        println!(
+            "{:>8} {:>10} {:>12}  {:<8} {:<10} {} {}",
+            r.samples, r.elements, r.bytes, r.kind.as_str(),
+            r.dtype.map(dtype_name).unwrap_or_else(|| "-".into()), r.object, r.path
+        );
+    }
+    let samples: usize = rows.iter().map(|r| r.samples).sum();
+    let bytes: usize = rows.iter().map(|r| r.bytes).sum();
+    println!("{} properties, {} samples, {} bytes", rows.len(), samples, bytes);
+    Ok(())
+}
+
+fn collect_object(obj: &IObject, opts: &PropsOptions, rows: &mut Vec<PropRow>) {
+    collect_compound(obj.getFullName(), "", &obj.getProperties(), opts, rows);
+    for child in obj.getChildren() {
+        collect_object(&child, opts, rows);
+    }
+}
+
+fn collect_compound(object: &str, prefix: &str, props: &ICompoundProperty, opts: &PropsOptions, rows: &mut Vec<PropRow>) {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        let header = prop.getHeader();
+        let path = format!("{}{}", prefix, header.name);
+        if let Some(compound) = prop.asCompound() {
+            let row = PropRow {
+                object: object.to_string(), path: path.clone(), kind: PropKind::Compound, dtype: None,
+                samples: 0, elements: 0, bytes: 0,
+            };
+            if matches(&row, opts) {
+                rows.push(row);
+            }
+            collect_compound(object, &format!("{}/", path), &compound, opts, rows);
+            continue;
+        }
+        let row = if let Some(array) = prop.asArray() {
+            let dt = header.data_type;
+            let samples = array.getNumSamples();
+            let mut elements = 0;
+            let mut bytes = 0;
+            for s in 0..samples {
+                let n: usize = array.getDimensions(s).map(|d| d.iter().product()).unwrap_or(0);
+                elements += n;
+                bytes += if is_string(dt) {
+                    array.getSampleVec(s).map_or(0, |v| v.len())
+                } else {
+                    n * dt.num_bytes()
+                };
+            }
+            PropRow { object: object.to_string(), path, kind: PropKind::Array, dtype: Some(dt), samples, elements, bytes }
+        } else if let Some(scalar) = prop.asScalar() {
+            let dt = header.data_type;
+            let samples = scalar.getNumSamples();
+            let bytes = if is_string(dt) {
+                (0..samples).map(|s| scalar.getSampleVec(s).map_or(0, |v| v.len() + 1)).sum()
+            } else {
+                samples * dt.num_bytes()
+            };
+            PropRow { object: object.to_string(), path, kind: PropKind::Scalar, dtype: Some(dt), samples, elements: samples, bytes }
+        } else {
+            continue;
+        };
+        if matches(&row, opts) {
+            rows.push(row);
+        }
+    }
+}
+
+fn is_string(dt: DataType) -> bool {
+    matches!(dt.pod, PlainOldDataType::String | PlainOldDataType::Wstring)
+}
+
+fn matches(row: &PropRow, opts: &PropsOptions) -> bool {
+    opts.kind.is_none_or(|k| k == row.kind)
+        && row.samples >= opts.min_samples
+        && opts.dtype.is_none_or(|d| row.dtype == Some(d))
+        && opts.name.as_deref().is_none_or(|n| row.path.contains(n) || row.object.contains(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dtype() {
+        assert_eq!(parse_dtype("float32x3"), Some(DataType::VEC3F));
+        assert_eq!(parse_dtype("int32"), Some(DataType::INT32));
+        assert_eq!(parse_dtype("float64_t"), Some(DataType::FLOAT64));
+        assert_eq!(parse_dtype("bool"), Some(DataType::BOOL));
+        assert_eq!(parse_dtype("string"), Some(DataType::STRING));
+        assert_eq!(parse_dtype("vec3"), None);
+        assert_eq!(dtype_name(DataType::VEC3F), "float32x3");
+        assert_eq!(dtype_name(DataType::BOOL), "bool");
+    }
+}
@@ -0,0 +1,56 @@
+//! `alembic render` - headless path-traced frames for cache QC.
+//!
+//! Thin CLI wrapper around [`alembic_viewer::batch`]: renders a frame range
+//! with the viewer's path tracer, several frames in flight, and writes one
+//! EXR per frame with the frame, camera and spp in the header.
+
+use alembic_viewer::batch::{render_batch, BatchOptions};
+use tracing::info;
+
+/// Parse `render <file.abc> [flags]`.
+pub fn parse_args(args: &[&str]) -> Result<BatchOptions, String> {
+    let (&input, rest) = args.split_first().ok_or("missing file argument")?;
+    let mut opts = BatchOptions::new(input, "render.####.exr");
+    let mut iter = rest.iter();
+    while let Some(&arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().copied()
+            .ok_or_else(|| format!("{} requires a value", flag));
+        match arg {
+            "-o" | "--output" => opts.output = value(arg)?.to_string(),
+            "--frames" | "-f" => {
+                let v = value(arg)?;
+                let range = super::parse_frame_range(v).ok_or_else(|| format!("invalid frame range: {} (expected a-b)", v))?;
+                opts.frames = Some((*range.start() as f64, *range.end() as f64));
+            }
+            "--step" => opts.step = parse_number(arg, value(arg)?)?,
+            "--spp" => opts.spp = parse_number(arg, value(arg)?)?,
+            "--bounces" => opts.max_bounces = parse_number(arg, value(arg)?)?,
+            "--size" => {
+                let v = value(arg)?;
+                let (w, h) = v.split_once('x').ok_or_else(|| format!("invalid size: {} (expected WxH)", v))?;
+                opts.width = parse_number(arg, w)?;
+                opts.height = parse_number(arg, h)?;
+            }
+            "--camera" | "--cam" => opts.camera = Some(value(arg)?.to_string()),
+            "--jobs" | "-j" => opts.frames_in_flight = parse_number(arg, value(arg)?)?,
+            "--vram-mb" => opts.vram_budget = parse_number::<u64>(arg, value(arg)?)? << 20,
+            _ => return Err(format!("unknown render option: {}", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, v: &str) -> Result<T, String> {
+    v.parse().map_err(|_| format!("invalid value for {}: {}", flag, v))
+}
+
+/// `render <file.abc> -o out.####.exr [--frames a-b] ...`
+pub fn render(opts: &BatchOptions) -> Result<(), String> {
+    info!("Render {} -> {}", opts.input.display(), opts.output);
+    let frames = render_batch(opts, |f| {
+        println!("  frame {:>6} -> {} ({} tris, {}, {:.1}s)", f.frame, f.path.display(), f.triangles, f.camera, f.seconds);
+    })
+    .map_err(|e| format!("render failed: {:#}", e))?;
+    println!("Rendered {} frame(s) at {}x{}, {} spp", frames.len(), opts.width, opts.height, opts.spp);
+    Ok(())
+}
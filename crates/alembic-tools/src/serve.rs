@@ -0,0 +1,963 @@
+//! `alembic serve` - read-only HTTP service for archive queries.
+//!
+//! Lets pipeline services query archives over HTTP instead of spawning the
+//! CLI per request. Archives stay open between requests (reopened when the
+//! file changes on disk). Requests are handled by a fixed worker pool with a
+//! bounded backlog; connections beyond the backlog get `503`.
+//!
+//! Endpoints (GET/HEAD):
+//!
+//! | Path | Response |
+//! |------|----------|
+//! | `/health` | `ok` |
+//! | `/archives` | JSON list of served archive names |
+//! | `/manifest/<archive>` | JSON archive info, time samplings, flat object list |
+//! | `/object/<archive>/<object path>` | JSON object info (metadata, samples, bounds) |
+//! | `/sample/<archive>/<object path>?attr=P&index=0` | Raw little-endian blob |
+//! | `/thumbnail/<archive>[/<object path>]?size=256&index=0` | PNG wireframe |
+//!
+//! Sample blobs carry `X-Alembic-Type` (e.g. `f32x3`) and `X-Alembic-Count`
+//! headers describing the element layout.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use alembic::abc::IArchive;
+use alembic::core::TimeSamplingType;
+use alembic::prelude::{IObject, IPolyMesh, ISubD, ICurves, IPoints, ICamera, IXform, INuPatch, ILight};
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+/// Largest request head we accept (request line + headers).
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+/// Largest thumbnail edge in pixels.
+const MAX_THUMBNAIL_SIZE: u32 = 2048;
+
+/// Options for `alembic serve`.
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    /// Address to listen on.
+    pub bind: String,
+    /// Number of worker threads.
+    pub threads: usize,
+    /// Accepted connections waiting for a worker before new ones get 503.
+    pub max_pending: usize,
+    /// Archives (or directories of archives) to serve.
+    pub inputs: Vec<PathBuf>,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            bind: "127.0.0.1:8080".to_string(),
+            threads: 4,
+            max_pending: 64,
+            inputs: Vec::new(),
+        }
+    }
+}
+
+/// Parse `serve` arguments: inputs plus `--bind`, `--threads`, `--max-pending`.
+pub fn parse_args(args: &[&str]) -> Result<ServeOptions, String> {
+    let mut opts = ServeOptions::default();
+    let mut iter = args.iter();
+
+    while let Some(&arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().copied()
+            .ok_or_else(|| format!("{} requires a value", flag));
+        match arg {
+            "--bind" | "-b" => opts.bind = value(arg)?.to_string(),
+            "--port" | "-p" => {
+                let v = value(arg)?;
+                let port: u16 = v.parse().map_err(|_| format!("invalid port: {}", v))?;
+                opts.bind = format!("127.0.0.1:{}", port);
+            }
+            "--threads" | "-j" => {
+                let v = value(arg)?;
+                opts.threads = v.parse().ok().filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid thread count: {}", v))?;
+            }
+            "--max-pending" => {
+                let v = value(arg)?;
+                opts.max_pending = v.parse().map_err(|_| format!("invalid backlog: {}", v))?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown serve option: {}", arg)),
+            _ => opts.inputs.push(PathBuf::from(arg)),
+        }
+    }
+    if opts.inputs.is_empty() {
+        return Err("no archives or directories given".to_string());
+    }
+    Ok(opts)
+}
+
+/// Run the server until the process is killed.
+pub fn run(opts: ServeOptions) -> Result<(), String> {
+    let registry = Arc::new(Registry::scan(&opts.inputs)?);
+    if registry.paths.is_empty() {
+        return Err("no .abc files found".to_string());
+    }
+
+    let listener = TcpListener::bind(&opts.bind)
+        .map_err(|e| format!("failed to bind {}: {}", opts.bind, e))?;
+    let addr = listener.local_addr().map(|a| a.to_string()).unwrap_or_else(|_| opts.bind.clone());
+    println!("Serving {} archive(s) on http://{}", registry.paths.len(), addr);
+    info!("serve: {} workers, backlog {}", opts.threads, opts.max_pending);
+
+    let (tx, rx) = mpsc::sync_channel::<TcpStream>(opts.max_pending);
+    let rx = Arc::new(Mutex::new(rx));
+    for i in 0..opts.threads {
+        let rx = rx.clone();
+        let registry = registry.clone();
+        std::thread::Builder::new()
+            .name(format!("serve-worker-{}", i))
+            .spawn(move || loop {
+                let stream = match rx.lock().unwrap().recv() {
+                    Ok(s) => s,
+                    Err(_) => break,
+                };
+                // A bug in one request must not take the worker down with it
+                let handled = std::panic::catch_unwind(AssertUnwindSafe(|| handle_connection(stream, &registry)));
+                if handled.is_err() {
+                    warn!("serve-worker-{}: request handler panicked", i);
+                }
+            })
+            .map_err(|e| format!("failed to spawn worker: {}", e))?;
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                warn!("accept failed: {}", e);
+                continue;
+            }
+        };
+        match tx.try_send(stream) {
+            Ok(()) => {}
+            Err(TrySendError::Full(mut stream)) => {
+                let resp = Response::error(503, "server busy");
+                let _ = resp.write_to(&mut stream, false);
+            }
+            Err(TrySendError::Disconnected(_)) => break,
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Archive registry
+// ============================================================================
+
+/// Open archive plus the file mtime it was opened at.
+type CachedArchive = (Option<SystemTime>, Arc<IArchive>);
+
+/// Served archives by name, opened lazily and cached.
+struct Registry {
+    paths: HashMap<String, PathBuf>,
+    open: Mutex<HashMap<String, CachedArchive>>,
+}
+
+impl Registry {
+    /// Collect `.abc` files from the inputs (directories are searched recursively).
+    fn scan(inputs: &[PathBuf]) -> Result<Self, String> {
+        let mut paths = HashMap::new();
+        for input in inputs {
+            if input.is_dir() {
+                let mut files = Vec::new();
+                collect_abc_files(input, &mut files);
+                files.sort();
+                for file in files {
+                    Self::insert(&mut paths, file);
+                }
+            } else if input.is_file() {
+                Self::insert(&mut paths, input.clone());
+            } else {
+                return Err(format!("not found: {}", input.display()));
+            }
+        }
+        Ok(Self { paths, open: Mutex::new(HashMap::new()) })
+    }
+
+    fn insert(paths: &mut HashMap<String, PathBuf>, file: PathBuf) {
+        let name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if let Some(existing) = paths.get(&name) {
+            warn!("skipping {}: name '{}' already used by {}", file.display(), name, existing.display());
+            return;
+        }
+        paths.insert(name, file);
+    }
+
+    fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.paths.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Get an open archive, reopening it if the file was modified.
+    ///
+    /// Archives are opened without holding the cache lock, so a slow open
+    /// doesn't stall requests for other archives. If two requests race to
+    /// open the same file, the first one cached wins.
+    fn get(&self, name: &str) -> Result<Arc<IArchive>, HttpError> {
+        let path = self.paths.get(name)
+            .ok_or_else(|| HttpError::new(404, format!("unknown archive: {}", name)))?;
+        let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        if let Some(archive) = self.cached(name, mtime) {
+            return Ok(archive);
+        }
+        debug!("opening {}", path.display());
+        let archive = Arc::new(IArchive::open(path)
+            .map_err(|e| HttpError::new(500, format!("failed to open {}: {}", name, e)))?);
+
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        match open.get(name) {
+            Some((cached_mtime, cached)) if *cached_mtime == mtime => Ok(cached.clone()),
+            _ => {
+                open.insert(name.to_string(), (mtime, archive.clone()));
+                Ok(archive)
+            }
+        }
+    }
+
+    /// The cached archive for `name` if it was opened at `mtime`.
+    fn cached(&self, name: &str, mtime: Option<SystemTime>) -> Option<Arc<IArchive>> {
+        let open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        open.get(name).filter(|(cached_mtime, _)| *cached_mtime == mtime).map(|(_, archive)| archive.clone())
+    }
+}
+
+fn collect_abc_files(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_abc_files(&path, out);
+        } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("abc")) {
+            out.push(path);
+        }
+    }
+}
+
+// ============================================================================
+// HTTP plumbing
+// ============================================================================
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+}
+
+impl Response {
+    fn json(value: &Value) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/json",
+            headers: Vec::new(),
+            body: serde_json::to_vec_pretty(value).unwrap_or_default(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        let mut resp = Self::json(&json!({ "error": message }));
+        resp.status = status;
+        resp
+    }
+
+    fn write_to(&self, stream: &mut TcpStream, head_only: bool) -> std::io::Result<()> {
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status, reason(self.status), self.content_type, self.body.len()
+        );
+        for (k, v) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", k, v));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        if !head_only {
+            stream.write_all(&self.body)?;
+        }
+        stream.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, registry: &Registry) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(30)));
+
+    let (resp, head_only) = match read_request(&stream) {
+        Ok(req) => {
+            let head_only = req.method == "HEAD";
+            let resp = if req.method == "GET" || head_only {
+                route(&req, registry).unwrap_or_else(|e| Response::error(e.status, &e.message))
+            } else {
+                Response::error(405, "only GET and HEAD are supported")
+            };
+            debug!("{} {} -> {}", req.method, req.path, resp.status);
+            (resp, head_only)
+        }
+        Err(e) => (Response::error(e.status, &e.message), false),
+    };
+    if let Err(e) = resp.write_to(&mut stream, head_only) {
+        debug!("write failed: {}", e);
+    }
+}
+
+fn read_request(stream: impl Read) -> Result<Request, HttpError> {
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST_HEAD as u64));
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| HttpError::new(400, e.to_string()))?;
+
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(HttpError::new(400, "malformed request line"));
+    };
+
+    // Drain headers; we don't need any of them.
+    loop {
+        let mut header = String::new();
+        let n = reader.read_line(&mut header).map_err(|e| HttpError::new(400, e.to_string()))?;
+        if n == 0 {
+            return Err(HttpError::new(413, "request head too large or truncated"));
+        }
+        if header == "\r\n" || header == "\n" {
+            break;
+        }
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|kv| !kv.is_empty())
+        .map(|kv| {
+            let (k, v) = kv.split_once('=').unwrap_or((kv, ""));
+            (percent_decode(k, true), percent_decode(v, true))
+        })
+        .collect();
+
+    Ok(Request { method: method.to_string(), path: percent_decode(path, false), query })
+}
+
+/// Decode `%XX` escapes, and `+` as a space if `plus_as_space` (query
+/// strings only; in a path `+` is a literal plus).
+fn percent_decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                match (hex_digit(bytes[i + 1]), hex_digit(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => { out.push(hi << 4 | lo); i += 3; }
+                    _ => { out.push(b'%'); i += 1; }
+                }
+            }
+            b'+' if plus_as_space => { out.push(b' '); i += 1; }
+            b => { out.push(b); i += 1; }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn hex_digit(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+fn query_usize(req: &Request, key: &str, default: usize) -> Result<usize, HttpError> {
+    match req.query.get(key) {
+        Some(v) => v.parse().map_err(|_| HttpError::new(400, format!("invalid {}: {}", key, v))),
+        None => Ok(default),
+    }
+}
+
+// ============================================================================
+// Routes
+// ============================================================================
+
+fn route(req: &Request, registry: &Registry) -> Result<Response, HttpError> {
+    let trimmed = req.path.trim_matches('/');
+    let (endpoint, rest) = trimmed.split_once('/').unwrap_or((trimmed, ""));
+    // rest = "<archive>[/<object path>]"
+    let (archive_name, object_path) = match rest.split_once('/') {
+        Some((a, p)) => (a, format!("/{}", p)),
+        None => (rest, "/".to_string()),
+    };
+
+    match endpoint {
+        "health" => Ok(Response {
+            status: 200,
+            content_type: "text/plain",
+            headers: Vec::new(),
+            body: b"ok".to_vec(),
+        }),
+        "archives" => Ok(Response::json(&json!(registry.names()))),
+        "manifest" => {
+            let archive = registry.get(archive_name)?;
+            Ok(Response::json(&manifest_json(archive_name, &archive)))
+        }
+        "object" => {
+            let archive = registry.get(archive_name)?;
+            let obj = find_object(&archive, &object_path)?;
+            let index = query_usize(req, "index", 0)?;
+            Ok(Response::json(&object_json(&archive, &obj, index)))
+        }
+        "sample" => {
+            let archive = registry.get(archive_name)?;
+            let obj = find_object(&archive, &object_path)?;
+            let index = query_usize(req, "index", 0)?;
+            let attr = req.query.get("attr").map(String::as_str).unwrap_or("P");
+            sample_blob(&archive, &obj, attr, index)
+        }
+        "thumbnail" => {
+            let archive = registry.get(archive_name)?;
+            let obj = find_object(&archive, &object_path)?;
+            let index = query_usize(req, "index", 0)?;
+            let size = query_usize(req, "size", 256)?.clamp(16, MAX_THUMBNAIL_SIZE as usize) as u32;
+            let png = thumbnail::render(&archive, &obj, index, size);
+            Ok(Response { status: 200, content_type: "image/png", headers: Vec::new(), body: png })
+        }
+        "" => Ok(Response::json(&json!({
+            "endpoints": ["/health", "/archives", "/manifest/<archive>",
+                "/object/<archive>/<path>", "/sample/<archive>/<path>?attr=P&index=0",
+                "/thumbnail/<archive>[/<path>]?size=256&index=0"],
+        }))),
+        _ => Err(HttpError::new(404, format!("unknown endpoint: /{}", endpoint))),
+    }
+}
+
+fn find_object<'a>(archive: &'a IArchive, path: &str) -> Result<IObject<'a>, HttpError> {
+    if path == "/" {
+        return Ok(archive.getTop());
+    }
+    archive.findObject(path)
+        .ok_or_else(|| HttpError::new(404, format!("object not found: {}", path)))
+}
+
+/// Time sampling index and number of samples of an object's schema.
+fn object_sampling(obj: &IObject) -> Option<(u32, usize)> {
+    if let Some(m) = IPolyMesh::new(obj) { return Some((m.getTimeSamplingIndex(), m.getNumSamples())); }
+    if let Some(x) = IXform::new(obj) { return Some((x.getTimeSamplingIndex(), x.getNumSamples())); }
+    if let Some(s) = ISubD::new(obj) { return Some((s.getTimeSamplingIndex(), s.getNumSamples())); }
+    if let Some(c) = ICurves::new(obj) { return Some((c.getTimeSamplingIndex(), c.getNumSamples())); }
+    if let Some(p) = IPoints::new(obj) { return Some((p.getTimeSamplingIndex(), p.getNumSamples())); }
+    if let Some(c) = ICamera::new(obj) { return Some((c.getTimeSamplingIndex(), c.getNumSamples())); }
+    if let Some(l) = ILight::new(obj) { return Some((l.getTimeSamplingIndex(), l.getNumSamples())); }
+    if let Some(n) = INuPatch::new(obj) { return Some((n.getTimeSamplingIndex(), n.getNumSamples())); }
+    None
+}
+
+fn manifest_json(name: &str, archive: &IArchive) -> Value {
+    let time_samplings: Vec<Value> = (0..archive.getNumTimeSamplings())
+        .filter_map(|i| archive.getTimeSampling(i).map(|ts| (i, ts)))
+        .map(|(i, ts)| {
+            let kind = match &ts.sampling_type {
+                TimeSamplingType::Identity => "identity",
+                TimeSamplingType::Uniform { .. } => "uniform",
+                TimeSamplingType::Cyclic { .. } => "cyclic",
+                TimeSamplingType::Acyclic { .. } => "acyclic",
+            };
+            json!({
+                "index": i,
+                "type": kind,
+                "timePerCycle": ts.time_per_cycle(),
+                "storedTimes": ts.stored_times(),
+                "maxSamples": archive.getMaxNumSamplesForTimeSamplingIndex(i),
+            })
+        })
+        .collect();
+
+    let mut objects = Vec::new();
+    collect_manifest(&archive.getTop(), &mut objects);
+
+    json!({
+        "name": name,
+        "archiveVersion": archive.getArchiveVersion(),
+        "appName": archive.getAppName(),
+        "dateWritten": archive.getDateWritten(),
+        "description": archive.getUserDescription(),
+        "fps": archive.getDccFps(),
+        "timeSamplings": time_samplings,
+        "objects": objects,
+    })
+}
+
+fn collect_manifest(obj: &IObject, out: &mut Vec<Value>) {
+    for child in obj.getChildren() {
+        let (ts, samples) = object_sampling(&child).unwrap_or((0, 0));
+        out.push(json!({
+            "path": child.getFullName(),
+            "schema": child.getMetaData().get("schema").unwrap_or_default(),
+            "numSamples": samples,
+            "timeSamplingIndex": ts,
+        }));
+        collect_manifest(&child, out);
+    }
+}
+
+fn object_json(archive: &IArchive, obj: &IObject, index: usize) -> Value {
+    let (ts_index, num_samples) = object_sampling(obj).unwrap_or((0, 0));
+    let sample_times: Vec<f64> = archive.getTimeSampling(ts_index as usize)
+        .map(|ts| (0..num_samples).map(|i| ts.sample_time(i, num_samples)).collect())
+        .unwrap_or_default();
+
+    let props = obj.getProperties();
+    let geom_props: Vec<String> = props.getPropertyByName(".geom")
+        .and_then(|p| p.asCompound().map(|c| c.getPropertyNames()))
+        .unwrap_or_default();
+
+    let bounds = self_bounds(obj, index)
+        .or_else(|| alembic::geom::util::read_child_bounds(obj, index))
+        .map(|b| json!({ "min": b.min.to_array(), "max": b.max.to_array() }));
+
+    json!({
+        "path": obj.getFullName(),
+        "name": obj.getName(),
+        "schema": obj.getMetaData().get("schema").unwrap_or_default(),
+        "metadata": obj.getMetaData().get_all(),
+        "numSamples": num_samples,
+        "timeSamplingIndex": ts_index,
+        "sampleTimes": sample_times,
+        "properties": props.getPropertyNames(),
+        "geomProperties": geom_props,
+        "children": obj.getChildren().map(|c| c.getName().to_string()).collect::<Vec<_>>(),
+        "bounds": bounds,
+    })
+}
+
+fn self_bounds(obj: &IObject, index: usize) -> Option<alembic::util::BBox3d> {
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    alembic::geom::util::read_self_bounds(geom.as_reader(), index)
+}
+
+/// Binary blob with element type/count headers.
+fn blob(kind: &'static str, count: usize, body: Vec<u8>) -> Response {
+    Response {
+        status: 200,
+        content_type: "application/octet-stream",
+        headers: vec![("X-Alembic-Type", kind.to_string()), ("X-Alembic-Count", count.to_string())],
+        body,
+    }
+}
+
+fn vec3_blob(v: &[glam::Vec3]) -> Response {
+    blob("f32x3", v.len(), bytemuck::cast_slice(v).to_vec())
+}
+
+fn sample_blob(archive: &IArchive, obj: &IObject, attr: &str, index: usize) -> Result<Response, HttpError> {
+    let err = |e: alembic::util::Error| HttpError::new(500, e.to_string());
+    let missing = || HttpError::new(404, format!("attribute '{}' not available on {}", attr, obj.getFullName()));
+
+    if attr == "world" {
+        let m = thumbnail::world_matrix(archive, obj.getFullName(), index);
+        return Ok(blob("f32x16", 1, bytemuck::cast_slice(&m.to_cols_array()).to_vec()));
+    }
+
+    if let Some(mesh) = IPolyMesh::new(obj) {
+        let s = mesh.getSample(index).map_err(err)?;
+        return match attr {
+            "P" => Ok(vec3_blob(&s.positions)),
+            "faceCounts" => Ok(blob("i32", s.face_counts.len(), bytemuck::cast_slice(&s.face_counts).to_vec())),
+            "faceIndices" => Ok(blob("i32", s.face_indices.len(), bytemuck::cast_slice(&s.face_indices).to_vec())),
+            "N" => s.normals.as_deref().map(vec3_blob).ok_or_else(missing),
+            "v" => s.velocities.as_deref().map(vec3_blob).ok_or_else(missing),
+            "uv" => s.uvs.as_deref()
+                .map(|uv| blob("f32x2", uv.len(), bytemuck::cast_slice(uv).to_vec()))
+                .ok_or_else(missing),
+            _ => Err(missing()),
+        };
+    }
+    if let Some(subd) = ISubD::new(obj) {
+        let s = subd.getSample(index).map_err(err)?;
+        return match attr {
+            "P" => Ok(vec3_blob(&s.positions)),
+            "faceCounts" => Ok(blob("i32", s.face_counts.len(), bytemuck::cast_slice(&s.face_counts).to_vec())),
+            "faceIndices" => Ok(blob("i32", s.face_indices.len(), bytemuck::cast_slice(&s.face_indices).to_vec())),
+            _ => Err(missing()),
+        };
+    }
+    if let Some(curves) = ICurves::new(obj) {
+        let s = curves.getSample(index).map_err(err)?;
+        return match attr {
+            "P" => Ok(vec3_blob(&s.positions)),
+            "nVertices" => Ok(blob("i32", s.num_vertices.len(), bytemuck::cast_slice(&s.num_vertices).to_vec())),
+            "width" if !s.widths.is_empty() => Ok(blob("f32", s.widths.len(), bytemuck::cast_slice(&s.widths).to_vec())),
+            _ => Err(missing()),
+        };
+    }
+    if let Some(points) = IPoints::new(obj) {
+        let s = points.getSample(index).map_err(err)?;
+        return match attr {
+            "P" => Ok(vec3_blob(&s.positions)),
+            "id" => Ok(blob("u64", s.ids.len(), bytemuck::cast_slice(&s.ids).to_vec())),
+            "v" if !s.velocities.is_empty() => Ok(vec3_blob(&s.velocities)),
+            "width" if !s.widths.is_empty() => Ok(blob("f32", s.widths.len(), bytemuck::cast_slice(&s.widths).to_vec())),
+            _ => Err(missing()),
+        };
+    }
+    if let Some(xform) = IXform::new(obj) {
+        let s = xform.getSample(index).map_err(err)?;
+        return match attr {
+            "matrix" => Ok(blob("f32x16", 1, bytemuck::cast_slice(&s.matrix().to_cols_array()).to_vec())),
+            _ => Err(missing()),
+        };
+    }
+    Err(missing())
+}
+
+// ============================================================================
+// Thumbnails
+// ============================================================================
+
+/// CPU wireframe thumbnails encoded as PNG.
+mod thumbnail {
+    use super::*;
+    use glam::{Mat4, Vec3};
+
+    /// World matrix of the object at `path` (concatenated ancestor xforms).
+    pub fn world_matrix(archive: &IArchive, path: &str, index: usize) -> Mat4 {
+        let mut world = Mat4::IDENTITY;
+        let mut current = String::new();
+        for name in path.split('/').filter(|s| !s.is_empty()) {
+            current.push('/');
+            current.push_str(name);
+            let Some(obj) = archive.findObject(&current) else { break };
+            world = apply_xform(&obj, index, world);
+        }
+        world
+    }
+
+    fn apply_xform(obj: &IObject, index: usize, parent: Mat4) -> Mat4 {
+        let Some(xform) = IXform::new(obj) else { return parent };
+        let n = xform.getNumSamples();
+        if n == 0 {
+            return parent;
+        }
+        match xform.getSample(index.min(n - 1)) {
+            Ok(s) if s.inherits => parent * s.matrix(),
+            Ok(s) => s.matrix(),
+            Err(_) => parent,
+        }
+    }
+
+    /// World-space line segments (edges) and points under `obj`.
+    #[derive(Default)]
+    struct Wire {
+        lines: Vec<(Vec3, Vec3)>,
+        points: Vec<Vec3>,
+    }
+
+    fn push_faces(wire: &mut Wire, world: Mat4, positions: &[Vec3], counts: &[i32], indices: &[i32]) {
+        let p = |i: i32| positions.get(i as usize).map(|&v| world.transform_point3(v));
+        let mut start = 0usize;
+        for &count in counts {
+            let count = count.max(0) as usize;
+            let Some(face) = indices.get(start..start + count) else { break };
+            for k in 0..count {
+                if let (Some(a), Some(b)) = (p(face[k]), p(face[(k + 1) % count])) {
+                    wire.lines.push((a, b));
+                }
+            }
+            start += count;
+        }
+    }
+
+    fn collect(obj: &IObject, index: usize, parent: Mat4, wire: &mut Wire) {
+        let world = apply_xform(obj, index, parent);
+        let clamp = |n: usize| index.min(n.saturating_sub(1));
+
+        if let Some(mesh) = IPolyMesh::new(obj) {
+            if let Ok(s) = mesh.getSample(clamp(mesh.getNumSamples())) {
+                push_faces(wire, world, &s.positions, &s.face_counts, &s.face_indices);
+            }
+        } else if let Some(subd) = ISubD::new(obj) {
+            if let Ok(s) = subd.getSample(clamp(subd.getNumSamples())) {
+                push_faces(wire, world, &s.positions, &s.face_counts, &s.face_indices);
+            }
+        } else if let Some(curves) = ICurves::new(obj) {
+            if let Ok(s) = curves.getSample(clamp(curves.getNumSamples())) {
+                let mut start = 0usize;
+                for &n in &s.num_vertices {
+                    let n = n.max(0) as usize;
+                    let Some(cv) = s.positions.get(start..start + n) else { break };
+                    for pair in cv.windows(2) {
+                        wire.lines.push((world.transform_point3(pair[0]), world.transform_point3(pair[1])));
+                    }
+                    start += n;
+                }
+            }
+        } else if let Some(points) = IPoints::new(obj) {
+            if let Ok(s) = points.getSample(clamp(points.getNumSamples())) {
+                wire.points.extend(s.positions.iter().map(|&p| world.transform_point3(p)));
+            }
+        }
+
+        for child in obj.getChildren() {
+            collect(&child, index, world, wire);
+        }
+    }
+
+    /// Render a `size`x`size` wireframe of `obj` (and descendants) from a 3/4 view.
+    pub fn render(archive: &IArchive, obj: &IObject, index: usize, size: u32) -> Vec<u8> {
+        // Place the subtree in world space using its ancestors' transforms.
+        let parent = match obj.getParentFullName() {
+            Some(parent_path) => world_matrix(archive, &parent_path, index),
+            None => Mat4::IDENTITY,
+        };
+        let mut wire = Wire::default();
+        collect(obj, index, parent, &mut wire);
+        rasterize(&wire, size)
+    }
+
+    fn rasterize(wire: &Wire, size: u32) -> Vec<u8> {
+        let mut img = vec![40u8; (size * size) as usize];
+
+        // Fixed 3/4 view: yaw 45deg, pitch 30deg, orthographic fit to bounds.
+        let view = Mat4::from_rotation_x(30f32.to_radians()) * Mat4::from_rotation_y(-45f32.to_radians());
+        let all = wire.lines.iter().flat_map(|(a, b)| [*a, *b]).chain(wire.points.iter().copied());
+        let projected: Vec<Vec3> = all.map(|p| view.transform_point3(p)).collect();
+        if projected.is_empty() {
+            return png::encode_gray(&img, size, size);
+        }
+        let (mut lo, mut hi) = (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN));
+        for p in &projected {
+            lo = lo.min(*p);
+            hi = hi.max(*p);
+        }
+        let center = (lo + hi) * 0.5;
+        let extent = (hi - lo).truncate().max_element().max(1e-6);
+        let scale = size as f32 * 0.9 / extent;
+        let to_px = |p: Vec3| {
+            let q = (p - center) * scale;
+            (size as f32 * 0.5 + q.x, size as f32 * 0.5 - q.y)
+        };
+
+        let n_lines = wire.lines.len();
+        for i in 0..n_lines {
+            let a = to_px(projected[i * 2]);
+            let b = to_px(projected[i * 2 + 1]);
+            draw_line(&mut img, size, a, b);
+        }
+        for p in &projected[n_lines * 2..] {
+            let (x, y) = to_px(*p);
+            plot(&mut img, size, x as i32, y as i32);
+        }
+        png::encode_gray(&img, size, size)
+    }
+
+    fn plot(img: &mut [u8], size: u32, x: i32, y: i32) {
+        if x >= 0 && y >= 0 && (x as u32) < size && (y as u32) < size {
+            img[(y as u32 * size + x as u32) as usize] = 230;
+        }
+    }
+
+    /// DDA line (no anti-aliasing).
+    fn draw_line(img: &mut [u8], size: u32, a: (f32, f32), b: (f32, f32)) {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let steps = dx.abs().max(dy.abs()).ceil().clamp(1.0, (size * 4) as f32) as i32;
+        for s in 0..=steps {
+            let t = s as f32 / steps as f32;
+            plot(img, size, (a.0 + dx * t).round() as i32, (a.1 + dy * t).round() as i32);
+        }
+    }
+}
+
+/// Minimal PNG writer (8-bit grayscale).
+mod png {
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    pub fn encode_gray(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+        for row in pixels.chunks(width as usize) {
+            raw.push(0); // filter: none
+            raw.extend_from_slice(row);
+        }
+        let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
+        let _ = enc.write_all(&raw);
+        let idat = enc.finish().unwrap_or_default();
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 0, 0, 0, 0]); // 8-bit, grayscale, deflate, no filter, no interlace
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut out, b"IHDR", &ihdr);
+        chunk(&mut out, b"IDAT", &idat);
+        chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        out.extend_from_slice(kind);
+        out.extend_from_slice(data);
+        let mut crc = crc32(kind, 0xFFFF_FFFF);
+        crc = crc32(data, crc);
+        out.extend_from_slice(&(crc ^ 0xFFFF_FFFF).to_be_bytes());
+    }
+
+    fn crc32(data: &[u8], mut crc: u32) -> u32 {
+        for &b in data {
+            crc ^= b as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            }
+        }
+        crc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alembic::ogawa::writer::{OArchive, OObject, OPolyMesh, OPolyMeshSample, OXform, OXformSample};
+
+    fn request(raw: &str) -> Result<Request, HttpError> {
+        read_request(raw.as_bytes())
+    }
+
+    /// Registry serving `scene.abc`: /xf/tri, a triangle under a translated xform.
+    fn registry(dir: &std::path::Path) -> Registry {
+        let path = dir.join("scene.abc");
+        let mut archive = OArchive::create(&path).unwrap();
+        let mut xform = OXform::new("xf");
+        xform.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::X), true));
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+        xform.add_child(mesh.build());
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).unwrap();
+        Registry::scan(&[dir.to_path_buf()]).unwrap()
+    }
+
+    fn get(registry: &Registry, target: &str) -> Result<Response, HttpError> {
+        let req = request(&format!("GET {} HTTP/1.1\r\nHost: x\r\n\r\n", target))?;
+        route(&req, registry)
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b+c", true), "a b c");
+        assert_eq!(percent_decode("a%20b+c", false), "a b+c");
+        assert_eq!(percent_decode("%2Fxf%2ftri", false), "/xf/tri");
+        assert_eq!(percent_decode("%C3%A9", false), "é");
+        // Malformed or truncated escapes are kept as-is
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%4", false), "%4");
+        assert_eq!(percent_decode("%zz", false), "%zz");
+        // Multi-byte characters right after '%' must not split a char
+        assert_eq!(percent_decode("%aé", false), "%aé");
+        assert_eq!(percent_decode("%é1", false), "%é1");
+        assert_eq!(percent_decode("é%", false), "é%");
+    }
+
+    #[test]
+    fn test_read_request() {
+        let req = request("GET /sample/scene.abc/xf/tri?attr=faceIndices&index=2 HTTP/1.1\r\nHost: x\r\n\r\n")
+            .unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.path, "/sample/scene.abc/xf/tri");
+        assert_eq!(req.query["attr"], "faceIndices");
+        assert_eq!(req.query["index"], "2");
+
+        let req = request("HEAD /object/my%20shot.abc/a%2Bb?flag HTTP/1.1\n\n").unwrap();
+        assert_eq!(req.method, "HEAD");
+        assert_eq!(req.path, "/object/my shot.abc/a+b");
+        assert_eq!(req.query["flag"], "");
+        // '+' is only a space in the query string
+        let req = request("GET /object/c++.abc/a+b?q=x+y HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(req.path, "/object/c++.abc/a+b");
+        assert_eq!(req.query["q"], "x y");
+
+        assert_eq!(request("\r\n\r\n").unwrap_err().status, 400);
+        // Headers never terminated
+        assert_eq!(request("GET / HTTP/1.1\r\nHost: x\r\n").unwrap_err().status, 413);
+        let huge = format!("GET / HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_REQUEST_HEAD));
+        assert_eq!(request(&huge).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn test_route() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry(dir.path());
+
+        let resp = get(&registry, "/health").unwrap();
+        assert_eq!(resp.body, b"ok");
+        let resp = get(&registry, "/archives").unwrap();
+        assert_eq!(serde_json::from_slice::<Value>(&resp.body).unwrap(), json!(["scene.abc"]));
+
+        let resp = get(&registry, "/manifest/scene.abc").unwrap();
+        let manifest: Value = serde_json::from_slice(&resp.body).unwrap();
+        assert!(manifest.to_string().contains("/xf/tri"), "{}", manifest);
+
+        let resp = get(&registry, "/sample/scene.abc/xf/tri?attr=faceIndices").unwrap();
+        assert_eq!(resp.body.len(), 3 * 4);
+        assert!(resp.headers.iter().any(|(k, v)| *k == "X-Alembic-Count" && v == "3"));
+
+        let resp = get(&registry, "/thumbnail/scene.abc?size=32").unwrap();
+        assert_eq!(resp.content_type, "image/png");
+        assert!(resp.body.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // Sizes past u32 clamp to the maximum instead of wrapping to a tiny image
+        let resp = get(&registry, "/thumbnail/scene.abc?size=4294967312").unwrap();
+        let width = u32::from_be_bytes(resp.body[16..20].try_into().unwrap());
+        assert_eq!(width, MAX_THUMBNAIL_SIZE);
+
+        assert_eq!(get(&registry, "/manifest/missing.abc").unwrap_err().status, 404);
+        assert_eq!(get(&registry, "/object/scene.abc/nope").unwrap_err().status, 404);
+        assert_eq!(get(&registry, "/object/scene.abc/xf?index=x").unwrap_err().status, 400);
+        assert_eq!(get(&registry, "/bogus").unwrap_err().status, 404);
+        // Escapes that would have split a UTF-8 character
+        assert_eq!(get(&registry, "/object/scene.abc/%a\u{e9}").unwrap_err().status, 404);
+    }
+}
@@ -0,0 +1,301 @@
+//! `alembic strip <in> <out> --drop N,uv [--recompute-normals] [--fix-winding]` - drop shading data.
+//!
+//! Simulation inputs rarely need normals, UVs or other shading attributes, but
+//! they often dominate the size of a cache. `strip` copies the archive
+//! property-for-property while leaving out the requested geometry attributes,
+//! optionally replacing mesh normals with smooth vertex normals recomputed
+//! from `P`, and reports how many bytes were saved.
+//!
+//! `--fix-winding` rewinds PolyMesh/SubD faces that disagree with their
+//! neighbours (see [`alembic::geom::winding`]), reordering face-varying
+//! GeomParams along with `.faceIndices`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use alembic::abc::IArchive as AbcIArchive;
+use alembic::core::MetaData;
+use alembic::geom::{analyze_winding, reverse_faces};
+use alembic::ogawa::writer::{ArraySampleWithDigest, OArchive, OObject, OProperty, OPropertyData};
+use alembic::prelude::{IObject, IPolyMesh};
+use alembic::util::{DataType, PlainOldDataType};
+use tracing::{debug, info};
+
+/// Options for a strip copy.
+#[derive(Debug, Clone, Default)]
+pub struct StripOptions {
+    /// Attribute names to drop (`N`, `uv`, `velocities` or any arbGeomParam name).
+    pub drop: Vec<String>,
+    /// Replace PolyMesh normals with smooth vertex normals computed from `P`.
+    pub recompute_normals: bool,
+    /// Rewind faces wound against their neighbours.
+    pub fix_winding: bool,
+}
+
+/// Parse the flags following `strip <in> <out>`.
+pub fn parse_args(args: &[&str]) -> Result<StripOptions, String> {
+    let mut opts = StripOptions::default();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--drop" => {
+                let v = iter.next().ok_or("--drop requires a comma-separated list of attributes")?;
+                for name in v.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                    let name = match name {
+                        "v" | "vel" | ".velocities" => "velocities",
+                        other => other,
+                    };
+                    if !opts.drop.iter().any(|d| d == name) {
+                        opts.drop.push(name.to_string());
+                    }
+                }
+            }
+            "--recompute-normals" => opts.recompute_normals = true,
+            "--fix-winding" => opts.fix_winding = true,
+            _ => return Err(format!("unknown strip option: {}", arg)),
+        }
+    }
+    if opts.drop.is_empty() && !opts.recompute_normals && !opts.fix_winding {
+        return Err("nothing to do: pass --drop <attrs>, --recompute-normals and/or --fix-winding".to_string());
+    }
+    Ok(opts)
+}
+
+/// Shared state for one strip copy.
+struct Stripper<'a> {
+    opts: &'a StripOptions,
+    ts_map: HashMap<u32, u32>,
+    /// Dropped property count per requested attribute name.
+    dropped: BTreeMap<String, usize>,
+    recomputed: usize,
+    /// Meshes rewound by `--fix-winding`, and their flipped faces (per sample).
+    rewound_meshes: usize,
+    rewound_faces: usize,
+}
+
+/// Copy `input` to `output` without the attributes selected in `opts`.
+pub fn strip(input: &str, output: &str, opts: &StripOptions) -> Result<(), String> {
+    info!(
+        "Strip {} -> {} (drop {:?}, recompute normals: {}, fix winding: {})",
+        input, output, opts.drop, opts.recompute_normals, opts.fix_winding
+    );
+
+    let archive = AbcIArchive::open(input).map_err(|e| format!("failed to open {}: {}", input, e))?;
+    let mut out_archive = OArchive::create(output).map_err(|e| format!("failed to create {}: {}", output, e))?;
+    out_archive.set_archive_metadata(archive.getArchiveMetaData().clone());
+    out_archive.set_library_version(archive.getArchiveVersion());
+
+    let mut ts_map = HashMap::new();
+    ts_map.insert(0, 0);
+    for i in 1..archive.getNumTimeSamplings() {
+        if let Some(ts) = archive.getTimeSampling(i) {
+            ts_map.insert(i as u32, out_archive.addTimeSampling(ts.clone()));
+        }
+    }
+
+    let mut s = Stripper { opts, ts_map, dropped: BTreeMap::new(), recomputed: 0, rewound_meshes: 0, rewound_faces: 0 };
+
+    let root = archive.getTop();
+    let mut out_root = OObject::new("");
+    super::copy_root_properties(&root, &mut out_root, &s.ts_map);
+    for child in root.getChildren() {
+        out_root.add_child(s.copy_object(&child)?);
+    }
+
+    out_archive.write_archive(&out_root).map_err(|e| format!("failed to write archive: {}", e))?;
+    // Close the file before measuring it.
+    drop(out_archive);
+
+    let in_size = file_size(input)?;
+    let out_size = file_size(output)?;
+    let saved = in_size as i64 - out_size as i64;
+    let percent = if in_size > 0 { saved as f64 * 100.0 / in_size as f64 } else { 0.0 };
+
+    println!("Stripped {} -> {}", input, output);
+    for name in &opts.drop {
+        println!("  Dropped {:<10} {} properties", format!("{}:", name), s.dropped.get(name).copied().unwrap_or(0));
+    }
+    if opts.recompute_normals {
+        println!("  Recomputed N:      {} meshes", s.recomputed);
+    }
+    if opts.fix_winding {
+        println!("  Fixed winding:     {} faces in {} meshes", s.rewound_faces, s.rewound_meshes);
+    }
+    println!("  Input:             {} bytes", in_size);
+    println!("  Output:            {} bytes", out_size);
+    println!("  Saved:             {} bytes ({:.1}%)", saved, percent);
+    Ok(())
+}
+
+fn file_size(path: &str) -> Result<u64, String> {
+    std::fs::metadata(path).map(|m| m.len()).map_err(|e| format!("failed to stat {}: {}", path, e))
+}
+
+impl Stripper<'_> {
+    fn copy_object(&mut self, obj: &IObject) -> Result<OObject, String> {
+        debug!("strip: {}", obj.getFullName());
+        let mut out = OObject::new(obj.getName());
+        out.meta_data = obj.getMetaData().clone();
+        let props = obj.getProperties();
+        super::copy_properties_from(&props, &mut out.properties, &self.ts_map);
+
+        if let Some(geom) = out.properties.iter_mut().find(|p| p.name == ".geom") {
+            self.strip_geom(geom);
+            if self.opts.recompute_normals {
+                if let Some(mesh) = IPolyMesh::new(obj) {
+                    let n = self.vertex_normals(&mesh)?;
+                    if let OPropertyData::Compound(children) = &mut geom.data {
+                        children.retain(|c| c.name != "N");
+                        children.push(n);
+                    }
+                    self.recomputed += 1;
+                }
+            }
+            if self.opts.fix_winding {
+                let faces = fix_geom_winding(geom);
+                if faces > 0 {
+                    info!("{}: rewound {} faces", obj.getFullName(), faces);
+                    self.rewound_meshes += 1;
+                    self.rewound_faces += faces;
+                }
+            }
+        }
+
+        for child in obj.getChildren() {
+            out.add_child(self.copy_object(&child)?);
+        }
+        Ok(out)
+    }
+
+    /// Remove dropped attributes from a `.geom` compound and its `.arbGeomParams`.
+    fn strip_geom(&mut self, geom: &mut OProperty) {
+        let OPropertyData::Compound(children) = &mut geom.data else { return };
+        for name in &self.opts.drop {
+            let builtin = match name.as_str() {
+                "N" | "uv" => Some(name.as_str()),
+                "velocities" => Some(".velocities"),
+                _ => None,
+            };
+            let mut count = 0;
+            if let Some(builtin) = builtin {
+                let before = children.len();
+                children.retain(|c| c.name != builtin);
+                count += before - children.len();
+            }
+            if let Some(arb) = children.iter_mut().find(|c| c.name == ".arbGeomParams") {
+                if let OPropertyData::Compound(params) = &mut arb.data {
+                    let before = params.len();
+                    params.retain(|c| c.name != *name);
+                    count += before - params.len();
+                }
+            }
+            *self.dropped.entry(name.clone()).or_default() += count;
+        }
+        // Don't leave an empty arbGeomParams compound behind.
+        children.retain(|c| !(c.name == ".arbGeomParams" && matches!(&c.data, OPropertyData::Compound(p) if p.is_empty())));
+    }
+
+    /// Smooth vertex-scope normals for every sample of the mesh.
+    fn vertex_normals(&self, mesh: &IPolyMesh<'_>) -> Result<OProperty, String> {
+        let mut meta = MetaData::new();
+        meta.set("arrayExtent", "1");
+        meta.set("geoScope", "vtx");
+        meta.set("interpretation", "normal");
+        meta.set("isGeomParam", "true");
+        meta.set("podExtent", "3");
+        meta.set("podName", "float32_t");
+
+        let mut n = OProperty::array("N", DataType::new(PlainOldDataType::Float32, 3))
+            .with_meta_data(meta)
+            .with_time_sampling(super::map_ts(&self.ts_map, mesh.getTimeSamplingIndex()));
+        for i in 0..mesh.getNumSamples() {
+            let sample = mesh
+                .getSample(i)
+                .map_err(|e| format!("failed to read {} sample {}: {}", mesh.getFullName(), i, e))?;
+            // compute_vertex_normals() assumes counter-clockwise faces; Alembic
+            // faces wind clockwise, so flip to point outward
+            let normals: Vec<[f32; 3]> = sample.compute_vertex_normals().iter().map(|v| (-*v).to_array()).collect();
+            n.add_array_pod(&normals);
+        }
+        Ok(n)
+    }
+}
+
+/// Rewind inconsistent faces of a PolyMesh/SubD `.geom` compound in place:
+/// every `.faceIndices` sample, and the face-varying (`geoScope=fvr`)
+/// GeomParams, whose values or `.indices` follow the faces. Samples are
+/// matched by index, clamped to the last topology sample. Returns the most
+/// faces flipped in one sample.
+fn fix_geom_winding(geom: &mut OProperty) -> usize {
+    let OPropertyData::Compound(children) = &mut geom.data else { return 0 };
+    let i32s = |data: &[u8]| -> Vec<i32> {
+        data.chunks_exact(4).map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect()
+    };
+    let counts: Vec<Vec<i32>> = match children.iter().find(|c| c.name == ".faceCounts").map(|c| &c.data) {
+        Some(OPropertyData::Array(samples)) => samples.iter().map(|s| i32s(&s.data)).collect(),
+        _ => return 0,
+    };
+    let Some(OPropertyData::Array(index_samples)) =
+        children.iter_mut().find(|c| c.name == ".faceIndices").map(|c| &mut c.data)
+    else {
+        return 0;
+    };
+    if counts.is_empty() || index_samples.is_empty() {
+        return 0;
+    }
+    let counts_at = |i: usize| &counts[i.min(counts.len() - 1)];
+
+    let mut flipped: Vec<Vec<usize>> = Vec::with_capacity(index_samples.len());
+    for (i, sample) in index_samples.iter_mut().enumerate() {
+        let mut indices = i32s(&sample.data);
+        let faces = analyze_winding(counts_at(i), &indices).flipped_faces;
+        if !faces.is_empty() {
+            reverse_faces(counts_at(i), &mut indices, 1, &faces);
+            *sample = ArraySampleWithDigest::new(bytemuck::cast_slice(&indices).to_vec(), sample.dims.clone());
+        }
+        flipped.push(faces);
+    }
+    let most = flipped.iter().map(Vec::len).max().unwrap_or(0);
+    if most == 0 {
+        return 0;
+    }
+
+    let rewind = |prop: &mut OProperty| {
+        if prop.meta_data.get("geoScope") != Some("fvr") {
+            return;
+        }
+        // Indexed params reorder their indices, plain ones their values
+        let own_elem = prop.data_type.num_bytes();
+        let (target, elem) = match &mut prop.data {
+            OPropertyData::Compound(parts) => {
+                let indexed = parts.iter().any(|p| p.name == ".indices");
+                let key = if indexed { ".indices" } else { ".vals" };
+                let Some(part) = parts.iter_mut().find(|p| p.name == key) else { return };
+                let elem = part.data_type.num_bytes();
+                (&mut part.data, elem)
+            }
+            data => (data, own_elem),
+        };
+        let OPropertyData::Array(samples) = target else { return };
+        for (j, sample) in samples.iter_mut().enumerate() {
+            let faces = &flipped[j.min(flipped.len() - 1)];
+            if faces.is_empty() || elem == 0 {
+                continue;
+            }
+            let mut data = std::mem::take(&mut sample.data);
+            reverse_faces(counts_at(j), &mut data, elem, faces);
+            *sample = ArraySampleWithDigest::new(data, sample.dims.clone());
+        }
+    };
+    for child in children.iter_mut() {
+        match child.name.as_str() {
+            "uv" | "N" => rewind(child),
+            ".arbGeomParams" => {
+                if let OPropertyData::Compound(params) = &mut child.data {
+                    params.iter_mut().for_each(&rewind);
+                }
+            }
+            _ => {}
+        }
+    }
+    most
+}
@@ -0,0 +1,689 @@
+//! `alembic watch` - validate archives as they land in a folder.
+//!
+//! Polls a directory for new or modified `.abc` files, waits until each file
+//! stops changing, validates it against a rules file and writes a JSON
+//! pass/fail report (`<name>.abc.report.json`). Intended to gate publishes.
+//!
+//! Each archive gets the same lint as `alembic check` ([`alembic::validate`]);
+//! the `[checks]` switches turn some of its checks off or make unknown
+//! schemas fail, and the naming, bounds and object count rules are added on
+//! top.
+//!
+//! Rules use a small TOML subset (sections, `key = value`, strings, numbers,
+//! booleans and one-line arrays):
+//!
+//! ```toml
+//! [watch]
+//! interval = 2.0          # seconds between directory scans
+//! settle = 1.0            # file must be unchanged this long before validation
+//! recursive = true
+//! reports = "reports"     # report directory (default: next to each file)
+//!
+//! [checks]
+//! nan_scan = true         # NaN/Inf in positions and transforms
+//! max_extent = 100000.0   # largest allowed |coordinate|
+//! require_bounds = true   # geometry must store .selfBnds
+//! bounds_contain_points = true
+//! topology = true         # face counts/indices consistent
+//! unknown_schemas = false # allow schemas this library does not know
+//! min_objects = 1
+//!
+//! [naming]
+//! forbidden_chars = " .-"
+//! mesh = ["*_GEO", "*Shape"]   # wildcard patterns (* and ?)
+//! xform = ["*"]
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use alembic::abc::{IArchive as AbcIArchive, IObject, OpenMode};
+use alembic::geom::util as geom_util;
+use alembic::geom::{
+    CAMERA_SCHEMA, CURVES_SCHEMA, FACESET_SCHEMA, LIGHT_SCHEMA, NUPATCH_SCHEMA, POINTS_SCHEMA,
+    POLYMESH_SCHEMA, SUBD_SCHEMA, XFORM_SCHEMA,
+};
+use alembic::validate::{Check, Diagnostic, Severity};
+use serde_json::json;
+use tracing::{debug, info, warn};
+
+// ============================================================================
+// Rules
+// ============================================================================
+
+/// Validation and polling settings loaded from the rules file.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    pub interval: f64,
+    pub settle: f64,
+    pub recursive: bool,
+    pub reports: Option<PathBuf>,
+
+    pub nan_scan: bool,
+    pub max_extent: Option<f64>,
+    pub require_bounds: bool,
+    pub bounds_contain_points: bool,
+    pub topology: bool,
+    pub unknown_schemas: bool,
+    pub min_objects: usize,
+
+    pub forbidden_chars: String,
+    /// Allowed name patterns per object kind ("mesh", "xform", "curves", ...).
+    pub naming: HashMap<String, Vec<String>>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            interval: 2.0,
+            settle: 1.0,
+            recursive: true,
+            reports: None,
+            nan_scan: true,
+            max_extent: None,
+            require_bounds: false,
+            bounds_contain_points: true,
+            topology: true,
+            unknown_schemas: true,
+            min_objects: 1,
+            forbidden_chars: String::new(),
+            naming: HashMap::new(),
+        }
+    }
+}
+
+/// Parsed TOML-subset value.
+#[derive(Debug, Clone, PartialEq)]
+enum RuleValue {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    List(Vec<RuleValue>),
+}
+
+impl RuleValue {
+    fn as_str(&self) -> Option<&str> {
+        match self { RuleValue::Str(s) => Some(s), _ => None }
+    }
+    fn as_f64(&self) -> Option<f64> {
+        match self { RuleValue::Num(n) => Some(*n), _ => None }
+    }
+    fn as_bool(&self) -> Option<bool> {
+        match self { RuleValue::Bool(b) => Some(*b), _ => None }
+    }
+    fn as_str_list(&self) -> Option<Vec<String>> {
+        match self {
+            RuleValue::List(items) => items.iter().map(|v| v.as_str().map(str::to_string)).collect(),
+            RuleValue::Str(s) => Some(vec![s.clone()]),
+            _ => None,
+        }
+    }
+}
+
+impl Rules {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut rules = Rules::default();
+        for (section, key, value, line) in parse_toml_subset(text)? {
+            let bad = || format!("line {}: invalid value for {}.{}", line, section, key);
+            match (section.as_str(), key.as_str()) {
+                ("watch", "interval") => rules.interval = value.as_f64().ok_or_else(bad)?,
+                ("watch", "settle") => rules.settle = value.as_f64().ok_or_else(bad)?,
+                ("watch", "recursive") => rules.recursive = value.as_bool().ok_or_else(bad)?,
+                ("watch", "reports") => rules.reports = Some(PathBuf::from(value.as_str().ok_or_else(bad)?)),
+                ("checks", "nan_scan") => rules.nan_scan = value.as_bool().ok_or_else(bad)?,
+                ("checks", "max_extent") => rules.max_extent = Some(value.as_f64().ok_or_else(bad)?),
+                ("checks", "require_bounds") => rules.require_bounds = value.as_bool().ok_or_else(bad)?,
+                ("checks", "bounds_contain_points") => rules.bounds_contain_points = value.as_bool().ok_or_else(bad)?,
+                ("checks", "topology") => rules.topology = value.as_bool().ok_or_else(bad)?,
+                ("checks", "unknown_schemas") => rules.unknown_schemas = value.as_bool().ok_or_else(bad)?,
+                ("checks", "min_objects") => rules.min_objects = value.as_f64().ok_or_else(bad)? as usize,
+                ("naming", "forbidden_chars") => rules.forbidden_chars = value.as_str().ok_or_else(bad)?.to_string(),
+                ("naming", kind) => {
+                    rules.naming.insert(kind.to_string(), value.as_str_list().ok_or_else(bad)?);
+                }
+                _ => warn!("rules line {}: unknown key {}.{}", line, section, key),
+            }
+        }
+        Ok(rules)
+    }
+}
+
+/// Parse `[section]` / `key = value` lines into (section, key, value, line).
+fn parse_toml_subset(text: &str) -> Result<Vec<(String, String, RuleValue, usize)>, String> {
+    let mut out = Vec::new();
+    let mut section = String::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| format!("line {}: expected key = value", line_no))?;
+        let value = parse_value(value.trim())
+            .ok_or_else(|| format!("line {}: cannot parse value '{}'", line_no, value.trim()))?;
+        out.push((section.clone(), key.trim().to_string(), value, line_no));
+    }
+    Ok(out)
+}
+
+/// Remove a trailing `# comment` that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('#', None) => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(s: &str) -> Option<RuleValue> {
+    if let Some(inner) = s.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        let items = split_list(inner)
+            .into_iter()
+            .map(|item| parse_value(item.trim()))
+            .collect::<Option<Vec<_>>>()?;
+        return Some(RuleValue::List(items));
+    }
+    for q in ['"', '\''] {
+        if s.len() >= 2 && s.starts_with(q) && s.ends_with(q) {
+            return Some(RuleValue::Str(s[1..s.len() - 1].to_string()));
+        }
+    }
+    match s {
+        "true" => return Some(RuleValue::Bool(true)),
+        "false" => return Some(RuleValue::Bool(false)),
+        _ => {}
+    }
+    s.replace('_', "").parse::<f64>().ok().map(RuleValue::Num)
+}
+
+/// Split a list body on commas outside of quotes.
+fn split_list(s: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (',', None) => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !s[start..].trim().is_empty() {
+        items.push(&s[start..]);
+    }
+    items
+}
+
+/// Wildcard match supporting `*` (any run) and `?` (any one char).
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+#[derive(Debug, Clone)]
+struct Issue {
+    severity: Severity,
+    check: &'static str,
+    object: String,
+    sample: Option<usize>,
+    message: String,
+}
+
+impl From<Diagnostic> for Issue {
+    fn from(d: Diagnostic) -> Self {
+        Self { severity: d.severity, check: d.check.as_str(), object: d.object, sample: d.sample, message: d.message }
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    issues: Vec<Issue>,
+    objects: usize,
+}
+
+impl Validator {
+    fn error(&mut self, check: &'static str, object: &str, sample: Option<usize>, message: String) {
+        self.issues.push(Issue { severity: Severity::Error, check, object: object.to_string(), sample, message });
+    }
+
+    fn passed(&self) -> bool {
+        !self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+}
+
+/// Object kind used for naming rules.
+fn kind_of(schema: &str) -> &'static str {
+    match schema {
+        XFORM_SCHEMA => "xform",
+        POLYMESH_SCHEMA => "mesh",
+        SUBD_SCHEMA => "subd",
+        CURVES_SCHEMA => "curves",
+        POINTS_SCHEMA => "points",
+        CAMERA_SCHEMA => "camera",
+        LIGHT_SCHEMA => "light",
+        NUPATCH_SCHEMA => "nupatch",
+        FACESET_SCHEMA => "faceset",
+        "" => "group",
+        _ => "other",
+    }
+}
+
+/// Validate one archive: the [`alembic::validate`] diagnostics filtered by
+/// the `[checks]` switches, plus the naming, bounds and object count rules.
+fn validate(path: &Path, rules: &Rules) -> Validator {
+    let mut v = Validator::default();
+    let archive = match AbcIArchive::open_with_mode(path, OpenMode::Tolerant) {
+        Ok(a) => a,
+        Err(e) => {
+            v.error(Check::Open.as_str(), "/", None, e.to_string());
+            return v;
+        }
+    };
+    for mut d in alembic::validate::validate(&archive) {
+        match d.check {
+            Check::Nan if !rules.nan_scan => continue,
+            Check::Topology if !rules.topology => continue,
+            Check::Schema if !rules.unknown_schemas => d.severity = Severity::Error,
+            _ => {}
+        }
+        v.issues.push(d.into());
+    }
+    for child in archive.getTop().getChildren() {
+        check_object(&child, rules, &mut v);
+    }
+    if v.objects < rules.min_objects {
+        v.error("min_objects", "/", None, format!("{} objects, expected at least {}", v.objects, rules.min_objects));
+    }
+    v
+}
+
+/// Naming and bounds rules, which `alembic::validate` has no opinion on.
+fn check_object(obj: &IObject, rules: &Rules, v: &mut Validator) {
+    v.objects += 1;
+    let full = obj.getFullName().to_string();
+    let name = obj.getName();
+    let schema = obj.getMetaData().get("schema").unwrap_or_default().to_string();
+
+    if let Some(c) = name.chars().find(|c| rules.forbidden_chars.contains(*c)) {
+        v.error("naming", &full, None, format!("name contains forbidden character '{}'", c));
+    }
+    let kind = kind_of(&schema);
+    if let Some(patterns) = rules.naming.get(kind) {
+        if !patterns.iter().any(|p| wildcard_match(p, name)) {
+            v.error("naming", &full, None, format!("{} name '{}' does not match {:?}", kind, name, patterns));
+        }
+    }
+    check_bounds(obj, rules, &full, v);
+
+    for child in obj.getChildren() {
+        check_object(&child, rules, v);
+    }
+}
+
+/// Compare `.geom/P` of any point-based schema against `max_extent` and its
+/// stored `.selfBnds`. Reports the first offending sample per object.
+fn check_bounds(obj: &IObject, rules: &Rules, object: &str, v: &mut Validator) {
+    if rules.max_extent.is_none() && !rules.require_bounds && !rules.bounds_contain_points {
+        return;
+    }
+    let props = obj.getProperties();
+    let Some(geom_prop) = props.getPropertyByName(".geom") else { return };
+    let Some(geom) = geom_prop.asCompound() else { return };
+    if geom.getPropertyByName("P").is_none() {
+        return;
+    }
+    for i in 0..geom_util::num_samples_from_positions(obj) {
+        let Some(positions) = geom_util::read_vec3_array(geom.as_reader(), "P", i) else { continue };
+        // Non-finite positions are reported by the nan check
+        if positions.iter().any(|p| !p.is_finite()) {
+            continue;
+        }
+        if let Some(limit) = rules.max_extent {
+            let max = positions.iter().map(|p| p.abs().max_element() as f64).fold(0.0, f64::max);
+            if max > limit {
+                v.error("bounds", object, Some(i), format!("coordinate {:.3} exceeds max_extent {}", max, limit));
+                return;
+            }
+        }
+        match geom_util::read_self_bounds(geom.as_reader(), i) {
+            None if rules.require_bounds => {
+                v.error("bounds", object, Some(i), "missing .selfBnds".to_string());
+                return;
+            }
+            Some(b) if rules.bounds_contain_points && !positions.is_empty() => {
+                let (lo, hi) = geom_util::compute_bounds_vec3(&positions);
+                let tol = 1e-4 * (1.0 + (b.max - b.min).max_element());
+                let outside = (lo.as_dvec3() - b.min).min_element() < -tol
+                    || (b.max - hi.as_dvec3()).min_element() < -tol;
+                if outside {
+                    v.error("bounds", object, Some(i), format!(
+                        "stored bounds {:?}..{:?} do not contain points {:?}..{:?}",
+                        b.min.to_array(), b.max.to_array(), lo.to_array(), hi.to_array()));
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Write the JSON report and return its path.
+fn write_report(path: &Path, rules: &Rules, v: &Validator, elapsed: Duration) -> std::io::Result<PathBuf> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let dir = match &rules.reports {
+        Some(d) if d.is_absolute() => d.clone(),
+        Some(d) => path.parent().unwrap_or(Path::new(".")).join(d),
+        None => path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+    };
+    std::fs::create_dir_all(&dir)?;
+    let report_path = dir.join(format!("{}.report.json", file_name));
+
+    let issues: Vec<_> = v.issues.iter().map(|i| json!({
+        "severity": if i.severity == Severity::Error { "error" } else { "warning" },
+        "check": i.check,
+        "object": i.object,
+        "sample": i.sample,
+        "message": i.message,
+    })).collect();
+    let report = json!({
+        "file": path.to_string_lossy(),
+        "status": if v.passed() { "pass" } else { "fail" },
+        "objects": v.objects,
+        "errors": v.issues.iter().filter(|i| i.severity == Severity::Error).count(),
+        "warnings": v.issues.iter().filter(|i| i.severity == Severity::Warning).count(),
+        "elapsedMs": elapsed.as_millis() as u64,
+        "issues": issues,
+    });
+    std::fs::write(&report_path, serde_json::to_string_pretty(&report).unwrap_or_default())?;
+    Ok(report_path)
+}
+
+// ============================================================================
+// Watch loop
+// ============================================================================
+
+/// Options for `alembic watch`.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub dir: PathBuf,
+    pub rules: Option<PathBuf>,
+    /// Validate the current contents once and exit (non-zero if any fail).
+    pub once: bool,
+}
+
+/// Parse `watch` arguments: `<dir> [--rules rules.toml] [--once]`.
+pub fn parse_args(args: &[&str]) -> Result<WatchOptions, String> {
+    let mut dir = None;
+    let mut rules = None;
+    let mut once = false;
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--rules" | "-r" => {
+                rules = Some(PathBuf::from(iter.next().ok_or("--rules requires a value")?));
+            }
+            "--once" => once = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown watch option: {}", arg)),
+            _ if dir.is_none() => dir = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    let dir = dir.ok_or("missing directory argument")?;
+    if !dir.is_dir() {
+        return Err(format!("not a directory: {}", dir.display()));
+    }
+    Ok(WatchOptions { dir, rules, once })
+}
+
+/// Observed state of a file in the watched folder.
+struct Tracked {
+    size: u64,
+    modified: Option<SystemTime>,
+    /// When the current size/mtime was first observed.
+    stable_since: Instant,
+    validated: bool,
+}
+
+/// Run the watcher. Returns the number of failed archives in `--once` mode.
+pub fn run(opts: WatchOptions) -> Result<usize, String> {
+    let rules = match &opts.rules {
+        Some(p) => Rules::load(p)?,
+        None => Rules::default(),
+    };
+    debug!("watch rules: {:?}", rules);
+
+    if opts.once {
+        let mut files = Vec::new();
+        collect_abc(&opts.dir, rules.recursive, &mut files);
+        files.sort();
+        let failed = files.iter().filter(|f| !validate_and_report(f, &rules)).count();
+        println!("{} file(s) checked, {} failed", files.len(), failed);
+        return Ok(failed);
+    }
+
+    println!("Watching {} (interval {:.1}s, Ctrl+C to stop)", opts.dir.display(), rules.interval);
+    let settle = Duration::from_secs_f64(rules.settle.max(0.0));
+    let mut tracked: HashMap<PathBuf, Tracked> = HashMap::new();
+
+    loop {
+        let mut files = Vec::new();
+        collect_abc(&opts.dir, rules.recursive, &mut files);
+        let now = Instant::now();
+
+        tracked.retain(|path, _| files.contains(path));
+        for path in files {
+            let Ok(meta) = std::fs::metadata(&path) else { continue };
+            let (size, modified) = (meta.len(), meta.modified().ok());
+            let entry = tracked.entry(path.clone()).or_insert(Tracked {
+                size, modified, stable_since: now, validated: false,
+            });
+            if entry.size != size || entry.modified != modified {
+                *entry = Tracked { size, modified, stable_since: now, validated: false };
+            }
+            if !entry.validated && now.duration_since(entry.stable_since) >= settle {
+                entry.validated = true;
+                validate_and_report(&path, &rules);
+            }
+        }
+        std::thread::sleep(Duration::from_secs_f64(rules.interval.max(0.1)));
+    }
+}
+
+/// Validate one file, write its report and print a one-line summary.
+fn validate_and_report(path: &Path, rules: &Rules) -> bool {
+    let start = Instant::now();
+    let v = validate(path, rules);
+    let elapsed = start.elapsed();
+    let passed = v.passed();
+    let errors = v.issues.iter().filter(|i| i.severity == Severity::Error).count();
+
+    match write_report(path, rules, &v, elapsed) {
+        Ok(report) => info!("report written: {}", report.display()),
+        Err(e) => warn!("failed to write report for {}: {}", path.display(), e),
+    }
+    println!("[{}] {} ({} objects, {} errors, {} warnings, {:.0} ms)",
+        if passed { "PASS" } else { "FAIL" },
+        path.display(), v.objects, errors, v.issues.len() - errors, elapsed.as_secs_f64() * 1000.0);
+    for issue in v.issues.iter().filter(|i| i.severity == Severity::Error).take(5) {
+        match issue.sample {
+            Some(sample) => println!("    {} [{}] sample {}: {}", issue.object, issue.check, sample, issue.message),
+            None => println!("    {} [{}] {}", issue.object, issue.check, issue.message),
+        }
+    }
+    passed
+}
+
+fn collect_abc(dir: &Path, recursive: bool, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if recursive {
+                collect_abc(&path, recursive, out);
+            }
+        } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("abc")) {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = Rules::parse(r#"
+            # full-line comment
+            [watch]
+            interval = 0.5   # trailing comment
+            recursive = false
+            reports = 'out # dir'
+
+            [checks]
+            max_extent = 1_000.0
+            min_objects = 3
+            unknown_schemas = false
+
+            [ naming ]
+            forbidden_chars = " .#-"
+            mesh = ["*_GEO", 'x,y', ]
+            xform = "*"
+            camera = []
+        "#).unwrap();
+        assert_eq!(rules.interval, 0.5);
+        assert!(!rules.recursive);
+        assert_eq!(rules.reports, Some(PathBuf::from("out # dir")));
+        assert_eq!(rules.max_extent, Some(1000.0));
+        assert_eq!(rules.min_objects, 3);
+        assert!(!rules.unknown_schemas);
+        assert_eq!(rules.forbidden_chars, " .#-");
+        assert_eq!(rules.naming["mesh"], ["*_GEO", "x,y"]);
+        assert_eq!(rules.naming["xform"], ["*"]);
+        assert!(rules.naming["camera"].is_empty());
+        // Untouched keys keep their defaults; unknown keys are ignored
+        assert_eq!(rules.settle, 1.0);
+        assert!(Rules::parse("[checks]\nno_such_check = true").is_ok());
+    }
+
+    #[test]
+    fn test_parse_rules_errors() {
+        let err = |text: &str| Rules::parse(text).unwrap_err();
+        assert!(err("[watch]\ninterval").starts_with("line 2: expected key = value"));
+        assert!(err("[watch\ninterval = 1").starts_with("line 1:"));
+        assert!(err("interval =").contains("cannot parse value"));
+        assert!(err("x = \"unterminated").contains("cannot parse value"));
+        assert!(err("x = [1, 2").contains("cannot parse value"));
+        assert!(err("x = [1, bogus]").contains("cannot parse value"));
+        // Well-formed values of the wrong type
+        assert_eq!(err("[watch]\ninterval = \"fast\""), "line 2: invalid value for watch.interval");
+        assert!(err("[checks]\nnan_scan = 1").contains("checks.nan_scan"));
+        assert!(err("[naming]\nmesh = [1]").contains("naming.mesh"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("**", "a"));
+        assert!(wildcard_match("", ""));
+        assert!(!wildcard_match("", "a"));
+        assert!(wildcard_match("?", "a"));
+        assert!(!wildcard_match("?", ""));
+        assert!(!wildcard_match("?", "ab"));
+        assert!(wildcard_match("*_GEO", "body_GEO"));
+        assert!(wildcard_match("*_GEO", "_GEO"));
+        assert!(!wildcard_match("*_GEO", "body_GEO1"));
+        assert!(wildcard_match("a*b*c", "aXbYbZc"));
+        assert!(!wildcard_match("a*b*c", "aXbYbZ"));
+        assert!(wildcard_match("?ody*", "body_GEO"));
+        assert!(wildcard_match("caf?", "café"));
+    }
+
+    #[test]
+    fn test_validate() {
+        use alembic::ogawa::writer::{OArchive, OObject, OPolyMesh, OPolyMeshSample};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shot.abc");
+        let mut archive = OArchive::create(&path).unwrap();
+        let mut root = OObject::new("");
+        for (name, z) in [("body_GEO", 0.0), ("bad name", f32::NAN)] {
+            let mut mesh = OPolyMesh::new(name);
+            mesh.add_sample(&OPolyMeshSample::new(
+                vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::new(0.0, 1.0, z)],
+                vec![3],
+                vec![0, 1, 5],
+            ));
+            root.add_child(mesh.build());
+        }
+        archive.write_archive(&root).unwrap();
+
+        let rules = Rules::parse("[naming]\nforbidden_chars = \" \"\nmesh = [\"*_GEO\"]").unwrap();
+        let v = validate(&path, &rules);
+        assert!(!v.passed());
+        assert_eq!(v.objects, 2);
+        let found = |check: &str, object: &str| v.issues.iter().any(|i| i.check == check && i.object == object);
+        assert!(found("naming", "/bad name"));
+        assert!(!found("naming", "/body_GEO"));
+        // Geometry findings are the shared lint's, unchanged
+        let shared = alembic::validate::validate_archive(&path);
+        assert!(shared.iter().any(|d| d.check == Check::Topology && d.object == "/body_GEO"));
+        assert!(shared.iter().any(|d| d.check == Check::Nan && d.object == "/bad name"));
+        for d in &shared {
+            assert!(v.issues.iter().any(|i| i.check == d.check.as_str() && i.object == d.object
+                && i.sample == d.sample && i.message == d.message), "missing {}", d);
+        }
+
+        // [checks] switches drop the shared topology and NaN findings
+        let rules = Rules::parse("[checks]\ntopology = false\nnan_scan = false").unwrap();
+        let v = validate(&path, &rules);
+        assert!(!v.issues.iter().any(|i| i.check == "topology" || i.check == "nan"));
+
+        let v = validate(&dir.path().join("missing.abc"), &rules);
+        assert_eq!(v.issues[0].check, "open");
+    }
+}
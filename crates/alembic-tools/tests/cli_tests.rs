@@ -0,0 +1,1299 @@
+//! Integration tests for the `alembic-cli` binary.
+
+use alembic::abc::IArchive;
+use alembic::core::TimeSampling;
+use alembic::geom::{IPolyMesh, POLYMESH_SCHEMA};
+use alembic::ogawa::writer::{OArchive, OObject, OPolyMesh, OPolyMeshSample, OXform, OXformSample};
+use alembic::util::PlainOldDataType;
+
+use tempfile::NamedTempFile;
+
+/// Triangle animated along X at 24 fps, first sample at `start_frame`.
+fn write_animated_triangle(path: &std::path::Path, start_frame: f64, frames: usize) {
+    let mut archive = OArchive::create(path).expect("Failed to create archive");
+    let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, start_frame / 24.0));
+    let mut mesh = OPolyMesh::new("tri");
+    mesh.set_time_sampling(ts);
+    for frame in 0..frames {
+        let x = frame as f32;
+        let mut sample = OPolyMeshSample::new(
+            vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::new(x + 1.0, 0.0, 0.0), glam::Vec3::new(x, 1.0, 0.0)],
+            vec![3],
+            vec![0, 1, 2],
+        );
+        sample.uvs = Some(vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::Y]);
+        sample.normals = Some(vec![glam::Vec3::Z; 3]);
+        mesh.add_sample(&sample);
+    }
+    let mut root = OObject::new("");
+    root.add_child(mesh.build());
+    archive.write_archive(&root).expect("Failed to write archive");
+}
+
+#[test]
+fn test_cli_export_gltf() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    write_animated_triangle(temp.path(), 1.0, 1);
+
+    // .gltf JSON with a sibling .bin
+    let dir = tempfile::tempdir().expect("temp dir");
+    let gltf = dir.path().join("scene.gltf");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("export")
+        .arg(temp.path())
+        .arg(&gltf)
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "{}", String::from_utf8_lossy(&status.stderr));
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&gltf).unwrap()).unwrap();
+    assert_eq!(written["buffers"][0]["uri"], "scene.bin");
+    let bin_len = std::fs::metadata(dir.path().join("scene.bin")).unwrap().len();
+    assert_eq!(written["buffers"][0]["byteLength"].as_u64(), Some(bin_len));
+}
+
+#[test]
+fn test_cli_export_obj_single_frame() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    write_animated_triangle(temp.path(), 1001.0, 3);
+
+    // Single frame, default naming
+    let dir = tempfile::tempdir().expect("temp dir");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("export")
+        .arg(temp.path())
+        .arg(dir.path().join("cli.obj"))
+        .args(["--frames", "1001"])
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "{}", String::from_utf8_lossy(&status.stderr));
+    assert!(dir.path().join("cli.1001.obj").exists());
+    assert!(!dir.path().join("cli.1002.obj").exists());
+}
+
+#[test]
+fn test_cli_export_usda_fps() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    write_animated_triangle(temp.path(), 1.0, 2);
+
+    // Explicit fps rescales the time codes
+    let dir = tempfile::tempdir().expect("temp dir");
+    let usda = dir.path().join("scene.usda");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("export")
+        .arg(temp.path())
+        .arg(&usda)
+        .args(["--fps", "48"])
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "{}", String::from_utf8_lossy(&status.stderr));
+    let written = std::fs::read_to_string(&usda).unwrap();
+    assert!(written.contains("timeCodesPerSecond = 48\n"));
+    assert!(written.contains("endTimeCode = 4\n"));
+}
+
+#[test]
+fn test_cli_export_pbrt_mitsuba() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let input = dir.path().join("scene.abc");
+    write_animated_triangle(&input, 1.0, 3);
+    let cli = |args: &[&std::ffi::OsStr]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .args(args)
+            .output()
+            .expect("run alembic-cli");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    // Gives the mesh a Standard Surface material
+    cli(&["materialize".as_ref(), input.as_os_str()]);
+    let input = dir.path().join("scene_mat.abc");
+
+    let pbrt = dir.path().join("scene.pbrt");
+    let out = cli(&["export".as_ref(), input.as_os_str(), pbrt.as_os_str(), "--frame".as_ref(), "3".as_ref(), "--size".as_ref(), "320x240".as_ref()]);
+    assert!(out.contains("1 meshes (1 triangles), 1 materials"), "{}", out);
+    let text = std::fs::read_to_string(&pbrt).unwrap();
+    assert!(text.contains("\"integer xresolution\" [ 320 ] \"integer yresolution\" [ 240 ]"));
+    assert!(text.contains("NamedMaterial \"m0\""));
+    let ply = std::fs::read(dir.path().join("scene_meshes/0000_tri.ply")).unwrap();
+    assert!(ply.starts_with(b"ply\nformat binary_little_endian 1.0\n"));
+
+    let xml = dir.path().join("scene.xml");
+    cli(&["export".as_ref(), input.as_os_str(), xml.as_os_str(), "--env-color".as_ref(), "0.25".as_ref()]);
+    let text = std::fs::read_to_string(&xml).unwrap();
+    assert!(text.contains("<bsdf type=\"principled\" id=\"m0\">"));
+    assert!(text.contains("<rgb name=\"radiance\" value=\"0.25, 0.25, 0.25\"/>"));
+    assert!(text.contains("value=\"scene_meshes/0000_tri.ply\""));
+}
+
+#[test]
+fn test_cli_strip_shading_attributes() {
+    use alembic::geom::GeomParamSample;
+    use alembic::core::GeometryScope;
+    use alembic::util::DataType;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("grid");
+        // 8x8 quad grid, two samples, with fvr normals, uvs and a per-vertex color
+        let n = 9;
+        for frame in 0..2 {
+            let positions: Vec<glam::Vec3> = (0..n * n)
+                .map(|i| glam::Vec3::new((i % n) as f32, frame as f32, (i / n) as f32))
+                .collect();
+            let mut indices = Vec::new();
+            for y in 0..n - 1 {
+                for x in 0..n - 1 {
+                    let i = (y * n + x) as i32;
+                    indices.extend_from_slice(&[i, i + n as i32, i + n as i32 + 1, i + 1]);
+                }
+            }
+            let mut sample = OPolyMeshSample::new(positions, vec![4; (n - 1) * (n - 1)], indices.clone());
+            sample.normals = Some(vec![glam::Vec3::Y; indices.len()]);
+            sample.uvs = Some(vec![glam::Vec2::ONE; indices.len()]);
+            sample.set_arb_geom_param(
+                "Cd",
+                GeomParamSample::from_values(
+                    DataType::new(PlainOldDataType::Float32, 3),
+                    &vec![[1.0f32, 0.5, 0.0]; n * n],
+                    None,
+                    GeometryScope::Vertex,
+                ),
+            );
+            mesh.add_sample(&sample);
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .args(["strip"])
+        .arg(path)
+        .arg(out.path())
+        .args(["--drop", "uv,Cd", "--recompute-normals"])
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "strip failed: {}", String::from_utf8_lossy(&status.stderr));
+    let stdout = String::from_utf8_lossy(&status.stdout);
+    assert!(stdout.contains("Saved:"), "{stdout}");
+    assert!(std::fs::metadata(out.path()).unwrap().len() < std::fs::metadata(path).unwrap().len());
+
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("grid").expect("grid");
+    let mesh = IPolyMesh::new(&obj).expect("polymesh");
+    assert_eq!(mesh.getNumSamples(), 2);
+    assert!(!mesh.arb_geom_param_names().iter().any(|n| n == "Cd" || n == "uv"));
+    let sample = mesh.getSample(1).expect("sample");
+    assert!(sample.uvs.is_none());
+    let normals = sample.normals.expect("recomputed normals");
+    assert_eq!(normals.len(), sample.positions.len());
+    // Grid lies in the XZ plane and its faces wind clockwise seen from -Y
+    assert!(normals.iter().all(|v| (v.y + 1.0).abs() < 1e-5), "{:?}", normals[0]);
+
+    // Nothing requested is an error
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("strip")
+        .arg(path)
+        .arg(out.path())
+        .output()
+        .expect("run alembic-cli");
+    assert!(!status.status.success());
+}
+
+#[test]
+fn test_cli_strip_fix_winding() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    // 3x3 quad grid with face 4 wound backwards; fvr UVs are the vertex's XZ
+    let n = 4;
+    let positions: Vec<glam::Vec3> = (0..n * n).map(|i| glam::Vec3::new((i % n) as f32, 0.0, (i / n) as f32)).collect();
+    let mut indices = Vec::new();
+    for y in 0..n - 1 {
+        for x in 0..n - 1 {
+            let i = (y * n + x) as i32;
+            let quad = [i, i + n as i32, i + n as i32 + 1, i + 1];
+            if y * (n - 1) + x == 4 {
+                indices.extend(quad.iter().rev());
+            } else {
+                indices.extend(quad);
+            }
+        }
+    }
+    let uv_of = |v: i32| glam::Vec2::new(positions[v as usize].x, positions[v as usize].z);
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("grid");
+        let mut sample = OPolyMeshSample::new(positions.clone(), vec![4; (n - 1) * (n - 1)], indices.clone());
+        sample.uvs = Some(indices.iter().map(|&v| uv_of(v)).collect());
+        mesh.add_sample(&sample);
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let cli = |args: &[&std::ffi::OsStr]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli")).args(args).output().expect("run alembic-cli")
+    };
+    let out = cli(&["check".as_ref(), temp.path().as_os_str(), "--json".as_ref()]);
+    let diagnostics: serde_json::Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    let winding: Vec<_> = diagnostics.as_array().unwrap().iter().filter(|d| d["check"] == "winding").collect();
+    assert_eq!(winding.len(), 1, "{diagnostics}");
+    assert!(winding[0]["message"].as_str().unwrap().starts_with("1 of 9 faces"));
+
+    let fixed = NamedTempFile::new().expect("Failed to create temp file");
+    let out = cli(&["strip".as_ref(), temp.path().as_os_str(), fixed.path().as_os_str(), "--fix-winding".as_ref()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Fixed winding:     1 faces in 1 meshes"));
+
+    let archive = IArchive::open(fixed.path()).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("grid").expect("grid");
+    let mesh = IPolyMesh::new(&obj).expect("polymesh");
+    let sample = mesh.getSample(0).expect("sample");
+    assert!(sample.winding_report().is_consistent());
+    assert_eq!(&sample.face_indices[..16], &indices[..16]);
+    // UVs were reordered with the face
+    let uvs = mesh.get_uv_set("uv", 0).expect("uvs");
+    assert!(sample.face_indices.iter().zip(&uvs).all(|(&v, &uv)| uv == uv_of(v)));
+}
+
+#[test]
+fn test_cli_extract_subtree() {
+    use alembic::geom::IXform;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        // /set (animated +X) / building (+Y) / {box, copy -> box}, plus a sibling /set/other
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let tri = || OPolyMeshSample::new(vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y], vec![3], vec![0, 1, 2]);
+        let mut set = OXform::new("set");
+        set.set_time_sampling(ts);
+        for x in [10.0, 20.0] {
+            set.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::new(x, 0.0, 0.0)), true));
+        }
+        let mut building = OXform::new("building");
+        building.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::new(0.0, 5.0, 0.0)), true));
+        let mut mesh = OPolyMesh::new("box");
+        mesh.add_sample(&tri());
+        building.add_child(mesh.build());
+        let mut building = building.build();
+        building.add_instance_named("copy", "/set/building/box");
+        set.add_child(building);
+        let mut other = OPolyMesh::new("other");
+        other.add_sample(&tri());
+        set.add_child(other.build());
+        let mut root = OObject::new("");
+        root.add_child(set.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let run = |path: &str, out: &std::path::Path, bake: bool| {
+        let mut cmd = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"));
+        cmd.arg("extract").arg(temp.path()).arg(path).arg(out);
+        if bake {
+            cmd.arg("--bake");
+        }
+        let status = cmd.output().expect("run alembic-cli");
+        assert!(status.status.success(), "extract failed: {}", String::from_utf8_lossy(&status.stderr));
+    };
+    let translation = |archive: &IArchive, path: &str, index: usize| {
+        let obj = archive.findObject(path).expect(path);
+        IXform::new(&obj).expect("xform").getSample(index).expect("sample").matrix().w_axis.truncate()
+    };
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+
+    // Ancestors kept, siblings left out
+    run("/set/building", out.path(), false);
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(archive.getTop().getNumChildren(), 1);
+    assert!(archive.findObject("/set/other").is_none());
+    assert!(archive.findObject("/set/building/box").is_some());
+    assert_eq!(archive.findObject("/set/building/copy").unwrap().instance_source_path(), Some("/set/building/box"));
+    assert_eq!(translation(&archive, "/set", 1), glam::Vec3::new(20.0, 0.0, 0.0));
+    drop(archive);
+
+    // Baked: the subtree root carries the world transform, instances follow it
+    run("set/building", out.path(), true);
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    assert!(archive.findObject("/set").is_none());
+    assert_eq!(translation(&archive, "/building", 0), glam::Vec3::new(10.0, 5.0, 0.0));
+    assert_eq!(translation(&archive, "/building", 1), glam::Vec3::new(20.0, 5.0, 0.0));
+    assert_eq!(archive.findObject("/building/copy").unwrap().instance_source_path(), Some("/building/box"));
+    drop(archive);
+
+    // A mesh gets a wrapper Xform named after its parent
+    run("/set/building/box", out.path(), true);
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(translation(&archive, "/building", 1), glam::Vec3::new(20.0, 5.0, 0.0));
+    assert!(IPolyMesh::new(&archive.findObject("/building/box").unwrap()).is_some());
+    drop(archive);
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("extract")
+        .arg(temp.path())
+        .arg("/set/missing")
+        .arg(out.path())
+        .output()
+        .expect("run alembic-cli");
+    assert!(!status.status.success());
+}
+
+#[test]
+fn test_cli_query_patterns() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        // /root/{charA,charB,prop}/geo/body (mesh) next to /root/*/geo/rig (xform)
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut root_xform = OXform::new("root");
+        root_xform.add_sample(OXformSample::identity());
+        for name in ["charA", "charB", "prop"] {
+            let mut asset = OXform::new(name);
+            asset.add_sample(OXformSample::identity());
+            let mut geo = OXform::new("geo");
+            geo.add_sample(OXformSample::identity());
+            let mut body = OPolyMesh::new("body");
+            body.add_sample(&OPolyMeshSample::new(vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y], vec![3], vec![0, 1, 2]));
+            geo.add_child(body.build());
+            let mut rig = OXform::new("rig");
+            rig.add_sample(OXformSample::identity());
+            geo.add_child(rig.build());
+            asset.add_child(geo.build());
+            root_xform.add_child(asset.build());
+        }
+        let mut root = OObject::new("");
+        root.add_child(root_xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let cli = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .args(args)
+            .output()
+            .expect("run alembic-cli");
+        (output.status.success(), String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let file = temp.path().to_str().unwrap();
+
+    // Tree: matches plus their ancestors only
+    let (ok, out) = cli(&["tree", file, "/root/char*/geo/**[PolyMesh]"]);
+    assert!(ok);
+    assert_eq!(out.matches("body [PolyMesh]").count(), 2, "{}", out);
+    assert!(!out.contains("prop") && !out.contains("rig"), "{}", out);
+
+    // Dump: a bare word still filters by substring
+    let (ok, out) = cli(&["dump", file, "charB", "--json"]);
+    assert!(ok);
+    let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let paths: Vec<&str> = json["objects"].as_array().unwrap().iter().map(|o| o["path"].as_str().unwrap()).collect();
+    assert_eq!(paths, ["/root/charB", "/root/charB/geo", "/root/charB/geo/body", "/root/charB/geo/rig"]);
+
+    // Extract: every match, with shared ancestors
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+    let out_file = out.path().to_str().unwrap();
+    let (ok, _) = cli(&["extract", file, "/root/{charA,prop}/geo/body", out_file]);
+    assert!(ok);
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(archive.findObject("/root").unwrap().getNumChildren(), 2);
+    assert!(archive.findObject("/root/charA/geo/body").is_some());
+    assert!(archive.findObject("/root/prop/geo/body").is_some());
+    assert!(archive.findObject("/root/charA/geo/rig").is_none());
+    drop(archive);
+
+    // --bake needs exactly one match; bad patterns are errors
+    assert!(!cli(&["extract", file, "/root/*/geo/body", out_file, "--bake"]).0);
+    assert!(!cli(&["tree", file, "/root/{char"]).0);
+}
+
+#[test]
+fn test_cli_merge_archives() {
+    // Both write /shot (same transform) with a mesh `char` of different size;
+    // `a` also instances it, `b` adds /shot/fx and an instance of its own char
+    let write = |path: &std::path::Path, size: f32, extra: &str| {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let tri = |s: f32| OPolyMeshSample::new(vec![glam::Vec3::ZERO, glam::Vec3::X * s, glam::Vec3::Y * s], vec![3], vec![0, 1, 2]);
+        let mut shot = OXform::new("shot");
+        shot.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::X), true));
+        let mut mesh = OPolyMesh::new("char");
+        mesh.set_time_sampling(ts);
+        mesh.add_sample(&tri(size));
+        mesh.add_sample(&tri(size));
+        shot.add_child(mesh.build());
+        let mut other = OPolyMesh::new(extra);
+        other.add_sample(&tri(1.0));
+        shot.add_child(other.build());
+        let mut shot = shot.build();
+        shot.add_instance_named(&format!("{}_ref", extra), "/shot/char");
+        let mut root = OObject::new("");
+        root.add_child(shot);
+        archive.write_archive(&root).expect("Failed to write archive");
+    };
+    let a = NamedTempFile::new().expect("Failed to create temp file");
+    let b = NamedTempFile::new().expect("Failed to create temp file");
+    write(a.path(), 1.0, "layout");
+    write(b.path(), 2.0, "fx");
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+    let run = |policy: &str| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("merge")
+            .arg(a.path())
+            .arg(b.path())
+            .arg("-o")
+            .arg(out.path())
+            .args(["--on-collision", policy])
+            .output()
+            .expect("run alembic-cli")
+    };
+    let size = |archive: &IArchive, path: &str| {
+        let obj = archive.findObject(path).expect(path);
+        IPolyMesh::new(&obj).expect("polymesh").getSample(0).expect("sample").positions[1].x
+    };
+
+    // /shot is shared, the two chars collide
+    let status = run("error");
+    assert!(!status.status.success());
+    assert!(String::from_utf8_lossy(&status.stderr).contains("/shot/char"));
+
+    let status = run("rename");
+    assert!(status.status.success(), "merge failed: {}", String::from_utf8_lossy(&status.stderr));
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(archive.getTop().getNumChildren(), 1);
+    assert_eq!(archive.findObject("/shot").unwrap().getNumChildren(), 6);
+    assert_eq!(size(&archive, "/shot/char"), 1.0);
+    assert_eq!(size(&archive, "/shot/char_1"), 2.0);
+    assert_eq!(archive.findObject("/shot/layout_ref").unwrap().instance_source_path(), Some("/shot/char"));
+    assert_eq!(archive.findObject("/shot/fx_ref").unwrap().instance_source_path(), Some("/shot/char_1"));
+    // One uniform sampling shared by both inputs, plus the default
+    assert_eq!(archive.getNumTimeSamplings(), 2);
+    drop(archive);
+
+    let status = run("replace");
+    assert!(status.status.success(), "merge failed: {}", String::from_utf8_lossy(&status.stderr));
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(archive.findObject("/shot").unwrap().getNumChildren(), 5);
+    assert_eq!(size(&archive, "/shot/char"), 2.0);
+    assert!(archive.findObject("/shot/layout").is_some());
+    assert_eq!(archive.findObject("/shot/fx_ref").unwrap().instance_source_path(), Some("/shot/char"));
+}
+
+/// Built by cargo next to the test binaries from `examples/schema_plugin.rs`.
+#[cfg(feature = "plugins")]
+#[test]
+fn test_cli_schema_plugin() {
+    use alembic::ogawa::writer::OProperty;
+    use alembic::util::DataType;
+    let plugin = std::path::Path::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .parent()
+        .unwrap()
+        .join("examples")
+        .join(format!("{}schema_plugin{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX));
+    assert!(plugin.exists(), "example plugin not built: {}", plugin.display());
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut crowd = OObject::new("crowd");
+        crowd.meta_data.set("schema", "acme_Crowd_v1");
+        let mut props = OProperty::compound(".crowd");
+        let mut agent_type = OProperty::scalar("agentType", DataType::STRING);
+        agent_type.add_scalar_string("soldier");
+        props.add_child(agent_type);
+        let mut p = OProperty::array("P", DataType::VEC3F);
+        for _ in 0..2 {
+            p.add_array_sample(bytemuck::cast_slice(&[[0.0f32; 3]; 5]), &[5]);
+        }
+        props.add_child(p);
+        crowd.add_property(props);
+        let mut root = OObject::new("");
+        root.add_child(crowd);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    for command in ["tree", "stats", "meta"] {
+        let out = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("--plugin")
+            .arg(&plugin)
+            .arg(command)
+            .arg(temp.path())
+            .output()
+            .expect("run alembic-cli");
+        assert!(out.status.success(), "{} failed: {}", command, String::from_utf8_lossy(&out.stderr));
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(stdout.contains("5 soldier agents, 2 samples"), "{}: {}", command, stdout);
+    }
+}
+
+#[test]
+fn test_cli_manifest_verify() {
+    let write = |path: &std::path::Path, y: f32| {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("tri");
+        for frame in 0..3 {
+            let x = frame as f32;
+            mesh.add_sample(&OPolyMeshSample::new(
+                vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::new(x + 1.0, y, 0.0), glam::Vec3::new(x, 1.0, 0.0)],
+                vec![3],
+                vec![0, 1, 2],
+            ));
+        }
+        let mut xform = OXform::new("group");
+        xform.add_sample(OXformSample::from_matrix(glam::Mat4::IDENTITY, true));
+        xform.add_child(mesh.build());
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    };
+    let cli = |args: &[&std::ffi::OsStr]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("manifest")
+            .args(args)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let golden = NamedTempFile::new().expect("Failed to create temp file");
+    write(golden.path(), 0.0);
+    let dir = tempfile::tempdir().expect("temp dir");
+    let manifest = dir.path().join("golden.json");
+    let out = cli(&[golden.path().as_os_str(), "-o".as_ref(), manifest.as_os_str()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&manifest).unwrap()).unwrap();
+    let p = &json["objects"]["/group/tri"]["properties"][".geom/P"];
+    assert_eq!(p["samples"].as_array().unwrap().len(), 3);
+    assert_eq!(json["objects"]["/group/tri"]["schema"], POLYMESH_SCHEMA);
+
+    // Same data written again (and re-written by copy2) verifies clean
+    let same = NamedTempFile::new().expect("Failed to create temp file");
+    write(same.path(), 0.0);
+    let out = cli(&[same.path().as_os_str(), "--verify".as_ref(), manifest.as_os_str()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stdout));
+    let copy = NamedTempFile::new().expect("Failed to create temp file");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("copy2")
+        .arg(golden.path())
+        .arg(copy.path())
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success());
+    let out = cli(&[copy.path().as_os_str(), "--verify".as_ref(), manifest.as_os_str()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stdout));
+
+    // Changed positions fail with exit code 2 and name the sample
+    let changed = NamedTempFile::new().expect("Failed to create temp file");
+    write(changed.path(), 0.5);
+    let out = cli(&[changed.path().as_os_str(), "--verify".as_ref(), manifest.as_os_str()]);
+    assert_eq!(out.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("/group/tri .geom/P: sample 0 differs"), "{stdout}");
+}
+
+#[test]
+fn test_cli_diff() {
+    let write = |path: &std::path::Path, y: f32| {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::Vec3::ZERO, glam::Vec3::new(1.0, y, 0.0), glam::Vec3::Y],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    };
+    let diff = |a: &NamedTempFile, b: &NamedTempFile, extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("diff")
+            .arg(a.path())
+            .arg(b.path())
+            .args(extra)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let a = NamedTempFile::new().expect("Failed to create temp file");
+    write(a.path(), 0.0);
+    let b = NamedTempFile::new().expect("Failed to create temp file");
+    write(b.path(), 0.01);
+
+    let out = diff(&a, &a, &[]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stdout));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Identical"));
+
+    let out = diff(&a, &b, &[]);
+    assert_eq!(out.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("~ /tri .geom/P[0]: 1 values differ"), "{stdout}");
+
+    let out = diff(&a, &b, &["--tol", "0.1"]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stdout));
+
+    let out = diff(&a, &b, &["--tol", "nope"]);
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_copy2_keeps_skin_data() {
+    use alembic::geom::SkinSample;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    let positions = vec![
+        glam::Vec3::new(0.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 1.0, 0.0),
+        glam::Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let skin = SkinSample {
+        joint_indices: vec![0, 1, 0, 1, 1, 0, 1, 0],
+        joint_weights: vec![1.0, 0.0, 0.5, 0.5, 0.8, 0.2, 1.0, 0.0],
+        influences: 2,
+        rest_positions: Some(positions.clone()),
+        bind_matrices: vec![
+            glam::Mat4::IDENTITY,
+            glam::Mat4::from_translation(glam::Vec3::new(0.0, -1.0, 0.0)),
+        ],
+        geom_bind_transform: Some(glam::Mat4::from_scale(glam::Vec3::splat(2.0))),
+    };
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut mesh = OPolyMesh::new("skinned");
+        let mut sample = OPolyMeshSample::new(positions.clone(), vec![4], vec![0, 1, 2, 3]);
+        sample.uvs = Some(vec![glam::Vec2::ZERO; 4]);
+        mesh.add_sample(&sample);
+        for param in skin.arb_geom_params() {
+            mesh.add_arb_geom_param(&param);
+        }
+        for prop in skin.user_properties() {
+            mesh.add_user_property(prop);
+        }
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let check = |path: &std::path::Path| {
+        let archive = IArchive::open(path).expect("Failed to open archive");
+        let top = archive.getTop();
+        let obj = top.getChildByName("skinned").expect("mesh");
+        let mesh = IPolyMesh::new(&obj).expect("IPolyMesh");
+        let names = mesh.arb_geom_param_names();
+        for name in ["uv", "jointIndices", "jointWeights", "Pref"] {
+            assert!(names.iter().any(|n| n == name), "missing {name} in {names:?}");
+        }
+        assert_eq!(mesh.skin_sample(0).expect("skin"), skin);
+        assert_eq!(mesh.rest_positions(0).expect("Pref"), positions);
+    };
+    check(path);
+
+    // copy2 keeps the skin attributes
+    let copy = NamedTempFile::new().expect("Failed to create temp file");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("copy2")
+        .arg(path)
+        .arg(copy.path())
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "copy2 failed: {}", String::from_utf8_lossy(&status.stderr));
+    check(copy.path());
+}
+
+#[test]
+fn test_cli_copy2_keeps_unknown_pod_properties() {
+    use alembic::ogawa::writer::OProperty;
+    use alembic::util::DataType;
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut obj = OObject::new("thing");
+        let mut prop = OProperty::scalar("future", DataType::new(PlainOldDataType::Unknown, 2));
+        prop.unknown_pod = Some(14);
+        prop.add_scalar_sample(&[1, 2, 3, 4, 5]);
+        obj.properties.push(prop);
+        let mut root = OObject::new("");
+        root.add_child(obj);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let copy = NamedTempFile::new().expect("Failed to create temp file");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("copy2")
+        .arg(temp.path())
+        .arg(copy.path())
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "copy2 failed: {}", String::from_utf8_lossy(&status.stderr));
+
+    let archive = IArchive::open(copy.path()).expect("Failed to open copy");
+    let top = archive.getTop();
+    let obj = top.getChildByName("thing").expect("thing");
+    let props = obj.getProperties();
+    let prop = props.getPropertyByName("future").expect("future");
+    let raw = prop.asRaw().expect("raw");
+    assert_eq!((raw.pod_code(), raw.extent()), (14, 2));
+    assert_eq!(raw.getSample(0).unwrap(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_cli_copy2_keeps_sampled_arb_geom_params() {
+    use alembic::core::GeometryScope;
+    use alembic::geom::GeomParamSample;
+    use alembic::util::DataType;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    let positions = vec![
+        glam::Vec3::new(0.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 1.0, 0.0),
+        glam::Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let colors = |t: f32| vec![[t, 0.0, 0.0]; 4];
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut mesh = OPolyMesh::new("attrs");
+        for frame in 0..3 {
+            let mut sample = if frame == 0 {
+                OPolyMeshSample::new(positions.clone(), vec![4], vec![0, 1, 2, 3])
+            } else {
+                OPolyMeshSample::deformed(positions.clone())
+            };
+            // Cd changes every sample; st and temperature only on the first
+            sample.set_arb_geom_param(
+                "Cd",
+                GeomParamSample::from_values(DataType::VEC3F, &colors(frame as f32), None, GeometryScope::Vertex),
+            );
+            if frame == 0 {
+                sample.set_arb_geom_param(
+                    "st",
+                    GeomParamSample::from_values(
+                        DataType::VEC2F,
+                        &[[0.0f32, 0.0], [1.0, 1.0]],
+                        Some(vec![0, 1, 1, 0]),
+                        GeometryScope::FaceVarying,
+                    ),
+                );
+                sample.set_arb_geom_param(
+                    "temperature",
+                    GeomParamSample::from_values(DataType::FLOAT32, &[36.6f32], None, GeometryScope::Uniform),
+                );
+            }
+            mesh.add_sample(&sample);
+        }
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let check = |path: &std::path::Path| {
+        let archive = IArchive::open(path).expect("Failed to open archive");
+        let top = archive.getTop();
+        let obj = top.getChildByName("attrs").expect("mesh");
+        let mesh = IPolyMesh::new(&obj).expect("IPolyMesh");
+        assert_eq!(mesh.getNumSamples(), 3);
+        for frame in 0..3 {
+            let cd = mesh.arb_geom_param("Cd", frame).expect("Cd");
+            assert_eq!(cd.scope, GeometryScope::Vertex);
+            assert!(!cd.is_indexed);
+            assert_eq!(cd.values_as_f32(), colors(frame as f32).concat().as_slice());
+
+            let st = mesh.arb_geom_param("st", frame).expect("st");
+            assert_eq!(st.scope, GeometryScope::FaceVarying);
+            assert_eq!(st.indices.as_deref(), Some(&[0u32, 1, 1, 0][..]));
+            assert_eq!(st.expand_vec2()[1], glam::Vec2::ONE);
+
+            let temperature = mesh.arb_geom_param("temperature", frame).expect("temperature");
+            assert_eq!(temperature.scope, GeometryScope::Uniform);
+            assert_eq!(temperature.values_as_f32(), &[36.6]);
+        }
+    };
+    check(path);
+
+    // copy2 keeps the attributes
+    let copy = NamedTempFile::new().expect("Failed to create temp file");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("copy2")
+        .arg(path)
+        .arg(copy.path())
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "copy2 failed: {}", String::from_utf8_lossy(&status.stderr));
+    check(copy.path());
+}
+
+#[test]
+fn test_cli_copy2_retime() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    write_animated_triangle(temp.path(), 1001.0, 8);
+
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("copy2")
+        .arg(temp.path())
+        .arg(out.path())
+        .args(["--fps", "30", "--frames", "1002-1006"])
+        .output()
+        .expect("run alembic-cli");
+    assert!(status.status.success(), "{}", String::from_utf8_lossy(&status.stderr));
+    assert!(String::from_utf8_lossy(&status.stdout).contains("Frames:     5"));
+
+    let archive = IArchive::open(out.path()).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("tri").expect("tri");
+    let mesh = IPolyMesh::new(&obj).expect("mesh");
+    assert_eq!(mesh.getNumSamples(), 5);
+    // First output frame (1253/30 s) is source frame 1002.4
+    let x = mesh.getSample(0).expect("sample").positions[0].x;
+    assert!((x - 1.4).abs() < 1e-4, "{x}");
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("copy2")
+        .arg(temp.path())
+        .arg(out.path())
+        .args(["--fps", "-1"])
+        .output()
+        .expect("run alembic-cli");
+    assert!(!status.status.success());
+}
+
+#[test]
+fn test_cli_check() {
+    let check = |path: &std::path::Path, extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("check")
+            .arg(path)
+            .args(extra)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let good = NamedTempFile::new().expect("Failed to create temp file");
+    write_animated_triangle(good.path(), 1.0, 3);
+    let out = check(good.path(), &[]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stdout));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("0 errors, 0 warnings"));
+
+    let bad = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(bad.path()).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y],
+            vec![3],
+            vec![0, 1, 7],
+        ));
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let out = check(bad.path(), &[]);
+    assert_eq!(out.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("error[topology] /tri sample 0: face index 7 out of range (0..3)"), "{stdout}");
+
+    let out = check(bad.path(), &["--json"]);
+    let list: serde_json::Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    assert_eq!(list[0]["check"], "topology");
+    assert_eq!(list[0]["object"], "/tri");
+
+    let out = check(bad.path(), &["--max", "nope"]);
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_props() {
+    let file = NamedTempFile::new().expect("Failed to create temp file");
+    write_animated_triangle(file.path(), 1.0, 3);
+    let props = |extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("props")
+            .arg(file.path())
+            .args(extra)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let out = props(&["--type", "array", "--min-samples", "2", "--dtype", "float32x3", "--json"]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let list: serde_json::Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    let list = list.as_array().unwrap();
+    assert!(list.iter().any(|p| p["property"] == ".geom/P"), "{list:?}");
+    for p in list {
+        assert_eq!(p["type"], "array");
+        assert_eq!(p["dtype"], "float32x3");
+        assert!(p["samples"].as_u64().unwrap() >= 2);
+        assert_eq!(p["bytes"].as_u64().unwrap(), p["elements"].as_u64().unwrap() * 12);
+    }
+
+    let out = props(&["--name", ".faceIndices"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("int32"), "{stdout}");
+    assert!(stdout.contains("1 properties"), "{stdout}");
+
+    let out = props(&["--dtype", "vec3"]);
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_collections() {
+    use alembic::ogawa::writer::{OCollections, OCollectionsSample};
+    let file = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(file.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let mut layers = OCollections::new("layers");
+        layers.set_time_sampling(ts);
+        layers.add_collection("all", vec!["/hero".into(), "/set".into()]);
+        for members in [vec!["/hero".to_string()], vec!["/hero".into(), "/fx".into()]] {
+            let mut sample = OCollectionsSample::new();
+            sample.add_collection("fg", members);
+            layers.add_sample(&sample);
+        }
+        let mut root = OObject::new("");
+        root.add_child(layers.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let collections = |extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("collections")
+            .arg(file.path())
+            .args(extra)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let out = collections(&[]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("fg  1 members, animated (2 samples)"), "{stdout}");
+    assert!(stdout.contains("2 collections in 1 objects"), "{stdout}");
+
+    let out = collections(&["--time", "0.05", "--all-samples", "--json"]);
+    let list: serde_json::Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    let fg = list.as_array().unwrap().iter().find(|c| c["name"] == "fg").expect("fg");
+    assert_eq!(fg["object"], "/layers");
+    assert_eq!(fg["members"], serde_json::json!(["/hero", "/fx"]));
+    assert_eq!(fg["membership"][0]["members"], serde_json::json!(["/hero"]));
+    assert_eq!(fg["membership"][1]["time"], 1.0 / 24.0);
+
+    assert_eq!(collections(&["--time"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_echo() {
+    let (a, b, c) = (NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap());
+    write_animated_triangle(a.path(), 1.0, 3);
+    write_animated_triangle(b.path(), 1.0, 4);
+    {
+        // c: sample 0 of P moved by half a unit
+        let mut archive = OArchive::create(c.path()).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::Vec3::new(0.5, 0.0, 0.0), glam::Vec3::new(1.0, 0.0, 0.0), glam::Vec3::new(0.0, 1.0, 0.0)],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let echo = |file: &std::path::Path, extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("echo")
+            .arg(file)
+            .arg("/tri.P")
+            .args(extra)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let out = echo(a.path(), &["--sample", "1", "--format", "floats"]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("/tri .geom/P  array float32_t[3]  3 samples"), "{stdout}");
+    assert!(stdout.contains("sample 1  dims [3]  36 bytes"), "{stdout}");
+    assert!(stdout.contains("[1] (2, 0, 0)"), "{stdout}");
+
+    let out = echo(a.path(), &["--sample", "0"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 80 3f"), "{stdout}");
+
+    let b_path = b.path().to_str().unwrap();
+    let out = echo(a.path(), &["--sample", "2", "--compare", b_path]);
+    assert_eq!(out.status.code(), Some(2), "sample counts differ");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("sample 2: identical, 36 bytes"));
+
+    let out = echo(a.path(), &["--sample", "0", "--compare", c.path().to_str().unwrap()]);
+    assert_eq!(out.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("sample 0: 1 of 36 bytes differ"), "{stdout}");
+    assert!(stdout.contains("+ 00000000  00 00 00 3f"), "{stdout}");
+
+    assert_eq!(echo(a.path(), &["--sample", "9"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_hash() {
+    let (a, b, c) = (NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap());
+    write_animated_triangle(a.path(), 1.0, 3);
+    write_animated_triangle(b.path(), 1.0, 3);
+    write_animated_triangle(c.path(), 1.0, 4);
+    let hash = |path: &std::path::Path, extra: &[&str]| {
+        let out = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("hash")
+            .arg(path)
+            .args(extra)
+            .output()
+            .expect("run alembic-cli");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        serde_json::from_slice::<serde_json::Value>(&out.stdout).expect("valid JSON")
+    };
+
+    let (ha, hb, hc) = (hash(a.path(), &["--json"]), hash(b.path(), &["--json"]), hash(c.path(), &["--json"]));
+    assert!(ha["digest"].is_string());
+    assert_eq!(ha["digest"], hb["digest"]);
+    assert_ne!(ha["digest"], hc["digest"]);
+    assert_eq!(ha["objects"][1]["path"], "/tri");
+
+    let with_props = hash(a.path(), &["--json", "--properties"]);
+    let props = &with_props["objects"][1]["properties"];
+    assert!(props[".geom/P"].is_string(), "{props}");
+    assert_eq!(props[".geom/P"].as_str().unwrap().len(), 32);
+
+    // Text output starts with the archive digest
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .arg("hash")
+        .arg(a.path())
+        .output()
+        .expect("run alembic-cli");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with(ha["digest"].as_str().unwrap()), "{stdout}");
+}
+
+#[test]
+fn test_cli_make_and_apply_patch() {
+    let (v1, v2) = (NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap());
+    write_animated_triangle(v1.path(), 1.0, 3);
+    write_animated_triangle(v2.path(), 1.0, 5);
+    let dir = tempfile::tempdir().expect("temp dir");
+    let patch = dir.path().join("v1_v2.abcpatch");
+    let rebuilt = dir.path().join("v2.abc");
+    let run = |args: &[&std::ffi::OsStr]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli")).args(args).output().expect("run alembic-cli")
+    };
+
+    let out = run(&["make-patch".as_ref(), v1.path().as_os_str(), v2.path().as_os_str(), patch.as_os_str()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Added:"));
+
+    let out = run(&["apply-patch".as_ref(), v1.path().as_os_str(), patch.as_os_str(), rebuilt.as_os_str()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let archive = IArchive::open(&rebuilt).expect("open rebuilt archive");
+    let top = archive.getTop();
+    let mesh_obj = top.getChildByName("tri").expect("tri");
+    assert_eq!(IPolyMesh::new(&mesh_obj).expect("mesh").getNumSamples(), 5);
+
+    // Wrong base is rejected
+    let out = run(&["apply-patch".as_ref(), v2.path().as_os_str(), patch.as_os_str(), rebuilt.as_os_str()]);
+    assert!(!out.status.success());
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn test_cli_pack_and_unpack() {
+    let input = NamedTempFile::new().unwrap();
+    write_animated_triangle(input.path(), 1.0, 5);
+    let dir = tempfile::tempdir().expect("temp dir");
+    let packed = dir.path().join("packed.abc");
+    let restored = dir.path().join("restored.abc");
+    let run = |args: &[&std::ffi::OsStr]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli")).args(args).output().expect("run alembic-cli")
+    };
+
+    let out = run(&["pack".as_ref(), input.path().as_os_str(), packed.as_os_str(), "--codec".as_ref(), "zlib".as_ref()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Verified"));
+    let archive = IArchive::open(&packed).expect("open packed archive");
+    assert_eq!(archive.getArchiveMetaData().get("_ai_BlockCompression"), Some("zlib"));
+
+    let out = run(&["unpack".as_ref(), packed.as_os_str(), restored.as_os_str()]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let archive = IArchive::open(&restored).expect("open restored archive");
+    assert!(archive.getArchiveMetaData().get("_ai_BlockCompression").is_none());
+    let top = archive.getTop();
+    let mesh_obj = top.getChildByName("tri").expect("tri");
+    assert_eq!(IPolyMesh::new(&mesh_obj).expect("mesh").getNumSamples(), 5);
+
+    let out = run(&["pack".as_ref(), input.path().as_os_str(), packed.as_os_str(), "--codec".as_ref(), "brotli".as_ref()]);
+    assert!(!out.status.success());
+}
+
+#[test]
+fn test_cli_histo() {
+    use alembic::ogawa::writer::{OPoints, OPointsSample};
+
+    // Two samples: widths 0.1..0.4 then one sub-threshold width in the second
+    let file = NamedTempFile::new().expect("Failed to create temp file");
+    let mut archive = OArchive::create(file.path()).expect("Failed to create archive");
+    let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+    let mut points = OPoints::new("pts");
+    points.set_time_sampling(ts);
+    for widths in [[0.1, 0.2, 0.3, 0.4], [0.1, 0.2, 0.3, 0.0001]] {
+        let mut sample = OPointsSample::new(vec![glam::Vec3::ZERO; 4], vec![0, 1, 2, 3]);
+        sample.widths = Some(widths.to_vec());
+        sample.velocities = Some(vec![glam::Vec3::new(3.0, 4.0, 0.0); 4]);
+        points.add_sample(&sample);
+    }
+    let mut root = OObject::new("");
+    root.add_child(points.build());
+    archive.write_archive(&root).expect("Failed to write archive");
+
+    let histo = |extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .arg("histo")
+            .arg(file.path())
+            .args(extra)
+            .output()
+            .expect("run alembic-cli")
+    };
+
+    let out = histo(&["/pts.widths", "--bins", "4", "--json"]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    assert_eq!(report["property"], ".geom/.widths");
+    assert_eq!(report["samples"], 2);
+    assert_eq!(report["stats"]["count"], 8);
+    assert_eq!(report["bins"].as_array().unwrap().len(), 4);
+    let binned: u64 = report["bins"].as_array().unwrap().iter().map(|b| b["count"].as_u64().unwrap()).sum();
+    assert_eq!(binned, 8);
+
+    // Velocities are binned by magnitude
+    let out = histo(&["/pts.geom/.velocities", "--json"]);
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    assert_eq!(report["measure"], "magnitude");
+    assert!((report["stats"]["max"].as_f64().unwrap() - 5.0).abs() < 1e-6);
+
+    // QC threshold: the tiny width fails in sample 1 only
+    let out = histo(&["/pts.widths", "--below", "0.001"]);
+    assert_eq!(out.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("FAIL: 1 values below") && stdout.contains("in samples 1"), "{stdout}");
+    assert!(histo(&["/pts.widths", "--below", "0.00001"]).status.success());
+
+    assert_eq!(histo(&["/pts.nope"]).status.code(), Some(1));
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn test_cli_serve() {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    /// Kills the server when the test ends, pass or fail.
+    struct Server(std::process::Child);
+    impl Drop for Server {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    let dir = tempfile::tempdir().expect("temp dir");
+    let input = dir.path().join("shot.abc");
+    write_animated_triangle(&input, 1.0, 2);
+
+    let mut server = Server(
+        std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+            .args(["serve".as_ref(), input.as_os_str(), "--bind".as_ref(), "127.0.0.1:0".as_ref()])
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("run alembic-cli"),
+    );
+    // "Serving 1 archive(s) on http://127.0.0.1:<port>"
+    let mut line = String::new();
+    BufReader::new(server.0.stdout.take().unwrap()).read_line(&mut line).unwrap();
+    let addr = line.trim().rsplit("http://").next().unwrap().to_string();
+
+    // Raw HTTP/1.1 GET; returns the response head and body
+    let fetch = |target: &str| {
+        let mut stream = std::net::TcpStream::connect(&addr).expect("connect");
+        write!(stream, "GET {} HTTP/1.1\r\nHost: test\r\n\r\n", target).unwrap();
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).unwrap();
+        let split = raw.windows(4).position(|w| w == b"\r\n\r\n").expect("response head");
+        (String::from_utf8_lossy(&raw[..split]).into_owned(), raw[split + 4..].to_vec())
+    };
+
+    let (head, body) = fetch("/manifest/shot.abc");
+    assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+    let manifest: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(manifest.to_string().contains("/tri"), "{}", manifest);
+
+    // Escapes that used to panic a worker, more times than there are workers
+    for _ in 0..8 {
+        let (head, _) = fetch("/object/shot.abc/%a\u{e9}");
+        assert!(head.starts_with("HTTP/1.1 404"), "{}", head);
+    }
+
+    let (head, body) = fetch("/thumbnail/shot.abc/tri?size=64");
+    assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+    assert!(head.contains("Content-Type: image/png"), "{}", head);
+    assert!(body.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[test]
+fn test_cli_watch_once() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let inbox = dir.path().join("inbox");
+    std::fs::create_dir(&inbox).unwrap();
+    write_animated_triangle(&inbox.join("good.abc"), 1.0, 2);
+    write_animated_triangle(&inbox.join("also_good.abc"), 1.0, 1);
+    std::fs::write(inbox.join("broken.abc"), b"not an archive").unwrap();
+    let bad_topology = inbox.join("bad_topology.abc");
+    {
+        let mut archive = OArchive::create(&bad_topology).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("body_GEO");
+        mesh.add_sample(&OPolyMeshSample::new(vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y], vec![3], vec![0, 1, 5]));
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let rules = dir.path().join("rules.toml");
+    std::fs::write(&rules, "[watch]\nreports = \"reports\"  # next to the files\n\n[naming]\nmesh = [\"tri\", \"*_GEO\"]\n").unwrap();
+
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .args(["watch".as_ref(), inbox.as_os_str(), "--rules".as_ref(), rules.as_os_str(), "--once".as_ref()])
+        .output()
+        .expect("run alembic-cli");
+    // Exit code 2: at least one archive failed
+    assert_eq!(out.status.code(), Some(2), "{}", String::from_utf8_lossy(&out.stderr));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("4 file(s) checked, 2 failed"), "{}", stdout);
+
+    let report = |name: &str| -> serde_json::Value {
+        let text = std::fs::read_to_string(inbox.join("reports").join(format!("{}.report.json", name))).unwrap();
+        serde_json::from_str(&text).unwrap()
+    };
+    assert_eq!(report("good.abc")["status"], "pass");
+    assert_eq!(report("good.abc")["objects"], 1);
+    assert_eq!(report("broken.abc")["status"], "fail");
+    assert_eq!(report("broken.abc")["issues"][0]["check"], "open");
+    // Geometry findings come from the same lint as `alembic check`
+    let shared = alembic::validate::validate_archive(&bad_topology);
+    assert_eq!(shared.len(), 1, "{:?}", shared);
+    let issue = &report("bad_topology.abc")["issues"][0];
+    assert_eq!(issue["check"], shared[0].check.as_str());
+    assert_eq!(issue["object"], "/body_GEO");
+    assert_eq!(issue["sample"], 0);
+    assert_eq!(issue["message"], shared[0].message.as_str());
+
+    // A malformed rules file is an error, not a silent default
+    std::fs::write(&rules, "[naming]\nmesh = [\"tri\"\n").unwrap();
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_alembic-cli"))
+        .args(["watch".as_ref(), inbox.as_os_str(), "--rules".as_ref(), rules.as_os_str(), "--once".as_ref()])
+        .output()
+        .expect("run alembic-cli");
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("line 2"));
+}
@@ -1,9 +1,10 @@
 //! Main application state and UI
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
 use std::sync::Arc;
-use std::time::Instant;
+use web_time::Instant;
 
 /// Format FPS for display (hide decimals for whole numbers)
 fn format_fps(fps: f32) -> String {
@@ -14,29 +15,56 @@ fn format_fps(fps: f32) -> String {
     }
 }
 
+/// Format a scene frame number (sub-frames with up to two decimals)
+fn format_frame(frame: f64) -> String {
+    if (frame - frame.round()).abs() < 1e-3 {
+        format!("{:.0}", frame)
+    } else {
+        format!("{:.2}", frame)
+    }
+}
+
 use egui::{Color32, RichText, TopBottomPanel, CentralPanel, SidePanel};
 use glam::{Mat4, Vec3};
 
 use standard_surface::{StandardSurfaceParams, Vertex};
 
+use super::audio::{AudioPlayer, AudioTrack, WavClip};
+use super::keymap::{Action, KeyChord, Keymap, KeymapPreset};
+use super::frame_stats::{FrameStats, StatMetric};
+use super::exposure::{Exposure, MAX_KELVIN, MIN_KELVIN, NEUTRAL_KELVIN};
+use super::load_log::LoadLog;
+use super::nav_device::{self, DeviceKind, NavDevices};
+use super::outliner::{Outliner, OutlinerEvent};
+use super::renderer::DofLens;
 use super::mesh_converter;
-use super::settings::Settings;
+use super::scene_scale::{SceneScale, SceneUnit};
+use super::selection::{SelectionSet, Selection};
+use super::settings::{DisplayMode, Settings};
 use super::viewport::Viewport;
 
 /// Scene hierarchy node
 #[derive(Clone, Debug)]
 pub struct SceneNode {
     pub name: String,
+    /// Full archive path (selection / hide key)
+    pub path: String,
     pub node_type: String,
     pub children: Vec<SceneNode>,
+    /// Vertex / face counts of the first sample (outliner badges)
+    pub verts: Option<usize>,
+    pub faces: Option<usize>,
 }
 
 impl SceneNode {
     pub fn new(name: &str, node_type: &str) -> Self {
         Self {
             name: name.to_string(),
+            path: String::new(),
             node_type: node_type.to_string(),
             children: Vec::new(),
+            verts: None,
+            faces: None,
         }
     }
 }
@@ -57,17 +85,33 @@ pub struct ViewerApp {
     // File state
     current_file: Option<PathBuf>,
     pending_file: Option<PathBuf>,
+    /// Browser file dialog result (name, contents)
+    #[cfg(target_arch = "wasm32")]
+    upload_rx: Option<Receiver<(String, Vec<u8>)>>,
     pending_hdr_file: Option<PathBuf>,
-    archive: Option<Arc<crate::abc::IArchive>>,
+    archive: Option<Arc<alembic::abc::IArchive>>,
     
     // Animation state
     num_samples: usize,
     current_frame: usize,
+    applied_frame: Option<usize>,  // Last sample uploaded to the renderer (motion blur steps)
     playing: bool,
     playback_dir: i32, // 1 = forward, -1 = backward
     last_frame_time: Instant,
+    playback_pos: f64,  // Fractional sample position while playing interpolated
     scene_is_static: bool,
+    rest_pose_meshes: usize,  // Meshes with Pref in the last applied frame
+    load_log: LoadLog,  // Per-object conversion warnings (deduplicated) + log window
     last_scene_hash: Option<u64>,
+    topology_changes: super::topology::TopologyChanges,
+    // Per-frame statistics for the timeline graph (computed in the background)
+    frame_stats: Option<FrameStats>,
+    frame_stats_rx: Option<Receiver<FrameStats>>,
+    /// Particle trails with the (frame, length) they were built for
+    particle_trails: Option<((usize, u32), super::trails::ParticleTrails)>,
+    /// Soundtrack placed on the timeline, and its output stream
+    audio: Option<AudioTrack>,
+    audio_player: AudioPlayer,
     
     // UI state
     status_message: String,
@@ -79,10 +123,15 @@ pub struct ViewerApp {
     vertex_count: usize,
     face_count: usize,
     scene_bounds: Option<mesh_converter::Bounds>,
-    scene_tree: Vec<SceneNode>,
+    /// Unit and size of the loaded scene (clip planes, zoom range, grid)
+    scene_scale: SceneScale,
+    /// Detect the scene scale once the first frame of a new file arrives
+    scale_pending: bool,
+    outliner: Outliner,
     selected_object: Option<String>,
     object_filter: String,  // Wildcard filter for hierarchy (e.g., "wheel*")
-    expanded_nodes: HashSet<String>,  // Track expanded tree nodes (Shift+click = recursive)
+    selection: Selection,  // Multi-selection + hidden objects (Ctrl+click in tree)
+    new_set_name: String,  // Name field for saving selection sets
 
     // Scene cameras
     scene_cameras: Vec<mesh_converter::SceneCamera>,
@@ -106,49 +155,29 @@ pub struct ViewerApp {
 
     // Auto turntable state
     turntable_last_input: Instant,  // Last time user moved camera
-}
 
-/// Guess material properties from object path for auto-materialization
-fn guess_material_from_path(path: &str) -> (Vec3, f32, f32, f32, f32) {
-    // Returns: (base_color, roughness, metallic, transmission, ior)
-    let lower = path.to_lowercase();
-    
-    // Glass
-    if lower.contains("glass") || lower.contains("windshield") || lower.contains("window") {
-        return (Vec3::new(0.9, 0.95, 1.0), 0.0, 0.0, 0.95, 1.5);
-    }
-    
-    // Metal/Chrome
-    if lower.contains("chrome") || lower.contains("metal") || lower.contains("rim") 
-        || lower.contains("nuts") || lower.contains("bolt") || lower.contains("steel")
-        || lower.contains("brake_disc") || lower.contains("baraban") {
-        return (Vec3::new(0.8, 0.8, 0.85), 0.3, 1.0, 0.0, 1.5);
-    }
-    
-    // Rubber/Tire
-    if lower.contains("tire") || lower.contains("tyre") || lower.contains("rubber") {
-        return (Vec3::new(0.05, 0.05, 0.05), 0.9, 0.0, 0.0, 1.5);
-    }
-    
-    // Plastic
-    if lower.contains("plastic") || lower.contains("grill") || lower.contains("bumper") {
-        return (Vec3::new(0.2, 0.2, 0.22), 0.4, 0.0, 0.0, 1.5);
-    }
-    
-    // Paint/Body -> metallic paint
-    if lower.contains("paint") || lower.contains("body") {
-        return (Vec3::new(0.7, 0.1, 0.1), 0.3, 0.9, 0.0, 1.5); // Red metallic
-    }
-    
-    // Default: use path hash to pick a random material type
-    let hash: u32 = path.bytes().fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32));
-    match hash % 5 {
-        0 => (Vec3::new(0.9, 0.95, 1.0), 0.0, 0.0, 0.95, 1.5), // glass
-        1 => (Vec3::new(0.8, 0.8, 0.85), 0.3, 1.0, 0.0, 1.5),  // metal
-        2 => (Vec3::new(0.2, 0.2, 0.22), 0.4, 0.0, 0.0, 1.5),  // plastic
-        3 => (Vec3::new(0.15, 0.08, 0.05), 0.7, 0.0, 0.0, 1.4), // leather
-        _ => (Vec3::new(0.05, 0.05, 0.05), 0.9, 0.0, 0.0, 1.5), // rubber
-    }
+    // One-shot startup overrides from the command line
+    launch: super::LaunchOptions,
+
+    // Custom viewport overlays (shared with the embedder via LaunchOptions)
+    overlays: super::OverlayHandle,
+    camera_path_overlay_keys: usize,  // Camera path samples drawn in the overlay
+
+    // Camera bookmarks / fly-through recording
+    camera_path: super::bookmarks::CameraPathRecorder,
+    bookmark_name: String,
+    pt_preset_name: String,  // Name field for saving path tracer presets
+
+    // "Export Preview" window settings (Some = window open)
+    preview_export: Option<super::export::PreviewOptions>,
+
+    // Keymap editor / quick menu
+    show_keymap: bool,
+    rebinding: Option<Action>,
+    quick_menu: Option<egui::Pos2>,
+
+    // SpaceMouse / gamepad input threads
+    nav_devices: NavDevices,
 }
 
 impl ViewerApp {
@@ -349,29 +378,70 @@ impl ViewerApp {
         (acc, mesh_hashes, curve_hashes, point_hashes)
     }
     pub fn new(
-        _cc: &eframe::CreationContext<'_>,
+        cc: &eframe::CreationContext<'_>,
         initial_file: Option<PathBuf>,
+        launch: super::LaunchOptions,
         trace_guard: Option<tracing_chrome::FlushGuard>,
     ) -> Self {
-        let settings = Settings::load();
+        let mut settings = Settings::load();
+        let overlays = launch.overlays.clone().unwrap_or_default();
+        overlays.attach(&cc.egui_ctx);
+
+        // CLI overrides take precedence over persisted settings, for this
+        // session only
+        settings.apply_session_overrides(|settings| {
+            if let Some(grid) = launch.grid {
+                settings.show_grid = grid;
+            }
+            if let Some([r, g, b]) = launch.background {
+                settings.background_color = [r, g, b, settings.background_color[3]];
+            }
+            if let Some(pt) = launch.path_tracing {
+                settings.path_tracing = pt;
+                if pt {
+                    settings.show_wireframe = false;
+                }
+            }
+            if let Some(name) = &launch.pt_preset {
+                if !settings.apply_pt_preset(name) {
+                    let known: Vec<&str> = settings.pt_presets.iter().map(|p| p.name.as_str()).collect();
+                    tracing::warn!("Unknown path tracer preset: {} (available: {})", name, known.join(", "));
+                }
+            }
+        });
         
         // Use last file if no initial file provided
         let pending = initial_file.or_else(|| settings.last_file.clone());
         
+        let mut viewport = Viewport::new();
+        viewport.keymap = Keymap::from_settings(settings.keymap_preset, &settings.key_bindings);
+
         Self {
-            viewport: Viewport::new(),
+            viewport,
             initialized: false,
             settings,
             current_file: None,
             pending_file: pending,
+            #[cfg(target_arch = "wasm32")]
+            upload_rx: None,
             pending_hdr_file: None,
             archive: None,
             num_samples: 0,
             current_frame: 0,
+            applied_frame: None,
+            topology_changes: Default::default(),
+            frame_stats: None,
+            frame_stats_rx: None,
+            particle_trails: None,
+            audio: None,
+            audio_player: AudioPlayer::default(),
             playing: false,
             playback_dir: 1,
             last_frame_time: Instant::now(),
+            playback_pos: 0.0,
             scene_is_static: false,
+            rest_pose_meshes: 0,
+            load_log: LoadLog::default(),
             last_scene_hash: None,
             status_message: "Ready".into(),
             frame_times: VecDeque::with_capacity(20),
@@ -380,10 +450,13 @@ impl ViewerApp {
             vertex_count: 0,
             face_count: 0,
             scene_bounds: None,
-            scene_tree: Vec::new(),
+            scene_scale: SceneScale::default(),
+            scale_pending: false,
+            outliner: Outliner::default(),
             selected_object: None,
             object_filter: String::new(),
-            expanded_nodes: HashSet::new(),
+            selection: Selection::default(),
+            new_set_name: String::new(),
             scene_cameras: Vec::new(),
             active_camera: None,
             scene_lights: Vec::new(),
@@ -395,6 +468,17 @@ impl ViewerApp {
             _trace_guard: trace_guard,
             needs_scene_reload: false,
             turntable_last_input: Instant::now(),
+            overlays,
+            camera_path_overlay_keys: 0,
+            launch,
+            camera_path: super::bookmarks::CameraPathRecorder::new(24.0),
+            bookmark_name: String::new(),
+            pt_preset_name: String::new(),
+            preview_export: None,
+            show_keymap: false,
+            rebinding: None,
+            quick_menu: None,
+            nav_devices: NavDevices::default(),
         }
     }
 
@@ -407,7 +491,7 @@ impl ViewerApp {
         let mut fonts = egui::FontDefinitions::default();
         fonts.font_data.insert(
             "noto_sans".to_owned(),
-            egui::FontData::from_static(include_bytes!("../../assets/NotoSans-Regular.ttf")).into(),
+            egui::FontData::from_static(include_bytes!("../assets/NotoSans-Regular.ttf")).into(),
         );
         // Use Noto Sans as primary font
         fonts.families
@@ -431,16 +515,27 @@ impl ViewerApp {
         egui::MenuBar::new().ui(ui, |ui| {
             ui.menu_button("File", |ui| {
                 if ui.button("Open...").clicked() {
-                    self.open_file_dialog();
+                    self.open_file_dialog(ctx);
                     ui.close();
                 }
                 
                 // Export As... (only enabled when file is loaded)
-                let has_file = self.current_file.is_some();
+                let has_file = self.current_file.is_some() && super::dialogs::HAS_PATHS;
                 if ui.add_enabled(has_file, egui::Button::new("Export As...")).clicked() {
                     self.export_file_dialog();
                     ui.close();
                 }
+                if ui.add_enabled(has_file, egui::Button::new("Export Preview..."))
+                    .on_hover_text("Bake visible meshes (decimated, world space) to a small .abc")
+                    .clicked()
+                {
+                    self.preview_export = Some(super::export::PreviewOptions {
+                        start: 0,
+                        end: self.num_samples.saturating_sub(1),
+                        resolution: 64,
+                    });
+                    ui.close();
+                }
                 
                 // Recent files submenu
                 if !recent.is_empty() {
@@ -480,12 +575,60 @@ impl ViewerApp {
                         self.settings.save();
                     }
                 }
+                if ui.checkbox(&mut self.settings.show_perf_overlay, "Performance Overlay").changed() {
+                    self.settings.save();
+                }
+                if ui.checkbox(&mut self.settings.show_stats_graph, "Timeline Graph")
+                    .on_hover_text("Per-frame vertex/point counts, bounds volume and max velocity under the timeline")
+                    .changed()
+                {
+                    self.settings.save();
+                }
+                ui.checkbox(&mut self.load_log.open, "Load Log");
+                ui.menu_button("Rest Pose (Pref)", |ui| {
+                    if self.rest_pose_meshes == 0 {
+                        ui.label(RichText::new("No meshes with Pref").weak());
+                    } else {
+                        ui.label(format!("{} mesh(es) with Pref", self.rest_pose_meshes));
+                    }
+                    let mut changed = ui.checkbox(&mut self.settings.show_rest_pose, "Show Rest Pose")
+                        .on_hover_text("Display meshes at their rest positions (Pref)")
+                        .changed();
+                    ui.add_enabled_ui(self.settings.show_rest_pose, |ui| {
+                        changed |= ui.add(egui::Slider::new(&mut self.settings.rest_pose_blend, 0.0..=1.0)
+                            .text("Animated ↔ Rest")).changed();
+                    });
+                    if changed {
+                        self.settings.save();
+                        self.request_frame(self.current_frame);
+                    }
+                });
+                ui.menu_button("Particle Trails", |ui| {
+                    let mut changed = ui.checkbox(&mut self.settings.show_particle_trails, "Show Trails").changed();
+                    changed |= ui.add(egui::Slider::new(&mut self.settings.trail_length, 1..=100).text("Length (frames)")).changed();
+                    changed |= ui.checkbox(&mut self.settings.trail_color_by_speed, "Color by Speed").changed();
+                    if changed {
+                        self.settings.save();
+                    }
+                });
                 ui.separator();
                 if ui.button("Reset Camera").clicked() {
                     self.viewport.camera.reset();
                     self.active_camera = None;
                     ui.close();
                 }
+                ui.menu_button("Bookmarks", |ui| {
+                    self.bookmarks_menu(ui);
+                });
+                ui.menu_button("Navigation Devices", |ui| {
+                    self.nav_devices_menu(ui);
+                });
+                ui.menu_button("Scene Scale", |ui| {
+                    self.scene_scale_menu(ui);
+                });
+                ui.menu_button("Camera Path", |ui| {
+                    self.camera_path_menu(ui);
+                });
                 
                 // Scene cameras submenu
                 if !self.scene_cameras.is_empty() {
@@ -511,6 +654,10 @@ impl ViewerApp {
             });
 
             ui.menu_button("Help", |ui| {
+                if ui.button("Keymap...").clicked() {
+                    self.show_keymap = true;
+                    ui.close();
+                }
                 if ui.button("About").clicked() {
                     self.status_message = "Alembic Viewer v0.1.0".into();
                     ui.close();
@@ -519,234 +666,196 @@ impl ViewerApp {
         });
     }
 
-    /// Hierarchy panel - object tree
-    fn hierarchy_panel(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Hierarchy");
+    /// Navigation devices submenu: enable SpaceMouse / gamepad, speed, connected devices
+    fn nav_devices_menu(&mut self, ui: &mut egui::Ui) {
+        if !nav_device::SUPPORTED {
+            ui.label(RichText::new("Navigation devices are only supported on Linux").weak());
+            return;
+        }
+        let mut changed = ui.checkbox(&mut self.settings.spacemouse_enabled, "SpaceMouse").changed();
+        changed |= ui.checkbox(&mut self.settings.gamepad_enabled, "Gamepad").changed();
+        changed |= ui.add(egui::Slider::new(&mut self.settings.nav_device_speed, 0.1..=4.0).text("Speed")).changed();
+        if changed {
+            self.settings.save();
+        }
         ui.separator();
+        let names = self.nav_devices.names();
+        if names.is_empty() {
+            ui.label(RichText::new("No devices connected").weak());
+        }
+        for (kind, name) in names {
+            let icon = match kind {
+                DeviceKind::SpaceMouse => "🖱",
+                DeviceKind::Gamepad => "🎮",
+            };
+            ui.label(format!("{} {}", icon, name));
+        }
+        for error in &self.nav_devices.errors {
+            ui.label(RichText::new(error).color(Color32::from_rgb(220, 120, 80)).small());
+        }
+    }
 
-        // Filter input
+    /// Bookmarks submenu: add current view, restore or delete saved views
+    fn bookmarks_menu(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            ui.label("Filter:");
-            ui.add(egui::TextEdit::singleline(&mut self.object_filter)
-                .hint_text("wheel*")
-                .desired_width(ui.available_width()));
-        });
-        if !self.object_filter.is_empty()
-            && ui.small_button("✕ Clear").clicked() {
-                self.object_filter.clear();
+            ui.add(egui::TextEdit::singleline(&mut self.bookmark_name)
+                .hint_text("name")
+                .desired_width(120.0));
+            if ui.button("Add").clicked() {
+                let name = if self.bookmark_name.trim().is_empty() {
+                    format!("View {}", self.settings.camera_bookmarks.len() + 1)
+                } else {
+                    self.bookmark_name.trim().to_string()
+                };
+                let bookmark = super::bookmarks::CameraBookmark::capture(&name, &self.viewport.camera);
+                // Same name replaces the existing bookmark
+                self.settings.camera_bookmarks.retain(|b| b.name != name);
+                self.settings.camera_bookmarks.push(bookmark);
+                self.settings.save();
+                self.bookmark_name.clear();
+                self.status_message = format!("Bookmark saved: {}", name);
             }
-        ui.separator();
-
-        let filter = self.object_filter.to_lowercase();
-
-        egui::ScrollArea::vertical()
-            .auto_shrink([false, false])
-            .show(ui, |ui| {
-            let tree = std::mem::take(&mut self.scene_tree);
-            let mut selected = self.selected_object.clone();
-            let mut expanded = std::mem::take(&mut self.expanded_nodes);
-            for node in &tree {
-                Self::show_tree_node(ui, node, &mut selected, &filter, &mut expanded, 0);
-            }
-            self.selected_object = selected;
-            self.expanded_nodes = expanded;
-            self.scene_tree = tree;
         });
-    }
-    
-    /// Check if name matches wildcard filter (e.g., "wheel*" matches "wheel_lb")
-    fn matches_filter(name: &str, filter: &str) -> bool {
-        if filter.is_empty() {
-            return true;
-        }
-        let name_lower = name.to_lowercase();
-        // Split by * for wildcard matching
-        let parts: Vec<&str> = filter.split('*').collect();
-        
-        if parts.len() == 1 {
-            // No wildcard - exact substring match
-            return name_lower.contains(filter);
+        if self.settings.camera_bookmarks.is_empty() {
+            ui.label(RichText::new("No bookmarks").weak());
+            return;
         }
-        
-        let mut pos = 0;
-        for (i, part) in parts.iter().enumerate() {
-            if part.is_empty() {
-                continue;
-            }
-            if let Some(found) = name_lower[pos..].find(part) {
-                if i == 0 && found != 0 {
-                    // First part must match at start (no leading *)
-                    return false;
+        ui.separator();
+        let mut remove = None;
+        for (i, bookmark) in self.settings.camera_bookmarks.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.button(&bookmark.name).clicked() {
+                    bookmark.apply(&mut self.viewport.camera);
+                    self.active_camera = None;
+                    self.status_message = format!("Bookmark: {}", bookmark.name);
+                    ui.close();
                 }
-                pos += found + part.len();
-            } else {
-                return false;
-            }
+                if ui.small_button("✕").clicked() {
+                    remove = Some(i);
+                }
+            });
         }
-        // If filter ends with *, any trailing chars are OK
-        // If not, must match to end
-        if !filter.ends_with('*') && pos != name_lower.len() {
-            return false;
+        if let Some(i) = remove {
+            self.settings.camera_bookmarks.remove(i);
+            self.settings.save();
         }
-        true
     }
-    
-    /// Check if node or any descendant matches filter
-    fn node_matches_filter(node: &SceneNode, filter: &str) -> bool {
-        if Self::matches_filter(&node.name, filter) {
-            return true;
-        }
-        for child in &node.children {
-            if Self::node_matches_filter(child, filter) {
-                return true;
+
+    /// Camera path submenu: record fly-through and export it as an ICamera archive
+    fn camera_path_menu(&mut self, ui: &mut egui::Ui) {
+        if self.camera_path.is_recording() {
+            if ui.button("⏹ Stop Recording").clicked() {
+                self.camera_path.stop();
+                self.status_message = format!(
+                    "Recorded {} camera samples ({:.1}s)",
+                    self.camera_path.keys.len(), self.camera_path.duration()
+                );
+                ui.close();
             }
+        } else if ui.button("⏺ Record").clicked() {
+            self.camera_path.fps = self.settings.playback_fps;
+            self.camera_path.start();
+            self.status_message = format!("Recording camera path at {} fps", format_fps(self.camera_path.fps));
+            ui.close();
+        }
+        ui.label(format!("{} samples, {:.1}s", self.camera_path.keys.len(), self.camera_path.duration()));
+        let can_export = !self.camera_path.is_recording() && !self.camera_path.keys.is_empty() && super::dialogs::HAS_PATHS;
+        if ui.add_enabled(can_export, egui::Button::new("Export as Camera...")).clicked() {
+            self.export_camera_path_dialog();
+            ui.close();
         }
-        false
     }
-    
-    /// Render tree node with custom expand state (supports Shift+click for recursive toggle)
-    fn show_tree_node(
-        ui: &mut egui::Ui,
-        node: &SceneNode,
-        selected: &mut Option<String>,
-        filter: &str,
-        expanded: &mut HashSet<String>,
-        depth: usize,
-    ) {
-        // Skip nodes that don't match filter
-        if !filter.is_empty() && !Self::node_matches_filter(node, filter) {
+
+    /// Draw the recorded camera path (sample positions joined by lines)
+    fn update_camera_path_overlay(&mut self) {
+        const KEY: &str = "viewer:camera_path";
+        let keys = &self.camera_path.keys;
+        if keys.len() == self.camera_path_overlay_keys {
             return;
         }
-        
-        let is_selected = selected.as_ref() == Some(&node.name);
-        let matches_directly = Self::matches_filter(&node.name, filter);
-        let has_children = !node.children.is_empty();
-        let is_expanded = expanded.contains(&node.name);
-        
-        // Icon based on type
-        let icon = match node.node_type.as_str() {
-            "PolyMesh" => "▲",  // triangle
-            "SubD" => "■",      // square
-            "Xform" => "↺",     // rotation
-            "Camera" => "◎",    // target
-            "Light" => "☀",     // sun
-            "Curves" => "∿",    // wave
-            "Points" => "•",    // bullet
-            _ => "○",           // circle
-        };
-        
-        // Arrow for expandable nodes
-        let arrow = if has_children {
-            if is_expanded { "▼" } else { "▶" }
-        } else {
-            "  " // spacing for leaf nodes
-        };
-        
-        let label_text = format!("{} {} {}", arrow, icon, node.name);
-        let label = if !filter.is_empty() && matches_directly {
-            RichText::new(label_text).color(Color32::YELLOW)
-        } else if is_selected {
-            RichText::new(label_text).color(Color32::LIGHT_BLUE)
-        } else {
-            RichText::new(label_text)
-        };
-        
-        // Indent based on depth
-        ui.horizontal(|ui| {
-            ui.add_space(depth as f32 * 16.0);
-            let response = ui.selectable_label(is_selected, label);
-            
-            if response.clicked() {
-                // Select the node
-                *selected = Some(node.name.clone());
-                
-                // Toggle expand state for nodes with children
-                if has_children {
-                    let shift = ui.input(|i| i.modifiers.shift);
-                    if shift {
-                        // Recursive toggle
-                        Self::toggle_recursive(node, expanded, !is_expanded);
-                    } else {
-                        // Single toggle
-                        if is_expanded {
-                            expanded.remove(&node.name);
-                        } else {
-                            expanded.insert(node.name.clone());
-                        }
-                    }
-                }
-            }
-        });
-        
-        // Show children if expanded
-        if has_children && is_expanded {
-            for child in &node.children {
-                Self::show_tree_node(ui, child, selected, filter, expanded, depth + 1);
-            }
+        self.camera_path_overlay_keys = keys.len();
+        if keys.is_empty() {
+            self.overlays.remove(KEY);
+            return;
         }
+        let points: Vec<Vec3> = keys.iter().map(|k| k.world.w_axis.truncate()).collect();
+        self.overlays.set(KEY, super::Overlay {
+            lines: points.windows(2).map(|w| [w[0], w[1]]).collect(),
+            points,
+            color: Vec3::new(0.3, 0.8, 1.0),
+            ..Default::default()
+        });
     }
-    
-    /// Recursively set expand state for node and all descendants
-    fn toggle_recursive(node: &SceneNode, expanded: &mut HashSet<String>, expand: bool) {
-        if expand {
-            expanded.insert(node.name.clone());
-        } else {
-            expanded.remove(&node.name);
-        }
-        for child in &node.children {
-            Self::toggle_recursive(child, expanded, expand);
+
+    fn export_camera_path_dialog(&mut self) {
+        let default_name = self.current_file
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}_camera.abc", s.to_string_lossy()))
+            .unwrap_or_else(|| "camera.abc".to_string());
+        if let Some(path) = super::dialogs::save_file("Alembic", &["abc"], &default_name) {
+            match super::export::export_camera_path(&self.camera_path.keys, self.camera_path.fps, &path) {
+                Ok(n) => {
+                    self.status_message = format!(
+                        "Exported camera path ({} samples) to {}",
+                        n,
+                        path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+                    );
+                }
+                Err(e) => {
+                    self.status_message = format!("Camera export failed: {}", e);
+                }
+            }
         }
     }
-    
-    /// Render tree node with filtering (old version, kept for reference)
-    #[allow(dead_code)]
-    fn show_tree_node_filtered(ui: &mut egui::Ui, node: &SceneNode, selected: &mut Option<String>, filter: &str) {
-        // Skip nodes that don't match filter (and have no matching descendants)
-        if !filter.is_empty() && !Self::node_matches_filter(node, filter) {
-            return;
+
+    /// Hierarchy panel - object tree
+    fn hierarchy_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Hierarchy");
+        ui.separator();
+
+        // Search as you type
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(egui::TextEdit::singleline(&mut self.object_filter)
+                .hint_text("wheel*")
+                .desired_width(ui.available_width()));
+        });
+        self.outliner.set_filter(&self.object_filter);
+        if !self.object_filter.trim().is_empty() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("{} of {} match", self.outliner.hit_count(), self.outliner.len())).small().weak());
+                if ui.small_button("✕ Clear").clicked() {
+                    self.object_filter.clear();
+                }
+            });
         }
-        
-        let id = ui.make_persistent_id(&node.name);
-        let is_selected = selected.as_ref() == Some(&node.name);
-        let matches_directly = Self::matches_filter(&node.name, filter);
-        
-        // Icon based on type
-        let icon = match node.node_type.as_str() {
-            "PolyMesh" => "▲",
-            "SubD" => "■",
-            "Xform" => "↺",
-            "Camera" => "◎",
-            "Light" => "☀",
-            "Curves" => "∿",
-            "Points" => "•",
-            _ => "○",
-        };
-        
-        let label = format!("{} {}", icon, node.name);
-        // Highlight matching nodes
-        let label = if !filter.is_empty() && matches_directly {
-            RichText::new(label).color(Color32::YELLOW)
-        } else {
-            RichText::new(label)
-        };
-        
-        if node.children.is_empty() {
-            let response = ui.selectable_label(is_selected, label);
-            if response.clicked() {
-                *selected = Some(node.name.clone());
+        ui.separator();
+
+        egui::CollapsingHeader::new("Selection Sets")
+            .default_open(false)
+            .show(ui, |ui| self.selection_sets_ui(ui));
+        ui.separator();
+
+        // Double-click frames, Shift+click on an arrow toggles the subtree
+        match self.outliner.show(ui, &self.selection, self.selected_object.as_deref()) {
+            Some(OutlinerEvent::Select { path, add: true }) => {
+                self.selected_object = Some(path.clone());
+                self.selection.toggle(&path);
             }
-        } else {
-            egui::CollapsingHeader::new(label)
-                .id_salt(id)
-                .default_open(!filter.is_empty())
-                .show(ui, |ui| {
-                    for child in &node.children {
-                        Self::show_tree_node_filtered(ui, child, selected, filter);
-                    }
-                });
+            Some(OutlinerEvent::Select { path, add: false }) => {
+                self.selected_object = Some(path.clone());
+                self.selection.select_only(&path);
+            }
+            Some(OutlinerEvent::Frame(path)) => {
+                self.selected_object = Some(path.clone());
+                self.selection.select_only(&path);
+                self.focus_camera(true);
+            }
+            None => {}
         }
     }
-    
+
     fn side_panel(&mut self, ui: &mut egui::Ui) {
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
@@ -766,6 +875,18 @@ impl ViewerApp {
                 ui.label(format!("{} tris", self.face_count));
             });
             
+            // Conversion warnings (NaN positions, degenerate faces, bad indices, ...)
+            if !self.load_log.is_empty() {
+                ui.separator();
+                let errors = self.load_log.count(mesh_converter::WarningLevel::Error);
+                let warnings = self.load_log.count(mesh_converter::WarningLevel::Warning);
+                let text = format!("⚠ {} error(s), {} warning(s)", errors, warnings);
+                let color = if errors > 0 { Color32::from_rgb(255, 90, 90) } else { Color32::YELLOW };
+                if ui.button(RichText::new(text).color(color)).on_hover_text("Open load log").clicked() {
+                    self.load_log.open = true;
+                }
+            }
+
             // Selected object properties
             if let Some(name) = &self.selected_object {
                 ui.separator();
@@ -795,7 +916,7 @@ impl ViewerApp {
                 }
 
                 // Archive properties (type, samples, etc.)
-                if let Some(node) = self.find_node_by_name(name) {
+                if let Some(node) = self.outliner.find(name) {
                     ui.label(format!("Type: {}", node.node_type));
                 }
                 if let Some(archive) = &self.archive {
@@ -834,7 +955,11 @@ impl ViewerApp {
                             }
                             
                             // Path Tracer mode
-                            if ui.selectable_label(self.settings.path_tracing, "Path Tracer").clicked() {
+                            let pt_label = ui.add_enabled(
+                                renderer.supports_path_tracing(),
+                                egui::Button::selectable(self.settings.path_tracing, "Path Tracer"),
+                            ).on_disabled_hover_text("Needs compute shaders (not available on WebGL2)");
+                            if pt_label.clicked() {
                                 self.settings.path_tracing = true;
                                 self.settings.show_wireframe = false;
                                 renderer.use_path_tracing = true;
@@ -850,6 +975,11 @@ impl ViewerApp {
                                 renderer.pt_aperture = self.settings.pt_aperture;
                                 renderer.pt_focus_distance = self.settings.pt_focus_distance;
                                 renderer.pt_global_opacity = self.settings.pt_global_opacity;
+                                renderer.pt_shadow_catcher = self.settings.pt_shadow_catcher;
+                                renderer.pt_split_view = self.settings.pt_split_view;
+                                renderer.pt_split_position = self.settings.pt_split_position;
+                                renderer.pt_denoise = self.settings.pt_denoise;
+                                renderer.pt_resolution_scale = self.settings.pt_resolution_scale;
                                 renderer.init_path_tracer(1280, 720);
                                 renderer.upload_scene_to_path_tracer_with_normals(
                                     self.settings.smooth_normals,
@@ -975,87 +1105,268 @@ impl ViewerApp {
                             }
                         }
                     });
-            }
 
-            // ============================================================
-            // Path Tracer Settings (only when path tracing)
-            // ============================================================
-            if self.settings.path_tracing {
-                egui::CollapsingHeader::new("Path Tracer")
-                    .default_open(true)
+                // Motion Blur Section
+                egui::CollapsingHeader::new("Motion Blur")
+                    .default_open(self.settings.motion_blur)
                     .show(ui, |ui| {
                         if let Some(renderer) = &mut self.viewport.renderer {
                             let mut changed = false;
-                            
-                            ui.horizontal(|ui| {
-                                ui.label("Bounces:");
-                                if ui.add(egui::Slider::new(&mut self.settings.pt_max_bounces, 1..=8)).changed() {
-                                    renderer.pt_max_bounces = self.settings.pt_max_bounces;
-                                    if let Some(pt) = &mut renderer.path_tracer {
-                                        pt.reset_accumulation();
-                                    }
-                                    changed = true;
-                                }
-                            });
-                            ui.horizontal(|ui| {
-                                ui.label("Glass depth:");
-                                if ui.add(egui::Slider::new(&mut self.settings.pt_max_transmission_depth, 1..=16)).changed() {
-                                    renderer.pt_max_transmission_depth = self.settings.pt_max_transmission_depth;
-                                    if let Some(pt) = &mut renderer.path_tracer {
-                                        pt.reset_accumulation();
-                                    }
-                                    changed = true;
-                                }
-                            });
-                            ui.horizontal(|ui| {
-                                ui.label("Max SPP:");
-                                if ui.add(egui::Slider::new(&mut self.settings.pt_max_samples, 16..=4096).logarithmic(true)).changed() {
-                                    renderer.pt_max_samples = self.settings.pt_max_samples;
-                                    changed = true;
-                                }
-                            });
-                            // Auto SPP mode
-                            if ui.checkbox(&mut self.settings.pt_auto_spp, "Auto SPP").changed() {
-                                renderer.pt_auto_spp = self.settings.pt_auto_spp;
-                                // Restore manual SPP value when turning off Auto
-                                if !self.settings.pt_auto_spp {
-                                    renderer.pt_samples_per_update = self.settings.pt_samples_per_update;
-                                }
-                            }
 
-                            // Camera snapping (prevents ghost samples when SPP > 1)
-                            if ui.checkbox(&mut self.settings.pt_camera_snap, "Camera Snap")
-                                .on_hover_text("Snap camera at Target FPS intervals. Prevents ghost samples when rendering multiple samples per frame.")
+                            if ui.checkbox(&mut self.settings.motion_blur, "Enable Motion Blur")
+                                .on_hover_text("Preview blur from sample-to-sample motion (or stored velocities)")
                                 .changed()
                             {
-                                renderer.pt_camera_snap = self.settings.pt_camera_snap;
+                                renderer.motion_blur = self.settings.motion_blur;
+                                // Motion is recorded on the next frame step
+                                changed = true;
                             }
 
-                            // Target FPS - used by both Auto SPP and Camera Snap
-                            if self.settings.pt_auto_spp || self.settings.pt_camera_snap {
+                            if self.settings.motion_blur {
                                 ui.horizontal(|ui| {
-                                    ui.label("Target FPS:");
-                                    if ui.add(egui::Slider::new(&mut self.settings.pt_target_fps, 1.0..=120.0).integer()).changed() {
-                                        renderer.pt_target_fps = self.settings.pt_target_fps;
+                                    ui.label("Shutter:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.motion_blur_shutter, 0.0..=360.0).step_by(15.0).suffix("°")).changed() {
+                                        renderer.motion_blur_shutter = self.settings.motion_blur_shutter;
+                                        changed = true;
                                     }
                                 });
-                            }
-
-                            if self.settings.pt_auto_spp {
-                                // Show current auto SPP value
-                                ui.horizontal(|ui| {
-                                    ui.label(format!("SPP/frame: {}", renderer.pt_samples_per_update));
-                                });
-                            } else {
                                 ui.horizontal(|ui| {
-                                    ui.label("SPP/frame:");
-                                    if ui.add(egui::Slider::new(&mut self.settings.pt_samples_per_update, 1..=64)).changed() {
-                                        renderer.pt_samples_per_update = self.settings.pt_samples_per_update;
+                                    ui.label("Samples:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.motion_blur_samples, 2..=32)).changed() {
+                                        renderer.motion_blur_samples = self.settings.motion_blur_samples;
+                                        changed = true;
                                     }
-                                }).response.on_hover_text("Samples per frame. Higher = faster convergence, lower FPS");
+                                });
+                                if renderer.use_path_tracing || renderer.show_wireframe {
+                                    ui.label(egui::RichText::new("Raster shaded mode only").weak());
+                                }
                             }
-                            
-                            ui.separator();
+
+                            if changed {
+                                self.settings.save();
+                            }
+                        }
+                    });
+
+                // Lens Effects Section
+                egui::CollapsingHeader::new("Lens Effects")
+                    .default_open(self.settings.dof || self.settings.bloom)
+                    .show(ui, |ui| {
+                        if let Some(renderer) = &mut self.viewport.renderer {
+                            let mut changed = false;
+
+                            if ui.checkbox(&mut self.settings.dof, "Depth of Field")
+                                .on_hover_text("Blur by the focus distance and f-stop of the camera looked through")
+                                .changed()
+                            {
+                                renderer.dof = self.settings.dof;
+                                changed = true;
+                            }
+                            if self.settings.dof {
+                                let lens = renderer.dof_lens;
+                                match self.active_camera.and_then(|i| self.scene_cameras.get(i)) {
+                                    Some(cam) => {
+                                        ui.label(format!(
+                                            "{}: {:.0}mm f/{:.1}, focus {:.2}",
+                                            cam.name, lens.focal_length, lens.f_stop, lens.focus_distance
+                                        ));
+                                    }
+                                    None => {
+                                        ui.horizontal(|ui| {
+                                            ui.label("F-Stop:");
+                                            if ui.add(egui::Slider::new(&mut self.settings.dof_f_stop, 0.7..=32.0).logarithmic(true).prefix("f/")).changed() {
+                                                changed = true;
+                                            }
+                                        });
+                                        ui.label(egui::RichText::new(format!("Focus {:.2} (F / Ctrl+Click to set)", lens.focus_distance)).weak());
+                                    }
+                                }
+                                ui.horizontal(|ui| {
+                                    ui.label("Max Blur:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.dof_max_blur, 2.0..=64.0).suffix(" px")).changed() {
+                                        renderer.dof_max_blur_px = self.settings.dof_max_blur;
+                                        changed = true;
+                                    }
+                                });
+                            }
+
+                            if ui.checkbox(&mut self.settings.bloom, "Bloom")
+                                .on_hover_text("Glow around highlights above the threshold")
+                                .changed()
+                            {
+                                renderer.bloom = self.settings.bloom;
+                                changed = true;
+                            }
+                            if self.settings.bloom {
+                                ui.horizontal(|ui| {
+                                    ui.label("Threshold:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.bloom_threshold, 0.0..=1.0)).changed() {
+                                        renderer.bloom_threshold = self.settings.bloom_threshold;
+                                        changed = true;
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Intensity:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.bloom_intensity, 0.0..=2.0)).changed() {
+                                        renderer.bloom_intensity = self.settings.bloom_intensity;
+                                        changed = true;
+                                    }
+                                });
+                            }
+
+                            if (self.settings.dof || self.settings.bloom)
+                                && (renderer.use_path_tracing || renderer.show_wireframe)
+                            {
+                                ui.label(egui::RichText::new("Raster shaded mode only").weak());
+                            }
+
+                            if changed {
+                                self.settings.save();
+                            }
+                        }
+                    });
+            }
+
+            // ============================================================
+            // Path Tracer Settings (only when path tracing)
+            // ============================================================
+            if self.settings.path_tracing {
+                egui::CollapsingHeader::new("Path Tracer")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        if let Some(renderer) = &mut self.viewport.renderer {
+                            let mut changed = false;
+
+                            // Quality presets (bounces, SPP, glass depth, DoF, denoise, resolution)
+                            let mut apply_preset = None;
+                            ui.horizontal(|ui| {
+                                ui.label("Preset:");
+                                let selected = if self.settings.pt_preset.is_empty() {
+                                    "Custom".to_string()
+                                } else {
+                                    self.settings.pt_preset.clone()
+                                };
+                                egui::ComboBox::from_id_salt("pt_preset")
+                                    .selected_text(selected)
+                                    .show_ui(ui, |ui| {
+                                        for preset in &self.settings.pt_presets {
+                                            if ui.selectable_label(preset.name == self.settings.pt_preset, preset.name.as_str()).clicked() {
+                                                apply_preset = Some(preset.name.clone());
+                                            }
+                                        }
+                                    });
+                            });
+                            if let Some(name) = apply_preset {
+                                self.settings.apply_pt_preset(&name);
+                                renderer.pt_max_samples = self.settings.pt_max_samples;
+                                renderer.pt_max_bounces = self.settings.pt_max_bounces;
+                                renderer.pt_max_transmission_depth = self.settings.pt_max_transmission_depth;
+                                renderer.pt_dof_enabled = self.settings.pt_dof_enabled;
+                                renderer.pt_denoise = self.settings.pt_denoise;
+                                renderer.pt_resolution_scale = self.settings.pt_resolution_scale;
+                                if let Some(pt) = &mut renderer.path_tracer {
+                                    pt.reset_accumulation();
+                                }
+                                changed = true;
+                            }
+                            ui.horizontal(|ui| {
+                                ui.add(egui::TextEdit::singleline(&mut self.pt_preset_name)
+                                    .hint_text("preset name")
+                                    .desired_width(100.0));
+                                if ui.button("Save Preset").clicked() && !self.pt_preset_name.trim().is_empty() {
+                                    self.settings.save_pt_preset(self.pt_preset_name.trim());
+                                    self.pt_preset_name.clear();
+                                    changed = true;
+                                }
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.label("Bounces:");
+                                if ui.add(egui::Slider::new(&mut self.settings.pt_max_bounces, 1..=8)).changed() {
+                                    renderer.pt_max_bounces = self.settings.pt_max_bounces;
+                                    if let Some(pt) = &mut renderer.path_tracer {
+                                        pt.reset_accumulation();
+                                    }
+                                    changed = true;
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Glass depth:");
+                                if ui.add(egui::Slider::new(&mut self.settings.pt_max_transmission_depth, 1..=16)).changed() {
+                                    renderer.pt_max_transmission_depth = self.settings.pt_max_transmission_depth;
+                                    if let Some(pt) = &mut renderer.path_tracer {
+                                        pt.reset_accumulation();
+                                    }
+                                    changed = true;
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Max SPP:");
+                                if ui.add(egui::Slider::new(&mut self.settings.pt_max_samples, 16..=4096).logarithmic(true)).changed() {
+                                    renderer.pt_max_samples = self.settings.pt_max_samples;
+                                    changed = true;
+                                }
+                            });
+                            // Auto SPP mode
+                            if ui.checkbox(&mut self.settings.pt_auto_spp, "Auto SPP").changed() {
+                                renderer.pt_auto_spp = self.settings.pt_auto_spp;
+                                // Restore manual SPP value when turning off Auto
+                                if !self.settings.pt_auto_spp {
+                                    renderer.pt_samples_per_update = self.settings.pt_samples_per_update;
+                                }
+                            }
+
+                            // Camera snapping (prevents ghost samples when SPP > 1)
+                            if ui.checkbox(&mut self.settings.pt_camera_snap, "Camera Snap")
+                                .on_hover_text("Snap camera at Target FPS intervals. Prevents ghost samples when rendering multiple samples per frame.")
+                                .changed()
+                            {
+                                renderer.pt_camera_snap = self.settings.pt_camera_snap;
+                            }
+
+                            // Target FPS - used by both Auto SPP and Camera Snap
+                            if self.settings.pt_auto_spp || self.settings.pt_camera_snap {
+                                ui.horizontal(|ui| {
+                                    ui.label("Target FPS:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.pt_target_fps, 1.0..=120.0).integer()).changed() {
+                                        renderer.pt_target_fps = self.settings.pt_target_fps;
+                                    }
+                                });
+                            }
+
+                            if self.settings.pt_auto_spp {
+                                // Show current auto SPP value
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("SPP/frame: {}", renderer.pt_samples_per_update));
+                                });
+                            } else {
+                                ui.horizontal(|ui| {
+                                    ui.label("SPP/frame:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.pt_samples_per_update, 1..=64)).changed() {
+                                        renderer.pt_samples_per_update = self.settings.pt_samples_per_update;
+                                    }
+                                }).response.on_hover_text("Samples per frame. Higher = faster convergence, lower FPS");
+                            }
+
+                            if ui.checkbox(&mut self.settings.pt_denoise, "Denoise")
+                                .on_hover_text("Edge-preserving filter on the displayed image")
+                                .changed()
+                            {
+                                renderer.pt_denoise = self.settings.pt_denoise;
+                                changed = true;
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label("Resolution:");
+                                if ui.add(egui::Slider::new(&mut self.settings.pt_resolution_scale, 0.25..=1.0))
+                                    .on_hover_text("Path tracer resolution relative to the viewport")
+                                    .changed()
+                                {
+                                    renderer.pt_resolution_scale = self.settings.pt_resolution_scale;
+                                    changed = true;
+                                }
+                            });
+                            
+                            ui.separator();
 
                             // Global Opacity (transparency control)
                             ui.horizontal(|ui| {
@@ -1069,6 +1380,35 @@ impl ViewerApp {
                                 }
                             });
 
+                            if ui.checkbox(&mut self.settings.pt_shadow_catcher, "Shadow Catcher Floor")
+                                .on_hover_text("Floor shows the background and only catches shadows and occlusion")
+                                .changed()
+                            {
+                                renderer.pt_shadow_catcher = self.settings.pt_shadow_catcher;
+                                if let Some(pt) = &mut renderer.path_tracer {
+                                    pt.reset_accumulation();
+                                }
+                                changed = true;
+                            }
+
+                            // Split view (ground truth vs raster)
+                            if ui.checkbox(&mut self.settings.pt_split_view, "Split View (PT | Raster)")
+                                .on_hover_text("Path tracer left of the divider, rasterizer right of it")
+                                .changed()
+                            {
+                                renderer.pt_split_view = self.settings.pt_split_view;
+                                changed = true;
+                            }
+                            if self.settings.pt_split_view {
+                                ui.horizontal(|ui| {
+                                    ui.label("Divider:");
+                                    if ui.add(egui::Slider::new(&mut self.settings.pt_split_position, 0.05..=0.95)).changed() {
+                                        renderer.pt_split_position = self.settings.pt_split_position;
+                                        changed = true;
+                                    }
+                                });
+                            }
+
                             ui.separator();
 
                             // Depth of Field
@@ -1119,6 +1459,13 @@ impl ViewerApp {
                                         format!("{} / {} samples", pt.frame_count, self.settings.pt_max_samples)
                                     }));
                             }
+
+                            // Editing a bundled value away from the preset makes it custom
+                            let edited = self.settings.pt_preset(&self.settings.pt_preset)
+                                .is_some_and(|p| *p != super::settings::PtPreset::from_settings(&p.name, &self.settings));
+                            if edited {
+                                self.settings.pt_preset.clear();
+                            }
                             
                             if changed {
                                 self.settings.save();
@@ -1167,6 +1514,47 @@ impl ViewerApp {
                         }
                     });
                     
+                    // Exposure + white balance (tonemap stage, raster and path tracer)
+                    let mut exposure_changed = false;
+                    ui.horizontal(|ui| {
+                        exposure_changed |= ui.checkbox(&mut self.settings.auto_exposure, "Auto Exposure")
+                            .on_hover_text("Expose the HDR environment to middle gray (histogram metered)")
+                            .changed();
+                        if self.settings.auto_exposure && has_env {
+                            if let Some(renderer) = &self.viewport.renderer {
+                                ui.weak(format!("{:+.1} EV", renderer.exposure_ev() - self.settings.exposure_ev));
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("EV:");
+                        exposure_changed |= ui.add(egui::Slider::new(&mut self.settings.exposure_ev, -5.0..=5.0).step_by(0.1))
+                            .on_hover_text("Exposure compensation in stops")
+                            .changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("WB:");
+                        exposure_changed |= ui.add(egui::Slider::new(&mut self.settings.white_balance, MIN_KELVIN..=MAX_KELVIN).suffix(" K"))
+                            .on_hover_text("Color temperature of the light to neutralize (6500 K = off)")
+                            .changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Tint:");
+                        exposure_changed |= ui.add(egui::Slider::new(&mut self.settings.white_balance_tint, -1.0..=1.0))
+                            .on_hover_text("Green (-) to magenta (+)")
+                            .changed();
+                        if ui.small_button("Reset").clicked() {
+                            self.settings.exposure_ev = 0.0;
+                            self.settings.white_balance = NEUTRAL_KELVIN;
+                            self.settings.white_balance_tint = 0.0;
+                            exposure_changed = true;
+                        }
+                    });
+                    if exposure_changed {
+                        self.apply_exposure();
+                        self.settings.save();
+                    }
+                    
                     if has_env && ui.checkbox(&mut self.settings.hdr_visible, "Show Background").changed() {
                         if let Some(renderer) = &mut self.viewport.renderer {
                             renderer.hdr_visible = self.settings.hdr_visible;
@@ -1202,7 +1590,7 @@ impl ViewerApp {
                     }
                     
                     ui.horizontal(|ui| {
-                        if ui.button("Load HDR...").clicked() {
+                        if ui.add_enabled(super::dialogs::HAS_PATHS, egui::Button::new("Load HDR...")).clicked() {
                             self.load_environment_dialog();
                         }
                         if has_env && ui.button("Clear").clicked() {
@@ -1265,6 +1653,31 @@ impl ViewerApp {
                         }
                         ui.label("(restart)");
                     });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Render Scale:");
+                        if ui.add(egui::Slider::new(&mut self.settings.render_scale, 0.5..=2.0)
+                            .step_by(0.05)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)))
+                            .on_hover_text("Render resolution relative to the viewport, upscaled (<100%) or supersampled (>100%)")
+                            .changed()
+                        {
+                            self.settings.save();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let mut changed = ui.checkbox(&mut self.settings.dynamic_resolution, "Dynamic Resolution")
+                            .on_hover_text("Lower the render scale (down to 50%) while frames are slower than the target; Render Scale is the ceiling")
+                            .changed();
+                        ui.add_enabled_ui(self.settings.dynamic_resolution, |ui| {
+                            changed |= ui.add(egui::DragValue::new(&mut self.settings.dynamic_resolution_fps)
+                                .range(15.0..=240.0)
+                                .suffix(" fps")).changed();
+                        });
+                        if changed {
+                            self.settings.save();
+                        }
+                    });
                     
                     ui.horizontal(|ui| {
                         ui.label("Background:");
@@ -1300,6 +1713,26 @@ impl ViewerApp {
                         self.needs_scene_reload = true;
                     }
                     
+                    // Global display mode (clay, random colors, UV checker)
+                    ui.horizontal(|ui| {
+                        ui.label("Display:");
+                        egui::ComboBox::from_id_salt("display_mode")
+                            .selected_text(self.settings.display_mode.as_str())
+                            .show_ui(ui, |ui| {
+                                for mode in DisplayMode::ALL {
+                                    if ui.selectable_label(
+                                        self.settings.display_mode == mode,
+                                        mode.as_str()
+                                    ).clicked() && self.settings.display_mode != mode {
+                                        self.settings.display_mode = mode;
+                                        self.settings.save();
+                                        // Materials are assigned when meshes are added
+                                        self.needs_scene_reload = true;
+                                    }
+                                }
+                            });
+                    });
+                    
                     // Hover highlighting mode
                     ui.horizontal(|ui| {
                         ui.label("Hover:");
@@ -1413,6 +1846,7 @@ impl ViewerApp {
     
     fn timeline_panel(&mut self, ui: &mut egui::Ui) {
         let has_animation = self.num_samples > 1;
+        let mut slider_x = None;
         
         ui.horizontal(|ui| {
             // Play/Pause button (disabled for static files)
@@ -1430,8 +1864,63 @@ impl ViewerApp {
                 }
             }
             
-            // Frame counter (left side)
-            ui.label(format!("{} / {}", self.current_frame + 1, self.num_samples.max(1)));
+            // Sample step / topology change navigation
+            let prev_topo = self.topology_changes.prev_before(self.current_frame);
+            let next_topo = self.topology_changes.next_after(self.current_frame);
+            if ui.add_enabled(prev_topo.is_some(), egui::Button::new("⏮"))
+                .on_hover_text("Previous topology change (Shift+Left)")
+                .clicked()
+            {
+                self.jump_to_frame(prev_topo);
+            }
+            if ui.add_enabled(has_animation && self.current_frame > 0, egui::Button::new("◀"))
+                .on_hover_text("Previous sample")
+                .clicked()
+            {
+                self.jump_to_frame(self.current_frame.checked_sub(1));
+            }
+            if ui.add_enabled(has_animation && self.current_frame + 1 < self.num_samples, egui::Button::new("▶"))
+                .on_hover_text("Next sample")
+                .clicked()
+            {
+                self.jump_to_frame(Some(self.current_frame + 1));
+            }
+            if ui.add_enabled(next_topo.is_some(), egui::Button::new("⏭"))
+                .on_hover_text("Next topology change (Shift+Right)")
+                .clicked()
+            {
+                self.jump_to_frame(next_topo);
+            }
+
+            // Frame counter (left side): sample position, plus the DCC frame number
+            let scene_frame = self.archive.as_ref()
+                .and_then(|a| Self::sample_index_to_frame(a, self.current_frame));
+            match scene_frame {
+                Some(f) => ui.label(format!("{} / {}  (frame {})", self.current_frame + 1, self.num_samples.max(1), format_frame(f))),
+                None => ui.label(format!("{} / {}", self.current_frame + 1, self.num_samples.max(1))),
+            };
+
+            // Topology change summary for the current frame
+            if !self.topology_changes.is_empty() {
+                let changed = self.topology_changes.objects_at(self.current_frame);
+                let label = if changed.is_empty() {
+                    egui::RichText::new(format!("{} topo changes", self.topology_changes.len()))
+                } else {
+                    egui::RichText::new(format!("topo change: {} objects", changed.len()))
+                        .color(egui::Color32::from_rgb(230, 90, 90))
+                };
+                let response = ui.label(label);
+                if !changed.is_empty() {
+                    response.on_hover_ui(|ui| {
+                        for name in changed.iter().take(20) {
+                            ui.label(name);
+                        }
+                        if changed.len() > 20 {
+                            ui.label(format!("... and {} more", changed.len() - 20));
+                        }
+                    });
+                }
+            }
             
             // FPS selector
             if has_animation {
@@ -1463,7 +1952,17 @@ impl ViewerApp {
                     });
                 if self.settings.playback_fps != prev_fps {
                     self.settings.save();
+                    // Re-sync the soundtrack at the new rate
+                    self.audio_player.stop();
                 }
+                if ui.checkbox(&mut self.settings.interpolate_playback, "Interp")
+                    .on_hover_text("Blend transforms between samples while playing (rotation slerped), for smooth and slow-motion playback")
+                    .changed()
+                {
+                    self.settings.save();
+                }
+                ui.separator();
+                self.audio_controls(ui);
             }
             
             ui.separator();
@@ -1486,12 +1985,14 @@ impl ViewerApp {
                 if new_frame != self.current_frame {
                     self.current_frame = new_frame;  // Instant visual update
                     self.request_frame(new_frame);   // Async load
+                    self.seek_audio(new_frame as f64);
                 }
             }
             
             // Draw keyframe markers under slider if animated
             if has_animation && max_frame > 0.0 {
                 let rect = response.rect;
+                slider_x = Some(rect.x_range());
                 let painter = ui.painter();
                 let marker_y = rect.max.y + 2.0;
                 
@@ -1511,6 +2012,15 @@ impl ViewerApp {
                     );
                 }
                 
+                // Topology change markers (red, above the keyframe ticks)
+                for i in self.topology_changes.frames() {
+                    let x = rect.min.x + (i as f32 / max_frame) * rect.width();
+                    painter.line_segment(
+                        [egui::pos2(x, rect.min.y - 2.0), egui::pos2(x, marker_y + 4.0)],
+                        egui::Stroke::new(1.5, egui::Color32::from_rgb(230, 90, 90)),
+                    );
+                }
+
                 // Highlight current frame marker
                 let t = self.current_frame as f32 / max_frame;
                 let x = rect.min.x + t * rect.width();
@@ -1520,10 +2030,259 @@ impl ViewerApp {
                 );
             }
         });
+
+        if let (true, Some(x_range)) = (self.settings.show_stats_graph, slider_x) {
+            self.stats_graph(ui, x_range);
+        }
+        if let (true, Some(x_range)) = (self.audio.is_some(), slider_x) {
+            self.audio_waveform(ui, x_range);
+        }
+    }
+
+    /// Soundtrack load/mute/offset controls in the timeline bar
+    fn audio_controls(&mut self, ui: &mut egui::Ui) {
+        let Some(track) = &mut self.audio else {
+            if ui.add_enabled(super::dialogs::HAS_PATHS, egui::Button::new("🔊 Audio..."))
+                .on_hover_text("Load a WAV soundtrack to play in sync with the timeline")
+                .clicked()
+            {
+                self.load_audio_dialog();
+            }
+            return;
+        };
+        let clip = &track.clip;
+        let info = format!("{} ({:.1}s, {} Hz, {} ch)", clip.file_name(), clip.duration(), clip.sample_rate, clip.channels);
+        let icon = if track.muted { "🔇" } else { "🔊" };
+        ui.toggle_value(&mut track.muted, icon).on_hover_text(format!("{}\nClick to mute", info));
+        ui.label("Start:");
+        if ui.add(egui::DragValue::new(&mut track.start_frame).speed(0.1).max_decimals(2))
+            .on_hover_text("Scene frame where the audio starts (timecode offset)")
+            .changed()
+        {
+            // Picked up again from the current frame on the next update
+            self.audio_player.stop();
+        }
+        if let Some(error) = &self.audio_player.error {
+            ui.label(RichText::new("⚠").color(Color32::from_rgb(230, 170, 60)))
+                .on_hover_text(format!("No audio output: {}", error));
+        }
+        if ui.small_button("✖").on_hover_text("Remove soundtrack").clicked() {
+            self.audio = None;
+            self.audio_player.set_clip(None);
+        }
+    }
+
+    /// Waveform of the soundtrack under the timeline slider. Click or drag to
+    /// jump to a frame.
+    fn audio_waveform(&mut self, ui: &mut egui::Ui, x_range: egui::Rangef) {
+        let Some(track) = &self.audio else { return };
+        let fps = self.settings.playback_fps as f64;
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 28.0), egui::Sense::click_and_drag());
+        let plot = egui::Rect::from_x_y_ranges(x_range, rect.y_range());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(plot, 2.0, egui::Color32::from_gray(24));
+
+        let max_frame = (self.num_samples.max(2) - 1) as f64;
+        let pos_at = |x: f32| (x - plot.left()) as f64 / plot.width() as f64 * max_frame;
+        let color = if track.muted { egui::Color32::from_gray(90) } else { egui::Color32::from_rgb(90, 170, 200) };
+        let half = plot.height() * 0.5 - 2.0;
+        let mut x = plot.left();
+        while x < plot.right() {
+            let t0 = track.clip_time(self.scene_frame_at(pos_at(x)), fps);
+            let t1 = track.clip_time(self.scene_frame_at(pos_at(x + 1.0)), fps);
+            let h = track.clip.peak(t0, t1) * half;
+            if h > 0.0 {
+                painter.line_segment(
+                    [egui::pos2(x, plot.center().y - h), egui::pos2(x, plot.center().y + h.max(0.5))],
+                    egui::Stroke::new(1.0, color),
+                );
+            }
+            x += 1.0;
+        }
+        let x = plot.left() + (self.current_frame as f64 / max_frame) as f32 * plot.width();
+        painter.line_segment(
+            [egui::pos2(x, plot.top()), egui::pos2(x, plot.bottom())],
+            egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 180, 50)),
+        );
+
+        if let Some(p) = response.interact_pointer_pos().filter(|p| plot.x_range().contains(p.x)) {
+            if response.clicked() || response.dragged() {
+                let frame = (pos_at(p.x).round() as usize).min(self.num_samples.saturating_sub(1));
+                self.jump_to_frame(Some(frame));
+            }
+        }
+    }
+
+    fn load_audio_dialog(&mut self) {
+        if let Some(path) = super::dialogs::pick_file("WAV audio", &["wav"]) {
+            self.load_audio(path);
+        }
+    }
+
+    /// Load a WAV soundtrack, starting at the first frame of the scene
+    fn load_audio(&mut self, path: PathBuf) {
+        match WavClip::load(&path) {
+            Ok(clip) => {
+                let clip = Arc::new(clip);
+                self.status_message = format!("Audio: {} ({:.1}s)", clip.file_name(), clip.duration());
+                self.audio_player.set_clip(Some(clip.clone()));
+                if let Some(error) = &self.audio_player.error {
+                    self.status_message.push_str(&format!(" - no output: {}", error));
+                }
+                self.audio = Some(AudioTrack { clip, start_frame: self.scene_frame_at(0.0), muted: false });
+            }
+            Err(e) => self.status_message = format!("Failed to load audio: {:#}", e),
+        }
+    }
+
+    /// Scene frame number at a fractional sample position (sample index when
+    /// the archive has no frame mapping).
+    fn scene_frame_at(&self, pos: f64) -> f64 {
+        let Some(archive) = &self.archive else { return pos };
+        let i = pos.max(0.0).floor() as usize;
+        let Some(f0) = Self::sample_index_to_frame(archive, i) else { return pos };
+        let f1 = Self::sample_index_to_frame(archive, i + 1).filter(|&f| f > f0).unwrap_or(f0 + 1.0);
+        f0 + (f1 - f0) * (pos - i as f64)
+    }
+
+    /// Fractional sample position of a scene frame (inverse of [`Self::scene_frame_at`]).
+    fn sample_pos_at(&self, frame: f64) -> f64 {
+        if self.num_samples <= 1 {
+            return 0.0;
+        }
+        // Frames increase with the sample index: binary search the floor sample
+        let (mut lo, mut hi) = (0usize, self.num_samples - 1);
+        if frame <= self.scene_frame_at(0.0) {
+            return frame - self.scene_frame_at(0.0);
+        }
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self.scene_frame_at(mid as f64) <= frame { lo = mid } else { hi = mid - 1 }
+        }
+        let (f0, f1) = (self.scene_frame_at(lo as f64), self.scene_frame_at(lo as f64 + 1.0));
+        lo as f64 + (frame - f0) / (f1 - f0).max(1e-9)
+    }
+
+    /// Move the soundtrack to sample position `pos` (only audible while playing).
+    fn seek_audio(&mut self, pos: f64) {
+        if let Some(track) = &self.audio {
+            let time = track.clip_time(self.scene_frame_at(pos), self.settings.playback_fps as f64);
+            self.audio_player.seek(time);
+        }
+    }
+
+    /// Start/stop the soundtrack with playback and return the sample position
+    /// it is at, which then drives the timeline. `None` when there is no
+    /// audible soundtrack (muted, reverse playback, no output device).
+    fn sync_audio(&mut self) -> Option<f64> {
+        let fps = self.settings.playback_fps as f64;
+        let audible = self.playing && self.playback_dir > 0 && self.num_samples > 1;
+        let Some(track) = self.audio.as_ref().filter(|t| audible && !t.muted) else {
+            self.audio_player.stop();
+            return None;
+        };
+        let start = track.clip_time(self.scene_frame_at(self.current_frame as f64), fps);
+        self.audio_player.play_from(start);
+        let pos = self.sample_pos_at(track.scene_frame(self.audio_player.clock()?, fps));
+        // Loop: one sample past the end wraps to the start
+        if pos >= self.num_samples as f64 {
+            let restart = track.clip_time(self.scene_frame_at(0.0), fps);
+            self.audio_player.seek(restart);
+            return Some(0.0);
+        }
+        Some(pos.max(0.0))
+    }
+
+    /// Per-frame statistics plotted under the timeline slider (each metric
+    /// normalized to its own maximum). Click or drag to jump to a frame;
+    /// right-click to choose metrics.
+    fn stats_graph(&mut self, ui: &mut egui::Ui, x_range: egui::Rangef) {
+        // Pick up background results
+        if let Some(rx) = &self.frame_stats_rx {
+            match rx.try_recv() {
+                Ok(stats) => {
+                    self.frame_stats = Some(stats);
+                    self.frame_stats_rx = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ui.label(RichText::new("Computing frame statistics...").weak().small());
+                    ui.ctx().request_repaint_after(std::time::Duration::from_millis(200));
+                    return;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => self.frame_stats_rx = None,
+            }
+        }
+        let Some(stats) = &self.frame_stats else { return };
+        let metrics: Vec<StatMetric> = self.settings.stats_graph_metrics.iter()
+            .copied()
+            .filter(|&m| stats.has(m))
+            .collect();
+        let available: Vec<StatMetric> = StatMetric::ALL.into_iter().filter(|&m| stats.has(m)).collect();
+
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 44.0), egui::Sense::click_and_drag());
+        let plot = egui::Rect::from_x_y_ranges(x_range, rect.y_range());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(plot, 2.0, egui::Color32::from_gray(24));
+
+        let max_frame = (self.num_samples.max(2) - 1) as f32;
+        let x_of = |frame: usize| plot.left() + frame as f32 / max_frame * plot.width();
+        for &metric in &metrics {
+            let max = stats.max(metric);
+            let line: Vec<egui::Pos2> = stats.get(metric).iter().enumerate()
+                .map(|(i, v)| egui::pos2(x_of(i), plot.bottom() - 2.0 - (v / max) as f32 * (plot.height() - 4.0)))
+                .collect();
+            painter.add(egui::Shape::line(line, egui::Stroke::new(1.2, metric.color())));
+        }
+        let x = x_of(self.current_frame);
+        painter.line_segment(
+            [egui::pos2(x, plot.top()), egui::pos2(x, plot.bottom())],
+            egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 180, 50)),
+        );
+        if metrics.is_empty() {
+            painter.text(plot.center(), egui::Align2::CENTER_CENTER, "No statistics selected (right-click)",
+                egui::FontId::proportional(11.0), egui::Color32::GRAY);
+        }
+
+        let hover_frame = response.hover_pos()
+            .filter(|p| plot.x_range().contains(p.x))
+            .map(|p| (((p.x - plot.left()) / plot.width() * max_frame).round() as usize).min(self.num_samples - 1));
+        if let Some(frame) = hover_frame {
+            painter.line_segment(
+                [egui::pos2(x_of(frame), plot.top()), egui::pos2(x_of(frame), plot.bottom())],
+                egui::Stroke::new(1.0, egui::Color32::from_white_alpha(60)),
+            );
+            let lines: Vec<(StatMetric, String)> = metrics.iter()
+                .map(|&m| (m, m.format(stats.get(m)[frame])))
+                .collect();
+            response.clone().on_hover_ui_at_pointer(|ui| {
+                ui.label(format!("Sample {}", frame + 1));
+                for (m, value) in lines {
+                    ui.label(RichText::new(format!("{}: {}", m.label(), value)).color(m.color()));
+                }
+            });
+            if response.clicked() || response.dragged() {
+                self.jump_to_frame(Some(frame));
+            }
+        }
+
+        response.context_menu(|ui| {
+            for m in available {
+                let mut on = self.settings.stats_graph_metrics.contains(&m);
+                if ui.checkbox(&mut on, RichText::new(m.label()).color(m.color())).changed() {
+                    self.settings.stats_graph_metrics.retain(|&x| x != m);
+                    if on {
+                        self.settings.stats_graph_metrics.push(m);
+                    }
+                    self.settings.save();
+                }
+            }
+        });
     }
     
     fn update_animation(&mut self) {
         let _span = tracing::info_span!("update_animation").entered();
+        // The soundtrack keeps running while frames load; it is the clock
+        let audio_pos = self.sync_audio();
         if self.scene_is_static {
             return;
         }
@@ -1541,6 +2300,37 @@ impl ViewerApp {
         let elapsed = now.duration_since(self.last_frame_time).as_secs_f32();
         let frame_duration = 1.0 / self.settings.playback_fps;
 
+        // Audio-driven: show the sample under the audio playhead, dropping
+        // frames if loading falls behind
+        if let Some(pos) = audio_pos {
+            self.last_frame_time = now;
+            let frame = (pos.floor() as usize).min(self.num_samples - 1);
+            if self.settings.interpolate_playback {
+                self.playback_pos = pos;
+                let subframe = if frame + 1 < self.num_samples { pos.fract() as f32 } else { 0.0 };
+                self.request_subframe(frame, subframe);
+            } else if frame != self.current_frame {
+                self.request_frame(frame);
+            }
+            return;
+        }
+
+        // Interpolated: advance continuously, Xforms blended between samples
+        if self.settings.interpolate_playback {
+            self.last_frame_time = now;
+            if self.playback_pos.floor() as usize != self.current_frame {
+                // Scrubbed or stepped since the last request
+                self.playback_pos = self.current_frame as f64;
+            }
+            let step = (elapsed * self.settings.playback_fps) as f64 * self.playback_dir as f64;
+            self.playback_pos = (self.playback_pos + step).rem_euclid(self.num_samples as f64);
+            let frame = (self.playback_pos.floor() as usize).min(self.num_samples - 1);
+            // Hold the last sample rather than blending back to the first
+            let subframe = if frame + 1 < self.num_samples { self.playback_pos.fract() as f32 } else { 0.0 };
+            self.request_subframe(frame, subframe);
+            return;
+        }
+
         if elapsed >= frame_duration {
             self.last_frame_time = now;
 
@@ -1551,20 +2341,61 @@ impl ViewerApp {
         }
     }
 
-    fn open_file_dialog(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Alembic", &["abc"])
-            .pick_file()
-        {
+    fn open_file_dialog(&mut self, ctx: &egui::Context) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = super::dialogs::pick_file("Alembic", &["abc"]) {
             self.load_file(path);
         }
+        // Picked asynchronously; loaded by `poll_upload`
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.upload_rx = Some(super::dialogs::pick_upload(ctx, "Alembic", &["abc"]));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = ctx;
+    }
+
+    /// Open an archive already in memory once the renderer is up
+    #[cfg(target_arch = "wasm32")]
+    pub fn open_bytes(&mut self, name: String, bytes: Vec<u8>) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let _ = tx.send((name, bytes));
+        self.upload_rx = Some(rx);
+    }
+
+    /// Load an archive picked in the browser file dialog once it arrives
+    #[cfg(target_arch = "wasm32")]
+    fn poll_upload(&mut self) {
+        let Some(rx) = &self.upload_rx else { return };
+        match rx.try_recv() {
+            Ok((name, bytes)) => {
+                self.upload_rx = None;
+                self.load_bytes(name, bytes);
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.upload_rx = None,
+        }
+    }
+
+    /// Open files dropped on the window: by path natively, by contents in
+    /// the browser
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        let Some(file) = dropped.into_iter().find(|f| {
+            let name = f.path.as_ref().map(|p| p.to_string_lossy().into_owned()).unwrap_or_else(|| f.name.clone());
+            name.to_lowercase().ends_with(".abc")
+        }) else {
+            return;
+        };
+        if let Some(path) = file.path {
+            self.pending_file = Some(path);
+        } else if let Some(bytes) = file.bytes {
+            self.load_bytes(file.name, bytes.to_vec());
+        }
     }
 
     fn load_environment_dialog(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("HDR/EXR", &["hdr", "exr"])
-            .pick_file()
-        {
+        if let Some(path) = super::dialogs::pick_file("HDR/EXR", &["hdr", "exr"]) {
             self.load_environment(path);
         }
     }
@@ -1586,11 +2417,7 @@ impl ViewerApp {
             .map(|s| format!("{}_export.abc", s.to_string_lossy()))
             .unwrap_or_else(|| "export.abc".to_string());
         
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Alembic", &["abc"])
-            .set_file_name(&default_name)
-            .save_file()
-        {
+        if let Some(path) = super::dialogs::save_file("Alembic", &["abc"], &default_name) {
             self.status_message = "Exporting...".into();
             
             match super::export::export_archive(&archive, &path) {
@@ -1608,6 +2435,75 @@ impl ViewerApp {
         }
     }
 
+    /// Options window for "Export Preview"; runs the bake on "Export..."
+    fn preview_export_window(&mut self, ctx: &egui::Context) {
+        let Some(mut options) = self.preview_export else { return };
+        let last = self.num_samples.saturating_sub(1);
+        let mut open = true;
+        let mut export = false;
+        egui::Window::new("Export Preview")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("preview_export").num_columns(2).show(ui, |ui| {
+                    ui.label("Start frame:");
+                    ui.add(egui::DragValue::new(&mut options.start).range(0..=last));
+                    ui.end_row();
+                    ui.label("End frame:");
+                    ui.add(egui::DragValue::new(&mut options.end).range(options.start..=last));
+                    ui.end_row();
+                    ui.label("Detail:");
+                    ui.add(egui::Slider::new(&mut options.resolution, 0..=512))
+                        .on_hover_text("Grid cells along each mesh's longest axis (0 = full detail)");
+                    ui.end_row();
+                });
+                ui.label(egui::RichText::new(format!("{} hidden objects are skipped", self.selection.hidden.len())).weak());
+                export = ui.button("Export...").clicked();
+            });
+        options.end = options.end.max(options.start);
+        self.preview_export = open.then_some(options);
+        if export {
+            self.export_preview_dialog(options);
+        }
+    }
+
+    /// Ask for a path and bake the preview archive
+    fn export_preview_dialog(&mut self, options: super::export::PreviewOptions) {
+        let Some(archive) = self.archive.clone() else { return };
+        let default_name = self.current_file
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}_preview.abc", s.to_string_lossy()))
+            .unwrap_or_else(|| "preview.abc".to_string());
+        let Some(path) = super::dialogs::save_file("Alembic", &["abc"], &default_name) else {
+            return;
+        };
+
+        let selection = &self.selection;
+        match super::export::export_preview(&archive, &path, &options, |p| !selection.is_hidden(p)) {
+            Ok(stats) => {
+                self.status_message = format!(
+                    "Preview: {} meshes, {} frames, {} -> {} faces",
+                    stats.meshes, stats.frames, stats.faces_in, stats.faces_out
+                );
+                self.preview_export = None;
+            }
+            Err(e) => self.status_message = format!("Preview export failed: {}", e),
+        }
+    }
+
+    /// Push the exposure/white balance settings to the renderer
+    fn apply_exposure(&mut self) {
+        if let Some(renderer) = &mut self.viewport.renderer {
+            renderer.exposure = Exposure {
+                auto: self.settings.auto_exposure,
+                compensation_ev: self.settings.exposure_ev,
+                white_balance_k: self.settings.white_balance,
+                tint: self.settings.white_balance_tint,
+            };
+        }
+    }
+
     fn load_environment(&mut self, path: PathBuf) {
         let renderer = match &mut self.viewport.renderer {
             Some(r) => r,
@@ -1619,6 +2515,8 @@ impl ViewerApp {
         
         match renderer.load_environment(&path) {
             Ok(()) => {
+                // Keep the saved intensity instead of resetting to 1.0
+                renderer.set_env_intensity(self.settings.hdr_exposure);
                 self.status_message = format!("Loaded environment: {}", 
                     path.file_name().unwrap_or_default().to_string_lossy());
                 // Save HDR file and enable
@@ -1642,87 +2540,153 @@ impl ViewerApp {
             return;
         }
         
-        match crate::abc::IArchive::open(&path) {
-            Ok(archive) => {
-                // Detect animation - find max samples across all meshes
-                let num_samples = Self::detect_num_samples(&archive);
-                
-                // Build scene hierarchy tree
-                self.scene_tree = Self::build_scene_tree(&archive);
-                self.selected_object = None;
-                
-                // Store archive for animation playback
-                let archive = Arc::new(archive);
-                self.archive = Some(archive.clone());
-                self.num_samples = num_samples;
-                self.current_frame = 0;
-                self.playing = false;
-                
-                // Spawn background worker for async frame loading
-                self.worker = Some(super::worker::WorkerHandle::spawn(archive));
-                self.pending_frame = None;
-                
-                // Request frame 0
-                self.request_frame(0);
-                
-                self.current_file = Some(path.clone());
-                
-                // Add to recent files
-                self.settings.add_recent(path.clone());
-                self.settings.save();
-                
-                let frames_info = if num_samples > 1 {
-                    format!(", {} frames", num_samples)
-                } else {
-                    String::new()
-                };
-                
-                self.status_message = format!(
-                    "Loaded: {} meshes, {} vertices, {} triangles{}",
-                    self.mesh_count, self.vertex_count, self.face_count, frames_info
-                );
+        match alembic::abc::IArchive::open(&path) {
+            Ok(archive) => self.load_archive(archive, path),
+            Err(e) => {
+                self.status_message = format!("Error: {}", e);
             }
+        }
+    }
+
+    /// Load an archive from memory (browser uploads); `name` stands in for
+    /// the file path
+    fn load_bytes(&mut self, name: String, bytes: Vec<u8>) {
+        self.status_message = format!("Loading: {}", name);
+
+        if self.viewport.renderer.is_none() {
+            self.status_message = "Renderer not initialized".into();
+            return;
+        }
+
+        match alembic::abc::IArchive::from_bytes(name.clone(), bytes) {
+            Ok(archive) => self.load_archive(archive, PathBuf::from(name)),
             Err(e) => {
                 self.status_message = format!("Error: {}", e);
             }
         }
     }
+
+    /// Show a newly opened archive: outliner, timeline and frame worker
+    fn load_archive(&mut self, archive: alembic::abc::IArchive, path: PathBuf) {
+        // Detect animation - find max samples across all meshes
+        let num_samples = Self::detect_num_samples(&archive);
+        
+        // Build scene hierarchy tree
+        self.outliner = Outliner::new(&Self::build_scene_tree(&archive));
+        self.outliner.set_filter(&self.object_filter);
+        self.selected_object = None;
+        self.selection = Selection::default();
+        self.load_log.clear();
+        
+        // Store archive for animation playback
+        // Start frame: CLI --frame (first load only), otherwise 0
+        let start_frame = self.launch.frame.take()
+            .map(|f| Self::frame_to_sample_index(&archive, f, num_samples))
+            .unwrap_or(0);
+
+        // Frames where mesh/curve/point topology changes (timeline markers)
+        self.topology_changes = if num_samples > 1 {
+            super::topology::TopologyChanges::analyze(&archive)
+        } else {
+            Default::default()
+        };
+
+        let archive = Arc::new(archive);
+        self.archive = Some(archive.clone());
+        self.frame_stats = None;
+        self.frame_stats_rx = (num_samples > 1).then(|| FrameStats::spawn(archive.clone(), num_samples));
+        self.particle_trails = None;
+        self.num_samples = num_samples;
+        self.current_frame = 0;
+        self.applied_frame = None;
+        self.playing = false;
+        
+        // Spawn background worker for async frame loading
+        self.worker = Some(super::worker::WorkerHandle::spawn(archive));
+        self.pending_frame = None;
+        
+        self.scale_pending = true;
+        self.request_frame(start_frame);
+        
+        self.current_file = Some(path.clone());
+        
+        // Add to recent files
+        self.settings.add_recent(path.clone());
+        self.settings.save();
+        
+        let frames_info = if num_samples > 1 {
+            format!(", {} frames", num_samples)
+        } else {
+            String::new()
+        };
+        
+        self.status_message = format!(
+            "Loaded: {} meshes, {} vertices, {} triangles{}",
+            self.mesh_count, self.vertex_count, self.face_count, frames_info
+        );
+    }
     
+    /// First animated time sampling and its sample count (drives the timeline).
+    fn animated_sampling(archive: &alembic::abc::IArchive) -> Option<(&alembic::core::TimeSampling, usize)> {
+        (1..archive.getNumTimeSamplings()).find_map(|i| {
+            let ts = archive.getTimeSampling(i)?;
+            let n = archive.getMaxNumSamplesForTimeSamplingIndex(i).unwrap_or(0);
+            (!ts.is_identity() && n > 1).then_some((ts, n))
+        })
+    }
+
+    /// Map a scene frame number (e.g. 1015) to a sample index through the
+    /// archive's frame mapping (fps + Maya/Houdini convention).
+    /// Falls back to treating `frame` as a sample index.
+    fn frame_to_sample_index(archive: &alembic::abc::IArchive, frame: f64, num_samples: usize) -> usize {
+        let last = num_samples.saturating_sub(1);
+        match Self::animated_sampling(archive) {
+            Some((ts, n)) => archive.frame_mapping().frame_to_sample(ts, frame, n).min(last),
+            None => (frame.max(0.0).round() as usize).min(last),
+        }
+    }
+
+    /// Scene frame number of a sample index, if the archive is animated.
+    fn sample_index_to_frame(archive: &alembic::abc::IArchive, index: usize) -> Option<f64> {
+        let (ts, n) = Self::animated_sampling(archive)?;
+        Some(archive.frame_mapping().sample_to_frame(ts, index.min(n - 1), n))
+    }
+
     /// Detect maximum number of samples in archive
-    fn detect_num_samples(archive: &crate::abc::IArchive) -> usize {
+    fn detect_num_samples(archive: &alembic::abc::IArchive) -> usize {
         let root = archive.getTop();
         Self::detect_num_samples_recursive(&root, 1)
     }
     
-    fn detect_num_samples_recursive(obj: &crate::abc::IObject, max: usize) -> usize {
+    fn detect_num_samples_recursive(obj: &alembic::abc::IObject, max: usize) -> usize {
         let mut current_max = max;
         
         // Check ALL geometry schemas
-        if let Some(g) = crate::geom::IPolyMesh::new(obj) {
+        if let Some(g) = alembic::geom::IPolyMesh::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::ISubD::new(obj) {
+        if let Some(g) = alembic::geom::ISubD::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::ICurves::new(obj) {
+        if let Some(g) = alembic::geom::ICurves::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::IPoints::new(obj) {
+        if let Some(g) = alembic::geom::IPoints::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::INuPatch::new(obj) {
+        if let Some(g) = alembic::geom::INuPatch::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::IXform::new(obj) {
+        if let Some(g) = alembic::geom::IXform::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::ICamera::new(obj) {
+        if let Some(g) = alembic::geom::ICamera::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::ILight::new(obj) {
+        if let Some(g) = alembic::geom::ILight::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
-        if let Some(g) = crate::geom::IFaceSet::new(obj) {
+        if let Some(g) = alembic::geom::IFaceSet::new(obj) {
             current_max = current_max.max(g.getNumSamples());
         }
         
@@ -1735,7 +2699,7 @@ impl ViewerApp {
     }
     
     /// Build scene hierarchy tree from archive
-    fn build_scene_tree(archive: &crate::abc::IArchive) -> Vec<SceneNode> {
+    fn build_scene_tree(archive: &alembic::abc::IArchive) -> Vec<SceneNode> {
         let root = archive.getTop();
         let mut children = Vec::new();
         for child in root.getChildren() {
@@ -1744,29 +2708,136 @@ impl ViewerApp {
         children
     }
     
-    fn build_scene_node(obj: &crate::abc::IObject) -> SceneNode {
+    /// Push hidden state from `selection` to every renderer object
+    fn sync_hidden(renderer: &mut super::renderer::Renderer, selection: &Selection) {
+        let paths: Vec<String> = renderer.object_paths().map(str::to_string).collect();
+        for path in paths {
+            renderer.set_object_visible(&path, !selection.is_hidden(&path));
+        }
+    }
+
+    /// Re-sync visibility after a hide/unhide and restart PT accumulation
+    fn refresh_hidden(&mut self) {
+        if let Some(renderer) = &mut self.viewport.renderer {
+            Self::sync_hidden(renderer, &self.selection);
+            if let Some(pt) = &mut renderer.path_tracer {
+                pt.reset_accumulation();
+            }
+        }
+    }
+
+    /// Select an object by full path and expand its ancestors in the hierarchy
+    fn select_path(&mut self, path: &str) {
+        self.selected_object = Some(path.to_string());
+        self.selection.select_only(path);
+        self.outliner.reveal(path);
+    }
+
+    fn hide_selected(&mut self) {
+        let added = self.selection.hide_selected();
+        self.refresh_hidden();
+        self.status_message = format!("Hidden {} object(s)", added);
+    }
+
+    fn unhide_all(&mut self) {
+        self.selection.unhide_all();
+        self.refresh_hidden();
+        self.status_message = "All objects visible".into();
+    }
+
+    fn invert_selection(&mut self) {
+        let Some(renderer) = &self.viewport.renderer else { return };
+        let all: Vec<String> = renderer.object_paths().map(str::to_string).collect();
+        self.selection.invert(all.iter().map(String::as_str));
+        self.status_message = format!("Selected {} object(s)", self.selection.len());
+    }
+
+    /// Selection count, hide/unhide buttons and saved selection sets
+    fn selection_sets_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{} selected, {} hidden", self.selection.len(), self.selection.hidden.len()));
+        });
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("Hide"))
+                .on_hover_text("Hide selected (Ctrl+H)").clicked() {
+                self.hide_selected();
+            }
+            if ui.add_enabled(!self.selection.hidden.is_empty(), egui::Button::new("Unhide all"))
+                .on_hover_text("Alt+H").clicked() {
+                self.unhide_all();
+            }
+            if ui.button("Invert").on_hover_text("Ctrl+I").clicked() {
+                self.invert_selection();
+            }
+            if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("Clear")).clicked() {
+                self.selection.clear();
+            }
+        });
+
+        let Some(file) = self.current_file.clone() else { return };
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_set_name)
+                .hint_text("set name")
+                .desired_width(120.0));
+            let can_save = !self.new_set_name.trim().is_empty() && !self.selection.is_empty();
+            if ui.add_enabled(can_save, egui::Button::new("Save set")).clicked() {
+                let set = self.selection.to_set(self.new_set_name.trim());
+                let sets = self.settings.selection_sets_for_mut(&file);
+                match sets.iter_mut().find(|s| s.name == set.name) {
+                    Some(existing) => *existing = set,
+                    None => sets.push(set),
+                }
+                self.settings.save();
+                self.new_set_name.clear();
+            }
+        });
+
+        let sets: Vec<SelectionSet> = self.settings.selection_sets_for(&file).to_vec();
+        let mut remove = None;
+        for (i, set) in sets.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.button(set.name.as_str())
+                    .on_hover_text(format!("{} path(s)", set.paths.len()))
+                    .clicked() {
+                    self.selection.apply_set(set);
+                    self.status_message = format!("Selection set: {}", set.name);
+                }
+                if ui.small_button("✕").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.settings.selection_sets_for_mut(&file).remove(i);
+            self.settings.save();
+        }
+    }
+
+    fn build_scene_node(obj: &alembic::abc::IObject) -> SceneNode {
         let name = obj.getName();
         
         // Detect object type
-        let node_type = if crate::geom::IPolyMesh::new(obj).is_some() {
+        let node_type = if alembic::geom::IPolyMesh::new(obj).is_some() {
             "PolyMesh"
-        } else if crate::geom::IXform::new(obj).is_some() {
+        } else if alembic::geom::IXform::new(obj).is_some() {
             "Xform"
-        } else if crate::geom::ICamera::new(obj).is_some() {
+        } else if alembic::geom::ICamera::new(obj).is_some() {
             "Camera"
-        } else if crate::geom::ILight::new(obj).is_some() {
+        } else if alembic::geom::ILight::new(obj).is_some() {
             "Light"
-        } else if crate::geom::ICurves::new(obj).is_some() {
+        } else if alembic::geom::ICurves::new(obj).is_some() {
             "Curves"
-        } else if crate::geom::IPoints::new(obj).is_some() {
+        } else if alembic::geom::IPoints::new(obj).is_some() {
             "Points"
-        } else if crate::geom::ISubD::new(obj).is_some() {
+        } else if alembic::geom::ISubD::new(obj).is_some() {
             "SubD"
         } else {
             "Object"
         };
         
         let mut node = SceneNode::new(name, node_type);
+        node.path = obj.getFullName().to_string();
+        (node.verts, node.faces) = Self::geometry_counts(obj);
         
         for child in obj.getChildren() {
             node.children.push(Self::build_scene_node(&child));
@@ -1775,33 +2846,30 @@ impl ViewerApp {
         node
     }
     
-    /// Find node by name in scene tree
-    fn find_node_by_name(&self, name: &str) -> Option<SceneNode> {
-        Self::find_node_recursive(&self.scene_tree, name)
-    }
-    
-    fn find_node_recursive(nodes: &[SceneNode], name: &str) -> Option<SceneNode> {
-        for node in nodes {
-            if node.name == name {
-                return Some(node.clone());
-            }
-            if let Some(found) = Self::find_node_recursive(&node.children, name) {
-                return Some(found);
-            }
-        }
-        None
+    /// First-sample vertex and face counts from the array lengths (no
+    /// payload reads)
+    fn geometry_counts(obj: &alembic::abc::IObject) -> (Option<usize>, Option<usize>) {
+        let props = obj.getProperties();
+        let Some(geom) = props.getPropertyByName(".geom") else { return (None, None) };
+        let Some(geom) = geom.asCompound() else { return (None, None) };
+        let len = |name: &str| {
+            let prop = geom.getPropertyByName(name)?;
+            let array = prop.asArray().filter(|a| a.getNumSamples() > 0)?;
+            array.getSampleLen(0).ok()
+        };
+        (len("P"), len(".faceCounts"))
     }
-    
+
     /// Show object properties by searching archive
-    fn show_object_properties_by_name(&self, ui: &mut egui::Ui, archive: &crate::abc::IArchive, name: &str) {
+    fn show_object_properties_by_name(&self, ui: &mut egui::Ui, archive: &alembic::abc::IArchive, name: &str) {
         let root = archive.getTop();
         Self::show_props_recursive(ui, &root, name, self.current_frame);
     }
     
-    fn show_props_recursive(ui: &mut egui::Ui, obj: &crate::abc::IObject, name: &str, frame: usize) -> bool {
-        if obj.getName() == name {
+    fn show_props_recursive(ui: &mut egui::Ui, obj: &alembic::abc::IObject, name: &str, frame: usize) -> bool {
+        if obj.getName() == name || obj.getFullName() == name {
             // Found the object - show its properties
-            if let Some(mesh) = crate::geom::IPolyMesh::new(obj) {
+            if let Some(mesh) = alembic::geom::IPolyMesh::new(obj) {
                 ui.label("Type: PolyMesh");
                 let num_samples = mesh.getNumSamples();
                 ui.label(format!("Samples: {}", num_samples));
@@ -1839,7 +2907,7 @@ impl ViewerApp {
                         }
                     }
                 }
-            } else if let Some(xform) = crate::geom::IXform::new(obj) {
+            } else if let Some(xform) = alembic::geom::IXform::new(obj) {
                 ui.label("Type: Xform");
                 let num_samples = xform.getNumSamples();
                 ui.label(format!("Samples: {}", num_samples));
@@ -1859,7 +2927,7 @@ impl ViewerApp {
                             euler.0.to_degrees(), euler.1.to_degrees(), euler.2.to_degrees()));
                     }
                 }
-            } else if let Some(cam) = crate::geom::ICamera::new(obj) {
+            } else if let Some(cam) = alembic::geom::ICamera::new(obj) {
                 ui.label("Type: Camera");
                 let num_samples = cam.getNumSamples();
                 ui.label(format!("Samples: {}", num_samples));
@@ -1875,7 +2943,7 @@ impl ViewerApp {
                         ui.label(format!("Aperture: {:.1}mm", sample.horizontal_aperture));
                     }
                 }
-            } else if let Some(subd) = crate::geom::ISubD::new(obj) {
+            } else if let Some(subd) = alembic::geom::ISubD::new(obj) {
                 ui.label("Type: SubD".to_string());
                 let num_samples = subd.getNumSamples();
                 ui.label(format!("Samples: {}", num_samples));
@@ -1891,7 +2959,7 @@ impl ViewerApp {
                         ui.label(format!("Faces: {}", sample.face_counts.len()));
                     }
                 }
-            } else if let Some(curves) = crate::geom::ICurves::new(obj) {
+            } else if let Some(curves) = alembic::geom::ICurves::new(obj) {
                 ui.label("Type: Curves".to_string());
                 let num_samples = curves.getNumSamples();
                 ui.label(format!("Samples: {}", num_samples));
@@ -1907,7 +2975,7 @@ impl ViewerApp {
                         ui.label(format!("Curves: {}", sample.num_curves()));
                     }
                 }
-            } else if let Some(points) = crate::geom::IPoints::new(obj) {
+            } else if let Some(points) = alembic::geom::IPoints::new(obj) {
                 ui.label("Type: Points".to_string());
                 let num_samples = points.getNumSamples();
                 ui.label(format!("Samples: {}", num_samples));
@@ -1928,10 +2996,10 @@ impl ViewerApp {
                         }
                     }
                 }
-            } else if let Some(light) = crate::geom::ILight::new(obj) {
+            } else if let Some(light) = alembic::geom::ILight::new(obj) {
                 ui.label("Type: Light".to_string());
                 ui.label(format!("Samples: {}", light.getNumSamples()));
-            } else if let Some(mat) = crate::material::IMaterial::new(obj) {
+            } else if let Some(mat) = alembic::material::IMaterial::new(obj) {
                 ui.label("Type: Material".to_string());
                 let targets = mat.target_names();
                 ui.label(format!("Targets: {}", targets.join(", ")));
@@ -2054,18 +3122,62 @@ impl ViewerApp {
         self.archive = None;
         self.num_samples = 0;
         self.current_frame = 0;
+        self.applied_frame = None;
+        self.topology_changes = Default::default();
+        self.frame_stats = None;
+        self.frame_stats_rx = None;
+        self.particle_trails = None;
+        self.selection = Selection::default();
         self.playing = false;
         self.scene_is_static = false;
+        self.rest_pose_meshes = 0;
+        self.load_log.clear();
         self.last_scene_hash = None;
         self.status_message = "Scene cleared".into();
     }
     
+    /// Draw particle trails for the current frame, rebuilding them when the
+    /// frame or trail length changed.
+    fn draw_particle_trails(&mut self, ui: &egui::Ui) {
+        let (Some(archive), Some((view_proj, rect))) = (&self.archive, self.viewport.last_view_proj) else {
+            return;
+        };
+        let key = (self.current_frame, self.settings.trail_length);
+        if self.particle_trails.as_ref().map(|(k, _)| *k) != Some(key) {
+            let trails = super::trails::ParticleTrails::build(archive, key.0, key.1 as usize);
+            self.particle_trails = Some((key, trails));
+        }
+        if let Some((_, trails)) = &self.particle_trails {
+            if !trails.is_empty() {
+                trails.paint(&ui.painter_at(rect), view_proj, rect, self.settings.trail_color_by_speed);
+            }
+        }
+    }
+
+    /// Stop playback and go to `frame` (no-op for `None`).
+    fn jump_to_frame(&mut self, frame: Option<usize>) {
+        let Some(frame) = frame.filter(|&f| f < self.num_samples) else { return };
+        self.playing = false;
+        self.current_frame = frame;
+        self.request_frame(frame);
+    }
+
+    /// Effective rest-pose blend sent to the worker (0 when the toggle is off).
+    fn rest_blend(&self) -> f32 {
+        if self.settings.show_rest_pose { self.settings.rest_pose_blend } else { 0.0 }
+    }
+
     /// Request a frame to be loaded asynchronously.
     fn request_frame(&mut self, frame: usize) {
+        self.request_subframe(frame, 0.0);
+    }
+
+    /// Request a frame with Xforms blended `subframe` (0..1) toward the next sample.
+    fn request_subframe(&mut self, frame: usize, subframe: f32) {
         if let Some(worker) = &self.worker {
             // Increment epoch on every request - this allows us to discard stale results
             self.epoch = self.epoch.wrapping_add(1);
-            worker.request_frame(frame, self.epoch);
+            worker.request_frame(frame, subframe, self.epoch, self.rest_blend());
             self.pending_frame = Some(frame);
         }
     }
@@ -2094,7 +3206,7 @@ impl ViewerApp {
     }
     
     /// Apply scene data to renderer (called when worker delivers results).
-    fn apply_scene(&mut self, frame: usize, scene: mesh_converter::CollectedScene) {
+    fn apply_scene(&mut self, frame: usize, mut scene: mesh_converter::CollectedScene) {
         let _span = tracing::info_span!("apply_scene").entered();
         let renderer = match &mut self.viewport.renderer {
             Some(r) => r,
@@ -2102,6 +3214,8 @@ impl ViewerApp {
         };
 
         self.scene_is_static = scene.is_static;
+        self.rest_pose_meshes = scene.rest_pose_meshes;
+        self.load_log.extend(std::mem::take(&mut scene.warnings));
         if self.scene_is_static {
             self.playing = false;
             self.pending_frame = None;
@@ -2140,6 +3254,22 @@ impl ViewerApp {
         // Always update scene cameras (clear stale data when loading new file)
         self.scene_cameras = scene.cameras;
 
+        // CLI --camera: match full path first, then bare name
+        if let Some(wanted) = self.launch.camera.take() {
+            let found = self.scene_cameras.iter().position(|c| c.path == wanted)
+                .or_else(|| {
+                    let name = wanted.rsplit('/').next().unwrap_or(&wanted);
+                    self.scene_cameras.iter().position(|c| c.name == name)
+                });
+            match found {
+                Some(i) => self.active_camera = Some(i),
+                None => {
+                    tracing::warn!("Camera not found: {}", wanted);
+                    self.status_message = format!("Camera not found: {}", wanted);
+                }
+            }
+        }
+
         // Always update scene lights (clear stale data when loading new file)
         self.scene_lights = scene.lights;
         // Apply scene lights if setting enabled
@@ -2162,7 +3292,14 @@ impl ViewerApp {
         renderer.points.retain(|path, _| new_point_paths.contains(path.as_str()));
 
         let mut smooth_dirty = false;
-        let t_meshes = std::time::Instant::now();
+        let t_meshes = web_time::Instant::now();
+
+        // Motion blur: frame-to-frame motion only between neighbouring samples
+        if let Some(archive) = &self.archive {
+            renderer.motion_blur_fps = archive.frame_mapping().fps as f32;
+        }
+        renderer.begin_motion_step(self.applied_frame.is_some_and(|f| f.abs_diff(frame) == 1));
+        self.applied_frame = Some(frame);
 
         // Update or add meshes (use path as key for uniqueness)
         for mesh in scene.meshes.into_iter() {
@@ -2172,56 +3309,34 @@ impl ViewerApp {
                 let old_hash = renderer.get_vertex_hash(&mesh.path).unwrap_or(0);
                 if mesh.data_hash != old_hash {
                     let bounds = (mesh.bounds.min, mesh.bounds.max);
-                    renderer.update_mesh_vertices(&mesh.path, &mesh.vertices, &mesh.indices, mesh.data_hash, bounds);
+                    let velocities = mesh.velocities.as_deref().map(Vec::as_slice);
+                    renderer.update_mesh_vertices(&mesh.path, &mesh.vertices, &mesh.indices, velocities, mesh.data_hash, bounds);
+                    if self.settings.display_mode == DisplayMode::RandomFaceSet {
+                        let material = mesh_converter::display_material(&mesh, self.settings.display_mode, self.settings.materialize_missing);
+                        let groups = mesh_converter::face_set_materials(&mesh, mesh.indices.len() as u32, &material);
+                        renderer.set_mesh_face_set_materials(&mesh.path, &groups);
+                    }
                     smooth_dirty = true;
                 }
             } else {
-                // Build material from mesh properties
-                // Check if mesh has explicit material, or use auto-materialization
-                let has_material = mesh.base_color.is_some() || mesh.metallic.is_some() 
-                    || mesh.roughness.is_some() || mesh.transmission.is_some();
-                
-                let (base_color, roughness, metallic, transmission, ior) = if has_material {
-                    // Use explicit material properties
-                    (
-                        mesh.base_color.unwrap_or(Vec3::new(0.7, 0.7, 0.75)),
-                        mesh.roughness.unwrap_or(0.4),
-                        mesh.metallic.unwrap_or(0.0),
-                        mesh.transmission.unwrap_or(0.0),
-                        mesh.specular_ior.unwrap_or(1.5),
-                    )
-                } else if self.settings.materialize_missing {
-                    // Auto-materialize based on path
-                    guess_material_from_path(&mesh.path)
-                } else {
-                    // Default grey plastic
-                    (Vec3::new(0.7, 0.7, 0.75), 0.4, 0.0, 0.0, 1.5)
-                };
-                
-                let mut material = if transmission > 0.1 {
-                    // Glass-like material with transmission
-                    StandardSurfaceParams::glass(base_color, ior)
-                } else if metallic > 0.5 {
-                    StandardSurfaceParams::metal(base_color, roughness)
+                let material = mesh_converter::display_material(&mesh, self.settings.display_mode, self.settings.materialize_missing);
+                let face_sets = if self.settings.display_mode == DisplayMode::RandomFaceSet {
+                    mesh_converter::face_set_materials(&mesh, mesh.indices.len() as u32, &material)
                 } else {
-                    StandardSurfaceParams::plastic(base_color, roughness)
+                    Vec::new()
                 };
                 
-                // Apply additional properties
-                if transmission > 0.1 {
-                    // Glass: set roughness separately (glass() defaults to 0)
-                    material.params1.z = roughness;
-                }
-                material.set_metalness(metallic);
-                
                 renderer.add_mesh(
-                    mesh.path,  // Use path for unique key
+                    mesh.path.clone(),  // Use path for unique key
                     &mesh.vertices,
                     &mesh.indices,
                     mesh.transform,
                     &material,
                     mesh.smooth_data,
                 );
+                if !face_sets.is_empty() {
+                    renderer.set_mesh_face_set_materials(&mesh.path, &face_sets);
+                }
                 smooth_dirty = true;
             }
         }
@@ -2282,6 +3397,11 @@ impl ViewerApp {
             tracing::warn!("apply_scene: update took {meshes_ms:.1}ms ({n_meshes} meshes, {n_curves} curves)");
         }
 
+        // New objects come in visible; re-apply hidden state
+        if !self.selection.hidden.is_empty() {
+            Self::sync_hidden(renderer, &self.selection);
+        }
+
         if self.settings.smooth_normals && smooth_dirty {
             renderer.recalculate_smooth_normals(
                 self.settings.smooth_angle,
@@ -2290,9 +3410,9 @@ impl ViewerApp {
             );
         }
         
-        let t_pt = std::time::Instant::now();
+        let t_pt = web_time::Instant::now();
         // Init/update path tracer if enabled
-        if self.settings.path_tracing {
+        if self.settings.path_tracing && renderer.supports_path_tracing() {
             renderer.use_path_tracing = true;
             // Sync all PT settings from saved settings
             renderer.pt_max_samples = self.settings.pt_max_samples;
@@ -2302,6 +3422,11 @@ impl ViewerApp {
             renderer.pt_aperture = self.settings.pt_aperture;
             renderer.pt_focus_distance = self.settings.pt_focus_distance;
             renderer.pt_global_opacity = self.settings.pt_global_opacity;
+            renderer.pt_shadow_catcher = self.settings.pt_shadow_catcher;
+            renderer.pt_split_view = self.settings.pt_split_view;
+            renderer.pt_split_position = self.settings.pt_split_position;
+            renderer.pt_denoise = self.settings.pt_denoise;
+            renderer.pt_resolution_scale = self.settings.pt_resolution_scale;
             if renderer.path_tracer.is_none() {
                 renderer.init_path_tracer(1280, 720);
             }
@@ -2321,6 +3446,12 @@ impl ViewerApp {
         self.vertex_count = stats.vertex_count;
         self.face_count = stats.triangle_count;
         self.current_frame = frame;
+
+        // First frame of a newly loaded file: adopt its scale
+        if self.scale_pending && self.scene_bounds.is_some() {
+            self.scale_pending = false;
+            self.detect_scene_scale(self.settings.frame_on_load);
+        }
     }
     
     /// Find next or previous file with given extensions in the same directory
@@ -2371,6 +3502,338 @@ impl ViewerApp {
     }
     
     /// Navigate to next or previous ABC file in directory
+    /// Run a keymap action. Returns false if the app is closing.
+    fn run_action(&mut self, ctx: &egui::Context, action: Action) -> bool {
+        match action {
+            // Escape - exit fullscreen first, then close app
+            Action::Exit => {
+                if self.quick_menu.take().is_some() {
+                    // Just dismiss the quick menu
+                } else if self.is_fullscreen {
+                    self.is_fullscreen = false;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
+                } else {
+                    if let Some(mut worker) = self.worker.take() {
+                        worker.stop();
+                    }
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                    return false;
+                }
+            }
+            Action::ToggleFullscreen => {
+                self.is_fullscreen = !self.is_fullscreen;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.is_fullscreen));
+            }
+            // Navigate ABC / HDR files in the same directory
+            Action::PrevFile => self.navigate_sibling_abc(-1),
+            Action::NextFile => self.navigate_sibling_abc(1),
+            Action::PrevEnvironment => self.navigate_sibling_hdr(-1),
+            Action::NextEnvironment => self.navigate_sibling_hdr(1),
+            Action::HomeCamera => {
+                self.viewport.camera.reset();
+                self.status_message = "Camera reset".into();
+            }
+            Action::HideSelected => self.hide_selected(),
+            Action::UnhideAll => self.unhide_all(),
+            Action::InvertSelection => self.invert_selection(),
+            Action::FocusSelected => self.focus_camera(true),
+            Action::FrameAll => self.focus_camera(false),
+            Action::PlayPause => {
+                if self.num_samples > 1 {
+                    self.playing = !self.playing;
+                }
+            }
+            Action::PrevTopologyChange => {
+                self.jump_to_frame(self.topology_changes.prev_before(self.current_frame));
+            }
+            Action::NextTopologyChange => {
+                self.jump_to_frame(self.topology_changes.next_after(self.current_frame));
+            }
+            // Frame step also sets the playback direction
+            Action::PrevFrame => {
+                if self.num_samples > 1 {
+                    self.playing = false;
+                    self.playback_dir = -1;
+                    let prev = if self.current_frame == 0 { self.num_samples - 1 } else { self.current_frame - 1 };
+                    self.request_frame(prev);
+                }
+            }
+            Action::NextFrame => {
+                if self.num_samples > 1 {
+                    self.playing = false;
+                    self.playback_dir = 1;
+                    let next = (self.current_frame + 1) % self.num_samples;
+                    self.request_frame(next);
+                }
+            }
+            Action::FirstFrame => {
+                if self.num_samples > 0 {
+                    self.playing = false;
+                    self.request_frame(0);
+                }
+            }
+        }
+        true
+    }
+
+    /// Detect the scene scale from the bounds and archive metadata and apply it.
+    /// With `reframe`, the orbit camera is fitted to the scene when its arm
+    /// length is more than 10x off the scene size (e.g. a cm cache opened with
+    /// a camera left at meter scale).
+    fn detect_scene_scale(&mut self, reframe: bool) {
+        let radius = self.scene_bounds.as_ref().map_or(0.0, |b| b.radius());
+        let meta = self.archive.as_ref().map(|a| a.getArchiveMetaData());
+        self.scene_scale = SceneScale::detect(self.settings.scene_unit, meta, radius);
+        self.apply_scene_scale();
+        if reframe && self.scene_scale.radius > 0.0 {
+            let ratio = self.viewport.camera.distance / (self.scene_scale.radius * 2.5);
+            if !(0.1..=10.0).contains(&ratio) {
+                self.focus_camera(false);
+            }
+        }
+        tracing::info!("Scene scale: radius {}", self.scene_scale.describe());
+    }
+
+    /// Push the scene scale and clip/grid settings to the orbit camera and grid
+    fn apply_scene_scale(&mut self) {
+        let camera = &mut self.viewport.camera;
+        camera.set_scene_radius(self.scene_scale.radius);
+        camera.auto_clip = self.settings.auto_clip;
+        camera.near_clip = self.settings.clip_near;
+        camera.far_clip = self.settings.clip_far;
+        if let Some(renderer) = &mut self.viewport.renderer {
+            renderer.grid_unit = self.scene_scale.units_per_meter();
+            renderer.grid_step_override = self.settings.grid_step;
+        }
+    }
+
+    /// Scene scale submenu: unit override, clip planes, grid step
+    fn scene_scale_menu(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("Scene radius: {}", self.scene_scale.describe()));
+        let mut changed = false;
+        let mut redetect = false;
+        ui.horizontal(|ui| {
+            ui.label("Unit:");
+            for unit in SceneUnit::ALL {
+                redetect |= ui.selectable_value(&mut self.settings.scene_unit, unit, unit.as_str()).changed();
+            }
+        });
+        ui.separator();
+        changed |= ui.checkbox(&mut self.settings.auto_clip, "Auto Clip Planes")
+            .on_hover_text("Derive near/far from the camera distance and scene size")
+            .changed();
+        let (near, far) = (self.settings.clip_near, self.settings.clip_far);
+        ui.add_enabled_ui(!self.settings.auto_clip, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Near:");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.clip_near)
+                    .speed(near * 0.01).range(1e-6..=far)).changed();
+                ui.label("Far:");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.clip_far)
+                    .speed(far * 0.01).range(near..=1e9)).changed();
+            });
+        });
+        let mut fixed_grid = self.settings.grid_step.is_some();
+        if ui.checkbox(&mut fixed_grid, "Fixed Grid Step").changed() {
+            self.settings.grid_step = fixed_grid.then(|| self.scene_scale.units_per_meter());
+            changed = true;
+        }
+        if let Some(step) = &mut self.settings.grid_step {
+            let speed = *step * 0.01;
+            changed |= ui.add(egui::DragValue::new(step).speed(speed).range(1e-6..=1e9).prefix("Step: ")).changed();
+        }
+        changed |= ui.checkbox(&mut self.settings.frame_on_load, "Frame Scene on Load")
+            .on_hover_text("Fit the camera when a loaded scene is far larger or smaller than the current view")
+            .changed();
+        if redetect {
+            self.detect_scene_scale(false);
+        } else if changed {
+            self.apply_scene_scale();
+        }
+        if changed || redetect {
+            self.settings.save();
+        }
+    }
+
+    /// Focus on the selected object (or fit the whole scene if nothing is selected
+    /// or `selected` is false). Also sets DoF focus to the new camera arm distance.
+    fn focus_camera(&mut self, selected: bool) {
+        let mut focused = false;
+        if let Some(name) = self.selected_object.as_ref().filter(|_| selected) {
+            if let Some(renderer) = &self.viewport.renderer {
+                // A group frames every mesh below it
+                let bounds = renderer.meshes.iter()
+                    .filter(|(path, _)| super::selection::is_under(path, name))
+                    .map(|(_, mesh)| mesh.bounds)
+                    .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)));
+                if let Some((min, max)) = bounds {
+                    let center = (min + max) * 0.5;
+                    let radius = (max - min).length() * 0.5;
+                    self.viewport.camera.focus(center, radius.max(0.1));
+                    self.status_message = format!("Focus: {} (r={:.2})", name, radius);
+                    focused = true;
+                }
+            }
+        }
+        if !focused {
+            if let Some(bounds) = &self.scene_bounds {
+                self.viewport.camera.focus(bounds.center(), bounds.radius().max(0.1));
+                self.status_message = format!("Fit to scene (radius: {:.2})", bounds.radius());
+            } else {
+                self.viewport.camera.focus(glam::Vec3::ZERO, 5.0);
+                self.status_message = "No scene bounds".into();
+            }
+        }
+        // Set DoF focus point = camera pivot (recomputed to distance each frame)
+        let focus_pt = self.viewport.camera.target;
+        self.settings.pt_focus_distance = self.viewport.camera.distance;
+        if let Some(renderer) = &mut self.viewport.renderer {
+            renderer.pt_focus_point = Some(focus_pt);
+            renderer.pt_focus_distance = self.viewport.camera.distance;
+            if let Some(pt) = &mut renderer.path_tracer {
+                pt.reset_accumulation();
+            }
+        }
+    }
+
+    /// Hold-to-show action menu under the cursor (Maya hotbox / Blender Q menu)
+    fn update_quick_menu(&mut self, ctx: &egui::Context) {
+        let Some(key) = self.viewport.keymap.menu_key else {
+            self.quick_menu = None;
+            return;
+        };
+        let (pressed, down, pos) = ctx.input(|i| {
+            (i.key_pressed(key) && i.modifiers.is_none(), i.key_down(key), i.pointer.hover_pos())
+        });
+        if pressed && !ctx.wants_keyboard_input() {
+            self.quick_menu = pos;
+        }
+        let Some(pos) = self.quick_menu else { return };
+
+        const ITEMS: [Action; 8] = [
+            Action::FocusSelected,
+            Action::FrameAll,
+            Action::HomeCamera,
+            Action::HideSelected,
+            Action::UnhideAll,
+            Action::InvertSelection,
+            Action::PlayPause,
+            Action::ToggleFullscreen,
+        ];
+        let mut chosen = None;
+        egui::Area::new(egui::Id::new("quick_menu"))
+            .fixed_pos(pos)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    for action in ITEMS {
+                        let shortcut = self.viewport.keymap.describe(action);
+                        if ui.add(egui::Button::new(action.label()).shortcut_text(shortcut)).clicked() {
+                            chosen = Some(action);
+                        }
+                    }
+                });
+            });
+        // Release the key (or pick an item) to dismiss
+        if !down || chosen.is_some() {
+            self.quick_menu = None;
+        }
+        if let Some(action) = chosen {
+            self.run_action(ctx, action);
+        }
+    }
+
+    /// Persist keymap preset and overrides
+    fn save_keymap(&mut self) {
+        self.settings.keymap_preset = self.viewport.keymap.preset;
+        self.settings.key_bindings = self.viewport.keymap.overrides();
+        self.settings.save();
+    }
+
+    /// "Keymap" window: preset selection and per-action rebinding
+    fn keymap_window(&mut self, ctx: &egui::Context) {
+        if !self.show_keymap {
+            return;
+        }
+        let mut open = true;
+        let mut changed = false;
+        egui::Window::new("Keymap")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let keymap = &mut self.viewport.keymap;
+                let mut preset = keymap.preset;
+                egui::ComboBox::from_label("Preset")
+                    .selected_text(preset.as_str())
+                    .show_ui(ui, |ui| {
+                        for p in KeymapPreset::ALL {
+                            ui.selectable_value(&mut preset, p, p.as_str());
+                        }
+                    });
+                if preset != keymap.preset {
+                    // Keep user overrides on top of the new preset
+                    *keymap = Keymap::from_settings(preset, &keymap.overrides());
+                    changed = true;
+                }
+
+                let mouse = keymap.mouse;
+                ui.label(RichText::new(format!(
+                    "Orbit: {}   Pan: {}   Zoom: {} / wheel",
+                    mouse.orbit.label(), mouse.pan.label(), mouse.zoom.label()
+                )).weak());
+                if let Some(key) = keymap.menu_key {
+                    ui.label(RichText::new(format!("Hold {} for the quick menu", key.name())).weak());
+                }
+                ui.separator();
+
+                egui::Grid::new("keymap_grid").num_columns(3).striped(true).show(ui, |ui| {
+                    for action in Action::ALL {
+                        ui.label(action.label());
+                        let text = if self.rebinding == Some(action) {
+                            "press a key... (Esc cancels)".to_string()
+                        } else {
+                            keymap.describe(action)
+                        };
+                        let mut button = ui.button(text);
+                        if let Some(chord) = keymap.chords(action).first() {
+                            let conflicts = keymap.conflicts(action, *chord);
+                            if !conflicts.is_empty() {
+                                let names: Vec<_> = conflicts.iter().map(|a| a.label()).collect();
+                                button = button.on_hover_text(format!("Also bound to: {}", names.join(", ")));
+                            }
+                        }
+                        if button.clicked() {
+                            self.rebinding = Some(action);
+                        }
+                        ui.horizontal(|ui| {
+                            if ui.small_button("Clear").clicked() {
+                                keymap.set(action, Vec::new());
+                                changed = true;
+                            }
+                            if keymap.is_overridden(action) && ui.small_button("Reset").clicked() {
+                                keymap.reset(action);
+                                changed = true;
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+                ui.separator();
+                if ui.button("Reset all to preset").clicked() {
+                    *keymap = Keymap::new(keymap.preset);
+                    changed = true;
+                }
+            });
+        if changed {
+            self.save_keymap();
+        }
+        if !open {
+            self.show_keymap = false;
+            self.rebinding = None;
+        }
+    }
+
     fn navigate_sibling_abc(&mut self, direction: i32) {
         if let Some(current) = &self.current_file {
             if let Some(path) = Self::find_sibling_file(current, direction, &["abc"]) {
@@ -2438,7 +3901,7 @@ impl eframe::App for ViewerApp {
             renderer.update_frame_time(frame_time_ms);
         }
 
-        let _update_start = std::time::Instant::now();
+        let _update_start = web_time::Instant::now();
         macro_rules! checkpoint {
             ($name:expr) => {
                 let _cp_elapsed = _update_start.elapsed().as_secs_f64() * 1000.0;
@@ -2461,7 +3924,33 @@ impl eframe::App for ViewerApp {
                 }
             })
         });
+
+        // Raster DoF lens: the scene camera's own, or a virtual 35mm lens on the orbit camera
+        if let Some(renderer) = &mut self.viewport.renderer {
+            let units_per_meter = self.scene_scale.units_per_meter();
+            renderer.dof_lens = match self.active_camera.and_then(|i| self.scene_cameras.get(i)) {
+                Some(cam) => DofLens {
+                    focal_length: cam.focal_length,
+                    f_stop: cam.f_stop,
+                    focus_distance: cam.focus_distance,
+                    film_height: cam.v_aperture * 10.0,
+                    units_per_meter,
+                },
+                None => DofLens {
+                    focal_length: 12.0 / (self.viewport.camera.fov.to_radians() * 0.5).tan(),
+                    f_stop: self.settings.dof_f_stop,
+                    focus_distance: renderer.pt_focus_distance,
+                    film_height: 24.0,
+                    units_per_meter,
+                },
+            };
+        }
         
+        self.viewport.show_perf_overlay = self.settings.show_perf_overlay;
+        self.viewport.render_scale = self.settings.render_scale;
+        self.viewport.dynamic_resolution = self.settings.dynamic_resolution;
+        self.viewport.dynamic_resolution_fps = self.settings.dynamic_resolution_fps;
+
         // Near/far planes are now calculated dynamically in OrbitCamera::near()/far()
         // based on camera distance, which provides stable z-buffer behavior
         
@@ -2482,112 +3971,42 @@ impl eframe::App for ViewerApp {
         self.process_worker_results();
         checkpoint!("worker_results");
         
-        // Escape - exit fullscreen first, then close app
-        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            if self.is_fullscreen {
-                self.is_fullscreen = false;
-                ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
-            } else {
-                if let Some(mut worker) = self.worker.take() {
-                    worker.stop();
-                }
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                return;
-            }
-        }
-        
-        // Z = Toggle fullscreen
-        if ctx.input(|i| i.key_pressed(egui::Key::Z)) {
-            self.is_fullscreen = !self.is_fullscreen;
-            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.is_fullscreen));
-        }
-        
-        // Navigate ABC files in directory: PageUp/Left = prev, PageDown/Right = next
-        // Navigate HDR files: Ctrl+PageUp/Left = prev, Ctrl+PageDown/Right = next
-        if ctx.input(|i| i.key_pressed(egui::Key::PageUp) || i.key_pressed(egui::Key::ArrowLeft)) {
-            if ctx.input(|i| i.modifiers.ctrl) {
-                self.navigate_sibling_hdr(-1);
-            } else {
-                self.navigate_sibling_abc(-1);
-            }
-        }
-        if ctx.input(|i| i.key_pressed(egui::Key::PageDown) || i.key_pressed(egui::Key::ArrowRight)) {
-            if ctx.input(|i| i.modifiers.ctrl) {
-                self.navigate_sibling_hdr(1);
-            } else {
-                self.navigate_sibling_abc(1);
-            }
-        }
-        
-        // H = Home camera (reset to default view)
-        if ctx.input(|i| i.key_pressed(egui::Key::H)) {
-            self.viewport.camera.reset();
-            self.status_message = "Camera reset".into();
-        }
-        
-        // F = Focus on selected object, or fit whole scene if nothing selected
-        //     Also sets DoF focus distance to the new camera arm distance
-        if ctx.input(|i| i.key_pressed(egui::Key::F)) {
-            let mut focused = false;
-            if let Some(name) = &self.selected_object {
-                if let Some(renderer) = &self.viewport.renderer {
-                    if let Some(mesh) = renderer.meshes.get(name.as_str()) {
-                        let (min, max) = mesh.bounds;
-                        let center = (min + max) * 0.5;
-                        let radius = (max - min).length() * 0.5;
-                        self.viewport.camera.focus(center, radius.max(0.1));
-                        self.status_message = format!("Focus: {} (r={:.2})", name, radius);
-                        focused = true;
-                    }
-                }
-            }
-            if !focused {
-                if let Some(bounds) = &self.scene_bounds {
-                    self.viewport.camera.focus(bounds.center(), bounds.radius().max(0.1));
-                    self.status_message = format!("Fit to scene (radius: {:.2})", bounds.radius());
-                } else {
-                    self.viewport.camera.focus(glam::Vec3::ZERO, 5.0);
-                    self.status_message = "No scene bounds".into();
+        // Keyboard shortcuts (see keymap.rs; rebinding captures the next key instead)
+        if let Some(action) = self.rebinding {
+            if let Some(chord) = ctx.input(KeyChord::capture) {
+                if chord.key != egui::Key::Escape {
+                    self.viewport.keymap.set(action, vec![chord]);
+                    self.save_keymap();
                 }
+                self.rebinding = None;
             }
-            // Set DoF focus point = camera pivot (recomputed to distance each frame)
-            let focus_pt = self.viewport.camera.target;
-            self.settings.pt_focus_distance = self.viewport.camera.distance;
-            if let Some(renderer) = &mut self.viewport.renderer {
-                renderer.pt_focus_point = Some(focus_pt);
-                renderer.pt_focus_distance = self.viewport.camera.distance;
-                if let Some(pt) = &mut renderer.path_tracer {
-                    pt.reset_accumulation();
+        } else {
+            let keymap = &self.viewport.keymap;
+            let actions: Vec<Action> = ctx.input(|i| {
+                Action::ALL.into_iter().filter(|&a| keymap.pressed(i, a)).collect()
+            });
+            for action in actions {
+                if !self.run_action(ctx, action) {
+                    return;
                 }
             }
+            self.update_quick_menu(ctx);
         }
 
-        // Playback controls (unified)
-        // Space/Up = play/pause toggle
-        let toggle_play = ctx.input(|i| {
-            i.key_pressed(egui::Key::Space) || i.key_pressed(egui::Key::ArrowUp)
-        });
-        if toggle_play && self.num_samples > 1 {
-            self.playing = !self.playing;
-        }
-
-        // Left/Right = frame step + set playback direction
-        if ctx.input(|i| i.key_pressed(egui::Key::ArrowLeft)) && self.num_samples > 1 {
-            self.playing = false;
-            self.playback_dir = -1;
-            let prev = if self.current_frame == 0 { self.num_samples - 1 } else { self.current_frame - 1 };
-            self.request_frame(prev);
-        }
-        if ctx.input(|i| i.key_pressed(egui::Key::ArrowRight)) && self.num_samples > 1 {
-            self.playing = false;
-            self.playback_dir = 1;
-            let next = (self.current_frame + 1) % self.num_samples;
-            self.request_frame(next);
+        // SpaceMouse / gamepad navigation (buttons trigger keymap actions)
+        self.nav_devices.update(ctx, self.settings.spacemouse_enabled, self.settings.gamepad_enabled);
+        let (nav, nav_actions) = self.nav_devices.poll();
+        for action in nav_actions {
+            if !self.run_action(ctx, action) {
+                return;
+            }
         }
-        // Down = go to first frame
-        if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) && self.num_samples > 0 {
-            self.playing = false;
-            self.request_frame(0);
+        if !nav.is_idle() {
+            let dt = ctx.input(|i| i.stable_dt);
+            let speed = self.settings.nav_device_speed;
+            self.viewport.camera.navigate(nav.orbit * speed, nav.pan * speed, nav.zoom * speed, dt);
+            self.turntable_last_input = Instant::now();
+            ctx.request_repaint();
         }
 
         checkpoint!("input_keys");
@@ -2611,6 +4030,14 @@ impl eframe::App for ViewerApp {
                     renderer.use_ssao = self.settings.ssao;
                     renderer.ssao_strength = self.settings.ssao_strength;
                     renderer.ssao_radius = self.settings.ssao_radius;
+                    renderer.motion_blur = self.settings.motion_blur;
+                    renderer.motion_blur_shutter = self.settings.motion_blur_shutter;
+                    renderer.motion_blur_samples = self.settings.motion_blur_samples;
+                    renderer.dof = self.settings.dof;
+                    renderer.dof_max_blur_px = self.settings.dof_max_blur;
+                    renderer.bloom = self.settings.bloom;
+                    renderer.bloom_threshold = self.settings.bloom_threshold;
+                    renderer.bloom_intensity = self.settings.bloom_intensity;
                     renderer.hdr_visible = self.settings.hdr_visible;
                     renderer.xray_alpha = self.settings.xray_alpha;
                     renderer.double_sided = self.settings.double_sided;
@@ -2631,16 +4058,23 @@ impl eframe::App for ViewerApp {
                     renderer.pt_aperture = self.settings.pt_aperture;
                     renderer.pt_focus_distance = self.settings.pt_focus_distance;
                     renderer.pt_global_opacity = self.settings.pt_global_opacity;
+                    renderer.pt_shadow_catcher = self.settings.pt_shadow_catcher;
+                    renderer.pt_split_view = self.settings.pt_split_view;
+                    renderer.pt_split_position = self.settings.pt_split_position;
+                    renderer.pt_denoise = self.settings.pt_denoise;
+                    renderer.pt_resolution_scale = self.settings.pt_resolution_scale;
                     // Set floor if enabled (uses scene_bounds for sizing)
                     if self.settings.show_floor {
                         renderer.set_floor(&self.scene_bounds);
                     }
                 }
+                self.apply_exposure();
                 // Ensure settings file exists
                 self.settings.save();
                 // Apply saved camera settings
                 self.viewport.camera.set_distance(self.settings.camera_distance);
                 self.viewport.camera.set_angles(self.settings.camera_yaw, self.settings.camera_pitch);
+                self.apply_scene_scale();
                 // Restore HDR if was enabled
                 if self.settings.hdr_enabled {
                     if let Some(path) = self.settings.last_hdr_file.clone() {
@@ -2649,11 +4083,18 @@ impl eframe::App for ViewerApp {
                         }
                     }
                 }
+                // CLI environment wins over the restored one
+                if let Some(path) = self.launch.env.take() {
+                    self.pending_hdr_file = Some(path);
+                }
             }
         }
         
-        // Load pending file (from CLI argument or recent)
+        // Load pending file (from CLI argument, recent or drag and drop)
         if self.viewport.renderer.is_some() {
+            self.handle_dropped_files(ctx);
+            #[cfg(target_arch = "wasm32")]
+            self.poll_upload();
             if let Some(path) = self.pending_file.take() {
                 self.load_file(path);
             }
@@ -2686,7 +4127,7 @@ impl eframe::App for ViewerApp {
         });
 
         // Left panel - object hierarchy
-        if !self.scene_tree.is_empty() {
+        if !self.outliner.is_empty() {
             let response = SidePanel::left("hierarchy_panel")
                 .default_width(self.settings.hierarchy_panel_width)
                 .min_width(100.0)
@@ -2718,12 +4159,35 @@ impl eframe::App for ViewerApp {
         }
 
         checkpoint!("panels_ui");
+        self.update_camera_path_overlay();
+        if let Some(renderer) = &mut self.viewport.renderer {
+            renderer.apply_overlay_commands(&self.overlays);
+        }
         // Central viewport
         CentralPanel::default().show(ctx, |ui| {
             let render_state = frame.wgpu_render_state();
             self.viewport.show(ui, render_state);
+            if self.settings.show_particle_trails {
+                self.draw_particle_trails(ui);
+            }
         });
 
+        if let Some(path) = self.load_log.show(ctx) {
+            self.select_path(&path);
+        }
+        self.preview_export_window(ctx);
+        self.keymap_window(ctx);
+
+        // Camera path recording samples whatever camera is being looked through
+        if self.camera_path.is_recording() {
+            let (view, fov) = match &self.viewport.scene_camera {
+                Some(sc) => (sc.view, sc.fov_y.to_degrees()),
+                None => (self.viewport.camera.view_matrix(), self.viewport.camera.fov),
+            };
+            self.camera_path.tick(view, fov);
+            ctx.request_repaint();
+        }
+
         // Auto turntable: rotate camera if enabled and no user input for 1 second
         if self.settings.turntable_enabled {
             if self.viewport.camera_moved_by_user {
@@ -2793,6 +4257,7 @@ impl eframe::App for ViewerApp {
                     eprintln!("OBJECT PICK: \"{}\" at ({:.1}, {:.1}, {:.1}), z_depth={:.2}",
                         pick.mesh_name, pick.point.x, pick.point.y, pick.point.z, pick.t);
                     self.selected_object = Some(pick.mesh_name.clone());
+                    self.selection.select_only(&pick.mesh_name);
                     // Auto-set DoF focus to picked world point
                     self.settings.pt_focus_distance = pick.t;
                     if let Some(renderer) = &mut self.viewport.renderer {
@@ -2805,6 +4270,7 @@ impl eframe::App for ViewerApp {
                 } else {
                     eprintln!("OBJECT PICK: <miss>");
                     self.selected_object = None;
+                    self.selection.clear();
                 }
             }
         }
@@ -0,0 +1,317 @@
+//! Soundtrack for animation review
+//!
+//! A WAV file is decoded to interleaved f32 in memory ([`WavClip`]) and
+//! placed on the timeline at a start frame ([`AudioTrack`]). While playing
+//! forward the [`AudioPlayer`] output stream is the playback clock: the app
+//! reads [`AudioPlayer::clock`] and shows the matching sample, dropping
+//! frames rather than letting picture and sound drift apart.
+//!
+//! Output goes through cpal with the `audio` feature (default). Without it
+//! the waveform and offset still work, the player just stays silent.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+
+/// Peak envelope resolution (blocks per second) for the timeline waveform
+const PEAKS_PER_SECOND: f64 = 200.0;
+
+/// Decoded WAV file
+pub struct WavClip {
+    pub path: PathBuf,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Interleaved samples in -1..1
+    pub samples: Vec<f32>,
+    /// Max |sample| per block of `sample_rate / PEAKS_PER_SECOND` frames
+    peaks: Vec<f32>,
+}
+
+impl WavClip {
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        let (sample_rate, channels, samples) = decode_wav(&bytes)
+            .with_context(|| format!("decoding {}", path.display()))?;
+        let mut clip = Self { path: path.to_path_buf(), sample_rate, channels, samples, peaks: Vec::new() };
+        clip.peaks = clip.compute_peaks();
+        Ok(clip)
+    }
+
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1) as usize
+    }
+
+    /// Length in seconds
+    pub fn duration(&self) -> f64 {
+        self.frames() as f64 / self.sample_rate as f64
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    /// Loudest sample between clip times `t0` and `t1` (seconds); 0 outside the clip
+    pub fn peak(&self, t0: f64, t1: f64) -> f32 {
+        let (a, b) = (t0.min(t1).max(0.0), t0.max(t1));
+        let first = (a * PEAKS_PER_SECOND) as usize;
+        let last = ((b * PEAKS_PER_SECOND) as usize).min(self.peaks.len().saturating_sub(1));
+        if b < 0.0 || first >= self.peaks.len() {
+            return 0.0;
+        }
+        self.peaks[first..=last.max(first)].iter().fold(0.0f32, |m, &p| m.max(p))
+    }
+
+    fn compute_peaks(&self) -> Vec<f32> {
+        let channels = self.channels.max(1) as usize;
+        let block = ((self.sample_rate as f64 / PEAKS_PER_SECOND).round() as usize).max(1) * channels;
+        self.samples.chunks(block)
+            .map(|c| c.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+            .collect()
+    }
+}
+
+/// Parse a RIFF/WAVE file: PCM 8/16/24/32-bit, IEEE float 32/64-bit, plain
+/// or WAVE_FORMAT_EXTENSIBLE. Returns (sample rate, channels, samples).
+fn decode_wav(bytes: &[u8]) -> Result<(u32, u16, Vec<f32>)> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        bail!("not a RIFF/WAVE file");
+    }
+    let u16_at = |o: usize| u16::from_le_bytes([bytes[o], bytes[o + 1]]);
+    let u32_at = |o: usize| u32::from_le_bytes([bytes[o], bytes[o + 1], bytes[o + 2], bytes[o + 3]]);
+
+    let mut format = None;
+    let mut data = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32_at(pos + 4) as usize;
+        let body = pos + 8;
+        let end = (body + size).min(bytes.len());
+        match id {
+            b"fmt " if size >= 16 => {
+                let mut tag = u16_at(body);
+                // WAVE_FORMAT_EXTENSIBLE: the real tag leads the sub-format GUID
+                if tag == 0xFFFE && size >= 26 {
+                    tag = u16_at(body + 24);
+                }
+                format = Some((tag, u16_at(body + 2), u32_at(body + 4), u16_at(body + 14)));
+            }
+            b"data" => data = Some(&bytes[body..end]),
+            _ => {}
+        }
+        // Chunks are word aligned
+        pos = body + size + (size & 1);
+    }
+
+    let Some((tag, channels, rate, bits)) = format else { bail!("missing fmt chunk") };
+    let Some(data) = data else { bail!("missing data chunk") };
+    if channels == 0 || rate == 0 {
+        bail!("invalid format: {} channels at {} Hz", channels, rate);
+    }
+    let samples: Vec<f32> = match (tag, bits) {
+        (1, 8) => data.iter().map(|&b| (b as f32 - 128.0) / 128.0).collect(),
+        (1, 16) => data.chunks_exact(2).map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0).collect(),
+        (1, 24) => data.chunks_exact(3)
+            .map(|c| (i32::from_le_bytes([0, c[0], c[1], c[2]]) >> 8) as f32 / 8_388_608.0)
+            .collect(),
+        (1, 32) => data.chunks_exact(4)
+            .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f32 / 2_147_483_648.0)
+            .collect(),
+        (3, 32) => data.chunks_exact(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect(),
+        (3, 64) => data.chunks_exact(8)
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap_or_default()) as f32)
+            .collect(),
+        _ => bail!("unsupported encoding (format {}, {} bits)", tag, bits),
+    };
+    Ok((rate, channels, samples))
+}
+
+/// A clip placed on the timeline
+pub struct AudioTrack {
+    pub clip: Arc<WavClip>,
+    /// Scene frame at which the clip starts (the timecode offset)
+    pub start_frame: f64,
+    pub muted: bool,
+}
+
+impl AudioTrack {
+    /// Clip time (seconds) at scene frame `frame` when playing at `fps`
+    pub fn clip_time(&self, frame: f64, fps: f64) -> f64 {
+        (frame - self.start_frame) / fps
+    }
+
+    /// Scene frame at clip time `time`
+    pub fn scene_frame(&self, time: f64, fps: f64) -> f64 {
+        self.start_frame + time * fps
+    }
+}
+
+/// State shared with the output callback
+#[derive(Default)]
+struct PlayState {
+    clip: Option<Arc<WavClip>>,
+    /// Read position in clip frames (negative = before the clip starts)
+    position: f64,
+    playing: bool,
+}
+
+impl PlayState {
+    /// Mix the next `out.len() / channels` frames into `out` (f32, interleaved)
+    #[cfg_attr(not(feature = "audio"), allow(dead_code))]
+    fn render(&mut self, out: &mut [f32], channels: usize, device_rate: u32) {
+        out.fill(0.0);
+        let Some(clip) = self.clip.as_ref().filter(|_| self.playing) else { return };
+        let step = clip.sample_rate as f64 / device_rate as f64;
+        let clip_channels = clip.channels as usize;
+        let frames = clip.frames();
+        for frame in out.chunks_mut(channels) {
+            let pos = self.position;
+            self.position += step;
+            if pos < 0.0 || pos >= frames as f64 {
+                continue;
+            }
+            // Linear resampling between neighbouring clip frames
+            let i = pos as usize;
+            let t = (pos - i as f64) as f32;
+            let j = (i + 1).min(frames - 1);
+            for (c, out) in frame.iter_mut().enumerate() {
+                let c = c.min(clip_channels - 1);
+                let a = clip.samples[i * clip_channels + c];
+                let b = clip.samples[j * clip_channels + c];
+                *out = a + (b - a) * t;
+            }
+        }
+    }
+}
+
+/// Audio output driven by the timeline
+#[derive(Default)]
+pub struct AudioPlayer {
+    state: Arc<Mutex<PlayState>>,
+    #[cfg(feature = "audio")]
+    stream: Option<cpal::Stream>,
+    /// Why output is unavailable, for the UI
+    pub error: Option<String>,
+}
+
+impl AudioPlayer {
+    /// Replace the clip (stops playback). Opens the output device on first use.
+    pub fn set_clip(&mut self, clip: Option<Arc<WavClip>>) {
+        {
+            let mut state = self.state.lock();
+            state.playing = false;
+            state.clip = clip;
+        }
+        if self.state.lock().clip.is_some() {
+            self.open();
+        }
+    }
+
+    /// Start (or keep) playing from clip time `time`, seeking if it isn't already there
+    pub fn play_from(&mut self, time: f64) {
+        let mut state = self.state.lock();
+        let Some(rate) = state.clip.as_ref().map(|c| c.sample_rate as f64) else { return };
+        if !state.playing {
+            state.position = time * rate;
+            state.playing = true;
+        }
+    }
+
+    /// Jump to clip time `time` without changing the play state
+    pub fn seek(&mut self, time: f64) {
+        let mut state = self.state.lock();
+        if let Some(rate) = state.clip.as_ref().map(|c| c.sample_rate as f64) {
+            state.position = time * rate;
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.state.lock().playing = false;
+    }
+
+    /// Current clip time while playing through a working output device
+    pub fn clock(&self) -> Option<f64> {
+        if !self.is_available() {
+            return None;
+        }
+        let state = self.state.lock();
+        let clip = state.clip.as_ref().filter(|_| state.playing)?;
+        Some(state.position / clip.sample_rate as f64)
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn is_available(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    #[cfg(not(feature = "audio"))]
+    pub fn is_available(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "audio")]
+    fn open(&mut self) {
+        use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+        if self.stream.is_some() {
+            return;
+        }
+        let result = (|| -> Result<cpal::Stream> {
+            let device = cpal::default_host().default_output_device().context("no audio output device")?;
+            let supported = device.default_output_config()?;
+            let format = supported.sample_format();
+            let config: cpal::StreamConfig = supported.into();
+            let stream = match format {
+                cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, self.state.clone())?,
+                cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, self.state.clone())?,
+                cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, self.state.clone())?,
+                cpal::SampleFormat::I32 => build_stream::<i32>(&device, &config, self.state.clone())?,
+                other => bail!("unsupported output sample format {:?}", other),
+            };
+            stream.play()?;
+            Ok(stream)
+        })();
+        match result {
+            Ok(stream) => {
+                self.stream = Some(stream);
+                self.error = None;
+            }
+            Err(e) => {
+                tracing::warn!("Audio output unavailable: {:#}", e);
+                self.error = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    #[cfg(not(feature = "audio"))]
+    fn open(&mut self) {
+        self.error = Some("built without the `audio` feature".into());
+    }
+}
+
+#[cfg(feature = "audio")]
+fn build_stream<T>(device: &cpal::Device, config: &cpal::StreamConfig, state: Arc<Mutex<PlayState>>) -> Result<cpal::Stream>
+where
+    T: cpal::SizedSample + cpal::FromSample<f32>,
+{
+    use cpal::traits::DeviceTrait;
+
+    let channels = config.channels as usize;
+    let rate = config.sample_rate;
+    let mut mix = Vec::new();
+    let stream = device.build_output_stream(
+        config,
+        move |out: &mut [T], _: &cpal::OutputCallbackInfo| {
+            mix.resize(out.len(), 0.0f32);
+            state.lock().render(&mut mix, channels, rate);
+            for (o, &s) in out.iter_mut().zip(&mix) {
+                *o = T::from_sample(s);
+            }
+        },
+        |e| tracing::warn!("Audio stream error: {}", e),
+        None,
+    )?;
+    Ok(stream)
+}
@@ -0,0 +1,390 @@
+//! Headless path-traced batch rendering
+//!
+//! Renders a frame range of an archive with the viewer's path tracer, without
+//! a window, and writes one EXR per frame - a minimal farm renderer for cache
+//! QC. Several frames are traced at once, each with its own scene upload and
+//! accumulation buffer; how many is bounded by `frames_in_flight` and by the
+//! VRAM budget (estimated from the first frame's scene size).
+//!
+//! Each EXR is linear RGBA float and carries `alembic:frame`,
+//! `alembic:sample`, `alembic:camera`, `alembic:spp`, `alembic:fps` and
+//! `alembic:source` header attributes. Lighting is the path tracer's procedural sky; meshes
+//! use their assigned materials (grey plastic otherwise).
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic_viewer::batch::{render_batch, BatchOptions};
+//!
+//! let mut opts = BatchOptions::new("shot.abc", "qc/shot.####.exr");
+//! opts.frames = Some((1001.0, 1100.0));
+//! opts.spp = 128;
+//! render_batch(&opts, |f| println!("{} ({:.1}s)", f.path.display(), f.seconds))?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Context, Result};
+use glam::{Mat4, Vec3};
+
+use super::camera::wgpu_projection;
+use super::mesh_converter::{self, CollectedScene};
+use super::pathtracer::{build, gpu_data, scene_convert, GpuSceneData, PathTraceCompute, PtCameraUniform};
+
+/// Vertical field of view of the fallback camera (no scene camera)
+const FALLBACK_FOV_Y: f32 = 0.6;
+/// Dispatches submitted between GPU waits, so the queue doesn't grow unbounded
+const SUBMIT_BATCH: u32 = 16;
+
+/// What to render and where.
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub input: PathBuf,
+    /// Output path; a run of `#` is replaced by the zero-padded frame number
+    /// ("qc/shot.####.exr"). Without `#`, the frame is added before the extension.
+    pub output: String,
+    /// Inclusive scene frame range (mapped through the archive time sampling);
+    /// `None` renders every sample.
+    pub frames: Option<(f64, f64)>,
+    /// Frame step within `frames`
+    pub step: f64,
+    pub width: u32,
+    pub height: u32,
+    /// Samples per pixel
+    pub spp: u32,
+    pub max_bounces: u32,
+    /// Scene camera by full path or name; `None` uses the first scene camera,
+    /// or frames the whole scene when there is none
+    pub camera: Option<String>,
+    /// Upper bound on frames traced at once
+    pub frames_in_flight: usize,
+    /// VRAM budget in bytes for the frames in flight
+    pub vram_budget: u64,
+}
+
+impl BatchOptions {
+    pub fn new(input: impl Into<PathBuf>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            frames: None,
+            step: 1.0,
+            width: 1280,
+            height: 720,
+            spp: 64,
+            max_bounces: 4,
+            camera: None,
+            frames_in_flight: 4,
+            vram_budget: 2 << 30,
+        }
+    }
+}
+
+/// One written frame.
+#[derive(Debug, Clone)]
+pub struct RenderedFrame {
+    /// Scene frame number
+    pub frame: f64,
+    /// Archive sample index that was rendered
+    pub sample_index: usize,
+    pub path: PathBuf,
+    /// Camera path, or "auto" for the fallback framing
+    pub camera: String,
+    pub triangles: usize,
+    /// Wall time from scene load to written EXR
+    pub seconds: f64,
+}
+
+/// A frame prepared on the CPU, waiting for its GPU slot.
+struct PreparedFrame {
+    frame: f64,
+    sample_index: usize,
+    camera_name: String,
+    camera: PtCameraUniform,
+    scene: GpuSceneData,
+    max_object_id: u32,
+    started: Instant,
+}
+
+/// Render the frames of `opts` and write one EXR each; `on_frame` is called
+/// after every written file.
+pub fn render_batch(opts: &BatchOptions, mut on_frame: impl FnMut(&RenderedFrame)) -> Result<Vec<RenderedFrame>> {
+    if opts.width == 0 || opts.height == 0 || opts.spp == 0 {
+        bail!("width, height and spp must be positive");
+    }
+    let archive = alembic::abc::IArchive::open(&opts.input)
+        .with_context(|| format!("failed to open {}", opts.input.display()))?;
+    let frames = frame_list(&archive, opts)?;
+    let (device, queue) = headless_device()?;
+    let cache = mesh_converter::new_mesh_cache();
+    let aspect = opts.width as f32 / opts.height as f32;
+    let fps = archive.frame_mapping().fps;
+
+    // The fallback camera frames the first frame and stays put, so a
+    // sequence doesn't swim
+    let mut fallback = None;
+    let mut prepare = |frame: f64, sample_index: usize| -> Result<PreparedFrame> {
+        let started = Instant::now();
+        let scene = mesh_converter::collect_scene_cached(&archive, sample_index, 0.0, Some(&cache), 0.0);
+        let (camera_name, view, fov_y, near, far) = pick_camera(&scene, opts.camera.as_deref(), &mut fallback)?;
+        let (gpu, max_object_id) = build_scene(&scene);
+        let proj = wgpu_projection(fov_y, aspect, near, far);
+        let inv_view = view.inverse();
+        Ok(PreparedFrame {
+            frame,
+            sample_index,
+            camera_name,
+            camera: PtCameraUniform {
+                inv_view: inv_view.to_cols_array_2d(),
+                inv_proj: proj.inverse().to_cols_array_2d(),
+                position: inv_view.w_axis.truncate().to_array(),
+                _pad0: 0,
+                frame_count: 0,
+                max_bounces: opts.max_bounces,
+                max_transmission_depth: 8,
+                dof_enabled: 0,
+                aperture: 0.0,
+                focus_distance: 1.0,
+                global_opacity: 1.0,
+                shadow_catcher_id: 0,
+                _pad2: [0; 4],
+            },
+            scene: gpu,
+            max_object_id,
+            started,
+        })
+    };
+
+    let mut pending = frames.into_iter();
+    let Some((frame, index)) = pending.next() else { bail!("no frames to render") };
+    let first = prepare(frame, index)?;
+    let in_flight = frames_in_flight(opts, &first.scene);
+    tracing::info!("batch: {} frame(s) in flight", in_flight);
+
+    let mut slots: Vec<PathTraceCompute> = Vec::new();
+    let mut written = Vec::new();
+    let mut batch = vec![first];
+    loop {
+        while batch.len() < in_flight {
+            let Some((frame, index)) = pending.next() else { break };
+            batch.push(prepare(frame, index)?);
+        }
+        if batch.is_empty() {
+            break;
+        }
+        while slots.len() < batch.len() {
+            slots.push(PathTraceCompute::new(&device, opts.width, opts.height, wgpu::TextureFormat::Rgba8Unorm));
+        }
+
+        for (slot, frame) in slots.iter_mut().zip(&batch) {
+            slot.upload_scene(&device, &queue, &frame.scene, frame.max_object_id);
+            slot.update_camera(&queue, &frame.camera);
+            slot.max_samples = opts.spp;
+        }
+        // Interleave the frames so they all progress on the GPU together
+        for spp in 1..=opts.spp {
+            for slot in slots.iter_mut().take(batch.len()) {
+                slot.dispatch(&device, &queue);
+            }
+            if spp % SUBMIT_BATCH == 0 {
+                let _ = device.poll(wgpu::PollType::wait_indefinitely());
+            }
+        }
+
+        for (slot, frame) in slots.iter().zip(batch.drain(..)) {
+            let pixels = slot.read_output(&device, &queue);
+            let path = frame_path(&opts.output, frame.frame);
+            write_exr(&path, opts, &frame, fps, &pixels)?;
+            let done = RenderedFrame {
+                frame: frame.frame,
+                sample_index: frame.sample_index,
+                path,
+                camera: frame.camera_name,
+                triangles: frame.scene.tri_count as usize,
+                seconds: frame.started.elapsed().as_secs_f64(),
+            };
+            on_frame(&done);
+            written.push(done);
+        }
+    }
+    Ok(written)
+}
+
+/// (scene frame, sample index) pairs to render
+fn frame_list(archive: &alembic::abc::IArchive, opts: &BatchOptions) -> Result<Vec<(f64, usize)>> {
+    let sampling = (1..archive.getNumTimeSamplings()).find_map(|i| {
+        let ts = archive.getTimeSampling(i)?;
+        let n = archive.getMaxNumSamplesForTimeSamplingIndex(i).unwrap_or(0);
+        (!ts.is_identity() && n > 1).then_some((ts, n))
+    });
+    let mapping = archive.frame_mapping();
+    let Some((a, b)) = opts.frames else {
+        return Ok(match sampling {
+            Some((ts, n)) => (0..n).map(|i| (mapping.sample_to_frame(ts, i, n), i)).collect(),
+            None => vec![(0.0, 0)],
+        });
+    };
+    if opts.step <= 0.0 || b < a {
+        bail!("invalid frame range {}-{} step {}", a, b, opts.step);
+    }
+    let count = ((b - a) / opts.step).floor() as usize + 1;
+    Ok((0..count)
+        .map(|k| {
+            let frame = a + k as f64 * opts.step;
+            let index = match sampling {
+                Some((ts, n)) => mapping.frame_to_sample(ts, frame, n).min(n - 1),
+                None => 0,
+            };
+            (frame, index)
+        })
+        .collect())
+}
+
+/// World-to-camera matrix, vertical FOV and clip range for a frame
+fn pick_camera(
+    scene: &CollectedScene,
+    wanted: Option<&str>,
+    fallback: &mut Option<Mat4>,
+) -> Result<(String, Mat4, f32, f32, f32)> {
+    let camera = match wanted {
+        Some(key) => Some(
+            scene.cameras.iter()
+                .find(|c| c.path == key || c.name == key)
+                .ok_or_else(|| anyhow!("camera not found: {}", key))?,
+        ),
+        None => scene.cameras.first(),
+    };
+    if let Some(cam) = camera {
+        let near = cam.near.max(1e-3);
+        let far = cam.far.max(near * 2.0);
+        return Ok((cam.path.clone(), cam.transform.inverse(), cam.fov_y(), near, far));
+    }
+
+    let bounds = mesh_converter::compute_scene_bounds(&scene.meshes, &scene.points, &scene.curves);
+    let radius = bounds.radius().max(0.1);
+    let view = *fallback.get_or_insert_with(|| {
+        let center = bounds.center();
+        let distance = radius / (FALLBACK_FOV_Y * 0.5).tan() * 1.1;
+        let eye = center + Vec3::new(1.0, 0.6, 1.0).normalize() * distance;
+        Mat4::look_at_rh(eye, center, Vec3::Y)
+    });
+    Ok(("auto".to_string(), view, FALLBACK_FOV_Y, radius * 0.01, radius * 100.0))
+}
+
+/// Triangles, BVH and materials of every mesh in `scene`
+fn build_scene(scene: &CollectedScene) -> (GpuSceneData, u32) {
+    let mut triangles = Vec::new();
+    let mut materials = Vec::new();
+    for (i, mesh) in scene.meshes.iter().enumerate() {
+        let material_id = materials.len() as u32;
+        let object_id = i as u32 + 1;
+        materials.push(scene_convert::material_from_params(&mesh_converter::mesh_material(mesh, false)));
+        triangles.extend(scene_convert::extract_triangles(&mesh.vertices, &mesh.indices, &mesh.transform, material_id, object_id));
+    }
+    if materials.is_empty() {
+        materials.push(scene_convert::default_material());
+    }
+    let bvh = build::build_bvh(&triangles);
+    (gpu_data::build_gpu_data(&bvh, &triangles, &materials), scene.meshes.len() as u32)
+}
+
+/// Frames traced at once: accumulation + output + scene buffers per frame
+fn frames_in_flight(opts: &BatchOptions, scene: &GpuSceneData) -> usize {
+    let image = opts.width as u64 * opts.height as u64 * 32;
+    let scene_bytes = (scene.nodes_bytes().len() + scene.triangles_bytes().len() + scene.materials_bytes().len()) as u64;
+    let per_frame = image + scene_bytes;
+    ((opts.vram_budget / per_frame.max(1)) as usize).clamp(1, opts.frames_in_flight.max(1))
+}
+
+fn headless_device() -> Result<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        force_fallback_adapter: false,
+        compatible_surface: None,
+    }))
+    .context("no GPU adapter available")?;
+    tracing::info!("batch: rendering on {}", adapter.get_info().name);
+
+    // Same limits as the interactive viewer, so scenes that trace there trace here
+    let mut features = wgpu::Features::empty();
+    if adapter.features().contains(wgpu::Features::FLOAT32_FILTERABLE) {
+        features |= wgpu::Features::FLOAT32_FILTERABLE;
+    }
+    let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        label: Some("alembic-batch device"),
+        required_features: features,
+        required_limits: wgpu::Limits {
+            max_texture_dimension_2d: 8192,
+            max_bind_groups: 8,
+            max_storage_buffer_binding_size: 512 * 1024 * 1024,
+            max_buffer_size: 512 * 1024 * 1024,
+            ..wgpu::Limits::default()
+        },
+        ..Default::default()
+    }))
+    .context("failed to create GPU device")?;
+    Ok((device, queue))
+}
+
+/// Output path for `frame`: replace the first `#` run with the padded frame
+/// number, or add ".NNNN" before the extension when there is none
+pub fn frame_path(pattern: &str, frame: f64) -> PathBuf {
+    let number = frame.round() as i64;
+    if let Some(start) = pattern.find('#') {
+        let len = pattern[start..].chars().take_while(|&c| c == '#').count();
+        let padded = format!("{:0width$}", number, width = len);
+        return PathBuf::from(format!("{}{}{}", &pattern[..start], padded, &pattern[start + len..]));
+    }
+    let path = Path::new(pattern);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = path.extension().map(|e| e.to_string_lossy().into_owned()).unwrap_or_else(|| "exr".into());
+    path.with_file_name(format!("{}.{:04}.{}", stem, number, ext))
+}
+
+fn write_exr(path: &Path, opts: &BatchOptions, frame: &PreparedFrame, fps: f64, pixels: &[f32]) -> Result<()> {
+    use exr::prelude::*;
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let width = opts.width as usize;
+    let mut attributes = LayerAttributes::named("beauty");
+    attributes.software_name = Some(Text::from("alembic-rs batch renderer"));
+    let mut set = |name: &str, value: AttributeValue| {
+        attributes.other.insert(Text::from(name), value);
+    };
+    set("alembic:frame", AttributeValue::F64(frame.frame));
+    set("alembic:sample", AttributeValue::I32(frame.sample_index as i32));
+    set("alembic:camera", AttributeValue::Text(Text::from(frame.camera_name.as_str())));
+    set("alembic:spp", AttributeValue::I32(opts.spp as i32));
+    set("alembic:fps", AttributeValue::F64(fps));
+    set("alembic:source", AttributeValue::Text(Text::from(opts.input.to_string_lossy().as_ref())));
+
+    let layer = Layer::new(
+        (width, opts.height as usize),
+        attributes,
+        Encoding::SMALL_FAST_LOSSLESS,
+        SpecificChannels::rgba(|pos: Vec2<usize>| {
+            let i = (pos.y() * width + pos.x()) * 4;
+            (pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3])
+        }),
+    );
+    Image::from_layer(layer)
+        .write()
+        .to_file(path)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_path() {
+        assert_eq!(frame_path("qc/shot.####.exr", 1001.0), PathBuf::from("qc/shot.1001.exr"));
+        assert_eq!(frame_path("shot.##.exr", 7.0), PathBuf::from("shot.07.exr"));
+        assert_eq!(frame_path("qc/shot.exr", 12.0), PathBuf::from("qc/shot.0012.exr"));
+    }
+}
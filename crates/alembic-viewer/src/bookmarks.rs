@@ -0,0 +1,100 @@
+//! Camera bookmarks and fly-through path recording
+
+use glam::{Mat4, Vec3};
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use super::camera::OrbitCamera;
+
+/// Named orbit camera viewpoint (persisted in settings)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub target: [f32; 3],
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub fov: f32,
+}
+
+impl CameraBookmark {
+    /// Capture the current orbit camera state
+    pub fn capture(name: &str, camera: &OrbitCamera) -> Self {
+        Self {
+            name: name.to_string(),
+            target: camera.target.to_array(),
+            yaw: camera.yaw,
+            pitch: camera.pitch,
+            distance: camera.distance,
+            fov: camera.fov,
+        }
+    }
+
+    /// Restore this viewpoint on the orbit camera
+    pub fn apply(&self, camera: &mut OrbitCamera) {
+        camera.kill_inertia();
+        camera.target = Vec3::from_array(self.target);
+        camera.set_angles(self.yaw, self.pitch);
+        camera.set_distance(self.distance);
+        camera.fov = self.fov;
+    }
+}
+
+/// One recorded camera sample
+#[derive(Debug, Clone, Copy)]
+pub struct CameraPathKey {
+    /// Camera-to-world transform
+    pub world: Mat4,
+    /// Vertical FOV in degrees
+    pub fov: f32,
+}
+
+/// Fly-through recorder: samples the viewing camera at a fixed rate
+#[derive(Debug, Clone)]
+pub struct CameraPathRecorder {
+    pub fps: f32,
+    pub keys: Vec<CameraPathKey>,
+    recording: bool,
+    last_sample: Option<Instant>,
+}
+
+impl CameraPathRecorder {
+    pub fn new(fps: f32) -> Self {
+        Self { fps, keys: Vec::new(), recording: false, last_sample: None }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Start a new recording (clears previous keys)
+    pub fn start(&mut self) {
+        self.keys.clear();
+        self.recording = true;
+        self.last_sample = None;
+    }
+
+    pub fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    /// Record the current camera if a sample is due. Call once per UI frame.
+    pub fn tick(&mut self, view: Mat4, fov: f32) {
+        if !self.recording {
+            return;
+        }
+        let interval = 1.0 / self.fps.max(1.0);
+        let due = self.last_sample
+            .map(|t| t.elapsed().as_secs_f32() >= interval)
+            .unwrap_or(true);
+        if due {
+            self.last_sample = Some(Instant::now());
+            self.keys.push(CameraPathKey { world: view.inverse(), fov });
+        }
+    }
+
+    /// Duration of the recorded path in seconds
+    pub fn duration(&self) -> f32 {
+        self.keys.len().saturating_sub(1) as f32 / self.fps.max(1.0)
+    }
+}
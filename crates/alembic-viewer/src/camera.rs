@@ -1,6 +1,6 @@
 //! Simple Maya-style orbit camera using glam (no external rig library)
 
-use glam::{Mat4, Vec3, Quat};
+use glam::{Mat4, Vec2, Vec3, Quat};
 
 const OPENGL_TO_WGPU_MATRIX: Mat4 = Mat4::from_cols_array(&[
     1.0, 0.0, 0.0, 0.0,
@@ -25,6 +25,13 @@ pub struct OrbitCamera {
     pub distance: f32,
     /// Vertical FOV in degrees
     pub fov: f32,
+    /// Derive clip planes from the camera distance and scene radius
+    pub auto_clip: bool,
+    /// Near/far planes used when `auto_clip` is off
+    pub near_clip: f32,
+    pub far_clip: f32,
+    /// Scene bounding radius; scales the auto clip planes and zoom range (0 = unknown)
+    scene_radius: f32,
 
     // Inertia velocities (per second)
     vel_yaw: f32,
@@ -45,6 +52,10 @@ impl OrbitCamera {
             pitch: -30.0,
             distance,
             fov: 45.0,
+            auto_clip: true,
+            near_clip: 0.1,
+            far_clip: 10000.0,
+            scene_radius: 0.0,
             vel_yaw: 0.0,
             vel_pitch: 0.0,
             vel_pan: Vec3::ZERO,
@@ -54,8 +65,34 @@ impl OrbitCamera {
         }
     }
 
-    pub fn near(&self) -> f32 { 0.1 }
-    pub fn far(&self) -> f32 { 10000.0 }
+    /// Near plane: 1% of the arm length, kept within 1e5 of the far plane
+    pub fn near(&self) -> f32 {
+        if !self.auto_clip {
+            return self.near_clip;
+        }
+        (self.distance * 0.01).max(self.far() * 1e-5)
+    }
+
+    /// Far plane: far enough to see the whole scene and the grid from here
+    pub fn far(&self) -> f32 {
+        if !self.auto_clip {
+            return self.far_clip;
+        }
+        (self.distance * 100.0).max((self.distance + self.scene_radius * 2.0) * 2.0)
+    }
+
+    /// Set the scene radius used for auto clip planes and the zoom range
+    pub fn set_scene_radius(&mut self, radius: f32) {
+        self.scene_radius = radius.max(0.0);
+        self.distance = self.clamp_distance(self.distance);
+    }
+
+    /// Zoom range: never tighter than 0.01..50000, widened for huge or tiny scenes
+    fn clamp_distance(&self, distance: f32) -> f32 {
+        let r = self.scene_radius;
+        let (min, max) = if r > 0.0 { ((r * 1e-4).min(0.01), (r * 1e3).max(50000.0)) } else { (0.01, 50000.0) };
+        distance.clamp(min, max)
+    }
 
     /// Call when user starts dragging
     pub fn begin_drag(&mut self) {
@@ -99,10 +136,23 @@ impl OrbitCamera {
     pub fn zoom(&mut self, delta: f32) {
         let sensitivity = 0.003;
         let factor = (-delta * sensitivity).exp();
-        self.distance = (self.distance * factor).clamp(0.01, 50000.0);
+        self.distance = self.clamp_distance(self.distance * factor);
         self.vel_zoom = delta * sensitivity;
     }
 
+    /// Rate-based navigation (SpaceMouse / gamepad), axes in -1..1.
+    /// Full deflection orbits 120 deg/s, pans one camera distance per second
+    /// and dollies by e^1.5 per second.
+    pub fn navigate(&mut self, orbit: Vec2, pan: Vec2, zoom: f32, dt: f32) {
+        self.yaw -= orbit.x * 120.0 * dt;
+        self.pitch = (self.pitch - orbit.y * 120.0 * dt).clamp(-89.0, 89.0);
+        let rot = self.rotation();
+        self.target += (rot * Vec3::X * pan.x + rot * Vec3::Y * pan.y) * self.distance * dt;
+        self.distance = self.clamp_distance(self.distance * (-zoom * 1.5 * dt).exp());
+        // Input is continuous; don't let mouse inertia fight it
+        self.kill_inertia();
+    }
+
     /// Focus on bounding box center with given radius
     pub fn focus(&mut self, center: Vec3, radius: f32) {
         self.target = center;
@@ -121,7 +171,7 @@ impl OrbitCamera {
 
     /// Set distance from target
     pub fn set_distance(&mut self, dist: f32) {
-        self.distance = dist.clamp(0.01, 50000.0);
+        self.distance = self.clamp_distance(dist);
     }
 
     /// Get yaw and pitch angles in degrees
@@ -141,9 +191,11 @@ impl OrbitCamera {
             return false;
         }
 
+        // Pan threshold follows the arm length so inertia feels the same at any scene scale
+        let pan_eps = (self.distance * 2e-5).powi(2);
         let has_velocity = self.vel_yaw.abs() > 0.001
             || self.vel_pitch.abs() > 0.001
-            || self.vel_pan.length_squared() > 1e-8
+            || self.vel_pan.length_squared() > pan_eps
             || self.vel_zoom.abs() > 1e-6;
 
         if !has_velocity {
@@ -158,7 +210,7 @@ impl OrbitCamera {
         self.pitch = (self.pitch + self.vel_pitch).clamp(-89.0, 89.0);
         self.target += self.vel_pan;
         let zoom_factor = 1.0 - self.vel_zoom;
-        self.distance = (self.distance * zoom_factor).clamp(0.01, 50000.0);
+        self.distance = self.clamp_distance(self.distance * zoom_factor);
 
         // Decay
         self.vel_yaw *= decay;
@@ -169,7 +221,7 @@ impl OrbitCamera {
         // Stop when negligible
         if self.vel_yaw.abs() < 0.001 { self.vel_yaw = 0.0; }
         if self.vel_pitch.abs() < 0.001 { self.vel_pitch = 0.0; }
-        if self.vel_pan.length_squared() < 1e-8 { self.vel_pan = Vec3::ZERO; }
+        if self.vel_pan.length_squared() < pan_eps { self.vel_pan = Vec3::ZERO; }
         if self.vel_zoom.abs() < 1e-6 { self.vel_zoom = 0.0; }
 
         true
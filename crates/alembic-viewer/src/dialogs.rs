@@ -0,0 +1,56 @@
+//! File dialogs
+//!
+//! Native builds use rfd's blocking dialogs. The browser has no file system
+//! paths: there the path dialogs return `None` and archives are opened as
+//! bytes through `pick_upload` or drag and drop.
+
+use std::path::PathBuf;
+
+/// Whether the path dialogs can return a file (menu items that need one are
+/// disabled otherwise)
+pub const HAS_PATHS: bool = cfg!(not(target_arch = "wasm32"));
+
+/// Ask for an existing file
+pub fn pick_file(filter: &str, extensions: &[&str]) -> Option<PathBuf> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return rfd::FileDialog::new().add_filter(filter, extensions).pick_file();
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (filter, extensions);
+        None
+    }
+}
+
+/// Ask for a file to write, suggesting `file_name`
+pub fn save_file(filter: &str, extensions: &[&str], file_name: &str) -> Option<PathBuf> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return rfd::FileDialog::new()
+        .add_filter(filter, extensions)
+        .set_file_name(file_name)
+        .save_file();
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (filter, extensions, file_name);
+        None
+    }
+}
+
+/// Browser file picker: the chosen file's name and contents arrive on the
+/// returned channel (nothing if the user cancels), followed by a repaint
+#[cfg(target_arch = "wasm32")]
+pub fn pick_upload(
+    ctx: &egui::Context,
+    filter: &str,
+    extensions: &[&str],
+) -> std::sync::mpsc::Receiver<(String, Vec<u8>)> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let dialog = rfd::AsyncFileDialog::new().add_filter(filter, extensions);
+    let ctx = ctx.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Some(file) = dialog.pick_file().await {
+            let _ = tx.send((file.file_name(), file.read().await));
+            ctx.request_repaint();
+        }
+    });
+    rx
+}
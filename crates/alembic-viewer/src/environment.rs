@@ -24,6 +24,8 @@ pub struct EnvironmentMap {
     pub conditional_cdf_data: Vec<f32>,
     /// Total luminance (for PDF normalization)
     pub total_luminance: f32,
+    /// Metered luminance for auto exposure (None for the default environment)
+    pub exposure_key: Option<f32>,
     /// Dimensions for importance sampling
     pub width: u32,
     pub height: u32,
@@ -253,6 +255,7 @@ pub fn load_env_map(
     // Build importance sampling CDFs
     let (conditional_cdf_data, marginal_cdf_data, total_luminance) = 
         build_env_cdfs(width, height, &luminance);
+    let exposure_key = crate::exposure::histogram_key(width, height, &luminance);
     
     // Create GPU buffers for CDFs (used by path tracer)
     let conditional_cdf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -279,6 +282,7 @@ pub fn load_env_map(
         marginal_cdf_data,
         conditional_cdf_data,
         total_luminance,
+        exposure_key,
         width,
         height,
     })
@@ -371,6 +375,7 @@ pub fn create_default_env(
         marginal_cdf_data: vec![1.0f32],
         conditional_cdf_data: vec![1.0f32],
         total_luminance: 0.0,
+        exposure_key: None,
         width: 1,
         height: 1,
     }
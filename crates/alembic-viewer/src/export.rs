@@ -5,11 +5,16 @@
 use std::path::Path;
 use log::{info, debug};
 
-use crate::abc::{IArchive, IObject};
-use crate::geom::{IPolyMesh, IXform, ISubD, ICurves, IPoints, ICamera, INuPatch, ILight, IFaceSet};
-use crate::ogawa::{OArchive, OObject, OPolyMesh, OPolyMeshSample, OXform, OXformSample};
-use crate::ogawa::{OSubD, OSubDSample, OCurves, OCurvesSample, OPoints, OPointsSample};
-use crate::ogawa::{OCamera, ONuPatch, ONuPatchSample, OLight, OFaceSet, OFaceSetSample};
+use alembic::abc::{IArchive, IObject};
+use alembic::geom::{IPolyMesh, IXform, ISubD, ICurves, IPoints, ICamera, INuPatch, ILight, IFaceSet};
+use alembic::ogawa::{OArchive, OObject, OPolyMesh, OPolyMeshSample, OXform, OXformSample};
+use alembic::ogawa::{OSubD, OSubDSample, OCurves, OCurvesSample, OPoints, OPointsSample};
+use alembic::ogawa::{OCamera, ONuPatch, ONuPatchSample, OLight, OFaceSet, OFaceSetSample};
+use alembic::core::TimeSampling;
+use alembic::geom::{collect_meshes, CameraSample, LoadOptions, MeshData};
+use glam::Vec3;
+
+use super::bookmarks::CameraPathKey;
 
 /// Export statistics
 #[derive(Default, Debug)]
@@ -69,6 +74,230 @@ pub fn export_archive(input: &IArchive, output_path: &Path) -> Result<ExportStat
     Ok(stats)
 }
 
+/// Export a recorded camera fly-through as `/recordedCam/recordedCamShape`
+/// (animated Xform + Camera) sampled uniformly at `fps`.
+pub fn export_camera_path(keys: &[CameraPathKey], fps: f32, output_path: &Path) -> Result<usize, String> {
+    if keys.is_empty() {
+        return Err("Camera path is empty".into());
+    }
+    info!("Exporting camera path ({} samples) to {}", keys.len(), output_path.display());
+
+    let mut out_archive = OArchive::create(output_path)
+        .map_err(|e| format!("Failed to create output: {}", e))?;
+    let fps = fps.max(1.0) as f64;
+    out_archive.setDccFps(fps);
+    let ts_index = out_archive.addTimeSampling(TimeSampling::uniform_fps(fps, 0.0));
+
+    let mut out_xform = OXform::new("recordedCam");
+    out_xform.set_time_sampling(ts_index);
+    let mut out_cam = OCamera::new("recordedCamShape");
+    out_cam.set_time_sampling(ts_index);
+
+    for key in keys {
+        out_xform.add_sample(OXformSample::from_matrix(key.world, true));
+        let mut sample = CameraSample::default();
+        // fov_y = 2 * atan(aperture_cm * 10 / (2 * focal_mm))
+        let half_fov = (key.fov.to_radians() as f64 * 0.5).max(1e-4);
+        sample.focal_length = sample.vertical_aperture * 10.0 / (2.0 * half_fov.tan());
+        out_cam.add_sample(sample);
+    }
+
+    let mut xform_obj = out_xform.build();
+    xform_obj.add_child(out_cam.build());
+    let mut out_root = OObject::new("");
+    out_root.add_child(xform_obj);
+
+    out_archive.write_archive(&out_root)
+        .map_err(|e| format!("Failed to write archive: {}", e))?;
+    Ok(keys.len())
+}
+
+/// Options for [`export_preview`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewOptions {
+    /// First and last sample frame to bake (inclusive)
+    pub start: usize,
+    pub end: usize,
+    /// Vertex clustering grid cells along each mesh's longest axis (0 = keep full detail)
+    pub resolution: u32,
+}
+
+/// Result of [`export_preview`]
+#[derive(Default, Debug)]
+pub struct PreviewStats {
+    pub meshes: usize,
+    pub frames: usize,
+    pub faces_in: usize,
+    pub faces_out: usize,
+}
+
+/// Bake visible meshes to a lightweight preview archive: world-space,
+/// decimated by vertex clustering, one flat PolyMesh per source mesh,
+/// sampled at every frame of `start..=end`.
+///
+/// Topology is decimated once (on the first frame) and reused, so animated
+/// meshes stay consistent. Meshes missing at the first frame are skipped;
+/// frames where a mesh's point count changes repeat its previous positions.
+pub fn export_preview(
+    archive: &IArchive,
+    output_path: &Path,
+    options: &PreviewOptions,
+    is_visible: impl Fn(&str) -> bool,
+) -> Result<PreviewStats, String> {
+    let frames = alembic::export::obj::sequence_frames(archive);
+    let end = options.end.min(frames.len().saturating_sub(1));
+    let frames = frames.get(options.start..=end).ok_or("Empty frame range")?;
+    info!("Baking preview ({} frames) to {}", frames.len(), output_path.display());
+
+    let load = LoadOptions { triangulate: false, world_space: true, compute_normals: false, ..Default::default() };
+    let mut tracks: Vec<PreviewTrack> = Vec::new();
+    let mut stats = PreviewStats { frames: frames.len(), ..Default::default() };
+    for (i, frame) in frames.iter().enumerate() {
+        let meshes = collect_meshes(archive, frame.time, &load);
+        if i == 0 {
+            for (path, mesh, _) in meshes.into_iter().filter(|(path, _, _)| is_visible(path)) {
+                let cluster = cluster_mesh(&mesh, options.resolution);
+                if cluster.face_counts.is_empty() {
+                    continue;
+                }
+                stats.faces_in += mesh.face_counts.len();
+                stats.faces_out += cluster.face_counts.len();
+                let positions = cluster.positions(&mesh.positions);
+                tracks.push(PreviewTrack { path, cluster, samples: vec![positions] });
+            }
+            continue;
+        }
+        let mut by_path: std::collections::HashMap<String, MeshData> =
+            meshes.into_iter().map(|(path, mesh, _)| (path, mesh)).collect();
+        for track in &mut tracks {
+            let positions = match by_path.remove(&track.path) {
+                Some(mesh) if mesh.positions.len() == track.cluster.cluster_of.len() => {
+                    track.cluster.positions(&mesh.positions)
+                }
+                _ => track.samples.last().cloned().unwrap_or_default(),
+            };
+            track.samples.push(positions);
+        }
+    }
+    if tracks.is_empty() {
+        return Err("No visible meshes to export".into());
+    }
+
+    let mut out_archive = OArchive::create(output_path)
+        .map_err(|e| format!("Failed to create output: {}", e))?;
+    let step = frames.get(1).map(|f| f.time - frames[0].time).filter(|s| *s > 0.0);
+    let ts_index = match step {
+        Some(step) => {
+            out_archive.setDccFps(1.0 / step);
+            out_archive.addTimeSampling(TimeSampling::uniform(step, frames[0].time))
+        }
+        None => 0,
+    };
+
+    let mut out_root = OObject::new("");
+    let mut names = std::collections::HashSet::new();
+    for track in &tracks {
+        let base = track.path.trim_start_matches('/').replace('/', "_");
+        let mut name = base.clone();
+        let mut n = 1;
+        while !names.insert(name.clone()) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        let mut out_mesh = OPolyMesh::new(&name);
+        out_mesh.set_time_sampling(ts_index);
+        for positions in &track.samples {
+            out_mesh.add_sample(&OPolyMeshSample::new(
+                positions.clone(),
+                track.cluster.face_counts.clone(),
+                track.cluster.face_indices.clone(),
+            ));
+        }
+        out_root.add_child(out_mesh.build());
+    }
+    stats.meshes = tracks.len();
+
+    out_archive.write_archive(&out_root)
+        .map_err(|e| format!("Failed to write archive: {}", e))?;
+    info!("Preview export complete: {} meshes, {} -> {} faces", stats.meshes, stats.faces_in, stats.faces_out);
+    Ok(stats)
+}
+
+/// One baked mesh: its decimated topology and per-frame positions
+struct PreviewTrack {
+    path: String,
+    cluster: Clustering,
+    samples: Vec<Vec<Vec3>>,
+}
+
+/// Vertex clustering result: source point -> cluster, plus the surviving faces
+struct Clustering {
+    cluster_of: Vec<u32>,
+    num_clusters: usize,
+    face_counts: Vec<i32>,
+    face_indices: Vec<i32>,
+}
+
+impl Clustering {
+    /// Cluster positions: the average of their member points
+    fn positions(&self, points: &[Vec3]) -> Vec<Vec3> {
+        let mut sums = vec![(Vec3::ZERO, 0u32); self.num_clusters];
+        for (p, &c) in points.iter().zip(&self.cluster_of) {
+            let entry = &mut sums[c as usize];
+            entry.0 += *p;
+            entry.1 += 1;
+        }
+        sums.into_iter().map(|(sum, n)| sum / n.max(1) as f32).collect()
+    }
+}
+
+/// Merge points falling into the same grid cell (cell size = longest bounds
+/// axis / `resolution`) and drop faces collapsing below 3 corners.
+/// `resolution == 0` keeps every point.
+fn cluster_mesh(mesh: &MeshData, resolution: u32) -> Clustering {
+    let cluster_of: Vec<u32> = if resolution == 0 {
+        (0..mesh.positions.len() as u32).collect()
+    } else {
+        let (min, max) = mesh.positions.iter()
+            .filter(|p| p.is_finite())
+            .fold((Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)), |(lo, hi), p| (lo.min(*p), hi.max(*p)));
+        let cell = ((max - min).max_element() / resolution as f32).max(f32::MIN_POSITIVE);
+        let mut cells: std::collections::HashMap<[i32; 3], u32> = std::collections::HashMap::new();
+        mesh.positions.iter()
+            .map(|p| {
+                let key = ((*p - min) / cell).floor().as_ivec3().to_array();
+                let next = cells.len() as u32;
+                *cells.entry(key).or_insert(next)
+            })
+            .collect()
+    };
+    let num_clusters = cluster_of.iter().map(|&c| c as usize + 1).max().unwrap_or(0);
+
+    let mut face_counts = Vec::new();
+    let mut face_indices = Vec::new();
+    let mut corners: Vec<i32> = Vec::new();
+    let mut offset = 0usize;
+    for &count in &mesh.face_counts {
+        let count = count.max(0) as usize;
+        corners.clear();
+        for &i in &mesh.face_indices[offset..offset + count] {
+            let c = cluster_of[i as usize] as i32;
+            if corners.last() != Some(&c) {
+                corners.push(c);
+            }
+        }
+        while corners.len() > 1 && corners.first() == corners.last() {
+            corners.pop();
+        }
+        if corners.len() >= 3 {
+            face_counts.push(corners.len() as i32);
+            face_indices.extend_from_slice(&corners);
+        }
+        offset += count;
+    }
+    Clustering { cluster_of, num_clusters, face_counts, face_indices }
+}
+
 fn export_object(obj: &IObject, archive: &IArchive, stats: &mut ExportStats) -> Option<OObject> {
     let name = obj.getName();
     let schema = obj.getMetaData().get("schema").unwrap_or_default();
@@ -347,9 +576,10 @@ fn export_light(obj: &IObject, stats: &mut ExportStats) -> Option<OObject> {
         let mut out_light = OLight::new(name);
         out_light.set_time_sampling(light.getTimeSamplingIndex());
         
+        // Typed light parameters (type, intensity, color, cone) are re-written too
         for i in 0..light.getNumSamples() {
             if let Ok(sample) = light.getSample(i) {
-                out_light.add_camera_sample(sample.camera);
+                out_light.add_sample(&sample);
             }
         }
         
@@ -0,0 +1,181 @@
+//! Exposure and white balance
+//!
+//! Both are a per-channel gain applied in the tonemap stage: the path tracer
+//! blit (before the ACES curve) and the raster lighting resolve. Auto
+//! exposure meters the loaded HDR environment: a solid-angle weighted
+//! log2-luminance histogram, averaged over its middle percentiles so the sun
+//! or a black ground doesn't dominate, is mapped to middle gray. Manual EV
+//! compensation is added on top. White balance neutralizes a light source of
+//! the given color temperature (6500 K = no change) plus a green/magenta tint.
+
+use glam::Vec3;
+
+/// Middle gray the metered environment luminance is exposed to
+const KEY_VALUE: f32 = 0.18;
+/// Histogram range in stops of luminance
+const HISTOGRAM_MIN_EV: f32 = -16.0;
+const HISTOGRAM_MAX_EV: f32 = 16.0;
+const HISTOGRAM_BINS: usize = 128;
+/// Fractions of the histogram ignored at the dark and bright ends
+const LOW_PERCENTILE: f32 = 0.5;
+const HIGH_PERCENTILE: f32 = 0.95;
+/// Auto exposure is clamped to this many stops either way
+pub const MAX_AUTO_EV: f32 = 10.0;
+/// Color temperature range of the white balance control (Kelvin)
+pub const MIN_KELVIN: f32 = 2000.0;
+pub const MAX_KELVIN: f32 = 12000.0;
+/// Neutral white balance
+pub const NEUTRAL_KELVIN: f32 = 6500.0;
+
+/// Luminance weights (Rec. 709)
+const LUMA: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+
+/// Exposure and white balance controls
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exposure {
+    /// Meter the HDR environment
+    pub auto: bool,
+    /// Manual compensation in stops, added to the auto exposure
+    pub compensation_ev: f32,
+    /// Color temperature of the light to neutralize (Kelvin)
+    pub white_balance_k: f32,
+    /// Green (-1) to magenta (+1) correction
+    pub tint: f32,
+}
+
+impl Default for Exposure {
+    fn default() -> Self {
+        Self { auto: false, compensation_ev: 0.0, white_balance_k: NEUTRAL_KELVIN, tint: 0.0 }
+    }
+}
+
+impl Exposure {
+    /// Exposure in stops for an environment of metered luminance `env_key`
+    /// (see [`histogram_key`]; `None` without an environment)
+    pub fn ev(&self, env_key: Option<f32>) -> f32 {
+        let auto = match env_key {
+            Some(key) if self.auto && key > 0.0 => (KEY_VALUE / key).log2().clamp(-MAX_AUTO_EV, MAX_AUTO_EV),
+            _ => 0.0,
+        };
+        auto + self.compensation_ev
+    }
+
+    /// Linear RGB gain applied before tone mapping
+    pub fn gain(&self, env_key: Option<f32>) -> Vec3 {
+        white_balance_gains(self.white_balance_k, self.tint) * self.ev(env_key).exp2()
+    }
+}
+
+/// Metered luminance of an equirectangular map (`luminance` is row-major,
+/// `width * height`): the mean log2-luminance of the histogram between the
+/// low and high percentiles, weighted by each row's solid angle. `None` for
+/// an empty or black map.
+pub fn histogram_key(width: u32, height: u32, luminance: &[f32]) -> Option<f32> {
+    let (w, h) = (width as usize, height as usize);
+    if w == 0 || h == 0 || luminance.len() < w * h {
+        return None;
+    }
+    let bin_size = (HISTOGRAM_MAX_EV - HISTOGRAM_MIN_EV) / HISTOGRAM_BINS as f32;
+    let mut histogram = [0.0f64; HISTOGRAM_BINS];
+    for (y, row) in luminance.chunks_exact(w).take(h).enumerate() {
+        let sin_theta = (std::f32::consts::PI * (y as f32 + 0.5) / h as f32).sin() as f64;
+        for &lum in row {
+            // Black (and NaN) pixels carry no exposure information
+            if lum.is_nan() || lum <= 0.0 {
+                continue;
+            }
+            let bin = ((lum.log2() - HISTOGRAM_MIN_EV) / bin_size).floor();
+            histogram[bin.clamp(0.0, (HISTOGRAM_BINS - 1) as f32) as usize] += sin_theta;
+        }
+    }
+    let total: f64 = histogram.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    // Average the bin centers over the [low, high] slice of the weight
+    let (low, high) = (total * LOW_PERCENTILE as f64, total * HIGH_PERCENTILE as f64);
+    let (mut below, mut sum, mut weight) = (0.0f64, 0.0f64, 0.0f64);
+    for (i, &count) in histogram.iter().enumerate() {
+        let take = (below + count).min(high) - below.max(low);
+        if take > 0.0 {
+            let ev = HISTOGRAM_MIN_EV + (i as f32 + 0.5) * bin_size;
+            sum += ev as f64 * take;
+            weight += take;
+        }
+        below += count;
+    }
+    (weight > 0.0).then(|| ((sum / weight) as f32).exp2())
+}
+
+/// Per-channel gains that turn a light of color temperature `kelvin` white,
+/// with a green/magenta `tint`; normalized to keep luminance.
+pub fn white_balance_gains(kelvin: f32, tint: f32) -> Vec3 {
+    let source = blackbody_rgb(kelvin.clamp(MIN_KELVIN, MAX_KELVIN));
+    let neutral = blackbody_rgb(NEUTRAL_KELVIN);
+    let mut gains = neutral / source;
+    // Positive tint adds magenta (less green)
+    gains.y *= (-tint.clamp(-1.0, 1.0) * 0.5).exp2();
+    gains / gains.dot(LUMA)
+}
+
+/// Linear sRGB color of a blackbody at `kelvin` (1667-25000 K), from the
+/// Kim et al. cubic fit of the Planckian locus, at luminance 1.
+fn blackbody_rgb(kelvin: f32) -> Vec3 {
+    let t = kelvin.clamp(1667.0, 25000.0) as f64;
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 4000.0 {
+        -0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910
+    } else {
+        -3.0258469e9 / t3 + 2.1070379e6 / t2 + 0.2226347e3 / t + 0.240390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+    } else if t <= 4000.0 {
+        -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+    } else {
+        3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+    };
+    // xyY (Y = 1) -> XYZ -> linear sRGB
+    let (cx, cz) = (x / y, (1.0 - x - y) / y);
+    Vec3::new(
+        (3.2404542 * cx - 1.5371385 - 0.4985314 * cz) as f32,
+        (-0.9692660 * cx + 1.8760108 + 0.0415560 * cz) as f32,
+        (0.0556434 * cx - 0.2040259 + 1.0572252 * cz) as f32,
+    )
+    .max(Vec3::splat(1e-4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_key() {
+        // Uniform map: the key is its luminance (to a histogram bin)
+        let key = histogram_key(8, 4, &[2.0; 32]).unwrap();
+        assert!((key.log2() - 1.0).abs() < 0.2, "{}", key);
+        // A few very bright texels (sun) don't move the key much
+        let mut lum = vec![0.5f32; 64 * 32];
+        lum[16 * 64..16 * 64 + 8].fill(50000.0);
+        let key = histogram_key(64, 32, &lum).unwrap();
+        assert!((key.log2() + 1.0).abs() < 0.2, "{}", key);
+        assert!(histogram_key(4, 2, &[0.0; 8]).is_none());
+
+        let exposure = Exposure { auto: true, ..Default::default() };
+        assert!((exposure.ev(Some(KEY_VALUE * 4.0)) + 2.0).abs() < 1e-4);
+        assert_eq!(exposure.ev(None), 0.0);
+    }
+
+    #[test]
+    fn test_white_balance_gains() {
+        let neutral = white_balance_gains(NEUTRAL_KELVIN, 0.0);
+        assert!((neutral - Vec3::ONE).abs().max_element() < 1e-4, "{}", neutral);
+        // Tungsten light is orange: neutralizing it cools the image
+        let tungsten = white_balance_gains(3200.0, 0.0);
+        assert!(tungsten.z > tungsten.x);
+        assert!((tungsten.dot(LUMA) - 1.0).abs() < 1e-4);
+        assert!(white_balance_gains(NEUTRAL_KELVIN, 1.0).y < 1.0);
+    }
+}
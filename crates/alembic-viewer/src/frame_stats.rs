@@ -0,0 +1,212 @@
+//! Per-frame scene statistics for the timeline graph
+//!
+//! Vertex and point counts come from array lengths and bounds from the stored
+//! `.selfBnds`, so those are cheap; the maximum velocity needs the
+//! `.velocities` arrays themselves. Everything is computed on a background
+//! thread (objects in parallel) and handed to the UI when done. Frames are
+//! sample indices, as for the topology markers.
+
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use alembic::abc::{IArchive, IObject};
+use alembic::geom::{CURVES_SCHEMA, POINTS_SCHEMA, POLYMESH_SCHEMA, SUBD_SCHEMA};
+
+/// Statistic plotted under the timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatMetric {
+    Vertices,
+    Points,
+    BoundsVolume,
+    MaxVelocity,
+}
+
+impl StatMetric {
+    pub const ALL: [StatMetric; 4] = [
+        StatMetric::Vertices,
+        StatMetric::Points,
+        StatMetric::BoundsVolume,
+        StatMetric::MaxVelocity,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatMetric::Vertices => "Vertices",
+            StatMetric::Points => "Points",
+            StatMetric::BoundsVolume => "Bounds volume",
+            StatMetric::MaxVelocity => "Max velocity",
+        }
+    }
+
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            StatMetric::Vertices => egui::Color32::from_rgb(110, 170, 240),
+            StatMetric::Points => egui::Color32::from_rgb(120, 210, 130),
+            StatMetric::BoundsVolume => egui::Color32::from_rgb(230, 190, 80),
+            StatMetric::MaxVelocity => egui::Color32::from_rgb(230, 110, 200),
+        }
+    }
+
+    /// Value formatted for tooltips
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            StatMetric::Vertices | StatMetric::Points => format!("{}", value as u64),
+            StatMetric::BoundsVolume => format!("{:.4}", value),
+            StatMetric::MaxVelocity => format!("{:.3}/s", value),
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Per-frame values of every [`StatMetric`]
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    values: [Vec<f64>; 4],
+}
+
+impl FrameStats {
+    /// Values of `metric`, one per frame
+    pub fn get(&self, metric: StatMetric) -> &[f64] {
+        &self.values[metric.index()]
+    }
+
+    /// Largest value of `metric` (0 if empty)
+    pub fn max(&self, metric: StatMetric) -> f64 {
+        self.get(metric).iter().copied().fold(0.0, f64::max)
+    }
+
+    /// True if `metric` is nonzero on some frame
+    pub fn has(&self, metric: StatMetric) -> bool {
+        self.max(metric) > 0.0
+    }
+
+    /// Scan all geometry in the archive for `num_frames` frames
+    pub fn compute(archive: &IArchive, num_frames: usize) -> Self {
+        let mut stats = Self { values: std::array::from_fn(|_| vec![0.0; num_frames]) };
+        let mut bounds: Vec<Option<([f64; 3], [f64; 3])>> = vec![None; num_frames];
+        for object in archive.getTop().collect_par(&|obj| scan_object(obj, num_frames)) {
+            for (frame, union) in bounds.iter_mut().enumerate() {
+                stats.values[StatMetric::Vertices.index()][frame] += object.vertices[frame];
+                stats.values[StatMetric::Points.index()][frame] += object.points[frame];
+                let v = &mut stats.values[StatMetric::MaxVelocity.index()][frame];
+                *v = v.max(object.max_velocity[frame]);
+                if let Some((lo, hi)) = object.bounds[frame] {
+                    let b = union.get_or_insert((lo, hi));
+                    for k in 0..3 {
+                        b.0[k] = b.0[k].min(lo[k]);
+                        b.1[k] = b.1[k].max(hi[k]);
+                    }
+                }
+            }
+        }
+        for (frame, b) in bounds.iter().enumerate() {
+            if let Some((lo, hi)) = b {
+                stats.values[StatMetric::BoundsVolume.index()][frame] =
+                    (0..3).map(|k| (hi[k] - lo[k]).max(0.0)).product();
+            }
+        }
+        stats
+    }
+
+    /// Compute on a background thread; poll the receiver from the UI
+    /// (computed up front in the browser build, which has no threads)
+    pub fn spawn(archive: Arc<IArchive>, num_frames: usize) -> Receiver<FrameStats> {
+        let (tx, rx) = channel();
+        let compute = move || {
+            let _span = tracing::info_span!("frame_stats").entered();
+            let _ = tx.send(Self::compute(&archive, num_frames));
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(compute);
+        #[cfg(target_arch = "wasm32")]
+        compute();
+        rx
+    }
+}
+
+/// Per-frame values of one object
+struct ObjectStats {
+    vertices: Vec<f64>,
+    points: Vec<f64>,
+    max_velocity: Vec<f64>,
+    bounds: Vec<Option<([f64; 3], [f64; 3])>>,
+}
+
+fn scan_object(obj: &IObject, num_frames: usize) -> Option<ObjectStats> {
+    let schema = obj.getMetaData().get("schema").unwrap_or_default();
+    let is_points = match schema {
+        POLYMESH_SCHEMA | SUBD_SCHEMA | CURVES_SCHEMA => false,
+        POINTS_SCHEMA => true,
+        _ => return None,
+    };
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+
+    let mut stats = ObjectStats {
+        vertices: vec![0.0; num_frames],
+        points: vec![0.0; num_frames],
+        max_velocity: vec![0.0; num_frames],
+        bounds: vec![None; num_frames],
+    };
+    // Objects with fewer samples hold their last sample
+    let clamp = |frame: usize, n: usize| frame.min(n.saturating_sub(1));
+
+    if let Some(p) = geom.getPropertyByName("P") {
+        if let Some(array) = p.asArray().filter(|a| a.getNumSamples() > 0) {
+            let n = array.getNumSamples();
+            let counts = if is_points { &mut stats.points } else { &mut stats.vertices };
+            for (frame, count) in counts.iter_mut().enumerate() {
+                *count = array.getSampleLen(clamp(frame, n)).unwrap_or(0) as f64;
+            }
+        }
+    }
+
+    if let Some(bnds) = geom.getPropertyByName(".selfBnds") {
+        if let Some(scalar) = bnds.asScalar().filter(|s| s.getNumSamples() > 0) {
+            let n = scalar.getNumSamples();
+            for (frame, bounds) in stats.bounds.iter_mut().enumerate() {
+                let mut buf = [0u8; 48];
+                if scalar.getSample(clamp(frame, n), &mut buf).is_ok() {
+                    let d: Vec<f64> = buf.chunks_exact(8).map(|c| f64::from_le_bytes(c.try_into().unwrap())).collect();
+                    // Empty bounds are stored inverted (min > max)
+                    if d[0] <= d[3] {
+                        *bounds = Some(([d[0], d[1], d[2]], [d[3], d[4], d[5]]));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(vel) = geom.getPropertyByName(".velocities") {
+        if let Some(array) = vel.asArray().filter(|a| a.getNumSamples() > 0) {
+            let n = array.getNumSamples();
+            let mut per_sample = Vec::with_capacity(n);
+            for i in 0..n {
+                let max = array
+                    .getSampleVec(i)
+                    .map(|bytes| {
+                        bytes
+                            .chunks_exact(12)
+                            .map(|c| {
+                                let f = |k: usize| f32::from_le_bytes(c[k * 4..k * 4 + 4].try_into().unwrap()) as f64;
+                                (f(0) * f(0) + f(1) * f(1) + f(2) * f(2)).sqrt()
+                            })
+                            .filter(|v| v.is_finite())
+                            .fold(0.0, f64::max)
+                    })
+                    .unwrap_or(0.0);
+                per_sample.push(max);
+            }
+            for (frame, v) in stats.max_velocity.iter_mut().enumerate() {
+                *v = per_sample[clamp(frame, n)];
+            }
+        }
+    }
+    Some(stats)
+}
@@ -0,0 +1,502 @@
+//! Rebindable keyboard shortcuts and mouse navigation presets
+//!
+//! A [`Keymap`] starts from a [`KeymapPreset`] (the viewer's own bindings or
+//! Maya/Houdini/Blender emulation) and applies per-action overrides from the
+//! settings file. Keyboard chords are stored as text ("Ctrl+Shift+H") so
+//! settings stay readable and survive egui key enum changes.
+
+use std::collections::BTreeMap;
+
+use egui::{InputState, Key, PointerButton};
+use serde::{Deserialize, Serialize};
+
+/// Keyboard-triggered viewer action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    Exit,
+    ToggleFullscreen,
+    HomeCamera,
+    FocusSelected,
+    FrameAll,
+    HideSelected,
+    UnhideAll,
+    InvertSelection,
+    PlayPause,
+    PrevFrame,
+    NextFrame,
+    FirstFrame,
+    PrevTopologyChange,
+    NextTopologyChange,
+    PrevFile,
+    NextFile,
+    PrevEnvironment,
+    NextEnvironment,
+}
+
+impl Action {
+    pub const ALL: [Action; 18] = [
+        Action::Exit,
+        Action::ToggleFullscreen,
+        Action::HomeCamera,
+        Action::FocusSelected,
+        Action::FrameAll,
+        Action::HideSelected,
+        Action::UnhideAll,
+        Action::InvertSelection,
+        Action::PlayPause,
+        Action::PrevFrame,
+        Action::NextFrame,
+        Action::FirstFrame,
+        Action::PrevTopologyChange,
+        Action::NextTopologyChange,
+        Action::PrevFile,
+        Action::NextFile,
+        Action::PrevEnvironment,
+        Action::NextEnvironment,
+    ];
+
+    /// Stable identifier used in settings
+    pub fn id(&self) -> &'static str {
+        match self {
+            Action::Exit => "exit",
+            Action::ToggleFullscreen => "toggle_fullscreen",
+            Action::HomeCamera => "home_camera",
+            Action::FocusSelected => "focus_selected",
+            Action::FrameAll => "frame_all",
+            Action::HideSelected => "hide_selected",
+            Action::UnhideAll => "unhide_all",
+            Action::InvertSelection => "invert_selection",
+            Action::PlayPause => "play_pause",
+            Action::PrevFrame => "prev_frame",
+            Action::NextFrame => "next_frame",
+            Action::FirstFrame => "first_frame",
+            Action::PrevTopologyChange => "prev_topology_change",
+            Action::NextTopologyChange => "next_topology_change",
+            Action::PrevFile => "prev_file",
+            Action::NextFile => "next_file",
+            Action::PrevEnvironment => "prev_environment",
+            Action::NextEnvironment => "next_environment",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Exit => "Exit / leave fullscreen",
+            Action::ToggleFullscreen => "Toggle fullscreen",
+            Action::HomeCamera => "Home camera",
+            Action::FocusSelected => "Focus selected",
+            Action::FrameAll => "Frame all",
+            Action::HideSelected => "Hide selected",
+            Action::UnhideAll => "Unhide all",
+            Action::InvertSelection => "Invert selection",
+            Action::PlayPause => "Play / pause",
+            Action::PrevFrame => "Previous frame",
+            Action::NextFrame => "Next frame",
+            Action::FirstFrame => "First frame",
+            Action::PrevTopologyChange => "Previous topology change",
+            Action::NextTopologyChange => "Next topology change",
+            Action::PrevFile => "Previous file in folder",
+            Action::NextFile => "Next file in folder",
+            Action::PrevEnvironment => "Previous HDR in folder",
+            Action::NextEnvironment => "Next HDR in folder",
+        }
+    }
+}
+
+/// Key plus exact modifier state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyChord {
+    pub const fn new(key: Key) -> Self {
+        Self { key, ctrl: false, shift: false, alt: false }
+    }
+
+    pub const fn ctrl(key: Key) -> Self {
+        Self { key, ctrl: true, shift: false, alt: false }
+    }
+
+    pub const fn shift(key: Key) -> Self {
+        Self { key, ctrl: false, shift: true, alt: false }
+    }
+
+    pub const fn alt(key: Key) -> Self {
+        Self { key, ctrl: false, shift: false, alt: true }
+    }
+
+    /// Pressed this frame with exactly these modifiers (Cmd counts as Ctrl)
+    pub fn pressed(&self, input: &InputState) -> bool {
+        let m = input.modifiers;
+        input.key_pressed(self.key) && m.command == self.ctrl && m.shift == self.shift && m.alt == self.alt
+    }
+
+    /// First key pressed this frame with its modifiers (for rebinding)
+    pub fn capture(input: &InputState) -> Option<Self> {
+        input.events.iter().find_map(|e| match e {
+            egui::Event::Key { key, pressed: true, repeat: false, modifiers, .. } => Some(Self {
+                key: *key,
+                ctrl: modifiers.command,
+                shift: modifiers.shift,
+                alt: modifiers.alt,
+            }),
+            _ => None,
+        })
+    }
+
+    /// Parse "Ctrl+Shift+H"
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key = Key::from_name(parts.pop()?)?;
+        let mut chord = Self::new(key);
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" => chord.alt = true,
+                _ => return None,
+            }
+        }
+        Some(chord)
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+/// Modifier that must be held for a mouse navigation gesture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavModifier {
+    /// Any modifier state
+    Any,
+    /// No modifier held
+    None,
+    Alt,
+    Shift,
+    Ctrl,
+    /// Alt or Space held
+    AltOrSpace,
+}
+
+impl NavModifier {
+    fn held(&self, input: &InputState) -> bool {
+        let m = input.modifiers;
+        let space = input.key_down(Key::Space);
+        match self {
+            NavModifier::Any => true,
+            NavModifier::None => !m.alt && !m.shift && !m.command && !space,
+            NavModifier::Alt => m.alt,
+            NavModifier::Shift => m.shift && !m.alt && !m.command,
+            NavModifier::Ctrl => m.command && !m.alt && !m.shift,
+            NavModifier::AltOrSpace => m.alt || space,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            NavModifier::Any | NavModifier::None => "",
+            NavModifier::Alt => "Alt+",
+            NavModifier::Shift => "Shift+",
+            NavModifier::Ctrl => "Ctrl+",
+            NavModifier::AltOrSpace => "Alt/Space+",
+        }
+    }
+}
+
+/// Mouse button plus held modifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseGesture {
+    pub button: PointerButton,
+    pub modifier: NavModifier,
+}
+
+impl MouseGesture {
+    const fn new(button: PointerButton, modifier: NavModifier) -> Self {
+        Self { button, modifier }
+    }
+
+    /// True while the viewport is dragged with this gesture
+    pub fn dragged(&self, input: &InputState, response: &egui::Response) -> bool {
+        response.dragged_by(self.button) && self.modifier.held(input)
+    }
+
+    /// True if the gesture needs a modifier and it is held (suppresses click picking)
+    pub fn modifier_held(&self, input: &InputState) -> bool {
+        !matches!(self.modifier, NavModifier::Any | NavModifier::None) && self.modifier.held(input)
+    }
+
+    pub fn label(&self) -> String {
+        let button = match self.button {
+            PointerButton::Primary => "LMB",
+            PointerButton::Secondary => "RMB",
+            PointerButton::Middle => "MMB",
+            PointerButton::Extra1 => "Mouse4",
+            PointerButton::Extra2 => "Mouse5",
+        };
+        format!("{}{} drag", self.modifier.label(), button)
+    }
+}
+
+/// Mouse navigation bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseNav {
+    pub orbit: MouseGesture,
+    pub pan: MouseGesture,
+    pub zoom: MouseGesture,
+    /// Ctrl+LMB samples DoF focus instead of orbiting
+    pub ctrl_lmb_focus: bool,
+}
+
+/// Base binding set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KeymapPreset {
+    #[default]
+    Viewer,
+    Maya,
+    Houdini,
+    Blender,
+}
+
+impl KeymapPreset {
+    pub const ALL: [KeymapPreset; 4] = [
+        KeymapPreset::Viewer,
+        KeymapPreset::Maya,
+        KeymapPreset::Houdini,
+        KeymapPreset::Blender,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeymapPreset::Viewer => "Viewer",
+            KeymapPreset::Maya => "Maya",
+            KeymapPreset::Houdini => "Houdini",
+            KeymapPreset::Blender => "Blender",
+        }
+    }
+
+    /// Mouse navigation for this preset
+    pub fn mouse_nav(&self) -> MouseNav {
+        use NavModifier as M;
+        use PointerButton::{Middle, Primary, Secondary};
+        let g = MouseGesture::new;
+        match self {
+            KeymapPreset::Viewer => MouseNav {
+                orbit: g(Primary, M::Any),
+                pan: g(Middle, M::Any),
+                zoom: g(Secondary, M::Any),
+                ctrl_lmb_focus: true,
+            },
+            KeymapPreset::Maya => MouseNav {
+                orbit: g(Primary, M::Alt),
+                pan: g(Middle, M::Alt),
+                zoom: g(Secondary, M::Alt),
+                ctrl_lmb_focus: false,
+            },
+            KeymapPreset::Houdini => MouseNav {
+                orbit: g(Primary, M::AltOrSpace),
+                pan: g(Middle, M::AltOrSpace),
+                zoom: g(Secondary, M::AltOrSpace),
+                ctrl_lmb_focus: false,
+            },
+            KeymapPreset::Blender => MouseNav {
+                orbit: g(Middle, M::None),
+                pan: g(Middle, M::Shift),
+                zoom: g(Middle, M::Ctrl),
+                ctrl_lmb_focus: false,
+            },
+        }
+    }
+
+    /// Key held to show the quick action menu under the cursor
+    pub fn menu_key(&self) -> Option<Key> {
+        match self {
+            // Maya hotbox
+            KeymapPreset::Maya => Some(Key::Space),
+            // Blender "Q" quick favorites
+            KeymapPreset::Blender => Some(Key::Q),
+            KeymapPreset::Viewer | KeymapPreset::Houdini => None,
+        }
+    }
+
+    /// Keyboard bindings for this preset (several chords per action allowed)
+    pub fn bindings(&self) -> Vec<(Action, KeyChord)> {
+        use Action as A;
+        use KeyChord as C;
+        // Shared by every preset: arrows step/scrub, PageUp/Down browse the folder
+        let mut b = vec![
+            (A::Exit, C::new(Key::Escape)),
+            (A::PrevFrame, C::new(Key::ArrowLeft)),
+            (A::NextFrame, C::new(Key::ArrowRight)),
+            (A::FirstFrame, C::new(Key::ArrowDown)),
+            (A::PlayPause, C::new(Key::ArrowUp)),
+            (A::PrevTopologyChange, C::shift(Key::ArrowLeft)),
+            (A::NextTopologyChange, C::shift(Key::ArrowRight)),
+            (A::PrevFile, C::new(Key::PageUp)),
+            (A::NextFile, C::new(Key::PageDown)),
+            (A::PrevEnvironment, C::ctrl(Key::PageUp)),
+            (A::NextEnvironment, C::ctrl(Key::PageDown)),
+            (A::InvertSelection, C::ctrl(Key::I)),
+        ];
+        match self {
+            KeymapPreset::Viewer => b.extend([
+                (A::ToggleFullscreen, C::new(Key::Z)),
+                (A::HomeCamera, C::new(Key::H)),
+                (A::HomeCamera, C::new(Key::Home)),
+                (A::FocusSelected, C::new(Key::F)),
+                (A::HideSelected, C::ctrl(Key::H)),
+                (A::UnhideAll, C::alt(Key::H)),
+                (A::PlayPause, C::new(Key::Space)),
+                // Arrows also browse the folder (historic behaviour)
+                (A::PrevFile, C::new(Key::ArrowLeft)),
+                (A::NextFile, C::new(Key::ArrowRight)),
+                (A::PrevEnvironment, C::ctrl(Key::ArrowLeft)),
+                (A::NextEnvironment, C::ctrl(Key::ArrowRight)),
+            ]),
+            KeymapPreset::Maya => b.extend([
+                (A::ToggleFullscreen, C::ctrl(Key::Space)),
+                (A::HomeCamera, C::new(Key::Home)),
+                (A::FocusSelected, C::new(Key::F)),
+                (A::FrameAll, C::new(Key::A)),
+                (A::HideSelected, C::ctrl(Key::H)),
+                (A::UnhideAll, C { key: Key::H, ctrl: true, shift: true, alt: false }),
+                (A::PlayPause, C::alt(Key::V)),
+                (A::PrevFrame, C::alt(Key::Comma)),
+                (A::NextFrame, C::alt(Key::Period)),
+            ]),
+            KeymapPreset::Houdini => b.extend([
+                (A::ToggleFullscreen, C::ctrl(Key::B)),
+                (A::HomeCamera, C::new(Key::H)),
+                (A::FocusSelected, C::new(Key::F)),
+                (A::FrameAll, C::new(Key::A)),
+                (A::HideSelected, C::ctrl(Key::H)),
+                (A::UnhideAll, C::alt(Key::H)),
+            ]),
+            KeymapPreset::Blender => b.extend([
+                (A::ToggleFullscreen, C::ctrl(Key::Space)),
+                (A::HomeCamera, C::shift(Key::C)),
+                (A::FocusSelected, C::new(Key::F)),
+                (A::FocusSelected, C::new(Key::Period)),
+                (A::FrameAll, C::new(Key::Home)),
+                (A::HideSelected, C::new(Key::H)),
+                (A::UnhideAll, C::alt(Key::H)),
+                (A::PlayPause, C::new(Key::Space)),
+            ]),
+        }
+        b
+    }
+}
+
+/// Active bindings: preset plus user overrides
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    pub preset: KeymapPreset,
+    pub mouse: MouseNav,
+    pub menu_key: Option<Key>,
+    bindings: Vec<(Action, KeyChord)>,
+    /// Actions rebound by the user (replace all preset chords of the action)
+    overrides: BTreeMap<Action, Vec<KeyChord>>,
+}
+
+impl Keymap {
+    pub fn new(preset: KeymapPreset) -> Self {
+        Self {
+            preset,
+            mouse: preset.mouse_nav(),
+            menu_key: preset.menu_key(),
+            bindings: preset.bindings(),
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// Build from persisted preset and overrides (action id -> chord strings)
+    pub fn from_settings(preset: KeymapPreset, overrides: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut keymap = Self::new(preset);
+        for (id, chords) in overrides {
+            if let Some(action) = Action::from_id(id) {
+                keymap.set(action, chords.iter().filter_map(|c| KeyChord::parse(c)).collect());
+            }
+        }
+        keymap
+    }
+
+    /// Overrides in persisted form
+    pub fn overrides(&self) -> BTreeMap<String, Vec<String>> {
+        self.overrides
+            .iter()
+            .map(|(a, chords)| (a.id().to_string(), chords.iter().map(|c| c.to_string()).collect()))
+            .collect()
+    }
+
+    /// Chords currently bound to `action`
+    pub fn chords(&self, action: Action) -> Vec<KeyChord> {
+        match self.overrides.get(&action) {
+            Some(chords) => chords.clone(),
+            None => self.bindings.iter().filter(|(a, _)| *a == action).map(|(_, c)| *c).collect(),
+        }
+    }
+
+    /// Replace the chords of `action` (empty = unbound)
+    pub fn set(&mut self, action: Action, chords: Vec<KeyChord>) {
+        self.overrides.insert(action, chords);
+    }
+
+    /// Drop the user override of `action`, back to the preset
+    pub fn reset(&mut self, action: Action) {
+        self.overrides.remove(&action);
+    }
+
+    pub fn is_overridden(&self, action: Action) -> bool {
+        self.overrides.contains_key(&action)
+    }
+
+    /// True if any chord of `action` was pressed this frame
+    pub fn pressed(&self, input: &InputState, action: Action) -> bool {
+        match self.overrides.get(&action) {
+            Some(chords) => chords.iter().any(|c| c.pressed(input)),
+            None => self.bindings.iter().any(|(a, c)| *a == action && c.pressed(input)),
+        }
+    }
+
+    /// Human-readable chord list for menus ("H, Home")
+    pub fn describe(&self, action: Action) -> String {
+        let chords = self.chords(action);
+        if chords.is_empty() {
+            return "—".to_string();
+        }
+        chords.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Other actions sharing `chord` (conflict hint in the editor)
+    pub fn conflicts(&self, action: Action, chord: KeyChord) -> Vec<Action> {
+        Action::ALL
+            .into_iter()
+            .filter(|&a| a != action && self.chords(a).contains(&chord))
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(KeymapPreset::default())
+    }
+}
@@ -0,0 +1,287 @@
+//! Alembic Viewer - 3D viewer for .abc files
+
+mod app;
+mod audio;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod batch;
+pub mod bookmarks;
+mod camera;
+mod dialogs;
+mod environment;
+mod exposure;
+mod frame_stats;
+mod keymap;
+pub mod export;
+mod load_log;
+mod mesh_converter;
+mod nav_device;
+mod outliner;
+mod render_scale;
+mod renderer;
+mod scene_scale;
+mod selection;
+mod settings;
+mod smooth_normals;
+mod topology;
+mod trails;
+mod viewport;
+mod worker;
+pub mod pathtracer;
+
+pub use renderer::{Overlay, OverlayHandle};
+pub use settings::{PtPreset, Settings};
+
+use std::path::PathBuf;
+use anyhow::Result;
+#[cfg(not(target_arch = "wasm32"))]
+use tracing_subscriber::prelude::*;
+
+/// Initial viewer state requested on the command line.
+///
+/// Every field is optional; `None` keeps the persisted [`Settings`] value.
+/// Overrides are applied once at startup (after the first scene is loaded
+/// for camera/frame), so review sessions can be launched reproducibly.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Scene camera to look through, by full path ("/shot/shotCam") or name.
+    pub camera: Option<String>,
+    /// Start frame in scene frame numbers (mapped through the archive time sampling).
+    pub frame: Option<f64>,
+    /// HDR environment map to load.
+    pub env: Option<PathBuf>,
+    /// Background color (RGB, linear 0..1).
+    pub background: Option<[f32; 3]>,
+    /// Show/hide the ground grid.
+    pub grid: Option<bool>,
+    /// Start in path tracer mode.
+    pub path_tracing: Option<bool>,
+    /// Path tracer preset to apply (e.g. "Interactive", "Preview", "Final").
+    pub pt_preset: Option<String>,
+    /// Overlay handle kept by the caller (plugins, measurement tools) to
+    /// draw custom lines and points in the viewport; the viewer creates its
+    /// own when `None`.
+    pub overlays: Option<OverlayHandle>,
+}
+
+/// Run the viewer with optional initial file.
+/// `verbosity`: 0=warn, 1=info, 2=debug, 3=trace.
+/// `log_file`: optional path to redirect log output.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run(initial_file: Option<PathBuf>, verbosity: u8, log_file: Option<PathBuf>) -> Result<()> {
+    run_with_options(initial_file, LaunchOptions::default(), verbosity, log_file)
+}
+
+/// Run the viewer with optional initial file and startup overrides.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_with_options(
+    initial_file: Option<PathBuf>,
+    launch: LaunchOptions,
+    verbosity: u8,
+    log_file: Option<PathBuf>,
+) -> Result<()> {
+    let trace_guard = init_tracing(verbosity, log_file.as_deref());
+
+    // Friendly panic handler for GPU errors
+    std::panic::set_hook(Box::new(|info| {
+        let msg = info.payload()
+            .downcast_ref::<String>()
+            .map(|s| s.as_str())
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or("Unknown error");
+        
+        if msg.contains("wgpu") || msg.contains("Buffer") || msg.contains("shader") {
+            eprintln!("\n[GPU Error] {}", msg);
+            eprintln!("\nThis is likely a shader/buffer mismatch. Try updating or rebuilding.");
+        } else {
+            eprintln!("\n[Error] {}", msg);
+            if let Some(loc) = info.location() {
+                eprintln!("  at {}:{}:{}", loc.file(), loc.line(), loc.column());
+            }
+        }
+    }));
+    
+    let settings = Settings::load();
+    
+    let options = eframe::NativeOptions {
+        viewport: {
+            let mut vp = egui::ViewportBuilder::default()
+                .with_inner_size([settings.window_width, settings.window_height])
+                .with_title("Alembic Viewer");
+            if let (Some(x), Some(y)) = (settings.window_x, settings.window_y) {
+                vp = vp.with_position([x, y]);
+            }
+            vp
+        },
+        multisampling: settings.antialiasing as u16, // MSAA (0, 2, 4, 8)
+        renderer: eframe::Renderer::Wgpu,
+        wgpu_options: egui_wgpu::WgpuConfiguration {
+            wgpu_setup: egui_wgpu::WgpuSetup::CreateNew(egui_wgpu::WgpuSetupCreateNew {
+                device_descriptor: std::sync::Arc::new(device_descriptor),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Alembic Viewer",
+        options,
+        Box::new(move |cc| Ok(Box::new(app::ViewerApp::new(cc, initial_file.clone(), launch, trace_guard)))),
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to run: {}", e))
+}
+
+/// Run the viewer in a browser canvas on the WebGL2 backend, optionally
+/// opening an archive already in memory (`(file name, contents)`).
+///
+/// The browser build has no threads, native file dialogs or settings file:
+/// frames load on the UI thread, archives are opened by upload or drag and
+/// drop, settings reset on reload, and the compute path tracer and the
+/// export dialogs are unavailable.
+#[cfg(target_arch = "wasm32")]
+pub async fn start_web(
+    canvas: web_sys::HtmlCanvasElement,
+    archive: Option<(String, Vec<u8>)>,
+) -> Result<eframe::WebRunner> {
+    let options = eframe::WebOptions {
+        wgpu_options: egui_wgpu::WgpuConfiguration {
+            wgpu_setup: egui_wgpu::WgpuSetup::CreateNew(egui_wgpu::WgpuSetupCreateNew {
+                instance_descriptor: wgpu::InstanceDescriptor {
+                    backends: wgpu::Backends::GL,
+                    ..Default::default()
+                },
+                device_descriptor: std::sync::Arc::new(device_descriptor),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let runner = eframe::WebRunner::new();
+    runner
+        .start(
+            canvas,
+            options,
+            Box::new(move |cc| {
+                let mut app = app::ViewerApp::new(cc, None, LaunchOptions::default(), None);
+                if let Some((name, bytes)) = archive {
+                    app.open_bytes(name, bytes);
+                }
+                Ok(Box::new(app))
+            }),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run: {:?}", e))?;
+    Ok(runner)
+}
+
+/// Device request for both runners: optional features when the adapter
+/// has them, limits capped to what it supports (WebGL2 has no compute,
+/// storage buffers or line polygon mode).
+fn device_descriptor(adapter: &wgpu::Adapter) -> wgpu::DeviceDescriptor<'static> {
+    let base_limits = if adapter.get_info().backend == wgpu::Backend::Gl {
+        wgpu::Limits::downlevel_webgl2_defaults()
+    } else {
+        wgpu::Limits::default()
+    };
+    // Request optional features when adapter supports them
+    let supported = adapter.features();
+    let mut features = wgpu::Features::empty();
+    // Wireframe display mode
+    if supported.contains(wgpu::Features::POLYGON_MODE_LINE) {
+        features |= wgpu::Features::POLYGON_MODE_LINE;
+    }
+    // Needed for 8x MSAA on some formats
+    if supported.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+        features |= wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+    }
+    // Needed for filtering Rgba32Float textures (path tracer blit)
+    if supported.contains(wgpu::Features::FLOAT32_FILTERABLE) {
+        features |= wgpu::Features::FLOAT32_FILTERABLE;
+    }
+    // Per-pass GPU timings in the performance overlay
+    if supported.contains(wgpu::Features::TIMESTAMP_QUERY) {
+        features |= wgpu::Features::TIMESTAMP_QUERY;
+    }
+    let limits = adapter.limits();
+    wgpu::DeviceDescriptor {
+        label: Some("alembic-viewer device"),
+        required_features: features,
+        required_limits: wgpu::Limits {
+            max_texture_dimension_2d: limits.max_texture_dimension_2d.min(8192),
+            max_bind_groups: limits.max_bind_groups.min(8),
+            max_storage_buffer_binding_size: limits.max_storage_buffer_binding_size.min(512 * 1024 * 1024), // 512MB for large PT scenes
+            max_buffer_size: limits.max_buffer_size.min(512 * 1024 * 1024),
+            ..base_limits
+        },
+        ..Default::default()
+    }
+}
+
+/// Initialize tracing subscriber with console/file output and optional chrome profiler.
+/// Returns chrome flush guard if ALEMBIC_TRACE=1 is set.
+#[cfg(not(target_arch = "wasm32"))]
+fn init_tracing(verbosity: u8, log_file: Option<&std::path::Path>) -> Option<tracing_chrome::FlushGuard> {
+    use tracing_subscriber::{fmt, EnvFilter};
+
+    let level = match verbosity {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(level));
+
+    // Chrome profiler layer (optional, via ALEMBIC_TRACE=1)
+    let chrome_guard = if std::env::var("ALEMBIC_TRACE").ok().as_deref() == Some("1") {
+        let (chrome_layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+            .file("trace.json")
+            .build();
+        // Build with chrome layer + fmt
+        if let Some(path) = log_file {
+            let file = std::fs::File::create(path).expect("Failed to create log file");
+            let file_layer = fmt::layer()
+                .with_writer(file)
+                .with_ansi(false);
+            let subscriber = tracing_subscriber::registry()
+                .with(filter)
+                .with(chrome_layer)
+                .with(file_layer);
+            let _ = tracing::subscriber::set_global_default(subscriber);
+        } else {
+            let stderr_layer = fmt::layer()
+                .with_writer(std::io::stderr);
+            let subscriber = tracing_subscriber::registry()
+                .with(filter)
+                .with(chrome_layer)
+                .with(stderr_layer);
+            let _ = tracing::subscriber::set_global_default(subscriber);
+        }
+        Some(guard)
+    } else {
+        // No chrome profiler, just fmt output
+        if let Some(path) = log_file {
+            let file = std::fs::File::create(path).expect("Failed to create log file");
+            let file_layer = fmt::layer()
+                .with_writer(file)
+                .with_ansi(false);
+            let subscriber = tracing_subscriber::registry()
+                .with(filter)
+                .with(file_layer);
+            let _ = tracing::subscriber::set_global_default(subscriber);
+        } else {
+            let stderr_layer = fmt::layer()
+                .with_writer(std::io::stderr);
+            let subscriber = tracing_subscriber::registry()
+                .with(filter)
+                .with(stderr_layer);
+            let _ = tracing::subscriber::set_global_default(subscriber);
+        }
+        None
+    };
+
+    chrome_guard
+}
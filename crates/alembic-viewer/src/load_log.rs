@@ -0,0 +1,139 @@
+//! Per-object load log
+//!
+//! Collects conversion warnings from every loaded frame (deduplicated, capped)
+//! and shows them in a filterable window. Object paths are links: clicking one
+//! returns the path so the app can select the offending object.
+
+use egui::{Color32, RichText};
+
+use super::mesh_converter::{MeshWarning, WarningLevel};
+
+/// Cap on stored entries so broken animated files don't grow the log forever
+const MAX_ENTRIES: usize = 1000;
+
+/// Deduplicated load warnings plus the log window state
+pub struct LoadLog {
+    entries: Vec<MeshWarning>,
+    /// Window visibility
+    pub open: bool,
+    /// Case-insensitive substring filter on path and message
+    filter: String,
+    show_info: bool,
+    show_warning: bool,
+    show_error: bool,
+}
+
+impl Default for LoadLog {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            open: false,
+            filter: String::new(),
+            show_info: true,
+            show_warning: true,
+            show_error: true,
+        }
+    }
+}
+
+impl LoadLog {
+    /// Add warnings, skipping duplicates. New entries are logged via tracing.
+    pub fn extend(&mut self, warnings: impl IntoIterator<Item = MeshWarning>) {
+        for warning in warnings {
+            if self.entries.len() >= MAX_ENTRIES || self.entries.contains(&warning) {
+                continue;
+            }
+            match warning.level {
+                WarningLevel::Info => tracing::info!("{}: {}", warning.path, warning.message),
+                _ => tracing::warn!("{}: {}", warning.path, warning.message),
+            }
+            self.entries.push(warning);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries at `level`
+    pub fn count(&self, level: WarningLevel) -> usize {
+        self.entries.iter().filter(|w| w.level == level).count()
+    }
+
+    /// Entries passing the level toggles and text filter
+    fn visible(&self) -> impl Iterator<Item = &MeshWarning> {
+        let filter = self.filter.to_lowercase();
+        self.entries.iter().filter(move |w| {
+            let level_on = match w.level {
+                WarningLevel::Info => self.show_info,
+                WarningLevel::Warning => self.show_warning,
+                WarningLevel::Error => self.show_error,
+            };
+            level_on
+                && (filter.is_empty()
+                    || w.path.to_lowercase().contains(&filter)
+                    || w.message.to_lowercase().contains(&filter))
+        })
+    }
+
+    /// Draw the log window. Returns the object path whose link was clicked.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<String> {
+        if !self.open {
+            return None;
+        }
+        let mut open = self.open;
+        let mut clicked = None;
+        egui::Window::new("Load Log")
+            .open(&mut open)
+            .default_size([480.0, 300.0])
+            .show(ctx, |ui| {
+                let (errors, warnings, infos) = (
+                    self.count(WarningLevel::Error),
+                    self.count(WarningLevel::Warning),
+                    self.count(WarningLevel::Info),
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Filter:");
+                    ui.add(egui::TextEdit::singleline(&mut self.filter)
+                        .hint_text("path or message")
+                        .desired_width(160.0));
+                    ui.checkbox(&mut self.show_error, format!("Errors ({})", errors));
+                    ui.checkbox(&mut self.show_warning, format!("Warnings ({})", warnings));
+                    ui.checkbox(&mut self.show_info, format!("Info ({})", infos));
+                });
+                ui.separator();
+                if self.entries.len() >= MAX_ENTRIES {
+                    ui.label(RichText::new(format!("Log capped at {} entries", MAX_ENTRIES)).weak().small());
+                }
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for warning in self.visible() {
+                            ui.horizontal(|ui| {
+                                let (icon, color) = level_style(warning.level);
+                                ui.label(RichText::new(icon).color(color));
+                                if ui.link(&warning.path).on_hover_text("Select object").clicked() {
+                                    clicked = Some(warning.path.clone());
+                                }
+                                ui.label(&warning.message);
+                            });
+                        }
+                    });
+            });
+        self.open = open;
+        clicked
+    }
+}
+
+/// Icon and color for a warning level
+fn level_style(level: WarningLevel) -> (&'static str, Color32) {
+    match level {
+        WarningLevel::Info => ("ℹ", Color32::LIGHT_BLUE),
+        WarningLevel::Warning => ("⚠", Color32::YELLOW),
+        WarningLevel::Error => ("✖", Color32::from_rgb(255, 90, 90)),
+    }
+}
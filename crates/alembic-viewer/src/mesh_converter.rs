@@ -1,11 +1,12 @@
 //! Convert Alembic geometry to GPU-ready data
 
-use crate::geom::{IPolyMesh, PolyMeshSample, ICurves, CurvesSample, ISubD, IPoints, PointsSample, ICamera, ILight};
-use crate::material::{IMaterial, get_material_assignment};
+use alembic::geom::{IPolyMesh, PolyMeshSample, ICurves, CurvesSample, ISubD, IPoints, PointsSample, ICamera, ILight, LightSample, LightType, IFaceSet, REST_POSITIONS};
+use alembic::material::{IMaterial, get_material_assignment};
+use super::settings::DisplayMode;
 use super::smooth_normals::SmoothNormalData;
 use glam::{Mat4, Vec3};
 use rayon::prelude::*;
-use standard_surface::Vertex;
+use standard_surface::{StandardSurfaceParams, Vertex};
 use std::collections::HashMap;
 use std::sync::Arc;
 use parking_lot::Mutex;  // faster than std::sync::Mutex
@@ -35,12 +36,19 @@ impl Bounds {
         }
     }
     
+    /// Grow to include `point` (NaN/Inf points are ignored)
     pub fn expand(&mut self, point: Vec3) {
+        if !point.is_finite() {
+            return;
+        }
         self.min = self.min.min(point);
         self.max = self.max.max(point);
     }
     
     pub fn merge(&mut self, other: &Bounds) {
+        if !other.is_valid() {
+            return;
+        }
         self.min = self.min.min(other.min);
         self.max = self.max.max(other.max);
     }
@@ -73,6 +81,106 @@ impl Bounds {
     }
 }
 
+/// Problems found while converting one object. Offending faces, curves or
+/// points are skipped instead of producing broken geometry or bounds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvertIssues {
+    /// Sample has no positions or topology
+    pub empty: bool,
+    /// Positions with NaN/Inf components
+    pub non_finite_positions: usize,
+    /// Faces with fewer than 3 vertices plus zero-area triangles
+    pub degenerate: usize,
+    /// Faces/curves referencing missing or negative vertex indices
+    pub bad_indices: usize,
+    /// Topology counts were negative or ran past the end of the index array
+    pub truncated: bool,
+    /// Nothing drawable was left after skipping
+    pub no_geometry: bool,
+    /// Normals count matches neither face-vertices nor vertices
+    pub normals_mismatch: bool,
+    /// UVs present but not face-varying (count != face-vertices)
+    pub uvs_mismatch: bool,
+    /// No UVs at all
+    pub missing_uvs: bool,
+}
+
+impl ConvertIssues {
+    /// Human-readable warnings with severity, one per problem
+    pub fn messages(&self) -> Vec<(WarningLevel, String)> {
+        use WarningLevel::*;
+        let mut out = Vec::new();
+        if self.empty {
+            out.push((Error, "empty sample".to_string()));
+        }
+        if self.non_finite_positions > 0 {
+            out.push((Warning, format!("{} NaN/Inf positions skipped", self.non_finite_positions)));
+        }
+        if self.degenerate > 0 {
+            out.push((Warning, format!("{} degenerate faces/triangles skipped", self.degenerate)));
+        }
+        if self.bad_indices > 0 {
+            out.push((Error, format!("{} primitives with out-of-range indices skipped", self.bad_indices)));
+        }
+        if self.truncated {
+            out.push((Error, "topology counts exceed index data (truncated)".to_string()));
+        }
+        if self.no_geometry && !self.empty {
+            out.push((Error, "no drawable geometry".to_string()));
+        }
+        if self.normals_mismatch {
+            out.push((Warning, "normals match neither vertex nor face-varying scope; ignored".to_string()));
+        }
+        if self.uvs_mismatch {
+            out.push((Warning, "UVs are not face-varying; ignored".to_string()));
+        }
+        if self.missing_uvs {
+            out.push((Info, "no UVs".to_string()));
+        }
+        out
+    }
+}
+
+/// Severity of a load warning
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Load warning for one object
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshWarning {
+    pub path: String,
+    pub level: WarningLevel,
+    pub message: String,
+}
+
+/// Append the warnings of `issues` for the object at `path`
+fn push_warnings(warnings: &mut Vec<MeshWarning>, path: &str, issues: &ConvertIssues) {
+    warnings.extend(issues.messages().into_iter().map(|(level, message)| MeshWarning {
+        path: path.to_string(),
+        level,
+        message,
+    }));
+}
+
+/// Arb geom params the viewer doesn't display (reported as info)
+fn push_ignored_params(warnings: &mut Vec<MeshWarning>, path: &str, arb_names: &[String]) {
+    let ignored: Vec<&str> = arb_names.iter()
+        .map(String::as_str)
+        .filter(|n| !matches!(*n, "uv" | "N" | REST_POSITIONS))
+        .collect();
+    if !ignored.is_empty() {
+        warnings.push(MeshWarning {
+            path: path.to_string(),
+            level: WarningLevel::Info,
+            message: format!("geom params not displayed: {}", ignored.join(", ")),
+        });
+    }
+}
+
 /// Converted mesh data ready for GPU
 pub struct ConvertedMesh {
     pub path: String,  // full object path
@@ -90,6 +198,19 @@ pub struct ConvertedMesh {
     pub smooth_data: Option<SmoothNormalData>,
     /// Pre-computed vertex+index hash (from worker thread)
     pub data_hash: u64,
+    /// Per-vertex velocities (units/second) expanded like `vertices`, if stored
+    pub velocities: Option<Arc<Vec<[f32; 3]>>>,
+    /// Index ranges of the mesh's FaceSets (triangles are grouped by set,
+    /// faces in no set come last)
+    pub face_sets: Arc<Vec<FaceSetRange>>,
+}
+
+/// Triangles of one FaceSet: `indices[start..start + count]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaceSetRange {
+    pub name: String,
+    pub start: u32,
+    pub count: u32,
 }
 
 /// Converted curves data ready for GPU (as line strips)
@@ -116,6 +237,8 @@ pub struct ConvertedPoints {
 #[derive(Clone, Debug)]
 pub struct SceneCamera {
     pub name: String,
+    /// Full object path (e.g. "/shot/shotCam")
+    pub path: String,
     pub transform: Mat4,
     /// Focal length in mm
     pub focal_length: f32,
@@ -128,6 +251,9 @@ pub struct SceneCamera {
     pub near: f32,
     /// Far clip
     pub far: f32,
+    /// Focus distance in scene units
+    pub focus_distance: f32,
+    pub f_stop: f32,
 }
 
 impl SceneCamera {
@@ -155,10 +281,14 @@ pub struct SceneLight {
     pub position: Vec3,
     /// Direction (Z-axis of transform, normalized)
     pub direction: Vec3,
-    /// Color (default white, could be read from user properties)
+    /// Color (white unless the light stores one)
     pub color: Vec3,
-    /// Intensity (derived from camera params or default)
+    /// Intensity (1.0 unless the light stores one)
     pub intensity: f32,
+    /// Light type (Unknown is treated as distant)
+    pub light_type: LightType,
+    /// Full spot cone angle in degrees
+    pub cone_angle: Option<f32>,
 }
 
 impl SceneLight {
@@ -174,8 +304,20 @@ impl SceneLight {
             direction: dir,
             color: Vec3::ONE,
             intensity: 1.0,
+            light_type: LightType::Unknown,
+            cone_angle: None,
         }
     }
+
+    /// Create from transform matrix and the light's typed parameters
+    pub fn from_sample(name: String, transform: Mat4, sample: &LightSample) -> Self {
+        let mut light = Self::from_transform(name, transform);
+        light.color = sample.color.unwrap_or(Vec3::ONE);
+        light.intensity = sample.intensity.unwrap_or(1.0);
+        light.light_type = sample.light_type;
+        light.cone_angle = sample.cone_angle;
+        light
+    }
 }
 
 /// Scene material from Alembic file
@@ -209,10 +351,12 @@ pub struct MaterialAssignment {
 }
 
 /// Convert CurvesSample to line strips for GPU
-pub fn convert_curves(sample: &CurvesSample, path: &str, transform: Mat4) -> Option<ConvertedCurves> {
+pub fn convert_curves(sample: &CurvesSample, path: &str, transform: Mat4, issues: &mut ConvertIssues) -> Option<ConvertedCurves> {
     if !sample.is_valid() {
+        issues.empty = true;
         return None;
     }
+    issues.non_finite_positions = sample.positions.iter().filter(|p| !p.is_finite()).count();
     
     let positions = &sample.positions;
     let num_vertices = &sample.num_vertices;
@@ -223,23 +367,31 @@ pub fn convert_curves(sample: &CurvesSample, path: &str, transform: Mat4) -> Opt
     // Build vertices and line indices
     let mut vertices = Vec::with_capacity(positions.len());
     let mut indices = Vec::new();
-    let mut vertex_offset = 0u32;
+    let mut pos_offset = 0usize;
     
     for &count in num_vertices {
-        let count = count as usize;
+        let Ok(count) = usize::try_from(count) else {
+            issues.truncated = true;
+            break;
+        };
+        let curve_positions = positions.get(pos_offset..pos_offset + count);
+        pos_offset += count;
+        let Some(curve_positions) = curve_positions else {
+            issues.truncated = true;
+            break;
+        };
         if count < 2 {
-            vertex_offset += count as u32;
+            issues.degenerate += 1;
+            continue;
+        }
+        if curve_positions.iter().any(|p| !p.is_finite()) {
             continue;
         }
+        let vertex_offset = vertices.len() as u32;
         
         // Add vertices for this curve
-        for i in 0..count {
-            let pos_idx = vertex_offset as usize + i;
-            if pos_idx >= positions.len() {
-                break;
-            }
-            
-            let pos = positions[pos_idx];
+        for (i, &pos) in curve_positions.iter().enumerate() {
+            let pos_idx = pos_offset - count + i;
             
             // Get width if available (default 0.01)
             let width = if !sample.widths.is_empty() && pos_idx < sample.widths.len() {
@@ -260,11 +412,10 @@ pub fn convert_curves(sample: &CurvesSample, path: &str, transform: Mat4) -> Opt
             indices.push(vertex_offset + i as u32);
             indices.push(vertex_offset + i as u32 + 1);
         }
-        
-        vertex_offset += count as u32;
     }
     
     if vertices.is_empty() {
+        issues.no_geometry = true;
         return None;
     }
     
@@ -281,10 +432,19 @@ pub fn convert_curves(sample: &CurvesSample, path: &str, transform: Mat4) -> Opt
 }
 
 /// Convert PolyMeshSample to triangulated GPU mesh
-pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<ConvertedMesh> {
+/// `face_sets` are the (name, face indices) of the mesh's FaceSets; the
+/// triangles are grouped by set so each set is one index range.
+pub fn convert_polymesh(
+    sample: &PolyMeshSample,
+    transform: Mat4,
+    face_sets: &[(String, Vec<i32>)],
+    issues: &mut ConvertIssues,
+) -> Option<ConvertedMesh> {
     if !sample.is_valid() {
+        issues.empty = true;
         return None;
     }
+    issues.non_finite_positions = sample.positions.iter().filter(|p| !p.is_finite()).count();
 
     let positions = &sample.positions;
     let face_counts = &sample.face_counts;
@@ -301,31 +461,80 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
     };
     
     let uvs = sample.uvs.as_ref();
+    let point_velocities = sample.velocities.as_ref().filter(|v| v.len() == positions.len());
+    if let Some(norms) = normals {
+        issues.normals_mismatch = norms.len() != face_indices.len() && norms.len() != positions.len();
+    }
+    match uvs {
+        Some(uv_data) => issues.uvs_mismatch = uv_data.len() != face_indices.len(),
+        None => issues.missing_uvs = true,
+    }
     
     // Count triangles for pre-allocation
-    let tri_count: usize = face_counts.iter().map(|&c| (c as usize).saturating_sub(2)).sum();
+    // (clamped to the index data so corrupt counts can't blow up the allocation)
+    let tri_count = face_counts.iter()
+        .fold(0usize, |n, &c| n.saturating_add((c.max(0) as usize).saturating_sub(2)))
+        .min(face_indices.len());
     
     let mut vertices = Vec::with_capacity(tri_count * 3);
     let mut indices = Vec::with_capacity(tri_count * 3);
+    let mut velocities = Vec::with_capacity(if point_velocities.is_some() { tri_count * 3 } else { 0 });
     // For smooth normals: position and face normal per vertex
     let mut smooth_positions = Vec::with_capacity(tri_count * 3);
     let mut smooth_face_normals = Vec::with_capacity(tri_count * 3);
     
+    // FaceSet slot per face (first set wins) and per emitted triangle
+    let mut face_slot = Vec::new();
+    let mut tri_slots = Vec::new();
+    if !face_sets.is_empty() {
+        face_slot = vec![u32::MAX; face_counts.len()];
+        for (slot, (_, faces)) in face_sets.iter().enumerate() {
+            for &f in faces {
+                if let Some(s) = usize::try_from(f).ok().and_then(|f| face_slot.get_mut(f)) {
+                    if *s == u32::MAX {
+                        *s = slot as u32;
+                    }
+                }
+            }
+        }
+        tri_slots.reserve(tri_count);
+    }
+
     let mut idx_offset = 0usize;
     let mut face_idx = 0usize;
     
     for &count in face_counts {
-        let count = count as usize;
+        // Negative counts or counts past the index data: the rest of the
+        // topology can't be trusted
+        let Ok(count) = usize::try_from(count) else {
+            issues.truncated = true;
+            break;
+        };
+        let Some(face) = face_indices.get(idx_offset..idx_offset + count) else {
+            issues.truncated = true;
+            break;
+        };
         if count < 3 {
+            issues.degenerate += 1;
             idx_offset += count;
             face_idx += 1;
             continue;
         }
         
-        // Get face vertex indices
-        let face_vertex_indices: Vec<usize> = (0..count)
-            .map(|i| face_indices[idx_offset + i] as usize)
+        // Get face vertex indices (skip faces with out-of-range indices)
+        let face_vertex_indices: Vec<usize> = face.iter()
+            .filter_map(|&i| usize::try_from(i).ok().filter(|&i| i < positions.len()))
             .collect();
+        let face_ok = face_vertex_indices.len() == count;
+        if !face_ok {
+            issues.bad_indices += 1;
+        }
+        let finite = face_ok && face_vertex_indices.iter().all(|&i| positions[i].is_finite());
+        if !finite {
+            idx_offset += count;
+            face_idx += 1;
+            continue;
+        }
         
         // Fan triangulation: v0, v1, v2, then v0, v2, v3, etc.
         for i in 1..count - 1 {
@@ -334,9 +543,18 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
             let i2 = face_vertex_indices[i + 1];
             
             // Get positions
-            let p0 = positions.get(i0).copied().unwrap_or(Vec3::ZERO);
-            let p1 = positions.get(i1).copied().unwrap_or(Vec3::ZERO);
-            let p2 = positions.get(i2).copied().unwrap_or(Vec3::ZERO);
+            let p0 = positions[i0];
+            let p1 = positions[i1];
+            let p2 = positions[i2];
+            
+            // Skip zero-area triangles
+            let edge1 = p1 - p0;
+            let edge2 = p2 - p0;
+            let cross = edge1.cross(edge2);
+            if cross.length_squared() == 0.0 {
+                issues.degenerate += 1;
+                continue;
+            }
             
             // Get normals
             let (n0, n1, n2) = if let Some(norms) = normals {
@@ -377,9 +595,7 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
             };
             
             // Compute geometric face normal for this triangle
-            let edge1 = p1 - p0;
-            let edge2 = p2 - p0;
-            let geo_face_normal = edge1.cross(edge2).normalize_or_zero();
+            let geo_face_normal = cross.normalize_or_zero();
             
             // Add vertices
             let base_idx = vertices.len() as u32;
@@ -400,6 +616,10 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
                 uv: uv2.into(),
             });
             
+            if let Some(vel) = point_velocities {
+                velocities.extend([vel[i0], vel[i1], vel[i2]].map(<[f32; 3]>::from));
+            }
+
             // Store data for smooth normals recalculation
             smooth_positions.push(p0);
             smooth_positions.push(p1);
@@ -411,12 +631,20 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
             indices.push(base_idx);
             indices.push(base_idx + 1);
             indices.push(base_idx + 2);
+            if !face_slot.is_empty() {
+                tri_slots.push(face_slot[face_idx]);
+            }
         }
         
         idx_offset += count;
         face_idx += 1;
     }
     
+    if vertices.is_empty() {
+        issues.no_geometry = true;
+        return None;
+    }
+    
     // Compute world-space bounds
     let mut bounds = Bounds::empty();
     for pos in positions {
@@ -426,6 +654,12 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
     
     // Build smooth normal data for dynamic recalculation
     let smooth_data = SmoothNormalData::from_vertices(&smooth_positions, &smooth_face_normals);
+
+    let face_set_ranges = if face_sets.is_empty() {
+        Vec::new()
+    } else {
+        group_face_sets(&mut indices, &tri_slots, face_sets)
+    };
     
     let data_hash = compute_data_hash(&vertices, &indices);
     Some(ConvertedMesh {
@@ -441,25 +675,59 @@ pub fn convert_polymesh(sample: &PolyMeshSample, transform: Mat4) -> Option<Conv
         specular_ior: None,
         smooth_data: Some(smooth_data),
         data_hash,
+        velocities: point_velocities.map(|_| Arc::new(velocities)),
+        face_sets: Arc::new(face_set_ranges),
     })
 }
 
+/// Reorder triangles by FaceSet slot (`u32::MAX` = no set, placed last) and
+/// return the range of each non-empty set.
+fn group_face_sets(indices: &mut Vec<u32>, tri_slots: &[u32], face_sets: &[(String, Vec<i32>)]) -> Vec<FaceSetRange> {
+    let mut order: Vec<usize> = (0..tri_slots.len()).collect();
+    order.sort_by_key(|&t| tri_slots[t]);
+    *indices = order.iter().flat_map(|&t| [indices[t * 3], indices[t * 3 + 1], indices[t * 3 + 2]]).collect();
+
+    let mut ranges = Vec::new();
+    let mut start = 0u32;
+    for (slot, (name, _)) in face_sets.iter().enumerate() {
+        let count = tri_slots.iter().filter(|&&s| s == slot as u32).count() as u32 * 3;
+        if count > 0 {
+            ranges.push(FaceSetRange { name: name.clone(), start, count });
+            start += count;
+        }
+    }
+    ranges
+}
+
 /// Convert PointsSample to GPU points
-pub fn convert_points(sample: &PointsSample, path: &str, transform: Mat4) -> Option<ConvertedPoints> {
+pub fn convert_points(sample: &PointsSample, path: &str, transform: Mat4, issues: &mut ConvertIssues) -> Option<ConvertedPoints> {
     if !sample.is_valid() {
+        issues.empty = true;
         return None;
     }
     
-    let positions: Vec<[f32; 3]> = sample.positions.iter()
-        .map(|p| [p.x, p.y, p.z])
-        .collect();
-    
-    // Use widths if available, otherwise default radius
-    let widths = if sample.has_widths() {
-        sample.widths.clone()
-    } else {
-        vec![0.02; sample.positions.len()]  // default 2cm radius
-    };
+    // Drop NaN/Inf points (with their widths)
+    let has_widths = sample.has_widths();
+    let mut positions = Vec::with_capacity(sample.positions.len());
+    let mut widths = Vec::with_capacity(sample.positions.len());
+    for (i, p) in sample.positions.iter().enumerate() {
+        if !p.is_finite() {
+            issues.non_finite_positions += 1;
+            continue;
+        }
+        positions.push([p.x, p.y, p.z]);
+        // Use widths if available, otherwise default radius (2cm)
+        let width = if has_widths {
+            sample.widths.get(i).or(sample.widths.first()).copied().unwrap_or(0.02)
+        } else {
+            0.02
+        };
+        widths.push(if width.is_finite() { width } else { 0.02 });
+    }
+    if positions.is_empty() {
+        issues.no_geometry = true;
+        return None;
+    }
     
     // Compute bounds
     let mut bounds = Bounds::empty();
@@ -485,6 +753,10 @@ pub struct CollectedScene {
     pub cameras: Vec<SceneCamera>,
     pub lights: Vec<SceneLight>,
     pub is_static: bool,
+    /// Meshes carrying rest positions (Pref)
+    pub rest_pose_meshes: usize,
+    /// Per-object conversion warnings (skipped faces, NaN positions, ...)
+    pub warnings: Vec<MeshWarning>,
     #[allow(dead_code)]  // used internally for color resolution
     pub materials: Vec<SceneMaterial>,
     #[allow(dead_code)]  // used internally for color resolution
@@ -498,6 +770,7 @@ pub(crate) struct CachedMesh {
     indices: Arc<Vec<u32>>,
     local_bounds: Bounds,
     data_hash: u64,
+    face_sets: Arc<Vec<FaceSetRange>>,
 }
 
 /// Cached curves data for constant geometry
@@ -542,6 +815,7 @@ struct MeshTask {
     sample: PolyMeshSample,
     transform: Mat4,
     is_constant: bool,
+    face_sets: Vec<(String, Vec<i32>)>,
 }
 
 /// Cached mesh result (from cache hit)
@@ -552,6 +826,7 @@ struct CachedResult {
     transform: Mat4,
     local_bounds: Bounds,
     data_hash: u64,
+    face_sets: Arc<Vec<FaceSetRange>>,
 }
 
 struct CachedCurvesResult {
@@ -570,8 +845,32 @@ struct CachedPointsResult {
     local_bounds: Bounds,
 }
 
+/// (name, faces) of the FaceSet children of a mesh object at `sample_index`
+fn read_face_sets(obj: &alembic::abc::IObject, sample_index: usize) -> Vec<(String, Vec<i32>)> {
+    obj.getChildren()
+        .filter_map(|child| {
+            let face_set = IFaceSet::new(&child)?;
+            let n = face_set.getNumSamples();
+            let sample = face_set.getSample(sample_index.min(n.checked_sub(1)?)).ok()?;
+            Some((child.getName().to_string(), sample.faces))
+        })
+        .collect()
+}
+
 /// Recursively collect all geometry with optional caching for constant meshes
-pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize, cache: Option<&MeshCache>) -> CollectedScene {
+///
+/// Meshes with rest positions (Pref) are blended toward their rest pose by
+/// `rest_blend` (0 = animated, 1 = rest); blended meshes bypass the cache.
+/// Xforms are blended `subframe` (0..1) of the way to the next sample, by
+/// [`XformSample::lerp`](alembic::geom::XformSample::lerp); geometry stays
+/// at `sample_index`.
+pub fn collect_scene_cached(
+    archive: &alembic::abc::IArchive,
+    sample_index: usize,
+    subframe: f32,
+    cache: Option<&MeshCache>,
+    rest_blend: f32,
+) -> CollectedScene {
     let mut mesh_tasks = Vec::new();
     let mut cached_results = Vec::new();
     let mut cached_curve_results = Vec::new();
@@ -583,6 +882,8 @@ pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize,
     let mut materials = Vec::new();
     let mut material_assignments = Vec::new();
     let mut has_animation = false;
+    let mut rest_pose_meshes = 0;
+    let mut warnings = Vec::new();
     let root = archive.getTop();
 
     // Phase 1: Collect all mesh samples, curves, points, cameras, lights, materials (sequential file reads)
@@ -590,6 +891,7 @@ pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize,
         &root,
         Mat4::IDENTITY,
         sample_index,
+        subframe,
         &mut mesh_tasks,
         &mut cached_results,
         &mut cached_curve_results,
@@ -602,13 +904,17 @@ pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize,
         &mut material_assignments,
         &mut has_animation,
         cache,
+        rest_blend,
+        &mut rest_pose_meshes,
+        &mut warnings,
     );
     
     // Phase 2: Convert meshes in parallel (CPU-bound)
-    let converted: Vec<ConvertedMesh> = mesh_tasks
+    let results: Vec<(Option<ConvertedMesh>, Vec<MeshWarning>)> = mesh_tasks
         .into_par_iter()
-        .filter_map(|task| {
-            convert_polymesh(&task.sample, task.transform).map(|mut converted| {
+        .map(|task| {
+            let mut issues = ConvertIssues::default();
+            let converted = convert_polymesh(&task.sample, task.transform, &task.face_sets, &mut issues).map(|mut converted| {
                 // Set path from task
                 converted.path = task.path.clone();
                 
@@ -625,13 +931,22 @@ pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize,
                             indices: Arc::clone(&converted.indices),
                             local_bounds,
                             data_hash: converted.data_hash,
+                            face_sets: Arc::clone(&converted.face_sets),
                         });
                     }
                 }
                 converted
-            })
+            });
+            let mut task_warnings = Vec::new();
+            push_warnings(&mut task_warnings, &task.path, &issues);
+            (converted, task_warnings)
         })
         .collect();
+    let mut converted = Vec::with_capacity(results.len());
+    for (mesh, mesh_warnings) in results {
+        converted.extend(mesh);
+        warnings.extend(mesh_warnings);
+    }
     
     // Phase 3: Combine results - cached meshes + converted meshes
     let mut meshes = Vec::with_capacity(cached_results.len() + converted.len());
@@ -652,6 +967,8 @@ pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize,
             specular_ior: None,
             smooth_data: None,  // cached meshes don't store smooth data
             data_hash: cached.data_hash,
+            velocities: None,  // constant meshes don't move
+            face_sets: cached.face_sets,
         });
     }
     
@@ -716,6 +1033,8 @@ pub fn collect_scene_cached(archive: &crate::abc::IArchive, sample_index: usize,
         cameras,
         lights,
         is_static: !has_animation,
+        rest_pose_meshes,
+        warnings,
         materials,
         material_assignments,
     }
@@ -758,12 +1077,25 @@ fn bounds_from_vec3(positions: &[Vec3]) -> Bounds {
     bounds
 }
 
+/// Blend positions toward rest positions (0 = animated, 1 = rest).
+/// Left unchanged when the point counts differ.
+fn blend_to_rest(positions: &mut [Vec3], rest: &[Vec3], blend: f32) {
+    if positions.len() != rest.len() {
+        return;
+    }
+    let blend = blend.clamp(0.0, 1.0);
+    for (p, r) in positions.iter_mut().zip(rest) {
+        *p = p.lerp(*r, blend);
+    }
+}
+
 /// Phase 1: Collect all mesh samples (sequential reads from file)
 #[allow(clippy::too_many_arguments)]
 fn collect_samples_recursive(
-    obj: &crate::abc::IObject,
+    obj: &alembic::abc::IObject,
     parent_transform: Mat4,
     sample_index: usize,
+    subframe: f32,
     mesh_tasks: &mut Vec<MeshTask>,
     cached_results: &mut Vec<CachedResult>,
     cached_curve_results: &mut Vec<CachedCurvesResult>,
@@ -776,9 +1108,12 @@ fn collect_samples_recursive(
     material_assignments: &mut Vec<MaterialAssignment>,
     has_animation: &mut bool,
     cache: Option<&MeshCache>,
+    rest_blend: f32,
+    rest_pose_meshes: &mut usize,
+    warnings: &mut Vec<MeshWarning>,
 ) {
     // Check if this object is an Xform
-    let (local_transform, inherits) = if let Some(xform) = crate::geom::IXform::new(obj) {
+    let (local_transform, inherits) = if let Some(xform) = alembic::geom::IXform::new(obj) {
         let num_samples = xform.getNumSamples();
         let sample_idx = if num_samples > 0 {
             // Clamp to last sample to mirror SampleSelector behavior.
@@ -790,7 +1125,13 @@ fn collect_samples_recursive(
             *has_animation = true;
         }
         if num_samples > 0 {
-            if let Ok(sample) = xform.getSample(sample_idx) {
+            if let Ok(mut sample) = xform.getSample(sample_idx) {
+                // Blend toward the next sample (TRS, rotation slerped)
+                if subframe > 0.0 && sample_idx + 1 < num_samples {
+                    if let Ok(next) = xform.getSample(sample_idx + 1) {
+                        sample = sample.lerp(&next, subframe);
+                    }
+                }
                 (sample.matrix(), sample.inherits)
             } else {
                 (Mat4::IDENTITY, true)
@@ -823,6 +1164,15 @@ fn collect_samples_recursive(
             0
         };
         
+        let arb_names = polymesh.arb_geom_param_names();
+        push_ignored_params(warnings, &mesh_path, &arb_names);
+        let has_pref = arb_names.iter().any(|n| n == REST_POSITIONS);
+        if has_pref {
+            *rest_pose_meshes += 1;
+        }
+        let rest = if has_pref && rest_blend > 0.0 { polymesh.rest_positions(sample_idx) } else { None };
+        let is_constant = is_constant && rest.is_none();
+
         // Try cache first for constant meshes
         // IMPORTANT: Use mesh_path as key, not mesh_name - different objects may have same name
         // but different world-space positions (e.g., brake_discShape in multiple wheels)
@@ -840,14 +1190,19 @@ fn collect_samples_recursive(
                 transform: world_transform,
                 local_bounds: cached_mesh.local_bounds,
                 data_hash: cached_mesh.data_hash,
+                face_sets: cached_mesh.face_sets,
             });
         } else if num_samples > 0 {
-            if let Ok(sample) = polymesh.getSample(sample_idx) {
+            if let Ok(mut sample) = polymesh.getSample(sample_idx) {
+                if let Some(rest) = &rest {
+                    blend_to_rest(&mut sample.positions, rest, rest_blend);
+                }
                 mesh_tasks.push(MeshTask {
                     path: mesh_path,
                     sample,
                     transform: world_transform,
                     is_constant,
+                    face_sets: read_face_sets(obj, sample_index),
                 });
             }
         }
@@ -868,6 +1223,15 @@ fn collect_samples_recursive(
             0
         };
         
+        let arb_names = subd.arb_geom_param_names();
+        push_ignored_params(warnings, &mesh_path, &arb_names);
+        let has_pref = arb_names.iter().any(|n| n == REST_POSITIONS);
+        if has_pref {
+            *rest_pose_meshes += 1;
+        }
+        let rest = if has_pref && rest_blend > 0.0 { subd.rest_positions(sample_idx) } else { None };
+        let is_constant = is_constant && rest.is_none();
+
         // Use mesh_path as key for SubD too
         let cached = if is_constant {
             cache.and_then(|c| c.lock().meshes.get(&mesh_path).cloned())
@@ -883,9 +1247,13 @@ fn collect_samples_recursive(
                 transform: world_transform,
                 local_bounds: cached_mesh.local_bounds,
                 data_hash: cached_mesh.data_hash,
+                face_sets: cached_mesh.face_sets,
             });
         } else if num_samples > 0 {
-            if let Ok(sample) = subd.getSample(sample_idx) {
+            if let Ok(mut sample) = subd.getSample(sample_idx) {
+                if let Some(rest) = &rest {
+                    blend_to_rest(&mut sample.positions, rest, rest_blend);
+                }
                 // Convert SubD sample to PolyMesh sample for the task.
                 let poly_sample = PolyMeshSample {
                     positions: sample.positions,
@@ -902,6 +1270,7 @@ fn collect_samples_recursive(
                     sample: poly_sample,
                     transform: world_transform,
                     is_constant,
+                    face_sets: read_face_sets(obj, sample_index),
                 });
             }
         }
@@ -936,7 +1305,10 @@ fn collect_samples_recursive(
             });
         } else if num_samples > 0 {
             if let Ok(sample) = icurves.getSample(sample_idx) {
-                if let Some(converted) = convert_curves(&sample, icurves.getFullName(), world_transform) {
+                let mut issues = ConvertIssues::default();
+                let converted = convert_curves(&sample, icurves.getFullName(), world_transform, &mut issues);
+                push_warnings(warnings, &curve_path, &issues);
+                if let Some(converted) = converted {
                     if is_constant {
                         if let Some(cache) = cache {
                             cache.lock().curves.insert(converted.path.clone(), CachedCurves {
@@ -981,7 +1353,10 @@ fn collect_samples_recursive(
             });
         } else if num_samples > 0 {
             if let Ok(sample) = ipoints.getSample(sample_idx) {
-                if let Some(converted) = convert_points(&sample, ipoints.getFullName(), world_transform) {
+                let mut issues = ConvertIssues::default();
+                let converted = convert_points(&sample, ipoints.getFullName(), world_transform, &mut issues);
+                push_warnings(warnings, &points_path, &issues);
+                if let Some(converted) = converted {
                     if is_constant {
                         if let Some(cache) = cache {
                             let local_bounds = bounds_from_vec3(&sample.positions);
@@ -1014,12 +1389,15 @@ fn collect_samples_recursive(
             if let Ok(sample) = icamera.getSample(sample_idx) {
                 cameras.push(SceneCamera {
                     name: icamera.getName().to_string(),
+                    path: icamera.getFullName().to_string(),
                     transform: world_transform,
                     focal_length: sample.focal_length as f32,
                     h_aperture: sample.horizontal_aperture as f32,
                     v_aperture: sample.vertical_aperture as f32,
                     near: sample.near_clipping_plane as f32,
                     far: sample.far_clipping_plane as f32,
+                    focus_distance: sample.focus_distance as f32,
+                    f_stop: sample.f_stop as f32,
                 });
             }
         }
@@ -1031,10 +1409,13 @@ fn collect_samples_recursive(
         if num_samples > 1 && !ilight.isConstant() {
             *has_animation = true;
         }
-        // Lights use transform for position/direction
-        lights.push(SceneLight::from_transform(
+        // Lights use transform for position/direction, parameters from the sample
+        let sample_idx = sample_index.min(num_samples.saturating_sub(1));
+        let sample = ilight.getSample(sample_idx).unwrap_or_default();
+        lights.push(SceneLight::from_sample(
             ilight.getName().to_string(),
             world_transform,
+            &sample,
         ));
     }
 
@@ -1093,6 +1474,7 @@ fn collect_samples_recursive(
             &child,
             world_transform,
             sample_index,
+            subframe,
             mesh_tasks,
             cached_results,
             cached_curve_results,
@@ -1105,6 +1487,9 @@ fn collect_samples_recursive(
             material_assignments,
             has_animation,
             cache,
+            rest_blend,
+            rest_pose_meshes,
+            warnings,
         );
     }
 }
@@ -1172,6 +1557,139 @@ pub fn compute_stats(meshes: &[ConvertedMesh]) -> MeshStats {
     }
 }
 
+/// Surface material for a converted mesh: its assigned material when it has
+/// one, otherwise a guess from its path (`materialize_missing`) or grey plastic
+pub fn mesh_material(mesh: &ConvertedMesh, materialize_missing: bool) -> StandardSurfaceParams {
+    let has_material = mesh.base_color.is_some() || mesh.metallic.is_some()
+        || mesh.roughness.is_some() || mesh.transmission.is_some();
+
+    let (base_color, roughness, metallic, transmission, ior) = if has_material {
+        (
+            mesh.base_color.unwrap_or(Vec3::new(0.7, 0.7, 0.75)),
+            mesh.roughness.unwrap_or(0.4),
+            mesh.metallic.unwrap_or(0.0),
+            mesh.transmission.unwrap_or(0.0),
+            mesh.specular_ior.unwrap_or(1.5),
+        )
+    } else if materialize_missing {
+        guess_material_from_path(&mesh.path)
+    } else {
+        (Vec3::new(0.7, 0.7, 0.75), 0.4, 0.0, 0.0, 1.5)
+    };
+
+    let mut material = if transmission > 0.1 {
+        StandardSurfaceParams::glass(base_color, ior)
+    } else if metallic > 0.5 {
+        StandardSurfaceParams::metal(base_color, roughness)
+    } else {
+        StandardSurfaceParams::plastic(base_color, roughness)
+    };
+    if transmission > 0.1 {
+        // Glass: set roughness separately (glass() defaults to 0)
+        material.params1.z = roughness;
+    }
+    material.set_metalness(metallic);
+    material
+}
+
+/// Surface material for a display mode: the mesh's own material for
+/// `Materials`, otherwise a QC shading. For `RandomFaceSet` this is the
+/// color of faces in no set (see [`face_set_materials`]).
+pub fn display_material(mesh: &ConvertedMesh, mode: DisplayMode, materialize_missing: bool) -> StandardSurfaceParams {
+    let clay = StandardSurfaceParams::diffuse(Vec3::splat(0.55));
+    match mode {
+        DisplayMode::Materials => mesh_material(mesh, materialize_missing),
+        DisplayMode::Clay | DisplayMode::RandomFaceSet => clay,
+        DisplayMode::RandomObject => StandardSurfaceParams::plastic(random_color(&mesh.path), 0.5),
+        DisplayMode::UvChecker => {
+            let mut checker = StandardSurfaceParams::diffuse(Vec3::splat(0.8));
+            checker.set_uv_checker(8.0);
+            checker
+        }
+    }
+}
+
+/// One material per FaceSet range for `RandomFaceSet`, plus `base` for the
+/// faces in no set; together they cover all `index_count` indices.
+/// Empty when the mesh has no FaceSets.
+pub fn face_set_materials(
+    mesh: &ConvertedMesh,
+    index_count: u32,
+    base: &StandardSurfaceParams,
+) -> Vec<(std::ops::Range<u32>, StandardSurfaceParams)> {
+    let mut out: Vec<_> = mesh.face_sets.iter()
+        .map(|set| {
+            let color = random_color(&format!("{}/{}", mesh.path, set.name));
+            (set.start..set.start + set.count, StandardSurfaceParams::plastic(color, 0.5))
+        })
+        .collect();
+    let assigned = out.last().map_or(0, |(range, _)| range.end);
+    if !out.is_empty() && assigned < index_count {
+        out.push((assigned..index_count, *base));
+    }
+    out
+}
+
+/// Stable, well-spread color for a path (hue from its hash)
+fn random_color(key: &str) -> Vec3 {
+    let hash: u32 = key.bytes().fold(2166136261u32, |acc, b| (acc ^ b as u32).wrapping_mul(16777619));
+    let hue = (hash as f32 / u32::MAX as f32) * 6.0;
+    let (s, v) = (0.6, 0.85);
+    let f = hue.fract();
+    let (p, q, t) = (v * (1.0 - s), v * (1.0 - s * f), v * (1.0 - s * (1.0 - f)));
+    match hue as u32 % 6 {
+        0 => Vec3::new(v, t, p),
+        1 => Vec3::new(q, v, p),
+        2 => Vec3::new(p, v, t),
+        3 => Vec3::new(p, q, v),
+        4 => Vec3::new(t, p, v),
+        _ => Vec3::new(v, p, q),
+    }
+}
+
+/// Guess material properties from object path for auto-materialization
+fn guess_material_from_path(path: &str) -> (Vec3, f32, f32, f32, f32) {
+    // Returns: (base_color, roughness, metallic, transmission, ior)
+    let lower = path.to_lowercase();
+    
+    // Glass
+    if lower.contains("glass") || lower.contains("windshield") || lower.contains("window") {
+        return (Vec3::new(0.9, 0.95, 1.0), 0.0, 0.0, 0.95, 1.5);
+    }
+    
+    // Metal/Chrome
+    if lower.contains("chrome") || lower.contains("metal") || lower.contains("rim") 
+        || lower.contains("nuts") || lower.contains("bolt") || lower.contains("steel")
+        || lower.contains("brake_disc") || lower.contains("baraban") {
+        return (Vec3::new(0.8, 0.8, 0.85), 0.3, 1.0, 0.0, 1.5);
+    }
+    
+    // Rubber/Tire
+    if lower.contains("tire") || lower.contains("tyre") || lower.contains("rubber") {
+        return (Vec3::new(0.05, 0.05, 0.05), 0.9, 0.0, 0.0, 1.5);
+    }
+    
+    // Plastic
+    if lower.contains("plastic") || lower.contains("grill") || lower.contains("bumper") {
+        return (Vec3::new(0.2, 0.2, 0.22), 0.4, 0.0, 0.0, 1.5);
+    }
+    
+    // Paint/Body -> metallic paint
+    if lower.contains("paint") || lower.contains("body") {
+        return (Vec3::new(0.7, 0.1, 0.1), 0.3, 0.9, 0.0, 1.5); // Red metallic
+    }
+    
+    // Default: use path hash to pick a random material type
+    let hash: u32 = path.bytes().fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32));
+    match hash % 5 {
+        0 => (Vec3::new(0.9, 0.95, 1.0), 0.0, 0.0, 0.95, 1.5), // glass
+        1 => (Vec3::new(0.8, 0.8, 0.85), 0.3, 1.0, 0.0, 1.5),  // metal
+        2 => (Vec3::new(0.2, 0.2, 0.22), 0.4, 0.0, 0.0, 1.5),  // plastic
+        3 => (Vec3::new(0.15, 0.08, 0.05), 0.7, 0.0, 0.0, 1.4), // leather
+        _ => (Vec3::new(0.05, 0.05, 0.05), 0.9, 0.0, 0.0, 1.5), // rubber
+    }
+}
+
 /// Compute combined bounds of all meshes and points
 pub fn compute_scene_bounds(meshes: &[ConvertedMesh], points: &[ConvertedPoints], curves: &[ConvertedCurves]) -> Bounds {
     let mut bounds = Bounds::empty();
@@ -0,0 +1,395 @@
+//! 3Dconnexion SpaceMouse and gamepad camera navigation
+//!
+//! Devices are read on background threads straight from the Linux kernel
+//! interfaces, so no extra dependencies are needed: SpaceMice through
+//! `/dev/hidraw*` (the same reports hidapi exposes) and gamepads through the
+//! joystick API (`/dev/input/js*`). Axes are normalized to -1..1 with a dead
+//! zone; buttons are mapped to keymap [`Action`]s.
+//!
+//! Device support is Linux-only: on other platforms (and in the browser)
+//! discovery finds nothing, [`SUPPORTED`] is false and the View menu says so.
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
+
+use glam::Vec2;
+use parking_lot::Mutex;
+
+use super::keymap::Action;
+
+/// Whether this platform can read navigation devices at all
+pub const SUPPORTED: bool = cfg!(target_os = "linux");
+
+/// 3Dconnexion vendor ids (old Logitech-era devices and current ones)
+#[cfg(target_os = "linux")]
+const VENDOR_LOGITECH: u32 = 0x046d;
+#[cfg(target_os = "linux")]
+const VENDOR_3DCONNEXION: u32 = 0x256f;
+
+/// Typical full-scale deflection reported by SpaceMouse axes
+const SPACEMOUSE_RANGE: f32 = 350.0;
+
+/// Stick dead zone (fraction of full deflection)
+const DEAD_ZONE: f32 = 0.12;
+
+/// How often to look for newly plugged devices
+const RESCAN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Camera rates requested by the devices this frame (each axis in -1..1)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NavInput {
+    /// Yaw (x) and pitch (y) rate
+    pub orbit: Vec2,
+    /// Screen-space pan rate
+    pub pan: Vec2,
+    /// Dolly rate, positive = towards the pivot
+    pub zoom: f32,
+}
+
+impl NavInput {
+    pub fn is_idle(&self) -> bool {
+        self.orbit == Vec2::ZERO && self.pan == Vec2::ZERO && self.zoom == 0.0
+    }
+
+    fn accumulate(&mut self, other: &NavInput) {
+        self.orbit += other.orbit;
+        self.pan += other.pan;
+        self.zoom += other.zoom;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceKind {
+    SpaceMouse,
+    Gamepad,
+}
+
+/// State written by a device thread
+#[derive(Default)]
+struct DeviceState {
+    input: NavInput,
+    actions: Vec<Action>,
+}
+
+struct Device {
+    name: String,
+    kind: DeviceKind,
+    path: PathBuf,
+    state: Arc<Mutex<DeviceState>>,
+    alive: Arc<AtomicBool>,
+}
+
+/// Connected navigation devices
+pub struct NavDevices {
+    devices: Vec<Device>,
+    last_scan: Option<Instant>,
+    /// Set to stop all device threads
+    stop: Arc<AtomicBool>,
+    /// Last open error per device path (permissions are the usual culprit)
+    pub errors: Vec<String>,
+}
+
+impl Default for NavDevices {
+    fn default() -> Self {
+        Self { devices: Vec::new(), last_scan: None, stop: Arc::new(AtomicBool::new(false)), errors: Vec::new() }
+    }
+}
+
+impl NavDevices {
+    /// Names of the connected devices
+    pub fn names(&self) -> Vec<(DeviceKind, &str)> {
+        self.devices.iter().map(|d| (d.kind, d.name.as_str())).collect()
+    }
+
+    /// Open newly plugged devices (rate-limited) and drop unplugged ones.
+    /// `ctx` is repainted by the device threads whenever input arrives.
+    pub fn update(&mut self, ctx: &egui::Context, spacemouse: bool, gamepad: bool) {
+        self.devices.retain(|d| {
+            let keep = d.alive.load(Ordering::Relaxed)
+                && match d.kind {
+                    DeviceKind::SpaceMouse => spacemouse,
+                    DeviceKind::Gamepad => gamepad,
+                };
+            if !keep {
+                d.alive.store(false, Ordering::Relaxed);
+            }
+            keep
+        });
+        if !spacemouse && !gamepad {
+            self.last_scan = None;
+            return;
+        }
+        if self.last_scan.is_some_and(|t| t.elapsed() < RESCAN_INTERVAL) {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+        self.stop.store(false, Ordering::Relaxed);
+
+        let open: HashSet<PathBuf> = self.devices.iter().map(|d| d.path.clone()).collect();
+        let mut found = Vec::new();
+        if spacemouse {
+            found.extend(find_spacemice().into_iter().map(|(p, n)| (p, n, DeviceKind::SpaceMouse)));
+        }
+        if gamepad {
+            found.extend(find_gamepads().into_iter().map(|(p, n)| (p, n, DeviceKind::Gamepad)));
+        }
+        self.errors.clear();
+        for (path, name, kind) in found.into_iter().filter(|(p, _, _)| !open.contains(p)) {
+            match self.spawn(ctx, &path, &name, kind) {
+                Ok(device) => {
+                    tracing::info!("Navigation device: {} ({})", name, path.display());
+                    self.devices.push(device);
+                }
+                Err(e) => self.errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    fn spawn(&self, ctx: &egui::Context, path: &Path, name: &str, kind: DeviceKind) -> std::io::Result<Device> {
+        let file = std::fs::File::open(path)?;
+        let state = Arc::new(Mutex::new(DeviceState::default()));
+        let alive = Arc::new(AtomicBool::new(true));
+        let (thread_state, thread_alive, stop, ctx) = (state.clone(), alive.clone(), self.stop.clone(), ctx.clone());
+        std::thread::Builder::new()
+            .name(format!("nav-{}", name))
+            .spawn(move || {
+                let keep_going = || thread_alive.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed);
+                let result = match kind {
+                    DeviceKind::SpaceMouse => read_spacemouse(file, &thread_state, &ctx, keep_going),
+                    DeviceKind::Gamepad => read_gamepad(file, &thread_state, &ctx, keep_going),
+                };
+                if let Err(e) = result {
+                    tracing::debug!("Navigation device closed: {}", e);
+                }
+                *thread_state.lock() = DeviceState::default();
+                thread_alive.store(false, Ordering::Relaxed);
+            })?;
+        Ok(Device { name: name.to_string(), kind, path: path.to_path_buf(), state, alive })
+    }
+
+    /// Combined axes of all devices plus button actions since the last poll
+    pub fn poll(&mut self) -> (NavInput, Vec<Action>) {
+        let mut input = NavInput::default();
+        let mut actions = Vec::new();
+        for device in &self.devices {
+            let mut state = device.state.lock();
+            input.accumulate(&state.input);
+            actions.append(&mut state.actions);
+        }
+        input.orbit = input.orbit.clamp(Vec2::splat(-1.0), Vec2::splat(1.0));
+        input.pan = input.pan.clamp(Vec2::splat(-1.0), Vec2::splat(1.0));
+        input.zoom = input.zoom.clamp(-1.0, 1.0);
+        (input, actions)
+    }
+}
+
+impl Drop for NavDevices {
+    fn drop(&mut self) {
+        // Threads blocked in read() exit on their next event
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Dead zone plus squared response for fine control near the center
+fn shape_axis(v: f32) -> f32 {
+    let a = v.abs().min(1.0);
+    if a < DEAD_ZONE {
+        return 0.0;
+    }
+    let t = (a - DEAD_ZONE) / (1.0 - DEAD_ZONE);
+    (t * t).copysign(v)
+}
+
+/// `/dev/hidrawN` nodes of 3Dconnexion devices, with their HID names
+#[cfg(target_os = "linux")]
+fn find_spacemice() -> Vec<(PathBuf, String)> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/hidraw") else { return Vec::new() };
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(uevent) = std::fs::read_to_string(entry.path().join("device/uevent")) else { continue };
+        let mut ids = None;
+        let mut name = String::from("SpaceMouse");
+        for line in uevent.lines() {
+            if let Some(id) = line.strip_prefix("HID_ID=") {
+                // HID_ID=0003:0000046D:0000C626 (bus:vendor:product)
+                let parts: Vec<u32> = id.split(':').filter_map(|p| u32::from_str_radix(p, 16).ok()).collect();
+                if let [_, vendor, product] = parts[..] {
+                    ids = Some((vendor, product));
+                }
+            } else if let Some(n) = line.strip_prefix("HID_NAME=") {
+                name = n.to_string();
+            }
+        }
+        let is_spacemouse = match ids {
+            Some((VENDOR_3DCONNEXION, _)) => true,
+            Some((VENDOR_LOGITECH, product)) => (0xc603..=0xc6ff).contains(&product),
+            _ => false,
+        };
+        if is_spacemouse {
+            out.push((PathBuf::from("/dev").join(entry.file_name()), name));
+        }
+    }
+    out.sort();
+    out
+}
+
+/// `/dev/input/jsN` nodes that are not SpaceMice (those also register as joysticks)
+#[cfg(target_os = "linux")]
+fn find_gamepads() -> Vec<(PathBuf, String)> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/input") else { return Vec::new() };
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        if !file_name.to_string_lossy().starts_with("js") {
+            continue;
+        }
+        let name = std::fs::read_to_string(entry.path().join("device/name"))
+            .map(|n| n.trim().to_string())
+            .unwrap_or_else(|_| "Gamepad".to_string());
+        let lower = name.to_lowercase();
+        if lower.contains("3dconnexion") || lower.contains("space") {
+            continue;
+        }
+        out.push((PathBuf::from("/dev/input").join(file_name), name));
+    }
+    out.sort();
+    out
+}
+
+#[cfg(not(target_os = "linux"))]
+fn find_spacemice() -> Vec<(PathBuf, String)> {
+    Vec::new()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn find_gamepads() -> Vec<(PathBuf, String)> {
+    Vec::new()
+}
+
+/// SpaceMouse HID reports: id 1 = translation (or all six axes on newer
+/// devices), id 2 = rotation, id 3 = button bitmask. Axes are i16 LE in HID
+/// coordinates (x right, y towards the user, z down).
+fn read_spacemouse(
+    mut file: std::fs::File,
+    state: &Mutex<DeviceState>,
+    ctx: &egui::Context,
+    keep_going: impl Fn() -> bool,
+) -> std::io::Result<()> {
+    let mut buf = [0u8; 64];
+    let mut translate = [0.0f32; 3];
+    let mut rotate = [0.0f32; 3];
+    let mut buttons = 0u32;
+    let axes = |bytes: &[u8]| -> [f32; 3] {
+        let v = |i: usize| i16::from_le_bytes([bytes[i * 2], bytes[i * 2 + 1]]) as f32 / SPACEMOUSE_RANGE;
+        [shape_axis(v(0)), shape_axis(v(1)), shape_axis(v(2))]
+    };
+    while keep_going() {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        match (buf[0], n) {
+            (1, 13..) => {
+                translate = axes(&buf[1..7]);
+                rotate = axes(&buf[7..13]);
+            }
+            (1, 7..) => translate = axes(&buf[1..7]),
+            (2, 7..) => rotate = axes(&buf[1..7]),
+            (3, 2..) => {
+                let mut bits = [0u8; 4];
+                let len = (n - 1).min(4);
+                bits[..len].copy_from_slice(&buf[1..1 + len]);
+                let now = u32::from_le_bytes(bits);
+                let pressed = now & !buttons;
+                buttons = now;
+                let mut s = state.lock();
+                // Left button = home, right button = frame all (3Dconnexion "Fit")
+                if pressed & 1 != 0 {
+                    s.actions.push(Action::HomeCamera);
+                }
+                if pressed & 2 != 0 {
+                    s.actions.push(Action::FrameAll);
+                }
+            }
+            _ => continue,
+        }
+        let [tx, ty, tz] = translate;
+        let [rx, _ry, rz] = rotate;
+        state.lock().input = NavInput {
+            // Twist turns around the pivot, tilting forward/back pitches
+            orbit: Vec2::new(rz, rx),
+            // Slide sideways / lift up
+            pan: Vec2::new(tx, -tz),
+            // Push the cap forward to move in
+            zoom: -ty,
+        };
+        ctx.request_repaint();
+    }
+    Ok(())
+}
+
+/// Linux joystick events: `struct js_event { u32 time; i16 value; u8 type; u8 number; }`.
+/// Axis/button numbers follow the xpad layout most gamepads use.
+fn read_gamepad(
+    mut file: std::fs::File,
+    state: &Mutex<DeviceState>,
+    ctx: &egui::Context,
+    keep_going: impl Fn() -> bool,
+) -> std::io::Result<()> {
+    const JS_EVENT_BUTTON: u8 = 0x01;
+    const JS_EVENT_AXIS: u8 = 0x02;
+    const JS_EVENT_INIT: u8 = 0x80;
+
+    let mut event = [0u8; 8];
+    // Triggers rest at -1 and are only valid once they have reported a value
+    let mut axes = [0.0f32; 8];
+    let mut triggers_seen = [false; 2];
+    while keep_going() {
+        file.read_exact(&mut event)?;
+        let value = i16::from_le_bytes([event[4], event[5]]) as f32 / 32767.0;
+        let kind = event[6];
+        let number = event[7] as usize;
+        if kind & JS_EVENT_BUTTON != 0 {
+            if kind & JS_EVENT_INIT != 0 || value == 0.0 {
+                continue;
+            }
+            let action = match number {
+                0 => Some(Action::FocusSelected), // A
+                1 => Some(Action::HomeCamera),    // B
+                3 => Some(Action::FrameAll),      // Y
+                4 => Some(Action::PrevFrame),     // LB
+                5 => Some(Action::NextFrame),     // RB
+                7 => Some(Action::PlayPause),     // Start
+                _ => None,
+            };
+            if let Some(action) = action {
+                state.lock().actions.push(action);
+                ctx.request_repaint();
+            }
+            continue;
+        }
+        if kind & JS_EVENT_AXIS == 0 || number >= axes.len() {
+            continue;
+        }
+        axes[number] = value;
+        match number {
+            2 => triggers_seen[0] = true,
+            5 => triggers_seen[1] = true,
+            _ => {}
+        }
+        let trigger = |i: usize, seen: bool| if seen { shape_axis((axes[i] + 1.0) * 0.5) } else { 0.0 };
+        state.lock().input = NavInput {
+            // Left stick orbits, right stick pans, RT/LT dolly in/out
+            orbit: Vec2::new(shape_axis(axes[0]), shape_axis(axes[1])),
+            pan: Vec2::new(shape_axis(axes[3]), -shape_axis(axes[4])),
+            zoom: trigger(5, triggers_seen[1]) - trigger(2, triggers_seen[0]),
+        };
+        ctx.request_repaint();
+    }
+    Ok(())
+}
@@ -0,0 +1,349 @@
+//! Scene outliner (hierarchy panel)
+//!
+//! The scene tree is flattened once per load into pre-order nodes that know
+//! where their subtree ends. Expanding, collapsing and filtering are then
+//! linear passes over that array, and only the rows on screen are laid out
+//! (`ScrollArea::show_rows`), so the panel stays responsive with hundreds of
+//! thousands of objects.
+//!
+//! The filter is applied as you type: every object whose name matches (with
+//! `*` wildcards) is shown together with its ancestors, expanded. Expansion
+//! while filtering is kept apart from the normal one, which comes back when
+//! the filter is cleared.
+
+use egui::{Color32, RichText};
+
+use super::app::SceneNode;
+use super::selection::Selection;
+
+/// Indent per hierarchy level in points
+const INDENT: f32 = 14.0;
+
+/// One object of the flattened tree
+#[derive(Debug, Clone)]
+pub struct OutlinerNode {
+    pub name: String,
+    pub path: String,
+    pub node_type: String,
+    pub depth: usize,
+    pub parent: Option<usize>,
+    /// One past the last node of this node's subtree
+    pub end: usize,
+    /// Vertex count of the first sample (geometry only)
+    pub verts: Option<usize>,
+    /// Face count of the first sample (meshes only)
+    pub faces: Option<usize>,
+}
+
+impl OutlinerNode {
+    pub fn has_children(&self, index: usize) -> bool {
+        self.end > index + 1
+    }
+}
+
+/// What the user did in the outliner this frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutlinerEvent {
+    /// Click: select `path` (`add` = Ctrl held, toggle in multi-selection)
+    Select { path: String, add: bool },
+    /// Double-click: select `path` and frame it in the viewport
+    Frame(String),
+}
+
+/// Flattened, filterable scene hierarchy
+#[derive(Debug, Default)]
+pub struct Outliner {
+    nodes: Vec<OutlinerNode>,
+    expanded: Vec<bool>,
+    /// Expansion while filtering: starts with the paths to every match and
+    /// is dropped with the filter, so searching doesn't unfold the tree
+    filter_expanded: Vec<bool>,
+    /// Lowercased filter the match flags were computed for
+    filter: String,
+    /// Node name matches the filter
+    hit: Vec<bool>,
+    /// Node or a descendant matches the filter
+    subtree_hit: Vec<bool>,
+    hits: usize,
+    /// Visible node indices, rebuilt when expansion or filter change
+    rows: Vec<usize>,
+    rows_dirty: bool,
+    /// Row to bring into view on the next frame
+    scroll_to: Option<usize>,
+}
+
+impl Outliner {
+    pub fn new(tree: &[SceneNode]) -> Self {
+        let mut nodes = Vec::new();
+        for node in tree {
+            flatten(node, 0, None, &mut nodes);
+        }
+        let n = nodes.len();
+        Self {
+            nodes,
+            expanded: vec![false; n],
+            hit: vec![true; n],
+            subtree_hit: vec![true; n],
+            hits: n,
+            rows_dirty: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Find a node by full path, or by name when `key` has no slash
+    pub fn find(&self, key: &str) -> Option<&OutlinerNode> {
+        if key.starts_with('/') {
+            self.nodes.iter().find(|n| n.path == key)
+        } else {
+            self.nodes.iter().find(|n| n.name == key)
+        }
+    }
+
+    /// Number of objects matching the current filter
+    pub fn hit_count(&self) -> usize {
+        self.hits
+    }
+
+    /// Set the filter (wildcard, case-insensitive); recomputes matches only
+    /// when it changed
+    pub fn set_filter(&mut self, filter: &str) {
+        let filter = filter.trim().to_lowercase();
+        if filter == self.filter {
+            return;
+        }
+        self.filter = filter;
+        for (i, node) in self.nodes.iter().enumerate() {
+            self.hit[i] = matches_filter(&node.name, &self.filter);
+        }
+        self.subtree_hit.copy_from_slice(&self.hit);
+        // Children come after their parent, so one reverse pass propagates up
+        self.filter_expanded = vec![false; self.nodes.len()];
+        for i in (0..self.nodes.len()).rev() {
+            if self.subtree_hit[i] {
+                if let Some(parent) = self.nodes[i].parent {
+                    self.subtree_hit[parent] = true;
+                    self.filter_expanded[parent] = true;
+                }
+            }
+        }
+        self.hits = self.hit.iter().filter(|&&h| h).count();
+        self.rows_dirty = true;
+    }
+
+    fn filtering(&self) -> bool {
+        !self.filter.is_empty()
+    }
+
+    pub fn is_expanded(&self, index: usize) -> bool {
+        if self.filtering() {
+            self.filter_expanded[index]
+        } else {
+            self.expanded[index]
+        }
+    }
+
+    /// Toggle one node, or its whole subtree (`recursive`)
+    pub fn toggle(&mut self, index: usize, recursive: bool) {
+        let expand = !self.is_expanded(index);
+        let end = if recursive { self.nodes[index].end } else { index + 1 };
+        if self.filtering() {
+            self.filter_expanded[index..end].fill(expand);
+        } else {
+            self.expanded[index..end].fill(expand);
+        }
+        self.rows_dirty = true;
+    }
+
+    /// Expand the ancestors of `path` and scroll it into view
+    pub fn reveal(&mut self, path: &str) {
+        let Some(index) = self.nodes.iter().position(|n| n.path == path) else { return };
+        let mut parent = self.nodes[index].parent;
+        while let Some(p) = parent {
+            self.expanded[p] = true;
+            if self.filtering() {
+                self.filter_expanded[p] = true;
+            }
+            parent = self.nodes[p].parent;
+        }
+        self.rows_dirty = true;
+        self.rebuild_rows();
+        self.scroll_to = self.rows.iter().position(|&r| r == index);
+    }
+
+    fn rebuild_rows(&mut self) {
+        if !self.rows_dirty {
+            return;
+        }
+        self.rows.clear();
+        let mut i = 0;
+        while i < self.nodes.len() {
+            if !self.subtree_hit[i] {
+                i = self.nodes[i].end;
+                continue;
+            }
+            self.rows.push(i);
+            i = if self.is_expanded(i) { i + 1 } else { self.nodes[i].end };
+        }
+        self.rows_dirty = false;
+    }
+
+    /// Draw the visible rows; returns the click/double-click of this frame
+    pub fn show(&mut self, ui: &mut egui::Ui, selection: &Selection, selected: Option<&str>) -> Option<OutlinerEvent> {
+        self.rebuild_rows();
+        let row_height = ui.spacing().interact_size.y;
+        let mut area = egui::ScrollArea::vertical().auto_shrink([false, false]);
+        if let Some(row) = self.scroll_to.take() {
+            let spacing = ui.spacing().item_spacing.y;
+            area = area.vertical_scroll_offset((row as f32 * (row_height + spacing) - 3.0 * row_height).max(0.0));
+        }
+
+        let mut event = None;
+        let mut toggle = None;
+        let rows = std::mem::take(&mut self.rows);
+        area.show_rows(ui, row_height, rows.len(), |ui, range| {
+            for &index in &rows[range] {
+                let node = &self.nodes[index];
+                let is_selected = selected == Some(node.path.as_str()) || selection.contains(&node.path);
+                ui.horizontal(|ui| {
+                    ui.add_space(node.depth as f32 * INDENT);
+                    if node.has_children(index) {
+                        let arrow = if self.is_expanded(index) { "▼" } else { "▶" };
+                        if ui.add(egui::Label::new(RichText::new(arrow).small()).sense(egui::Sense::click())).clicked() {
+                            toggle = Some((index, ui.input(|i| i.modifiers.shift)));
+                        }
+                    } else {
+                        ui.add_space(INDENT);
+                    }
+
+                    ui.label(RichText::new(schema_icon(&node.node_type)).color(schema_color(&node.node_type)));
+                    let mut name = RichText::new(&node.name);
+                    if self.filtering() && self.hit[index] {
+                        name = name.color(Color32::YELLOW);
+                    } else if selection.is_hidden(&node.path) {
+                        name = name.color(Color32::DARK_GRAY).italics();
+                    }
+                    let response = ui.selectable_label(is_selected, name)
+                        .on_hover_text(format!("{}\n{}", node.path, node.node_type));
+                    if response.double_clicked() {
+                        event = Some(OutlinerEvent::Frame(node.path.clone()));
+                    } else if response.clicked() {
+                        let add = ui.input(|i| i.modifiers.ctrl);
+                        event = Some(OutlinerEvent::Select { path: node.path.clone(), add });
+                    }
+
+                    if let Some(badge) = count_badge(node.verts, node.faces) {
+                        ui.label(RichText::new(badge).small().weak());
+                    }
+                });
+            }
+        });
+        self.rows = rows;
+
+        if let Some((index, recursive)) = toggle {
+            self.toggle(index, recursive);
+        }
+        event
+    }
+}
+
+fn flatten(node: &SceneNode, depth: usize, parent: Option<usize>, out: &mut Vec<OutlinerNode>) {
+    let index = out.len();
+    out.push(OutlinerNode {
+        name: node.name.clone(),
+        path: node.path.clone(),
+        node_type: node.node_type.clone(),
+        depth,
+        parent,
+        end: 0,
+        verts: node.verts,
+        faces: node.faces,
+    });
+    for child in &node.children {
+        flatten(child, depth + 1, Some(index), out);
+    }
+    out[index].end = out.len();
+}
+
+/// Check if name matches wildcard filter (e.g., "wheel*" matches "wheel_lb").
+/// `filter` must be lowercase; without `*` it is a substring match.
+pub fn matches_filter(name: &str, filter: &str) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+    let name_lower = name.to_lowercase();
+    let parts: Vec<&str> = filter.split('*').collect();
+    if parts.len() == 1 {
+        return name_lower.contains(filter);
+    }
+
+    let mut pos = 0;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        match name_lower[pos..].find(part) {
+            // First part must match at start (no leading *)
+            Some(found) if i == 0 && found != 0 => return false,
+            Some(found) => pos += found + part.len(),
+            None => return false,
+        }
+    }
+    // Without a trailing * the pattern must match to the end
+    filter.ends_with('*') || pos == name_lower.len()
+}
+
+/// Icon per schema type
+pub fn schema_icon(node_type: &str) -> &'static str {
+    match node_type {
+        "PolyMesh" => "▲",
+        "SubD" => "■",
+        "Xform" => "↺",
+        "Camera" => "◎",
+        "Light" => "☀",
+        "Curves" => "∿",
+        "Points" => "•",
+        _ => "○",
+    }
+}
+
+/// Color per schema type, so geometry, transforms, cameras and lights are
+/// told apart at a glance
+pub fn schema_color(node_type: &str) -> Color32 {
+    match node_type {
+        "PolyMesh" => Color32::from_rgb(110, 200, 120),
+        "SubD" => Color32::from_rgb(90, 190, 170),
+        "Xform" => Color32::from_rgb(150, 160, 180),
+        "Camera" => Color32::from_rgb(120, 160, 255),
+        "Light" => Color32::from_rgb(255, 210, 90),
+        "Curves" => Color32::from_rgb(220, 140, 220),
+        "Points" => Color32::from_rgb(240, 150, 90),
+        _ => Color32::GRAY,
+    }
+}
+
+/// Short "12.4k v · 6k f" badge for geometry rows
+pub fn count_badge(verts: Option<usize>, faces: Option<usize>) -> Option<String> {
+    match (verts, faces) {
+        (Some(v), Some(f)) => Some(format!("{} v · {} f", short_count(v), short_count(f))),
+        (Some(v), None) => Some(format!("{} v", short_count(v))),
+        (None, Some(f)) => Some(format!("{} f", short_count(f))),
+        (None, None) => None,
+    }
+}
+
+/// 950, 12.4k, 3.1M
+fn short_count(n: usize) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_949 => format!("{:.1}k", n as f64 / 1e3).replace(".0k", "k"),
+        _ => format!("{:.1}M", n as f64 / 1e6).replace(".0M", "M"),
+    }
+}
@@ -0,0 +1,86 @@
+// Blit shader: copy path tracer output texture to screen.
+// Uses a fullscreen triangle with tone mapping; fs_denoise additionally
+// applies an edge-preserving (bilateral) filter to the accumulated image.
+
+@group(0) @binding(0) var pt_texture: texture_2d<f32>;
+@group(0) @binding(1) var pt_sampler: sampler;
+
+struct TonemapParams {
+    gain: vec3<f32>,  // Exposure x white balance, applied before the curve
+    _pad: f32,
+}
+@group(0) @binding(2) var<uniform> tonemap: TonemapParams;
+
+struct VsOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Fullscreen triangle (3 vertices cover entire screen).
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> VsOut {
+    var out: VsOut;
+    let x = f32(i32(idx & 1u)) * 4.0 - 1.0;
+    let y = f32(i32(idx >> 1u)) * 4.0 - 1.0;
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>((x + 1.0) * 0.5, (1.0 - y) * 0.5);
+    return out;
+}
+
+// ACES filmic tone mapping.
+fn aces_tonemap(color: vec3<f32>) -> vec3<f32> {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return saturate((color * (a * color + b)) / (color * (c * color + d) + e));
+}
+
+@fragment
+fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
+    let color = textureSample(pt_texture, pt_sampler, in.uv);
+
+    // Tone map HDR → LDR
+    let mapped = aces_tonemap(color.rgb * tonemap.gain);
+
+    // Gamma correction (linear → sRGB)
+    let gamma = pow(mapped, vec3<f32>(1.0 / 2.2));
+
+    return vec4<f32>(gamma, 1.0);
+}
+
+// Bilateral filter radius (texels) and color sigma (relative to the center).
+const DENOISE_RADIUS: i32 = 3;
+const DENOISE_SIGMA_COLOR: f32 = 0.25;
+
+@fragment
+fn fs_denoise(in: VsOut) -> @location(0) vec4<f32> {
+    let dims = vec2<i32>(textureDimensions(pt_texture));
+    let center_px = clamp(vec2<i32>(in.uv * vec2<f32>(dims)), vec2<i32>(0), dims - 1);
+    let center = textureLoad(pt_texture, center_px, 0).rgb;
+    let center_luma = dot(center, vec3<f32>(0.2126, 0.7152, 0.0722));
+
+    var sum = vec3<f32>(0.0);
+    var weight_sum = 0.0;
+    let sigma_space = f32(DENOISE_RADIUS) * 0.5;
+    for (var dy = -DENOISE_RADIUS; dy <= DENOISE_RADIUS; dy++) {
+        for (var dx = -DENOISE_RADIUS; dx <= DENOISE_RADIUS; dx++) {
+            let px = clamp(center_px + vec2<i32>(dx, dy), vec2<i32>(0), dims - 1);
+            let c = textureLoad(pt_texture, px, 0).rgb;
+            // Spatial falloff, and color distance relative to the center so
+            // edges between bright and dark regions are kept
+            let d2 = f32(dx * dx + dy * dy);
+            let diff = length(c - center) / (center_luma + 0.05);
+            let w = exp(-d2 / (2.0 * sigma_space * sigma_space))
+                * exp(-(diff * diff) / (2.0 * DENOISE_SIGMA_COLOR * DENOISE_SIGMA_COLOR));
+            sum += c * w;
+            weight_sum += w;
+        }
+    }
+    let color = sum / max(weight_sum, 1e-6);
+
+    let mapped = aces_tonemap(color * tonemap.gain);
+    let gamma = pow(mapped, vec3<f32>(1.0 / 2.2));
+    return vec4<f32>(gamma, 1.0);
+}
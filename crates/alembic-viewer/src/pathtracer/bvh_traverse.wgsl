@@ -57,7 +57,7 @@ struct Camera {
     aperture: f32,              // offset 160, 4 bytes
     focus_distance: f32,        // offset 164, 4 bytes
     global_opacity: f32,        // offset 168, 4 bytes
-    _pad1: u32,                 // offset 172, 4 bytes
+    shadow_catcher_id: u32,     // offset 172, 4 bytes (object_id, 0 = off)
     _pad2: vec4<u32>,           // offset 176, 16 bytes
     // Total: 192 bytes
 };
@@ -658,6 +658,31 @@ fn sky_color(dir: vec3<f32>) -> vec3<f32> {
     }
 }
 
+// Shadow catcher: fraction of the background light that reaches a point
+// on the catcher surface. One cosine-weighted ray estimates sky occlusion
+// (ambient occlusion against the whole scene); with the procedural sky the
+// sun shadow is added on top, as the sun carries most of the light there.
+const SHADOW_CATCHER_SUN_WEIGHT: f32 = 0.7;
+
+fn shadow_catcher_visibility(p: vec3<f32>, normal: vec3<f32>, rng: ptr<function, u32>) -> f32 {
+    var shadow_ray: Ray;
+    shadow_ray.origin = p + normal * 0.001;
+
+    let basis = onb_from_normal(normal);
+    shadow_ray.dir = normalize(basis * cosine_hemisphere(rand(rng), rand(rng)));
+    let sky_vis = select(1.0, 0.0, trace_shadow_ray(shadow_ray, T_MAX));
+    if env.enabled > 0.5 {
+        return sky_vis;
+    }
+
+    shadow_ray.dir = sample_sun_direction(rand(rng), rand(rng));
+    var sun_vis = 0.0;
+    if dot(normal, shadow_ray.dir) > 0.0 && !trace_shadow_ray(shadow_ray, T_MAX) {
+        sun_vis = 1.0;
+    }
+    return mix(sky_vis, sun_vis, SHADOW_CATCHER_SUN_WEIGHT);
+}
+
 // ---- Path tracing kernel ----
 
 @compute @workgroup_size(8, 8)
@@ -711,6 +736,15 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
             normal = -normal;
         }
 
+        // Camera rays hitting the shadow catcher see the background behind
+        // it, darkened where the scene blocks light. Indirect rays treat it
+        // as a regular surface so reflections and bounce light stay intact.
+        if bounce == 0u && camera.shadow_catcher_id != 0u
+            && triangles[hit.tri_idx].object_id == camera.shadow_catcher_id {
+            radiance = sky_color(ray.dir) * shadow_catcher_visibility(p, normal, &rng);
+            break;
+        }
+
         // Unpack material fields (Standard Surface)
         // Global opacity blends between original material and clear glass
         let glass_blend = 1.0 - camera.global_opacity;
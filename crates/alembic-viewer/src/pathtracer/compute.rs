@@ -49,8 +49,8 @@ pub struct PtCameraUniform {
     pub focus_distance: f32,
     /// Global opacity multiplier (0-1). Offset 168, 4 bytes.
     pub global_opacity: f32,
-    /// Padding. Offset 172, 4 bytes.
-    pub _pad1: u32,
+    /// Object ID of the shadow catcher surface (0 = none). Offset 172, 4 bytes.
+    pub shadow_catcher_id: u32,
     /// Final padding. Offset 176, 16 bytes.
     pub _pad2: [u32; 4],
     // Total: 192 bytes
@@ -59,6 +59,15 @@ pub struct PtCameraUniform {
 /// Workgroup size (must match @workgroup_size in WGSL).
 const WG_SIZE: u32 = 8;
 
+/// Tonemap uniform for the blit pass.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct PtTonemapUniform {
+    /// Exposure x white balance gain, applied before the ACES curve
+    gain: [f32; 3],
+    _pad: f32,
+}
+
 /// Environment uniform for path tracer.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
@@ -143,9 +152,14 @@ pub struct PathTraceCompute {
 
     // Blit pipeline (renders PT output to screen with tone mapping)
     blit_pipeline: wgpu::RenderPipeline,
+    /// Blit variant with an edge-preserving denoise filter
+    blit_denoise_pipeline: wgpu::RenderPipeline,
+    /// Blit through the denoise filter
+    pub denoise: bool,
     blit_bind_group_layout: wgpu::BindGroupLayout,
     blit_bind_group: Option<wgpu::BindGroup>,
     blit_sampler: wgpu::Sampler,
+    tonemap_buffer: wgpu::Buffer,
 }
 
 impl PathTraceCompute {
@@ -342,6 +356,16 @@ impl PathTraceCompute {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -351,8 +375,8 @@ impl PathTraceCompute {
             push_constant_ranges: &[],
         });
 
-        let blit_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("pt_blit_pipeline"),
+        let create_blit_pipeline = |label: &str, fs_entry: &str| device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
             layout: Some(&blit_pipeline_layout),
             vertex: wgpu::VertexState {
                 module: &blit_shader,
@@ -362,7 +386,7 @@ impl PathTraceCompute {
             },
             fragment: Some(wgpu::FragmentState {
                 module: &blit_shader,
-                entry_point: Some("fs_main"),
+                entry_point: Some(fs_entry),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: surface_format,
@@ -376,6 +400,8 @@ impl PathTraceCompute {
             multiview: None,
             cache: None,
         });
+        let blit_pipeline = create_blit_pipeline("pt_blit_pipeline", "fs_main");
+        let blit_denoise_pipeline = create_blit_pipeline("pt_blit_denoise_pipeline", "fs_denoise");
 
         let blit_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("pt_blit_sampler"),
@@ -384,6 +410,12 @@ impl PathTraceCompute {
             ..Default::default()
         });
 
+        let tonemap_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("pt_tonemap_uniform"),
+            contents: bytemuck::bytes_of(&PtTonemapUniform { gain: [1.0; 3], _pad: 0.0 }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
         // Pre-build blit bind group
         let blit_bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("pt_blit_bg"),
@@ -397,6 +429,10 @@ impl PathTraceCompute {
                     binding: 1,
                     resource: wgpu::BindingResource::Sampler(&blit_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: tonemap_buffer.as_entire_binding(),
+                },
             ],
         }));
 
@@ -465,9 +501,12 @@ impl PathTraceCompute {
             visibility_buffer,
             max_object_id: 0,
             blit_pipeline,
+            blit_denoise_pipeline,
+            denoise: false,
             blit_bind_group_layout,
             blit_bind_group,
             blit_sampler,
+            tonemap_buffer,
         }
     }
 
@@ -491,7 +530,8 @@ impl PathTraceCompute {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba32Float,
-            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            // COPY_SRC for headless readback (batch rendering)
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
         let view = tex.create_view(&wgpu::TextureViewDescriptor::default());
@@ -665,6 +705,10 @@ impl PathTraceCompute {
                     binding: 1,
                     resource: wgpu::BindingResource::Sampler(&self.blit_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.tonemap_buffer.as_entire_binding(),
+                },
             ],
         }));
     }
@@ -838,6 +882,16 @@ impl PathTraceCompute {
     /// encoder commands are deferred - mixing them in a loop causes all dispatches
     /// to see the final frame_count value.
     pub fn dispatch(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) -> bool {
+        self.dispatch_timed(device, queue, None)
+    }
+
+    /// Like [`dispatch`](Self::dispatch), with optional GPU timestamp writes for the compute pass.
+    pub fn dispatch_timed(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        timestamp_writes: Option<wgpu::ComputePassTimestampWrites<'_>>,
+    ) -> bool {
         let Some(bg) = &self.bind_group else { return false; };
         if !self.scene_ready { return false; }
         if self.frame_count >= self.max_samples { return true; } // converged
@@ -866,7 +920,7 @@ impl PathTraceCompute {
         {
             let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("pt_compute_pass"),
-                timestamp_writes: None,
+                timestamp_writes,
             });
             pass.set_pipeline(&self.pipeline);
             pass.set_bind_group(0, bg, &[]);
@@ -892,9 +946,74 @@ impl PathTraceCompute {
         (self.width, self.height)
     }
 
+    /// Read the accumulated output back as linear RGBA f32, top row first.
+    /// Blocks until the GPU has finished all submitted dispatches.
+    pub fn read_output(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<f32> {
+        let row_bytes = self.width * 16;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row = row_bytes.div_ceil(align) * align;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("pt_readback"),
+            size: (padded_row * self.height) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("pt_readback_encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.output_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(self.height),
+                },
+            },
+            wgpu::Extent3d { width: self.width, height: self.height, depth_or_array_layers: 1 },
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        let _ = device.poll(wgpu::PollType::wait_indefinitely());
+        let mut pixels = Vec::with_capacity((self.width * self.height * 4) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks_exact(padded_row as usize) {
+                pixels.extend(row[..row_bytes as usize].chunks_exact(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])));
+            }
+        }
+        buffer.unmap();
+        pixels
+    }
+
+    /// Set the exposure x white balance gain the blit applies before tone
+    /// mapping. Only affects display, so accumulation is kept.
+    pub fn set_tonemap_gain(&self, queue: &wgpu::Queue, gain: [f32; 3]) {
+        queue.write_buffer(&self.tonemap_buffer, 0, bytemuck::bytes_of(&PtTonemapUniform { gain, _pad: 0.0 }));
+    }
+
     /// Blit the path tracer output to a render target with tone mapping.
     /// Call after dispatch() to display the result.
     pub fn blit(&self, encoder: &mut wgpu::CommandEncoder, target: &wgpu::TextureView) {
+        self.blit_scissored(encoder, target, None);
+    }
+
+    /// Blit limited to `[x, y, width, height]` of the target (split view).
+    pub fn blit_scissored(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        scissor: Option<[u32; 4]>,
+    ) {
         let Some(bg) = &self.blit_bind_group else { return; };
 
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -912,7 +1031,10 @@ impl PathTraceCompute {
             timestamp_writes: None,
             occlusion_query_set: None,
         });
-        pass.set_pipeline(&self.blit_pipeline);
+        if let Some([x, y, w, h]) = scissor {
+            pass.set_scissor_rect(x, y, w, h);
+        }
+        pass.set_pipeline(if self.denoise { &self.blit_denoise_pipeline } else { &self.blit_pipeline });
         pass.set_bind_group(0, bg, &[]);
         pass.draw(0..3, 0..1); // fullscreen triangle
     }
@@ -0,0 +1,109 @@
+//! Render scale and dynamic resolution
+//!
+//! The viewport renders at `render_scale` times its display size and the
+//! renderer resolves the result to display size (Catmull-Rom upscale below
+//! 100%, supersampled downsample above). With dynamic resolution on, the
+//! scale follows the measured frame time towards a target FPS, capped by
+//! the user's render scale, so heavy scenes stay interactive on laptops.
+
+use web_time::Instant;
+
+/// Lowest render scale (fixed or dynamic)
+pub const MIN_SCALE: f32 = 0.5;
+/// Highest render scale (2x supersampling)
+pub const MAX_SCALE: f32 = 2.0;
+
+/// Scale changes are quantized so the render targets are not reallocated
+/// for every small fluctuation
+const SCALE_STEP: f32 = 0.05;
+/// Minimum time between two scale changes
+const ADJUST_INTERVAL_S: f32 = 0.5;
+/// Frame-time smoothing factor (exponential moving average)
+const SMOOTHING: f32 = 0.2;
+/// Frame intervals longer than this are idle gaps, not render cost
+const IDLE_FRAME_MS: f32 = 1000.0;
+
+/// Frame-time driven render scale controller
+pub struct DynamicResolution {
+    scale: f32,
+    avg_frame_ms: Option<f32>,
+    last_change: Instant,
+}
+
+impl DynamicResolution {
+    pub fn new(scale: f32) -> Self {
+        Self {
+            scale: quantize(scale),
+            avg_frame_ms: None,
+            last_change: Instant::now(),
+        }
+    }
+
+    /// Current dynamic scale
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Jump to `scale` and forget the timing history (e.g. when toggled on)
+    pub fn reset(&mut self, scale: f32) {
+        self.scale = quantize(scale);
+        self.avg_frame_ms = None;
+        self.last_change = Instant::now();
+    }
+
+    /// Feed the last frame's cost in ms and get the scale for the next frame.
+    ///
+    /// Pixel cost grows with the square of the scale, so the scale moves by
+    /// the square root of the budget ratio; steps are limited to keep the
+    /// image from pumping.
+    pub fn update(&mut self, frame_ms: f32, target_fps: f32, max_scale: f32) -> f32 {
+        let max_scale = max_scale.clamp(MIN_SCALE, MAX_SCALE);
+        if self.scale > max_scale {
+            self.reset(max_scale);
+            return self.scale;
+        }
+        if !(frame_ms > 0.0 && frame_ms < IDLE_FRAME_MS) || target_fps <= 0.0 {
+            return self.scale;
+        }
+
+        let avg = match self.avg_frame_ms {
+            Some(avg) => avg + (frame_ms - avg) * SMOOTHING,
+            None => frame_ms,
+        };
+        self.avg_frame_ms = Some(avg);
+        if self.last_change.elapsed().as_secs_f32() < ADJUST_INTERVAL_S {
+            return self.scale;
+        }
+
+        let budget_ms = 1000.0 / target_fps;
+        let ideal = self.scale * (budget_ms / avg).sqrt();
+        let next = if avg > budget_ms * 1.05 {
+            ideal.max(self.scale * 0.75)
+        } else if avg < budget_ms * 0.75 {
+            ideal.min(self.scale * 1.1)
+        } else {
+            self.scale
+        };
+        let next = quantize(next.clamp(MIN_SCALE, max_scale));
+        if next != self.scale {
+            self.scale = next;
+            // Timings measured at the old resolution no longer apply
+            self.avg_frame_ms = None;
+            self.last_change = Instant::now();
+        }
+        self.scale
+    }
+}
+
+/// Round to the scale step
+fn quantize(scale: f32) -> f32 {
+    ((scale / SCALE_STEP).round() * SCALE_STEP).clamp(MIN_SCALE, MAX_SCALE)
+}
+
+/// Render target size for a display size at `scale`
+pub fn scaled_size(width: u32, height: u32, scale: f32) -> (u32, u32) {
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
@@ -0,0 +1,181 @@
+//! Ground plane grid.
+//!
+//! A single fullscreen pass intersects each pixel's camera ray with the
+//! y = 0 plane and draws minor/major lines and the X/Z axes analytically,
+//! anti-aliased with screen-space derivatives and faded with distance. The
+//! hit point's depth is written so the grid is occluded by geometry; only
+//! a uniform changes when the camera moves.
+
+use glam::{Mat4, Vec3};
+
+use super::Renderer;
+
+/// Minor cells per major cell
+const MAJOR_EVERY: f32 = 10.0;
+
+/// Grid pipeline and its uniform
+pub struct GridPipeline {
+    pub pipeline: wgpu::RenderPipeline,
+    pub bind_group: wgpu::BindGroup,
+    pub params_buffer: wgpu::Buffer,
+}
+
+/// Grid parameters (must match WGSL struct)
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GridParams {
+    pub view_proj: [[f32; 4]; 4],
+    pub inv_view_proj: [[f32; 4]; 4],
+    pub camera_pos: [f32; 3],
+    pub step: f32,
+    pub fade_distance: f32,
+    pub major_every: f32,
+    pub _pad: [f32; 2],
+}
+
+/// Create the grid pipeline (alpha blended, depth tested, no depth write)
+pub fn create_grid_pipeline(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> GridPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("grid_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/grid.wgsl").into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("grid_bind_group_layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("grid_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("grid_pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],  // Fullscreen triangle, no vertex buffer
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth32Float,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("grid_params_buffer"),
+        size: std::mem::size_of::<GridParams>() as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("grid_bind_group"),
+        layout: &bind_group_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: params_buffer.as_entire_binding(),
+        }],
+    });
+
+    GridPipeline { pipeline, bind_group, params_buffer }
+}
+
+impl Renderer {
+    /// Keep the camera the grid rays are cast from
+    pub(super) fn set_grid_camera(&mut self, view_proj: Mat4, position: Vec3) {
+        self.grid_view_proj = view_proj;
+        self.grid_camera_pos = position;
+    }
+
+    /// Pick the grid step for the camera distance (Lightwave-style: a power
+    /// of 10 of the scene unit) and upload the grid uniform.
+    pub(super) fn update_grid(&mut self, camera_distance: f32) {
+        // e.g. distance 5 -> step 1, distance 50 -> step 10, distance 0.5 -> step 0.1
+        let unit = if self.grid_unit > 0.0 { self.grid_unit } else { 1.0 };
+        let step = match self.grid_step_override {
+            Some(step) if step > 0.0 => step,
+            _ => unit * 10.0_f32.powf((camera_distance / unit).max(1e-6).log10().floor()),
+        };
+
+        // Fade out past a few major cells, or further when the camera is high up
+        let fade_distance = (step * MAJOR_EVERY * 2.0).max(camera_distance * 4.0);
+        let params = GridParams {
+            view_proj: self.grid_view_proj.to_cols_array_2d(),
+            inv_view_proj: self.grid_view_proj.inverse().to_cols_array_2d(),
+            camera_pos: self.grid_camera_pos.to_array(),
+            step,
+            fade_distance,
+            major_every: MAJOR_EVERY,
+            _pad: [0.0; 2],
+        };
+        self.queue.write_buffer(&self.grid_pipeline.params_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
+    /// Draw the grid over `color_view`, depth tested against the scene
+    pub(super) fn render_grid_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth_view: &wgpu::TextureView,
+        color_view: &wgpu::TextureView,
+    ) {
+        if !self.show_grid {
+            return;
+        }
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("grid_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.grid_pipeline.pipeline);
+        pass.set_bind_group(0, &self.grid_pipeline.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+        self.profiler.draw_stats.record(1);
+    }
+}
@@ -0,0 +1,397 @@
+//! Depth of field and bloom preview.
+//!
+//! Shaded color is rendered into an intermediate target. Bloom runs a bright
+//! pass into a half-resolution buffer and blurs it separably; the composite
+//! pass gathers the color over each pixel's thin-lens circle of confusion
+//! (from the depth buffer and the lens of the camera looked through) and adds
+//! the bloom on top.
+
+use glam::Mat4;
+
+use super::Renderer;
+
+const BLOOM_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Thin lens driving the depth of field
+#[derive(Debug, Clone, Copy)]
+pub struct DofLens {
+    /// Focal length in mm
+    pub focal_length: f32,
+    pub f_stop: f32,
+    /// Focus plane distance in scene units
+    pub focus_distance: f32,
+    /// Film back height in mm
+    pub film_height: f32,
+    /// Scene units per meter, to bring the focal length into scene units
+    pub units_per_meter: f32,
+}
+
+impl Default for DofLens {
+    fn default() -> Self {
+        Self { focal_length: 50.0, f_stop: 2.8, focus_distance: 5.0, film_height: 24.0, units_per_meter: 1.0 }
+    }
+}
+
+impl DofLens {
+    /// Circle of confusion in pixels for a point at infinity; the CoC at
+    /// distance `z` is `coc_scale * |z - focus| / z`. 0 disables the blur.
+    pub fn coc_scale(&self, viewport_height: f32) -> f32 {
+        let focal_units = self.focal_length * 0.001 * self.units_per_meter;
+        if !(self.f_stop > 0.0 && self.film_height > 0.0 && self.focus_distance > focal_units) {
+            return 0.0;
+        }
+        let aperture = self.focal_length / self.f_stop;  // mm
+        let coc_mm = aperture * focal_units / (self.focus_distance - focal_units);
+        coc_mm / self.film_height * viewport_height
+    }
+}
+
+/// Pipelines and shared resources for the lens effects
+pub struct LensFxPipeline {
+    pub bright_pipeline: wgpu::RenderPipeline,
+    pub blur_h_pipeline: wgpu::RenderPipeline,
+    pub blur_v_pipeline: wgpu::RenderPipeline,
+    pub composite_pipeline: wgpu::RenderPipeline,
+    pub bloom_bind_group_layout: wgpu::BindGroupLayout,
+    pub composite_bind_group_layout: wgpu::BindGroupLayout,
+    pub sampler: wgpu::Sampler,
+    pub params_buffer: wgpu::Buffer,
+}
+
+/// Lens effects parameters (must match WGSL struct)
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LensFxParams {
+    pub inv_proj: [[f32; 4]; 4],
+    pub viewport_size: [f32; 2],
+    pub focus_distance: f32,
+    pub coc_scale: f32,
+    pub max_coc_px: f32,
+    pub dof_samples: u32,
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    pub dof_enabled: u32,
+    pub bloom_enabled: u32,
+    pub _pad: [f32; 2],
+}
+
+/// Scene color the effects read from, and the half-resolution bloom chain
+pub struct LensTargets {
+    #[allow(dead_code)]
+    pub color: wgpu::Texture,
+    pub color_view: wgpu::TextureView,
+    #[allow(dead_code)]
+    pub bloom: [wgpu::Texture; 2],
+    pub bloom_views: [wgpu::TextureView; 2],
+    /// Bright pass (color -> bloom 0), H blur (bloom 0 -> 1), V blur (bloom 1 -> 0)
+    pub bloom_bind_groups: [wgpu::BindGroup; 3],
+    pub composite_bind_group: wgpu::BindGroup,
+    pub size: (u32, u32),
+}
+
+pub(super) fn fullscreen_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    entry_point: &str,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],  // Fullscreen triangle, no vertex buffer
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(entry_point),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
+
+pub(super) fn texture_entry(binding: u32, sample_type: wgpu::TextureSampleType) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            sample_type,
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    }
+}
+
+pub(super) fn sampler_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+        count: None,
+    }
+}
+
+fn uniform_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Create the bloom and composite pipelines
+pub fn create_lens_fx_pipeline(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> LensFxPipeline {
+    let filterable = wgpu::TextureSampleType::Float { filterable: true };
+
+    let bloom_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("bloom_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/bloom.wgsl").into()),
+    });
+    let bloom_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("bloom_bind_group_layout"),
+        entries: &[
+            texture_entry(0, filterable),  // Source
+            sampler_entry(1),
+            uniform_entry(2),              // Params
+        ],
+    });
+    let bloom_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("bloom_pipeline_layout"),
+        bind_group_layouts: &[&bloom_bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let bright_pipeline =
+        fullscreen_pipeline(device, "bloom_bright_pipeline", &bloom_layout, &bloom_shader, "fs_bright", BLOOM_FORMAT);
+    let blur_h_pipeline =
+        fullscreen_pipeline(device, "bloom_blur_h_pipeline", &bloom_layout, &bloom_shader, "fs_blur_h", BLOOM_FORMAT);
+    let blur_v_pipeline =
+        fullscreen_pipeline(device, "bloom_blur_v_pipeline", &bloom_layout, &bloom_shader, "fs_blur_v", BLOOM_FORMAT);
+
+    let composite_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("lens_composite_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/lens_composite.wgsl").into()),
+    });
+    let composite_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("lens_composite_bind_group_layout"),
+        entries: &[
+            texture_entry(0, filterable),                        // Scene color
+            texture_entry(1, wgpu::TextureSampleType::Depth),    // Depth
+            texture_entry(2, filterable),                        // Bloom
+            sampler_entry(3),
+            uniform_entry(4),                                    // Params
+        ],
+    });
+    let composite_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("lens_composite_pipeline_layout"),
+        bind_group_layouts: &[&composite_bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let composite_pipeline = fullscreen_pipeline(
+        device,
+        "lens_composite_pipeline",
+        &composite_layout,
+        &composite_shader,
+        "fs_main",
+        surface_format,
+    );
+
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("lens_fx_sampler"),
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+
+    let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("lens_fx_params_buffer"),
+        size: std::mem::size_of::<LensFxParams>() as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    LensFxPipeline {
+        bright_pipeline,
+        blur_h_pipeline,
+        blur_v_pipeline,
+        composite_pipeline,
+        bloom_bind_group_layout,
+        composite_bind_group_layout,
+        sampler,
+        params_buffer,
+    }
+}
+
+impl Renderer {
+    /// Whether the lens effects post passes run this frame
+    pub(super) fn lens_fx_active(&self) -> bool {
+        self.dof || (self.bloom && self.bloom_intensity > 0.0)
+    }
+
+    /// Keep the inverse projection the DoF pass reconstructs view depth with
+    pub(super) fn set_lens_projection(&mut self, view_proj: Mat4, view: Mat4) {
+        self.lens_inv_proj = (view_proj * view.inverse()).inverse();
+    }
+
+    /// (Re)create the lens targets; also needed after the depth buffer is
+    /// recreated, since the composite bind group samples it.
+    pub(super) fn ensure_lens_targets(&mut self, width: u32, height: u32) {
+        let needs_recreate = match &self.lens_targets {
+            Some(t) => t.size != (width, height),
+            None => true,
+        };
+        let Some(depth) = &self.depth_texture else {
+            return;
+        };
+        if !needs_recreate || width == 0 || height == 0 {
+            return;
+        }
+
+        let texture = |label: &str, width: u32, height: u32, format: wgpu::TextureFormat| {
+            self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+        };
+        let color = texture("lens_scene_color", width, height, self.surface_format);
+        let (half_w, half_h) = ((width / 2).max(1), (height / 2).max(1));
+        let bloom = [
+            texture("lens_bloom_0", half_w, half_h, BLOOM_FORMAT),
+            texture("lens_bloom_1", half_w, half_h, BLOOM_FORMAT),
+        ];
+        let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
+        let bloom_views = [
+            bloom[0].create_view(&wgpu::TextureViewDescriptor::default()),
+            bloom[1].create_view(&wgpu::TextureViewDescriptor::default()),
+        ];
+
+        let pipeline = &self.lens_fx_pipeline;
+        let bloom_bind_group = |label: &str, source: &wgpu::TextureView| {
+            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &pipeline.bloom_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(source) },
+                    wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&pipeline.sampler) },
+                    wgpu::BindGroupEntry { binding: 2, resource: pipeline.params_buffer.as_entire_binding() },
+                ],
+            })
+        };
+        let bloom_bind_groups = [
+            bloom_bind_group("bloom_bright_bind_group", &color_view),
+            bloom_bind_group("bloom_blur_h_bind_group", &bloom_views[0]),
+            bloom_bind_group("bloom_blur_v_bind_group", &bloom_views[1]),
+        ];
+        let composite_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("lens_composite_bind_group"),
+            layout: &pipeline.composite_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&color_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&depth.view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(&bloom_views[0]) },
+                wgpu::BindGroupEntry { binding: 3, resource: wgpu::BindingResource::Sampler(&pipeline.sampler) },
+                wgpu::BindGroupEntry { binding: 4, resource: pipeline.params_buffer.as_entire_binding() },
+            ],
+        });
+
+        self.lens_targets = Some(LensTargets {
+            color,
+            color_view,
+            bloom,
+            bloom_views,
+            bloom_bind_groups,
+            composite_bind_group,
+            size: (width, height),
+        });
+    }
+
+    /// Bloom chain at half resolution, then DoF + bloom composite into `view`
+    pub(super) fn render_lens_fx_passes(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        width: u32,
+        height: u32,
+    ) {
+        let Some(targets) = &self.lens_targets else {
+            return;
+        };
+        let coc_scale = self.dof_lens.coc_scale(height as f32);
+        let bloom = self.bloom && self.bloom_intensity > 0.0;
+        let params = LensFxParams {
+            inv_proj: self.lens_inv_proj.to_cols_array_2d(),
+            viewport_size: [width as f32, height as f32],
+            focus_distance: self.dof_lens.focus_distance,
+            coc_scale,
+            max_coc_px: self.dof_max_blur_px,
+            dof_samples: 32,
+            bloom_threshold: self.bloom_threshold,
+            bloom_intensity: self.bloom_intensity,
+            dof_enabled: (self.dof && coc_scale > 0.0) as u32,
+            bloom_enabled: bloom as u32,
+            _pad: [0.0; 2],
+        };
+        self.queue.write_buffer(&self.lens_fx_pipeline.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let mut fullscreen_pass = |label: &str, pipeline: &wgpu::RenderPipeline, bind_group: &wgpu::BindGroup, target: &wgpu::TextureView| {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);  // Fullscreen triangle
+            self.profiler.draw_stats.record(1);
+        };
+
+        let pipeline = &self.lens_fx_pipeline;
+        if bloom {
+            let [bright, blur_h, blur_v] = &targets.bloom_bind_groups;
+            fullscreen_pass("bloom_bright_pass", &pipeline.bright_pipeline, bright, &targets.bloom_views[0]);
+            fullscreen_pass("bloom_blur_h_pass", &pipeline.blur_h_pipeline, blur_h, &targets.bloom_views[1]);
+            fullscreen_pass("bloom_blur_v_pass", &pipeline.blur_v_pipeline, blur_v, &targets.bloom_views[0]);
+        }
+        fullscreen_pass("lens_composite_pass", &pipeline.composite_pipeline, &targets.composite_bind_group, view);
+    }
+}
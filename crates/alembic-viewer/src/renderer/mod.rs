@@ -10,10 +10,25 @@ mod shaders;
 mod postfx;
 mod passes;
 mod pipelines;
+mod profiler;
+mod motion_blur;
+mod lens_fx;
+mod grid;
+mod overlays;
+mod upscale;
 
 use resources::{DepthTexture, GBuffer, LightingParams, ObjectIdTexture, SsaoBlurParams, SsaoParams, SsaoTargets};
 use postfx::{create_postfx_pipelines, PostFxPipelines};
 use pipelines::{create_pipelines, create_hover_pipeline, HoverParams, HoverPipeline, Pipelines};
+use profiler::{GpuPass, GpuProfiler};
+use motion_blur::{create_motion_blur_pipeline, MeshMotion, MotionBlurPipeline, MotionTargets};
+use lens_fx::{create_lens_fx_pipeline, LensFxPipeline, LensTargets};
+use grid::{create_grid_pipeline, GridPipeline};
+use overlays::SceneOverlay;
+use upscale::{create_upscale_pipeline, UpscalePipeline};
+pub use overlays::{Overlay, OverlayHandle};
+pub use lens_fx::DofLens;
+pub use profiler::FrameStats;
 
 use standard_surface::{
     BindGroupLayouts, CameraUniform, LightRig, ModelUniform,
@@ -21,6 +36,7 @@ use standard_surface::{
 };
 
 use super::environment::{self, EnvironmentMap, EnvUniform};
+use super::exposure::Exposure;
 use super::smooth_normals::SmoothNormalData;
 
 /// Shadow map resolution
@@ -78,13 +94,16 @@ pub struct Renderer {
     shadow_bind_group: wgpu::BindGroup,
     shadow_pass_bind_group: wgpu::BindGroup,
     
+    // Render scale resolve (scaled frame -> display size)
+    upscale_pipeline: UpscalePipeline,
+    
     // Grid
-    grid_mesh: Option<Mesh>,
-    grid_material: wgpu::BindGroup,
-    grid_model: wgpu::BindGroup,
-    #[allow(dead_code)]
-    grid_model_buffer: wgpu::Buffer,
-    grid_step: f32, // current grid step for adaptive sizing
+    grid_pipeline: GridPipeline,
+    grid_view_proj: Mat4,
+    grid_camera_pos: Vec3,
+
+    // Custom line/point overlays by key (tools, plugins)
+    overlays: HashMap<String, SceneOverlay>,
     
     // Environment map
     env_map: EnvironmentMap,
@@ -108,6 +127,10 @@ pub struct Renderer {
     pub show_wireframe: bool,
     pub flat_shading: bool,
     pub show_grid: bool,
+    /// Scene units per meter; adaptive grid steps are 10^n of this
+    pub grid_unit: f32,
+    /// Fixed grid step in scene units (None = adaptive)
+    pub grid_step_override: Option<f32>,
     pub show_shadows: bool,
     pub use_ssao: bool,
     pub ssao_strength: f32,
@@ -136,13 +159,13 @@ pub struct Renderer {
     pub pt_camera_snap: bool,  // Snap camera at target FPS intervals
     last_render_time_ms: f32,  // Actual render time (not including wait)
     pt_batch_rendering: bool,  // True during multi-sample batch (skip camera updates)
-    pt_camera_snap_time: std::time::Instant,  // When PT camera was last snapped
+    pt_camera_snap_time: web_time::Instant,  // When PT camera was last snapped
     pt_snap_ready: bool,  // True when it's time to dispatch new samples (snap interval reached)
-    pt_last_dispatch_time: std::time::Instant,  // When PT last dispatched samples (for FPS limiting)
+    pt_last_dispatch_time: web_time::Instant,  // When PT last dispatched samples (for FPS limiting)
     /// Samples dispatched in last second (for samples/sec display)
     pub pt_samples_last_sec: u32,
     pt_samples_counter: u32,  // Running counter for current second
-    pt_samples_sec_start: std::time::Instant,  // Start of current second
+    pt_samples_sec_start: web_time::Instant,  // Start of current second
     // Snapped camera state (used for PT when snap is enabled)
     pt_snapped_view_proj: Option<glam::Mat4>,
     pt_snapped_view: Option<glam::Mat4>,
@@ -159,6 +182,16 @@ pub struct Renderer {
     pub pt_focus_point: Option<glam::Vec3>,
     /// Global opacity multiplier for all PT materials (0-1)
     pub pt_global_opacity: f32,
+    /// Floor acts as a shadow catcher (shows background, keeps shadows)
+    pub pt_shadow_catcher: bool,
+    /// Split view: path tracer left of the divider, rasterizer right of it
+    pub pt_split_view: bool,
+    /// Blit the path tracer output through the denoise filter
+    pub pt_denoise: bool,
+    /// Path tracer resolution relative to the viewport (0.25-1)
+    pub pt_resolution_scale: f32,
+    /// Divider position as a fraction of the viewport width
+    pub pt_split_position: f32,
     /// Surface format needed for path tracer blit pipeline creation.
     #[allow(dead_code)]
     surface_format: wgpu::TextureFormat,
@@ -176,6 +209,39 @@ pub struct Renderer {
     pending_hover_pick: Option<(u32, u32)>,  // Pixel to read for hover detection
     mesh_id_map: HashMap<u32, String>,       // Map object ID -> mesh path
     next_object_id: u32,                     // Counter for assigning object IDs
+
+    // Motion blur preview (velocity buffer + gather post pass)
+    pub motion_blur: bool,
+    /// Shutter angle in degrees (180 = half-frame exposure)
+    pub motion_blur_shutter: f32,
+    pub motion_blur_samples: u32,
+    /// Frames per second used to turn stored `.velocities` into per-frame motion
+    pub motion_blur_fps: f32,
+    motion_blur_pipeline: MotionBlurPipeline,
+    motion_targets: Option<MotionTargets>,
+    motion_blur_bind_group: Option<wgpu::BindGroup>,
+    motion_consecutive: bool,  // Current animation step follows the displayed sample
+
+    // Lens effects preview (depth of field + bloom post passes)
+    pub dof: bool,
+    /// Lens of the camera looked through (scene camera, or the orbit camera's virtual lens)
+    pub dof_lens: DofLens,
+    /// Circle of confusion clamp in pixels
+    pub dof_max_blur_px: f32,
+    pub bloom: bool,
+    /// Brightness (0-1 display range) above which highlights bloom
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    lens_fx_pipeline: LensFxPipeline,
+    lens_targets: Option<LensTargets>,
+    lens_inv_proj: Mat4,
+
+    /// Exposure (auto from the HDR environment + EV compensation) and white
+    /// balance, applied in the tonemap stage
+    pub exposure: Exposure,
+
+    // Per-pass GPU timings + draw counters (performance overlay)
+    profiler: GpuProfiler,
 }
 
 /// GPU mesh data
@@ -183,6 +249,7 @@ pub struct Mesh {
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
     index_count: u32,
+    vertex_count: u32,
     vertex_buffer_size: usize,
     index_buffer_size: usize,
 }
@@ -209,6 +276,23 @@ pub struct SceneMesh {
     pub base_indices: Option<Vec<u32>>,      // face indices for path tracer
     pub material_params: StandardSurfaceParams, // CPU-side material for path tracer
     pub smooth_dirty: bool,
+    /// Previous sample for the motion blur velocity pass (None = static)
+    pub motion: Option<MeshMotion>,
+    /// Per-FaceSet materials (index range, material); empty = one draw with
+    /// `material_bind_group`. Rasterizer only.
+    pub face_set_groups: Vec<(std::ops::Range<u32>, wgpu::BindGroup)>,
+}
+
+impl SceneMesh {
+    /// Material bind group and index range of each draw call.
+    pub fn material_draws(&self) -> Vec<(&wgpu::BindGroup, std::ops::Range<u32>)> {
+        if self.face_set_groups.is_empty() {
+            return vec![(&self.material_bind_group, 0..self.mesh.index_count)];
+        }
+        self.face_set_groups.iter()
+            .map(|(range, group)| (group, range.start..range.end.min(self.mesh.index_count)))
+            .collect()
+    }
 }
 
 /// Compute a content hash for mesh data (vertices + indices).
@@ -362,6 +446,8 @@ pub struct SceneCurves {
     pub base_vertices: Option<Vec<Vertex>>,
     pub base_indices: Option<Vec<u32>>,
     pub material_params: StandardSurfaceParams,
+    /// Per-object visibility (skipped in draw calls and PT ribbons when false)
+    pub visible: bool,
 }
 
 /// Scene points with transform and material
@@ -382,6 +468,8 @@ pub struct ScenePoints {
     /// Per-point widths (radius) for point sprites (not yet used in rendering)
     #[allow(dead_code)]
     pub widths: Vec<f32>,
+    /// Per-object visibility (skipped in draw calls when false)
+    pub visible: bool,
 }
 
 impl Renderer {
@@ -426,7 +514,8 @@ impl Renderer {
             background: self::DEFAULT_BACKGROUND_COLOR,
             hdr_visible: 1.0,
             _pad0: [0.0; 3],
-            _pad1: [0.0; 4],
+            exposure: [1.0; 3],
+            _pad1: 0.0,
         };
         let lighting_params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("lighting_params_buffer"),
@@ -576,33 +665,6 @@ impl Renderer {
             }],
         });
 
-        // Grid material (gray diffuse)
-        let mut grid_params = StandardSurfaceParams::default();
-        grid_params.set_base_color(Vec3::splat(0.3));
-        grid_params.set_specular(0.1);
-        let grid_material_buffer = standard_surface::create_material_buffer(&device, &grid_params);
-        let grid_material = standard_surface::create_material_bind_group(
-            &device,
-            &layouts.material,
-            &grid_material_buffer,
-        );
-
-        // Grid model transform
-        let grid_model_uniform = ModelUniform::default();
-        let grid_model_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("grid_model_buffer"),
-            contents: bytemuck::bytes_of(&grid_model_uniform),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-        let grid_model = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("grid_model_bind_group"),
-            layout: &layouts.model,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: grid_model_buffer.as_entire_binding(),
-            }],
-        });
-
         // Default environment map (disabled)
         let env_map = environment::create_default_env(&device, &queue, &layouts.environment);
         let env_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -622,6 +684,11 @@ impl Renderer {
         });
         
         let hover_pipeline = create_hover_pipeline(&device, format);
+        let motion_blur_pipeline = create_motion_blur_pipeline(&device, &layouts, format);
+        let lens_fx_pipeline = create_lens_fx_pipeline(&device, format);
+        let grid_pipeline = create_grid_pipeline(&device, format);
+        let upscale_pipeline = create_upscale_pipeline(&device, format);
+        let profiler = GpuProfiler::new(&device, &queue);
         
         Self {
             device,
@@ -656,11 +723,11 @@ impl Renderer {
             shadow_uniform_buffer,
             shadow_bind_group,
             shadow_pass_bind_group,
-            grid_mesh: None,
-            grid_material,
-            grid_model,
-            grid_model_buffer,
-            grid_step: 0.0, // will be set on first render
+            grid_pipeline,
+            upscale_pipeline,
+            grid_view_proj: Mat4::IDENTITY,
+            grid_camera_pos: Vec3::ZERO,
+            overlays: HashMap::new(),
             env_map,
             env_uniform_buffer,
             floor_mesh: None,
@@ -670,6 +737,8 @@ impl Renderer {
             show_wireframe: false,
             flat_shading: false,
             show_grid: true,
+            grid_unit: 1.0,
+            grid_step_override: None,
             show_shadows: true,
             use_ssao: false,
             ssao_strength: 0.5,
@@ -692,12 +761,12 @@ impl Renderer {
             pt_camera_snap: true,
             last_render_time_ms: 10.0,  // Conservative initial estimate
             pt_batch_rendering: false,
-            pt_camera_snap_time: std::time::Instant::now(),
+            pt_camera_snap_time: web_time::Instant::now(),
             pt_snap_ready: true,
-            pt_last_dispatch_time: std::time::Instant::now(),
+            pt_last_dispatch_time: web_time::Instant::now(),
             pt_samples_last_sec: 0,
             pt_samples_counter: 0,
-            pt_samples_sec_start: std::time::Instant::now(),
+            pt_samples_sec_start: web_time::Instant::now(),
             pt_snapped_view_proj: None,
             pt_snapped_view: None,
             pt_snapped_position: None,
@@ -707,6 +776,11 @@ impl Renderer {
             pt_focus_distance: 10.0,
             pt_focus_point: None,
             pt_global_opacity: 1.0,
+            pt_shadow_catcher: false,
+            pt_split_view: false,
+            pt_denoise: false,
+            pt_resolution_scale: 1.0,
+            pt_split_position: 0.5,
             surface_format: format,
             object_id_texture: None,
             object_id_pick_buffer,
@@ -720,6 +794,25 @@ impl Renderer {
             pending_hover_pick: None,
             mesh_id_map: HashMap::new(),
             next_object_id: 1,  // 0 is reserved for background
+            motion_blur: false,
+            motion_blur_shutter: 180.0,
+            motion_blur_samples: 12,
+            motion_blur_fps: 24.0,
+            motion_blur_pipeline,
+            motion_targets: None,
+            motion_blur_bind_group: None,
+            motion_consecutive: false,
+            dof: false,
+            dof_lens: DofLens::default(),
+            dof_max_blur_px: 24.0,
+            bloom: false,
+            bloom_threshold: 0.8,
+            bloom_intensity: 0.5,
+            lens_fx_pipeline,
+            lens_targets: None,
+            lens_inv_proj: Mat4::IDENTITY,
+            exposure: Exposure::default(),
+            profiler,
         }
     }
 
@@ -739,6 +832,8 @@ impl Renderer {
         };
         self.queue.write_buffer(&self.camera_buffer, 0, bytemuck::bytes_of(&uniform));
         self.camera_position = position;
+        self.set_lens_projection(view_proj, view);
+        self.set_grid_camera(view_proj, position);
 
         // Recompute focus distance from world-space focus point
         if let Some(fp) = self.pt_focus_point {
@@ -781,7 +876,7 @@ impl Renderer {
                 self.pt_snapped_view_proj = Some(view_proj);
                 self.pt_snapped_view = Some(view);
                 self.pt_snapped_position = Some(position);
-                self.pt_camera_snap_time = std::time::Instant::now();
+                self.pt_camera_snap_time = web_time::Instant::now();
                 self.pt_snap_ready = true;
             } else {
                 // Not time for new snap yet - keep using old snapped camera
@@ -801,6 +896,12 @@ impl Renderer {
             (view_proj, view, position)
         };
 
+        // Object IDs start at 1, so 0 leaves the catcher off in the shader
+        let shadow_catcher_id = match &self.floor_mesh {
+            Some(floor) if self.pt_shadow_catcher && floor.visible => floor.object_id,
+            _ => 0,
+        };
+
         if let Some(pt) = &mut self.path_tracer {
             let inv_view = pt_view.inverse();
             let proj = pt_view_proj * pt_view.inverse();
@@ -835,7 +936,7 @@ impl Renderer {
                 aperture: self.pt_aperture,
                 focus_distance: self.pt_focus_distance,
                 global_opacity: self.pt_global_opacity,
-                _pad1: 0,
+                shadow_catcher_id,
                 _pad2: [0; 4],
             };
             pt.update_camera(&self.queue, &cam);
@@ -860,11 +961,31 @@ impl Renderer {
         }
     }
 
+    /// Path tracer and rasterizer shown side by side this frame
+    pub fn pt_split_active(&self) -> bool {
+        self.use_path_tracing && self.pt_split_view && self.path_tracer.is_some()
+    }
+
+    /// Blit the path tracer result over the left part of the raster frame
+    fn render_pt_split(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, width: u32, height: u32) {
+        let Some(pt) = &self.path_tracer else { return };
+        let split = (width as f32 * self.pt_split_position.clamp(0.0, 1.0)).round() as u32;
+        let split = split.clamp(1, width.max(1));
+        pt.blit_scissored(encoder, view, Some([0, 0, split, height.max(1)]));
+    }
+
+    /// Whether the device runs compute shaders with storage buffers, which
+    /// the path tracer needs (not on WebGL2).
+    pub fn supports_path_tracing(&self) -> bool {
+        let limits = self.device.limits();
+        limits.max_compute_workgroups_per_dimension > 0 && limits.max_storage_buffers_per_shader_stage > 0
+    }
+
     /// Initialize the path tracer compute pipeline (lazy, on first toggle).
     /// Called from the UI when the user enables path tracing mode.
     #[allow(dead_code)]
     pub fn init_path_tracer(&mut self, width: u32, height: u32) {
-        if self.path_tracer.is_some() {
+        if self.path_tracer.is_some() || !self.supports_path_tracing() {
             return;
         }
         self.path_tracer = Some(super::pathtracer::PathTraceCompute::new(
@@ -928,7 +1049,7 @@ impl Renderer {
         }
 
         // Curves (ribbon quads)
-        for curve in self.curves.values() {
+        for curve in self.curves.values().filter(|c| c.visible) {
             if let (Some(verts), Some(indices)) = (&curve.base_vertices, &curve.base_indices) {
                 let mat_id = materials.len() as u32;
                 materials.push(scene_convert::material_from_params(&curve.material_params));
@@ -982,6 +1103,38 @@ impl Renderer {
         }
     }
     
+    /// Show or hide a scene object (mesh, curves or points) by path.
+    /// Mesh visibility is synced to the PT visibility buffer immediately; curve
+    /// ribbons follow on the next PT scene upload. Returns false if no object
+    /// has that path.
+    pub fn set_object_visible(&mut self, path: &str, visible: bool) -> bool {
+        if let Some(mesh) = self.meshes.get_mut(path) {
+            if mesh.visible != visible {
+                mesh.visible = visible;
+                let id = mesh.object_id;
+                self.set_pt_object_visible(id, visible);
+            }
+            return true;
+        }
+        if let Some(curves) = self.curves.get_mut(path) {
+            curves.visible = visible;
+            return true;
+        }
+        if let Some(points) = self.points.get_mut(path) {
+            points.visible = visible;
+            return true;
+        }
+        false
+    }
+
+    /// Paths of all meshes, curves and points in the scene
+    pub fn object_paths(&self) -> impl Iterator<Item = &str> {
+        self.meshes.keys()
+            .chain(self.curves.keys())
+            .chain(self.points.keys())
+            .map(String::as_str)
+    }
+
     /// Update frame time for auto SPP calculation (deprecated - now measured internally)
     pub fn update_frame_time(&mut self, _frame_time_ms: f32) {
         // Now measured internally in render() for accuracy
@@ -996,12 +1149,23 @@ impl Renderer {
     /// Update lights from scene lights
     /// Takes up to 3 scene lights and maps them to key/fill/rim
     pub fn set_scene_lights(&self, scene_lights: &[super::mesh_converter::SceneLight]) {
+        use alembic::geom::LightType;
         use standard_surface::Light;
         
         let make_light = |sl: &super::mesh_converter::SceneLight| -> Light {
-            // SceneLight direction points where light goes, shader expects direction toward source
-            Light::new(-sl.direction, sl.color, sl.intensity)
+            // SceneLight direction points where light goes, shader expects direction toward source.
+            // Point lights have no axis: light the scene from where they sit.
+            let to_source = match sl.light_type {
+                LightType::Point => (sl.position - self.scene_center).normalize_or(-sl.direction),
+                _ => -sl.direction,
+            };
+            Light::new(to_source, sl.color, sl.intensity)
         };
+        // Dome lights are covered by the environment, not the directional rig
+        let scene_lights: Vec<_> = scene_lights.iter()
+            .filter(|sl| sl.light_type != LightType::Dome)
+            .cloned()
+            .collect();
         
         let rig = match scene_lights.len() {
             0 => LightRig::three_point(), // fallback to default
@@ -1104,6 +1268,8 @@ impl Renderer {
                 view,
                 size: (width, height),
             });
+            // The lens composite samples the old depth texture
+            self.lens_targets = None;
         }
     }
     
@@ -1593,65 +1759,6 @@ impl Renderer {
         self.postfx_bind_groups_dirty = false;
     }
 
-    /// Update grid mesh based on camera distance (Lightwave-style adaptive grid)
-    pub fn update_grid(&mut self, camera_distance: f32) {
-        // Calculate grid step as power of 10 based on camera distance
-        // e.g. distance 5 -> step 1, distance 50 -> step 10, distance 0.5 -> step 0.1
-        let log_dist = camera_distance.max(0.01).log10();
-        let step = 10.0_f32.powf(log_dist.floor());
-        
-        // Only rebuild if step changed
-        if (step - self.grid_step).abs() < 0.001 && self.grid_mesh.is_some() {
-            return;
-        }
-        self.grid_step = step;
-        
-        // Grid covers area proportional to camera distance
-        let half_size = step * 10.0; // 10 major divisions visible
-        let divisions = 20; // 20 lines each direction
-        
-        let mut vertices = Vec::new();
-        let mut indices = Vec::new();
-
-        // Create grid as LineList - two vertices per line
-        for i in 0..=divisions {
-            let t = i as f32 / divisions as f32;
-            let pos = -half_size + t * half_size * 2.0;
-            let idx = vertices.len() as u32;
-            
-            // X-axis line (parallel to X)
-            vertices.push(Vertex { position: [-half_size, 0.0, pos], normal: [0.0, 1.0, 0.0], uv: [0.0, 0.0] });
-            vertices.push(Vertex { position: [half_size, 0.0, pos], normal: [0.0, 1.0, 0.0], uv: [1.0, 0.0] });
-            indices.extend_from_slice(&[idx, idx + 1]);
-            
-            // Z-axis line (parallel to Z)
-            let idx = vertices.len() as u32;
-            vertices.push(Vertex { position: [pos, 0.0, -half_size], normal: [0.0, 1.0, 0.0], uv: [0.0, 0.0] });
-            vertices.push(Vertex { position: [pos, 0.0, half_size], normal: [0.0, 1.0, 0.0], uv: [0.0, 1.0] });
-            indices.extend_from_slice(&[idx, idx + 1]);
-        }
-
-        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("grid_vertex_buffer"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("grid_index_buffer"),
-            contents: bytemuck::cast_slice(&indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-
-        self.grid_mesh = Some(Mesh {
-            vertex_buffer,
-            index_buffer,
-            index_count: indices.len() as u32,
-            vertex_buffer_size: std::mem::size_of::<Vertex>() * vertices.len(),
-            index_buffer_size: std::mem::size_of::<u32>() * indices.len(),
-        });
-    }
-
     /// Create a mesh from vertices and indices
     pub fn create_mesh(
         device: &wgpu::Device,
@@ -1676,6 +1783,7 @@ impl Renderer {
             vertex_buffer,
             index_buffer,
             index_count: indices.len() as u32,
+            vertex_count: vertices.len() as u32,
             vertex_buffer_size,
             index_buffer_size,
         }
@@ -1748,6 +1856,8 @@ impl Renderer {
             material_params: params.clone(),
             smooth_dirty: true,
             visible: true,
+            motion: None,
+            face_set_groups: Vec::new(),
         });
     }
 
@@ -1808,6 +1918,7 @@ impl Renderer {
             base_vertices: Some(vertices.to_vec()),
             base_indices: Some(indices.to_vec()),
             material_params: params.clone(),
+            visible: true,
         });
     }
 
@@ -1884,6 +1995,7 @@ impl Renderer {
             data_hash,
             name,
             widths: widths.to_vec(),
+            visible: true,
         });
     }
 
@@ -1941,6 +2053,10 @@ impl Renderer {
     /// Returns true if mesh was found and updated
     pub fn update_mesh_transform(&mut self, name: &str, transform: Mat4) -> bool {
         if let Some(scene_mesh) = self.meshes.get_mut(name) {
+            if self.motion_blur {
+                let prev = if self.motion_consecutive { scene_mesh.transform } else { transform };
+                Self::record_prev_transform(&self.device, &self.queue, &self.layouts.model, scene_mesh, prev);
+            }
             scene_mesh.transform = transform;
             let normal_matrix = transform.inverse().transpose();
             let model_uniform = ModelUniform {
@@ -1965,14 +2081,21 @@ impl Renderer {
     /// Update vertex data for existing mesh (for deforming animation)
     /// Returns true if mesh was found and updated
     /// Update mesh GPU buffers with pre-computed hash and bounds from worker thread.
+    /// `velocities` (per vertex, units/second) feed the motion blur preview.
     pub fn update_mesh_vertices(
         &mut self, name: &str, vertices: &[Vertex], indices: &[u32],
-        data_hash: u64, bounds: (Vec3, Vec3),
+        velocities: Option<&[[f32; 3]]>, data_hash: u64, bounds: (Vec3, Vec3),
     ) -> bool {
         let scene_mesh = match self.meshes.get_mut(name) {
             Some(m) => m,
             None => return false,
         };
+        if self.motion_blur {
+            Self::record_prev_vertices(
+                &self.device, scene_mesh, vertices, velocities,
+                self.motion_blur_fps, self.motion_consecutive,
+            );
+        }
         let vertex_bytes = bytemuck::cast_slice(vertices);
         let index_bytes = bytemuck::cast_slice(indices);
         if vertex_bytes.len() <= scene_mesh.mesh.vertex_buffer_size
@@ -1981,6 +2104,7 @@ impl Renderer {
             self.queue.write_buffer(&scene_mesh.mesh.vertex_buffer, 0, vertex_bytes);
             self.queue.write_buffer(&scene_mesh.mesh.index_buffer, 0, index_bytes);
             scene_mesh.mesh.index_count = indices.len() as u32;
+            scene_mesh.mesh.vertex_count = vertices.len() as u32;
         } else {
             let new_mesh = Self::create_mesh(&self.device, vertices, indices);
             scene_mesh.mesh = new_mesh;
@@ -1996,6 +2120,19 @@ impl Renderer {
         true
     }
     
+    /// Draw a mesh with one material per index range (e.g. per FaceSet);
+    /// an empty list goes back to its single material.
+    pub fn set_mesh_face_set_materials(&mut self, name: &str, groups: &[(std::ops::Range<u32>, StandardSurfaceParams)]) {
+        let Some(scene_mesh) = self.meshes.get_mut(name) else { return };
+        scene_mesh.face_set_groups = groups.iter()
+            .map(|(range, params)| {
+                let buffer = standard_surface::create_material_buffer(&self.device, params);
+                let group = standard_surface::create_material_bind_group(&self.device, &self.layouts.material, &buffer);
+                (range.clone(), group)
+            })
+            .collect();
+    }
+
     /// Check if mesh exists
     pub fn has_mesh(&self, name: &str) -> bool {
         self.meshes.contains_key(name)
@@ -2032,6 +2169,7 @@ impl Renderer {
                     usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
                 });
                 scene_mesh.mesh.vertex_buffer = vertex_buffer;
+                scene_mesh.mesh.vertex_count = new_vertices.len() as u32;
                 scene_mesh.smooth_dirty = false;
             }
         }
@@ -2076,6 +2214,7 @@ impl Renderer {
             self.queue.write_buffer(&curves.mesh.vertex_buffer, 0, vertex_bytes);
             self.queue.write_buffer(&curves.mesh.index_buffer, 0, index_bytes);
             curves.mesh.index_count = indices.len() as u32;
+            curves.mesh.vertex_count = vertices.len() as u32;
         } else {
             let new_mesh = Self::create_mesh(&self.device, vertices, indices);
             curves.mesh = new_mesh;
@@ -2226,6 +2365,21 @@ impl Renderer {
         }
     }
     
+    /// Exposure in stops currently applied (auto + compensation)
+    pub fn exposure_ev(&self) -> f32 {
+        self.exposure.ev(self.env_exposure_key())
+    }
+
+    /// Exposure x white balance gain for the tonemap stage
+    fn exposure_gain(&self) -> Vec3 {
+        self.exposure.gain(self.env_exposure_key())
+    }
+
+    /// Metered environment luminance as displayed (scaled by its intensity)
+    fn env_exposure_key(&self) -> Option<f32> {
+        self.env_map.exposure_key.map(|key| key * self.env_map.intensity)
+    }
+    
     /// Set floor plane based on scene bounds (call when checkbox enabled)
     pub fn set_floor(&mut self, bounds: &Option<super::mesh_converter::Bounds>) {
         // Get scene size and floor Y position
@@ -2303,6 +2457,7 @@ impl Renderer {
                 vertex_buffer,
                 index_buffer,
                 index_count: indices.len() as u32,
+                vertex_count: vertices.len() as u32,
                 vertex_buffer_size: std::mem::size_of::<Vertex>() * vertices.len(),
                 index_buffer_size: std::mem::size_of::<u32>() * indices.len(),
             },
@@ -2320,19 +2475,41 @@ impl Renderer {
             material_params: material,
             smooth_dirty: false,
             visible: true,
+            motion: None,
+            face_set_groups: Vec::new(),
         });
     }
     
 
     /// Render the scene
+    /// Draw counters and smoothed per-pass GPU timings of the last frame.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.profiler.stats()
+    }
+
+    /// Whether per-pass GPU timings are available (TIMESTAMP_QUERY supported).
+    pub fn gpu_timing_supported(&self) -> bool {
+        self.profiler.timing_supported()
+    }
+
     pub fn render(&mut self, view: &wgpu::TextureView, width: u32, height: u32, camera_distance: f32, _near: f32, _far: f32) {
-        let render_start = std::time::Instant::now();
-        
+        let render_start = web_time::Instant::now();
+        self.profiler.begin_frame(&self.device);
+        let pt_split = self.pt_split_active();
+        if let Some(pt) = &self.path_tracer {
+            pt.set_tonemap_gain(&self.queue, self.exposure_gain().to_array());
+        }
+
         // Path tracing mode: dispatch compute shader and blit to screen
+        // (split view: blitted over the raster frame further down instead)
         if self.use_path_tracing {
             if let Some(pt) = &mut self.path_tracer {
-                pt.resize(&self.device, width, height);
+                let scale = self.pt_resolution_scale.clamp(0.25, 1.0);
+                let pt_width = ((width as f32 * scale).round() as u32).max(1);
+                let pt_height = ((height as f32 * scale).round() as u32).max(1);
+                pt.resize(&self.device, pt_width, pt_height);
                 pt.max_samples = self.pt_max_samples;
+                pt.denoise = self.pt_denoise;
                 
                 let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
                     label: Some("pt_encoder"),
@@ -2366,14 +2543,15 @@ impl Renderer {
                     self.pt_batch_rendering = true;
 
                     // Measure actual render time
-                    let render_start = std::time::Instant::now();
+                    let render_start = web_time::Instant::now();
 
                     // Multiple samples per frame for target FPS control
                     // Each dispatch creates and submits its own encoder to ensure
                     // frame_count is synchronized (write_buffer is immediate, dispatch is deferred)
                     let mut samples_dispatched = 0u32;
                     for i in 0..samples_this_frame {
-                        if !pt.dispatch(&self.device, &self.queue) {
+                        let timestamps = self.profiler.compute_pass_writes(GpuPass::PathTrace);
+                        if !pt.dispatch_timed(&self.device, &self.queue, timestamps) {
                             tracing::debug!("PT dispatch loop: break at sample {}/{}", i, samples_this_frame);
                             break; // Scene not ready or converged
                         }
@@ -2385,7 +2563,7 @@ impl Renderer {
                     if self.pt_samples_sec_start.elapsed().as_secs_f32() >= 1.0 {
                         self.pt_samples_last_sec = self.pt_samples_counter;
                         self.pt_samples_counter = 0;
-                        self.pt_samples_sec_start = std::time::Instant::now();
+                        self.pt_samples_sec_start = web_time::Instant::now();
                     }
 
                     // Update render time estimate (EMA)
@@ -2400,27 +2578,34 @@ impl Renderer {
                     // Unlock camera after batch
                     self.pt_batch_rendering = false;
                 }
-                // Always blit (shows accumulated result even between snap intervals)
+                // Split view: the raster path below records the frame and the PT half
+                if !pt_split {
+                    // Always blit (shows accumulated result even between snap intervals)
                 
-                pt.blit(&mut encoder, view);
+                    pt.blit(&mut encoder, view);
                 
-                // Add hover overlay in PT mode if enabled
-                if self.hover_mode != super::settings::HoverMode::None {
-                    self.ensure_depth_texture(width, height);
-                    self.ensure_object_id_texture(width, height);
+                    // Add hover overlay in PT mode if enabled
+                    if self.hover_mode != super::settings::HoverMode::None {
+                        self.ensure_depth_texture(width, height);
+                        self.ensure_object_id_texture(width, height);
                     
-                    if let Some(dt) = &self.depth_texture {
-                        let depth_view = dt.view.clone();
-                        // Render object IDs with depth clear (PT doesn't have depth buffer)
-                        self.render_object_id_pass(&mut encoder, &depth_view, true);
-                        self.process_hover_pick(&mut encoder);
-                        self.render_hover_pass(&mut encoder, view, width, height);
+                        if let Some(dt) = &self.depth_texture {
+                            let depth_view = dt.view.clone();
+                            // Render object IDs with depth clear (PT doesn't have depth buffer)
+                            self.render_object_id_pass(&mut encoder, &depth_view, true);
+                            self.process_hover_pick(&mut encoder);
+                            self.render_hover_pass(&mut encoder, view, width, height);
+                        }
                     }
-                }
                 
-                self.queue.submit(std::iter::once(encoder.finish()));
+                    self.profiler.resolve(&mut encoder);
+                    self.queue.submit(std::iter::once(encoder.finish()));
+                    self.profiler.end_frame();
+                }
+            }
+            if !pt_split {
+                return;
             }
-            return;
         }
 
         self.ensure_depth_texture(width, height);
@@ -2467,10 +2652,34 @@ impl Renderer {
                 None,
                 opaque_depth_load,
             );
+            self.render_grid_pass(&mut encoder, &depth_view, color_target_view_ref);
+            self.render_overlay_pass(&mut encoder, &depth_view, color_target_view_ref);
+            self.profiler.resolve(&mut encoder);
             self.queue.submit(std::iter::once(encoder.finish()));
+            self.profiler.end_frame();
             return;
         }
 
+        // Lens effects (DoF, bloom) run last: everything before renders into their input
+        let lens_fx = self.lens_fx_active();
+        if lens_fx {
+            self.ensure_lens_targets(width, height);
+        }
+        let lens_color_view = self.lens_targets.as_ref()
+            .filter(|_| lens_fx)
+            .map(|t| t.color_view.clone());
+        let post_view = lens_color_view.as_ref().unwrap_or(view);
+
+        // Motion blur: shade into an intermediate target, gather into `post_view`
+        let motion_blur = self.motion_blur_active();
+        if motion_blur {
+            self.ensure_motion_targets(width, height);
+        }
+        let motion_color_view = self.motion_targets.as_ref()
+            .filter(|_| motion_blur)
+            .map(|t| t.color_view.clone());
+        let color_target_view_ref = motion_color_view.as_ref().unwrap_or(post_view);
+
         let opacity_threshold = 0.999;
         let mut opaque_mesh_names: Vec<String> = Vec::new();
         let mut transparent_meshes: Vec<(f32, String)> = Vec::new();
@@ -2515,6 +2724,9 @@ impl Renderer {
             if use_gbuffer {
                 self.render_gbuffer_pass(&mut encoder, &depth_view, &opaque_meshes);
             }
+            if motion_blur {
+                self.render_velocity_pass(&mut encoder, &depth_view, &opaque_meshes);
+            }
         }
 
         if use_gbuffer {
@@ -2545,7 +2757,8 @@ impl Renderer {
                 background: self.background_color,
                 hdr_visible: if self.hdr_visible { 1.0 } else { 0.0 },
                 _pad0: [0.0; 3],
-                _pad1: [0.0; 4],
+                exposure: self.exposure_gain().to_array(),
+                _pad1: 0.0,
             };
             self.queue.write_buffer(
                 &self.lighting_params_buffer,
@@ -2554,6 +2767,7 @@ impl Renderer {
             );
             self.render_lighting_pass(&mut encoder, color_target_view_ref);
         }
+        self.render_grid_pass(&mut encoder, &depth_view, color_target_view_ref);
 
         if !transparent_meshes.is_empty()
             || floor_transparent_distance.is_some()
@@ -2588,15 +2802,28 @@ impl Renderer {
                 transparent_pipeline,
             );
         }
+        self.render_overlay_pass(&mut encoder, &depth_view, color_target_view_ref);
+
+        if motion_color_view.is_some() {
+            self.render_motion_blur_pass(&mut encoder, post_view, width, height);
+        }
+        if lens_color_view.is_some() {
+            self.render_lens_fx_passes(&mut encoder, view, width, height);
+        }
+        if pt_split {
+            self.render_pt_split(&mut encoder, view, width, height);
+        }
 
         // Object ID pass for hover detection (reuse depth from main pass)
         if self.hover_mode != super::settings::HoverMode::None {
             self.render_object_id_pass(&mut encoder, &depth_view, false);
             self.process_hover_pick(&mut encoder);
-            self.render_hover_pass(&mut encoder, color_target_view_ref, width, height);
+            self.render_hover_pass(&mut encoder, view, width, height);
         }
 
+        self.profiler.resolve(&mut encoder);
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.profiler.end_frame();
         
         // Poll for hover pick result if we did a pick this frame
         if self.hover_mode != super::settings::HoverMode::None && self.pending_hover_pick.is_none() {
@@ -0,0 +1,543 @@
+//! Velocity-buffer motion blur preview.
+//!
+//! Opaque meshes are drawn a second time into an `Rg16Float` velocity buffer
+//! using their previous positions (the last displayed sample, or `P - v / fps`
+//! when the cache stores `.velocities`) and previous transform. A fullscreen
+//! pass then gathers the lit scene color along each pixel's velocity, scaled
+//! by the shutter angle.
+
+use glam::Mat4;
+use standard_surface::{BindGroupLayouts, ModelUniform, Vertex};
+use wgpu::util::DeviceExt;
+
+use super::{GpuPass, Renderer, SceneMesh};
+
+const VELOCITY_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg16Float;
+
+/// Pipelines and shared resources for the motion blur preview
+pub struct MotionBlurPipeline {
+    pub velocity_pipeline: wgpu::RenderPipeline,
+    pub velocity_pipeline_double_sided: wgpu::RenderPipeline,
+    pub blur_pipeline: wgpu::RenderPipeline,
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    pub sampler: wgpu::Sampler,
+    pub params_buffer: wgpu::Buffer,
+}
+
+/// Motion blur parameters (must match WGSL struct)
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MotionBlurParams {
+    pub shutter: f32,
+    pub samples: u32,
+    pub max_blur_px: f32,
+    pub _pad0: f32,
+    pub viewport_size: [f32; 2],
+    pub _pad1: [f32; 2],
+}
+
+/// Velocity buffer and intermediate scene color the blur reads from
+pub struct MotionTargets {
+    #[allow(dead_code)]
+    pub velocity: wgpu::Texture,
+    pub velocity_view: wgpu::TextureView,
+    #[allow(dead_code)]
+    pub color: wgpu::Texture,
+    pub color_view: wgpu::TextureView,
+    pub size: (u32, u32),
+}
+
+/// Previous-sample state of a scene mesh
+pub struct MeshMotion {
+    /// Vertices of the previous sample (same layout and count as the current ones)
+    pub prev_vertices: Option<wgpu::Buffer>,
+    /// Whether `prev_vertices` belongs to the current animation step
+    pub deformed: bool,
+    pub prev_model_buffer: wgpu::Buffer,
+    pub prev_model_bind_group: wgpu::BindGroup,
+}
+
+impl MeshMotion {
+    fn new(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, transform: Mat4, object_id: u32) -> Self {
+        let prev_model_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("prev_model_buffer"),
+            contents: bytemuck::bytes_of(&model_uniform(transform, object_id)),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let prev_model_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("prev_model_bind_group"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: prev_model_buffer.as_entire_binding(),
+            }],
+        });
+        Self {
+            prev_vertices: None,
+            deformed: false,
+            prev_model_buffer,
+            prev_model_bind_group,
+        }
+    }
+}
+
+fn model_uniform(transform: Mat4, object_id: u32) -> ModelUniform {
+    ModelUniform {
+        model: transform.to_cols_array_2d(),
+        normal_matrix: transform.inverse().transpose().to_cols_array_2d(),
+        object_id,
+        _pad: [0; 3],
+    }
+}
+
+/// Vertex layout of the previous-position stream (slot 1, location 3)
+fn prev_vertex_buffer_layout() -> wgpu::VertexBufferLayout<'static> {
+    wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &[wgpu::VertexAttribute {
+            offset: 0,
+            shader_location: 3,
+            format: wgpu::VertexFormat::Float32x3,
+        }],
+    }
+}
+
+/// Create the velocity and blur pipelines
+pub fn create_motion_blur_pipeline(
+    device: &wgpu::Device,
+    layouts: &BindGroupLayouts,
+    surface_format: wgpu::TextureFormat,
+) -> MotionBlurPipeline {
+    let velocity_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("velocity_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/velocity.wgsl").into()),
+    });
+    let velocity_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("velocity_pipeline_layout"),
+        bind_group_layouts: &[
+            &layouts.camera_light,  // group 0: camera
+            &layouts.model,         // group 1: current model
+            &layouts.model,         // group 2: previous model
+        ],
+        push_constant_ranges: &[],
+    });
+    let velocity_pipeline_for = |double_sided: bool| {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(if double_sided { "velocity_pipeline_double_sided" } else { "velocity_pipeline" }),
+            layout: Some(&velocity_layout),
+            vertex: wgpu::VertexState {
+                module: &velocity_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[standard_surface::vertex_buffer_layout(), prev_vertex_buffer_layout()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &velocity_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: VELOCITY_FORMAT,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: if double_sided { None } else { Some(wgpu::Face::Back) },
+                ..Default::default()
+            },
+            // Depth already laid down by the G-Buffer pass: keep nearest surfaces only
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        })
+    };
+    let velocity_pipeline = velocity_pipeline_for(false);
+    let velocity_pipeline_double_sided = velocity_pipeline_for(true);
+
+    let blur_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("motion_blur_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/motion_blur.wgsl").into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("motion_blur_bind_group_layout"),
+        entries: &[
+            // Scene color
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            // Velocity
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            // Color sampler
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            // Params uniform
+            wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+    let blur_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("motion_blur_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let blur_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("motion_blur_pipeline"),
+        layout: Some(&blur_layout),
+        vertex: wgpu::VertexState {
+            module: &blur_shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],  // Fullscreen triangle, no vertex buffer
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &blur_shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("motion_blur_sampler"),
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+
+    let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("motion_blur_params_buffer"),
+        size: std::mem::size_of::<MotionBlurParams>() as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    MotionBlurPipeline {
+        velocity_pipeline,
+        velocity_pipeline_double_sided,
+        blur_pipeline,
+        bind_group_layout,
+        sampler,
+        params_buffer,
+    }
+}
+
+impl Renderer {
+    /// Start a new animation step before meshes are updated for a new sample.
+    /// `consecutive` is true when the new sample directly follows the one on
+    /// screen; otherwise (scrubbing, file load) frame-to-frame motion is reset.
+    pub fn begin_motion_step(&mut self, consecutive: bool) {
+        self.motion_consecutive = consecutive;
+        for scene_mesh in self.meshes.values_mut() {
+            if let Some(motion) = &mut scene_mesh.motion {
+                motion.deformed = false;
+            }
+        }
+    }
+
+    /// Whether the motion blur post pass runs this frame
+    pub(super) fn motion_blur_active(&self) -> bool {
+        self.motion_blur && self.motion_blur_shutter > 0.0 && self.motion_blur_samples > 1
+    }
+
+    /// Record the outgoing transform of `scene_mesh` as its previous transform.
+    pub(super) fn record_prev_transform(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &wgpu::BindGroupLayout,
+        scene_mesh: &mut SceneMesh,
+        prev: Mat4,
+    ) {
+        let object_id = scene_mesh.object_id;
+        match &mut scene_mesh.motion {
+            Some(motion) => queue.write_buffer(
+                &motion.prev_model_buffer,
+                0,
+                bytemuck::bytes_of(&model_uniform(prev, object_id)),
+            ),
+            None => scene_mesh.motion = Some(MeshMotion::new(device, layout, prev, object_id)),
+        }
+    }
+
+    /// Keep the previous vertices of `scene_mesh` before `vertices` replace them.
+    ///
+    /// Stored velocities win (they survive topology changes); otherwise the
+    /// outgoing vertex buffer is kept when the new sample directly follows it
+    /// with the same vertex count. The caller writes `vertices` into the
+    /// (possibly swapped) current buffer afterwards.
+    pub(super) fn record_prev_vertices(
+        device: &wgpu::Device,
+        scene_mesh: &mut SceneMesh,
+        vertices: &[Vertex],
+        velocities: Option<&[[f32; 3]]>,
+        fps: f32,
+        consecutive: bool,
+    ) {
+        let Some(motion) = &mut scene_mesh.motion else {
+            return;
+        };
+        motion.deformed = true;
+        if let Some(velocities) = velocities.filter(|v| v.len() == vertices.len()) {
+            let dt = 1.0 / fps.max(1.0);
+            let prev: Vec<Vertex> = vertices
+                .iter()
+                .zip(velocities)
+                .map(|(v, vel)| Vertex {
+                    position: [
+                        v.position[0] - vel[0] * dt,
+                        v.position[1] - vel[1] * dt,
+                        v.position[2] - vel[2] * dt,
+                    ],
+                    ..*v
+                })
+                .collect();
+            motion.prev_vertices = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("mesh_prev_vertex_buffer"),
+                contents: bytemuck::cast_slice(&prev),
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            }));
+        } else if consecutive && scene_mesh.mesh.vertex_count as usize == vertices.len() {
+            // Swap instead of copy: the outgoing buffer becomes the previous one
+            let size = scene_mesh.mesh.vertex_buffer.size();
+            let next = motion
+                .prev_vertices
+                .take()
+                .filter(|b| b.size() == size)
+                .unwrap_or_else(|| {
+                    device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("mesh_vertex_buffer"),
+                        size,
+                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    })
+                });
+            motion.prev_vertices = Some(std::mem::replace(&mut scene_mesh.mesh.vertex_buffer, next));
+        } else {
+            motion.prev_vertices = None;
+            motion.deformed = false;
+        }
+    }
+
+    pub(super) fn ensure_motion_targets(&mut self, width: u32, height: u32) {
+        let needs_recreate = match &self.motion_targets {
+            Some(t) => t.size != (width, height),
+            None => true,
+        };
+
+        if needs_recreate && width > 0 && height > 0 {
+            let size = wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            };
+            let velocity = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("motion_velocity"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: VELOCITY_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            let color = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("motion_scene_color"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.surface_format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            let velocity_view = velocity.create_view(&wgpu::TextureViewDescriptor::default());
+            let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
+
+            self.motion_blur_bind_group = Some(self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("motion_blur_bind_group"),
+                layout: &self.motion_blur_pipeline.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&color_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&velocity_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(&self.motion_blur_pipeline.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: self.motion_blur_pipeline.params_buffer.as_entire_binding(),
+                    },
+                ],
+            }));
+            self.motion_targets = Some(MotionTargets {
+                velocity,
+                velocity_view,
+                color,
+                color_view,
+                size: (width, height),
+            });
+        }
+    }
+
+    /// Draw opaque meshes into the velocity buffer (depth-tested, no depth write)
+    pub(super) fn render_velocity_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth_view: &wgpu::TextureView,
+        meshes: &[&SceneMesh],
+    ) {
+        let Some(targets) = &self.motion_targets else {
+            return;
+        };
+        let mut velocity_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("velocity_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &targets.velocity_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: self.profiler.render_pass_writes(GpuPass::Velocity),
+            occlusion_query_set: None,
+        });
+
+        velocity_pass.set_pipeline(if self.double_sided {
+            &self.motion_blur_pipeline.velocity_pipeline_double_sided
+        } else {
+            &self.motion_blur_pipeline.velocity_pipeline
+        });
+        velocity_pass.set_bind_group(0, &self.camera_light_bind_group, &[]);
+
+        for mesh in meshes {
+            // Meshes without motion state (e.g. the floor) are static
+            let (prev_vertices, prev_model) = match &mesh.motion {
+                Some(motion) => (
+                    motion
+                        .prev_vertices
+                        .as_ref()
+                        .filter(|_| motion.deformed)
+                        .unwrap_or(&mesh.mesh.vertex_buffer),
+                    &motion.prev_model_bind_group,
+                ),
+                None => (&mesh.mesh.vertex_buffer, &mesh.model_bind_group),
+            };
+            velocity_pass.set_bind_group(1, &mesh.model_bind_group, &[]);
+            velocity_pass.set_bind_group(2, prev_model, &[]);
+            velocity_pass.set_vertex_buffer(0, mesh.mesh.vertex_buffer.slice(..));
+            velocity_pass.set_vertex_buffer(1, prev_vertices.slice(..));
+            velocity_pass.set_index_buffer(mesh.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            velocity_pass.draw_indexed(0..mesh.mesh.index_count, 0, 0..1);
+            self.profiler.draw_stats.record((mesh.mesh.index_count / 3) as u64);
+        }
+    }
+
+    /// Gather the scene color along velocity into the final target
+    pub(super) fn render_motion_blur_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        width: u32,
+        height: u32,
+    ) {
+        let Some(bind_group) = &self.motion_blur_bind_group else {
+            return;
+        };
+        let params = MotionBlurParams {
+            shutter: self.motion_blur_shutter / 360.0,
+            samples: self.motion_blur_samples,
+            max_blur_px: 0.05 * width.max(height) as f32,
+            _pad0: 0.0,
+            viewport_size: [width as f32, height as f32],
+            _pad1: [0.0; 2],
+        };
+        self.queue.write_buffer(&self.motion_blur_pipeline.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let mut blur_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("motion_blur_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        blur_pass.set_pipeline(&self.motion_blur_pipeline.blur_pipeline);
+        blur_pass.set_bind_group(0, bind_group, &[]);
+        blur_pass.draw(0..3, 0..1);  // Fullscreen triangle
+        self.profiler.draw_stats.record(1);
+    }
+}
@@ -0,0 +1,288 @@
+//! Custom line and point overlays.
+//!
+//! Tools and plugins submit overlay geometry under a key of their choosing
+//! (e.g. `"measure:/root/geo"`) and the renderer draws it every frame with
+//! the curves/points line pipelines, depth tested against the scene, until
+//! the key is replaced or removed. Re-submitting a key each frame reuses its
+//! GPU buffers while they are large enough, so callers never touch wgpu.
+//!
+//! Code outside the render loop (plugins, other threads, embedders of
+//! [`run_with_options`](crate::run_with_options)) goes through an
+//! [`OverlayHandle`]: its commands are queued and applied at the start of
+//! the next frame.
+
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+use parking_lot::Mutex;
+use standard_surface::{ModelUniform, StandardSurfaceParams, Vertex};
+use wgpu::util::DeviceExt;
+
+use super::Renderer;
+
+/// Overlay geometry in object space
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    /// Line segments
+    pub lines: Vec<[Vec3; 2]>,
+    pub points: Vec<Vec3>,
+    /// Unlit color
+    pub color: Vec3,
+    /// Object space -> world (e.g. the world matrix of the object annotated)
+    pub transform: Mat4,
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            points: Vec::new(),
+            color: Vec3::new(1.0, 0.8, 0.2),
+            transform: Mat4::IDENTITY,
+        }
+    }
+}
+
+/// Command queued on an [`OverlayHandle`]
+#[derive(Debug, Clone)]
+enum OverlayCommand {
+    Set(String, Overlay),
+    Remove(String),
+    RemovePrefix(String),
+    Clear,
+}
+
+#[derive(Default)]
+struct OverlayQueue {
+    commands: Vec<OverlayCommand>,
+    /// Viewer to wake when a command arrives
+    repaint: Option<egui::Context>,
+}
+
+/// Cloneable, thread-safe handle for submitting overlays to a running viewer
+#[derive(Clone, Default)]
+pub struct OverlayHandle {
+    queue: Arc<Mutex<OverlayQueue>>,
+}
+
+impl std::fmt::Debug for OverlayHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayHandle")
+            .field("pending", &self.queue.lock().commands.len())
+            .finish()
+    }
+}
+
+impl OverlayHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submit (or replace) the overlay stored under `key`; an empty overlay removes it
+    pub fn set(&self, key: impl Into<String>, overlay: Overlay) {
+        self.push(OverlayCommand::Set(key.into(), overlay));
+    }
+
+    /// Remove the overlay stored under `key`
+    pub fn remove(&self, key: impl Into<String>) {
+        self.push(OverlayCommand::Remove(key.into()));
+    }
+
+    /// Remove every overlay whose key starts with `prefix` (one tool's overlays)
+    pub fn remove_prefix(&self, prefix: impl Into<String>) {
+        self.push(OverlayCommand::RemovePrefix(prefix.into()));
+    }
+
+    /// Remove all overlays
+    pub fn clear(&self) {
+        self.push(OverlayCommand::Clear);
+    }
+
+    /// Request a repaint of `ctx` whenever a command is queued
+    pub(crate) fn attach(&self, ctx: &egui::Context) {
+        self.queue.lock().repaint = Some(ctx.clone());
+    }
+
+    fn push(&self, command: OverlayCommand) {
+        let mut queue = self.queue.lock();
+        queue.commands.push(command);
+        if let Some(ctx) = &queue.repaint {
+            ctx.request_repaint();
+        }
+    }
+
+    fn take(&self) -> Vec<OverlayCommand> {
+        std::mem::take(&mut self.queue.lock().commands)
+    }
+}
+
+/// GPU side of one overlay
+pub struct SceneOverlay {
+    /// Line vertices (2 per segment) followed by point vertices
+    vertex_buffer: wgpu::Buffer,
+    line_vertex_count: u32,
+    point_count: u32,
+    material_buffer: wgpu::Buffer,
+    material_bind_group: wgpu::BindGroup,
+    model_buffer: wgpu::Buffer,
+    model_bind_group: wgpu::BindGroup,
+}
+
+fn overlay_vertices(overlay: &Overlay) -> Vec<Vertex> {
+    let vertex = |p: &Vec3| Vertex { position: p.to_array(), normal: [0.0, 1.0, 0.0], uv: [0.0, 0.0] };
+    overlay.lines.iter().flatten().chain(&overlay.points).map(vertex).collect()
+}
+
+fn overlay_model(transform: Mat4) -> ModelUniform {
+    ModelUniform {
+        model: transform.to_cols_array_2d(),
+        normal_matrix: transform.inverse().transpose().to_cols_array_2d(),
+        object_id: 0,  // Not pickable
+        _pad: [0; 3],
+    }
+}
+
+impl Renderer {
+    /// Apply the commands queued on `handle` since the last call
+    pub fn apply_overlay_commands(&mut self, handle: &OverlayHandle) {
+        for command in handle.take() {
+            match command {
+                OverlayCommand::Set(key, overlay) => self.set_overlay(&key, &overlay),
+                OverlayCommand::Remove(key) => {
+                    self.remove_overlay(&key);
+                }
+                OverlayCommand::RemovePrefix(prefix) => self.remove_overlays_with_prefix(&prefix),
+                OverlayCommand::Clear => self.clear_overlays(),
+            }
+        }
+    }
+
+    /// Submit (or replace) the overlay stored under `key`
+    pub fn set_overlay(&mut self, key: &str, overlay: &Overlay) {
+        let vertices = overlay_vertices(overlay);
+        if vertices.is_empty() {
+            self.overlays.remove(key);
+            return;
+        }
+        let material = StandardSurfaceParams::emissive(overlay.color, 1.0);
+        let model = overlay_model(overlay.transform);
+        let line_vertex_count = (overlay.lines.len() * 2) as u32;
+        let point_count = overlay.points.len() as u32;
+        let data: &[u8] = bytemuck::cast_slice(&vertices);
+
+        if let Some(existing) = self.overlays.get_mut(key) {
+            if existing.vertex_buffer.size() >= data.len() as u64 {
+                self.queue.write_buffer(&existing.vertex_buffer, 0, data);
+                self.queue.write_buffer(&existing.material_buffer, 0, bytemuck::bytes_of(&material));
+                self.queue.write_buffer(&existing.model_buffer, 0, bytemuck::bytes_of(&model));
+                existing.line_vertex_count = line_vertex_count;
+                existing.point_count = point_count;
+                return;
+            }
+        }
+
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("overlay_vertex_buffer"),
+            contents: data,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+        let material_buffer = standard_surface::create_material_buffer(&self.device, &material);
+        let material_bind_group = standard_surface::create_material_bind_group(
+            &self.device,
+            &self.layouts.material,
+            &material_buffer,
+        );
+        let model_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("overlay_model_buffer"),
+            contents: bytemuck::bytes_of(&model),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let model_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("overlay_model_bind_group"),
+            layout: &self.layouts.model,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: model_buffer.as_entire_binding(),
+            }],
+        });
+        self.overlays.insert(key.to_string(), SceneOverlay {
+            vertex_buffer,
+            line_vertex_count,
+            point_count,
+            material_buffer,
+            material_bind_group,
+            model_buffer,
+            model_bind_group,
+        });
+    }
+
+    /// Remove the overlay stored under `key`; returns false if there was none
+    pub fn remove_overlay(&mut self, key: &str) -> bool {
+        self.overlays.remove(key).is_some()
+    }
+
+    /// Remove every overlay whose key starts with `prefix` (one tool's overlays)
+    pub fn remove_overlays_with_prefix(&mut self, prefix: &str) {
+        self.overlays.retain(|key, _| !key.starts_with(prefix));
+    }
+
+    /// Remove all overlays
+    pub fn clear_overlays(&mut self) {
+        self.overlays.clear();
+    }
+
+    /// Draw the overlays over `color_view`, depth tested against the scene
+    pub(super) fn render_overlay_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth_view: &wgpu::TextureView,
+        color_view: &wgpu::TextureView,
+    ) {
+        if self.overlays.is_empty() {
+            return;
+        }
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("overlay_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_bind_group(0, &self.camera_light_bind_group, &[]);
+        pass.set_bind_group(3, &self.shadow_bind_group, &[]);
+        pass.set_bind_group(4, &self.env_map.bind_group, &[]);
+
+        for overlay in self.overlays.values() {
+            pass.set_bind_group(1, &overlay.material_bind_group, &[]);
+            pass.set_bind_group(2, &overlay.model_bind_group, &[]);
+            pass.set_vertex_buffer(0, overlay.vertex_buffer.slice(..));
+            if overlay.line_vertex_count > 0 {
+                pass.set_pipeline(&self.pipelines.line_pipeline);
+                pass.draw(0..overlay.line_vertex_count, 0..1);
+                self.profiler.draw_stats.record(0);
+            }
+            if overlay.point_count > 0 {
+                let first = overlay.line_vertex_count;
+                pass.set_pipeline(&self.pipelines.point_pipeline);
+                pass.draw(first..first + overlay.point_count, 0..1);
+                self.profiler.draw_stats.record(0);
+            }
+        }
+    }
+}
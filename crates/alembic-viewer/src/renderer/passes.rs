@@ -1,6 +1,6 @@
 //! Render passes used by the viewer pipeline.
 
-use super::{Renderer, SceneMesh};
+use super::{GpuPass, Renderer, SceneMesh};
 
 impl Renderer {
     pub fn render_shadow_pass(&self, encoder: &mut wgpu::CommandEncoder) {
@@ -19,7 +19,7 @@ impl Renderer {
                 }),
                 stencil_ops: None,
             }),
-            timestamp_writes: None,
+            timestamp_writes: self.profiler.render_pass_writes(GpuPass::Shadow),
             occlusion_query_set: None,
         });
 
@@ -31,6 +31,7 @@ impl Renderer {
             shadow_pass.set_vertex_buffer(0, mesh.mesh.vertex_buffer.slice(..));
             shadow_pass.set_index_buffer(mesh.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
             shadow_pass.draw_indexed(0..mesh.mesh.index_count, 0, 0..1);
+            self.profiler.draw_stats.record((mesh.mesh.index_count / 3) as u64);
         }
     }
 
@@ -93,7 +94,7 @@ impl Renderer {
                 }),
                 stencil_ops: None,
             }),
-            timestamp_writes: None,
+            timestamp_writes: self.profiler.render_pass_writes(GpuPass::GBuffer),
             occlusion_query_set: None,
         });
 
@@ -103,11 +104,14 @@ impl Renderer {
         gbuffer_pass.set_bind_group(4, &self.env_map.bind_group, &[]);
 
         for mesh in meshes {
-            gbuffer_pass.set_bind_group(1, &mesh.material_bind_group, &[]);
             gbuffer_pass.set_bind_group(2, &mesh.model_bind_group, &[]);
             gbuffer_pass.set_vertex_buffer(0, mesh.mesh.vertex_buffer.slice(..));
             gbuffer_pass.set_index_buffer(mesh.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-            gbuffer_pass.draw_indexed(0..mesh.mesh.index_count, 0, 0..1);
+            for (material, range) in mesh.material_draws() {
+                gbuffer_pass.set_bind_group(1, material, &[]);
+                gbuffer_pass.draw_indexed(range, 0, 0..1);
+            }
+            self.profiler.draw_stats.record((mesh.mesh.index_count / 3) as u64);
         }
     }
 
@@ -162,7 +166,7 @@ impl Renderer {
                 depth_slice: None,
             })],
             depth_stencil_attachment: None,
-            timestamp_writes: None,
+            timestamp_writes: self.profiler.render_pass_writes(GpuPass::Ssao),
             occlusion_query_set: None,
         });
 
@@ -170,6 +174,7 @@ impl Renderer {
             ssao_pass.set_pipeline(&self.postfx.ssao_pipeline);
             ssao_pass.set_bind_group(0, ssao_bind_group, &[]);
             ssao_pass.draw(0..3, 0..1);
+            self.profiler.draw_stats.record(1);
         }
     }
 
@@ -219,54 +224,49 @@ impl Renderer {
             render_pass.set_vertex_buffer(0, self.skybox_vertex_buffer.slice(..));
             render_pass.set_index_buffer(self.skybox_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
             render_pass.draw_indexed(0..self.skybox_index_count, 0, 0..1);
+            self.profiler.draw_stats.record((self.skybox_index_count / 3) as u64);
         }
 
         render_pass.set_bind_group(0, &self.camera_light_bind_group, &[]);
         render_pass.set_bind_group(3, &self.shadow_bind_group, &[]);
         render_pass.set_bind_group(4, &self.env_map.bind_group, &[]);
 
-        if self.show_grid {
-            if let Some(grid) = &self.grid_mesh {
-                render_pass.set_pipeline(&self.pipelines.line_pipeline);
-                render_pass.set_bind_group(1, &self.grid_material, &[]);
-                render_pass.set_bind_group(2, &self.grid_model, &[]);
-                render_pass.set_vertex_buffer(0, grid.vertex_buffer.slice(..));
-                render_pass.set_index_buffer(grid.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..grid.index_count, 0, 0..1);
-            }
-        }
-
         if let Some(xray_pipeline) = xray_pipeline {
             render_pass.set_pipeline(xray_pipeline);
         } else {
             render_pass.set_pipeline(opaque_pipeline);
         }
         for mesh in meshes {
-            render_pass.set_bind_group(1, &mesh.material_bind_group, &[]);
             render_pass.set_bind_group(2, &mesh.model_bind_group, &[]);
             render_pass.set_vertex_buffer(0, mesh.mesh.vertex_buffer.slice(..));
             render_pass.set_index_buffer(mesh.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-            render_pass.draw_indexed(0..mesh.mesh.index_count, 0, 0..1);
+            for (material, range) in mesh.material_draws() {
+                render_pass.set_bind_group(1, material, &[]);
+                render_pass.draw_indexed(range, 0, 0..1);
+            }
+            self.profiler.draw_stats.record((mesh.mesh.index_count / 3) as u64);
         }
 
         if !self.curves.is_empty() {
             render_pass.set_pipeline(&self.pipelines.line_pipeline);
-            for curve in self.curves.values() {
+            for curve in self.curves.values().filter(|c| c.visible) {
                 render_pass.set_bind_group(1, &curve.material_bind_group, &[]);
                 render_pass.set_bind_group(2, &curve.model_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, curve.mesh.vertex_buffer.slice(..));
                 render_pass.set_index_buffer(curve.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
                 render_pass.draw_indexed(0..curve.mesh.index_count, 0, 0..1);
+                self.profiler.draw_stats.record(0);
             }
         }
 
         if !self.points.is_empty() {
             render_pass.set_pipeline(&self.pipelines.point_pipeline);
-            for pts in self.points.values() {
+            for pts in self.points.values().filter(|p| p.visible) {
                 render_pass.set_bind_group(1, &pts.material_bind_group, &[]);
                 render_pass.set_bind_group(2, &pts.model_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, pts.vertex_buffer.slice(..));
                 render_pass.draw(0..pts.vertex_count, 0..1);
+                self.profiler.draw_stats.record(0);
             }
         }
     }
@@ -299,7 +299,7 @@ impl Renderer {
                 }),
                 stencil_ops: None,
             }),
-            timestamp_writes: None,
+            timestamp_writes: self.profiler.render_pass_writes(GpuPass::Transparent),
             occlusion_query_set: None,
         });
 
@@ -310,32 +310,37 @@ impl Renderer {
         if !meshes.is_empty() {
             render_pass.set_pipeline(transparent_pipeline);
             for mesh in meshes {
-                render_pass.set_bind_group(1, &mesh.material_bind_group, &[]);
                 render_pass.set_bind_group(2, &mesh.model_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, mesh.mesh.vertex_buffer.slice(..));
                 render_pass.set_index_buffer(mesh.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..mesh.mesh.index_count, 0, 0..1);
+                for (material, range) in mesh.material_draws() {
+                    render_pass.set_bind_group(1, material, &[]);
+                    render_pass.draw_indexed(range, 0, 0..1);
+                }
+                self.profiler.draw_stats.record((mesh.mesh.index_count / 3) as u64);
             }
         }
 
         if !self.curves.is_empty() {
             render_pass.set_pipeline(&self.pipelines.line_pipeline);
-            for curve in self.curves.values() {
+            for curve in self.curves.values().filter(|c| c.visible) {
                 render_pass.set_bind_group(1, &curve.material_bind_group, &[]);
                 render_pass.set_bind_group(2, &curve.model_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, curve.mesh.vertex_buffer.slice(..));
                 render_pass.set_index_buffer(curve.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
                 render_pass.draw_indexed(0..curve.mesh.index_count, 0, 0..1);
+                self.profiler.draw_stats.record(0);
             }
         }
 
         if !self.points.is_empty() {
             render_pass.set_pipeline(&self.pipelines.point_pipeline);
-            for pts in self.points.values() {
+            for pts in self.points.values().filter(|p| p.visible) {
                 render_pass.set_bind_group(1, &pts.material_bind_group, &[]);
                 render_pass.set_bind_group(2, &pts.model_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, pts.vertex_buffer.slice(..));
                 render_pass.draw(0..pts.vertex_count, 0..1);
+                self.profiler.draw_stats.record(0);
             }
         }
     }
@@ -367,6 +372,7 @@ impl Renderer {
         blur_pass.set_pipeline(&self.postfx.ssao_blur_pipeline);
         blur_pass.set_bind_group(0, bind_group, &[]);
         blur_pass.draw(0..3, 0..1);
+        self.profiler.draw_stats.record(1);
     }
 
     /// Lighting pass that shades from the G-Buffer into the final color target.
@@ -388,7 +394,7 @@ impl Renderer {
                 depth_slice: None,
             })],
             depth_stencil_attachment: None,
-            timestamp_writes: None,
+            timestamp_writes: self.profiler.render_pass_writes(GpuPass::Lighting),
             occlusion_query_set: None,
         });
 
@@ -396,6 +402,7 @@ impl Renderer {
             lighting_pass.set_pipeline(&self.postfx.lighting_pipeline);
             lighting_pass.set_bind_group(0, lighting_bind_group, &[]);
             lighting_pass.draw(0..3, 0..1);
+            self.profiler.draw_stats.record(1);
         }
     }
 }
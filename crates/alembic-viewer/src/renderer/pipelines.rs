@@ -67,9 +67,11 @@ pub fn create_pipelines(
         wireframe: false,
         ..Default::default()
     };
+    // Without line polygon mode (WebGL2) wireframe draws shaded
+    let wireframe = device.features().contains(wgpu::Features::POLYGON_MODE_LINE);
     let wireframe_config = PipelineConfig {
         label: Some("wireframe_pipeline"),
-        wireframe: true,
+        wireframe,
         ..config.clone()
     };
     let wireframe_pipeline = standard_surface::create_pipeline(device, layouts, &wireframe_config);
@@ -81,7 +83,7 @@ pub fn create_pipelines(
     };
     let wireframe_double_sided_config = PipelineConfig {
         label: Some("wireframe_pipeline_double_sided"),
-        wireframe: true,
+        wireframe,
         cull_mode: None,
         ..double_sided_config.clone()
     };
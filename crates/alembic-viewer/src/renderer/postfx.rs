@@ -1,6 +1,6 @@
 //! Post-processing pipelines (SSAO + lighting).
 
-use crate::viewer::renderer::shaders::{LIGHTING_SHADER, SSAO_BLUR_SHADER, SSAO_SHADER};
+use crate::renderer::shaders::{LIGHTING_SHADER, SSAO_BLUR_SHADER, SSAO_SHADER};
 
 pub struct PostFxPipelines {
     pub ssao_pipeline: wgpu::RenderPipeline,
@@ -0,0 +1,290 @@
+//! Per-pass GPU timings (timestamp queries) and per-frame draw counters.
+//!
+//! Timestamps are resolved into a readback buffer at the end of each frame and
+//! mapped asynchronously, so results lag one or more frames behind but never
+//! stall the render loop. Requires `Features::TIMESTAMP_QUERY`; without it only
+//! the CPU-side draw counters are available.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// Render passes with individual GPU timings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuPass {
+    Shadow,
+    GBuffer,
+    Ssao,
+    Lighting,
+    Transparent,
+    Velocity,
+    PathTrace,
+}
+
+impl GpuPass {
+    pub const ALL: [GpuPass; 7] = [
+        GpuPass::Shadow,
+        GpuPass::GBuffer,
+        GpuPass::Ssao,
+        GpuPass::Lighting,
+        GpuPass::Transparent,
+        GpuPass::Velocity,
+        GpuPass::PathTrace,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GpuPass::Shadow => "Shadow",
+            GpuPass::GBuffer => "GBuffer",
+            GpuPass::Ssao => "SSAO",
+            GpuPass::Lighting => "Lighting",
+            GpuPass::Transparent => "Transparent",
+            GpuPass::Velocity => "Velocity",
+            GpuPass::PathTrace => "Path Trace",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+const NUM_PASSES: usize = GpuPass::ALL.len();
+const NUM_QUERIES: u32 = (NUM_PASSES * 2) as u32;
+const RESOLVE_SIZE: u64 = NUM_QUERIES as u64 * 8;
+
+// Readback map states
+const MAP_WAITING: u8 = 0;
+const MAP_OK: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+/// Draw-call and triangle counters for the current frame.
+/// Atomics so passes can record through `&self`.
+#[derive(Debug, Default)]
+pub struct DrawStats {
+    draw_calls: AtomicU32,
+    triangles: AtomicU64,
+}
+
+impl DrawStats {
+    pub fn reset(&self) {
+        self.draw_calls.store(0, Ordering::Relaxed);
+        self.triangles.store(0, Ordering::Relaxed);
+    }
+
+    /// Record one draw call of `triangles` triangles (0 for lines/points).
+    pub fn record(&self, triangles: u64) {
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+        self.triangles.fetch_add(triangles, Ordering::Relaxed);
+    }
+
+    pub fn draw_calls(&self) -> u32 {
+        self.draw_calls.load(Ordering::Relaxed)
+    }
+
+    pub fn triangles(&self) -> u64 {
+        self.triangles.load(Ordering::Relaxed)
+    }
+}
+
+/// Snapshot of the last completed frame's statistics (for UI display).
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    pub draw_calls: u32,
+    pub triangles: u64,
+    /// GPU time per pass in ms; `None` if the pass did not run or timing is unsupported.
+    pub pass_ms: [Option<f32>; NUM_PASSES],
+}
+
+impl FrameStats {
+    /// Iterate over passes that produced a timing.
+    pub fn timings(&self) -> impl Iterator<Item = (GpuPass, f32)> + '_ {
+        GpuPass::ALL.iter().filter_map(|&p| self.pass_ms[p.index()].map(|ms| (p, ms)))
+    }
+
+    /// Sum of all measured pass timings in ms.
+    pub fn total_gpu_ms(&self) -> f32 {
+        self.timings().map(|(_, ms)| ms).sum()
+    }
+}
+
+/// Timestamp-query based GPU pass profiler.
+pub struct GpuProfiler {
+    query_set: Option<wgpu::QuerySet>,
+    resolve_buffer: Option<wgpu::Buffer>,
+    readback_buffer: Option<wgpu::Buffer>,
+    /// Nanoseconds per timestamp tick
+    period_ns: f32,
+    /// Passes that wrote timestamps in the frame being recorded
+    written: [AtomicBool; NUM_PASSES],
+    /// Passes that wrote timestamps in the frame currently being read back
+    inflight: [bool; NUM_PASSES],
+    /// Readback buffer has a map request outstanding
+    map_pending: bool,
+    map_state: Arc<AtomicU8>,
+    /// Resolved this frame; map after submit
+    resolved: bool,
+    /// Smoothed timings (EMA) in ms
+    pass_ms: [Option<f32>; NUM_PASSES],
+    /// Frame draw counters
+    pub draw_stats: DrawStats,
+}
+
+impl GpuProfiler {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let supported = device.features().contains(wgpu::Features::TIMESTAMP_QUERY);
+        let (query_set, resolve_buffer, readback_buffer) = if supported {
+            let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("gpu_profiler_queries"),
+                ty: wgpu::QueryType::Timestamp,
+                count: NUM_QUERIES,
+            });
+            let resolve = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("gpu_profiler_resolve"),
+                size: RESOLVE_SIZE,
+                usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            });
+            let readback = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("gpu_profiler_readback"),
+                size: RESOLVE_SIZE,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+            (Some(query_set), Some(resolve), Some(readback))
+        } else {
+            (None, None, None)
+        };
+
+        Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            period_ns: queue.get_timestamp_period(),
+            written: Default::default(),
+            inflight: [false; NUM_PASSES],
+            map_pending: false,
+            map_state: Arc::new(AtomicU8::new(MAP_WAITING)),
+            resolved: false,
+            pass_ms: [None; NUM_PASSES],
+            draw_stats: DrawStats::default(),
+        }
+    }
+
+    /// True if the device supports timestamp queries.
+    pub fn timing_supported(&self) -> bool {
+        self.query_set.is_some()
+    }
+
+    /// Start a new frame: collect finished readbacks and reset counters.
+    pub fn begin_frame(&mut self, device: &wgpu::Device) {
+        self.draw_stats.reset();
+        for w in &self.written {
+            w.store(false, Ordering::Relaxed);
+        }
+        self.resolved = false;
+
+        if !self.map_pending {
+            return;
+        }
+        let _ = device.poll(wgpu::PollType::Poll);
+        match self.map_state.load(Ordering::Acquire) {
+            MAP_WAITING => return,
+            MAP_FAILED => {
+                self.map_pending = false;
+                self.map_state.store(MAP_WAITING, Ordering::Release);
+                return;
+            }
+            _ => {}
+        }
+        let Some(readback) = &self.readback_buffer else { return };
+
+        {
+            let data = readback.slice(..).get_mapped_range();
+            let stamps: &[u64] = bytemuck::cast_slice(&data);
+            for (i, ran) in self.inflight.iter().enumerate() {
+                if !*ran {
+                    self.pass_ms[i] = None;
+                    continue;
+                }
+                let (begin, end) = (stamps[i * 2], stamps[i * 2 + 1]);
+                let ms = end.saturating_sub(begin) as f32 * self.period_ns / 1_000_000.0;
+                self.pass_ms[i] = Some(match self.pass_ms[i] {
+                    Some(prev) => prev * 0.9 + ms * 0.1,
+                    None => ms,
+                });
+            }
+        }
+        readback.unmap();
+        self.map_pending = false;
+        self.map_state.store(MAP_WAITING, Ordering::Release);
+    }
+
+    /// Timestamp writes for a render pass (None if unsupported).
+    pub fn render_pass_writes(&self, pass: GpuPass) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        let query_set = self.query_set.as_ref()?;
+        self.written[pass.index()].store(true, Ordering::Relaxed);
+        let i = pass.index() as u32 * 2;
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set,
+            beginning_of_pass_write_index: Some(i),
+            end_of_pass_write_index: Some(i + 1),
+        })
+    }
+
+    /// Timestamp writes for a compute pass (None if unsupported).
+    pub fn compute_pass_writes(&self, pass: GpuPass) -> Option<wgpu::ComputePassTimestampWrites<'_>> {
+        let query_set = self.query_set.as_ref()?;
+        self.written[pass.index()].store(true, Ordering::Relaxed);
+        let i = pass.index() as u32 * 2;
+        Some(wgpu::ComputePassTimestampWrites {
+            query_set,
+            beginning_of_pass_write_index: Some(i),
+            end_of_pass_write_index: Some(i + 1),
+        })
+    }
+
+    /// Resolve this frame's queries into the readback buffer.
+    /// Skipped while a previous readback is still in flight.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if self.map_pending {
+            return;
+        }
+        let (Some(query_set), Some(resolve), Some(readback)) =
+            (&self.query_set, &self.resolve_buffer, &self.readback_buffer)
+        else {
+            return;
+        };
+        for (dst, w) in self.inflight.iter_mut().zip(&self.written) {
+            *dst = w.load(Ordering::Relaxed);
+        }
+        if !self.inflight.iter().any(|&w| w) {
+            return;
+        }
+        encoder.resolve_query_set(query_set, 0..NUM_QUERIES, resolve, 0);
+        encoder.copy_buffer_to_buffer(resolve, 0, readback, 0, RESOLVE_SIZE);
+        self.resolved = true;
+    }
+
+    /// Request async readback (call after the encoder passed to `resolve` was submitted).
+    pub fn end_frame(&mut self) {
+        if !self.resolved {
+            return;
+        }
+        self.resolved = false;
+        let Some(readback) = &self.readback_buffer else { return };
+        let state = self.map_state.clone();
+        readback.slice(..).map_async(wgpu::MapMode::Read, move |res| {
+            state.store(if res.is_ok() { MAP_OK } else { MAP_FAILED }, Ordering::Release);
+        });
+        self.map_pending = true;
+    }
+
+    /// Snapshot of current statistics.
+    pub fn stats(&self) -> FrameStats {
+        FrameStats {
+            draw_calls: self.draw_stats.draw_calls(),
+            triangles: self.draw_stats.triangles(),
+            pass_ms: self.pass_ms,
+        }
+    }
+}
@@ -54,8 +54,10 @@ pub struct LightingParams {
     pub hdr_visible: f32,
     /// Padding to 16-byte alignment after hdr_visible.
     pub _pad0: [f32; 3],
+    /// Exposure x white balance gain (see `crate::exposure`).
+    pub exposure: [f32; 3],
     /// Pad to match WGSL uniform layout (vec3 occupies 16 bytes).
-    pub _pad1: [f32; 4],
+    pub _pad1: f32,
 }
 
 /// Parameters for separable SSAO blur.
@@ -221,7 +221,7 @@ struct LightRig {
 struct LightingParams {
     background: vec4<f32>,
     hdr_visible: f32,
-    _pad: vec3<f32>,
+    exposure: vec3<f32>,  // Exposure x white balance gain
 }
 @group(0) @binding(5) var<uniform> params: LightingParams;
 
@@ -269,7 +269,7 @@ fn sample_background(ndc_xy: vec2<f32>) -> vec4<f32> {
     let dir = normalize(world_pos - camera.position);
     let env_uv = dir_to_equirect_uv(dir, env.rotation);
     let color = textureSample(env_map, env_sampler, env_uv).rgb * env.intensity;
-    return vec4<f32>(color, 1.0);
+    return vec4<f32>(color * params.exposure, 1.0);
 }
 
 @fragment
@@ -310,7 +310,7 @@ fn fs_lighting(in: VsOut) -> @location(0) vec4<f32> {
     let half_key = normalize(key_l + view_dir);
     let spec = pow(max(dot(n, half_key), 0.0), spec_exp) * spec_color * key_ndotl;
 
-    return vec4<f32>((diffuse + spec) * occlusion, 1.0);
+    return vec4<f32>((diffuse + spec) * occlusion * params.exposure, 1.0);
 }
 "#;
 
@@ -0,0 +1,74 @@
+// Bloom post-process shader
+// Bright pass (full res -> half res) and separable Gaussian blur at half res
+
+struct LensFxParams {
+    inv_proj: mat4x4<f32>,     // Inverse projection (depth -> view space)
+    viewport_size: vec2<f32>,  // Viewport dimensions
+    focus_distance: f32,       // Focus plane distance (scene units)
+    coc_scale: f32,            // CoC in pixels at infinity
+    max_coc_px: f32,           // CoC clamp in pixels
+    dof_samples: u32,          // Gather taps
+    bloom_threshold: f32,      // Brightness where bloom starts
+    bloom_intensity: f32,      // Bloom amount added back
+    dof_enabled: u32,
+    bloom_enabled: u32,
+    _pad: vec2<f32>,
+}
+
+@group(0) @binding(0) var src_texture: texture_2d<f32>;
+@group(0) @binding(1) var src_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: LensFxParams;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// Fullscreen triangle (3 vertices cover entire screen)
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(i32(vertex_idx & 1u) * 4 - 1);
+    let y = f32(i32(vertex_idx >> 1u) * 4 - 1);
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>((x + 1.0) * 0.5, (1.0 - y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs_bright(in: VertexOutput) -> @location(0) vec4<f32> {
+    // 4 bilinear taps = 4x4 box downsample, keeps thin highlights stable
+    let texel = 1.0 / vec2<f32>(textureDimensions(src_texture));
+    var color = vec3<f32>(0.0);
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + texel * vec2<f32>(-1.0, -1.0), 0.0).rgb;
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + texel * vec2<f32>(1.0, -1.0), 0.0).rgb;
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + texel * vec2<f32>(-1.0, 1.0), 0.0).rgb;
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + texel * vec2<f32>(1.0, 1.0), 0.0).rgb;
+    color *= 0.25;
+
+    // Soft threshold on the brightest channel (keeps hue)
+    let brightness = max(color.r, max(color.g, color.b));
+    let knee = max(brightness - params.bloom_threshold, 0.0) / max(brightness, 1e-4);
+    return vec4<f32>(color * knee, 1.0);
+}
+
+// 9-tap Gaussian folded into 5 bilinear taps, spread 2x for a wider glow
+fn blur(uv: vec2<f32>, dir: vec2<f32>) -> vec4<f32> {
+    let texel = dir * 2.0 / vec2<f32>(textureDimensions(src_texture));
+    var color = textureSampleLevel(src_texture, src_sampler, uv, 0.0).rgb * 0.2270270270;
+    color += textureSampleLevel(src_texture, src_sampler, uv + texel * 1.3846153846, 0.0).rgb * 0.3162162162;
+    color += textureSampleLevel(src_texture, src_sampler, uv - texel * 1.3846153846, 0.0).rgb * 0.3162162162;
+    color += textureSampleLevel(src_texture, src_sampler, uv + texel * 3.2307692308, 0.0).rgb * 0.0702702703;
+    color += textureSampleLevel(src_texture, src_sampler, uv - texel * 3.2307692308, 0.0).rgb * 0.0702702703;
+    return vec4<f32>(color, 1.0);
+}
+
+@fragment
+fn fs_blur_h(in: VertexOutput) -> @location(0) vec4<f32> {
+    return blur(in.uv, vec2<f32>(1.0, 0.0));
+}
+
+@fragment
+fn fs_blur_v(in: VertexOutput) -> @location(0) vec4<f32> {
+    return blur(in.uv, vec2<f32>(0.0, 1.0));
+}
@@ -0,0 +1,102 @@
+// Ground plane grid shader
+// Camera rays through each pixel are intersected with y = 0; lines are drawn
+// analytically with screen-space derivatives, so they stay anti-aliased at
+// any distance and no geometry is rebuilt when the step changes.
+
+struct GridParams {
+    view_proj: mat4x4<f32>,      // World -> clip (for the hit point depth)
+    inv_view_proj: mat4x4<f32>,  // Clip -> world (pixel rays)
+    camera_pos: vec3<f32>,
+    step: f32,                   // Minor line spacing (scene units)
+    fade_distance: f32,          // Distance where the grid has faded out
+    major_every: f32,            // Minor cells per major cell
+    _pad: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> params: GridParams;
+
+const MINOR_COLOR: vec3<f32> = vec3<f32>(0.32, 0.32, 0.34);
+const MAJOR_COLOR: vec3<f32> = vec3<f32>(0.45, 0.45, 0.48);
+const X_AXIS_COLOR: vec3<f32> = vec3<f32>(0.85, 0.25, 0.25);
+const Z_AXIS_COLOR: vec3<f32> = vec3<f32>(0.25, 0.45, 0.9);
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    @builtin(frag_depth) depth: f32,
+}
+
+// Fullscreen triangle (3 vertices cover entire screen)
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(i32(vertex_idx & 1u) * 4 - 1);
+    let y = f32(i32(vertex_idx >> 1u) * 4 - 1);
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.ndc = vec2<f32>(x, y);
+    return out;
+}
+
+fn unproject(ndc: vec2<f32>, z: f32) -> vec3<f32> {
+    let p = params.inv_view_proj * vec4<f32>(ndc, z, 1.0);
+    return p.xyz / p.w;
+}
+
+// Line coverage (0-1) for lines at integer values of `coord`
+fn line_coverage(coord: vec2<f32>) -> f32 {
+    let deriv = max(fwidth(coord), vec2<f32>(1e-6));
+    let dist = abs(fract(coord - 0.5) - 0.5) / deriv;
+    return 1.0 - min(min(dist.x, dist.y), 1.0);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> FragmentOutput {
+    let near = unproject(in.ndc, 0.0);
+    let far = unproject(in.ndc, 1.0);
+    let dir = far - near;
+    // Ray parameter of the ground plane hit; no hit when parallel or behind
+    let t = select(-1.0, -near.y / dir.y, abs(dir.y) > 1e-8);
+    let p = near + dir * max(t, 0.0);
+
+    // Derivatives first: everything below is evaluated for every pixel
+    let minor_coord = p.xz / params.step;
+    let major_coord = minor_coord / params.major_every;
+    let minor = line_coverage(minor_coord);
+    let major = line_coverage(major_coord);
+    let axis_deriv = max(fwidth(p.xz), vec2<f32>(1e-6));
+    let axis = vec2<f32>(1.0) - min(abs(p.zx) / axis_deriv.yx, vec2<f32>(1.0));
+
+    // Minor lines fade before a cell shrinks to a couple of pixels
+    let cells_per_px = max(fwidth(minor_coord).x, fwidth(minor_coord).y);
+    let minor_fade = 1.0 - smoothstep(0.15, 0.4, cells_per_px);
+
+    var color = MINOR_COLOR;
+    var alpha = minor * minor_fade * 0.6;
+    if major * 0.9 > alpha {
+        color = MAJOR_COLOR;
+        alpha = major * 0.9;
+    }
+    if axis.x > 0.0 {
+        color = mix(color, X_AXIS_COLOR, axis.x);
+        alpha = max(alpha, axis.x);
+    }
+    if axis.y > 0.0 {
+        color = mix(color, Z_AXIS_COLOR, axis.y);
+        alpha = max(alpha, axis.y);
+    }
+
+    // Distance fade around the camera, which also hides the horizon aliasing
+    let dist = length(p.xz - params.camera_pos.xz);
+    alpha *= 1.0 - smoothstep(params.fade_distance * 0.4, params.fade_distance, dist);
+    alpha *= select(0.0, 1.0, t > 0.0);
+
+    let clip = params.view_proj * vec4<f32>(p, 1.0);
+    var out: FragmentOutput;
+    out.color = vec4<f32>(color, alpha);
+    out.depth = select(1.0, clamp(clip.z / clip.w, 0.0, 1.0), t > 0.0);
+    return out;
+}
@@ -0,0 +1,85 @@
+// Lens composite post-process shader
+// Depth of field gather from a thin-lens circle of confusion, then bloom add
+
+struct LensFxParams {
+    inv_proj: mat4x4<f32>,     // Inverse projection (depth -> view space)
+    viewport_size: vec2<f32>,  // Viewport dimensions
+    focus_distance: f32,       // Focus plane distance (scene units)
+    coc_scale: f32,            // CoC in pixels at infinity
+    max_coc_px: f32,           // CoC clamp in pixels
+    dof_samples: u32,          // Gather taps
+    bloom_threshold: f32,      // Brightness where bloom starts
+    bloom_intensity: f32,      // Bloom amount added back
+    dof_enabled: u32,
+    bloom_enabled: u32,
+    _pad: vec2<f32>,
+}
+
+@group(0) @binding(0) var color_texture: texture_2d<f32>;
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+@group(0) @binding(2) var bloom_texture: texture_2d<f32>;
+@group(0) @binding(3) var color_sampler: sampler;
+@group(0) @binding(4) var<uniform> params: LensFxParams;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// Fullscreen triangle (3 vertices cover entire screen)
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(i32(vertex_idx & 1u) * 4 - 1);
+    let y = f32(i32(vertex_idx >> 1u) * 4 - 1);
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>((x + 1.0) * 0.5, (1.0 - y) * 0.5);
+    return out;
+}
+
+// Circle of confusion diameter in pixels at `pixel`
+fn coc_px(pixel: vec2<i32>) -> f32 {
+    let d = textureLoad(depth_texture, pixel, 0);
+    let uv = (vec2<f32>(pixel) + 0.5) / params.viewport_size;
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let view_pos = params.inv_proj * ndc;
+    let z = max(-view_pos.z / view_pos.w, 1e-4);
+    let coc = params.coc_scale * abs(z - params.focus_distance) / z;
+    return min(coc, params.max_coc_px);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    var color = textureSampleLevel(color_texture, color_sampler, in.uv, 0.0);
+
+    if params.dof_enabled != 0u {
+        let coc = coc_px(pixel);
+        if coc > 1.0 {
+            // Golden-angle spiral over the CoC disk. A tap only contributes if
+            // its own CoC reaches the center, so blurred foreground spreads
+            // over sharp background but not the other way around.
+            let max_pixel = vec2<i32>(params.viewport_size) - 1;
+            let n = max(params.dof_samples, 1u);
+            var sum = color.rgb;
+            var weight = 1.0;
+            for (var i = 0u; i < n; i = i + 1u) {
+                let r = 0.5 * coc * sqrt((f32(i) + 0.5) / f32(n));
+                let theta = f32(i) * 2.39996323;
+                let offset = vec2<f32>(cos(theta), sin(theta)) * r;
+                let tap = clamp(pixel + vec2<i32>(round(offset)), vec2<i32>(0), max_pixel);
+                let w = clamp(0.5 * coc_px(tap) - r + 1.0, 0.0, 1.0);
+                let tap_uv = (vec2<f32>(tap) + 0.5) / params.viewport_size;
+                sum += textureSampleLevel(color_texture, color_sampler, tap_uv, 0.0).rgb * w;
+                weight += w;
+            }
+            color = vec4<f32>(sum / weight, color.a);
+        }
+    }
+
+    if params.bloom_enabled != 0u {
+        let bloom = textureSampleLevel(bloom_texture, color_sampler, in.uv, 0.0).rgb;
+        color = vec4<f32>(color.rgb + bloom * params.bloom_intensity, color.a);
+    }
+    return color;
+}
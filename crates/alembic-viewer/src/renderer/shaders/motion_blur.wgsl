@@ -0,0 +1,56 @@
+// Motion blur post-process shader
+// Gathers the scene color along the per-pixel velocity scaled by the shutter
+
+struct MotionBlurParams {
+    shutter: f32,              // Shutter fraction (angle / 360)
+    samples: u32,              // Taps along the velocity vector
+    max_blur_px: f32,          // Blur length clamp in pixels
+    _pad0: f32,
+    viewport_size: vec2<f32>,  // Viewport dimensions
+    _pad1: vec2<f32>,
+}
+
+@group(0) @binding(0) var color_texture: texture_2d<f32>;
+@group(0) @binding(1) var velocity_texture: texture_2d<f32>;
+@group(0) @binding(2) var color_sampler: sampler;
+@group(0) @binding(3) var<uniform> params: MotionBlurParams;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// Fullscreen triangle (3 vertices cover entire screen)
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(i32(vertex_idx & 1u) * 4 - 1);
+    let y = f32(i32(vertex_idx >> 1u) * 4 - 1);
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>((x + 1.0) * 0.5, (1.0 - y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let center = textureSampleLevel(color_texture, color_sampler, in.uv, 0.0);
+
+    var velocity = textureLoad(velocity_texture, pixel, 0).xy * params.shutter;
+    let length_px = length(velocity * params.viewport_size);
+    if length_px < 0.5 || params.samples < 2u {
+        return center;
+    }
+    if length_px > params.max_blur_px {
+        velocity = velocity * (params.max_blur_px / length_px);
+    }
+
+    // Shutter centered on the frame: taps span [-0.5, 0.5] of the motion
+    var sum = vec4<f32>(0.0);
+    let n = params.samples;
+    for (var i = 0u; i < n; i = i + 1u) {
+        let t = f32(i) / f32(n - 1u) - 0.5;
+        sum = sum + textureSampleLevel(color_texture, color_sampler, in.uv - velocity * t, 0.0);
+    }
+    return sum / f32(n);
+}
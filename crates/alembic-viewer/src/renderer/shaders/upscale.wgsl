@@ -0,0 +1,78 @@
+// Render scale resolve: draws the scaled viewport image at display size.
+// fs_upscale is a 9-tap Catmull-Rom filter built from bilinear fetches
+// (sharper than plain bilinear when rendering below 100%); fs_downsample
+// averages four bilinear taps over each output pixel's footprint when
+// supersampling above 100%.
+
+@group(0) @binding(0) var src_texture: texture_2d<f32>;
+@group(0) @binding(1) var src_sampler: sampler;
+
+struct VsOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Fullscreen triangle (3 vertices cover entire screen).
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> VsOut {
+    var out: VsOut;
+    let x = f32(i32(idx & 1u)) * 4.0 - 1.0;
+    let y = f32(i32(idx >> 1u)) * 4.0 - 1.0;
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>((x + 1.0) * 0.5, (1.0 - y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs_upscale(in: VsOut) -> @location(0) vec4<f32> {
+    let tex_size = vec2<f32>(textureDimensions(src_texture));
+    let inv_size = 1.0 / tex_size;
+
+    // Texel center at or before the sample point, and the fraction past it
+    let sample_pos = in.uv * tex_size;
+    let tex_pos1 = floor(sample_pos - 0.5) + 0.5;
+    let f = sample_pos - tex_pos1;
+
+    // Catmull-Rom weights for the 4 texels per axis
+    let w0 = f * (-0.5 + f * (1.0 - 0.5 * f));
+    let w1 = 1.0 + f * f * (-2.5 + 1.5 * f);
+    let w2 = f * (0.5 + f * (2.0 - 1.5 * f));
+    let w3 = f * f * (-0.5 + 0.5 * f);
+
+    // The middle two texels are fetched with one bilinear tap
+    let w12 = w1 + w2;
+    let offset12 = w2 / w12;
+
+    let tc0 = (tex_pos1 - 1.0) * inv_size;
+    let tc3 = (tex_pos1 + 2.0) * inv_size;
+    let tc12 = (tex_pos1 + offset12) * inv_size;
+
+    var color = vec4<f32>(0.0);
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc0.x, tc0.y), 0.0) * w0.x * w0.y;
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc12.x, tc0.y), 0.0) * w12.x * w0.y;
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc3.x, tc0.y), 0.0) * w3.x * w0.y;
+
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc0.x, tc12.y), 0.0) * w0.x * w12.y;
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc12.x, tc12.y), 0.0) * w12.x * w12.y;
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc3.x, tc12.y), 0.0) * w3.x * w12.y;
+
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc0.x, tc3.y), 0.0) * w0.x * w3.y;
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc12.x, tc3.y), 0.0) * w12.x * w3.y;
+    color += textureSampleLevel(src_texture, src_sampler, vec2<f32>(tc3.x, tc3.y), 0.0) * w3.x * w3.y;
+
+    // Negative lobes can overshoot at hard edges
+    return vec4<f32>(saturate(color.rgb), 1.0);
+}
+
+@fragment
+fn fs_downsample(in: VsOut) -> @location(0) vec4<f32> {
+    // One output pixel in uv; the fullscreen triangle has constant derivatives
+    let footprint = vec2<f32>(dpdx(in.uv).x, dpdy(in.uv).y);
+    let o = footprint * 0.25;
+
+    var color = textureSampleLevel(src_texture, src_sampler, in.uv + vec2<f32>(-o.x, -o.y), 0.0);
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + vec2<f32>(o.x, -o.y), 0.0);
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + vec2<f32>(-o.x, o.y), 0.0);
+    color += textureSampleLevel(src_texture, src_sampler, in.uv + vec2<f32>(o.x, o.y), 0.0);
+    return vec4<f32>(color.rgb * 0.25, 1.0);
+}
@@ -0,0 +1,59 @@
+// Velocity buffer shader - outputs per-pixel screen-space motion
+// Renders to Rg16Float texture; velocity is in UV units (current - previous)
+// Previous positions come from a second vertex buffer (last sample or pos - v/fps)
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inv_view_proj: mat4x4<f32>,
+    position: vec3<f32>,
+    xray_alpha: f32,
+    flat_shading: f32,
+    auto_normals: f32,
+    _pad2: f32,
+    _pad3: f32,
+}
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+    object_id: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> camera: CameraUniform;
+@group(1) @binding(0) var<uniform> model: ModelUniform;
+@group(2) @binding(0) var<uniform> prev_model: ModelUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) prev_position: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) curr_clip: vec4<f32>,
+    @location(1) prev_clip: vec4<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    // Object motion only: both positions go through the current camera
+    out.curr_clip = camera.view_proj * model.model * vec4<f32>(in.position, 1.0);
+    out.prev_clip = camera.view_proj * prev_model.model * vec4<f32>(in.prev_position, 1.0);
+    out.position = out.curr_clip;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec2<f32> {
+    let curr = in.curr_clip.xy / in.curr_clip.w;
+    let prev = in.prev_clip.xy / max(in.prev_clip.w, 1e-6);
+    // NDC -> UV (y flipped)
+    return (curr - prev) * vec2<f32>(0.5, -0.5);
+}
@@ -0,0 +1,107 @@
+//! Render scale resolve.
+//!
+//! The viewport renders at a fraction (or multiple) of its display size;
+//! this pass draws that image into the display-sized texture egui shows,
+//! with a Catmull-Rom filter below 100% and a box downsample above it.
+
+use super::lens_fx::{fullscreen_pipeline, sampler_entry, texture_entry};
+use super::Renderer;
+
+/// Upscale/downsample pipelines and their shared sampler
+pub struct UpscalePipeline {
+    pub upscale_pipeline: wgpu::RenderPipeline,
+    pub downsample_pipeline: wgpu::RenderPipeline,
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    pub sampler: wgpu::Sampler,
+}
+
+/// Create the resolve pipelines targeting the viewport format
+pub fn create_upscale_pipeline(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> UpscalePipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("upscale_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/upscale.wgsl").into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("upscale_bind_group_layout"),
+        entries: &[
+            texture_entry(0, wgpu::TextureSampleType::Float { filterable: true }),  // Scaled frame
+            sampler_entry(1),
+        ],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("upscale_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let upscale_pipeline =
+        fullscreen_pipeline(device, "upscale_pipeline", &layout, &shader, "fs_upscale", surface_format);
+    let downsample_pipeline =
+        fullscreen_pipeline(device, "downsample_pipeline", &layout, &shader, "fs_downsample", surface_format);
+
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("upscale_sampler"),
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+
+    UpscalePipeline { upscale_pipeline, downsample_pipeline, bind_group_layout, sampler }
+}
+
+impl Renderer {
+    /// Resolve a frame rendered at `src_size` into `dst` at `dst_size`.
+    ///
+    /// Called by the viewport after [`Renderer::render`] when the render
+    /// scale is not 100%.
+    pub fn resolve_scaled(
+        &self,
+        src: &wgpu::TextureView,
+        src_size: (u32, u32),
+        dst: &wgpu::TextureView,
+        dst_size: (u32, u32),
+    ) {
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("upscale_bind_group"),
+            layout: &self.upscale_pipeline.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(src) },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.upscale_pipeline.sampler),
+                },
+            ],
+        });
+        let pipeline = if src_size.0 > dst_size.0 {
+            &self.upscale_pipeline.downsample_pipeline
+        } else {
+            &self.upscale_pipeline.upscale_pipeline
+        };
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("upscale_encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("upscale_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: dst,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+    }
+}
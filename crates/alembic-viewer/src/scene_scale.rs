@@ -0,0 +1,120 @@
+//! Scene unit scale detection
+//!
+//! Alembic has no standard unit, so the scale of a cache is inferred on load:
+//! a unit written into the archive metadata by the exporter when present,
+//! otherwise just the size of the scene bounds. The result drives the orbit
+//! camera's clip planes and zoom range and the grid step, so centimeter and
+//! meter caches both open framed and unclipped.
+
+use alembic::core::MetaData;
+use serde::{Deserialize, Serialize};
+
+/// Archive metadata keys exporters use for the linear unit
+const UNIT_KEYS: [&str; 5] = ["linearUnit", "linearUnits", "unit", "units", "sceneUnit"];
+
+/// Linear unit of the scene (`Auto` = detect from the archive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SceneUnit {
+    #[default]
+    Auto,
+    Millimeter,
+    Centimeter,
+    Meter,
+    Inch,
+    Foot,
+}
+
+impl SceneUnit {
+    pub const ALL: [SceneUnit; 6] = [
+        SceneUnit::Auto,
+        SceneUnit::Millimeter,
+        SceneUnit::Centimeter,
+        SceneUnit::Meter,
+        SceneUnit::Inch,
+        SceneUnit::Foot,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SceneUnit::Auto => "Auto",
+            SceneUnit::Millimeter => "mm",
+            SceneUnit::Centimeter => "cm",
+            SceneUnit::Meter => "m",
+            SceneUnit::Inch => "in",
+            SceneUnit::Foot => "ft",
+        }
+    }
+
+    /// Meters per scene unit (`None` for `Auto`)
+    pub fn meters(&self) -> Option<f32> {
+        match self {
+            SceneUnit::Auto => None,
+            SceneUnit::Millimeter => Some(0.001),
+            SceneUnit::Centimeter => Some(0.01),
+            SceneUnit::Meter => Some(1.0),
+            SceneUnit::Inch => Some(0.0254),
+            SceneUnit::Foot => Some(0.3048),
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mm" | "millimeter" | "millimeters" | "millimetre" | "millimetres" => Some(SceneUnit::Millimeter),
+            "cm" | "centimeter" | "centimeters" | "centimetre" | "centimetres" => Some(SceneUnit::Centimeter),
+            "m" | "meter" | "meters" | "metre" | "metres" => Some(SceneUnit::Meter),
+            "in" | "inch" | "inches" => Some(SceneUnit::Inch),
+            "ft" | "foot" | "feet" => Some(SceneUnit::Foot),
+            _ => None,
+        }
+    }
+
+    /// Unit recorded in archive metadata: a unit name under one of the common
+    /// keys, or a USD-style `metersPerUnit` matching one of the known units.
+    pub fn from_metadata(md: &MetaData) -> Option<Self> {
+        if let Some(unit) = UNIT_KEYS.iter().find_map(|k| md.get(k).and_then(Self::parse)) {
+            return Some(unit);
+        }
+        let mpu: f32 = md.get("metersPerUnit")?.trim().parse().ok()?;
+        Self::ALL.into_iter().find(|u| u.meters().is_some_and(|m| (m - mpu).abs() <= m * 1e-3))
+    }
+}
+
+/// Detected scale of the loaded scene
+#[derive(Debug, Clone, Copy)]
+pub struct SceneScale {
+    /// Resolved unit; `Auto` when neither metadata nor the user named one
+    pub unit: SceneUnit,
+    /// Bounding sphere radius in scene units (0 = empty scene)
+    pub radius: f32,
+}
+
+impl Default for SceneScale {
+    fn default() -> Self {
+        Self { unit: SceneUnit::Auto, radius: 0.0 }
+    }
+}
+
+impl SceneScale {
+    /// Resolve the scale from a user override, the archive metadata and the
+    /// scene bounds radius.
+    pub fn detect(unit_override: SceneUnit, archive_meta: Option<&MetaData>, radius: f32) -> Self {
+        let unit = match unit_override {
+            SceneUnit::Auto => archive_meta.and_then(SceneUnit::from_metadata).unwrap_or(SceneUnit::Auto),
+            unit => unit,
+        };
+        Self { unit, radius: if radius.is_finite() { radius.max(0.0) } else { 0.0 } }
+    }
+
+    /// Scene units per meter, so grid steps land on 10^n meters (1 if unknown)
+    pub fn units_per_meter(&self) -> f32 {
+        self.unit.meters().map_or(1.0, |m| 1.0 / m)
+    }
+
+    /// Scene radius for display, e.g. "523.4 cm" or "523.4 units"
+    pub fn describe(&self) -> String {
+        match self.unit {
+            SceneUnit::Auto => format!("{:.4} units", self.radius),
+            unit => format!("{:.4} {}", self.radius, unit.as_str()),
+        }
+    }
+}
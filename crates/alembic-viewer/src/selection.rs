@@ -0,0 +1,107 @@
+//! Multi-object selection, named selection sets and hiding
+//!
+//! Objects are identified by full archive path. Selecting or hiding a path
+//! also covers everything below it, so picking an Xform group acts on all
+//! its shapes.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Named list of object paths (persisted per archive in settings)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectionSet {
+    pub name: String,
+    pub paths: Vec<String>,
+}
+
+/// True if `path` is `ancestor` or lies below it
+pub fn is_under(path: &str, ancestor: &str) -> bool {
+    match path.strip_prefix(ancestor) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || ancestor.ends_with('/'),
+        None => false,
+    }
+}
+
+/// True if any path in `roots` covers `path`
+pub fn covered_by<'a>(path: &str, roots: impl IntoIterator<Item = &'a String>) -> bool {
+    roots.into_iter().any(|root| is_under(path, root))
+}
+
+/// Current selection and hidden objects
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Selected object paths
+    pub selected: BTreeSet<String>,
+    /// Hidden object paths (hide covers descendants)
+    pub hidden: BTreeSet<String>,
+}
+
+impl Selection {
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.selected.contains(path)
+    }
+
+    /// Replace the selection with a single path
+    pub fn select_only(&mut self, path: &str) {
+        self.selected.clear();
+        self.selected.insert(path.to_string());
+    }
+
+    /// Add or remove a path from the selection
+    pub fn toggle(&mut self, path: &str) {
+        if !self.selected.remove(path) {
+            self.selected.insert(path.to_string());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Select the objects in `all` not covered by the current selection
+    pub fn invert<'a>(&mut self, all: impl IntoIterator<Item = &'a str>) {
+        let inverted = all
+            .into_iter()
+            .filter(|p| !covered_by(p, &self.selected))
+            .map(str::to_string)
+            .collect();
+        self.selected = inverted;
+    }
+
+    /// Hide the selected objects. Returns the number of paths added.
+    pub fn hide_selected(&mut self) -> usize {
+        let before = self.hidden.len();
+        self.hidden.extend(self.selected.iter().cloned());
+        self.hidden.len() - before
+    }
+
+    pub fn unhide_all(&mut self) {
+        self.hidden.clear();
+    }
+
+    pub fn is_hidden(&self, path: &str) -> bool {
+        covered_by(path, &self.hidden)
+    }
+
+    /// Snapshot the selection as a named set
+    pub fn to_set(&self, name: &str) -> SelectionSet {
+        SelectionSet {
+            name: name.to_string(),
+            paths: self.selected.iter().cloned().collect(),
+        }
+    }
+
+    /// Select exactly the paths of `set`
+    pub fn apply_set(&mut self, set: &SelectionSet) {
+        self.selected = set.paths.iter().cloned().collect();
+    }
+}
@@ -0,0 +1,588 @@
+//! Persistent application settings
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use super::bookmarks::CameraBookmark;
+use super::frame_stats::StatMetric;
+use super::keymap::KeymapPreset;
+use super::scene_scale::SceneUnit;
+use super::selection::SelectionSet;
+
+/// Hover highlight mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HoverMode {
+    #[default]
+    None,
+    Outline,
+    Tint,
+    Both,
+}
+
+impl HoverMode {
+    pub const ALL: [HoverMode; 4] = [
+        HoverMode::None,
+        HoverMode::Outline,
+        HoverMode::Tint,
+        HoverMode::Both,
+    ];
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HoverMode::None => "None",
+            HoverMode::Outline => "Outline",
+            HoverMode::Tint => "Tint",
+            HoverMode::Both => "Both",
+        }
+    }
+}
+
+/// Global shading mode for QC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DisplayMode {
+    /// Assigned materials (and Materialize heuristics)
+    #[default]
+    Materials,
+    /// Uniform gray
+    Clay,
+    /// Random color per object
+    RandomObject,
+    /// Random color per FaceSet, gray for faces in no set
+    RandomFaceSet,
+    /// UV checker on clay
+    UvChecker,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 5] = [
+        DisplayMode::Materials,
+        DisplayMode::Clay,
+        DisplayMode::RandomObject,
+        DisplayMode::RandomFaceSet,
+        DisplayMode::UvChecker,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisplayMode::Materials => "Materials",
+            DisplayMode::Clay => "Clay",
+            DisplayMode::RandomObject => "Random per object",
+            DisplayMode::RandomFaceSet => "Random per FaceSet",
+            DisplayMode::UvChecker => "UV checker",
+        }
+    }
+}
+
+/// Named bundle of path tracer quality settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PtPreset {
+    pub name: String,
+    pub max_samples: u32,
+    pub max_bounces: u32,
+    pub max_transmission_depth: u32,
+    pub dof_enabled: bool,
+    pub denoise: bool,
+    pub resolution_scale: f32,
+}
+
+impl Default for PtPreset {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            max_samples: 512,
+            max_bounces: 4,
+            max_transmission_depth: 8,
+            dof_enabled: false,
+            denoise: false,
+            resolution_scale: 1.0,
+        }
+    }
+}
+
+impl PtPreset {
+    /// Interactive, Preview and Final
+    pub fn builtin() -> Vec<PtPreset> {
+        vec![
+            PtPreset {
+                name: "Interactive".into(),
+                max_samples: 64,
+                max_bounces: 2,
+                max_transmission_depth: 4,
+                dof_enabled: false,
+                denoise: true,
+                resolution_scale: 0.5,
+            },
+            PtPreset {
+                name: "Preview".into(),
+                max_samples: 256,
+                max_bounces: 4,
+                max_transmission_depth: 8,
+                dof_enabled: false,
+                denoise: true,
+                resolution_scale: 0.75,
+            },
+            PtPreset {
+                name: "Final".into(),
+                max_samples: 2048,
+                max_bounces: 8,
+                max_transmission_depth: 12,
+                dof_enabled: true,
+                denoise: false,
+                resolution_scale: 1.0,
+            },
+        ]
+    }
+
+    /// Capture the current path tracer settings under `name`
+    pub fn from_settings(name: &str, settings: &Settings) -> Self {
+        Self {
+            name: name.to_string(),
+            max_samples: settings.pt_max_samples,
+            max_bounces: settings.pt_max_bounces,
+            max_transmission_depth: settings.pt_max_transmission_depth,
+            dof_enabled: settings.pt_dof_enabled,
+            denoise: settings.pt_denoise,
+            resolution_scale: settings.pt_resolution_scale,
+        }
+    }
+}
+
+/// Application settings that persist between sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // Display
+    pub show_grid: bool,
+    pub show_wireframe: bool,
+    pub flat_shading: bool,
+    pub show_shadows: bool,
+    pub ssao: bool,
+    pub ssao_strength: f32,
+    pub ssao_radius: f32,
+    pub motion_blur: bool,
+    pub motion_blur_shutter: f32,  // shutter angle, degrees
+    pub motion_blur_samples: u32,
+    pub dof: bool,
+    pub dof_f_stop: f32,  // orbit camera lens; scene cameras use their own
+    pub dof_max_blur: f32,  // CoC clamp, pixels
+    pub bloom: bool,
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    pub xray_alpha: f32,
+    pub double_sided: bool,
+    pub auto_normals: bool,
+    pub smooth_normals: bool,
+    pub smooth_angle: f32,  // 0-180 degrees
+    pub show_floor: bool,
+    pub background_color: [f32; 4],
+    
+    // Window
+    pub window_width: f32,
+    pub window_height: f32,
+    pub window_x: Option<f32>,
+    pub window_y: Option<f32>,
+    
+    // Camera
+    pub camera_distance: f32,
+    pub camera_yaw: f32,
+    pub camera_pitch: f32,
+
+    // Scene scale: clip planes, zoom range and grid follow the loaded scene
+    pub auto_clip: bool,
+    pub clip_near: f32,           // used when auto_clip is off
+    pub clip_far: f32,
+    pub scene_unit: SceneUnit,    // Auto = archive metadata, else unitless
+    pub grid_step: Option<f32>,   // None = adaptive
+    pub frame_on_load: bool,      // reframe when the scene is far off the current view scale
+    
+    // Last opened file
+    pub last_file: Option<PathBuf>,
+    
+    // Recent files (most recent first, max 10)
+    pub recent_files: Vec<PathBuf>,
+    
+    // Environment
+    pub hdr_enabled: bool,
+    pub hdr_visible: bool,
+    pub hdr_exposure: f32,
+    pub last_hdr_file: Option<PathBuf>,
+    /// Meter the HDR environment to set the exposure
+    pub auto_exposure: bool,
+    /// Exposure compensation in stops (on top of auto exposure)
+    pub exposure_ev: f32,
+    /// White balance: color temperature to neutralize (Kelvin, 6500 = off)
+    pub white_balance: f32,
+    /// White balance tint: green (-1) to magenta (+1)
+    pub white_balance_tint: f32,
+    
+    // Anti-aliasing (requires restart)
+    pub antialiasing: u8,
+    
+    // UI layout
+    pub hierarchy_panel_width: f32,
+    pub side_panel_width: f32,
+    
+    // Playback
+    pub playback_fps: f32,
+    /// Blend Xforms between samples while playing (rotation slerped), for
+    /// smooth motion at display rate or in slow motion
+    pub interpolate_playback: bool,
+    
+    // Lighting
+    pub use_scene_lights: bool,
+    
+    // Path tracing
+    pub path_tracing: bool,
+    pub pt_max_bounces: u32,
+    pub pt_max_samples: u32,
+    pub pt_samples_per_update: u32,
+    pub pt_target_fps: f32,       // Target FPS for auto SPP mode
+    pub pt_auto_spp: bool,        // Auto-adjust samples per frame to hit target FPS
+    pub pt_camera_snap: bool,     // Snap camera at target FPS intervals (prevents ghost samples)
+    pub pt_max_transmission_depth: u32,
+    pub pt_dof_enabled: bool,
+    pub pt_aperture: f32,       // Aperture radius in world units
+    pub pt_focus_distance: f32, // Focus distance in world units
+    pub pt_global_opacity: f32, // Global opacity multiplier (0-1)
+    pub pt_shadow_catcher: bool,  // Floor only catches shadows, shows the background
+    pub pt_split_view: bool,      // Path tracer | raster side by side
+    pub pt_split_position: f32,   // Divider position (fraction of width)
+    pub pt_denoise: bool,         // Edge-preserving filter on the PT output
+    pub pt_resolution_scale: f32, // PT resolution relative to the viewport (0.25-1)
+    pub pt_preset: String,        // Last applied preset ("" = custom)
+    pub pt_presets: Vec<PtPreset>,
+
+    // Material randomization
+    pub materialize_missing: bool,
+    pub display_mode: DisplayMode,
+    
+    // Hover highlighting
+    pub hover_mode: HoverMode,
+    pub hover_outline_thickness: f32,
+    pub hover_outline_alpha: f32,
+
+    // Auto turntable
+    pub turntable_enabled: bool,
+    pub turntable_speed: f32,  // degrees per second
+
+    // Performance overlay (draw calls, triangles, per-pass GPU timings)
+    pub show_perf_overlay: bool,
+
+    // Render resolution for raster and PT, optionally driven by frame time
+    pub render_scale: f32,           // Relative to the viewport (0.5-2); ceiling when dynamic
+    pub dynamic_resolution: bool,    // Lower the scale to hold dynamic_resolution_fps
+    pub dynamic_resolution_fps: f32,
+
+    // Particle trails for Points objects
+    pub show_particle_trails: bool,
+    pub trail_length: u32,        // frames
+    pub trail_color_by_speed: bool,
+
+    // Rest pose (meshes with Pref)
+    pub show_rest_pose: bool,
+    pub rest_pose_blend: f32,     // 0 = animated, 1 = rest
+
+    // Named camera viewpoints
+    pub camera_bookmarks: Vec<CameraBookmark>,
+
+    // Named selection sets, keyed by archive path
+    pub selection_sets: HashMap<String, Vec<SelectionSet>>,
+
+    // Keymap preset and per-action overrides (action id -> "Ctrl+H" chords)
+    pub keymap_preset: KeymapPreset,
+    pub key_bindings: BTreeMap<String, Vec<String>>,
+
+    // SpaceMouse / gamepad navigation
+    pub spacemouse_enabled: bool,
+    pub gamepad_enabled: bool,
+    pub nav_device_speed: f32,
+
+    // Per-frame statistics graph under the timeline
+    pub show_stats_graph: bool,
+    pub stats_graph_metrics: Vec<StatMetric>,
+
+    /// Values replaced by launch flags for this session only (see
+    /// [`Settings::apply_session_overrides`]); never written to disk.
+    #[serde(skip)]
+    session_overrides: Option<SessionOverrides>,
+}
+
+/// Settings as loaded and as overridden at launch, one JSON value per field.
+#[derive(Debug, Clone)]
+struct SessionOverrides {
+    persisted: serde_json::Map<String, serde_json::Value>,
+    applied: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_grid: true,
+            show_wireframe: false,
+            flat_shading: false,
+            show_shadows: true,
+            ssao: false,
+            ssao_strength: 0.5,
+            ssao_radius: 0.015,
+            motion_blur: false,
+            motion_blur_shutter: 180.0,
+            motion_blur_samples: 12,
+            dof: false,
+            dof_f_stop: 2.8,
+            dof_max_blur: 24.0,
+            bloom: false,
+            bloom_threshold: 0.8,
+            bloom_intensity: 0.5,
+            xray_alpha: 1.0,
+            double_sided: true,
+            auto_normals: true,  // Auto-flip inverted normals by default
+            smooth_normals: true,
+            smooth_angle: 62.0,
+            show_floor: true,
+            background_color: [0.1, 0.1, 0.12, 1.0],
+            window_width: 1280.0,
+            window_height: 720.0,
+            window_x: None,
+            window_y: None,
+            camera_distance: 5.0,
+            camera_yaw: 0.0,
+            camera_pitch: 0.0,
+            auto_clip: true,
+            clip_near: 0.1,
+            clip_far: 10000.0,
+            scene_unit: SceneUnit::Auto,
+            grid_step: None,
+            frame_on_load: true,
+            last_file: None,
+            recent_files: Vec::new(),
+            hdr_enabled: true,
+            hdr_visible: true,
+            hdr_exposure: 1.0,
+            last_hdr_file: None,
+            auto_exposure: false,
+            exposure_ev: 0.0,
+            white_balance: 6500.0,
+            white_balance_tint: 0.0,
+            antialiasing: 4,
+            hierarchy_panel_width: 200.0,
+            side_panel_width: 200.0,
+            playback_fps: 24.0,
+            interpolate_playback: false,
+            use_scene_lights: false,
+            path_tracing: false,
+            pt_max_bounces: 4,
+            pt_max_samples: 512,
+            pt_samples_per_update: 1,
+            pt_target_fps: 30.0,
+            pt_auto_spp: false,
+            pt_camera_snap: true,  // On by default to prevent ghost samples
+            pt_max_transmission_depth: 8,
+            pt_dof_enabled: false,
+            pt_aperture: 0.1,
+            pt_focus_distance: 10.0,
+            pt_global_opacity: 1.0,
+            pt_shadow_catcher: false,
+            pt_split_view: false,
+            pt_split_position: 0.5,
+            pt_denoise: false,
+            pt_resolution_scale: 1.0,
+            pt_preset: String::new(),
+            pt_presets: PtPreset::builtin(),
+            materialize_missing: false,
+            display_mode: DisplayMode::Materials,
+            hover_mode: HoverMode::Outline,  // Default to outline hover
+            hover_outline_thickness: 2.0,
+            hover_outline_alpha: 1.0,
+            turntable_enabled: false,
+            turntable_speed: 15.0,  // 15 deg/sec = 24 sec per revolution
+            show_perf_overlay: false,
+            render_scale: 1.0,
+            dynamic_resolution: false,
+            dynamic_resolution_fps: 60.0,
+            show_particle_trails: false,
+            trail_length: 10,
+            trail_color_by_speed: true,
+            show_rest_pose: false,
+            rest_pose_blend: 1.0,
+            camera_bookmarks: Vec::new(),
+            selection_sets: HashMap::new(),
+            keymap_preset: KeymapPreset::default(),
+            key_bindings: BTreeMap::new(),
+            spacemouse_enabled: false,
+            gamepad_enabled: false,
+            nav_device_speed: 1.0,
+            show_stats_graph: true,
+            stats_graph_metrics: StatMetric::ALL.to_vec(),
+            session_overrides: None,
+        }
+    }
+}
+
+const MAX_RECENT_FILES: usize = 10;
+
+impl Settings {
+    /// Get settings file path
+    #[cfg(not(target_arch = "wasm32"))]
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|mut p| {
+            p.push("alembic-viewer");
+            std::fs::create_dir_all(&p).ok();
+            p.push("settings.json");
+            p
+        })
+    }
+
+    /// The browser build has no config directory: settings start from the
+    /// defaults and last for the page session
+    #[cfg(target_arch = "wasm32")]
+    fn path() -> Option<PathBuf> {
+        None
+    }
+
+    /// Load settings from file
+    pub fn load() -> Self {
+        let mut settings: Self = Self::path()
+            .and_then(|p| std::fs::read_to_string(&p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        
+        // Validate antialiasing values (8x requires TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        if !matches!(settings.antialiasing, 1 | 2 | 4 | 8) {
+            settings.antialiasing = 4;
+        }
+        
+        settings
+    }
+
+    /// Save settings to file
+    pub fn save(&self) {
+        if let Some(path) = Self::path() {
+            if let Ok(json) = serde_json::to_string_pretty(&self.to_persisted()) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+    }
+
+    /// Apply one-off changes (command-line flags) that last for this session
+    /// only: [`save`](Self::save) keeps writing the loaded values of the
+    /// fields `apply` changed, unless the user edits them afterwards.
+    pub fn apply_session_overrides(&mut self, apply: impl FnOnce(&mut Self)) {
+        let persisted = self.to_persisted();
+        apply(self);
+        let (serde_json::Value::Object(persisted), serde_json::Value::Object(applied)) = (persisted, self.to_persisted()) else {
+            return;
+        };
+        self.session_overrides = Some(SessionOverrides { persisted, applied });
+    }
+
+    /// JSON written by `save`: session overrides the user hasn't touched are
+    /// replaced by their loaded values.
+    fn to_persisted(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let (Some(overrides), serde_json::Value::Object(fields)) = (&self.session_overrides, &mut value) {
+            for (key, loaded) in &overrides.persisted {
+                let launched = overrides.applied.get(key);
+                if launched != Some(loaded) && fields.get(key) == launched {
+                    fields.insert(key.clone(), loaded.clone());
+                }
+            }
+        }
+        value
+    }
+    
+    /// Add file to recent files list (moves to top if already present)
+    pub fn add_recent(&mut self, path: PathBuf) {
+        // Remove if already in list
+        self.recent_files.retain(|p| p != &path);
+        
+        // Insert at front
+        self.recent_files.insert(0, path.clone());
+        
+        // Trim to max size
+        self.recent_files.truncate(MAX_RECENT_FILES);
+        
+        // Also update last_file
+        self.last_file = Some(path);
+    }
+    
+    /// Selection sets saved for an archive
+    pub fn selection_sets_for(&self, archive: &std::path::Path) -> &[SelectionSet] {
+        self.selection_sets
+            .get(archive.to_string_lossy().as_ref())
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Mutable selection sets for an archive (created on demand)
+    pub fn selection_sets_for_mut(&mut self, archive: &std::path::Path) -> &mut Vec<SelectionSet> {
+        self.selection_sets
+            .entry(archive.to_string_lossy().into_owned())
+            .or_default()
+    }
+
+    /// Path tracer preset by name (case-insensitive)
+    pub fn pt_preset(&self, name: &str) -> Option<&PtPreset> {
+        self.pt_presets.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Copy a preset's values into the path tracer settings; false if unknown
+    pub fn apply_pt_preset(&mut self, name: &str) -> bool {
+        let Some(preset) = self.pt_preset(name).cloned() else { return false };
+        self.pt_max_samples = preset.max_samples;
+        self.pt_max_bounces = preset.max_bounces;
+        self.pt_max_transmission_depth = preset.max_transmission_depth;
+        self.pt_dof_enabled = preset.dof_enabled;
+        self.pt_denoise = preset.denoise;
+        self.pt_resolution_scale = preset.resolution_scale;
+        self.pt_preset = preset.name;
+        true
+    }
+
+    /// Store the current path tracer settings as preset `name` (replacing
+    /// a preset of the same name)
+    pub fn save_pt_preset(&mut self, name: &str) {
+        let preset = PtPreset::from_settings(name, self);
+        match self.pt_presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(name)) {
+            Some(existing) => *existing = preset,
+            None => self.pt_presets.push(preset),
+        }
+        self.pt_preset = name.to_string();
+    }
+
+    /// Get recent files (filters out non-existent)
+    pub fn recent_files(&self) -> Vec<&PathBuf> {
+        self.recent_files.iter().filter(|p| p.exists()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_overrides_are_not_persisted() {
+        let mut settings = Settings { show_grid: true, path_tracing: false, ..Default::default() };
+        settings.apply_session_overrides(|s| {
+            s.show_grid = false;
+            s.path_tracing = true;
+        });
+        assert!(!settings.show_grid);
+        let saved = settings.to_persisted();
+        assert_eq!(saved["show_grid"], true);
+        assert_eq!(saved["path_tracing"], false);
+
+        // Fields the user changes afterwards are saved as edited
+        settings.path_tracing = false;
+        settings.show_wireframe = !settings.show_wireframe;
+        let saved = settings.to_persisted();
+        assert_eq!(saved["show_grid"], true);
+        assert_eq!(saved["show_wireframe"], settings.show_wireframe);
+        settings.show_grid = true;
+        assert_eq!(settings.to_persisted()["show_grid"], true);
+        // Loading a saved file starts without overrides
+        let reloaded: Settings = serde_json::from_value(saved).unwrap();
+        assert!(reloaded.session_overrides.is_none());
+    }
+}
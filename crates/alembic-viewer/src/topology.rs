@@ -0,0 +1,109 @@
+//! Topology change analysis for timeline navigation
+//!
+//! Compares the stored sample digests of topology properties (face counts /
+//! indices, curve vertex counts) and point counts between consecutive samples.
+//! Only digests and array lengths are read, so the scan stays cheap even for
+//! long caches; objects are scanned in parallel.
+
+use std::collections::BTreeMap;
+
+use alembic::abc::{IArchive, IObject};
+use alembic::geom::{CURVES_SCHEMA, POINTS_SCHEMA, POLYMESH_SCHEMA, SUBD_SCHEMA};
+
+/// Frames where at least one object changed topology
+#[derive(Debug, Clone, Default)]
+pub struct TopologyChanges {
+    /// Frame -> full names of objects whose topology differs from the previous frame
+    changes: BTreeMap<usize, Vec<String>>,
+}
+
+impl TopologyChanges {
+    /// Scan all meshes, subds, curves and points in the archive
+    pub fn analyze(archive: &IArchive) -> Self {
+        let mut changes: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (path, frames) in archive.getTop().collect_par(&scan_object) {
+            for frame in frames {
+                changes.entry(frame).or_default().push(path.clone());
+            }
+        }
+        Self { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of frames with a topology change
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// All frames with a topology change, ascending
+    pub fn frames(&self) -> impl Iterator<Item = usize> + '_ {
+        self.changes.keys().copied()
+    }
+
+    /// Objects that changed topology at `frame` (empty if none)
+    pub fn objects_at(&self, frame: usize) -> &[String] {
+        self.changes.get(&frame).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// First change strictly after `frame`
+    pub fn next_after(&self, frame: usize) -> Option<usize> {
+        self.changes.range(frame + 1..).next().map(|(&f, _)| f)
+    }
+
+    /// Last change strictly before `frame`
+    pub fn prev_before(&self, frame: usize) -> Option<usize> {
+        self.changes.range(..frame).next_back().map(|(&f, _)| f)
+    }
+}
+
+/// Full name and changed frames of one object (None if it has no topology or never changes)
+fn scan_object(obj: &IObject) -> Option<(String, Vec<usize>)> {
+    let schema = obj.getMetaData().get("schema").unwrap_or_default();
+    let props: &[&str] = match schema {
+        POLYMESH_SCHEMA | SUBD_SCHEMA => &[".faceCounts", ".faceIndices"],
+        CURVES_SCHEMA => &["nVertices"],
+        POINTS_SCHEMA => &["P"],
+        _ => return None,
+    };
+    let frames = changed_frames(obj, props, schema == POINTS_SCHEMA);
+    (!frames.is_empty()).then(|| (obj.getFullName().to_string(), frames))
+}
+
+/// Sample indices whose topology signature differs from the previous sample.
+/// For points the signature is the point count, otherwise the sample digests.
+fn changed_frames(obj: &IObject, names: &[&str], by_len: bool) -> Vec<usize> {
+    let props = obj.getProperties();
+    let Some(geom_prop) = props.getPropertyByName(".geom") else { return Vec::new() };
+    let Some(geom) = geom_prop.asCompound() else { return Vec::new() };
+
+    let mut frames = Vec::new();
+    for name in names {
+        let Some(prop) = geom.getPropertyByName(name) else { continue };
+        let Some(array) = prop.asArray() else { continue };
+        let signature = |i: usize| -> Option<[u8; 16]> {
+            if by_len {
+                array.getSampleLen(i).ok().map(|n| {
+                    let mut sig = [0u8; 16];
+                    sig[..8].copy_from_slice(&(n as u64).to_le_bytes());
+                    sig
+                })
+            } else {
+                array.getKey(i).ok()
+            }
+        };
+        let mut prev = signature(0);
+        for i in 1..array.getNumSamples() {
+            let cur = signature(i);
+            if cur != prev {
+                frames.push(i);
+            }
+            prev = cur;
+        }
+    }
+    frames.sort_unstable();
+    frames.dedup();
+    frames
+}
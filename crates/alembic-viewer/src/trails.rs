@@ -0,0 +1,177 @@
+//! Particle trails for Points objects
+//!
+//! Particles are matched between consecutive samples by id (falling back to
+//! point index when ids are missing and the count is unchanged), producing
+//! world-space segments for the last `length` frames. Segments carry the
+//! per-frame displacement as speed so the overlay can color them.
+
+use std::collections::HashMap;
+
+use glam::{Mat4, Vec3};
+
+use alembic::abc::{IArchive, IObject};
+use alembic::geom::{IPoints, IXform, PointsSample};
+
+/// Upper bound on trails per object to keep the overlay responsive
+const MAX_TRAILS_PER_OBJECT: usize = 5000;
+
+/// One trail segment between two consecutive frames
+#[derive(Debug, Clone, Copy)]
+pub struct TrailSegment {
+    pub from: Vec3,
+    pub to: Vec3,
+    /// Displacement length over the frame (world units per frame)
+    pub speed: f32,
+    /// 0 = newest segment, 1 = oldest
+    pub age: f32,
+}
+
+/// Trail segments for all Points objects at one frame
+#[derive(Debug, Clone, Default)]
+pub struct ParticleTrails {
+    pub segments: Vec<TrailSegment>,
+    /// Largest segment speed, for color normalization
+    pub max_speed: f32,
+}
+
+impl ParticleTrails {
+    /// Build trails ending at `frame`, reaching back `length` frames
+    pub fn build(archive: &IArchive, frame: usize, length: usize) -> Self {
+        let mut trails = Self::default();
+        if length == 0 || frame == 0 {
+            return trails;
+        }
+        let first = frame.saturating_sub(length);
+        let frames: Vec<usize> = (first..=frame).collect();
+        let parents = vec![Mat4::IDENTITY; frames.len()];
+        for child in archive.getTop().getChildren() {
+            trails.scan(&child, &frames, &parents);
+        }
+        trails.max_speed = trails.segments.iter().map(|s| s.speed).fold(0.0, f32::max);
+        trails
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Draw segments over the viewport `rect` using the camera `view_proj`
+    pub fn paint(&self, painter: &egui::Painter, view_proj: Mat4, rect: egui::Rect, color_by_speed: bool) {
+        let project = |p: Vec3| -> Option<egui::Pos2> {
+            let clip = view_proj * p.extend(1.0);
+            if clip.w <= 1e-6 {
+                return None;
+            }
+            let ndc = clip.truncate() / clip.w;
+            Some(egui::pos2(
+                rect.min.x + (ndc.x * 0.5 + 0.5) * rect.width(),
+                rect.min.y + (0.5 - ndc.y * 0.5) * rect.height(),
+            ))
+        };
+        let inv_max = if self.max_speed > 0.0 { 1.0 / self.max_speed } else { 0.0 };
+        for seg in &self.segments {
+            let (Some(a), Some(b)) = (project(seg.from), project(seg.to)) else { continue };
+            let rgb = if color_by_speed {
+                speed_color(seg.speed * inv_max)
+            } else {
+                [0.3, 0.8, 0.9]
+            };
+            let alpha = ((1.0 - seg.age) * 0.8 + 0.2) * 255.0;
+            let color = egui::Color32::from_rgba_unmultiplied(
+                (rgb[0] * 255.0) as u8,
+                (rgb[1] * 255.0) as u8,
+                (rgb[2] * 255.0) as u8,
+                alpha as u8,
+            );
+            painter.line_segment([a, b], egui::Stroke::new(1.5, color));
+        }
+    }
+
+    fn scan(&mut self, obj: &IObject, frames: &[usize], parents: &[Mat4]) {
+        let world = world_transforms(obj, frames, parents);
+
+        if let Some(points) = IPoints::new(obj) {
+            let n = points.getNumSamples();
+            if n > 1 {
+                let samples: Vec<Option<PointsSample>> = frames
+                    .iter()
+                    .map(|&f| points.getSample(f.min(n - 1)).ok())
+                    .collect();
+                self.add_object(&samples, &world);
+            }
+        }
+
+        for child in obj.getChildren() {
+            self.scan(&child, frames, &world);
+        }
+    }
+
+    /// Segments between consecutive samples (oldest first in `samples`)
+    fn add_object(&mut self, samples: &[Option<PointsSample>], world: &[Mat4]) {
+        let steps = samples.len() - 1;
+        for step in 0..steps {
+            let (Some(prev), Some(cur)) = (&samples[step], &samples[step + 1]) else { continue };
+            let pairs = match_points(prev, cur);
+            let stride = pairs.len().div_ceil(MAX_TRAILS_PER_OBJECT).max(1);
+            let age = 1.0 - step as f32 / steps as f32;
+            for &(a, b) in pairs.iter().step_by(stride) {
+                let from = world[step].transform_point3(prev.positions[a]);
+                let to = world[step + 1].transform_point3(cur.positions[b]);
+                let speed = from.distance(to);
+                if speed.is_finite() {
+                    self.segments.push(TrailSegment { from, to, speed, age });
+                }
+            }
+        }
+    }
+}
+
+/// World transform of `obj` at each frame, given the parents' transforms
+fn world_transforms(obj: &IObject, frames: &[usize], parents: &[Mat4]) -> Vec<Mat4> {
+    let Some(xform) = IXform::new(obj) else { return parents.to_vec() };
+    let n = xform.getNumSamples();
+    if n == 0 {
+        return parents.to_vec();
+    }
+    frames
+        .iter()
+        .zip(parents)
+        .map(|(&f, &parent)| match xform.getSample(f.min(n - 1)) {
+            Ok(s) if s.inherits => parent * s.matrix(),
+            Ok(s) => s.matrix(),
+            Err(_) => parent,
+        })
+        .collect()
+}
+
+/// Index pairs (prev, cur) of the same particle in two samples
+fn match_points(prev: &PointsSample, cur: &PointsSample) -> Vec<(usize, usize)> {
+    if prev.has_ids() && cur.has_ids() {
+        let lookup: HashMap<u64, usize> = prev.id_to_index();
+        cur.ids
+            .iter()
+            .enumerate()
+            .filter_map(|(b, id)| lookup.get(id).map(|&a| (a, b)))
+            .filter(|&(a, b)| a < prev.positions.len() && b < cur.positions.len())
+            .collect()
+    } else if prev.num_points() == cur.num_points() {
+        (0..cur.num_points()).map(|i| (i, i)).collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Blue -> green -> yellow -> red ramp for normalized speed `t` in 0..1
+pub fn speed_color(t: f32) -> [f32; 3] {
+    let t = t.clamp(0.0, 1.0);
+    if t < 1.0 / 3.0 {
+        let k = t * 3.0;
+        [0.1, 0.3 + 0.6 * k, 1.0 - 0.8 * k]
+    } else if t < 2.0 / 3.0 {
+        let k = (t - 1.0 / 3.0) * 3.0;
+        [0.1 + 0.9 * k, 0.9, 0.2]
+    } else {
+        let k = (t - 2.0 / 3.0) * 3.0;
+        [1.0, 0.9 - 0.75 * k, 0.2 - 0.1 * k]
+    }
+}
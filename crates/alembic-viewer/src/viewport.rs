@@ -3,6 +3,8 @@
 use egui::{Response, Sense, Ui, Vec2};
 
 use super::camera::OrbitCamera;
+use super::keymap::Keymap;
+use super::render_scale::{self, DynamicResolution};
 use super::renderer::Renderer;
 
 /// Scene camera override parameters
@@ -20,6 +22,8 @@ pub struct Viewport {
     pub renderer: Option<Renderer>,
     texture_id: Option<egui::TextureId>,
     render_texture: Option<RenderTexture>,
+    /// Display-size resolve target when the render scale is not 100%
+    display_texture: Option<RenderTexture>,
     last_size: Vec2,
     /// Optional scene camera override
     pub scene_camera: Option<SceneCameraOverride>,
@@ -31,13 +35,25 @@ pub struct Viewport {
     pub hover_position: Option<(f32, f32)>,
     /// True if user moved the camera this frame (for turntable pause)
     pub camera_moved_by_user: bool,
-    /// Last render time for FPS throttling
-    last_render_time: std::time::Instant,
+    /// Last render time (frame interval for dynamic resolution)
+    last_render_time: web_time::Instant,
     /// Render FPS tracking
     render_count: u32,
-    render_count_start: std::time::Instant,
+    render_count_start: web_time::Instant,
     /// Current render FPS (updated every second)
     pub render_fps: f32,
+    /// Draw performance overlay (draw calls, triangles, GPU pass timings)
+    pub show_perf_overlay: bool,
+    /// View-projection and screen rect of the last rendered frame (for overlays)
+    pub last_view_proj: Option<(glam::Mat4, egui::Rect)>,
+    /// Mouse navigation and keyboard bindings
+    pub keymap: Keymap,
+    /// Render resolution relative to the viewport (0.5-2); the ceiling when dynamic
+    pub render_scale: f32,
+    /// Lower the render scale to hold `dynamic_resolution_fps`
+    pub dynamic_resolution: bool,
+    pub dynamic_resolution_fps: f32,
+    dynamic: DynamicResolution,
 }
 
 struct RenderTexture {
@@ -54,16 +70,24 @@ impl Viewport {
             renderer: None,
             texture_id: None,
             render_texture: None,
+            display_texture: None,
             last_size: Vec2::ZERO,
             scene_camera: None,
             pending_focus_pick: None,
             pending_object_pick: None,
             hover_position: None,
             camera_moved_by_user: false,
-            last_render_time: std::time::Instant::now(),
+            last_render_time: web_time::Instant::now(),
             render_count: 0,
-            render_count_start: std::time::Instant::now(),
+            render_count_start: web_time::Instant::now(),
             render_fps: 0.0,
+            show_perf_overlay: false,
+            last_view_proj: None,
+            keymap: Keymap::default(),
+            render_scale: 1.0,
+            dynamic_resolution: false,
+            dynamic_resolution_fps: 60.0,
+            dynamic: DynamicResolution::new(1.0),
         }
     }
 
@@ -120,6 +144,7 @@ impl Viewport {
                     (self.camera.view_proj_matrix(aspect), self.camera.view_matrix(), self.camera.position())
                 };
                 
+                self.last_view_proj = Some((view_proj, rect));
                 if let Some(renderer) = &mut self.renderer {
                     renderer.update_camera(view_proj, view, position);
                     // Update shadow map for key light direction
@@ -129,18 +154,24 @@ impl Viewport {
                 }
 
                 // Ensure render texture exists and is correct size
-                self.ensure_render_texture(render_state, width, height);
+                let scale = self.next_render_scale();
+                let render_size = render_scale::scaled_size(width, height, scale);
+                self.ensure_render_texture(render_state, (width, height), render_size);
 
-                // Render scene
+                // Render scene (at the scaled size, then resolved to display size)
                 if let (Some(renderer), Some(rt)) = (&mut self.renderer, &self.render_texture) {
-                    renderer.render(&rt.view, width, height, self.camera.distance, self.camera.near(), self.camera.far());
+                    let (rw, rh) = rt.size;
+                    renderer.render(&rt.view, rw, rh, self.camera.distance, self.camera.near(), self.camera.far());
+                    if let Some(display) = &self.display_texture {
+                        renderer.resolve_scaled(&rt.view, rt.size, &display.view, display.size);
+                    }
                     
                     // Track render FPS
                     self.render_count += 1;
                     if self.render_count_start.elapsed().as_secs_f32() >= 1.0 {
                         self.render_fps = self.render_count as f32;
                         self.render_count = 0;
-                        self.render_count_start = std::time::Instant::now();
+                        self.render_count_start = web_time::Instant::now();
                     }
                     
                     // Poll for hover pick result (must be after render submits GPU commands)
@@ -158,6 +189,16 @@ impl Viewport {
                         egui::Color32::WHITE,
                     );
                 }
+
+                if let Some(renderer) = self.renderer.as_ref().filter(|r| r.pt_split_active()) {
+                    Self::draw_split_divider(ui, rect, renderer.pt_split_position);
+                }
+
+                if self.show_perf_overlay {
+                    if let Some(renderer) = &self.renderer {
+                        self.draw_perf_overlay(ui, rect, renderer);
+                    }
+                }
             }
         } else {
             // No renderer - draw placeholder
@@ -175,22 +216,101 @@ impl Viewport {
         response
     }
 
-    fn ensure_render_texture(&mut self, render_state: &egui_wgpu::RenderState, width: u32, height: u32) {
-        let needs_recreate = match &self.render_texture {
-            Some(rt) => rt.size != (width, height),
-            None => true,
-        };
+    /// Draw frame statistics in the top-left corner of the viewport.
+    fn draw_perf_overlay(&self, ui: &Ui, rect: egui::Rect, renderer: &Renderer) {
+        let stats = renderer.frame_stats();
+        let mut lines = vec![
+            format!("{:.0} fps", self.render_fps),
+            format!("Draw calls: {}", stats.draw_calls),
+            format!("Triangles:  {}", stats.triangles),
+        ];
+        if let Some((w, h)) = self.render_texture_size() {
+            let mode = if self.dynamic_resolution { " dynamic" } else { "" };
+            lines.push(format!("Resolution: {}x{} ({:.0}%{})", w, h, self.effective_render_scale() * 100.0, mode));
+        }
+        if renderer.gpu_timing_supported() {
+            for (pass, ms) in stats.timings() {
+                lines.push(format!("{:<12} {:6.2} ms", pass.as_str(), ms));
+            }
+            lines.push(format!("{:<12} {:6.2} ms", "GPU total", stats.total_gpu_ms()));
+        } else {
+            lines.push("GPU timings: unsupported".to_string());
+        }
+        let text = lines.join("\n");
+
+        let painter = ui.painter_at(rect);
+        let font = egui::FontId::monospace(12.0);
+        let galley = painter.layout_no_wrap(text, font, egui::Color32::from_rgb(220, 220, 220));
+        let pos = rect.min + egui::vec2(8.0, 8.0);
+        painter.rect_filled(
+            egui::Rect::from_min_size(pos, galley.size()).expand(4.0),
+            4.0,
+            egui::Color32::from_black_alpha(160),
+        );
+        painter.galley(pos, galley, egui::Color32::WHITE);
+    }
 
-        if !needs_recreate {
-            return;
+    /// Divider line and side labels for the PT | raster split view
+    fn draw_split_divider(ui: &Ui, rect: egui::Rect, position: f32) {
+        let painter = ui.painter_at(rect);
+        let x = rect.left() + rect.width() * position.clamp(0.0, 1.0);
+        painter.vline(x, rect.y_range(), egui::Stroke::new(1.5, egui::Color32::from_white_alpha(200)));
+
+        let font = egui::FontId::proportional(12.0);
+        let y = rect.bottom() - 8.0;
+        for (text, pos, align) in [
+            ("Path Tracer", egui::pos2(x - 8.0, y), egui::Align2::RIGHT_BOTTOM),
+            ("Raster", egui::pos2(x + 8.0, y), egui::Align2::LEFT_BOTTOM),
+        ] {
+            let galley = painter.layout_no_wrap(text.to_string(), font.clone(), egui::Color32::WHITE);
+            let label = align.anchor_size(pos, galley.size());
+            painter.rect_filled(label.expand(3.0), 3.0, egui::Color32::from_black_alpha(160));
+            painter.galley(label.min, galley, egui::Color32::WHITE);
+        }
+    }
+
+    /// Render scale for this frame: the fixed scale, or the dynamic
+    /// controller's pick from the last frame's cost
+    fn next_render_scale(&mut self) -> f32 {
+        let now = web_time::Instant::now();
+        let interval_ms = now.duration_since(self.last_render_time).as_secs_f32() * 1000.0;
+        self.last_render_time = now;
+
+        let max_scale = self.render_scale.clamp(render_scale::MIN_SCALE, render_scale::MAX_SCALE);
+        if !self.dynamic_resolution {
+            self.dynamic.reset(max_scale);
+            return max_scale;
+        }
+        let Some(renderer) = &self.renderer else {
+            return self.dynamic.scale();
+        };
+        // Path tracer frames are paced by samples per update; hold the scale
+        if renderer.use_path_tracing {
+            return self.dynamic.scale().min(max_scale);
         }
+        // GPU time when timestamps are available (excludes vsync waits)
+        let gpu_ms = renderer.gpu_timing_supported()
+            .then(|| renderer.frame_stats().total_gpu_ms())
+            .filter(|ms| *ms > 0.0);
+        let frame_ms = gpu_ms.unwrap_or(interval_ms);
+        self.dynamic.update(frame_ms, self.dynamic_resolution_fps, max_scale)
+    }
 
-        let device = &render_state.device;
-        let format = render_state.target_format;
+    /// Render scale the last frame was drawn at
+    pub fn effective_render_scale(&self) -> f32 {
+        match (&self.render_texture, &self.display_texture) {
+            (Some(rt), Some(display)) => rt.size.0 as f32 / display.size.0.max(1) as f32,
+            _ => 1.0,
+        }
+    }
 
-        // Create new render texture
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("viewport_render_texture"),
+    fn create_render_texture(
+        render_state: &egui_wgpu::RenderState,
+        label: &str,
+        (width, height): (u32, u32),
+    ) -> RenderTexture {
+        let texture = render_state.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
             size: wgpu::Extent3d {
                 width,
                 height,
@@ -199,17 +319,50 @@ impl Viewport {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format,
+            format: render_state.target_format,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
         });
-
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        RenderTexture {
+            texture,
+            view,
+            size: (width, height),
+        }
+    }
+
+    /// Keep the render texture at `render_size` and, when that differs from
+    /// the viewport, a display-size texture to resolve into; egui shows the latter
+    fn ensure_render_texture(
+        &mut self,
+        render_state: &egui_wgpu::RenderState,
+        display_size: (u32, u32),
+        render_size: (u32, u32),
+    ) {
+        let render_ok = self.render_texture.as_ref().is_some_and(|rt| rt.size == render_size);
+        let display_ok = if render_size == display_size {
+            self.display_texture.is_none()
+        } else {
+            self.display_texture.as_ref().is_some_and(|rt| rt.size == display_size)
+        };
+        if render_ok && display_ok {
+            return;
+        }
+
+        if !render_ok {
+            self.render_texture = Some(Self::create_render_texture(render_state, "viewport_render_texture", render_size));
+        }
+        if !display_ok {
+            self.display_texture = (render_size != display_size)
+                .then(|| Self::create_render_texture(render_state, "viewport_display_texture", display_size));
+        }
 
         // Register with egui
+        let shown = self.display_texture.as_ref().or(self.render_texture.as_ref());
+        let Some(shown) = shown else { return };
         let tex_id = render_state.renderer.write().register_native_texture(
-            device,
-            &view,
+            &render_state.device,
+            &shown.view,
             wgpu::FilterMode::Linear,
         );
 
@@ -219,11 +372,6 @@ impl Viewport {
         }
 
         self.texture_id = Some(tex_id);
-        self.render_texture = Some(RenderTexture {
-            texture,
-            view,
-            size: (width, height),
-        });
     }
 
     /// Handle camera input, returns true if user moved the camera
@@ -241,25 +389,27 @@ impl Viewport {
             self.camera.end_drag();
         }
 
+        let nav = self.keymap.mouse;
+
         // Ctrl+LMB drag = continuous focus sampling (disable orbit)
-        let ctrl_held = input.modifiers.ctrl;
+        let ctrl_held = nav.ctrl_lmb_focus && input.modifiers.ctrl;
 
-        // Orbit with left mouse drag (only when Ctrl not held)
-        if response.dragged_by(egui::PointerButton::Primary) && !ctrl_held {
+        // Orbit (LMB drag by default, only when Ctrl not held)
+        if nav.orbit.dragged(&input, response) && !(ctrl_held && nav.orbit.button == egui::PointerButton::Primary) {
             let delta = response.drag_delta();
             self.camera.orbit(delta.x, delta.y);
             camera_moved = true;
         }
 
-        // Pan with middle mouse drag
-        if response.dragged_by(egui::PointerButton::Middle) {
+        // Pan (MMB drag by default)
+        if nav.pan.dragged(&input, response) {
             let delta = response.drag_delta();
             self.camera.pan(delta.x, delta.y);
             camera_moved = true;
         }
 
-        // Zoom with right mouse drag
-        if response.dragged_by(egui::PointerButton::Secondary) {
+        // Zoom (RMB drag by default)
+        if nav.zoom.dragged(&input, response) {
             let delta = response.drag_delta();
             self.camera.zoom(delta.y * 0.1);
             camera_moved = true;
@@ -274,12 +424,6 @@ impl Viewport {
             }
         }
 
-        // Reset camera with Home key
-        if response.has_focus() && input.key_pressed(egui::Key::Home) {
-            self.camera.reset();
-            camera_moved = true;
-        }
-
         // MMB click or Ctrl+LMB = DoF focus pick
         if response.clicked_by(egui::PointerButton::Middle)
             || (ctrl_held && (response.clicked() || response.dragged_by(egui::PointerButton::Primary)))
@@ -294,8 +438,9 @@ impl Viewport {
             }
         }
 
-        // LMB click = object picking (selection); click-drag = orbit, click-release = select
-        if response.clicked() && !ctrl_held {
+        // LMB click = object picking (selection); click-drag = orbit, click-release = select.
+        // Alt-navigation presets don't pick while the nav modifier is held.
+        if response.clicked() && !ctrl_held && !nav.orbit.modifier_held(&input) {
             if let Some(pos) = input.pointer.hover_pos() {
                 let rect = response.rect;
                 if rect.contains(pos) {
@@ -1,19 +1,26 @@
 //! Background worker for async frame loading.
 //!
 //! Separates heavy computation from UI thread to keep interface responsive.
+//! The browser build has no threads: there the latest request is loaded on
+//! the UI thread when polled.
 
+#[cfg(not(target_arch = "wasm32"))]
 use std::sync::mpsc::{Receiver, Sender, channel};
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
 use std::thread::{self, JoinHandle};
 
-use crate::abc::IArchive;
+use alembic::abc::IArchive;
 use super::mesh_converter::{self, CollectedScene, MeshCache};
 
 /// Commands sent from UI to worker.
+#[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug)]
 pub enum WorkerCommand {
-    /// Load frame data for the given frame index.
-    LoadFrame { frame: usize, epoch: u64 },
+    /// Load frame data for the given frame index, blending meshes with
+    /// rest positions toward their rest pose by `rest_blend` (0 = off).
+    /// Xforms are blended `subframe` (0..1) of the way to the next sample.
+    LoadFrame { frame: usize, subframe: f32, epoch: u64, rest_blend: f32 },
     /// Stop the worker thread.
     Stop,
 }
@@ -29,6 +36,7 @@ pub enum WorkerResult {
 }
 
 /// Handle to communicate with the background worker.
+#[cfg(not(target_arch = "wasm32"))]
 pub struct WorkerHandle {
     /// Send commands to worker.
     pub tx: Sender<WorkerCommand>,
@@ -38,6 +46,7 @@ pub struct WorkerHandle {
     handle: Option<JoinHandle<()>>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl WorkerHandle {
     /// Spawn a new worker thread for the given archive.
     pub fn spawn(archive: Arc<IArchive>) -> Self {
@@ -56,8 +65,8 @@ impl WorkerHandle {
     }
 
     /// Request a frame to be loaded with given epoch.
-    pub fn request_frame(&self, frame: usize, epoch: u64) {
-        let _ = self.tx.send(WorkerCommand::LoadFrame { frame, epoch });
+    pub fn request_frame(&self, frame: usize, subframe: f32, epoch: u64, rest_blend: f32) {
+        let _ = self.tx.send(WorkerCommand::LoadFrame { frame, subframe, epoch, rest_blend });
     }
 
     /// Check for ready results (non-blocking).
@@ -74,13 +83,50 @@ impl WorkerHandle {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Drop for WorkerHandle {
     fn drop(&mut self) {
         self.stop();
     }
 }
 
+/// Frame loader polled on the UI thread (browser build).
+#[cfg(target_arch = "wasm32")]
+pub struct WorkerHandle {
+    archive: Arc<IArchive>,
+    cache: MeshCache,
+    /// Latest request: (frame, subframe, epoch, rest_blend)
+    pending: std::cell::Cell<Option<(usize, f32, u64, f32)>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl WorkerHandle {
+    /// Create a loader for the given archive.
+    pub fn spawn(archive: Arc<IArchive>) -> Self {
+        Self { archive, cache: mesh_converter::new_mesh_cache(), pending: std::cell::Cell::new(None) }
+    }
+
+    /// Request a frame to be loaded with given epoch (replaces an unpolled request).
+    pub fn request_frame(&self, frame: usize, subframe: f32, epoch: u64, rest_blend: f32) {
+        self.pending.set(Some((frame, subframe, epoch, rest_blend)));
+    }
+
+    /// Load the latest requested frame, if any.
+    pub fn try_recv(&self) -> Option<WorkerResult> {
+        let (frame, subframe, epoch, rest_blend) = self.pending.take()?;
+        let _span = tracing::info_span!("collect_scene_cached").entered();
+        let scene = mesh_converter::collect_scene_cached(&self.archive, frame, subframe, Some(&self.cache), rest_blend);
+        Some(WorkerResult::FrameReady { frame, epoch, scene })
+    }
+
+    /// Drop the pending request.
+    pub fn stop(&mut self) {
+        self.pending.set(None);
+    }
+}
+
 /// Main worker loop - runs in background thread.
+#[cfg(not(target_arch = "wasm32"))]
 fn worker_loop(
     archive: Arc<IArchive>,
     rx: Receiver<WorkerCommand>,
@@ -91,15 +137,15 @@ fn worker_loop(
     
     while let Ok(cmd) = rx.recv() {
         match cmd {
-            WorkerCommand::LoadFrame { frame, epoch } => {
+            WorkerCommand::LoadFrame { frame, subframe, epoch, rest_blend } => {
                 // Before doing work, drain any newer requests
                 // This handles rapid scrubbing - only process the latest
-                let (final_frame, final_epoch) = drain_to_latest(&rx, frame, epoch);
+                let (final_frame, subframe, final_epoch, rest_blend) = drain_to_latest(&rx, frame, subframe, epoch, rest_blend);
                 
                 // Collect scene data for this frame (with caching for constant meshes)
-                let t0 = std::time::Instant::now();
+                let t0 = web_time::Instant::now();
                 let _span = tracing::info_span!("collect_scene_cached").entered();
-                let scene = mesh_converter::collect_scene_cached(&archive, final_frame, Some(&cache));
+                let scene = mesh_converter::collect_scene_cached(&archive, final_frame, subframe, Some(&cache), rest_blend);
                 let elapsed = t0.elapsed();
                 if elapsed.as_millis() > 10 {
                     log::trace!("[PERF] Frame {} loaded in {:?}", final_frame, elapsed);
@@ -123,25 +169,30 @@ fn worker_loop(
 }
 
 /// Drain channel to get the latest frame request, discarding older ones.
+#[cfg(not(target_arch = "wasm32"))]
 fn drain_to_latest(
     rx: &Receiver<WorkerCommand>,
     mut frame: usize,
+    mut subframe: f32,
     mut epoch: u64,
-) -> (usize, u64) {
+    mut rest_blend: f32,
+) -> (usize, f32, u64, f32) {
     // Non-blocking drain of queued requests
     while let Ok(cmd) = rx.try_recv() {
         match cmd {
-            WorkerCommand::LoadFrame { frame: f, epoch: e } => {
+            WorkerCommand::LoadFrame { frame: f, subframe: s, epoch: e, rest_blend: b } => {
                 frame = f;
+                subframe = s;
                 epoch = e;
+                rest_blend = b;
             }
             WorkerCommand::Stop => {
                 // Put stop back and return current
                 // Actually we can't put it back, so just return
                 // The main loop will get Stop on next recv()
-                return (frame, epoch);
+                return (frame, subframe, epoch, rest_blend);
             }
         }
     }
-    (frame, epoch)
+    (frame, subframe, epoch, rest_blend)
 }
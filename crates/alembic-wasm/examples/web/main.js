@@ -0,0 +1,33 @@
+// alembic-viewer in the browser: the desktop viewer's wgpu renderer and UI
+// running on WebGL2.
+//
+// Build the package with the viewer first (`wasm-pack build --release
+// --target web --features viewer` in crates/alembic-wasm), then serve
+// crates/alembic-wasm over HTTP and open examples/web/index.html. Archives
+// are opened from File > Open... or by dropping them on the page;
+// `?url=scene.abc` opens one on startup.
+
+import init, { startViewer } from "../../pkg/alembic_wasm.js";
+
+const canvas = document.getElementById("view");
+const status = document.getElementById("status");
+
+async function fetchArchive(url) {
+  status.textContent = `Fetching ${url}...`;
+  const response = await fetch(url);
+  if (!response.ok) throw new Error(`${url}: HTTP ${response.status}`);
+  return new Uint8Array(await response.arrayBuffer());
+}
+
+await init();
+try {
+  const url = new URLSearchParams(location.search).get("url");
+  const bytes = url ? await fetchArchive(url) : undefined;
+  const name = url ? url.split("/").pop() : undefined;
+  // Kept on window so `viewer.destroy()` works from the console
+  window.viewer = await startViewer(canvas, bytes, name);
+  status.remove();
+} catch (e) {
+  status.textContent = `Error: ${e.message ?? e}`;
+  console.error(e);
+}
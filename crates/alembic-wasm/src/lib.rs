@@ -0,0 +1,337 @@
+//! WebAssembly bindings for alembic-rs.
+//!
+//! Read-only inspection of Alembic archives from JavaScript: hierarchy
+//! walking, metadata, bounds and small sample reads. Archives are opened
+//! from a byte buffer (there is no filesystem in the browser).
+//!
+//! ```js
+//! const archive = new Archive(bytes, "scene.abc");
+//! const tree = archive.hierarchy();
+//! const pts = archive.positions("/geo/meshShape", 0, 10000);
+//! const meshes = archive.meshes(1.0); // triangulated, world space, for WebGL
+//! ```
+//!
+//! With the `viewer` feature, `startViewer(canvas, bytes, name)` runs the
+//! wgpu viewer from `alembic-viewer` in a canvas.
+
+use std::collections::BTreeMap;
+
+use alembic::abc::{IArchive, IObject};
+use alembic::core::{SampleSelector, TimeSampling};
+use alembic::geom::{collect_meshes, ICamera, ICurves, ILight, INuPatch, IPoints, IPolyMesh, ISubD, IXform, LoadOptions};
+use alembic::util::BBox3d;
+use js_sys::{Array, Float32Array, Float64Array, Int32Array, Object, Reflect, Uint32Array};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "viewer")]
+mod viewer;
+
+/// Default cap on elements returned by sample reads.
+const DEFAULT_MAX_ELEMENTS: usize = 1_000_000;
+
+/// Archive-level information.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveInfo {
+    name: String,
+    app_name: Option<String>,
+    date_written: Option<String>,
+    description: Option<String>,
+    fps: Option<f64>,
+    archive_version: i32,
+    num_time_samplings: usize,
+    num_objects: usize,
+}
+
+/// One node of the object hierarchy.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Node {
+    name: String,
+    path: String,
+    schema: String,
+    num_samples: usize,
+    num_children: usize,
+    children: Vec<Node>,
+}
+
+/// A read-only Alembic archive held in memory.
+#[wasm_bindgen]
+pub struct Archive {
+    inner: IArchive,
+}
+
+#[wasm_bindgen]
+impl Archive {
+    /// Open an archive from its bytes. `name` is reported by `info()`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: &[u8], name: Option<String>) -> Result<Archive, JsError> {
+        let name = name.unwrap_or_else(|| "<memory>".to_string());
+        let inner = IArchive::from_bytes(name, bytes.to_vec())
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(Self { inner })
+    }
+
+    /// Archive metadata: `{ name, appName, dateWritten, description, fps, archiveVersion, numTimeSamplings, numObjects }`.
+    pub fn info(&self) -> Result<JsValue, JsError> {
+        let info = ArchiveInfo {
+            name: self.inner.getName().to_string(),
+            app_name: self.inner.getAppName().map(str::to_string),
+            date_written: self.inner.getDateWritten().map(str::to_string),
+            description: self.inner.getUserDescription().map(str::to_string),
+            fps: self.inner.getDccFps(),
+            archive_version: self.inner.getArchiveVersion(),
+            num_time_samplings: self.inner.getNumTimeSamplings(),
+            num_objects: count_objects(&self.inner.getTop()),
+        };
+        to_js(&info)
+    }
+
+    /// Object tree below `path` (root if omitted), down to `max_depth` levels.
+    pub fn hierarchy(&self, max_depth: Option<u32>, path: Option<String>) -> Result<JsValue, JsError> {
+        let obj = self.object(path.as_deref().unwrap_or("/"))?;
+        to_js(&build_node(&obj, max_depth.unwrap_or(u32::MAX)))
+    }
+
+    /// Full paths of the direct children of `path`.
+    #[wasm_bindgen(js_name = childPaths)]
+    pub fn child_paths(&self, path: &str) -> Result<Vec<String>, JsError> {
+        let obj = self.object(path)?;
+        Ok(obj.getChildren().map(|c| c.getFullName().to_string()).collect())
+    }
+
+    /// Object metadata as a plain `{ key: value }` object.
+    pub fn metadata(&self, path: &str) -> Result<JsValue, JsError> {
+        let obj = self.object(path)?;
+        let map: BTreeMap<String, String> = obj.getMetaData().get_all().into_iter().collect();
+        to_js(&map)
+    }
+
+    /// Number of samples of the object's schema (0 for plain groups).
+    #[wasm_bindgen(js_name = numSamples)]
+    pub fn num_samples(&self, path: &str) -> Result<usize, JsError> {
+        Ok(sampling(&self.object(path)?).1)
+    }
+
+    /// Times in seconds of every sample.
+    #[wasm_bindgen(js_name = sampleTimes)]
+    pub fn sample_times(&self, path: &str) -> Result<Float64Array, JsError> {
+        let obj = self.object(path)?;
+        let (ts_index, n) = sampling(&obj);
+        let ts = self.time_sampling(ts_index);
+        let times: Vec<f64> = (0..n).map(|i| ts.sample_time(i, n)).collect();
+        Ok(Float64Array::from(times.as_slice()))
+    }
+
+    /// Nearest sample index for `time` seconds.
+    #[wasm_bindgen(js_name = sampleIndexAtTime)]
+    pub fn sample_index_at_time(&self, path: &str, time: f64) -> Result<usize, JsError> {
+        let obj = self.object(path)?;
+        let (ts_index, n) = sampling(&obj);
+        Ok(SampleSelector::time_near(time).get_index(&self.time_sampling(ts_index), n))
+    }
+
+    /// Bounds as `[minX, minY, minZ, maxX, maxY, maxZ]`, or `undefined` if not stored.
+    ///
+    /// Uses `.selfBnds` for geometry and `.childBnds` for xforms; the root
+    /// returns the archive bounds.
+    pub fn bounds(&self, path: &str, index: usize) -> Result<Option<Float64Array>, JsError> {
+        let obj = self.object(path)?;
+        let bounds = if obj.isRoot() {
+            self.inner.getArchiveBounds(index)
+        } else {
+            self_bounds(&obj, index).or_else(|| alembic::geom::util::read_child_bounds(&obj, index))
+        };
+        Ok(bounds.map(|b| {
+            Float64Array::from([b.min.x, b.min.y, b.min.z, b.max.x, b.max.y, b.max.z].as_slice())
+        }))
+    }
+
+    /// Flat `xyz` positions of a PolyMesh, SubD, Curves or Points sample.
+    ///
+    /// Fails if the sample has more than `max_points` points (default 1M) so
+    /// a review UI never pulls a huge cache by accident.
+    pub fn positions(&self, path: &str, index: usize, max_points: Option<usize>) -> Result<Float32Array, JsError> {
+        let obj = self.object(path)?;
+        let positions = read_positions(&obj, index)?;
+        check_limit(positions.len(), max_points)?;
+        let flat: &[f32] = bytemuck::cast_slice(&positions);
+        Ok(Float32Array::from(flat))
+    }
+
+    /// Face vertex counts of a PolyMesh or SubD sample.
+    #[wasm_bindgen(js_name = faceCounts)]
+    pub fn face_counts(&self, path: &str, index: usize, max_faces: Option<usize>) -> Result<Int32Array, JsError> {
+        let obj = self.object(path)?;
+        let (counts, _) = read_topology(&obj, index)?;
+        check_limit(counts.len(), max_faces)?;
+        Ok(Int32Array::from(counts.as_slice()))
+    }
+
+    /// Face vertex indices of a PolyMesh or SubD sample.
+    #[wasm_bindgen(js_name = faceIndices)]
+    pub fn face_indices(&self, path: &str, index: usize, max_indices: Option<usize>) -> Result<Int32Array, JsError> {
+        let obj = self.object(path)?;
+        let (_, indices) = read_topology(&obj, index)?;
+        check_limit(indices.len(), max_indices)?;
+        Ok(Int32Array::from(indices.as_slice()))
+    }
+
+    /// Local 4x4 matrix of an Xform (column-major).
+    #[wasm_bindgen(js_name = localMatrix)]
+    pub fn local_matrix(&self, path: &str, index: usize) -> Result<Float64Array, JsError> {
+        let obj = self.object(path)?;
+        let xform = IXform::new(&obj).ok_or_else(|| JsError::new("not an Xform"))?;
+        let sample = xform.getSample(index).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(mat_to_js(sample.matrix()))
+    }
+
+    /// Local-to-world 4x4 matrix (column-major), concatenating ancestor xforms.
+    #[wasm_bindgen(js_name = worldMatrix)]
+    pub fn world_matrix(&self, path: &str, index: usize) -> Result<Float64Array, JsError> {
+        let mut world = glam::Mat4::IDENTITY;
+        let mut current = String::new();
+        for name in path.split('/').filter(|s| !s.is_empty()) {
+            current.push('/');
+            current.push_str(name);
+            let obj = self.object(&current)?;
+            if let Some(xform) = IXform::new(&obj) {
+                let n = xform.getNumSamples();
+                if n == 0 {
+                    continue;
+                }
+                if let Ok(s) = xform.getSample(index.min(n - 1)) {
+                    world = if s.inherits { world * s.matrix() } else { s.matrix() };
+                }
+            }
+        }
+        Ok(mat_to_js(world))
+    }
+
+    /// Every visible mesh at `time` seconds, ready to upload to a GPU.
+    ///
+    /// Returns `[{ path, positions, normals, indices }]`: triangulated,
+    /// world-space `Float32Array` xyz positions and normals and a
+    /// `Uint32Array` triangle list. Fails above `max_triangles` in total
+    /// (default 1M), like the other sample reads.
+    pub fn meshes(&self, time: f64, max_triangles: Option<usize>) -> Result<Array, JsError> {
+        let meshes = collect_meshes(&self.inner, time, &LoadOptions::default());
+        let triangles: usize = meshes.iter().map(|(_, m, _)| m.face_indices.len() / 3).sum();
+        check_limit(triangles, max_triangles)?;
+
+        let out = Array::new();
+        for (path, mesh, _) in meshes {
+            let indices: Vec<u32> = mesh.face_indices.iter().map(|&i| i as u32).collect();
+            let obj = Object::new();
+            set(&obj, "path", &JsValue::from_str(&path))?;
+            set(&obj, "positions", &Float32Array::from(bytemuck::cast_slice::<_, f32>(&mesh.positions)))?;
+            set(&obj, "normals", &Float32Array::from(bytemuck::cast_slice::<_, f32>(&mesh.normals)))?;
+            set(&obj, "indices", &Uint32Array::from(indices.as_slice()))?;
+            out.push(&obj);
+        }
+        Ok(out)
+    }
+}
+
+impl Archive {
+    fn object(&self, path: &str) -> Result<IObject<'_>, JsError> {
+        if path.is_empty() || path == "/" {
+            return Ok(self.inner.getTop());
+        }
+        self.inner
+            .findObject(path)
+            .ok_or_else(|| JsError::new(&format!("object not found: {}", path)))
+    }
+
+    fn time_sampling(&self, index: u32) -> TimeSampling {
+        self.inner
+            .getTimeSampling(index as usize)
+            .cloned()
+            .unwrap_or_else(TimeSampling::identity)
+    }
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    value.serialize(&serializer).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn set(obj: &Object, key: &str, value: &JsValue) -> Result<(), JsError> {
+    Reflect::set(obj, &JsValue::from_str(key), value)
+        .map(|_| ())
+        .map_err(|_| JsError::new("failed to build result object"))
+}
+
+fn check_limit(len: usize, max: Option<usize>) -> Result<(), JsError> {
+    let max = max.unwrap_or(DEFAULT_MAX_ELEMENTS);
+    if len > max {
+        return Err(JsError::new(&format!("sample has {} elements, limit is {}", len, max)));
+    }
+    Ok(())
+}
+
+fn mat_to_js(m: glam::Mat4) -> Float64Array {
+    let cols: Vec<f64> = m.to_cols_array().iter().map(|&v| v as f64).collect();
+    Float64Array::from(cols.as_slice())
+}
+
+fn count_objects(obj: &IObject<'_>) -> usize {
+    1 + obj.getChildren().map(|c| count_objects(&c)).sum::<usize>()
+}
+
+fn build_node(obj: &IObject<'_>, depth: u32) -> Node {
+    let children = if depth == 0 {
+        Vec::new()
+    } else {
+        obj.getChildren().map(|c| build_node(&c, depth - 1)).collect()
+    };
+    Node {
+        name: obj.getName().to_string(),
+        path: obj.getFullName().to_string(),
+        schema: obj.getMetaData().get("schema").unwrap_or_default().to_string(),
+        num_samples: sampling(obj).1,
+        num_children: obj.getNumChildren(),
+        children,
+    }
+}
+
+/// Time sampling index and sample count of the object's schema.
+fn sampling(obj: &IObject<'_>) -> (u32, usize) {
+    if let Some(m) = IPolyMesh::new(obj) { return (m.getTimeSamplingIndex(), m.getNumSamples()); }
+    if let Some(x) = IXform::new(obj) { return (x.getTimeSamplingIndex(), x.getNumSamples()); }
+    if let Some(s) = ISubD::new(obj) { return (s.getTimeSamplingIndex(), s.getNumSamples()); }
+    if let Some(c) = ICurves::new(obj) { return (c.getTimeSamplingIndex(), c.getNumSamples()); }
+    if let Some(p) = IPoints::new(obj) { return (p.getTimeSamplingIndex(), p.getNumSamples()); }
+    if let Some(c) = ICamera::new(obj) { return (c.getTimeSamplingIndex(), c.getNumSamples()); }
+    if let Some(l) = ILight::new(obj) { return (l.getTimeSamplingIndex(), l.getNumSamples()); }
+    if let Some(n) = INuPatch::new(obj) { return (n.getTimeSamplingIndex(), n.getNumSamples()); }
+    (0, 0)
+}
+
+fn self_bounds(obj: &IObject<'_>, index: usize) -> Option<BBox3d> {
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    alembic::geom::util::read_self_bounds(geom.as_reader(), index)
+}
+
+fn read_positions(obj: &IObject<'_>, index: usize) -> Result<Vec<glam::Vec3>, JsError> {
+    let err = |e: alembic::util::Error| JsError::new(&e.to_string());
+    if let Some(m) = IPolyMesh::new(obj) { return m.getSample(index).map(|s| s.positions).map_err(err); }
+    if let Some(s) = ISubD::new(obj) { return s.getSample(index).map(|s| s.positions).map_err(err); }
+    if let Some(c) = ICurves::new(obj) { return c.getSample(index).map(|s| s.positions).map_err(err); }
+    if let Some(p) = IPoints::new(obj) { return p.getSample(index).map(|s| s.positions).map_err(err); }
+    Err(JsError::new("object has no positions"))
+}
+
+fn read_topology(obj: &IObject<'_>, index: usize) -> Result<(Vec<i32>, Vec<i32>), JsError> {
+    let err = |e: alembic::util::Error| JsError::new(&e.to_string());
+    if let Some(m) = IPolyMesh::new(obj) {
+        return m.getSample(index).map(|s| (s.face_counts, s.face_indices)).map_err(err);
+    }
+    if let Some(s) = ISubD::new(obj) {
+        return s.getSample(index).map(|s| (s.face_counts, s.face_indices)).map_err(err);
+    }
+    Err(JsError::new("object has no faces"))
+}
@@ -0,0 +1,39 @@
+//! The wgpu viewer (`alembic-viewer`) in a browser canvas, on WebGL2.
+
+use wasm_bindgen::prelude::*;
+
+/// A running viewer.
+#[wasm_bindgen]
+pub struct Viewer {
+    runner: eframe::WebRunner,
+}
+
+#[wasm_bindgen]
+impl Viewer {
+    /// Stop rendering and release the canvas.
+    pub fn destroy(&self) {
+        self.runner.destroy();
+    }
+
+    /// Whether the viewer panicked (the error is logged to the console).
+    #[wasm_bindgen(js_name = hasPanicked)]
+    pub fn has_panicked(&self) -> bool {
+        self.runner.has_panicked()
+    }
+}
+
+/// Start the viewer in `canvas`, optionally opening an archive from its
+/// bytes (`name` is shown as the file name). Further archives are opened
+/// from the File menu or by dropping them on the canvas.
+#[wasm_bindgen(js_name = startViewer)]
+pub async fn start_viewer(
+    canvas: web_sys::HtmlCanvasElement,
+    bytes: Option<Vec<u8>>,
+    name: Option<String>,
+) -> Result<Viewer, JsError> {
+    let archive = bytes.map(|bytes| (name.unwrap_or_else(|| "scene.abc".to_string()), bytes));
+    let runner = alembic_viewer::start_web(canvas, archive)
+        .await
+        .map_err(|e| JsError::new(&format!("{:#}", e)))?;
+    Ok(Viewer { runner })
+}
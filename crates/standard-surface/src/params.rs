@@ -33,7 +33,7 @@ pub struct StandardSurfaceParams {
     pub opacity: Vec4,
     /// Packed params: x=diffuse_roughness, y=metalness, z=specular_roughness, w=specular_IOR
     pub params1: Vec4,
-    /// Packed params: x=specular_anisotropy, y=coat_roughness, z=coat_IOR, w=unused
+    /// Packed params: x=specular_anisotropy, y=coat_roughness, z=coat_IOR, w=UV checker frequency (0 = off)
     pub params2: Vec4,
 }
 
@@ -49,7 +49,7 @@ impl Default for StandardSurfaceParams {
             opacity: Vec4::new(1.0, 1.0, 1.0, 1.0),
             // x=diffuse_roughness, y=metalness, z=specular_roughness, w=specular_IOR
             params1: Vec4::new(0.0, 0.0, 0.2, 1.5),
-            // x=specular_anisotropy, y=coat_roughness, z=coat_IOR, w=unused
+            // x=specular_anisotropy, y=coat_roughness, z=coat_IOR, w=uv_checker
             params2: Vec4::new(0.0, 0.1, 1.5, 0.0),
         }
     }
@@ -146,6 +146,13 @@ impl StandardSurfaceParams {
     pub fn set_specular(&mut self, weight: f32) {
         self.specular_color_weight.w = weight;
     }
+
+    /// Modulate the base color with a UV checker (`frequency` checks per
+    /// UV unit, 0 = off). Preview only: the rasterizer honours it, it is not
+    /// part of Standard Surface.
+    pub fn set_uv_checker(&mut self, frequency: f32) {
+        self.params2.w = frequency;
+    }
 }
 
 /// Camera uniform data
@@ -64,7 +64,7 @@ struct StandardSurfaceParams {
     // Packed scalars
     // x=diffuse_roughness, y=metalness, z=specular_roughness, w=specular_IOR
     params1: vec4<f32>,
-    // x=specular_anisotropy, y=coat_roughness, z=coat_IOR, w=unused
+    // x=specular_anisotropy, y=coat_roughness, z=coat_IOR, w=uv_checker (checks per UV unit, 0 = off)
     params2: vec4<f32>,
 }
 
@@ -337,6 +337,20 @@ struct GBufferOutput {
     @location(2) occlusion: vec4<f32>,
 }
 
+// Base color, optionally modulated by the UV checker preview
+fn surface_base_color(uv: vec2<f32>) -> vec3<f32> {
+    let color = material.base_color_weight.rgb;
+    let frequency = material.params2.w;
+    if frequency <= 0.0 {
+        return color;
+    }
+    let cell = floor(uv * frequency);
+    let check = fract((cell.x + cell.y) * 0.5) * 2.0;
+    // Tint along U (red) and V (green) so flipped or rotated shells stand out
+    let tint = vec3<f32>(0.7 + 0.3 * fract(uv.x), 0.7 + 0.3 * fract(uv.y), 0.85);
+    return color * tint * mix(0.3, 1.0, check);
+}
+
 @fragment
 fn fs_main(in: VertexOutput, @builtin(front_facing) is_front: bool) -> @location(0) vec4<f32> {
     // View direction (needed for backface detection)
@@ -365,7 +379,7 @@ fn fs_main(in: VertexOutput, @builtin(front_facing) is_front: bool) -> @location
     let NdotV = max(dot(N, V), EPSILON);
 
     // Unpack material parameters
-    let base_color = material.base_color_weight.rgb;
+    let base_color = surface_base_color(in.uv);
     let base = material.base_color_weight.a;
     let specular_color = material.specular_color_weight.rgb;
     let specular = material.specular_color_weight.a;
@@ -498,7 +512,7 @@ fn fs_gbuffer(in: VertexOutput, @builtin(front_facing) is_front: bool) -> GBuffe
     }
 
     // Material unpack
-    let base_color = material.base_color_weight.rgb;
+    let base_color = surface_base_color(in.uv);
     let base = material.base_color_weight.a;
     let specular_roughness = max(material.params1.z, 0.04);
     let metalness = material.params1.y;
@@ -1,6 +1,9 @@
 """Type stubs for alembic_rs - Alembic file format bindings for Python."""
 
-from typing import List, Optional, Tuple, Iterator, Any
+from typing import Dict, List, Optional, Tuple, Iterator, Any
+
+# Sample data: numpy arrays (or other buffer-protocol objects) or nested sequences
+ArrayLike = Any
 
 # =============================================================================
 # Archives
@@ -27,7 +30,13 @@ class OArchive:
     def create(path: str) -> OArchive: ...
     def setAppName(self, name: str) -> None: ...
     def setUserDescription(self, desc: str) -> None: ...
+    def addTimeSampling(self, ts: TimeSampling) -> int: ...
+    def addUniformTimeSampling(self, fps: float, start_time: float = 0.0) -> int: ...
+    def addAcyclicTimeSampling(self, times: List[float]) -> int: ...
+    def addCyclicTimeSampling(self, time_per_cycle: float, times: List[float]) -> int: ...
     def writeArchive(self, root: OObject) -> None: ...
+    def close(self) -> None: ...
+    def abort(self) -> None: ...
     def __enter__(self) -> OArchive: ...
     def __exit__(self, exc_type: Any, exc_val: Any, exc_tb: Any) -> None: ...
 
@@ -66,6 +75,12 @@ class IObject:
     def getFaceCounts(self, index: int = 0) -> List[int]: ...
     def getFaceIndices(self, index: int = 0) -> List[int]: ...
     def getMatrix(self, index: int = 0) -> List[List[float]]: ...
+    def getTimeSamplingIndex(self) -> int: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
+    def getSampleIndexAtTime(self, time: float, mode: str = "near") -> int: ...
+    def getWorldMatrix(self, index: int = 0, time: Optional[float] = None, mode: str = "near") -> List[List[float]]: ...
+    def getWorldTranslation(self, index: int = 0, time: Optional[float] = None, mode: str = "near") -> List[float]: ...
     def getProperties(self) -> ICompoundProperty: ...
     def getFaceSetNames(self) -> List[str]: ...
     def getFaceSet(self, name: str) -> IFaceSet: ...
@@ -95,6 +110,7 @@ class IPolyMesh:
     """PolyMesh schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> IPolyMeshSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> PolyMeshSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -103,13 +119,17 @@ class IPolyMeshSchema:
     """PolyMesh schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> PolyMeshSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> PolyMeshSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> PolyMeshSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class IXform:
     """Xform schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> IXformSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> XformSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -118,7 +138,10 @@ class IXformSchema:
     """Xform schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> XformSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> XformSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> XformSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
     def getInheritsXforms(self, index: int = 0) -> bool: ...
 
@@ -126,6 +149,7 @@ class ISubD:
     """SubD schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> ISubDSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> SubDSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -134,13 +158,17 @@ class ISubDSchema:
     """SubD schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> SubDSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> SubDSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> SubDSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class ICurves:
     """Curves schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> ICurvesSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> CurvesSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -149,13 +177,17 @@ class ICurvesSchema:
     """Curves schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> CurvesSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> CurvesSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> CurvesSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class IPoints:
     """Points schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> IPointsSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> PointsSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -164,13 +196,17 @@ class IPointsSchema:
     """Points schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> PointsSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> PointsSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> PointsSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class ICamera:
     """Camera schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> ICameraSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> CameraSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -179,13 +215,17 @@ class ICameraSchema:
     """Camera schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> CameraSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> CameraSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> CameraSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class ILight:
     """Light schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> ILightSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> LightSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -194,13 +234,17 @@ class ILightSchema:
     """Light schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> LightSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> LightSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> LightSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class INuPatch:
     """NuPatch schema wrapper."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> INuPatchSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> NuPatchSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -209,7 +253,10 @@ class INuPatchSchema:
     """NuPatch schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> NuPatchSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> NuPatchSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> NuPatchSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
 
 class IFaceSet:
@@ -225,6 +272,7 @@ class IFaceSetTyped:
     """FaceSet schema wrapper (original API style)."""
     def __init__(self, obj: IObject) -> None: ...
     def getSchema(self) -> IFaceSetSchema: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> FaceSetSample: ...
     def valid(self) -> bool: ...
     def getName(self) -> str: ...
     def getFullName(self) -> str: ...
@@ -233,7 +281,10 @@ class IFaceSetSchema:
     """FaceSet schema accessor."""
     def getNumSamples(self) -> int: ...
     def isConstant(self) -> bool: ...
-    def getValue(self, index: int = 0) -> FaceSetSample: ...
+    def getValue(self, index: int = 0, selector: Optional[ISampleSelector] = None) -> FaceSetSample: ...
+    def sample_at_time(self, time: float, mode: str = "near") -> FaceSetSample: ...
+    def getTimeSampling(self) -> TimeSampling: ...
+    def getSampleTimes(self) -> List[float]: ...
     def getTimeSamplingIndex(self) -> int: ...
     def getFaceExclusivity(self) -> str: ...
 
@@ -313,6 +364,8 @@ class PointsSample:
     def positions(self) -> List[List[float]]: ...
     @property
     def ids(self) -> List[int]: ...
+    def indexOfId(self, id: int) -> Optional[int]: ...
+    def idToIndex(self) -> Dict[int, int]: ...
     @property
     def velocities(self) -> Optional[List[List[float]]]: ...
     @property
@@ -395,7 +448,8 @@ class GeomParamSample:
 class OPolyMesh:
     """Write PolyMesh geometry."""
     def __init__(self, name: str) -> None: ...
-    def addSample(self, positions: List[List[float]], face_counts: List[int], face_indices: List[int]) -> None: ...
+    def setTimeSamplingIndex(self, index: int) -> None: ...
+    def addSample(self, positions: ArrayLike, face_counts: ArrayLike, face_indices: ArrayLike, normals: Optional[ArrayLike] = None, uvs: Optional[ArrayLike] = None) -> None: ...
     def setNormals(self, normals: List[List[float]], indices: Optional[List[int]] = None) -> None: ...
     def setUVs(self, uvs: List[List[float]], indices: Optional[List[int]] = None) -> None: ...
     def setVelocities(self, velocities: List[List[float]]) -> None: ...
@@ -404,6 +458,7 @@ class OPolyMesh:
 class OXform:
     """Write transform."""
     def __init__(self, name: str) -> None: ...
+    def setTimeSamplingIndex(self, index: int) -> None: ...
     def addMatrixSample(self, matrix: List[List[float]]) -> None: ...
     def addTranslationSample(self, x: float, y: float, z: float) -> None: ...
     def addRotationSample(self, x: float, y: float, z: float) -> None: ...
@@ -412,13 +467,15 @@ class OXform:
 class OSubD:
     """Write subdivision surface."""
     def __init__(self, name: str) -> None: ...
-    def addSample(self, positions: List[List[float]], face_counts: List[int], face_indices: List[int]) -> None: ...
+    def setTimeSamplingIndex(self, index: int) -> None: ...
+    def addSample(self, positions: ArrayLike, face_counts: ArrayLike, face_indices: ArrayLike, scheme: str = "catmullClark") -> None: ...
     def setScheme(self, scheme: str) -> None: ...
 
 class OCurves:
     """Write curves."""
     def __init__(self, name: str) -> None: ...
-    def addSample(self, positions: List[List[float]], num_vertices: List[int]) -> None: ...
+    def setTimeSamplingIndex(self, index: int) -> None: ...
+    def addSample(self, positions: ArrayLike, num_vertices: ArrayLike, curve_type: str = "linear", wrap: str = "nonperiodic", basis: str = "nobasis", velocities: Optional[ArrayLike] = None, widths: Optional[ArrayLike] = None) -> None: ...
     def setCurveType(self, curve_type: str) -> None: ...
     def setBasis(self, basis: str) -> None: ...
     def setWrap(self, wrap: str) -> None: ...
@@ -427,7 +484,11 @@ class OCurves:
 class OPoints:
     """Write point cloud."""
     def __init__(self, name: str) -> None: ...
-    def addSample(self, positions: List[List[float]], ids: List[int]) -> None: ...
+    def setIdMode(self, mode: str) -> None:
+        """Id generation for samples without ids: "sequential" or "hashed"."""
+        ...
+    def setTimeSamplingIndex(self, index: int) -> None: ...
+    def addSample(self, positions: ArrayLike, ids: Optional[ArrayLike] = None, velocities: Optional[ArrayLike] = None, widths: Optional[ArrayLike] = None) -> None: ...
     def setVelocities(self, velocities: List[List[float]]) -> None: ...
     def setWidths(self, widths: List[float]) -> None: ...
 
@@ -543,6 +604,41 @@ class OVisibilityProperty:
 
 class TimeSampling:
     """Time sampling configuration."""
-    @property
-    def numSamples(self) -> int: ...
-    def getSampleTime(self, index: int) -> float: ...
+    @staticmethod
+    def identity() -> TimeSampling: ...
+    @staticmethod
+    def uniform(time_per_cycle: float, start_time: float) -> TimeSampling: ...
+    @staticmethod
+    def acyclic(times: List[float]) -> TimeSampling: ...
+    @staticmethod
+    def cyclic(time_per_cycle: float, times: List[float]) -> TimeSampling: ...
+    def isIdentity(self) -> bool: ...
+    def isUniform(self) -> bool: ...
+    def isCyclic(self) -> bool: ...
+    def isAcyclic(self) -> bool: ...
+    def getTypeName(self) -> str: ...
+    def getFps(self) -> Optional[float]: ...
+    def getTimePerCycle(self) -> float: ...
+    def getStoredTimes(self) -> List[float]: ...
+    def getSampleTime(self, index: int, num_samples: int) -> float: ...
+    def getSampleTimes(self, num_samples: int) -> List[float]: ...
+    def getTimeRange(self, num_samples: int) -> Tuple[float, float]: ...
+    def getFloorIndex(self, time: float, num_samples: int) -> Tuple[int, float]: ...
+    def getCeilIndex(self, time: float, num_samples: int) -> Tuple[int, float]: ...
+    def getNearIndex(self, time: float, num_samples: int) -> Tuple[int, float]: ...
+    def getIndexAtTime(self, time: float, num_samples: int, mode: str = "near") -> int: ...
+
+class ISampleSelector:
+    """Index- or time-based sample selection."""
+    def __init__(self, value: Optional[float] = None) -> None: ...
+    @staticmethod
+    def floor(time: float) -> ISampleSelector: ...
+    @staticmethod
+    def ceil(time: float) -> ISampleSelector: ...
+    @staticmethod
+    def near(time: float) -> ISampleSelector: ...
+    @staticmethod
+    def index(idx: int) -> ISampleSelector: ...
+    def getIndex(self, ts: TimeSampling, num_samples: int) -> int: ...
+    def getRequestedIndex(self) -> int: ...
+    def getRequestedTime(self) -> float: ...
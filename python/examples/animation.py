@@ -25,6 +25,7 @@ def write_animated_transform(output_path: str, fps: float = 24.0, num_frames: in
     
     # Create spinning transform
     xform = alembic_rs.Abc.OXform("spinner")
+    xform.setTimeSamplingIndex(ts_index)
     
     # Add rotation samples for 2 seconds (48 frames at 24fps)
     for frame in range(num_frames):
@@ -75,6 +76,7 @@ def write_animated_mesh(output_path: str, fps: float = 24.0, num_frames: int = 2
     
     # Create deforming sphere
     mesh = alembic_rs.Abc.OPolyMesh("ball")
+    mesh.setTimeSamplingIndex(ts_index)
     
     # Generate sphere topology (simplified)
     def make_sphere(radius: float, squash: float = 1.0, y_offset: float = 0.0):
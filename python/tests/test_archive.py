@@ -1,5 +1,7 @@
 """Tests for IArchive and OArchive."""
 
+import os
+
 import pytest
 import alembic_rs as abc
 
@@ -120,3 +122,18 @@ class TestOArchive:
         
         root = abc.OObject("")
         archive.writeArchive(root)
+
+    def test_abort_removes_file(self, temp_abc_file):
+        """abort() and a failing with-block leave no partial file."""
+        archive = abc.OArchive.create(temp_abc_file)
+        archive.abort()
+        assert not os.path.exists(temp_abc_file)
+
+        with pytest.raises(RuntimeError):
+            with abc.OArchive.create(temp_abc_file):
+                raise RuntimeError("export failed")
+        assert not os.path.exists(temp_abc_file)
+
+        with abc.OArchive.create(temp_abc_file):
+            pass
+        assert abc.IArchive(temp_abc_file).valid()
@@ -0,0 +1,76 @@
+"""Tests for the write API with array inputs and time samplings."""
+
+import array
+
+import pytest
+import alembic_rs as abc
+
+
+def write_triangles(path, make_positions, make_ints, frames=3):
+    """Write an animated triangle and a point cloud using the given array factories."""
+    archive = abc.OArchive.create(path)
+    ts = archive.addTimeSampling(abc.TimeSampling.uniform(1.0 / 24.0, 0.0))
+
+    mesh = abc.OPolyMesh("tri")
+    mesh.setTimeSamplingIndex(ts)
+    for frame in range(frames):
+        y = float(frame)
+        mesh.addSample(make_positions([0, y, 0, 1, y, 0, 0, y, 1]), make_ints([3]), make_ints([0, 1, 2]))
+
+    points = abc.OPoints("pts")
+    points.setTimeSamplingIndex(ts)
+    points.addSample(make_positions([0, 0, 0, 1, 1, 1]), ids=make_ints([5, 7]))
+
+    root = abc.OObject("")
+    root.addPolyMesh(mesh)
+    root.addPoints(points)
+    archive.writeArchive(root)
+    archive.close()
+    return ts
+
+
+def check_triangles(path, ts, frames=3):
+    reader = abc.IArchive(path)
+    assert reader.getTimeSampling(ts).isUniform()
+    tri = reader.getTop().getChildByName("tri")
+    assert tri.getNumSamples() == frames
+    for frame in range(frames):
+        assert tri.getPolyMeshSample(frame).positions[1] == [1.0, float(frame), 0.0]
+    pts = reader.getTop().getChildByName("pts").getPointsSample(0)
+    assert list(pts.ids) == [5, 7]
+
+
+class TestArrayInputs:
+    """Buffer-protocol arrays are accepted wherever lists are."""
+
+    def test_array_module_buffers(self, temp_abc_file):
+        ts = write_triangles(
+            temp_abc_file,
+            lambda v: array.array("d", v),
+            lambda v: array.array("q", v),
+        )
+        check_triangles(temp_abc_file, ts)
+
+    def test_numpy_arrays(self, temp_abc_file):
+        np = pytest.importorskip("numpy")
+        ts = write_triangles(
+            temp_abc_file,
+            lambda v: np.asarray(v, dtype=np.float32).reshape(-1, 3),
+            lambda v: np.asarray(v, dtype=np.int64),
+        )
+        check_triangles(temp_abc_file, ts)
+
+    def test_nested_lists_still_work(self, temp_abc_file):
+        ts = write_triangles(
+            temp_abc_file,
+            lambda v: [v[i:i + 3] for i in range(0, len(v), 3)],
+            list,
+        )
+        check_triangles(temp_abc_file, ts)
+
+    def test_bad_shapes_are_rejected(self):
+        mesh = abc.OPolyMesh("bad")
+        with pytest.raises(ValueError):
+            mesh.addSample(array.array("f", [0, 0, 0, 1]), [3], [0, 1, 2])
+        with pytest.raises(ValueError):
+            mesh.addSample(array.array("f", [0] * 9), [3], array.array("q", [0, 1, 1 << 40]))
@@ -22,31 +22,183 @@ use crate::core::{
     ArchiveReader, ObjectReader, CompoundPropertyReader, PropertyReader,
     ScalarPropertyReader, ArrayPropertyReader,
     ObjectHeader, PropertyHeader, TimeSampling, SampleSelector, MetaData,
+    ReadArraySampleCache, SampleCacheStats, DecodeArena, DecodeArenaStats, FrameConvention, FrameMapping,
 };
 use crate::ogawa::OgawaArchiveReader;
-use crate::util::Result;
+use crate::util::{Error, PlainOldDataType, Result};
+
+#[cfg(feature = "parallel")]
+mod parallel;
 
 // ============================================================================
 // Archives
 // ============================================================================
 
+/// How [`IArchive::open_with_mode`] treats malformed data.
+///
+/// Both modes walk every object and property header, plus the offsets of
+/// every sample block, when the archive is opened (sample data itself is
+/// not decoded). Plain [`IArchive::open`] skips that walk; unreadable
+/// children then just don't show up when traversing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenMode {
+    /// Fail to open on the first problem, with its [`Error::Corrupt`].
+    Strict,
+    /// Open whatever is readable. Unreadable subtrees are skipped during
+    /// traversal and listed by [`IArchive::skipped_subtrees`].
+    #[default]
+    Tolerant,
+}
+
+/// Object or property subtree skipped by [`OpenMode::Tolerant`].
+#[derive(Debug)]
+pub struct SkippedSubtree {
+    /// Full object path, or object path and property names ("/a/b/.geom/P").
+    pub path: String,
+    /// Why it couldn't be read (usually [`Error::Corrupt`]).
+    pub error: Error,
+}
+
 /// Input archive for reading Alembic files.
 ///
 /// This is the main entry point for reading .abc files.
 pub struct IArchive {
     reader: Box<dyn ArchiveReader>,
+    skipped: Vec<SkippedSubtree>,
 }
 
 impl IArchive {
     /// Open an Alembic file for reading.
     ///
+    /// With the `hdf5` feature, legacy HDF5 archives are accepted too and
+    /// converted in memory (see the `hdf5` module); without it they fail with
+    /// [`Error::Hdf5Archive`].
+    ///
     /// # Example
     /// ```ignore
     /// let archive = IArchive::open("scene.abc")?;
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let reader = OgawaArchiveReader::open(path)?;
-        Ok(Self { reader: Box::new(reader) })
+        let reader = open_reader(path.as_ref())?;
+        Ok(Self { reader: Box::new(reader), skipped: Vec::new() })
+    }
+
+    /// Open an Alembic file, checking its structure up front (see [`OpenMode`]).
+    ///
+    /// Meant for files from untrusted sources: malformed input yields
+    /// errors, never panics.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let archive = IArchive::open_with_mode("upload.abc", OpenMode::Tolerant)?;
+    /// for skipped in archive.skipped_subtrees() {
+    ///     eprintln!("skipped {}: {}", skipped.path, skipped.error);
+    /// }
+    /// ```
+    pub fn open_with_mode<P: AsRef<Path>>(path: P, mode: OpenMode) -> Result<Self> {
+        Self::checked(open_reader(path.as_ref())?, mode)
+    }
+
+    /// Open a large Alembic file for streaming traversal.
+    ///
+    /// Only headers are read while walking the hierarchy; sample payloads are
+    /// read when requested and kept in an LRU block cache of at most
+    /// `block_cache_bytes` (see [`crate::ogawa::IArchive::open_mmap`]).
+    /// Decoded samples still go through the regular sample cache. Legacy
+    /// HDF5 archives (`hdf5` feature) are converted whole, in memory.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let archive = IArchive::open_mmap("huge_cache.abc", 256 << 20)?;
+    /// ```
+    pub fn open_mmap<P: AsRef<Path>>(path: P, block_cache_bytes: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let reader = match OgawaArchiveReader::open_mmap(path, block_cache_bytes) {
+            #[cfg(feature = "hdf5")]
+            Err(Error::Hdf5Archive) => crate::hdf5::open_reader(path)?,
+            result => result?,
+        };
+        Ok(Self { reader: Box::new(reader), skipped: Vec::new() })
+    }
+
+    /// Open an Alembic archive from an in-memory buffer.
+    ///
+    /// Useful where there is no filesystem (e.g. WASM) or the data was
+    /// fetched over the network. `name` is returned by `getName()`. Takes an
+    /// owned buffer without copying, or copies a borrowed slice.
+    pub fn from_bytes(name: impl Into<String>, data: impl Into<Vec<u8>>) -> Result<Self> {
+        let reader = reader_from_bytes(name.into(), data.into())?;
+        Ok(Self { reader: Box::new(reader), skipped: Vec::new() })
+    }
+
+    /// Open an archive read through a custom byte source (see
+    /// [`crate::ogawa::OgawaSource`]). `name` is returned by `getName()`.
+    /// Ogawa only: legacy HDF5 archives are read whole, so open those with
+    /// [`from_bytes`](Self::from_bytes).
+    pub fn from_source(name: impl Into<String>, source: Box<dyn crate::ogawa::OgawaSource>) -> Result<Self> {
+        let reader = OgawaArchiveReader::from_source(name, source)?;
+        Ok(Self { reader: Box::new(reader), skipped: Vec::new() })
+    }
+
+    /// Open an archive over HTTP, fetching only the byte ranges that are
+    /// read (see [`crate::ogawa::remote::HttpSource`]).
+    ///
+    /// Only plain `http://` URLs are supported; `https://` is an error, so
+    /// read `https` stores through a TLS-terminating proxy.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let archive = IArchive::open_url("http://cache-store:9000/shots/sh010/anim.abc")?;
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn open_url(url: &str) -> Result<Self> {
+        let source = crate::ogawa::remote::HttpSource::open(url)?;
+        Self::from_source(url, Box::new(source))
+    }
+
+    /// [`open_url`](Self::open_url) as a future, for async code (see
+    /// [`crate::ogawa::remote::RemoteFuture`]).
+    #[cfg(feature = "remote")]
+    pub fn open_url_async(url: &str) -> crate::ogawa::remote::RemoteFuture<Self> {
+        let url = url.to_string();
+        crate::ogawa::remote::RemoteFuture::spawn(move || Self::open_url(&url))
+    }
+
+    /// Open several archives as one layered archive, base layer first.
+    ///
+    /// Later layers override same-named objects and properties of earlier
+    /// ones and add new ones; `prune`/`replace` metadata hints remove or
+    /// replace instead of merging (see [`crate::ogawa::LayeredArchiveReader`]).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shot = IArchive::open_layered(&["anim.abc", "lookdev.abc", "fixes.abc"])?;
+    /// ```
+    pub fn open_layered<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let layers = paths.iter().map(|path| open_reader(path.as_ref())).collect::<Result<Vec<_>>>()?;
+        let reader = crate::ogawa::LayeredArchiveReader::new(layers)?;
+        Ok(Self { reader: Box::new(reader), skipped: Vec::new() })
+    }
+
+    /// Open an in-memory archive with an up-front structure check, e.g. an
+    /// upload to a web service (see [`OpenMode`]).
+    pub fn from_bytes_with_mode(name: impl Into<String>, data: impl Into<Vec<u8>>, mode: OpenMode) -> Result<Self> {
+        Self::checked(reader_from_bytes(name.into(), data.into())?, mode)
+    }
+
+    fn checked(reader: OgawaArchiveReader, mode: OpenMode) -> Result<Self> {
+        let mut problems = reader.check_hierarchy(mode == OpenMode::Strict);
+        if mode == OpenMode::Strict && !problems.is_empty() {
+            return Err(problems.swap_remove(0).1);
+        }
+        let skipped = problems.into_iter().map(|(path, error)| SkippedSubtree { path, error }).collect();
+        Ok(Self { reader: Box::new(reader), skipped })
+    }
+
+    /// Subtrees that couldn't be read when the archive was opened with
+    /// [`OpenMode::Tolerant`] (always empty for the other constructors).
+    pub fn skipped_subtrees(&self) -> &[SkippedSubtree] {
+        &self.skipped
     }
 
     /// Get the file name/path.
@@ -162,6 +314,25 @@ impl IArchive {
             .and_then(|s: &str| s.parse().ok())
     }
     
+    /// Frame mapping for this archive.
+    ///
+    /// fps is the DCC fps, else the rate of the most-sampled uniform time
+    /// sampling, else 24. Archives written by Houdini use its convention
+    /// (frame 1 at time 0), everything else Maya's (frame 0 at time 0).
+    pub fn frame_mapping(&self) -> FrameMapping {
+        let fps = self.getDccFps()
+            .filter(|f| f.is_finite() && *f > 0.0)
+            .or_else(|| {
+                (1..self.getNumTimeSamplings())
+                    .filter_map(|i| Some((self.getTimeSampling(i)?.fps()?, self.getMaxNumSamplesForTimeSamplingIndex(i)?)))
+                    .max_by_key(|&(_, n)| n)
+                    .map(|(fps, _)| fps)
+            })
+            .unwrap_or(FrameMapping::DEFAULT_FPS);
+        let convention = self.getAppName().map(FrameConvention::from_app_name).unwrap_or_default();
+        FrameMapping::new(fps, convention)
+    }
+
     /// Get raw archive metadata.
     pub fn getArchiveMetaData(&self) -> &MetaData {
         self.reader.getArchiveMetaData()
@@ -171,7 +342,74 @@ impl IArchive {
     pub fn getIndexedMetaData(&self) -> &[MetaData] {
         self.reader.getIndexedMetaData()
     }
+
+    /// Array sample cache statistics (hits, misses, evictions, size).
+    ///
+    /// `None` if the backend doesn't cache samples.
+    pub fn cache_stats(&self) -> Option<SampleCacheStats> {
+        self.reader.getSampleCache().map(ReadArraySampleCache::stats)
+    }
+
+    /// Tune the array sample cache: total byte budget and samples kept per
+    /// property (0 = byte budget only). Larger per-property limits help
+    /// scrubbing over wider frame windows.
+    pub fn set_cache_limits(&self, max_bytes: usize, samples_per_property: usize) {
+        if let Some(cache) = self.reader.getSampleCache() {
+            cache.set_max_size(max_bytes);
+            cache.set_samples_per_property(samples_per_property);
+        }
+    }
+
+    /// Drop all cached samples and reset the statistics.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = self.reader.getSampleCache() {
+            cache.clear();
+            cache.reset_stats();
+        }
+    }
+
+    /// Pool transient decode buffers (scalar payloads, dimensions,
+    /// compressed blocks) up to `budget_bytes` instead of allocating them per
+    /// sample; 0 turns pooling off. See [`crate::core::DecodeArena`].
+    pub fn set_decode_arena(&self, budget_bytes: usize) {
+        if let Some(arena) = self.reader.getDecodeArena() {
+            arena.set_budget(budget_bytes);
+        }
+    }
+
+    /// Free the pooled decode buffers, e.g. after each frame of a batch job.
+    pub fn reset_decode_arena(&self) {
+        if let Some(arena) = self.reader.getDecodeArena() {
+            arena.reset();
+        }
+    }
+
+    /// Decode arena counters; `None` if the backend has no arena.
+    pub fn decode_arena_stats(&self) -> Option<DecodeArenaStats> {
+        self.reader.getDecodeArena().map(DecodeArena::stats)
+    }
     
+    /// Validate every sample against its stored digest as it is decoded,
+    /// failing the read with [`Error::Corrupt`](crate::util::Error::Corrupt)
+    /// on a mismatch. Costs one hash per sample read; meant for
+    /// high-assurance reads such as final render submission.
+    ///
+    /// Enabling it drops the sample cache, so nothing read unverified is
+    /// served afterwards. Returns false if the backend can't verify.
+    pub fn set_verify_hashes(&self, verify: bool) -> bool {
+        if verify && !self.verifies_hashes() {
+            if let Some(cache) = self.reader.getSampleCache() {
+                cache.clear();
+            }
+        }
+        self.reader.setVerifyHashes(verify)
+    }
+
+    /// Whether samples are validated against their digest on read.
+    pub fn verifies_hashes(&self) -> bool {
+        self.reader.getVerifyHashes()
+    }
+
     /// Get the combined bounding box of all geometry in the archive.
     /// 
     /// Computes the union of self_bounds from all geometry objects
@@ -215,6 +453,25 @@ impl BoundsSelector {
     }
 }
 
+/// Open an archive file, converting legacy HDF5 archives with the `hdf5`
+/// feature.
+fn open_reader(path: &Path) -> Result<OgawaArchiveReader> {
+    match OgawaArchiveReader::open(path) {
+        #[cfg(feature = "hdf5")]
+        Err(Error::Hdf5Archive) => crate::hdf5::open_reader(path),
+        result => result,
+    }
+}
+
+/// [`open_reader`] for an in-memory archive.
+fn reader_from_bytes(name: String, data: Vec<u8>) -> Result<OgawaArchiveReader> {
+    #[cfg(feature = "hdf5")]
+    if crate::hdf5::is_hdf5(&data) {
+        return crate::hdf5::reader_from_bytes(name, &data);
+    }
+    OgawaArchiveReader::from_bytes(name, data)
+}
+
 /// Estimate sample index from time.
 /// Uses simple linear interpolation assuming 24fps if multiple samples exist.
 /// For accurate results, use schema-specific time sampling queries.
@@ -292,6 +549,64 @@ fn merge_bounds(combined: &mut Option<crate::util::BBox3d>, bounds: crate::util:
     }
 }
 
+/// See [`IObject::bounds_at`].
+fn object_bounds_at(obj: &IObject<'_>, archive: &IArchive, time: crate::util::Chrono) -> Option<crate::util::BBox3d> {
+    use crate::geom::IXform;
+
+    let index = |ts_index: u32, num_samples: usize| {
+        archive.getTimeSampling(ts_index as usize).map_or(0, |ts| ts.near_index(time, num_samples).0)
+    };
+    if let Some(xform) = IXform::new(obj) {
+        if xform.has_child_bounds() {
+            let i = index(xform.child_bounds_time_sampling_index(), xform.child_bounds_num_samples());
+            if let Some(bounds) = xform.child_bounds(i) {
+                return Some(bounds);
+            }
+        }
+    }
+
+    let mut combined = geometry_bounds_at(obj, &index);
+    for child in obj.getChildren() {
+        let Some(mut bounds) = object_bounds_at(&child, archive, time) else { continue };
+        if let Some(xform) = IXform::new(&child) {
+            if let Ok(sample) = xform.getSample(index(xform.getTimeSamplingIndex(), xform.getNumSamples())) {
+                bounds = bounds.transformed(&sample.matrix().as_dmat4());
+            }
+        }
+        merge_bounds(&mut combined, bounds);
+    }
+    combined
+}
+
+/// Stored or computed self bounds of a geometry object.
+fn geometry_bounds_at(obj: &IObject<'_>, index: &dyn Fn(u32, usize) -> usize) -> Option<crate::util::BBox3d> {
+    use crate::geom::{util as geom_util, ICurves, INuPatch, IPoints, IPolyMesh, ISubD};
+
+    let (ts_index, num_samples) = if let Some(s) = IPolyMesh::new(obj) {
+        (s.getTimeSamplingIndex(), s.getNumSamples())
+    } else if let Some(s) = ISubD::new(obj) {
+        (s.getTimeSamplingIndex(), s.getNumSamples())
+    } else if let Some(s) = IPoints::new(obj) {
+        (s.getTimeSamplingIndex(), s.getNumSamples())
+    } else if let Some(s) = ICurves::new(obj) {
+        (s.getTimeSamplingIndex(), s.getNumSamples())
+    } else if let Some(s) = INuPatch::new(obj) {
+        (s.getTimeSamplingIndex(), s.getNumSamples())
+    } else {
+        return None;
+    };
+    let i = index(ts_index, num_samples);
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    if let Some(bounds) = geom_util::read_self_bounds(geom.as_reader(), i) {
+        return Some(bounds);
+    }
+    let positions = geom_util::read_vec3_array(geom.as_reader(), "P", i).filter(|p| !p.is_empty())?;
+    let (min, max) = geom_util::compute_bounds_vec3(&positions);
+    Some(crate::util::BBox3d::new(min.as_dvec3(), max.as_dvec3()))
+}
+
 /// Output archive for writing Alembic files.
 pub struct OArchive {
     inner: crate::ogawa::OArchive,
@@ -303,6 +618,21 @@ impl OArchive {
         let inner = crate::ogawa::OArchive::create(path)?;
         Ok(Self { inner })
     }
+
+    /// Create an archive written into memory; finish it with
+    /// [`into_bytes`](Self::into_bytes).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut archive = OArchive::create_in_memory("cache.abc")?;
+    /// archive.write_archive(&root)?;
+    /// let bytes = archive.into_bytes()?;
+    /// let reread = IArchive::from_bytes("cache.abc", bytes)?;
+    /// ```
+    pub fn create_in_memory(name: impl Into<String>) -> Result<Self> {
+        let inner = crate::ogawa::OArchive::create_in_memory(name)?;
+        Ok(Self { inner })
+    }
     
     /// Get the archive name/path.
     pub fn getName(&self) -> &str {
@@ -337,6 +667,13 @@ impl OArchive {
         self.inner.getCompressionHint()
     }
 
+    /// Compress sample data with `codec` (non-standard, see
+    /// [`crate::ogawa::compress`]); `None` writes a standard archive.
+    #[cfg(feature = "compress")]
+    pub fn set_block_compression(&mut self, codec: Option<crate::ogawa::BlockCodec>) {
+        self.inner.set_block_compression(codec);
+    }
+
     /// Write the object hierarchy to the archive.
     /// 
     /// Call this with the root object after building your scene hierarchy.
@@ -391,6 +728,16 @@ impl OArchive {
         self.inner.isDedupEnabled()
     }
     
+    /// Number of sample writes that reused an already written block.
+    pub fn dedup_hits(&self) -> usize {
+        self.inner.dedup_hits()
+    }
+    
+    /// Number of bytes deduplication kept out of the file.
+    pub fn dedup_bytes_saved(&self) -> u64 {
+        self.inner.dedup_bytes_saved()
+    }
+    
     /// Check if this archive is valid.
     /// 
     /// In Rust, this always returns true for a successfully constructed archive.
@@ -403,6 +750,30 @@ impl OArchive {
     pub fn close(self) -> Result<()> {
         self.inner.close()
     }
+
+    /// Finalize an in-memory archive and return its bytes.
+    pub fn into_bytes(self) -> Result<Vec<u8>> {
+        self.inner.into_bytes()
+    }
+
+    /// Discard an unfinished archive and delete its temp file.
+    ///
+    /// Dropping an archive that was never written or closed does the same,
+    /// so error paths in exporters don't leave partial files behind. An
+    /// existing file at its path is left untouched. Once `write_archive` has
+    /// published the file this is a no-op.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut archive = OArchive::create("shot.abc")?;
+    /// if let Err(e) = build_root(&mut archive) {
+    ///     archive.abort(); // the previous shot.abc, if any, is kept
+    ///     return Err(e);
+    /// }
+    /// ```
+    pub fn abort(self) {
+        self.inner.abort()
+    }
 }
 
 // ============================================================================
@@ -515,10 +886,26 @@ impl<'a> IObject<'a> {
     }
     
     /// Get a child object by name.
+    /// 
+    /// Constant-time lookup (name index built when the object is opened).
     pub fn getChildByName(&self, name: &str) -> Option<IObject<'_>> {
         self.reader.as_ref().getChild(name).map(IObject::from_owned)
     }
     
+    /// Get a child object by name (alias of [`getChildByName`](Self::getChildByName)).
+    pub fn child_by_name(&self, name: &str) -> Option<IObject<'_>> {
+        self.getChildByName(name)
+    }
+    
+    /// Get the index of a child by name.
+    /// 
+    /// Child indices follow the order children were written to the archive,
+    /// so `getChild(child_index(name)?)` is the same object as
+    /// `getChildByName(name)`, and `getChildren()` yields children in that order.
+    pub fn child_index(&self, name: &str) -> Option<usize> {
+        self.reader.as_ref().getChildIndex(name)
+    }
+    
     /// Iterate over all children.
     pub fn getChildren(&self) -> impl Iterator<Item = IObject<'_>> + '_ {
         (0..self.getNumChildren()).filter_map(|i| self.getChild(i))
@@ -539,6 +926,12 @@ impl<'a> IObject<'a> {
         self.reader.as_ref().getMetaData()
     }
     
+    /// Get an archive time sampling by index, as referenced by the
+    /// `time_sampling_index` of this object's properties.
+    pub fn getArchiveTimeSampling(&self, index: u32) -> Option<&TimeSampling> {
+        self.reader.as_ref().getArchiveTimeSampling(index as usize)
+    }
+
     /// Get the header of a child object by index without creating a full object.
     pub fn getChildHeader(&self, index: usize) -> Option<&ObjectHeader> {
         self.reader.as_ref().getChildHeader(index)
@@ -587,6 +980,19 @@ impl<'a> IObject<'a> {
     pub fn isChildInstanceByName(&self, name: &str) -> bool {
         self.reader.as_ref().isChildInstanceByName(name)
     }
+
+    /// Check if this object is an instance (alias of [`isInstanceRoot`](Self::isInstanceRoot)).
+    ///
+    /// Instances read as their source object: schema, properties and
+    /// children come from the source, the name and path are the instance's.
+    pub fn is_instance(&self) -> bool {
+        self.isInstanceRoot()
+    }
+
+    /// Path of the object this instance refers to, `None` if not an instance.
+    pub fn instance_source_path(&self) -> Option<&str> {
+        Some(self.getInstanceSourcePath()).filter(|p| !p.is_empty())
+    }
     
     // ========================================================================
     // Hash support
@@ -607,7 +1013,37 @@ impl<'a> IObject<'a> {
     pub fn getChildrenHash(&self) -> Option<[u8; 16]> {
         self.reader.as_ref().getChildrenHash()
     }
+
+    /// SpookyHash digest of this object's properties as stored by the
+    /// writer (C++ `getPropertiesHash`); `None` if the archive has none.
+    pub fn properties_hash(&self) -> Option<[u8; 16]> {
+        self.getPropertiesHash()
+    }
+
+    /// SpookyHash digest of this object's children (names, metadata,
+    /// properties and grandchildren) as stored by the writer (C++
+    /// `getChildrenHash`). Equal digests mean identical subtrees, so it can
+    /// key asset dedup and cache invalidation without reading any samples.
+    pub fn children_hash(&self) -> Option<[u8; 16]> {
+        self.getChildrenHash()
+    }
     
+    // ========================================================================
+    // Bounds
+    // ========================================================================
+
+    /// Bounds of this object and everything below it at `time`, in this
+    /// object's own space: an Xform's own transform is not applied (the
+    /// `.childBnds` convention), child transforms are.
+    ///
+    /// Stored `.childBnds` / `.selfBnds` are used when present, otherwise
+    /// the bounds are computed from `P` and the child transforms. Samples are
+    /// picked nearest to `time`. `archive` must be the archive this object
+    /// was read from.
+    pub fn bounds_at(&self, archive: &IArchive, time: crate::util::Chrono) -> Option<crate::util::BBox3d> {
+        object_bounds_at(self, archive, time)
+    }
+
     // Note: getArchive() is not implemented in Rust due to ownership constraints.
     // In C++ Alembic, IObject stores a pointer back to its archive, but Rust's
     // borrow checker prevents this pattern. Use the archive reference directly
@@ -735,6 +1171,11 @@ impl<'a> IProperty<'a> {
         self.reader.getName()
     }
     
+    /// Get the time sampling the property's samples are laid out on.
+    pub fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        self.reader.getTimeSampling()
+    }
+    
     /// Check if this is a scalar property.
     pub fn isScalar(&self) -> bool {
         self.reader.isScalar()
@@ -764,6 +1205,19 @@ impl<'a> IProperty<'a> {
     pub fn asArray(&self) -> Option<&dyn ArrayPropertyReader> {
         self.reader.asArray()
     }
+
+    /// View a scalar or array property as raw sample bytes.
+    ///
+    /// Works for every POD type, and is the only way to read properties
+    /// whose POD code this library doesn't know (see [`RawProperty`]).
+    pub fn asRaw(&self) -> Option<RawProperty<'_>> {
+        if let Some(array) = self.reader.asArray() {
+            Some(RawProperty { header: self.getHeader(), scalar: None, array: Some(array) })
+        } else {
+            self.reader.asScalar()
+                .map(|scalar| RawProperty { header: self.getHeader(), scalar: Some(scalar), array: None })
+        }
+    }
     
     /// Check if this property is valid.
     #[inline]
@@ -777,6 +1231,42 @@ impl<'a> IProperty<'a> {
     pub fn getTimeSamplingIndex(&self) -> u32 {
         self.reader.getHeader().time_sampling_index
     }
+
+    /// SpookyHash digest of this property: name, data type, metadata and
+    /// the stored key of every sample (sub-property digests for compounds).
+    ///
+    /// Only sample keys are read, not sample data. The time sampling index
+    /// is not hashed, so the digest is stable across archives that number
+    /// their time samplings differently.
+    pub fn digest(&self) -> Result<[u8; 16]> {
+        let header = self.getHeader();
+        let mut hasher = spooky_hash::SpookyHash::new(0, 0);
+        hasher.update(header.name.as_bytes());
+        hasher.update(&[header.pod_code(), header.data_type.extent]);
+        hasher.update(header.meta_data.serialize().as_bytes());
+        if let Some(compound) = self.asCompound() {
+            for i in 0..compound.getNumProperties() {
+                if let Some(child) = compound.getProperty(i) {
+                    hasher.update(&child.digest()?);
+                }
+            }
+        } else if let Some(scalar) = self.asScalar() {
+            hasher.update(&(scalar.getNumSamples() as u64).to_le_bytes());
+            for i in 0..scalar.getNumSamples() {
+                hasher.update(&scalar.getKey(i)?);
+            }
+        } else if let Some(array) = self.asArray() {
+            hasher.update(&(array.getNumSamples() as u64).to_le_bytes());
+            for i in 0..array.getNumSamples() {
+                hasher.update(&array.getKey(i)?);
+            }
+        }
+        let (h1, h2) = hasher.finalize();
+        let mut digest = [0u8; 16];
+        digest[..8].copy_from_slice(&h1.to_le_bytes());
+        digest[8..].copy_from_slice(&h2.to_le_bytes());
+        Ok(digest)
+    }
 }
 
 /// Input scalar property (single value per sample).
@@ -804,20 +1294,14 @@ impl<'a> IScalarProperty<'a> {
 
     /// Read a sample into the provided buffer.
     /// 
-    /// For time-based selectors, use `read_sample_with_ts()` which properly resolves time.
-    /// This method defaults time-based selectors to sample 0.
+    /// Time-based selectors resolve against the property's own time sampling
+    /// (see [`getTimeSampling`](Self::getTimeSampling)).
     pub fn read_sample(&self, sel: impl Into<SampleSelector>, out: &mut [u8]) -> Result<()> {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0, // Use read_sample_with_ts() for time-based selection
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         self.reader.getSample(index, out)
     }
     
-    /// Read a sample with proper time-based selection.
-    /// 
-    /// Pass the TimeSampling from `archive.getTimeSampling(prop.getTimeSamplingIndex())`.
+    /// Read a sample with time-based selection against an explicit time sampling.
     pub fn read_sample_with_ts(&self, sel: impl Into<SampleSelector>, ts: &TimeSampling, out: &mut [u8]) -> Result<()> {
         let index = sel.into().get_index(ts, self.getNumSamples());
         self.reader.getSample(index, out)
@@ -829,6 +1313,13 @@ impl<'a> IScalarProperty<'a> {
     pub fn getTimeSamplingIndex(&self) -> u32 {
         self.reader.getHeader().time_sampling_index
     }
+
+    /// Get the time sampling the samples are laid out on.
+    ///
+    /// Reference: IScalarProperty::getTimeSampling() (Abc/IScalarProperty.h)
+    pub fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        self.reader.getTimeSampling()
+    }
     
     /// Get sample by index (Alembic-style API).
     pub fn getSample(&self, index: usize, out: &mut [u8]) -> Result<()> {
@@ -920,11 +1411,7 @@ impl<'a, T: bytemuck::Pod + Default> ITypedScalarProperty<'a, T> {
     
     /// Get a typed value using a sample selector.
     pub fn get(&self, sel: impl Into<SampleSelector>) -> Result<T> {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0,
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         self.get_value(index)
     }
     
@@ -940,6 +1427,13 @@ impl<'a, T: bytemuck::Pod + Default> ITypedScalarProperty<'a, T> {
     pub fn getTimeSamplingIndex(&self) -> u32 {
         self.reader.getHeader().time_sampling_index
     }
+
+    /// Get the time sampling the samples are laid out on.
+    ///
+    /// Reference: IScalarProperty::getTimeSampling() (Abc/IScalarProperty.h)
+    pub fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        self.reader.getTimeSampling()
+    }
     
     /// Check if valid.
     pub fn valid(&self) -> bool {
@@ -998,6 +1492,11 @@ pub struct IArrayProperty<'a> {
 }
 
 impl<'a> IArrayProperty<'a> {
+    /// Wrap an array property reader (e.g. from [`IProperty::asArray`]).
+    pub fn new(reader: &'a dyn ArrayPropertyReader) -> Self {
+        Self { reader }
+    }
+
     /// Get the property header.
     pub fn getHeader(&self) -> &PropertyHeader {
         self.reader.getHeader()
@@ -1016,14 +1515,8 @@ impl<'a> IArrayProperty<'a> {
     }
 
     /// Get the number of elements in a sample.
-    /// 
-    /// For time-based selectors, use `sample_len_with_ts()`.
     pub fn sample_len(&self, sel: impl Into<SampleSelector>) -> Result<usize> {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0,
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         self.reader.getSampleLen(index)
     }
     
@@ -1035,23 +1528,45 @@ impl<'a> IArrayProperty<'a> {
 
     /// Read a sample as bytes.
     /// 
-    /// For time-based selectors, use `read_sample_vec_with_ts()`.
+    /// Time-based selectors resolve against the property's own time sampling
+    /// (see [`getTimeSampling`](Self::getTimeSampling)).
     pub fn read_sample_vec(&self, sel: impl Into<SampleSelector>) -> Result<Vec<u8>> {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0,
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         self.reader.getSampleVec(index)
     }
     
-    /// Read a sample as bytes with time-based selection.
-    /// 
-    /// Pass the TimeSampling from `archive.getTimeSampling(prop.getTimeSamplingIndex())`.
+    /// Read a sample as bytes with time-based selection against an explicit
+    /// time sampling.
     pub fn read_sample_vec_with_ts(&self, sel: impl Into<SampleSelector>, ts: &TimeSampling) -> Result<Vec<u8>> {
         let index = sel.into().get_index(ts, self.getNumSamples());
         self.reader.getSampleVec(index)
     }
+
+    /// Read a float32 array sample linearly interpolated between the samples
+    /// bracketing `sel`.
+    ///
+    /// Both bracketing samples go through the archive's sample cache, so
+    /// scrubbing back and forth between the same frames doesn't re-read the
+    /// file. Falls back to the floor sample when the two sample lengths
+    /// differ (topology change).
+    pub fn read_interpolated_f32(&self, sel: impl Into<SampleSelector>, ts: &TimeSampling) -> Result<Vec<f32>> {
+        if self.getHeader().data_type.pod != PlainOldDataType::Float32 {
+            return Err(crate::util::Error::invalid(format!(
+                "{}: expected float32 data, got {:?}", self.getHeader().name, self.getHeader().data_type.pod
+            )));
+        }
+        let interp = sel.into().get_sample_interp(ts, self.getNumSamples());
+        let floor: Vec<f32> = bytemuck::pod_collect_to_vec(&self.reader.getSampleVec(interp.floor_index)?);
+        if interp.floor_index == interp.ceil_index || interp.alpha <= 0.0 {
+            return Ok(floor);
+        }
+        let ceil: Vec<f32> = bytemuck::pod_collect_to_vec(&self.reader.getSampleVec(interp.ceil_index)?);
+        if ceil.len() != floor.len() {
+            return Ok(floor);
+        }
+        let t = interp.alpha as f32;
+        Ok(floor.iter().zip(&ceil).map(|(a, b)| a + (b - a) * t).collect())
+    }
     
     /// Get the time sampling index.
     /// 
@@ -1059,17 +1574,20 @@ impl<'a> IArrayProperty<'a> {
     pub fn getTimeSamplingIndex(&self) -> u32 {
         self.reader.getHeader().time_sampling_index
     }
+
+    /// Get the time sampling the samples are laid out on.
+    ///
+    /// Reference: IArrayProperty::getTimeSampling() (Abc/IArrayProperty.h)
+    pub fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        self.reader.getTimeSampling()
+    }
     
     /// Get the key (digest) of a sample for deduplication.
     /// 
     /// Returns the 16-byte MD5 digest stored with the sample.
     /// Samples with the same key contain identical data.
     pub fn get_key(&self, sel: impl Into<SampleSelector>) -> Result<crate::core::SampleDigest> {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0,
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         self.reader.getKey(index)
     }
     
@@ -1083,11 +1601,7 @@ impl<'a> IArrayProperty<'a> {
     /// 
     /// Returns `[num_elements]` for 1D arrays, `[rows, cols]` for 2D, etc.
     pub fn get_dimensions(&self, sel: impl Into<SampleSelector>) -> Result<Vec<usize>> {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0,
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         self.reader.getDimensions(index)
     }
     
@@ -1112,11 +1626,7 @@ impl<'a> IArrayProperty<'a> {
         Src: bytemuck::Pod + Copy,
         Dst: From<Src> + Clone,
     {
-        let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i.min(self.getNumSamples().saturating_sub(1)),
-            _ => 0,
-        };
+        let index = sel.into().resolve(self.reader.getTimeSampling(), self.getNumSamples());
         let data = self.reader.getSampleVec(index)?;
         let src_slice: &[Src] = bytemuck::try_cast_slice(&data).map_err(|_| crate::util::Error::invalid("cast error"))?;
         Ok(src_slice.iter().map(|&v| Dst::from(v)).collect())
@@ -1216,6 +1726,77 @@ impl<'a> IArrayProperty<'a> {
     }
 }
 
+/// Scalar or array property read as uninterpreted sample bytes.
+///
+/// Files from newer writers may use POD codes this library doesn't know.
+/// Such properties report `PlainOldDataType::Unknown` as their POD type;
+/// their original code, extent and sample bytes stay available here, so
+/// they can be inspected or copied unchanged.
+pub struct RawProperty<'a> {
+    header: &'a PropertyHeader,
+    scalar: Option<&'a dyn ScalarPropertyReader>,
+    array: Option<&'a dyn ArrayPropertyReader>,
+}
+
+impl<'a> RawProperty<'a> {
+    /// Get the property header.
+    pub fn getHeader(&self) -> &PropertyHeader {
+        self.header
+    }
+
+    /// POD code as stored in the file.
+    pub fn pod_code(&self) -> u8 {
+        self.header.pod_code()
+    }
+
+    /// Number of components per element.
+    pub fn extent(&self) -> u8 {
+        self.header.data_type.extent
+    }
+
+    /// Whether the POD type is one this library can interpret.
+    pub fn is_known(&self) -> bool {
+        self.header.unknown_pod.is_none()
+    }
+
+    /// Check if this is an array property.
+    pub fn isArray(&self) -> bool {
+        self.array.is_some()
+    }
+
+    /// Get the number of samples.
+    pub fn getNumSamples(&self) -> usize {
+        match (self.scalar, self.array) {
+            (_, Some(array)) => array.getNumSamples(),
+            (Some(scalar), _) => scalar.getNumSamples(),
+            _ => 0,
+        }
+    }
+
+    /// Stored bytes of a sample.
+    pub fn getSample(&self, index: usize) -> Result<Vec<u8>> {
+        match (self.scalar, self.array) {
+            (_, Some(array)) => array.getSampleVec(index),
+            (Some(scalar), _) => scalar.getRawSample(index),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Stored dimensions of an array sample (in bytes when neither the file
+    /// nor the POD type gives a shape); empty for scalars.
+    pub fn getDimensions(&self, index: usize) -> Result<Vec<usize>> {
+        match self.array {
+            Some(array) => array.getDimensions(index),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Size of a sample in bytes.
+    pub fn sample_size(&self, index: usize) -> Result<usize> {
+        self.getSample(index).map(|data| data.len())
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
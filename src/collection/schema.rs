@@ -1,8 +1,11 @@
 //! Collection schema implementation.
 //!
-//! Provides reading of collection data from Alembic files.
+//! Provides reading of collection data from Alembic files. Each collection
+//! is a string array property of object paths under `.collections`, with
+//! its own samples when membership is animated.
 
-use crate::abc::IObject;
+use crate::abc::{ICompoundProperty, IObject};
+use crate::core::SampleSelector;
 use super::COLLECTIONS_SCHEMA;
 
 /// A single collection containing object paths.
@@ -83,66 +86,55 @@ impl<'a> ICollections<'a> {
         self.object.getFullName()
     }
     
+    /// Run `f` on the `.collections` compound.
+    fn with_collections<R>(&self, f: impl FnOnce(&ICompoundProperty<'_>) -> Option<R>) -> Option<R> {
+        let props = self.object.getProperties();
+        let coll_prop = props.getPropertyByName(".collections")?;
+        f(&coll_prop.asCompound()?)
+    }
+
     /// Get the number of collections.
     pub fn num_collections(&self) -> usize {
-        let props = self.object.getProperties();
-        let Some(coll_prop) = props.getPropertyByName(".collections") else {
-            return 0;
-        };
-        let Some(coll) = coll_prop.asCompound() else {
-            return 0;
-        };
-        coll.getNumProperties()
+        self.with_collections(|coll| Some(coll.getNumProperties())).unwrap_or(0)
     }
     
     /// Get collection names.
     pub fn collection_names(&self) -> Vec<String> {
-        let props = self.object.getProperties();
-        let Some(coll_prop) = props.getPropertyByName(".collections") else {
-            return Vec::new();
-        };
-        let Some(coll) = coll_prop.asCompound() else {
-            return Vec::new();
-        };
-        coll.getPropertyNames()
+        self.with_collections(|coll| Some(coll.getPropertyNames())).unwrap_or_default()
     }
     
-    /// Get a collection by name.
+    /// Get a collection by name (its first sample).
     pub fn get(&self, name: &str) -> Option<Collection> {
-        let props = self.object.getProperties();
-        let coll_prop = props.getPropertyByName(".collections")?;
-        let coll = coll_prop.asCompound()?;
-        let col_prop = coll.getPropertyByName(name)?;
-        let array = col_prop.asArray()?;
-        
-        // Read the array of strings (paths)
-        let data = array.getSampleVec(0).ok()?;
-        
-        let mut collection = Collection::new(name);
-        
-        // Parse null-terminated strings from the data
-        let mut start = 0;
-        for (i, &byte) in data.iter().enumerate() {
-            if byte == 0 {
-                if i > start {
-                    if let Ok(s) = String::from_utf8(data[start..i].to_vec()) {
-                        collection.add(&s);
-                    }
-                }
-                start = i + 1;
-            }
-        }
-        
-        // Handle case where there's no trailing null
-        if start < data.len() {
-            if let Ok(s) = String::from_utf8(data[start..].to_vec()) {
-                if !s.is_empty() {
-                    collection.add(&s);
-                }
-            }
-        }
-        
-        Some(collection)
+        self.get_sample(name, 0)
+    }
+
+    /// Get the members of a collection at a sample.
+    ///
+    /// Time-based selectors resolve against the collection's own time
+    /// sampling; constant collections return their single sample.
+    pub fn get_sample(&self, name: &str, sel: impl Into<SampleSelector>) -> Option<Collection> {
+        self.with_collections(|coll| {
+            let col_prop = coll.getPropertyByName(name)?;
+            let array = col_prop.asArray()?;
+            let index = sel.into().resolve(array.getTimeSampling(), array.getNumSamples());
+            let paths = array.getAsStringArray(index).ok()?;
+            Some(Collection { name: name.to_string(), paths })
+        })
+    }
+
+    /// Number of samples of a collection (0 if there's no such collection).
+    pub fn num_samples(&self, name: &str) -> usize {
+        self.with_collections(|coll| Some(coll.getPropertyByName(name)?.asArray()?.getNumSamples())).unwrap_or(0)
+    }
+
+    /// Check if a collection has the same members at every sample.
+    pub fn is_constant(&self, name: &str) -> bool {
+        self.with_collections(|coll| Some(coll.getPropertyByName(name)?.asArray()?.isConstant())).unwrap_or(true)
+    }
+
+    /// Time sampling index of a collection.
+    pub fn time_sampling_index(&self, name: &str) -> Option<u32> {
+        self.with_collections(|coll| Some(coll.getPropertyHeaderByName(name)?.time_sampling_index))
     }
     
     /// Get a collection by index.
@@ -153,14 +145,7 @@ impl<'a> ICollections<'a> {
     
     /// Check if a collection exists.
     pub fn has_collection(&self, name: &str) -> bool {
-        let props = self.object.getProperties();
-        let Some(coll_prop) = props.getPropertyByName(".collections") else {
-            return false;
-        };
-        let Some(coll) = coll_prop.asCompound() else {
-            return false;
-        };
-        coll.hasProperty(name)
+        self.with_collections(|coll| Some(coll.hasProperty(name))).unwrap_or(false)
     }
     
     /// Get all collections.
@@ -170,6 +155,15 @@ impl<'a> ICollections<'a> {
             .filter_map(|name| self.get(name))
             .collect()
     }
+
+    /// Get all collections at a sample (see [`get_sample`](Self::get_sample)).
+    pub fn all_at(&self, sel: impl Into<SampleSelector>) -> Vec<Collection> {
+        let sel = sel.into();
+        self.collection_names()
+            .iter()
+            .filter_map(|name| self.get_sample(name, sel))
+            .collect()
+    }
     
     /// Check if this schema is valid.
     #[inline]
@@ -0,0 +1,474 @@
+//! Structural and numeric comparison of two archives.
+//!
+//! [`compare_archives`] walks both object hierarchies in parallel and reports
+//! added/removed objects and properties, schema and data type mismatches,
+//! differing sample counts and times, and per-sample numeric deltas that
+//! exceed the configured tolerances. It is meant for validating the writer
+//! against files produced by the C++ library, so the comparison works on
+//! decoded sample values and ignores how the files are laid out on disk.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::compare::{compare_archives, CompareOptions};
+//!
+//! let a = IArchive::open("cpp.abc")?;
+//! let b = IArchive::open("rust.abc")?;
+//! let report = compare_archives(&a, &b, &CompareOptions::with_tolerance(1e-6))?;
+//! for diff in &report.differences {
+//!     println!("{}", diff);
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::abc::{IArchive, ICompoundProperty, IObject, IProperty};
+use crate::util::{Chrono, PlainOldDataType, Result};
+
+/// Tolerances and limits for [`compare_archives`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareOptions {
+    /// Absolute tolerance for numeric sample values.
+    pub abs_tolerance: f64,
+    /// Relative tolerance, scaled by the larger magnitude of the two values.
+    pub rel_tolerance: f64,
+    /// Tolerance for sample times (seconds).
+    pub time_tolerance: Chrono,
+    /// Also compare object and property metadata.
+    pub compare_metadata: bool,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            abs_tolerance: 0.0,
+            rel_tolerance: 0.0,
+            time_tolerance: 1e-9,
+            compare_metadata: false,
+        }
+    }
+}
+
+impl CompareOptions {
+    /// Exact comparison except for an absolute numeric tolerance.
+    pub fn with_tolerance(abs_tolerance: f64) -> Self {
+        Self { abs_tolerance, ..Default::default() }
+    }
+
+    /// True if `a` and `b` are equal within the tolerances (NaNs compare equal).
+    pub fn values_match(&self, a: f64, b: f64) -> bool {
+        if a == b || (a.is_nan() && b.is_nan()) {
+            return true;
+        }
+        (a - b).abs() <= self.abs_tolerance + self.rel_tolerance * a.abs().max(b.abs())
+    }
+}
+
+/// One difference between archive `a` and archive `b`.
+///
+/// Object paths are full names (`/` for the root); property paths are
+/// relative to the object, with compounds separated by `/` (`.geom/P`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    /// Object present only in `b`.
+    ObjectAdded { path: String },
+    /// Object present only in `a` (its descendants are not listed).
+    ObjectRemoved { path: String },
+    /// Objects with different schemas.
+    SchemaMismatch { path: String, a: String, b: String },
+    /// Metadata differs (only with [`CompareOptions::compare_metadata`]).
+    MetaDataMismatch { path: String, property: Option<String>, a: String, b: String },
+    /// Property present only in `b`.
+    PropertyAdded { path: String, property: String },
+    /// Property present only in `a`.
+    PropertyRemoved { path: String, property: String },
+    /// Property kind (scalar/array/compound) or data type differs.
+    TypeMismatch { path: String, property: String, a: String, b: String },
+    /// Different number of samples.
+    SampleCountMismatch { path: String, property: String, a: usize, b: usize },
+    /// First sample whose time differs.
+    TimeMismatch { path: String, property: String, sample: usize, a: Chrono, b: Chrono },
+    /// Array sample with different dimensions.
+    ExtentMismatch { path: String, property: String, sample: usize, a: Vec<usize>, b: Vec<usize> },
+    /// Numeric sample with values outside the tolerances.
+    SampleMismatch {
+        path: String,
+        property: String,
+        sample: usize,
+        /// Number of values outside the tolerances.
+        count: usize,
+        /// Index of the value with the largest delta.
+        element: usize,
+        a: f64,
+        b: f64,
+    },
+    /// Non-numeric (string) sample differs.
+    ValueMismatch { path: String, property: String, sample: usize },
+}
+
+impl Difference {
+    /// Object path the difference refers to.
+    pub fn path(&self) -> &str {
+        match self {
+            Difference::ObjectAdded { path }
+            | Difference::ObjectRemoved { path }
+            | Difference::SchemaMismatch { path, .. }
+            | Difference::MetaDataMismatch { path, .. }
+            | Difference::PropertyAdded { path, .. }
+            | Difference::PropertyRemoved { path, .. }
+            | Difference::TypeMismatch { path, .. }
+            | Difference::SampleCountMismatch { path, .. }
+            | Difference::TimeMismatch { path, .. }
+            | Difference::ExtentMismatch { path, .. }
+            | Difference::SampleMismatch { path, .. }
+            | Difference::ValueMismatch { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::ObjectAdded { path } => write!(f, "+ {}", path),
+            Difference::ObjectRemoved { path } => write!(f, "- {}", path),
+            Difference::SchemaMismatch { path, a, b } => write!(f, "~ {}: schema {:?} -> {:?}", path, a, b),
+            Difference::MetaDataMismatch { path, property: None, a, b } => {
+                write!(f, "~ {}: metadata {:?} -> {:?}", path, a, b)
+            }
+            Difference::MetaDataMismatch { path, property: Some(p), a, b } => {
+                write!(f, "~ {} {}: metadata {:?} -> {:?}", path, p, a, b)
+            }
+            Difference::PropertyAdded { path, property } => write!(f, "+ {} {}", path, property),
+            Difference::PropertyRemoved { path, property } => write!(f, "- {} {}", path, property),
+            Difference::TypeMismatch { path, property, a, b } => write!(f, "~ {} {}: type {} -> {}", path, property, a, b),
+            Difference::SampleCountMismatch { path, property, a, b } => {
+                write!(f, "~ {} {}: {} -> {} samples", path, property, a, b)
+            }
+            Difference::TimeMismatch { path, property, sample, a, b } => {
+                write!(f, "~ {} {}[{}]: time {} -> {}", path, property, sample, a, b)
+            }
+            Difference::ExtentMismatch { path, property, sample, a, b } => {
+                write!(f, "~ {} {}[{}]: dimensions {:?} -> {:?}", path, property, sample, a, b)
+            }
+            Difference::SampleMismatch { path, property, sample, count, element, a, b } => write!(
+                f,
+                "~ {} {}[{}]: {} values differ, max at [{}]: {} -> {} (delta {:e})",
+                path, property, sample, count, element, a, b, (a - b).abs()
+            ),
+            Difference::ValueMismatch { path, property, sample } => {
+                write!(f, "~ {} {}[{}]: value differs", path, property, sample)
+            }
+        }
+    }
+}
+
+/// Result of [`compare_archives`].
+#[derive(Debug, Clone, Default)]
+pub struct CompareReport {
+    pub differences: Vec<Difference>,
+    /// Objects present in both archives.
+    pub objects_compared: usize,
+    /// Leaf properties present in both archives with matching types.
+    pub properties_compared: usize,
+    /// Samples compared value-by-value.
+    pub samples_compared: usize,
+    /// Largest numeric delta seen, including deltas within tolerance.
+    pub max_delta: f64,
+}
+
+impl CompareReport {
+    /// True if no differences were found.
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// Compare archive `a` (reference) against archive `b`.
+pub fn compare_archives(a: &IArchive, b: &IArchive, opts: &CompareOptions) -> Result<CompareReport> {
+    let mut cmp = Comparer { a, b, opts, report: CompareReport::default() };
+    cmp.compare_objects(&a.getTop(), &b.getTop())?;
+    Ok(cmp.report)
+}
+
+struct Comparer<'x> {
+    a: &'x IArchive,
+    b: &'x IArchive,
+    opts: &'x CompareOptions,
+    report: CompareReport,
+}
+
+impl Comparer<'_> {
+    fn push(&mut self, diff: Difference) {
+        self.report.differences.push(diff);
+    }
+
+    fn compare_objects(&mut self, a: &IObject, b: &IObject) -> Result<()> {
+        let path = if a.getFullName().is_empty() { "/" } else { a.getFullName() }.to_string();
+        self.report.objects_compared += 1;
+
+        let schema_a = a.getMetaData().get("schema").unwrap_or_default().to_string();
+        let schema_b = b.getMetaData().get("schema").unwrap_or_default().to_string();
+        if schema_a != schema_b {
+            self.push(Difference::SchemaMismatch { path: path.clone(), a: schema_a, b: schema_b });
+        }
+        if self.opts.compare_metadata {
+            let (ma, mb) = (a.getMetaData().serialize(), b.getMetaData().serialize());
+            if ma != mb {
+                self.push(Difference::MetaDataMismatch { path: path.clone(), property: None, a: ma, b: mb });
+            }
+        }
+
+        self.compare_compounds(&path, "", &a.getProperties(), &b.getProperties())?;
+
+        let children_b: Vec<IObject> = b.getChildren().collect();
+        let mut matched = BTreeSet::new();
+        for child_a in a.getChildren() {
+            match children_b.iter().find(|c| c.getName() == child_a.getName()) {
+                Some(child_b) => {
+                    matched.insert(child_a.getName().to_string());
+                    self.compare_objects(&child_a, child_b)?;
+                }
+                None => self.push(Difference::ObjectRemoved { path: child_a.getFullName().to_string() }),
+            }
+        }
+        for child_b in children_b.iter().filter(|c| !matched.contains(c.getName())) {
+            self.push(Difference::ObjectAdded { path: child_b.getFullName().to_string() });
+        }
+        Ok(())
+    }
+
+    fn compare_compounds(
+        &mut self,
+        path: &str,
+        prefix: &str,
+        a: &ICompoundProperty<'_>,
+        b: &ICompoundProperty<'_>,
+    ) -> Result<()> {
+        for name in a.getPropertyNames() {
+            let property = format!("{}{}", prefix, name);
+            let (Some(pa), Some(pb)) = (a.getPropertyByName(&name), b.getPropertyByName(&name)) else {
+                self.push(Difference::PropertyRemoved { path: path.to_string(), property });
+                continue;
+            };
+            self.compare_properties(path, &property, &pa, &pb)?;
+        }
+        for name in b.getPropertyNames().into_iter().filter(|n| !a.hasProperty(n)) {
+            self.push(Difference::PropertyAdded { path: path.to_string(), property: format!("{}{}", prefix, name) });
+        }
+        Ok(())
+    }
+
+    fn compare_properties(&mut self, path: &str, property: &str, a: &IProperty<'_>, b: &IProperty<'_>) -> Result<()> {
+        let (ha, hb) = (a.getHeader(), b.getHeader());
+        if self.opts.compare_metadata {
+            let (ma, mb) = (ha.meta_data.serialize(), hb.meta_data.serialize());
+            if ma != mb {
+                self.push(Difference::MetaDataMismatch {
+                    path: path.to_string(),
+                    property: Some(property.to_string()),
+                    a: ma,
+                    b: mb,
+                });
+            }
+        }
+
+        if let (Some(ca), Some(cb)) = (a.asCompound(), b.asCompound()) {
+            return self.compare_compounds(path, &format!("{}/", property), &ca, &cb);
+        }
+        let (ta, tb) = (type_name(a), type_name(b));
+        if ta != tb {
+            self.push(Difference::TypeMismatch { path: path.to_string(), property: property.to_string(), a: ta, b: tb });
+            return Ok(());
+        }
+        self.report.properties_compared += 1;
+
+        let (na, nb) = (num_samples(a), num_samples(b));
+        if na != nb {
+            self.push(Difference::SampleCountMismatch { path: path.to_string(), property: property.to_string(), a: na, b: nb });
+        }
+        let n = na.min(nb);
+
+        let ts_a = self.a.getTimeSampling(ha.time_sampling_index as usize);
+        let ts_b = self.b.getTimeSampling(hb.time_sampling_index as usize);
+        if let (Some(ts_a), Some(ts_b)) = (ts_a, ts_b) {
+            for i in 0..n {
+                let (t_a, t_b) = (ts_a.sample_time(i, na), ts_b.sample_time(i, nb));
+                if (t_a - t_b).abs() > self.opts.time_tolerance {
+                    self.push(Difference::TimeMismatch {
+                        path: path.to_string(),
+                        property: property.to_string(),
+                        sample: i,
+                        a: t_a,
+                        b: t_b,
+                    });
+                    break;
+                }
+            }
+        }
+
+        let pod = ha.data_type.pod;
+        for i in 0..n {
+            let (sa, sb) = (read_sample(a, i)?, read_sample(b, i)?);
+            self.report.samples_compared += 1;
+            if sa.dims != sb.dims {
+                self.push(Difference::ExtentMismatch {
+                    path: path.to_string(),
+                    property: property.to_string(),
+                    sample: i,
+                    a: sa.dims,
+                    b: sb.dims,
+                });
+                continue;
+            }
+            match (pod_values(pod, &sa.data), pod_values(pod, &sb.data)) {
+                (Some(va), Some(vb)) if va.len() == vb.len() => {
+                    if let Some(diff) = self.compare_values(&va, &vb) {
+                        let SampleDelta { count, element, a, b } = diff;
+                        self.push(Difference::SampleMismatch {
+                            path: path.to_string(),
+                            property: property.to_string(),
+                            sample: i,
+                            count,
+                            element,
+                            a,
+                            b,
+                        });
+                    }
+                }
+                _ => {
+                    if sa.data != sb.data {
+                        self.push(Difference::ValueMismatch { path: path.to_string(), property: property.to_string(), sample: i });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Worst out-of-tolerance value of a sample, if any.
+    fn compare_values(&mut self, a: &[f64], b: &[f64]) -> Option<SampleDelta> {
+        let mut worst: Option<SampleDelta> = None;
+        let mut count = 0;
+        for (i, (&x, &y)) in a.iter().zip(b).enumerate() {
+            let delta = if x.is_nan() && y.is_nan() { 0.0 } else { (x - y).abs() };
+            if !delta.is_nan() {
+                self.report.max_delta = self.report.max_delta.max(delta);
+            }
+            if self.opts.values_match(x, y) {
+                continue;
+            }
+            count += 1;
+            let worse = match &worst {
+                Some(w) => delta.is_nan() || delta > (w.a - w.b).abs(),
+                None => true,
+            };
+            if worse {
+                worst = Some(SampleDelta { count: 0, element: i, a: x, b: y });
+            }
+        }
+        worst.map(|w| SampleDelta { count, ..w })
+    }
+}
+
+struct SampleDelta {
+    count: usize,
+    element: usize,
+    a: f64,
+    b: f64,
+}
+
+/// Decoded sample bytes plus array dimensions (empty for scalars).
+struct RawSample {
+    data: Vec<u8>,
+    dims: Vec<usize>,
+}
+
+fn read_sample(prop: &IProperty<'_>, index: usize) -> Result<RawSample> {
+    if let Some(scalar) = prop.asScalar() {
+        Ok(RawSample { data: scalar.getSampleVec(index)?, dims: Vec::new() })
+    } else if let Some(array) = prop.asArray() {
+        Ok(RawSample { data: array.getSampleVec(index)?, dims: array.getDimensions(index)? })
+    } else {
+        Ok(RawSample { data: Vec::new(), dims: Vec::new() })
+    }
+}
+
+fn num_samples(prop: &IProperty<'_>) -> usize {
+    if let Some(scalar) = prop.asScalar() {
+        scalar.getNumSamples()
+    } else if let Some(array) = prop.asArray() {
+        array.getNumSamples()
+    } else {
+        0
+    }
+}
+
+/// "scalar float32_t[3]" style description of kind and data type.
+fn type_name(prop: &IProperty<'_>) -> String {
+    let kind = if prop.isScalar() {
+        "scalar"
+    } else if prop.isArray() {
+        "array"
+    } else {
+        return "compound".to_string();
+    };
+    let dt = prop.getHeader().data_type;
+    format!("{} {}[{}]", kind, dt.pod.name(), dt.extent)
+}
+
+/// Numeric values of a raw little-endian sample, one per component, or
+/// `None` for strings and unknown types.
+pub fn pod_values(pod: PlainOldDataType, data: &[u8]) -> Option<Vec<f64>> {
+    use PlainOldDataType as P;
+    fn chunks<const N: usize>(data: &[u8], f: impl Fn([u8; N]) -> f64) -> Vec<f64> {
+        data.chunks_exact(N).map(|c| f(c.try_into().unwrap())).collect()
+    }
+    Some(match pod {
+        P::Boolean => data.iter().map(|&v| (v != 0) as u8 as f64).collect(),
+        P::Uint8 => data.iter().map(|&v| v as f64).collect(),
+        P::Int8 => data.iter().map(|&v| v as i8 as f64).collect(),
+        P::Uint16 => chunks(data, |c| u16::from_le_bytes(c) as f64),
+        P::Int16 => chunks(data, |c| i16::from_le_bytes(c) as f64),
+        P::Uint32 => chunks(data, |c| u32::from_le_bytes(c) as f64),
+        P::Int32 => chunks(data, |c| i32::from_le_bytes(c) as f64),
+        P::Uint64 => chunks(data, |c| u64::from_le_bytes(c) as f64),
+        P::Int64 => chunks(data, |c| i64::from_le_bytes(c) as f64),
+        P::Float16 => chunks(data, |c| half::f16::from_le_bytes(c).to_f64()),
+        P::Float32 => chunks(data, |c| f32::from_le_bytes(c) as f64),
+        P::Float64 => chunks(data, f64::from_le_bytes),
+        P::String | P::Wstring | P::Unknown => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values_match_tolerances() {
+        let exact = CompareOptions::default();
+        assert!(exact.values_match(1.0, 1.0));
+        assert!(exact.values_match(f64::NAN, f64::NAN));
+        assert!(!exact.values_match(1.0, 1.0 + 1e-12));
+
+        let abs = CompareOptions::with_tolerance(1e-3);
+        assert!(abs.values_match(1.0, 1.0005));
+        assert!(!abs.values_match(1.0, 1.002));
+        assert!(!abs.values_match(1.0, f64::NAN));
+
+        let rel = CompareOptions { rel_tolerance: 1e-3, ..Default::default() };
+        assert!(rel.values_match(1000.0, 1000.5));
+        assert!(!rel.values_match(1.0, 1.5));
+    }
+
+    #[test]
+    fn test_pod_values() {
+        let data: Vec<u8> = [1.5f32, -2.0].iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(pod_values(PlainOldDataType::Float32, &data), Some(vec![1.5, -2.0]));
+        assert_eq!(pod_values(PlainOldDataType::Int16, &[0xff, 0xff]), Some(vec![-1.0]));
+        assert_eq!(pod_values(PlainOldDataType::Boolean, &[0, 7]), Some(vec![0.0, 1.0]));
+        assert_eq!(pod_values(PlainOldDataType::String, b"abc"), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Copying objects and properties from a read archive into writer objects.
+//!
+//! [`copy_object`] and [`copy_property`] rebuild the read hierarchy as
+//! [`OObject`]/[`OProperty`] trees: names, metadata, data types and compound
+//! nesting are kept, and the samples of every scalar and array property are
+//! written by a [`CopyTransform`]. The default transform copies the samples
+//! unchanged; tools that retime, re-key or filter properties supply their own
+//! (see [`crate::pack`], [`crate::resample`] and the `alembic-cli` strip,
+//! merge and extract commands).
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::copy::{copy_object, CopySamples};
+//! use alembic::ogawa::writer::OArchive;
+//!
+//! let input = IArchive::open("shot.abc")?;
+//! let mut output = OArchive::create("shot_copy.abc")?;
+//! for i in 1..input.getNumTimeSamplings() {
+//!     output.addTimeSampling(input.getTimeSampling(i).cloned().unwrap_or_default());
+//! }
+//! output.write_archive(&copy_object(&input.getTop(), &mut CopySamples)?)?;
+//! ```
+
+use crate::abc::{ICompoundProperty, IObject, IProperty};
+use crate::ogawa::writer::{OObject, OProperty, OPropertyData};
+use crate::util::{DataType, Error, PlainOldDataType, Result};
+
+/// Per-property hook for [`copy_object`], [`copy_properties`] and
+/// [`copy_property`].
+pub trait CopyTransform {
+    /// Write the samples of the scalar or array property `prop` into `out`,
+    /// which already has the source's name, data type, metadata and time
+    /// sampling index. Return `Ok(false)` to leave the property out.
+    ///
+    /// The default copies every sample unchanged ([`copy_samples`]).
+    fn copy_samples(&mut self, prop: &IProperty<'_>, out: &mut OProperty) -> Result<bool> {
+        copy_samples(prop, out)?;
+        Ok(true)
+    }
+}
+
+/// Transform that copies every sample unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopySamples;
+
+impl CopyTransform for CopySamples {}
+
+/// Copy `obj`, its properties and all of its descendants.
+pub fn copy_object(obj: &IObject, transform: &mut dyn CopyTransform) -> Result<OObject> {
+    let mut out = OObject::new(obj.getName());
+    out.meta_data = obj.getMetaData().clone();
+    copy_properties(&obj.getProperties(), &mut out.properties, transform)?;
+    for child in obj.getChildren() {
+        out.add_child(copy_object(&child, transform)?);
+    }
+    Ok(out)
+}
+
+/// Copy every property of `props` into `out`, in order.
+pub fn copy_properties(
+    props: &ICompoundProperty<'_>,
+    out: &mut Vec<OProperty>,
+    transform: &mut dyn CopyTransform,
+) -> Result<()> {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        if let Some(copy) = copy_property(&prop, transform)? {
+            out.push(copy);
+        }
+    }
+    Ok(())
+}
+
+/// Copy one property, recursing into compounds. `None` if the transform
+/// dropped it (or it is neither compound, scalar nor array).
+pub fn copy_property(prop: &IProperty<'_>, transform: &mut dyn CopyTransform) -> Result<Option<OProperty>> {
+    let header = prop.getHeader();
+    if let Some(compound) = prop.asCompound() {
+        let mut out = OProperty::compound(&header.name);
+        out.meta_data = header.meta_data.clone();
+        let mut children = Vec::new();
+        copy_properties(&compound, &mut children, transform)?;
+        for child in children {
+            out.add_child(child);
+        }
+        return Ok(Some(out));
+    }
+
+    let mut out = if prop.asArray().is_some() {
+        OProperty::array(&header.name, header.data_type)
+    } else if prop.asScalar().is_some() {
+        OProperty::scalar(&header.name, header.data_type)
+    } else {
+        return Ok(None);
+    };
+    out.meta_data = header.meta_data.clone();
+    out.unknown_pod = header.unknown_pod;
+    out.time_sampling_index = header.time_sampling_index;
+    Ok(transform.copy_samples(prop, &mut out)?.then_some(out))
+}
+
+/// Append every sample of `prop` to `out` unchanged. Returns the number of
+/// samples copied; fails on the first sample that can't be read.
+pub fn copy_samples(prop: &IProperty<'_>, out: &mut OProperty) -> Result<usize> {
+    let header = prop.getHeader();
+    let n = num_samples(prop);
+    for index in 0..n {
+        let raw = read_sample(prop, header.data_type, index)
+            .ok_or_else(|| Error::other(format!("failed to read sample {} of {}", index, header.name)))?;
+        raw.push_to(out);
+    }
+    Ok(n)
+}
+
+/// Number of samples of a scalar or array property (0 for compounds).
+pub fn num_samples(prop: &IProperty<'_>) -> usize {
+    if let Some(s) = prop.asScalar() {
+        s.getNumSamples()
+    } else if let Some(a) = prop.asArray() {
+        a.getNumSamples()
+    } else {
+        0
+    }
+}
+
+/// One raw sample: bytes plus array dimensions (empty for scalars).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawSample {
+    pub data: Vec<u8>,
+    pub dims: Vec<usize>,
+}
+
+impl RawSample {
+    /// Append this sample to `out` as an array sample if `out` is an array
+    /// property, otherwise as a scalar one.
+    pub fn push_to(&self, out: &mut OProperty) {
+        if matches!(out.data, OPropertyData::Array(_)) {
+            out.add_array_sample(&self.data, &self.dims);
+        } else {
+            out.add_scalar_sample(&self.data);
+        }
+    }
+}
+
+/// Read sample `index` of a scalar or array property as stored. Strings are
+/// read NUL-separated and unknown POD types as raw bytes.
+pub fn read_sample(prop: &IProperty<'_>, data_type: DataType, index: usize) -> Option<RawSample> {
+    if let Some(array) = prop.asArray() {
+        let data = array.getSampleVec(index).ok()?;
+        let dims = array.getDimensions(index).ok()?;
+        return Some(RawSample { data, dims });
+    }
+    let scalar = prop.asScalar()?;
+    if matches!(data_type.pod, PlainOldDataType::String | PlainOldDataType::Wstring) {
+        let data = scalar.getSampleVec(index).ok()?;
+        return Some(RawSample { data, dims: Vec::new() });
+    }
+    if data_type.pod == PlainOldDataType::Unknown {
+        let data = scalar.getRawSample(index).ok()?;
+        return Some(RawSample { data, dims: Vec::new() });
+    }
+    let mut data = vec![0u8; data_type.num_bytes()];
+    scalar.getSample(index, &mut data).ok()?;
+    Some(RawSample { data, dims: Vec::new() })
+}
@@ -0,0 +1,195 @@
+//! Scratch buffer arena for transient decode buffers.
+//!
+//! Reading a sample allocates short-lived byte buffers besides the sample
+//! itself: scalar payloads copied into the caller's value, dimension blocks,
+//! compressed payloads before decoding. In batch conversion that is
+//! thousands of allocations per frame. A [`DecodeArena`] keeps released
+//! buffers in a pool (up to a byte budget) and hands them out again, so a
+//! frame's decode reuses the same few allocations.
+//!
+//! The arena is off by default: buffers are freed as soon as the sample is
+//! decoded. Enable it per archive with `IArchive::set_decode_arena`, and
+//! call `IArchive::reset_decode_arena` between frames to release the pool.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let archive = IArchive::open("sim.abc")?;
+//! archive.set_decode_arena(16 << 20);
+//! for frame in 0..num_frames {
+//!     convert_frame(&archive, frame)?;
+//!     archive.reset_decode_arena();
+//! }
+//! println!("{:?}", archive.decode_arena_stats());
+//! ```
+
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use parking_lot::Mutex;
+
+/// Counters of a [`DecodeArena`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeArenaStats {
+    /// Scratch buffers handed out.
+    pub takes: u64,
+    /// Of those, served from the pool without allocating.
+    pub reuses: u64,
+    /// Buffers currently pooled.
+    pub pooled: usize,
+    /// Capacity of the pooled buffers in bytes.
+    pub pooled_bytes: usize,
+    /// Pool byte budget (0 = arena disabled).
+    pub budget: usize,
+}
+
+/// Pool of reusable byte buffers with a byte budget.
+#[derive(Debug, Default)]
+pub struct DecodeArena {
+    pool: Mutex<Vec<Vec<u8>>>,
+    /// Capacity held by `pool`.
+    pooled_bytes: AtomicUsize,
+    budget: AtomicUsize,
+    takes: AtomicU64,
+    reuses: AtomicU64,
+}
+
+impl DecodeArena {
+    /// Create an arena pooling at most `budget` bytes (0 = disabled).
+    pub fn new(budget: usize) -> Self {
+        let arena = Self::default();
+        arena.budget.store(budget, Ordering::Relaxed);
+        arena
+    }
+
+    /// Change the pool budget; 0 disables pooling and frees the pool.
+    pub fn set_budget(&self, budget: usize) {
+        self.budget.store(budget, Ordering::Relaxed);
+        if budget == 0 {
+            self.reset();
+        }
+    }
+
+    /// Whether released buffers are pooled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.budget.load(Ordering::Relaxed) > 0
+    }
+
+    /// An empty scratch buffer with at least `capacity` bytes reserved.
+    ///
+    /// Reuses the smallest pooled buffer that fits (or the largest one,
+    /// grown) and returns it to the pool when dropped.
+    pub fn take(&self, capacity: usize) -> ScratchBuf<'_> {
+        self.takes.fetch_add(1, Ordering::Relaxed);
+        let mut buf = if self.is_enabled() { self.pop(capacity) } else { None }
+            .unwrap_or_default();
+        buf.clear();
+        buf.reserve(capacity);
+        ScratchBuf { buf, arena: self }
+    }
+
+    fn pop(&self, capacity: usize) -> Option<Vec<u8>> {
+        let mut pool = self.pool.lock();
+        let index = pool.iter().enumerate()
+            .filter(|(_, b)| b.capacity() >= capacity)
+            .min_by_key(|(_, b)| b.capacity())
+            .or_else(|| pool.iter().enumerate().max_by_key(|(_, b)| b.capacity()))
+            .map(|(i, _)| i)?;
+        let buf = pool.swap_remove(index);
+        self.pooled_bytes.fetch_sub(buf.capacity(), Ordering::Relaxed);
+        if buf.capacity() >= capacity {
+            self.reuses.fetch_add(1, Ordering::Relaxed);
+        }
+        Some(buf)
+    }
+
+    fn give_back(&self, buf: Vec<u8>) {
+        let size = buf.capacity();
+        if size == 0 || !self.is_enabled() {
+            return;
+        }
+        let mut pool = self.pool.lock();
+        if self.pooled_bytes.load(Ordering::Relaxed) + size <= self.budget.load(Ordering::Relaxed) {
+            self.pooled_bytes.fetch_add(size, Ordering::Relaxed);
+            pool.push(buf);
+        }
+    }
+
+    /// Free all pooled buffers (e.g. at the end of a frame).
+    pub fn reset(&self) {
+        let mut pool = self.pool.lock();
+        pool.clear();
+        self.pooled_bytes.store(0, Ordering::Relaxed);
+    }
+
+    /// Current counters.
+    pub fn stats(&self) -> DecodeArenaStats {
+        DecodeArenaStats {
+            takes: self.takes.load(Ordering::Relaxed),
+            reuses: self.reuses.load(Ordering::Relaxed),
+            pooled: self.pool.lock().len(),
+            pooled_bytes: self.pooled_bytes.load(Ordering::Relaxed),
+            budget: self.budget.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Byte buffer borrowed from a [`DecodeArena`]; goes back to the pool on drop.
+pub struct ScratchBuf<'a> {
+    buf: Vec<u8>,
+    arena: &'a DecodeArena,
+}
+
+impl Deref for ScratchBuf<'_> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.buf
+    }
+}
+
+impl DerefMut for ScratchBuf<'_> {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buf
+    }
+}
+
+impl Drop for ScratchBuf<'_> {
+    fn drop(&mut self) {
+        self.arena.give_back(std::mem::take(&mut self.buf));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_reuses_buffers() {
+        let arena = DecodeArena::new(1024);
+        {
+            let mut a = arena.take(100);
+            a.extend_from_slice(&[1; 100]);
+        }
+        let b = arena.take(64);
+        assert!(b.is_empty() && b.capacity() >= 100);
+        drop(b);
+        let stats = arena.stats();
+        assert_eq!((stats.takes, stats.reuses, stats.pooled), (2, 1, 1));
+
+        // Grown past the budget: freed instead of pooled
+        drop(arena.take(4096));
+        assert_eq!(arena.stats().pooled, 0);
+        arena.reset();
+        assert_eq!(arena.stats().pooled_bytes, 0);
+    }
+
+    #[test]
+    fn test_disabled_arena_frees() {
+        let arena = DecodeArena::default();
+        drop(arena.take(100));
+        let stats = arena.stats();
+        assert_eq!((stats.takes, stats.reuses, stats.pooled), (1, 0, 0));
+    }
+}
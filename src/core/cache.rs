@@ -5,9 +5,9 @@
 //!
 //! Also provides content-based keys for write deduplication.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use parking_lot::RwLock;
 /// 128-bit digest for content-based deduplication.
 pub type SampleDigest = [u8; 16];
@@ -89,6 +89,59 @@ pub struct CachedSample {
     pub size: usize,
     /// Last access timestamp for LRU eviction (monotonic counter).
     pub last_access: u64,
+    /// Owning property (its group position), if inserted per property.
+    pub property: Option<u64>,
+}
+
+/// Counters and limits of a [`ReadArraySampleCache`], for tuning.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SampleCacheStats {
+    /// Lookups served from the cache.
+    pub hits: u64,
+    /// Lookups that had to read the file.
+    pub misses: u64,
+    /// Entries dropped by the byte budget or the per-property limit.
+    pub evictions: u64,
+    /// Entries currently cached.
+    pub entries: usize,
+    /// Bytes currently cached.
+    pub bytes: usize,
+    /// Byte budget.
+    pub max_bytes: usize,
+    /// Samples kept per property (0 = only the byte budget applies).
+    pub samples_per_property: usize,
+}
+
+impl SampleCacheStats {
+    /// Fraction of lookups served from the cache (0 when nothing was read yet).
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+    }
+}
+
+/// Entries plus the recency order of each property's samples.
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<ArraySampleKey, CachedSample>,
+    /// Cached keys per property, least recently used first.
+    recent: HashMap<u64, VecDeque<ArraySampleKey>>,
+}
+
+impl CacheState {
+    /// Remove an entry (and its recency slot); returns its size.
+    fn remove(&mut self, key: &ArraySampleKey) -> Option<usize> {
+        let sample = self.entries.remove(key)?;
+        if let Some(property) = sample.property {
+            if let Some(keys) = self.recent.get_mut(&property) {
+                keys.retain(|k| k != key);
+                if keys.is_empty() {
+                    self.recent.remove(&property);
+                }
+            }
+        }
+        Some(sample.size)
+    }
 }
 
 /// Thread-safe cache for array samples.
@@ -96,27 +149,43 @@ pub struct CachedSample {
 /// Uses `parking_lot::RwLock` for faster, non-poisoning locks
 /// and `AtomicUsize` for lock-free size tracking.
 /// 
-/// Implements approximate LRU eviction - entries with older access
-/// timestamps are evicted first when the cache is full.
+/// Two limits apply: a byte budget with approximate LRU eviction across
+/// the archive, and (for samples inserted with
+/// [`insert_for_property`](Self::insert_for_property)) the last K samples
+/// per property, so scrubbing back and forth around a frame - including
+/// the floor/ceil pairs read for interpolation - stays in memory while
+/// one long animated property can't push everything else out.
 pub struct ReadArraySampleCache {
     /// Cache storage.
-    cache: RwLock<HashMap<ArraySampleKey, CachedSample>>,
+    state: RwLock<CacheState>,
     /// Maximum cache size in bytes.
-    max_size: usize,
+    max_size: AtomicUsize,
+    /// Samples kept per property (0 = unlimited).
+    samples_per_property: AtomicUsize,
     /// Current cache size in bytes (atomic for lock-free reads).
     current_size: AtomicUsize,
     /// Monotonic counter for LRU timestamps.
     access_counter: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
 }
 
 impl ReadArraySampleCache {
+    /// Default number of samples kept per property.
+    pub const DEFAULT_SAMPLES_PER_PROPERTY: usize = 16;
+
     /// Create a new cache with the given maximum size in bytes.
     pub fn new(max_size: usize) -> Self {
         Self {
-            cache: RwLock::new(HashMap::new()),
-            max_size,
+            state: RwLock::new(CacheState::default()),
+            max_size: AtomicUsize::new(max_size),
+            samples_per_property: AtomicUsize::new(Self::DEFAULT_SAMPLES_PER_PROPERTY),
             current_size: AtomicUsize::new(0),
             access_counter: AtomicUsize::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
         }
     }
     
@@ -129,21 +198,21 @@ impl ReadArraySampleCache {
     /// Updates access timestamp for LRU tracking.
     #[inline]
     pub fn get(&self, key: &ArraySampleKey) -> Option<Arc<Vec<u8>>> {
-        // Try read-only first for common case
-        {
-            let cache = self.cache.read();
-            if let Some(sample) = cache.get(key) {
-                // Found - need to update timestamp, but return data first
-                let data = Arc::clone(&sample.data);
-                drop(cache);
-                // Update timestamp (best effort, ok if we lose the race)
-                if let Some(entry) = self.cache.write().get_mut(key) {
-                    entry.last_access = self.access_counter.fetch_add(1, Ordering::Relaxed) as u64;
-                }
-                return Some(data);
+        let mut state = self.state.write();
+        let Some(sample) = state.entries.get_mut(key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        sample.last_access = self.access_counter.fetch_add(1, Ordering::Relaxed) as u64;
+        let data = Arc::clone(&sample.data);
+        if let Some(keys) = sample.property.and_then(|p| state.recent.get_mut(&p)) {
+            if let Some(i) = keys.iter().position(|k| k == key) {
+                keys.remove(i);
+                keys.push_back(*key);
             }
         }
-        None
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(data)
     }
     
     /// Insert a sample into the cache.
@@ -156,60 +225,97 @@ impl ReadArraySampleCache {
     /// significantly impact read performance. The eviction strategy ensures
     /// the cache converges back to target size quickly.
     pub fn insert(&self, key: ArraySampleKey, data: Vec<u8>) {
+        self.insert_impl(None, key, Arc::new(data));
+    }
+
+    /// [`insert`](Self::insert) for data the caller also keeps, without
+    /// copying it.
+    pub fn insert_shared(&self, key: ArraySampleKey, data: Arc<Vec<u8>>) {
+        self.insert_impl(None, key, data);
+    }
+
+    /// Insert a sample of `property` (any stable id, e.g. its group position),
+    /// evicting that property's least recently used sample beyond the
+    /// per-property limit.
+    pub fn insert_for_property(&self, property: u64, key: ArraySampleKey, data: Vec<u8>) {
+        self.insert_impl(Some(property), key, Arc::new(data));
+    }
+
+    fn insert_impl(&self, property: Option<u64>, key: ArraySampleKey, data: Arc<Vec<u8>>) {
         let size = data.len();
         
         // Don't cache if larger than max size
-        if size > self.max_size {
+        if size > self.max_size() {
             return;
         }
         
         // Check if we need to evict (relaxed ordering - may briefly exceed max)
         let current = self.current_size.load(Ordering::Relaxed);
-        if current + size > self.max_size {
+        if current + size > self.max_size() {
             self.evict_some();
         }
         
         let sample = CachedSample {
-            data: Arc::new(data),
+            data,
             size,
             last_access: self.access_counter.fetch_add(1, Ordering::Relaxed) as u64,
+            property,
         };
         
-        let mut cache = self.cache.write();
+        let mut state = self.state.write();
         // Don't insert duplicates
-        if cache.contains_key(&key) {
+        if state.entries.contains_key(&key) {
             return;
         }
         
-        cache.insert(key, sample);
+        state.entries.insert(key, sample);
         self.current_size.fetch_add(size, Ordering::Relaxed);
+
+        let Some(property) = property else { return };
+        state.recent.entry(property).or_default().push_back(key);
+        let limit = self.samples_per_property();
+        if limit > 0 {
+            self.trim_property(&mut state, property, limit);
+        }
+    }
+
+    /// Drop a property's oldest samples until at most `limit` remain.
+    fn trim_property(&self, state: &mut CacheState, property: u64, limit: usize) {
+        while state.recent.get(&property).is_some_and(|keys| keys.len() > limit) {
+            let Some(oldest) = state.recent.get_mut(&property).and_then(VecDeque::pop_front) else { break };
+            if let Some(size) = state.remove(&oldest) {
+                self.current_size.fetch_sub(size, Ordering::Relaxed);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
     }
     
     /// Evict entries using LRU strategy until we're below 75% capacity.
     /// Evicts oldest entries (by last_access timestamp) first.
     fn evict_some(&self) {
-        let mut cache = self.cache.write();
+        let mut state = self.state.write();
         
         // Collect entries with their access timestamps
-        let mut entries: Vec<_> = cache.iter()
-            .map(|(k, v)| (*k, v.last_access, v.size))
+        let mut entries: Vec<_> = state.entries.iter()
+            .map(|(k, v)| (*k, v.last_access))
             .collect();
         
         // Sort by access time (oldest first)
-        entries.sort_by_key(|(_, access, _)| *access);
+        entries.sort_by_key(|(_, access)| *access);
         
         // Target: evict until we're at 75% capacity
-        let target_size = self.max_size * 3 / 4;
+        let target_size = self.max_size() * 3 / 4;
         let mut current = self.current_size.load(Ordering::Relaxed);
         let mut evicted_size = 0;
         
-        for (key, _, size) in entries {
+        for (key, _) in entries {
             if current <= target_size {
                 break;
             }
-            if cache.remove(&key).is_some() {
+            if let Some(size) = state.remove(&key) {
                 evicted_size += size;
                 current = current.saturating_sub(size);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
             }
         }
         
@@ -221,17 +327,18 @@ impl ReadArraySampleCache {
         );
     }
     
-    /// Clear the entire cache.
+    /// Clear the entire cache (statistics are kept).
     pub fn clear(&self) {
-        let mut cache = self.cache.write();
-        cache.clear();
+        let mut state = self.state.write();
+        state.entries.clear();
+        state.recent.clear();
         self.current_size.store(0, Ordering::Relaxed);
     }
     
     /// Get the number of cached entries.
     #[inline]
     pub fn len(&self) -> usize {
-        self.cache.read().len()
+        self.state.read().entries.len()
     }
     
     /// Check if cache is empty.
@@ -249,7 +356,55 @@ impl ReadArraySampleCache {
     /// Get maximum cache size in bytes.
     #[inline]
     pub fn max_size(&self) -> usize {
-        self.max_size
+        self.max_size.load(Ordering::Relaxed)
+    }
+
+    /// Change the byte budget, evicting down to it if needed.
+    pub fn set_max_size(&self, max_size: usize) {
+        self.max_size.store(max_size, Ordering::Relaxed);
+        if self.size() > max_size {
+            self.evict_some();
+        }
+    }
+
+    /// Samples kept per property (0 = only the byte budget applies).
+    #[inline]
+    pub fn samples_per_property(&self) -> usize {
+        self.samples_per_property.load(Ordering::Relaxed)
+    }
+
+    /// Change the per-property limit, trimming properties above it.
+    pub fn set_samples_per_property(&self, limit: usize) {
+        self.samples_per_property.store(limit, Ordering::Relaxed);
+        if limit == 0 {
+            return;
+        }
+        let mut state = self.state.write();
+        let properties: Vec<u64> = state.recent.keys().copied().collect();
+        for property in properties {
+            self.trim_property(&mut state, property, limit);
+        }
+    }
+
+    /// Current counters and limits.
+    pub fn stats(&self) -> SampleCacheStats {
+        let entries = self.len();
+        SampleCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries,
+            bytes: self.size(),
+            max_bytes: self.max_size(),
+            samples_per_property: self.samples_per_property(),
+        }
+    }
+
+    /// Reset hit/miss/eviction counters.
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.evictions.store(0, Ordering::Relaxed);
     }
 }
 
@@ -313,6 +468,37 @@ mod tests {
         assert!(cache.len() <= 5);
     }
     
+    #[test]
+    fn test_cache_per_property_limit() {
+        let cache = ReadArraySampleCache::new(1024);
+        cache.set_samples_per_property(2);
+        for i in 0..3u64 {
+            cache.insert_for_property(7, ArraySampleKey::new(i * 10, i as usize), vec![0u8; 4]);
+        }
+        cache.insert_for_property(8, ArraySampleKey::new(500, 0), vec![0u8; 4]);
+
+        // Property 7 keeps its last two samples; property 8 is unaffected
+        assert!(cache.get(&ArraySampleKey::new(0, 0)).is_none());
+        assert!(cache.get(&ArraySampleKey::new(10, 1)).is_some());
+        assert!(cache.get(&ArraySampleKey::new(20, 2)).is_some());
+        assert!(cache.get(&ArraySampleKey::new(500, 0)).is_some());
+        assert_eq!(cache.size(), 12);
+
+        // A hit refreshes recency: sample 1 survives the next insert, 2 doesn't
+        cache.get(&ArraySampleKey::new(10, 1));
+        cache.insert_for_property(7, ArraySampleKey::new(30, 3), vec![0u8; 4]);
+        assert!(cache.get(&ArraySampleKey::new(10, 1)).is_some());
+        assert!(cache.get(&ArraySampleKey::new(20, 2)).is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (5, 2, 2));
+        assert_eq!(stats.entries, 3);
+        assert!((stats.hit_rate() - 5.0 / 7.0).abs() < 1e-9);
+
+        cache.set_samples_per_property(1);
+        assert_eq!(cache.len(), 2);
+    }
+
     #[test]
     fn test_cache_skip_large() {
         let cache = ReadArraySampleCache::new(100);
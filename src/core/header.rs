@@ -64,6 +64,10 @@ pub struct PropertyHeader {
     pub property_type: PropertyType,
     /// Data type (POD + extent).
     pub data_type: DataType,
+    /// Stored POD code for types this library doesn't know, e.g. from a
+    /// newer writer (`data_type.pod` is then `Unknown`). Samples of such
+    /// properties are available as raw bytes through `IProperty::asRaw`.
+    pub unknown_pod: Option<u8>,
     /// Time sampling index (0 = identity/static).
     pub time_sampling_index: u32,
     /// Metadata.
@@ -77,6 +81,7 @@ impl PropertyHeader {
             name: name.into(),
             property_type: PropertyType::Scalar,
             data_type,
+            unknown_pod: None,
             time_sampling_index: 0,
             meta_data: MetaData::new(),
         }
@@ -88,6 +93,7 @@ impl PropertyHeader {
             name: name.into(),
             property_type: PropertyType::Array,
             data_type,
+            unknown_pod: None,
             time_sampling_index: 0,
             meta_data: MetaData::new(),
         }
@@ -99,6 +105,7 @@ impl PropertyHeader {
             name: name.into(),
             property_type: PropertyType::Compound,
             data_type: DataType::UNKNOWN,
+            unknown_pod: None,
             time_sampling_index: 0,
             meta_data: MetaData::new(),
         }
@@ -135,6 +142,11 @@ impl PropertyHeader {
     pub fn interpretation(&self) -> Option<&str> {
         self.meta_data.interpretation()
     }
+
+    /// POD code as stored in the file, including unknown ones.
+    pub fn pod_code(&self) -> u8 {
+        self.unknown_pod.unwrap_or(self.data_type.pod as u8)
+    }
 }
 
 /// Type of property.
@@ -176,6 +176,9 @@ impl MetaData {
     /// Schema base type key.
     pub const SCHEMA_BASE_KEY: &'static str = "schemaBaseType";
 
+    /// Schema object title key ("<schema>:<property>").
+    pub const SCHEMA_OBJ_TITLE_KEY: &'static str = "schemaObjTitle";
+
     /// Interpretation key (e.g., "point", "vector", "normal").
     pub const INTERPRETATION_KEY: &'static str = "interpretation";
 
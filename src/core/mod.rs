@@ -13,9 +13,10 @@ mod header;
 mod traits;
 mod sample;
 mod cache;
+mod arena;
 mod compression;
 
-pub use time_sampling::{TimeSampling, TimeSamplingType};
+pub use time_sampling::{TimeSampling, TimeSamplingType, FrameConvention, FrameMapping};
 pub use metadata::MetaData;
 pub use header::{ObjectHeader, PropertyHeader, PropertyType};
 pub use traits::{
@@ -31,7 +32,8 @@ pub use traits::{
 };
 pub use sample::{SampleSelector, SampleInterp, GeometryScope, TopologyVariance};
 pub use cache::{
-    ReadArraySampleCache, ArraySampleKey, CachedSample,
+    ReadArraySampleCache, ArraySampleKey, CachedSample, SampleCacheStats,
     ArraySampleContentKey, SampleDigest, compute_digest,
 };
+pub use arena::{DecodeArena, DecodeArenaStats, ScratchBuf};
 pub use compression::{compress, decompress, is_compressed};
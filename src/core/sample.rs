@@ -64,6 +64,15 @@ impl SampleSelector {
         idx.min(num_samples.saturating_sub(1))
     }
     
+    /// Resolve against a property's time sampling, if it has one.
+    ///
+    /// Readers that don't know their time sampling resolve time-based
+    /// selectors against [`TimeSampling::IDENTITY`] (one sample per second
+    /// from 0).
+    pub fn resolve(&self, ts: Option<&TimeSampling>, num_samples: usize) -> usize {
+        self.get_index(ts.unwrap_or(&TimeSampling::IDENTITY), num_samples)
+    }
+
     /// Check if this selector requests a specific index (not time-based).
     pub fn is_index(&self) -> bool {
         matches!(self, Self::Index(_))
@@ -130,6 +130,46 @@ impl TimeSampling {
         }
     }
     
+    /// Uniform sampling at `fps` whose first sample is frame `start_frame`
+    /// (`time = frame / fps`).
+    ///
+    /// ```ignore
+    /// let ts = TimeSampling::uniform_fps(24.0, 1001.0); // 1001, 1002, ... at 24fps
+    /// ```
+    pub fn uniform_fps(fps: f64, start_frame: f64) -> Self {
+        Self::uniform(1.0 / fps, start_frame / fps)
+    }
+
+    /// Cyclic sampling with the same sub-frame offsets every frame at `fps`,
+    /// starting at frame `start_frame` (typical motion blur shutter samples).
+    ///
+    /// ```ignore
+    /// // Three samples per frame: -0.25, 0 and +0.25 around frames 1, 2, ...
+    /// let ts = TimeSampling::cyclic_fps(24.0, 1.0, &[-0.25, 0.0, 0.25]);
+    /// ```
+    pub fn cyclic_fps(fps: f64, start_frame: f64, subframes: &[f64]) -> Self {
+        Self::cyclic(1.0 / fps, subframes.iter().map(|s| (start_frame + s) / fps).collect())
+    }
+
+    /// Acyclic sampling at explicit (possibly fractional) frames at `fps`.
+    pub fn acyclic_frames(fps: f64, frames: &[f64]) -> Self {
+        Self::acyclic(frames.iter().map(|f| f / fps).collect())
+    }
+
+    /// Acyclic sampling that rejects empty, non-finite or non-increasing times.
+    pub fn try_acyclic(times: Vec<Chrono>) -> Result<Self, &'static str> {
+        let ts = Self::acyclic(times);
+        ts.validate()?;
+        Ok(ts)
+    }
+
+    /// Cyclic sampling that rejects invalid cycles (see [`validate`](Self::validate)).
+    pub fn try_cyclic(time_per_cycle: Chrono, times: Vec<Chrono>) -> Result<Self, &'static str> {
+        let ts = Self::cyclic(time_per_cycle, times);
+        ts.validate()?;
+        Ok(ts)
+    }
+
     /// Create from a TimeSamplingType.
     /// 
     /// Note: The times are already contained within the TimeSamplingType for
@@ -430,18 +470,19 @@ impl TimeSampling {
                 if !time_per_cycle.is_finite() {
                     return Err("Cyclic sampling time_per_cycle must be finite");
                 }
-                // Check times are within cycle and monotonically increasing
+                // Times are the absolute times of the first cycle; they must
+                // increase and fit in one cycle so the next cycle stays ordered
                 for i in 0..times.len() {
                     if !times[i].is_finite() {
                         return Err("Cyclic sampling times must be finite");
                     }
-                    if times[i] < 0.0 || times[i] >= *time_per_cycle {
-                        return Err("Cyclic times must be in [0, time_per_cycle)");
-                    }
                     if i > 0 && times[i] <= times[i - 1] {
                         return Err("Cyclic times must be monotonically increasing");
                     }
                 }
+                if times[times.len() - 1] - times[0] >= *time_per_cycle {
+                    return Err("Cyclic times must span less than time_per_cycle");
+                }
                 Ok(())
             }
             TimeSamplingType::Acyclic { times } => {
@@ -477,6 +518,138 @@ impl Default for TimeSampling {
     }
 }
 
+/// How a DCC numbers frames relative to archive time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FrameConvention {
+    /// `time = frame / fps` (Maya, Blender, most exporters): frame 0 is time 0.
+    #[default]
+    Maya,
+    /// `time = (frame - 1) / fps` (Houdini `$T`): frame 1 is time 0.
+    Houdini,
+}
+
+impl FrameConvention {
+    /// Frame number at time 0.
+    #[inline]
+    pub fn frame_offset(self) -> f64 {
+        match self {
+            Self::Maya => 0.0,
+            Self::Houdini => 1.0,
+        }
+    }
+
+    /// Parse "maya" / "houdini" (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "maya" => Some(Self::Maya),
+            "houdini" | "hou" => Some(Self::Houdini),
+            _ => None,
+        }
+    }
+
+    /// Guess the convention from an archive's `_ai_Application` string.
+    pub fn from_app_name(app: &str) -> Self {
+        if app.to_ascii_lowercase().contains("houdini") { Self::Houdini } else { Self::Maya }
+    }
+}
+
+/// Conversion between archive time (seconds) and DCC frame numbers.
+///
+/// Keeps fps and the frame convention together so scripts stop converting
+/// with ad-hoc `time * 24` arithmetic:
+///
+/// ```ignore
+/// let map = archive.frame_mapping();          // fps + convention from the archive
+/// let t = map.frame_to_time(1015.0);
+/// let index = map.frame_to_sample(ts, 1015.0, num_samples);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameMapping {
+    /// Frames per second.
+    pub fps: f64,
+    /// Frame numbering convention.
+    pub convention: FrameConvention,
+}
+
+impl FrameMapping {
+    /// Fallback rate when an archive carries no fps information.
+    pub const DEFAULT_FPS: f64 = 24.0;
+
+    /// Mapping at `fps` with the given convention.
+    pub fn new(fps: f64, convention: FrameConvention) -> Self {
+        let fps = if fps.is_finite() && fps > 0.0 { fps } else { Self::DEFAULT_FPS };
+        Self { fps, convention }
+    }
+
+    /// Maya-style mapping (`time = frame / fps`).
+    pub fn maya(fps: f64) -> Self {
+        Self::new(fps, FrameConvention::Maya)
+    }
+
+    /// Houdini-style mapping (`time = (frame - 1) / fps`).
+    pub fn houdini(fps: f64) -> Self {
+        Self::new(fps, FrameConvention::Houdini)
+    }
+
+    /// Archive time of a (possibly fractional) frame.
+    #[inline]
+    pub fn frame_to_time(&self, frame: f64) -> Chrono {
+        (frame - self.convention.frame_offset()) / self.fps
+    }
+
+    /// Frame at archive time `time` (fractional for sub-frame samples).
+    #[inline]
+    pub fn time_to_frame(&self, time: Chrono) -> f64 {
+        time * self.fps + self.convention.frame_offset()
+    }
+
+    /// Nearest whole frame at `time`.
+    ///
+    /// Rounds instead of truncating, so times stored as `n / fps` that land a
+    /// hair below the frame (e.g. `1000.9999999`) don't report the previous frame.
+    #[inline]
+    pub fn nearest_frame(&self, time: Chrono) -> i64 {
+        self.time_to_frame(time).round() as i64
+    }
+
+    /// Frame of sample `index`.
+    pub fn sample_to_frame(&self, ts: &TimeSampling, index: usize, num_samples: usize) -> f64 {
+        self.time_to_frame(ts.sample_time(index, num_samples))
+    }
+
+    /// Sample closest to `frame`.
+    pub fn frame_to_sample(&self, ts: &TimeSampling, frame: f64, num_samples: usize) -> usize {
+        ts.near_index(self.frame_to_time(frame), num_samples).0
+    }
+
+    /// Uniform sampling, one sample per frame from `start_frame`.
+    pub fn uniform_sampling(&self, start_frame: f64) -> TimeSampling {
+        TimeSampling::uniform(1.0 / self.fps, self.frame_to_time(start_frame))
+    }
+
+    /// Cyclic sampling with `subframes` offsets around every frame from
+    /// `start_frame`.
+    pub fn cyclic_sampling(&self, start_frame: f64, subframes: &[f64]) -> TimeSampling {
+        TimeSampling::cyclic(1.0 / self.fps, subframes.iter().map(|s| self.frame_to_time(start_frame + s)).collect())
+    }
+}
+
+impl Default for FrameMapping {
+    fn default() -> Self {
+        Self::maya(Self::DEFAULT_FPS)
+    }
+}
+
+impl TimeSampling {
+    /// Sampling rate (samples per second) for uniform sampling.
+    pub fn fps(&self) -> Option<f64> {
+        match &self.sampling_type {
+            TimeSamplingType::Uniform { time_per_cycle, .. } if *time_per_cycle > 0.0 => Some(1.0 / time_per_cycle),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -490,6 +663,28 @@ mod tests {
         assert!((ts.sample_time(48, 100) - 2.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_frame_mapping() {
+        let maya = FrameMapping::maya(24.0);
+        let hou = FrameMapping::houdini(24.0);
+        assert_eq!(maya.frame_to_time(24.0), 1.0);
+        assert_eq!(hou.frame_to_time(25.0), 1.0);
+        assert_eq!(hou.time_to_frame(0.0), 1.0);
+        assert_eq!(maya.nearest_frame(1001.0 / 24.0 - 1e-9), 1001);
+
+        // Frame 1001 of a 24 fps shot, sampled from 1001 / 24 s
+        let ts = TimeSampling::uniform(1.0 / 24.0, 1001.0 / 24.0);
+        assert_eq!(ts.fps(), Some(24.0));
+        assert_eq!(maya.frame_to_sample(&ts, 1003.0, 10), 2);
+        assert_eq!(maya.sample_to_frame(&ts, 2, 10).round(), 1003.0);
+        assert_eq!(hou.frame_to_sample(&ts, 1004.0, 10), 2);
+
+        assert_eq!(FrameConvention::from_app_name("Houdini 20.5.332"), FrameConvention::Houdini);
+        assert_eq!(FrameConvention::from_app_name("Maya 2025 AbcExport v1.8"), FrameConvention::Maya);
+        assert_eq!(FrameMapping::new(0.0, FrameConvention::Maya).fps, FrameMapping::DEFAULT_FPS);
+        assert!(TimeSampling::acyclic(vec![0.0, 1.0]).fps().is_none());
+    }
+
     #[test]
     fn test_acyclic_sampling() {
         let ts = TimeSampling::acyclic(vec![0.0, 0.5, 1.0, 2.0]);
@@ -608,6 +803,33 @@ mod tests {
         assert!(TimeSampling::acyclic(vec![0.0, 2.0, 1.0]).validate().is_err());
     }
     
+    #[test]
+    fn test_fps_constructors() {
+        let ts = TimeSampling::uniform_fps(24.0, 1001.0);
+        assert!((ts.sample_time(0, 10) - 1001.0 / 24.0).abs() < 1e-12);
+        assert!((ts.sample_time(23, 30) - 1024.0 / 24.0).abs() < 1e-12);
+        assert_eq!(ts.fps(), Some(24.0));
+
+        // Shutter samples around frames 1, 2, ...; absolute first-cycle times validate
+        let shutter = TimeSampling::cyclic_fps(24.0, 1.0, &[-0.25, 0.0, 0.25]);
+        assert!(shutter.validate().is_ok());
+        assert!((shutter.sample_time(3, 9) - 1.75 / 24.0).abs() < 1e-12);
+        assert!((shutter.sample_time(5, 9) - 2.25 / 24.0).abs() < 1e-12);
+        assert!(TimeSampling::cyclic_fps(24.0, 1.0, &[0.0, 1.0]).validate().is_err());
+
+        let frames = TimeSampling::acyclic_frames(25.0, &[1.0, 2.0, 10.0]);
+        assert!((frames.sample_time(2, 3) - 0.4).abs() < 1e-12);
+        assert!(TimeSampling::try_acyclic(vec![0.0, 0.5, 0.5]).is_err());
+        assert!(TimeSampling::try_acyclic(vec![f64::NAN]).is_err());
+        assert!(TimeSampling::try_cyclic(1.0, vec![0.0, 0.5]).is_ok());
+
+        // Houdini frame 1 is time 0
+        let houdini = FrameMapping::houdini(24.0);
+        assert_eq!(houdini.uniform_sampling(1.0).sample_time(0, 1), 0.0);
+        let blur = houdini.cyclic_sampling(1.0, &[-0.5, 0.5]);
+        assert!((blur.sample_time(2, 4) - 0.5 / 24.0).abs() < 1e-12);
+    }
+
     #[test]
     fn test_needs_interpolation() {
         let ts = TimeSampling::uniform(1.0, 0.0); // 1 sample per second
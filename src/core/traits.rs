@@ -10,7 +10,7 @@
 //! - `_ref/alembic/lib/Alembic/AbcCoreAbstract/` for core abstract interfaces
 //! - `_ref/alembic/lib/Alembic/Abc/` for high-level API
 
-use crate::core::{ObjectHeader, PropertyHeader, TimeSampling, MetaData, SampleDigest};
+use crate::core::{ObjectHeader, PropertyHeader, TimeSampling, MetaData, SampleDigest, ReadArraySampleCache, DecodeArena, SampleSelector};
 use crate::util::Result;
 
 // ============================================================================
@@ -67,6 +67,35 @@ pub trait ArchiveReader: Send + Sync {
         &[]
     }
 
+    /// Get the array sample cache, if this backend has one.
+    ///
+    /// Note: Extended method (the C++ cache lives in `AbcCoreFactory`).
+    fn getSampleCache(&self) -> Option<&ReadArraySampleCache> {
+        None
+    }
+
+    /// Get the pool for transient decode buffers, if this backend has one.
+    ///
+    /// Note: Extended method (see [`crate::core::DecodeArena`]).
+    fn getDecodeArena(&self) -> Option<&DecodeArena> {
+        None
+    }
+
+    /// Check each decoded sample against its stored digest on read.
+    /// Returns false if this backend can't verify samples.
+    ///
+    /// Note: Extended method (see `IArchive::set_verify_hashes`).
+    fn setVerifyHashes(&self, _verify: bool) -> bool {
+        false
+    }
+
+    /// Whether samples are checked against their stored digest on read.
+    ///
+    /// Note: Extended method.
+    fn getVerifyHashes(&self) -> bool {
+        false
+    }
+
     /// Find an object by full path.
     fn findObject(&self, path: &str) -> Option<Box<dyn ObjectReader + '_>> {
         let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
@@ -151,6 +180,17 @@ pub trait ObjectReader: Send + Sync {
     /// Reference: `AbcCoreAbstract::ObjectReader::getChild(const std::string&)`
     fn getChild(&self, name: &str) -> Option<Box<dyn ObjectReader + '_>>;
 
+    /// Get the index of a child by name.
+    ///
+    /// Indices follow file order and are stable for the lifetime of the
+    /// reader. The default implementation scans child headers; backends
+    /// should override this with a constant-time lookup.
+    fn getChildIndex(&self, name: &str) -> Option<usize> {
+        (0..self.getNumChildren()).find(|&i| {
+            self.getChildByIndex(i).is_some_and(|c| c.getName() == name)
+        })
+    }
+
     /// Get the properties compound.
     ///
     /// Reference: `AbcCoreAbstract::ObjectReader::getProperties()`
@@ -216,19 +256,26 @@ pub trait ObjectReader: Send + Sync {
     }
     
     /// Check if child at index is an instance.
-    fn isChildInstance(&self, _index: usize) -> bool {
-        false
+    fn isChildInstance(&self, index: usize) -> bool {
+        self.getChildByIndex(index).is_some_and(|c| c.isInstanceRoot())
     }
     
     /// Check if child with given name is an instance.
-    fn isChildInstanceByName(&self, _name: &str) -> bool {
-        false
+    fn isChildInstanceByName(&self, name: &str) -> bool {
+        self.getChild(name).is_some_and(|c| c.isInstanceRoot())
     }
     
     // ========================================================================
     // Hash support
     // ========================================================================
     
+    /// Get the archive time sampling at `index`, if the backend has it.
+    ///
+    /// Reference: `AbcCoreAbstract::ArchiveReader::getTimeSampling()`
+    fn getArchiveTimeSampling(&self, _index: usize) -> Option<&TimeSampling> {
+        None
+    }
+
     /// Get aggregated properties hash if available.
     ///
     /// Reference: `AbcCoreAbstract::ObjectReader::getPropertiesHash()`
@@ -304,6 +351,16 @@ pub trait PropertyReader: Send + Sync {
         &self.getHeader().name
     }
 
+    /// Get the time sampling this property's samples are laid out on.
+    ///
+    /// `None` when the backend doesn't track archive time samplings; callers
+    /// then treat samples as one per second from 0.
+    ///
+    /// Reference: `AbcCoreAbstract::BasePropertyReader::getTimeSampling()`
+    fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        None
+    }
+
     /// Try to cast to scalar property reader.
     fn asScalar(&self) -> Option<&dyn ScalarPropertyReader> {
         None
@@ -336,6 +393,14 @@ pub trait ScalarPropertyReader: PropertyReader {
         self.getNumSamples() <= 1
     }
 
+    /// Index of the sample `sel` selects, resolved against this property's
+    /// own time sampling and sample count (past the end, the last sample).
+    ///
+    /// Note: Extended method.
+    fn getSampleIndex(&self, sel: SampleSelector) -> usize {
+        sel.resolve(self.getTimeSampling(), self.getNumSamples())
+    }
+
     /// Read a sample into the provided buffer.
     ///
     /// Reference: `AbcCoreAbstract::ScalarPropertyReader::getSample()`
@@ -362,6 +427,14 @@ pub trait ScalarPropertyReader: PropertyReader {
         Ok(buf)
     }
     
+    /// Read the stored bytes of a sample without interpreting its POD type.
+    ///
+    /// This is how samples of unknown POD types are read; the default
+    /// falls back to [`getSampleVec`](Self::getSampleVec).
+    fn getRawSample(&self, index: usize) -> Result<Vec<u8>> {
+        self.getSampleVec(index)
+    }
+
     /// Get the key (digest) of a sample for deduplication/raw copy.
     ///
     /// This returns a 16-byte digest that can be used to preserve
@@ -385,6 +458,14 @@ pub trait ArrayPropertyReader: PropertyReader {
         self.getNumSamples() <= 1
     }
 
+    /// Index of the sample `sel` selects, resolved against this property's
+    /// own time sampling and sample count (past the end, the last sample).
+    ///
+    /// Note: Extended method.
+    fn getSampleIndex(&self, sel: SampleSelector) -> usize {
+        sel.resolve(self.getTimeSampling(), self.getNumSamples())
+    }
+
     /// Get the size (in elements) of a sample.
     fn getSampleLen(&self, index: usize) -> Result<usize>;
 
@@ -395,6 +476,17 @@ pub trait ArrayPropertyReader: PropertyReader {
 
     /// Read sample as Vec of bytes.
     fn getSampleVec(&self, index: usize) -> Result<Vec<u8>>;
+
+    /// Call `f` with the bytes of a sample, lent without a copy where the
+    /// backend can (e.g. straight from its sample cache). Saves the
+    /// temporary `Vec` of [`getSampleVec`](Self::getSampleVec) when the
+    /// bytes are converted right away.
+    ///
+    /// Note: Extended method.
+    fn withSample(&self, index: usize, f: &mut dyn FnMut(&[u8])) -> Result<()> {
+        f(&self.getSampleVec(index)?);
+        Ok(())
+    }
     
     /// Get the key (digest) of a sample for deduplication.
     ///
@@ -411,25 +503,17 @@ pub trait ArrayPropertyReader: PropertyReader {
     where
         Self: Sized,
     {
-        let data = self.getSampleVec(index)?;
-        let slice: &[T] = bytemuck::try_cast_slice(&data).map_err(|_| crate::util::Error::invalid("cast error"))?;
-        Ok(slice.to_vec())
+        cast_sample(self, index, "cast error")
     }
     
     /// Read sample as f32 array.
     fn getAsFloat32Array(&self, index: usize) -> Result<Vec<f32>> {
-        let data = self.getSampleVec(index)?;
-        let slice: &[f32] = bytemuck::try_cast_slice(&data)
-            .map_err(|_| crate::util::Error::invalid("cannot cast to f32"))?;
-        Ok(slice.to_vec())
+        cast_sample(self, index, "cannot cast to f32")
     }
     
     /// Read sample as i32 array.
     fn getAsInt32Array(&self, index: usize) -> Result<Vec<i32>> {
-        let data = self.getSampleVec(index)?;
-        let slice: &[i32] = bytemuck::try_cast_slice(&data)
-            .map_err(|_| crate::util::Error::invalid("cannot cast to i32"))?;
-        Ok(slice.to_vec())
+        cast_sample(self, index, "cannot cast to i32")
     }
     
     /// Read sample as string array.
@@ -458,6 +542,14 @@ pub trait ArrayPropertyReader: PropertyReader {
     }
 }
 
+/// Copy a sample out as `T` values, borrowing its bytes through
+/// [`ArrayPropertyReader::withSample`].
+fn cast_sample<T: bytemuck::Pod>(reader: &(impl ArrayPropertyReader + ?Sized), index: usize, err: &str) -> Result<Vec<T>> {
+    let mut values = None;
+    reader.withSample(index, &mut |bytes| values = bytemuck::try_cast_slice::<u8, T>(bytes).ok().map(<[T]>::to_vec))?;
+    values.ok_or_else(|| crate::util::Error::invalid(err))
+}
+
 /// Reader for compound properties (container of sub-properties).
 ///
 /// Corresponds to `Alembic::AbcCoreAbstract::CompoundPropertyReader` in the reference.
@@ -585,6 +677,10 @@ impl<'a> ObjectReader for ObjectReaderRef<'a> {
         self.0.getChild(name)
     }
 
+    fn getChildIndex(&self, name: &str) -> Option<usize> {
+        self.0.getChildIndex(name)
+    }
+
     fn getProperties(&self) -> &dyn CompoundPropertyReader {
         self.0.getProperties()
     }
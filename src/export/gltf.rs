@@ -0,0 +1,467 @@
+//! glTF 2.0 export.
+//!
+//! [`GltfDocument::build`] converts one time sample of an archive:
+//! - Xforms become nodes carrying their local matrix
+//! - PolyMeshes (and SubD control cages) become triangle meshes with
+//!   `NORMAL` and one `TEXCOORD_n` per UV set (see `IPolyMesh::uv_set_names`)
+//! - Cameras become perspective cameras
+//! - Any other object becomes an empty node, so paths and hierarchy survive
+//!
+//! Alembic polygons wind clockwise; triangles are reversed to glTF's
+//! counter-clockwise convention and UVs are flipped to a top-left origin.
+//! Every node stores its Alembic path in `extras.path`.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use glam::{Mat4, Vec2, Vec3};
+use serde_json::{json, Value};
+
+use crate::abc::{IArchive, IObject};
+use crate::core::TimeSampling;
+use crate::geom::mesh_data::build_mesh;
+use crate::geom::{
+    ICamera, IPolyMesh, ISubD, IXform, MeshData, ObjectVisibility,
+    VISIBILITY_PROPERTY_NAME, get_visibility,
+};
+use crate::util::{Chrono, Result};
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_CHUNK_JSON: &[u8; 4] = b"JSON";
+const GLB_CHUNK_BIN: &[u8; 4] = b"BIN\0";
+
+/// Options for [`export_gltf`].
+#[derive(Debug, Clone)]
+pub struct GltfOptions {
+    /// Time (seconds) to sample; each object uses its nearest sample (default: 0).
+    pub time: Chrono,
+    /// Include SubD objects as their control cage (default: true).
+    pub include_subd: bool,
+    /// Skip objects hidden by their visibility property, with their subtree (default: true).
+    pub skip_hidden: bool,
+}
+
+impl Default for GltfOptions {
+    fn default() -> Self {
+        Self { time: 0.0, include_subd: true, skip_hidden: true }
+    }
+}
+
+/// What ended up in the exported file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GltfStats {
+    pub nodes: usize,
+    pub meshes: usize,
+    pub cameras: usize,
+    pub triangles: usize,
+    /// Largest number of UV sets on a single mesh.
+    pub max_uv_sets: usize,
+}
+
+/// glTF JSON plus its binary buffer.
+#[derive(Debug, Clone)]
+pub struct GltfDocument {
+    /// The glTF JSON, without a buffer `uri` (added by [`GltfDocument::write`]).
+    pub json: Value,
+    /// Contents of buffer 0.
+    pub bin: Vec<u8>,
+    pub stats: GltfStats,
+}
+
+/// Export `archive` to `output`: `.glb` embeds the buffer, anything else
+/// is written as `.gltf` JSON with a sibling `.bin`.
+pub fn export_gltf(archive: &IArchive, output: impl AsRef<Path>, options: &GltfOptions) -> Result<GltfStats> {
+    let doc = GltfDocument::build(archive, options);
+    doc.write(output)?;
+    Ok(doc.stats)
+}
+
+impl GltfDocument {
+    /// Convert the archive at `options.time`.
+    pub fn build(archive: &IArchive, options: &GltfOptions) -> Self {
+        let mut builder = Builder {
+            archive,
+            options,
+            nodes: Vec::new(),
+            meshes: Vec::new(),
+            cameras: Vec::new(),
+            accessors: Vec::new(),
+            views: Vec::new(),
+            bin: Vec::new(),
+            stats: GltfStats::default(),
+        };
+        let top = archive.getTop();
+        let roots: Vec<usize> = top.getChildren()
+            .filter_map(|child| builder.visit(&child))
+            .collect();
+
+        let mut json = json!({
+            "asset": { "version": "2.0", "generator": "alembic-rs" },
+            "scene": 0,
+            "scenes": [{ "nodes": roots }],
+        });
+        let doc = json.as_object_mut().expect("object literal");
+        for (key, items) in [
+            ("nodes", builder.nodes),
+            ("meshes", builder.meshes),
+            ("cameras", builder.cameras),
+            ("accessors", builder.accessors),
+            ("bufferViews", builder.views),
+        ] {
+            if !items.is_empty() {
+                doc.insert(key.into(), Value::Array(items));
+            }
+        }
+        if !builder.bin.is_empty() {
+            doc.insert("buffers".into(), json!([{ "byteLength": builder.bin.len() }]));
+        }
+        builder.stats.nodes = doc.get("nodes").and_then(Value::as_array).map_or(0, Vec::len);
+
+        Self { json, bin: builder.bin, stats: builder.stats }
+    }
+
+    /// Serialize as a binary glTF container.
+    pub fn to_glb(&self) -> Vec<u8> {
+        let mut json = serde_json::to_vec(&self.json).unwrap_or_default();
+        pad4(&mut json, b' ');
+        let mut bin = self.bin.clone();
+        pad4(&mut bin, 0);
+
+        let mut total = 12 + 8 + json.len();
+        if !bin.is_empty() {
+            total += 8 + bin.len();
+        }
+        let mut out = Vec::with_capacity(total);
+        out.extend_from_slice(GLB_MAGIC);
+        out.extend_from_slice(&2u32.to_le_bytes());
+        out.extend_from_slice(&(total as u32).to_le_bytes());
+        out.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        out.extend_from_slice(GLB_CHUNK_JSON);
+        out.extend_from_slice(&json);
+        if !bin.is_empty() {
+            out.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+            out.extend_from_slice(GLB_CHUNK_BIN);
+            out.extend_from_slice(&bin);
+        }
+        out
+    }
+
+    /// Write to `path`: `.glb` as a binary container, otherwise JSON plus `<stem>.bin`.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let is_glb = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("glb"));
+        if is_glb {
+            std::fs::write(path, self.to_glb())?;
+            return Ok(());
+        }
+
+        let mut json = self.json.clone();
+        if !self.bin.is_empty() {
+            let bin_path = path.with_extension("bin");
+            let uri = bin_path.file_name().and_then(|n| n.to_str()).unwrap_or("buffer.bin").to_string();
+            std::fs::write(&bin_path, &self.bin)?;
+            json["buffers"][0]["uri"] = Value::String(uri);
+        }
+        let text = serde_json::to_string_pretty(&json).unwrap_or_default();
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+struct Builder<'a> {
+    archive: &'a IArchive,
+    options: &'a GltfOptions,
+    nodes: Vec<Value>,
+    meshes: Vec<Value>,
+    cameras: Vec<Value>,
+    accessors: Vec<Value>,
+    views: Vec<Value>,
+    bin: Vec<u8>,
+    stats: GltfStats,
+}
+
+/// Mesh resolved per face-vertex, ready for welding.
+struct CornerMesh {
+    data: MeshData,
+    /// UV sets, each one value per entry of `data.face_indices`.
+    uv_sets: Vec<Vec<Vec2>>,
+}
+
+impl Builder<'_> {
+    /// Sample index nearest to the export time.
+    fn index(&self, ts_index: u32, num_samples: usize) -> usize {
+        if num_samples <= 1 {
+            return 0;
+        }
+        let identity = TimeSampling::identity();
+        let ts = self.archive.getTimeSampling(ts_index as usize).unwrap_or(&identity);
+        ts.near_index(self.options.time, num_samples).0
+    }
+
+    fn is_hidden(&self, obj: &IObject) -> bool {
+        let props = obj.getProperties();
+        let Some(prop) = props.getPropertyByName(VISIBILITY_PROPERTY_NAME) else { return false };
+        let Some(scalar) = prop.asScalar() else { return false };
+        let index = self.index(prop.getHeader().time_sampling_index, scalar.getNumSamples());
+        get_visibility(obj, index) == ObjectVisibility::Hidden
+    }
+
+    /// Emit a node for `obj` and its subtree; returns the node index.
+    fn visit(&mut self, obj: &IObject) -> Option<usize> {
+        if self.options.skip_hidden && self.is_hidden(obj) {
+            return None;
+        }
+
+        let mut node = json!({
+            "name": obj.getName(),
+            "extras": { "path": obj.getFullName() },
+        });
+        if let Some(xform) = IXform::new(obj) {
+            let index = self.index(xform.getTimeSamplingIndex(), xform.getNumSamples());
+            if let Ok(sample) = xform.getSample(index) {
+                let local = sample.matrix();
+                // glTF has no inheritsTransform; approximate with the local matrix
+                if local != Mat4::IDENTITY && local.is_finite() {
+                    node["matrix"] = json!(local.to_cols_array());
+                }
+            }
+        } else if let Some(mesh) = self.read_mesh(obj) {
+            if let Some(mesh_index) = self.push_mesh(obj.getName(), mesh) {
+                node["mesh"] = json!(mesh_index);
+            }
+        } else if let Some(camera) = ICamera::new(obj) {
+            let index = self.index(camera.getTimeSamplingIndex(), camera.getNumSamples());
+            if let Ok(sample) = camera.getSample(index) {
+                let mut yfov = sample.vertical_fov() as f32;
+                if !(yfov.is_finite() && yfov > 0.0) {
+                    yfov = 0.8;
+                }
+                let mut perspective = json!({
+                    "yfov": yfov,
+                    "znear": (sample.near_clipping_plane as f32).max(1e-6),
+                });
+                let aspect = sample.aspect_ratio() as f32;
+                if aspect.is_finite() && aspect > 0.0 {
+                    perspective["aspectRatio"] = json!(aspect);
+                }
+                if sample.far_clipping_plane > sample.near_clipping_plane {
+                    perspective["zfar"] = json!(sample.far_clipping_plane as f32);
+                }
+                node["camera"] = json!(self.cameras.len());
+                self.cameras.push(json!({
+                    "name": obj.getName(),
+                    "type": "perspective",
+                    "perspective": perspective,
+                }));
+                self.stats.cameras += 1;
+            }
+        }
+
+        // Reserve the slot so parents precede children
+        let slot = self.nodes.len();
+        self.nodes.push(Value::Null);
+        let children: Vec<usize> = obj.getChildren().filter_map(|c| self.visit(&c)).collect();
+        if !children.is_empty() {
+            node["children"] = json!(children);
+        }
+        self.nodes[slot] = node;
+        Some(slot)
+    }
+
+    fn read_mesh(&self, obj: &IObject) -> Option<CornerMesh> {
+        let (data, params) = if let Some(mesh) = IPolyMesh::new(obj) {
+            let index = self.index(mesh.getTimeSamplingIndex(), mesh.getNumSamples());
+            let sample = mesh.getSample(index).ok()?;
+            let data = build_mesh(sample.positions, sample.face_counts, sample.face_indices, mesh.get_normals(index), mesh.get_uvs(index), true);
+            let params = extra_uv_sets(&mesh.uv_set_names(), data.uvs.is_some(), |name| mesh.get_uv_set(name, index));
+            (data, params)
+        } else if let Some(subd) = ISubD::new(obj).filter(|_| self.options.include_subd) {
+            let index = self.index(subd.getTimeSamplingIndex(), subd.getNumSamples());
+            let sample = subd.getSample(index).ok()?;
+            let data = build_mesh(sample.positions, sample.face_counts, sample.face_indices, subd.get_normals(index), subd.get_uvs(index), true);
+            let params = extra_uv_sets(&subd.uv_set_names(), data.uvs.is_some(), |name| subd.get_uv_set(name, index));
+            (data, params)
+        } else {
+            return None;
+        };
+
+        let corners = data.face_indices.len();
+        let num_points = data.positions.len();
+        let mut uv_sets: Vec<Vec<Vec2>> = data.uvs.iter().cloned().collect();
+        for values in params {
+            if values.len() == corners {
+                uv_sets.push(values);
+            } else if values.len() == num_points {
+                uv_sets.push(data.face_indices.iter().map(|&i| values[i as usize]).collect());
+            }
+        }
+        Some(CornerMesh { data, uv_sets })
+    }
+
+    /// Weld corners into glTF vertices and emit the mesh; None if nothing is drawable.
+    fn push_mesh(&mut self, name: &str, mesh: CornerMesh) -> Option<usize> {
+        let CornerMesh { data, uv_sets } = mesh;
+
+        let mut welded: HashMap<Vec<u32>, u32> = HashMap::new();
+        let mut positions: Vec<Vec3> = Vec::new();
+        let mut normals: Vec<Vec3> = Vec::new();
+        let mut uvs: Vec<Vec<Vec2>> = vec![Vec::new(); uv_sets.len()];
+        let mut indices: Vec<u32> = Vec::new();
+
+        let mut vertex = |corner: usize| -> u32 {
+            let point = data.face_indices[corner];
+            let normal = data.normals.get(corner).copied().unwrap_or(Vec3::ZERO);
+            let mut key = vec![point as u32, normal.x.to_bits(), normal.y.to_bits(), normal.z.to_bits()];
+            for set in &uv_sets {
+                key.extend([set[corner].x.to_bits(), set[corner].y.to_bits()]);
+            }
+            *welded.entry(key).or_insert_with(|| {
+                positions.push(data.positions[point as usize]);
+                normals.push(normal);
+                for (dst, set) in uvs.iter_mut().zip(&uv_sets) {
+                    dst.push(Vec2::new(set[corner].x, 1.0 - set[corner].y));
+                }
+                (positions.len() - 1) as u32
+            })
+        };
+
+        let mut offset = 0usize;
+        for &count in &data.face_counts {
+            let count = count.max(0) as usize;
+            // Fan triangulation with reversed winding (clockwise -> counter-clockwise)
+            for i in 1..count.saturating_sub(1) {
+                for corner in [offset, offset + i + 1, offset + i] {
+                    indices.push(vertex(corner));
+                }
+            }
+            offset += count;
+        }
+        if indices.is_empty() {
+            return None;
+        }
+
+        let (min, max) = positions.iter().fold(
+            (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+            |(lo, hi), &p| (lo.min(p), hi.max(p)),
+        );
+        let mut attributes = json!({
+            "POSITION": self.push_accessor(
+                bytemuck::cast_slice(&positions), ARRAY_BUFFER, FLOAT, positions.len(), "VEC3",
+                Some((min.to_array().to_vec(), max.to_array().to_vec())),
+            ),
+            "NORMAL": self.push_accessor(bytemuck::cast_slice(&normals), ARRAY_BUFFER, FLOAT, normals.len(), "VEC3", None),
+        });
+        for (set, values) in uvs.iter().enumerate() {
+            attributes[format!("TEXCOORD_{}", set)] =
+                json!(self.push_accessor(bytemuck::cast_slice(values), ARRAY_BUFFER, FLOAT, values.len(), "VEC2", None));
+        }
+        let index_accessor =
+            self.push_accessor(bytemuck::cast_slice(&indices), ELEMENT_ARRAY_BUFFER, UNSIGNED_INT, indices.len(), "SCALAR", None);
+
+        self.stats.meshes += 1;
+        self.stats.triangles += indices.len() / 3;
+        self.stats.max_uv_sets = self.stats.max_uv_sets.max(uvs.len());
+        self.meshes.push(json!({
+            "name": name,
+            "primitives": [{ "attributes": attributes, "indices": index_accessor, "mode": 4 }],
+        }));
+        Some(self.meshes.len() - 1)
+    }
+
+    /// Append a buffer view plus accessor over `bytes`; returns the accessor index.
+    fn push_accessor(
+        &mut self,
+        bytes: &[u8],
+        target: u32,
+        component_type: u32,
+        count: usize,
+        kind: &str,
+        bounds: Option<(Vec<f32>, Vec<f32>)>,
+    ) -> usize {
+        pad4(&mut self.bin, 0);
+        self.views.push(json!({
+            "buffer": 0,
+            "byteOffset": self.bin.len(),
+            "byteLength": bytes.len(),
+            "target": target,
+        }));
+        self.bin.extend_from_slice(bytes);
+
+        let mut accessor = json!({
+            "bufferView": self.views.len() - 1,
+            "componentType": component_type,
+            "count": count,
+            "type": kind,
+        });
+        if let Some((min, max)) = bounds {
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+}
+
+/// UV sets after the primary one (which `build_mesh` already read), in
+/// `uv_set_names` order.
+fn extra_uv_sets(
+    names: &[String],
+    has_primary: bool,
+    read: impl Fn(&str) -> Option<Vec<Vec2>>,
+) -> Vec<Vec<Vec2>> {
+    names.iter()
+        .filter(|n| !has_primary || n.as_str() != "uv")
+        .filter_map(|name| read(name))
+        .collect()
+}
+
+/// Pad to a 4-byte boundary (glTF alignment for views and GLB chunks).
+fn pad4(bytes: &mut Vec<u8>, fill: u8) {
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(fill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glb_layout() {
+        let doc = GltfDocument {
+            json: json!({ "asset": { "version": "2.0" } }),
+            bin: vec![1, 2, 3, 4, 5],
+            stats: GltfStats::default(),
+        };
+        let glb = doc.to_glb();
+        assert_eq!(&glb[0..4], GLB_MAGIC);
+        assert_eq!(u32::from_le_bytes(glb[4..8].try_into().unwrap()), 2);
+        assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+
+        let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        assert_eq!(json_len % 4, 0);
+        assert_eq!(&glb[16..20], GLB_CHUNK_JSON);
+        let json: Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+        assert_eq!(json["asset"]["version"], "2.0");
+
+        let bin = &glb[20 + json_len..];
+        assert_eq!(u32::from_le_bytes(bin[0..4].try_into().unwrap()), 8);
+        assert_eq!(&bin[4..8], GLB_CHUNK_BIN);
+        assert_eq!(&bin[8..13], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_pad4() {
+        let mut bytes = vec![0u8; 5];
+        pad4(&mut bytes, b' ');
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(bytes[7], b' ');
+        pad4(&mut bytes, 0);
+        assert_eq!(bytes.len(), 8);
+    }
+}
@@ -0,0 +1,25 @@
+//! Export archives to other interchange formats.
+//!
+//! Backends:
+//! - [`gltf`] - glTF 2.0 (`.gltf` + `.bin`, or binary `.glb`)
+//! - [`obj`] - Wavefront OBJ, one file per time sample
+//! - [`usd`] - USD ASCII (`.usda`) with time samples
+//! - [`pbrt`] - PBRT v4 scene (`.pbrt`) for cross-checking renders
+//! - [`mitsuba`] - Mitsuba 3 scene (`.xml`) for cross-checking renders
+//!
+//! The two renderer exports share [`render_scene`]: meshes, camera and
+//! Standard Surface materials at one time.
+
+pub mod gltf;
+pub mod mitsuba;
+pub mod obj;
+pub mod pbrt;
+pub mod render_scene;
+pub mod usd;
+
+pub use gltf::{export_gltf, GltfDocument, GltfOptions, GltfStats};
+pub use mitsuba::export_mitsuba;
+pub use obj::{export_obj_sequence, ObjOptions, ObjStats};
+pub use pbrt::export_pbrt;
+pub use render_scene::{RenderExportOptions, RenderExportStats, RenderScene, Surface};
+pub use usd::{export_usda, write_usda, UsdOptions, UsdStats};
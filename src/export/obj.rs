@@ -0,0 +1,247 @@
+//! Wavefront OBJ sequence export.
+//!
+//! [`export_obj_sequence`] writes one `.obj` per time sample of the archive,
+//! every visible PolyMesh (and SubD control cage) as an `o <path>` block
+//! with polygons kept as-is, file normals (`vn`) and UVs (`vt`).
+//!
+//! Samples follow the archive's most-sampled time sampling. For uniform
+//! sampling, frame numbers are the nearest DCC frame at the sampling rate and
+//! the archive's frame convention (see [`IArchive::frame_mapping`]), so a
+//! 24 fps shot starting at frame 1001 numbers its files 1001, 1002, ...;
+//! otherwise they are the sample index. Output names come from a pattern: `#` runs are replaced by
+//! the zero-padded frame (`shot.####.obj`); without `#` the frame is inserted
+//! before the extension (`shot.obj` -> `shot.0001.obj`), unless the archive
+//! is static, in which case the path is used as-is.
+//!
+//! Like glTF, OBJ expects counter-clockwise faces, so face winding is reversed.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use glam::Mat4;
+
+use crate::abc::IArchive;
+use crate::core::{FrameMapping, TimeSampling};
+use crate::geom::{collect_meshes, LoadOptions, MeshData};
+use crate::util::{Chrono, Error, Result};
+
+/// Options for [`export_obj_sequence`].
+#[derive(Debug, Clone)]
+pub struct ObjOptions {
+    /// Inclusive frame range to write (default: every sample).
+    pub frames: Option<RangeInclusive<i64>>,
+    /// Transform meshes to world space (default: true).
+    pub world_space: bool,
+    /// Include SubD objects as their control cage (default: true).
+    pub include_subd: bool,
+    /// Skip hidden objects (default: true).
+    pub skip_hidden: bool,
+}
+
+impl Default for ObjOptions {
+    fn default() -> Self {
+        Self { frames: None, world_space: true, include_subd: true, skip_hidden: true }
+    }
+}
+
+/// One sample of the sequence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObjFrame {
+    pub frame: i64,
+    pub time: Chrono,
+}
+
+/// Files written by [`export_obj_sequence`], in frame order.
+#[derive(Debug, Clone, Default)]
+pub struct ObjStats {
+    pub files: Vec<PathBuf>,
+    /// Meshes written, summed over all files.
+    pub meshes: usize,
+}
+
+/// Frames of the archive: one per sample of its most-sampled time sampling.
+pub fn sequence_frames(archive: &IArchive) -> Vec<ObjFrame> {
+    let (ts, num_samples) = (1..archive.getNumTimeSamplings())
+        .filter_map(|i| {
+            let n = archive.getMaxNumSamplesForTimeSamplingIndex(i)?;
+            Some((archive.getTimeSampling(i)?, n))
+        })
+        .max_by_key(|&(_, n)| n)
+        .filter(|&(_, n)| n > 1)
+        .unwrap_or((&TimeSampling::IDENTITY, 1));
+
+    // Frames at the sampling rate, so sub-frame samples still get distinct numbers
+    let mapping = ts.fps().map(|fps| FrameMapping::new(fps, archive.frame_mapping().convention));
+    (0..num_samples)
+        .map(|index| {
+            let time = ts.sample_time(index, num_samples);
+            let frame = match mapping {
+                Some(mapping) => mapping.nearest_frame(time),
+                None => index as i64,
+            };
+            ObjFrame { frame, time }
+        })
+        .collect()
+}
+
+/// Export every sample (or `options.frames`) to one OBJ file each.
+pub fn export_obj_sequence(archive: &IArchive, pattern: impl AsRef<Path>, options: &ObjOptions) -> Result<ObjStats> {
+    let pattern = pattern.as_ref();
+    let all = sequence_frames(archive);
+    let is_sequence = all.len() > 1;
+    let frames: Vec<ObjFrame> = all.into_iter()
+        .filter(|f| options.frames.as_ref().is_none_or(|r| r.contains(&f.frame)))
+        .collect();
+    if frames.is_empty() {
+        return Err(Error::other(format!("no samples in frame range {:?}", options.frames)));
+    }
+
+    let load = LoadOptions {
+        triangulate: false,
+        world_space: options.world_space,
+        include_subd: options.include_subd,
+        skip_hidden: options.skip_hidden,
+        compute_normals: false,
+    };
+    let mut stats = ObjStats::default();
+    for frame in frames {
+        let path = frame_path(pattern, frame.frame, is_sequence);
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let meshes = collect_meshes(archive, frame.time, &load);
+        let mut out = BufWriter::new(File::create(&path)?);
+        write_obj(&mut out, &meshes)?;
+        out.flush()?;
+        stats.meshes += meshes.len();
+        stats.files.push(path);
+    }
+    Ok(stats)
+}
+
+/// Output path for `frame`: fill `#` runs, else insert `.<frame:04>` before
+/// the extension (sequences only).
+pub fn frame_path(pattern: &Path, frame: i64, is_sequence: bool) -> PathBuf {
+    let text = pattern.to_string_lossy();
+    if let Some(start) = text.rfind('#') {
+        let run_start = text[..start].rfind(|c| c != '#').map_or(0, |i| i + 1);
+        let width = start + 1 - run_start;
+        let number = format!("{:0width$}", frame, width = width);
+        return PathBuf::from(format!("{}{}{}", &text[..run_start], number, &text[start + 1..]));
+    }
+    if !is_sequence {
+        return pattern.to_path_buf();
+    }
+    let stem = pattern.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = pattern.extension().map(|e| e.to_string_lossy().into_owned()).unwrap_or_else(|| "obj".into());
+    pattern.with_file_name(format!("{}.{:04}.{}", stem, frame, ext))
+}
+
+/// Write meshes as OBJ objects; vertex, UV and normal indices run across objects.
+pub fn write_obj<W: Write>(out: &mut W, meshes: &[(String, MeshData, Mat4)]) -> std::io::Result<()> {
+    writeln!(out, "# alembic-rs OBJ export")?;
+    let (mut v_base, mut vt_base, mut vn_base) = (1usize, 1usize, 1usize);
+    for (path, mesh, _) in meshes {
+        let name: String = path.chars().map(|c| if c.is_whitespace() { '_' } else { c }).collect();
+        writeln!(out, "o {}", name)?;
+        for p in &mesh.positions {
+            writeln!(out, "v {} {} {}", p.x, p.y, p.z)?;
+        }
+        let (uvs, uv_index) = match &mesh.uvs {
+            Some(uvs) => weld(uvs, |uv| (uv.x.to_bits(), uv.y.to_bits())),
+            None => (Vec::new(), Vec::new()),
+        };
+        for uv in &uvs {
+            writeln!(out, "vt {} {}", uv.x, uv.y)?;
+        }
+        let (normals, normal_index) = weld(&mesh.normals, |n| (n.x.to_bits(), n.y.to_bits(), n.z.to_bits()));
+        for n in &normals {
+            writeln!(out, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+
+        let mut offset = 0usize;
+        for &count in &mesh.face_counts {
+            let count = count.max(0) as usize;
+            write!(out, "f")?;
+            // Reversed corner order: clockwise (Alembic) -> counter-clockwise (OBJ)
+            for corner in (offset..offset + count).rev() {
+                let v = v_base + mesh.face_indices[corner] as usize;
+                match (uv_index.get(corner), normal_index.get(corner)) {
+                    (Some(t), Some(n)) => write!(out, " {}/{}/{}", v, vt_base + t, vn_base + n)?,
+                    (Some(t), None) => write!(out, " {}/{}", v, vt_base + t)?,
+                    (None, Some(n)) => write!(out, " {}//{}", v, vn_base + n)?,
+                    (None, None) => write!(out, " {}", v)?,
+                }
+            }
+            writeln!(out)?;
+            offset += count;
+        }
+        v_base += mesh.positions.len();
+        vt_base += uvs.len();
+        vn_base += normals.len();
+    }
+    Ok(())
+}
+
+/// Unique values (first occurrence order) and per-entry indices into them.
+fn weld<T: Copy, K: Hash + Eq>(values: &[T], key: impl Fn(&T) -> K) -> (Vec<T>, Vec<usize>) {
+    let mut unique = Vec::new();
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let indices = values.iter()
+        .map(|v| {
+            *seen.entry(key(v)).or_insert_with(|| {
+                unique.push(*v);
+                unique.len() - 1
+            })
+        })
+        .collect();
+    (unique, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Vec2, Vec3};
+
+    #[test]
+    fn test_frame_path() {
+        let p = |s: &str, f, seq| frame_path(Path::new(s), f, seq).to_string_lossy().into_owned();
+        assert_eq!(p("out/shot.####.obj", 1001, true), "out/shot.1001.obj");
+        assert_eq!(p("out/shot.##.obj", 7, true), "out/shot.07.obj");
+        assert_eq!(p("out/shot.obj", 12, true), "out/shot.0012.obj");
+        assert_eq!(p("out/shot.obj", 0, false), "out/shot.obj");
+    }
+
+    #[test]
+    fn test_write_obj() {
+        let mesh = MeshData {
+            positions: vec![Vec3::ZERO, Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::Y],
+            face_counts: vec![4],
+            face_indices: vec![0, 1, 2, 3],
+            normals: vec![Vec3::Z; 4],
+            uvs: Some(vec![Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]),
+        };
+        let tri = MeshData {
+            positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y],
+            face_counts: vec![3],
+            face_indices: vec![0, 1, 2],
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_obj(&mut out, &[
+            ("/quad".into(), mesh, Mat4::IDENTITY),
+            ("/my tri".into(), tri, Mat4::IDENTITY),
+        ]).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines.contains(&"o /quad"));
+        assert_eq!(lines.iter().filter(|l| l.starts_with("vn ")).count(), 1);
+        assert!(lines.contains(&"f 4/4/1 3/3/1 2/2/1 1/1/1"));
+        assert!(lines.contains(&"o /my_tri"));
+        assert!(lines.contains(&"f 7 6 5"));
+    }
+}
@@ -0,0 +1,176 @@
+//! PBRT v4 scene export.
+//!
+//! [`export_pbrt`] writes a `.pbrt` scene for one time sample (see
+//! [`RenderScene`]) with the meshes as `plymesh` shapes in a sibling
+//! `<stem>_meshes/` directory. Standard Surface parameters map to PBRT
+//! materials:
+//! - `metalness >= 0.5`: `conductor` with `reflectance = base * base_color`
+//! - `transmission >= 0.5`: `dielectric` with `eta = specular_IOR`
+//! - `specular == 0`: `diffuse`
+//! - otherwise `coateddiffuse` (diffuse base under a dielectric interface)
+//!
+//! Roughness is passed as the microfacet alpha (`specular_roughness²`, with
+//! `remaproughness` off) so it matches the Standard Surface lobe. Emissive
+//! materials add a `diffuse` area light.
+//!
+//! PBRT is left-handed; the camera is mirrored (`Scale -1 1 1`) so images
+//! match the right-handed Alembic scene, and the environment is rotated from
+//! PBRT's +Z up to Alembic's +Y up.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use glam::Vec3;
+
+use super::render_scene::{mesh_dir, RenderExportOptions, RenderExportStats, RenderScene, Surface};
+use crate::abc::IArchive;
+use crate::util::Result;
+
+/// Export `archive` at `options.time` to the PBRT scene `output`.
+pub fn export_pbrt(archive: &IArchive, output: impl AsRef<Path>, options: &RenderExportOptions) -> Result<RenderExportStats> {
+    let output = output.as_ref();
+    let scene = RenderScene::collect(archive, options)?;
+    let (dir, dir_name) = mesh_dir(output);
+    let files = scene.write_meshes(&dir)?;
+    let stem = output.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_else(|| "scene".into());
+    std::fs::write(output, write_pbrt(&scene, options, &dir_name, &files, &format!("{}.exr", stem)))?;
+
+    let mut stats = RenderExportStats {
+        meshes: scene.meshes.len(),
+        triangles: scene.triangles(),
+        materials: scene.materials.len(),
+        camera: scene.camera.path.clone(),
+        files: vec![output.to_path_buf()],
+    };
+    stats.files.extend(files.iter().map(|f| dir.join(f)));
+    Ok(stats)
+}
+
+/// The scene file text; `files` are the mesh file names inside `mesh_dir`.
+pub fn write_pbrt(scene: &RenderScene, options: &RenderExportOptions, mesh_dir: &str, files: &[String], image: &str) -> String {
+    let mut s = String::new();
+    let camera = &scene.camera;
+    let (eye, target, up) = camera.look_at();
+    let _ = writeln!(s, "# alembic-rs PBRT v4 export, t = {}s", options.time);
+    if let Some(path) = &camera.path {
+        let _ = writeln!(s, "# camera {}", path);
+    }
+    let _ = writeln!(s, "Scale -1 1 1");
+    let _ = writeln!(s, "LookAt {}  {}  {}", v3(eye), v3(target), v3(up));
+    // PBRT's fov is for the shorter image axis
+    let fov = if camera.width >= camera.height { camera.fov_y() } else { camera.fov_x };
+    let _ = writeln!(s, "Camera \"perspective\" \"float fov\" [ {} ]", fov);
+    let _ = writeln!(
+        s,
+        "Film \"rgb\" \"integer xresolution\" [ {} ] \"integer yresolution\" [ {} ] \"string filename\" [ \"{}\" ]",
+        camera.width, camera.height, image
+    );
+    let _ = writeln!(s, "Sampler \"zsobol\" \"integer pixelsamples\" [ {} ]", options.spp);
+    let _ = writeln!(s, "Integrator \"volpath\" \"integer maxdepth\" [ {} ]", options.max_depth);
+    let _ = writeln!(s, "\nWorldBegin\n");
+
+    let _ = writeln!(s, "AttributeBegin");
+    let _ = writeln!(s, "  Rotate -90 1 0 0");
+    match &options.env {
+        Some(env) => {
+            let _ = writeln!(s, "  LightSource \"infinite\" \"string filename\" [ \"{}\" ]", env.display());
+        }
+        None => {
+            let _ = writeln!(s, "  LightSource \"infinite\" \"rgb L\" [ {} ]", v3(Vec3::from(options.env_color)));
+        }
+    }
+    let _ = writeln!(s, "AttributeEnd\n");
+
+    for (i, (path, surface)) in scene.materials.iter().enumerate() {
+        let _ = writeln!(s, "# {}", path);
+        let _ = writeln!(s, "MakeNamedMaterial \"m{}\"\n    {}", i, material(surface));
+    }
+    let _ = writeln!(s, "MakeNamedMaterial \"default\"\n    {}\n", material(&Surface::default()));
+
+    for (mesh, file) in scene.meshes.iter().zip(files) {
+        let surface = mesh.material.map_or(Surface::default(), |i| scene.materials[i].1);
+        let _ = writeln!(s, "AttributeBegin  # {}", mesh.path);
+        match mesh.material {
+            Some(i) => {
+                let _ = writeln!(s, "  NamedMaterial \"m{}\"", i);
+            }
+            None => {
+                let _ = writeln!(s, "  NamedMaterial \"default\"");
+            }
+        }
+        if surface.emission > 0.0 {
+            let _ = writeln!(s, "  AreaLightSource \"diffuse\" \"rgb L\" [ {} ]", v3(surface.radiance()));
+        }
+        let _ = writeln!(s, "  Shape \"plymesh\" \"string filename\" [ \"{}/{}\" ]", mesh_dir, file);
+        let _ = writeln!(s, "AttributeEnd");
+    }
+    s
+}
+
+/// `MakeNamedMaterial` parameters for a Standard Surface.
+fn material(surface: &Surface) -> String {
+    let alpha = surface.specular_roughness * surface.specular_roughness;
+    if surface.metalness >= 0.5 {
+        format!(
+            "\"string type\" [ \"conductor\" ] \"rgb reflectance\" [ {} ] \"float roughness\" [ {} ] \"bool remaproughness\" false",
+            v3(surface.albedo()), alpha
+        )
+    } else if surface.transmission >= 0.5 {
+        format!(
+            "\"string type\" [ \"dielectric\" ] \"float eta\" [ {} ] \"float roughness\" [ {} ] \"bool remaproughness\" false",
+            surface.specular_ior, alpha
+        )
+    } else if surface.specular <= 0.0 {
+        format!("\"string type\" [ \"diffuse\" ] \"rgb reflectance\" [ {} ]", v3(surface.albedo()))
+    } else {
+        format!(
+            "\"string type\" [ \"coateddiffuse\" ] \"rgb reflectance\" [ {} ] \"float roughness\" [ {} ] \"float eta\" [ {} ] \"bool remaproughness\" false",
+            v3(surface.albedo()), alpha, surface.specular_ior
+        )
+    }
+}
+
+fn v3(v: Vec3) -> String {
+    format!("{} {} {}", v.x, v.y, v.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::render_scene::{RenderCamera, RenderMesh};
+    use crate::geom::MeshData;
+    use glam::Mat4;
+
+    #[test]
+    fn test_write_pbrt() {
+        let metal = Surface { metalness: 1.0, base_color: Vec3::new(1.0, 0.5, 0.0), base: 1.0, ..Default::default() };
+        let lamp = Surface { emission: 2.0, ..Default::default() };
+        let scene = RenderScene {
+            meshes: vec![
+                RenderMesh { path: "/a".into(), mesh: MeshData::default(), material: Some(0) },
+                RenderMesh { path: "/b".into(), mesh: MeshData::default(), material: Some(1) },
+                RenderMesh { path: "/c".into(), mesh: MeshData::default(), material: None },
+            ],
+            materials: vec![("/mat/metal".into(), metal), ("/mat/lamp".into(), lamp)],
+            camera: RenderCamera {
+                path: Some("/cam".into()),
+                world: Mat4::from_translation(Vec3::new(0.0, 0.0, 5.0)),
+                fov_x: 90.0,
+                near: 0.1,
+                far: 100.0,
+                width: 200,
+                height: 100,
+            },
+        };
+        let files = vec!["0000_a.ply".to_string(), "0001_b.ply".into(), "0002_c.ply".into()];
+        let text = write_pbrt(&scene, &RenderExportOptions::default(), "shot_meshes", &files, "shot.exr");
+        assert!(text.contains("LookAt 0 0 5  0 0 4  0 1 0"));
+        // 90 degrees across a 2:1 image is atan(0.5) * 2 vertically
+        let fov = 2.0 * 0.5f64.atan().to_degrees();
+        assert!(text.contains(&format!("\"float fov\" [ {} ]", fov)), "{}", text);
+        assert!(text.contains("\"conductor\" ] \"rgb reflectance\" [ 1 0.5 0 ]"));
+        assert!(text.contains("AreaLightSource \"diffuse\" \"rgb L\" [ 2 2 2 ]"));
+        assert!(text.contains("NamedMaterial \"default\""));
+        assert!(text.contains("Shape \"plymesh\" \"string filename\" [ \"shot_meshes/0002_c.ply\" ]"));
+    }
+}
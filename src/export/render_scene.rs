@@ -0,0 +1,486 @@
+//! Scene description shared by the offline renderer exports ([`pbrt`](super::pbrt), [`mitsuba`](super::mitsuba)).
+//!
+//! [`RenderScene::collect`] gathers what a path tracer needs at one time:
+//! - visible PolyMeshes (and SubD control cages), triangulated in world space
+//! - their assigned material, read as Autodesk Standard Surface parameters
+//!   ([`Surface`]); material inheritance is followed, face set assignments
+//!   are not (the mesh-level assignment applies to all faces)
+//! - a camera: the one requested, else the first in the archive, else one
+//!   framing the scene bounds
+//!
+//! Meshes are written as binary PLY files (see [`write_ply`]) that both
+//! renderers load natively, so the scene files stay small and readable.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::abc::{IArchive, IObject};
+use crate::core::TimeSampling;
+use crate::geom::{collect_meshes, CameraSample, ICamera, IXform, LoadOptions, MeshData};
+use crate::material::{get_material_assignment, merge_flattened_materials, FlattenedMaterial, IMaterial, ShaderNode};
+use crate::util::{Chrono, Error, Result};
+
+/// Options for [`export_pbrt`](super::export_pbrt) and [`export_mitsuba`](super::export_mitsuba).
+#[derive(Debug, Clone)]
+pub struct RenderExportOptions {
+    /// Time (seconds) to sample; each object uses its nearest sample (default: 0).
+    pub time: Chrono,
+    /// Full path of the camera to render through (default: the first camera).
+    pub camera: Option<String>,
+    /// Image size; the height defaults to the camera's film aspect (default: 1280 wide).
+    pub resolution: Option<(u32, u32)>,
+    /// Samples per pixel (default: 64).
+    pub spp: u32,
+    /// Maximum path length (default: 8).
+    pub max_depth: u32,
+    /// Environment map (lat-long for Mitsuba; PBRT v4 wants an equal-area
+    /// square map, see `imgtool makeequiarea`). Without one, a constant
+    /// environment of `env_color` lights the scene.
+    pub env: Option<PathBuf>,
+    /// Constant environment radiance (default: 1).
+    pub env_color: [f32; 3],
+    /// Include SubD objects as their control cage (default: true).
+    pub include_subd: bool,
+    /// Skip hidden objects (default: true).
+    pub skip_hidden: bool,
+}
+
+impl Default for RenderExportOptions {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            camera: None,
+            resolution: None,
+            spp: 64,
+            max_depth: 8,
+            env: None,
+            env_color: [1.0; 3],
+            include_subd: true,
+            skip_hidden: true,
+        }
+    }
+}
+
+/// What ended up in an exported scene.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderExportStats {
+    pub meshes: usize,
+    pub triangles: usize,
+    /// Distinct materials, not counting the default one.
+    pub materials: usize,
+    /// Camera path, or `None` if the camera was made up to frame the scene.
+    pub camera: Option<String>,
+    /// Scene file first, then the PLY meshes.
+    pub files: Vec<PathBuf>,
+}
+
+/// The subset of Autodesk Standard Surface the exports map. Defaults are the
+/// Standard Surface defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Surface {
+    pub base: f32,
+    pub base_color: Vec3,
+    pub metalness: f32,
+    pub specular: f32,
+    pub specular_roughness: f32,
+    pub specular_ior: f32,
+    pub transmission: f32,
+    pub transmission_color: Vec3,
+    pub coat: f32,
+    pub coat_roughness: f32,
+    pub emission: f32,
+    pub emission_color: Vec3,
+}
+
+impl Default for Surface {
+    fn default() -> Self {
+        Self {
+            base: 0.8,
+            base_color: Vec3::ONE,
+            metalness: 0.0,
+            specular: 1.0,
+            specular_roughness: 0.2,
+            specular_ior: 1.5,
+            transmission: 0.0,
+            transmission_color: Vec3::ONE,
+            coat: 0.0,
+            coat_roughness: 0.1,
+            emission: 0.0,
+            emission_color: Vec3::ONE,
+        }
+    }
+}
+
+impl Surface {
+    /// Read the surface shader of `material`, preferring a `standard_surface`
+    /// shader; names used by other shaders for the same inputs are accepted
+    /// (`diffuse_color`, `metallic`, `roughness`, `ior`, ...).
+    pub fn from_material(material: &FlattenedMaterial) -> Self {
+        let mut targets: Vec<&str> = material.target_names();
+        targets.sort_unstable();
+        let shaders: Vec<&ShaderNode> = targets
+            .iter()
+            .filter_map(|t| material.network(t)?.surface_shader())
+            .collect();
+        let Some(shader) = shaders
+            .iter()
+            .find(|s| s.shader_type.contains("standard_surface"))
+            .or(shaders.first())
+        else {
+            return Self::default();
+        };
+
+        let float = |names: &[&str], default: f32| {
+            names.iter().find_map(|n| shader.param(n)?.as_float()).unwrap_or(default)
+        };
+        let color = |names: &[&str], default: Vec3| {
+            names.iter().find_map(|n| shader.param(n)?.as_vec3()).unwrap_or(default)
+        };
+        let d = Self::default();
+        Self {
+            base: float(&["base", "base_weight"], d.base),
+            base_color: color(&["base_color", "diffuse_color", "color"], d.base_color),
+            metalness: float(&["metalness", "metallic", "metal"], d.metalness),
+            specular: float(&["specular", "specular_weight"], d.specular),
+            specular_roughness: float(&["specular_roughness", "roughness"], d.specular_roughness),
+            specular_ior: float(&["specular_IOR", "specular_ior", "IOR", "ior"], d.specular_ior),
+            transmission: float(&["transmission", "transmission_weight"], d.transmission),
+            transmission_color: color(&["transmission_color"], d.transmission_color),
+            coat: float(&["coat", "coat_weight"], d.coat),
+            coat_roughness: float(&["coat_roughness"], d.coat_roughness),
+            emission: float(&["emission", "emission_weight"], d.emission),
+            emission_color: color(&["emission_color"], d.emission_color),
+        }
+    }
+
+    /// Diffuse albedo: `base * base_color`.
+    pub fn albedo(&self) -> Vec3 {
+        self.base_color * self.base
+    }
+
+    /// Emitted radiance: `emission * emission_color`.
+    pub fn radiance(&self) -> Vec3 {
+        self.emission_color * self.emission
+    }
+}
+
+/// A triangulated world-space mesh and its material.
+#[derive(Debug, Clone)]
+pub struct RenderMesh {
+    pub path: String,
+    pub mesh: MeshData,
+    /// Index into [`RenderScene::materials`]; `None` uses [`Surface::default`].
+    pub material: Option<usize>,
+}
+
+/// Camera placement and lens, resolved for the output resolution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderCamera {
+    /// `None` for a camera framing the scene bounds.
+    pub path: Option<String>,
+    /// Camera-to-world; the camera looks down -Z with +Y up.
+    pub world: Mat4,
+    /// Horizontal field of view in degrees.
+    pub fov_x: f64,
+    pub near: f64,
+    pub far: f64,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RenderCamera {
+    /// Eye position, a point one unit along the view direction, and the up vector.
+    pub fn look_at(&self) -> (Vec3, Vec3, Vec3) {
+        let eye = self.world.transform_point3(Vec3::ZERO);
+        let forward = self.world.transform_vector3(Vec3::NEG_Z).normalize_or(Vec3::NEG_Z);
+        let up = self.world.transform_vector3(Vec3::Y).normalize_or(Vec3::Y);
+        (eye, eye + forward, up)
+    }
+
+    /// Vertical field of view in degrees.
+    pub fn fov_y(&self) -> f64 {
+        let aspect = self.width as f64 / self.height.max(1) as f64;
+        (2.0 * ((self.fov_x.to_radians() / 2.0).tan() / aspect).atan()).to_degrees()
+    }
+}
+
+/// Everything the offline exports write.
+#[derive(Debug, Clone)]
+pub struct RenderScene {
+    pub meshes: Vec<RenderMesh>,
+    /// Material path and its surface.
+    pub materials: Vec<(String, Surface)>,
+    pub camera: RenderCamera,
+}
+
+impl RenderScene {
+    /// Gather meshes, materials and the camera at `options.time`.
+    pub fn collect(archive: &IArchive, options: &RenderExportOptions) -> Result<Self> {
+        let load = LoadOptions {
+            triangulate: true,
+            world_space: true,
+            include_subd: options.include_subd,
+            skip_hidden: options.skip_hidden,
+            compute_normals: true,
+        };
+        let mut materials: Vec<(String, Surface)> = Vec::new();
+        let mut meshes = Vec::new();
+        for (path, mesh, _) in collect_meshes(archive, options.time, &load) {
+            let assigned = archive.findObject(&path).as_ref().and_then(get_material_assignment);
+            let material = assigned.and_then(|mat_path| {
+                if let Some(i) = materials.iter().position(|(p, _)| *p == mat_path) {
+                    return Some(i);
+                }
+                let flattened = flatten_material(archive, &mat_path)?;
+                materials.push((mat_path, Surface::from_material(&flattened)));
+                Some(materials.len() - 1)
+            });
+            meshes.push(RenderMesh { path, mesh, material });
+        }
+        let camera = find_camera(archive, options, &meshes)?;
+        Ok(Self { meshes, materials, camera })
+    }
+
+    /// Write each mesh to `dir` as `<index>_<name>.ply`; returns the file names.
+    pub fn write_meshes(&self, dir: &Path) -> Result<Vec<String>> {
+        std::fs::create_dir_all(dir)?;
+        let mut names = Vec::with_capacity(self.meshes.len());
+        for (i, mesh) in self.meshes.iter().enumerate() {
+            let leaf = mesh.path.rsplit('/').next().unwrap_or_default();
+            let name = format!("{:04}_{}.ply", i, sanitize(leaf));
+            let mut out = BufWriter::new(File::create(dir.join(&name))?);
+            write_ply(&mut out, &mesh.mesh)?;
+            out.flush()?;
+            names.push(name);
+        }
+        Ok(names)
+    }
+
+    /// Total triangle count.
+    pub fn triangles(&self) -> usize {
+        self.meshes.iter().map(|m| m.mesh.num_faces()).sum()
+    }
+}
+
+/// `name` with anything but ASCII alphanumerics, `-` and `_` replaced by `_`.
+pub(crate) fn sanitize(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+/// Directory for the PLY meshes of `scene_file`: `<stem>_meshes` next to it,
+/// and its name as referenced from the scene file.
+pub(crate) fn mesh_dir(scene_file: &Path) -> (PathBuf, String) {
+    let stem = scene_file.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_else(|| "scene".into());
+    let name = format!("{}_meshes", stem);
+    (scene_file.with_file_name(&name), name)
+}
+
+/// The material at `path` with its `.inherits` chain merged in.
+fn flatten_material(archive: &IArchive, path: &str) -> Option<FlattenedMaterial> {
+    let obj = archive.findObject(path)?;
+    let material = IMaterial::new(&obj)?;
+    let mut flattened = material.flatten();
+    let mut parent_path = material.inherits_path();
+    // Bounded, in case of an inheritance cycle
+    for _ in 0..16 {
+        let Some(path) = parent_path.take() else { break };
+        let Some(obj) = archive.findObject(&path) else { break };
+        let Some(parent) = IMaterial::new(&obj) else { break };
+        merge_flattened_materials(&mut flattened, &parent.flatten());
+        parent_path = parent.inherits_path();
+    }
+    Some(flattened)
+}
+
+fn find_camera(archive: &IArchive, options: &RenderExportOptions, meshes: &[RenderMesh]) -> Result<RenderCamera> {
+    let mut cameras = Vec::new();
+    for child in archive.getTop().getChildren() {
+        visit_cameras(archive, options.time, &child, Mat4::IDENTITY, &mut cameras);
+    }
+    let found = match &options.camera {
+        Some(wanted) => {
+            let wanted = format!("/{}", wanted.trim_matches('/'));
+            Some(cameras.into_iter().find(|c| c.0 == wanted).ok_or_else(|| Error::other(format!("no camera at {}", wanted)))?)
+        }
+        None => cameras.into_iter().next(),
+    };
+
+    let size = |aspect: f64| {
+        options.resolution.unwrap_or_else(|| {
+            let width = 1280;
+            (width, ((width as f64 / aspect).round() as u32).max(1))
+        })
+    };
+    if let Some((path, sample, world)) = found {
+        let aspect = if sample.vertical_aperture > 0.0 { sample.aspect_ratio() } else { 16.0 / 9.0 };
+        let (width, height) = size(aspect);
+        return Ok(RenderCamera {
+            path: Some(path),
+            world,
+            fov_x: sample.field_of_view(),
+            near: sample.near_clipping_plane,
+            far: sample.far_clipping_plane,
+            width,
+            height,
+        });
+    }
+
+    // No camera: look at the bounds from the front-right, above
+    let (mut min, mut max) = (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN));
+    for p in meshes.iter().flat_map(|m| &m.mesh.positions) {
+        min = min.min(*p);
+        max = max.max(*p);
+    }
+    if min.x > max.x {
+        (min, max) = (Vec3::splat(-1.0), Vec3::ONE);
+    }
+    let center = (min + max) / 2.0;
+    let radius = ((max - min).length() / 2.0).max(1e-3);
+    let fov_x = 45.0_f64;
+    let distance = radius / (fov_x.to_radians() as f32 / 2.0).tan() * 1.2;
+    let eye = center + Vec3::new(1.0, 0.6, 1.0).normalize() * distance;
+    let (width, height) = size(16.0 / 9.0);
+    Ok(RenderCamera {
+        path: None,
+        world: Mat4::look_at_rh(eye, center, Vec3::Y).inverse(),
+        fov_x,
+        near: (distance - radius * 2.0).max(distance * 1e-3) as f64,
+        far: (distance + radius * 2.0) as f64,
+        width,
+        height,
+    })
+}
+
+fn visit_cameras(
+    archive: &IArchive,
+    time: Chrono,
+    obj: &IObject,
+    parent_world: Mat4,
+    out: &mut Vec<(String, CameraSample, Mat4)>,
+) {
+    let index = |ts_index: u32, num_samples: usize| {
+        let identity = TimeSampling::identity();
+        let ts = archive.getTimeSampling(ts_index as usize).unwrap_or(&identity);
+        if num_samples <= 1 { 0 } else { ts.near_index(time, num_samples).0 }
+    };
+    let mut world = parent_world;
+    if let Some(xform) = IXform::new(obj) {
+        if let Ok(sample) = xform.getSample(index(xform.getTimeSamplingIndex(), xform.getNumSamples())) {
+            let local = sample.matrix();
+            world = if sample.inherits { parent_world * local } else { local };
+        }
+    } else if let Some(camera) = ICamera::new(obj) {
+        if let Ok(sample) = camera.getSample(index(camera.getTimeSamplingIndex(), camera.getNumSamples())) {
+            out.push((obj.getFullName().to_string(), sample, world));
+        }
+    }
+    for child in obj.getChildren() {
+        visit_cameras(archive, time, &child, world, out);
+    }
+}
+
+/// Write a triangulated mesh as binary little-endian PLY with `x y z nx ny nz
+/// u v` vertices, split wherever a position has several normals or UVs.
+/// Triangles are reversed to counter-clockwise.
+pub fn write_ply<W: Write>(out: &mut W, mesh: &MeshData) -> std::io::Result<()> {
+    // One vertex per distinct (position, normal, uv) corner
+    let mut vertices: Vec<(Vec3, Vec3, Vec2)> = Vec::new();
+    let mut seen = std::collections::HashMap::new();
+    let corners: Vec<u32> = mesh.face_indices.iter().enumerate()
+        .map(|(corner, &p)| {
+            let position = mesh.positions[p as usize];
+            let normal = mesh.normals.get(corner).copied().unwrap_or(Vec3::ZERO);
+            let uv = mesh.uvs.as_ref().and_then(|uvs| uvs.get(corner)).copied().unwrap_or(Vec2::ZERO);
+            let key = (p, normal.to_array().map(f32::to_bits), uv.to_array().map(f32::to_bits));
+            *seen.entry(key).or_insert_with(|| {
+                vertices.push((position, normal, uv));
+                vertices.len() as u32 - 1
+            })
+        })
+        .collect();
+    let has_normals = !mesh.normals.is_empty();
+    let has_uvs = mesh.uvs.is_some();
+
+    writeln!(out, "ply\nformat binary_little_endian 1.0\ncomment alembic-rs export")?;
+    writeln!(out, "element vertex {}", vertices.len())?;
+    writeln!(out, "property float x\nproperty float y\nproperty float z")?;
+    if has_normals {
+        writeln!(out, "property float nx\nproperty float ny\nproperty float nz")?;
+    }
+    if has_uvs {
+        writeln!(out, "property float u\nproperty float v")?;
+    }
+    writeln!(out, "element face {}", corners.len() / 3)?;
+    writeln!(out, "property list uchar int vertex_indices\nend_header")?;
+    for (position, normal, uv) in &vertices {
+        let mut values = position.to_array().to_vec();
+        if has_normals {
+            values.extend(normal.to_array());
+        }
+        if has_uvs {
+            values.extend(uv.to_array());
+        }
+        for v in values {
+            out.write_all(&v.to_le_bytes())?;
+        }
+    }
+    for tri in corners.chunks_exact(3) {
+        out.write_all(&[3])?;
+        for i in [tri[0], tri[2], tri[1]] {
+            out.write_all(&i.to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::{ShaderNetwork, ShaderParam, ShaderParamValue};
+
+    #[test]
+    fn test_surface_from_material() {
+        let mut shader = ShaderNode::new("surface", "standard_surface", "arnold");
+        shader.add_param(ShaderParam::new("base_color", ShaderParamValue::Color3(Vec3::new(0.8, 0.2, 0.1))));
+        shader.add_param(ShaderParam::new("metalness", ShaderParamValue::Float(1.0)));
+        let mut network = ShaderNetwork::new();
+        network.add_node(shader);
+        network.set_terminal("surface", "surface");
+        let mut material = FlattenedMaterial::new();
+        material.networks.insert("arnold".into(), network);
+
+        let surface = Surface::from_material(&material);
+        assert_eq!(surface.base_color, Vec3::new(0.8, 0.2, 0.1));
+        assert_eq!(surface.metalness, 1.0);
+        assert_eq!(surface.specular_roughness, Surface::default().specular_roughness);
+        assert_eq!(Surface::from_material(&FlattenedMaterial::new()), Surface::default());
+    }
+
+    #[test]
+    fn test_write_ply() {
+        // Quad split into two triangles sharing a hard-edged corner
+        let mesh = MeshData {
+            positions: vec![Vec3::ZERO, Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::Y],
+            face_counts: vec![3, 3],
+            face_indices: vec![0, 1, 2, 0, 2, 3],
+            normals: vec![Vec3::Z, Vec3::Z, Vec3::Z, Vec3::X, Vec3::Z, Vec3::Z],
+            uvs: None,
+        };
+        let mut out = Vec::new();
+        write_ply(&mut out, &mesh).unwrap();
+        let header_end = out.windows(11).position(|w| w == b"end_header\n").unwrap() + 11;
+        let header = String::from_utf8_lossy(&out[..header_end]);
+        assert!(header.contains("element vertex 5\n"));
+        assert!(header.contains("property float nx\n"));
+        assert!(!header.contains("property float u\n"));
+        assert!(header.contains("element face 2\n"));
+        assert_eq!(out.len() - header_end, 5 * 6 * 4 + 2 * (1 + 3 * 4));
+        // First triangle reversed: 0, 2, 1
+        let face = &out[header_end + 5 * 24..];
+        assert_eq!(face[0], 3);
+        assert_eq!(&face[1..13], [0u32, 2, 1].iter().flat_map(|i| i.to_le_bytes()).collect::<Vec<_>>());
+    }
+}
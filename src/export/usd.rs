@@ -0,0 +1,443 @@
+//! USD ASCII (`.usda`) export.
+//!
+//! [`write_usda`] converts the whole archive, keeping every time sample:
+//! - Xforms become `Xform` prims with a single `xformOp:transform`
+//!   (`!resetXformStack!` when the Alembic xform doesn't inherit)
+//! - PolyMeshes become `Mesh` prims; SubDs too, with their subdivision scheme
+//! - Cameras become `Camera` prims (apertures converted from cm to mm)
+//! - Points become `Points` prims with widths, ids and velocities
+//! - Any other object becomes a `Scope`, so paths and hierarchy survive
+//!
+//! Attributes whose value changes over time are written as `.timeSamples`
+//! keyed by time code (the DCC frame from [`IArchive::frame_mapping`], so a
+//! Houdini cache keeps its frame numbers), others as plain defaults. Meshes are
+//! written with `orientation = "leftHanded"`, which matches Alembic's
+//! clockwise winding, so topology and face-varying data are kept verbatim.
+//! Names that aren't valid USD identifiers are sanitized; the original
+//! Alembic path is then kept in `customData.abcPath`.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::Path;
+
+use glam::{Vec2, Vec3};
+
+use crate::abc::{IArchive, IObject};
+use crate::core::{FrameMapping, TimeSampling};
+use crate::geom::mesh_data::arb_uvs;
+use crate::geom::{
+    get_visibility, CameraSample, ICamera, IPoints, IPolyMesh, ISubD, IXform, ObjectVisibility, PointsSample,
+    SubDScheme, VISIBILITY_PROPERTY_NAME,
+};
+use crate::util::Result;
+
+/// Options for [`write_usda`].
+#[derive(Debug, Clone, Default)]
+pub struct UsdOptions {
+    /// Time codes per second (default: the archive's frame mapping fps -
+    /// its DCC fps, else its uniform sampling rate, else 24).
+    pub fps: Option<f64>,
+}
+
+/// What ended up in the exported layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsdStats {
+    pub prims: usize,
+    pub xforms: usize,
+    pub meshes: usize,
+    pub cameras: usize,
+    pub points: usize,
+    /// Time codes per second written to the layer.
+    pub fps: f64,
+    /// First and last time code with a sample, if anything is animated.
+    pub time_range: Option<(f64, f64)>,
+}
+
+/// Export `archive` to a `.usda` file.
+pub fn export_usda(archive: &IArchive, output: impl AsRef<Path>, options: &UsdOptions) -> Result<UsdStats> {
+    let (text, stats) = write_usda(archive, options);
+    std::fs::write(output, text)?;
+    Ok(stats)
+}
+
+/// Convert `archive` to USD ASCII text.
+pub fn write_usda(archive: &IArchive, options: &UsdOptions) -> (String, UsdStats) {
+    let archive_mapping = archive.frame_mapping();
+    let frames = match options.fps {
+        Some(fps) => FrameMapping::new(fps, archive_mapping.convention),
+        None => archive_mapping,
+    };
+    let fps = frames.fps;
+    let mut writer = Writer { archive, frames, body: String::new(), stats: UsdStats { fps, ..Default::default() } };
+
+    let top = archive.getTop();
+    let mut names = HashSet::new();
+    let mut roots = Vec::new();
+    for child in top.getChildren() {
+        roots.push(writer.prim(&child, 0, &mut names));
+    }
+
+    let mut out = String::from("#usda 1.0\n(\n");
+    if let [root] = roots.as_slice() {
+        out.push_str(&format!("    defaultPrim = \"{}\"\n", root));
+    }
+    out.push_str("    doc = \"Exported by alembic-rs\"\n");
+    if let Some((start, end)) = writer.stats.time_range {
+        out.push_str(&format!("    endTimeCode = {}\n", num(end)));
+        out.push_str(&format!("    startTimeCode = {}\n", num(start)));
+    }
+    out.push_str(&format!("    framesPerSecond = {}\n", num(fps)));
+    out.push_str(&format!("    timeCodesPerSecond = {}\n", num(fps)));
+    out.push_str("    upAxis = \"Y\"\n)\n");
+    out.push_str(&writer.body);
+    (out, writer.stats)
+}
+
+/// Formats one camera attribute of a sample.
+type CameraField = fn(&CameraSample) -> String;
+
+struct Writer<'a> {
+    archive: &'a IArchive,
+    frames: FrameMapping,
+    body: String,
+    stats: UsdStats,
+}
+
+/// Mesh data of one sample.
+struct MeshFrame {
+    positions: Vec<Vec3>,
+    counts: Vec<i32>,
+    indices: Vec<i32>,
+    normals: Option<Vec<Vec3>>,
+    uvs: Option<Vec<Vec2>>,
+}
+
+impl Writer<'_> {
+    /// Time codes of `num_samples` samples of a time sampling.
+    fn codes(&mut self, ts_index: u32, num_samples: usize) -> Vec<f64> {
+        let identity = TimeSampling::identity();
+        let ts = self.archive.getTimeSampling(ts_index as usize).unwrap_or(&identity);
+        let codes: Vec<f64> = (0..num_samples).map(|i| self.frames.sample_to_frame(ts, i, num_samples)).collect();
+        if let (true, Some(&first), Some(&last)) = (codes.len() > 1, codes.first(), codes.last()) {
+            self.stats.time_range = Some(match self.stats.time_range {
+                Some((start, end)) => (start.min(first), end.max(last)),
+                None => (first, last),
+            });
+        }
+        codes
+    }
+
+    fn line(&mut self, depth: usize, text: &str) {
+        for _ in 0..depth {
+            self.body.push_str("    ");
+        }
+        self.body.push_str(text);
+        self.body.push('\n');
+    }
+
+    /// Write an attribute: a default if every sample has the same value,
+    /// else `.timeSamples`. `meta` is attribute metadata such as interpolation.
+    fn attr(&mut self, depth: usize, decl: &str, meta: Option<&str>, samples: &[(f64, String)]) {
+        let Some((_, first)) = samples.first() else { return };
+        let meta = meta.map(|m| format!(" ({})", m)).unwrap_or_default();
+        if samples.iter().all(|(_, v)| v == first) {
+            self.line(depth, &format!("{} = {}{}", decl, first, meta));
+            return;
+        }
+        if !meta.is_empty() {
+            self.line(depth, &format!("{}{}", decl, meta));
+        }
+        self.line(depth, &format!("{}.timeSamples = {{", decl));
+        for (code, value) in samples {
+            self.line(depth + 1, &format!("{}: {},", num(*code), value));
+        }
+        self.line(depth, "}");
+    }
+
+    /// Write `obj` and its subtree; returns the prim name used.
+    fn prim(&mut self, obj: &IObject, depth: usize, siblings: &mut HashSet<String>) -> String {
+        let name = unique_name(obj.getName(), siblings);
+        let type_name = if IXform::new(obj).is_some() {
+            "Xform"
+        } else if IPolyMesh::new(obj).is_some() || ISubD::new(obj).is_some() {
+            "Mesh"
+        } else if ICamera::new(obj).is_some() {
+            "Camera"
+        } else if IPoints::new(obj).is_some() {
+            "Points"
+        } else {
+            "Scope"
+        };
+        self.stats.prims += 1;
+
+        if name == obj.getName() {
+            self.line(depth, &format!("def {} \"{}\"", type_name, name));
+        } else {
+            self.line(depth, &format!("def {} \"{}\" (", type_name, name));
+            self.line(depth + 1, &format!("customData = {{ string abcPath = \"{}\" }}", escape(obj.getFullName())));
+            self.line(depth, ")");
+        }
+        self.line(depth, "{");
+        let inner = depth + 1;
+
+        if let Some(xform) = IXform::new(obj) {
+            self.write_xform(&xform, inner);
+        } else if let Some(mesh) = IPolyMesh::new(obj) {
+            let codes = self.codes(mesh.getTimeSamplingIndex(), mesh.getNumSamples());
+            let frames = codes.into_iter().enumerate().filter_map(|(i, code)| {
+                let sample = mesh.getSample(i).ok()?;
+                Some((code, MeshFrame {
+                    positions: sample.positions,
+                    counts: sample.face_counts,
+                    indices: sample.face_indices,
+                    normals: mesh.get_normals(i),
+                    uvs: mesh.get_uvs(i).or_else(|| arb_uvs(mesh.arb_geom_param("uv", i))),
+                }))
+            }).collect();
+            self.write_mesh(inner, "none", frames);
+        } else if let Some(subd) = ISubD::new(obj) {
+            let codes = self.codes(subd.getTimeSamplingIndex(), subd.getNumSamples());
+            let mut rules = None;
+            let frames = codes.into_iter().enumerate().filter_map(|(i, code)| {
+                let sample = subd.getSample(i).ok()?;
+                rules.get_or_insert((sample.scheme, sample.interp_boundary, sample.fv_interp_boundary));
+                Some((code, MeshFrame {
+                    positions: sample.positions,
+                    counts: sample.face_counts,
+                    indices: sample.face_indices,
+                    normals: subd.get_normals(i),
+                    uvs: subd.get_uvs(i).or_else(|| arb_uvs(subd.arb_geom_param("uv", i))),
+                }))
+            }).collect();
+            let (scheme, boundary, fv) = rules.unwrap_or_default();
+            let scheme = match scheme {
+                SubDScheme::CatmullClark => "catmullClark",
+                SubDScheme::Loop => "loop",
+                SubDScheme::Bilinear => "bilinear",
+            };
+            self.write_mesh(inner, scheme, frames);
+            self.line(inner, &format!("uniform token interpolateBoundary = \"{}\"", boundary.usd_token()));
+            self.line(inner, &format!("uniform token faceVaryingLinearInterpolation = \"{}\"", fv.usd_token()));
+        } else if let Some(camera) = ICamera::new(obj) {
+            self.write_camera(&camera, inner);
+        } else if let Some(points) = IPoints::new(obj) {
+            self.write_points(&points, inner);
+        }
+        self.write_visibility(obj, inner);
+
+        let mut names = HashSet::new();
+        let children: Vec<IObject> = obj.getChildren().collect();
+        if !children.is_empty() {
+            self.line(0, "");
+        }
+        for child in &children {
+            self.prim(child, inner, &mut names);
+        }
+        self.line(depth, "}");
+        name
+    }
+
+    fn write_xform(&mut self, xform: &IXform, depth: usize) {
+        self.stats.xforms += 1;
+        let codes = self.codes(xform.getTimeSamplingIndex(), xform.getNumSamples());
+        let mut inherits = true;
+        let samples: Vec<(f64, String)> = codes.into_iter().enumerate()
+            .filter_map(|(i, code)| {
+                let sample = xform.getSample(i).ok()?;
+                inherits &= sample.inherits;
+                // glam columns are the rows of USD's row-vector matrix
+                let rows = sample.matrix().to_cols_array_2d();
+                let rows: Vec<String> = rows.iter().map(|r| tuple(r)).collect();
+                Some((code, format!("( {} )", rows.join(", "))))
+            })
+            .collect();
+        self.attr(depth, "matrix4d xformOp:transform", None, &samples);
+        let order = if inherits { "[\"xformOp:transform\"]" } else { "[\"!resetXformStack!\", \"xformOp:transform\"]" };
+        self.line(depth, &format!("uniform token[] xformOpOrder = {}", order));
+    }
+
+    fn write_mesh(&mut self, depth: usize, scheme: &str, frames: Vec<(f64, MeshFrame)>) {
+        self.stats.meshes += 1;
+        self.line(depth, "uniform token orientation = \"leftHanded\"");
+        self.line(depth, &format!("uniform token subdivisionScheme = \"{}\"", scheme));
+
+        let each = |f: &dyn Fn(&MeshFrame) -> Option<String>| -> Vec<(f64, String)> {
+            frames.iter().filter_map(|(code, frame)| Some((*code, f(frame)?))).collect()
+        };
+        self.attr(depth, "int[] faceVertexCounts", None, &each(&|f| Some(list(&f.counts, |c| c.to_string()))));
+        self.attr(depth, "int[] faceVertexIndices", None, &each(&|f| Some(list(&f.indices, |i| i.to_string()))));
+        self.attr(depth, "point3f[] points", None, &each(&|f| Some(list(&f.positions, |p| tuple(&p.to_array())))));
+        self.attr(depth, "float3[] extent", None, &each(&|f| extent(&f.positions, 0.0)));
+
+        // Interpolation comes from the first sample; samples not matching it are dropped
+        let interpolation = |frame: &MeshFrame, len: usize| {
+            if len == frame.indices.len() {
+                Some("faceVarying")
+            } else if len == frame.positions.len() {
+                Some("vertex")
+            } else {
+                None
+            }
+        };
+        let normals_mode = frames.iter()
+            .find_map(|(_, f)| f.normals.as_ref().map(|n| interpolation(f, n.len())))
+            .flatten();
+        if let Some(mode) = normals_mode {
+            let samples = each(&|f| {
+                let n = f.normals.as_ref().filter(|n| interpolation(f, n.len()) == Some(mode))?;
+                Some(list(n, |v| tuple(&v.to_array())))
+            });
+            self.attr(depth, "normal3f[] normals", Some(&format!("interpolation = \"{}\"", mode)), &samples);
+        }
+        let uvs_mode = frames.iter()
+            .find_map(|(_, f)| f.uvs.as_ref().map(|uv| interpolation(f, uv.len())))
+            .flatten();
+        if let Some(mode) = uvs_mode {
+            let samples = each(&|f| {
+                let uv = f.uvs.as_ref().filter(|uv| interpolation(f, uv.len()) == Some(mode))?;
+                Some(list(uv, |v| tuple(&v.to_array())))
+            });
+            self.attr(depth, "texCoord2f[] primvars:st", Some(&format!("interpolation = \"{}\"", mode)), &samples);
+        }
+    }
+
+    fn write_camera(&mut self, camera: &ICamera, depth: usize) {
+        self.stats.cameras += 1;
+        let codes = self.codes(camera.getTimeSamplingIndex(), camera.getNumSamples());
+        let samples: Vec<_> = codes.into_iter().enumerate()
+            .filter_map(|(i, code)| Some((code, camera.getSample(i).ok()?)))
+            .collect();
+        // USD apertures and offsets are in mm (tenths of a scene unit), Alembic's in cm
+        let fields: [(&str, CameraField); 8] = [
+            ("float focalLength", |s| num(s.focal_length as f32)),
+            ("float horizontalAperture", |s| num((s.horizontal_aperture * 10.0) as f32)),
+            ("float verticalAperture", |s| num((s.vertical_aperture * 10.0) as f32)),
+            ("float horizontalApertureOffset", |s| num((s.horizontal_film_offset * 10.0) as f32)),
+            ("float verticalApertureOffset", |s| num((s.vertical_film_offset * 10.0) as f32)),
+            ("float2 clippingRange", |s| tuple(&[s.near_clipping_plane as f32, s.far_clipping_plane as f32])),
+            ("float fStop", |s| num(s.f_stop as f32)),
+            ("float focusDistance", |s| num(s.focus_distance as f32)),
+        ];
+        for (decl, value) in fields {
+            let values: Vec<(f64, String)> = samples.iter().map(|(code, s)| (*code, value(s))).collect();
+            self.attr(depth, decl, None, &values);
+        }
+    }
+
+    fn write_points(&mut self, points: &IPoints, depth: usize) {
+        self.stats.points += 1;
+        let codes = self.codes(points.getTimeSamplingIndex(), points.getNumSamples());
+        let samples: Vec<_> = codes.into_iter().enumerate()
+            .filter_map(|(i, code)| Some((code, points.getSample(i).ok()?)))
+            .collect();
+        let each = |f: &dyn Fn(&PointsSample) -> Option<String>| -> Vec<(f64, String)> {
+            samples.iter().filter_map(|(code, s)| Some((*code, f(s)?))).collect()
+        };
+        self.attr(depth, "point3f[] points", None, &each(&|s| Some(list(&s.positions, |p| tuple(&p.to_array())))));
+        self.attr(depth, "float3[] extent", None, &each(&|s| {
+            let radius = s.widths.iter().copied().fold(0.0f32, f32::max) * 0.5;
+            extent(&s.positions, radius)
+        }));
+        let widths = each(&|s| s.has_widths().then(|| list(&s.widths, |w| num(*w))));
+        let constant = samples.iter().all(|(_, s)| s.widths.len() == 1 && s.positions.len() != 1);
+        let mode = if constant { "interpolation = \"constant\"" } else { "interpolation = \"vertex\"" };
+        self.attr(depth, "float[] widths", Some(mode), &widths);
+        self.attr(depth, "int64[] ids", None, &each(&|s| s.has_ids().then(|| list(&s.ids, |id| (*id as i64).to_string()))));
+        self.attr(depth, "vector3f[] velocities", None, &each(&|s| {
+            s.has_velocities().then(|| list(&s.velocities, |v| tuple(&v.to_array())))
+        }));
+    }
+
+    fn write_visibility(&mut self, obj: &IObject, depth: usize) {
+        let props = obj.getProperties();
+        let Some(prop) = props.getPropertyByName(VISIBILITY_PROPERTY_NAME) else { return };
+        let Some(scalar) = prop.asScalar() else { return };
+        let codes = self.codes(prop.getHeader().time_sampling_index, scalar.getNumSamples());
+        let samples: Vec<(f64, String)> = codes.into_iter().enumerate()
+            .map(|(i, code)| {
+                let token = match get_visibility(obj, i) {
+                    ObjectVisibility::Hidden => "\"invisible\"",
+                    _ => "\"inherited\"",
+                };
+                (code, token.to_string())
+            })
+            .collect();
+        self.attr(depth, "token visibility", None, &samples);
+    }
+}
+
+/// USD number literal (`nan`/`inf` for non-finite values).
+fn num<T: Copy + Display + Into<f64>>(v: T) -> String {
+    let f: f64 = v.into();
+    if f.is_nan() {
+        "nan".into()
+    } else if f.is_infinite() {
+        if f > 0.0 { "inf".into() } else { "-inf".into() }
+    } else {
+        v.to_string()
+    }
+}
+
+fn tuple<T: Copy + Display + Into<f64>>(values: &[T]) -> String {
+    let parts: Vec<String> = values.iter().map(|v| num(*v)).collect();
+    format!("({})", parts.join(", "))
+}
+
+fn list<T>(values: &[T], f: impl Fn(&T) -> String) -> String {
+    let parts: Vec<String> = values.iter().map(f).collect();
+    format!("[{}]", parts.join(", "))
+}
+
+/// `[(min), (max)]` of the positions grown by `pad`; None without points.
+fn extent(positions: &[Vec3], pad: f32) -> Option<String> {
+    let first = *positions.first()?;
+    let (min, max) = positions.iter().fold((first, first), |(lo, hi), p| (lo.min(*p), hi.max(*p)));
+    Some(format!("[{}, {}]", tuple(&(min - pad).to_array()), tuple(&(max + pad).to_array())))
+}
+
+/// Valid USD identifier for `name`, unique among `taken` (which it is added to).
+fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut base: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+        base.insert(0, '_');
+    }
+    let mut candidate = base.clone();
+    let mut n = 1;
+    while taken.contains(&candidate) {
+        candidate = format!("{}_{}", base, n);
+        n += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name() {
+        let mut taken = HashSet::new();
+        assert_eq!(unique_name("body", &mut taken), "body");
+        assert_eq!(unique_name("1st", &mut taken), "_1st");
+        assert_eq!(unique_name("my mesh", &mut taken), "my_mesh");
+        assert_eq!(unique_name("my:mesh", &mut taken), "my_mesh_1");
+        assert_eq!(unique_name("", &mut taken), "_");
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(num(0.5f32), "0.5");
+        assert_eq!(num(f32::NAN), "nan");
+        assert_eq!(num(f64::NEG_INFINITY), "-inf");
+        assert_eq!(tuple(&[1.0f32, 2.5, -3.0]), "(1, 2.5, -3)");
+        assert_eq!(extent(&[Vec3::ZERO, Vec3::new(1.0, 2.0, 3.0)], 0.5).unwrap(), "[(-0.5, -0.5, -0.5), (1.5, 2.5, 3.5)]");
+        assert_eq!(extent(&[], 0.0), None);
+    }
+}
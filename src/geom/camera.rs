@@ -3,7 +3,8 @@
 //! Provides reading of camera data from Alembic files.
 
 use crate::abc::IObject;
-use crate::util::Result;
+use crate::core::{SampleSelector, TimeSampling};
+use crate::util::{Chrono, Result};
 
 /// Camera schema identifier.
 pub const CAMERA_SCHEMA: &str = "AbcGeom_Camera_v1";
@@ -288,11 +289,89 @@ impl CameraSample {
         2.0 * (self.vertical_aperture / (2.0 * self.focal_length / 10.0)).atan()
     }
     
+    /// Horizontal field of view in degrees (C++ `getFieldOfView()`).
+    pub fn field_of_view(&self) -> f64 {
+        self.horizontal_fov().to_degrees()
+    }
+
     /// Compute aspect ratio.
     pub fn aspect_ratio(&self) -> f64 {
         self.horizontal_aperture / self.vertical_aperture
     }
-    
+
+    /// Screen window as `(top, bottom, left, right)` (C++ `getScreenWindow()`).
+    ///
+    /// The film back spans -1..1 horizontally and -1/aspect..1/aspect
+    /// vertically (aspect includes the lens squeeze), grown by the overscan,
+    /// shifted by the film offsets and transformed by the film back ops.
+    pub fn screen_window(&self) -> (f64, f64, f64, f64) {
+        let aspect = self.horizontal_aperture * self.lens_squeeze_ratio / self.vertical_aperture;
+        let offset_x = 2.0 * self.horizontal_film_offset / self.horizontal_aperture;
+        let offset_y = 2.0 * self.vertical_film_offset / self.horizontal_aperture;
+        let m = self.film_back_matrix();
+        // Row vector times matrix, as Imath's V2d * M33d
+        let xform = |x: f64, y: f64| {
+            let w = x * m[0][2] + y * m[1][2] + m[2][2];
+            let w = if w != 0.0 { w } else { 1.0 };
+            (
+                (x * m[0][0] + y * m[1][0] + m[2][0]) / w,
+                (x * m[0][1] + y * m[1][1] + m[2][1]) / w,
+            )
+        };
+        let (left, bottom) = xform(
+            -(1.0 + self.overscan_left) + offset_x,
+            -(1.0 + self.overscan_bottom) / aspect + offset_y,
+        );
+        let (right, top) = xform(
+            1.0 + self.overscan_right + offset_x,
+            (1.0 + self.overscan_top) / aspect + offset_y,
+        );
+        (top, bottom, left, right)
+    }
+    
+    /// Blend towards `other` by `t` (0 = self, 1 = other).
+    ///
+    /// All lens and film parameters are lerped; film back ops are lerped
+    /// channel-wise when both samples have the same op stack.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t as f64;
+        let l = |a: f64, b: f64| a + (b - a) * t;
+        let nearest = if t < 0.5 { self } else { other };
+        let same_ops = self.film_back_xform_ops.len() == other.film_back_xform_ops.len()
+            && self.film_back_xform_ops.iter().zip(&other.film_back_xform_ops)
+                .all(|(a, b)| a.op_type == b.op_type && a.channels.len() == b.channels.len());
+        let film_back_xform_ops = if same_ops {
+            self.film_back_xform_ops.iter().zip(&other.film_back_xform_ops)
+                .map(|(a, b)| FilmBackXformOp {
+                    op_type: a.op_type,
+                    hint: a.hint.clone(),
+                    channels: a.channels.iter().zip(&b.channels).map(|(&x, &y)| l(x, y)).collect(),
+                })
+                .collect()
+        } else {
+            nearest.film_back_xform_ops.clone()
+        };
+        Self {
+            focal_length: l(self.focal_length, other.focal_length),
+            horizontal_aperture: l(self.horizontal_aperture, other.horizontal_aperture),
+            vertical_aperture: l(self.vertical_aperture, other.vertical_aperture),
+            horizontal_film_offset: l(self.horizontal_film_offset, other.horizontal_film_offset),
+            vertical_film_offset: l(self.vertical_film_offset, other.vertical_film_offset),
+            near_clipping_plane: l(self.near_clipping_plane, other.near_clipping_plane),
+            far_clipping_plane: l(self.far_clipping_plane, other.far_clipping_plane),
+            focus_distance: l(self.focus_distance, other.focus_distance),
+            f_stop: l(self.f_stop, other.f_stop),
+            shutter_open: l(self.shutter_open, other.shutter_open),
+            shutter_close: l(self.shutter_close, other.shutter_close),
+            lens_squeeze_ratio: l(self.lens_squeeze_ratio, other.lens_squeeze_ratio),
+            overscan_left: l(self.overscan_left, other.overscan_left),
+            overscan_right: l(self.overscan_right, other.overscan_right),
+            overscan_top: l(self.overscan_top, other.overscan_top),
+            overscan_bottom: l(self.overscan_bottom, other.overscan_bottom),
+            film_back_xform_ops,
+        }
+    }
+
     /// Get number of film back transform operations.
     pub fn num_ops(&self) -> usize {
         self.film_back_xform_ops.len()
@@ -336,6 +415,68 @@ impl CameraSample {
     }
 }
 
+/// Film back op encoding in `.filmBackOps`: type character + hint.
+pub(crate) fn encode_film_back_op(op: &FilmBackXformOp) -> String {
+    let kind = match op.op_type {
+        FilmBackXformOpType::Translate => 't',
+        FilmBackXformOpType::Scale => 's',
+        FilmBackXformOpType::Matrix => 'm',
+    };
+    format!("{}{}", kind, op.hint)
+}
+
+/// Read the film back ops of the sample selected by `sel`.
+///
+/// Op types and hints come from the constant `.filmBackOps` string array,
+/// channel values from `.filmBackChannels` (a scalar double property of
+/// extent N, or a double array for very long op stacks). Ops whose
+/// channels are missing keep their identity defaults.
+fn read_film_back_ops(cam: &crate::abc::ICompoundProperty<'_>, sel: SampleSelector) -> Vec<FilmBackXformOp> {
+    let Some(ops_prop) = cam.getPropertyByName(".filmBackOps") else {
+        return Vec::new();
+    };
+    let Some(ops_array) = ops_prop.asArray() else {
+        return Vec::new();
+    };
+    let names = ops_array.getAsStringArray(0).unwrap_or_default();
+    let mut ops: Vec<FilmBackXformOp> = names.iter()
+        .filter_map(|name| {
+            let mut chars = name.chars();
+            let op = match chars.next()? {
+                't' => FilmBackXformOp::translate(0.0, 0.0),
+                's' => FilmBackXformOp::scale(1.0, 1.0),
+                'm' => FilmBackXformOp::matrix([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]),
+                _ => return None,
+            };
+            Some(op.with_hint(chars.as_str()))
+        })
+        .collect();
+
+    let channels: Vec<f64> = cam.getPropertyByName(".filmBackChannels")
+        .and_then(|prop| {
+            let data = if let Some(scalar) = prop.asScalar() {
+                let mut buf = vec![0u8; scalar.getHeader().data_type.num_bytes()];
+                scalar.getSample(scalar.getSampleIndex(sel), &mut buf).ok()?;
+                buf
+            } else {
+                let array = prop.asArray()?;
+                array.getSampleVec(array.getSampleIndex(sel)).ok()?
+            };
+            Some(data.chunks_exact(8).map(|c| f64::from_le_bytes(c.try_into().unwrap())).collect())
+        })
+        .unwrap_or_default();
+    let needed: usize = ops.iter().map(|op| op.num_channels()).sum();
+    if channels.len() == needed {
+        let mut values = channels.into_iter();
+        for op in &mut ops {
+            for c in 0..op.num_channels() {
+                op.set_channel_value(c, values.next().unwrap_or_default());
+            }
+        }
+    }
+    ops
+}
+
 /// Multiply two 3x3 matrices.
 fn mul_mat3(a: [[f64; 3]; 3], b: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
     let mut r = [[0.0; 3]; 3];
@@ -399,13 +540,25 @@ impl<'a> ICamera<'a> {
         scalar.getNumSamples()
     }
     
+    /// Read the camera at an arbitrary time, blending the two bracketing
+    /// samples (see [`CameraSample::lerp`]).
+    ///
+    /// Pass `archive.getTimeSampling(camera.getTimeSamplingIndex())`.
+    pub fn get_interpolated_sample(&self, time: Chrono, ts: &TimeSampling) -> Result<CameraSample> {
+        super::util::interpolate_samples(ts, self.getNumSamples(), time, |i| self.getSample(i), |a, b, t| a.lerp(b, t))
+    }
+
     /// Check if camera is constant.
     pub fn isConstant(&self) -> bool {
         self.getNumSamples() <= 1
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<CameraSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<CameraSample> {
+        let sel = sel.into();
         use crate::util::Error;
         
         let props = self.object.getProperties();
@@ -417,21 +570,21 @@ impl<'a> ICamera<'a> {
         let mut sample = CameraSample::new();
         
         // Read .core (combined camera parameters as doubles array)
-        // Order: focalLength, horizontalAperture, verticalAperture,
-        //        horizontalFilmOffset, verticalFilmOffset, lensSqueezeRatio,
+        // Order: focalLength, horizontalAperture, horizontalFilmOffset,
+        //        verticalAperture, verticalFilmOffset, lensSqueezeRatio,
         //        overscanLeft, overscanRight, overscanTop, overscanBottom,
         //        fStop, focusDistance, shutterOpen, shutterClose,
         //        nearClippingPlane, farClippingPlane
         if let Some(core_prop) = cam.getPropertyByName(".core") {
             if let Some(scalar) = core_prop.asScalar() {
                 let mut buf = vec![0u8; 16 * 8]; // 16 doubles
-                if scalar.getSample(index, &mut buf).is_ok() {
+                if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                     let doubles: &[f64] = bytemuck::try_cast_slice(&buf).unwrap_or(&[]);
                     if doubles.len() >= 16 {
                         sample.focal_length = doubles[0];
                         sample.horizontal_aperture = doubles[1];
-                        sample.vertical_aperture = doubles[2];
-                        sample.horizontal_film_offset = doubles[3];
+                        sample.horizontal_film_offset = doubles[2];
+                        sample.vertical_aperture = doubles[3];
                         sample.vertical_film_offset = doubles[4];
                         sample.lens_squeeze_ratio = doubles[5];
                         sample.overscan_left = doubles[6];
@@ -448,6 +601,8 @@ impl<'a> ICamera<'a> {
                 }
             }
         }
+
+        sample.film_back_xform_ops = read_film_back_ops(&cam, sel);
         
         Ok(sample)
     }
@@ -473,7 +628,7 @@ impl<'a> ICamera<'a> {
         let scalar = bnds_prop.asScalar()?;
         
         let mut buf = [0u8; 48]; // 6 x f64
-        if scalar.getSample(index, &mut buf).is_ok() {
+        if scalar.getSample(scalar.getSampleIndex(index.into()), &mut buf).is_ok() {
             let doubles: &[f64] = bytemuck::try_cast_slice(&buf).unwrap_or(&[]);
             if doubles.len() >= 6 {
                 return Some(crate::util::BBox3d::new(
@@ -594,5 +749,23 @@ mod tests {
         // Approximate check for 50mm lens FOV
         let fov = sample.horizontal_fov().to_degrees();
         assert!(fov > 35.0 && fov < 45.0);
+        assert!((sample.field_of_view() - fov).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_camera_screen_window() {
+        let mut sample = CameraSample::new();
+        let (top, bottom, left, right) = sample.screen_window();
+        assert!((left + 1.0).abs() < 1e-12 && (right - 1.0).abs() < 1e-12);
+        assert!((top - 1.0 / 1.5).abs() < 1e-12 && (bottom + 1.0 / 1.5).abs() < 1e-12);
+
+        // Overscan grows the window, film offset shifts it, a scale op scales it
+        sample.overscan_right = 0.1;
+        sample.horizontal_film_offset = 0.36; // 10% of the 3.6cm aperture
+        sample.add_op(FilmBackXformOp::scale(2.0, 2.0));
+        let (top, _, left, right) = sample.screen_window();
+        assert!((left - 2.0 * (-1.0 + 0.2)).abs() < 1e-12);
+        assert!((right - 2.0 * (1.1 + 0.2)).abs() < 1e-12);
+        assert!((top - 2.0 / 1.5).abs() < 1e-12);
     }
 }
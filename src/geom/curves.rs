@@ -1,11 +1,31 @@
 //! Curves schema implementation.
 //!
 //! Provides reading of curve data (NURBS, Bezier, linear) from Alembic files.
+//!
+//! All strands of a sample are stored concatenated. How an attribute array
+//! maps back onto strands depends on its length (RenderMan primvar classes):
+//!
+//! | Scope    | Values per strand                                   |
+//! |----------|-----------------------------------------------------|
+//! | Vertex   | `num_vertices[i]` (one per control point)           |
+//! | Varying  | segments + 1, or segments if periodic               |
+//! | Uniform  | 1                                                   |
+//! | Constant | 1 for the whole sample                              |
+//!
+//! Knots are `num_vertices[i] + order[i]` per strand. Use
+//! [`CurvesSample::strands`] rather than computing the offsets by hand.
+//!
+//! Variable-order NURBS strands (groom caches from Maya) store one order per
+//! strand in `.orders` (a uchar array, as in C++) and their knot vectors in
+//! `.knots`; [`Strand::nurbs_points`] evaluates them, periodic ones included.
+
+use std::cmp::Ordering;
+use std::ops::Range;
 
 use crate::abc::IObject;
-use crate::core::TopologyVariance;
+use crate::core::{CompoundPropertyReader, GeometryScope, TopologyVariance, SampleSelector};
 use crate::geom::util as geom_util;
-use crate::util::{Result, BBox3d};
+use crate::util::{Error, PlainOldDataType, Result, BBox3d};
 
 /// Curves schema identifier.
 pub const CURVES_SCHEMA: &str = "AbcGeom_Curve_v2";
@@ -104,6 +124,18 @@ pub enum BasisType {
 }
 
 impl BasisType {
+    /// Control points to advance per cubic segment (RenderMan `vstep`).
+    ///
+    /// `NoBasis` is treated as Bezier, the RenderMan default.
+    pub fn step(self) -> usize {
+        match self {
+            BasisType::NoBasis | BasisType::Bezier => 3,
+            BasisType::Bspline | BasisType::CatmullRom => 1,
+            BasisType::Hermite => 2,
+            BasisType::Power => 4,
+        }
+    }
+
     /// Parse from Alembic u8 value.
     pub fn from_u8(val: u8) -> Self {
         match val {
@@ -234,6 +266,311 @@ impl CurvesSample {
     pub fn compute_bounds(&self) -> (glam::Vec3, glam::Vec3) {
         geom_util::compute_bounds_vec3(&self.positions)
     }
+
+    /// Order (degree + 1) of a strand: `orders[i]` when present, else 2 for
+    /// linear and 4 for cubic curves.
+    pub fn curve_order(&self, curve_idx: usize) -> usize {
+        order_of(self.curve_type, &self.orders, self.num_curves(), curve_idx)
+    }
+
+    /// Number of spans of a strand.
+    pub fn curve_segments(&self, curve_idx: usize) -> usize {
+        let n = self.num_vertices.get(curve_idx).map_or(0, |&n| n.max(0) as usize);
+        let periodic = self.wrap == CurvePeriodicity::Periodic;
+        match self.curve_type {
+            CurveType::Linear if periodic => n,
+            CurveType::Linear => n.saturating_sub(1),
+            CurveType::Cubic if periodic => n / self.basis.step(),
+            CurveType::Cubic if n < 4 => 0,
+            CurveType::Cubic => (n - 4) / self.basis.step() + 1,
+            CurveType::VariableOrder if periodic => n,
+            CurveType::VariableOrder => (n + 1).saturating_sub(self.curve_order(curve_idx)),
+        }
+    }
+
+    /// Number of varying values of a strand (segments + 1, or segments if periodic).
+    pub fn curve_varying(&self, curve_idx: usize) -> usize {
+        let segments = self.curve_segments(curve_idx);
+        if self.wrap == CurvePeriodicity::Periodic || segments == 0 { segments } else { segments + 1 }
+    }
+
+    /// Prefix sums of `num_vertices`: strand `i` owns `offsets[i]..offsets[i + 1]`.
+    pub fn vertex_offsets(&self) -> Vec<usize> {
+        prefix_sums((0..self.num_curves()).map(|i| self.num_vertices[i].max(0) as usize))
+    }
+
+    /// Prefix sums of the per-strand varying counts.
+    pub fn varying_offsets(&self) -> Vec<usize> {
+        prefix_sums((0..self.num_curves()).map(|i| self.curve_varying(i)))
+    }
+
+    /// Prefix sums of the per-strand knot counts (`num_vertices[i] + order`).
+    pub fn knot_offsets(&self) -> Vec<usize> {
+        prefix_sums((0..self.num_curves()).map(|i| self.num_vertices[i].max(0) as usize + self.curve_order(i)))
+    }
+
+    /// Scope of a per-strand attribute array, inferred from its length.
+    ///
+    /// Vertex wins over Varying when the counts coincide (linear curves),
+    /// which maps identically. Returns `Unknown` for lengths that fit no scope.
+    pub fn attribute_scope(&self, len: usize) -> GeometryScope {
+        let vertices = self.vertex_offsets().last().copied().unwrap_or(0);
+        let varying = self.varying_offsets().last().copied().unwrap_or(0);
+        if len == vertices && len > 0 {
+            GeometryScope::Vertex
+        } else if len == varying && len > 0 {
+            GeometryScope::Varying
+        } else if len == self.num_curves() && len > 0 {
+            GeometryScope::Uniform
+        } else if len == 1 {
+            GeometryScope::Constant
+        } else {
+            GeometryScope::Unknown
+        }
+    }
+
+    /// Iterate the strands with every attribute sliced to that strand.
+    ///
+    /// Attributes are mapped by [`attribute_scope`](Self::attribute_scope);
+    /// ones whose length fits no scope, or that overrun their array, come
+    /// back empty rather than panicking (see [`validate`](Self::validate)).
+    pub fn strands(&self) -> impl ExactSizeIterator<Item = Strand<'_>> + '_ {
+        let vertex = self.vertex_offsets();
+        let varying = self.varying_offsets();
+        let knots = self.knot_offsets();
+        let scope = |len: usize| self.attribute_scope(len);
+        let (width_scope, uv_scope, normal_scope) = (scope(self.widths.len()), scope(self.uvs.len()), scope(self.normals.len()));
+        let knots_valid = knots.last() == Some(&self.knots.len());
+
+        (0..self.num_curves()).map(move |i| {
+            let vertices = vertex[i]..vertex[i + 1];
+            let var = varying[i]..varying[i + 1];
+            let pick = |scope: GeometryScope| match scope {
+                GeometryScope::Vertex => vertices.clone(),
+                GeometryScope::Varying | GeometryScope::FaceVarying => var.clone(),
+                GeometryScope::Uniform => i..i + 1,
+                GeometryScope::Constant => 0..1,
+                GeometryScope::Unknown => 0..0,
+            };
+            Strand {
+                index: i,
+                vertices: vertices.clone(),
+                order: self.curve_order(i),
+                positions: self.positions.get(vertices.clone()).unwrap_or(&[]),
+                velocities: self.velocities.as_deref().and_then(|v| v.get(vertices.clone())),
+                widths: self.widths.get(pick(width_scope)).unwrap_or(&[]),
+                uvs: self.uvs.get(pick(uv_scope)).unwrap_or(&[]),
+                normals: self.normals.get(pick(normal_scope)).unwrap_or(&[]),
+                knots: if knots_valid { &self.knots[knots[i]..knots[i + 1]] } else { &[] },
+                periodic: self.wrap == CurvePeriodicity::Periodic,
+            }
+        })
+    }
+
+    /// Check that counts, attributes and knots are consistent across strands.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(i) = self.num_vertices.iter().position(|&n| n < 0) {
+            return Err(Error::invalid(format!("curve {} has negative vertex count {}", i, self.num_vertices[i])));
+        }
+        let total = self.vertex_offsets().last().copied().unwrap_or(0);
+        if total != self.positions.len() {
+            return Err(Error::invalid(format!(
+                "nVertices sums to {} but there are {} positions", total, self.positions.len()
+            )));
+        }
+        if let Some(v) = &self.velocities {
+            if v.len() != total {
+                return Err(Error::invalid(format!("{} velocities for {} vertices", v.len(), total)));
+            }
+        }
+        for (name, len) in [("width", self.widths.len()), ("uv", self.uvs.len()), ("N", self.normals.len())] {
+            if len > 0 && self.attribute_scope(len) == GeometryScope::Unknown {
+                return Err(Error::invalid(format!(
+                    "{} has {} values; expected {} (vertex), {} (varying), {} (uniform) or 1",
+                    name, len, total, self.varying_offsets().last().copied().unwrap_or(0), self.num_curves()
+                )));
+            }
+        }
+        validate_nurbs(&self.num_vertices, self.curve_type, &self.orders, &self.knots)?;
+        for i in 0..self.num_curves() {
+            let n = self.num_vertices[i] as usize;
+            let min = match self.curve_type {
+                CurveType::Linear => 2,
+                _ if self.wrap == CurvePeriodicity::Periodic => 1,
+                _ => self.curve_order(i),
+            };
+            if n < min {
+                return Err(Error::invalid(format!("curve {} has {} vertices; needs at least {}", i, n, min)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One strand of a [`CurvesSample`], see [`CurvesSample::strands`].
+#[derive(Clone, Debug)]
+pub struct Strand<'a> {
+    /// Strand index.
+    pub index: usize,
+    /// Range of this strand's control points in the concatenated arrays.
+    pub vertices: Range<usize>,
+    /// Order (degree + 1).
+    pub order: usize,
+    /// Control point positions.
+    pub positions: &'a [glam::Vec3],
+    /// Control point velocities.
+    pub velocities: Option<&'a [glam::Vec3]>,
+    /// Widths for this strand: per vertex, per varying point, or a single value.
+    pub widths: &'a [f32],
+    /// UVs for this strand, mapped like `widths`.
+    pub uvs: &'a [glam::Vec2],
+    /// Normals for this strand, mapped like `widths`.
+    pub normals: &'a [glam::Vec3],
+    /// Knot vector (`positions.len() + order` values), empty if absent.
+    pub knots: &'a [f32],
+    /// Whether the strand closes on itself.
+    pub periodic: bool,
+}
+
+impl Strand<'_> {
+    /// Knot vector used for evaluation, paired with the control points it
+    /// applies to.
+    ///
+    /// Stored knots are used as is: Maya writes periodic strands with their
+    /// first `order - 1` control points repeated at the end, so they close
+    /// without special handling. Without knots, uniform knots are generated,
+    /// clamped at the ends, or unclamped over wrapped control points for a
+    /// periodic strand (RenderMan convention, no repeated points).
+    pub fn evaluation_knots(&self) -> (Vec<glam::Vec3>, Vec<f32>) {
+        let order = self.order.max(1);
+        if self.knots.len() == self.positions.len() + order {
+            return (self.positions.to_vec(), self.knots.to_vec());
+        }
+        let mut cvs = self.positions.to_vec();
+        if self.periodic && !self.positions.is_empty() {
+            cvs.extend((0..order - 1).map(|i| self.positions[i % self.positions.len()]));
+        }
+        let n = cvs.len();
+        let knots = if self.periodic {
+            (0..n + order).map(|i| i as f32).collect()
+        } else {
+            let last = n.saturating_sub(order) + 1;
+            (0..n + order).map(|i| i.saturating_sub(order - 1).min(last) as f32).collect()
+        };
+        (cvs, knots)
+    }
+
+    /// Points along the strand evaluated as a non-rational NURBS curve (de
+    /// Boor), `samples_per_span` per non-empty knot span plus the end point.
+    /// Strands with fewer control points than their order come back as is.
+    pub fn nurbs_points(&self, samples_per_span: usize) -> Vec<glam::Vec3> {
+        let order = self.order.max(1);
+        let (cvs, knots) = self.evaluation_knots();
+        let n = cvs.len();
+        if n < order || samples_per_span == 0 {
+            return self.positions.to_vec();
+        }
+        let p = order - 1;
+        let eval = |span: usize, t: f32| {
+            let mut d: Vec<glam::Vec3> = cvs[span - p..=span].to_vec();
+            for r in 1..=p {
+                for j in (r..=p).rev() {
+                    let i = j + span - p;
+                    let denom = knots[i + p + 1 - r] - knots[i];
+                    let alpha = if denom > 0.0 { (t - knots[i]) / denom } else { 0.0 };
+                    d[j] = d[j - 1].lerp(d[j], alpha);
+                }
+            }
+            d[p]
+        };
+
+        let mut points = Vec::new();
+        let mut last_span = None;
+        for span in p..n {
+            let (t0, t1) = (knots[span], knots[span + 1]);
+            if t1 <= t0 {
+                continue;
+            }
+            for s in 0..samples_per_span {
+                points.push(eval(span, t0 + (t1 - t0) * s as f32 / samples_per_span as f32));
+            }
+            last_span = Some(span);
+        }
+        match last_span {
+            Some(span) => points.push(eval(span, knots[span + 1])),
+            None => return self.positions.to_vec(),
+        }
+        points
+    }
+}
+
+/// Order of strand `i`: `orders[i]` when present, else 2 for linear and 4
+/// for cubic curves.
+fn order_of(curve_type: CurveType, orders: &[i32], num_curves: usize, i: usize) -> usize {
+    match orders.get(i) {
+        Some(&o) if o > 0 && (curve_type == CurveType::VariableOrder || orders.len() == num_curves) => o as usize,
+        _ => match curve_type {
+            CurveType::Linear => 2,
+            CurveType::Cubic | CurveType::VariableOrder => 4,
+        },
+    }
+}
+
+/// Check `orders` and `knots` against the strand vertex counts: one order
+/// per curve in `1..=255` (`.orders` is stored as uchar), and
+/// `num_vertices[i] + order` non-decreasing knots per curve.
+pub(crate) fn validate_nurbs(num_vertices: &[i32], curve_type: CurveType, orders: &[i32], knots: &[f32]) -> Result<()> {
+    let num_curves = num_vertices.len();
+    if !orders.is_empty() {
+        if orders.len() != num_curves {
+            return Err(Error::invalid(format!("{} orders for {} curves", orders.len(), num_curves)));
+        }
+        if let Some(i) = orders.iter().position(|o| !(1..=255).contains(o)) {
+            return Err(Error::invalid(format!("curve {} has order {}; expected 1-255", i, orders[i])));
+        }
+    }
+    if knots.is_empty() {
+        return Ok(());
+    }
+    let counts: Vec<usize> = (0..num_curves)
+        .map(|i| num_vertices[i].max(0) as usize + order_of(curve_type, orders, num_curves, i))
+        .collect();
+    let expected: usize = counts.iter().sum();
+    if knots.len() != expected {
+        return Err(Error::invalid(format!("{} knots; expected {} (vertices + order per curve)", knots.len(), expected)));
+    }
+    let mut start = 0;
+    for (i, &count) in counts.iter().enumerate() {
+        let strand = &knots[start..start + count];
+        let decreasing = |w: &[f32]| !matches!(w[0].partial_cmp(&w[1]), Some(Ordering::Less | Ordering::Equal));
+        if let Some(j) = strand.windows(2).position(decreasing) {
+            return Err(Error::invalid(format!("curve {} knots decrease at knot {}", i, j + 1)));
+        }
+        start += count;
+    }
+    Ok(())
+}
+
+/// `.orders` is a uchar array in C++ files; earlier files from this crate
+/// stored int32.
+fn read_orders(geom: &dyn CompoundPropertyReader, sel: SampleSelector) -> Option<Vec<i32>> {
+    let prop = geom.getPropertyByName(".orders")?;
+    if prop.getHeader().data_type.pod != PlainOldDataType::Uint8 {
+        return geom_util::read_i32_array(geom, ".orders", sel);
+    }
+    let array = prop.asArray()?;
+    let data = array.getSampleVec(array.getSampleIndex(sel)).ok()?;
+    Some(data.iter().map(|&o| o as i32).collect())
+}
+
+fn prefix_sums(counts: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut offsets = vec![0];
+    let mut total = 0;
+    for n in counts {
+        total += n;
+        offsets.push(total);
+    }
+    offsets
 }
 
 /// Input Curves schema reader.
@@ -348,8 +685,12 @@ impl<'a> ICurves<'a> {
         geom_util::user_property_names(self.object)
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<CurvesSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<CurvesSample> {
+        let sel = sel.into();
         use crate::util::Error;
         
         let props = self.object.getProperties();
@@ -362,11 +703,11 @@ impl<'a> ICurves<'a> {
         let mut sample = CurvesSample::new();
         
         // Read core geometry using helpers
-        if let Some(pos) = geom_util::read_vec3_array(g, "P", index) {
+        if let Some(pos) = geom_util::read_vec3_array(g, "P", sel) {
             sample.positions = pos;
         }
-        sample.velocities = geom_util::read_vec3_array(g, ".velocities", index);
-        if let Some(nv) = geom_util::read_i32_array(g, "nVertices", index) {
+        sample.velocities = geom_util::read_vec3_array(g, ".velocities", sel);
+        if let Some(nv) = geom_util::read_i32_array(g, "nVertices", sel) {
             sample.num_vertices = nv;
         }
         
@@ -383,24 +724,24 @@ impl<'a> ICurves<'a> {
         }
         
         // Read optional attributes
-        if let Some(w) = geom_util::read_f32_array(g, "width", index) {
+        if let Some(w) = geom_util::read_f32_array(g, "width", sel) {
             sample.widths = w;
         }
-        if let Some(uvs) = geom_util::read_vec2_array(g, "uv", index) {
+        if let Some(uvs) = geom_util::read_vec2_array(g, "uv", sel) {
             sample.uvs = uvs;
         }
-        if let Some(n) = geom_util::read_vec3_array(g, "N", index) {
+        if let Some(n) = geom_util::read_vec3_array(g, "N", sel) {
             sample.normals = n;
         }
         
         // NURBS data
-        if let Some(k) = geom_util::read_f32_array(g, ".knots", index) {
+        if let Some(k) = geom_util::read_f32_array(g, ".knots", sel) {
             sample.knots = k;
         }
-        if let Some(o) = geom_util::read_i32_array(g, ".orders", index) {
+        if let Some(o) = read_orders(g, sel) {
             sample.orders = o;
         }
-        sample.self_bounds = geom_util::read_self_bounds(g, index);
+        sample.self_bounds = geom_util::read_self_bounds(g, sel);
         
         Ok(sample)
     }
@@ -449,6 +790,120 @@ mod tests {
         assert_eq!(max, glam::vec3(3.0, 1.0, 0.0));
     }
     
+    #[test]
+    fn test_strands_map_attribute_scopes() {
+        // Two cubic bspline strands: 4 and 6 control points
+        let mut sample = CurvesSample::new();
+        sample.positions = (0..10).map(|i| glam::vec3(i as f32, 0.0, 0.0)).collect();
+        sample.num_vertices = vec![4, 6];
+        sample.basis = BasisType::Bspline;
+        assert_eq!(sample.vertex_offsets(), vec![0, 4, 10]);
+        // 1 and 3 segments -> 2 and 4 varying values
+        assert_eq!(sample.varying_offsets(), vec![0, 2, 6]);
+
+        sample.widths = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+        sample.uvs = vec![glam::vec2(0.0, 0.0), glam::vec2(1.0, 0.0)];
+        sample.normals = vec![glam::Vec3::Z];
+        assert_eq!(sample.attribute_scope(6), GeometryScope::Varying);
+        assert_eq!(sample.attribute_scope(2), GeometryScope::Uniform);
+        assert!(sample.validate().is_ok());
+
+        let strands: Vec<_> = sample.strands().collect();
+        assert_eq!(strands.len(), 2);
+        assert_eq!(strands[1].vertices, 4..10);
+        assert_eq!(strands[1].positions[0].x, 4.0);
+        assert_eq!(strands[0].widths, &[0.1, 0.2]);
+        assert_eq!(strands[1].widths, &[0.3, 0.4, 0.5, 0.6]);
+        assert_eq!(strands[1].uvs, &[glam::vec2(1.0, 0.0)]);
+        assert_eq!(strands[1].normals, &[glam::Vec3::Z]);
+        assert!(strands[1].knots.is_empty());
+    }
+
+    #[test]
+    fn test_strands_bezier_and_nurbs_knots() {
+        let mut sample = CurvesSample::new();
+        sample.positions = vec![glam::Vec3::ZERO; 11];
+        sample.num_vertices = vec![7, 4];
+        sample.basis = BasisType::Bezier;
+        // Bezier: (7 - 4) / 3 + 1 = 2 segments, then 1
+        assert_eq!(sample.curve_segments(0), 2);
+        assert_eq!(sample.curve_varying(1), 2);
+
+        sample.curve_type = CurveType::VariableOrder;
+        sample.orders = vec![3, 4];
+        sample.knots = (0..18).map(|k| k as f32).collect();
+        assert_eq!(sample.knot_offsets(), vec![0, 10, 18]);
+        assert!(sample.validate().is_ok());
+        let strands: Vec<_> = sample.strands().collect();
+        assert_eq!(strands[0].order, 3);
+        assert_eq!(strands[1].knots, &[10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0]);
+    }
+
+    #[test]
+    fn test_curves_validate_reports_mismatches() {
+        let mut sample = CurvesSample::new();
+        sample.curve_type = CurveType::Linear;
+        sample.positions = vec![glam::Vec3::ZERO; 5];
+        sample.num_vertices = vec![2, 2];
+        assert!(sample.validate().unwrap_err().to_string().contains("5 positions"));
+
+        sample.num_vertices = vec![3, 2];
+        sample.widths = vec![1.0; 3];
+        assert!(sample.validate().unwrap_err().to_string().contains("width has 3 values"));
+        // Bad attributes come back empty instead of panicking
+        assert!(sample.strands().all(|s| s.widths.is_empty()));
+
+        sample.widths.clear();
+        sample.num_vertices = vec![4, 1];
+        assert!(sample.validate().unwrap_err().to_string().contains("curve 1 has 1 vertices"));
+    }
+
+    #[test]
+    fn test_nurbs_points_clamped_and_periodic() {
+        let cvs = vec![glam::vec3(0.0, 0.0, 0.0), glam::vec3(1.0, 2.0, 0.0), glam::vec3(3.0, 2.0, 0.0), glam::vec3(4.0, 0.0, 0.0)];
+        let mut sample = CurvesSample::new();
+        sample.positions = cvs.clone();
+        sample.num_vertices = vec![4];
+        sample.curve_type = CurveType::VariableOrder;
+        sample.orders = vec![4];
+        sample.knots = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+        // Clamped single-span cubic is a Bezier curve
+        let points = sample.strands().next().unwrap().nurbs_points(2);
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0], cvs[0]);
+        assert!(points[1].abs_diff_eq((cvs[0] + 3.0 * cvs[1] + 3.0 * cvs[2] + cvs[3]) / 8.0, 1e-6));
+        assert_eq!(points[2], cvs[3]);
+
+        // Without knots a periodic strand wraps its control points and closes
+        sample.knots.clear();
+        sample.wrap = CurvePeriodicity::Periodic;
+        let strand = sample.strands().next().unwrap();
+        let (wrapped, knots) = strand.evaluation_knots();
+        assert_eq!((wrapped.len(), knots.len()), (7, 11));
+        let points = strand.nurbs_points(4);
+        assert_eq!(points.len(), 17);
+        assert!(points[0].abs_diff_eq(points[16], 1e-5));
+
+        // Non-periodic without knots: clamped uniform, hits both end points
+        sample.wrap = CurvePeriodicity::NonPeriodic;
+        let (_, knots) = sample.strands().next().unwrap().evaluation_knots();
+        assert_eq!(knots, vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_validate_nurbs_orders_and_knots() {
+        assert!(validate_nurbs(&[4, 3], CurveType::VariableOrder, &[4, 3], &[0.0; 14]).is_ok());
+        let err = validate_nurbs(&[4], CurveType::VariableOrder, &[4, 3], &[]).unwrap_err();
+        assert!(err.to_string().contains("2 orders for 1 curves"));
+        let err = validate_nurbs(&[4], CurveType::VariableOrder, &[300], &[]).unwrap_err();
+        assert!(err.to_string().contains("order 300"));
+        let err = validate_nurbs(&[4], CurveType::VariableOrder, &[4], &[0.0; 7]).unwrap_err();
+        assert!(err.to_string().contains("7 knots; expected 8"));
+        let knots = [0.0, 0.0, 0.0, 0.0, 1.0, 0.5, 1.0, 1.0];
+        let err = validate_nurbs(&[4], CurveType::VariableOrder, &[4], &knots).unwrap_err();
+        assert!(err.to_string().contains("curve 0 knots decrease at knot 5"));
+    }
+
     #[test]
     fn test_curve_type_parsing() {
         assert_eq!(CurveType::from_u8(0), CurveType::Cubic);
@@ -4,6 +4,7 @@
 //! or other organizational purposes.
 
 use crate::abc::IObject;
+use crate::core::SampleSelector;
 use crate::util::{Result, Error, BBox3d};
 
 /// FaceSet schema identifier.
@@ -189,8 +190,12 @@ impl<'a> IFaceSet<'a> {
         bnds_prop.getHeader().time_sampling_index
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<FaceSetSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<FaceSetSample> {
+        let sel = sel.into();
         let mut sample = FaceSetSample::new();
         
         let props = self.object.as_ref().getProperties();
@@ -202,7 +207,7 @@ impl<'a> IFaceSet<'a> {
         // Read .faces
         if let Some(faces_prop) = geom.getPropertyByName(".faces") {
             if let Some(array_reader) = faces_prop.asArray() {
-                let data = array_reader.getSampleVec(index)?;
+                let data = array_reader.getSampleVec(array_reader.getSampleIndex(sel))?;
                 sample.faces = bytemuck::try_cast_slice::<_, i32>(&data).unwrap_or(&[]).to_vec();
             }
         }
@@ -212,7 +217,7 @@ impl<'a> IFaceSet<'a> {
             if let Some(scalar) = bnds_prop.asScalar() {
                 // BBox3d is 6 f64 values: min_x, min_y, min_z, max_x, max_y, max_z
                 let mut buf = [0u8; 48];
-                if scalar.getSample(index, &mut buf).is_ok() {
+                if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                     let values: &[f64] = bytemuck::try_cast_slice(&buf).unwrap_or(&[]);
                     if values.len() >= 6 {
                         sample.self_bounds = Some(BBox3d::new(
@@ -3,9 +3,20 @@
 //! IGeomParam provides typed access to geometry attributes like UVs, normals, 
 //! colors, etc. These can be either indexed (shared values with index array)
 //! or non-indexed (one value per element).
+//!
+//! `IGeomParam<'a, T>` with a POD element type (e.g. `IV2fGeomParam`) also
+//! resolves indices and expands any scope to a per-face-vertex array:
+//!
+//! ```ignore
+//! let uv = IV2fGeomParam::new_typed(&arb, "uv")?;
+//! let per_corner: Vec<glam::Vec2> = uv.expand(0, &mesh.face_counts, &mesh.face_indices)?;
+//! ```
+
+use std::marker::PhantomData;
 
 use crate::abc::ICompoundProperty;
 use crate::core::{GeometryScope, SampleSelector, PropertyHeader};
+use crate::geom::typed::AbcPod;
 use crate::util::{DataType, Result, Error};
 
 /// Metadata key for geometry scope.
@@ -16,6 +27,8 @@ pub const ARRAY_EXTENT_KEY: &str = "arrayExtent";
 pub const POD_NAME_KEY: &str = "podName";
 /// Metadata key for POD extent.
 pub const POD_EXTENT_KEY: &str = "podExtent";
+/// Metadata key marking a Vec2f param as a UV set (C++ `SetIsUV`).
+pub const IS_UV_KEY: &str = "isUV";
 
 /// Name of values sub-property in indexed GeomParam.
 pub const VALS_PROPERTY_NAME: &str = ".vals";
@@ -50,6 +63,22 @@ impl Default for GeomParamSample {
 }
 
 impl GeomParamSample {
+    /// Build a sample from typed values, e.g. for `OPolyMeshSample::arb_geom_params`.
+    pub fn from_values<T: bytemuck::Pod>(
+        data_type: DataType,
+        values: &[T],
+        indices: Option<Vec<u32>>,
+        scope: GeometryScope,
+    ) -> Self {
+        Self {
+            values: bytemuck::cast_slice(values).to_vec(),
+            is_indexed: indices.is_some(),
+            indices,
+            scope,
+            data_type,
+        }
+    }
+
     /// Check if sample has valid data.
     pub fn is_valid(&self) -> bool {
         !self.values.is_empty()
@@ -128,6 +157,88 @@ impl GeomParamSample {
             .map(|c| glam::vec3(c[0], c[1], c[2]))
             .collect()
     }
+
+    /// Values with indices resolved, one per element of the param's scope.
+    pub fn resolved<T: bytemuck::Pod>(&self) -> Result<Vec<T>> {
+        let vals: &[T] = bytemuck::try_cast_slice(&self.values)
+            .map_err(|_| Error::invalid("GeomParam values do not match the element type"))?;
+        match &self.indices {
+            Some(indices) => indices
+                .iter()
+                .map(|&i| vals.get(i as usize).copied().ok_or_else(|| {
+                    Error::invalid(format!("GeomParam index {} out of range ({} values)", i, vals.len()))
+                }))
+                .collect(),
+            None => Ok(vals.to_vec()),
+        }
+    }
+
+    /// Expand to one value per face-vertex of a polygon mesh.
+    ///
+    /// Constant values are repeated, uniform values repeated per face corner,
+    /// vertex/varying values looked up through `face_indices`. `Unknown`
+    /// scope is inferred from the element count.
+    pub fn expand_face_varying<T: bytemuck::Pod>(&self, face_counts: &[i32], face_indices: &[i32]) -> Result<Vec<T>> {
+        let vals = self.resolved::<T>()?;
+        let scope = match self.scope {
+            GeometryScope::Unknown => infer_scope(vals.len(), face_counts, face_indices),
+            scope => scope,
+        };
+        let mismatch = |expected: usize| {
+            Error::invalid(format!(
+                "GeomParam has {} values, {} scope needs {}",
+                vals.len(), scope.as_str(), expected
+            ))
+        };
+        match scope {
+            GeometryScope::Constant => {
+                let v = *vals.first().ok_or_else(|| mismatch(1))?;
+                Ok(vec![v; face_indices.len()])
+            }
+            GeometryScope::Uniform => {
+                if vals.len() != face_counts.len() {
+                    return Err(mismatch(face_counts.len()));
+                }
+                let mut out = Vec::with_capacity(face_indices.len());
+                for (&v, &count) in vals.iter().zip(face_counts) {
+                    out.extend(std::iter::repeat_n(v, count.max(0) as usize));
+                }
+                Ok(out)
+            }
+            GeometryScope::Vertex | GeometryScope::Varying => face_indices
+                .iter()
+                .map(|&i| vals.get(i as usize).copied().ok_or_else(|| {
+                    let needed = face_indices.iter().copied().max().map_or(0, |m| m as usize + 1);
+                    mismatch(needed)
+                }))
+                .collect(),
+            GeometryScope::FaceVarying => {
+                if vals.len() != face_indices.len() {
+                    return Err(mismatch(face_indices.len()));
+                }
+                Ok(vals)
+            }
+            GeometryScope::Unknown => Err(Error::invalid(format!(
+                "cannot infer GeomParam scope from {} values", vals.len()
+            ))),
+        }
+    }
+}
+
+/// Guess the scope of an untagged param from its element count.
+fn infer_scope(len: usize, face_counts: &[i32], face_indices: &[i32]) -> GeometryScope {
+    let num_points = face_indices.iter().copied().max().map_or(0, |m| m.max(-1) + 1) as usize;
+    if len == face_indices.len() {
+        GeometryScope::FaceVarying
+    } else if len == num_points {
+        GeometryScope::Vertex
+    } else if len == face_counts.len() {
+        GeometryScope::Uniform
+    } else if len == 1 {
+        GeometryScope::Constant
+    } else {
+        GeometryScope::Unknown
+    }
 }
 
 /// Input geometry parameter reader.
@@ -135,7 +246,10 @@ impl GeomParamSample {
 /// Handles both indexed and non-indexed geometry parameters.
 /// Indexed params have a compound property with `.vals` and `.indices`.
 /// Non-indexed params are just an array property.
-pub struct IGeomParam<'a> {
+///
+/// `T` is the element type (C++ `ITypedGeomParam<TRAITS>`); the default `()`
+/// is an untyped reader that returns raw [`GeomParamSample`]s.
+pub struct IGeomParam<'a, T = ()> {
     /// Parent compound property.
     parent: &'a ICompoundProperty<'a>,
     /// Property name.
@@ -146,6 +260,7 @@ pub struct IGeomParam<'a> {
     scope: GeometryScope,
     /// Data type.
     data_type: DataType,
+    _marker: PhantomData<T>,
 }
 
 impl<'a> IGeomParam<'a> {
@@ -183,6 +298,7 @@ impl<'a> IGeomParam<'a> {
             is_indexed,
             scope,
             data_type,
+            _marker: PhantomData,
         })
     }
     
@@ -194,7 +310,27 @@ impl<'a> IGeomParam<'a> {
             GeometryScope::Unknown
         }
     }
-    
+
+    /// View this param with element type `U`, checking the stored data type.
+    pub fn into_typed<U: AbcPod>(self) -> Result<IGeomParam<'a, U>> {
+        if self.data_type != U::DATA_TYPE {
+            return Err(Error::TypeMismatch {
+                expected: format!("{:?}", U::DATA_TYPE),
+                actual: format!("{:?}", self.data_type),
+            });
+        }
+        Ok(IGeomParam {
+            parent: self.parent,
+            name: self.name,
+            is_indexed: self.is_indexed,
+            scope: self.scope,
+            data_type: self.data_type,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<'a, T> IGeomParam<'a, T> {
     /// Get the parameter name.
     pub fn getName(&self) -> &str {
         &self.name
@@ -253,13 +389,12 @@ impl<'a> IGeomParam<'a> {
         self.getNumSamples() <= 1
     }
     
-    /// Read a sample at the given index.
+    /// Read a sample by index or time.
+    ///
+    /// Time-based selectors resolve against the time sampling of the values.
     pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<GeomParamSample> {
         let sel = sel.into();
-        let index = match sel {
-            SampleSelector::Index(i) => i,
-            _ => 0,
-        };
+        let num_samples = self.getNumSamples();
         
         let prop = self.parent.getPropertyByName(&self.name)
             .ok_or_else(|| Error::invalid(format!("Property {} not found", self.name)))?;
@@ -277,7 +412,9 @@ impl<'a> IGeomParam<'a> {
                 .ok_or_else(|| Error::invalid("Expected compound for indexed param"))?;
             
             // Read values
+            let mut index = sel.resolve(None, num_samples);
             if let Some(vals_prop) = compound.getPropertyByName(VALS_PROPERTY_NAME) {
+                index = sel.resolve(vals_prop.getTimeSampling(), num_samples);
                 if let Some(array) = vals_prop.asArray() {
                     sample.values = array.getSampleVec(index)?;
                 }
@@ -300,6 +437,7 @@ impl<'a> IGeomParam<'a> {
             }
         } else {
             // Non-indexed - just read the array
+            let index = sel.resolve(prop.getTimeSampling(), num_samples);
             if let Some(array) = prop.asArray() {
                 sample.values = array.getSampleVec(index)?;
             }
@@ -416,37 +554,57 @@ impl<'a> IGeomParam<'a> {
     }
 }
 
+impl<'a, T: AbcPod> IGeomParam<'a, T> {
+    /// Open param `name` of `parent` as element type `T`.
+    /// Returns None if the parameter doesn't exist, an error if its type differs.
+    pub fn new_typed(parent: &'a ICompoundProperty<'a>, name: &str) -> Result<Option<Self>> {
+        IGeomParam::new(parent, name).map(IGeomParam::into_typed).transpose()
+    }
+
+    /// Read values with indices resolved, one per element of [`scope`](Self::scope).
+    pub fn get_values(&self, sel: impl Into<SampleSelector>) -> Result<Vec<T>> {
+        self.getSample(sel)?.resolved()
+    }
+
+    /// Read values expanded to one per face-vertex of a polygon mesh
+    /// (see [`GeomParamSample::expand_face_varying`]).
+    pub fn expand(&self, sel: impl Into<SampleSelector>, face_counts: &[i32], face_indices: &[i32]) -> Result<Vec<T>> {
+        self.getSample(sel)?.expand_face_varying(face_counts, face_indices)
+    }
+}
+
 // Type aliases for common geometry parameter types
 
 /// Vec2f geometry parameter (UVs).
-pub type IV2fGeomParam<'a> = IGeomParam<'a>;
+pub type IV2fGeomParam<'a> = IGeomParam<'a, glam::Vec2>;
 
 /// Vec3f geometry parameter (normals, colors).
-pub type IV3fGeomParam<'a> = IGeomParam<'a>;
+pub type IV3fGeomParam<'a> = IGeomParam<'a, glam::Vec3>;
 
 /// Normal3f geometry parameter.
-pub type IN3fGeomParam<'a> = IGeomParam<'a>;
+pub type IN3fGeomParam<'a> = IGeomParam<'a, glam::Vec3>;
 
 /// Color3f geometry parameter.
-pub type IC3fGeomParam<'a> = IGeomParam<'a>;
+pub type IC3fGeomParam<'a> = IGeomParam<'a, glam::Vec3>;
 
 /// Color4f geometry parameter.
-pub type IC4fGeomParam<'a> = IGeomParam<'a>;
+pub type IC4fGeomParam<'a> = IGeomParam<'a, glam::Vec4>;
 
 /// Int32 geometry parameter.
-pub type IInt32GeomParam<'a> = IGeomParam<'a>;
+pub type IInt32GeomParam<'a> = IGeomParam<'a, i32>;
 
 /// UInt32 geometry parameter.
-pub type IUInt32GeomParam<'a> = IGeomParam<'a>;
+pub type IUInt32GeomParam<'a> = IGeomParam<'a, u32>;
 
 /// Float geometry parameter.
-pub type IFloatGeomParam<'a> = IGeomParam<'a>;
+pub type IFloatGeomParam<'a> = IGeomParam<'a, f32>;
 
 // ============================================================================
 // Output Geometry Parameter
 // ============================================================================
 
 use crate::core::MetaData;
+use crate::ogawa::writer::OProperty;
 
 /// Output geometry parameter sample.
 #[derive(Clone, Debug)]
@@ -505,6 +663,8 @@ pub struct OGeomParam {
     indices_samples: Vec<Vec<u32>>,
     /// Time sampling index.
     time_sampling_index: u32,
+    /// Elements per point (arrayExtent), e.g. joint influences per vertex.
+    array_extent: Option<u32>,
 }
 
 impl OGeomParam {
@@ -518,6 +678,7 @@ impl OGeomParam {
             values_samples: Vec::new(),
             indices_samples: Vec::new(),
             time_sampling_index: 0,
+            array_extent: None,
         }
     }
     
@@ -552,6 +713,12 @@ impl OGeomParam {
         self
     }
     
+    /// Set the number of values per point (written as `arrayExtent`).
+    pub fn with_array_extent(mut self, extent: u32) -> Self {
+        self.array_extent = Some(extent);
+        self
+    }
+    
     /// Add a sample with typed data.
     pub fn add_sample<T: bytemuck::Pod>(&mut self, sample: &OGeomParamSample<T>) {
         self.values_samples.push(bytemuck::try_cast_slice::<_, u8>(&sample.values).unwrap_or(&[]).to_vec());
@@ -601,7 +768,9 @@ impl OGeomParam {
     pub fn build_meta_data(&self) -> MetaData {
         let mut meta = MetaData::new();
         meta.set(GEOM_SCOPE_KEY, self.scope.as_str());
-        if self.data_type.extent > 1 {
+        if let Some(extent) = self.array_extent {
+            meta.set(ARRAY_EXTENT_KEY, extent.to_string());
+        } else if self.data_type.extent > 1 {
             meta.set(ARRAY_EXTENT_KEY, self.data_type.extent.to_string());
         }
         meta.set(POD_NAME_KEY, self.data_type.pod.name());
@@ -623,6 +792,39 @@ impl OGeomParam {
     pub fn getTimeSamplingIndex(&self) -> u32 {
         self.time_sampling_index
     }
+    
+    /// Build the property tree for this parameter: an array property, or a
+    /// compound with `.vals` and `.indices` when indexed.
+    pub fn to_property(&self) -> OProperty {
+        let mut meta = self.build_meta_data();
+        meta.set("isGeomParam", "true");
+        let elem_bytes = self.data_type.num_bytes().max(1);
+        
+        let mut vals = OProperty::array(
+            if self.is_indexed { VALS_PROPERTY_NAME } else { &self.name },
+            self.data_type,
+        );
+        vals.time_sampling_index = self.time_sampling_index;
+        for values in &self.values_samples {
+            vals.add_array_sample(values, &[values.len() / elem_bytes]);
+        }
+        
+        if !self.is_indexed {
+            vals.meta_data = meta;
+            return vals;
+        }
+        
+        let mut indices = OProperty::array(INDICES_PROPERTY_NAME, DataType::UINT32);
+        indices.time_sampling_index = self.time_sampling_index;
+        for idx in &self.indices_samples {
+            indices.add_array_pod(idx);
+        }
+        let mut compound = OProperty::compound(&self.name);
+        compound.meta_data = meta;
+        compound.add_child(vals);
+        compound.add_child(indices);
+        compound
+    }
 }
 
 // Type aliases for output geometry parameters
@@ -697,4 +899,44 @@ mod tests {
         assert_eq!(vecs[1], glam::vec2(0.0, 0.0)); // index 0
         assert_eq!(vecs[2], glam::vec2(1.0, 1.0)); // index 1
     }
+
+    #[test]
+    fn test_geom_param_expand_face_varying() {
+        // A quad and a triangle sharing the edge 1-2.
+        let counts = [4, 3];
+        let indices = [0, 1, 2, 3, 1, 4, 2];
+        let sample = |values: &[f32], idx: Option<Vec<u32>>, scope| {
+            GeomParamSample::from_values(DataType::FLOAT32, values, idx, scope)
+        };
+
+        let con = sample(&[7.0], None, GeometryScope::Constant);
+        assert_eq!(con.expand_face_varying::<f32>(&counts, &indices).unwrap(), vec![7.0; 7]);
+
+        let uni = sample(&[1.0, 2.0], None, GeometryScope::Uniform);
+        assert_eq!(
+            uni.expand_face_varying::<f32>(&counts, &indices).unwrap(),
+            vec![1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0]
+        );
+
+        let vtx = sample(&[0.0, 10.0, 20.0, 30.0, 40.0], None, GeometryScope::Vertex);
+        assert_eq!(
+            vtx.expand_face_varying::<f32>(&counts, &indices).unwrap(),
+            vec![0.0, 10.0, 20.0, 30.0, 10.0, 40.0, 20.0]
+        );
+
+        // Indexed face-varying values resolve through .indices
+        let fvr = sample(&[0.5, 1.5], Some(vec![0, 1, 0, 1, 1, 1, 0]), GeometryScope::FaceVarying);
+        assert_eq!(
+            fvr.expand_face_varying::<f32>(&counts, &indices).unwrap(),
+            vec![0.5, 1.5, 0.5, 1.5, 1.5, 1.5, 0.5]
+        );
+
+        // Untagged scope is inferred from the element count
+        let unk = sample(&[1.0, 2.0], None, GeometryScope::Unknown);
+        assert_eq!(unk.expand_face_varying::<f32>(&counts, &indices).unwrap()[4], 2.0);
+
+        // Wrong counts and bad indices are errors, not zero-filled
+        assert!(sample(&[1.0], None, GeometryScope::Uniform).expand_face_varying::<f32>(&counts, &indices).is_err());
+        assert!(sample(&[1.0], Some(vec![3]), GeometryScope::Constant).resolved::<f32>().is_err());
+    }
 }
@@ -3,25 +3,89 @@
 //! Provides reading of light data from Alembic files.
 //! Lights in Alembic are container schemas that can contain
 //! camera-like properties for light parameters.
+//!
+//! Alembic doesn't define light parameters itself; DCCs export them as
+//! `.arbGeomParams` or `.userProperties` of the light schema. The common
+//! ones (type, intensity, color, cone angle) are read into [`LightSample`]
+//! whatever the exporter's spelling (`intensity`, `light_intensity`,
+//! `lightColor`, `cone_angle`, ...).
 
 use crate::abc::IObject;
+use crate::core::SampleSelector;
+use crate::geom::util as geom_util;
 use crate::util::{Result, BBox3d};
+use crate::util::DataType;
 use super::camera::CameraSample;
 
 /// Light schema identifier.
 pub const LIGHT_SCHEMA: &str = "AbcGeom_Light_v1";
 
+/// Kind of light, from the `type` light parameter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LightType {
+    /// No type parameter, or one that isn't recognized.
+    #[default]
+    Unknown,
+    /// Omni light at the object's origin.
+    Point,
+    /// Directional light shining down the object's -Z axis.
+    Distant,
+    /// Cone light shining down the object's -Z axis.
+    Spot,
+    /// Emitting shape (rectangle, disk, ...).
+    Area,
+    /// Environment light surrounding the scene.
+    Dome,
+}
+
+impl LightType {
+    /// Parse a type name, case-insensitively; accepts the common DCC
+    /// spellings (`omni`, `directional`, `sun`, `rect`, `skydome`, ...).
+    pub fn from_name(name: &str) -> Self {
+        let name = name.trim().to_ascii_lowercase().replace(['_', ' '], "");
+        let name = name.strip_suffix("light").filter(|n| !n.is_empty()).unwrap_or(&name);
+        match name {
+            "point" | "omni" | "sphere" => Self::Point,
+            "distant" | "directional" | "sun" => Self::Distant,
+            "spot" => Self::Spot,
+            "area" | "rect" | "quad" | "disk" | "cylinder" | "mesh" => Self::Area,
+            "dome" | "env" | "environment" | "skydome" => Self::Dome,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The name written by [`OLight`](crate::ogawa::OLight).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Point => "point",
+            Self::Distant => "distant",
+            Self::Spot => "spot",
+            Self::Area => "area",
+            Self::Dome => "dome",
+        }
+    }
+}
+
 /// Light sample data.
 /// 
 /// Lights in Alembic use camera-like parameters for their properties.
-/// The actual light type and parameters are determined by the application
-/// reading the file - Alembic itself doesn't define light types.
+/// The light parameters below are read from `.arbGeomParams` and
+/// `.userProperties`; fields the file doesn't store are left unset.
 #[derive(Clone, Debug, Default)]
 pub struct LightSample {
     /// Camera parameters (shared with ICamera).
     pub camera: CameraSample,
     /// Child bounds (optional).
     pub child_bounds: Option<BBox3d>,
+    /// Light type.
+    pub light_type: LightType,
+    /// Intensity multiplier.
+    pub intensity: Option<f32>,
+    /// Linear RGB color.
+    pub color: Option<glam::Vec3>,
+    /// Full cone angle of a spot light, in degrees.
+    pub cone_angle: Option<f32>,
 }
 
 impl LightSample {
@@ -34,6 +98,51 @@ impl LightSample {
     pub fn is_valid(&self) -> bool {
         true // Light samples are always valid even if empty
     }
+
+    /// Check if any typed light parameter is set.
+    pub fn has_light_params(&self) -> bool {
+        self.light_type != LightType::Unknown
+            || self.intensity.is_some()
+            || self.color.is_some()
+            || self.cone_angle.is_some()
+    }
+
+    /// Set one light parameter from a stored value; `key` is a
+    /// [`param_key`]. Returns false if the key or value isn't usable.
+    fn set_param(&mut self, key: &str, data_type: DataType, data: &[u8]) -> bool {
+        if key == "type" {
+            self.light_type = if data_type.pod.is_string() {
+                let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                LightType::from_name(&String::from_utf8_lossy(&data[..end]))
+            } else {
+                LightType::Unknown
+            };
+            return self.light_type != LightType::Unknown;
+        }
+        let Some(values) = crate::compare::pod_values(data_type.pod, data) else { return false };
+        match (key, values.as_slice()) {
+            ("intensity", [v, ..]) => self.intensity = Some(*v as f32),
+            ("color", [r, g, b, ..]) => self.color = Some(glam::vec3(*r as f32, *g as f32, *b as f32)),
+            ("coneangle", [v, ..]) => self.cone_angle = Some(*v as f32),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Lookup key of a light parameter name: lowercase, without underscores
+/// and a leading `light`, so `light_color`, `lightColor` and `color` all
+/// name the color. Returns None for names that aren't light parameters.
+fn param_key(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase().replace('_', "");
+    let name = name.strip_prefix("light").filter(|n| !n.is_empty()).unwrap_or(&name);
+    match name {
+        "type" | "shape" => Some("type"),
+        "intensity" => Some("intensity"),
+        "color" | "colour" => Some("color"),
+        "coneangle" | "spotangle" => Some("coneangle"),
+        _ => None,
+    }
 }
 
 /// Input Light schema reader.
@@ -139,8 +248,12 @@ impl<'a> ILight<'a> {
         self.object.getProperties().getPropertyNames()
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<LightSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<LightSample> {
+        let sel = sel.into();
         let mut sample = LightSample::new();
         
         let props = self.object.getProperties();
@@ -151,15 +264,17 @@ impl<'a> ILight<'a> {
                 // Read camera parameters from embedded camera schema
                 if let Some(cam_prop) = geom.getPropertyByName(".camera") {
                     if let Some(cam) = cam_prop.asCompound() {
-                        sample.camera = Self::read_camera_core(&cam, index);
+                        sample.camera = Self::read_camera_core(&cam, sel);
                     }
                 }
                 
+                self.read_light_params(sel, &mut sample);
+
                 // Read .childBnds if present
                 if let Some(bnds_prop) = geom.getPropertyByName(".childBnds") {
                     if let Some(scalar) = bnds_prop.asScalar() {
                         let mut buf = [0u8; 48];
-                        if scalar.getSample(index, &mut buf).is_ok() {
+                        if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                             let values: &[f64] = bytemuck::try_cast_slice(&buf).unwrap_or(&[]);
                             if values.len() >= 6 {
                                 sample.child_bounds = Some(BBox3d::new(
@@ -176,15 +291,39 @@ impl<'a> ILight<'a> {
         Ok(sample)
     }
     
+    /// Read the typed light parameters; `.arbGeomParams` take precedence
+    /// over `.userProperties` of the same name.
+    fn read_light_params(&self, sel: SampleSelector, sample: &mut LightSample) {
+        let mut found: Vec<&str> = Vec::new();
+        for name in geom_util::arb_geom_param_names(self.object) {
+            let Some(key) = param_key(&name).filter(|k| !found.contains(k)) else { continue };
+            let Some(param) = geom_util::read_arb_geom_param(self.object, &name, sel) else { continue };
+            // Constant-scope params hold a single element (maybe indexed).
+            let size = param.data_type.num_bytes();
+            let element = param.indices.as_ref().and_then(|i| i.first()).copied().unwrap_or(0) as usize;
+            let Some(data) = param.values.get(element * size..(element + 1) * size) else { continue };
+            if sample.set_param(key, param.data_type, data) {
+                found.push(key);
+            }
+        }
+        for name in geom_util::user_property_names(self.object) {
+            let Some(key) = param_key(&name).filter(|k| !found.contains(k)) else { continue };
+            let Some((data_type, data)) = geom_util::read_user_property(self.object, &name, sel) else { continue };
+            if sample.set_param(key, data_type, &data) {
+                found.push(key);
+            }
+        }
+    }
+
     /// Read camera parameters from .core scalar property.
-    fn read_camera_core(cam: &crate::abc::ICompoundProperty<'_>, index: usize) -> CameraSample {
+    fn read_camera_core(cam: &crate::abc::ICompoundProperty<'_>, sel: SampleSelector) -> CameraSample {
         let mut sample = CameraSample::default();
         
         // Read .core (combined camera parameters as 16 doubles)
         if let Some(core_prop) = cam.getPropertyByName(".core") {
             if let Some(scalar) = core_prop.asScalar() {
                 let mut buf = vec![0u8; 16 * 8]; // 16 doubles
-                if scalar.getSample(index, &mut buf).is_ok() {
+                if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                     let doubles: &[f64] = bytemuck::try_cast_slice(&buf).unwrap_or(&[]);
                     if doubles.len() >= 16 {
                         sample.focal_length = doubles[0];
@@ -254,5 +393,19 @@ mod tests {
         let sample = LightSample::new();
         assert!(sample.is_valid());
         assert!(sample.child_bounds.is_none());
+        assert!(!sample.has_light_params());
+    }
+
+    #[test]
+    fn test_light_param_names() {
+        assert_eq!(param_key("light_color"), Some("color"));
+        assert_eq!(param_key("lightColor"), Some("color"));
+        assert_eq!(param_key("cone_angle"), Some("coneangle"));
+        assert_eq!(param_key("lightType"), Some("type"));
+        assert_eq!(param_key("exposure"), None);
+        assert_eq!(LightType::from_name("directionalLight"), LightType::Distant);
+        assert_eq!(LightType::from_name("Sky_Dome"), LightType::Dome);
+        assert_eq!(LightType::from_name("spot"), LightType::Spot);
+        assert_eq!(LightType::from_name("laser"), LightType::Unknown);
     }
 }
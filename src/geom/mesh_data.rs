@@ -0,0 +1,385 @@
+//! One-call loader for render-ready meshes.
+//!
+//! [`load_meshes`] walks an archive at a given time and returns every visible
+//! PolyMesh (and SubD control cage) with positions, normals and UVs resolved,
+//! optionally triangulated and transformed to world space:
+//!
+//! ```ignore
+//! for (path, mesh, world) in alembic::load_meshes("shot.abc", 1001.0 / 24.0)? {
+//!     println!("{}: {} triangles", path, mesh.num_faces());
+//! }
+//! ```
+
+use std::path::Path;
+
+use glam::{Mat3, Mat4, Vec2, Vec3};
+
+use super::{GeomParamSample, IPolyMesh, ISubD, IXform, ObjectVisibility, VISIBILITY_PROPERTY_NAME};
+use crate::abc::{IArchive, IObject};
+use crate::core::TimeSampling;
+use crate::util::{Chrono, PlainOldDataType, Result};
+
+/// Mesh data resolved for rendering.
+///
+/// `normals` and `uvs` are face-varying: one value per entry of
+/// `face_indices`, in the same order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshData {
+    /// Vertex positions (world space unless `LoadOptions::world_space` is off).
+    pub positions: Vec<Vec3>,
+    /// Vertices per face (all 3 when triangulated).
+    pub face_counts: Vec<i32>,
+    /// Position indices, `face_counts` consecutive entries per face.
+    pub face_indices: Vec<i32>,
+    /// Per face-vertex unit normals (from the file, or smooth normals computed from positions;
+    /// empty if the file has none and `LoadOptions::compute_normals` is off).
+    pub normals: Vec<Vec3>,
+    /// Per face-vertex UVs, if the mesh has them.
+    pub uvs: Option<Vec<Vec2>>,
+}
+
+impl MeshData {
+    pub fn num_vertices(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn num_faces(&self) -> usize {
+        self.face_counts.len()
+    }
+
+    /// True if every face is a triangle.
+    pub fn is_triangulated(&self) -> bool {
+        self.face_counts.iter().all(|&c| c == 3)
+    }
+
+    /// Fan-triangulate in place. Degenerate faces (< 3 vertices) are dropped.
+    pub fn triangulate(&mut self) {
+        if self.is_triangulated() {
+            return;
+        }
+        let tris: usize = self.face_counts.iter().map(|&c| (c.max(2) - 2) as usize).sum();
+        let mut indices = Vec::with_capacity(tris * 3);
+        let mut normals = Vec::with_capacity(tris * 3);
+        let mut uvs = self.uvs.as_ref().map(|_| Vec::with_capacity(tris * 3));
+
+        let mut offset = 0usize;
+        for &count in &self.face_counts {
+            let count = count.max(0) as usize;
+            for i in 1..count.saturating_sub(1) {
+                for corner in [offset, offset + i, offset + i + 1] {
+                    indices.push(self.face_indices[corner]);
+                    if let Some(&n) = self.normals.get(corner) {
+                        normals.push(n);
+                    }
+                    if let (Some(dst), Some(src)) = (uvs.as_mut(), self.uvs.as_ref()) {
+                        dst.push(src[corner]);
+                    }
+                }
+            }
+            offset += count;
+        }
+
+        self.face_counts = vec![3; tris];
+        self.face_indices = indices;
+        self.normals = normals;
+        self.uvs = uvs;
+    }
+
+    /// Apply a transform to positions and normals.
+    pub fn transform(&mut self, m: Mat4) {
+        let normal_matrix = Mat3::from_mat4(m).inverse().transpose();
+        for p in &mut self.positions {
+            *p = m.transform_point3(*p);
+        }
+        for n in &mut self.normals {
+            *n = (normal_matrix * *n).normalize_or_zero();
+        }
+    }
+}
+
+/// Options for [`load_meshes_with`].
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    /// Fan-triangulate all faces (default: true).
+    pub triangulate: bool,
+    /// Transform positions and normals to world space (default: true).
+    pub world_space: bool,
+    /// Include SubD objects as their control cage (default: true).
+    pub include_subd: bool,
+    /// Skip objects hidden by their own or an ancestor's visibility (default: true).
+    pub skip_hidden: bool,
+    /// Compute smooth normals for meshes without file normals (default: true).
+    pub compute_normals: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self { triangulate: true, world_space: true, include_subd: true, skip_hidden: true, compute_normals: true }
+    }
+}
+
+/// Load all visible meshes at `time` (seconds), triangulated and in world space.
+///
+/// Returns `(full_path, mesh, world_matrix)` per mesh in hierarchy order.
+/// Samples are picked by nearest time, without interpolation.
+pub fn load_meshes(path: impl AsRef<Path>, time: Chrono) -> Result<Vec<(String, MeshData, Mat4)>> {
+    load_meshes_with(path, time, &LoadOptions::default())
+}
+
+/// [`load_meshes`] with explicit options.
+pub fn load_meshes_with(
+    path: impl AsRef<Path>,
+    time: Chrono,
+    options: &LoadOptions,
+) -> Result<Vec<(String, MeshData, Mat4)>> {
+    let archive = IArchive::open(path)?;
+    Ok(collect_meshes(&archive, time, options))
+}
+
+/// Collect meshes from an already opened archive.
+pub fn collect_meshes(archive: &IArchive, time: Chrono, options: &LoadOptions) -> Vec<(String, MeshData, Mat4)> {
+    let mut out = Vec::new();
+    let ctx = Ctx { archive, time, options };
+    for child in archive.getTop().getChildren() {
+        ctx.visit(&child, Mat4::IDENTITY, &mut out);
+    }
+    out
+}
+
+struct Ctx<'a> {
+    archive: &'a IArchive,
+    time: Chrono,
+    options: &'a LoadOptions,
+}
+
+impl Ctx<'_> {
+    /// Sample index nearest to the requested time for a property time sampling.
+    fn index(&self, ts_index: u32, num_samples: usize) -> usize {
+        if num_samples <= 1 {
+            return 0;
+        }
+        let identity = TimeSampling::identity();
+        let ts = self.archive.getTimeSampling(ts_index as usize).unwrap_or(&identity);
+        ts.near_index(self.time, num_samples).0
+    }
+
+    fn is_hidden(&self, obj: &IObject) -> bool {
+        let props = obj.getProperties();
+        let Some(prop) = props.getPropertyByName(VISIBILITY_PROPERTY_NAME) else { return false };
+        let Some(scalar) = prop.asScalar() else { return false };
+        let index = self.index(prop.getHeader().time_sampling_index, scalar.getNumSamples());
+        super::get_visibility(obj, index) == ObjectVisibility::Hidden
+    }
+
+    fn visit(&self, obj: &IObject, parent_world: Mat4, out: &mut Vec<(String, MeshData, Mat4)>) {
+        if self.options.skip_hidden && self.is_hidden(obj) {
+            return;
+        }
+
+        let mut world = parent_world;
+        if let Some(xform) = IXform::new(obj) {
+            let index = self.index(xform.getTimeSamplingIndex(), xform.getNumSamples());
+            if let Ok(sample) = xform.getSample(index) {
+                let local = sample.matrix();
+                world = if sample.inherits { parent_world * local } else { local };
+            }
+        } else if let Some(mesh) = IPolyMesh::new(obj) {
+            let index = self.index(mesh.getTimeSamplingIndex(), mesh.getNumSamples());
+            if let Ok(sample) = mesh.getSample(index) {
+                let data = build_mesh(
+                    sample.positions,
+                    sample.face_counts,
+                    sample.face_indices,
+                    mesh.get_normals(index),
+                    mesh.get_uvs(index).or_else(|| arb_uvs(mesh.arb_geom_param("uv", index))),
+                    self.options.compute_normals,
+                );
+                self.push(obj, data, world, out);
+            }
+        } else if let Some(subd) = ISubD::new(obj).filter(|_| self.options.include_subd) {
+            let index = self.index(subd.getTimeSamplingIndex(), subd.getNumSamples());
+            if let Ok(sample) = subd.getSample(index) {
+                let data = build_mesh(
+                    sample.positions,
+                    sample.face_counts,
+                    sample.face_indices,
+                    subd.get_normals(index),
+                    subd.get_uvs(index).or_else(|| arb_uvs(subd.arb_geom_param("uv", index))),
+                    self.options.compute_normals,
+                );
+                self.push(obj, data, world, out);
+            }
+        }
+
+        for child in obj.getChildren() {
+            self.visit(&child, world, out);
+        }
+    }
+
+    fn push(&self, obj: &IObject, mut data: MeshData, world: Mat4, out: &mut Vec<(String, MeshData, Mat4)>) {
+        if data.face_indices.is_empty() {
+            return;
+        }
+        if self.options.triangulate {
+            data.triangulate();
+        }
+        if self.options.world_space {
+            data.transform(world);
+        }
+        out.push((obj.getFullName().to_string(), data, world));
+    }
+}
+
+/// UVs from an arb `uv` param (where `OPolyMesh` writes them) if it holds float2 values.
+pub(crate) fn arb_uvs(param: Option<GeomParamSample>) -> Option<Vec<Vec2>> {
+    param
+        .filter(|p| p.data_type.pod == PlainOldDataType::Float32 && p.data_type.extent == 2)
+        .map(|p| p.expand_vec2())
+}
+
+/// Validate topology and resolve normals/UVs to face-varying.
+/// Without usable file normals, smooth normals are computed if `compute_normals`.
+pub(crate) fn build_mesh(
+    positions: Vec<Vec3>,
+    mut face_counts: Vec<i32>,
+    mut face_indices: Vec<i32>,
+    normals: Option<Vec<Vec3>>,
+    uvs: Option<Vec<Vec2>>,
+    compute_normals: bool,
+) -> MeshData {
+    let num_points = positions.len();
+    let valid = face_counts.iter().all(|&c| c >= 0)
+        && face_counts.iter().map(|&c| c as usize).sum::<usize>() == face_indices.len()
+        && face_indices.iter().all(|&i| i >= 0 && (i as usize) < num_points);
+    if !valid {
+        face_counts.clear();
+        face_indices.clear();
+    }
+
+    let corners = face_indices.len();
+    let per_corner = |values: Vec<_>| -> Option<Vec<_>> {
+        if values.len() == corners {
+            Some(values)
+        } else if values.len() == num_points {
+            Some(face_indices.iter().map(|&i| values[i as usize]).collect())
+        } else {
+            None
+        }
+    };
+
+    let normals = normals
+        .and_then(per_corner)
+        .map(|n| n.into_iter().map(Vec3::normalize_or_zero).collect())
+        .unwrap_or_else(|| {
+            if compute_normals {
+                smooth_normals(&positions, &face_counts, &face_indices)
+            } else {
+                Vec::new()
+            }
+        });
+    let uvs = uvs.and_then(|uv| {
+        if uv.len() == corners {
+            Some(uv)
+        } else if uv.len() == num_points {
+            Some(face_indices.iter().map(|&i| uv[i as usize]).collect())
+        } else {
+            None
+        }
+    });
+
+    MeshData { positions, face_counts, face_indices, normals, uvs }
+}
+
+/// Area-weighted vertex normals, expanded per face-vertex. Faces wind
+/// clockwise (Alembic), so the normals match file-stored `N`.
+fn smooth_normals(positions: &[Vec3], face_counts: &[i32], face_indices: &[i32]) -> Vec<Vec3> {
+    let mut accum = vec![Vec3::ZERO; positions.len()];
+    let mut offset = 0usize;
+    for &count in face_counts {
+        let face = &face_indices[offset..offset + count as usize];
+        offset += count as usize;
+        if face.len() < 3 {
+            continue;
+        }
+        // Newell's method handles non-planar polygons; it gives the
+        // counter-clockwise normal, hence the subtraction
+        let mut n = Vec3::ZERO;
+        for (k, &a) in face.iter().enumerate() {
+            let p = positions[a as usize];
+            let q = positions[face[(k + 1) % face.len()] as usize];
+            n -= Vec3::new((p.y - q.y) * (p.z + q.z), (p.z - q.z) * (p.x + q.x), (p.x - q.x) * (p.y + q.y));
+        }
+        for &a in face {
+            accum[a as usize] += n;
+        }
+    }
+    face_indices.iter().map(|&i| accum[i as usize].normalize_or_zero()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad() -> MeshData {
+        let positions = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ];
+        let uvs = positions.iter().map(|p| Vec2::new(p.x, p.y)).collect();
+        build_mesh(positions, vec![4], vec![0, 1, 2, 3], None, Some(uvs), true)
+    }
+
+    #[test]
+    fn test_build_mesh_resolves_attributes() {
+        let mesh = quad();
+        assert_eq!(mesh.normals.len(), 4);
+        // Counter-clockwise seen from +Z is clockwise seen from -Z
+        for n in &mesh.normals {
+            assert!((n.z + 1.0).abs() < 1e-6, "{}", n);
+        }
+        // Vertex-scoped UVs are expanded per face-vertex
+        assert_eq!(mesh.uvs.as_ref().map(Vec::len), Some(4));
+    }
+
+    #[test]
+    fn test_triangulate_keeps_corner_attributes() {
+        let mut mesh = quad();
+        mesh.triangulate();
+        assert!(mesh.is_triangulated());
+        assert_eq!(mesh.face_counts, vec![3, 3]);
+        assert_eq!(mesh.face_indices, vec![0, 1, 2, 0, 2, 3]);
+        let uvs = mesh.uvs.unwrap();
+        assert_eq!(uvs[4], Vec2::new(1.0, 1.0));
+        assert_eq!(mesh.normals.len(), 6);
+    }
+
+    #[test]
+    fn test_invalid_topology_is_dropped() {
+        let mesh = build_mesh(vec![Vec3::ZERO; 3], vec![3], vec![0, 1, 7], None, None, true);
+        assert!(mesh.face_indices.is_empty());
+        assert!(mesh.normals.is_empty());
+    }
+
+    #[test]
+    fn test_no_computed_normals() {
+        let positions = vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::ONE];
+        let mut mesh = build_mesh(positions, vec![4], vec![0, 1, 3, 2], None, None, false);
+        assert!(mesh.normals.is_empty());
+        mesh.triangulate();
+        assert_eq!(mesh.face_indices.len(), 6);
+        assert!(mesh.normals.is_empty());
+    }
+
+    #[test]
+    fn test_transform_normals() {
+        let mut mesh = quad();
+        mesh.transform(Mat4::from_scale(Vec3::new(2.0, 1.0, 1.0)) * Mat4::from_rotation_x(std::f32::consts::FRAC_PI_2));
+        assert!(mesh.positions[2].abs_diff_eq(Vec3::new(2.0, 0.0, 1.0), 1e-6));
+        for n in &mesh.normals {
+            assert!((n.length() - 1.0).abs() < 1e-5);
+            assert!(n.y.abs() > 0.99);
+        }
+    }
+}
@@ -43,21 +43,29 @@ pub mod geom_param;
 pub mod faceset;
 pub mod nupatch;
 pub mod light;
+pub mod schema_info;
+pub mod mesh_data;
+pub mod motion_blur;
+pub mod skin;
+pub mod typed;
+pub mod winding;
+#[cfg(feature = "parallel")]
+mod parallel;
 
 // Re-export xform types
 pub use xform::{IXform, XformSample, XformOp, XformOpType, XFORM_SCHEMA};
 
 // Re-export polymesh types
-pub use polymesh::{IPolyMesh, PolyMeshSample, POLYMESH_SCHEMA};
+pub use polymesh::{IPolyMesh, PolyMeshSample, FaceSetSubmesh, POLYMESH_SCHEMA};
 
 // Re-export curves types
-pub use curves::{ICurves, CurvesSample, CurveType, CurvePeriodicity, BasisType, CURVES_SCHEMA};
+pub use curves::{ICurves, CurvesSample, Strand, CurveType, CurvePeriodicity, BasisType, CURVES_SCHEMA};
 
 // Re-export points types
-pub use points::{IPoints, PointsSample, POINTS_SCHEMA};
+pub use points::{IPoints, IPointsAttributes, PointsSample, POINTS_SCHEMA};
 
 // Re-export subd types
-pub use subd::{ISubD, SubDSample, SubDScheme, SUBD_SCHEMA};
+pub use subd::{FaceVaryingInterpolation, ISubD, InterpolateBoundary, SubDSample, SubDScheme, SUBD_SCHEMA};
 
 // Re-export camera types
 pub use camera::{ICamera, CameraSample, CAMERA_SCHEMA, FilmBackXformOp, FilmBackXformOpType};
@@ -84,9 +92,15 @@ pub use geom_param::{
     OC3fGeomParam, OC4fGeomParam,
     OInt32GeomParam, OUInt32GeomParam, OFloatGeomParam,
     // Constants
-    GEOM_SCOPE_KEY, VALS_PROPERTY_NAME, INDICES_PROPERTY_NAME,
+    GEOM_SCOPE_KEY, IS_UV_KEY, VALS_PROPERTY_NAME, INDICES_PROPERTY_NAME,
 };
 
+// Re-export skin types
+pub use skin::{SkinSample, JOINT_INDICES, JOINT_WEIGHTS, REST_POSITIONS, BIND_MATRICES, GEOM_BIND_TRANSFORM};
+
+// Re-export typed property access (trait + derive macro share the AbcStruct name)
+pub use typed::{AbcProperty, AbcStruct, AbcPod, read_arb, read_user};
+
 // Re-export faceset types
 pub use faceset::{IFaceSet, FaceSetSample, FaceSetExclusivity, FACESET_SCHEMA};
 
@@ -94,7 +108,19 @@ pub use faceset::{IFaceSet, FaceSetSample, FaceSetExclusivity, FACESET_SCHEMA};
 pub use nupatch::{INuPatch, NuPatchSample, TrimCurveData, TrimCurve, NUPATCH_SCHEMA};
 
 // Re-export light types
-pub use light::{ILight, LightSample, LIGHT_SCHEMA};
+pub use light::{ILight, LightSample, LightType, LIGHT_SCHEMA};
+
+// Re-export schema title/version table
+pub use schema_info::{SchemaInfo, GEOM_BASE_SCHEMA};
+
+// Re-export motion blur helpers
+pub use motion_blur::{mesh_motion_positions, MotionSamples, MotionSource};
+
+// Re-export winding analysis
+pub use winding::{analyze_winding, reverse_faces, WindingReport};
+
+// Re-export render-ready mesh loading
+pub use mesh_data::{MeshData, LoadOptions, load_meshes, load_meshes_with, collect_meshes};
 
 // ============================================================================
 // Output Schema Writers (re-exported from ogawa::writer)
@@ -109,7 +135,7 @@ pub use crate::ogawa::writer::{
     // Curves
     OCurves, OCurvesSample,
     // Points
-    OPoints, OPointsSample,
+    OPoints, OPointsSample, PointIdMode,
     // SubD
     OSubD, OSubDSample,
     // Camera (uses CameraSample from geom::camera)
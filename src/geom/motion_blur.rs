@@ -0,0 +1,135 @@
+//! Motion blur position extrapolation.
+//!
+//! Renderers want deformation motion as a few position arrays spread over
+//! the shutter interval. [`mesh_motion_positions`] builds them from one
+//! PolyMesh sample: from the stored `.velocities` when present, otherwise
+//! from a central difference of the neighbouring samples. Topology is
+//! never touched, so the face arrays of the source sample apply to every
+//! step.
+
+use crate::core::TimeSampling;
+use crate::util::{Chrono, Result};
+
+use super::polymesh::{IPolyMesh, PolyMeshSample};
+
+/// Where the per-vertex motion came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MotionSource {
+    /// Stored `.velocities` (units per second).
+    Velocities,
+    /// Difference of the neighbouring samples.
+    CentralDifference,
+    /// No usable motion; every step repeats the sample positions.
+    Static,
+}
+
+/// Position arrays across a shutter interval.
+#[derive(Clone, Debug)]
+pub struct MotionSamples {
+    /// The sample the motion was extrapolated from.
+    pub sample: PolyMeshSample,
+    /// Absolute time of each step.
+    pub times: Vec<Chrono>,
+    /// Positions at each step, same length and order as `sample.positions`.
+    pub positions: Vec<Vec<glam::Vec3>>,
+    /// Source of the motion.
+    pub source: MotionSource,
+}
+
+/// Extrapolate mesh positions over a shutter interval around `time`.
+///
+/// `shutter` holds the open and close offsets from `time` in seconds (e.g.
+/// `(-0.5 / fps, 0.5 / fps)` for a centred 180 degree shutter), split into
+/// `steps` evenly spaced times (1 gives the shutter centre). The nearest
+/// sample to `time` is extrapolated linearly. Without velocities, samples
+/// before and after it are differenced (one-sided at the ends). A neighbour
+/// with a different point count is ignored.
+///
+/// Pass `archive.getTimeSampling(mesh.getTimeSamplingIndex())` as `ts`.
+pub fn mesh_motion_positions(
+    mesh: &IPolyMesh<'_>,
+    ts: &TimeSampling,
+    time: Chrono,
+    shutter: (Chrono, Chrono),
+    steps: usize,
+) -> Result<MotionSamples> {
+    let num_samples = mesh.getNumSamples();
+    let (index, sample_time) = ts.near_index(time, num_samples);
+    let sample = mesh.getSample(index)?;
+
+    let (velocities, source) = match &sample.velocities {
+        Some(v) if v.len() == sample.positions.len() => (v.clone(), MotionSource::Velocities),
+        _ => match difference_velocities(mesh, ts, index, &sample)? {
+            Some(v) => (v, MotionSource::CentralDifference),
+            None => (Vec::new(), MotionSource::Static),
+        },
+    };
+
+    let times = shutter_times(time, shutter, steps);
+    let positions = times
+        .iter()
+        .map(|&t| {
+            let dt = (t - sample_time) as f32;
+            if velocities.is_empty() {
+                sample.positions.clone()
+            } else {
+                sample.positions.iter().zip(&velocities).map(|(p, v)| *p + *v * dt).collect()
+            }
+        })
+        .collect();
+
+    Ok(MotionSamples { sample, times, positions, source })
+}
+
+/// Per-vertex velocities from the samples around `index`, if they line up.
+fn difference_velocities(
+    mesh: &IPolyMesh<'_>,
+    ts: &TimeSampling,
+    index: usize,
+    sample: &PolyMeshSample,
+) -> Result<Option<Vec<glam::Vec3>>> {
+    let num_samples = mesh.getNumSamples();
+    if num_samples < 2 {
+        return Ok(None);
+    }
+    let matching = |i: usize| -> Result<Option<(Chrono, Vec<glam::Vec3>)>> {
+        let s = mesh.getSample(i)?;
+        Ok((s.positions.len() == sample.positions.len()).then(|| (ts.sample_time(i, num_samples), s.positions)))
+    };
+    let prev = if index > 0 { matching(index - 1)? } else { None };
+    let next = if index + 1 < num_samples { matching(index + 1)? } else { None };
+    let here = || (ts.sample_time(index, num_samples), sample.positions.clone());
+
+    let (a, b) = match (prev, next) {
+        (Some(p), Some(n)) => (p, n),
+        (Some(p), None) => (p, here()),
+        (None, Some(n)) => (here(), n),
+        (None, None) => return Ok(None),
+    };
+    let span = (b.0 - a.0) as f32;
+    if span.abs() < 1e-12 {
+        return Ok(None);
+    }
+    Ok(Some(a.1.iter().zip(&b.1).map(|(pa, pb)| (*pb - *pa) / span).collect()))
+}
+
+/// Evenly spaced absolute times across the shutter.
+fn shutter_times(time: Chrono, (open, close): (Chrono, Chrono), steps: usize) -> Vec<Chrono> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![time + 0.5 * (open + close)],
+        n => (0..n).map(|i| time + open + (close - open) * i as Chrono / (n - 1) as Chrono).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutter_times() {
+        assert_eq!(shutter_times(1.0, (-0.25, 0.25), 3), vec![0.75, 1.0, 1.25]);
+        assert_eq!(shutter_times(1.0, (0.0, 0.5), 1), vec![1.25]);
+        assert!(shutter_times(1.0, (0.0, 0.5), 0).is_empty());
+    }
+}
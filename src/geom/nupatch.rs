@@ -5,7 +5,7 @@
 use crate::abc::IObject;
 use crate::geom::util as geom_util;
 use crate::util::{Result, Error, BBox3d};
-use crate::core::TopologyVariance;
+use crate::core::{SampleSelector, TopologyVariance};
 
 /// NuPatch schema identifier.
 pub const NUPATCH_SCHEMA: &str = "AbcGeom_NuPatch_v2";
@@ -367,8 +367,12 @@ impl<'a> INuPatch<'a> {
         geom_util::has_user_properties(self.object)
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<NuPatchSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<NuPatchSample> {
+        let sel = sel.into();
         let mut sample = NuPatchSample::new();
         
         let props = self.object.getProperties();
@@ -379,56 +383,56 @@ impl<'a> INuPatch<'a> {
         let g = geom.as_reader();
         
         // Read core geometry using helpers
-        if let Some(pos) = geom_util::read_vec3_array(g, "P", index) {
+        if let Some(pos) = geom_util::read_vec3_array(g, "P", sel) {
             sample.positions = pos;
         }
         
         // Read NURBS parameters (scalars)
-        if let Some(nu) = geom_util::read_i32_scalar(g, "nu", index) {
+        if let Some(nu) = geom_util::read_i32_scalar(g, "nu", sel) {
             sample.num_u = nu;
         }
-        if let Some(nv) = geom_util::read_i32_scalar(g, "nv", index) {
+        if let Some(nv) = geom_util::read_i32_scalar(g, "nv", sel) {
             sample.num_v = nv;
         }
-        if let Some(uo) = geom_util::read_i32_scalar(g, "uOrder", index) {
+        if let Some(uo) = geom_util::read_i32_scalar(g, "uOrder", sel) {
             sample.u_order = uo;
         }
-        if let Some(vo) = geom_util::read_i32_scalar(g, "vOrder", index) {
+        if let Some(vo) = geom_util::read_i32_scalar(g, "vOrder", sel) {
             sample.v_order = vo;
         }
         
         // Read knots
-        if let Some(uk) = geom_util::read_f32_array(g, "uKnot", index) {
+        if let Some(uk) = geom_util::read_f32_array(g, "uKnot", sel) {
             sample.u_knots = uk;
         }
-        if let Some(vk) = geom_util::read_f32_array(g, "vKnot", index) {
+        if let Some(vk) = geom_util::read_f32_array(g, "vKnot", sel) {
             sample.v_knots = vk;
         }
         
         // Read optional attributes
-        sample.position_weights = geom_util::read_f32_array(g, "Pw", index);
-        sample.velocities = geom_util::read_vec3_array(g, ".velocities", index);
-        sample.normals = geom_util::read_vec3_array(g, "N", index);
-        sample.uvs = geom_util::read_vec2_array(g, "uv", index);
+        sample.position_weights = geom_util::read_f32_array(g, "Pw", sel);
+        sample.velocities = geom_util::read_vec3_array(g, ".velocities", sel);
+        sample.normals = geom_util::read_vec3_array(g, "N", sel);
+        sample.uvs = geom_util::read_vec2_array(g, "uv", sel);
         
         // Read trim curve data if present
-        sample.trim_curve = self.read_trim_curve(&geom, index)?;
+        sample.trim_curve = self.read_trim_curve(&geom, sel)?;
         
         // Read bounds
-        sample.self_bounds = geom_util::read_self_bounds(g, index);
+        sample.self_bounds = geom_util::read_self_bounds(g, sel);
         
         Ok(sample)
     }
     
     /// Read trim curve data from properties.
-    fn read_trim_curve(&self, geom: &crate::abc::ICompoundProperty<'_>, index: usize) -> Result<Option<TrimCurveData>> {
+    fn read_trim_curve(&self, geom: &crate::abc::ICompoundProperty<'_>, sel: SampleSelector) -> Result<Option<TrimCurveData>> {
         let mut trim = TrimCurveData::default();
         
         // Read trim_nloops
         if let Some(prop) = geom.getPropertyByName("trim_nloops") {
             if let Some(scalar) = prop.asScalar() {
                 let mut buf = [0u8; 4];
-                if scalar.getSample(index, &mut buf).is_ok() {
+                if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                     trim.num_loops = i32::from_le_bytes(buf);
                 }
             }
@@ -442,7 +446,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_ncurves
         if let Some(prop) = geom.getPropertyByName("trim_ncurves") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.num_curves = bytemuck::try_cast_slice::<_, i32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -451,7 +455,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_n
         if let Some(prop) = geom.getPropertyByName("trim_n") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.num_vertices = bytemuck::try_cast_slice::<_, i32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -460,7 +464,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_order
         if let Some(prop) = geom.getPropertyByName("trim_order") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.orders = bytemuck::try_cast_slice::<_, i32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -469,7 +473,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_knot
         if let Some(prop) = geom.getPropertyByName("trim_knot") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.knots = bytemuck::try_cast_slice::<_, f32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -478,7 +482,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_min
         if let Some(prop) = geom.getPropertyByName("trim_min") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.mins = bytemuck::try_cast_slice::<_, f32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -487,7 +491,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_max
         if let Some(prop) = geom.getPropertyByName("trim_max") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.maxes = bytemuck::try_cast_slice::<_, f32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -496,7 +500,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_u
         if let Some(prop) = geom.getPropertyByName("trim_u") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.u = bytemuck::try_cast_slice::<_, f32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -505,7 +509,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_v
         if let Some(prop) = geom.getPropertyByName("trim_v") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.v = bytemuck::try_cast_slice::<_, f32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
@@ -514,7 +518,7 @@ impl<'a> INuPatch<'a> {
         // Read trim_w
         if let Some(prop) = geom.getPropertyByName("trim_w") {
             if let Some(array) = prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
+                if let Ok(data) = array.getSampleVec(array.getSampleIndex(sel)) {
                     trim.w = bytemuck::try_cast_slice::<_, f32>(&data).map(|s| s.to_vec()).unwrap_or_default();
                 }
             }
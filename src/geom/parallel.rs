@@ -0,0 +1,40 @@
+//! Batched parallel sample reads for the input schemas (`parallel` feature).
+
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use super::{
+    CameraSample, CurvesSample, FaceSetSample, ICamera, ICurves, IFaceSet, ILight, INuPatch, IPoints,
+    IPolyMesh, ISubD, IXform, LightSample, NuPatchSample, PointsSample, PolyMeshSample, SubDSample,
+    XformSample,
+};
+use crate::util::Result;
+
+macro_rules! impl_samples_par {
+    ($($schema:ident => $sample:ty),* $(,)?) => {$(
+        impl $schema<'_> {
+            /// Read samples `range` (clamped to the sample count) in parallel,
+            /// returned in sample order.
+            pub fn get_samples_par(&self, range: Range<usize>) -> Result<Vec<$sample>> {
+                let end = range.end.min(self.getNumSamples());
+                (range.start.min(end)..end)
+                    .into_par_iter()
+                    .map(|index| self.getSample(index))
+                    .collect()
+            }
+        }
+    )*};
+}
+
+impl_samples_par! {
+    IPolyMesh => PolyMeshSample,
+    ISubD => SubDSample,
+    ICurves => CurvesSample,
+    IPoints => PointsSample,
+    IXform => XformSample,
+    ICamera => CameraSample,
+    INuPatch => NuPatchSample,
+    ILight => LightSample,
+    IFaceSet => FaceSetSample,
+}
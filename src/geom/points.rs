@@ -2,10 +2,14 @@
 //!
 //! Provides reading of point cloud / particle data from Alembic files.
 
+use std::collections::HashMap;
+
 use crate::abc::IObject;
+use crate::geom::geom_param::{GeomParamSample, IGeomParam};
+use crate::geom::typed::AbcPod;
 use crate::geom::util as geom_util;
-use crate::util::{Result, BBox3d};
-use crate::core::TopologyVariance;
+use crate::util::{Chrono, DataType, Error, Result, BBox3d};
+use crate::core::{GeometryScope, TimeSampling, TopologyVariance, SampleSelector};
 
 /// Points schema identifier.
 pub const POINTS_SCHEMA: &str = "AbcGeom_Points_v1";
@@ -56,6 +60,63 @@ impl PointsSample {
         self.self_bounds.is_some()
     }
     
+    /// Blend towards `other` by `t` (0 = self, 1 = other).
+    ///
+    /// The point set of the nearest sample is kept. With ids on both sides
+    /// points are matched by id, so births and deaths between samples don't
+    /// scramble the blend; without ids the counts must match. Unmatched
+    /// points keep their nearest-sample values.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let (nearest, far) = if t < 0.5 { (self, other) } else { (other, self) };
+        let mut out = nearest.clone();
+        let partners: Vec<Option<usize>> = if !self.ids.is_empty() && !other.ids.is_empty() {
+            let map = far.id_to_index();
+            nearest.ids.iter().map(|id| map.get(id).copied()).collect()
+        } else if self.positions.len() == other.positions.len() {
+            (0..nearest.positions.len()).map(Some).collect()
+        } else {
+            return out;
+        };
+
+        let per_point_widths = self.widths.len() == self.positions.len() && other.widths.len() == other.positions.len();
+        for (i, j) in partners.into_iter().enumerate() {
+            let Some(j) = j else { continue };
+            // a indexes self, b indexes other
+            let (a, b) = if t < 0.5 { (i, j) } else { (j, i) };
+            if let (Some(pa), Some(pb), Some(o)) = (self.positions.get(a), other.positions.get(b), out.positions.get_mut(i)) {
+                *o = pa.lerp(*pb, t);
+            }
+            if let (Some(va), Some(vb), Some(o)) = (self.velocities.get(a), other.velocities.get(b), out.velocities.get_mut(i)) {
+                *o = va.lerp(*vb, t);
+            }
+            if per_point_widths {
+                if let (Some(wa), Some(wb), Some(o)) = (self.widths.get(a), other.widths.get(b), out.widths.get_mut(i)) {
+                    *o = wa + (wb - wa) * t;
+                }
+            }
+        }
+        if let ([wa], [wb], [o]) = (self.widths.as_slice(), other.widths.as_slice(), out.widths.as_mut_slice()) {
+            *o = wa + (wb - wa) * t;
+        }
+        out.self_bounds = geom_util::lerp_bounds(self.self_bounds, other.self_bounds, t);
+        out
+    }
+
+    /// Find the point index with the given id (linear scan).
+    /// For repeated lookups build a map with [`id_to_index`](Self::id_to_index).
+    pub fn index_of_id(&self, id: u64) -> Option<usize> {
+        self.ids.iter().position(|&i| i == id)
+    }
+    
+    /// Build an id -> point index map. On duplicate ids the first index wins.
+    pub fn id_to_index(&self) -> HashMap<u64, usize> {
+        let mut map = HashMap::with_capacity(self.ids.len());
+        for (index, &id) in self.ids.iter().enumerate() {
+            map.entry(id).or_insert(index);
+        }
+        map
+    }
+    
     /// Check if sample is valid.
     pub fn is_valid(&self) -> bool {
         !self.positions.is_empty()
@@ -108,9 +169,12 @@ impl<'a> IPoints<'a> {
         geom_util::num_samples_from_positions(self.object)
     }
     
-    /// Check if points are constant.
+    /// Check if points are constant (positions and ids never change).
+    /// Ids are looked up as `.pointIds` (C++) and `id`, like [`getSample`](Self::getSample).
     pub fn isConstant(&self) -> bool {
-        self.getNumSamples() <= 1
+        geom_util::property_is_constant(self.object, ".geom", "P")
+            && geom_util::property_is_constant(self.object, ".geom", ".pointIds")
+            && geom_util::property_is_constant(self.object, ".geom", "id")
     }
     
     /// Get time sampling index from positions property.
@@ -133,6 +197,16 @@ impl<'a> IPoints<'a> {
         geom_util::arb_geom_param_names(self.object)
     }
     
+    /// Read an arbitrary geometry parameter sample by name.
+    pub fn arb_geom_param(&self, name: &str, index: usize) -> Option<GeomParamSample> {
+        geom_util::read_arb_geom_param(self.object, name, index)
+    }
+
+    /// Typed access to the per-point attributes in `.arbGeomParams`.
+    pub fn attributes(&self) -> IPointsAttributes<'a> {
+        IPointsAttributes { object: self.object }
+    }
+    
     /// Check if points have user properties.
     pub fn has_user_properties(&self) -> bool {
         geom_util::has_user_properties(self.object)
@@ -152,7 +226,7 @@ impl<'a> IPoints<'a> {
     /// 
     /// Points are typically heterogeneous since particle count can change.
     pub fn topology_variance(&self) -> TopologyVariance {
-        if self.getNumSamples() <= 1 {
+        if self.isConstant() {
             TopologyVariance::Static
         } else {
             // Points typically have changing topology (particle birth/death)
@@ -160,8 +234,20 @@ impl<'a> IPoints<'a> {
         }
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<PointsSample> {
+    /// Read the points at an arbitrary time, blending the two bracketing
+    /// samples (see [`PointsSample::lerp`]).
+    ///
+    /// Pass `archive.getTimeSampling(points.getTimeSamplingIndex())`.
+    pub fn get_interpolated_sample(&self, time: Chrono, ts: &TimeSampling) -> Result<PointsSample> {
+        geom_util::interpolate_samples(ts, self.getNumSamples(), time, |i| self.getSample(i), |a, b, t| a.lerp(b, t))
+    }
+
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<PointsSample> {
+        let sel = sel.into();
         use crate::util::Error;
         
         let props = self.object.getProperties();
@@ -174,30 +260,125 @@ impl<'a> IPoints<'a> {
         let mut sample = PointsSample::new();
         
         // Read core geometry using helpers
-        if let Some(pos) = geom_util::read_vec3_array(g, "P", index) {
+        if let Some(pos) = geom_util::read_vec3_array(g, "P", sel) {
             sample.positions = pos;
         }
         // Try C++ name ".pointIds" first, then fallback to "id"
-        if let Some(ids) = geom_util::read_u64_array(g, ".pointIds", index)
-            .or_else(|| geom_util::read_u64_array(g, "id", index)) {
+        if let Some(ids) = geom_util::read_u64_array(g, ".pointIds", sel)
+            .or_else(|| geom_util::read_u64_array(g, "id", sel)) {
             sample.ids = ids;
         }
         
         // C++ ref: IPoints.cpp:62 uses ".velocities"
-        sample.velocities = geom_util::read_vec3_array(g, ".velocities", index)
+        sample.velocities = geom_util::read_vec3_array(g, ".velocities", sel)
             .unwrap_or_default();
         
         // Read width and bounds
         // C++ ref: IPoints.cpp:70 uses ".widths" (GeomParam)
-        if let Some(w) = geom_util::read_f32_array(g, ".widths", index) {
+        if let Some(w) = geom_util::read_f32_array(g, ".widths", sel) {
             sample.widths = w;
         }
-        sample.self_bounds = geom_util::read_self_bounds(g, index);
+        sample.self_bounds = geom_util::read_self_bounds(g, sel);
         
         Ok(sample)
     }
 }
 
+/// Per-point attributes of a Points schema (`.geom/.arbGeomParams`).
+///
+/// FX packages store everything beyond positions, ids, velocities and widths
+/// here: Houdini writes `Cd`, `orient`, `scale`, `pscale`, `age`, `life` and
+/// any custom point attribute. Samples are read lazily; sample indices are
+/// clamped to each attribute's own sample count.
+pub struct IPointsAttributes<'a> {
+    object: &'a IObject<'a>,
+}
+
+impl<'a> IPointsAttributes<'a> {
+    /// Attribute names in file order.
+    pub fn names(&self) -> Vec<String> {
+        geom_util::arb_geom_param_names(self.object)
+    }
+
+    /// Check if the attribute exists.
+    pub fn has(&self, name: &str) -> bool {
+        self.with_param(name, |_| ()).is_some()
+    }
+
+    /// Stored data type of the attribute (e.g. `float32_t[4]` for `orient`).
+    pub fn data_type(&self, name: &str) -> Option<DataType> {
+        self.with_param(name, |p| p.data_type())
+    }
+
+    /// Geometry scope of the attribute (`vtx`/`var` for per-point data).
+    pub fn scope(&self, name: &str) -> Option<GeometryScope> {
+        self.with_param(name, |p| p.scope())
+    }
+
+    /// Number of samples of the attribute.
+    pub fn num_samples(&self, name: &str) -> usize {
+        self.with_param(name, |p| p.getNumSamples()).unwrap_or(0)
+    }
+
+    /// Raw sample (values and indices as stored).
+    pub fn sample(&self, name: &str, index: usize) -> Option<GeomParamSample> {
+        geom_util::read_arb_geom_param(self.object, name, index)
+    }
+
+    /// Values of `name` as `T` with indices resolved; `Ok(None)` if the
+    /// attribute is missing, an error if it is stored as another type.
+    pub fn get<T: AbcPod>(&self, name: &str, index: usize) -> Result<Option<Vec<T>>> {
+        self.with_param(name, |p| {
+            if p.data_type() != T::DATA_TYPE {
+                return Err(Error::TypeMismatch {
+                    expected: format!("{:?}", T::DATA_TYPE),
+                    actual: format!("{:?}", p.data_type()),
+                });
+            }
+            let last = p.getNumSamples().saturating_sub(1);
+            p.getSample(index.min(last))?.resolved::<T>()
+        })
+        .transpose()
+    }
+
+    /// Orientations (`orient`, quaternions stored as float4 `x, y, z, w`).
+    pub fn orientations(&self, index: usize) -> Option<Vec<glam::Quat>> {
+        self.get::<glam::Quat>("orient", index).ok().flatten()
+    }
+
+    /// Per-axis scales: `scale`, or uniform `pscale` when that is all there is.
+    pub fn scales(&self, index: usize) -> Option<Vec<glam::Vec3>> {
+        if let Some(scale) = self.get::<glam::Vec3>("scale", index).ok().flatten() {
+            return Some(scale);
+        }
+        let pscale = self.get::<f32>("pscale", index).ok().flatten()?;
+        Some(pscale.into_iter().map(glam::Vec3::splat).collect())
+    }
+
+    /// Colors (`Cd`, RGB; RGBA is truncated).
+    pub fn colors(&self, index: usize) -> Option<Vec<glam::Vec3>> {
+        match self.data_type("Cd")? {
+            DataType::VEC4F => Some(self.get::<glam::Vec4>("Cd", index).ok()??.into_iter().map(|c| c.truncate()).collect()),
+            _ => self.get::<glam::Vec3>("Cd", index).ok().flatten(),
+        }
+    }
+
+    /// Particle ages (`age`).
+    pub fn ages(&self, index: usize) -> Option<Vec<f32>> {
+        self.get::<f32>("age", index).ok().flatten()
+    }
+
+    fn with_param<R>(&self, name: &str, f: impl FnOnce(&IGeomParam<'_>) -> R) -> Option<R> {
+        let props = self.object.getProperties();
+        let geom_prop = props.getPropertyByName(".geom")?;
+        let geom = geom_prop.asCompound()?;
+        let arb_prop = geom.getPropertyByName(".arbGeomParams")?;
+        let arb = arb_prop.asCompound()?;
+        let param = IGeomParam::new(&arb, name)?;
+        Some(f(&param))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,4 +409,17 @@ mod tests {
         assert_eq!(min, glam::vec3(-1.0, -2.0, -3.0));
         assert_eq!(max, glam::vec3(1.0, 2.0, 3.0));
     }
+    
+    #[test]
+    fn test_points_id_lookup() {
+        let mut sample = PointsSample::new();
+        sample.positions = vec![glam::Vec3::ZERO; 3];
+        sample.ids = vec![100, 42, 7];
+        
+        assert_eq!(sample.index_of_id(42), Some(1));
+        assert_eq!(sample.index_of_id(5), None);
+        let map = sample.id_to_index();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&7], 2);
+    }
 }
@@ -3,10 +3,12 @@
 //! Provides reading of polygon mesh data from Alembic files.
 
 use crate::abc::IObject;
-use crate::core::TopologyVariance;
+use crate::core::{TimeSampling, TopologyVariance, SampleSelector};
 use crate::geom::faceset::FACESET_SCHEMA;
+use crate::geom::geom_param::GeomParamSample;
+use crate::geom::skin::SkinSample;
 use crate::geom::util as geom_util;
-use crate::util::{Result, BBox3d};
+use crate::util::{Chrono, Result, BBox3d};
 
 /// PolyMesh schema identifier.
 pub const POLYMESH_SCHEMA: &str = "AbcGeom_PolyMesh_v1";
@@ -80,16 +82,16 @@ impl PolyMeshSample {
         !self.face_indices.is_empty()
     }
     
-    /// Calculate face normals.
+    /// Calculate face normals (one per face; +Y for faces with missing or
+    /// out-of-range indices).
     pub fn compute_face_normals(&self) -> Vec<glam::Vec3> {
         let mut normals = Vec::with_capacity(self.face_counts.len());
         let mut idx = 0usize;
         
         for &count in &self.face_counts {
-            if count >= 3 {
-                let i0 = self.face_indices[idx] as usize;
-                let i1 = self.face_indices[idx + 1] as usize;
-                let i2 = self.face_indices[idx + 2] as usize;
+            let corners = self.face_indices.get(idx..idx + 3).filter(|_| count >= 3);
+            if let Some(&[i0, i1, i2]) = corners {
+                let (i0, i1, i2) = (i0 as usize, i1 as usize, i2 as usize);
                 
                 if i0 < self.positions.len() && i1 < self.positions.len() && i2 < self.positions.len() {
                     let v0 = self.positions[i0];
@@ -106,16 +108,160 @@ impl PolyMeshSample {
             } else {
                 normals.push(glam::Vec3::Y);
             }
-            idx += count as usize;
+            idx += count.max(0) as usize;
         }
         
         normals
     }
     
+    /// Compute smooth per-vertex normals (area-weighted face normals, fan-triangulated).
+    ///
+    /// Uses the same winding as [`compute_face_normals`](Self::compute_face_normals).
+    /// Vertices not referenced by any valid face get a zero normal.
+    pub fn compute_vertex_normals(&self) -> Vec<glam::Vec3> {
+        let mut normals = vec![glam::Vec3::ZERO; self.positions.len()];
+        let mut idx = 0usize;
+
+        for &count in &self.face_counts {
+            let count = count.max(0) as usize;
+            if let Some(face) = self.face_indices.get(idx..idx + count) {
+                let valid = face.iter().all(|&i| i >= 0 && (i as usize) < self.positions.len());
+                if count >= 3 && valid {
+                    let p0 = self.positions[face[0] as usize];
+                    // Unnormalized cross products weight each face by its area.
+                    let mut n = glam::Vec3::ZERO;
+                    for k in 1..count - 1 {
+                        let p1 = self.positions[face[k] as usize];
+                        let p2 = self.positions[face[k + 1] as usize];
+                        n += (p1 - p0).cross(p2 - p0);
+                    }
+                    for &i in face {
+                        normals[i as usize] += n;
+                    }
+                }
+            }
+            idx += count;
+        }
+
+        for n in &mut normals {
+            *n = n.normalize_or_zero();
+        }
+        normals
+    }
+
+    /// Extract `faces` (indices into `face_counts`) as a standalone mesh.
+    ///
+    /// Only the referenced points are kept and face indices are remapped to
+    /// them. Velocities and vertex-scope normals/UVs follow the points;
+    /// face-varying normals/UVs follow the face corners. Out-of-range and
+    /// duplicate faces are skipped. Self bounds are not carried over.
+    pub fn extract_faces(&self, faces: &[i32]) -> PolyMeshSample {
+        let mut offsets = Vec::with_capacity(self.face_counts.len());
+        let mut offset = 0usize;
+        for &count in &self.face_counts {
+            offsets.push(offset);
+            offset += count.max(0) as usize;
+        }
+
+        let num_points = self.positions.len();
+        let corners = self.face_indices.len();
+        let mut remap = vec![u32::MAX; num_points];
+        let mut taken = vec![false; self.face_counts.len()];
+        let mut out = PolyMeshSample {
+            normals_is_simple_array: self.normals_is_simple_array,
+            ..Default::default()
+        };
+        let mut points = Vec::new();
+        let mut corner_ids = Vec::new();
+
+        for &face in faces {
+            let Some(f) = usize::try_from(face).ok().filter(|&f| f < taken.len() && !taken[f]) else { continue };
+            let count = self.face_counts[f].max(0) as usize;
+            let Some(ids) = self.face_indices.get(offsets[f]..offsets[f] + count) else { continue };
+            if ids.iter().any(|&i| i < 0 || i as usize >= num_points) {
+                continue;
+            }
+            taken[f] = true;
+            out.face_counts.push(count as i32);
+            for (k, &i) in ids.iter().enumerate() {
+                let slot = &mut remap[i as usize];
+                if *slot == u32::MAX {
+                    *slot = points.len() as u32;
+                    points.push(i as usize);
+                }
+                out.face_indices.push(*slot as i32);
+                corner_ids.push(offsets[f] + k);
+            }
+        }
+
+        out.positions = points.iter().map(|&i| self.positions[i]).collect();
+        out.velocities = self.velocities.as_ref()
+            .filter(|v| v.len() == num_points)
+            .map(|v| points.iter().map(|&i| v[i]).collect());
+        // Per-corner data first: a mesh can have as many points as corners.
+        fn pick<T: Copy>(values: &[T], corners: usize, corner_ids: &[usize], num_points: usize, points: &[usize]) -> Option<Vec<T>> {
+            if values.len() == corners {
+                Some(corner_ids.iter().map(|&c| values[c]).collect())
+            } else if values.len() == num_points {
+                Some(points.iter().map(|&i| values[i]).collect())
+            } else {
+                None
+            }
+        }
+        out.normals = self.normals.as_deref().and_then(|n| pick(n, corners, &corner_ids, num_points, &points));
+        out.uvs = self.uvs.as_deref().and_then(|uv| pick(uv, corners, &corner_ids, num_points, &points));
+        out
+    }
+
     /// Calculate bounding box.
     pub fn compute_bounds(&self) -> (glam::Vec3, glam::Vec3) {
         geom_util::compute_bounds_vec3(&self.positions)
     }
+
+    /// Blend towards `other` by `t` (0 = self, 1 = other).
+    ///
+    /// Positions, velocities, normals and UVs are lerped when the topology
+    /// matches; otherwise the nearest sample is returned unchanged.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let nearest = if t < 0.5 { self } else { other };
+        if self.face_counts != other.face_counts || self.face_indices != other.face_indices {
+            return nearest.clone();
+        }
+        let Some(positions) = geom_util::lerp_vec3_array(&self.positions, &other.positions, t) else {
+            return nearest.clone();
+        };
+        let lerp_opt = |a: &Option<Vec<glam::Vec3>>, b: &Option<Vec<glam::Vec3>>| match (a, b) {
+            (Some(a), Some(b)) => geom_util::lerp_vec3_array(a, b, t),
+            _ => None,
+        };
+        let normals = lerp_opt(&self.normals, &other.normals)
+            .map(|n| n.into_iter().map(|n| n.normalize_or_zero()).collect());
+        let uvs = match (&self.uvs, &other.uvs) {
+            (Some(a), Some(b)) if a.len() == b.len() => Some(a.iter().zip(b).map(|(a, b)| a.lerp(*b, t)).collect()),
+            _ => nearest.uvs.clone(),
+        };
+        Self {
+            positions,
+            face_counts: self.face_counts.clone(),
+            face_indices: self.face_indices.clone(),
+            velocities: lerp_opt(&self.velocities, &other.velocities).or_else(|| nearest.velocities.clone()),
+            uvs,
+            normals: normals.or_else(|| nearest.normals.clone()),
+            normals_is_simple_array: nearest.normals_is_simple_array,
+            self_bounds: geom_util::lerp_bounds(self.self_bounds, other.self_bounds, t),
+        }
+    }
+}
+
+/// Part of a mesh sample selected by a FaceSet, see [`IPolyMesh::split_by_face_sets`].
+#[derive(Clone, Debug)]
+pub struct FaceSetSubmesh {
+    /// FaceSet name, `None` for faces not in any FaceSet.
+    pub name: Option<String>,
+    /// Face indices into the source mesh.
+    pub faces: Vec<i32>,
+    /// The extracted faces with compacted points.
+    pub sample: PolyMeshSample,
 }
 
 /// Input PolyMesh schema reader.
@@ -154,9 +300,12 @@ impl<'a> IPolyMesh<'a> {
         geom_util::num_samples_from_positions(self.object)
     }
     
-    /// Check if this mesh is constant (single sample).
+    /// Check if this mesh is constant (positions and topology never change).
+    ///
+    /// A mesh written with identical samples keeps its sample count but
+    /// stores the data once; it is constant.
     pub fn isConstant(&self) -> bool {
-        self.getNumSamples() <= 1
+        self.topology_variance() == TopologyVariance::Static
     }
     
     /// Get time sampling index from positions property.
@@ -167,43 +316,11 @@ impl<'a> IPolyMesh<'a> {
     /// Get the topology variance for this mesh.
     /// 
     /// Returns:
-    /// - Static: Only one sample exists
+    /// - Static: Positions and topology are constant
     /// - Homogeneous: Topology is constant, only positions change
     /// - Heterogeneous: Topology can change between samples
     pub fn topology_variance(&self) -> TopologyVariance {
-        let props = self.object.getProperties();
-        let Some(geom_prop) = props.getPropertyByName(".geom") else {
-            return TopologyVariance::Static;
-        };
-        let Some(geom) = geom_prop.asCompound() else {
-            return TopologyVariance::Static;
-        };
-        
-        // Get sample counts for positions and topology
-        let p_samples = if let Some(p) = geom.getPropertyByName("P") {
-            p.asArray().map(|a| a.getNumSamples()).unwrap_or(1)
-        } else { 1 };
-        
-        let fc_samples = if let Some(fc) = geom.getPropertyByName(".faceCounts") {
-            fc.asArray().map(|a| a.getNumSamples()).unwrap_or(1)
-        } else { 1 };
-        
-        let fi_samples = if let Some(fi) = geom.getPropertyByName(".faceIndices") {
-            fi.asArray().map(|a| a.getNumSamples()).unwrap_or(1)
-        } else { 1 };
-        
-        // Determine variance
-        let max_samples = p_samples.max(fc_samples).max(fi_samples);
-        
-        if max_samples <= 1 {
-            TopologyVariance::Static
-        } else if fc_samples <= 1 && fi_samples <= 1 {
-            // Topology constant, only positions animated
-            TopologyVariance::Homogeneous
-        } else {
-            // Topology can change
-            TopologyVariance::Heterogeneous
-        }
+        geom_util::mesh_topology_variance(self.object)
     }
     
     /// Get property names available on this mesh.
@@ -215,15 +332,10 @@ impl<'a> IPolyMesh<'a> {
     /// 
     /// FaceSets are child objects with the FaceSet schema.
     pub fn face_set_names(&self) -> Vec<String> {
-        let mut names = Vec::new();
-        for i in 0..self.object.getNumChildren() {
-            if let Some(header) = self.object.getChildHeader(i) {
-                if header.meta_data.get("schema").map(|s| s == FACESET_SCHEMA).unwrap_or(false) {
-                    names.push(header.name.clone());
-                }
-            }
-        }
-        names
+        self.object.getChildren()
+            .filter(|child| child.matchesSchema(FACESET_SCHEMA))
+            .map(|child| child.getName().to_string())
+            .collect()
     }
     
     /// Check if this mesh has a FaceSet with the given name.
@@ -241,43 +353,8 @@ impl<'a> IPolyMesh<'a> {
     /// Use this when you need the face indices for a specific sample.
     pub fn get_face_set_sample(&self, name: &str, index: usize) -> Option<super::faceset::FaceSetSample> {
         let child = self.object.getChildByName(name)?;
-        if !child.matchesSchema(FACESET_SCHEMA) {
-            return None;
-        }
-        
-        let mut sample = super::faceset::FaceSetSample::new();
-        
-        let props = child.getProperties();
-        let geom_prop = props.getPropertyByName(".geom")?;
-        let geom = geom_prop.asCompound()?;
-        
-        // Read .faces
-        if let Some(faces_prop) = geom.getPropertyByName(".faces") {
-            if let Some(array) = faces_prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
-                    sample.faces = super::safe_cast_vec(&data);
-                }
-            }
-        }
-        
-        // Read .selfBnds if present
-        if let Some(bnds_prop) = geom.getPropertyByName(".selfBnds") {
-            if let Some(scalar) = bnds_prop.asScalar() {
-                let mut buf = [0u8; 48];
-                if scalar.getSample(index, &mut buf).is_ok() {
-                    if let Some(values) = super::safe_cast_slice::<f64>(&buf) {
-                        if values.len() >= 6 {
-                            sample.self_bounds = Some(BBox3d::new(
-                                glam::dvec3(values[0], values[1], values[2]),
-                                glam::dvec3(values[3], values[4], values[5]),
-                            ));
-                        }
-                    }
-                }
-            }
-        }
-        
-        Some(sample)
+        let face_set = super::faceset::IFaceSet::new(&child)?;
+        face_set.getSample(index).ok()
     }
     
     /// Get the exclusivity setting for a FaceSet.
@@ -298,22 +375,48 @@ impl<'a> IPolyMesh<'a> {
     /// Get number of samples in a FaceSet.
     pub fn face_set_num_samples(&self, name: &str) -> usize {
         let Some(child) = self.object.getChildByName(name) else { return 0 };
-        if !child.matchesSchema(FACESET_SCHEMA) {
-            return 0;
-        }
-        
-        let props = child.getProperties();
-        let Some(geom_prop) = props.getPropertyByName(".geom") else { return 1 };
-        let Some(geom) = geom_prop.asCompound() else { return 1 };
-        let Some(faces_prop) = geom.getPropertyByName(".faces") else { return 1 };
-        let Some(array) = faces_prop.asArray() else { return 1 };
-        array.getNumSamples()
+        super::faceset::IFaceSet::new(&child).map_or(0, |fs| fs.getNumSamples())
     }
     
     /// Get number of FaceSets on this mesh.
     pub fn num_face_sets(&self) -> usize {
         self.face_set_names().len()
     }
+
+    /// Face indices of FaceSet `name` at the given sample index.
+    /// The index is clamped to the FaceSet's own sample count.
+    pub fn faces_for_faceset(&self, name: &str, index: usize) -> Option<Vec<i32>> {
+        let last = self.face_set_num_samples(name).saturating_sub(1);
+        self.get_face_set_sample(name, index.min(last)).map(|s| s.faces)
+    }
+
+    /// Split sample `index` into one submesh per FaceSet (in
+    /// [`face_set_names`](Self::face_set_names) order), plus a trailing
+    /// submesh with `name: None` for faces not in any FaceSet.
+    ///
+    /// Non-exclusive FaceSets may overlap; their shared faces then appear
+    /// in each submesh.
+    pub fn split_by_face_sets(&self, index: usize) -> Result<Vec<FaceSetSubmesh>> {
+        let sample = self.getSample(index)?;
+        let mut assigned = vec![false; sample.face_counts.len()];
+        let mut out = Vec::new();
+        for name in self.face_set_names() {
+            let Some(faces) = self.faces_for_faceset(&name, index) else { continue };
+            for &f in &faces {
+                if let Some(a) = usize::try_from(f).ok().and_then(|f| assigned.get_mut(f)) {
+                    *a = true;
+                }
+            }
+            let mesh = sample.extract_faces(&faces);
+            out.push(FaceSetSubmesh { name: Some(name), faces, sample: mesh });
+        }
+        let rest: Vec<i32> = (0..assigned.len()).filter(|&f| !assigned[f]).map(|f| f as i32).collect();
+        if !rest.is_empty() {
+            let mesh = sample.extract_faces(&rest);
+            out.push(FaceSetSubmesh { name: None, faces: rest, sample: mesh });
+        }
+        Ok(out)
+    }
     
     /// Check if this mesh has arbitrary geometry parameters.
     pub fn has_arb_geom_params(&self) -> bool {
@@ -335,6 +438,31 @@ impl<'a> IPolyMesh<'a> {
         geom_util::user_property_names(self.object)
     }
     
+    /// Read an arbitrary geometry parameter sample by name.
+    pub fn arb_geom_param(&self, name: &str, index: usize) -> Option<GeomParamSample> {
+        geom_util::read_arb_geom_param(self.object, name, index)
+    }
+
+    /// Names of all UV sets on this mesh, primary `uv` first.
+    pub fn uv_set_names(&self) -> Vec<String> {
+        geom_util::uv_set_names(self.object)
+    }
+    
+    /// Read UV set `name` at the given sample index, indices resolved.
+    pub fn get_uv_set(&self, name: &str, index: usize) -> Option<Vec<glam::Vec2>> {
+        geom_util::read_uv_set(self.object, name, index)
+    }
+    
+    /// Read skin data (joint weights, Pref, bind matrices), if this mesh has any.
+    pub fn skin_sample(&self, index: usize) -> Option<SkinSample> {
+        SkinSample::read(self.object, index)
+    }
+    
+    /// Read rest positions (`Pref`), if present.
+    pub fn rest_positions(&self, index: usize) -> Option<Vec<glam::Vec3>> {
+        super::skin::read_rest_positions(self.object, index)
+    }
+    
     /// Check if this mesh has child bounds property.
     pub fn has_child_bounds(&self) -> bool {
         geom_util::has_child_bounds(self.object)
@@ -355,8 +483,12 @@ impl<'a> IPolyMesh<'a> {
         geom_util::child_bounds_time_sampling_index(self.object)
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<PolyMeshSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<PolyMeshSample> {
+        let sel = sel.into();
         use crate::util::Error;
         
         let mut sample = PolyMeshSample::new();
@@ -369,26 +501,34 @@ impl<'a> IPolyMesh<'a> {
         let g = geom.as_reader();
         
         // Read core geometry data using helpers
-        if let Some(pos) = geom_util::read_vec3_array(g, "P", index) {
+        if let Some(pos) = geom_util::read_vec3_array(g, "P", sel) {
             sample.positions = pos;
         }
-        sample.velocities = geom_util::read_vec3_array(g, ".velocities", index);
-        if let Some(fc) = geom_util::read_i32_array(g, ".faceCounts", index) {
+        sample.velocities = geom_util::read_vec3_array(g, ".velocities", sel);
+        if let Some(fc) = geom_util::read_i32_array(g, ".faceCounts", sel) {
             sample.face_counts = fc;
         }
-        if let Some(fi) = geom_util::read_i32_array(g, ".faceIndices", index) {
+        if let Some(fi) = geom_util::read_i32_array(g, ".faceIndices", sel) {
             sample.face_indices = fi;
         }
         
         // Read optional attributes
-        sample.normals = geom_util::read_vec3_array(g, "N", index);
+        sample.normals = geom_util::read_vec3_array(g, "N", sel);
         sample.normals_is_simple_array = geom_util::is_simple_array(g, "N");
-        sample.uvs = geom_util::read_vec2_array(g, "uv", index);
-        sample.self_bounds = geom_util::read_self_bounds(g, index);
+        sample.uvs = geom_util::read_vec2_array(g, "uv", sel);
+        sample.self_bounds = geom_util::read_self_bounds(g, sel);
         
         Ok(sample)
     }
     
+    /// Read the mesh at an arbitrary time, blending the two bracketing
+    /// samples (see [`PolyMeshSample::lerp`]).
+    ///
+    /// Pass `archive.getTimeSampling(mesh.getTimeSamplingIndex())`.
+    pub fn get_interpolated_sample(&self, time: Chrono, ts: &TimeSampling) -> Result<PolyMeshSample> {
+        geom_util::interpolate_samples(ts, self.getNumSamples(), time, |i| self.getSample(i), |a, b, t| a.lerp(b, t))
+    }
+
     /// Check if this mesh has UVs.
     pub fn has_uvs(&self) -> bool {
         geom_util::has_geom_property(self.object, "uv")
@@ -412,13 +552,13 @@ impl<'a> IPolyMesh<'a> {
             // Indexed UVs - read .vals and .indices
             let vals_prop = compound.getPropertyByName(".vals")?;
             let array = vals_prop.asArray()?;
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             
             // Check for indices
             if let Some(idx_prop) = compound.getPropertyByName(".indices") {
                 if let Some(idx_array) = idx_prop.asArray() {
-                    if let Ok(idx_data) = idx_array.getSampleVec(index) {
+                    if let Ok(idx_data) = idx_array.getSampleVec(idx_array.getSampleIndex(index.into())) {
                         let indices: &[u32] = bytemuck::try_cast_slice(&idx_data).ok()?;
                         return Some(indices.iter()
                             .map(|&i| {
@@ -440,7 +580,7 @@ impl<'a> IPolyMesh<'a> {
                 .collect())
         } else if let Some(array) = uv_prop.asArray() {
             // Non-indexed UVs
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             Some(floats.chunks_exact(2)
                 .map(|c| glam::vec2(c[0], c[1]))
@@ -463,13 +603,13 @@ impl<'a> IPolyMesh<'a> {
             // Indexed normals - read .vals and .indices
             let vals_prop = compound.getPropertyByName(".vals")?;
             let array = vals_prop.asArray()?;
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             
             // Check for indices
             if let Some(idx_prop) = compound.getPropertyByName(".indices") {
                 if let Some(idx_array) = idx_prop.asArray() {
-                    if let Ok(idx_data) = idx_array.getSampleVec(index) {
+                    if let Ok(idx_data) = idx_array.getSampleVec(idx_array.getSampleIndex(index.into())) {
                         let indices: &[u32] = bytemuck::try_cast_slice(&idx_data).ok()?;
                         return Some(indices.iter()
                             .map(|&i| {
@@ -491,7 +631,7 @@ impl<'a> IPolyMesh<'a> {
                 .collect())
         } else if let Some(array) = n_prop.asArray() {
             // Non-indexed normals
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             Some(floats.chunks_exact(3)
                 .map(|c| glam::vec3(c[0], c[1], c[2]))
@@ -578,6 +718,70 @@ mod tests {
         // Normal should point in Z direction for XY plane triangle
         assert!((normals[0].z - 1.0).abs() < 0.001 || (normals[0].z + 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_polymesh_extract_faces() {
+        // Strip of three quads: points 0..8, faces 0, 1, 2 left to right.
+        let mut sample = PolyMeshSample::new();
+        sample.positions = (0..8).map(|i| glam::vec3((i / 2) as f32, (i % 2) as f32, 0.0)).collect();
+        sample.face_counts = vec![4, 4, 4];
+        sample.face_indices = vec![0, 2, 3, 1, 2, 4, 5, 3, 4, 6, 7, 5];
+        sample.uvs = Some((0..12).map(|c| glam::vec2(c as f32, 0.0)).collect());
+        sample.velocities = Some(sample.positions.clone());
+
+        let sub = sample.extract_faces(&[2, 1, 2, 9]);
+        assert_eq!(sub.face_counts, vec![4, 4]);
+        assert_eq!(sub.num_vertices(), 6);
+        // Remapped indices address the same positions as the source
+        let src = [4, 6, 7, 5, 2, 4, 5, 3];
+        for (k, &i) in sub.face_indices.iter().enumerate() {
+            assert_eq!(sub.positions[i as usize], sample.positions[src[k]]);
+        }
+        assert_eq!(sub.velocities.as_ref().unwrap(), &sub.positions);
+        let uvs: Vec<f32> = sub.uvs.unwrap().iter().map(|uv| uv.x).collect();
+        assert_eq!(uvs, vec![8.0, 9.0, 10.0, 11.0, 4.0, 5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn test_polymesh_vertex_normals() {
+        // Two quads folded 90 degrees along the shared edge (x = 1).
+        let mut sample = PolyMeshSample::new();
+        sample.positions = vec![
+            glam::vec3(0.0, 0.0, 0.0),
+            glam::vec3(1.0, 0.0, 0.0),
+            glam::vec3(1.0, 1.0, 0.0),
+            glam::vec3(0.0, 1.0, 0.0),
+            glam::vec3(1.0, 0.0, -1.0),
+            glam::vec3(1.0, 1.0, -1.0),
+        ];
+        sample.face_counts = vec![4, 4];
+        sample.face_indices = vec![0, 1, 2, 3, 1, 4, 5, 2];
+
+        let normals = sample.compute_vertex_normals();
+        assert_eq!(normals.len(), 6);
+        let face = sample.compute_face_normals();
+        // Unshared vertices take their face normal, shared ones the average.
+        assert!(normals[0].abs_diff_eq(face[0], 1e-5));
+        assert!(normals[4].abs_diff_eq(face[1], 1e-5));
+        let shared = (face[0] + face[1]).normalize();
+        assert!(normals[1].abs_diff_eq(shared, 1e-5));
+        assert!(normals[2].abs_diff_eq(shared, 1e-5));
+    }
+    
+    #[test]
+    fn test_face_normals_bad_topology() {
+        let mut sample = PolyMeshSample::new();
+        sample.positions = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y];
+        // Out-of-range index, negative count, face past the end of the indices
+        sample.face_counts = vec![3, -1, 3, 4];
+        sample.face_indices = vec![0, 1, 7, 0, 1, 2, 0];
+        
+        let normals = sample.compute_face_normals();
+        assert_eq!(normals.len(), 4);
+        assert_eq!(normals[0], glam::Vec3::Y);
+        assert!((normals[2].z.abs() - 1.0).abs() < 0.001);
+        assert_eq!(normals[3], glam::Vec3::Y);
+    }
     
     #[test]
     fn test_polymesh_bounds() {
@@ -0,0 +1,121 @@
+//! Schema title/version metadata written per schema.
+//!
+//! Mirrors the C++ `ALEMBIC_ABC_DECLARE_SCHEMA_INFO` declarations: each
+//! schema object carries `schema`, `schemaObjTitle` (`<schema>:<property>`)
+//! and, for geometry deriving from GeomBase, `schemaBaseType`; the schema's
+//! compound property carries `schema` and `schemaBaseType`. The writers build
+//! their metadata from this table so the strings cannot drift from what the
+//! readers match against.
+//!
+//! References:
+//! - `_ref/alembic/lib/Alembic/AbcGeom/SchemaInfoDeclarations.h`
+//! - `_ref/alembic/lib/Alembic/Abc/OSchemaObject.h`
+
+use crate::collection::COLLECTIONS_SCHEMA;
+use crate::core::MetaData;
+use crate::material::MATERIAL_SCHEMA;
+
+use super::{
+    CAMERA_SCHEMA, CURVES_SCHEMA, FACESET_SCHEMA, LIGHT_SCHEMA, NUPATCH_SCHEMA, POINTS_SCHEMA,
+    POLYMESH_SCHEMA, SUBD_SCHEMA, XFORM_SCHEMA,
+};
+
+/// Base type of the GeomBase-derived schemas (PolyMesh, SubD, Points, ...).
+pub const GEOM_BASE_SCHEMA: &str = "AbcGeom_GeomBase_v1";
+
+/// Title, base type and property name of one schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaInfo {
+    /// Schema title, e.g. "AbcGeom_Xform_v3".
+    pub schema: &'static str,
+    /// Schema base type ("AbcGeom_GeomBase_v1"), if any.
+    pub base_type: Option<&'static str>,
+    /// Name of the schema's compound property, e.g. ".xform".
+    pub property: &'static str,
+}
+
+impl SchemaInfo {
+    pub const XFORM: Self = Self { schema: XFORM_SCHEMA, base_type: None, property: ".xform" };
+    pub const POLYMESH: Self = Self { schema: POLYMESH_SCHEMA, base_type: Some(GEOM_BASE_SCHEMA), property: ".geom" };
+    pub const SUBD: Self = Self { schema: SUBD_SCHEMA, base_type: Some(GEOM_BASE_SCHEMA), property: ".geom" };
+    pub const CURVES: Self = Self { schema: CURVES_SCHEMA, base_type: Some(GEOM_BASE_SCHEMA), property: ".geom" };
+    pub const POINTS: Self = Self { schema: POINTS_SCHEMA, base_type: Some(GEOM_BASE_SCHEMA), property: ".geom" };
+    pub const NUPATCH: Self = Self { schema: NUPATCH_SCHEMA, base_type: Some(GEOM_BASE_SCHEMA), property: ".geom" };
+    pub const FACESET: Self = Self { schema: FACESET_SCHEMA, base_type: Some(GEOM_BASE_SCHEMA), property: ".faceset" };
+    pub const CAMERA: Self = Self { schema: CAMERA_SCHEMA, base_type: None, property: ".geom" };
+    pub const LIGHT: Self = Self { schema: LIGHT_SCHEMA, base_type: None, property: ".geom" };
+    pub const MATERIAL: Self = Self { schema: MATERIAL_SCHEMA, base_type: None, property: ".material" };
+    pub const COLLECTIONS: Self = Self { schema: COLLECTIONS_SCHEMA, base_type: None, property: ".collections" };
+
+    /// Every schema the writer produces.
+    pub const ALL: [Self; 11] = [
+        Self::XFORM, Self::POLYMESH, Self::SUBD, Self::CURVES, Self::POINTS, Self::NUPATCH,
+        Self::FACESET, Self::CAMERA, Self::LIGHT, Self::MATERIAL, Self::COLLECTIONS,
+    ];
+
+    /// Look up a schema by its title.
+    pub fn for_schema(schema: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|info| info.schema == schema)
+    }
+
+    /// Object title, e.g. "AbcGeom_Xform_v3:.xform".
+    pub fn obj_title(&self) -> String {
+        format!("{}:{}", self.schema, self.property)
+    }
+
+    /// Metadata of the schema object (`schema`, `schemaBaseType`, `schemaObjTitle`).
+    pub fn object_metadata(&self) -> MetaData {
+        let mut meta = self.compound_metadata();
+        meta.set(MetaData::SCHEMA_OBJ_TITLE_KEY, self.obj_title());
+        meta
+    }
+
+    /// Metadata of the schema compound property (`schema`, `schemaBaseType`).
+    pub fn compound_metadata(&self) -> MetaData {
+        let mut meta = MetaData::new();
+        meta.set_schema(self.schema);
+        if let Some(base) = self.base_type {
+            meta.set(MetaData::SCHEMA_BASE_KEY, base);
+        }
+        meta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_info_matches_cpp() {
+        // Strings as written by Alembic 1.8
+        let expected = [
+            ("AbcGeom_Xform_v3", None, "AbcGeom_Xform_v3:.xform"),
+            ("AbcGeom_PolyMesh_v1", Some("AbcGeom_GeomBase_v1"), "AbcGeom_PolyMesh_v1:.geom"),
+            ("AbcGeom_SubD_v1", Some("AbcGeom_GeomBase_v1"), "AbcGeom_SubD_v1:.geom"),
+            ("AbcGeom_Curve_v2", Some("AbcGeom_GeomBase_v1"), "AbcGeom_Curve_v2:.geom"),
+            ("AbcGeom_Points_v1", Some("AbcGeom_GeomBase_v1"), "AbcGeom_Points_v1:.geom"),
+            ("AbcGeom_NuPatch_v2", Some("AbcGeom_GeomBase_v1"), "AbcGeom_NuPatch_v2:.geom"),
+            ("AbcGeom_FaceSet_v1", Some("AbcGeom_GeomBase_v1"), "AbcGeom_FaceSet_v1:.faceset"),
+            ("AbcGeom_Camera_v1", None, "AbcGeom_Camera_v1:.geom"),
+            ("AbcGeom_Light_v1", None, "AbcGeom_Light_v1:.geom"),
+            ("AbcMaterial_Material_v1", None, "AbcMaterial_Material_v1:.material"),
+            ("AbcCollection_Collection_v1", None, "AbcCollection_Collection_v1:.collections"),
+        ];
+        for (info, (schema, base, title)) in SchemaInfo::ALL.iter().zip(expected) {
+            assert_eq!(info.schema, schema);
+            assert_eq!(info.base_type, base);
+            assert_eq!(info.obj_title(), title);
+        }
+        assert_eq!(SchemaInfo::for_schema("AbcGeom_Curve_v2"), Some(SchemaInfo::CURVES));
+        assert_eq!(SchemaInfo::for_schema("AbcGeom_Curve_v1"), None);
+    }
+
+    #[test]
+    fn test_schema_info_metadata() {
+        assert_eq!(
+            SchemaInfo::POLYMESH.object_metadata().serialize(),
+            "schema=AbcGeom_PolyMesh_v1;schemaBaseType=AbcGeom_GeomBase_v1;schemaObjTitle=AbcGeom_PolyMesh_v1:.geom"
+        );
+        assert_eq!(SchemaInfo::XFORM.compound_metadata().serialize(), "schema=AbcGeom_Xform_v3");
+    }
+}
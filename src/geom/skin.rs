@@ -0,0 +1,212 @@
+//! Skinning and rig-export attributes.
+//!
+//! DCC rig exporters store skin data on meshes as plain Alembic properties:
+//! - `.arbGeomParams/jointIndices` (int32) and `jointWeights` (float32),
+//!   vertex scoped with `arrayExtent` = influences per vertex
+//! - `.arbGeomParams/Pref` (V3f) - rest (bind pose) positions
+//! - `.userProperties/bindMatrices` (M44f/M44d array) - inverse bind matrices
+//!   per joint, and `geomBindTransform` (M44f/M44d) - mesh transform at bind
+//!
+//! [`SkinSample`] reads and writes these through the GeomParam layer so they
+//! survive a read/write round trip.
+
+use glam::{Mat4, Vec3};
+
+use crate::abc::IObject;
+use crate::core::GeometryScope;
+use crate::ogawa::writer::OProperty;
+use crate::util::{DataType, PlainOldDataType};
+
+use super::geom_param::{GeomParamSample, OGeomParam};
+use super::util as geom_util;
+
+/// Arb geom param holding joint indices.
+pub const JOINT_INDICES: &str = "jointIndices";
+/// Arb geom param holding joint weights.
+pub const JOINT_WEIGHTS: &str = "jointWeights";
+/// Arb geom param holding rest positions.
+pub const REST_POSITIONS: &str = "Pref";
+/// User property holding per-joint bind matrices.
+pub const BIND_MATRICES: &str = "bindMatrices";
+/// User property holding the mesh transform at bind time.
+pub const GEOM_BIND_TRANSFORM: &str = "geomBindTransform";
+
+/// Skin binding data of a mesh.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SkinSample {
+    /// Joint index per influence (`influences` entries per vertex).
+    pub joint_indices: Vec<i32>,
+    /// Joint weight per influence, parallel to `joint_indices`.
+    pub joint_weights: Vec<f32>,
+    /// Influences per vertex.
+    pub influences: usize,
+    /// Rest (bind pose) positions.
+    pub rest_positions: Option<Vec<Vec3>>,
+    /// Per-joint bind matrices.
+    pub bind_matrices: Vec<Mat4>,
+    /// Mesh transform at bind time.
+    pub geom_bind_transform: Option<Mat4>,
+}
+
+impl SkinSample {
+    /// Read skin data from a mesh object at the given sample index.
+    /// Returns None if the object carries none of the skin attributes.
+    pub fn read(object: &IObject<'_>, index: usize) -> Option<Self> {
+        let joint_indices: Vec<i32> = geom_util::read_arb_geom_param(object, JOINT_INDICES, index)
+            .map(|s| expand_pod(&s))
+            .unwrap_or_default();
+        let joint_weights: Vec<f32> = geom_util::read_arb_geom_param(object, JOINT_WEIGHTS, index)
+            .map(|s| expand_pod(&s))
+            .unwrap_or_default();
+        let rest_positions = read_rest_positions(object, index);
+        let bind_matrices = geom_util::read_user_property(object, BIND_MATRICES, index)
+            .map(|(dt, data)| matrices_from_bytes(dt, &data))
+            .unwrap_or_default();
+        let geom_bind_transform = geom_util::read_user_property(object, GEOM_BIND_TRANSFORM, index)
+            .and_then(|(dt, data)| matrices_from_bytes(dt, &data).into_iter().next());
+
+        if joint_indices.is_empty() && joint_weights.is_empty() && rest_positions.is_none()
+            && bind_matrices.is_empty() && geom_bind_transform.is_none()
+        {
+            return None;
+        }
+
+        let influences = geom_util::arb_geom_param_array_extent(object, JOINT_INDICES)
+            .or_else(|| geom_util::arb_geom_param_array_extent(object, JOINT_WEIGHTS))
+            .unwrap_or(1)
+            .max(1);
+        Some(Self {
+            joint_indices,
+            joint_weights,
+            influences,
+            rest_positions,
+            bind_matrices,
+            geom_bind_transform,
+        })
+    }
+
+    /// Check if per-vertex joint influences are present.
+    pub fn has_weights(&self) -> bool {
+        !self.joint_indices.is_empty() && self.joint_indices.len() == self.joint_weights.len()
+    }
+
+    /// Number of skinned vertices.
+    pub fn num_vertices(&self) -> usize {
+        self.joint_indices.len() / self.influences.max(1)
+    }
+
+    /// Joint influences `(joint, weight)` of a vertex.
+    pub fn vertex_influences(&self, vertex: usize) -> impl Iterator<Item = (i32, f32)> + '_ {
+        let n = self.influences.max(1);
+        let range = (vertex * n).min(self.joint_indices.len())..((vertex + 1) * n).min(self.joint_indices.len());
+        self.joint_indices[range.clone()]
+            .iter()
+            .zip(self.joint_weights.get(range).unwrap_or(&[]))
+            .map(|(&j, &w)| (j, w))
+    }
+
+    /// Arbitrary geometry params for writing (joint indices/weights, Pref).
+    pub fn arb_geom_params(&self) -> Vec<OGeomParam> {
+        let mut params = Vec::new();
+        if self.has_weights() {
+            let extent = self.influences.max(1) as u32;
+            let mut indices = OGeomParam::new(JOINT_INDICES, DataType::INT32, GeometryScope::Vertex, false)
+                .with_array_extent(extent);
+            indices.add_values(&self.joint_indices);
+            params.push(indices);
+            let mut weights = OGeomParam::float(JOINT_WEIGHTS, GeometryScope::Vertex)
+                .with_array_extent(extent);
+            weights.add_values(&self.joint_weights);
+            params.push(weights);
+        }
+        if let Some(rest) = &self.rest_positions {
+            let mut pref = OGeomParam::vec3f(REST_POSITIONS, GeometryScope::Vertex);
+            pref.add_values(rest);
+            params.push(pref);
+        }
+        params
+    }
+
+    /// User properties for writing (bind matrices, geom bind transform).
+    pub fn user_properties(&self) -> Vec<OProperty> {
+        let m44f = DataType::new(PlainOldDataType::Float32, 16);
+        let mut props = Vec::new();
+        if !self.bind_matrices.is_empty() {
+            let mut bind = OProperty::array(BIND_MATRICES, m44f);
+            bind.meta_data.set("interpretation", "matrix");
+            let values: Vec<[f32; 16]> = self.bind_matrices.iter().map(Mat4::to_cols_array).collect();
+            bind.add_array_pod(&values);
+            props.push(bind);
+        }
+        if let Some(m) = self.geom_bind_transform {
+            let mut geom_bind = OProperty::scalar(GEOM_BIND_TRANSFORM, m44f);
+            geom_bind.meta_data.set("interpretation", "matrix");
+            geom_bind.add_scalar_pod(&m.to_cols_array());
+            props.push(geom_bind);
+        }
+        props
+    }
+}
+
+/// Read rest positions (`Pref`) at the given sample index.
+pub fn read_rest_positions(object: &IObject<'_>, index: usize) -> Option<Vec<Vec3>> {
+    let sample = geom_util::read_arb_geom_param(object, REST_POSITIONS, index)?;
+    if sample.data_type.pod != PlainOldDataType::Float32 || sample.data_type.extent != 3 {
+        return None;
+    }
+    Some(sample.expand_vec3())
+}
+
+/// Values of a GeomParam sample with indices resolved.
+fn expand_pod<T: bytemuck::Pod>(sample: &GeomParamSample) -> Vec<T> {
+    let values: Vec<T> = super::safe_cast_vec(&sample.values);
+    match &sample.indices {
+        Some(indices) => indices.iter().filter_map(|&i| values.get(i as usize).copied()).collect(),
+        None => values,
+    }
+}
+
+/// Decode M44f/M44d values (16 elements per matrix).
+fn matrices_from_bytes(data_type: DataType, data: &[u8]) -> Vec<Mat4> {
+    match data_type.pod {
+        PlainOldDataType::Float32 => super::safe_cast_vec::<f32>(data)
+            .chunks_exact(16)
+            .map(Mat4::from_cols_slice)
+            .collect(),
+        PlainOldDataType::Float64 => super::safe_cast_vec::<f64>(data)
+            .chunks_exact(16)
+            .map(|m| {
+                let m: Vec<f32> = m.iter().map(|&v| v as f32).collect();
+                Mat4::from_cols_slice(&m)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertex_influences() {
+        let skin = SkinSample {
+            joint_indices: vec![0, 1, 2, 3],
+            joint_weights: vec![0.75, 0.25, 1.0, 0.0],
+            influences: 2,
+            ..Default::default()
+        };
+        assert!(skin.has_weights());
+        assert_eq!(skin.num_vertices(), 2);
+        assert_eq!(skin.vertex_influences(1).collect::<Vec<_>>(), vec![(2, 1.0), (3, 0.0)]);
+        assert_eq!(skin.vertex_influences(5).count(), 0);
+    }
+
+    #[test]
+    fn test_matrices_from_bytes() {
+        let m = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let f64s: Vec<f64> = m.to_cols_array().iter().map(|&v| v as f64).collect();
+        let decoded = matrices_from_bytes(DataType::new(PlainOldDataType::Float64, 16), bytemuck::cast_slice(&f64s));
+        assert_eq!(decoded, vec![m]);
+    }
+}
@@ -3,8 +3,10 @@
 //! Provides reading of subdivision surface data from Alembic files.
 
 use crate::abc::IObject;
-use crate::core::TopologyVariance;
+use crate::core::{SampleSelector, TopologyVariance};
 use crate::geom::faceset::{IFaceSet, FACESET_SCHEMA};
+use crate::geom::geom_param::GeomParamSample;
+use crate::geom::skin::SkinSample;
 use crate::geom::util as geom_util;
 use crate::util::{Result, BBox3d};
 
@@ -34,6 +36,101 @@ impl SubDScheme {
             _ => SubDScheme::CatmullClark,
         }
     }
+
+    /// Canonical name as written to `.scheme` ("catmull-clark", "loop", "bilinear").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubDScheme::CatmullClark => "catmull-clark",
+            SubDScheme::Loop => "loop",
+            SubDScheme::Bilinear => "bilinear",
+        }
+    }
+}
+
+/// Boundary interpolation rule (`.interpolateBoundary`).
+///
+/// Values match the C++ integers; a missing property reads as `None` (0).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InterpolateBoundary {
+    /// Boundary faces are dropped from the limit surface (0).
+    #[default]
+    None,
+    /// Boundary edges and corners are sharp (1).
+    EdgeAndCorner,
+    /// Boundary edges are sharp, corners are smoothed (2).
+    EdgeOnly,
+}
+
+impl InterpolateBoundary {
+    /// From the stored integer; `None` for values outside 0..=2.
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::EdgeAndCorner),
+            2 => Some(Self::EdgeOnly),
+            _ => None,
+        }
+    }
+
+    /// Stored integer value.
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    /// USD `interpolateBoundary` token.
+    pub fn usd_token(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::EdgeAndCorner => "edgeAndCorner",
+            Self::EdgeOnly => "edgeOnly",
+        }
+    }
+}
+
+/// Face-varying (UV) boundary interpolation (`.faceVaryingInterpolateBoundary`).
+///
+/// Variants are named after USD's `faceVaryingLinearInterpolation` tokens,
+/// with the integer mapping used by the USD Alembic plugin and Maya's
+/// `osdFvarBoundary`. A missing property reads as `All` (0).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FaceVaryingInterpolation {
+    /// Linear everywhere, no UV smoothing (0, Maya "Bilinear").
+    #[default]
+    All,
+    /// Smooth, with sharp corners and junctions (1, "Edge And Corner").
+    CornersPlus1,
+    /// Smooth everywhere including boundaries (2, "Edge Only").
+    None,
+    /// Linear along boundaries only (3, "Always Sharp").
+    Boundaries,
+}
+
+impl FaceVaryingInterpolation {
+    /// From the stored integer; `None` for values outside 0..=3.
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::All),
+            1 => Some(Self::CornersPlus1),
+            2 => Some(Self::None),
+            3 => Some(Self::Boundaries),
+            _ => None,
+        }
+    }
+
+    /// Stored integer value.
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    /// USD `faceVaryingLinearInterpolation` token.
+    pub fn usd_token(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::CornersPlus1 => "cornersPlus1",
+            Self::None => "none",
+            Self::Boundaries => "boundaries",
+        }
+    }
 }
 
 /// SubD sample data.
@@ -70,11 +167,11 @@ pub struct SubDSample {
     /// Hole face indices.
     pub holes: Vec<i32>,
     /// Face-varying interpolation boundary.
-    pub fv_interp_boundary: i32,
+    pub fv_interp_boundary: FaceVaryingInterpolation,
     /// Face-varying propagate corners.
     pub fv_propagate_corners: i32,
     /// Interpolate boundary.
-    pub interp_boundary: i32,
+    pub interp_boundary: InterpolateBoundary,
     /// Self bounds - bounding box of this geometry (optional).
     pub self_bounds: Option<BBox3d>,
 }
@@ -177,9 +274,9 @@ impl<'a> ISubD<'a> {
         geom_util::num_samples_from_positions(self.object)
     }
     
-    /// Check if SubD is constant.
+    /// Check if SubD is constant (positions and topology never change).
     pub fn isConstant(&self) -> bool {
-        self.getNumSamples() <= 1
+        self.topology_variance() == TopologyVariance::Static
     }
     
     /// Get time sampling index from positions property.
@@ -190,56 +287,21 @@ impl<'a> ISubD<'a> {
     /// Get the topology variance for this subdivision surface.
     /// 
     /// Returns:
-    /// - Static: Only one sample exists
+    /// - Static: Positions and topology are constant
     /// - Homogeneous: Topology is constant, only positions change
     /// - Heterogeneous: Topology can change between samples
     pub fn topology_variance(&self) -> TopologyVariance {
-        let props = self.object.getProperties();
-        let Some(geom_prop) = props.getPropertyByName(".geom") else {
-            return TopologyVariance::Static;
-        };
-        let Some(geom) = geom_prop.asCompound() else {
-            return TopologyVariance::Static;
-        };
-        
-        // Get sample counts for positions and topology
-        let p_samples = if let Some(p) = geom.getPropertyByName("P") {
-            p.asArray().map(|a| a.getNumSamples()).unwrap_or(1)
-        } else { 1 };
-        
-        let fc_samples = if let Some(fc) = geom.getPropertyByName(".faceCounts") {
-            fc.asArray().map(|a| a.getNumSamples()).unwrap_or(1)
-        } else { 1 };
-        
-        let fi_samples = if let Some(fi) = geom.getPropertyByName(".faceIndices") {
-            fi.asArray().map(|a| a.getNumSamples()).unwrap_or(1)
-        } else { 1 };
-        
-        // Determine variance
-        let max_samples = p_samples.max(fc_samples).max(fi_samples);
-        
-        if max_samples <= 1 {
-            TopologyVariance::Static
-        } else if fc_samples <= 1 && fi_samples <= 1 {
-            TopologyVariance::Homogeneous
-        } else {
-            TopologyVariance::Heterogeneous
-        }
+        geom_util::mesh_topology_variance(self.object)
     }
     
     /// Get the names of all FaceSets on this SubD.
     /// 
     /// FaceSets are child objects with the FaceSet schema.
     pub fn face_set_names(&self) -> Vec<String> {
-        let mut names = Vec::new();
-        for i in 0..self.object.getNumChildren() {
-            if let Some(header) = self.object.getChildHeader(i) {
-                if header.meta_data.get("schema").map(|s| s == FACESET_SCHEMA).unwrap_or(false) {
-                    names.push(header.name.clone());
-                }
-            }
-        }
-        names
+        self.object.getChildren()
+            .filter(|child| child.matchesSchema(FACESET_SCHEMA))
+            .map(|child| child.getName().to_string())
+            .collect()
     }
     
     /// Check if this SubD has a FaceSet with the given name.
@@ -284,6 +346,31 @@ impl<'a> ISubD<'a> {
         geom_util::user_property_names(self.object)
     }
     
+    /// Read an arbitrary geometry parameter sample by name.
+    pub fn arb_geom_param(&self, name: &str, index: usize) -> Option<GeomParamSample> {
+        geom_util::read_arb_geom_param(self.object, name, index)
+    }
+
+    /// Names of all UV sets on this SubD, primary `uv` first.
+    pub fn uv_set_names(&self) -> Vec<String> {
+        geom_util::uv_set_names(self.object)
+    }
+    
+    /// Read UV set `name` at the given sample index, indices resolved.
+    pub fn get_uv_set(&self, name: &str, index: usize) -> Option<Vec<glam::Vec2>> {
+        geom_util::read_uv_set(self.object, name, index)
+    }
+    
+    /// Read skin data (joint weights, Pref, bind matrices), if this SubD has any.
+    pub fn skin_sample(&self, index: usize) -> Option<SkinSample> {
+        SkinSample::read(self.object, index)
+    }
+    
+    /// Read rest positions (`Pref`), if present.
+    pub fn rest_positions(&self, index: usize) -> Option<Vec<glam::Vec3>> {
+        super::skin::read_rest_positions(self.object, index)
+    }
+    
     /// Check if this SubD has child bounds property.
     pub fn has_child_bounds(&self) -> bool {
         geom_util::has_child_bounds(self.object)
@@ -304,8 +391,12 @@ impl<'a> ISubD<'a> {
         geom_util::child_bounds_time_sampling_index(self.object)
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<SubDSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<SubDSample> {
+        let sel = sel.into();
         use crate::util::Error;
         
         let props = self.object.getProperties();
@@ -318,55 +409,69 @@ impl<'a> ISubD<'a> {
         let mut sample = SubDSample::new();
         
         // Read core geometry using helpers
-        if let Some(pos) = geom_util::read_vec3_array(g, "P", index) {
+        if let Some(pos) = geom_util::read_vec3_array(g, "P", sel) {
             sample.positions = pos;
         }
-        sample.velocities = geom_util::read_vec3_array(g, ".velocities", index);
+        sample.velocities = geom_util::read_vec3_array(g, ".velocities", sel);
         
         // Read indexed UVs
-        if let Some((uvs, uv_idx)) = geom_util::read_indexed_vec2(g, "uv", index) {
+        if let Some((uvs, uv_idx)) = geom_util::read_indexed_vec2(g, "uv", sel) {
             sample.uvs = Some(uvs);
             sample.uv_indices = uv_idx;
         }
         
         // Read indexed normals
-        if let Some((normals, normal_idx)) = geom_util::read_indexed_vec3(g, "N", index) {
+        if let Some((normals, normal_idx)) = geom_util::read_indexed_vec3(g, "N", sel) {
             sample.normals = Some(normals);
             sample.normal_indices = normal_idx;
         }
         
         // Read face data
-        if let Some(fc) = geom_util::read_i32_array(g, ".faceCounts", index) {
+        if let Some(fc) = geom_util::read_i32_array(g, ".faceCounts", sel) {
             sample.face_counts = fc;
         }
-        if let Some(fi) = geom_util::read_i32_array(g, ".faceIndices", index) {
+        if let Some(fi) = geom_util::read_i32_array(g, ".faceIndices", sel) {
             sample.face_indices = fi;
         }
         
         // Read crease data
-        if let Some(ci) = geom_util::read_i32_array(g, ".creaseIndices", index) {
+        if let Some(ci) = geom_util::read_i32_array(g, ".creaseIndices", sel) {
             sample.crease_indices = ci;
         }
-        if let Some(cl) = geom_util::read_i32_array(g, ".creaseLengths", index) {
+        if let Some(cl) = geom_util::read_i32_array(g, ".creaseLengths", sel) {
             sample.crease_lengths = cl;
         }
-        if let Some(cs) = geom_util::read_f32_array(g, ".creaseSharpnesses", index) {
+        if let Some(cs) = geom_util::read_f32_array(g, ".creaseSharpnesses", sel) {
             sample.crease_sharpnesses = cs;
         }
         
         // Read corner data
-        if let Some(cri) = geom_util::read_i32_array(g, ".cornerIndices", index) {
+        if let Some(cri) = geom_util::read_i32_array(g, ".cornerIndices", sel) {
             sample.corner_indices = cri;
         }
-        if let Some(crs) = geom_util::read_f32_array(g, ".cornerSharpnesses", index) {
+        if let Some(crs) = geom_util::read_f32_array(g, ".cornerSharpnesses", sel) {
             sample.corner_sharpnesses = crs;
         }
         
         // Read holes and bounds
-        if let Some(h) = geom_util::read_i32_array(g, ".holes", index) {
+        if let Some(h) = geom_util::read_i32_array(g, ".holes", sel) {
             sample.holes = h;
         }
-        sample.self_bounds = geom_util::read_self_bounds(g, index);
+
+        // Scheme and boundary rules; missing properties keep the C++ defaults
+        if let Some(scheme) = geom_util::read_string_scalar(g, ".scheme", sel) {
+            sample.scheme = SubDScheme::parse(&scheme);
+        }
+        if let Some(v) = geom_util::read_i32_scalar(g, ".interpolateBoundary", sel) {
+            sample.interp_boundary = InterpolateBoundary::from_i32(v).unwrap_or_default();
+        }
+        if let Some(v) = geom_util::read_i32_scalar(g, ".faceVaryingInterpolateBoundary", sel) {
+            sample.fv_interp_boundary = FaceVaryingInterpolation::from_i32(v).unwrap_or_default();
+        }
+        if let Some(v) = geom_util::read_i32_scalar(g, ".faceVaryingPropagateCorners", sel) {
+            sample.fv_propagate_corners = v;
+        }
+        sample.self_bounds = geom_util::read_self_bounds(g, sel);
         
         Ok(sample)
     }
@@ -400,12 +505,12 @@ impl<'a> ISubD<'a> {
             // Indexed UVs
             let vals_prop = compound.getPropertyByName(".vals")?;
             let array = vals_prop.asArray()?;
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             
             if let Some(idx_prop) = compound.getPropertyByName(".indices") {
                 if let Some(idx_array) = idx_prop.asArray() {
-                    if let Ok(idx_data) = idx_array.getSampleVec(index) {
+                    if let Ok(idx_data) = idx_array.getSampleVec(idx_array.getSampleIndex(index.into())) {
                         let indices: &[u32] = bytemuck::try_cast_slice(&idx_data).ok()?;
                         return Some(indices.iter()
                             .map(|&i| {
@@ -425,7 +530,7 @@ impl<'a> ISubD<'a> {
                 .map(|c| glam::vec2(c[0], c[1]))
                 .collect())
         } else if let Some(array) = uv_prop.asArray() {
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             Some(floats.chunks_exact(2)
                 .map(|c| glam::vec2(c[0], c[1]))
@@ -445,12 +550,12 @@ impl<'a> ISubD<'a> {
         if let Some(compound) = n_prop.asCompound() {
             let vals_prop = compound.getPropertyByName(".vals")?;
             let array = vals_prop.asArray()?;
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             
             if let Some(idx_prop) = compound.getPropertyByName(".indices") {
                 if let Some(idx_array) = idx_prop.asArray() {
-                    if let Ok(idx_data) = idx_array.getSampleVec(index) {
+                    if let Ok(idx_data) = idx_array.getSampleVec(idx_array.getSampleIndex(index.into())) {
                         let indices: &[u32] = bytemuck::try_cast_slice(&idx_data).ok()?;
                         return Some(indices.iter()
                             .map(|&i| {
@@ -470,7 +575,7 @@ impl<'a> ISubD<'a> {
                 .map(|c| glam::vec3(c[0], c[1], c[2]))
                 .collect())
         } else if let Some(array) = n_prop.asArray() {
-            let data = array.getSampleVec(index).ok()?;
+            let data = array.getSampleVec(array.getSampleIndex(index.into())).ok()?;
             let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
             Some(floats.chunks_exact(3)
                 .map(|c| glam::vec3(c[0], c[1], c[2]))
@@ -499,6 +604,20 @@ mod tests {
         assert_eq!(SubDScheme::parse("loop"), SubDScheme::Loop);
         assert_eq!(SubDScheme::parse("bilinear"), SubDScheme::Bilinear);
         assert_eq!(SubDScheme::parse("unknown"), SubDScheme::CatmullClark);
+        assert_eq!(SubDScheme::parse(SubDScheme::Loop.as_str()), SubDScheme::Loop);
+    }
+
+    #[test]
+    fn test_boundary_rules_from_i32() {
+        for v in 0..3 {
+            assert_eq!(InterpolateBoundary::from_i32(v).unwrap().as_i32(), v);
+        }
+        for v in 0..4 {
+            assert_eq!(FaceVaryingInterpolation::from_i32(v).unwrap().as_i32(), v);
+        }
+        assert_eq!(InterpolateBoundary::from_i32(3), None);
+        assert_eq!(FaceVaryingInterpolation::from_i32(-1), None);
+        assert_eq!(FaceVaryingInterpolation::from_i32(2).unwrap().usd_token(), "none");
     }
     
     #[test]
@@ -0,0 +1,325 @@
+//! Typed access to compound properties.
+//!
+//! [`AbcProperty`] reads/writes one Rust value as a named property of a
+//! compound; [`AbcStruct`] maps a whole struct onto a compound, one property
+//! per field. With the `derive` feature, `#[derive(AbcStruct)]` generates
+//! both for a struct with named fields:
+//!
+//! ```ignore
+//! #[derive(AbcStruct)]
+//! struct AssetInfo {
+//!     #[abc(rename = "assetName")]
+//!     name: String,
+//!     version: i32,
+//!     pivot: glam::Vec3,
+//!     tags: Vec<String>,
+//!     lod: Option<u8>,
+//! }
+//!
+//! mesh.write_user(&info);                        // writer, one sample per call
+//! let info: AssetInfo = read_user(&object, 0)?;  // reader
+//! ```
+//!
+//! Supported field types: the numeric PODs, `half::f16`, `bool`, `String`,
+//! glam vectors/matrices/quaternions (see [`AbcPod`]), `Vec<T>` of those
+//! (array properties), `Vec<String>`, `Option<T>` (missing property = `None`)
+//! and other `AbcStruct`s (nested compounds).
+
+use glam::{DMat4, DVec2, DVec3, DVec4, IVec2, IVec3, Mat3, Mat4, Quat, Vec2, Vec3, Vec4};
+
+use crate::abc::{ICompoundProperty, IObject, IProperty};
+use crate::ogawa::writer::{OProperty, OPropertyData};
+use crate::util::{DataType, Error, PlainOldDataType, Result};
+
+#[cfg(feature = "derive")]
+pub use alembic_derive::AbcStruct;
+
+/// A value stored as one named property of a compound.
+pub trait AbcProperty: Sized {
+    /// Read sample `index` (clamped to the last sample) of property `name`.
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self>;
+
+    /// Append one sample to property `name` of `compound`, creating it on first write.
+    fn write_property(&self, compound: &mut OProperty, name: &str);
+}
+
+/// A struct mapped field-by-field onto a compound property.
+pub trait AbcStruct: Sized {
+    /// Read every field at sample `index`.
+    fn read_compound(compound: &ICompoundProperty<'_>, index: usize) -> Result<Self>;
+
+    /// Append one sample of every field to `compound`.
+    fn write_compound(&self, compound: &mut OProperty);
+}
+
+/// Fixed-size POD values with an Alembic data type.
+pub trait AbcPod: bytemuck::Pod {
+    const DATA_TYPE: DataType;
+}
+
+macro_rules! abc_pod {
+    ($($ty:ty => $dt:expr),* $(,)?) => {
+        $(impl AbcPod for $ty { const DATA_TYPE: DataType = $dt; })*
+    };
+}
+
+abc_pod! {
+    u8 => DataType::UINT8, i8 => DataType::INT8,
+    u16 => DataType::UINT16, i16 => DataType::INT16,
+    u32 => DataType::UINT32, i32 => DataType::INT32,
+    u64 => DataType::UINT64, i64 => DataType::INT64,
+    half::f16 => DataType::FLOAT16, f32 => DataType::FLOAT32, f64 => DataType::FLOAT64,
+    Vec2 => DataType::VEC2F, Vec3 => DataType::VEC3F, Vec4 => DataType::VEC4F,
+    DVec2 => DataType::VEC2D, DVec3 => DataType::VEC3D, DVec4 => DataType::VEC4D,
+    IVec2 => DataType::VEC2I, IVec3 => DataType::VEC3I,
+    Quat => DataType::QUATF,
+    Mat3 => DataType::MAT33F, Mat4 => DataType::MAT44F, DMat4 => DataType::MAT44D,
+}
+
+/// Look up `name` in `compound`.
+fn property<'a>(compound: &'a ICompoundProperty<'_>, name: &str) -> Result<IProperty<'a>> {
+    compound.getPropertyByName(name).ok_or_else(|| Error::PropertyNotFound(name.to_string()))
+}
+
+fn check_type(prop: &IProperty<'_>, expected: DataType) -> Result<()> {
+    let actual = prop.getHeader().data_type;
+    if actual != expected {
+        return Err(Error::TypeMismatch {
+            expected: format!("{:?}", expected),
+            actual: format!("{:?}", actual),
+        });
+    }
+    Ok(())
+}
+
+/// Raw bytes of sample `index` (clamped), from a scalar or array property.
+fn read_bytes(prop: &IProperty<'_>, index: usize) -> Result<Vec<u8>> {
+    if let Some(array) = prop.asArray() {
+        let last = array.getNumSamples().saturating_sub(1);
+        return array.getSampleVec(index.min(last));
+    }
+    let scalar = prop.asScalar()
+        .ok_or_else(|| Error::invalid(format!("{} is a compound, expected a value", prop.getName())))?;
+    let last = scalar.getNumSamples().saturating_sub(1);
+    let data_type = prop.getHeader().data_type;
+    if data_type.pod == PlainOldDataType::String {
+        return scalar.getSampleVec(index.min(last));
+    }
+    let mut buf = vec![0u8; data_type.num_bytes()];
+    scalar.getSample(index.min(last), &mut buf)?;
+    Ok(buf)
+}
+
+impl<T: AbcPod> AbcProperty for T {
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self> {
+        let prop = property(compound, name)?;
+        check_type(&prop, T::DATA_TYPE)?;
+        // Scalar-like arrays (one element) are accepted as scalars
+        let bytes = read_bytes(&prop, index)?;
+        let size = std::mem::size_of::<T>();
+        match bytes.get(..size) {
+            Some(value) => Ok(bytemuck::pod_read_unaligned(value)),
+            None => Err(Error::invalid(format!("{}: empty sample", name))),
+        }
+    }
+
+    fn write_property(&self, compound: &mut OProperty, name: &str) {
+        compound.get_or_create_scalar_child(name, T::DATA_TYPE).add_scalar_pod(self);
+    }
+}
+
+impl<T: AbcPod> AbcProperty for Vec<T> {
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self> {
+        let prop = property(compound, name)?;
+        check_type(&prop, T::DATA_TYPE)?;
+        Ok(bytemuck::pod_collect_to_vec(&read_bytes(&prop, index)?))
+    }
+
+    fn write_property(&self, compound: &mut OProperty, name: &str) {
+        compound.get_or_create_array_child(name, T::DATA_TYPE).add_array_pod(self);
+    }
+}
+
+impl AbcProperty for bool {
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self> {
+        let prop = property(compound, name)?;
+        check_type(&prop, DataType::BOOL)?;
+        Ok(read_bytes(&prop, index)?.first().is_some_and(|&b| b != 0))
+    }
+
+    fn write_property(&self, compound: &mut OProperty, name: &str) {
+        compound.get_or_create_scalar_child(name, DataType::BOOL).add_scalar_pod(&(*self as u8));
+    }
+}
+
+impl AbcProperty for String {
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self> {
+        let prop = property(compound, name)?;
+        check_type(&prop, DataType::STRING)?;
+        let bytes = read_bytes(&prop, index)?;
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    fn write_property(&self, compound: &mut OProperty, name: &str) {
+        compound.get_or_create_scalar_child(name, DataType::STRING).add_scalar_string(self);
+    }
+}
+
+impl AbcProperty for Vec<String> {
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self> {
+        let prop = property(compound, name)?;
+        check_type(&prop, DataType::STRING)?;
+        let bytes = read_bytes(&prop, index)?;
+        let count = match prop.asArray() {
+            Some(array) => {
+                let last = array.getNumSamples().saturating_sub(1);
+                array.getDimensions(index.min(last))?.iter().product()
+            }
+            None => 1,
+        };
+        // Array strings are stored back to back, each null-terminated
+        Ok(bytes.split(|&b| b == 0)
+            .take(count)
+            .map(|s| String::from_utf8_lossy(s).into_owned())
+            .collect())
+    }
+
+    fn write_property(&self, compound: &mut OProperty, name: &str) {
+        let mut data = Vec::new();
+        for s in self {
+            data.extend_from_slice(s.as_bytes());
+            data.push(0);
+        }
+        compound.get_or_create_array_child(name, DataType::STRING).add_array_sample(&data, &[self.len()]);
+    }
+}
+
+/// A missing property reads as `None`. Writing `None` leaves an existing
+/// property unchanged for the sample ([`OProperty::hold_sample`]), otherwise
+/// writes nothing.
+impl<T: AbcProperty> AbcProperty for Option<T> {
+    fn read_property(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<Self> {
+        if !compound.hasProperty(name) {
+            return Ok(None);
+        }
+        T::read_property(compound, name, index).map(Some)
+    }
+
+    fn write_property(&self, compound: &mut OProperty, name: &str) {
+        match self {
+            Some(value) => value.write_property(compound, name),
+            None => {
+                if let Some(child) = child_mut(compound, name) {
+                    child.hold_sample();
+                }
+            }
+        }
+    }
+}
+
+/// Child property of a compound being written.
+fn child_mut<'a>(compound: &'a mut OProperty, name: &str) -> Option<&'a mut OProperty> {
+    match &mut compound.data {
+        OPropertyData::Compound(children) => children.iter_mut().find(|p| p.name == name),
+        _ => None,
+    }
+}
+
+/// Read a nested [`AbcStruct`] from sub-compound `name` (used by the derive).
+pub fn read_nested<T: AbcStruct>(compound: &ICompoundProperty<'_>, name: &str, index: usize) -> Result<T> {
+    let prop = property(compound, name)?;
+    let sub = prop.asCompound()
+        .ok_or_else(|| Error::invalid(format!("{} is not a compound", name)))?;
+    T::read_compound(&sub, index)
+}
+
+/// Write a nested [`AbcStruct`] into sub-compound `name` (used by the derive).
+pub fn write_nested<T: AbcStruct>(value: &T, compound: &mut OProperty, name: &str) {
+    value.write_compound(compound.get_or_create_compound_child(name));
+}
+
+/// Mark child `name` as a geom param with the given `geoScope` (used by the derive).
+pub fn set_geo_scope(compound: &mut OProperty, name: &str, scope: &str) {
+    if let Some(child) = child_mut(compound, name) {
+        child.meta_data.set("isGeomParam", "true");
+        child.meta_data.set("geoScope", scope);
+    }
+}
+
+/// Mark every direct child of an `.arbGeomParams` compound as a geom param
+/// (constant scope unless a scope was already set).
+pub(crate) fn mark_geom_params(arb: &mut OProperty) {
+    if let OPropertyData::Compound(children) = &mut arb.data {
+        for child in children {
+            child.meta_data.set("isGeomParam", "true");
+            if child.meta_data.get("geoScope").is_none() {
+                child.meta_data.set("geoScope", "con");
+            }
+        }
+    }
+}
+
+/// Run `f` on `.geom/<name>` of a schema object.
+fn with_geom_compound<T>(
+    object: &IObject<'_>,
+    name: &str,
+    f: impl FnOnce(&ICompoundProperty<'_>) -> Result<T>,
+) -> Result<T> {
+    let props = object.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")
+        .ok_or_else(|| Error::PropertyNotFound(".geom".to_string()))?;
+    let geom = geom_prop.asCompound()
+        .ok_or_else(|| Error::invalid(".geom is not a compound"))?;
+    let prop = geom.getPropertyByName(name).ok_or_else(|| Error::PropertyNotFound(name.to_string()))?;
+    let compound = prop.asCompound()
+        .ok_or_else(|| Error::invalid(format!("{} is not a compound", name)))?;
+    f(&compound)
+}
+
+/// Read a typed struct from `.geom/.arbGeomParams` at sample `index`.
+pub fn read_arb<T: AbcStruct>(object: &IObject<'_>, index: usize) -> Result<T> {
+    with_geom_compound(object, ".arbGeomParams", |c| T::read_compound(c, index))
+}
+
+/// Read a typed struct from `.geom/.userProperties` at sample `index`.
+pub fn read_user<T: AbcStruct>(object: &IObject<'_>, index: usize) -> Result<T> {
+    with_geom_compound(object, ".userProperties", |c| T::read_compound(c, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_data_types() {
+        assert_eq!(<f32 as AbcPod>::DATA_TYPE, DataType::FLOAT32);
+        assert_eq!(<Mat4 as AbcPod>::DATA_TYPE.extent, 16);
+        assert_eq!(std::mem::size_of::<Vec3>(), <Vec3 as AbcPod>::DATA_TYPE.num_bytes());
+        assert_eq!(std::mem::size_of::<DMat4>(), <DMat4 as AbcPod>::DATA_TYPE.num_bytes());
+    }
+
+    #[test]
+    fn test_option_none_holds_previous() {
+        let mut compound = OProperty::compound(".userProperties");
+        Some(3i32).write_property(&mut compound, "lod");
+        None::<i32>.write_property(&mut compound, "lod");
+        None::<i32>.write_property(&mut compound, "missing");
+        assert_eq!(child_mut(&mut compound, "lod").map(|p| p.getNumSamples()), Some(1));
+        assert!(child_mut(&mut compound, "missing").is_none());
+        // A later value writes the held sample first, keeping its index
+        Some(4i32).write_property(&mut compound, "lod");
+        assert_eq!(child_mut(&mut compound, "lod").map(|p| p.getNumSamples()), Some(3));
+    }
+
+    #[test]
+    fn test_set_geo_scope() {
+        let mut arb = OProperty::compound(".arbGeomParams");
+        vec![1.0f32, 2.0].write_property(&mut arb, "mask");
+        7i32.write_property(&mut arb, "id");
+        set_geo_scope(&mut arb, "mask", "vtx");
+        mark_geom_params(&mut arb);
+        assert_eq!(child_mut(&mut arb, "mask").unwrap().meta_data.get("geoScope"), Some("vtx"));
+        assert_eq!(child_mut(&mut arb, "id").unwrap().meta_data.get("geoScope"), Some("con"));
+    }
+}
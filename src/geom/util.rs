@@ -3,8 +3,11 @@
 //! This module provides shared functionality to reduce code duplication
 //! across geometry schema implementations.
 
-use crate::abc::IObject;
-use crate::util::BBox3d;
+use crate::abc::{ICompoundProperty, IObject};
+use crate::core::{SampleSelector, TimeSampling, TopologyVariance};
+use crate::util::{BBox3d, Chrono, DataType, Result};
+
+use super::geom_param::{GeomParamSample, IGeomParam};
 
 // ============================================================================
 // Sample Count Helpers  
@@ -27,6 +30,40 @@ pub fn num_samples_from_positions(object: &IObject<'_>) -> usize {
     num_samples_from_property(object, "P")
 }
 
+/// Check if a scalar or array property of a schema compound (`.geom`,
+/// `.xform`) holds the same value at every sample (C++ `isConstant()`).
+///
+/// Writers store repeated samples once and keep the sample count, so this
+/// reads the changed-index range rather than the number of samples.
+/// Missing properties count as constant.
+pub fn property_is_constant(object: &IObject<'_>, compound: &str, prop_name: &str) -> bool {
+    let props = object.getProperties();
+    let Some(schema_prop) = props.getPropertyByName(compound) else { return true };
+    let Some(schema) = schema_prop.asCompound() else { return true };
+    let Some(prop) = schema.getPropertyByName(prop_name) else { return true };
+    if let Some(array) = prop.asArray() {
+        array.isConstant()
+    } else if let Some(scalar) = prop.asScalar() {
+        scalar.isConstant()
+    } else {
+        true
+    }
+}
+
+/// Mesh topology variance from the constancy of P, `.faceIndices` and
+/// `.faceCounts` (C++ `getTopologyVariance()`).
+pub fn mesh_topology_variance(object: &IObject<'_>) -> TopologyVariance {
+    let topology_constant = property_is_constant(object, ".geom", ".faceIndices")
+        && property_is_constant(object, ".geom", ".faceCounts");
+    if !topology_constant {
+        TopologyVariance::Heterogeneous
+    } else if property_is_constant(object, ".geom", "P") {
+        TopologyVariance::Static
+    } else {
+        TopologyVariance::Homogeneous
+    }
+}
+
 // ============================================================================
 // Arbitrary Geometry Parameters
 // ============================================================================
@@ -49,6 +86,79 @@ pub fn arb_geom_param_names(object: &IObject<'_>) -> Vec<String> {
     arb.getPropertyNames()
 }
 
+/// Read a sample of an arbitrary geometry parameter by name.
+/// Indexed params keep their indices (see `GeomParamSample::expand_f32`).
+/// The selector resolves against the param's own time sampling and sample count.
+pub fn read_arb_geom_param(object: &IObject<'_>, name: &str, sel: impl Into<SampleSelector>) -> Option<GeomParamSample> {
+    let props = object.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    let arb_prop = geom.getPropertyByName(".arbGeomParams")?;
+    let arb = arb_prop.asCompound()?;
+    let param = IGeomParam::new(&arb, name)?;
+    param.getSample(sel).ok()
+}
+
+/// Names of all UV sets: the primary `uv` set first, then every other
+/// float2 param in `.arbGeomParams` (Maya writes extra sets there).
+pub fn uv_set_names(object: &IObject<'_>) -> Vec<String> {
+    let mut names = Vec::new();
+    if has_geom_property(object, "uv") {
+        names.push("uv".to_string());
+    }
+    let props = object.getProperties();
+    let Some(geom_prop) = props.getPropertyByName(".geom") else { return names };
+    let Some(geom) = geom_prop.asCompound() else { return names };
+    let Some(arb_prop) = geom.getPropertyByName(".arbGeomParams") else { return names };
+    let Some(arb) = arb_prop.asCompound() else { return names };
+    for name in arb.getPropertyNames() {
+        let is_vec2f = IGeomParam::new(&arb, &name).is_some_and(|p| p.data_type() == DataType::VEC2F);
+        if !is_vec2f || names.contains(&name) {
+            continue;
+        }
+        // Our writer stores the primary set as `.arbGeomParams/uv`.
+        if name == "uv" {
+            names.insert(0, name);
+        } else {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Read UV set `name` (see [`uv_set_names`]) with indices resolved.
+/// Values keep the param's own scope (usually face-varying).
+/// The selector resolves against the param's own time sampling and sample count.
+pub fn read_uv_set(object: &IObject<'_>, name: &str, sel: impl Into<SampleSelector>) -> Option<Vec<glam::Vec2>> {
+    let sel = sel.into();
+    let props = object.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    let read = |parent: &ICompoundProperty<'_>| -> Option<Vec<glam::Vec2>> {
+        let param = IGeomParam::new(parent, name)?.into_typed::<glam::Vec2>().ok()?;
+        param.get_values(sel).ok()
+    };
+    if name == "uv" {
+        if let Some(uvs) = read(&geom) {
+            return Some(uvs);
+        }
+    }
+    let arb_prop = geom.getPropertyByName(".arbGeomParams")?;
+    let arb = arb_prop.asCompound()?;
+    read(&arb)
+}
+
+/// Get the `arrayExtent` (values per point) of an arbitrary geometry parameter.
+pub fn arb_geom_param_array_extent(object: &IObject<'_>, name: &str) -> Option<usize> {
+    let props = object.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    let arb_prop = geom.getPropertyByName(".arbGeomParams")?;
+    let arb = arb_prop.asCompound()?;
+    let param = IGeomParam::new(&arb, name)?;
+    Some(param.array_extent())
+}
+
 // ============================================================================
 // User Properties
 // ============================================================================
@@ -71,6 +181,31 @@ pub fn user_property_names(object: &IObject<'_>) -> Vec<String> {
     user.getPropertyNames()
 }
 
+/// Read a user property sample (scalar or array) as raw bytes with its type.
+/// The selector resolves against the property's own time sampling and sample count.
+pub fn read_user_property(object: &IObject<'_>, name: &str, sel: impl Into<SampleSelector>) -> Option<(DataType, Vec<u8>)> {
+    let sel = sel.into();
+    let props = object.getProperties();
+    let geom_prop = props.getPropertyByName(".geom")?;
+    let geom = geom_prop.asCompound()?;
+    let user_prop = geom.getPropertyByName(".userProperties")?;
+    let user = user_prop.asCompound()?;
+    let prop = user.getPropertyByName(name)?;
+    let data_type = prop.getHeader().data_type;
+    if let Some(array) = prop.asArray() {
+        return Some((data_type, array.getSampleVec(array.getSampleIndex(sel)).ok()?));
+    }
+    let scalar = prop.asScalar()?;
+    // Strings are variable-length
+    if data_type.pod.is_string() {
+        let index = scalar.getSampleIndex(sel);
+        return Some((data_type, scalar.getSampleVec(index).ok()?));
+    }
+    let mut buf = vec![0u8; data_type.num_bytes()];
+    scalar.getSample(scalar.getSampleIndex(sel), &mut buf).ok()?;
+    Some((data_type, buf))
+}
+
 // ============================================================================
 // Bounds Helpers
 // ============================================================================
@@ -91,8 +226,8 @@ pub fn has_child_bounds(object: &IObject<'_>) -> bool {
     geom.hasProperty(".childBnds")
 }
 
-/// Read child bounds at a given sample index.
-pub fn read_child_bounds(object: &IObject<'_>, index: usize) -> Option<BBox3d> {
+/// Read child bounds at a sample index or time.
+pub fn read_child_bounds(object: &IObject<'_>, sel: impl Into<SampleSelector>) -> Option<BBox3d> {
     let props = object.getProperties();
     let geom_prop = props.getPropertyByName(".geom")?;
     let geom = geom_prop.asCompound()?;
@@ -100,7 +235,7 @@ pub fn read_child_bounds(object: &IObject<'_>, index: usize) -> Option<BBox3d> {
     let scalar = bnds_prop.asScalar()?;
     
     let mut buf = [0u8; 48]; // 6 x f64
-    scalar.getSample(index, &mut buf).ok()?;
+    scalar.getSample(scalar.getSampleIndex(sel.into()), &mut buf).ok()?;
     
     let doubles: &[f64] = bytemuck::try_cast_slice(&buf).ok()?;
     if doubles.len() >= 6 {
@@ -184,48 +319,73 @@ pub fn geom_property_names(object: &IObject<'_>) -> Vec<String> {
 // Array Property Reading Helpers
 // ============================================================================
 
-use crate::core::CompoundPropertyReader;
+use crate::core::{ArrayPropertyReader, CompoundPropertyReader};
+
+/// Convert a sample's bytes in place (borrowed from the sample cache, no
+/// temporary copy).
+///
+/// `sel` resolves against the array's own time sampling and sample count, so
+/// a property written at another rate, or with fewer samples than its
+/// schema, picks its own matching (or last) sample.
+fn convert_sample<T>(
+    array: &dyn ArrayPropertyReader,
+    sel: SampleSelector,
+    convert: impl FnOnce(&[u8]) -> Option<T>,
+) -> Option<T> {
+    let index = array.getSampleIndex(sel);
+    let mut convert = Some(convert);
+    let mut out = None;
+    array.withSample(index, &mut |bytes| out = convert.take().and_then(|c| c(bytes))).ok()?;
+    out
+}
+
+fn vec3s_from_bytes(data: &[u8]) -> Option<Vec<glam::Vec3>> {
+    let floats: &[f32] = bytemuck::try_cast_slice(data).ok()?;
+    Some(floats.chunks_exact(3).map(|c| glam::vec3(c[0], c[1], c[2])).collect())
+}
+
+fn vec2s_from_bytes(data: &[u8]) -> Option<Vec<glam::Vec2>> {
+    let floats: &[f32] = bytemuck::try_cast_slice(data).ok()?;
+    Some(floats.chunks_exact(2).map(|c| glam::vec2(c[0], c[1])).collect())
+}
+
+fn pods_from_bytes<T: bytemuck::Pod>(data: &[u8]) -> Option<Vec<T>> {
+    Some(bytemuck::try_cast_slice::<u8, T>(data).ok()?.to_vec())
+}
 
 /// Read a Vec3 array property from a compound.
 /// Handles both simple arrays and GeomParam compounds (with .vals inside).
 pub fn read_vec3_array(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<Vec<glam::Vec3>> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
     
     // Try as GeomParam compound first (has .vals inside)
     if let Some(compound) = prop.asCompound() {
         if let Some(vals_prop) = compound.getPropertyByName(".vals") {
             if let Some(array) = vals_prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
-                    if let Ok(floats) = bytemuck::try_cast_slice::<u8, f32>(&data) {
-                        return Some(floats.chunks_exact(3)
-                            .map(|c| glam::vec3(c[0], c[1], c[2]))
-                            .collect());
-                    }
+                if let Some(values) = convert_sample(array, sel, vec3s_from_bytes) {
+                    return Some(values);
                 }
             }
         }
     }
     
     // Fall back to simple array
-    let array = prop.asArray()?;
-    let data = array.getSampleVec(index).ok()?;
-    let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
-    Some(floats.chunks_exact(3)
-        .map(|c| glam::vec3(c[0], c[1], c[2]))
-        .collect())
+    convert_sample(prop.asArray()?, sel, vec3s_from_bytes)
 }
 
 /// Read an optional Vec3 array (doesn't error if missing).
 pub fn read_vec3_array_opt(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<Vec<glam::Vec3>> {
-    read_vec3_array(geom, prop_name, index)
+    let sel = sel.into();
+    read_vec3_array(geom, prop_name, sel)
 }
 
 /// Check if a property is a simple array (not a GeomParam compound).
@@ -247,93 +407,97 @@ pub fn is_simple_array(
 pub fn read_vec2_array(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<Vec<glam::Vec2>> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
     
     // Try as GeomParam compound first (has .vals inside)
     if let Some(compound) = prop.asCompound() {
         if let Some(vals_prop) = compound.getPropertyByName(".vals") {
             if let Some(array) = vals_prop.asArray() {
-                if let Ok(data) = array.getSampleVec(index) {
-                    if let Ok(floats) = bytemuck::try_cast_slice::<u8, f32>(&data) {
-                        return Some(floats.chunks_exact(2)
-                            .map(|c| glam::vec2(c[0], c[1]))
-                            .collect());
-                    }
+                if let Some(values) = convert_sample(array, sel, vec2s_from_bytes) {
+                    return Some(values);
                 }
             }
         }
     }
     
     // Fall back to simple array
-    let array = prop.asArray()?;
-    let data = array.getSampleVec(index).ok()?;
-    let floats: &[f32] = bytemuck::try_cast_slice(&data).ok()?;
-    Some(floats.chunks_exact(2)
-        .map(|c| glam::vec2(c[0], c[1]))
-        .collect())
+    convert_sample(prop.asArray()?, sel, vec2s_from_bytes)
 }
 
 /// Read an i32 array property from a compound.
 pub fn read_i32_array(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<Vec<i32>> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
-    let array = prop.asArray()?;
-    let data = array.getSampleVec(index).ok()?;
-    Some(bytemuck::try_cast_slice::<u8, i32>(&data).ok()?.to_vec())
+    convert_sample(prop.asArray()?, sel, pods_from_bytes::<i32>)
 }
 
 /// Read an f32 array property from a compound.
 pub fn read_f32_array(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<Vec<f32>> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
-    let array = prop.asArray()?;
-    let data = array.getSampleVec(index).ok()?;
-    Some(bytemuck::try_cast_slice::<u8, f32>(&data).ok()?.to_vec())
+    convert_sample(prop.asArray()?, sel, pods_from_bytes::<f32>)
 }
 
 /// Read a u64 array property from a compound.
 pub fn read_u64_array(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<Vec<u64>> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
-    let array = prop.asArray()?;
-    let data = array.getSampleVec(index).ok()?;
-    Some(bytemuck::try_cast_slice::<u8, u64>(&data).ok()?.to_vec())
+    convert_sample(prop.asArray()?, sel, pods_from_bytes::<u64>)
 }
 
 /// Read an i32 scalar property from a compound.
 pub fn read_i32_scalar(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<i32> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
     let scalar = prop.asScalar()?;
     let mut buf = [0u8; 4];
-    scalar.getSample(index, &mut buf).ok()?;
+    scalar.getSample(scalar.getSampleIndex(sel), &mut buf).ok()?;
     Some(i32::from_le_bytes(buf))
 }
 
+/// Read a string scalar property from a compound.
+pub fn read_string_scalar(
+    geom: &dyn CompoundPropertyReader,
+    prop_name: &str,
+    sel: impl Into<SampleSelector>,
+) -> Option<String> {
+    let sel = sel.into();
+    let prop = geom.getPropertyByName(prop_name)?;
+    let scalar = prop.asScalar()?;
+    let data = scalar.getSampleVec(scalar.getSampleIndex(sel)).ok()?;
+    String::from_utf8(data).ok()
+}
+
 /// Read self bounds (.selfBnds) from a .geom compound.
 pub fn read_self_bounds(
     geom: &dyn CompoundPropertyReader,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<BBox3d> {
+    let sel = sel.into();
     let bnds_prop = geom.getPropertyByName(".selfBnds")?;
     let scalar = bnds_prop.asScalar()?;
     
     let mut buf = [0u8; 48]; // 6 x f64
-    scalar.getSample(index, &mut buf).ok()?;
+    scalar.getSample(scalar.getSampleIndex(sel), &mut buf).ok()?;
     
     let doubles: &[f64] = bytemuck::try_cast_slice(&buf).ok()?;
     if doubles.len() >= 6 {
@@ -351,16 +515,17 @@ pub fn read_self_bounds(
 pub fn read_indexed_vec3(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<(Vec<glam::Vec3>, Option<Vec<i32>>)> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
     let compound = prop.asCompound()?;
     
     // Read values
-    let vals = read_vec3_array(compound, ".vals", index)?;
+    let vals = read_vec3_array(compound, ".vals", sel)?;
     
     // Read optional indices
-    let indices = read_i32_array(compound, ".indices", index);
+    let indices = read_i32_array(compound, ".indices", sel);
     
     Some((vals, indices))
 }
@@ -370,16 +535,17 @@ pub fn read_indexed_vec3(
 pub fn read_indexed_vec2(
     geom: &dyn CompoundPropertyReader,
     prop_name: &str,
-    index: usize,
+    sel: impl Into<SampleSelector>,
 ) -> Option<(Vec<glam::Vec2>, Option<Vec<i32>>)> {
+    let sel = sel.into();
     let prop = geom.getPropertyByName(prop_name)?;
     let compound = prop.asCompound()?;
     
     // Read values
-    let vals = read_vec2_array(compound, ".vals", index)?;
+    let vals = read_vec2_array(compound, ".vals", sel)?;
     
     // Read optional indices
-    let indices = read_i32_array(compound, ".indices", index);
+    let indices = read_i32_array(compound, ".indices", sel);
     
     Some((vals, indices))
 }
@@ -406,6 +572,55 @@ pub fn compute_bounds_vec3(positions: &[glam::Vec3]) -> (glam::Vec3, glam::Vec3)
     (min, max)
 }
 
+// ============================================================================
+// Sub-frame Interpolation
+// ============================================================================
+
+/// Read the two samples bracketing `time` and blend them with `blend`.
+///
+/// Exact hits (or a single sample) read one sample and skip the blend.
+pub fn interpolate_samples<T>(
+    ts: &TimeSampling,
+    num_samples: usize,
+    time: Chrono,
+    read: impl Fn(usize) -> Result<T>,
+    blend: impl FnOnce(&T, &T, f32) -> T,
+) -> Result<T> {
+    let (floor, ceil, alpha) = ts.get_bracketing_time_samples(time, num_samples);
+    if floor == ceil || alpha <= 1e-9 {
+        return read(floor);
+    }
+    if alpha >= 1.0 - 1e-9 {
+        return read(ceil);
+    }
+    let (a, b) = (read(floor)?, read(ceil)?);
+    Ok(blend(&a, &b, alpha as f32))
+}
+
+/// Linearly blend two Vec3 arrays; `None` if their lengths differ.
+pub fn lerp_vec3_array(a: &[glam::Vec3], b: &[glam::Vec3], t: f32) -> Option<Vec<glam::Vec3>> {
+    (a.len() == b.len()).then(|| a.iter().zip(b).map(|(a, b)| a.lerp(*b, t)).collect())
+}
+
+/// Linearly blend two optional bounding boxes.
+pub fn lerp_bounds(a: Option<BBox3d>, b: Option<BBox3d>, t: f32) -> Option<BBox3d> {
+    let t = t as f64;
+    match (a, b) {
+        (Some(a), Some(b)) => Some(BBox3d::new(a.min.lerp(b.min, t), a.max.lerp(b.max, t))),
+        (a, b) => if t < 0.5 { a } else { b },
+    }
+}
+
+/// Blend two transforms by decomposing into scale/rotation/translation,
+/// slerping the rotation and lerping the rest.
+///
+/// Avoids the shearing and shrinking of a plain component-wise matrix lerp.
+pub fn lerp_matrix(a: glam::Mat4, b: glam::Mat4, t: f32) -> glam::Mat4 {
+    let (sa, ra, ta) = a.to_scale_rotation_translation();
+    let (sb, rb, tb) = b.to_scale_rotation_translation();
+    glam::Mat4::from_scale_rotation_translation(sa.lerp(sb, t), ra.slerp(rb, t), ta.lerp(tb, t))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,6 +640,34 @@ mod tests {
         assert_eq!(max, glam::vec3(1.0, 2.0, 3.0));
     }
     
+    #[test]
+    fn test_lerp_matrix_slerps_rotation() {
+        let a = glam::Mat4::from_translation(glam::vec3(0.0, 0.0, 0.0));
+        let b = glam::Mat4::from_scale_rotation_translation(
+            glam::Vec3::splat(3.0),
+            glam::Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            glam::vec3(10.0, 0.0, 0.0),
+        );
+        let m = lerp_matrix(a, b, 0.5);
+        let (s, r, t) = m.to_scale_rotation_translation();
+        assert!((s - glam::Vec3::splat(2.0)).length() < 1e-5);
+        assert!((t - glam::vec3(5.0, 0.0, 0.0)).length() < 1e-5);
+        // Half of a 90 degree turn, not a shrunk matrix
+        assert!((r.angle_between(glam::Quat::IDENTITY) - std::f32::consts::FRAC_PI_4).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_interpolate_samples() {
+        let ts = TimeSampling::uniform(1.0, 0.0);
+        let read = |i: usize| Ok(i as f32 * 10.0);
+        let lerp = |a: &f32, b: &f32, t: f32| a + (b - a) * t;
+        assert_eq!(interpolate_samples(&ts, 3, 0.25, read, lerp).unwrap(), 2.5);
+        assert_eq!(interpolate_samples(&ts, 3, 2.0, read, lerp).unwrap(), 20.0);
+        assert_eq!(interpolate_samples(&ts, 3, 9.0, read, lerp).unwrap(), 20.0);
+        assert_eq!(interpolate_samples(&ts, 3, -1.0, read, lerp).unwrap(), 0.0);
+        assert_eq!(lerp_vec3_array(&[glam::Vec3::ZERO], &[], 0.5), None);
+    }
+
     #[test]
     fn test_compute_bounds_multiple() {
         let positions = vec![
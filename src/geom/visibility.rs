@@ -111,11 +111,7 @@ pub fn get_visibility(obj: &IObject<'_>, sel: impl Into<SampleSelector>) -> Obje
         if prop.isScalar() {
             if let Some(scalar) = prop.asScalar() {
                 let mut buf = [0u8; 1];
-                let index = match sel {
-                    SampleSelector::Index(i) => i,
-                    _ => 0,
-                };
-                if scalar.getSample(index, &mut buf).is_ok() {
+                if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                     return ObjectVisibility::from_i8(buf[0] as i8);
                 }
             }
@@ -0,0 +1,278 @@
+//! Face winding consistency.
+//!
+//! Two faces sharing an edge are wound consistently when they walk that
+//! edge in opposite directions; otherwise one of them has its normal
+//! flipped. Exporters with broken "reverse normals" options produce meshes
+//! where patches of faces disagree with their neighbours, which shows up as
+//! black faces and inverted displacement.
+//!
+//! [`analyze_winding`] propagates orientation across manifold edges (shared
+//! by exactly two faces) and reports, per connected component, the faces
+//! wound against the majority. [`reverse_faces`] rewrites face-vertex data
+//! for those faces, so repairing a mesh is reversing its `faceIndices` and
+//! every face-varying GeomParam with the same face list
+//! ([`PolyMeshSample::fix_winding`] does both for a sample).
+//!
+//! Only consistency is checked: a mesh wound uniformly inside out is
+//! consistent, and a non-orientable surface (a Möbius strip) can't be made
+//! consistent; such components are reported and left alone.
+
+use std::collections::VecDeque;
+
+use super::polymesh::PolyMeshSample;
+
+/// Result of [`analyze_winding`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindingReport {
+    /// Faces with at least 3 vertices that took part in the analysis.
+    pub faces: usize,
+    /// Edge-connected components of those faces.
+    pub components: usize,
+    /// Faces wound against the majority of their component, ascending.
+    pub flipped_faces: Vec<usize>,
+    /// Edges shared by more than two faces (ignored for propagation).
+    pub non_manifold_edges: usize,
+    /// Components whose faces can't all agree (left out of `flipped_faces`).
+    pub non_orientable_components: usize,
+}
+
+impl WindingReport {
+    /// No face disagrees with its neighbours.
+    pub fn is_consistent(&self) -> bool {
+        self.flipped_faces.is_empty() && self.non_orientable_components == 0
+    }
+}
+
+/// Check that the faces of a mesh are wound consistently across shared edges.
+///
+/// `face_counts`/`face_indices` are the PolyMesh/SubD topology arrays. Faces
+/// with fewer than 3 vertices, negative counts, or past the end of
+/// `face_indices` are skipped.
+pub fn analyze_winding(face_counts: &[i32], face_indices: &[i32]) -> WindingReport {
+    let mut report = WindingReport::default();
+
+    // Directed edges as (undirected key, face, walks low -> high)
+    let mut edges: Vec<(u64, u32, bool)> = Vec::with_capacity(face_indices.len());
+    let mut start = 0usize;
+    for (face, &count) in face_counts.iter().enumerate() {
+        let n = count.max(0) as usize;
+        let end = start + n;
+        if n >= 3 && end <= face_indices.len() {
+            report.faces += 1;
+            let verts = &face_indices[start..end];
+            for (k, &a) in verts.iter().enumerate() {
+                let b = verts[(k + 1) % n];
+                if a == b {
+                    continue;
+                }
+                let (lo, hi) = (a.min(b) as u32, a.max(b) as u32);
+                edges.push(((lo as u64) << 32 | hi as u64, face as u32, a < b));
+            }
+        }
+        start = end;
+    }
+    edges.sort_unstable();
+
+    // Adjacency over manifold edges: (face, neighbour, must flip relative to it)
+    let mut links: Vec<(u32, u32, bool)> = Vec::new();
+    for group in edges.chunk_by(|a, b| a.0 == b.0) {
+        match group {
+            [(_, fa, da), (_, fb, db)] if fa != fb => {
+                // Walking the shared edge the same way means opposite windings
+                links.push((*fa, *fb, da == db));
+                links.push((*fb, *fa, da == db));
+            }
+            [_] | [_, _] => {}
+            _ => report.non_manifold_edges += 1,
+        }
+    }
+    links.sort_unstable();
+    let mut first_link = vec![0usize; face_counts.len() + 1];
+    for &(face, _, _) in &links {
+        first_link[face as usize + 1] += 1;
+    }
+    for i in 0..face_counts.len() {
+        first_link[i + 1] += first_link[i];
+    }
+
+    // Breadth-first orientation per component: flip[f] relative to its root
+    let mut flip: Vec<Option<bool>> = vec![None; face_counts.len()];
+    let mut queue = VecDeque::new();
+    let mut start = 0usize;
+    for (root, &count) in face_counts.iter().enumerate() {
+        let n = count.max(0) as usize;
+        let usable = n >= 3 && start + n <= face_indices.len();
+        start += n;
+        if !usable || flip[root].is_some() {
+            continue;
+        }
+        report.components += 1;
+        flip[root] = Some(false);
+        queue.push_back(root);
+        let mut members = vec![root];
+        let mut orientable = true;
+        while let Some(face) = queue.pop_front() {
+            let here = flip[face] == Some(true);
+            for &(_, next, must_flip) in &links[first_link[face]..first_link[face + 1]] {
+                let next = next as usize;
+                let want = here ^ must_flip;
+                match flip[next] {
+                    None => {
+                        flip[next] = Some(want);
+                        members.push(next);
+                        queue.push_back(next);
+                    }
+                    Some(have) if have != want => orientable = false,
+                    Some(_) => {}
+                }
+            }
+        }
+        if !orientable {
+            report.non_orientable_components += 1;
+            continue;
+        }
+        let flipped = members.iter().filter(|&&f| flip[f] == Some(true)).count();
+        // Keep the majority; ties keep the root's winding
+        let root_is_minority = flipped * 2 > members.len();
+        report.flipped_faces.extend(members.into_iter().filter(|&f| flip[f] == Some(!root_is_minority)));
+    }
+    report.flipped_faces.sort_unstable();
+    report
+}
+
+/// Reverse the winding of `faces` in face-vertex ordered `values`, where
+/// each face-vertex occupies `stride` consecutive elements (1 for
+/// `faceIndices`, the element size for raw bytes).
+///
+/// The first vertex of a face stays first and the rest are reversed, so
+/// `[a, b, c, d]` becomes `[a, d, c, b]`; apply the same call to
+/// `faceIndices` and to every face-varying GeomParam (values or indices).
+/// Faces past the end of `values` are skipped.
+pub fn reverse_faces<T>(face_counts: &[i32], values: &mut [T], stride: usize, faces: &[usize]) {
+    let mut offsets = Vec::with_capacity(face_counts.len());
+    let mut start = 0usize;
+    for &count in face_counts {
+        offsets.push(start);
+        start += count.max(0) as usize;
+    }
+    for &face in faces {
+        let (Some(&first), Some(&count)) = (offsets.get(face), face_counts.get(face)) else { continue };
+        let n = count.max(0) as usize;
+        if n < 3 || (first + n) * stride > values.len() {
+            continue;
+        }
+        // Vertices 1..n reversed, each a block of `stride` elements
+        let (mut i, mut j) = (first + 1, first + n - 1);
+        while i < j {
+            for k in 0..stride {
+                values.swap(i * stride + k, j * stride + k);
+            }
+            i += 1;
+            j -= 1;
+        }
+    }
+}
+
+impl PolyMeshSample {
+    /// Check the winding of this sample's faces (see [`analyze_winding`]).
+    pub fn winding_report(&self) -> WindingReport {
+        analyze_winding(&self.face_counts, &self.face_indices)
+    }
+
+    /// Rewind faces that disagree with their neighbours, along with
+    /// face-varying UVs and normals. Returns the number of faces flipped.
+    pub fn fix_winding(&mut self) -> usize {
+        let faces = self.winding_report().flipped_faces;
+        if faces.is_empty() {
+            return 0;
+        }
+        let face_vertices = self.face_indices.len();
+        reverse_faces(&self.face_counts, &mut self.face_indices, 1, &faces);
+        if let Some(uvs) = self.uvs.as_mut().filter(|v| v.len() == face_vertices) {
+            reverse_faces(&self.face_counts, uvs, 1, &faces);
+        }
+        if let Some(normals) = self.normals.as_mut().filter(|v| v.len() == face_vertices) {
+            reverse_faces(&self.face_counts, normals, 1, &faces);
+        }
+        faces.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit cube as 6 quads, all wound the same way.
+    fn cube() -> (Vec<i32>, Vec<i32>) {
+        let indices = vec![
+            0, 1, 3, 2, // -z
+            4, 6, 7, 5, // +z
+            0, 4, 5, 1, // -y
+            2, 3, 7, 6, // +y
+            0, 2, 6, 4, // -x
+            1, 5, 7, 3, // +x
+        ];
+        (vec![4; 6], indices)
+    }
+
+    #[test]
+    fn test_consistent_cube() {
+        let (counts, indices) = cube();
+        let report = analyze_winding(&counts, &indices);
+        assert!(report.is_consistent(), "{report:?}");
+        assert_eq!((report.faces, report.components, report.non_manifold_edges), (6, 1, 0));
+    }
+
+    #[test]
+    fn test_flipped_face_detected_and_fixed() {
+        let (counts, mut indices) = cube();
+        reverse_faces(&counts, &mut indices, 1, &[3]);
+        assert_eq!(&indices[12..16], &[2, 6, 7, 3]);
+        let report = analyze_winding(&counts, &indices);
+        assert_eq!(report.flipped_faces, vec![3]);
+
+        reverse_faces(&counts, &mut indices, 1, &report.flipped_faces);
+        assert_eq!(indices, cube().1);
+
+        // Face-varying data follows the faces: stride 2 keeps pairs together
+        let mut uv: Vec<f32> = (0..8).map(|v| v as f32).collect();
+        reverse_faces(&[4], &mut uv, 2, &[0]);
+        assert_eq!(uv, vec![0.0, 1.0, 6.0, 7.0, 4.0, 5.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_sample_fix_winding() {
+        // Two triangles sharing edge 1-2, the second wound backwards
+        let mut sample = PolyMeshSample {
+            positions: vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y, glam::Vec3::ONE],
+            face_counts: vec![3, 3],
+            face_indices: vec![0, 1, 2, 1, 2, 3],
+            uvs: Some(vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::Y, glam::Vec2::X, glam::Vec2::Y, glam::Vec2::ONE]),
+            ..Default::default()
+        };
+        let report = sample.winding_report();
+        assert_eq!((report.components, report.flipped_faces.len()), (1, 1));
+        assert_eq!(sample.fix_winding(), 1);
+        assert!(sample.winding_report().is_consistent());
+        let second = &sample.face_indices[3..];
+        let uvs = &sample.uvs.as_ref().unwrap()[3..];
+        // Each corner keeps its UV
+        let corner_uv = |v: i32| uvs[second.iter().position(|&i| i == v).unwrap()];
+        assert_eq!((corner_uv(1), corner_uv(2), corner_uv(3)), (glam::Vec2::X, glam::Vec2::Y, glam::Vec2::ONE));
+    }
+
+    #[test]
+    fn test_non_orientable_strip() {
+        // Möbius strip: a ring of quads closed with a half twist
+        let n = 6i32;
+        let mut indices = Vec::new();
+        for i in 0..n {
+            let (a, b) = (i, i + n);
+            let (c, d) = if i + 1 == n { (0, n) } else { (i + 1 + n, i + 1) };
+            indices.extend([a, d, c, b]);
+        }
+        let report = analyze_winding(&vec![4; n as usize], &indices);
+        assert_eq!(report.non_orientable_components, 1);
+        assert!(report.flipped_faces.is_empty());
+    }
+}
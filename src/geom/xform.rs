@@ -3,7 +3,9 @@
 //! Provides reading of transform data from Alembic files.
 
 use crate::abc::IObject;
-use crate::util::Result;
+use crate::core::{SampleSelector, TimeSampling};
+use crate::geom::util as geom_util;
+use crate::util::{Chrono, Result};
 
 /// Xform schema identifier.
 pub const XFORM_SCHEMA: &str = "AbcGeom_Xform_v3";
@@ -25,37 +27,98 @@ pub enum XformOpType {
 pub struct XformOp {
     pub op_type: XformOpType,
     pub values: Vec<f64>,
+    /// DCC hint (low nibble of the op encoding), e.g. [`XformOp::ROTATE_PIVOT_POINT_HINT`].
+    pub hint: u8,
 }
 
 impl XformOp {
+    /// Translate hints (C++ `TranslateHint`).
+    pub const TRANSLATE_HINT: u8 = 0;
+    pub const SCALE_PIVOT_POINT_HINT: u8 = 1;
+    pub const SCALE_PIVOT_TRANSLATION_HINT: u8 = 2;
+    pub const ROTATE_PIVOT_POINT_HINT: u8 = 3;
+    pub const ROTATE_PIVOT_TRANSLATION_HINT: u8 = 4;
+    /// Rotate hints (C++ `RotateHint`).
+    pub const ROTATE_HINT: u8 = 0;
+    pub const ROTATE_ORIENTATION_HINT: u8 = 1;
+    /// Matrix hints (C++ `MatrixHint`).
+    pub const MATRIX_HINT: u8 = 0;
+    pub const MAYA_SHEAR_HINT: u8 = 1;
+
     /// Create a scale operation.
     pub fn scale(x: f64, y: f64, z: f64) -> Self {
-        Self { op_type: XformOpType::Scale, values: vec![x, y, z] }
+        Self { op_type: XformOpType::Scale, values: vec![x, y, z], hint: 0 }
     }
     
     /// Create a translate operation.
     pub fn translate(x: f64, y: f64, z: f64) -> Self {
-        Self { op_type: XformOpType::Translate, values: vec![x, y, z] }
+        Self { op_type: XformOpType::Translate, values: vec![x, y, z], hint: 0 }
     }
     
     /// Create a rotation around X axis (angle in degrees).
     pub fn rotate_x(angle: f64) -> Self {
-        Self { op_type: XformOpType::RotateX, values: vec![angle] }
+        Self { op_type: XformOpType::RotateX, values: vec![angle], hint: 0 }
     }
     
     /// Create a rotation around Y axis (angle in degrees).
     pub fn rotate_y(angle: f64) -> Self {
-        Self { op_type: XformOpType::RotateY, values: vec![angle] }
+        Self { op_type: XformOpType::RotateY, values: vec![angle], hint: 0 }
     }
     
     /// Create a rotation around Z axis (angle in degrees).
     pub fn rotate_z(angle: f64) -> Self {
-        Self { op_type: XformOpType::RotateZ, values: vec![angle] }
+        Self { op_type: XformOpType::RotateZ, values: vec![angle], hint: 0 }
+    }
+
+    /// Create a rotation around an arbitrary axis (angle in degrees).
+    pub fn rotate(axis: [f64; 3], angle: f64) -> Self {
+        Self { op_type: XformOpType::Rotate, values: vec![axis[0], axis[1], axis[2], angle], hint: 0 }
     }
     
     /// Create a 4x4 matrix operation.
     pub fn matrix(m: [f64; 16]) -> Self {
-        Self { op_type: XformOpType::Matrix, values: m.to_vec() }
+        Self { op_type: XformOpType::Matrix, values: m.to_vec(), hint: 0 }
+    }
+
+    /// Set the DCC hint (only the low 4 bits are stored).
+    pub fn with_hint(mut self, hint: u8) -> Self {
+        self.hint = hint & 0xF;
+        self
+    }
+
+    /// Maya-style attribute name of the op from its type and hint,
+    /// e.g. `rotatePivot` or `rotateAxis`.
+    pub fn name(&self) -> &'static str {
+        match (self.op_type, self.hint) {
+            (XformOpType::Translate, Self::SCALE_PIVOT_POINT_HINT) => "scalePivot",
+            (XformOpType::Translate, Self::SCALE_PIVOT_TRANSLATION_HINT) => "scalePivotTranslate",
+            (XformOpType::Translate, Self::ROTATE_PIVOT_POINT_HINT) => "rotatePivot",
+            (XformOpType::Translate, Self::ROTATE_PIVOT_TRANSLATION_HINT) => "rotatePivotTranslate",
+            (XformOpType::Translate, _) => "translate",
+            (XformOpType::Scale, _) => "scale",
+            (XformOpType::Matrix, Self::MAYA_SHEAR_HINT) => "shear",
+            (XformOpType::Matrix, _) => "matrix",
+            (_, Self::ROTATE_ORIENTATION_HINT) => "rotateAxis",
+            _ => "rotate",
+        }
+    }
+
+    /// Name of channel `index`, e.g. `translateX`, `rotateY`, `rotateAngle`
+    /// or `matrix[3][0]`.
+    pub fn channel_name(&self, index: usize) -> String {
+        const XYZ: [&str; 3] = ["X", "Y", "Z"];
+        let name = self.name();
+        match self.op_type {
+            XformOpType::Scale | XformOpType::Translate => format!("{}{}", name, XYZ.get(index).unwrap_or(&"?")),
+            XformOpType::RotateX => format!("{}X", name),
+            XformOpType::RotateY => format!("{}Y", name),
+            XformOpType::RotateZ => format!("{}Z", name),
+            XformOpType::Rotate => {
+                let suffix = ["AxisX", "AxisY", "AxisZ", "Angle"].get(index).unwrap_or(&"?");
+                format!("{}{}", name, suffix)
+            }
+            XformOpType::Matrix => format!("{}[{}][{}]", name, index / 4, index % 4),
+        }
     }
 }
 
@@ -170,6 +233,22 @@ impl XformSample {
         }
         glam::Vec3::ONE
     }
+
+    /// Blend towards `other` by `t` (0 = self, 1 = other).
+    ///
+    /// The two matrices are decomposed into scale/rotation/translation and
+    /// the rotation slerped, so the result is a single matrix op.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let nearest = if t < 0.5 { self } else { other };
+        let same_ops = self.ops.len() == other.ops.len()
+            && self.ops.iter().zip(&other.ops).all(|(a, b)| a.op_type == b.op_type && a.values == b.values);
+        if same_ops {
+            return nearest.clone();
+        }
+        let m = super::util::lerp_matrix(self.matrix(), other.matrix(), t);
+        let values = m.to_cols_array().map(|v| v as f64);
+        Self { ops: vec![XformOp::matrix(values)], inherits: nearest.inherits }
+    }
 }
 
 /// Input Xform schema reader.
@@ -239,7 +318,9 @@ impl<'a> IXform<'a> {
         }
         
         if let Some(vals_prop) = geom.getPropertyByName(".vals") {
-            if let Some(array_reader) = vals_prop.asArray() {
+            if let Some(scalar) = vals_prop.asScalar() {
+                max_samples = max_samples.max(scalar.getNumSamples());
+            } else if let Some(array_reader) = vals_prop.asArray() {
                 max_samples = max_samples.max(array_reader.getNumSamples());
             }
         }
@@ -254,9 +335,10 @@ impl<'a> IXform<'a> {
         max_samples
     }
     
-    /// Check if this xform is constant (single sample).
+    /// Check if this xform is constant (`.vals` and `.inherits` never change).
     pub fn isConstant(&self) -> bool {
-        self.getNumSamples() <= 1
+        geom_util::property_is_constant(self.object, ".xform", ".vals")
+            && geom_util::property_is_constant(self.object, ".xform", ".inherits")
     }
     
     /// Check if this xform is constant AND identity transform.
@@ -272,8 +354,12 @@ impl<'a> IXform<'a> {
         }
     }
     
-    /// Read a sample at the given index.
-    pub fn getSample(&self, index: usize) -> Result<XformSample> {
+    /// Read a sample by index or time.
+    ///
+    /// Each property resolves the selector against its own time sampling and
+    /// sample count, holding its last sample past the end.
+    pub fn getSample(&self, sel: impl Into<SampleSelector>) -> Result<XformSample> {
+        let sel = sel.into();
         use crate::util::Error;
         
         let props = self.object.getProperties();
@@ -289,9 +375,8 @@ impl<'a> IXform<'a> {
         if let Some(inh_prop) = geom.getPropertyByName(".inherits") {
             if let Some(scalar) = inh_prop.asScalar() {
                 let mut buf = [0u8; 1];
-                // Use the requested sample index (not 0)
-                let sample_idx = index.min(scalar.getNumSamples().saturating_sub(1));
-                if scalar.getSample(sample_idx, &mut buf).is_ok() {
+                // Use the requested sample (not 0)
+                if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                     sample.inherits = buf[0] != 0;
                 }
             }
@@ -324,7 +409,7 @@ impl<'a> IXform<'a> {
                     let num_vals = scalar.getHeader().data_type.extent as usize;
                     let byte_count = num_vals * 8; // f64 = 8 bytes
                     let mut buf = vec![0u8; byte_count];
-                    if scalar.getSample(index, &mut buf).is_ok() {
+                    if scalar.getSample(scalar.getSampleIndex(sel), &mut buf).is_ok() {
                         Some(buf)
                     } else {
                         None
@@ -334,7 +419,7 @@ impl<'a> IXform<'a> {
                 }
             } else if let Some(array_reader) = vals_prop.asArray() {
                 // Array property - size comes from the sample itself
-                array_reader.getSampleVec(index).ok()
+                array_reader.getSampleVec(array_reader.getSampleIndex(sel)).ok()
             } else {
                 None
             }
@@ -358,7 +443,7 @@ impl<'a> IXform<'a> {
                 val_idx += num_vals;
                 
                 if let Some(op_type) = op_type {
-                    sample.ops.push(XformOp { op_type, values });
+                    sample.ops.push(XformOp { op_type, values, hint: op_code & 0xF });
                 }
             }
         }
@@ -366,6 +451,30 @@ impl<'a> IXform<'a> {
         Ok(sample)
     }
     
+    /// Indices of the animated channels in the flattened op values
+    /// (`.animChans`, written by C++ Alembic and by [`OXform`]).
+    ///
+    /// Empty for static transforms and files written without the property.
+    ///
+    /// [`OXform`]: crate::ogawa::writer::OXform
+    pub fn animated_channels(&self) -> Vec<u32> {
+        let props = self.object.getProperties();
+        let Some(geom_prop) = props.getPropertyByName(".xform") else { return Vec::new() };
+        let Some(geom) = geom_prop.asCompound() else { return Vec::new() };
+        let Some(prop) = geom.getPropertyByName(".animChans") else { return Vec::new() };
+        let Some(array) = prop.asArray() else { return Vec::new() };
+        let data = array.getSampleVec(0).unwrap_or_default();
+        data.chunks_exact(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect()
+    }
+
+    /// Read the transform at an arbitrary time, blending the two bracketing
+    /// samples (see [`XformSample::lerp`]).
+    ///
+    /// Pass `archive.getTimeSampling(xform.getTimeSamplingIndex())`.
+    pub fn get_interpolated_sample(&self, time: Chrono, ts: &TimeSampling) -> Result<XformSample> {
+        super::util::interpolate_samples(ts, self.getNumSamples(), time, |i| self.getSample(i), |a, b, t| a.lerp(b, t))
+    }
+
     /// Check if this xform has child bounds property.
     pub fn has_child_bounds(&self) -> bool {
         let props = self.object.getProperties();
@@ -388,7 +497,7 @@ impl<'a> IXform<'a> {
         let scalar = bnds_prop.asScalar()?;
         
         let mut buf = [0u8; 48]; // 6 x f64
-        if scalar.getSample(index, &mut buf).is_ok() {
+        if scalar.getSample(scalar.getSampleIndex(index.into()), &mut buf).is_ok() {
             let doubles: &[f64] = bytemuck::try_cast_slice(&buf).ok()?;
             if doubles.len() >= 6 {
                 return Some(crate::util::BBox3d::new(
@@ -491,7 +600,7 @@ impl<'a> IXform<'a> {
 
 /// Decode xform operation code to type and number of values.
 /// Per XformOp.cpp: getOpEncoding() returns (m_type << 4) | (m_hint & 0xF)
-fn decode_xform_op(code: u8) -> (Option<XformOpType>, usize) {
+pub(crate) fn decode_xform_op(code: u8) -> (Option<XformOpType>, usize) {
     // Upper nibble = op type, lower nibble = hint
     let op_type = code >> 4;
     
@@ -562,4 +671,15 @@ mod tests {
         assert!((v.x).abs() < 0.0001);
         assert!((v.y - 1.0).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_xform_op_channel_names() {
+        let pivot = XformOp::translate(0.0, 0.0, 0.0).with_hint(XformOp::ROTATE_PIVOT_POINT_HINT);
+        assert_eq!(pivot.channel_name(2), "rotatePivotZ");
+        let axis = XformOp::rotate_x(0.0).with_hint(XformOp::ROTATE_ORIENTATION_HINT);
+        assert_eq!(axis.channel_name(0), "rotateAxisX");
+        assert_eq!(XformOp::rotate([0.0, 1.0, 0.0], 45.0).channel_name(3), "rotateAngle");
+        assert_eq!(XformOp::matrix([0.0; 16]).channel_name(13), "matrix[3][1]");
+        assert_eq!(XformOp::scale(1.0, 1.0, 1.0).with_hint(0x1F).hint, 0xF);
+    }
 }
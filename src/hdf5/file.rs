@@ -0,0 +1,1264 @@
+//! Read-only parser for the parts of the HDF5 container AbcCoreHDF5 uses.
+//!
+//! Covers both the HDF5 1.6/1.8 default format (version 0 superblock,
+//! symbol-table groups, version 1 object headers) and the "latest" format
+//! (version 2/3 superblock and object headers, link and attribute storage in
+//! fractal heaps indexed by version 2 B-trees). Datasets may be compact,
+//! contiguous or chunked (version 1 B-tree, single chunk, implicit or
+//! unpaged fixed array index) with deflate, shuffle and fletcher32 filters.
+//!
+//! Not supported, failing with an error when met: shared object header
+//! messages (committed datatypes), variable-length data, huge fractal heap
+//! objects, filtered fractal heaps, external and virtual storage, and the
+//! extensible array / version 2 B-tree chunk indexes of extendible datasets.
+//! Checksums are not verified.
+
+use std::collections::HashSet;
+use std::io::Read;
+
+use crate::util::{Error, Result};
+
+/// HDF5 "undefined address".
+const UNDEFINED: u64 = u64::MAX;
+
+/// Bound on object header chunks and B-tree nodes visited, against cycles.
+const MAX_NODES: usize = 1 << 20;
+
+// Object header message types
+const MSG_DATASPACE: u16 = 0x0001;
+const MSG_LINK_INFO: u16 = 0x0002;
+const MSG_DATATYPE: u16 = 0x0003;
+const MSG_LINK: u16 = 0x0006;
+const MSG_LAYOUT: u16 = 0x0008;
+const MSG_GROUP_INFO: u16 = 0x000a;
+const MSG_FILTERS: u16 = 0x000b;
+const MSG_ATTRIBUTE: u16 = 0x000c;
+const MSG_CONTINUATION: u16 = 0x0010;
+const MSG_SYMBOL_TABLE: u16 = 0x0011;
+const MSG_ATTRIBUTE_INFO: u16 = 0x0015;
+
+/// Datatype classes
+pub(crate) const CLASS_FIXED: u8 = 0;
+pub(crate) const CLASS_FLOAT: u8 = 1;
+pub(crate) const CLASS_STRING: u8 = 3;
+
+/// An HDF5 file held in memory.
+pub(crate) struct H5File<'a> {
+    data: &'a [u8],
+    /// Base address all file addresses are relative to
+    base: u64,
+    offset_size: usize,
+    length_size: usize,
+    root: u64,
+}
+
+/// Little-endian reader over a byte slice; `at` is the file offset of the
+/// slice start, for error messages.
+#[derive(Clone)]
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    at: u64,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8], at: u64) -> Self {
+        Self { buf, pos: 0, at }
+    }
+
+    fn offset(&self) -> u64 {
+        self.at + self.pos as u64
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.buf.len());
+        let end = end.ok_or_else(|| Error::corrupt(self.offset(), "HDF5 structure truncated"))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.bytes(n).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(self.uint(2)? as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(self.uint(4)? as u32)
+    }
+
+    /// `n`-byte little-endian unsigned integer (n <= 8).
+    fn uint(&mut self, n: usize) -> Result<u64> {
+        let bytes = self.bytes(n)?;
+        if n == 0 {
+            return Ok(0);
+        }
+        if n > 8 {
+            return Err(Error::corrupt(self.offset(), format!("HDF5 integer field of {} bytes", n)));
+        }
+        Ok(bytes.iter().rev().fold(0u64, |v, &b| (v << 8) | b as u64))
+    }
+
+    fn signature(&mut self, expected: &[u8; 4]) -> Result<()> {
+        let at = self.offset();
+        if self.bytes(4)? != expected {
+            return Err(Error::corrupt(
+                at,
+                format!("expected HDF5 {} block", String::from_utf8_lossy(expected)),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One object header message.
+struct Message<'a> {
+    kind: u16,
+    flags: u8,
+    /// Creation order, when the header tracks it
+    order: Option<u16>,
+    data: &'a [u8],
+    at: u64,
+}
+
+impl<'a> Message<'a> {
+    fn cursor(&self) -> Cursor<'a> {
+        Cursor::new(self.data, self.at)
+    }
+}
+
+/// A parsed object header (group or dataset).
+pub(crate) struct Object<'a> {
+    pub addr: u64,
+    messages: Vec<Message<'a>>,
+}
+
+impl<'a> Object<'a> {
+    fn message(&self, kind: u16) -> Option<&Message<'a>> {
+        self.messages.iter().find(|m| m.kind == kind)
+    }
+
+    /// Whether the object is a group (has link storage of some kind).
+    pub fn is_group(&self) -> bool {
+        self.messages.iter().any(|m| {
+            matches!(m.kind, MSG_LINK_INFO | MSG_LINK | MSG_GROUP_INFO | MSG_SYMBOL_TABLE)
+        })
+    }
+}
+
+/// A named link from a group to an object.
+pub(crate) struct Link {
+    pub name: String,
+    /// Object header address; `None` for soft and external links
+    pub addr: Option<u64>,
+    order: Option<u64>,
+}
+
+/// Element type of an attribute or dataset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Datatype {
+    pub class: u8,
+    pub size: usize,
+    big_endian: bool,
+}
+
+/// Attribute or dataset contents, byte-swapped to little endian.
+#[derive(Debug, Clone)]
+pub(crate) struct Value {
+    pub dtype: Datatype,
+    /// Number of elements (0 for a null dataspace, 1 for a scalar one)
+    pub count: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl Value {
+    /// Integer elements as u32 (any fixed-point width up to 8 bytes).
+    pub fn u32s(&self) -> Option<Vec<u32>> {
+        if self.dtype.class != CLASS_FIXED || !matches!(self.dtype.size, 1 | 2 | 4 | 8) {
+            return None;
+        }
+        Some(self.elements().map(|e| e.iter().rev().fold(0u64, |v, &b| (v << 8) | b as u64) as u32).collect())
+    }
+
+    /// Floating-point elements as f64 (4 or 8 bytes wide).
+    pub fn f64s(&self) -> Option<Vec<f64>> {
+        match (self.dtype.class, self.dtype.size) {
+            (CLASS_FLOAT, 8) => Some(self.elements().map(|e| f64::from_le_bytes(e.try_into().unwrap())).collect()),
+            (CLASS_FLOAT, 4) => {
+                Some(self.elements().map(|e| f32::from_le_bytes(e.try_into().unwrap()) as f64).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Text of a fixed-length string or 8-bit integer value, up to the
+    /// first NUL.
+    pub fn text(&self) -> Option<String> {
+        if !matches!((self.dtype.class, self.dtype.size), (CLASS_STRING, _) | (CLASS_FIXED, 1)) {
+            return None;
+        }
+        let end = self.bytes.iter().position(|&b| b == 0).unwrap_or(self.bytes.len());
+        Some(String::from_utf8_lossy(&self.bytes[..end]).into_owned())
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &[u8]> {
+        self.bytes.chunks_exact(self.dtype.size.max(1))
+    }
+}
+
+/// A named attribute.
+pub(crate) struct Attribute {
+    pub name: String,
+    pub value: Value,
+    order: Option<u64>,
+}
+
+/// Raw-data layout of a dataset.
+enum Layout<'a> {
+    Compact(&'a [u8]),
+    Contiguous { addr: u64 },
+    Chunked { chunk: Vec<u64>, index: ChunkIndex },
+}
+
+enum ChunkIndex {
+    /// Version 1 B-tree; `rank` counts the trailing element-size dimension
+    BTree1 { addr: u64, rank: usize },
+    Single { addr: u64, filtered: Option<(u64, u32)> },
+    Implicit { addr: u64 },
+    FixedArray { addr: u64 },
+}
+
+/// One stored chunk: element offset per dimension, file address, stored
+/// size and the mask of filters that were skipped.
+struct Chunk {
+    offset: Vec<u64>,
+    addr: u64,
+    size: usize,
+    mask: u32,
+}
+
+struct Filter {
+    id: u16,
+    values: Vec<u32>,
+}
+
+impl<'a> H5File<'a> {
+    /// Parse the superblock of an HDF5 file starting at byte 0.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut c = Cursor::new(data, 0);
+        if c.bytes(8)? != crate::ogawa::HDF5_MAGIC {
+            return Err(Error::invalid("not an HDF5 file"));
+        }
+        let version = c.u8()?;
+        let mut file = Self { data, base: 0, offset_size: 8, length_size: 8, root: UNDEFINED };
+        match version {
+            0 | 1 => {
+                // Free-space, root group and shared header versions, reserved
+                c.skip(4)?;
+                file.offset_size = c.u8()? as usize;
+                file.length_size = c.u8()? as usize;
+                // Reserved, group leaf/internal node K, consistency flags
+                c.skip(1 + 2 + 2 + 4)?;
+                if version == 1 {
+                    // Indexed storage internal node K, reserved
+                    c.skip(4)?;
+                }
+                file.check_sizes()?;
+                file.base = file.offset(&mut c)?;
+                // Free-space info, end of file and driver info addresses
+                file.offset(&mut c)?;
+                file.offset(&mut c)?;
+                file.offset(&mut c)?;
+                // Root group symbol table entry: link name offset, header address
+                file.offset(&mut c)?;
+                file.root = file.offset(&mut c)?;
+            }
+            2 | 3 => {
+                file.offset_size = c.u8()? as usize;
+                file.length_size = c.u8()? as usize;
+                // Consistency flags
+                c.skip(1)?;
+                file.check_sizes()?;
+                file.base = file.offset(&mut c)?;
+                // Superblock extension and end of file addresses
+                file.offset(&mut c)?;
+                file.offset(&mut c)?;
+                file.root = file.offset(&mut c)?;
+            }
+            v => return Err(Error::invalid(format!("unsupported HDF5 superblock version {}", v))),
+        }
+        if file.base == UNDEFINED {
+            file.base = 0;
+        }
+        Ok(file)
+    }
+
+    fn check_sizes(&self) -> Result<()> {
+        let valid = |n: usize| matches!(n, 2 | 4 | 8);
+        if !valid(self.offset_size) || !valid(self.length_size) {
+            return Err(Error::invalid(format!(
+                "unsupported HDF5 offset/length sizes {}/{}",
+                self.offset_size, self.length_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Address of the root group's object header.
+    pub fn root(&self) -> u64 {
+        self.root
+    }
+
+    /// File address; all ones reads as [`UNDEFINED`].
+    fn offset(&self, c: &mut Cursor) -> Result<u64> {
+        let addr = c.uint(self.offset_size)?;
+        Ok(if addr == u64::MAX >> (64 - 8 * self.offset_size) { UNDEFINED } else { addr })
+    }
+
+    fn length(&self, c: &mut Cursor) -> Result<u64> {
+        c.uint(self.length_size)
+    }
+
+    /// Cursor at file address `addr` (relative to the base address).
+    fn cursor(&self, addr: u64) -> Result<Cursor<'a>> {
+        let start = self.position(addr)?;
+        Ok(Cursor::new(&self.data[start..], start as u64))
+    }
+
+    /// `len` bytes at file address `addr`.
+    fn slice(&self, addr: u64, len: usize) -> Result<&'a [u8]> {
+        let start = self.position(addr)?;
+        start
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .map(|end| &self.data[start..end])
+            .ok_or_else(|| Error::corrupt(start as u64, format!("HDF5 data of {} bytes past the end", len)))
+    }
+
+    fn position(&self, addr: u64) -> Result<usize> {
+        addr.checked_add(self.base)
+            .filter(|&pos| addr != UNDEFINED && pos < self.data.len() as u64)
+            .map(|pos| pos as usize)
+            .ok_or_else(|| Error::corrupt(addr, "HDF5 address out of range"))
+    }
+
+    // ------------------------------------------------------------------
+    // Object headers
+    // ------------------------------------------------------------------
+
+    /// Parse the object header at `addr`, following continuation chunks.
+    pub fn object(&self, addr: u64) -> Result<Object<'a>> {
+        let mut messages = Vec::new();
+        let mut c = self.cursor(addr)?;
+        // (chunk address, length, version 2 "OCHK" chunk)
+        let mut chunks = Vec::new();
+        let tracks_order;
+        let version;
+        if c.buf.starts_with(b"OHDR") {
+            c.skip(4)?;
+            version = c.u8()?;
+            if version != 2 {
+                return Err(Error::corrupt(addr, format!("unsupported HDF5 object header version {}", version)));
+            }
+            let flags = c.u8()?;
+            tracks_order = flags & 0x04 != 0;
+            if flags & 0x20 != 0 {
+                // Access, modification, change and birth times
+                c.skip(16)?;
+            }
+            if flags & 0x10 != 0 {
+                // Attribute phase change values
+                c.skip(4)?;
+            }
+            let size = c.uint(1 << (flags & 0x03))? as usize;
+            self.v2_messages(c.bytes(size)?, c.offset() - size as u64, tracks_order, &mut messages)?;
+        } else {
+            version = c.u8()?;
+            if version != 1 {
+                return Err(Error::corrupt(addr, format!("unsupported HDF5 object header version {}", version)));
+            }
+            tracks_order = false;
+            // Reserved, message count, reference count
+            c.skip(1 + 2 + 4)?;
+            let size = c.u32()? as usize;
+            // Messages start 8-byte aligned after the 12-byte prefix
+            c.skip(4)?;
+            let at = c.offset();
+            self.v1_messages(c.bytes(size)?, at, &mut messages)?;
+        }
+
+        let mut seen = HashSet::new();
+        let mut next = 0;
+        loop {
+            for m in &messages[next..] {
+                if m.kind == MSG_CONTINUATION {
+                    let mut mc = m.cursor();
+                    chunks.push((self.offset(&mut mc)?, self.length(&mut mc)? as usize));
+                }
+            }
+            next = messages.len();
+            let Some((chunk, len)) = chunks.pop() else { break };
+            if !seen.insert(chunk) || seen.len() > MAX_NODES {
+                return Err(Error::corrupt(chunk, "HDF5 object header continuation loop"));
+            }
+            let bytes = self.slice(chunk, len)?;
+            let at = self.position(chunk)? as u64;
+            if version == 2 {
+                if len < 8 || !bytes.starts_with(b"OCHK") {
+                    return Err(Error::corrupt(at, "expected HDF5 OCHK block"));
+                }
+                self.v2_messages(&bytes[4..len - 4], at + 4, tracks_order, &mut messages)?;
+            } else {
+                self.v1_messages(bytes, at, &mut messages)?;
+            }
+        }
+        Ok(Object { addr, messages })
+    }
+
+    fn v1_messages(&self, chunk: &'a [u8], at: u64, out: &mut Vec<Message<'a>>) -> Result<()> {
+        let mut c = Cursor::new(chunk, at);
+        while c.buf.len() - c.pos >= 8 {
+            let kind = c.u16()?;
+            let size = c.u16()? as usize;
+            let flags = c.u8()?;
+            c.skip(3)?;
+            let at = c.offset();
+            let data = c.bytes(size)?;
+            out.push(Message { kind, flags, order: None, data, at });
+        }
+        Ok(())
+    }
+
+    fn v2_messages(&self, chunk: &'a [u8], at: u64, tracks_order: bool, out: &mut Vec<Message<'a>>) -> Result<()> {
+        let mut c = Cursor::new(chunk, at);
+        let prefix = if tracks_order { 6 } else { 4 };
+        // Fewer bytes than a message prefix left is a gap
+        while c.buf.len() - c.pos >= prefix {
+            let kind = c.u8()? as u16;
+            let size = c.u16()? as usize;
+            let flags = c.u8()?;
+            let order = if tracks_order { Some(c.u16()?) } else { None };
+            let at = c.offset();
+            let data = c.bytes(size)?;
+            out.push(Message { kind, flags, order, data, at });
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Groups
+    // ------------------------------------------------------------------
+
+    /// Links of a group, in creation order when it is tracked and in name
+    /// order otherwise.
+    pub fn links(&self, group: &Object) -> Result<Vec<Link>> {
+        let mut links = Vec::new();
+        for m in &group.messages {
+            match m.kind {
+                MSG_LINK => links.push(self.link(m.cursor())?),
+                MSG_LINK_INFO => {
+                    let mut c = m.cursor();
+                    let _version = c.u8()?;
+                    let flags = c.u8()?;
+                    if flags & 0x01 != 0 {
+                        // Maximum creation index
+                        c.skip(8)?;
+                    }
+                    let heap = self.offset(&mut c)?;
+                    let names = self.offset(&mut c)?;
+                    if heap == UNDEFINED || names == UNDEFINED {
+                        continue;
+                    }
+                    let heap = FractalHeap::parse(self, heap)?;
+                    for record in self.btree2_records(names)? {
+                        // Name hash, then the heap ID
+                        let id = record.get(4..).unwrap_or_default();
+                        links.push(self.link(Cursor::new(heap.object(self, id)?, 0))?);
+                    }
+                }
+                MSG_SYMBOL_TABLE => {
+                    let mut c = m.cursor();
+                    let btree = self.offset(&mut c)?;
+                    let heap = self.offset(&mut c)?;
+                    self.symbol_table(btree, heap, &mut links)?;
+                }
+                _ => {}
+            }
+        }
+        if links.iter().all(|l| l.order.is_some()) {
+            links.sort_by_key(|l| l.order);
+        } else {
+            links.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        Ok(links)
+    }
+
+    /// Address of the object `name` links to from `group`.
+    pub fn child(&self, group: &Object, name: &str) -> Result<Option<u64>> {
+        Ok(self.links(group)?.into_iter().find(|l| l.name == name).and_then(|l| l.addr))
+    }
+
+    /// Link message.
+    fn link(&self, mut c: Cursor) -> Result<Link> {
+        let version = c.u8()?;
+        if version != 1 {
+            return Err(Error::corrupt(c.offset(), format!("unsupported HDF5 link message version {}", version)));
+        }
+        let flags = c.u8()?;
+        let kind = if flags & 0x08 != 0 { c.u8()? } else { 0 };
+        let order = if flags & 0x04 != 0 { Some(c.uint(8)?) } else { None };
+        if flags & 0x10 != 0 {
+            // Character set
+            c.skip(1)?;
+        }
+        let name_len = c.uint(1 << (flags & 0x03))? as usize;
+        let name = String::from_utf8_lossy(c.bytes(name_len)?).into_owned();
+        let addr = if kind == 0 { Some(self.offset(&mut c)?) } else { None };
+        Ok(Link { name, addr, order })
+    }
+
+    /// Entries of an old-style group: version 1 B-tree of symbol table nodes
+    /// with names in a local heap.
+    fn symbol_table(&self, btree: u64, heap: u64, out: &mut Vec<Link>) -> Result<()> {
+        let mut c = self.cursor(heap)?;
+        c.signature(b"HEAP")?;
+        c.skip(4)?;
+        let heap_size = self.length(&mut c)? as usize;
+        self.length(&mut c)?;
+        let names = self.slice(self.offset(&mut c)?, heap_size)?;
+
+        let mut nodes = Vec::new();
+        self.btree1(btree, 0, 0, &mut |_, child| {
+            nodes.push(child);
+            Ok(())
+        })?;
+        for node in nodes {
+            let mut c = self.cursor(node)?;
+            c.signature(b"SNOD")?;
+            c.skip(2)?;
+            let count = c.u16()?;
+            for _ in 0..count {
+                let name_offset = self.offset(&mut c)? as usize;
+                let addr = self.offset(&mut c)?;
+                // Cache type, reserved, scratch pad
+                c.skip(4 + 4 + 16)?;
+                let name = names.get(name_offset..).unwrap_or_default();
+                let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+                out.push(Link { name: String::from_utf8_lossy(&name[..end]).into_owned(), addr: Some(addr), order: None });
+            }
+        }
+        Ok(())
+    }
+
+    /// Visit the leaf entries of a version 1 B-tree of `node_type` (0 =
+    /// group, 1 = raw data chunks). `key_size` is the size of a chunk key
+    /// (ignored for groups, whose keys are heap offsets). `visit` gets the
+    /// key preceding each child and the child address.
+    fn btree1(
+        &self,
+        addr: u64,
+        node_type: u8,
+        key_size: usize,
+        visit: &mut dyn FnMut(&'a [u8], u64) -> Result<()>,
+    ) -> Result<()> {
+        let key_size = if node_type == 0 { self.length_size } else { key_size };
+        let mut pending = vec![addr];
+        let mut visited = 0;
+        while let Some(addr) = pending.pop() {
+            visited += 1;
+            if visited > MAX_NODES {
+                return Err(Error::corrupt(addr, "HDF5 B-tree too large"));
+            }
+            let mut c = self.cursor(addr)?;
+            c.signature(b"TREE")?;
+            if c.u8()? != node_type {
+                return Err(Error::corrupt(addr, "unexpected HDF5 B-tree node type"));
+            }
+            let level = c.u8()?;
+            let entries = c.u16()? as usize;
+            // Left and right siblings
+            self.offset(&mut c)?;
+            self.offset(&mut c)?;
+            let mut children = Vec::with_capacity(entries);
+            for _ in 0..entries {
+                let key = c.bytes(key_size)?;
+                children.push((key, self.offset(&mut c)?));
+            }
+            if level == 0 {
+                for (key, child) in children {
+                    visit(key, child)?;
+                }
+            } else {
+                // Depth first, keeping the children in order
+                pending.extend(children.into_iter().rev().map(|(_, child)| child));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records of a version 2 B-tree, in key order.
+    fn btree2_records(&self, addr: u64) -> Result<Vec<&'a [u8]>> {
+        let mut c = self.cursor(addr)?;
+        c.signature(b"BTHD")?;
+        c.skip(2)?;
+        let node_size = c.u32()? as usize;
+        let record_size = c.u16()? as usize;
+        let depth = c.u16()? as usize;
+        c.skip(2)?;
+        let root = self.offset(&mut c)?;
+        let root_records = c.u16()? as usize;
+        if root == UNDEFINED || root_records == 0 {
+            return Ok(Vec::new());
+        }
+        if record_size == 0 || node_size <= 10 {
+            return Err(Error::corrupt(addr, "invalid HDF5 B-tree header"));
+        }
+
+        // Widths of the per-child record counts (H5B2__hdr_init)
+        let enc_size = |n: u64| (n.max(1).ilog2() / 8 + 1) as usize;
+        let leaf_max = ((node_size - 10) / record_size) as u64;
+        let max_nrec_size = enc_size(leaf_max);
+        let mut cum_max = vec![leaf_max];
+        let mut cum_size = vec![0usize];
+        for d in 1..=depth {
+            let pointer = self.offset_size + max_nrec_size + if d > 1 { cum_size[d - 1] } else { 0 };
+            let max = node_size.saturating_sub(10 + pointer) as u64 / (record_size + pointer) as u64;
+            let cum = (max + 1).saturating_mul(cum_max[d - 1]).saturating_add(max);
+            cum_max.push(cum);
+            cum_size.push(enc_size(cum));
+        }
+
+        let mut records = Vec::new();
+        // (node address, record count, depth), depth first
+        let mut pending = vec![(root, root_records, depth)];
+        while let Some((addr, count, depth)) = pending.pop() {
+            if records.len() > MAX_NODES {
+                return Err(Error::corrupt(addr, "HDF5 B-tree too large"));
+            }
+            let mut c = self.cursor(addr)?;
+            c.signature(if depth == 0 { b"BTLF" } else { b"BTIN" })?;
+            c.skip(2)?;
+            let mut node_records = Vec::with_capacity(count);
+            for _ in 0..count {
+                node_records.push(c.bytes(record_size)?);
+            }
+            if depth == 0 {
+                records.extend(node_records);
+                continue;
+            }
+            // Internal node: records sit between children, so push the
+            // children (interleaved with their separating records) in reverse
+            let mut children = Vec::with_capacity(count + 1);
+            for _ in 0..=count {
+                let child = self.offset(&mut c)?;
+                let child_count = c.uint(max_nrec_size)? as usize;
+                if depth > 1 {
+                    c.skip(cum_size[depth - 1])?;
+                }
+                children.push((child, child_count));
+            }
+            // Internal records are emitted separately, after the subtrees;
+            // callers sort by their own keys
+            records.extend(node_records);
+            for (child, child_count) in children.into_iter().rev() {
+                pending.push((child, child_count, depth - 1));
+            }
+        }
+        Ok(records)
+    }
+
+    // ------------------------------------------------------------------
+    // Attributes
+    // ------------------------------------------------------------------
+
+    /// Attributes of an object, in creation order when it is tracked and
+    /// in storage order otherwise.
+    pub fn attributes(&self, object: &Object) -> Result<Vec<Attribute>> {
+        let mut attributes = Vec::new();
+        for m in &object.messages {
+            match m.kind {
+                MSG_ATTRIBUTE => {
+                    if m.flags & 0x02 != 0 {
+                        return Err(Error::corrupt(m.at, "shared HDF5 attributes are not supported"));
+                    }
+                    let mut attribute = self.attribute(m.cursor())?;
+                    attribute.order = m.order.map(u64::from);
+                    attributes.push(attribute);
+                }
+                MSG_ATTRIBUTE_INFO => {
+                    let mut c = m.cursor();
+                    let _version = c.u8()?;
+                    let flags = c.u8()?;
+                    if flags & 0x01 != 0 {
+                        // Maximum creation index
+                        c.skip(2)?;
+                    }
+                    let heap = self.offset(&mut c)?;
+                    let names = self.offset(&mut c)?;
+                    if heap == UNDEFINED || names == UNDEFINED {
+                        continue;
+                    }
+                    let heap = FractalHeap::parse(self, heap)?;
+                    for record in self.btree2_records(names)? {
+                        // Heap ID (8), message flags, creation order, name hash
+                        let mut rc = Cursor::new(record, 0);
+                        let id = rc.bytes(8)?;
+                        if rc.u8()? & 0x02 != 0 {
+                            return Err(Error::corrupt(m.at, "shared HDF5 attributes are not supported"));
+                        }
+                        let order = rc.u32()?;
+                        let mut attribute = self.attribute(Cursor::new(heap.object(self, id)?, 0))?;
+                        attribute.order = Some(order as u64);
+                        attributes.push(attribute);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if attributes.iter().all(|a| a.order.is_some()) {
+            attributes.sort_by_key(|a| a.order);
+        }
+        Ok(attributes)
+    }
+
+    /// Attribute message.
+    fn attribute(&self, mut c: Cursor) -> Result<Attribute> {
+        let at = c.offset();
+        let version = c.u8()?;
+        let flags = c.u8()?;
+        if version > 1 && flags & 0x03 != 0 {
+            return Err(Error::corrupt(at, "shared HDF5 attribute types are not supported"));
+        }
+        let name_size = c.u16()? as usize;
+        let type_size = c.u16()? as usize;
+        let space_size = c.u16()? as usize;
+        if version == 3 {
+            // Name character set
+            c.skip(1)?;
+        }
+        // Version 1 pads each field to a multiple of 8 bytes
+        let padded = |n: usize| if version == 1 { n.next_multiple_of(8) } else { n };
+        let name = c.bytes(padded(name_size))?;
+        let name = &name[..name_size.min(name.len())];
+        let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        let name = String::from_utf8_lossy(&name[..end]).into_owned();
+        let dtype = self.datatype(Cursor::new(c.bytes(padded(type_size))?, at))?;
+        let space = self.dataspace(Cursor::new(c.bytes(padded(space_size))?, at))?;
+        let count = element_count(space.as_deref(), at)?;
+        let len = count
+            .checked_mul(dtype.size)
+            .ok_or_else(|| Error::corrupt(at, "HDF5 attribute too large"))?;
+        let mut bytes = c.bytes(len)?.to_vec();
+        to_little_endian(&dtype, &mut bytes);
+        Ok(Attribute { name, value: Value { dtype, count, bytes }, order: None })
+    }
+
+    // ------------------------------------------------------------------
+    // Datatypes, dataspaces and datasets
+    // ------------------------------------------------------------------
+
+    fn datatype(&self, mut c: Cursor) -> Result<Datatype> {
+        let class_version = c.u8()?;
+        let bits = c.u8()?;
+        c.skip(2)?;
+        let size = c.u32()? as usize;
+        let class = class_version & 0x0f;
+        let big_endian = matches!(class, 0 | 1 | 4) && bits & 0x01 != 0;
+        if class == 9 {
+            return Err(Error::corrupt(c.offset(), "variable-length HDF5 data is not supported"));
+        }
+        Ok(Datatype { class, size, big_endian })
+    }
+
+    /// Dimensions of a dataspace; `None` for a null dataspace, empty for a
+    /// scalar one.
+    fn dataspace(&self, mut c: Cursor) -> Result<Option<Vec<u64>>> {
+        let version = c.u8()?;
+        let rank = c.u8()? as usize;
+        // Flags (maximum dimensions follow, unused here)
+        c.skip(1)?;
+        match version {
+            1 => c.skip(5)?,
+            2 => {
+                if c.u8()? == 2 {
+                    return Ok(None);
+                }
+            }
+            v => return Err(Error::corrupt(c.offset(), format!("unsupported HDF5 dataspace version {}", v))),
+        }
+        let dims = (0..rank).map(|_| self.length(&mut c)).collect::<Result<Vec<_>>>()?;
+        Ok(Some(dims))
+    }
+
+    fn layout(&self, mut c: Cursor<'a>) -> Result<Layout<'a>> {
+        let at = c.offset();
+        let version = c.u8()?;
+        if version < 3 {
+            let rank = c.u8()? as usize;
+            let class = c.u8()?;
+            c.skip(5)?;
+            let addr = if class != 0 { self.offset(&mut c)? } else { UNDEFINED };
+            let dims = (0..rank).map(|_| c.u32().map(u64::from)).collect::<Result<Vec<_>>>()?;
+            return match class {
+                0 => {
+                    let size = c.u32()? as usize;
+                    Ok(Layout::Compact(c.bytes(size)?))
+                }
+                1 => Ok(Layout::Contiguous { addr }),
+                2 => {
+                    let chunk = dims[..rank.saturating_sub(1)].to_vec();
+                    Ok(Layout::Chunked { chunk, index: ChunkIndex::BTree1 { addr, rank } })
+                }
+                _ => Err(Error::corrupt(at, format!("unsupported HDF5 layout class {}", class))),
+            };
+        }
+        match c.u8()? {
+            0 => {
+                let size = c.u16()? as usize;
+                Ok(Layout::Compact(c.bytes(size)?))
+            }
+            1 => Ok(Layout::Contiguous { addr: self.offset(&mut c)? }),
+            2 if version == 3 => {
+                let rank = c.u8()? as usize;
+                let addr = self.offset(&mut c)?;
+                let dims = (0..rank).map(|_| c.u32().map(u64::from)).collect::<Result<Vec<_>>>()?;
+                let chunk = dims[..rank.saturating_sub(1)].to_vec();
+                Ok(Layout::Chunked { chunk, index: ChunkIndex::BTree1 { addr, rank } })
+            }
+            2 => {
+                let flags = c.u8()?;
+                let rank = c.u8()? as usize;
+                let width = c.u8()? as usize;
+                let dims = (0..rank).map(|_| c.uint(width)).collect::<Result<Vec<_>>>()?;
+                let chunk = dims[..rank.saturating_sub(1)].to_vec();
+                let index = match c.u8()? {
+                    1 => {
+                        let filtered = if flags & 0x02 != 0 { Some((self.length(&mut c)?, c.u32()?)) } else { None };
+                        ChunkIndex::Single { addr: self.offset(&mut c)?, filtered }
+                    }
+                    2 => ChunkIndex::Implicit { addr: self.offset(&mut c)? },
+                    3 => {
+                        // Page bits
+                        c.skip(1)?;
+                        ChunkIndex::FixedArray { addr: self.offset(&mut c)? }
+                    }
+                    i => {
+                        return Err(Error::corrupt(
+                            at,
+                            format!("HDF5 chunk index type {} (extendible dataset) is not supported", i),
+                        ))
+                    }
+                };
+                Ok(Layout::Chunked { chunk, index })
+            }
+            class => Err(Error::corrupt(at, format!("unsupported HDF5 layout class {}", class))),
+        }
+    }
+
+    fn filters(&self, mut c: Cursor) -> Result<Vec<Filter>> {
+        let version = c.u8()?;
+        let count = c.u8()? as usize;
+        if version == 1 {
+            c.skip(6)?;
+        }
+        let mut filters = Vec::with_capacity(count);
+        for _ in 0..count {
+            let id = c.u16()?;
+            let name_len = if version == 1 || id >= 256 { c.u16()? as usize } else { 0 };
+            let _flags = c.u16()?;
+            let num_values = c.u16()? as usize;
+            c.skip(if version == 1 { name_len.next_multiple_of(8) } else { name_len })?;
+            let values = (0..num_values).map(|_| c.u32()).collect::<Result<Vec<_>>>()?;
+            if version == 1 && !num_values.is_multiple_of(2) {
+                c.skip(4)?;
+            }
+            filters.push(Filter { id, values });
+        }
+        Ok(filters)
+    }
+
+    /// Read a whole dataset.
+    pub fn dataset(&self, object: &Object<'a>) -> Result<Value> {
+        let at = self.position(object.addr)? as u64;
+        let message = |kind: u16, what: &str| {
+            object.message(kind).ok_or_else(|| Error::corrupt(at, format!("HDF5 dataset without a {} message", what)))
+        };
+        let shared = |m: &Message| {
+            if m.flags & 0x02 != 0 {
+                Err(Error::corrupt(m.at, "shared HDF5 datatypes are not supported"))
+            } else {
+                Ok(())
+            }
+        };
+        let type_message = message(MSG_DATATYPE, "datatype")?;
+        shared(type_message)?;
+        let dtype = self.datatype(type_message.cursor())?;
+        let dims = self.dataspace(message(MSG_DATASPACE, "dataspace")?.cursor())?;
+        let count = element_count(dims.as_deref(), at)?;
+        let len = count
+            .checked_mul(dtype.size)
+            .filter(|&len| len as u64 <= self.data.len() as u64 * 1024)
+            .ok_or_else(|| Error::corrupt(at, "HDF5 dataset too large"))?;
+        let filters = match object.message(MSG_FILTERS) {
+            Some(m) => self.filters(m.cursor())?,
+            None => Vec::new(),
+        };
+
+        let mut bytes = match self.layout(message(MSG_LAYOUT, "layout")?.cursor())? {
+            Layout::Compact(data) => data.get(..len).ok_or_else(|| Error::corrupt(at, "HDF5 compact data truncated"))?.to_vec(),
+            Layout::Contiguous { addr } if addr == UNDEFINED => vec![0; len],
+            Layout::Contiguous { addr } => self.slice(addr, len)?.to_vec(),
+            Layout::Chunked { chunk, index } => {
+                let dims = dims.unwrap_or_default();
+                if chunk.len() != dims.len() || chunk.contains(&0) {
+                    return Err(Error::corrupt(at, "HDF5 chunk rank does not match the dataspace"));
+                }
+                let chunks = self.chunks(&index, &dims, &chunk, dtype.size)?;
+                let mut out = vec![0; len];
+                for stored in chunks {
+                    let raw = self.slice(stored.addr, stored.size)?;
+                    let data = apply_filters(&filters, stored.mask, raw.to_vec(), stored.addr)?;
+                    place_chunk(&mut out, &data, &stored.offset, &dims, &chunk, dtype.size);
+                }
+                out
+            }
+        };
+        to_little_endian(&dtype, &mut bytes);
+        Ok(Value { dtype, count, bytes })
+    }
+
+    /// Chunks of a dataset with dimensions `dims` and chunk shape `chunk`.
+    fn chunks(&self, index: &ChunkIndex, dims: &[u64], chunk: &[u64], elem_size: usize) -> Result<Vec<Chunk>> {
+        let chunk_bytes = chunk.iter().product::<u64>() as usize * elem_size;
+        // Chunk grid, row-major
+        let grid: Vec<u64> = dims.iter().zip(chunk).map(|(d, c)| d.div_ceil(*c)).collect();
+        let grid_offset = |mut n: u64| {
+            let mut offset = vec![0; grid.len()];
+            for i in (0..grid.len()).rev() {
+                offset[i] = (n % grid[i].max(1)) * chunk[i];
+                n /= grid[i].max(1);
+            }
+            offset
+        };
+        let num_chunks: u64 = grid.iter().product();
+        match *index {
+            ChunkIndex::BTree1 { addr, rank } => {
+                let mut chunks = Vec::new();
+                if addr == UNDEFINED {
+                    return Ok(chunks);
+                }
+                self.btree1(addr, 1, 8 + 8 * rank, &mut |key, child| {
+                    let mut c = Cursor::new(key, 0);
+                    let size = c.u32()? as usize;
+                    let mask = c.u32()?;
+                    let offset = (0..rank.saturating_sub(1)).map(|_| c.uint(8)).collect::<Result<Vec<_>>>()?;
+                    chunks.push(Chunk { offset, addr: child, size, mask });
+                    Ok(())
+                })?;
+                Ok(chunks)
+            }
+            ChunkIndex::Single { addr, filtered } => {
+                if addr == UNDEFINED {
+                    return Ok(Vec::new());
+                }
+                let (size, mask) = filtered.map_or((chunk_bytes, 0), |(size, mask)| (size as usize, mask));
+                Ok(vec![Chunk { offset: vec![0; dims.len()], addr, size, mask }])
+            }
+            ChunkIndex::Implicit { addr } => {
+                if addr == UNDEFINED {
+                    return Ok(Vec::new());
+                }
+                Ok((0..num_chunks)
+                    .map(|n| Chunk { offset: grid_offset(n), addr: addr + n * chunk_bytes as u64, size: chunk_bytes, mask: 0 })
+                    .collect())
+            }
+            ChunkIndex::FixedArray { addr } => {
+                if addr == UNDEFINED {
+                    return Ok(Vec::new());
+                }
+                let mut c = self.cursor(addr)?;
+                c.signature(b"FAHD")?;
+                c.skip(1)?;
+                let filtered = c.u8()? == 1;
+                let entry_size = c.u8()? as usize;
+                let page_bits = c.u8()?;
+                let entries = self.length(&mut c)?;
+                let block = self.offset(&mut c)?;
+                if entries > 1u64 << page_bits {
+                    return Err(Error::corrupt(addr, "paged HDF5 fixed array chunk indexes are not supported"));
+                }
+                if block == UNDEFINED {
+                    return Ok(Vec::new());
+                }
+                let mut c = self.cursor(block)?;
+                c.signature(b"FADB")?;
+                c.skip(2)?;
+                self.offset(&mut c)?;
+                let mut chunks = Vec::new();
+                for n in 0..entries.min(num_chunks) {
+                    let chunk_addr = self.offset(&mut c)?;
+                    let (size, mask) = if filtered {
+                        let size = c.uint(entry_size.saturating_sub(self.offset_size + 4))? as usize;
+                        (size, c.u32()?)
+                    } else {
+                        (chunk_bytes, 0)
+                    };
+                    if chunk_addr != UNDEFINED {
+                        chunks.push(Chunk { offset: grid_offset(n), addr: chunk_addr, size, mask });
+                    }
+                }
+                Ok(chunks)
+            }
+        }
+    }
+}
+
+/// Number of elements of a dataspace (`None` = null dataspace).
+fn element_count(dims: Option<&[u64]>, at: u64) -> Result<usize> {
+    let Some(dims) = dims else { return Ok(0) };
+    dims.iter()
+        .try_fold(1u64, |n, &d| n.checked_mul(d))
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| Error::corrupt(at, "HDF5 dataspace too large"))
+}
+
+fn to_little_endian(dtype: &Datatype, bytes: &mut [u8]) {
+    if dtype.big_endian && dtype.size > 1 {
+        for element in bytes.chunks_exact_mut(dtype.size) {
+            element.reverse();
+        }
+    }
+}
+
+/// Undo the filter pipeline on one chunk. Bit `i` of `mask` marks filter
+/// `i` as not applied.
+fn apply_filters(filters: &[Filter], mask: u32, mut data: Vec<u8>, at: u64) -> Result<Vec<u8>> {
+    for (i, filter) in filters.iter().enumerate().rev() {
+        if i < 32 && mask & (1 << i) != 0 {
+            continue;
+        }
+        data = match filter.id {
+            // Deflate
+            1 => {
+                let mut out = Vec::new();
+                flate2::read::ZlibDecoder::new(data.as_slice())
+                    .read_to_end(&mut out)
+                    .map_err(|e| Error::corrupt(at, format!("HDF5 deflate chunk: {}", e)))?;
+                out
+            }
+            // Shuffle: bytes grouped by position within the element
+            2 => {
+                let size = filter.values.first().copied().unwrap_or(1) as usize;
+                if size > 1 {
+                    let count = data.len() / size;
+                    let mut out = data.clone();
+                    for (byte, plane) in data.chunks_exact(count.max(1)).take(size).enumerate() {
+                        for (j, &b) in plane.iter().enumerate() {
+                            out[j * size + byte] = b;
+                        }
+                    }
+                    out
+                } else {
+                    data
+                }
+            }
+            // Fletcher32: trailing checksum
+            3 => {
+                data.truncate(data.len().saturating_sub(4));
+                data
+            }
+            id => return Err(Error::corrupt(at, format!("HDF5 filter {} is not supported", id))),
+        };
+    }
+    Ok(data)
+}
+
+/// Copy a decoded chunk at element offset `offset` into the row-major
+/// dataset buffer `out`, clipping at the dataset edges.
+fn place_chunk(out: &mut [u8], data: &[u8], offset: &[u64], dims: &[u64], chunk: &[u64], elem_size: usize) {
+    let rank = dims.len();
+    if rank == 0 {
+        let n = out.len().min(data.len());
+        out[..n].copy_from_slice(&data[..n]);
+        return;
+    }
+    let last = rank - 1;
+    let row = dims[last].saturating_sub(offset[last]).min(chunk[last]) as usize * elem_size;
+    // Index within the chunk over all but the last dimension
+    let mut index = vec![0u64; last];
+    loop {
+        if (0..last).all(|i| offset[i] + index[i] < dims[i]) {
+            let mut src = 0u64;
+            let mut dst = 0u64;
+            for i in 0..last {
+                src = src * chunk[i] + index[i];
+                dst = dst * dims[i] + offset[i] + index[i];
+            }
+            let src = (src * chunk[last]) as usize * elem_size;
+            let dst = (dst * dims[last] + offset[last]) as usize * elem_size;
+            if let (Some(from), Some(to)) = (data.get(src..src + row), out.get_mut(dst..dst + row)) {
+                to.copy_from_slice(from);
+            }
+        }
+        // Next index, last varying fastest
+        let mut i = last;
+        loop {
+            if i == 0 {
+                return;
+            }
+            i -= 1;
+            index[i] += 1;
+            if index[i] < chunk[i] {
+                break;
+            }
+            index[i] = 0;
+        }
+    }
+}
+
+/// Fractal heap holding dense link and attribute messages.
+struct FractalHeap {
+    id_offset_size: usize,
+    id_length_size: usize,
+    table_width: u64,
+    start_block: u64,
+    max_direct_rows: u32,
+    root: u64,
+    root_rows: u32,
+}
+
+impl FractalHeap {
+    fn parse(file: &H5File, addr: u64) -> Result<Self> {
+        let mut c = file.cursor(addr)?;
+        c.signature(b"FRHP")?;
+        c.skip(1)?;
+        let _id_len = c.u16()?;
+        let filter_len = c.u16()?;
+        // Flags
+        c.skip(1)?;
+        let max_managed = c.u32()? as u64;
+        // Next huge ID, huge object B-tree, free space, free space manager,
+        // managed space, allocated space, iterator offset, managed/huge/tiny
+        // object counts and sizes
+        file.length(&mut c)?;
+        file.offset(&mut c)?;
+        file.length(&mut c)?;
+        file.offset(&mut c)?;
+        for _ in 0..8 {
+            file.length(&mut c)?;
+        }
+        let table_width = c.u16()? as u64;
+        let start_block = file.length(&mut c)?;
+        let max_direct = file.length(&mut c)?;
+        let max_heap_bits = c.u16()? as usize;
+        let _start_rows = c.u16()?;
+        let root = file.offset(&mut c)?;
+        let root_rows = c.u16()? as u32;
+        if filter_len > 0 {
+            return Err(Error::corrupt(addr, "filtered HDF5 fractal heaps are not supported"));
+        }
+        if table_width == 0 || !start_block.is_power_of_two() || !max_direct.is_power_of_two() {
+            return Err(Error::corrupt(addr, "invalid HDF5 fractal heap header"));
+        }
+        let offset_size = max_heap_bits.div_ceil(8);
+        let length_size = (max_direct.ilog2() as usize)
+            .div_ceil(8)
+            .min((max_managed.max(1).ilog2() / 8 + 1) as usize);
+        Ok(Self {
+            id_offset_size: offset_size,
+            id_length_size: length_size,
+            table_width,
+            start_block,
+            max_direct_rows: max_direct.ilog2() - start_block.ilog2() + 2,
+            root,
+            root_rows,
+        })
+    }
+
+    /// Bytes of the object with heap ID `id`.
+    fn object<'a>(&self, file: &H5File<'a>, id: &'a [u8]) -> Result<&'a [u8]> {
+        let first = *id.first().ok_or_else(|| Error::invalid("empty HDF5 heap ID"))?;
+        match (first >> 4) & 0x03 {
+            0 => {
+                let mut c = Cursor::new(&id[1..], 0);
+                let offset = c.uint(self.id_offset_size)?;
+                let length = c.uint(self.id_length_size)? as usize;
+                let (block, block_offset) = self.find_block(file, offset)?;
+                file.slice(block + (offset - block_offset), length)
+            }
+            2 => {
+                // Tiny object stored in the ID itself
+                if id.len() <= 18 {
+                    let len = (first & 0x0f) as usize + 1;
+                    id.get(1..1 + len).ok_or_else(|| Error::invalid("HDF5 tiny heap object truncated"))
+                } else {
+                    let len = (((first & 0x0f) as usize) << 8 | *id.get(1).unwrap_or(&0) as usize) + 1;
+                    id.get(2..2 + len).ok_or_else(|| Error::invalid("HDF5 tiny heap object truncated"))
+                }
+            }
+            _ => Err(Error::invalid("huge HDF5 heap objects are not supported")),
+        }
+    }
+
+    /// Size of the blocks in row `row` of the doubling table.
+    fn row_size(&self, row: u32) -> u64 {
+        if row == 0 {
+            self.start_block
+        } else {
+            self.start_block << (row - 1)
+        }
+    }
+
+    /// Direct block holding heap offset `offset`: (address, heap offset of
+    /// the block start).
+    fn find_block(&self, file: &H5File, offset: u64) -> Result<(u64, u64)> {
+        if self.root == UNDEFINED {
+            return Err(Error::invalid("HDF5 fractal heap is empty"));
+        }
+        if self.root_rows == 0 {
+            return Ok((self.root, 0));
+        }
+        let (mut block, mut rows, mut base) = (self.root, self.root_rows, 0u64);
+        for _ in 0..64 {
+            let mut c = file.cursor(block)?;
+            c.signature(b"FHIB")?;
+            c.skip(1)?;
+            file.offset(&mut c)?;
+            // Block offset, as wide as a heap ID offset
+            c.skip(self.id_offset_size)?;
+            let mut start = base;
+            let mut found = None;
+            'rows: for row in 0..rows {
+                let size = self.row_size(row);
+                for _ in 0..self.table_width {
+                    let child = file.offset(&mut c)?;
+                    if (start..start + size).contains(&offset) {
+                        found = Some((row, child, start, size));
+                        break 'rows;
+                    }
+                    start += size;
+                }
+            }
+            let (row, child, start, size) =
+                found.ok_or_else(|| Error::corrupt(block, "HDF5 heap offset outside the heap"))?;
+            if child == UNDEFINED {
+                return Err(Error::corrupt(block, "HDF5 heap object in an unallocated block"));
+            }
+            if row < self.max_direct_rows {
+                return Ok((child, start));
+            }
+            // Child indirect block covering `size` bytes
+            rows = size.ilog2() - (self.start_block * self.table_width).ilog2() + 1;
+            block = child;
+            base = start;
+        }
+        Err(Error::corrupt(self.root, "HDF5 fractal heap too deep"))
+    }
+}
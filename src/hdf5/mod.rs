@@ -0,0 +1,403 @@
+//! Reading legacy HDF5 archives (Alembic 1.0, AbcCoreHDF5).
+//!
+//! Archives written before Ogawa became the default store the Alembic
+//! hierarchy in an HDF5 container. With the `hdf5` feature,
+//! [`IArchive::open`](crate::abc::IArchive::open) and the other open
+//! functions accept them: the file is read with a pure-Rust HDF5 parser
+//! (no libhdf5) and converted to an in-memory Ogawa archive, which is then
+//! read as usual. [`convert`] exposes that conversion, e.g. to upgrade old
+//! caches on disk.
+//!
+//! ## Layout
+//!
+//! - The root group holds the `ABC` group (the top object) and the
+//!   `abc_version` attributes. Time sampling `N` (from 1) is stored as the
+//!   `N.tspc`, `N.ttpc` and `N.time` attributes of `ABC`.
+//! - An object is a group: its metadata is the `.prop.meta` attribute, its
+//!   properties live in its `.prop` group and every other child group is a
+//!   child object, in creation order.
+//! - Each property is described by `<name>.info` (type, sample counts,
+//!   time sampling) and `<name>.meta` attributes on its parent compound;
+//!   a compound property's children are in the group `<name>`.
+//! - Scalar samples are attributes and array samples datasets, named
+//!   `<name>.smp<i>`: sample 0 next to the header, later ones in the
+//!   `<name>.smpi` group. Array dimensions are in `<sample>.dims` when
+//!   they are not the plain element count. Repeated samples are not stored.
+//!
+//! Conversion reads the whole file into memory. Both the HDF5 1.8 default
+//! file format and the "latest" one are read; committed datatypes,
+//! variable-length data, external storage and the chunk indexes of
+//! extendible datasets are not, and fail with an error.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let data = std::fs::read("maya2011_cache.abc")?;
+//! std::fs::write("maya2011_cache_ogawa.abc", alembic::hdf5::convert("cache", &data)?)?;
+//! ```
+
+pub(crate) mod file;
+
+use std::path::Path;
+
+use crate::core::MetaData;
+use crate::ogawa::writer::{OArchive, OObject, OProperty};
+use crate::ogawa::{stored_time_sampling, OgawaArchiveReader, HDF5_MAGIC};
+use crate::util::{DataType, Error, PlainOldDataType, Result};
+use file::{Attribute, H5File, Object, Value, CLASS_STRING};
+
+/// Bits of the first `<name>.info` field.
+const PTYPE_MASK: u32 = 0x0003;
+const POD_SHIFT: u32 = 2;
+const POD_MASK: u32 = 0x003c;
+const HAS_TIME_SAMPLING: u32 = 0x0040;
+const NO_REPEATS: u32 = 0x0080;
+const EXTENT_SHIFT: u32 = 8;
+const EXTENT_MASK: u32 = 0xff00;
+
+const PTYPE_COMPOUND: u32 = 0;
+const PTYPE_SCALAR: u32 = 1;
+
+/// Whether `data` starts with the HDF5 signature (a legacy archive).
+pub fn is_hdf5(data: &[u8]) -> bool {
+    data.starts_with(HDF5_MAGIC)
+}
+
+/// Convert a legacy HDF5 archive to Ogawa archive bytes. `name` becomes the
+/// name of the in-memory archive.
+pub fn convert(name: impl Into<String>, data: &[u8]) -> Result<Vec<u8>> {
+    let file = H5File::parse(data)?;
+    let root = file.object(file.root())?;
+    let top_addr = file
+        .child(&root, "ABC")?
+        .ok_or_else(|| Error::invalid("HDF5 file has no ABC group (not an Alembic archive)"))?;
+    let top = file.object(top_addr)?;
+
+    let mut archive = OArchive::create_in_memory(name)?;
+    let mut converter = Converter { file: &file, time_samplings: vec![0] };
+    converter.read_time_samplings(&mut archive, &top, &root)?;
+
+    let attributes = file.attributes(&top)?;
+    if let Some(meta) = find(&attributes, ".prop.meta") {
+        archive.set_archive_metadata(converter.meta_data(meta)?);
+    }
+    let mut out = OObject::new("");
+    converter.object_contents(&top, &attributes, &mut out, 0)?;
+    archive.write_archive(&out)?;
+    archive.into_bytes()
+}
+
+/// Open the HDF5 archive at `path` as an Ogawa reader.
+pub(crate) fn open_reader(path: &Path) -> Result<OgawaArchiveReader> {
+    let data = std::fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Error::FileNotFound(path.to_path_buf()),
+        _ => Error::from(e),
+    })?;
+    reader_from_bytes(path.to_string_lossy().to_string(), &data)
+}
+
+/// Convert an in-memory HDF5 archive and open it as an Ogawa reader.
+pub(crate) fn reader_from_bytes(name: String, data: &[u8]) -> Result<OgawaArchiveReader> {
+    let bytes = convert(name.clone(), data)?;
+    OgawaArchiveReader::from_bytes(name, bytes)
+}
+
+/// Limit on object nesting, against link cycles.
+const MAX_DEPTH: usize = 1024;
+
+struct Converter<'f, 'a> {
+    file: &'f H5File<'a>,
+    /// Ogawa time sampling index of each HDF5 one
+    time_samplings: Vec<u32>,
+}
+
+/// Decoded `<name>.info` attribute.
+struct PropertyInfo {
+    ptype: u32,
+    data_type: DataType,
+    num_samples: usize,
+    first_changed: usize,
+    last_changed: usize,
+    time_sampling: u32,
+}
+
+impl<'f, 'a> Converter<'f, 'a> {
+    /// Add time samplings 1.. from the `N.tspc`/`N.ttpc`/`N.time`
+    /// attributes of the top group (or, in some writers, the root group).
+    fn read_time_samplings(&mut self, archive: &mut OArchive, top: &Object, root: &Object) -> Result<()> {
+        let mut attributes = self.file.attributes(top)?;
+        if find(&attributes, "1.time").is_none() {
+            attributes = self.file.attributes(root)?;
+        }
+        for index in 1.. {
+            let Some(times) = find(&attributes, &format!("{}.time", index)) else { break };
+            let times = times
+                .f64s()
+                .filter(|t| !t.is_empty())
+                .ok_or_else(|| Error::invalid(format!("HDF5 time sampling {} has no times", index)))?;
+            let tpc = find(&attributes, &format!("{}.ttpc", index))
+                .and_then(|v| v.f64s())
+                .and_then(|v| v.first().copied())
+                .ok_or_else(|| Error::invalid(format!("HDF5 time sampling {} has no time per cycle", index)))?;
+            self.time_samplings.push(archive.addTimeSampling(stored_time_sampling(tpc, times)));
+        }
+        Ok(())
+    }
+
+    fn meta_data(&self, value: &Value) -> Result<MetaData> {
+        let text = value.text().ok_or_else(|| Error::invalid("HDF5 metadata is not a string"))?;
+        Ok(MetaData::parse(&text))
+    }
+
+    /// Metadata, properties and children of the object group `group`
+    /// (whose attributes are `attributes`) into `out`.
+    fn object_contents(&self, group: &Object<'a>, attributes: &[Attribute], out: &mut OObject, depth: usize) -> Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(Error::invalid("HDF5 object hierarchy too deep"));
+        }
+        if depth > 0 {
+            if let Some(meta) = find(attributes, ".prop.meta") {
+                out.meta_data = self.meta_data(meta)?;
+            }
+        }
+        for link in self.file.links(group)? {
+            let Some(addr) = link.addr else { continue };
+            let child = self.file.object(addr)?;
+            if !child.is_group() {
+                continue;
+            }
+            if link.name == ".prop" {
+                self.compound_contents(&child, &mut out.properties)?;
+                continue;
+            }
+            let mut child_out = OObject::new(&link.name);
+            let child_attributes = self.file.attributes(&child)?;
+            self.object_contents(&child, &child_attributes, &mut child_out, depth + 1)?;
+            out.add_child(child_out);
+        }
+        Ok(())
+    }
+
+    /// Properties described by the `<name>.info` attributes of `group`, in
+    /// creation order.
+    fn compound_contents(&self, group: &Object<'a>, out: &mut Vec<OProperty>) -> Result<()> {
+        let attributes = self.file.attributes(group)?;
+        for attribute in &attributes {
+            let Some(name) = attribute.name.strip_suffix(".info") else { continue };
+            out.push(self.property(group, &attributes, name, &attribute.value)?);
+        }
+        Ok(())
+    }
+
+    fn property(&self, parent: &Object<'a>, attributes: &[Attribute], name: &str, info: &Value) -> Result<OProperty> {
+        let info = self.property_info(name, info)?;
+        let meta_data = match find(attributes, &format!("{}.meta", name)) {
+            Some(meta) => self.meta_data(meta)?,
+            None => MetaData::new(),
+        };
+
+        if info.ptype == PTYPE_COMPOUND {
+            let mut out = OProperty::compound(name);
+            out.meta_data = meta_data;
+            if let Some(addr) = self.file.child(parent, name)? {
+                let mut children = Vec::new();
+                self.compound_contents(&self.file.object(addr)?, &mut children)?;
+                for child in children {
+                    out.add_child(child);
+                }
+            }
+            return Ok(out);
+        }
+
+        let scalar = info.ptype == PTYPE_SCALAR;
+        let mut out = if scalar {
+            OProperty::scalar(name, info.data_type)
+        } else {
+            OProperty::array(name, info.data_type)
+        };
+        out.meta_data = meta_data;
+        out.time_sampling_index = info.time_sampling;
+
+        // Samples 1.. live in `<name>.smpi`
+        let later = match self.file.child(parent, &format!("{}.smpi", name))? {
+            Some(addr) => {
+                let group = self.file.object(addr)?;
+                let attributes = self.file.attributes(&group)?;
+                Some((group, attributes))
+            }
+            None => None,
+        };
+        let mut previous = None;
+        for index in 0..info.num_samples {
+            let stored = if index < info.first_changed {
+                0
+            } else {
+                index.min(info.last_changed)
+            };
+            if previous == Some(stored) {
+                out.set_from_previous();
+                continue;
+            }
+            previous = Some(stored);
+            let sample = format!("{}.smp{}", name, stored);
+            let (group, attributes) = match (stored, &later) {
+                (0, _) => (parent, attributes),
+                (_, Some((group, attributes))) => (group, attributes.as_slice()),
+                (_, None) => return Err(Error::invalid(format!("HDF5 property {} has no {}.smpi group", name, name))),
+            };
+            if scalar {
+                let value = find(attributes, &sample)
+                    .ok_or_else(|| Error::invalid(format!("HDF5 scalar sample {} missing", sample)))?;
+                out.add_scalar_sample(&scalar_bytes(value, info.data_type, &sample)?);
+            } else {
+                let addr = self
+                    .file
+                    .child(group, &sample)?
+                    .ok_or_else(|| Error::invalid(format!("HDF5 array sample {} missing", sample)))?;
+                let value = self.file.dataset(&self.file.object(addr)?)?;
+                let dims = find(attributes, &format!("{}.dims", sample)).and_then(|v| v.u32s());
+                let (data, dims) = array_sample(value, dims, info.data_type, &sample)?;
+                out.add_array_sample(&data, &dims);
+            }
+        }
+        Ok(out)
+    }
+
+    fn property_info(&self, name: &str, value: &Value) -> Result<PropertyInfo> {
+        let fields = value
+            .u32s()
+            .filter(|f| !f.is_empty())
+            .ok_or_else(|| Error::invalid(format!("HDF5 property header {}.info is not an integer array", name)))?;
+        let bits = fields[0];
+        let ptype = bits & PTYPE_MASK;
+        let pod = PlainOldDataType::from_u8(((bits & POD_MASK) >> POD_SHIFT) as u8);
+        let extent = ((bits & EXTENT_MASK) >> EXTENT_SHIFT) as u8;
+        let mut info = PropertyInfo {
+            ptype,
+            data_type: DataType::new(pod, extent.max(1)),
+            num_samples: 0,
+            first_changed: 0,
+            last_changed: 0,
+            time_sampling: 0,
+        };
+        if ptype == PTYPE_COMPOUND {
+            return Ok(info);
+        }
+        if pod == PlainOldDataType::Unknown {
+            return Err(Error::invalid(format!("HDF5 property {} has an unknown POD type", name)));
+        }
+        let has_time_sampling = bits & HAS_TIME_SAMPLING != 0;
+        let field = |i: usize| fields.get(i).map(|&f| f as usize);
+        let next = if bits & NO_REPEATS != 0 {
+            // Every sample after the first is stored
+            info.num_samples = field(1).unwrap_or(0);
+            info.first_changed = info.num_samples.min(1);
+            info.last_changed = info.num_samples.saturating_sub(1);
+            2
+        } else if fields.len() >= 4 {
+            info.num_samples = field(1).unwrap_or(0);
+            info.first_changed = field(2).unwrap_or(0);
+            info.last_changed = field(3).unwrap_or(0);
+            4
+        } else {
+            fields.len()
+        };
+        if has_time_sampling {
+            let index = field(next)
+                .ok_or_else(|| Error::invalid(format!("HDF5 property header {}.info truncated", name)))?;
+            info.time_sampling = *self
+                .time_samplings
+                .get(index)
+                .ok_or_else(|| Error::invalid(format!("HDF5 property {} uses missing time sampling {}", name, index)))?;
+        }
+        if info.num_samples > 0 && info.last_changed >= info.num_samples {
+            return Err(Error::invalid(format!("HDF5 property {} has an invalid changed-sample range", name)));
+        }
+        Ok(info)
+    }
+}
+
+fn find<'v>(attributes: &'v [Attribute], name: &str) -> Option<&'v Value> {
+    attributes.iter().find(|a| a.name == name).map(|a| &a.value)
+}
+
+fn is_string(data_type: DataType) -> bool {
+    matches!(data_type.pod, PlainOldDataType::String | PlainOldDataType::Wstring)
+}
+
+/// Characters of a string sample, each string NUL-terminated.
+fn string_bytes(value: &Value, data_type: DataType) -> Vec<u8> {
+    let mut bytes = if value.dtype.class == CLASS_STRING {
+        // Fixed-length strings: one element per string, NUL-padded
+        let mut joined = Vec::new();
+        for element in value.bytes.chunks(value.dtype.size.max(1)) {
+            let end = element.iter().position(|&b| b == 0).unwrap_or(element.len());
+            joined.extend_from_slice(&element[..end]);
+            joined.push(0);
+        }
+        joined
+    } else {
+        value.bytes.clone()
+    };
+    let width = char_width(data_type);
+    if bytes.len().is_multiple_of(width) && bytes.rchunks(width).next().is_some_and(|c| c.iter().any(|&b| b != 0)) {
+        bytes.resize(bytes.len() + width, 0);
+    }
+    bytes
+}
+
+fn char_width(data_type: DataType) -> usize {
+    if data_type.pod == PlainOldDataType::Wstring {
+        4
+    } else {
+        1
+    }
+}
+
+fn scalar_bytes(value: &Value, data_type: DataType, sample: &str) -> Result<Vec<u8>> {
+    if is_string(data_type) {
+        return Ok(string_bytes(value, data_type));
+    }
+    let expected = data_type.num_bytes();
+    if value.bytes.len() != expected {
+        return Err(Error::invalid(format!(
+            "HDF5 scalar sample {} has {} bytes, expected {}",
+            sample,
+            value.bytes.len(),
+            expected
+        )));
+    }
+    Ok(value.bytes.clone())
+}
+
+/// Bytes and dimensions of an array sample.
+fn array_sample(value: Value, dims: Option<Vec<u32>>, data_type: DataType, sample: &str) -> Result<(Vec<u8>, Vec<usize>)> {
+    if is_string(data_type) {
+        let data = if value.count == 0 { Vec::new() } else { string_bytes(&value, data_type) };
+        let width = char_width(data_type);
+        let strings = data.chunks_exact(width).filter(|c| c.iter().all(|&b| b == 0)).count();
+        let dims = dims.map_or_else(|| vec![strings], |d| d.iter().map(|&d| d as usize).collect());
+        return Ok((data, dims));
+    }
+    let element = data_type.num_bytes();
+    if !value.bytes.len().is_multiple_of(element) {
+        return Err(Error::invalid(format!(
+            "HDF5 array sample {} has {} bytes, not a multiple of {}",
+            sample,
+            value.bytes.len(),
+            element
+        )));
+    }
+    let count = value.bytes.len() / element;
+    let dims = match dims {
+        Some(dims) => {
+            let dims: Vec<usize> = dims.iter().map(|&d| d as usize).collect();
+            if dims.iter().product::<usize>() != count {
+                return Err(Error::invalid(format!("HDF5 array sample {} dimensions do not match its size", sample)));
+            }
+            dims
+        }
+        None => vec![count],
+    };
+    Ok((value.bytes, dims))
+}
@@ -10,13 +10,34 @@
 //!
 //! ## Modules
 //!
-//! - [`util`] - Basic types (POD, DataType, errors)
+//! - [`util`] - Basic types (POD, DataType, errors); `no_std` + `alloc` compatible
 //! - [`ogawa`] - Low-level Ogawa binary format
 //! - [`core`] - Abstract traits and core implementations
 //! - [`abc`] - High-level API (IArchive, OArchive, Objects, Properties)
 //! - [`geom`] - Geometry schemas (PolyMesh, Xform, Curves, etc.)
 //! - [`material`] - Material and shader network support
 //! - [`collection`] - Collection/grouping support
+//! - [`compare`] - Structural and numeric diff of two archives
+//! - [`copy`] - Copy read objects/properties into writer objects, with a per-property hook
+//! - [`resample`] - Retime archives onto a new uniform frame rate
+//! - [`patch`] - Binary patches between two versions of an archive
+//! - `pack` - Compressed copies of archives for internal storage (`compress` feature)
+//! - [`validate`] - Lint archives for broken topology, NaNs and bad metadata
+//! - [`query`] - Glob patterns selecting objects by path and schema
+//! - `hdf5` - Read legacy HDF5 (Alembic 1.0) archives (`hdf5` feature)
+//!
+//! ## `no_std`
+//!
+//! With `default-features = false` (no `std` feature) only [`util`] is built,
+//! as a `no_std` + `alloc` crate. This lets runtimes and plugins share the POD,
+//! DataType, bounds and error definitions without the std-only IO code.
+//!
+//! ## Workspace crates
+//!
+//! This crate is the format library only and has no GUI dependencies. The
+//! command-line tools live in `alembic-tools` (the `alembic-cli` binary), the
+//! wgpu/egui viewer in `alembic-viewer` and the Python bindings in
+//! `alembic-python`.
 //!
 //! ## Example
 //!
@@ -29,29 +50,62 @@
 //! for child in root.getChildren() {
 //!     println!("{}", child.getName());
 //! }
+//!
+//! // Or, for the common case: world-space, triangulated meshes at t = 1.0s
+//! for (path, mesh, _world) in alembic::load_meshes("animation.abc", 1.0)? {
+//!     println!("{}: {} triangles", path, mesh.num_faces());
+//! }
 //! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+// Lets `#[derive(AbcStruct)]` output (`::alembic::...` paths) compile inside this crate
+#[cfg(feature = "derive")]
+extern crate self as alembic;
+
 pub mod util;
+#[cfg(feature = "std")]
 pub mod ogawa;
+#[cfg(feature = "std")]
 pub mod core;
+#[cfg(feature = "std")]
 pub mod abc;
+#[cfg(feature = "std")]
 pub mod geom;
+#[cfg(feature = "std")]
 pub mod material;
+#[cfg(feature = "std")]
 pub mod collection;
-
-// Python bindings (optional, enabled with "python" feature)
-#[cfg(feature = "python")]
-pub mod python;
-
-// 3D Viewer (optional, enabled with "viewer" feature)
-#[cfg(feature = "viewer")]
-pub mod viewer;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "std")]
+pub mod compare;
+#[cfg(feature = "std")]
+pub mod copy;
+#[cfg(feature = "std")]
+pub mod resample;
+#[cfg(feature = "std")]
+pub mod patch;
+#[cfg(feature = "compress")]
+pub mod pack;
+#[cfg(feature = "std")]
+pub mod validate;
+#[cfg(feature = "std")]
+pub mod query;
+#[cfg(feature = "hdf5")]
+pub mod hdf5;
 
 // Re-export commonly used types
 pub use util::{DataType, PlainOldDataType, Error, Result};
+#[cfg(feature = "std")]
 pub use ogawa::{IArchive as OgawaIArchive, OArchive as OgawaOArchive};
+#[cfg(feature = "std")]
+pub use geom::mesh_data::{load_meshes, load_meshes_with, LoadOptions, MeshData};
 
 /// Prelude module for convenient imports
+#[cfg(feature = "std")]
 pub mod prelude {
     pub use crate::util::{DataType, PlainOldDataType, Error, Result};
     pub use crate::abc::{IArchive, OArchive, IObject};
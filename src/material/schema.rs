@@ -1,11 +1,15 @@
 //! Material schema implementation.
 //!
-//! Provides reading of material data from Alembic files.
+//! Provides reading of material data from Alembic files: monolithic shaders
+//! (`.shaderNames` + `<target>.<shaderType>.params`), shader networks
+//! (`.nodes`, `.terminals`, `.interface`, `.interfaceParams`) and
+//! object-to-material bindings (`.material.assign`).
 
 use std::collections::HashMap;
 
-use crate::abc::IObject;
-use super::MATERIAL_SCHEMA;
+use crate::abc::{ICompoundProperty, IObject};
+use crate::geom::util::read_string_scalar;
+use super::{MATERIAL_ASSIGN_PROP, MATERIAL_SCHEMA};
 
 /// Shader parameter value.
 #[derive(Clone, Debug)]
@@ -371,6 +375,10 @@ pub struct IMaterial<'a> {
     object: &'a IObject<'a>,
     /// Cached shader names from .shaderNames array: "target.shaderType" -> "shaderName"
     shader_names: HashMap<String, String>,
+    /// Cached network terminals from .terminals: "target.shaderType" -> "node.output"
+    terminals: HashMap<String, String>,
+    /// Cached interface mappings from .interface: "param" -> "node.param"
+    interface: Vec<(String, String)>,
 }
 
 impl<'a> IMaterial<'a> {
@@ -380,31 +388,42 @@ impl<'a> IMaterial<'a> {
         if !object.matchesSchema(MATERIAL_SCHEMA) {
             return None;
         }
-        
-        let mut mat = Self { 
-            object, 
+        Some(Self::read(object))
+    }
+
+    /// Material embedded on a non-material object (e.g. a mesh), stored as a
+    /// `.material` compound with the Material schema.
+    pub fn embedded(object: &'a IObject<'a>) -> Option<Self> {
+        let props = object.getProperties();
+        let header = props.getPropertyHeaderByName(".material")?;
+        if !header.is_compound() || header.meta_data.get("schema") != Some(MATERIAL_SCHEMA) {
+            return None;
+        }
+        Some(Self::read(object))
+    }
+
+    fn read(object: &'a IObject<'a>) -> Self {
+        let mut mat = Self {
+            object,
             shader_names: HashMap::new(),
+            terminals: HashMap::new(),
+            interface: Vec::new(),
         };
-        mat.parse_shader_names();
-        Some(mat)
+        if let Some((shader_names, terminals, interface)) = mat.with_material(|m| {
+            Some((read_string_pairs(m, ".shaderNames"), read_string_pairs(m, ".terminals"), read_string_pairs(m, ".interface")))
+        }) {
+            mat.shader_names = shader_names.into_iter().collect();
+            mat.terminals = terminals.into_iter().collect();
+            mat.interface = interface;
+        }
+        mat
     }
-    
-    /// Parse .shaderNames array into shader_names map.
-    fn parse_shader_names(&mut self) {
+
+    /// Run `f` on the `.material` compound.
+    fn with_material<R>(&self, f: impl FnOnce(&ICompoundProperty<'_>) -> Option<R>) -> Option<R> {
         let props = self.object.getProperties();
-        let Some(mat_prop) = props.getPropertyByName(".material") else { return };
-        let Some(mat) = mat_prop.asCompound() else { return };
-        let Some(names_prop) = mat.getPropertyByName(".shaderNames") else { return };
-        let Some(arr) = names_prop.asArray() else { return };
-        
-        // Read string array - pairs of ["target.shaderType", "shaderName", ...]
-        if let Ok(strings) = arr.getAsStringArray(0) {
-            for chunk in strings.chunks(2) {
-                if chunk.len() == 2 {
-                    self.shader_names.insert(chunk[0].clone(), chunk[1].clone());
-                }
-            }
-        }
+        let mat_prop = props.getPropertyByName(".material")?;
+        f(&mat_prop.asCompound()?)
     }
     
     /// Get the underlying object.
@@ -422,10 +441,11 @@ impl<'a> IMaterial<'a> {
         self.object.getFullName()
     }
     
-    /// Get target names (renderer targets like "arnold", "renderman").
+    /// Get target names (renderer targets like "arnold", "renderman"),
+    /// from both monolithic shaders and network terminals.
     pub fn target_names(&self) -> Vec<String> {
         let mut targets = std::collections::HashSet::new();
-        for key in self.shader_names.keys() {
+        for key in self.shader_names.keys().chain(self.terminals.keys()) {
             // key is "target.shaderType", extract target
             if let Some(dot_pos) = key.find('.') {
                 targets.insert(key[..dot_pos].to_string());
@@ -456,23 +476,79 @@ impl<'a> IMaterial<'a> {
     
     /// Read all parameters for a shader into ShaderParam list.
     pub fn read_shader_params(&self, target: &str, shader_type: &str) -> Vec<ShaderParam> {
-        let props = self.object.getProperties();
-        let Some(mat_prop) = props.getPropertyByName(".material") else {
-            return Vec::new();
-        };
-        let Some(mat) = mat_prop.asCompound() else {
-            return Vec::new();
-        };
-        
         // Property name is "target.shaderType.params"
         let prop_name = format!("{}.{}.params", target, shader_type);
-        let Some(params_prop) = mat.getPropertyByName(&prop_name) else {
+        self.with_material(|mat| Some(Self::read_params(mat, &prop_name))).unwrap_or_default()
+    }
+
+    /// Names of the nodes of this material's shader network, in file order.
+    pub fn network_node_names(&self) -> Vec<String> {
+        self.with_material(|mat| {
+            let nodes = mat.getPropertyByName(".nodes")?;
+            Some(nodes.asCompound()?.getPropertyNames())
+        })
+        .unwrap_or_default()
+    }
+
+    /// Read network node `name`: its target, type, parameters and input
+    /// connections.
+    pub fn network_node(&self, name: &str) -> Option<ShaderNode> {
+        self.with_material(|mat| {
+            let nodes_prop = mat.getPropertyByName(".nodes")?;
+            let nodes = nodes_prop.asCompound()?;
+            let node_prop = nodes.getPropertyByName(name)?;
+            let compound = node_prop.asCompound()?;
+            let target = read_string_scalar(compound.as_reader(), "target", 0).unwrap_or_default();
+            let node_type = read_string_scalar(compound.as_reader(), "type", 0).unwrap_or_default();
+            let mut node = ShaderNode::new(name, &node_type, &target);
+            node.parameters = Self::read_params(&compound, "params");
+            for (input, source) in read_string_pairs(&compound, ".connections") {
+                let (source_node, output) = split_output(&source);
+                node.connect(&input, source_node, output);
+            }
+            Some(node)
+        })
+    }
+
+    /// Network terminal for a target and shader type: the node (and its
+    /// output, empty for the default one) the network exposes there.
+    pub fn network_terminal(&self, target: &str, shader_type: &str) -> Option<(String, String)> {
+        let value = self.terminals.get(&format!("{}.{}", target, shader_type))?;
+        let (node, output) = split_output(value);
+        Some((node.to_string(), output.to_string()))
+    }
+
+    /// Shader types with a network terminal for `target`.
+    pub fn network_terminal_types(&self, target: &str) -> Vec<String> {
+        let prefix = format!("{}.", target);
+        self.terminals.keys().filter_map(|key| key.strip_prefix(&prefix).map(str::to_string)).collect()
+    }
+
+    /// Interface parameter mappings as (interface param, node, node param),
+    /// in file order.
+    pub fn interface_mappings(&self) -> Vec<(String, String, String)> {
+        self.interface
+            .iter()
+            .map(|(name, target)| {
+                let (node, param) = split_output(target);
+                (name.clone(), node.to_string(), param.to_string())
+            })
+            .collect()
+    }
+
+    /// Values of the network's interface parameters (`.interfaceParams`).
+    pub fn interface_params(&self) -> Vec<ShaderParam> {
+        self.with_material(|mat| Some(Self::read_params(mat, ".interfaceParams"))).unwrap_or_default()
+    }
+
+    /// Read every parameter of compound `name` under `parent`.
+    fn read_params(parent: &ICompoundProperty<'_>, name: &str) -> Vec<ShaderParam> {
+        let Some(params_prop) = parent.getPropertyByName(name) else {
             return Vec::new();
         };
         let Some(params) = params_prop.asCompound() else {
             return Vec::new();
         };
-        
         let mut result = Vec::new();
         for name in params.getPropertyNames() {
             if let Some(value) = Self::read_param_value(&params, &name) {
@@ -530,6 +606,12 @@ impl<'a> IMaterial<'a> {
                                 Some(ShaderParamValue::Vec4(glam::vec4(vals[0], vals[1], vals[2], vals[3])))
                             }
                         }
+                        16 => {
+                            let mut buf = [0u8; 64];
+                            scalar.getSample(0, &mut buf).ok()?;
+                            let vals: [f32; 16] = bytemuck::cast(buf);
+                            Some(ShaderParamValue::Matrix(glam::Mat4::from_cols_array(&vals)))
+                        }
                         _ => None
                     }
                 }
@@ -549,11 +631,7 @@ impl<'a> IMaterial<'a> {
                     Some(ShaderParamValue::Bool(buf[0] != 0))
                 }
                 crate::util::PlainOldDataType::String => {
-                    let mut buf = [0u8; 256];
-                    scalar.getSample(0, &mut buf).ok()?;
-                    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
-                    String::from_utf8(buf[..len].to_vec()).ok()
-                        .map(ShaderParamValue::String)
+                    read_string_scalar(params.as_reader(), name, 0).map(ShaderParamValue::String)
                 }
                 _ => None
             };
@@ -588,33 +666,46 @@ impl<'a> IMaterial<'a> {
     }
 }
 
+/// Pairs of a flattened `[key, value, key, value, ...]` string array.
+fn read_string_pairs(parent: &ICompoundProperty<'_>, name: &str) -> Vec<(String, String)> {
+    let Some(prop) = parent.getPropertyByName(name) else { return Vec::new() };
+    let Some(strings) = prop.asArray().and_then(|arr| arr.getAsStringArray(0).ok()) else {
+        return Vec::new();
+    };
+    strings.chunks_exact(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect()
+}
+
+/// Split `node.output` at the first dot; the output is empty without one.
+fn split_output(value: &str) -> (&str, &str) {
+    value.split_once('.').unwrap_or((value, ""))
+}
+
 // ============================================================================
 // Material Assignment Utilities
 // ============================================================================
 
 /// Get material assignment path from an object.
 /// 
-/// Returns the path to the assigned material, if any.
+/// Returns the path to the assigned material, if any: the `.material.assign`
+/// string property of AbcMaterial's `addMaterialAssignment`, or the nested
+/// `.material/assign` older files of this crate used.
 pub fn get_material_assignment(object: &IObject) -> Option<String> {
     let props = object.getProperties();
-    
-    // Look for .material.assign property
+    if let Some(path) = read_string_scalar(props.as_reader(), MATERIAL_ASSIGN_PROP, 0) {
+        return Some(path);
+    }
+
     let mat_prop = props.getPropertyByName(".material")?;
     let mat = mat_prop.asCompound()?;
-    let assign_prop = mat.getPropertyByName("assign")?;
-    let scalar = assign_prop.asScalar()?;
-    
-    // Read assignment path
-    let mut buf = [0u8; 512];
-    scalar.getSample(0, &mut buf).ok()?;
-    
-    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
-    String::from_utf8(buf[..len].to_vec()).ok()
+    read_string_scalar(mat.as_reader(), "assign", 0)
 }
 
 /// Check if an object has material assignments.
 pub fn has_material_assignment(object: &IObject) -> bool {
     let props = object.getProperties();
+    if props.hasProperty(MATERIAL_ASSIGN_PROP) {
+        return true;
+    }
     
     if let Some(mat_prop) = props.getPropertyByName(".material") {
         if let Some(mat) = mat_prop.asCompound() {
@@ -660,6 +751,8 @@ pub struct FlattenedMaterial {
     pub interface_params: Vec<ShaderParam>,
     /// Source material paths in inheritance order (root first).
     pub inheritance_chain: Vec<String>,
+    /// Interface parameter -> (node, node parameter) it drives.
+    pub interface_mappings: HashMap<String, (String, String)>,
 }
 
 impl FlattenedMaterial {
@@ -692,11 +785,22 @@ impl FlattenedMaterial {
 impl IMaterial<'_> {
     /// Flatten this material into a single representation.
     /// 
-    /// Collects all shader networks and parameters from this material.
+    /// Collects all shader networks and parameters from this material:
+    /// monolithic shaders become one node per shader type (named after it),
+    /// and network nodes of each target are added with their connections,
+    /// network terminals overriding the monolithic ones.
     /// Note: For full inheritance resolution, use flatten_material() with archive root.
     pub fn flatten(&self) -> FlattenedMaterial {
         let mut result = FlattenedMaterial::new();
         result.inheritance_chain.push(self.getFullName().to_string());
+        result.interface_params = self.interface_params();
+        result.interface_mappings = self
+            .interface_mappings()
+            .into_iter()
+            .map(|(name, node, param)| (name, (node, param)))
+            .collect();
+        let nodes: Vec<ShaderNode> =
+            self.network_node_names().iter().filter_map(|name| self.network_node(name)).collect();
         
         // Collect shader networks for each target
         for target in self.target_names() {
@@ -717,6 +821,15 @@ impl IMaterial<'_> {
                     network.set_terminal(&shader_type, &shader_type);
                 }
             }
+
+            for node in nodes.iter().filter(|n| n.target == target) {
+                network.add_node(node.clone());
+            }
+            for shader_type in self.network_terminal_types(&target) {
+                if let Some((node, _)) = self.network_terminal(&target, &shader_type) {
+                    network.set_terminal(&shader_type, &node);
+                }
+            }
             
             if !network.is_empty() {
                 result.networks.insert(target, network);
@@ -728,17 +841,7 @@ impl IMaterial<'_> {
     
     /// Get the inherits path if this material inherits from another.
     pub fn inherits_path(&self) -> Option<String> {
-        let props = self.object.getProperties();
-        let mat_prop = props.getPropertyByName(".material")?;
-        let mat = mat_prop.asCompound()?;
-        let inherits_prop = mat.getPropertyByName(".inherits")?;
-        let scalar = inherits_prop.asScalar()?;
-        
-        let mut buf = [0u8; 512];
-        scalar.getSample(0, &mut buf).ok()?;
-        
-        let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
-        String::from_utf8(buf[..len].to_vec()).ok()
+        self.with_material(|mat| read_string_scalar(mat.as_reader(), ".inherits", 0))
     }
     
     /// Check if this material inherits from another.
@@ -782,6 +885,9 @@ pub fn merge_flattened_materials(child: &mut FlattenedMaterial, parent: &Flatten
             child.interface_params.push(parent_param.clone());
         }
     }
+    for (name, mapping) in &parent.interface_mappings {
+        child.interface_mappings.entry(name.clone()).or_insert_with(|| mapping.clone());
+    }
 }
 
 #[cfg(test)]
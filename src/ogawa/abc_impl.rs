@@ -3,6 +3,7 @@
 //! This module bridges the low-level Ogawa reader/writer with the
 //! abstract Core layer traits.
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -16,13 +17,20 @@ use crate::core::{
     ArchiveReader, ObjectReader, CompoundPropertyReader, PropertyReader,
     ScalarPropertyReader, ArrayPropertyReader,
     ObjectHeader, PropertyHeader, MetaData, TimeSampling,
-    ArraySampleKey, ReadArraySampleCache,
+    ArraySampleKey, DecodeArena, ReadArraySampleCache,
 };
 use crate::util::{Result, Error, PlainOldDataType};
 
 /// Size of the key/digest prefix in data blocks (16 bytes).
 const DATA_KEY_SIZE: usize = 16;
 
+/// Property marking an object as an instance of another object.
+pub const INSTANCE_SOURCE_PROPERTY: &str = ".instanceSource";
+
+/// Deepest object or compound nesting [`OgawaArchiveReader::check_hierarchy`]
+/// follows; deeper chains are reported as corrupt (usually a cycle).
+const MAX_HIERARCHY_DEPTH: usize = 1024;
+
 // ============================================================================
 // Archive Reader
 // ============================================================================
@@ -33,13 +41,12 @@ pub struct OgawaArchiveReader {
     #[allow(dead_code)]
     inner: Arc<OgawaIArchive>,
     archive_version: i32,
-    time_samplings: Vec<TimeSampling>,
+    time_samplings: Arc<Vec<TimeSampling>>,
     max_samples: Vec<u32>,
     indexed_metadata: Arc<Vec<MetaData>>,
     root_data: Arc<ObjectData>,
     root_header: ObjectHeader,
     /// Array sample cache for read performance.
-    #[allow(dead_code)]
     cache: Arc<ReadArraySampleCache>,
 }
 
@@ -53,6 +60,27 @@ impl OgawaArchiveReader {
         Self::init(name, inner)
     }
     
+    /// Open an Alembic file in streaming mode (see [`OgawaIArchive::open_mmap`]).
+    pub fn open_mmap(path: impl AsRef<Path>, block_cache_bytes: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path.to_string_lossy().to_string();
+        let inner = Arc::new(OgawaIArchive::open_mmap(path, block_cache_bytes)?);
+        Self::init(name, inner)
+    }
+    
+    /// Open an Alembic archive from an in-memory buffer.
+    /// `name` is reported by `getName()` in place of a file path.
+    pub fn from_bytes(name: impl Into<String>, data: Vec<u8>) -> Result<Self> {
+        let inner = Arc::new(OgawaIArchive::from_bytes(data)?);
+        Self::init(name.into(), inner)
+    }
+    
+    /// Open an Alembic archive read through a custom byte source.
+    pub fn from_source(name: impl Into<String>, source: Box<dyn super::OgawaSource>) -> Result<Self> {
+        let inner = Arc::new(OgawaIArchive::from_source(source)?);
+        Self::init(name.into(), inner)
+    }
+    
     fn init(name: String, inner: Arc<OgawaIArchive>) -> Result<Self> {
         // Create cache (64 MB default)
         let cache = Arc::new(ReadArraySampleCache::default());
@@ -65,20 +93,20 @@ impl OgawaArchiveReader {
         // Child 2: root object (group), Child 3: metadata (data)
         // Child 4: time samplings (data), Child 5: indexed metadata (data)
         if num_children <= 5 {
-            return Err(Error::invalid("Invalid Alembic file: not enough children"));
+            return Err(Error::corrupt(group.pos(), "Invalid Alembic file: not enough children"));
         }
         
         // Validate child types
         if !group.is_child_data(0)? || !group.is_child_data(1)? ||
            !group.is_child_group(2)? || !group.is_child_data(3)? ||
            !group.is_child_data(4)? || !group.is_child_data(5)? {
-            return Err(Error::invalid("Invalid Alembic file structure"));
+            return Err(Error::corrupt(group.pos(), "Invalid Alembic file structure"));
         }
         
         // Read version
         let version_data = group.data(0)?;
         if version_data.size() != 4 {
-            return Err(Error::invalid("Invalid version data size"));
+            return Err(Error::corrupt(version_data.pos(), "Invalid version data size"));
         }
         let version_bytes = version_data.read_all()?;
         let version = i32::from_le_bytes([
@@ -92,7 +120,7 @@ impl OgawaArchiveReader {
         // Read file version
         let file_version_data = group.data(1)?;
         if file_version_data.size() != 4 {
-            return Err(Error::invalid("Invalid file version data size"));
+            return Err(Error::corrupt(file_version_data.pos(), "Invalid file version data size"));
         }
         let fv_bytes = file_version_data.read_all()?;
         let archive_version = i32::from_le_bytes([
@@ -106,6 +134,7 @@ impl OgawaArchiveReader {
         // Read time samplings
         let time_data = group.data(4)?;
         let (time_samplings, max_samples) = read_time_samplings_and_max(&time_data)?;
+        let time_samplings = Arc::new(time_samplings);
         
         // Read indexed metadata
         let metadata_data = group.data(5)?;
@@ -114,10 +143,13 @@ impl OgawaArchiveReader {
         // Create root object data
         let root_group = group.group(2)?;
         let root_data = Arc::new(ObjectData::new(
-            root_group,
+            root_group.clone(),
             "",
             indexed_metadata.clone(),
+            time_samplings.clone(),
             cache.clone(),
+            root_group,
+            false,
         )?);
         
         // Create root header
@@ -127,10 +159,24 @@ impl OgawaArchiveReader {
         let archive_meta_data = group.data(3)?;
         if archive_meta_data.size() > 0 {
             let meta_bytes = archive_meta_data.read_all()?;
-            let meta_str = std::str::from_utf8(&meta_bytes)
-                .map_err(|e| Error::other(format!("Invalid UTF-8 in archive metadata: {}", e)))?;
+            let meta_str = std::str::from_utf8(&meta_bytes).map_err(|e| {
+                Error::corrupt(archive_meta_data.pos(), format!("Invalid UTF-8 in archive metadata: {}", e))
+            })?;
             root_header.meta_data = MetaData::parse(meta_str);
         }
+        if let Some(codec) = root_header.meta_data.get(super::BLOCK_COMPRESSION_KEY) {
+            if super::BlockCodec::parse(codec).is_none() {
+                return Err(Error::invalid(format!("Unknown block compression: {}", codec)));
+            }
+            // Sample blocks are only read after open, so this covers them all
+            #[cfg(feature = "compress")]
+            inner.streams().set_block_compressed(true);
+            #[cfg(not(feature = "compress"))]
+            return Err(Error::other(format!(
+                "Archive uses {} block compression; build with the `compress` feature or run `alembic-cli unpack`",
+                codec
+            )));
+        }
         
         Ok(Self {
             name,
@@ -159,6 +205,105 @@ impl OgawaArchiveReader {
     pub fn indexed_metadata(&self) -> &[MetaData] {
         &self.indexed_metadata
     }
+
+    /// The top object as an owned reader (for [`super::LayeredArchiveReader`]).
+    pub(crate) fn root_object(&self) -> OgawaObjectReader {
+        OgawaObjectReader { header: self.root_header.clone(), data: self.root_data.clone(), instance_source: None }
+    }
+
+    /// Walk every object and property header and the sample block offsets
+    /// of every property, without decoding samples. Returns the path and
+    /// error of each unreadable subtree; with `stop_at_first`, only the first.
+    pub fn check_hierarchy(&self, stop_at_first: bool) -> Vec<(String, Error)> {
+        let mut check = HierarchyCheck { seen: HashSet::new(), stop_at_first, problems: Vec::new() };
+        check.object(&self.root_data, "", 0);
+        check.problems
+    }
+}
+
+/// State of one [`OgawaArchiveReader::check_hierarchy`] walk.
+struct HierarchyCheck {
+    /// Group positions already walked; a group reached twice is a cycle
+    /// or a shared subtree, neither of which writers produce.
+    seen: HashSet<u64>,
+    stop_at_first: bool,
+    problems: Vec<(String, Error)>,
+}
+
+impl HierarchyCheck {
+    fn done(&self) -> bool {
+        self.stop_at_first && !self.problems.is_empty()
+    }
+
+    fn visit(&mut self, group: &IGroup, depth: usize) -> Result<()> {
+        if depth > MAX_HIERARCHY_DEPTH {
+            return Err(Error::corrupt(group.pos(), format!("hierarchy nested deeper than {} levels", MAX_HIERARCHY_DEPTH)));
+        }
+        if group.pos() != 0 && !self.seen.insert(group.pos()) {
+            return Err(Error::corrupt(group.pos(), "group reached twice (cycle in the hierarchy)"));
+        }
+        Ok(())
+    }
+
+    fn object(&mut self, data: &ObjectData, path: &str, depth: usize) {
+        if let Err(e) = self.visit(&data.group, depth) {
+            self.problems.push((path_or_root(path), e));
+            return;
+        }
+        self.compound(&data.properties, path, depth);
+        for (index, header) in data.children.iter().enumerate() {
+            if self.done() {
+                return;
+            }
+            match data.create_child_reader(index as u64 + 1, header) {
+                // Instances are checked at their source
+                Ok(child) if child.instance_source.is_some() => {}
+                Ok(child) => self.object(&child.data, &header.full_name, depth + 1),
+                Err(e) => self.problems.push((header.full_name.clone(), e)),
+            }
+        }
+    }
+
+    fn compound(&mut self, compound: &CompoundData, path: &str, depth: usize) {
+        for (index, parsed) in compound.sub_properties.iter().enumerate() {
+            if self.done() {
+                return;
+            }
+            let prop_path = format!("{}/{}", path, parsed.name);
+            let Some(group) = compound.property_group(index) else { continue };
+            if let Err(e) = self.property(compound, parsed, group, &prop_path, depth + 1) {
+                self.problems.push((prop_path, e));
+            }
+        }
+    }
+
+    fn property(
+        &mut self,
+        parent: &CompoundData,
+        parsed: &ParsedPropertyHeader,
+        group: Result<IGroup>,
+        path: &str,
+        depth: usize,
+    ) -> Result<()> {
+        let group = group?;
+        self.visit(&group, depth)?;
+        if parsed.property_type == PropertyType::Compound {
+            let compound = CompoundData::from_group(group, &parent.indexed_metadata, parent.time_samplings.clone(), parent.cache.clone())?;
+            self.compound(&compound, path, depth);
+            return Ok(());
+        }
+        // Opening each sample block checks its offset and size
+        for i in 0..group.num_children() {
+            if group.is_child_data(i)? {
+                group.data(i)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn path_or_root(path: &str) -> String {
+    if path.is_empty() { "/".to_string() } else { path.to_string() }
 }
 
 impl ArchiveReader for OgawaArchiveReader {
@@ -196,6 +341,23 @@ impl ArchiveReader for OgawaArchiveReader {
     fn getIndexedMetaData(&self) -> &[MetaData] {
         &self.indexed_metadata
     }
+
+    fn getSampleCache(&self) -> Option<&ReadArraySampleCache> {
+        Some(&self.cache)
+    }
+
+    fn getDecodeArena(&self) -> Option<&DecodeArena> {
+        Some(self.inner.streams().arena())
+    }
+
+    fn setVerifyHashes(&self, verify: bool) -> bool {
+        self.inner.streams().set_verify_hashes(verify);
+        true
+    }
+
+    fn getVerifyHashes(&self) -> bool {
+        self.inner.streams().verifies_hashes()
+    }
     
     fn findObject(&self, path: &str) -> Option<Box<dyn ObjectReader + '_>> {
         let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
@@ -205,52 +367,12 @@ impl ArchiveReader for OgawaArchiveReader {
             return None; // Root object is accessed via archive.root() directly
         }
         
-        // Start from root_data and traverse the path using concrete types
-        let mut current_data = self.root_data.clone();
-        let mut current_header: Option<ObjectHeader>;
-        
-        for (i, part) in parts.iter().enumerate() {
-            // Find child by name in current data
-            let child_idx = current_data.children.iter().position(|h| h.name == *part)?;
-            let parsed_header = &current_data.children[child_idx];
-            
-            // Build object header
-            current_header = Some(ObjectHeader {
-                name: parsed_header.name.clone(),
-                full_name: parsed_header.full_name.clone(),
-                meta_data: parsed_header.metadata.clone(),
-            });
-            
-            // If not last part, need to descend into child
-            if i < parts.len() - 1 {
-                let group_index = (child_idx + 1) as u64;
-                let child_group = current_data.group.group(group_index).ok()?;
-                let cache = current_data.cache.clone();
-                current_data = Arc::new(ObjectData::new(
-                    child_group,
-                    &parsed_header.full_name,
-                    current_data.indexed_metadata.clone(),
-                    cache,
-                ).ok()?);
-            } else {
-                // Last part - create the reader
-                let group_index = (child_idx + 1) as u64;
-                let child_group = current_data.group.group(group_index).ok()?;
-                let child_data = Arc::new(ObjectData::new(
-                    child_group,
-                    &parsed_header.full_name,
-                    current_data.indexed_metadata.clone(),
-                    current_data.cache.clone(),
-                ).ok()?);
-                
-                return Some(Box::new(OgawaObjectReader {
-                    header: current_header?,
-                    data: child_data,
-                }));
-            }
+        // Walk through concrete readers so instances along the path resolve
+        let mut current = self.root_data.child_by_name(parts[0])?.ok()?;
+        for part in &parts[1..] {
+            current = current.data.child_by_name(part)?.ok()?;
         }
-        
-        None
+        Some(Box::new(current))
     }
 }
 
@@ -279,6 +401,10 @@ impl ObjectReader for OgawaArchiveReader {
         }
     }
     
+    fn getChildIndex(&self, name: &str) -> Option<usize> {
+        self.root_data.child_index(name)
+    }
+    
     fn getChild(&self, name: &str) -> Option<Box<dyn ObjectReader + '_>> {
         match self.root_data.child_by_name(name)? {
             Ok(reader) => Some(Box::new(reader)),
@@ -293,6 +419,18 @@ impl ObjectReader for OgawaArchiveReader {
     fn getProperties(&self) -> &dyn CompoundPropertyReader {
         self.root_data.properties()
     }
+
+    fn getArchiveTimeSampling(&self, index: usize) -> Option<&TimeSampling> {
+        self.time_samplings.get(index)
+    }
+
+    fn getPropertiesHash(&self) -> Option<[u8; 16]> {
+        self.root_data.hashes.and_then(|h| h[..16].try_into().ok())
+    }
+
+    fn getChildrenHash(&self) -> Option<[u8; 16]> {
+        self.root_data.hashes.and_then(|h| h[16..].try_into().ok())
+    }
 }
 
 // ============================================================================
@@ -303,6 +441,24 @@ impl ObjectReader for OgawaArchiveReader {
 pub struct OgawaObjectReader {
     header: ObjectHeader,
     data: Arc<ObjectData>,
+    /// Target path when this object is an instance.
+    instance_source: Option<String>,
+}
+
+impl OgawaObjectReader {
+    /// Headers of the direct children, in file order.
+    pub(crate) fn child_headers(&self) -> &[ParsedObjectHeader] {
+        &self.data.children
+    }
+
+    /// Owned reader of the child at `index`.
+    pub(crate) fn child_reader(&self, index: usize) -> Option<Result<OgawaObjectReader>> {
+        self.data.child(index)
+    }
+
+    pub(crate) fn compound_data(&self) -> &CompoundData {
+        self.data.properties()
+    }
 }
 
 impl ObjectReader for OgawaObjectReader {
@@ -335,6 +491,10 @@ impl ObjectReader for OgawaObjectReader {
         }
     }
     
+    fn getChildIndex(&self, name: &str) -> Option<usize> {
+        self.data.child_index(name)
+    }
+    
     fn getChild(&self, name: &str) -> Option<Box<dyn ObjectReader + '_>> {
         match self.data.child_by_name(name)? {
             Ok(reader) => Some(Box::new(reader)),
@@ -349,6 +509,30 @@ impl ObjectReader for OgawaObjectReader {
     fn getProperties(&self) -> &dyn CompoundPropertyReader {
         self.data.properties()
     }
+
+    fn getArchiveTimeSampling(&self, index: usize) -> Option<&TimeSampling> {
+        self.data.time_samplings.get(index)
+    }
+
+    fn isInstanceRoot(&self) -> bool {
+        self.instance_source.is_some()
+    }
+
+    fn isInstanceDescendant(&self) -> bool {
+        self.data.instance_descendant
+    }
+
+    fn getPropertiesHash(&self) -> Option<[u8; 16]> {
+        self.data.hashes.and_then(|h| h[..16].try_into().ok())
+    }
+
+    fn getChildrenHash(&self) -> Option<[u8; 16]> {
+        self.data.hashes.and_then(|h| h[16..].try_into().ok())
+    }
+
+    fn instanceSourcePath(&self) -> &str {
+        self.instance_source.as_deref().unwrap_or("")
+    }
 }
 
 // ============================================================================
@@ -359,9 +543,18 @@ impl ObjectReader for OgawaObjectReader {
 struct ObjectData {
     group: IGroup,
     children: Vec<ParsedObjectHeader>,
+    /// Child name -> index into `children` (file order).
+    child_indices: HashMap<String, usize>,
     properties: CompoundData,
     indexed_metadata: Arc<Vec<MetaData>>,
+    time_samplings: Arc<Vec<TimeSampling>>,
     cache: Arc<ReadArraySampleCache>,
+    /// Top object group, for resolving instance source paths.
+    root: IGroup,
+    /// Reached through an instance (children inherit this).
+    instance_descendant: bool,
+    /// Properties hash then children hash (the 32-byte suffix of the headers data).
+    hashes: Option<[u8; 32]>,
 }
 
 impl ObjectData {
@@ -369,22 +562,34 @@ impl ObjectData {
         group: IGroup,
         parent_name: &str,
         indexed_metadata: Arc<Vec<MetaData>>,
+        time_samplings: Arc<Vec<TimeSampling>>,
         cache: Arc<ReadArraySampleCache>,
+        root: IGroup,
+        instance_descendant: bool,
     ) -> Result<Self> {
         let num_children = group.num_children();
         
         // Parse child headers from last data child
-        let children = if num_children > 0 && group.is_child_data(num_children - 1)? {
+        let (children, hashes) = if num_children > 0 && group.is_child_data(num_children - 1)? {
             let headers_data = group.data(num_children - 1)?;
-            read_object_headers(&headers_data, parent_name, &indexed_metadata)?
+            let hashes = match headers_data.size() {
+                size if size >= 32 => headers_data.read_range(size - 32, 32)?.try_into().ok(),
+                _ => None,
+            };
+            (read_object_headers(&headers_data, parent_name, &indexed_metadata)?, hashes)
         } else {
-            Vec::new()
+            (Vec::new(), None)
         };
+        // First occurrence wins if a (malformed) archive repeats a name
+        let mut child_indices = HashMap::with_capacity(children.len());
+        for (i, h) in children.iter().enumerate() {
+            child_indices.entry(h.name.clone()).or_insert(i);
+        }
         
         // Parse properties from first child if it's a group
         let properties = if num_children > 0 && group.is_child_group(0)? {
             let props_group = group.group(0)?;
-            CompoundData::from_group(props_group, &indexed_metadata, cache.clone())?
+            CompoundData::from_group(props_group, &indexed_metadata, time_samplings.clone(), cache.clone())?
         } else {
             CompoundData::empty()
         };
@@ -392,9 +597,14 @@ impl ObjectData {
         Ok(Self {
             group,
             children,
+            child_indices,
             properties,
             indexed_metadata,
+            time_samplings,
             cache,
+            root,
+            instance_descendant,
+            hashes,
         })
     }
     
@@ -420,31 +630,97 @@ impl ObjectData {
         Some(self.create_child_reader(group_index, header))
     }
     
+    fn child_index(&self, name: &str) -> Option<usize> {
+        self.child_indices.get(name).copied()
+    }
+    
     fn child_by_name(&self, name: &str) -> Option<Result<OgawaObjectReader>> {
-        let index = self.children.iter().position(|h| h.name == name)?;
-        self.child(index)
+        self.child(self.child_index(name)?)
     }
     
     fn create_child_reader(&self, group_index: u64, header: &ParsedObjectHeader) -> Result<OgawaObjectReader> {
         let child_group = self.group.group(group_index)?;
-        let child_data = Arc::new(ObjectData::new(
+        let child_data = ObjectData::new(
             child_group,
             &header.full_name,
             self.indexed_metadata.clone(),
+            self.time_samplings.clone(),
             self.cache.clone(),
-        )?);
-        
-        let obj_header = ObjectHeader {
+            self.root.clone(),
+            self.instance_descendant,
+        )?;
+
+        let mut obj_header = ObjectHeader {
             name: header.name.clone(),
             full_name: header.full_name.clone(),
             meta_data: header.metadata.clone(),
         };
-        
+
+        // An instance is a proxy object holding only `.instanceSource`; it reads
+        // as its target (schema, properties, children) under its own path.
+        if let Some(source) = child_data.instance_source() {
+            if let Some((target_group, target_meta)) = self.resolve_instance(&source, &header.full_name) {
+                let target_data = ObjectData::new(
+                    target_group,
+                    &header.full_name,
+                    self.indexed_metadata.clone(),
+                    self.time_samplings.clone(),
+                    self.cache.clone(),
+                    self.root.clone(),
+                    true,
+                )?;
+                obj_header.meta_data = target_meta;
+                return Ok(OgawaObjectReader {
+                    header: obj_header,
+                    data: Arc::new(target_data),
+                    instance_source: Some(source),
+                });
+            }
+        }
+
         Ok(OgawaObjectReader {
             header: obj_header,
-            data: child_data,
+            data: Arc::new(child_data),
+            instance_source: None,
         })
     }
+
+    /// The `.instanceSource` path if this object is an instance proxy.
+    ///
+    /// Reference: `Abc::IObject::initInstance()`.
+    fn instance_source(&self) -> Option<String> {
+        let index = self.properties.sub_properties.iter().position(|p| {
+            p.name == INSTANCE_SOURCE_PROPERTY
+                && p.property_type == PropertyType::Scalar
+                && p.data_type.pod == PlainOldDataType::String
+        })?;
+        let prop = self.properties.getProperty(index)?;
+        let bytes = prop.asScalar()?.getSampleVec(0).ok()?;
+        String::from_utf8(bytes).ok().filter(|s| s.starts_with('/'))
+    }
+
+    /// Group and metadata of the object at `source`, walking raw headers from
+    /// the top. Targets that contain the instance itself are rejected, as
+    /// expanding them would never terminate.
+    fn resolve_instance(&self, source: &str, instance_path: &str) -> Option<(IGroup, MetaData)> {
+        let source = source.trim_end_matches('/');
+        if source.is_empty() || instance_path == source || instance_path.starts_with(&format!("{}/", source)) {
+            return None;
+        }
+        let mut group = self.root.clone();
+        let mut meta = None;
+        for part in source.split('/').filter(|p| !p.is_empty()) {
+            let num_children = group.num_children();
+            if num_children == 0 || !group.is_child_data(num_children - 1).ok()? {
+                return None;
+            }
+            let headers = read_object_headers(&group.data(num_children - 1).ok()?, "", &self.indexed_metadata).ok()?;
+            let index = headers.iter().position(|h| h.name == part)?;
+            meta = Some(headers[index].metadata.clone());
+            group = group.group(index as u64 + 1).ok()?;
+        }
+        Some((group, meta?))
+    }
 }
 
 // ============================================================================
@@ -457,6 +733,7 @@ pub struct CompoundData {
     sub_properties: Vec<ParsedPropertyHeader>,
     group: Option<IGroup>,
     indexed_metadata: Arc<Vec<MetaData>>,
+    time_samplings: Arc<Vec<TimeSampling>>,
     cache: Arc<ReadArraySampleCache>,
 }
 
@@ -467,11 +744,17 @@ impl CompoundData {
             sub_properties: Vec::new(),
             group: None,
             indexed_metadata: Arc::new(Vec::new()),
+            time_samplings: Arc::new(Vec::new()),
             cache: Arc::new(ReadArraySampleCache::new(0)), // Empty cache for empty compound
         }
     }
     
-    fn from_group(group: IGroup, indexed_metadata: &[MetaData], cache: Arc<ReadArraySampleCache>) -> Result<Self> {
+    fn from_group(
+        group: IGroup,
+        indexed_metadata: &[MetaData],
+        time_samplings: Arc<Vec<TimeSampling>>,
+        cache: Arc<ReadArraySampleCache>,
+    ) -> Result<Self> {
         let num_children = group.num_children();
         
         // Property headers are in the last data child
@@ -487,10 +770,29 @@ impl CompoundData {
             sub_properties,
             group: Some(group),
             indexed_metadata: Arc::new(indexed_metadata.to_vec()),
+            time_samplings,
             cache,
         })
     }
     
+    /// Headers of the sub-properties, in file order.
+    pub(crate) fn property_headers(&self) -> &[ParsedPropertyHeader] {
+        &self.sub_properties
+    }
+
+    /// Owned reader of the sub-property at `index`.
+    pub(crate) fn property_reader(&self, index: usize) -> Option<OgawaPropertyReader> {
+        let parsed = self.sub_properties.get(index)?;
+        let prop_group = self.property_group(index).and_then(|r| r.ok());
+        Some(OgawaPropertyReader::new(
+            parsed.clone(),
+            prop_group,
+            self.indexed_metadata.clone(),
+            self.time_samplings.clone(),
+            self.cache.clone(),
+        ))
+    }
+
     /// Get the property child group at the given index.
     /// Properties are stored as children of the compound group, with headers at the end.
     fn property_group(&self, index: usize) -> Option<Result<IGroup>> {
@@ -520,17 +822,7 @@ impl CompoundPropertyReader for CompoundData {
     }
     
     fn getProperty(&self, index: usize) -> Option<Box<dyn PropertyReader + '_>> {
-        let parsed = self.sub_properties.get(index)?;
-        
-        // Get the property's group if available
-        let prop_group = self.property_group(index).and_then(|r| r.ok());
-        
-        Some(Box::new(OgawaPropertyReader::new(
-            parsed.clone(),
-            prop_group,
-            self.indexed_metadata.clone(),
-            self.cache.clone(),
-        )))
+        Some(Box::new(self.property_reader(index)?))
     }
     
     fn getPropertyByName(&self, name: &str) -> Option<Box<dyn PropertyReader + '_>> {
@@ -549,6 +841,8 @@ pub struct OgawaPropertyReader {
     parsed: ParsedPropertyHeader,
     group: Option<IGroup>,
     indexed_metadata: Arc<Vec<MetaData>>,
+    /// The archive's time samplings, for [`PropertyReader::getTimeSampling`].
+    time_samplings: Arc<Vec<TimeSampling>>,
     /// Array sample cache for read performance.
     cache: Arc<ReadArraySampleCache>,
     /// Cached compound data (loaded on demand).
@@ -560,6 +854,7 @@ impl OgawaPropertyReader {
         parsed: ParsedPropertyHeader,
         group: Option<IGroup>,
         indexed_metadata: Arc<Vec<MetaData>>,
+        time_samplings: Arc<Vec<TimeSampling>>,
         cache: Arc<ReadArraySampleCache>,
     ) -> Self {
         let mut header = match parsed.property_type {
@@ -576,25 +871,27 @@ impl OgawaPropertyReader {
         // Copy time_sampling_index and metadata from parsed header
         header.time_sampling_index = parsed.time_sampling_index;
         header.meta_data = parsed.metadata.clone();
+        header.unknown_pod = parsed.unknown_pod;
         
         Self { 
             header, 
             parsed, 
             group, 
             indexed_metadata,
+            time_samplings,
             cache,
             compound_data: std::sync::OnceLock::new(),
         }
     }
     
     /// Get compound data, loading it on demand.
-    fn get_compound_data(&self) -> Option<&CompoundData> {
+    pub(crate) fn get_compound_data(&self) -> Option<&CompoundData> {
         self.compound_data.get_or_init(|| {
             if self.parsed.property_type != PropertyType::Compound {
                 return None;
             }
             let group = self.group.clone()?;
-            CompoundData::from_group(group, &self.indexed_metadata, self.cache.clone()).ok()
+            CompoundData::from_group(group, &self.indexed_metadata, self.time_samplings.clone(), self.cache.clone()).ok()
         }).as_ref()
     }
     
@@ -604,6 +901,9 @@ impl OgawaPropertyReader {
     }
 
     /// Map logical sample index to stored group index (C++ verifyIndex).
+    ///
+    /// Indices past the last sample are an error here; holding the last
+    /// sample is up to the selector (see `SampleSelector::resolve`).
     fn map_sample_index(&self, index: usize) -> Result<usize> {
         let next = self.num_samples_internal();
         if next == 0 {
@@ -623,7 +923,8 @@ impl OgawaPropertyReader {
             return Ok(0);
         }
         if index >= last {
-            return Ok(last - first + 1);
+            return (last + 1).checked_sub(first)
+                .ok_or_else(|| Error::invalid("First changed sample index is past the last one"));
         }
         Ok(index - first + 1)
     }
@@ -650,8 +951,9 @@ impl OgawaPropertyReader {
             return Err(Error::invalid("Scalar sample data too small"));
         }
         
-        let data_bytes = data.read_all()?;
-        let actual_data = &data_bytes[DATA_KEY_SIZE..];
+        let mut actual_data = data.arena().take(out.len());
+        data.read_keyed_payload_into(&mut actual_data)?;
+        data.verify_payload(&actual_data, self.parsed.data_type.pod)?;
         
         let copy_len = out.len().min(actual_data.len());
         out[..copy_len].copy_from_slice(&actual_data[..copy_len]);
@@ -659,12 +961,51 @@ impl OgawaPropertyReader {
         Ok(())
     }
     
+    /// Read a scalar sample's whole payload, whatever its POD type.
+    fn read_scalar_payload(&self, index: usize) -> Result<Vec<u8>> {
+        let group = self.group.as_ref()
+            .ok_or_else(|| Error::invalid("No property group"))?;
+        let data = group.data(index as u64)?;
+        if data.size() < DATA_KEY_SIZE as u64 {
+            return Ok(Vec::new());
+        }
+        let payload = data.read_keyed_payload()?;
+        data.verify_payload(&payload, self.parsed.data_type.pod)?;
+        Ok(payload)
+    }
+
     /// Get array sample length (number of elements).
     fn array_sample_len(&self, index: usize) -> Result<usize> {
         let dims = self.read_array_sample_dimensions(index)?;
-        Ok(dims.iter().product())
+        dims_product(&dims).ok_or_else(|| Error::invalid("Array sample dimensions overflow"))
     }
     
+    /// Call `f` with an array sample's bytes, from the cache when possible.
+    fn with_array_sample(&self, index: usize, f: &mut dyn FnMut(&[u8])) -> Result<()> {
+        let group = self.group.as_ref()
+            .ok_or_else(|| Error::invalid("No property group"))?;
+        let data_index = (index * 2) as u64;
+        if data_index >= group.num_children() {
+            return Err(Error::invalid("Array sample index out of range"));
+        }
+        let data = group.data(data_index)?;
+        if data.size() <= DATA_KEY_SIZE as u64 {
+            f(&[]);
+            return Ok(());
+        }
+
+        let cache_key = ArraySampleKey::new(data.pos(), index);
+        if let Some(cached) = self.cache.get(&cache_key) {
+            f(&cached);
+            return Ok(());
+        }
+        let result = data.read_keyed_payload()?;
+        data.verify_payload(&result, self.parsed.data_type.pod)?;
+        f(&result);
+        self.cache.insert_for_property(group.pos(), cache_key, result);
+        Ok(())
+    }
+
     /// Read array sample data.
     fn read_array_sample(&self, index: usize) -> Result<Vec<u8>> {
         let group = self.group.as_ref()
@@ -691,11 +1032,11 @@ impl OgawaPropertyReader {
         }
         
         // Cache miss - read from file
-        let data_bytes = data.read_all()?;
-        let result = data_bytes[DATA_KEY_SIZE..].to_vec();
+        let result = data.read_keyed_payload()?;
+        data.verify_payload(&result, self.parsed.data_type.pod)?;
         
         // Store in cache
-        self.cache.insert(cache_key, result.clone());
+        self.cache.insert_for_property(group.pos(), cache_key, result.clone());
         
         Ok(result)
     }
@@ -716,10 +1057,7 @@ impl OgawaPropertyReader {
         }
         
         // Read only the first 16 bytes (the key)
-        let all_data = data.read_all()?;
-        let mut key = [0u8; 16];
-        key.copy_from_slice(&all_data[..16]);
-        Ok(key)
+        data.read_key()
     }
     
     /// Read array sample key (digest) without reading full data.
@@ -740,10 +1078,7 @@ impl OgawaPropertyReader {
         }
         
         // Read only the first 16 bytes (the key)
-        let all_data = data.read_all()?;
-        let mut key = [0u8; 16];
-        key.copy_from_slice(&all_data[..16]);
-        Ok(key)
+        data.read_key()
     }
     
     /// Read array sample dimensions.
@@ -766,28 +1101,29 @@ impl OgawaPropertyReader {
         let dims_data = group.data(dims_index)?;
         let mut dims = read_dimensions(&dims_data)?;
 
-        let data_size = data.size();
-        if data_size < DATA_KEY_SIZE as u64 {
+        if data.size() < DATA_KEY_SIZE as u64 {
             return Ok(vec![0]);
         }
+        let payload_size = data.keyed_payload_size()?;
 
         let pod = self.parsed.data_type.pod;
         if dims.is_empty() && !matches!(pod, PlainOldDataType::String | PlainOldDataType::Wstring) {
-            let elem_size = self.parsed.data_type.num_bytes() as u64;
-            let mut num_items = (data_size - DATA_KEY_SIZE as u64) / elem_size;
-            if !(data_size - DATA_KEY_SIZE as u64).is_multiple_of(elem_size) {
+            // Unknown PODs have no element size; count raw bytes instead
+            let elem_size = (self.parsed.data_type.num_bytes() as u64).max(1);
+            let mut num_items = payload_size / elem_size;
+            if !payload_size.is_multiple_of(elem_size) {
                 num_items += 1;
             }
             dims = vec![num_items as usize];
         } else if !dims.is_empty()
             && !matches!(pod, PlainOldDataType::String | PlainOldDataType::Wstring)
         {
-            let points = dims.iter().product::<usize>() as u64;
-            let needed = self.parsed.data_type.num_bytes() as u64 * points;
-            if needed > data_size.saturating_sub(DATA_KEY_SIZE as u64) {
-                let elem_size = self.parsed.data_type.num_bytes() as u64;
-                let mut num_items = (data_size - DATA_KEY_SIZE as u64) / elem_size;
-                if !(data_size - DATA_KEY_SIZE as u64).is_multiple_of(elem_size) {
+            let needed = dims_product(&dims)
+                .and_then(|points| (self.parsed.data_type.num_bytes() as u64).checked_mul(points as u64));
+            if needed.is_none_or(|needed| needed > payload_size) {
+                let elem_size = (self.parsed.data_type.num_bytes() as u64).max(1);
+                let mut num_items = payload_size / elem_size;
+                if !payload_size.is_multiple_of(elem_size) {
                     num_items += 1;
                 }
                 dims = vec![num_items as usize];
@@ -802,6 +1138,10 @@ impl PropertyReader for OgawaPropertyReader {
     fn getHeader(&self) -> &PropertyHeader {
         &self.header
     }
+
+    fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        self.time_samplings.get(self.header.time_sampling_index as usize)
+    }
     
     fn asCompound(&self) -> Option<&dyn CompoundPropertyReader> {
         self.get_compound_data().map(|c| c as &dyn CompoundPropertyReader)
@@ -837,6 +1177,11 @@ impl ScalarPropertyReader for OgawaPropertyReader {
         let actual_index = self.map_sample_index(index)?;
         self.read_scalar_sample(actual_index, out)
     }
+
+    fn getRawSample(&self, index: usize) -> Result<Vec<u8>> {
+        let actual_index = self.map_sample_index(index)?;
+        self.read_scalar_payload(actual_index)
+    }
     
     fn getKey(&self, index: usize) -> Result<[u8; 16]> {
         let actual_index = self.map_sample_index(index)?;
@@ -870,6 +1215,11 @@ impl ArrayPropertyReader for OgawaPropertyReader {
         let actual_index = self.map_sample_index(index)?;
         self.read_array_sample(actual_index)
     }
+
+    fn withSample(&self, index: usize, f: &mut dyn FnMut(&[u8])) -> Result<()> {
+        let actual_index = self.map_sample_index(index)?;
+        self.with_array_sample(actual_index, f)
+    }
     
     fn getKey(&self, index: usize) -> Result<[u8; 16]> {
         let actual_index = self.map_sample_index(index)?;
@@ -886,17 +1236,24 @@ impl ArrayPropertyReader for OgawaPropertyReader {
 // Helper functions
 // ============================================================================
 
+/// Element count of a sample shape; `None` if it overflows (corrupt dims).
+fn dims_product(dims: &[usize]) -> Option<usize> {
+    dims.iter().try_fold(1usize, |acc, &d| acc.checked_mul(d))
+}
+
 /// Read dimensions from a dimensions data block.
 fn read_dimensions(data: &IData) -> Result<Vec<usize>> {
     if data.is_empty() {
         return Ok(Vec::new());
     }
     
-    let bytes = data.read_all()?;
+    let mut bytes = data.arena().take(data.size() as usize);
+    bytes.resize(data.size() as usize, 0);
+    data.read_into(&mut bytes)?;
     
     // Dimensions are stored as u64 values
-    if bytes.len() % 8 != 0 {
-        return Err(Error::invalid("Invalid dimensions data size"));
+    if !bytes.len().is_multiple_of(8) {
+        return Err(Error::corrupt(data.pos(), "Invalid dimensions data size"));
     }
     
     let mut dims = Vec::with_capacity(bytes.len() / 8);
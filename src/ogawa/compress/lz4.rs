@@ -0,0 +1,205 @@
+//! LZ4 block format (no frame header), compatible with `LZ4_compress_default`
+//! / `LZ4_decompress_safe` output.
+//!
+//! The compressor is a plain greedy matcher over a 4-byte hash table; sample
+//! data (positions, indices, repeated attributes) compresses well enough
+//! with it and decoding speed, which is what matters on read, is the same.
+
+use crate::util::{Error, Result};
+
+const MIN_MATCH: usize = 4;
+/// The last match must start at least this many bytes before the end.
+const MF_LIMIT: usize = 12;
+/// The last bytes of a block are always literals.
+const LAST_LITERALS: usize = 5;
+const MAX_OFFSET: usize = u16::MAX as usize;
+const HASH_LOG: u32 = 12;
+
+#[inline]
+fn read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+#[inline]
+fn hash(seq: u32) -> usize {
+    (seq.wrapping_mul(2_654_435_761) >> (32 - HASH_LOG)) as usize
+}
+
+/// Compress `input` into one LZ4 block.
+pub(super) fn compress(input: &[u8]) -> Vec<u8> {
+    let n = input.len();
+    let mut out = Vec::with_capacity(n / 2 + 16);
+    let mut anchor = 0;
+
+    if n > MF_LIMIT {
+        // Positions + 1; 0 marks an empty slot
+        let mut table = vec![0usize; 1 << HASH_LOG];
+        let match_limit = n - MF_LIMIT;
+        let mut i = 0;
+        while i < match_limit {
+            let seq = read_u32(input, i);
+            let h = hash(seq);
+            let candidate = table[h];
+            table[h] = i + 1;
+            if candidate > 0 {
+                let c = candidate - 1;
+                if i - c <= MAX_OFFSET && read_u32(input, c) == seq {
+                    let max_end = n - LAST_LITERALS;
+                    let mut len = MIN_MATCH;
+                    while i + len < max_end && input[c + len] == input[i + len] {
+                        len += 1;
+                    }
+                    write_sequence(&mut out, &input[anchor..i], i - c, len);
+                    i += len;
+                    anchor = i;
+                    continue;
+                }
+            }
+            i += 1;
+        }
+    }
+
+    // Trailing literals, without a match part
+    let literals = &input[anchor..];
+    write_token(&mut out, literals.len(), 0);
+    out.extend_from_slice(literals);
+    out
+}
+
+fn write_sequence(out: &mut Vec<u8>, literals: &[u8], offset: usize, match_len: usize) {
+    let extra = match_len - MIN_MATCH;
+    write_token(out, literals.len(), extra);
+    out.extend_from_slice(literals);
+    out.extend_from_slice(&(offset as u16).to_le_bytes());
+    if extra >= 15 {
+        write_length(out, extra - 15);
+    }
+}
+
+/// Token byte plus the literal length extension.
+fn write_token(out: &mut Vec<u8>, literal_len: usize, match_extra: usize) {
+    out.push(((literal_len.min(15) as u8) << 4) | match_extra.min(15) as u8);
+    if literal_len >= 15 {
+        write_length(out, literal_len - 15);
+    }
+}
+
+fn write_length(out: &mut Vec<u8>, mut len: usize) {
+    while len >= 255 {
+        out.push(255);
+        len -= 255;
+    }
+    out.push(len as u8);
+}
+
+fn corrupt() -> Error {
+    Error::invalid("corrupt lz4 block")
+}
+
+fn read_length(input: &[u8], pos: &mut usize) -> Result<usize> {
+    let mut len = 0usize;
+    loop {
+        let b = *input.get(*pos).ok_or_else(corrupt)?;
+        *pos += 1;
+        len = len.checked_add(b as usize).ok_or_else(corrupt)?;
+        if b != 255 {
+            return Ok(len);
+        }
+    }
+}
+
+/// Decompress one LZ4 block that must decode to exactly `len` bytes.
+pub(super) fn decompress(input: &[u8], len: usize) -> Result<Vec<u8>> {
+    // LZ4 cannot expand by more than 255x; don't trust `len` for allocation
+    let mut out = Vec::with_capacity(len.min(input.len().saturating_mul(255)));
+    let mut pos = 0;
+    loop {
+        let token = *input.get(pos).ok_or_else(corrupt)?;
+        pos += 1;
+
+        let mut literal_len = (token >> 4) as usize;
+        if literal_len == 15 {
+            literal_len += read_length(input, &mut pos)?;
+        }
+        let end = pos.checked_add(literal_len).filter(|&e| e <= input.len()).ok_or_else(corrupt)?;
+        if out.len() + literal_len > len {
+            return Err(corrupt());
+        }
+        out.extend_from_slice(&input[pos..end]);
+        pos = end;
+        if pos == input.len() {
+            break;
+        }
+
+        let offset_bytes = input.get(pos..pos + 2).ok_or_else(corrupt)?;
+        let offset = u16::from_le_bytes([offset_bytes[0], offset_bytes[1]]) as usize;
+        pos += 2;
+        if offset == 0 || offset > out.len() {
+            return Err(corrupt());
+        }
+        let mut match_len = (token & 0x0F) as usize + MIN_MATCH;
+        if token & 0x0F == 0x0F {
+            match_len += read_length(input, &mut pos)?;
+        }
+        if out.len() + match_len > len {
+            return Err(corrupt());
+        }
+        // Byte by byte: the match may overlap the bytes it produces
+        let start = out.len() - offset;
+        for k in 0..match_len {
+            let b = out[start + k];
+            out.push(b);
+        }
+    }
+    if out.len() != len {
+        return Err(corrupt());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(data: &[u8]) -> Vec<u8> {
+        let packed = compress(data);
+        assert_eq!(decompress(&packed, data.len()).unwrap(), data);
+        packed
+    }
+
+    #[test]
+    fn test_roundtrip_edge_sizes() {
+        for n in [0, 1, 4, 12, 13, 16, 100] {
+            roundtrip(&vec![0xAB; n]);
+            roundtrip(&(0..n as u8).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_long_runs_and_literals() {
+        // Run and literal lengths well past the 15/255 length extensions
+        let mut data = vec![0u8; 70_000];
+        data.extend((0..1000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8));
+        data.extend(b"abcabcabcabc".repeat(500));
+        let packed = roundtrip(&data);
+        assert!(packed.len() < data.len() / 10);
+    }
+
+    #[test]
+    fn test_reference_block() {
+        // "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa" (32 x 'a') as produced by liblz4
+        let block = [0x1F, b'a', 0x01, 0x00, 0x07, 0x50, b'a', b'a', b'a', b'a', b'a'];
+        assert_eq!(decompress(&block, 32).unwrap(), vec![b'a'; 32]);
+        assert_eq!(compress(&[b'a'; 32]), block);
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let packed = compress(&b"hello hello hello hello hello".repeat(4));
+        assert!(decompress(&packed, 10).is_err());
+        assert!(decompress(&packed[..packed.len() - 3], 116).is_err());
+        // Offset pointing before the start of the output
+        assert!(decompress(&[0x10, b'x', 0x05, 0x00, 0x00], 20).is_err());
+        assert!(decompress(&[], 0).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Opt-in block compression for keyed sample data.
+//!
+//! Stock Alembic stores every sample as `[u64 size][16-byte key][payload]`.
+//! Archives written with [`OArchive::set_block_compression`] keep that
+//! layout, the hierarchy and the keys, but store each payload as
+//!
+//! ```text
+//! [codec u8][decoded length, LEB128 (lz4/zlib only)][encoded bytes]
+//! ```
+//!
+//! so sample dedup and digests behave exactly as in uncompressed archives.
+//! Blocks that do not shrink are stored as-is behind a single codec byte.
+//!
+//! Compressed archives are marked with the [`BLOCK_COMPRESSION_KEY`]
+//! archive metadata entry. They are **not** readable by stock Alembic: the
+//! format is meant for internal caches (e.g. farm storage), and
+//! `alembic-cli unpack` turns them back into standard archives. Reading and
+//! writing them needs the `compress` feature; without it, opening a marked
+//! archive fails with an error instead of returning garbage samples.
+//!
+//! [`OArchive::set_block_compression`]: crate::ogawa::writer::OArchive::set_block_compression
+
+#[cfg(feature = "compress")]
+mod lz4;
+
+#[cfg(feature = "compress")]
+use crate::util::{Error, Result};
+
+/// Archive metadata key marking block-compressed archives (value: codec name).
+pub const BLOCK_COMPRESSION_KEY: &str = "_ai_BlockCompression";
+
+/// Codec used for keyed sample payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlockCodec {
+    /// LZ4 block format: fast to decode, moderate ratio.
+    #[default]
+    Lz4,
+    /// zlib (deflate): slower, better ratio.
+    Zlib,
+}
+
+impl BlockCodec {
+    /// Codec name as stored in the archive metadata.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Lz4 => "lz4",
+            Self::Zlib => "zlib",
+        }
+    }
+
+    /// Parse a codec name ("lz4", "zlib" and "deflate").
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "lz4" => Some(Self::Lz4),
+            "zlib" | "deflate" => Some(Self::Zlib),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "compress")]
+    fn tag(self) -> u8 {
+        match self {
+            Self::Lz4 => TAG_LZ4,
+            Self::Zlib => TAG_ZLIB,
+        }
+    }
+}
+
+/// Payload stored uncompressed.
+#[cfg(feature = "compress")]
+const TAG_STORED: u8 = 0;
+#[cfg(feature = "compress")]
+const TAG_LZ4: u8 = 1;
+#[cfg(feature = "compress")]
+const TAG_ZLIB: u8 = 2;
+
+/// Encode a sample payload with `codec`, falling back to stored.
+#[cfg(feature = "compress")]
+pub(crate) fn encode_block(codec: BlockCodec, data: &[u8]) -> Vec<u8> {
+    let packed = match codec {
+        BlockCodec::Lz4 => lz4::compress(data),
+        BlockCodec::Zlib => zlib_compress(data),
+    };
+    let mut header = vec![codec.tag()];
+    put_varint(&mut header, data.len() as u64);
+    if header.len() + packed.len() < data.len() + 1 {
+        header.extend_from_slice(&packed);
+        header
+    } else {
+        let mut stored = Vec::with_capacity(data.len() + 1);
+        stored.push(TAG_STORED);
+        stored.extend_from_slice(data);
+        stored
+    }
+}
+
+/// Decode a payload written by [`encode_block`].
+#[cfg(feature = "compress")]
+pub(crate) fn decode_block(block: &[u8]) -> Result<Vec<u8>> {
+    let (&tag, rest) = block.split_first().ok_or_else(|| Error::invalid("empty compressed block"))?;
+    if tag == TAG_STORED {
+        return Ok(rest.to_vec());
+    }
+    let (len, used) = get_varint(rest)?;
+    let len = usize::try_from(len).map_err(|_| Error::invalid("compressed block too large"))?;
+    let body = &rest[used..];
+    match tag {
+        TAG_LZ4 => lz4::decompress(body, len),
+        TAG_ZLIB => zlib_decompress(body, len),
+        _ => Err(Error::invalid(format!("unknown block codec {}", tag))),
+    }
+}
+
+/// Decoded length of a block from its first bytes (at most
+/// [`BLOCK_HEADER_MAX`]) and its stored size.
+#[cfg(feature = "compress")]
+pub(crate) fn decoded_len(header: &[u8], stored_len: u64) -> Result<u64> {
+    match header.first() {
+        None => Err(Error::invalid("empty compressed block")),
+        Some(&TAG_STORED) => Ok(stored_len - 1),
+        Some(_) => get_varint(&header[1..]).map(|(len, _)| len),
+    }
+}
+
+/// Longest block header: codec byte plus a 10-byte varint.
+#[cfg(feature = "compress")]
+pub(crate) const BLOCK_HEADER_MAX: usize = 11;
+
+#[cfg(feature = "compress")]
+fn put_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+/// Read a LEB128 value, returning it and the number of bytes used.
+#[cfg(feature = "compress")]
+fn get_varint(bytes: &[u8]) -> Result<(u64, usize)> {
+    let mut v = 0u64;
+    for (i, &b) in bytes.iter().enumerate().take(10) {
+        v |= u64::from(b & 0x7F) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok((v, i + 1));
+        }
+    }
+    Err(Error::invalid("truncated compressed block header"))
+}
+
+#[cfg(feature = "compress")]
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    // Writing into a Vec cannot fail
+    let _ = encoder.write_all(data);
+    encoder.finish().unwrap_or_default()
+}
+
+#[cfg(feature = "compress")]
+fn zlib_decompress(data: &[u8], len: usize) -> Result<Vec<u8>> {
+    use std::io::Read;
+    let mut out = Vec::with_capacity(len.min(data.len().saturating_mul(1032)));
+    // One byte past the expected length catches oversized streams
+    flate2::read::ZlibDecoder::new(data)
+        .take(len as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| Error::invalid(format!("corrupt zlib block: {}", e)))?;
+    if out.len() != len {
+        return Err(Error::invalid(format!("zlib block decoded to {} bytes, expected {}", out.len(), len)));
+    }
+    Ok(out)
+}
+
+#[cfg(all(test, feature = "compress"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_roundtrip() {
+        let data: Vec<u8> = (0..4096u32).flat_map(|i| ((i / 7) as f32).to_le_bytes()).collect();
+        for codec in [BlockCodec::Lz4, BlockCodec::Zlib] {
+            let block = encode_block(codec, &data);
+            assert_eq!(block[0], codec.tag());
+            assert!(block.len() < data.len());
+            assert_eq!(decoded_len(&block, block.len() as u64).unwrap(), data.len() as u64);
+            assert_eq!(decode_block(&block).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_incompressible_block_is_stored() {
+        let data = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let block = encode_block(BlockCodec::Lz4, &data);
+        assert_eq!(block[0], TAG_STORED);
+        assert_eq!(block.len(), data.len() + 1);
+        assert_eq!(decoded_len(&block, block.len() as u64).unwrap(), data.len() as u64);
+        assert_eq!(decode_block(&block).unwrap(), data);
+    }
+
+    #[test]
+    fn test_corrupt_block_is_an_error() {
+        let data = vec![7u8; 1000];
+        let mut block = encode_block(BlockCodec::Zlib, &data);
+        block.truncate(block.len() / 2);
+        assert!(decode_block(&block).is_err());
+        assert!(decode_block(&[9, 4, 0, 0]).is_err());
+        assert!(decode_block(&[]).is_err());
+    }
+}
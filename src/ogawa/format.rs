@@ -3,6 +3,9 @@
 /// Magic bytes at the start of an Ogawa file.
 pub const OGAWA_MAGIC: &[u8; 5] = b"Ogawa";
 
+/// HDF5 file signature, found at the start of legacy (Alembic 1.0) archives.
+pub const HDF5_MAGIC: &[u8; 8] = b"\x89HDF\r\n\x1a\n";
+
 /// Size of the file header in bytes.
 pub const HEADER_SIZE: usize = 16;
 
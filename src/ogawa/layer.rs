@@ -0,0 +1,496 @@
+//! Archive layering (the AbcCoreLayer model).
+//!
+//! Several archives read as one: objects and properties are matched by name
+//! down the hierarchy, later layers override earlier ones and add what the
+//! earlier ones lack. Objects and compound properties merge recursively;
+//! scalar and array properties are replaced whole. Two metadata hints on an
+//! object or property header in a layer change that:
+//!
+//! - `prune=1` removes the object or property from the composite
+//! - `replace=1` drops what earlier layers had under that name instead of
+//!   merging with it
+//!
+//! Time samplings of all layers are pooled (equal ones shared, the base
+//! layer's keep their indices) and property headers are renumbered against
+//! the pool, so schema readers see one consistent archive. Opened through
+//! [`crate::abc::IArchive::open_layered`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::abc_impl::{CompoundData, OgawaArchiveReader, OgawaObjectReader, OgawaPropertyReader};
+use super::read_util::PropertyType;
+use crate::core::{
+    ArchiveReader, ArrayPropertyReader, CompoundPropertyReader, MetaData, ObjectHeader, ObjectReader,
+    PropertyHeader, PropertyReader, SampleDigest, ScalarPropertyReader, TimeSampling,
+};
+use crate::util::{Error, Result};
+
+/// Metadata key removing an object or property from the composite.
+pub const LAYER_PRUNE_KEY: &str = "prune";
+/// Metadata key replacing, rather than merging with, earlier layers.
+pub const LAYER_REPLACE_KEY: &str = "replace";
+
+/// Mark an object or property header as pruning its name from earlier layers.
+pub fn set_layer_prune(md: &mut MetaData, prune: bool) {
+    set_flag(md, LAYER_PRUNE_KEY, prune);
+}
+
+/// Mark an object or property header as replacing its name in earlier layers.
+pub fn set_layer_replace(md: &mut MetaData, replace: bool) {
+    set_flag(md, LAYER_REPLACE_KEY, replace);
+}
+
+fn set_flag(md: &mut MetaData, key: &str, on: bool) {
+    if on {
+        md.set(key, "1");
+    } else {
+        md.remove(key);
+    }
+}
+
+fn flag(md: &MetaData, key: &str) -> bool {
+    md.get(key) == Some("1")
+}
+
+/// Metadata of a composited header: later layers' keys win, hints dropped.
+fn merged_metadata<'a>(layers: impl IntoIterator<Item = &'a MetaData>) -> MetaData {
+    let mut md = MetaData::new();
+    for layer in layers {
+        md.append(layer);
+    }
+    md.remove(LAYER_PRUNE_KEY);
+    md.remove(LAYER_REPLACE_KEY);
+    md
+}
+
+/// Time sampling pool shared by every reader of a layered archive.
+struct LayerContext {
+    time_samplings: Vec<TimeSampling>,
+    /// Per layer: own time sampling index -> pooled index.
+    remap: Vec<Vec<u32>>,
+}
+
+impl LayerContext {
+    fn new(layers: &[OgawaArchiveReader]) -> Self {
+        let mut time_samplings: Vec<TimeSampling> = Vec::new();
+        let remap = layers.iter().map(|layer| {
+            (0..layer.getNumTimeSamplings()).map(|i| {
+                let ts = layer.getTimeSampling(i).cloned().unwrap_or_default();
+                let pooled = time_samplings.iter().position(|t| t.is_equivalent(&ts)).unwrap_or_else(|| {
+                    time_samplings.push(ts);
+                    time_samplings.len() - 1
+                });
+                pooled as u32
+            }).collect()
+        }).collect();
+        Self { time_samplings, remap }
+    }
+
+    fn pooled_index(&self, layer: usize, index: u32) -> u32 {
+        self.remap[layer].get(index as usize).copied().unwrap_or(0)
+    }
+}
+
+// ============================================================================
+// Archive
+// ============================================================================
+
+/// Read-only composite of several Ogawa archives, base layer first.
+pub struct LayeredArchiveReader {
+    name: String,
+    layers: Vec<OgawaArchiveReader>,
+    ctx: Arc<LayerContext>,
+    max_samples: Vec<usize>,
+    meta_data: MetaData,
+    root: LayeredObject,
+}
+
+impl LayeredArchiveReader {
+    /// Composite `layers`; later layers override earlier ones.
+    pub fn new(layers: Vec<OgawaArchiveReader>) -> Result<Self> {
+        if layers.is_empty() {
+            return Err(Error::invalid("layered archive needs at least one layer"));
+        }
+        let ctx = Arc::new(LayerContext::new(&layers));
+        let mut max_samples = vec![0; ctx.time_samplings.len()];
+        for (layer, reader) in layers.iter().enumerate() {
+            for (i, &pooled) in ctx.remap[layer].iter().enumerate() {
+                let n = reader.getMaxNumSamplesForTimeSamplingIndex(i).unwrap_or(0);
+                max_samples[pooled as usize] = max_samples[pooled as usize].max(n);
+            }
+        }
+        let meta_data = merged_metadata(layers.iter().map(|l| l.getArchiveMetaData()));
+        let sources = layers.iter().enumerate().map(|(i, l)| (i, l.root_object())).collect();
+        let root_header = ObjectHeader {
+            meta_data: meta_data.clone(),
+            ..ObjectReader::getHeader(&layers[0]).clone()
+        };
+        let root = LayeredObject::new(root_header, sources, ctx.clone());
+        Ok(Self { name: ArchiveReader::getName(&layers[0]).to_string(), layers, ctx, max_samples, meta_data, root })
+    }
+
+    /// Number of composited archives.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Name (path) of each layer, base first.
+    pub fn layer_names(&self) -> Vec<&str> {
+        self.layers.iter().map(ArchiveReader::getName).collect()
+    }
+}
+
+impl ArchiveReader for LayeredArchiveReader {
+    /// The base layer's name.
+    fn getName(&self) -> &str {
+        &self.name
+    }
+
+    fn getNumTimeSamplings(&self) -> usize {
+        self.ctx.time_samplings.len()
+    }
+
+    fn getTimeSampling(&self, index: usize) -> Option<&TimeSampling> {
+        self.ctx.time_samplings.get(index)
+    }
+
+    fn getTop(&self) -> &dyn ObjectReader {
+        &self.root
+    }
+
+    fn getArchiveVersion(&self) -> i32 {
+        self.layers[0].getArchiveVersion()
+    }
+
+    fn getMaxNumSamplesForTimeSamplingIndex(&self, index: usize) -> Option<usize> {
+        self.max_samples.get(index).copied()
+    }
+
+    fn getArchiveMetaData(&self) -> &MetaData {
+        &self.meta_data
+    }
+
+    fn setVerifyHashes(&self, verify: bool) -> bool {
+        self.layers.iter().all(|l| l.setVerifyHashes(verify))
+    }
+
+    fn getVerifyHashes(&self) -> bool {
+        self.layers.iter().all(|l| l.getVerifyHashes())
+    }
+
+    fn findObject(&self, path: &str) -> Option<Box<dyn ObjectReader + '_>> {
+        let mut parts = path.split('/').filter(|s| !s.is_empty());
+        let mut current = self.root.layered_child(self.root.getChildIndex(parts.next()?)?)?;
+        for part in parts {
+            current = current.layered_child(current.getChildIndex(part)?)?;
+        }
+        Some(Box::new(current))
+    }
+}
+
+// ============================================================================
+// Objects
+// ============================================================================
+
+/// A child name and where it comes from: (source index, child index) pairs.
+struct ChildEntry {
+    name: String,
+    from: Vec<(usize, usize)>,
+}
+
+/// Object merged from the same-named objects of several layers.
+struct LayeredObject {
+    header: ObjectHeader,
+    /// Contributing objects with their layer, earliest first.
+    sources: Vec<(usize, OgawaObjectReader)>,
+    children: Vec<ChildEntry>,
+    child_indices: HashMap<String, usize>,
+    properties: LayeredCompound,
+    ctx: Arc<LayerContext>,
+}
+
+impl LayeredObject {
+    fn new(header: ObjectHeader, sources: Vec<(usize, OgawaObjectReader)>, ctx: Arc<LayerContext>) -> Self {
+        let mut children: Vec<ChildEntry> = Vec::new();
+        for (si, (_, source)) in sources.iter().enumerate() {
+            for (ci, child) in source.child_headers().iter().enumerate() {
+                let existing = children.iter().position(|c| c.name == child.name);
+                match existing {
+                    _ if flag(&child.metadata, LAYER_PRUNE_KEY) => {
+                        if let Some(i) = existing {
+                            children.remove(i);
+                        }
+                    }
+                    Some(i) if flag(&child.metadata, LAYER_REPLACE_KEY) => children[i].from = vec![(si, ci)],
+                    Some(i) => children[i].from.push((si, ci)),
+                    None => children.push(ChildEntry { name: child.name.clone(), from: vec![(si, ci)] }),
+                }
+            }
+        }
+        let child_indices = children.iter().enumerate().map(|(i, c)| (c.name.clone(), i)).collect();
+        let properties = LayeredCompound::merge(
+            PropertyHeader::compound(".prop"),
+            sources.iter().map(|(layer, source)| (*layer, source.compound_data())),
+            ctx.clone(),
+        );
+        Self { header, sources, children, child_indices, properties, ctx }
+    }
+
+    fn layered_child(&self, index: usize) -> Option<LayeredObject> {
+        let entry = self.children.get(index)?;
+        let sources: Vec<(usize, OgawaObjectReader)> = entry.from.iter()
+            .filter_map(|&(si, ci)| {
+                let (layer, source) = &self.sources[si];
+                match source.child_reader(ci)? {
+                    Ok(reader) => Some((*layer, reader)),
+                    Err(_e) => {
+                        #[cfg(debug_assertions)]
+                        eprintln!("[alembic] Warning: failed to read layer {} child '{}': {}", layer, entry.name, _e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        let (_, last) = sources.last()?;
+        let header = ObjectHeader {
+            meta_data: merged_metadata(sources.iter().map(|(_, s)| &s.getHeader().meta_data)),
+            ..last.getHeader().clone()
+        };
+        Some(LayeredObject::new(header, sources, self.ctx.clone()))
+    }
+
+    fn last_source(&self) -> Option<&OgawaObjectReader> {
+        self.sources.last().map(|(_, s)| s)
+    }
+}
+
+impl ObjectReader for LayeredObject {
+    fn getHeader(&self) -> &ObjectHeader {
+        &self.header
+    }
+
+    fn getParent(&self) -> Option<&dyn ObjectReader> {
+        None
+    }
+
+    fn getNumChildren(&self) -> usize {
+        self.children.len()
+    }
+
+    fn getChildByIndex(&self, index: usize) -> Option<Box<dyn ObjectReader + '_>> {
+        Some(Box::new(self.layered_child(index)?))
+    }
+
+    fn getChild(&self, name: &str) -> Option<Box<dyn ObjectReader + '_>> {
+        self.getChildByIndex(self.getChildIndex(name)?)
+    }
+
+    fn getChildIndex(&self, name: &str) -> Option<usize> {
+        self.child_indices.get(name).copied()
+    }
+
+    fn getProperties(&self) -> &dyn CompoundPropertyReader {
+        &self.properties
+    }
+
+    fn getArchiveTimeSampling(&self, index: usize) -> Option<&TimeSampling> {
+        self.ctx.time_samplings.get(index)
+    }
+
+    fn isInstanceRoot(&self) -> bool {
+        self.last_source().is_some_and(|s| s.isInstanceRoot())
+    }
+
+    fn isInstanceDescendant(&self) -> bool {
+        self.last_source().is_some_and(|s| s.isInstanceDescendant())
+    }
+
+    fn instanceSourcePath(&self) -> &str {
+        self.last_source().map_or("", |s| s.instanceSourcePath())
+    }
+
+    /// Stored hashes only describe a single layer's object.
+    fn getPropertiesHash(&self) -> Option<[u8; 16]> {
+        match self.sources.as_slice() {
+            [(_, only)] => only.getPropertiesHash(),
+            _ => None,
+        }
+    }
+
+    fn getChildrenHash(&self) -> Option<[u8; 16]> {
+        match self.sources.as_slice() {
+            [(_, only)] => only.getChildrenHash(),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Properties
+// ============================================================================
+
+/// Same-named properties of several layers as (layer, reader), earliest first.
+type Contributions = Vec<(usize, Arc<OgawaPropertyReader>)>;
+
+/// Compound property merged from the same-named compounds of several layers.
+struct LayeredCompound {
+    header: PropertyHeader,
+    /// Sub-property names and their contributions.
+    entries: Vec<(String, Contributions)>,
+    ctx: Arc<LayerContext>,
+}
+
+impl LayeredCompound {
+    fn merge<'a>(
+        header: PropertyHeader,
+        sources: impl IntoIterator<Item = (usize, &'a CompoundData)>,
+        ctx: Arc<LayerContext>,
+    ) -> Self {
+        let mut entries: Vec<(String, Contributions)> = Vec::new();
+        for (layer, data) in sources {
+            for (i, parsed) in data.property_headers().iter().enumerate() {
+                let existing = entries.iter().position(|(name, _)| *name == parsed.name);
+                if flag(&parsed.metadata, LAYER_PRUNE_KEY) {
+                    if let Some(i) = existing {
+                        entries.remove(i);
+                    }
+                    continue;
+                }
+                let Some(reader) = data.property_reader(i) else { continue };
+                let reader = Arc::new(reader);
+                match existing {
+                    // Compounds over compounds merge; anything else replaces
+                    Some(e) if parsed.property_type == PropertyType::Compound
+                        && !flag(&parsed.metadata, LAYER_REPLACE_KEY)
+                        && entries[e].1.last().is_some_and(|(_, r)| r.isCompound()) =>
+                    {
+                        entries[e].1.push((layer, reader));
+                    }
+                    Some(e) => entries[e].1 = vec![(layer, reader)],
+                    None => entries.push((parsed.name.clone(), vec![(layer, reader)])),
+                }
+            }
+        }
+        Self { header, entries, ctx }
+    }
+
+    fn layered_property(&self, index: usize) -> Option<Box<dyn PropertyReader + '_>> {
+        let (_, from) = self.entries.get(index)?;
+        let (layer, last) = from.last()?;
+        let mut header = last.getHeader().clone();
+        header.meta_data = merged_metadata(from.iter().map(|(_, r)| &r.getHeader().meta_data));
+        if last.isCompound() {
+            let sources = from.iter().filter_map(|(l, r)| Some((*l, r.get_compound_data()?)));
+            return Some(Box::new(LayeredCompound::merge(header, sources, self.ctx.clone())));
+        }
+        header.time_sampling_index = self.ctx.pooled_index(*layer, header.time_sampling_index);
+        Some(Box::new(LayeredLeaf { header, reader: last.clone(), ctx: self.ctx.clone() }))
+    }
+}
+
+impl PropertyReader for LayeredCompound {
+    fn getHeader(&self) -> &PropertyHeader {
+        &self.header
+    }
+
+    fn asCompound(&self) -> Option<&dyn CompoundPropertyReader> {
+        Some(self)
+    }
+}
+
+impl CompoundPropertyReader for LayeredCompound {
+    fn getNumProperties(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn getProperty(&self, index: usize) -> Option<Box<dyn PropertyReader + '_>> {
+        self.layered_property(index)
+    }
+
+    fn getPropertyByName(&self, name: &str) -> Option<Box<dyn PropertyReader + '_>> {
+        self.layered_property(self.entries.iter().position(|(n, _)| n == name)?)
+    }
+}
+
+/// Scalar or array property taken whole from one layer, its time sampling
+/// index renumbered against the pool.
+struct LayeredLeaf {
+    header: PropertyHeader,
+    reader: Arc<OgawaPropertyReader>,
+    ctx: Arc<LayerContext>,
+}
+
+impl PropertyReader for LayeredLeaf {
+    fn getHeader(&self) -> &PropertyHeader {
+        &self.header
+    }
+
+    fn getTimeSampling(&self) -> Option<&TimeSampling> {
+        self.ctx.time_samplings.get(self.header.time_sampling_index as usize)
+    }
+
+    fn asScalar(&self) -> Option<&dyn ScalarPropertyReader> {
+        self.reader.asScalar().map(|_| self as &dyn ScalarPropertyReader)
+    }
+
+    fn asArray(&self) -> Option<&dyn ArrayPropertyReader> {
+        self.reader.asArray().map(|_| self as &dyn ArrayPropertyReader)
+    }
+}
+
+impl ScalarPropertyReader for LayeredLeaf {
+    fn getNumSamples(&self) -> usize {
+        ScalarPropertyReader::getNumSamples(&*self.reader)
+    }
+
+    fn isConstant(&self) -> bool {
+        ScalarPropertyReader::isConstant(&*self.reader)
+    }
+
+    fn getSample(&self, index: usize, out: &mut [u8]) -> Result<()> {
+        ScalarPropertyReader::getSample(&*self.reader, index, out)
+    }
+
+    fn getRawSample(&self, index: usize) -> Result<Vec<u8>> {
+        ScalarPropertyReader::getRawSample(&*self.reader, index)
+    }
+
+    fn getKey(&self, index: usize) -> Result<SampleDigest> {
+        ScalarPropertyReader::getKey(&*self.reader, index)
+    }
+}
+
+impl ArrayPropertyReader for LayeredLeaf {
+    fn getNumSamples(&self) -> usize {
+        ArrayPropertyReader::getNumSamples(&*self.reader)
+    }
+
+    fn isConstant(&self) -> bool {
+        ArrayPropertyReader::isConstant(&*self.reader)
+    }
+
+    fn getSampleLen(&self, index: usize) -> Result<usize> {
+        self.reader.getSampleLen(index)
+    }
+
+    fn getSample(&self, index: usize, out: &mut [u8]) -> Result<usize> {
+        ArrayPropertyReader::getSample(&*self.reader, index, out)
+    }
+
+    fn getSampleVec(&self, index: usize) -> Result<Vec<u8>> {
+        ArrayPropertyReader::getSampleVec(&*self.reader, index)
+    }
+
+    fn withSample(&self, index: usize, f: &mut dyn FnMut(&[u8])) -> Result<()> {
+        self.reader.withSample(index, f)
+    }
+
+    fn getKey(&self, index: usize) -> Result<SampleDigest> {
+        ArrayPropertyReader::getKey(&*self.reader, index)
+    }
+
+    fn getDimensions(&self, index: usize) -> Result<Vec<usize>> {
+        self.reader.getDimensions(index)
+    }
+}
@@ -24,9 +24,17 @@ mod reader;
 pub mod writer;
 mod abc_impl;
 mod read_util;
+mod layer;
+pub mod compress;
+mod source;
+#[cfg(feature = "remote")]
+pub mod remote;
 
 pub use format::*;
 pub use reader::*;
 pub use writer::*;
 pub use abc_impl::*;
 pub use read_util::*;
+pub use layer::*;
+pub use compress::{BlockCodec, BLOCK_COMPRESSION_KEY};
+pub use source::OgawaSource;
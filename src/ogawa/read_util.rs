@@ -44,6 +44,10 @@ pub const MIN_ALEMBIC_VERSION: i32 = 10000;
 pub fn read_time_samplings_and_max(
     data: &IData,
 ) -> Result<(Vec<TimeSampling>, Vec<u32>)> {
+    parse_time_samplings(data).map_err(|e| e.at_offset(data.pos()))
+}
+
+fn parse_time_samplings(data: &IData) -> Result<(Vec<TimeSampling>, Vec<u32>)> {
     let mut time_samples = Vec::new();
     let mut max_samples = Vec::new();
     
@@ -71,7 +75,7 @@ pub fn read_time_samplings_and_max(
         let num_samples = read_u32_le(&buf[pos..]) as usize;
         pos += 4;
         
-        if num_samples == 0 || pos + 8 * num_samples > buf_size {
+        if num_samples == 0 || num_samples > (buf_size - pos) / 8 {
             return Err(Error::invalid("TimeSamples sample times invalid"));
         }
         
@@ -82,33 +86,39 @@ pub fn read_time_samplings_and_max(
             pos += 8;
         }
         
-        // Determine sampling type
-        // Acyclic detection: C++ uses std::numeric_limits<chrono_t>::max() / 32.0
-        // That's ~5.6e306, so we check for values > 1e306 to detect acyclic.
-        let ts = if tpc > 1e306 {
-            // Acyclic: explicit times for each sample
-            TimeSampling::acyclic(sample_times)
-        } else if num_samples == 1 {
-            // Uniform: single stored time = start_time, tpc = time between samples
-            let start_time = sample_times[0];
-            TimeSampling::uniform(tpc, start_time)
-        } else {
-            // Cyclic: multiple times per cycle that repeat
-            TimeSampling::cyclic(tpc, sample_times)
-        };
-        
-        time_samples.push(ts);
+        time_samples.push(stored_time_sampling(tpc, sample_times));
     }
     
     Ok((time_samples, max_samples))
 }
 
+/// Time sampling from its stored time per cycle and sample times (at least
+/// one), as written by both Ogawa and legacy HDF5 archives.
+pub(crate) fn stored_time_sampling(tpc: f64, sample_times: Vec<f64>) -> TimeSampling {
+    // Acyclic detection: C++ uses std::numeric_limits<chrono_t>::max() / 32.0
+    // That's ~5.6e306, so we check for values > 1e306 to detect acyclic.
+    if tpc > 1e306 {
+        // Acyclic: explicit times for each sample
+        TimeSampling::acyclic(sample_times)
+    } else if sample_times.len() == 1 {
+        // Uniform: single stored time = start_time, tpc = time between samples
+        TimeSampling::uniform(tpc, sample_times[0])
+    } else {
+        // Cyclic: multiple times per cycle that repeat
+        TimeSampling::cyclic(tpc, sample_times)
+    }
+}
+
 // ============================================================================
 // Indexed Metadata Parsing
 // ============================================================================
 
 /// Read indexed metadata from archive data.
 pub fn read_indexed_metadata(data: &IData) -> Result<Vec<MetaData>> {
+    parse_indexed_metadata(data).map_err(|e| e.at_offset(data.pos()))
+}
+
+fn parse_indexed_metadata(data: &IData) -> Result<Vec<MetaData>> {
     let mut metadata_vec = Vec::new();
     
     // First entry is always empty metadata
@@ -143,7 +153,7 @@ pub fn read_indexed_metadata(data: &IData) -> Result<Vec<MetaData>> {
             metadata_vec.push(MetaData::new());
         } else {
             let metadata_str = std::str::from_utf8(&buf[pos..pos + metadata_size])
-                .map_err(|e| Error::other(format!("Invalid UTF-8 in metadata: {}", e)))?;
+                .map_err(|e| Error::invalid(format!("Invalid UTF-8 in metadata: {}", e)))?;
             pos += metadata_size;
             
             let md = MetaData::parse(metadata_str);
@@ -171,6 +181,14 @@ pub fn read_object_headers(
     data: &IData,
     parent_name: &str,
     indexed_metadata: &[MetaData],
+) -> Result<Vec<ParsedObjectHeader>> {
+    parse_object_headers(data, parent_name, indexed_metadata).map_err(|e| e.at_offset(data.pos()))
+}
+
+fn parse_object_headers(
+    data: &IData,
+    parent_name: &str,
+    indexed_metadata: &[MetaData],
 ) -> Result<Vec<ParsedObjectHeader>> {
     let mut headers = Vec::new();
     
@@ -201,7 +219,7 @@ pub fn read_object_headers(
         }
         
         let name = std::str::from_utf8(&buf[pos..pos + name_size])
-            .map_err(|e| Error::other(format!("Invalid UTF-8 in object name: {}", e)))?
+            .map_err(|e| Error::invalid(format!("Invalid UTF-8 in object name: {}", e)))?
             .to_string();
         pos += name_size;
         
@@ -228,7 +246,7 @@ pub fn read_object_headers(
             }
             
             let metadata_str = std::str::from_utf8(&buf[pos..pos + metadata_size])
-                .map_err(|e| Error::other(format!("Invalid UTF-8 in metadata: {}", e)))?;
+                .map_err(|e| Error::invalid(format!("Invalid UTF-8 in metadata: {}", e)))?;
             pos += metadata_size;
             
             MetaData::parse(metadata_str)
@@ -267,6 +285,9 @@ pub struct ParsedPropertyHeader {
     pub property_type: PropertyType,
     pub metadata: MetaData,
     pub data_type: DataType,
+    /// Stored POD code when it is not one this library knows
+    /// (`data_type.pod` is then `Unknown`).
+    pub unknown_pod: Option<u8>,
     pub time_sampling_index: u32,
     pub is_scalar_like: bool,
     pub is_homogenous: bool,
@@ -324,6 +345,13 @@ fn get_u32_with_hint(buf: &[u8], buf_size: usize, size_hint: u32, pos: &mut usiz
 pub fn read_property_headers(
     data: &IData,
     indexed_metadata: &[MetaData],
+) -> Result<Vec<ParsedPropertyHeader>> {
+    parse_property_headers(data, indexed_metadata).map_err(|e| e.at_offset(data.pos()))
+}
+
+fn parse_property_headers(
+    data: &IData,
+    indexed_metadata: &[MetaData],
 ) -> Result<Vec<ParsedPropertyHeader>> {
     let mut headers = Vec::new();
     
@@ -356,13 +384,16 @@ pub fn read_property_headers(
         // Size hint (bits 2-3)
         let size_hint = (info & 0x000c) >> 2;
         
-        let (data_type, time_sampling_index, is_homogenous, 
+        let (data_type, unknown_pod, time_sampling_index, is_homogenous,
              next_sample_index, first_changed_index, last_changed_index) = 
         if property_type != PropertyType::Compound {
             // POD type (bits 4-7)
             let pod = ((info & 0x00f0) >> 4) as u8;
-            let pod_type = pod_from_u8(pod)
-                .ok_or_else(|| Error::invalid(format!("Invalid POD type: {}", pod)))?;
+            // Codes from newer writers are kept as raw bytes, not rejected
+            let (pod_type, unknown_pod) = match pod_from_u8(pod) {
+                Some(pod_type) => (pod_type, None),
+                None => (PlainOldDataType::Unknown, Some(pod)),
+            };
             
             // Extent (bits 12-19)
             let extent = ((info & 0xff000) >> 12) as u8;
@@ -395,10 +426,10 @@ pub fn read_property_headers(
                 0
             };
             
-            (data_type, time_sampling_index, is_homogenous,
+            (data_type, unknown_pod, time_sampling_index, is_homogenous,
              next_sample_index, first_changed_index, last_changed_index)
         } else {
-            (DataType::default(), 0, false, 0, 0, 0)
+            (DataType::default(), None, 0, false, 0, 0, 0)
         };
         
         // Property name
@@ -408,7 +439,7 @@ pub fn read_property_headers(
         }
         
         let name = std::str::from_utf8(&buf[pos..pos + name_size])
-            .map_err(|e| Error::other(format!("Invalid UTF-8 in property name: {}", e)))?
+            .map_err(|e| Error::invalid(format!("Invalid UTF-8 in property name: {}", e)))?
             .to_string();
         pos += name_size;
         
@@ -426,7 +457,7 @@ pub fn read_property_headers(
                 MetaData::new()
             } else {
                 let metadata_str = std::str::from_utf8(&buf[pos..pos + metadata_size])
-                    .map_err(|e| Error::other(format!("Invalid UTF-8 in metadata: {}", e)))?;
+                    .map_err(|e| Error::invalid(format!("Invalid UTF-8 in metadata: {}", e)))?;
                 pos += metadata_size;
                 MetaData::parse(metadata_str)
             }
@@ -441,6 +472,7 @@ pub fn read_property_headers(
             property_type,
             metadata,
             data_type,
+            unknown_pod,
             time_sampling_index,
             is_scalar_like,
             is_homogenous,
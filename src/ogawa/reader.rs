@@ -2,35 +2,89 @@
 //!
 //! # Memory Mapping Safety
 //!
-//! By default, files are memory-mapped for better performance with large archives.
-//! This has the following implications:
+//! With the `mmap` feature (on by default), files are memory-mapped for better
+//! performance with large archives. Without it, [`IStreams::open`] reads the
+//! whole file into memory instead. Mapping has the following implications:
 //!
 //! - **Thread-safe reads**: Multiple threads can read concurrently without issues.
 //! - **External modification risk**: If another process modifies or truncates the file
 //!   while it's mapped, behavior is undefined (may cause SIGBUS or corrupt reads).
-//! - **Mitigation**: Use `IStreams::open_opts(path, false)` to disable mmap and use
-//!   buffered I/O instead when working with files that may be modified externally.
+//! - **Mitigation**: Use `IStreams::from_bytes` to read the whole archive into memory
+//!   when working with files that may be modified externally (also the only option
+//!   on targets without a filesystem, such as WASM).
+//!
+//! # Streaming Mode
+//!
+//! [`IArchive::open_mmap`] opens an archive for traversal of multi-GB caches:
+//! the OS is told to expect random access (no read-ahead of payload pages),
+//! group offset tables are read on demand instead of when a group is opened,
+//! and data blocks are only touched when a sample is actually read. Blocks
+//! that were read go through an LRU cache with a byte budget, so revisiting
+//! them doesn't fault the pages back in.
+//!
+//! # Other Sources
+//!
+//! [`IStreams::from_source`] reads through an [`OgawaSource`] instead (for
+//! example `HttpSource` from the `remote` feature). Such archives can't hand out borrowed slices
+//! ([`IData::slice`]); everything else works the same.
 
+#[cfg(feature = "mmap")]
 use std::fs::File;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+#[cfg(feature = "mmap")]
 use memmap2::Mmap;
 
 use super::format::*;
-use crate::util::{Error, Result};
+use super::source::OgawaSource;
+use crate::core::{compute_digest, ArraySampleKey, DecodeArena, ReadArraySampleCache, SampleCacheStats};
+use crate::util::{Error, PlainOldDataType, Result};
+
+/// Backing storage for input streams.
+enum Backing {
+    #[cfg(feature = "mmap")]
+    Mmap(Mmap),
+    Memory(Vec<u8>),
+    Source(Box<dyn OgawaSource>),
+}
+
+impl Backing {
+    /// The whole archive, when it is addressable memory.
+    #[inline]
+    fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            #[cfg(feature = "mmap")]
+            Backing::Mmap(m) => Some(m),
+            Backing::Memory(v) => Some(v),
+            Backing::Source(_) => None,
+        }
+    }
+}
 
-/// Input streams for reading Ogawa data via memory mapping.
+/// Input streams for reading Ogawa data via memory mapping or an in-memory buffer.
 /// Memory mapping provides efficient random access for large Alembic files.
 pub struct IStreams {
-    mmap: Mmap,
+    mmap: Backing,
     version: u16,
     frozen: bool,
     size: u64,
+    /// Data block cache (streaming mode only).
+    blocks: Option<ReadArraySampleCache>,
+    /// Pool for transient decode buffers (disabled until given a budget).
+    arena: DecodeArena,
+    /// Check keyed sample payloads against their stored digest on read.
+    verify_hashes: AtomicBool,
+    /// Keyed sample payloads are block compressed (set from the archive
+    /// metadata once the archive is opened).
+    #[cfg(feature = "compress")]
+    block_compressed: AtomicBool,
 }
 
 impl IStreams {
     /// Open a file for reading with memory mapping.
+    #[cfg(feature = "mmap")]
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let file = File::open(path).map_err(|e| {
@@ -57,7 +111,84 @@ impl IStreams {
 
         let (version, frozen) = Self::parse_header(&mmap)?;
 
-        Ok(Self { mmap, version, frozen, size })
+        Ok(Self {
+            mmap: Backing::Mmap(mmap),
+            version,
+            frozen,
+            size,
+            blocks: None,
+            arena: DecodeArena::default(),
+            verify_hashes: AtomicBool::new(false),
+            #[cfg(feature = "compress")]
+            block_compressed: AtomicBool::new(false),
+        })
+    }
+
+    /// Open a file for reading into memory (built without the `mmap` feature).
+    #[cfg(not(feature = "mmap"))]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Error::FileNotFound(path.to_path_buf())
+            } else {
+                Error::Io(e)
+            }
+        })?;
+        Self::from_bytes(data)
+    }
+
+    /// Open a file in streaming mode: random-access mapping, lazy group
+    /// offsets and an LRU cache of at most `block_cache_bytes` for data blocks.
+    pub fn open_mmap(path: impl AsRef<Path>, block_cache_bytes: usize) -> Result<Self> {
+        let mut streams = Self::open(path)?;
+        #[cfg(all(unix, feature = "mmap"))]
+        if let Backing::Mmap(mmap) = &streams.mmap {
+            // Only a hint; ignore platforms/filesystems that refuse it
+            let _ = mmap.advise(memmap2::Advice::Random);
+        }
+        let blocks = ReadArraySampleCache::new(block_cache_bytes);
+        blocks.set_samples_per_property(0);
+        streams.blocks = Some(blocks);
+        Ok(streams)
+    }
+
+    /// Create streams over an in-memory copy of an archive.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let size = data.len() as u64;
+        let (version, frozen) = Self::parse_header(&data)?;
+        Ok(Self {
+            mmap: Backing::Memory(data),
+            version,
+            frozen,
+            size,
+            blocks: None,
+            arena: DecodeArena::default(),
+            verify_hashes: AtomicBool::new(false),
+            #[cfg(feature = "compress")]
+            block_compressed: AtomicBool::new(false),
+        })
+    }
+
+    /// Create streams reading through a custom byte source.
+    pub fn from_source(source: Box<dyn OgawaSource>) -> Result<Self> {
+        let size = source.size();
+        let mut header = [0u8; HEADER_SIZE];
+        if size >= HEADER_SIZE as u64 {
+            source.read_at(0, &mut header)?;
+        }
+        let (version, frozen) = Self::parse_header(&header[..(size as usize).min(HEADER_SIZE)])?;
+        Ok(Self {
+            mmap: Backing::Source(source),
+            version,
+            frozen,
+            size,
+            blocks: None,
+            arena: DecodeArena::default(),
+            verify_hashes: AtomicBool::new(false),
+            #[cfg(feature = "compress")]
+            block_compressed: AtomicBool::new(false),
+        })
     }
 
     /// Parse and validate the Ogawa header.
@@ -68,6 +199,9 @@ impl IStreams {
 
         // Check magic bytes
         if &data[0..5] != OGAWA_MAGIC {
+            if data.starts_with(HDF5_MAGIC) {
+                return Err(Error::Hdf5Archive);
+            }
             return Err(Error::InvalidMagic);
         }
 
@@ -104,37 +238,112 @@ impl IStreams {
         self.size
     }
 
+    /// Check if the streams were opened with [`open_mmap`](Self::open_mmap).
+    #[inline]
+    pub fn is_streaming(&self) -> bool {
+        self.blocks.is_some()
+    }
+
+    /// Data block cache statistics (streaming mode only).
+    pub fn block_cache_stats(&self) -> Option<SampleCacheStats> {
+        self.blocks.as_ref().map(ReadArraySampleCache::stats)
+    }
+
+    /// Change the data block cache budget (streaming mode only).
+    pub fn set_block_cache_size(&self, bytes: usize) {
+        if let Some(blocks) = &self.blocks {
+            blocks.set_max_size(bytes);
+        }
+    }
+
+    /// Check every keyed sample payload against its stored digest when it
+    /// is read (see [`IData::verify_payload`]).
+    pub fn set_verify_hashes(&self, verify: bool) {
+        self.verify_hashes.store(verify, Ordering::Relaxed);
+    }
+
+    /// Check if sample payloads are verified on read.
+    #[inline]
+    pub fn verifies_hashes(&self) -> bool {
+        self.verify_hashes.load(Ordering::Relaxed)
+    }
+
+    /// Mark keyed sample payloads as block compressed.
+    #[cfg(feature = "compress")]
+    pub(crate) fn set_block_compressed(&self, compressed: bool) {
+        self.block_compressed.store(compressed, Ordering::Relaxed);
+    }
+
+    /// Check if keyed sample payloads are block compressed.
+    #[cfg(feature = "compress")]
+    pub fn is_block_compressed(&self) -> bool {
+        self.block_compressed.load(Ordering::Relaxed)
+    }
+
+    /// Scratch buffer pool for transient decode buffers.
+    #[inline]
+    pub fn arena(&self) -> &DecodeArena {
+        &self.arena
+    }
+
+    /// Read a whole data block, through the block cache in streaming mode.
+    pub fn read_block(&self, pos: u64, len: usize) -> Result<Vec<u8>> {
+        let Some(blocks) = &self.blocks else {
+            return self.read_bytes(pos, len);
+        };
+        let key = ArraySampleKey::new(pos, 0);
+        if let Some(data) = blocks.get(&key) {
+            return Ok(data.as_ref().clone());
+        }
+        let data = self.read_bytes(pos, len)?;
+        blocks.insert(key, data.clone());
+        Ok(data)
+    }
+
     /// Get the root group position from the header.
     pub fn root_pos(&self) -> Result<u64> {
         self.read_u64(ROOT_POS_OFFSET as u64)
     }
 
+    /// Check that `len` bytes at `pos` lie inside the file.
+    #[inline]
+    fn check_range(&self, pos: u64, len: u64) -> Result<()> {
+        match pos.checked_add(len) {
+            Some(end) if end <= self.size => Ok(()),
+            _ => Err(Error::corrupt(pos, format!("{} bytes run past the end of the file ({} bytes)", len, self.size))),
+        }
+    }
+
     /// Read bytes at a specific position.
     #[inline]
     pub fn read_bytes(&self, pos: u64, len: usize) -> Result<Vec<u8>> {
-        if pos + len as u64 > self.size {
-            return Err(Error::UnexpectedEof(pos + len as u64));
-        }
-        Ok(self.mmap[pos as usize..(pos as usize + len)].to_vec())
+        let mut buf = vec![0u8; len];
+        self.read_into(pos, &mut buf)?;
+        Ok(buf)
     }
 
     /// Read bytes into an existing buffer.
     #[inline]
     pub fn read_into(&self, pos: u64, buf: &mut [u8]) -> Result<()> {
-        if pos + buf.len() as u64 > self.size {
-            return Err(Error::UnexpectedEof(pos + buf.len() as u64));
+        self.check_range(pos, buf.len() as u64)?;
+        match &self.mmap {
+            Backing::Source(source) => source.read_at(pos, buf),
+            backing => {
+                let bytes = backing.bytes().unwrap_or_default();
+                buf.copy_from_slice(&bytes[pos as usize..pos as usize + buf.len()]);
+                Ok(())
+            }
         }
-        buf.copy_from_slice(&self.mmap[pos as usize..(pos as usize + buf.len())]);
-        Ok(())
     }
 
-    /// Get a slice of the memory-mapped data.
+    /// Get a slice of the memory-mapped data (not available for
+    /// [`from_source`](Self::from_source) streams).
     #[inline]
     pub fn slice(&self, pos: u64, len: usize) -> Result<&[u8]> {
-        if pos + len as u64 > self.size {
-            return Err(Error::UnexpectedEof(pos + len as u64));
-        }
-        Ok(&self.mmap[pos as usize..(pos as usize + len)])
+        self.check_range(pos, len as u64)?;
+        let bytes = self.mmap.bytes()
+            .ok_or_else(|| Error::other("archive is not memory-backed; use read_all instead of slice"))?;
+        Ok(&bytes[pos as usize..(pos as usize + len)])
     }
 
     /// Read a u64 value at the given position.
@@ -168,9 +377,31 @@ pub struct IArchive {
 impl IArchive {
     /// Open an Alembic file for reading.
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
-        let streams = Arc::new(IStreams::open(path)?);
+        Self::from_streams(IStreams::open(path)?)
+    }
+
+    /// Open an Alembic archive held in memory.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        Self::from_streams(IStreams::from_bytes(data)?)
+    }
+
+    /// Open an Alembic archive read through a custom byte source.
+    pub fn from_source(source: Box<dyn OgawaSource>) -> Result<Self> {
+        Self::from_streams(IStreams::from_source(source)?)
+    }
+
+    /// Open an Alembic file in streaming mode (see the [module docs](self)):
+    /// nothing beyond the header and root group is read until requested, and
+    /// data blocks are cached up to `block_cache_bytes`.
+    pub fn open_mmap(path: impl AsRef<Path>, block_cache_bytes: usize) -> Result<Self> {
+        Self::from_streams(IStreams::open_mmap(path, block_cache_bytes)?)
+    }
+
+    fn from_streams(streams: IStreams) -> Result<Self> {
+        let streams = Arc::new(streams);
         let root_pos = streams.root_pos()?;
-        let root = IGroup::new(streams.clone(), root_pos, false)?;
+        let light = streams.is_streaming();
+        let root = IGroup::new(streams.clone(), root_pos, light)?;
         Ok(Self { streams, root })
     }
 
@@ -227,6 +458,11 @@ impl IGroup {
         } else {
             streams.read_u64(pos)?
         };
+        // The offset table must fit in the file; rejects absurd counts before
+        // anything is allocated for them
+        if num_children.checked_mul(8).is_none_or(|table| streams.check_range(pos + 8, table).is_err()) {
+            return Err(Error::corrupt(pos, format!("group claims {} children, past the end of the file", num_children)));
+        }
 
         // Load child offsets (unless in light mode)
         let child_offsets = if light || num_children == 0 {
@@ -384,6 +620,9 @@ impl IChild {
     }
 }
 
+/// Size of the sample key in front of keyed data blocks.
+const KEY_SIZE: u64 = 16;
+
 /// Data block in the Ogawa hierarchy.
 pub struct IData {
     streams: Arc<IStreams>,
@@ -400,6 +639,9 @@ impl IData {
         } else {
             streams.read_u64(pos)?
         };
+        if pos != 0 && streams.check_range(pos + 8, size).is_err() {
+            return Err(Error::corrupt(pos, format!("data block of {} bytes runs past the end of the file", size)));
+        }
 
         Ok(Self { streams, pos, size })
     }
@@ -437,7 +679,21 @@ impl IData {
         if self.size == 0 {
             return Ok(Vec::new());
         }
-        self.streams.read_bytes(self.data_pos(), self.size as usize)
+        self.streams.read_block(self.data_pos(), self.size as usize)
+    }
+
+    /// Read `len` bytes starting `offset` bytes into the data.
+    pub fn read_range(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        if offset.checked_add(len as u64).is_none_or(|end| end > self.size) {
+            return Err(Error::other(format!(
+                "Range {}+{} exceeds data size {}",
+                offset, len, self.size
+            )));
+        }
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        self.streams.read_block(self.data_pos() + offset, len)
     }
 
     /// Read data into an existing buffer.
@@ -455,6 +711,12 @@ impl IData {
         self.streams.read_into(self.data_pos(), buf)
     }
 
+    /// Scratch buffer pool of the archive this data belongs to.
+    #[inline]
+    pub fn arena(&self) -> &DecodeArena {
+        self.streams.arena()
+    }
+
     /// Get a slice to the data (only works with mmap).
     pub fn slice(&self) -> Result<&[u8]> {
         if self.size == 0 {
@@ -463,6 +725,89 @@ impl IData {
         self.streams.slice(self.data_pos(), self.size as usize)
     }
 
+    /// Read the payload of a keyed sample block (the data after the 16-byte
+    /// key), decoding it in block-compressed archives.
+    pub fn read_keyed_payload(&self) -> Result<Vec<u8>> {
+        if self.size < KEY_SIZE {
+            return Err(Error::invalid("Keyed data block too small"));
+        }
+        let payload = self.read_range(KEY_SIZE, (self.size - KEY_SIZE) as usize)?;
+        #[cfg(feature = "compress")]
+        if self.streams.is_block_compressed() && !payload.is_empty() {
+            return super::compress::decode_block(&payload).map_err(|e| e.at_offset(self.pos));
+        }
+        Ok(payload)
+    }
+
+    /// Like [`read_keyed_payload`](Self::read_keyed_payload), into `out`
+    /// (reusing its capacity). Compressed blocks are read into an arena
+    /// scratch buffer before decoding.
+    pub fn read_keyed_payload_into(&self, out: &mut Vec<u8>) -> Result<()> {
+        if self.size < KEY_SIZE {
+            return Err(Error::invalid("Keyed data block too small"));
+        }
+        let len = (self.size - KEY_SIZE) as usize;
+        #[cfg(feature = "compress")]
+        if self.streams.is_block_compressed() && len > 0 {
+            let mut stored = self.streams.arena().take(len);
+            stored.resize(len, 0);
+            self.streams.read_into(self.data_pos() + KEY_SIZE, &mut stored)?;
+            *out = super::compress::decode_block(&stored).map_err(|e| e.at_offset(self.pos))?;
+            return Ok(());
+        }
+        out.clear();
+        out.resize(len, 0);
+        self.streams.read_into(self.data_pos() + KEY_SIZE, out)
+    }
+
+    /// The 16-byte key (digest) of a keyed sample block, without reading
+    /// the payload.
+    pub fn read_key(&self) -> Result<[u8; 16]> {
+        if self.size < KEY_SIZE {
+            return Err(Error::invalid("Keyed data block too small"));
+        }
+        let mut key = [0u8; 16];
+        self.streams.read_into(self.data_pos(), &mut key)?;
+        Ok(key)
+    }
+
+    /// Check a decoded keyed payload against the digest stored in front of
+    /// it, if the streams verify hashes (a no-op otherwise).
+    ///
+    /// The digest is MurmurHash3 x64_128 of the (uncompressed) payload, as
+    /// written by the C++ library and by `OArchive`.
+    pub fn verify_payload(&self, payload: &[u8], pod: PlainOldDataType) -> Result<()> {
+        if !self.streams.verifies_hashes() {
+            return Ok(());
+        }
+        let pod_size = match pod {
+            PlainOldDataType::String => 1,
+            PlainOldDataType::Wstring => 4,
+            _ => pod.num_bytes() as u32,
+        };
+        let stored = self.read_key()?;
+        let actual = compute_digest(payload, None, Some(pod_size));
+        if stored != actual {
+            return Err(Error::corrupt(self.pos, format!(
+                "Sample digest mismatch (stored {}, computed {})",
+                hex(&stored), hex(&actual)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Size of the (decoded) payload of a keyed sample block.
+    pub fn keyed_payload_size(&self) -> Result<u64> {
+        let stored = self.size.saturating_sub(KEY_SIZE);
+        #[cfg(feature = "compress")]
+        if self.streams.is_block_compressed() && stored > 0 {
+            let header_len = (stored as usize).min(super::compress::BLOCK_HEADER_MAX);
+            let header = self.read_range(KEY_SIZE, header_len)?;
+            return super::compress::decoded_len(&header, stored).map_err(|e| e.at_offset(self.pos));
+        }
+        Ok(stored)
+    }
+
     /// Read data as a string (UTF-8).
     pub fn read_string(&self) -> Result<String> {
         let bytes = self.read_all()?;
@@ -472,6 +817,11 @@ impl IData {
     }
 }
 
+/// Lowercase hex of a digest, for error messages.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -490,10 +840,45 @@ mod tests {
         assert!(frozen);
     }
 
+    #[test]
+    fn test_streaming_block_cache() {
+        let path = std::env::temp_dir().join(format!("ogawa_stream_{}.abc", std::process::id()));
+        {
+            let mut archive = super::super::writer::OArchive::create(&path).unwrap();
+            archive.write_archive(&super::super::writer::OObject::new("")).unwrap();
+        }
+
+        let archive = IArchive::open_mmap(&path, 1 << 16).unwrap();
+        assert!(archive.streams().is_streaming());
+        assert!(archive.root().is_light());
+        let stats = archive.streams().block_cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.entries), (0, 0));
+
+        // Same block twice: one miss, then a hit
+        let version = archive.root().data(0).unwrap();
+        assert_eq!(version.read_all().unwrap(), version.read_all().unwrap());
+        let stats = archive.streams().block_cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+        assert_eq!(stats.max_bytes, 1 << 16);
+
+        let plain = IArchive::open(&path).unwrap();
+        assert!(plain.streams().block_cache_stats().is_none());
+        assert_eq!(plain.root().data(0).unwrap().read_all().unwrap(), version.read_all().unwrap());
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_invalid_magic() {
         let header = [0u8; 16]; // All zeros, invalid magic
         let result = IStreams::parse_header(&header);
         assert!(matches!(result, Err(Error::InvalidMagic)));
     }
+
+    #[test]
+    fn test_hdf5_archive_detected() {
+        let mut header = [0u8; 16];
+        header[..8].copy_from_slice(HDF5_MAGIC);
+        let result = IStreams::parse_header(&header);
+        assert!(matches!(result, Err(Error::Hdf5Archive)));
+    }
 }
@@ -0,0 +1,508 @@
+//! Reading archives over HTTP range requests (`remote` feature).
+//!
+//! [`HttpSource`] is an [`OgawaSource`] that fetches only the parts of an
+//! archive that are actually read, so a viewer or a pipeline tool can walk
+//! a multi-GB cache in object storage without downloading it:
+//!
+//! - reads are rounded out to aligned chunks (256 KiB by default) fetched
+//!   with `Range: bytes=a-b`; runs of missing chunks go out as one request
+//! - fetched chunks are kept in an LRU cache with a byte budget
+//! - connections are kept alive and pooled, so readers on several threads
+//!   (e.g. the `parallel` feature) fetch concurrently
+//! - [`HttpSource::prefetch`] fetches ranges in the background of a read,
+//!   e.g. the sample blocks of the next frames
+//! - `*_async` variants ([`HttpSource::open_async`], [`HttpSource::read_async`],
+//!   [`HttpSource::prefetch_async`], `IArchive::open_url_async`) return a
+//!   [`RemoteFuture`] for use from async code. The transfer itself stays
+//!   blocking I/O on a shared pool of [`ASYNC_WORKERS`] helper threads that
+//!   wake the task when done, so the futures work with any executor and no
+//!   runtime is pulled in.
+//!
+//! Only plain `http://` is spoken: there is no TLS support, and `https://`
+//! URLs are rejected. Put a TLS-terminating proxy in front of `https`
+//! stores. The server must honour `Range` (answer `206`).
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//!
+//! let archive = IArchive::open_url("http://cache-store:9000/shots/sh010/anim.abc")?;
+//! for child in archive.getTop().getChildren() {
+//!     println!("{}", child.getName());
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use super::source::OgawaSource;
+use crate::core::{ArraySampleKey, ReadArraySampleCache, SampleCacheStats};
+use crate::util::{Error, Result};
+
+/// Largest response head we accept (status line + headers).
+const MAX_RESPONSE_HEAD: usize = 16 * 1024;
+
+/// Helper threads shared by all `*_async` calls; more concurrent calls queue.
+pub const ASYNC_WORKERS: usize = 8;
+
+/// Options for [`HttpSource::with_options`].
+#[derive(Debug, Clone)]
+pub struct RemoteOptions {
+    /// Fetch granularity in bytes.
+    pub chunk_size: u64,
+    /// Byte budget of the chunk cache.
+    pub cache_bytes: usize,
+    /// Connect/read timeout per request.
+    pub timeout: Duration,
+}
+
+impl Default for RemoteOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 256 * 1024,
+            cache_bytes: 64 << 20,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Transfer counters of an [`HttpSource`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoteStats {
+    /// Range requests sent.
+    pub requests: u64,
+    /// Body bytes received.
+    pub bytes_fetched: u64,
+    /// Chunk cache statistics.
+    pub cache: SampleCacheStats,
+}
+
+/// Archive bytes served over HTTP range requests (`http://` only, no TLS).
+pub struct HttpSource {
+    url: String,
+    /// `host[:port]` for the Host header.
+    host: String,
+    /// `host:port` to connect to.
+    addr: String,
+    path: String,
+    size: u64,
+    options: RemoteOptions,
+    chunks: ReadArraySampleCache,
+    /// Idle keep-alive connections.
+    pool: Mutex<Vec<BufReader<TcpStream>>>,
+    requests: AtomicU64,
+    bytes_fetched: AtomicU64,
+}
+
+impl HttpSource {
+    /// Open `url` with default options. `https://` URLs are an error.
+    pub fn open(url: &str) -> Result<Self> {
+        Self::with_options(url, RemoteOptions::default())
+    }
+
+    /// Open `url`. The first chunk is fetched right away; its response also
+    /// tells the archive size.
+    pub fn with_options(url: &str, options: RemoteOptions) -> Result<Self> {
+        let (host, addr, path) = parse_url(url)?;
+        let chunks = ReadArraySampleCache::new(options.cache_bytes);
+        chunks.set_samples_per_property(0);
+        let mut source = Self {
+            url: url.to_string(),
+            host,
+            addr,
+            path,
+            size: 0,
+            options: RemoteOptions { chunk_size: options.chunk_size.max(1), ..options },
+            chunks,
+            pool: Mutex::new(Vec::new()),
+            requests: AtomicU64::new(0),
+            bytes_fetched: AtomicU64::new(0),
+        };
+        let (body, total) = source.request(0, source.options.chunk_size - 1)?;
+        source.size = total;
+        source.store(0, body);
+        Ok(source)
+    }
+
+    /// URL this source reads.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Transfer and cache counters.
+    pub fn stats(&self) -> RemoteStats {
+        RemoteStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            bytes_fetched: self.bytes_fetched.load(Ordering::Relaxed),
+            cache: self.chunks.stats(),
+        }
+    }
+
+    /// [`open`](Self::open) without blocking the calling task.
+    pub fn open_async(url: &str) -> RemoteFuture<Self> {
+        Self::with_options_async(url, RemoteOptions::default())
+    }
+
+    /// [`with_options`](Self::with_options) without blocking the calling task.
+    pub fn with_options_async(url: &str, options: RemoteOptions) -> RemoteFuture<Self> {
+        let url = url.to_string();
+        RemoteFuture::spawn(move || Self::with_options(&url, options))
+    }
+
+    /// Read `len` bytes at `pos` without blocking the calling task.
+    pub fn read_async(self: &Arc<Self>, pos: u64, len: usize) -> RemoteFuture<Vec<u8>> {
+        let source = self.clone();
+        RemoteFuture::spawn(move || {
+            if pos.checked_add(len as u64).is_none_or(|end| end > source.size) {
+                return Err(Error::other(format!(
+                    "{}: read of {} bytes at {} is past the end ({} bytes)", source.url, len, pos, source.size
+                )));
+            }
+            let mut buf = vec![0u8; len];
+            source.read_at(pos, &mut buf)?;
+            Ok(buf)
+        })
+    }
+
+    /// [`prefetch`](Self::prefetch) without blocking the calling task.
+    pub fn prefetch_async(self: &Arc<Self>, ranges: Vec<(u64, u64)>) -> RemoteFuture<()> {
+        let source = self.clone();
+        RemoteFuture::spawn(move || source.prefetch(&ranges))
+    }
+
+    /// Fetch the chunks covering `(pos, len)` ranges that aren't cached yet,
+    /// one request per run of missing chunks, all in parallel. Later reads
+    /// of these ranges are served from the cache (budget permitting).
+    pub fn prefetch(&self, ranges: &[(u64, u64)]) -> Result<()> {
+        let mut missing = BTreeSet::new();
+        for &(pos, len) in ranges {
+            if let Some((first, last)) = self.chunk_range(pos, len) {
+                missing.extend((first..=last).filter(|&index| self.chunks.get(&self.chunk_key(index)).is_none()));
+            }
+        }
+        let runs = index_runs(missing);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = runs.iter().map(|&(first, last)| scope.spawn(move || self.fetch_run(first, last))).collect();
+            handles.into_iter().try_for_each(|h| h.join().unwrap_or_else(|_| Err(Error::other("prefetch thread panicked"))).map(drop))
+        })
+    }
+
+    fn chunk_key(&self, index: u64) -> ArraySampleKey {
+        ArraySampleKey::new(index * self.options.chunk_size, 0)
+    }
+
+    /// Indices of the chunks covering `len` bytes at `pos` (clamped to the file).
+    fn chunk_range(&self, pos: u64, len: u64) -> Option<(u64, u64)> {
+        let end = pos.saturating_add(len).min(self.size);
+        (end > pos).then(|| (pos / self.options.chunk_size, (end - 1) / self.options.chunk_size))
+    }
+
+    /// Fetch chunks `first..=last` with one request and cache them.
+    fn fetch_run(&self, first: u64, last: u64) -> Result<Vec<Arc<Vec<u8>>>> {
+        let chunk = self.options.chunk_size;
+        let start = first * chunk;
+        let end = ((last + 1) * chunk).min(self.size) - 1;
+        let (body, _) = self.request(start, end)?;
+        if body.len() as u64 != end - start + 1 {
+            return Err(Error::other(format!(
+                "{}: asked for {} bytes at {}, got {}", self.url, end - start + 1, start, body.len()
+            )));
+        }
+        Ok(body.chunks(chunk as usize).zip(first..).map(|(data, index)| self.store(index, data.to_vec())).collect())
+    }
+
+    fn store(&self, index: u64, data: Vec<u8>) -> Arc<Vec<u8>> {
+        let data = Arc::new(data);
+        self.chunks.insert_shared(self.chunk_key(index), data.clone());
+        data
+    }
+
+    /// GET `bytes=start-end` (inclusive); returns the body and the total
+    /// size from `Content-Range`. A pooled connection that turns out to be
+    /// closed is retried once on a fresh one.
+    fn request(&self, start: u64, end: u64) -> Result<(Vec<u8>, u64)> {
+        let pooled = self.pool.lock().ok().and_then(|mut pool| pool.pop());
+        let reused = pooled.is_some();
+        let conn = match pooled {
+            Some(conn) => conn,
+            None => self.connect()?,
+        };
+        match self.exchange(conn, start, end) {
+            Err(Error::Io(_)) if reused => self.exchange(self.connect()?, start, end),
+            result => result,
+        }
+    }
+
+    fn connect(&self) -> Result<BufReader<TcpStream>> {
+        let stream = TcpStream::connect(&self.addr)?;
+        stream.set_read_timeout(Some(self.options.timeout))?;
+        stream.set_write_timeout(Some(self.options.timeout))?;
+        stream.set_nodelay(true)?;
+        Ok(BufReader::new(stream))
+    }
+
+    fn exchange(&self, mut conn: BufReader<TcpStream>, start: u64, end: u64) -> Result<(Vec<u8>, u64)> {
+        let head = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nRange: bytes={}-{}\r\nUser-Agent: alembic-rs\r\nConnection: keep-alive\r\n\r\n",
+            self.path, self.host, start, end
+        );
+        conn.get_mut().write_all(head.as_bytes())?;
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        let response = read_response_head(&mut conn)?;
+        match response.status {
+            206 => {}
+            416 if start == 0 => return Ok((Vec::new(), 0)),
+            200 => return Err(Error::other(format!("{}: server ignores Range requests", self.url))),
+            status => return Err(Error::other(format!("{}: HTTP {}", self.url, status))),
+        }
+        let len = response.content_length
+            .ok_or_else(|| Error::other(format!("{}: range response without Content-Length", self.url)))?;
+        let (range, total) = response.range.zip(response.total)
+            .ok_or_else(|| Error::other(format!("{}: range response without Content-Range", self.url)))?;
+        // Check the body length before allocating it: only the asked-for
+        // range (shorter at the end of the file) is acceptable
+        if range.0 != start || range.1 < range.0 || range.1 > end || len != range.1 - range.0 + 1 {
+            return Err(Error::other(format!(
+                "{}: asked for bytes {}-{}, got bytes {}-{} with Content-Length {}",
+                self.url, start, end, range.0, range.1, len
+            )));
+        }
+        let mut body = vec![0u8; len as usize];
+        conn.read_exact(&mut body)?;
+        self.bytes_fetched.fetch_add(len, Ordering::Relaxed);
+        if response.keep_alive {
+            if let Ok(mut pool) = self.pool.lock() {
+                pool.push(conn);
+            }
+        }
+        Ok((body, total))
+    }
+}
+
+impl OgawaSource for HttpSource {
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    fn read_at(&self, pos: u64, buf: &mut [u8]) -> Result<()> {
+        let Some((first, last)) = self.chunk_range(pos, buf.len() as u64) else { return Ok(()) };
+        // Keep this read's chunks even if the cache evicts them meanwhile
+        let mut chunks: Vec<Option<Arc<Vec<u8>>>> =
+            (first..=last).map(|index| self.chunks.get(&self.chunk_key(index))).collect();
+        let missing = chunks.iter().zip(first..).filter(|(c, _)| c.is_none()).map(|(_, index)| index);
+        for (run_first, run_last) in index_runs(missing.collect::<Vec<_>>()) {
+            for (index, data) in (run_first..=run_last).zip(self.fetch_run(run_first, run_last)?) {
+                chunks[(index - first) as usize] = Some(data);
+            }
+        }
+
+        let chunk = self.options.chunk_size;
+        let mut written = 0usize;
+        for (data, index) in chunks.iter().zip(first..) {
+            let data = data.as_ref().ok_or_else(|| Error::other("chunk missing after fetch"))?;
+            let chunk_start = index * chunk;
+            let from = (pos + written as u64 - chunk_start) as usize;
+            let n = (data.len() - from).min(buf.len() - written);
+            buf[written..written + n].copy_from_slice(&data[from..from + n]);
+            written += n;
+        }
+        Ok(())
+    }
+}
+
+/// Group ascending chunk indices into runs of consecutive ones (inclusive).
+fn index_runs(indices: impl IntoIterator<Item = u64>) -> Vec<(u64, u64)> {
+    let mut runs: Vec<(u64, u64)> = Vec::new();
+    for index in indices {
+        match runs.last_mut() {
+            Some(run) if run.1 + 1 == index => run.1 = index,
+            _ => runs.push((index, index)),
+        }
+    }
+    runs
+}
+
+/// Parsed status line and the headers we care about.
+struct ResponseHead {
+    status: u16,
+    content_length: Option<u64>,
+    /// First and last byte from `Content-Range: bytes a-b/total`.
+    range: Option<(u64, u64)>,
+    /// Total size from `Content-Range: bytes a-b/total` (or `*/total`).
+    total: Option<u64>,
+    keep_alive: bool,
+}
+
+fn read_response_head(conn: &mut BufReader<TcpStream>) -> Result<ResponseHead> {
+    let mut line = String::new();
+    let mut head_len = conn.read_line(&mut line)?;
+    if head_len == 0 {
+        return Err(Error::Io(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "connection closed")));
+    }
+    let mut parts = line.split_whitespace();
+    let version = parts.next().unwrap_or_default().to_string();
+    let status = parts.next().and_then(|s| s.parse().ok())
+        .ok_or_else(|| Error::other(format!("bad HTTP status line: {}", line.trim_end())))?;
+    let mut head = ResponseHead { status, content_length: None, range: None, total: None, keep_alive: version != "HTTP/1.0" };
+    loop {
+        line.clear();
+        let n = conn.read_line(&mut line)?;
+        head_len += n;
+        if n == 0 || head_len > MAX_RESPONSE_HEAD {
+            return Err(Error::other("truncated or oversized HTTP response head"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(head);
+        }
+        let Some((name, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => head.content_length = value.parse().ok(),
+            "content-range" => {
+                let (range, total) = parse_content_range(value);
+                head.range = range;
+                head.total = total;
+            }
+            "connection" => head.keep_alive = !value.eq_ignore_ascii_case("close"),
+            "transfer-encoding" if !value.eq_ignore_ascii_case("identity") => {
+                return Err(Error::other(format!("unsupported Transfer-Encoding in range response: {}", value)));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `bytes a-b/total` (or `bytes */total`) into its byte range and total size.
+fn parse_content_range(value: &str) -> (Option<(u64, u64)>, Option<u64>) {
+    let spec = value.strip_prefix("bytes").unwrap_or(value).trim();
+    let Some((range, total)) = spec.rsplit_once('/') else { return (None, None) };
+    let range = range.split_once('-').and_then(|(a, b)| Some((a.trim().parse().ok()?, b.trim().parse().ok()?)));
+    (range, total.trim().parse().ok())
+}
+
+/// Future of an `*_async` [`HttpSource`] call. The work runs on one of the
+/// [`ASYNC_WORKERS`] pool threads, which stores the result and wakes the task.
+pub struct RemoteFuture<T> {
+    state: Arc<Mutex<FutureState<T>>>,
+}
+
+struct FutureState<T> {
+    result: Option<Result<T>>,
+    waker: Option<Waker>,
+}
+
+impl<T: Send + 'static> RemoteFuture<T> {
+    pub(crate) fn spawn(work: impl FnOnce() -> Result<T> + Send + 'static) -> Self {
+        let state = Arc::new(Mutex::new(FutureState { result: None, waker: None }));
+        let shared = state.clone();
+        let job: Job = Box::new(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(work))
+                .unwrap_or_else(|_| Err(Error::other("remote worker panicked")));
+            let waker = {
+                let mut state = shared.lock().unwrap_or_else(|e| e.into_inner());
+                state.result = Some(result);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        if worker_pool().send(job).is_err() {
+            state.lock().unwrap_or_else(|e| e.into_inner()).result =
+                Some(Err(Error::other("no remote worker threads could be started")));
+        }
+        Self { state }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Queue of the `*_async` worker pool, started on first use.
+fn worker_pool() -> &'static mpsc::Sender<Job> {
+    static POOL: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for i in 0..ASYNC_WORKERS {
+            let receiver = receiver.clone();
+            let _ = std::thread::Builder::new().name(format!("alembic-remote-{}", i)).spawn(move || loop {
+                let job = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => return,
+                }
+            });
+        }
+        sender
+    })
+}
+
+impl<T> Future for RemoteFuture<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Split `http://host[:port]/path` into (Host header, connect address, path).
+fn parse_url(url: &str) -> Result<(String, String, String)> {
+    if url.starts_with("https://") {
+        return Err(Error::other("https is not supported; read through a TLS-terminating proxy"));
+    }
+    let rest = url.strip_prefix("http://")
+        .ok_or_else(|| Error::other(format!("not an http:// URL: {}", url)))?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    if host.is_empty() {
+        return Err(Error::other(format!("no host in URL: {}", url)));
+    }
+    // "[::1]" has colons but no port
+    let has_port = host.rsplit_once(':').is_some_and(|(_, port)| !port.contains(']'));
+    let addr = if has_port { host.to_string() } else { format!("{}:80", host) };
+    Ok((host.to_string(), addr, path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        let (host, addr, path) = parse_url("http://store:9000/a/b.abc").unwrap();
+        assert_eq!((host.as_str(), addr.as_str(), path.as_str()), ("store:9000", "store:9000", "/a/b.abc"));
+        let (host, addr, path) = parse_url("http://store").unwrap();
+        assert_eq!((host.as_str(), addr.as_str(), path.as_str()), ("store", "store:80", "/"));
+        assert_eq!(parse_url("http://[::1]/x").unwrap().1, "[::1]:80");
+        assert!(parse_url("https://store/x").is_err());
+        assert!(parse_url("ftp://store/x").is_err());
+        assert!(parse_url("http:///x").is_err());
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(parse_content_range("bytes 0-99/1000"), (Some((0, 99)), Some(1000)));
+        assert_eq!(parse_content_range("bytes */1000"), (None, Some(1000)));
+        assert_eq!(parse_content_range("bytes 5-9/*"), (Some((5, 9)), None));
+        assert_eq!(parse_content_range("garbage"), (None, None));
+    }
+}
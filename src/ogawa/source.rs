@@ -0,0 +1,55 @@
+//! Pluggable byte sources for reading archives.
+//!
+//! Archives are normally memory-mapped files or in-memory buffers. Anything
+//! else that can serve random-access reads - a remote object store, a
+//! database blob, an encrypted container - implements [`OgawaSource`] and is
+//! opened with [`IArchive::from_source`](crate::abc::IArchive::from_source).
+//!
+//! Reads happen on the thread that asks for a sample, so a source should do
+//! its own caching when single reads are expensive (as `HttpSource` from
+//! the `remote` feature does).
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::ogawa::OgawaSource;
+//!
+//! struct Blob(Vec<u8>);
+//!
+//! impl OgawaSource for Blob {
+//!     fn size(&self) -> u64 { self.0.len() as u64 }
+//!     fn read_at(&self, pos: u64, buf: &mut [u8]) -> alembic::Result<()> {
+//!         buf.copy_from_slice(&self.0[pos as usize..pos as usize + buf.len()]);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let archive = IArchive::from_source("blob", Box::new(Blob(bytes)))?;
+//! ```
+
+use std::sync::Arc;
+
+use crate::util::Result;
+
+/// Random-access byte source an archive is read from.
+///
+/// Reads are always inside `0..size()`; the reader checks bounds first.
+pub trait OgawaSource: Send + Sync {
+    /// Total size in bytes.
+    fn size(&self) -> u64;
+
+    /// Fill `buf` with the bytes starting at `pos`.
+    fn read_at(&self, pos: u64, buf: &mut [u8]) -> Result<()>;
+}
+
+/// Shared sources, e.g. an `HttpSource` that is also prefetched from async code.
+impl<S: OgawaSource + ?Sized> OgawaSource for Arc<S> {
+    fn size(&self) -> u64 {
+        (**self).size()
+    }
+
+    fn read_at(&self, pos: u64, buf: &mut [u8]) -> Result<()> {
+        (**self).read_at(pos, buf)
+    }
+}
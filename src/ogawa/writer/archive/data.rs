@@ -2,6 +2,8 @@
 //!
 //! Mirrors `WriteData`/`CopyWrittenData` behavior from C++.
 
+use std::borrow::Cow;
+
 use super::OArchive;
 use super::super::constants::DATA_KEY_SIZE;
 use super::super::write_util::{encode_sample_for_pod, pod_seed, pod_to_u8};
@@ -48,15 +50,17 @@ impl OArchive {
 
         if self.dedup_enabled {
             if let Some(&existing_pos) = self.dedup_map.get(&content_key) {
+                self.record_dedup_hit(encoded.len());
                 return Ok(existing_pos);
             }
         }
 
+        let payload = self.block_payload(&encoded);
         let pos = self.stream.pos();
-        let total_size = DATA_KEY_SIZE + encoded.len();
+        let total_size = DATA_KEY_SIZE + payload.len();
         self.stream.write_u64(total_size as u64)?;
         self.stream.write_bytes(content_key.digest())?;
-        self.stream.write_bytes(&encoded)?;
+        self.stream.write_bytes(&payload)?;
 
         if self.dedup_enabled {
             self.dedup_map.insert(content_key, pos);
@@ -89,15 +93,17 @@ impl OArchive {
         let content_key = ArraySampleContentKey::from_digest(*key, data.len(), pod_tag);
         if self.dedup_enabled {
             if let Some(&existing_pos) = self.dedup_map.get(&content_key) {
+                self.record_dedup_hit(data.len());
                 return Ok(existing_pos);
             }
         }
 
+        let payload = self.block_payload(data);
         let pos = self.stream.pos();
-        let total_size = DATA_KEY_SIZE + data.len();
+        let total_size = DATA_KEY_SIZE + payload.len();
         self.stream.write_u64(total_size as u64)?;
         self.stream.write_bytes(key)?;
-        self.stream.write_bytes(data)?;
+        self.stream.write_bytes(&payload)?;
 
         if self.dedup_enabled {
             self.dedup_map.insert(content_key, pos);
@@ -125,4 +131,20 @@ impl OArchive {
         Ok(pos)
     }
 
+    /// Stored form of a keyed payload: as-is, or encoded when block
+    /// compression is on. Keys and dedup always use the raw data.
+    fn block_payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        #[cfg(feature = "compress")]
+        if let Some(codec) = self.block_codec {
+            return Cow::Owned(crate::ogawa::compress::encode_block(codec, data));
+        }
+        Cow::Borrowed(data)
+    }
+
+    /// Account for a keyed block that was reused instead of written.
+    fn record_dedup_hit(&mut self, data_len: usize) {
+        self.dedup_hits += 1;
+        // Size prefix + key + payload.
+        self.dedup_bytes_saved += (8 + DATA_KEY_SIZE + data_len) as u64;
+    }
 }
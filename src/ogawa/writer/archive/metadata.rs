@@ -3,6 +3,8 @@
 //! Mirrors `MetaDataMap` and time sampling table layout in C++.
 
 use super::OArchive;
+use super::super::object::OObject;
+use super::super::property::{OProperty, OPropertyData};
 use crate::core::{MetaData, TimeSamplingType};
 
 impl OArchive {
@@ -47,6 +49,50 @@ impl OArchive {
         }
     }
 
+    /// Copy of `obj` with schema titles rewritten per the version overrides.
+    pub(super) fn apply_schema_overrides(&self, obj: &OObject) -> OObject {
+        let mut out = obj.clone();
+        self.override_object(&mut out);
+        out
+    }
+
+    fn override_object(&self, obj: &mut OObject) {
+        self.override_metadata(&mut obj.meta_data);
+        for prop in &mut obj.properties {
+            self.override_property(prop);
+        }
+        for child in &mut obj.children {
+            self.override_object(child);
+        }
+    }
+
+    fn override_property(&self, prop: &mut OProperty) {
+        self.override_metadata(&mut prop.meta_data);
+        if let OPropertyData::Compound(children) = &mut prop.data {
+            for child in children {
+                self.override_property(child);
+            }
+        }
+    }
+
+    fn override_metadata(&self, md: &mut MetaData) {
+        for (from, to) in &self.schema_overrides {
+            for key in [MetaData::SCHEMA_KEY, MetaData::SCHEMA_BASE_KEY] {
+                if md.get(key) == Some(from.as_str()) {
+                    md.set(key, to.as_str());
+                }
+            }
+            let title = md
+                .get(MetaData::SCHEMA_OBJ_TITLE_KEY)
+                .and_then(|t| t.strip_prefix(from.as_str()))
+                .filter(|rest| rest.starts_with(':'))
+                .map(|rest| format!("{}{}", to, rest));
+            if let Some(title) = title {
+                md.set(MetaData::SCHEMA_OBJ_TITLE_KEY, title);
+            }
+        }
+    }
+
     /// Serialize time samplings and max samples table.
     ///
     /// Matches `AwImpl::writeTimeSamples` layout.
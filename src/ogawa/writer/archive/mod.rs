@@ -19,7 +19,8 @@ mod properties;
 mod types;
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use super::constants::{ALEMBIC_LIBRARY_VERSION, OGAWA_FILE_VERSION};
 use super::object::OObject;
@@ -27,13 +28,28 @@ use super::stream::OStream;
 use super::write_util::format_alembic_version;
 use crate::core::{ArraySampleContentKey, MetaData, TimeSampling};
 use crate::ogawa::format::*;
+#[cfg(feature = "compress")]
+use crate::ogawa::BlockCodec;
+use crate::ogawa::BLOCK_COMPRESSION_KEY;
 use crate::util::{Error, Result};
 use types::DeferredGroup;
 
 /// Ogawa archive writer.
+///
+/// A file-backed archive is written to a hidden temp file next to its target
+/// (`.<name>.tmp-<pid>-<n>`) and renamed over the target once
+/// [`write_archive`](Self::write_archive) (or [`close`](Self::close))
+/// succeeds. An archive dropped before that deletes only its temp file, so an
+/// early return or panic in exporter code never leaves an unreadable `.abc`
+/// behind, nor destroys the file it was going to replace. Use
+/// [`abort`](Self::abort) to discard an archive explicitly.
 pub struct OArchive {
     name: String,
     stream: OStream,
+    /// File being written, `None` for in-memory archives (or once discarded).
+    path: Option<PathBuf>,
+    /// Where `path` is renamed to once finished (`None` once published).
+    publish_to: Option<PathBuf>,
     frozen: bool,
     time_samplings: Vec<TimeSampling>,
     max_samples: Vec<u32>,
@@ -46,6 +62,10 @@ pub struct OArchive {
     dedup_map: HashMap<ArraySampleContentKey, u64>,
     /// Enable/disable deduplication (enabled by default).
     dedup_enabled: bool,
+    /// Keyed writes answered from `dedup_map` instead of the stream.
+    dedup_hits: usize,
+    /// Payload bytes (key + data) not written thanks to deduplication.
+    dedup_bytes_saved: u64,
     /// Deferred groups for bottom-up writing.
     deferred_groups: Vec<DeferredGroup>,
     /// Use deferred group writing (C++ compatible mode).
@@ -54,16 +74,44 @@ pub struct OArchive {
     library_version: i32,
     /// Preserve existing archive metadata keys when copying.
     preserve_archive_metadata: bool,
+    /// Schema titles rewritten on output (current -> legacy).
+    schema_overrides: Vec<(String, String)>,
+    /// Codec for keyed sample payloads (non-standard, see [`crate::ogawa::compress`]).
+    #[cfg(feature = "compress")]
+    block_codec: Option<BlockCodec>,
 }
 
 impl OArchive {
     /// Create a new Alembic file for writing.
     ///
-    /// Mirrors `AwImpl::init()` header setup.
+    /// An existing file at `path` is only replaced once the archive is
+    /// finished. Mirrors `AwImpl::init()` header setup.
     pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let target = path.as_ref().to_path_buf();
+        let mut archive = Self::create_unpublished(sibling_path(&target, &unique_tag("tmp")))?;
+        archive.name = target.to_string_lossy().to_string();
+        archive.publish_to = Some(target);
+        Ok(archive)
+    }
+
+    /// Write straight to `path`, which is deleted if the archive is dropped
+    /// unfinished (for callers that publish the file themselves).
+    pub(crate) fn create_unpublished(path: impl AsRef<Path>) -> Result<Self> {
         let name = path.as_ref().to_string_lossy().to_string();
-        let mut stream = OStream::create(&path)?;
+        let mut archive = Self::with_stream(name, OStream::create(&path)?)?;
+        archive.path = Some(path.as_ref().to_path_buf());
+        Ok(archive)
+    }
 
+    /// Create an archive that is written into memory instead of a file.
+    ///
+    /// Finish it with [`into_bytes`](Self::into_bytes); the result opens
+    /// with [`IArchive::from_bytes`](crate::abc::IArchive::from_bytes).
+    pub fn create_in_memory(name: impl Into<String>) -> Result<Self> {
+        Self::with_stream(name.into(), OStream::in_memory())
+    }
+
+    fn with_stream(name: String, mut stream: OStream) -> Result<Self> {
         // Header with placeholder for root position.
         stream.write_bytes(OGAWA_MAGIC)?;
         stream.write_u8(NOT_FROZEN_FLAG)?;
@@ -77,6 +125,8 @@ impl OArchive {
         Ok(Self {
             name,
             stream,
+            path: None,
+            publish_to: None,
             frozen: false,
             time_samplings: vec![identity_ts],
             max_samples: vec![0],
@@ -87,10 +137,15 @@ impl OArchive {
             compression_hint: -1,
             dedup_map: HashMap::new(),
             dedup_enabled: true,
+            dedup_hits: 0,
+            dedup_bytes_saved: 0,
             deferred_groups: Vec::new(),
             deferred_mode: false, // Disabled for binary parity - write groups inline.
             library_version: ALEMBIC_LIBRARY_VERSION,
             preserve_archive_metadata: false,
+            schema_overrides: Vec::new(),
+            #[cfg(feature = "compress")]
+            block_codec: None,
         })
     }
 
@@ -104,11 +159,24 @@ impl OArchive {
         self.dedup_enabled
     }
 
-    /// Get number of deduplicated samples.
+    /// Get number of unique keyed data blocks written.
     pub fn dedup_count(&self) -> usize {
         self.dedup_map.len()
     }
 
+    /// Get number of keyed writes that reused an already written block.
+    ///
+    /// Identical samples across properties and objects (and repeated
+    /// non-consecutive samples of one property) count as hits.
+    pub fn dedup_hits(&self) -> usize {
+        self.dedup_hits
+    }
+
+    /// Get number of bytes deduplication kept out of the file.
+    pub fn dedup_bytes_saved(&self) -> u64 {
+        self.dedup_bytes_saved
+    }
+
     /// Get the archive name/path.
     pub fn name(&self) -> &str {
         &self.name
@@ -139,6 +207,22 @@ impl OArchive {
         self.compression_hint
     }
 
+    /// Compress keyed sample payloads with `codec` (`None` writes a standard
+    /// archive).
+    ///
+    /// Block-compressed archives can only be read by this crate with the
+    /// `compress` feature; stock Alembic cannot read them.
+    #[cfg(feature = "compress")]
+    pub fn set_block_compression(&mut self, codec: Option<BlockCodec>) {
+        self.block_codec = codec;
+    }
+
+    /// Codec used for keyed sample payloads, if any.
+    #[cfg(feature = "compress")]
+    pub fn block_compression(&self) -> Option<BlockCodec> {
+        self.block_codec
+    }
+
     /// Set the library version to write (for copying archives).
     pub fn set_library_version(&mut self, version: i32) {
         self.library_version = version;
@@ -149,6 +233,24 @@ impl OArchive {
         self.library_version
     }
 
+    /// Write `schema` under a different title, e.g. `"AbcGeom_Xform_v2"` for
+    /// `"AbcGeom_Xform_v3"`, for consumers that only match older versions.
+    ///
+    /// Rewrites `schema`, `schemaBaseType` and the `schemaObjTitle` prefix on
+    /// every object and property at write time. The sample layout is not
+    /// changed, so only override to a version with the same layout.
+    pub fn set_schema_version_override(&mut self, schema: &str, written: &str) {
+        self.schema_overrides.retain(|(from, _)| from != schema);
+        if schema != written {
+            self.schema_overrides.push((schema.to_string(), written.to_string()));
+        }
+    }
+
+    /// Schema title overrides as `(schema, written)` pairs.
+    pub fn schema_version_overrides(&self) -> &[(String, String)] {
+        &self.schema_overrides
+    }
+
     /// Set the archive metadata (e.g. when copying from another file).
     /// This also clears the application_writer so we don't add our own app name.
     pub fn set_archive_metadata(&mut self, md: MetaData) {
@@ -239,6 +341,13 @@ impl OArchive {
 
         let version_pos = self.write_data(&OGAWA_FILE_VERSION.to_le_bytes())?;
         let file_version_pos = self.write_data(&self.library_version.to_le_bytes())?;
+        let overridden;
+        let root = if self.schema_overrides.is_empty() {
+            root
+        } else {
+            overridden = self.apply_schema_overrides(root);
+            &overridden
+        };
         let (root_obj_pos, _, _) = self.write_object(root, "/")?;
 
         let final_root_obj_pos = if self.deferred_mode {
@@ -266,6 +375,12 @@ impl OArchive {
             let version = format_alembic_version(self.library_version);
             archive_meta.set("_ai_AlembicVersion", &version);
         }
+        // The marker must match how samples were written, whatever was copied
+        archive_meta.remove(BLOCK_COMPRESSION_KEY);
+        #[cfg(feature = "compress")]
+        if let Some(codec) = self.block_codec {
+            archive_meta.set(BLOCK_COMPRESSION_KEY, codec.name());
+        }
         let archive_meta_str = archive_meta.serialize();
         let archive_meta_pos = if archive_meta_str.is_empty() {
             0
@@ -294,8 +409,6 @@ impl OArchive {
 
         let root_pos = self.write_group(&root_children)?;
 
-        self.frozen = true;
-
         self.stream.seek(FROZEN_OFFSET as u64)?;
         self.stream.write_u8(FROZEN_FLAG)?;
         self.stream.seek(ROOT_POS_OFFSET as u64)?;
@@ -304,9 +417,38 @@ impl OArchive {
         self.stream.seek_end()?;
         self.stream.flush()?;
 
+        // Only a fully flushed archive counts as finished (see `Drop`).
+        self.frozen = true;
+
+        self.publish()
+    }
+
+    /// Rename a finished temp file over the target.
+    fn publish(&mut self) -> Result<()> {
+        let (Some(target), Some(temp)) = (self.publish_to.as_ref(), self.path.as_ref()) else {
+            return Ok(());
+        };
+        // Release the file handle first (required on Windows)
+        drop(std::mem::replace(&mut self.stream, OStream::in_memory()));
+        std::fs::rename(temp, target).map_err(|e| Error::other(format!("{}: {}", target.display(), e)))?;
+        self.path = self.publish_to.take();
         Ok(())
     }
 
+    /// Finalize an in-memory archive and return its bytes.
+    ///
+    /// Writes an empty hierarchy first if `write_archive` was never called.
+    /// Fails for archives created with [`create`](Self::create).
+    pub fn into_bytes(mut self) -> Result<Vec<u8>> {
+        if self.path.is_some() || !self.stream.is_in_memory() {
+            return Err(Error::invalid(format!("{} is not an in-memory archive", self.name)));
+        }
+        if !self.frozen {
+            self.write_archive(&OObject::new(""))?;
+        }
+        self.stream.take_bytes().ok_or_else(|| Error::invalid("in-memory archive has no buffer"))
+    }
+
     /// Finalize and close the archive.
     pub fn close(mut self) -> Result<()> {
         if !self.frozen {
@@ -314,6 +456,52 @@ impl OArchive {
             self.write_archive(&empty_root)?;
         }
         self.stream.flush()?;
-        Ok(())
+        // Retry a rename that failed in `write_archive`
+        self.publish()
     }
+
+    /// Discard an unfinished archive.
+    ///
+    /// Only the temp file is deleted; an existing file at the target path is
+    /// left as it was. A no-op once [`write_archive`](Self::write_archive)
+    /// has published the file (delete it yourself to undo an export) and for
+    /// in-memory archives.
+    pub fn abort(mut self) {
+        // Unfinished, or finished but never renamed into place
+        if !self.frozen || self.publish_to.is_some() {
+            self.discard();
+        }
+    }
+
+    /// Close the file being written and remove it.
+    fn discard(&mut self) {
+        let Some(path) = self.path.take() else {
+            return;
+        };
+        // Release the file handle first (required on Windows)
+        drop(std::mem::replace(&mut self.stream, OStream::in_memory()));
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+impl Drop for OArchive {
+    fn drop(&mut self) {
+        // Unfinished, or finished but never renamed into place
+        if !self.frozen || self.publish_to.is_some() {
+            self.discard();
+        }
+    }
+}
+
+/// Hidden file next to `target`: `dir/.name.<tag>`.
+pub(crate) fn sibling_path(target: &Path, tag: &str) -> PathBuf {
+    let name = target.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    target.with_file_name(format!(".{}.{}", name, tag))
+}
+
+/// `<kind>-<pid>-<n>`, unique across processes and across archives written
+/// to the same target within this process.
+pub(crate) fn unique_tag(kind: &str) -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    format!("{}-{}-{}", kind, std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed))
 }
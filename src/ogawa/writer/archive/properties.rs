@@ -465,7 +465,7 @@ impl OArchive {
         }
 
         if !matches!(prop.data, OPropertyData::Compound(_)) {
-            let pod = pod_to_u8(prop.data_type.pod) as u32;
+            let pod = prop.unknown_pod.unwrap_or_else(|| pod_to_u8(prop.data_type.pod)) as u32;
             info |= (pod & 0x0f) << 4;
 
             info |= (prop.data_type.extent as u32 & 0xff) << 12;
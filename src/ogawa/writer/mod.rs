@@ -11,10 +11,12 @@ mod write_util;
 mod object;
 mod property;
 mod archive;
+mod transaction;
 
 pub mod schema;
 
 pub use archive::OArchive;
+pub use transaction::OArchiveTransaction;
 pub use object::OObject;
 pub use property::{OProperty, OPropertyData, SampleWithDigest, ArraySampleWithDigest};
 
@@ -23,13 +25,13 @@ pub use schema::{
     OPolyMesh, OPolyMeshSample,
     OXform, OXformSample,
     OCurves, OCurvesSample,
-    OPoints, OPointsSample,
+    OPoints, OPointsSample, PointIdMode,
     OSubD, OSubDSample,
     OCamera,
     ONuPatch, ONuPatchSample,
     OLight,
     OFaceSet, OFaceSetSample,
-    OMaterial, OMaterialSample,
+    OMaterial, OMaterialSample, add_material_assignment,
     OCollections, OCollectionsSample,
 };
 
@@ -3,6 +3,7 @@
 //! Reference: `_ref/alembic/lib/Alembic/AbcCoreOgawa/OwData.cpp`.
 
 use crate::core::MetaData;
+use crate::ogawa::INSTANCE_SOURCE_PROPERTY;
 use crate::util::DataType;
 
 use super::property::OProperty;
@@ -43,6 +44,36 @@ impl OObject {
         self.children.last_mut().unwrap()
     }
 
+    /// Add an instance of the object at `target_path`, named after the target.
+    ///
+    /// See [`add_instance_named`](Self::add_instance_named).
+    pub fn add_instance(&mut self, target_path: &str) -> &mut OObject {
+        let name = target_path.rsplit('/').find(|p| !p.is_empty()).unwrap_or(target_path);
+        let name = name.to_string();
+        self.add_instance_named(&name, target_path)
+    }
+
+    /// Add a child `name` that instances the object at `target_path`.
+    ///
+    /// The child is written as a proxy holding only an `.instanceSource`
+    /// string property; readers resolve it to the target's schema, properties
+    /// and children. The target must exist in the written archive and must not
+    /// be an ancestor of the instance.
+    ///
+    /// Reference: `Abc::OObject::addChildInstance()`.
+    pub fn add_instance_named(&mut self, name: &str, target_path: &str) -> &mut OObject {
+        let mut instance = OObject::new(name);
+        instance
+            .add_scalar(INSTANCE_SOURCE_PROPERTY, DataType::STRING)
+            .add_scalar_string(target_path);
+        self.add_child(instance)
+    }
+
+    /// Check if this object is an instance proxy.
+    pub fn is_instance(&self) -> bool {
+        self.properties.iter().any(|p| p.name == INSTANCE_SOURCE_PROPERTY)
+    }
+
     /// Add a property.
     pub fn add_property(&mut self, prop: OProperty) -> &mut OProperty {
         self.properties.push(prop);
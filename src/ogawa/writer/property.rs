@@ -86,6 +86,12 @@ pub struct OProperty {
     /// Data write order - determines order of data in file (C++ parity).
     /// Lower values are written first. Properties with same order use compound order.
     pub data_write_order: u32,
+    /// POD code to write instead of `data_type.pod`, for copying properties
+    /// of types this library doesn't know (samples are written as-is).
+    pub unknown_pod: Option<u8>,
+    /// Samples held unchanged since the last added one (see
+    /// [`hold_sample`](Self::hold_sample)); written only if another sample follows.
+    pub held_samples: u32,
 }
 
 impl OProperty {
@@ -101,6 +107,8 @@ impl OProperty {
             data: OPropertyData::Scalar(Vec::new()),
             is_scalar_like: true,
             data_write_order: u32::MAX, // Default: use compound order
+            unknown_pod: None,
+            held_samples: 0,
         }
     }
 
@@ -116,6 +124,8 @@ impl OProperty {
             data: OPropertyData::Array(Vec::new()),
             is_scalar_like: true,
             data_write_order: u32::MAX,
+            unknown_pod: None,
+            held_samples: 0,
         }
     }
 
@@ -131,6 +141,8 @@ impl OProperty {
             data: OPropertyData::Array(Vec::new()),
             is_scalar_like: true,
             data_write_order: u32::MAX,
+            unknown_pod: None,
+            held_samples: 0,
         }
     }
 
@@ -146,6 +158,8 @@ impl OProperty {
             data: OPropertyData::Compound(Vec::new()),
             is_scalar_like: false,
             data_write_order: u32::MAX,
+            unknown_pod: None,
+            held_samples: 0,
         }
     }
 
@@ -163,6 +177,7 @@ impl OProperty {
 
     /// Add a scalar sample.
     pub fn add_scalar_sample(&mut self, data: &[u8]) {
+        self.write_held_samples();
         if let OPropertyData::Scalar(samples) = &mut self.data {
             let sample_index = samples.len() as u32;
             let is_same = samples.last().is_some_and(|prev| prev.data == data);
@@ -181,6 +196,7 @@ impl OProperty {
 
     /// Add a scalar sample with pre-computed digest (for raw copy).
     pub fn add_scalar_sample_with_digest(&mut self, data: &[u8], digest: SampleDigest) {
+        self.write_held_samples();
         if let OPropertyData::Scalar(samples) = &mut self.data {
             let sample_index = samples.len() as u32;
             let is_same = samples.last().is_some_and(|prev| prev.data == data);
@@ -211,6 +227,7 @@ impl OProperty {
 
     /// Add an array sample.
     pub fn add_array_sample(&mut self, data: &[u8], dims: &[usize]) {
+        self.write_held_samples();
         if let OPropertyData::Array(samples) = &mut self.data {
             let sample_index = samples.len() as u32;
             let is_same = samples.last().is_some_and(|prev| prev.data == data);
@@ -232,6 +249,7 @@ impl OProperty {
 
     /// Add an array sample with pre-computed digest (for raw copy).
     pub fn add_array_sample_with_digest(&mut self, data: &[u8], dims: &[usize], digest: SampleDigest) {
+        self.write_held_samples();
         if let OPropertyData::Array(samples) = &mut self.data {
             let sample_index = samples.len() as u32;
             let is_same = samples.last().is_some_and(|prev| prev.data == data);
@@ -257,6 +275,62 @@ impl OProperty {
         self.add_array_sample(data, &[values.len()]);
     }
 
+    /// Repeat the previous sample (C++ `setFromPrevious`).
+    ///
+    /// The repeated sample shares the previous data, so it does not change
+    /// the changed-index range and writes no new data. Returns false when
+    /// there is no previous sample or the property is a compound.
+    pub fn set_from_previous(&mut self) -> bool {
+        self.write_held_samples();
+        self.repeat_last()
+    }
+
+    /// Leave the property unchanged for one sample of its schema.
+    ///
+    /// Unlike [`set_from_previous`](Self::set_from_previous) nothing is
+    /// written yet: held samples become repeats only when a later sample is
+    /// added, so that sample keeps its time. Trailing held samples are
+    /// dropped, leaving the property with fewer samples than its schema;
+    /// readers hold its last sample (see `SampleSelector::resolve`). Does
+    /// nothing before the first sample.
+    pub fn hold_sample(&mut self) {
+        if self.getNumSamples() > 0 {
+            self.held_samples += 1;
+        }
+    }
+
+    /// Write the samples held by [`hold_sample`](Self::hold_sample) as repeats.
+    fn write_held_samples(&mut self) {
+        for _ in 0..std::mem::take(&mut self.held_samples) {
+            self.repeat_last();
+        }
+    }
+
+    fn repeat_last(&mut self) -> bool {
+        match &mut self.data {
+            OPropertyData::Scalar(samples) => match samples.last().cloned() {
+                Some(prev) => {
+                    samples.push(prev);
+                    true
+                }
+                None => false,
+            },
+            OPropertyData::Array(samples) => match samples.last().cloned() {
+                Some(prev) => {
+                    samples.push(prev);
+                    true
+                }
+                None => false,
+            },
+            OPropertyData::Compound(_) => false,
+        }
+    }
+
+    /// Check if all samples added so far are identical.
+    pub fn isConstant(&self) -> bool {
+        self.first_changed_index == 0 && self.last_changed_index == 0
+    }
+
     /// Add a child property (for compound).
     pub fn add_child(&mut self, prop: OProperty) -> Option<&mut OProperty> {
         if let OPropertyData::Compound(children) = &mut self.data {
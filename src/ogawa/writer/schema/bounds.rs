@@ -0,0 +1,133 @@
+//! Child bounds computation for the schema writers.
+//!
+//! Works on built [`OObject`] trees: geometry contributes its `.selfBnds`
+//! samples, Xforms contribute their `.childBnds` transformed by their own
+//! matrices, plain objects the union of their children. Contributors with
+//! different sample counts are combined index by index, holding the last
+//! sample of the shorter ones; the result uses the time sampling of the
+//! contributor with the most samples.
+
+use crate::geom::xform::decode_xform_op;
+use crate::geom::{XformOp, XformSample};
+use crate::util::BBox3d;
+
+use super::super::object::OObject;
+use super::super::property::{OProperty, OPropertyData};
+
+/// Bounds samples of a written object.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BoundsSamples {
+    pub(crate) samples: Vec<BBox3d>,
+    pub(crate) time_sampling_index: u32,
+}
+
+impl BoundsSamples {
+    /// Per-index union of `parts`; `None` if there are none.
+    fn union(parts: Vec<BoundsSamples>) -> Option<Self> {
+        let longest = parts.iter().max_by_key(|p| p.samples.len())?;
+        let time_sampling_index = longest.time_sampling_index;
+        let count = longest.samples.len();
+        let samples = (0..count)
+            .map(|i| {
+                let mut b = BBox3d::EMPTY;
+                for part in &parts {
+                    if let Some(s) = part.samples.get(i).or(part.samples.last()) {
+                        b.expand_by_box(s);
+                    }
+                }
+                b
+            })
+            .collect();
+        Some(Self { samples, time_sampling_index })
+    }
+}
+
+/// Union of the bounds of `children`, in their parent's space.
+pub(crate) fn children_bounds(children: &[OObject]) -> Option<BoundsSamples> {
+    BoundsSamples::union(children.iter().filter_map(object_bounds).collect())
+}
+
+/// Bounds of `obj` and its descendants, in the space of its parent.
+fn object_bounds(obj: &OObject) -> Option<BoundsSamples> {
+    if let Some(xform) = obj.properties.iter().find(|p| p.name == ".xform") {
+        let local = read_bounds(xform, ".childBnds")?;
+        let matrices = xform_matrices(xform);
+        if matrices.is_empty() {
+            return Some(local);
+        }
+        let count = local.samples.len().max(matrices.len());
+        let time_sampling_index = if matrices.len() > local.samples.len() {
+            child(xform, ".vals").map_or(local.time_sampling_index, |p| p.time_sampling_index)
+        } else {
+            local.time_sampling_index
+        };
+        let samples = (0..count)
+            .map(|i| {
+                let b = local.samples[i.min(local.samples.len() - 1)];
+                b.transformed(&matrices[i.min(matrices.len() - 1)])
+            })
+            .collect();
+        return Some(BoundsSamples { samples, time_sampling_index });
+    }
+
+    let mut parts: Vec<BoundsSamples> = obj
+        .properties
+        .iter()
+        .filter_map(|schema| read_bounds(schema, ".selfBnds"))
+        .collect();
+    parts.extend(obj.children.iter().filter_map(object_bounds));
+    BoundsSamples::union(parts)
+}
+
+fn child<'a>(compound: &'a OProperty, name: &str) -> Option<&'a OProperty> {
+    match &compound.data {
+        OPropertyData::Compound(children) => children.iter().find(|p| p.name == name),
+        _ => None,
+    }
+}
+
+/// Samples of a `[f64; 6]` bounds property under `compound`.
+fn read_bounds(compound: &OProperty, name: &str) -> Option<BoundsSamples> {
+    let OPropertyData::Scalar(samples) = &child(compound, name)?.data else { return None };
+    let samples: Vec<BBox3d> = samples
+        .iter()
+        .filter_map(|s| {
+            let v = f64s(&s.data);
+            (v.len() == 6).then(|| BBox3d::new(glam::dvec3(v[0], v[1], v[2]), glam::dvec3(v[3], v[4], v[5])))
+        })
+        .collect();
+    let time_sampling_index = child(compound, name)?.time_sampling_index;
+    (!samples.is_empty()).then_some(BoundsSamples { samples, time_sampling_index })
+}
+
+/// Per-sample matrices of a written `.xform` compound (empty for identity).
+fn xform_matrices(xform: &OProperty) -> Vec<glam::DMat4> {
+    let ops = match child(xform, ".ops").map(|p| &p.data) {
+        Some(OPropertyData::Scalar(samples)) if !samples.is_empty() => samples[0].data.clone(),
+        _ => return Vec::new(),
+    };
+    let vals: Vec<&[u8]> = match child(xform, ".vals").map(|p| &p.data) {
+        Some(OPropertyData::Scalar(samples)) => samples.iter().map(|s| s.data.as_slice()).collect(),
+        Some(OPropertyData::Array(samples)) => samples.iter().map(|s| s.data.as_slice()).collect(),
+        _ => return Vec::new(),
+    };
+    vals.into_iter()
+        .map(|data| {
+            let doubles = f64s(data);
+            let mut xs = XformSample::identity();
+            let mut offset = 0;
+            for &code in &ops {
+                let (op_type, n) = decode_xform_op(code);
+                let Some(op_type) = op_type.filter(|_| offset + n <= doubles.len()) else { break };
+                xs.ops.push(XformOp { op_type, values: doubles[offset..offset + n].to_vec(), hint: code & 0xF });
+                offset += n;
+            }
+            xs.matrix().as_dmat4()
+        })
+        .collect()
+}
+
+/// Little-endian f64s of a sample buffer (which need not be 8-byte aligned).
+fn f64s(data: &[u8]) -> Vec<f64> {
+    data.chunks_exact(8).map(|c| f64::from_le_bytes(c.try_into().unwrap())).collect()
+}
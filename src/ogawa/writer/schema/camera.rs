@@ -4,12 +4,13 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OCamera.cpp`
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OCamera.h`
 
-use crate::core::MetaData;
-use crate::geom::CameraSample;
+use crate::geom::camera::encode_film_back_op;
+use crate::geom::{CameraSample, SchemaInfo};
 use crate::util::{DataType, PlainOldDataType};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
+use super::super::write_util::encode_string_array;
 
 /// Camera schema writer.
 pub struct OCamera {
@@ -22,10 +23,7 @@ impl OCamera {
     /// Create new Camera.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_Camera_v1");
-        meta.set("schemaObjTitle", "AbcGeom_Camera_v1:.geom");
-        object.meta_data = meta;
+        object.meta_data = SchemaInfo::CAMERA.object_metadata();
 
         Self { object, samples: Vec::new(), time_sampling_index: 0 }
     }
@@ -44,9 +42,7 @@ impl OCamera {
     pub fn build(mut self) -> OObject {
         if !self.samples.is_empty() {
             let mut geom = OProperty::compound(".geom");
-            let mut geom_meta = MetaData::new();
-            geom_meta.set("schema", "AbcGeom_Camera_v1");
-            geom.meta_data = geom_meta;
+            geom.meta_data = SchemaInfo::CAMERA.compound_metadata();
 
             let mut core = OProperty::scalar(
                 ".core",
@@ -77,6 +73,7 @@ impl OCamera {
 
             if let OPropertyData::Compound(children) = &mut geom.data {
                 children.push(core);
+                children.extend(self.film_back_properties());
             }
 
             self.object.properties.push(geom);
@@ -85,6 +82,42 @@ impl OCamera {
         self.object
     }
 
+    /// `.filmBackOps` and `.filmBackChannels`, empty when no sample has ops.
+    ///
+    /// The op stack (types and hints) is constant and taken from the first
+    /// sample, as in C++; later samples only contribute channel values and
+    /// are padded or truncated to the first sample's channel count.
+    fn film_back_properties(&self) -> Vec<OProperty> {
+        let Some(first) = self.samples.first() else { return Vec::new() };
+        if first.film_back_xform_ops.is_empty() {
+            return Vec::new();
+        }
+
+        let names: Vec<String> = first.film_back_xform_ops.iter().map(encode_film_back_op).collect();
+        let mut ops = OProperty::array(".filmBackOps", DataType::new(PlainOldDataType::String, 1));
+        ops.add_array_sample(&encode_string_array(&names), &[names.len()]);
+
+        let num_channels = first.num_op_channels();
+        let mut channels = if num_channels <= u8::MAX as usize {
+            OProperty::scalar(".filmBackChannels", DataType::new(PlainOldDataType::Float64, num_channels as u8))
+        } else {
+            OProperty::array(".filmBackChannels", DataType::new(PlainOldDataType::Float64, 1))
+        };
+        channels.time_sampling_index = self.time_sampling_index;
+        for sample in &self.samples {
+            let mut values: Vec<f64> = sample.film_back_xform_ops.iter()
+                .flat_map(|op| (0..op.num_channels()).map(|c| op.channel_value(c)))
+                .collect();
+            values.resize(num_channels, 0.0);
+            if num_channels <= u8::MAX as usize {
+                channels.add_scalar_sample(bytemuck::cast_slice(&values));
+            } else {
+                channels.add_array_pod(&values);
+            }
+        }
+        vec![ops, channels]
+    }
+
     /// Add child object.
     pub fn add_child(&mut self, child: OObject) {
         self.object.children.push(child);
@@ -6,14 +6,15 @@
 
 use std::collections::HashMap;
 
-use crate::core::MetaData;
+use crate::geom::SchemaInfo;
 use crate::util::{DataType, PlainOldDataType};
 
 use super::super::object::OObject;
-use super::super::property::{OProperty, OPropertyData};
+use super::super::property::OProperty;
 use super::super::write_util::encode_string_array;
 
-/// Collections sample data for output.
+/// Collections sample data for output: the members of each collection at
+/// one time (see [`OCollections::add_sample`]).
 pub struct OCollectionsSample {
     pub collections: HashMap<String, Vec<String>>,
 }
@@ -37,40 +38,86 @@ impl Default for OCollectionsSample {
 }
 
 /// Collections schema writer.
+///
+/// Each collection is a string array property under `.collections` holding
+/// object paths. [`add_collection`](Self::add_collection) writes a constant
+/// collection; [`add_sample`](Self::add_sample) appends one sample to every
+/// collection, so membership can change over time (selection sets or render
+/// layers keyed per frame).
 pub struct OCollections {
     object: OObject,
-    sample: OCollectionsSample,
+    /// Collections in creation order, with their membership per sample
+    /// and whether they take part in [`add_sample`](Self::add_sample).
+    collections: Vec<(String, Vec<Vec<String>>, bool)>,
+    num_samples: usize,
+    time_sampling_index: u32,
 }
 
 impl OCollections {
     /// Create new Collections.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcCollection_Collection_v1");
-        object.meta_data = meta;
+        object.meta_data = SchemaInfo::COLLECTIONS.object_metadata();
 
-        Self { object, sample: OCollectionsSample::new() }
+        Self { object, collections: Vec::new(), num_samples: 0, time_sampling_index: 0 }
     }
 
-    /// Add a collection.
+    /// Set time sampling index for animated collections.
+    pub fn set_time_sampling(&mut self, index: u32) {
+        self.time_sampling_index = index;
+    }
+
+    /// Add a constant collection, replacing any collection of that name.
     pub fn add_collection(&mut self, name: &str, paths: Vec<String>) {
-        self.sample.add_collection(name, paths);
+        match self.collections.iter_mut().find(|(n, _, _)| n == name) {
+            Some((_, samples, animated)) => (*samples, *animated) = (vec![paths], false),
+            None => self.collections.push((name.to_string(), vec![paths], false)),
+        }
+    }
+
+    /// Add a sample: the members of each collection at the next time.
+    ///
+    /// Collections missing from `sample` keep their previous members; ones
+    /// first seen here are empty at the earlier samples (constant ones keep
+    /// their members). New collections are added in name order.
+    pub fn add_sample(&mut self, sample: &OCollectionsSample) {
+        let mut names: Vec<&String> = sample.collections.keys().collect();
+        names.sort();
+        for name in names {
+            match self.collections.iter_mut().find(|(n, _, _)| n == name) {
+                // A constant collection becomes animated from here on
+                Some((_, samples, animated)) if !*animated => {
+                    *samples = vec![samples[0].clone(); self.num_samples];
+                    *animated = true;
+                }
+                Some(_) => {}
+                None => self.collections.push((name.clone(), vec![Vec::new(); self.num_samples], true)),
+            }
+        }
+        for (name, samples, _) in self.collections.iter_mut().filter(|c| c.2) {
+            let paths = match sample.collections.get(name) {
+                Some(paths) => paths.clone(),
+                None => samples.last().cloned().unwrap_or_default(),
+            };
+            samples.push(paths);
+        }
+        self.num_samples += 1;
     }
 
     /// Build the object.
     pub fn build(mut self) -> OObject {
         let mut coll = OProperty::compound(".collections");
+        coll.meta_data = SchemaInfo::COLLECTIONS.compound_metadata();
 
-        for (name, paths) in &self.sample.collections {
-            let paths_data = encode_string_array(paths);
-
+        for (name, samples, _) in &self.collections {
             let mut prop = OProperty::array(name, DataType::new(PlainOldDataType::String, 1));
-            prop.add_array_sample(&paths_data, &[paths.len()]);
-
-            if let OPropertyData::Compound(children) = &mut coll.data {
-                children.push(prop);
+            if samples.len() > 1 {
+                prop.time_sampling_index = self.time_sampling_index;
+            }
+            for paths in samples {
+                prop.add_array_sample(&encode_string_array(paths), &[paths.len()]);
             }
+            coll.add_child(prop);
         }
 
         self.object.properties.push(coll);
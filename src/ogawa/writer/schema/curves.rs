@@ -5,12 +5,13 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OCurves.h`
 
 use crate::core::MetaData;
-use crate::geom::{BasisType, CurvePeriodicity, CurveType};
-use crate::util::{DataType, PlainOldDataType};
+use crate::geom::curves::validate_nurbs;
+use crate::geom::{BasisType, CurvePeriodicity, CurveType, SchemaInfo};
+use crate::util::{DataType, Error, PlainOldDataType, Result};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
-use super::util::{bounds_meta, compute_bounds_vec3};
+use super::util::{bounds_meta, compute_bounds_vec3, find_child_mut, TimeSamplingOverrides};
 
 /// Curves sample data for output.
 pub struct OCurvesSample {
@@ -23,7 +24,9 @@ pub struct OCurvesSample {
     pub widths: Option<Vec<f32>>,
     pub normals: Option<Vec<glam::Vec3>>,
     pub uvs: Option<Vec<glam::Vec2>>,
+    /// Knot vectors, `num_vertices[i] + order` per curve.
     pub knots: Option<Vec<f32>>,
+    /// Per-curve orders (degree + 1) for `CurveType::VariableOrder`.
     pub orders: Option<Vec<i32>>,
 }
 
@@ -62,6 +65,15 @@ impl OCurvesSample {
         self.basis = basis;
         self
     }
+
+    /// Make this a variable-order NURBS sample with per-curve `orders` and
+    /// concatenated knot vectors.
+    pub fn with_nurbs(mut self, orders: Vec<i32>, knots: Vec<f32>) -> Self {
+        self.curve_type = CurveType::VariableOrder;
+        self.orders = Some(orders);
+        self.knots = Some(knots);
+        self
+    }
 }
 
 /// Curves schema writer.
@@ -69,25 +81,19 @@ pub struct OCurves {
     object: OObject,
     geom_compound: OProperty,
     time_sampling_index: u32,
+    ts_overrides: TimeSamplingOverrides,
 }
 
 impl OCurves {
     /// Create new Curves.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_Curve_v2");
-        meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
-        meta.set("schemaObjTitle", "AbcGeom_Curve_v2:.geom");
-        object.meta_data = meta;
-
-        let mut geom_meta = MetaData::new();
-        geom_meta.set("schema", "AbcGeom_Curve_v2");
-        geom_meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
+        object.meta_data = SchemaInfo::CURVES.object_metadata();
+
         let mut geom = OProperty::compound(".geom");
-        geom.meta_data = geom_meta;
+        geom.meta_data = SchemaInfo::CURVES.compound_metadata();
 
-        Self { object, geom_compound: geom, time_sampling_index: 0 }
+        Self { object, geom_compound: geom, time_sampling_index: 0, ts_overrides: TimeSamplingOverrides::default() }
     }
 
     /// Set time sampling index for animated properties.
@@ -95,7 +101,42 @@ impl OCurves {
         self.time_sampling_index = index;
     }
 
-    /// Add a sample.
+    /// Override the time sampling of a single property by name (e.g. `"uv"`,
+    /// `".widths"`), leaving the others on the schema time sampling. Applied
+    /// when the object is built.
+    pub fn set_property_time_sampling(&mut self, name: &str, index: u32) {
+        self.ts_overrides.set(name, index);
+    }
+
+    /// Mutable access to a property of the schema compound, e.g. to add
+    /// extra samples to a property with its own time sampling.
+    pub fn geom_property_mut(&mut self, name: &str) -> Option<&mut OProperty> {
+        find_child_mut(&mut self.geom_compound, name)
+    }
+
+    /// Add a sample, rejecting vertex counts that do not match the
+    /// positions and orders/knots that do not match the vertex counts.
+    pub fn try_add_sample(&mut self, sample: &OCurvesSample) -> Result<()> {
+        if let Some(i) = sample.num_vertices.iter().position(|&n| n < 0) {
+            return Err(Error::invalid(format!("curve {} has negative vertex count {}", i, sample.num_vertices[i])));
+        }
+        let total: usize = sample.num_vertices.iter().map(|&n| n as usize).sum();
+        if total != sample.positions.len() {
+            return Err(Error::invalid(format!(
+                "nVertices sums to {} but there are {} positions", total, sample.positions.len()
+            )));
+        }
+        validate_nurbs(
+            &sample.num_vertices,
+            sample.curve_type,
+            sample.orders.as_deref().unwrap_or_default(),
+            sample.knots.as_deref().unwrap_or_default(),
+        )?;
+        self.add_sample(sample);
+        Ok(())
+    }
+
+    /// Add a sample, written unchecked (see [`try_add_sample`](Self::try_add_sample)).
     pub fn add_sample(&mut self, sample: &OCurvesSample) {
         // .selfBnds is created by OGeomBase before P in C++.
         let bounds = compute_bounds_vec3(&sample.positions);
@@ -185,13 +226,15 @@ impl OCurves {
             prop.add_array_pod(knots);
         }
 
+        // C++ ref: OCurves.cpp stores orders as a uchar array
         if let Some(ref orders) = sample.orders {
             let prop = self.geom_compound.get_or_create_array_child(
                 ".orders",
-                DataType::new(PlainOldDataType::Int32, 1),
+                DataType::new(PlainOldDataType::Uint8, 1),
             );
             prop.data_write_order = 10;
-            prop.add_array_pod(orders);
+            let orders: Vec<u8> = orders.iter().map(|&o| o.clamp(0, 255) as u8).collect();
+            prop.add_array_pod(&orders);
         }
     }
 
@@ -219,6 +262,7 @@ impl OCurves {
 
     /// Build the object.
     pub fn build(mut self) -> OObject {
+        self.ts_overrides.apply(&mut self.geom_compound);
         self.object.properties.push(self.geom_compound);
         self.object
     }
@@ -4,7 +4,7 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OFaceSet.cpp`
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OFaceSet.h`
 
-use crate::core::MetaData;
+use crate::geom::SchemaInfo;
 use crate::util::{DataType, PlainOldDataType};
 
 use super::super::object::OObject;
@@ -33,17 +33,10 @@ impl OFaceSet {
     /// Create new FaceSet.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_FaceSet_v1");
-        meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
-        meta.set("schemaObjTitle", "AbcGeom_FaceSet_v1:.faceset");
-        object.meta_data = meta;
+        object.meta_data = SchemaInfo::FACESET.object_metadata();
 
-        let mut faceset_meta = MetaData::new();
-        faceset_meta.set("schema", "AbcGeom_FaceSet_v1");
-        faceset_meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
         let mut faceset = OProperty::compound(".faceset");
-        faceset.meta_data = faceset_meta;
+        faceset.meta_data = SchemaInfo::FACESET.compound_metadata();
 
         Self { object, geom_compound: faceset, time_sampling_index: 0 }
     }
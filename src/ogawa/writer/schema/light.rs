@@ -4,17 +4,20 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OLight.cpp`
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OLight.h`
 
-use crate::core::MetaData;
-use crate::geom::CameraSample;
+use crate::geom::{CameraSample, LightSample, LightType, SchemaInfo};
 use crate::util::{DataType, PlainOldDataType};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
 
 /// Light schema writer.
+///
+/// Typed light parameters ([`add_sample`](Self::add_sample)) are written to
+/// `.geom/.userProperties` as `type`, `intensity`, `color` and `coneAngle`.
 pub struct OLight {
     object: OObject,
     camera_samples: Vec<CameraSample>,
+    light_samples: Vec<LightSample>,
     time_sampling_index: u32,
 }
 
@@ -22,12 +25,9 @@ impl OLight {
     /// Create new Light.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_Light_v1");
-        meta.set("schemaObjTitle", "AbcGeom_Light_v1:.geom");
-        object.meta_data = meta;
+        object.meta_data = SchemaInfo::LIGHT.object_metadata();
 
-        Self { object, camera_samples: Vec::new(), time_sampling_index: 0 }
+        Self { object, camera_samples: Vec::new(), light_samples: Vec::new(), time_sampling_index: 0 }
     }
 
     /// Set time sampling index for animated properties.
@@ -40,18 +40,26 @@ impl OLight {
         self.camera_samples.push(sample);
     }
 
+    /// Add a sample with camera and light parameters.
+    ///
+    /// A parameter set in any sample is written for every sample; samples
+    /// that leave it unset store its default (point light, intensity 1,
+    /// white, 0 degrees).
+    pub fn add_sample(&mut self, sample: &LightSample) {
+        self.camera_samples.push(sample.camera.clone());
+        // Keep the parameter samples aligned with the camera samples.
+        self.light_samples.resize_with(self.camera_samples.len() - 1, LightSample::default);
+        self.light_samples.push(sample.clone());
+    }
+
     /// Build the object.
     pub fn build(mut self) -> OObject {
         if !self.camera_samples.is_empty() {
             let mut geom = OProperty::compound(".geom");
-            let mut geom_meta = MetaData::new();
-            geom_meta.set("schema", "AbcGeom_Light_v1");
-            geom.meta_data = geom_meta;
+            geom.meta_data = SchemaInfo::LIGHT.compound_metadata();
 
             let mut cam_compound = OProperty::compound(".camera");
-            let mut cam_meta = MetaData::new();
-            cam_meta.set("schema", "AbcGeom_Camera_v1");
-            cam_compound.meta_data = cam_meta;
+            cam_compound.meta_data = SchemaInfo::CAMERA.compound_metadata();
 
             let mut core = OProperty::scalar(
                 ".core",
@@ -89,12 +97,63 @@ impl OLight {
                 children.push(cam_compound);
             }
 
+            if let Some(user) = self.light_params() {
+                geom.add_child(user);
+            }
+
             self.object.properties.push(geom);
         }
 
         self.object
     }
 
+    /// `.userProperties` holding the typed light parameters, if any is set.
+    fn light_params(&self) -> Option<OProperty> {
+        if !self.light_samples.iter().any(LightSample::has_light_params) {
+            return None;
+        }
+        // One parameter sample per camera sample, whichever way they were added.
+        let mut samples = self.light_samples.clone();
+        samples.resize_with(self.camera_samples.len(), LightSample::default);
+        let scalar = |name: &str, data_type: DataType| {
+            let mut prop = OProperty::scalar(name, data_type);
+            prop.time_sampling_index = self.time_sampling_index;
+            prop
+        };
+        let mut user = OProperty::compound(".userProperties");
+        if samples.iter().any(|s| s.light_type != LightType::Unknown) {
+            let mut prop = scalar("type", DataType::STRING);
+            for s in &samples {
+                let light_type = if s.light_type == LightType::Unknown { LightType::Point } else { s.light_type };
+                prop.add_scalar_string(light_type.name());
+            }
+            user.add_child(prop);
+        }
+        if samples.iter().any(|s| s.intensity.is_some()) {
+            let mut prop = scalar("intensity", DataType::FLOAT32);
+            for s in &samples {
+                prop.add_scalar_sample(bytemuck::bytes_of(&s.intensity.unwrap_or(1.0)));
+            }
+            user.add_child(prop);
+        }
+        if samples.iter().any(|s| s.color.is_some()) {
+            let mut prop = scalar("color", DataType::COLOR3F);
+            prop.meta_data.set("interpretation", "rgb");
+            for s in &samples {
+                prop.add_scalar_sample(bytemuck::bytes_of(&s.color.unwrap_or(glam::Vec3::ONE).to_array()));
+            }
+            user.add_child(prop);
+        }
+        if samples.iter().any(|s| s.cone_angle.is_some()) {
+            let mut prop = scalar("coneAngle", DataType::FLOAT32);
+            for s in &samples {
+                prop.add_scalar_sample(bytemuck::bytes_of(&s.cone_angle.unwrap_or(0.0)));
+            }
+            user.add_child(prop);
+        }
+        Some(user)
+    }
+
     /// Add child object.
     pub fn add_child(&mut self, child: OObject) {
         self.object.children.push(child);
@@ -3,21 +3,37 @@
 //! References:
 //! - `_ref/alembic/lib/Alembic/AbcMaterial/OMaterial.cpp`
 //! - `_ref/alembic/lib/Alembic/AbcMaterial/OMaterial.h`
+//! - `_ref/alembic/lib/Alembic/AbcMaterial/MaterialAssignment.cpp`
 
 use std::collections::HashMap;
 
-use crate::core::MetaData;
-use crate::material::{ShaderParam, ShaderParamValue};
+use crate::geom::SchemaInfo;
+use crate::material::{ShaderNode, ShaderParam, ShaderParamValue, MATERIAL_ASSIGN_PROP};
 use crate::util::{DataType, PlainOldDataType};
 
 use super::super::object::OObject;
-use super::super::property::{OProperty, OPropertyData};
+use super::super::property::OProperty;
 use super::super::write_util::encode_string_array;
 
 /// Material sample data for output.
+///
+/// Holds both forms AbcMaterial supports: monolithic shaders per
+/// (target, shader type) with their parameters, and shader networks of
+/// named nodes wired together, exposed through terminals and interface
+/// parameters.
 pub struct OMaterialSample {
     pub shader_names: HashMap<(String, String), String>,
     pub params: HashMap<(String, String), Vec<ShaderParam>>,
+    /// Network nodes, with their parameters and input connections.
+    pub nodes: Vec<ShaderNode>,
+    /// (target, shader type) -> (node, output) the network exposes there.
+    pub terminals: HashMap<(String, String), (String, String)>,
+    /// Interface parameter -> (node, node parameter) it drives.
+    pub interface_mappings: HashMap<String, (String, String)>,
+    /// Values of the interface parameters.
+    pub interface_params: Vec<ShaderParam>,
+    /// Path of the material this one inherits from.
+    pub inherits: Option<String>,
 }
 
 impl OMaterialSample {
@@ -26,6 +42,11 @@ impl OMaterialSample {
         Self {
             shader_names: HashMap::new(),
             params: HashMap::new(),
+            nodes: Vec::new(),
+            terminals: HashMap::new(),
+            interface_mappings: HashMap::new(),
+            interface_params: Vec::new(),
+            inherits: None,
         }
     }
 
@@ -44,6 +65,36 @@ impl OMaterialSample {
             .or_default()
             .push(param);
     }
+
+    /// Add a network node; replaces an earlier node of the same name.
+    pub fn add_network_node(&mut self, node: ShaderNode) {
+        self.nodes.retain(|n| n.name != node.name);
+        self.nodes.push(node);
+    }
+
+    /// Expose `output` of network node `node` as the shader for a target
+    /// and shader type (an empty `output` means the node's default output).
+    pub fn set_network_terminal(&mut self, target: &str, shader_type: &str, node: &str, output: &str) {
+        self.terminals.insert(
+            (target.to_string(), shader_type.to_string()),
+            (node.to_string(), output.to_string()),
+        );
+    }
+
+    /// Map interface parameter `name` onto parameter `param` of network node `node`.
+    pub fn set_interface_mapping(&mut self, name: &str, node: &str, param: &str) {
+        self.interface_mappings.insert(name.to_string(), (node.to_string(), param.to_string()));
+    }
+
+    /// Add an interface parameter value.
+    pub fn add_interface_param(&mut self, param: ShaderParam) {
+        self.interface_params.push(param);
+    }
+
+    /// Inherit from the material at `path`.
+    pub fn set_inherits(&mut self, path: &str) {
+        self.inherits = Some(path.to_string());
+    }
 }
 
 impl Default for OMaterialSample {
@@ -62,10 +113,7 @@ impl OMaterial {
     /// Create new Material.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcMaterial_Material_v1");
-        meta.set("schemaObjTitle", "AbcMaterial_Material_v1:.material");
-        object.meta_data = meta;
+        object.meta_data = SchemaInfo::MATERIAL.object_metadata();
 
         Self { object, sample: OMaterialSample::new() }
     }
@@ -80,137 +128,230 @@ impl OMaterial {
         self.sample.add_shader(target, shader_type, shader_name);
     }
 
+    /// Add a network node (see [`OMaterialSample::add_network_node`]).
+    pub fn add_network_node(&mut self, node: ShaderNode) {
+        self.sample.add_network_node(node);
+    }
+
+    /// Set a network terminal (see [`OMaterialSample::set_network_terminal`]).
+    pub fn set_network_terminal(&mut self, target: &str, shader_type: &str, node: &str, output: &str) {
+        self.sample.set_network_terminal(target, shader_type, node, output);
+    }
+
     /// Build the object.
     pub fn build(mut self) -> OObject {
-        let mut mat = OProperty::compound(".material");
-        let mut mat_meta = MetaData::new();
-        mat_meta.set("schema", "AbcMaterial_Material_v1");
-        mat.meta_data = mat_meta;
-
-        if !self.sample.shader_names.is_empty() {
-            let mut entries: Vec<((String, String), String)> = self
-                .sample
-                .shader_names
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect();
-            entries.sort_by(|a, b| a.0.cmp(&b.0));
-
-            let mut strings = Vec::with_capacity(entries.len() * 2);
-            for ((target, shader_type), shader_name) in entries {
-                strings.push(format!("{}.{}", target, shader_type));
-                strings.push(shader_name);
-            }
-
-            let data = encode_string_array(&strings);
-            let mut shader_names_prop = OProperty::array(
-                ".shaderNames",
-                DataType::new(PlainOldDataType::String, 1),
-            );
-            shader_names_prop.add_array_sample(&data, &[strings.len()]);
+        let mat = std::mem::take(&mut self.sample).into_compound();
+        self.object.properties.push(mat);
+        self.object
+    }
+}
 
-            if let OPropertyData::Compound(children) = &mut mat.data {
-                children.push(shader_names_prop);
-            }
-        }
+impl OMaterialSample {
+    /// Build the `.material` schema compound.
+    ///
+    /// [`OMaterial::build`] puts it on a Material object; pushing it onto
+    /// any other object's properties embeds the material there, which is how
+    /// AbcMaterial binds a material directly to geometry.
+    pub fn into_compound(self) -> OProperty {
+        let mut mat = OProperty::compound(".material");
+        mat.meta_data = SchemaInfo::MATERIAL.compound_metadata();
 
-        let mut params_entries: Vec<((String, String), Vec<ShaderParam>)> = self
-            .sample
-            .params
+        let pairs = |map: HashMap<(String, String), String>| {
+            let mut entries: Vec<(String, String)> =
+                map.into_iter().map(|((target, shader_type), v)| (format!("{}.{}", target, shader_type), v)).collect();
+            entries.sort();
+            entries
+        };
+        let shader_names = string_pairs_property(".shaderNames", pairs(self.shader_names));
+        let terminals = self
+            .terminals
             .into_iter()
+            .map(|(key, (node, output))| (key, join_output(&node, &output)))
             .collect();
+        let terminals = string_pairs_property(".terminals", pairs(terminals));
+        let mut mappings: Vec<(String, String)> = self
+            .interface_mappings
+            .into_iter()
+            .map(|(name, (node, param))| (name, join_output(&node, &param)))
+            .collect();
+        mappings.sort();
+        let interface = string_pairs_property(".interface", mappings);
+        for prop in [shader_names, terminals, interface].into_iter().flatten() {
+            mat.add_child(prop);
+        }
+
+        let mut params_entries: Vec<((String, String), Vec<ShaderParam>)> = self.params.into_iter().collect();
         params_entries.sort_by(|a, b| a.0.cmp(&b.0));
+        for ((target, shader_type), params) in params_entries {
+            mat.add_child(params_compound(&format!("{}.{}.params", target, shader_type), params));
+        }
 
-        for ((target, shader_type), mut params) in params_entries {
-            params.sort_by(|a, b| a.name.cmp(&b.name));
-            let mut params_prop = OProperty::compound(&format!("{}.{}.params", target, shader_type));
-            if let OPropertyData::Compound(children) = &mut params_prop.data {
-                for param in &params {
-                    let (dt, data, dims) = match &param.value {
-                        ShaderParamValue::Float(v) => (
-                            DataType::new(PlainOldDataType::Float32, 1),
-                            bytemuck::bytes_of(v).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Double(v) => (
-                            DataType::new(PlainOldDataType::Float64, 1),
-                            bytemuck::bytes_of(v).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Vec2(v) => (
-                            DataType::new(PlainOldDataType::Float32, 2),
-                            bytemuck::bytes_of(v).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Vec3(v) | ShaderParamValue::Color3(v) => (
-                            DataType::new(PlainOldDataType::Float32, 3),
-                            bytemuck::bytes_of(v).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Vec4(v) | ShaderParamValue::Color4(v) => (
-                            DataType::new(PlainOldDataType::Float32, 4),
-                            bytemuck::bytes_of(v).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Matrix(m) => (
-                            DataType::new(PlainOldDataType::Float32, 16),
-                            bytemuck::bytes_of(m).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Int(v) => (
-                            DataType::new(PlainOldDataType::Int32, 1),
-                            bytemuck::bytes_of(v).to_vec(),
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::String(s) => (
-                            DataType::new(PlainOldDataType::String, 1),
-                            {
-                                let mut data = s.as_bytes().to_vec();
-                                data.push(0);
-                                data
-                            },
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::Bool(v) => (
-                            DataType::new(PlainOldDataType::Boolean, 1),
-                            vec![*v as u8],
-                            Vec::new(),
-                        ),
-                        ShaderParamValue::FloatArray(arr) => (
-                            DataType::new(PlainOldDataType::Float32, 1),
-                            bytemuck::cast_slice(arr).to_vec(),
-                            vec![arr.len()],
-                        ),
-                        ShaderParamValue::IntArray(arr) => (
-                            DataType::new(PlainOldDataType::Int32, 1),
-                            bytemuck::cast_slice(arr).to_vec(),
-                            vec![arr.len()],
-                        ),
-                        ShaderParamValue::StringArray(arr) => (
-                            DataType::new(PlainOldDataType::String, 1),
-                            encode_string_array(arr),
-                            vec![arr.len()],
-                        ),
-                    };
-
-                    let prop = if dims.is_empty() {
-                        let mut prop = OProperty::scalar(&param.name, dt);
-                        prop.add_scalar_sample(&data);
-                        prop
-                    } else {
-                        let mut prop = OProperty::array(&param.name, dt);
-                        prop.add_array_sample(&data, &dims);
-                        prop
-                    };
-                    children.push(prop);
+        if !self.nodes.is_empty() {
+            let mut nodes = OProperty::compound(".nodes");
+            for node in self.nodes {
+                let string = |name: &str, value: &str| {
+                    let mut prop = OProperty::scalar(name, DataType::new(PlainOldDataType::String, 1));
+                    prop.add_scalar_string(value);
+                    prop
+                };
+                let mut compound = OProperty::compound(&node.name);
+                compound.add_child(string("target", &node.target));
+                compound.add_child(string("type", &node.shader_type));
+                if !node.parameters.is_empty() {
+                    compound.add_child(params_compound("params", node.parameters));
                 }
+                let mut connections: Vec<(String, String)> = node
+                    .connections
+                    .into_iter()
+                    .map(|(input, (source, output))| (input, join_output(&source, &output)))
+                    .collect();
+                connections.sort();
+                if let Some(prop) = string_pairs_property(".connections", connections) {
+                    compound.add_child(prop);
+                }
+                nodes.add_child(compound);
             }
-            if let OPropertyData::Compound(children) = &mut mat.data {
-                children.push(params_prop);
-            }
+            mat.add_child(nodes);
         }
 
-        self.object.properties.push(mat);
-        self.object
+        if !self.interface_params.is_empty() {
+            mat.add_child(params_compound(".interfaceParams", self.interface_params));
+        }
+        if let Some(inherits) = self.inherits {
+            let mut prop = OProperty::scalar(".inherits", DataType::new(PlainOldDataType::String, 1));
+            prop.add_scalar_string(&inherits);
+            mat.add_child(prop);
+        }
+        mat
+    }
+}
+
+/// Bind the material at `material_path` to `object` (a mesh, face set, ...).
+///
+/// Writes the `.material.assign` string property that
+/// [`get_material_assignment`](crate::material::get_material_assignment)
+/// and `AbcMaterial::getMaterialAssignmentPath` read.
+pub fn add_material_assignment(object: &mut OObject, material_path: &str) {
+    let mut prop = OProperty::scalar(MATERIAL_ASSIGN_PROP, DataType::new(PlainOldDataType::String, 1));
+    prop.add_scalar_string(material_path);
+    object.properties.retain(|p| p.name != MATERIAL_ASSIGN_PROP);
+    object.properties.push(prop);
+}
+
+/// `node.output`, or just `node` when there's no output name.
+fn join_output(node: &str, output: &str) -> String {
+    if output.is_empty() {
+        node.to_string()
+    } else {
+        format!("{}.{}", node, output)
+    }
+}
+
+/// String array of flattened `[key, value, key, value, ...]` pairs; `None`
+/// when there are no pairs (the property is omitted, as in `OMaterial`).
+fn string_pairs_property(name: &str, pairs: Vec<(String, String)>) -> Option<OProperty> {
+    if pairs.is_empty() {
+        return None;
+    }
+    let strings: Vec<String> = pairs.into_iter().flat_map(|(k, v)| [k, v]).collect();
+    let mut prop = OProperty::array(name, DataType::new(PlainOldDataType::String, 1));
+    prop.add_array_sample(&encode_string_array(&strings), &[strings.len()]);
+    Some(prop)
+}
+
+/// Compound of shader parameters, sorted by name.
+fn params_compound(name: &str, mut params: Vec<ShaderParam>) -> OProperty {
+    params.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut compound = OProperty::compound(name);
+    for param in &params {
+        compound.add_child(param_property(param));
+    }
+    compound
+}
+
+/// One shader parameter as a scalar (or, for the array values, array) property.
+fn param_property(param: &ShaderParam) -> OProperty {
+    let (dt, data, dims) = match &param.value {
+        ShaderParamValue::Float(v) => (
+            DataType::new(PlainOldDataType::Float32, 1),
+            bytemuck::bytes_of(v).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::Double(v) => (
+            DataType::new(PlainOldDataType::Float64, 1),
+            bytemuck::bytes_of(v).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::Vec2(v) => (
+            DataType::new(PlainOldDataType::Float32, 2),
+            bytemuck::bytes_of(v).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::Vec3(v) | ShaderParamValue::Color3(v) => (
+            DataType::new(PlainOldDataType::Float32, 3),
+            bytemuck::bytes_of(v).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::Vec4(v) | ShaderParamValue::Color4(v) => (
+            DataType::new(PlainOldDataType::Float32, 4),
+            bytemuck::bytes_of(v).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::Matrix(m) => (
+            DataType::new(PlainOldDataType::Float32, 16),
+            bytemuck::bytes_of(m).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::Int(v) => (
+            DataType::new(PlainOldDataType::Int32, 1),
+            bytemuck::bytes_of(v).to_vec(),
+            Vec::new(),
+        ),
+        ShaderParamValue::String(s) => (
+            DataType::new(PlainOldDataType::String, 1),
+            {
+                let mut data = s.as_bytes().to_vec();
+                data.push(0);
+                data
+            },
+            Vec::new(),
+        ),
+        ShaderParamValue::Bool(v) => (
+            DataType::new(PlainOldDataType::Boolean, 1),
+            vec![*v as u8],
+            Vec::new(),
+        ),
+        ShaderParamValue::FloatArray(arr) => (
+            DataType::new(PlainOldDataType::Float32, 1),
+            bytemuck::cast_slice(arr).to_vec(),
+            vec![arr.len()],
+        ),
+        ShaderParamValue::IntArray(arr) => (
+            DataType::new(PlainOldDataType::Int32, 1),
+            bytemuck::cast_slice(arr).to_vec(),
+            vec![arr.len()],
+        ),
+        ShaderParamValue::StringArray(arr) => (
+            DataType::new(PlainOldDataType::String, 1),
+            encode_string_array(arr),
+            vec![arr.len()],
+        ),
+    };
+
+    let mut prop = if dims.is_empty() {
+        let mut prop = OProperty::scalar(&param.name, dt);
+        prop.add_scalar_sample(&data);
+        prop
+    } else {
+        let mut prop = OProperty::array(&param.name, dt);
+        prop.add_array_sample(&data, &dims);
+        prop
+    };
+    // Colors are told apart from vectors by their interpretation, as with C3f/C4f
+    match param.value {
+        ShaderParamValue::Color3(_) => prop.meta_data.set("interpretation", "rgb"),
+        ShaderParamValue::Color4(_) => prop.meta_data.set("interpretation", "rgba"),
+        _ => {}
     }
+    prop
 }
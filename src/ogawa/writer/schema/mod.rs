@@ -4,6 +4,7 @@
 //! References: AbcGeom / AbcMaterial / AbcCollection in `_ref/alembic/lib/Alembic`.
 
 mod util;
+mod bounds;
 
 pub mod polymesh;
 pub mod xform;
@@ -20,11 +21,11 @@ pub mod collections;
 pub use polymesh::{OPolyMesh, OPolyMeshSample};
 pub use xform::{OXform, OXformSample};
 pub use curves::{OCurves, OCurvesSample};
-pub use points::{OPoints, OPointsSample};
+pub use points::{OPoints, OPointsSample, PointIdMode};
 pub use subd::{OSubD, OSubDSample};
 pub use camera::OCamera;
 pub use nupatch::{ONuPatch, ONuPatchSample};
 pub use light::OLight;
 pub use faceset::{OFaceSet, OFaceSetSample};
-pub use material::{OMaterial, OMaterialSample};
+pub use material::{add_material_assignment, OMaterial, OMaterialSample};
 pub use collections::{OCollections, OCollectionsSample};
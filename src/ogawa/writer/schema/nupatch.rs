@@ -5,11 +5,12 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/ONuPatch.h`
 
 use crate::core::MetaData;
+use crate::geom::SchemaInfo;
 use crate::util::{DataType, PlainOldDataType};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
-use super::util::{bounds_meta, compute_bounds_vec3};
+use super::util::{bounds_meta, compute_bounds_vec3, find_child_mut, TimeSamplingOverrides};
 
 /// NuPatch sample data for output.
 pub struct ONuPatchSample {
@@ -58,25 +59,19 @@ pub struct ONuPatch {
     object: OObject,
     geom_compound: OProperty,
     time_sampling_index: u32,
+    ts_overrides: TimeSamplingOverrides,
 }
 
 impl ONuPatch {
     /// Create new NuPatch.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_NuPatch_v2");
-        meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
-        meta.set("schemaObjTitle", "AbcGeom_NuPatch_v2:.geom");
-        object.meta_data = meta;
-
-        let mut geom_meta = MetaData::new();
-        geom_meta.set("schema", "AbcGeom_NuPatch_v2");
-        geom_meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
+        object.meta_data = SchemaInfo::NUPATCH.object_metadata();
+
         let mut geom = OProperty::compound(".geom");
-        geom.meta_data = geom_meta;
+        geom.meta_data = SchemaInfo::NUPATCH.compound_metadata();
 
-        Self { object, geom_compound: geom, time_sampling_index: 0 }
+        Self { object, geom_compound: geom, time_sampling_index: 0, ts_overrides: TimeSamplingOverrides::default() }
     }
 
     /// Set time sampling index for animated properties.
@@ -84,6 +79,19 @@ impl ONuPatch {
         self.time_sampling_index = index;
     }
 
+    /// Override the time sampling of a single property by name (e.g. `"uv"`,
+    /// `".widths"`), leaving the others on the schema time sampling. Applied
+    /// when the object is built.
+    pub fn set_property_time_sampling(&mut self, name: &str, index: u32) {
+        self.ts_overrides.set(name, index);
+    }
+
+    /// Mutable access to a property of the schema compound, e.g. to add
+    /// extra samples to a property with its own time sampling.
+    pub fn geom_property_mut(&mut self, name: &str) -> Option<&mut OProperty> {
+        find_child_mut(&mut self.geom_compound, name)
+    }
+
     /// Add a sample.
     pub fn add_sample(&mut self, sample: &ONuPatchSample) {
         let bounds = compute_bounds_vec3(&sample.positions);
@@ -233,6 +241,7 @@ impl ONuPatch {
 
     /// Build the object.
     pub fn build(mut self) -> OObject {
+        self.ts_overrides.apply(&mut self.geom_compound);
         self.object.properties.push(self.geom_compound);
         self.object
     }
@@ -4,25 +4,64 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OPoints.cpp`
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OPoints.h`
 
-use crate::core::MetaData;
-use crate::util::{DataType, PlainOldDataType};
+use std::collections::HashSet;
+
+use crate::core::{GeometryScope, MetaData};
+use crate::geom::typed::AbcPod;
+use crate::geom::{GeomParamSample, OGeomParam, SchemaInfo};
+use crate::util::{DataType, Error, PlainOldDataType, Result};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
-use super::util::{bounds_meta, compute_bounds_vec3};
+use super::util::{
+    add_geom_param_sample, bounds_meta, children_constant, compute_bounds_vec3, find_child_mut, put_child, hold_geom_param_sample,
+    TimeSamplingOverrides,
+};
+
+/// How point ids are generated when a sample has none.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PointIdMode {
+    /// Ids are the point indices `0..n`.
+    #[default]
+    Sequential,
+    /// Ids are a hash of the object name and point index, so ids from
+    /// different point objects are unlikely to collide when merged.
+    Hashed,
+}
 
 /// Points sample data for output.
+///
+/// `ids` may be left empty; ids are then generated according to the
+/// writer's [`PointIdMode`]. Attributes left out of a later sample keep
+/// their previous value, like [`OPolyMeshSample`](super::OPolyMeshSample).
 pub struct OPointsSample {
     pub positions: Vec<glam::Vec3>,
     pub ids: Vec<i64>,
     pub velocities: Option<Vec<glam::Vec3>>,
     pub widths: Option<Vec<f32>>,
+    /// Per-point attributes for `.arbGeomParams` (`Cd`, `orient`, `age`, ...),
+    /// by name.
+    pub arb_geom_params: Vec<(String, GeomParamSample)>,
 }
 
 impl OPointsSample {
     /// Create new points sample.
     pub fn new(positions: Vec<glam::Vec3>, ids: Vec<i64>) -> Self {
-        Self { positions, ids, velocities: None, widths: None }
+        Self { positions, ids, velocities: None, widths: None, arb_geom_params: Vec::new() }
+    }
+
+    /// Add (or replace) an arbitrary GeomParam for this sample.
+    pub fn set_arb_geom_param(&mut self, name: &str, sample: GeomParamSample) {
+        match self.arb_geom_params.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = sample,
+            None => self.arb_geom_params.push((name.to_string(), sample)),
+        }
+    }
+
+    /// Add (or replace) a per-point attribute, one value per point
+    /// (e.g. `"orient"` as `glam::Quat`, `"Cd"` as `glam::Vec3`).
+    pub fn set_attribute<T: AbcPod>(&mut self, name: &str, values: &[T]) {
+        self.set_arb_geom_param(name, GeomParamSample::from_values(T::DATA_TYPE, values, None, GeometryScope::Vertex));
     }
 }
 
@@ -31,25 +70,31 @@ pub struct OPoints {
     object: OObject,
     geom_compound: OProperty,
     time_sampling_index: u32,
+    ts_overrides: TimeSamplingOverrides,
+    id_mode: PointIdMode,
+    arb_geom_compound: Option<OProperty>,
+    /// Arbitrary params written by samples so far (held when omitted).
+    sampled_arb_params: Vec<String>,
 }
 
 impl OPoints {
     /// Create new Points.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_Points_v1");
-        meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
-        meta.set("schemaObjTitle", "AbcGeom_Points_v1:.geom");
-        object.meta_data = meta;
-
-        let mut geom_meta = MetaData::new();
-        geom_meta.set("schema", "AbcGeom_Points_v1");
-        geom_meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
+        object.meta_data = SchemaInfo::POINTS.object_metadata();
+
         let mut geom = OProperty::compound(".geom");
-        geom.meta_data = geom_meta;
+        geom.meta_data = SchemaInfo::POINTS.compound_metadata();
 
-        Self { object, geom_compound: geom, time_sampling_index: 0 }
+        Self {
+            object,
+            geom_compound: geom,
+            time_sampling_index: 0,
+            ts_overrides: TimeSamplingOverrides::default(),
+            id_mode: PointIdMode::default(),
+            arb_geom_compound: None,
+            sampled_arb_params: Vec::new(),
+        }
     }
 
     /// Set time sampling index for animated properties.
@@ -57,7 +102,58 @@ impl OPoints {
         self.time_sampling_index = index;
     }
 
-    /// Add a sample.
+    /// Override the time sampling of a single property by name (e.g. `"uv"`,
+    /// `".widths"`), leaving the others on the schema time sampling. Applied
+    /// when the object is built.
+    pub fn set_property_time_sampling(&mut self, name: &str, index: u32) {
+        self.ts_overrides.set(name, index);
+    }
+
+    /// Mutable access to a property of the schema compound, e.g. to add
+    /// extra samples to a property with its own time sampling.
+    pub fn geom_property_mut(&mut self, name: &str) -> Option<&mut OProperty> {
+        find_child_mut(&mut self.geom_compound, name)
+    }
+
+    /// Add an arbitrary geometry parameter (`.geom/.arbGeomParams`) with all
+    /// its samples. Replaces a param of the same name.
+    pub fn add_arb_geom_param(&mut self, param: &OGeomParam) {
+        let arb = self.arb_geom_compound.get_or_insert_with(|| OProperty::compound(".arbGeomParams"));
+        put_child(arb, param.to_property());
+    }
+
+    /// Set how ids are generated for samples without ids.
+    pub fn set_id_mode(&mut self, mode: PointIdMode) {
+        self.id_mode = mode;
+    }
+
+    /// Generate ids for `count` points according to the id mode.
+    pub fn generate_ids(&self, count: usize) -> Vec<i64> {
+        match self.id_mode {
+            PointIdMode::Sequential => (0..count as i64).collect(),
+            PointIdMode::Hashed => {
+                let seed = name_hash(&self.object.name);
+                // splitmix64 is a bijection, so ids are unique within the object.
+                // Masked to stay non-negative (readers expose ids as u64).
+                (0..count as u64)
+                    .map(|i| (splitmix64(seed ^ i) & i64::MAX as u64) as i64)
+                    .collect()
+            }
+        }
+    }
+
+    /// Add a sample, rejecting ids that are not unique or whose count
+    /// does not match the positions. Empty ids are auto-generated.
+    pub fn try_add_sample(&mut self, sample: &OPointsSample) -> Result<()> {
+        if !sample.ids.is_empty() {
+            validate_ids(&sample.ids, sample.positions.len())?;
+        }
+        self.add_sample(sample);
+        Ok(())
+    }
+
+    /// Add a sample. Empty ids are auto-generated; provided ids are
+    /// written unchecked (see [`try_add_sample`](Self::try_add_sample)).
     pub fn add_sample(&mut self, sample: &OPointsSample) {
         // .selfBnds is created by OGeomBase before P in C++.
         let bounds = compute_bounds_vec3(&sample.positions);
@@ -78,12 +174,19 @@ impl OPoints {
         p_prop.data_write_order = 0;
         p_prop.add_array_pod(&sample.positions);
 
+        let generated;
+        let ids = if sample.ids.is_empty() {
+            generated = self.generate_ids(sample.positions.len());
+            &generated
+        } else {
+            &sample.ids
+        };
         let id_prop = self.geom_compound.get_or_create_array_child(
             "id",
             DataType::new(PlainOldDataType::Int64, 1),
         );
         id_prop.data_write_order = 1;
-        id_prop.add_array_pod(&sample.ids);
+        id_prop.add_array_pod(ids);
 
         if let Some(ref vels) = sample.velocities {
             let prop = self.geom_compound.get_or_create_array_child(
@@ -102,6 +205,22 @@ impl OPoints {
             prop.data_write_order = 3;
             prop.add_array_pod(widths);
         }
+
+        if !sample.arb_geom_params.is_empty() || !self.sampled_arb_params.is_empty() {
+            let ts_idx = self.time_sampling_index;
+            let arb = self.arb_geom_compound.get_or_insert_with(|| OProperty::compound(".arbGeomParams"));
+            for (name, param) in &sample.arb_geom_params {
+                add_geom_param_sample(arb, name, param, ts_idx);
+                if !self.sampled_arb_params.contains(name) {
+                    self.sampled_arb_params.push(name.clone());
+                }
+            }
+            for name in &self.sampled_arb_params {
+                if !sample.arb_geom_params.iter().any(|(n, _)| n == name) {
+                    hold_geom_param_sample(arb, name);
+                }
+            }
+        }
     }
 
     fn p_meta() -> MetaData {
@@ -126,8 +245,20 @@ impl OPoints {
         }
     }
 
+    /// Check if all samples added so far have the same positions and ids.
+    /// Repeated samples are stored once, and the points read back as
+    /// constant ([`IPoints::isConstant`](crate::geom::IPoints::isConstant)).
+    pub fn isConstant(&self) -> bool {
+        children_constant(&self.geom_compound, &["P", "id"])
+    }
+
     /// Build the object.
     pub fn build(mut self) -> OObject {
+        self.ts_overrides.apply(&mut self.geom_compound);
+        if let Some(mut arb) = self.arb_geom_compound {
+            self.ts_overrides.apply(&mut arb);
+            self.geom_compound.add_child(arb);
+        }
         self.object.properties.push(self.geom_compound);
         self.object
     }
@@ -137,3 +268,68 @@ impl OPoints {
         self.object.children.push(child);
     }
 }
+
+/// Check that ids match the point count and are unique.
+pub fn validate_ids(ids: &[i64], num_points: usize) -> Result<()> {
+    if ids.len() != num_points {
+        return Err(Error::WriteFailed(format!(
+            "points: {} ids for {} positions", ids.len(), num_points
+        )));
+    }
+    let mut seen = HashSet::with_capacity(ids.len());
+    for (i, &id) in ids.iter().enumerate() {
+        if !seen.insert(id) {
+            return Err(Error::WriteFailed(format!(
+                "points: duplicate id {} at index {}", id, i
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// FNV-1a hash of the object name, used to seed hashed ids.
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_ids() {
+        let mut points = OPoints::new("pts");
+        assert_eq!(points.generate_ids(3), vec![0, 1, 2]);
+
+        points.set_id_mode(PointIdMode::Hashed);
+        let ids = points.generate_ids(1000);
+        assert!(ids.iter().all(|&id| id >= 0));
+        assert!(validate_ids(&ids, 1000).is_ok());
+        assert_eq!(ids, points.generate_ids(1000));
+        let mut other = OPoints::new("other");
+        other.set_id_mode(PointIdMode::Hashed);
+        assert_ne!(ids, other.generate_ids(1000));
+    }
+
+    #[test]
+    fn test_validate_ids() {
+        assert!(validate_ids(&[3, 1, 2], 3).is_ok());
+        assert!(validate_ids(&[1, 2], 3).is_err());
+        assert!(validate_ids(&[1, 2, 1], 3).is_err());
+
+        let mut points = OPoints::new("pts");
+        let dup = OPointsSample::new(vec![glam::Vec3::ZERO; 2], vec![7, 7]);
+        assert!(points.try_add_sample(&dup).is_err());
+        let empty = OPointsSample::new(vec![glam::Vec3::ZERO; 2], Vec::new());
+        assert!(points.try_add_sample(&empty).is_ok());
+    }
+}
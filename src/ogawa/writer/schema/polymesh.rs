@@ -4,18 +4,32 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OPolyMesh.cpp`
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OPolyMesh.h`
 
-use crate::core::MetaData;
+use crate::core::{GeometryScope, MetaData};
+use crate::geom::{AbcStruct, GeomParamSample, OGeomParam, IS_UV_KEY, SchemaInfo};
+use crate::geom::typed::mark_geom_params;
 use crate::util::{BBox3d, DataType, PlainOldDataType};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
-use super::util::compute_bounds_vec3;
+use super::util::{
+    add_geom_param_sample, children_constant, compute_bounds_vec3, find_child_mut, put_child, hold_geom_param_sample,
+    hold_sample_at, TimeSamplingOverrides,
+};
 
 /// PolyMesh sample data.
+///
+/// After the first sample, `keep_topology` (see [`deformed`](Self::deformed))
+/// and `None` for velocities, normals or UVs that were written before leave
+/// those properties unchanged ([`OProperty::hold_sample`]). The same holds
+/// for arbitrary GeomParams and UV sets left out of later samples. Unchanged
+/// trailing samples are not written at all; readers hold the last one.
 pub struct OPolyMeshSample {
     pub positions: Vec<glam::Vec3>,
     pub face_counts: Vec<i32>,
     pub face_indices: Vec<i32>,
+    /// Keep the topology of the previous sample; `face_counts` and
+    /// `face_indices` are ignored. Empty topology vectors are written as-is.
+    pub keep_topology: bool,
     pub velocities: Option<Vec<glam::Vec3>>,
     pub normals: Option<Vec<glam::Vec3>>,
     /// Write normals as simple array (true) or GeomParam compound (false).
@@ -23,6 +37,12 @@ pub struct OPolyMeshSample {
     pub uvs: Option<Vec<glam::Vec2>>,
     /// Explicit self bounds (if None, computed from positions).
     pub self_bounds: Option<BBox3d>,
+    /// Arbitrary GeomParams for `.arbGeomParams` (color sets, `Pref`, custom
+    /// attributes), by name. Indexed when the sample carries indices.
+    pub arb_geom_params: Vec<(String, GeomParamSample)>,
+    /// Additional UV sets (Vec2f, usually face-varying), written to
+    /// `.arbGeomParams` with `isUV` metadata like Maya's extra UV sets.
+    pub uv_sets: Vec<(String, GeomParamSample)>,
 }
 
 impl OPolyMeshSample {
@@ -32,11 +52,38 @@ impl OPolyMeshSample {
             positions,
             face_counts,
             face_indices,
+            keep_topology: false,
             velocities: None,
             normals: None,
             normals_is_simple_array: false, // Default to compound format.
             uvs: None,
             self_bounds: None,
+            arb_geom_params: Vec::new(),
+            uv_sets: Vec::new(),
+        }
+    }
+
+    /// Create a sample that only moves the points, keeping the topology of
+    /// the previous sample.
+    pub fn deformed(positions: Vec<glam::Vec3>) -> Self {
+        Self { keep_topology: true, ..Self::new(positions, Vec::new(), Vec::new()) }
+    }
+
+    /// Add (or replace) an arbitrary GeomParam for this sample.
+    pub fn set_arb_geom_param(&mut self, name: &str, sample: GeomParamSample) {
+        match self.arb_geom_params.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = sample,
+            None => self.arb_geom_params.push((name.to_string(), sample)),
+        }
+    }
+
+    /// Add (or replace) an extra UV set. Face-varying unless `indices` are
+    /// given, in which case `uvs` holds the shared values.
+    pub fn set_uv_set(&mut self, name: &str, uvs: &[glam::Vec2], indices: Option<Vec<u32>>) {
+        let sample = GeomParamSample::from_values(DataType::VEC2F, uvs, indices, GeometryScope::FaceVarying);
+        match self.uv_sets.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = sample,
+            None => self.uv_sets.push((name.to_string(), sample)),
         }
     }
 }
@@ -46,26 +93,29 @@ pub struct OPolyMesh {
     object: OObject,
     geom_compound: OProperty,
     arb_geom_compound: Option<OProperty>,
+    /// Arbitrary GeomParams written per sample (held when left out)
+    sampled_arb_params: Vec<String>,
     time_sampling_index: u32,
+    ts_overrides: TimeSamplingOverrides,
 }
 
 impl OPolyMesh {
     /// Create a new PolyMesh.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_PolyMesh_v1");
-        meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
-        meta.set("schemaObjTitle", "AbcGeom_PolyMesh_v1:.geom");
-        object.meta_data = meta;
-
-        let mut geom_meta = MetaData::new();
-        geom_meta.set("schema", "AbcGeom_PolyMesh_v1");
-        geom_meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
+        object.meta_data = SchemaInfo::POLYMESH.object_metadata();
+
         let mut geom = OProperty::compound(".geom");
-        geom.meta_data = geom_meta;
+        geom.meta_data = SchemaInfo::POLYMESH.compound_metadata();
 
-        Self { object, geom_compound: geom, arb_geom_compound: None, time_sampling_index: 0 }
+        Self {
+            object,
+            geom_compound: geom,
+            arb_geom_compound: None,
+            sampled_arb_params: Vec::new(),
+            time_sampling_index: 0,
+            ts_overrides: TimeSamplingOverrides::default(),
+        }
     }
 
     /// Set time sampling index for animated properties.
@@ -79,6 +129,45 @@ impl OPolyMesh {
         self.time_sampling_index = index;
     }
 
+    /// Override the time sampling of a single property by name (e.g. `"uv"`,
+    /// `".widths"`), leaving the others on the schema time sampling. Applied
+    /// when the object is built.
+    pub fn set_property_time_sampling(&mut self, name: &str, index: u32) {
+        self.ts_overrides.set(name, index);
+    }
+
+    /// Mutable access to a property of the schema compound, e.g. to add
+    /// extra samples to a property with its own time sampling.
+    pub fn geom_property_mut(&mut self, name: &str) -> Option<&mut OProperty> {
+        find_child_mut(&mut self.geom_compound, name)
+    }
+
+    /// Add an arbitrary geometry parameter (`.geom/.arbGeomParams`), e.g.
+    /// joint weights or rest positions. Replaces a param of the same name.
+    pub fn add_arb_geom_param(&mut self, param: &OGeomParam) {
+        let arb = self.arb_geom_compound.get_or_insert_with(|| OProperty::compound(".arbGeomParams"));
+        put_child(arb, param.to_property());
+    }
+
+    /// Add a user property (`.geom/.userProperties`), e.g. bind matrices.
+    /// Replaces a property of the same name.
+    pub fn add_user_property(&mut self, prop: OProperty) {
+        put_child(self.geom_compound.get_or_create_compound_child(".userProperties"), prop);
+    }
+
+    /// Append one sample of a typed struct to `.geom/.arbGeomParams`
+    /// (one param per field, constant scope unless the field sets one).
+    pub fn write_arb<T: AbcStruct>(&mut self, value: &T) {
+        let arb = self.arb_geom_compound.get_or_insert_with(|| OProperty::compound(".arbGeomParams"));
+        value.write_compound(arb);
+        mark_geom_params(arb);
+    }
+
+    /// Append one sample of a typed struct to `.geom/.userProperties`.
+    pub fn write_user<T: AbcStruct>(&mut self, value: &T) {
+        value.write_compound(self.geom_compound.get_or_create_compound_child(".userProperties"));
+    }
+
     /// Add a sample (positions + topology + optional data).
     /// 
         /// PROPERTY CREATION ORDER matches C++ init():
@@ -183,14 +272,23 @@ impl OPolyMesh {
             ".faceIndices",
             DataType::new(PlainOldDataType::Int32, 1),
         );
-        face_indices_prop.add_array_pod(&sample.face_indices);
+        let keep_topology = sample.keep_topology && face_indices_prop.getNumSamples() > 0;
+        if keep_topology {
+            face_indices_prop.hold_sample();
+        } else {
+            face_indices_prop.add_array_pod(&sample.face_indices);
+        }
         
         // .faceCounts data
         let face_counts_prop = self.get_or_create_array_with_ts(
             ".faceCounts",
             DataType::new(PlainOldDataType::Int32, 1),
         );
-        face_counts_prop.add_array_pod(&sample.face_counts);
+        if keep_topology {
+            face_counts_prop.hold_sample();
+        } else {
+            face_counts_prop.add_array_pod(&sample.face_counts);
+        }
         
         // .selfBnds data
         let bounds = if let Some(ref bnds) = sample.self_bounds {
@@ -212,6 +310,8 @@ impl OPolyMesh {
                 DataType::new(PlainOldDataType::Float32, 3),
             );
             vel_prop.add_array_pod(vels);
+        } else {
+            self.hold_geom_child(&[".velocities"]);
         }
 
         // Normals (optional)
@@ -243,6 +343,10 @@ impl OPolyMesh {
                 vals_prop.time_sampling_index = self.time_sampling_index;
                 vals_prop.add_array_pod(normals);
             }
+        } else {
+            // Simple array or GeomParam compound, whichever was written.
+            self.hold_geom_child(&["N"]);
+            self.hold_geom_child(&["N", ".vals"]);
         }
 
         // UVs (optional)
@@ -262,9 +366,46 @@ impl OPolyMesh {
             );
             vals_prop.time_sampling_index = self.time_sampling_index;
             vals_prop.add_array_pod(uvs);
+        } else if let Some(arb) = self.arb_geom_compound.as_mut() {
+            hold_sample_at(arb, &["uv", ".vals"]);
+        }
+
+        // Arbitrary GeomParams and extra UV sets (optional)
+        let has_params = !sample.arb_geom_params.is_empty() || !sample.uv_sets.is_empty();
+        if has_params || !self.sampled_arb_params.is_empty() {
+            let ts_idx = self.time_sampling_index;
+            let arb = self.arb_geom_compound.get_or_insert_with(|| OProperty::compound(".arbGeomParams"));
+            for (name, param) in &sample.arb_geom_params {
+                add_geom_param_sample(arb, name, param, ts_idx);
+                if !self.sampled_arb_params.contains(name) {
+                    self.sampled_arb_params.push(name.clone());
+                }
+            }
+            for (name, param) in &sample.uv_sets {
+                add_geom_param_sample(arb, name, param, ts_idx);
+                if let Some(prop) = find_child_mut(arb, name) {
+                    prop.meta_data.set(IS_UV_KEY, "1");
+                }
+                if !self.sampled_arb_params.contains(name) {
+                    self.sampled_arb_params.push(name.clone());
+                }
+            }
+            let in_sample = |name: &String| {
+                sample.arb_geom_params.iter().chain(&sample.uv_sets).any(|(n, _)| n == name)
+            };
+            for name in &self.sampled_arb_params {
+                if !in_sample(name) {
+                    hold_geom_param_sample(arb, name);
+                }
+            }
         }
     }
 
+    /// Hold an existing .geom child (by path) unchanged for this sample.
+    fn hold_geom_child(&mut self, path: &[&str]) {
+        hold_sample_at(&mut self.geom_compound, path);
+    }
+
     fn get_or_create_array_with_ts(&mut self, prop_name: &str, data_type: DataType) -> &mut OProperty {
         let ts_idx = self.time_sampling_index;
         if let OPropertyData::Compound(children) = &mut self.geom_compound.data {
@@ -322,12 +463,23 @@ impl OPolyMesh {
         }
     }
 
+    /// Check if all samples added so far have the same positions and
+    /// topology. Repeated samples are stored once, and the mesh reads back
+    /// as constant ([`IPolyMesh::isConstant`](crate::geom::IPolyMesh::isConstant)).
+    pub fn isConstant(&self) -> bool {
+        children_constant(&self.geom_compound, &["P", ".faceIndices", ".faceCounts"])
+    }
+
     /// Build the object.
     pub fn build(mut self) -> OObject {
-        self.object.properties.push(self.geom_compound);
+        self.ts_overrides.apply(&mut self.geom_compound);
+        if let Some(arb) = self.arb_geom_compound.as_mut() {
+            self.ts_overrides.apply(arb);
+        }
         if let Some(arb) = self.arb_geom_compound {
-            self.object.properties.push(arb);
+            self.geom_compound.add_child(arb);
         }
+        self.object.properties.push(self.geom_compound);
         self.object
     }
 
@@ -336,3 +488,4 @@ impl OPolyMesh {
         self.object.children.push(child);
     }
 }
+
@@ -5,17 +5,25 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/OSubD.h`
 
 use crate::core::MetaData;
-use crate::util::{DataType, PlainOldDataType};
+use crate::geom::{AbcStruct, FaceVaryingInterpolation, InterpolateBoundary, OGeomParam, SchemaInfo};
+use crate::geom::typed::mark_geom_params;
+use crate::util::{AlembicPod, DataType, PlainOldDataType};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
-use super::util::{bounds_meta, compute_bounds_vec3};
+use super::util::{bounds_meta, compute_bounds_vec3, hold_sample_at, find_child_mut, put_child, TimeSamplingOverrides};
 
 /// SubD sample data.
+///
+/// As with `OPolyMeshSample`, `keep_topology` or `None` attributes after the
+/// first sample leave that property unchanged.
 pub struct OSubDSample {
     pub positions: Vec<glam::Vec3>,
     pub face_counts: Vec<i32>,
     pub face_indices: Vec<i32>,
+    /// Keep the topology of the previous sample; `face_counts` and
+    /// `face_indices` are ignored.
+    pub keep_topology: bool,
     pub subdivision_scheme: String,
     pub velocities: Option<Vec<glam::Vec3>>,
     pub uvs: Option<Vec<glam::Vec2>>,
@@ -28,6 +36,13 @@ pub struct OSubDSample {
     pub holes: Option<Vec<i32>>,
     pub normals: Option<Vec<glam::Vec3>>,
     pub normal_indices: Option<Vec<i32>>,
+    /// `.interpolateBoundary`; like the C++ writer, the property is only
+    /// written once set (earlier samples get the default).
+    pub interp_boundary: Option<InterpolateBoundary>,
+    /// `.faceVaryingInterpolateBoundary`, written once set.
+    pub fv_interp_boundary: Option<FaceVaryingInterpolation>,
+    /// `.faceVaryingPropagateCorners`, written once set.
+    pub fv_propagate_corners: Option<i32>,
 }
 
 impl OSubDSample {
@@ -41,6 +56,7 @@ impl OSubDSample {
             positions,
             face_counts,
             face_indices,
+            keep_topology: false,
             // C++ ref: OSubD.h:105 uses "catmull-clark" (hyphenated)
             subdivision_scheme: "catmull-clark".to_string(),
             velocities: None,
@@ -54,9 +70,18 @@ impl OSubDSample {
             holes: None,
             normals: None,
             normal_indices: None,
+            interp_boundary: None,
+            fv_interp_boundary: None,
+            fv_propagate_corners: None,
         }
     }
     
+    /// Create a sample that only moves the points, keeping the topology of
+    /// the previous sample.
+    pub fn deformed(positions: Vec<glam::Vec3>) -> Self {
+        Self { keep_topology: true, ..Self::new(positions, Vec::new(), Vec::new()) }
+    }
+
     /// Set subdivision scheme (builder pattern).
     /// Common values: "catmullClark", "loop", "bilinear"
     pub fn with_scheme(mut self, scheme: impl Into<String>) -> Self {
@@ -70,25 +95,19 @@ pub struct OSubD {
     object: OObject,
     geom_compound: OProperty,
     time_sampling_index: u32,
+    ts_overrides: TimeSamplingOverrides,
 }
 
 impl OSubD {
     /// Create new SubD.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_SubD_v1");
-        meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
-        meta.set("schemaObjTitle", "AbcGeom_SubD_v1:.geom");
-        object.meta_data = meta;
-
-        let mut geom_meta = MetaData::new();
-        geom_meta.set("schema", "AbcGeom_SubD_v1");
-        geom_meta.set("schemaBaseType", "AbcGeom_GeomBase_v1");
+        object.meta_data = SchemaInfo::SUBD.object_metadata();
+
         let mut geom = OProperty::compound(".geom");
-        geom.meta_data = geom_meta;
+        geom.meta_data = SchemaInfo::SUBD.compound_metadata();
 
-        Self { object, geom_compound: geom, time_sampling_index: 0 }
+        Self { object, geom_compound: geom, time_sampling_index: 0, ts_overrides: TimeSamplingOverrides::default() }
     }
 
     /// Set time sampling index for animated properties.
@@ -96,6 +115,44 @@ impl OSubD {
         self.time_sampling_index = index;
     }
 
+    /// Override the time sampling of a single property by name (e.g. `"uv"`,
+    /// `".widths"`), leaving the others on the schema time sampling. Applied
+    /// when the object is built.
+    pub fn set_property_time_sampling(&mut self, name: &str, index: u32) {
+        self.ts_overrides.set(name, index);
+    }
+
+    /// Mutable access to a property of the schema compound, e.g. to add
+    /// extra samples to a property with its own time sampling.
+    pub fn geom_property_mut(&mut self, name: &str) -> Option<&mut OProperty> {
+        find_child_mut(&mut self.geom_compound, name)
+    }
+
+    /// Add an arbitrary geometry parameter (`.geom/.arbGeomParams`), e.g.
+    /// joint weights or rest positions. Replaces a param of the same name.
+    pub fn add_arb_geom_param(&mut self, param: &OGeomParam) {
+        put_child(self.geom_compound.get_or_create_compound_child(".arbGeomParams"), param.to_property());
+    }
+
+    /// Add a user property (`.geom/.userProperties`), e.g. bind matrices.
+    /// Replaces a property of the same name.
+    pub fn add_user_property(&mut self, prop: OProperty) {
+        put_child(self.geom_compound.get_or_create_compound_child(".userProperties"), prop);
+    }
+
+    /// Append one sample of a typed struct to `.geom/.arbGeomParams`
+    /// (one param per field, constant scope unless the field sets one).
+    pub fn write_arb<T: AbcStruct>(&mut self, value: &T) {
+        let arb = self.geom_compound.get_or_create_compound_child(".arbGeomParams");
+        value.write_compound(arb);
+        mark_geom_params(arb);
+    }
+
+    /// Append one sample of a typed struct to `.geom/.userProperties`.
+    pub fn write_user<T: AbcStruct>(&mut self, value: &T) {
+        value.write_compound(self.geom_compound.get_or_create_compound_child(".userProperties"));
+    }
+
     /// Add a sample.
     pub fn add_sample(&mut self, sample: &OSubDSample) {
         let bounds = compute_bounds_vec3(&sample.positions);
@@ -120,14 +177,23 @@ impl OSubD {
             DataType::new(PlainOldDataType::Int32, 1),
         );
         fi_prop.data_write_order = 1;
-        fi_prop.add_array_pod(&sample.face_indices);
+        let keep_topology = sample.keep_topology && fi_prop.getNumSamples() > 0;
+        if keep_topology {
+            fi_prop.hold_sample();
+        } else {
+            fi_prop.add_array_pod(&sample.face_indices);
+        }
 
         let fc_prop = self.geom_compound.get_or_create_array_child(
             ".faceCounts",
             DataType::new(PlainOldDataType::Int32, 1),
         );
         fc_prop.data_write_order = 2;
-        fc_prop.add_array_pod(&sample.face_counts);
+        if keep_topology {
+            fc_prop.hold_sample();
+        } else {
+            fc_prop.add_array_pod(&sample.face_counts);
+        }
 
         let scheme_prop = self.geom_compound.get_or_create_scalar_child(
             ".scheme",
@@ -143,57 +209,25 @@ impl OSubD {
             );
             v_prop.data_write_order = 3;
             v_prop.add_array_pod(vels);
+        } else {
+            hold_sample_at(&mut self.geom_compound, &[".velocities"]);
         }
 
-        if let Some(ref indices) = sample.crease_indices {
-            let prop = self.geom_compound.get_or_create_array_child(
-                ".creaseIndices",
-                DataType::new(PlainOldDataType::Int32, 1),
-            );
-            prop.data_write_order = 11;
-            prop.add_array_pod(indices);
-        }
-        if let Some(ref lengths) = sample.crease_lengths {
-            let prop = self.geom_compound.get_or_create_array_child(
-                ".creaseLengths",
-                DataType::new(PlainOldDataType::Int32, 1),
-            );
-            prop.data_write_order = 12;
-            prop.add_array_pod(lengths);
-        }
-        if let Some(ref sharpnesses) = sample.crease_sharpnesses {
-            let prop = self.geom_compound.get_or_create_array_child(
-                ".creaseSharpnesses",
-                DataType::new(PlainOldDataType::Float32, 1),
-            );
-            prop.data_write_order = 13;
-            prop.add_array_pod(sharpnesses);
-        }
-
-        if let Some(ref indices) = sample.corner_indices {
-            let prop = self.geom_compound.get_or_create_array_child(
-                ".cornerIndices",
-                DataType::new(PlainOldDataType::Int32, 1),
-            );
-            prop.data_write_order = 14;
-            prop.add_array_pod(indices);
-        }
-        if let Some(ref sharpnesses) = sample.corner_sharpnesses {
-            let prop = self.geom_compound.get_or_create_array_child(
-                ".cornerSharpnesses",
-                DataType::new(PlainOldDataType::Float32, 1),
-            );
-            prop.data_write_order = 15;
-            prop.add_array_pod(sharpnesses);
-        }
+        // Crease, corner and hole arrays are optional per sample
+        self.add_optional_array(".creaseIndices", sample.crease_indices.as_deref(), 11);
+        self.add_optional_array(".creaseLengths", sample.crease_lengths.as_deref(), 12);
+        self.add_optional_array(".creaseSharpnesses", sample.crease_sharpnesses.as_deref(), 13);
+        self.add_optional_array(".cornerIndices", sample.corner_indices.as_deref(), 14);
+        self.add_optional_array(".cornerSharpnesses", sample.corner_sharpnesses.as_deref(), 15);
+        self.add_optional_array(".holes", sample.holes.as_deref(), 16);
 
-        if let Some(ref holes) = sample.holes {
-            let prop = self.geom_compound.get_or_create_array_child(
-                ".holes",
-                DataType::new(PlainOldDataType::Int32, 1),
-            );
-            prop.data_write_order = 16;
-            prop.add_array_pod(holes);
+        let boundary_rules = [
+            (".interpolateBoundary", sample.interp_boundary.map(|b| b.as_i32()), 17),
+            (".faceVaryingInterpolateBoundary", sample.fv_interp_boundary.map(|b| b.as_i32()), 18),
+            (".faceVaryingPropagateCorners", sample.fv_propagate_corners, 19),
+        ];
+        for (name, value, order) in boundary_rules {
+            self.add_int_rule(name, value, order);
         }
 
         if let Some(ref uvs) = sample.uvs {
@@ -218,7 +252,12 @@ impl OSubD {
                 idx_prop.time_sampling_index = self.time_sampling_index;
                 idx_prop.data_write_order = 6;
                 idx_prop.add_array_pod(uvi);
+            } else {
+                hold_sample_at(uv_compound, &[".indices"]);
             }
+        } else {
+            hold_sample_at(&mut self.geom_compound, &["uv", ".vals"]);
+            hold_sample_at(&mut self.geom_compound, &["uv", ".indices"]);
         }
 
         if let Some(ref normals) = sample.normals {
@@ -243,7 +282,60 @@ impl OSubD {
                 idx_prop.time_sampling_index = self.time_sampling_index;
                 idx_prop.data_write_order = 8;
                 idx_prop.add_array_pod(ni);
+            } else {
+                hold_sample_at(n_compound, &[".indices"]);
             }
+        } else {
+            hold_sample_at(&mut self.geom_compound, &["N", ".vals"]);
+            hold_sample_at(&mut self.geom_compound, &["N", ".indices"]);
+        }
+    }
+
+    /// Samples written so far, including the one being added.
+    fn num_written(&mut self) -> usize {
+        find_child_mut(&mut self.geom_compound, "P").map_or(0, |p| p.getNumSamples())
+    }
+
+    /// Add a sample of an optional int32 scalar. The property is created on
+    /// the first `Some`, with the samples written so far backfilled with 0
+    /// (the reader default); after that `None` leaves it unchanged.
+    fn add_int_rule(&mut self, name: &str, value: Option<i32>, order: u32) {
+        let written = self.num_written();
+        match value {
+            Some(v) => {
+                let exists = find_child_mut(&mut self.geom_compound, name).is_some();
+                let prop = self.geom_compound.get_or_create_scalar_child(name, DataType::new(PlainOldDataType::Int32, 1));
+                prop.data_write_order = order;
+                if !exists {
+                    prop.time_sampling_index = self.time_sampling_index;
+                    for _ in 1..written {
+                        prop.add_scalar_pod(&0i32);
+                    }
+                }
+                prop.add_scalar_pod(&v);
+            }
+            None => hold_sample_at(&mut self.geom_compound, &[name]),
+        }
+    }
+
+    /// Array counterpart of [`Self::add_int_rule`]: earlier samples of a
+    /// late array are backfilled empty so sample indices stay aligned.
+    fn add_optional_array<T: AlembicPod>(&mut self, name: &str, values: Option<&[T]>, order: u32) {
+        let written = self.num_written();
+        match values {
+            Some(values) => {
+                let exists = find_child_mut(&mut self.geom_compound, name).is_some();
+                let prop = self.geom_compound.get_or_create_array_child(name, DataType::new(T::POD_TYPE, 1));
+                prop.data_write_order = order;
+                if !exists {
+                    prop.time_sampling_index = self.time_sampling_index;
+                    for _ in 1..written {
+                        prop.add_array_pod::<T>(&[]);
+                    }
+                }
+                prop.add_array_pod(values);
+            }
+            None => hold_sample_at(&mut self.geom_compound, &[name]),
         }
     }
 
@@ -286,6 +378,7 @@ impl OSubD {
 
     /// Build the object.
     pub fn build(mut self) -> OObject {
+        self.ts_overrides.apply(&mut self.geom_compound);
         self.object.properties.push(self.geom_compound);
         self.object
     }
@@ -295,3 +388,4 @@ impl OSubD {
         self.object.children.push(child);
     }
 }
+
@@ -3,6 +3,10 @@
 //! These are small utilities to reduce duplication without changing behavior.
 
 use crate::core::MetaData;
+use crate::geom::{GeomParamSample, OGeomParam, INDICES_PROPERTY_NAME, VALS_PROPERTY_NAME};
+use crate::util::DataType;
+
+use super::super::property::{OProperty, OPropertyData};
 
 /// Compute bounding box from positions.
 /// Matches AbcGeom writer behavior (min/max over points).
@@ -26,3 +30,131 @@ pub(crate) fn bounds_meta() -> MetaData {
     meta.set("interpretation", "box");
     meta
 }
+
+/// Hold the property at `path` under `parent` unchanged for one sample
+/// (see [`OProperty::hold_sample`]). Does nothing if the property does not
+/// exist (yet).
+pub(crate) fn hold_sample_at(parent: &mut OProperty, path: &[&str]) {
+    let Some((name, rest)) = path.split_first() else {
+        parent.hold_sample();
+        return;
+    };
+    if let OPropertyData::Compound(children) = &mut parent.data {
+        if let Some(child) = children.iter_mut().find(|p| p.name == *name) {
+            hold_sample_at(child, rest);
+        }
+    }
+}
+
+/// Check if the direct children named `names` hold the same value at every
+/// sample added so far; missing children count as constant.
+pub(crate) fn children_constant(parent: &OProperty, names: &[&str]) -> bool {
+    match &parent.data {
+        OPropertyData::Compound(children) => children
+            .iter()
+            .filter(|p| names.contains(&p.name.as_str()))
+            .all(|p| p.isConstant()),
+        _ => true,
+    }
+}
+
+/// Find a direct child property by name.
+pub(crate) fn find_child_mut<'a>(parent: &'a mut OProperty, name: &str) -> Option<&'a mut OProperty> {
+    match &mut parent.data {
+        OPropertyData::Compound(children) => children.iter_mut().find(|p| p.name == name),
+        _ => None,
+    }
+}
+
+/// Add `prop` under `parent`, replacing an existing child of the same name.
+pub(crate) fn put_child(parent: &mut OProperty, prop: OProperty) {
+    if let OPropertyData::Compound(children) = &mut parent.data {
+        match children.iter_mut().find(|p| p.name == prop.name) {
+            Some(existing) => *existing = prop,
+            None => children.push(prop),
+        }
+    }
+}
+
+/// Append one sample of an arbitrary GeomParam under `parent`: an array
+/// property, or a `.vals`/`.indices` compound when the sample is indexed.
+/// The property is created on first use with the usual GeomParam metadata.
+pub(crate) fn add_geom_param_sample(parent: &mut OProperty, name: &str, sample: &GeomParamSample, ts_index: u32) {
+    let elem_bytes = sample.data_type.num_bytes().max(1);
+    let dims = [sample.values.len() / elem_bytes];
+    let mut meta = OGeomParam::new(name, sample.data_type, sample.scope, sample.indices.is_some()).build_meta_data();
+    meta.set("isGeomParam", "true");
+
+    match &sample.indices {
+        Some(indices) => {
+            let compound = parent.get_or_create_compound_child(name);
+            if compound.meta_data.is_empty() {
+                compound.meta_data = meta;
+            }
+            let vals = compound.get_or_create_array_child(VALS_PROPERTY_NAME, sample.data_type);
+            vals.time_sampling_index = ts_index;
+            vals.add_array_sample(&sample.values, &dims);
+            let idx = compound.get_or_create_array_child(INDICES_PROPERTY_NAME, DataType::UINT32);
+            idx.time_sampling_index = ts_index;
+            idx.add_array_pod(indices);
+        }
+        None => {
+            if find_child_mut(parent, name).is_none() {
+                let mut prop = OProperty::array(name, sample.data_type);
+                prop.time_sampling_index = ts_index;
+                prop.meta_data = meta;
+                parent.add_child(prop);
+            }
+            if let Some(prop) = find_child_mut(parent, name) {
+                prop.add_array_sample(&sample.values, &dims);
+            }
+        }
+    }
+}
+
+/// Hold a GeomParam written by [`add_geom_param_sample`] unchanged for one sample.
+pub(crate) fn hold_geom_param_sample(parent: &mut OProperty, name: &str) {
+    match find_child_mut(parent, name) {
+        Some(prop) if matches!(prop.data, OPropertyData::Compound(_)) => {
+            hold_sample_at(prop, &[VALS_PROPERTY_NAME]);
+            hold_sample_at(prop, &[INDICES_PROPERTY_NAME]);
+        }
+        Some(prop) => prop.hold_sample(),
+        None => {}
+    }
+}
+
+/// Per-property time sampling overrides for a schema writer.
+///
+/// Schema writers create their properties with the schema-wide time sampling;
+/// overrides are applied by property name when the schema is built. For a
+/// GeomParam compound (e.g. `uv`, `N`) the index is applied to its children.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TimeSamplingOverrides(Vec<(String, u32)>);
+
+impl TimeSamplingOverrides {
+    pub(crate) fn set(&mut self, name: &str, index: u32) {
+        match self.0.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = index,
+            None => self.0.push((name.to_string(), index)),
+        }
+    }
+
+    /// Apply overrides to the direct children of `compound`.
+    pub(crate) fn apply(&self, compound: &mut OProperty) {
+        for (name, index) in &self.0 {
+            if let Some(child) = find_child_mut(compound, name) {
+                set_time_sampling_recursive(child, *index);
+            }
+        }
+    }
+}
+
+fn set_time_sampling_recursive(prop: &mut OProperty, index: u32) {
+    prop.time_sampling_index = index;
+    if let OPropertyData::Compound(children) = &mut prop.data {
+        for child in children {
+            set_time_sampling_recursive(child, index);
+        }
+    }
+}
@@ -5,11 +5,12 @@
 //! - `_ref/alembic/lib/Alembic/AbcGeom/XformOp.cpp`
 
 use crate::core::MetaData;
-use crate::geom::{XformOp, XformOpType};
+use crate::geom::{XformOp, XformOpType, XformSample, SchemaInfo};
 use crate::util::{BBox3d, DataType, PlainOldDataType};
 
 use super::super::object::OObject;
 use super::super::property::{OProperty, OPropertyData};
+use super::bounds::children_bounds;
 
 /// Xform sample data.
 pub struct OXformSample {
@@ -51,6 +52,12 @@ impl OXformSample {
     pub fn from_ops(ops: Vec<XformOp>, inherits: bool) -> Self {
         Self { ops, inherits }
     }
+
+    /// Create from a read sample, keeping its op stack and hints so the
+    /// written file has the same editable channels (e.g. Maya TRS and pivots).
+    pub fn from_sample(sample: &XformSample) -> Self {
+        Self { ops: sample.ops.clone(), inherits: sample.inherits }
+    }
 }
 
 /// Xform schema writer.
@@ -62,16 +69,15 @@ pub struct OXform {
     child_bounds: Vec<BBox3d>,
     /// Time sampling index for child bounds (may differ from transform ts).
     child_bounds_ts_index: u32,
+    /// Compute `.childBnds` from the children when none were added.
+    compute_child_bounds: bool,
 }
 
 impl OXform {
     /// Create new Xform.
     pub fn new(name: &str) -> Self {
         let mut object = OObject::new(name);
-        let mut meta = MetaData::new();
-        meta.set("schema", "AbcGeom_Xform_v3");
-        meta.set("schemaObjTitle", "AbcGeom_Xform_v3:.xform");
-        object.meta_data = meta;
+        object.meta_data = SchemaInfo::XFORM.object_metadata();
 
         Self {
             object,
@@ -79,6 +85,7 @@ impl OXform {
             time_sampling_index: 0,
             child_bounds: Vec::new(),
             child_bounds_ts_index: 0,
+            compute_child_bounds: true,
         }
     }
 
@@ -108,13 +115,40 @@ impl OXform {
         self.child_bounds_ts_index = index;
     }
 
+    /// Enable or disable automatic `.childBnds` (enabled by default).
+    ///
+    /// When enabled and no bounds were added with [`Self::add_child_bounds`],
+    /// `build` writes the union of the children's bounds in this Xform's
+    /// space: geometry `.selfBnds`, and child Xform `.childBnds` transformed
+    /// by the child's matrix. Children must be added before `build`.
+    pub fn set_compute_child_bounds(&mut self, enabled: bool) {
+        self.compute_child_bounds = enabled;
+    }
+
+    /// Check if all samples added so far are identical (same inherits flag,
+    /// op types and values). `.vals` and `.inherits` are then stored once,
+    /// and the Xform reads back as constant
+    /// ([`IXform::isConstant`](crate::geom::IXform::isConstant)).
+    pub fn isConstant(&self) -> bool {
+        self.samples.windows(2).all(|pair| {
+            let (a, b) = (&pair[0], &pair[1]);
+            a.inherits == b.inherits
+                && a.ops.len() == b.ops.len()
+                && a.ops.iter().zip(&b.ops).all(|(x, y)| x.op_type == y.op_type && x.values == y.values)
+        })
+    }
+
     /// Build the object.
     pub fn build(mut self) -> OObject {
+        if self.child_bounds.is_empty() && self.compute_child_bounds {
+            if let Some(bounds) = children_bounds(&self.object.children) {
+                self.child_bounds = bounds.samples;
+                self.child_bounds_ts_index = bounds.time_sampling_index;
+            }
+        }
         if !self.samples.is_empty() {
             let mut geom = OProperty::compound(".xform");
-            let mut geom_meta = MetaData::new();
-            geom_meta.set("schema", "AbcGeom_Xform_v3");
-            geom.meta_data = geom_meta;
+            geom.meta_data = SchemaInfo::XFORM.compound_metadata();
 
             let is_not_identity = self.samples.iter().any(|s| !s.ops.is_empty());
 
@@ -136,18 +170,32 @@ impl OXform {
                     ".ops",
                     DataType::new(PlainOldDataType::Uint8, num_ops as u8),
                 );
-                let op_codes: Vec<u8> = first_sample.ops.iter().map(|op| encode_xform_op(op.op_type)).collect();
+                let op_codes: Vec<u8> = first_sample.ops.iter().map(encode_xform_op).collect();
                 ops.add_scalar_sample(&op_codes);
 
-                let mut vals = OProperty::scalar(
-                    ".vals",
-                    DataType::new(PlainOldDataType::Float64, total_vals as u8),
-                );
+                // Per OXform.cpp: more than 256 channels go to an array property
+                let use_array = total_vals > u8::MAX as usize;
+                let mut vals = if use_array {
+                    OProperty::array(".vals", DataType::new(PlainOldDataType::Float64, 1))
+                } else {
+                    OProperty::scalar(".vals", DataType::new(PlainOldDataType::Float64, total_vals as u8))
+                };
                 vals.time_sampling_index = self.time_sampling_index;
-                for sample in &self.samples {
-                    let all_vals: Vec<f64> = sample.ops.iter().flat_map(|op| op.values.iter().copied()).collect();
-                    vals.add_scalar_sample(bytemuck::cast_slice(&all_vals));
+                let sample_vals: Vec<Vec<f64>> = self.samples.iter()
+                    .map(|sample| {
+                        let mut all_vals: Vec<f64> = sample.ops.iter().flat_map(|op| op.values.iter().copied()).collect();
+                        all_vals.resize(total_vals, 0.0);
+                        all_vals
+                    })
+                    .collect();
+                for all_vals in &sample_vals {
+                    if use_array {
+                        vals.add_array_pod(all_vals);
+                    } else {
+                        vals.add_scalar_sample(bytemuck::cast_slice(all_vals));
+                    }
                 }
+                let anim_chans = animated_channels(&sample_vals);
 
                 let mut not_id = OProperty::scalar(
                     "isNotConstantIdentity",
@@ -160,6 +208,11 @@ impl OXform {
                     children.push(ops);
                     children.push(vals);
                     children.push(not_id);
+                    if !anim_chans.is_empty() {
+                        let mut anim = OProperty::array(".animChans", DataType::new(PlainOldDataType::Uint32, 1));
+                        anim.add_array_pod(&anim_chans);
+                        children.push(anim);
+                    }
                 }
             }
 
@@ -173,15 +226,7 @@ impl OXform {
                 bnds_meta.set("interpretation", "box");
                 bnds.meta_data = bnds_meta;
                 for bounds in &self.child_bounds {
-                    let data: [f64; 6] = [
-                        bounds.min.x,
-                        bounds.min.y,
-                        bounds.min.z,
-                        bounds.max.x,
-                        bounds.max.y,
-                        bounds.max.z,
-                    ];
-                    bnds.add_scalar_sample(bytemuck::cast_slice(&data));
+                    bnds.add_scalar_sample(bytemuck::cast_slice(&bounds.to_array()));
                 }
                 if let OPropertyData::Compound(children) = &mut geom.data {
                     children.push(bnds);
@@ -200,10 +245,19 @@ impl OXform {
     }
 }
 
-/// Encode xform operation type to byte.
+/// Indices of the channels whose value changes between samples (`.animChans`).
+fn animated_channels(sample_vals: &[Vec<f64>]) -> Vec<u32> {
+    let Some(first) = sample_vals.first() else { return Vec::new() };
+    (0..first.len())
+        .filter(|&i| sample_vals[1..].iter().any(|vals| vals[i].to_bits() != first[i].to_bits()))
+        .map(|i| i as u32)
+        .collect()
+}
+
+/// Encode xform operation to byte.
 /// Per XformOp.cpp: getOpEncoding() returns (m_type << 4) | (m_hint & 0xF).
-fn encode_xform_op(op_type: XformOpType) -> u8 {
-    let type_code = match op_type {
+fn encode_xform_op(op: &XformOp) -> u8 {
+    let type_code = match op.op_type {
         XformOpType::Scale => 0,
         XformOpType::Translate => 1,
         XformOpType::Rotate => 2,
@@ -212,5 +266,5 @@ fn encode_xform_op(op_type: XformOpType) -> u8 {
         XformOpType::RotateY => 5,
         XformOpType::RotateZ => 6,
     };
-    type_code << 4
+    (type_code << 4) | (op.hint & 0xF)
 }
@@ -3,16 +3,54 @@
 //! Reference: `_ref/alembic/lib/Alembic/AbcCoreOgawa/AwImpl.cpp` (stream writes).
 
 use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
 use std::path::Path;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
 use crate::util::{Error, Result};
 
-/// Output stream for writing Ogawa data.
+/// Where an [`OStream`] writes to.
+enum Sink {
+    File(BufWriter<File>),
+    Memory(Cursor<Vec<u8>>),
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Sink::File(w) => w.write(buf),
+            Sink::Memory(w) => w.write(buf),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        match self {
+            Sink::File(w) => w.write_all(buf),
+            Sink::Memory(w) => w.write_all(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Sink::File(w) => w.flush(),
+            Sink::Memory(_) => Ok(()),
+        }
+    }
+}
+
+impl Seek for Sink {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            Sink::File(w) => w.seek(pos),
+            Sink::Memory(w) => w.seek(pos),
+        }
+    }
+}
+
+/// Output stream for writing Ogawa data, to a file or a memory buffer.
 pub struct OStream {
-    writer: BufWriter<File>,
+    writer: Sink,
     pos: u64,
 }
 
@@ -26,11 +64,29 @@ impl OStream {
             .open(path)?;
 
         Ok(Self {
-            writer: BufWriter::with_capacity(2 * 1024 * 1024, file), // 2MB buffer
+            writer: Sink::File(BufWriter::with_capacity(2 * 1024 * 1024, file)), // 2MB buffer
             pos: 0,
         })
     }
 
+    /// Create an output stream that writes into a growable buffer.
+    pub fn in_memory() -> Self {
+        Self { writer: Sink::Memory(Cursor::new(Vec::new())), pos: 0 }
+    }
+
+    /// Whether this stream writes into memory.
+    pub fn is_in_memory(&self) -> bool {
+        matches!(self.writer, Sink::Memory(_))
+    }
+
+    /// Take the written bytes of an in-memory stream (`None` for files).
+    pub fn take_bytes(&mut self) -> Option<Vec<u8>> {
+        match &mut self.writer {
+            Sink::Memory(cursor) => Some(std::mem::take(cursor.get_mut())),
+            Sink::File(_) => None,
+        }
+    }
+
     /// Get the current write position.
     #[inline]
     pub fn pos(&self) -> u64 {
@@ -96,3 +96,57 @@ fn test_write_xform() -> crate::util::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transaction_commit() -> crate::util::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let (a, b) = (dir.path().join("a.abc"), dir.path().join("b.abc"));
+    std::fs::write(&a, b"old")?;
+
+    let mut tx = OArchiveTransaction::new();
+    tx.create(&a)?.write_archive(&OObject::new(""))?;
+    let mut root = OObject::new("");
+    root.add_child(OObject::new("asset"));
+    tx.create(&b)?.write_archive(&root)?;
+    assert!(tx.create(&a).is_err());
+    assert!(!b.exists());
+
+    assert_eq!(tx.commit()?, vec![a.clone(), b.clone()]);
+    assert!(super::super::IArchive::open(&a)?.is_valid());
+    let reader = crate::abc::IArchive::open(&b)?;
+    assert_eq!(reader.getTop().getNumChildren(), 1);
+    // Only the published files remain (no temps or backups)
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_transaction_rollback() -> crate::util::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let (a, b) = (dir.path().join("a.abc"), dir.path().join("b.abc"));
+    std::fs::write(&a, b"old")?;
+
+    // Dropped without commit: nothing published
+    let mut tx = OArchiveTransaction::new();
+    tx.create(&b)?;
+    drop(tx);
+    assert!(!b.exists());
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+
+    // Failing rename of the second output restores the first target
+    let mut tx = OArchiveTransaction::new();
+    tx.create(&a)?;
+    tx.create(&b)?;
+    let temp = std::fs::read_dir(dir.path())?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?
+        .into_iter()
+        .find(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with(".b.abc.tmp-")))
+        .expect("temp file for b.abc");
+    std::fs::remove_file(temp)?;
+    assert!(tx.commit().is_err());
+    assert_eq!(std::fs::read(&a)?, b"old");
+    assert!(!b.exists());
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! All-or-nothing publishing of several archives.
+//!
+//! [`OArchiveTransaction`] writes every archive to a temp file next to its
+//! target. [`commit`](OArchiveTransaction::commit) finalizes all of them and
+//! only then renames them into place; if anything fails (or the transaction
+//! is dropped uncommitted) the temp files are removed and existing targets
+//! are left untouched:
+//!
+//! ```ignore
+//! let mut tx = OArchiveTransaction::new();
+//! for (name, root) in assets {
+//!     tx.create(format!("publish/{name}.abc"))?.write_archive(&root)?;
+//! }
+//! tx.commit()?; // all files appear, or none do
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use super::archive::{sibling_path as sibling, unique_tag, OArchive};
+use crate::util::{Error, Result};
+
+/// One pending output.
+struct Pending {
+    target: PathBuf,
+    temp: PathBuf,
+    archive: Option<OArchive>,
+}
+
+/// Coordinator that publishes a set of archives atomically.
+#[derive(Default)]
+pub struct OArchiveTransaction {
+    pending: Vec<Pending>,
+}
+
+impl OArchiveTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start writing `path`. The data goes to a hidden temp file in the same
+    /// directory (so the final rename stays on one filesystem).
+    pub fn create(&mut self, path: impl AsRef<Path>) -> Result<&mut OArchive> {
+        let target = path.as_ref().to_path_buf();
+        if self.pending.iter().any(|p| p.target == target) {
+            return Err(Error::invalid(format!("{} is already part of this transaction", target.display())));
+        }
+        let temp = sibling(&target, &unique_tag("tmp"));
+        let archive = OArchive::create_unpublished(&temp)?;
+        self.pending.push(Pending { target, temp, archive: Some(archive) });
+        Ok(self.pending.last_mut().and_then(|p| p.archive.as_mut()).expect("just pushed"))
+    }
+
+    /// Archive at `index` (creation order).
+    pub fn archive_mut(&mut self, index: usize) -> Option<&mut OArchive> {
+        self.pending.get_mut(index).and_then(|p| p.archive.as_mut())
+    }
+
+    /// Target paths in creation order.
+    pub fn targets(&self) -> impl Iterator<Item = &Path> {
+        self.pending.iter().map(|p| p.target.as_path())
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Finalize every archive and move all of them into place.
+    ///
+    /// Archives never written get an empty root, as with [`OArchive::close`].
+    /// Existing targets are moved aside first and restored if any rename
+    /// fails, so on error the previous set of files is intact.
+    pub fn commit(mut self) -> Result<Vec<PathBuf>> {
+        for pending in &mut self.pending {
+            if let Some(archive) = pending.archive.take() {
+                archive.close().map_err(|e| context(&pending.target, e))?;
+            }
+        }
+
+        // Move existing targets aside, then publish; undo everything on failure.
+        let mut backups: Vec<(PathBuf, PathBuf)> = Vec::new();
+        let mut published: Vec<PathBuf> = Vec::new();
+        let result = (|| -> Result<()> {
+            for pending in &self.pending {
+                if pending.target.exists() {
+                    let backup = sibling(&pending.target, &unique_tag("bak"));
+                    std::fs::rename(&pending.target, &backup).map_err(|e| context(&pending.target, e.into()))?;
+                    backups.push((pending.target.clone(), backup));
+                }
+            }
+            for pending in &self.pending {
+                std::fs::rename(&pending.temp, &pending.target).map_err(|e| context(&pending.target, e.into()))?;
+                published.push(pending.target.clone());
+            }
+            Ok(())
+        })();
+
+        if let Err(err) = result {
+            for target in &published {
+                let _ = std::fs::remove_file(target);
+            }
+            for (target, backup) in &backups {
+                let _ = std::fs::rename(backup, target);
+            }
+            return Err(err);
+        }
+        for (_, backup) in &backups {
+            let _ = std::fs::remove_file(backup);
+        }
+        self.pending.clear();
+        Ok(published)
+    }
+
+    /// Discard all outputs (same as dropping the transaction).
+    pub fn abort(self) {}
+}
+
+impl Drop for OArchiveTransaction {
+    fn drop(&mut self) {
+        for pending in self.pending.drain(..) {
+            drop(pending.archive);
+            let _ = std::fs::remove_file(&pending.temp);
+        }
+    }
+}
+
+fn context(target: &Path, err: Error) -> Error {
+    Error::other(format!("{}: {}", target.display(), err))
+}
@@ -144,7 +144,7 @@ pub(crate) fn hash_property_header(
     // For non-compound properties
     if !matches!(prop.data, OPropertyData::Compound(_)) {
         // POD type
-        data.push(pod_to_u8(prop.data_type.pod));
+        data.push(prop.unknown_pod.unwrap_or_else(|| pod_to_u8(prop.data_type.pod)));
         // Extent
         data.push(prop.data_type.extent);
 
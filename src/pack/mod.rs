@@ -0,0 +1,106 @@
+//! Compact compressed copies of archives for internal storage.
+//!
+//! [`pack_archive`] rewrites an archive with block-compressed sample data
+//! (see [`crate::ogawa::compress`]); [`unpack_archive`] turns a packed
+//! archive back into a standard one that any Alembic reader can open. Both
+//! copy objects, properties, metadata, time samplings and sample keys
+//! unchanged and verify the output against the input's fingerprint, so a
+//! pack/unpack round trip gives back the same content.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::ogawa::BlockCodec;
+//! use alembic::pack::{pack_archive, unpack_archive};
+//!
+//! let stats = pack_archive(&IArchive::open("shot.abc")?, "shot.abcz", BlockCodec::Lz4)?;
+//! println!("{} -> {} bytes", stats.input_bytes, stats.output_bytes);
+//!
+//! // Back to a standard archive for DCCs
+//! unpack_archive(&IArchive::open("shot.abcz")?, "shot_restored.abc")?;
+//! ```
+
+use std::path::Path;
+
+use crate::abc::{IArchive, IProperty};
+use crate::copy::{copy_object, num_samples, read_sample, CopyTransform};
+use crate::ogawa::writer::{OArchive, OProperty, OPropertyData};
+use crate::ogawa::BlockCodec;
+use crate::patch::{archive_fingerprint, sample_key};
+use crate::util::{Error, Result};
+
+/// Summary of a [`pack_archive`] / [`unpack_archive`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PackStats {
+    /// Samples copied.
+    pub samples: u64,
+    /// Size of the input file (0 for in-memory archives).
+    pub input_bytes: u64,
+    /// Size of the written file.
+    pub output_bytes: u64,
+}
+
+/// Copy `input` to `output` with sample data compressed by `codec`.
+pub fn pack_archive(input: &IArchive, output: impl AsRef<Path>, codec: BlockCodec) -> Result<PackStats> {
+    copy_archive(input, output.as_ref(), Some(codec))
+}
+
+/// Copy `input` (packed or not) to `output` as a standard archive.
+pub fn unpack_archive(input: &IArchive, output: impl AsRef<Path>) -> Result<PackStats> {
+    copy_archive(input, output.as_ref(), None)
+}
+
+fn copy_archive(input: &IArchive, output: &Path, codec: Option<BlockCodec>) -> Result<PackStats> {
+    let mut out_archive = OArchive::create(output)?;
+    out_archive.set_block_compression(codec);
+    out_archive.set_archive_metadata(input.getArchiveMetaData().clone());
+    out_archive.set_library_version(input.getArchiveVersion());
+    let mut ts_map = vec![0u32];
+    for i in 1..input.getNumTimeSamplings() {
+        let ts = input.getTimeSampling(i).cloned().unwrap_or_default();
+        ts_map.push(out_archive.addTimeSampling(ts));
+    }
+
+    let mut copy = PackCopy { ts_map: &ts_map, samples: 0 };
+    let out_root = copy_object(&input.getTop(), &mut copy)?;
+    out_archive.write_archive(&out_root)?;
+    // Close the file before reading it back
+    drop(out_archive);
+
+    let written = IArchive::open(output)?;
+    if archive_fingerprint(&written)? != archive_fingerprint(input)? {
+        return Err(Error::other(format!("{} does not match {}", output.display(), input.getName())));
+    }
+    Ok(PackStats {
+        samples: copy.samples,
+        input_bytes: std::fs::metadata(input.getName()).map(|m| m.len()).unwrap_or(0),
+        output_bytes: std::fs::metadata(output)?.len(),
+    })
+}
+
+/// Copies samples with their original keys and remaps time samplings.
+struct PackCopy<'a> {
+    ts_map: &'a [u32],
+    samples: u64,
+}
+
+impl CopyTransform for PackCopy<'_> {
+    fn copy_samples(&mut self, prop: &IProperty<'_>, out: &mut OProperty) -> Result<bool> {
+        let header = prop.getHeader();
+        let is_array = matches!(out.data, OPropertyData::Array(_));
+        out.time_sampling_index = self.ts_map.get(header.time_sampling_index as usize).copied().unwrap_or(0);
+        for index in 0..num_samples(prop) {
+            let raw = read_sample(prop, header.data_type, index)
+                .ok_or_else(|| Error::other(format!("failed to read sample {} of {}", index, header.name)))?;
+            let (key, _) = sample_key(prop, index)?;
+            if is_array {
+                out.add_array_sample_with_digest(&raw.data, &raw.dims, key);
+            } else {
+                out.add_scalar_sample_with_digest(&raw.data, key);
+            }
+            self.samples += 1;
+        }
+        Ok(true)
+    }
+}
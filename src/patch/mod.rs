@@ -0,0 +1,866 @@
+//! Binary patches between two versions of an archive.
+//!
+//! [`make_patch`] records how to rebuild a target archive from a base
+//! archive: the full hierarchy (objects, properties, metadata, time
+//! samplings) plus, for every sample, either a reference to a sample with
+//! the same content in the base or the sample data itself. Samples are
+//! matched on their stored content keys, so unchanged, moved and renamed
+//! data is never carried in the patch; only added and changed samples are.
+//! [`apply_patch`] checks that it is given the right base, rebuilds the
+//! target and verifies the result against the target's fingerprint.
+//!
+//! Iterative cache updates can then be shipped to render sites as small
+//! patches instead of full files.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::patch::{apply_patch, make_patch, ArchivePatch};
+//!
+//! // Studio side
+//! let patch = make_patch(&IArchive::open("shot_v1.abc")?, &IArchive::open("shot_v2.abc")?)?;
+//! patch.write("shot_v1_v2.abcpatch")?;
+//!
+//! // Render site, which already has shot_v1.abc
+//! let patch = ArchivePatch::read("shot_v1_v2.abcpatch")?;
+//! apply_patch(&IArchive::open("shot_v1.abc")?, &patch, "shot_v2.abc")?;
+//! ```
+//!
+//! ## Format
+//!
+//! Little-endian throughout. An 8-byte magic (`ABCPATCH`) and a `u32`
+//! version are followed by the base and target fingerprints, the change
+//! summary, archive version and metadata, the time samplings, the table of
+//! referenced base properties, the inline sample blobs and finally the
+//! target hierarchy. Strings are `u32` length + UTF-8, lists are `u32`
+//! count + items.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::abc::{IArchive, ICompoundProperty, IObject, IProperty};
+use crate::core::{MetaData, SampleDigest, TimeSampling, TimeSamplingType};
+use crate::ogawa::writer::{OArchive, OObject, OProperty};
+use crate::copy::{num_samples, read_sample};
+use crate::util::{DataType, Error, PlainOldDataType, Result};
+
+/// Patch file magic.
+const MAGIC: &[u8; 8] = b"ABCPATCH";
+/// Current patch format version.
+const VERSION: u32 = 1;
+
+/// What changed between the base and the target, counted in samples.
+///
+/// Samples are compared by object and property path and sample index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PatchStats {
+    /// Same content at the same path and index.
+    pub unchanged: u64,
+    /// Different content at the same path and index.
+    pub changed: u64,
+    /// Samples with no counterpart in the base (new properties or longer ranges).
+    pub added: u64,
+    /// Base samples with no counterpart in the target.
+    pub removed: u64,
+    /// Target samples rebuilt from base data (unchanged or moved).
+    pub reused: u64,
+    /// Unique sample blobs carried in the patch.
+    pub inline_samples: u64,
+    /// Bytes of sample data carried in the patch.
+    pub inline_bytes: u64,
+}
+
+/// A base property referenced by the patch: object full name and property
+/// path inside the object's top compound (`/`-separated).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SourceProperty {
+    object: String,
+    property: String,
+}
+
+/// Sample data carried in the patch.
+#[derive(Debug, Clone, PartialEq)]
+struct Blob {
+    key: SampleDigest,
+    dims: Vec<usize>,
+    data: Vec<u8>,
+}
+
+/// Where a target sample comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatchSample {
+    /// Sample `index` of base property `source`.
+    Base { source: u32, index: u32 },
+    /// Inline blob.
+    Inline(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PropertyKind {
+    Compound = 0,
+    Scalar = 1,
+    Array = 2,
+}
+
+#[derive(Debug, Clone)]
+struct PatchProperty {
+    name: String,
+    kind: PropertyKind,
+    data_type: DataType,
+    meta_data: MetaData,
+    time_sampling_index: u32,
+    samples: Vec<PatchSample>,
+    children: Vec<PatchProperty>,
+}
+
+#[derive(Debug, Clone)]
+struct PatchObject {
+    name: String,
+    meta_data: MetaData,
+    properties: Vec<PatchProperty>,
+    children: Vec<PatchObject>,
+}
+
+/// Instructions for rebuilding a target archive from a base archive.
+#[derive(Debug, Clone)]
+pub struct ArchivePatch {
+    /// [`archive_fingerprint`] of the base the patch applies to.
+    pub base_fingerprint: SampleDigest,
+    /// [`archive_fingerprint`] of the archive the patch produces.
+    pub target_fingerprint: SampleDigest,
+    /// Change summary recorded by [`make_patch`].
+    pub stats: PatchStats,
+    archive_version: i32,
+    archive_meta_data: MetaData,
+    /// Target time samplings, index 0 (identity) excluded.
+    time_samplings: Vec<TimeSampling>,
+    sources: Vec<SourceProperty>,
+    blobs: Vec<Blob>,
+    root: PatchObject,
+}
+
+/// Content fingerprint of an archive: object names and metadata, property
+/// names, types, metadata, time samplings and the stored key of every
+/// sample. Only keys are read, not sample data, so it is cheap even for
+/// large caches. File layout and archive metadata are not included.
+pub fn archive_fingerprint(archive: &IArchive) -> Result<SampleDigest> {
+    let mut hasher = spooky_hash::SpookyHash::new(0, 0);
+    hash_object(archive, &archive.getTop(), &mut hasher)?;
+    let (h1, h2) = hasher.finalize();
+    Ok(digest_bytes(h1, h2))
+}
+
+fn digest_bytes(h1: u64, h2: u64) -> SampleDigest {
+    let mut digest = [0u8; 16];
+    digest[..8].copy_from_slice(&h1.to_le_bytes());
+    digest[8..].copy_from_slice(&h2.to_le_bytes());
+    digest
+}
+
+fn hash_object(archive: &IArchive, obj: &IObject, hasher: &mut spooky_hash::SpookyHash) -> Result<()> {
+    hasher.update(obj.getFullName().as_bytes());
+    // The top object's metadata is the archive metadata, which is left out
+    if obj.getFullName() != "/" {
+        hasher.update(obj.getMetaData().serialize().as_bytes());
+    }
+    hash_compound(archive, &obj.getProperties(), hasher)?;
+    hasher.update(&(obj.getNumChildren() as u64).to_le_bytes());
+    for child in obj.getChildren() {
+        hash_object(archive, &child, hasher)?;
+    }
+    Ok(())
+}
+
+fn hash_compound(archive: &IArchive, props: &ICompoundProperty<'_>, hasher: &mut spooky_hash::SpookyHash) -> Result<()> {
+    hasher.update(&(props.getNumProperties() as u64).to_le_bytes());
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        // Names, types, metadata and sample keys; compounds recurse
+        hasher.update(&prop.digest()?);
+        if let Some(compound) = prop.asCompound() {
+            hash_compound(archive, &compound, hasher)?;
+        } else {
+            // The property digest leaves out time sampling
+            let index = prop.getHeader().time_sampling_index as usize;
+            let mut buf = Vec::new();
+            if let Some(ts) = archive.getTimeSampling(index) {
+                encode_time_sampling(ts, &mut buf);
+            }
+            hasher.update(&buf);
+        }
+    }
+    Ok(())
+}
+
+/// Base sample lookup key: content key, POD type and array dimensions.
+type ContentKey = (SampleDigest, u8, Vec<usize>);
+
+/// Shared state while building a patch.
+struct PatchBuilder {
+    sources: Vec<SourceProperty>,
+    /// Every base sample by content.
+    base_samples: HashMap<ContentKey, (u32, u32)>,
+    /// Sample keys per base property path, for the change summary.
+    base_keys: HashMap<SourceProperty, Vec<SampleDigest>>,
+    blobs: Vec<Blob>,
+    blob_index: HashMap<ContentKey, u32>,
+    stats: PatchStats,
+}
+
+/// Build the patch that turns `base` into `target`.
+pub fn make_patch(base: &IArchive, target: &IArchive) -> Result<ArchivePatch> {
+    let mut b = PatchBuilder {
+        sources: Vec::new(),
+        base_samples: HashMap::new(),
+        base_keys: HashMap::new(),
+        blobs: Vec::new(),
+        blob_index: HashMap::new(),
+        stats: PatchStats::default(),
+    };
+    b.index_object(&base.getTop())?;
+
+    let root = b.diff_object(&target.getTop())?;
+    // Whatever the target did not match at the same path was removed
+    b.stats.removed += b.base_keys.values().map(|keys| keys.len() as u64).sum::<u64>();
+
+    let time_samplings = (1..target.getNumTimeSamplings())
+        .map(|i| target.getTimeSampling(i).cloned().unwrap_or_default())
+        .collect();
+    Ok(ArchivePatch {
+        base_fingerprint: archive_fingerprint(base)?,
+        target_fingerprint: archive_fingerprint(target)?,
+        stats: b.stats,
+        archive_version: target.getArchiveVersion(),
+        archive_meta_data: target.getArchiveMetaData().clone(),
+        time_samplings,
+        sources: b.sources,
+        blobs: b.blobs,
+        root,
+    })
+}
+
+/// Key and dimensions of sample `index` (dimensions are empty for scalars).
+pub(crate) fn sample_key(prop: &IProperty<'_>, index: usize) -> Result<(SampleDigest, Vec<usize>)> {
+    if let Some(array) = prop.asArray() {
+        Ok((array.getKey(index)?, array.getDimensions(index)?))
+    } else if let Some(scalar) = prop.asScalar() {
+        Ok((scalar.getKey(index)?, Vec::new()))
+    } else {
+        Err(Error::invalid(format!("{} has no samples", prop.getName())))
+    }
+}
+
+/// Join a property path inside an object.
+fn child_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+impl PatchBuilder {
+    fn index_object(&mut self, obj: &IObject) -> Result<()> {
+        self.index_compound(obj.getFullName(), "", &obj.getProperties())?;
+        for child in obj.getChildren() {
+            self.index_object(&child)?;
+        }
+        Ok(())
+    }
+
+    fn index_compound(&mut self, object: &str, path: &str, props: &ICompoundProperty<'_>) -> Result<()> {
+        for i in 0..props.getNumProperties() {
+            let Some(prop) = props.getProperty(i) else { continue };
+            let path = child_path(path, prop.getName());
+            if let Some(compound) = prop.asCompound() {
+                self.index_compound(object, &path, &compound)?;
+                continue;
+            }
+            let source = SourceProperty { object: object.to_string(), property: path };
+            let pod = prop.getHeader().data_type.pod as u8;
+            let mut keys = Vec::new();
+            let mut source_id = None;
+            for index in 0..num_samples(&prop) {
+                let (key, dims) = sample_key(&prop, index)?;
+                keys.push(key);
+                if let std::collections::hash_map::Entry::Vacant(e) = self.base_samples.entry((key, pod, dims)) {
+                    let id = *source_id.get_or_insert_with(|| {
+                        self.sources.push(source.clone());
+                        self.sources.len() as u32 - 1
+                    });
+                    e.insert((id, index as u32));
+                }
+            }
+            self.base_keys.insert(source, keys);
+        }
+        Ok(())
+    }
+
+    fn diff_object(&mut self, obj: &IObject) -> Result<PatchObject> {
+        let properties = self.diff_compound(obj.getFullName(), "", &obj.getProperties())?;
+        let mut children = Vec::new();
+        for child in obj.getChildren() {
+            children.push(self.diff_object(&child)?);
+        }
+        Ok(PatchObject {
+            name: obj.getName().to_string(),
+            meta_data: obj.getMetaData().clone(),
+            properties,
+            children,
+        })
+    }
+
+    fn diff_compound(&mut self, object: &str, path: &str, props: &ICompoundProperty<'_>) -> Result<Vec<PatchProperty>> {
+        let mut out = Vec::new();
+        for i in 0..props.getNumProperties() {
+            let Some(prop) = props.getProperty(i) else { continue };
+            let header = prop.getHeader();
+            let path = child_path(path, &header.name);
+            let mut p = PatchProperty {
+                name: header.name.clone(),
+                kind: PropertyKind::Compound,
+                data_type: header.data_type,
+                meta_data: header.meta_data.clone(),
+                time_sampling_index: header.time_sampling_index,
+                samples: Vec::new(),
+                children: Vec::new(),
+            };
+            if let Some(compound) = prop.asCompound() {
+                p.children = self.diff_compound(object, &path, &compound)?;
+                out.push(p);
+                continue;
+            }
+            p.kind = if prop.asArray().is_some() {
+                PropertyKind::Array
+            } else if prop.asScalar().is_some() {
+                PropertyKind::Scalar
+            } else {
+                continue;
+            };
+
+            let base_keys = self
+                .base_keys
+                .remove(&SourceProperty { object: object.to_string(), property: path })
+                .unwrap_or_default();
+            let pod = header.data_type.pod as u8;
+            for index in 0..num_samples(&prop) {
+                let (key, dims) = sample_key(&prop, index)?;
+                match base_keys.get(index) {
+                    Some(base_key) if *base_key == key => self.stats.unchanged += 1,
+                    Some(_) => self.stats.changed += 1,
+                    None => self.stats.added += 1,
+                }
+                let content = (key, pod, dims);
+                let sample = if let Some(&(source, base_index)) = self.base_samples.get(&content) {
+                    self.stats.reused += 1;
+                    PatchSample::Base { source, index: base_index }
+                } else if let Some(&blob) = self.blob_index.get(&content) {
+                    PatchSample::Inline(blob)
+                } else {
+                    let raw = read_sample(&prop, header.data_type, index).ok_or_else(|| {
+                        Error::other(format!("failed to read sample {} of {}", index, header.name))
+                    })?;
+                    let blob = self.blobs.len() as u32;
+                    self.stats.inline_samples += 1;
+                    self.stats.inline_bytes += raw.data.len() as u64;
+                    self.blobs.push(Blob { key, dims: content.2.clone(), data: raw.data });
+                    self.blob_index.insert(content, blob);
+                    PatchSample::Inline(blob)
+                };
+                p.samples.push(sample);
+            }
+            // Samples beyond the target's range were dropped
+            self.stats.removed += base_keys.len().saturating_sub(p.samples.len()) as u64;
+            out.push(p);
+        }
+        Ok(out)
+    }
+}
+
+/// Rebuild the patch target from `base` into `output`.
+///
+/// Fails without writing if `base` is not the archive the patch was made
+/// against, and after writing if the result does not match the target.
+pub fn apply_patch(base: &IArchive, patch: &ArchivePatch, output: impl AsRef<Path>) -> Result<()> {
+    let output = output.as_ref();
+    if archive_fingerprint(base)? != patch.base_fingerprint {
+        return Err(Error::other(format!("{} is not the base archive of this patch", base.getName())));
+    }
+
+    let mut out_archive = OArchive::create(output)?;
+    out_archive.set_archive_metadata(patch.archive_meta_data.clone());
+    out_archive.set_library_version(patch.archive_version);
+    let mut ts_map = vec![0u32];
+    for ts in &patch.time_samplings {
+        ts_map.push(out_archive.addTimeSampling(ts.clone()));
+    }
+
+    let out_root = build_object(base, patch, &ts_map, &patch.root)?;
+    out_archive.write_archive(&out_root)?;
+    // Close the file before reading it back
+    drop(out_archive);
+
+    let written = IArchive::open(output)?;
+    if archive_fingerprint(&written)? != patch.target_fingerprint {
+        return Err(Error::other(format!("{} does not match the patch target", output.display())));
+    }
+    Ok(())
+}
+
+fn build_object(base: &IArchive, patch: &ArchivePatch, ts_map: &[u32], obj: &PatchObject) -> Result<OObject> {
+    let mut out = OObject::new(&obj.name);
+    out.meta_data = obj.meta_data.clone();
+    for p in &obj.properties {
+        out.properties.push(build_property(base, patch, ts_map, p)?);
+    }
+    for child in &obj.children {
+        out.add_child(build_object(base, patch, ts_map, child)?);
+    }
+    Ok(out)
+}
+
+fn build_property(base: &IArchive, patch: &ArchivePatch, ts_map: &[u32], p: &PatchProperty) -> Result<OProperty> {
+    let mut out = match p.kind {
+        PropertyKind::Compound => {
+            let mut out = OProperty::compound(&p.name);
+            for child in &p.children {
+                out.add_child(build_property(base, patch, ts_map, child)?);
+            }
+            out.meta_data = p.meta_data.clone();
+            return Ok(out);
+        }
+        PropertyKind::Scalar => OProperty::scalar(&p.name, p.data_type),
+        PropertyKind::Array => OProperty::array(&p.name, p.data_type),
+    };
+    out.meta_data = p.meta_data.clone();
+    out.time_sampling_index = ts_map.get(p.time_sampling_index as usize).copied().ok_or_else(|| {
+        Error::invalid(format!("{}: time sampling {} out of range", p.name, p.time_sampling_index))
+    })?;
+
+    let is_array = p.kind == PropertyKind::Array;
+    let mut i = 0;
+    while i < p.samples.len() {
+        match p.samples[i] {
+            PatchSample::Inline(blob) => {
+                let blob = patch.blobs.get(blob as usize).ok_or_else(|| Error::invalid("blob index out of range"))?;
+                push_sample(&mut out, is_array, &blob.data, &blob.dims, blob.key);
+                i += 1;
+            }
+            PatchSample::Base { source, .. } => {
+                // Resolve the base property once for the run of samples it provides
+                let end = p.samples[i..]
+                    .iter()
+                    .position(|s| !matches!(s, PatchSample::Base { source: s2, .. } if *s2 == source))
+                    .map_or(p.samples.len(), |n| i + n);
+                let src = patch.sources.get(source as usize).ok_or_else(|| Error::invalid("source index out of range"))?;
+                with_base_property(base, src, |prop| {
+                    for sample in &p.samples[i..end] {
+                        let PatchSample::Base { index, .. } = *sample else { continue };
+                        let index = index as usize;
+                        let raw = read_sample(prop, prop.getHeader().data_type, index).ok_or_else(|| {
+                            Error::other(format!("failed to read sample {} of {}", index, src.property))
+                        })?;
+                        let (key, _) = sample_key(prop, index)?;
+                        push_sample(&mut out, is_array, &raw.data, &raw.dims, key);
+                    }
+                    Ok(())
+                })?;
+                i = end;
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn push_sample(out: &mut OProperty, is_array: bool, data: &[u8], dims: &[usize], key: SampleDigest) {
+    if is_array {
+        out.add_array_sample_with_digest(data, dims, key);
+    } else {
+        out.add_scalar_sample_with_digest(data, key);
+    }
+}
+
+/// Find a referenced base property and run `f` on it.
+fn with_base_property<T>(
+    base: &IArchive,
+    src: &SourceProperty,
+    f: impl FnOnce(&IProperty<'_>) -> Result<T>,
+) -> Result<T> {
+    let obj = if src.object.trim_matches('/').is_empty() {
+        base.getTop()
+    } else {
+        base.findObject(&src.object).ok_or_else(|| Error::ObjectNotFound(src.object.clone()))?
+    };
+    let names: Vec<&str> = src.property.split('/').collect();
+    find_in_compound(&obj.getProperties(), &names, src, f)
+}
+
+fn find_in_compound<T>(
+    props: &ICompoundProperty<'_>,
+    names: &[&str],
+    src: &SourceProperty,
+    f: impl FnOnce(&IProperty<'_>) -> Result<T>,
+) -> Result<T> {
+    let not_found = || Error::PropertyNotFound(format!("{}:{}", src.object, src.property));
+    let prop = props.getPropertyByName(names[0]).ok_or_else(not_found)?;
+    if names.len() == 1 {
+        return f(&prop);
+    }
+    let compound = prop.asCompound().ok_or_else(not_found)?;
+    find_in_compound(&compound, &names[1..], src, f)
+}
+
+// ============================================================================
+// Serialization
+// ============================================================================
+
+impl ArchivePatch {
+    /// Encode the patch.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        put_u32(&mut buf, VERSION);
+        buf.extend_from_slice(&self.base_fingerprint);
+        buf.extend_from_slice(&self.target_fingerprint);
+        let s = &self.stats;
+        for v in [s.unchanged, s.changed, s.added, s.removed, s.reused, s.inline_samples, s.inline_bytes] {
+            put_u64(&mut buf, v);
+        }
+        buf.extend_from_slice(&self.archive_version.to_le_bytes());
+        put_str(&mut buf, &self.archive_meta_data.serialize());
+
+        put_u32(&mut buf, self.time_samplings.len() as u32);
+        for ts in &self.time_samplings {
+            encode_time_sampling(ts, &mut buf);
+        }
+        put_u32(&mut buf, self.sources.len() as u32);
+        for src in &self.sources {
+            put_str(&mut buf, &src.object);
+            put_str(&mut buf, &src.property);
+        }
+        put_u32(&mut buf, self.blobs.len() as u32);
+        for blob in &self.blobs {
+            buf.extend_from_slice(&blob.key);
+            put_dims(&mut buf, &blob.dims);
+            put_u64(&mut buf, blob.data.len() as u64);
+            buf.extend_from_slice(&blob.data);
+        }
+        encode_object(&self.root, &mut buf);
+        buf
+    }
+
+    /// Decode a patch produced by [`ArchivePatch::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut r = PatchReader { bytes, pos: 0 };
+        if r.take(MAGIC.len())? != MAGIC {
+            return Err(Error::invalid("not an Alembic patch"));
+        }
+        let version = r.u32()?;
+        if version != VERSION {
+            return Err(Error::invalid(format!("unsupported patch version {}", version)));
+        }
+        let base_fingerprint = r.digest()?;
+        let target_fingerprint = r.digest()?;
+        let stats = PatchStats {
+            unchanged: r.u64()?,
+            changed: r.u64()?,
+            added: r.u64()?,
+            removed: r.u64()?,
+            reused: r.u64()?,
+            inline_samples: r.u64()?,
+            inline_bytes: r.u64()?,
+        };
+        let archive_version = r.u32()? as i32;
+        let archive_meta_data = MetaData::parse(&r.string()?);
+
+        let mut time_samplings = Vec::new();
+        for _ in 0..r.u32()? {
+            time_samplings.push(r.time_sampling()?);
+        }
+        let mut sources = Vec::new();
+        for _ in 0..r.u32()? {
+            sources.push(SourceProperty { object: r.string()?, property: r.string()? });
+        }
+        let mut blobs = Vec::new();
+        for _ in 0..r.u32()? {
+            let key = r.digest()?;
+            let dims = r.dims()?;
+            let len = r.len()?;
+            blobs.push(Blob { key, dims, data: r.take(len)?.to_vec() });
+        }
+        let root = r.object()?;
+        if r.pos != bytes.len() {
+            return Err(Error::invalid("trailing data after patch"));
+        }
+        Ok(Self {
+            base_fingerprint,
+            target_fingerprint,
+            stats,
+            archive_version,
+            archive_meta_data,
+            time_samplings,
+            sources,
+            blobs,
+            root,
+        })
+    }
+
+    /// Write the patch to a file.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Read a patch file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+fn put_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    put_u32(buf, s.len() as u32);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn put_dims(buf: &mut Vec<u8>, dims: &[usize]) {
+    put_u32(buf, dims.len() as u32);
+    for &d in dims {
+        put_u64(buf, d as u64);
+    }
+}
+
+/// Tag byte + fields; also used for fingerprints.
+fn encode_time_sampling(ts: &TimeSampling, buf: &mut Vec<u8>) {
+    let put_times = |buf: &mut Vec<u8>, times: &[f64]| {
+        put_u32(buf, times.len() as u32);
+        for t in times {
+            buf.extend_from_slice(&t.to_le_bytes());
+        }
+    };
+    match ts.time_sampling_type() {
+        TimeSamplingType::Identity => buf.push(0),
+        TimeSamplingType::Uniform { time_per_cycle, start_time } => {
+            buf.push(1);
+            buf.extend_from_slice(&time_per_cycle.to_le_bytes());
+            buf.extend_from_slice(&start_time.to_le_bytes());
+        }
+        TimeSamplingType::Cyclic { time_per_cycle, times } => {
+            buf.push(2);
+            buf.extend_from_slice(&time_per_cycle.to_le_bytes());
+            put_times(buf, times);
+        }
+        TimeSamplingType::Acyclic { times } => {
+            buf.push(3);
+            put_times(buf, times);
+        }
+    }
+}
+
+fn encode_object(obj: &PatchObject, buf: &mut Vec<u8>) {
+    put_str(buf, &obj.name);
+    put_str(buf, &obj.meta_data.serialize());
+    put_u32(buf, obj.properties.len() as u32);
+    for p in &obj.properties {
+        encode_property(p, buf);
+    }
+    put_u32(buf, obj.children.len() as u32);
+    for child in &obj.children {
+        encode_object(child, buf);
+    }
+}
+
+fn encode_property(p: &PatchProperty, buf: &mut Vec<u8>) {
+    buf.push(p.kind as u8);
+    put_str(buf, &p.name);
+    put_str(buf, &p.meta_data.serialize());
+    if p.kind == PropertyKind::Compound {
+        put_u32(buf, p.children.len() as u32);
+        for child in &p.children {
+            encode_property(child, buf);
+        }
+        return;
+    }
+    buf.push(p.data_type.pod as u8);
+    buf.push(p.data_type.extent);
+    put_u32(buf, p.time_sampling_index);
+    put_u32(buf, p.samples.len() as u32);
+    for s in &p.samples {
+        match *s {
+            PatchSample::Base { source, index } => {
+                buf.push(0);
+                put_u32(buf, source);
+                put_u32(buf, index);
+            }
+            PatchSample::Inline(blob) => {
+                buf.push(1);
+                put_u32(buf, blob);
+            }
+        }
+    }
+}
+
+/// Bounds-checked cursor over an encoded patch.
+struct PatchReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PatchReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or(Error::UnexpectedEof(self.pos as u64))?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap_or_default()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap_or_default()))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap_or_default()))
+    }
+
+    /// A length that must fit in the remaining input.
+    fn len(&mut self) -> Result<usize> {
+        let len = self.u64()?;
+        if len > (self.bytes.len() - self.pos) as u64 {
+            return Err(Error::UnexpectedEof(self.pos as u64));
+        }
+        Ok(len as usize)
+    }
+
+    fn digest(&mut self) -> Result<SampleDigest> {
+        Ok(self.take(16)?.try_into().unwrap_or_default())
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        Ok(String::from_utf8(self.take(len)?.to_vec())?)
+    }
+
+    fn dims(&mut self) -> Result<Vec<usize>> {
+        let n = self.u32()?;
+        (0..n).map(|_| self.u64().map(|d| d as usize)).collect()
+    }
+
+    fn times(&mut self) -> Result<Vec<f64>> {
+        let n = self.u32()?;
+        (0..n).map(|_| self.f64()).collect()
+    }
+
+    fn time_sampling(&mut self) -> Result<TimeSampling> {
+        Ok(match self.u8()? {
+            0 => TimeSampling::identity(),
+            1 => {
+                let time_per_cycle = self.f64()?;
+                TimeSampling::uniform(time_per_cycle, self.f64()?)
+            }
+            2 => {
+                let time_per_cycle = self.f64()?;
+                TimeSampling::cyclic(time_per_cycle, self.times()?)
+            }
+            3 => TimeSampling::acyclic(self.times()?),
+            tag => return Err(Error::invalid(format!("unknown time sampling type {}", tag))),
+        })
+    }
+
+    fn object(&mut self) -> Result<PatchObject> {
+        let name = self.string()?;
+        let meta_data = MetaData::parse(&self.string()?);
+        let mut properties = Vec::new();
+        for _ in 0..self.u32()? {
+            properties.push(self.property()?);
+        }
+        let mut children = Vec::new();
+        for _ in 0..self.u32()? {
+            children.push(self.object()?);
+        }
+        Ok(PatchObject { name, meta_data, properties, children })
+    }
+
+    fn property(&mut self) -> Result<PatchProperty> {
+        let kind = match self.u8()? {
+            0 => PropertyKind::Compound,
+            1 => PropertyKind::Scalar,
+            2 => PropertyKind::Array,
+            tag => return Err(Error::invalid(format!("unknown property kind {}", tag))),
+        };
+        let mut p = PatchProperty {
+            name: self.string()?,
+            kind,
+            data_type: DataType::new(PlainOldDataType::Unknown, 0),
+            meta_data: MetaData::parse(&self.string()?),
+            time_sampling_index: 0,
+            samples: Vec::new(),
+            children: Vec::new(),
+        };
+        if kind == PropertyKind::Compound {
+            for _ in 0..self.u32()? {
+                p.children.push(self.property()?);
+            }
+            return Ok(p);
+        }
+        let pod = PlainOldDataType::from_u8(self.u8()?);
+        p.data_type = DataType::new(pod, self.u8()?);
+        p.time_sampling_index = self.u32()?;
+        for _ in 0..self.u32()? {
+            let sample = match self.u8()? {
+                0 => PatchSample::Base { source: self.u32()?, index: self.u32()? },
+                1 => PatchSample::Inline(self.u32()?),
+                tag => return Err(Error::invalid(format!("unknown sample source {}", tag))),
+            };
+            p.samples.push(sample);
+        }
+        Ok(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_sampling_roundtrip() {
+        for ts in [
+            TimeSampling::identity(),
+            TimeSampling::uniform(1.0 / 24.0, 1001.0 / 24.0),
+            TimeSampling::cyclic(1.0 / 24.0, vec![0.0, 0.25 / 24.0]),
+            TimeSampling::acyclic(vec![0.0, 0.5, 2.0]),
+        ] {
+            let mut buf = Vec::new();
+            encode_time_sampling(&ts, &mut buf);
+            let mut r = PatchReader { bytes: &buf, pos: 0 };
+            let decoded = r.time_sampling().unwrap();
+            assert_eq!(decoded.time_sampling_type(), ts.time_sampling_type());
+            assert_eq!(r.pos, buf.len());
+        }
+    }
+
+    #[test]
+    fn test_truncated_patch_is_an_error() {
+        assert!(ArchivePatch::from_bytes(b"ABCPA").is_err());
+        assert!(ArchivePatch::from_bytes(b"NOTPATCH\x01\x00\x00\x00").is_err());
+        let mut bytes = MAGIC.to_vec();
+        put_u32(&mut bytes, VERSION);
+        assert!(matches!(ArchivePatch::from_bytes(&bytes), Err(Error::UnexpectedEof(_))));
+    }
+}
@@ -0,0 +1,355 @@
+//! Hierarchy queries: glob patterns over object paths.
+//!
+//! A [`Query`] selects objects by path, segment by segment:
+//!
+//! | Pattern | Matches |
+//! |---|---|
+//! | `name` | an object called exactly `name` |
+//! | `*`, `?` | any run of characters / any single character within a name |
+//! | `{a,b}` | either alternative (`char{A,B}_geo`) |
+//! | `**` | zero or more levels of hierarchy |
+//! | `[Type]` | (last segment only) objects of that schema, e.g. `[PolyMesh]`, `[Xform\|Camera]` |
+//!
+//! Types are compared against the schema without its prefix and version
+//! (`AbcGeom_PolyMesh_v1` is `PolyMesh`) or against the full schema string,
+//! ignoring case; objects without a schema are `Group`. A segment that is
+//! only `**[Type]` means `**/*[Type]`. Patterns without a leading `/` match
+//! at any depth (`bodyShape` is `/**/bodyShape`), and `\` escapes the next
+//! character.
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::query::Query;
+//!
+//! let archive = IArchive::open("shot.abc")?;
+//! let meshes = Query::parse("/root/char*/geo/**[PolyMesh]")?.select(&archive);
+//! for mesh in &meshes {
+//!     println!("{}", mesh.getFullName());
+//! }
+//! ```
+
+use crate::abc::{IArchive, IObject};
+use crate::util::{Error, Result};
+
+/// One element of a glob: a literal character or a wildcard.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Char(char),
+    /// `?`
+    One,
+    /// `*`
+    Any,
+    /// `{a,b}`: each alternative is a glob of its own
+    Alt(Vec<Vec<Token>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// `**`
+    AnyDepth,
+    Name(Vec<Token>),
+}
+
+/// A parsed hierarchy pattern. See the [module docs](self) for the syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    segments: Vec<Segment>,
+    /// Type filter of the last segment (any of these); empty = any type
+    types: Vec<String>,
+}
+
+impl Query {
+    /// Parse a pattern.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let err = |msg: &str| Error::other(format!("invalid query '{}': {}", pattern, msg));
+        let mut text = pattern.trim();
+        let mut segments = Vec::new();
+        if !text.starts_with('/') {
+            segments.push(Segment::AnyDepth);
+        }
+
+        // `[Type]` at the very end filters the last segment
+        let mut types = Vec::new();
+        if text.ends_with(']') && !text.ends_with("\\]") {
+            let open = text.rfind('[').ok_or_else(|| err("unmatched ']'"))?;
+            types = text[open + 1..text.len() - 1]
+                .split('|')
+                .map(|t| t.trim().to_ascii_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect();
+            if types.is_empty() {
+                return Err(err("empty type filter"));
+            }
+            text = &text[..open];
+        }
+
+        let parts = split_unescaped(text, '/');
+        let count = parts.iter().filter(|p| !p.is_empty()).count();
+        for part in parts.iter().filter(|p| !p.is_empty()) {
+            if *part == "**" {
+                if segments.last() != Some(&Segment::AnyDepth) {
+                    segments.push(Segment::AnyDepth);
+                }
+            } else if part.contains("**") {
+                return Err(err("'**' must be a whole segment"));
+            } else {
+                segments.push(Segment::Name(parse_glob(part).map_err(|m| err(&m))?));
+            }
+        }
+        if count == 0 && types.is_empty() {
+            return Err(err("empty pattern"));
+        }
+        // `a/**[Type]` and a bare `[Type]` select objects of that type below
+        if !types.is_empty() && !matches!(segments.last(), Some(Segment::Name(_))) {
+            if segments.last() != Some(&Segment::AnyDepth) {
+                segments.push(Segment::AnyDepth);
+            }
+            segments.push(Segment::Name(vec![Token::Any]));
+        }
+        Ok(Self { segments, types })
+    }
+
+    /// Whether the object at `path` with `schema` ("" for none) matches.
+    pub fn matches_path(&self, path: &str, schema: &str) -> bool {
+        let mut states = self.closure(vec![0]);
+        for name in path.split('/').filter(|p| !p.is_empty()) {
+            states = self.step(&states, name);
+            if states.is_empty() {
+                return false;
+            }
+        }
+        self.accepts(&states, schema)
+    }
+
+    /// Whether `obj` matches.
+    pub fn matches(&self, obj: &IObject) -> bool {
+        self.matches_path(obj.getFullName(), obj.getMetaData().get("schema").unwrap_or_default())
+    }
+
+    /// Paths of the matching objects below `root`, in hierarchy order.
+    ///
+    /// Subtrees that cannot match are not visited.
+    pub fn select_paths(&self, root: &IObject) -> Vec<String> {
+        let mut out = Vec::new();
+        self.walk(root, &self.closure(vec![0]), &mut out);
+        out
+    }
+
+    /// The matching objects of `archive`, in hierarchy order.
+    pub fn select<'a>(&self, archive: &'a IArchive) -> Vec<IObject<'a>> {
+        self.select_paths(&archive.getTop())
+            .iter()
+            .filter_map(|path| archive.findObject(path))
+            .collect()
+    }
+
+    fn walk(&self, obj: &IObject, states: &[usize], out: &mut Vec<String>) {
+        for child in obj.getChildren() {
+            let next = self.step(states, child.getName());
+            if next.is_empty() {
+                continue;
+            }
+            if self.accepts(&next, child.getMetaData().get("schema").unwrap_or_default()) {
+                out.push(child.getFullName().to_string());
+            }
+            self.walk(&child, &next, out);
+        }
+    }
+
+    /// Add the states reachable by letting a `**` match zero levels.
+    fn closure(&self, mut states: Vec<usize>) -> Vec<usize> {
+        let mut i = 0;
+        while i < states.len() {
+            let s = states[i];
+            if self.segments.get(s) == Some(&Segment::AnyDepth) && !states.contains(&(s + 1)) {
+                states.push(s + 1);
+            }
+            i += 1;
+        }
+        states
+    }
+
+    /// States after descending into a child called `name`.
+    fn step(&self, states: &[usize], name: &str) -> Vec<usize> {
+        let mut next = Vec::new();
+        for &s in states {
+            let to = match self.segments.get(s) {
+                Some(Segment::AnyDepth) => s,
+                Some(Segment::Name(glob)) if glob_matches(glob, name) => s + 1,
+                _ => continue,
+            };
+            if !next.contains(&to) {
+                next.push(to);
+            }
+        }
+        self.closure(next)
+    }
+
+    fn accepts(&self, states: &[usize], schema: &str) -> bool {
+        states.contains(&self.segments.len())
+            && (self.types.is_empty() || self.types.iter().any(|t| type_matches(t, schema)))
+    }
+}
+
+/// `AbcGeom_PolyMesh_v1` -> `PolyMesh`; "" -> `Group`.
+pub fn schema_type(schema: &str) -> &str {
+    if schema.is_empty() {
+        return "Group";
+    }
+    let base = match schema.rsplit_once("_v") {
+        Some((base, version)) if !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => schema,
+    };
+    base.split_once('_').map_or(base, |(_, name)| name)
+}
+
+fn type_matches(wanted: &str, schema: &str) -> bool {
+    schema_type(schema).eq_ignore_ascii_case(wanted) || schema.eq_ignore_ascii_case(wanted)
+}
+
+/// Split on `sep` outside `{}` and not escaped.
+fn split_unescaped(text: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut depth, mut escaped) = (0, 0usize, false);
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ if c == sep && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn parse_glob(text: &str) -> core::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => tokens.push(Token::Char(chars.next().ok_or("trailing '\\'")?.1)),
+            '*' => {
+                if tokens.last() != Some(&Token::Any) {
+                    tokens.push(Token::Any);
+                }
+            }
+            '?' => tokens.push(Token::One),
+            '{' => {
+                // Find the matching brace
+                let mut depth = 1;
+                let mut end = None;
+                let mut escaped = false;
+                for (j, d) in text[i + 1..].char_indices() {
+                    match d {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                end = Some(i + 1 + j);
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                let end = end.ok_or("unmatched '{'")?;
+                let alts = split_unescaped(&text[i + 1..end], ',')
+                    .into_iter()
+                    .map(parse_glob)
+                    .collect::<core::result::Result<_, _>>()?;
+                tokens.push(Token::Alt(alts));
+                // Skip past the closing brace
+                while chars.next().is_some_and(|(j, _)| j < end) {}
+            }
+            '}' => return Err("unmatched '}'".to_string()),
+            '[' | ']' => return Err("type filters ('[Type]') are only allowed at the end".to_string()),
+            _ => tokens.push(Token::Char(c)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn glob_matches(tokens: &[Token], name: &str) -> bool {
+    let Some((first, rest)) = tokens.split_first() else {
+        return name.is_empty();
+    };
+    match first {
+        Token::Char(c) => name.strip_prefix(*c).is_some_and(|tail| glob_matches(rest, tail)),
+        Token::One => {
+            let mut chars = name.chars();
+            chars.next().is_some() && glob_matches(rest, chars.as_str())
+        }
+        Token::Any => name
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(name.len()))
+            .any(|i| glob_matches(rest, &name[i..])),
+        Token::Alt(alts) => alts.iter().any(|alt| {
+            let joined: Vec<Token> = alt.iter().chain(rest).cloned().collect();
+            glob_matches(&joined, name)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(pattern: &str, path: &str, schema: &str) -> bool {
+        Query::parse(pattern).unwrap().matches_path(path, schema)
+    }
+
+    #[test]
+    fn test_globs() {
+        assert!(m("/root/char*/geo", "/root/charA/geo", ""));
+        assert!(!m("/root/char*/geo", "/root/charA/geo/body", ""));
+        assert!(!m("/root/char*/geo", "/root/prop/geo", ""));
+        assert!(m("/root/char?_geo", "/root/charB_geo", ""));
+        assert!(m("/root/{charA,prop*}", "/root/propTable", ""));
+        assert!(!m("/root/{charA,prop*}", "/root/charB", ""));
+        assert!(m("/a\\*b", "/a*b", ""));
+        assert!(!m("/a\\*b", "/axb", ""));
+    }
+
+    #[test]
+    fn test_any_depth_and_relative() {
+        assert!(m("/root/**/body", "/root/body", ""));
+        assert!(m("/root/**/body", "/root/a/b/body", ""));
+        assert!(m("/root/**", "/root/a/b", ""));
+        assert!(m("bodyShape", "/char/geo/bodyShape", ""));
+        assert!(m("geo/*Shape", "/char/geo/bodyShape", ""));
+        assert!(!m("geo/*Shape", "/char/geo/x/bodyShape", ""));
+    }
+
+    #[test]
+    fn test_type_filter() {
+        let q = "/root/char*/geo/**[PolyMesh]";
+        assert!(m(q, "/root/charA/geo/body", "AbcGeom_PolyMesh_v1"));
+        assert!(m(q, "/root/charA/geo/a/b/body", "AbcGeom_PolyMesh_v1"));
+        assert!(!m(q, "/root/charA/geo/body", "AbcGeom_Xform_v3"));
+        assert!(!m(q, "/root/charA/geo", "AbcGeom_PolyMesh_v1"));
+        assert!(m("*Shape[polymesh|subd]", "/x/bodyShape", "AbcGeom_SubD_v1"));
+        assert!(m("[Camera]", "/cams/main", "AbcGeom_Camera_v1"));
+        assert!(m("/*[Group]", "/set", ""));
+        assert!(m("**[acme_Crowd_v1]", "/crowd", "acme_Crowd_v1"));
+        assert_eq!(schema_type("AbcGeom_PolyMesh_v1"), "PolyMesh");
+        assert_eq!(schema_type("AbcMaterial_Material_v1"), "Material");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("/a/{b").is_err());
+        assert!(Query::parse("/a[Xform]/b").is_err());
+        assert!(Query::parse("/a/b**").is_err());
+        assert!(Query::parse("/a/[]").is_err());
+    }
+}
@@ -0,0 +1,315 @@
+//! Retiming archives onto a new uniform frame rate.
+//!
+//! [`resample_archive`] copies an archive and rewrites every animated
+//! property (any schema, any depth) onto a single uniform time sampling at
+//! the requested fps, optionally trimmed to a frame range, similar to
+//! `abcconvert`. New samples are interpolated linearly for float data
+//! (positions, normals, xform op values, bounds, ...) when the bracketing
+//! source samples have matching shapes; topology changes and non-float data
+//! fall back to the nearest sample. Constant properties are written once with
+//! the default time sampling.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::abc::IArchive;
+//! use alembic::resample::{resample_archive, ResampleOptions};
+//!
+//! // 24 -> 30 fps, keeping source frames 1001-1100
+//! let archive = IArchive::open("shot_24.abc")?;
+//! let options = ResampleOptions { frames: Some(1001..=1100), ..ResampleOptions::new(30.0) };
+//! let stats = resample_archive(&archive, "shot_30.abc", &options)?;
+//! println!("{} frames, {} animated properties", stats.frames, stats.resampled);
+//! ```
+
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::abc::{IArchive, ICompoundProperty, IObject, IProperty};
+use crate::copy::{copy_object, num_samples, read_sample, CopyTransform, RawSample};
+use crate::core::TimeSampling;
+use crate::ogawa::writer::{OArchive, OProperty};
+use crate::util::{Chrono, DataType, Error, PlainOldDataType, Result};
+
+/// Archive metadata key holding the DCC frame rate.
+const DCC_FPS_KEY: &str = "_ai_DCC_FPS";
+
+/// How new samples are derived from the source samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleMode {
+    /// Hold the source sample nearest in time.
+    Nearest,
+    /// Interpolate float data between the bracketing source samples.
+    #[default]
+    Linear,
+}
+
+impl ResampleMode {
+    /// Parse a mode name ("nearest", "linear" and short aliases).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "nearest" | "near" => Some(Self::Nearest),
+            "linear" | "lerp" | "interp" => Some(Self::Linear),
+            _ => None,
+        }
+    }
+}
+
+/// Options for [`resample_archive`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResampleOptions {
+    /// Output frame rate.
+    pub fps: f64,
+    /// Source scene frames to keep (mapped with [`IArchive::frame_mapping`]);
+    /// `None` keeps the whole animated range.
+    pub frames: Option<RangeInclusive<i64>>,
+    pub mode: ResampleMode,
+}
+
+impl ResampleOptions {
+    /// Linear resampling of the whole animated range at `fps`.
+    pub fn new(fps: f64) -> Self {
+        Self { fps, frames: None, mode: ResampleMode::default() }
+    }
+}
+
+/// Summary of a [`resample_archive`] run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResampleStats {
+    /// Output frames written for each animated property.
+    pub frames: usize,
+    /// First and last output sample time; `None` for static archives.
+    pub time_range: Option<(Chrono, Chrono)>,
+    /// Properties written on the new time sampling.
+    pub resampled: usize,
+    /// Constant properties copied as-is.
+    pub constant: usize,
+}
+
+/// Copy `input` to `output`, re-sampling all animated properties at
+/// `options.fps` over the animated range (or `options.frames`).
+///
+/// Archive metadata is kept; a stored DCC fps is updated to the new rate so
+/// frame numbers stay consistent when the output is read back.
+pub fn resample_archive(input: &IArchive, output: impl AsRef<Path>, options: &ResampleOptions) -> Result<ResampleStats> {
+    if !(options.fps.is_finite() && options.fps > 0.0) {
+        return Err(Error::other(format!("frame rate must be positive: {}", options.fps)));
+    }
+    let range = match &options.frames {
+        Some(frames) if frames.is_empty() => {
+            return Err(Error::other(format!("empty frame range {:?}", frames)));
+        }
+        Some(frames) => {
+            let mapping = input.frame_mapping();
+            Some((mapping.frame_to_time(*frames.start() as f64), mapping.frame_to_time(*frames.end() as f64)))
+        }
+        None => {
+            let mut range = None;
+            animated_range(input, &input.getTop(), &mut range);
+            range
+        }
+    };
+    let times = range.map(|(start, end)| frame_times(start, end, options.fps)).unwrap_or_default();
+
+    let mut out_archive = OArchive::create(output)?;
+    let mut meta = input.getArchiveMetaData().clone();
+    if meta.get(DCC_FPS_KEY).is_some() {
+        meta.set(DCC_FPS_KEY, options.fps.to_string());
+    }
+    out_archive.set_archive_metadata(meta);
+    out_archive.set_library_version(input.getArchiveVersion());
+    let ts_index = match times.first() {
+        Some(&start) => out_archive.addTimeSampling(TimeSampling::uniform(1.0 / options.fps, start)),
+        None => 0,
+    };
+
+    let mut r = Resampler { archive: input, mode: options.mode, times, ts_index, resampled: 0, constant: 0 };
+    let out_root = copy_object(&input.getTop(), &mut r)?;
+    out_archive.write_archive(&out_root)?;
+
+    Ok(ResampleStats {
+        frames: r.times.len(),
+        time_range: r.times.first().zip(r.times.last()).map(|(a, b)| (*a, *b)),
+        resampled: r.resampled,
+        constant: r.constant,
+    })
+}
+
+/// Shared state for one resampling copy.
+struct Resampler<'a> {
+    archive: &'a IArchive,
+    mode: ResampleMode,
+    /// Output frame times.
+    times: Vec<Chrono>,
+    /// Output time sampling index for animated properties.
+    ts_index: u32,
+    resampled: usize,
+    constant: usize,
+}
+
+/// Union of the time ranges of all properties with more than one sample.
+fn animated_range(archive: &IArchive, obj: &IObject, range: &mut Option<(Chrono, Chrono)>) {
+    compound_range(archive, &obj.getProperties(), range);
+    for child in obj.getChildren() {
+        animated_range(archive, &child, range);
+    }
+}
+
+fn compound_range(archive: &IArchive, props: &ICompoundProperty<'_>, range: &mut Option<(Chrono, Chrono)>) {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        if let Some(compound) = prop.asCompound() {
+            compound_range(archive, &compound, range);
+            continue;
+        }
+        let n = num_samples(&prop);
+        if n < 2 {
+            continue;
+        }
+        let Some(ts) = archive.getTimeSampling(prop.getHeader().time_sampling_index as usize) else { continue };
+        let (lo, hi) = ts.time_range(n);
+        *range = Some(match *range {
+            Some((a, b)) => (a.min(lo), b.max(hi)),
+            None => (lo, hi),
+        });
+    }
+}
+
+/// Frame times covering `start..=end`, snapped to whole frames at `fps`.
+fn frame_times(start: Chrono, end: Chrono, fps: f64) -> Vec<Chrono> {
+    let first = (start * fps - 1e-6).ceil() as i64;
+    let last = (end * fps + 1e-6).floor() as i64;
+    // Range shorter than one frame: keep a single sample at the start.
+    if last < first {
+        return vec![start];
+    }
+    (first..=last).map(|f| f as f64 / fps).collect()
+}
+
+impl CopyTransform for Resampler<'_> {
+    fn copy_samples(&mut self, prop: &IProperty<'_>, out: &mut OProperty) -> Result<bool> {
+        let header = prop.getHeader();
+        let data_type = header.data_type;
+        let n = num_samples(prop);
+        let ts = self.archive.getTimeSampling(header.time_sampling_index as usize);
+        let (Some(ts), true, false) = (ts, n > 1, self.times.is_empty()) else {
+            // Constant (or unreadable) property: copy as-is on the default sampling
+            self.constant += 1;
+            out.time_sampling_index = 0;
+            for i in 0..n {
+                if let Some(s) = read_sample(prop, data_type, i) {
+                    s.push_to(out);
+                }
+            }
+            return Ok(true);
+        };
+
+        self.resampled += 1;
+        out.time_sampling_index = self.ts_index;
+        let lerp_type = matches!(data_type.pod, PlainOldDataType::Float32 | PlainOldDataType::Float64);
+        // Cache the last bracketing pair; consecutive frames usually reuse it
+        let mut cache: Vec<(usize, RawSample)> = Vec::new();
+        for &t in &self.times {
+            let (floor, ceil, alpha) = ts.get_bracketing_time_samples(t, n);
+            let nearest = if alpha < 0.5 { floor } else { ceil };
+            let lerp_wanted = self.mode == ResampleMode::Linear
+                && lerp_type
+                && floor != ceil
+                && alpha > 1e-9
+                && alpha < 1.0 - 1e-9;
+
+            let sample = if lerp_wanted {
+                let a = cached_sample(&mut cache, prop, data_type, floor);
+                let b = cached_sample(&mut cache, prop, data_type, ceil);
+                match (a, b) {
+                    (Some(a), Some(b)) if a.dims == b.dims && a.data.len() == b.data.len() => {
+                        Some(lerp_sample(&a, &b, data_type.pod, alpha))
+                    }
+                    // Shape changed between samples: hold the nearest one
+                    (a, b) => if alpha < 0.5 { a } else { b },
+                }
+            } else {
+                cached_sample(&mut cache, prop, data_type, nearest)
+            };
+            if let Some(s) = sample {
+                s.push_to(out);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Read sample `index`, keeping the two most recently used samples around.
+fn cached_sample(
+    cache: &mut Vec<(usize, RawSample)>,
+    prop: &IProperty<'_>,
+    data_type: DataType,
+    index: usize,
+) -> Option<RawSample> {
+    if let Some((_, s)) = cache.iter().find(|(i, _)| *i == index) {
+        return Some(s.clone());
+    }
+    let s = read_sample(prop, data_type, index)?;
+    if cache.len() >= 2 {
+        cache.remove(0);
+    }
+    cache.push((index, s.clone()));
+    Some(s)
+}
+
+/// Component-wise linear interpolation of two float samples of equal size.
+fn lerp_sample(a: &RawSample, b: &RawSample, pod: PlainOldDataType, alpha: f64) -> RawSample {
+    let data = match pod {
+        PlainOldDataType::Float64 => a.data.chunks_exact(8).zip(b.data.chunks_exact(8))
+            .flat_map(|(x, y)| {
+                let x = f64::from_le_bytes(x.try_into().unwrap_or_default());
+                let y = f64::from_le_bytes(y.try_into().unwrap_or_default());
+                (x + (y - x) * alpha).to_le_bytes()
+            })
+            .collect(),
+        _ => a.data.chunks_exact(4).zip(b.data.chunks_exact(4))
+            .flat_map(|(x, y)| {
+                let x = f32::from_le_bytes(x.try_into().unwrap_or_default());
+                let y = f32::from_le_bytes(y.try_into().unwrap_or_default());
+                (x + (y - x) * alpha as f32).to_le_bytes()
+            })
+            .collect(),
+    };
+    RawSample { data, dims: a.dims.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_times_snap_to_output_rate() {
+        // 24 fps frames 1..=3 at 30 fps: 1/24..3/24 s covers 30 fps frames 2..=3
+        let times = frame_times(1.0 / 24.0, 3.0 / 24.0, 30.0);
+        assert_eq!(times, vec![2.0 / 30.0, 3.0 / 30.0]);
+        // Exact frame boundaries are kept despite rounding noise
+        assert_eq!(frame_times(0.1 - 1e-12, 0.2 + 1e-12, 10.0).len(), 2);
+        // Shorter than a frame: single sample at the start
+        assert_eq!(frame_times(0.01, 0.02, 24.0), vec![0.01]);
+    }
+
+    #[test]
+    fn test_lerp_sample() {
+        let f32s = |v: &[f32]| RawSample { data: bytemuck::cast_slice(v).to_vec(), dims: vec![v.len()] };
+        let mid = lerp_sample(&f32s(&[0.0, 2.0]), &f32s(&[1.0, 4.0]), PlainOldDataType::Float32, 0.25);
+        assert_eq!(bytemuck::cast_slice::<u8, f32>(&mid.data), &[0.25, 2.5]);
+        assert_eq!(mid.dims, vec![2]);
+
+        let a = RawSample { data: 1.0f64.to_le_bytes().to_vec(), dims: Vec::new() };
+        let b = RawSample { data: 3.0f64.to_le_bytes().to_vec(), dims: Vec::new() };
+        let mid = lerp_sample(&a, &b, PlainOldDataType::Float64, 0.5);
+        assert_eq!(f64::from_le_bytes(mid.data.try_into().unwrap()), 2.0);
+    }
+
+    #[test]
+    fn test_resample_mode_parse() {
+        assert_eq!(ResampleMode::parse("lerp"), Some(ResampleMode::Linear));
+        assert_eq!(ResampleMode::parse("nearest"), Some(ResampleMode::Nearest));
+        assert_eq!(ResampleMode::parse("cubic"), None);
+    }
+}
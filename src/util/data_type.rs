@@ -1,7 +1,7 @@
 //! DataType - combines POD type with extent (dimensionality).
 
 use super::PlainOldDataType;
-use std::fmt;
+use core::fmt;
 
 /// DataType describes how an element of a sample is stored.
 ///
@@ -129,15 +129,15 @@ impl fmt::Display for DataType {
 }
 
 impl PartialOrd for DataType {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for DataType {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         match self.pod.cmp(&other.pod) {
-            std::cmp::Ordering::Equal => self.extent.cmp(&other.extent),
+            core::cmp::Ordering::Equal => self.extent.cmp(&other.extent),
             ord => ord,
         }
     }
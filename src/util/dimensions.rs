@@ -2,6 +2,7 @@
 //!
 //! Dimensions describe the shape of multi-dimensional array data.
 
+use alloc::vec::Vec;
 use smallvec::SmallVec;
 
 /// Dimensions of a multi-dimensional array.
@@ -121,8 +122,8 @@ impl From<Vec<usize>> for Dimensions {
     }
 }
 
-impl std::fmt::Display for Dimensions {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Dimensions {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if self.dims.is_empty() {
             write!(f, "[]")
         } else {
@@ -1,5 +1,7 @@
 //! Error types for the Alembic library.
 
+use alloc::string::{FromUtf8Error, String};
+#[cfg(feature = "std")]
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -7,6 +9,7 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum Error {
     /// File does not exist or cannot be accessed
+    #[cfg(feature = "std")]
     #[error("File not found: {0}")]
     FileNotFound(PathBuf),
 
@@ -14,6 +17,10 @@ pub enum Error {
     #[error("Invalid Alembic file: expected Ogawa magic bytes")]
     InvalidMagic,
 
+    /// Legacy HDF5-layout archive (Alembic 1.0), read only with the `hdf5` feature
+    #[error("Legacy HDF5 Alembic archive; enable the `hdf5` feature or convert it to Ogawa (abcconvert -toOgawa)")]
+    Hdf5Archive,
+
     /// Unsupported file format version
     #[error("Unsupported Alembic version: {0}")]
     UnsupportedVersion(u16),
@@ -26,6 +33,11 @@ pub enum Error {
     #[error("Invalid file structure: {0}")]
     InvalidStructure(String),
 
+    /// Malformed archive data at a known file offset (bad child counts,
+    /// offsets or sizes, undecodable headers)
+    #[error("Corrupt archive at offset {offset}: {context}")]
+    Corrupt { offset: u64, context: String },
+
     /// Property not found by name
     #[error("Property not found: {0}")]
     PropertyNotFound(String),
@@ -71,12 +83,13 @@ pub enum Error {
     MmapFailed(String),
 
     /// I/O error
+    #[cfg(feature = "std")]
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
     /// UTF-8 conversion error
     #[error("Invalid UTF-8: {0}")]
-    Utf8(#[from] std::string::FromUtf8Error),
+    Utf8(#[from] FromUtf8Error),
 
     /// Generic error with message
     #[error("{0}")]
@@ -93,10 +106,24 @@ impl Error {
     pub fn invalid(msg: impl Into<String>) -> Self {
         Self::InvalidStructure(msg.into())
     }
+
+    /// Create a corrupt data error at a file offset.
+    pub fn corrupt(offset: u64, context: impl Into<String>) -> Self {
+        Self::Corrupt { offset, context: context.into() }
+    }
+
+    /// Attach a file offset to an invalid structure error, turning it into
+    /// [`Error::Corrupt`]. Other errors are returned unchanged.
+    pub fn at_offset(self, offset: u64) -> Self {
+        match self {
+            Self::InvalidStructure(context) => Self::Corrupt { offset, context },
+            other => other,
+        }
+    }
 }
 
 /// Result type alias for Alembic operations.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
 #[cfg(test)]
 mod tests {
@@ -110,8 +137,14 @@ mod tests {
         let e = Error::SampleOutOfBounds { index: 5, count: 3 };
         assert!(e.to_string().contains("5"));
         assert!(e.to_string().contains("3"));
+
+        let e = Error::invalid("bad child count").at_offset(1234);
+        assert!(matches!(&e, Error::Corrupt { offset: 1234, context } if context == "bad child count"));
+        assert_eq!(e.to_string(), "Corrupt archive at offset 1234: bad child count");
+        assert!(matches!(Error::other("x").at_offset(1), Error::Other(_)));
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "test");
@@ -23,7 +23,7 @@ pub use glam::{
 };
 
 use bytemuck::{Pod, Zeroable};
-use std::fmt;
+use core::fmt;
 
 /// 3D bounding box with single precision.
 #[derive(Clone, Copy, PartialEq, Pod, Zeroable)]
@@ -160,6 +160,30 @@ impl BBox3d {
         self.max - self.min
     }
 
+    /// Bounds of this box after transforming its eight corners by `m`.
+    /// An empty box stays empty.
+    pub fn transformed(&self, m: &DMat4) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+        let mut out = Self::EMPTY;
+        for i in 0..8 {
+            let corner = DVec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            );
+            out.expand_by_point(m.transform_point3(corner));
+        }
+        out
+    }
+
+    /// Stored layout: `[min.x, min.y, min.z, max.x, max.y, max.z]`.
+    #[inline]
+    pub fn to_array(&self) -> [f64; 6] {
+        [self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z]
+    }
+
     /// Convert to single precision.
     #[inline]
     pub fn as_f32(&self) -> BBox3f {
@@ -233,10 +257,20 @@ mod tests {
         assert_eq!(b.size(), DVec3::splat(2.0));
     }
 
+    #[test]
+    fn test_bbox3d_transformed() {
+        let b = BBox3d::new(DVec3::ZERO, DVec3::ONE);
+        let m = DMat4::from_translation(DVec3::new(10.0, 0.0, 0.0)) * DMat4::from_rotation_z(core::f64::consts::FRAC_PI_2);
+        let t = b.transformed(&m);
+        assert!((t.min - DVec3::new(9.0, 0.0, 0.0)).length() < 1e-12);
+        assert!((t.max - DVec3::new(10.0, 1.0, 1.0)).length() < 1e-12);
+        assert!(BBox3d::EMPTY.transformed(&m).is_empty());
+    }
+
     #[test]
     fn test_bbox_pod() {
         // Verify that BBox types are Pod-compatible
-        assert_eq!(std::mem::size_of::<BBox3f>(), 24);  // 2 * Vec3 = 2 * 12
-        assert_eq!(std::mem::size_of::<BBox3d>(), 48);  // 2 * DVec3 = 2 * 24
+        assert_eq!(core::mem::size_of::<BBox3f>(), 24);  // 2 * Vec3 = 2 * 12
+        assert_eq!(core::mem::size_of::<BBox3d>(), 48);  // 2 * DVec3 = 2 * 24
     }
 }
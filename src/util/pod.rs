@@ -2,7 +2,7 @@
 
 use bytemuck::{Pod, Zeroable};
 use half::f16;
-use std::fmt;
+use core::fmt;
 
 /// Plain Old Data type enum - represents basic storage types.
 ///
@@ -66,8 +66,8 @@ impl PlainOldDataType {
             Self::Float32 => 4,
             Self::Float64 => 8,
             // Strings are stored separately, this is just for in-memory representation
-            Self::String => std::mem::size_of::<usize>(),
-            Self::Wstring => std::mem::size_of::<usize>(),
+            Self::String => core::mem::size_of::<usize>(),
+            Self::Wstring => core::mem::size_of::<usize>(),
             Self::Unknown => 0,
         }
     }
@@ -200,7 +200,7 @@ pub trait AlembicPod: Pod + Zeroable + Copy + Default {
     const POD_TYPE: PlainOldDataType;
 
     /// Size of this type in bytes.
-    const SIZE: usize = std::mem::size_of::<Self>();
+    const SIZE: usize = core::mem::size_of::<Self>();
 }
 
 // Implement AlembicPod for primitive types
@@ -325,7 +325,7 @@ mod tests {
         let f = Bool::new(false);
         assert!(t.get());
         assert!(!f.get());
-        assert_eq!(std::mem::size_of::<Bool>(), 1);
+        assert_eq!(core::mem::size_of::<Bool>(), 1);
     }
 
     #[test]
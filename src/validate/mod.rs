@@ -0,0 +1,500 @@
+//! Archive validation (lint).
+//!
+//! [`validate_archive`] opens a file, walks every object and sample and
+//! reports data that readers downstream tend to choke on:
+//!
+//! - object and property subtrees that can't be read (corrupt headers or
+//!   offsets; see [`OpenMode::Tolerant`])
+//! - time samplings with non-monotonic or non-finite times
+//! - unknown schemas, and unsupported versions of known ones
+//! - NaN/Inf positions and transforms
+//! - face counts that don't sum to the index count, out-of-range face
+//!   indices and degenerate (< 3 vertex) faces
+//! - GeomParams (`uv`, `N`, `.arbGeomParams`) whose length doesn't match
+//!   their scope, or whose indices point past the values
+//! - faces wound against their neighbours (flipped normals) and
+//!   non-orientable surfaces
+//! - curve strand / knot / order mismatches and out-of-range FaceSet faces
+//! - properties with POD types this library doesn't know (kept as raw bytes)
+//!
+//! Each problem is reported once per object and check (the first offending
+//! sample), so a broken 1000-frame cache doesn't produce 1000 diagnostics.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use alembic::validate::{validate_archive, Severity};
+//!
+//! for d in validate_archive("export.abc") {
+//!     println!("{}", d);
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use crate::abc::{IArchive, ICompoundProperty, IObject, OpenMode};
+use crate::core::GeometryScope;
+use crate::geom::{
+    analyze_winding, ICurves, IGeomParam, INuPatch, IPoints, IPolyMesh, ISubD, IXform, SchemaInfo,
+};
+
+/// How bad a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Suspicious but readable (e.g. degenerate faces, custom schemas).
+    Warning,
+    /// Data that is wrong or won't be read.
+    Error,
+}
+
+/// Which check produced a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// The file could not be opened.
+    Open,
+    /// A sample could not be decoded.
+    Read,
+    /// Unknown schema or schema version.
+    Schema,
+    /// Invalid time sampling table entry.
+    TimeSampling,
+    /// Non-finite positions or transforms.
+    Nan,
+    /// Face/strand counts and indices.
+    Topology,
+    /// GeomParam length or index range.
+    GeomParam,
+    /// Faces wound inconsistently with their neighbours.
+    Winding,
+    /// FaceSet faces outside the parent mesh.
+    FaceSet,
+    /// Property with a POD type this library doesn't know.
+    UnknownPod,
+}
+
+impl Check {
+    /// Short name used in reports ("topology", "geom_param", ...).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Check::Open => "open",
+            Check::Read => "read",
+            Check::Schema => "schema",
+            Check::TimeSampling => "time_sampling",
+            Check::Nan => "nan",
+            Check::Topology => "topology",
+            Check::GeomParam => "geom_param",
+            Check::Winding => "winding",
+            Check::FaceSet => "faceset",
+            Check::UnknownPod => "unknown_pod",
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One validation finding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub check: Check,
+    /// Full object path ("/" for archive-level problems).
+    pub object: String,
+    /// First offending sample, if the problem is per-sample.
+    pub sample: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}[{}] {}", severity, self.check, self.object)?;
+        if let Some(sample) = self.sample {
+            write!(f, " sample {}", sample)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Open `path` in tolerant mode and validate it. An unreadable file yields
+/// a single [`Check::Open`] error.
+pub fn validate_archive(path: impl AsRef<Path>) -> Vec<Diagnostic> {
+    match IArchive::open_with_mode(path.as_ref(), OpenMode::Tolerant) {
+        Ok(archive) => validate(&archive),
+        Err(e) => vec![Diagnostic {
+            severity: Severity::Error,
+            check: Check::Open,
+            object: "/".to_string(),
+            sample: None,
+            message: e.to_string(),
+        }],
+    }
+}
+
+/// Validate an open archive. Diagnostics are in hierarchy order, after the
+/// subtrees skipped when opening and the archive-level time sampling checks.
+pub fn validate(archive: &IArchive) -> Vec<Diagnostic> {
+    let mut out: Vec<Diagnostic> = archive.skipped_subtrees().iter().map(|s| Diagnostic {
+        severity: Severity::Error,
+        check: Check::Read,
+        object: s.path.clone(),
+        sample: None,
+        message: s.error.to_string(),
+    }).collect();
+    for i in 0..archive.getNumTimeSamplings() {
+        if let Some(Err(msg)) = archive.getTimeSampling(i).map(|ts| ts.validate()) {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                check: Check::TimeSampling,
+                object: "/".to_string(),
+                sample: None,
+                message: format!("time sampling {}: {}", i, msg),
+            });
+        }
+    }
+    for child in archive.getTop().getChildren() {
+        validate_object(&child, &mut out);
+    }
+    out
+}
+
+/// Diagnostics for one object, deduplicated per check and key.
+struct ObjectReport<'a> {
+    object: String,
+    seen: HashSet<(Check, String)>,
+    out: &'a mut Vec<Diagnostic>,
+}
+
+impl ObjectReport<'_> {
+    /// Report unless `(check, key)` was already reported for this object.
+    fn report(&mut self, severity: Severity, check: Check, key: &str, sample: Option<usize>, message: String) {
+        if self.seen.insert((check, key.to_string())) {
+            self.out.push(Diagnostic { severity, check, object: self.object.clone(), sample, message });
+        }
+    }
+
+    fn error(&mut self, check: Check, sample: usize, message: String) {
+        self.report(Severity::Error, check, "", Some(sample), message);
+    }
+}
+
+fn validate_object(obj: &IObject, out: &mut Vec<Diagnostic>) {
+    let mut r = ObjectReport { object: obj.getFullName().to_string(), seen: HashSet::new(), out };
+    check_schema(obj, &mut r);
+    check_unknown_pods(&obj.getProperties(), "", &mut r);
+
+    if let Some(xform) = IXform::new(obj) {
+        for i in 0..xform.getNumSamples() {
+            match xform.getSample(i) {
+                Ok(s) if !s.matrix().is_finite() => r.error(Check::Nan, i, "non-finite transform".into()),
+                Ok(_) => {}
+                Err(e) => r.error(Check::Read, i, e.to_string()),
+            }
+        }
+    } else if let Some(mesh) = IPolyMesh::new(obj) {
+        let face_sets = mesh.face_set_names();
+        let mut wound = Vec::new();
+        for i in 0..mesh.getNumSamples() {
+            let s = match mesh.getSample(i) {
+                Ok(s) => s,
+                Err(e) => {
+                    r.error(Check::Read, i, e.to_string());
+                    continue;
+                }
+            };
+            check_positions(&mut r, i, &s.positions);
+            check_faces(&mut r, i, s.positions.len(), &s.face_counts, &s.face_indices);
+            check_winding(&mut r, i, &s.face_counts, &s.face_indices, &mut wound);
+            check_geom_params(obj, &mut r, i, &MeshCounts::new(s.positions.len(), &s.face_counts));
+            for name in &face_sets {
+                let Some(faces) = mesh.faces_for_faceset(name, i) else { continue };
+                if let Some(&bad) = faces.iter().find(|&&f| f < 0 || f as usize >= s.face_counts.len()) {
+                    r.report(Severity::Error, Check::FaceSet, name, Some(i), format!(
+                        "FaceSet '{}' face {} out of range (0..{})", name, bad, s.face_counts.len()
+                    ));
+                }
+            }
+        }
+    } else if let Some(subd) = ISubD::new(obj) {
+        let mut wound = Vec::new();
+        for i in 0..subd.getNumSamples() {
+            match subd.getSample(i) {
+                Ok(s) => {
+                    check_positions(&mut r, i, &s.positions);
+                    check_faces(&mut r, i, s.positions.len(), &s.face_counts, &s.face_indices);
+                    check_winding(&mut r, i, &s.face_counts, &s.face_indices, &mut wound);
+                    check_geom_params(obj, &mut r, i, &MeshCounts::new(s.positions.len(), &s.face_counts));
+                }
+                Err(e) => r.error(Check::Read, i, e.to_string()),
+            }
+        }
+    } else if let Some(curves) = ICurves::new(obj) {
+        for i in 0..curves.getNumSamples() {
+            match curves.getSample(i) {
+                Ok(s) => {
+                    check_positions(&mut r, i, &s.positions);
+                    if let Err(e) = s.validate() {
+                        r.error(Check::Topology, i, e.to_string());
+                    }
+                }
+                Err(e) => r.error(Check::Read, i, e.to_string()),
+            }
+        }
+    } else if let Some(points) = IPoints::new(obj) {
+        for i in 0..points.getNumSamples() {
+            match points.getSample(i) {
+                Ok(s) => {
+                    check_positions(&mut r, i, &s.positions);
+                    let n = s.positions.len();
+                    if !s.ids.is_empty() && s.ids.len() != n {
+                        r.error(Check::Topology, i, format!("{} ids for {} points", s.ids.len(), n));
+                    }
+                    if !s.velocities.is_empty() && s.velocities.len() != n {
+                        r.report(Severity::Error, Check::GeomParam, ".velocities", Some(i),
+                            format!(".velocities has {} values for {} points", s.velocities.len(), n));
+                    }
+                    if s.widths.len() > 1 && s.widths.len() != n {
+                        r.report(Severity::Error, Check::GeomParam, "width", Some(i),
+                            format!("width has {} values for {} points", s.widths.len(), n));
+                    }
+                }
+                Err(e) => r.error(Check::Read, i, e.to_string()),
+            }
+        }
+    } else if let Some(patch) = INuPatch::new(obj) {
+        for i in 0..patch.getNumSamples() {
+            match patch.getSample(i) {
+                Ok(s) => {
+                    check_positions(&mut r, i, &s.positions);
+                    let (nu, nv) = (s.num_u.max(0) as usize, s.num_v.max(0) as usize);
+                    if nu * nv != s.positions.len() {
+                        r.error(Check::Topology, i, format!("{}x{} control points but {} positions", nu, nv, s.positions.len()));
+                    } else if s.u_knots.len() != nu + s.u_order.max(0) as usize
+                        || s.v_knots.len() != nv + s.v_order.max(0) as usize
+                    {
+                        r.error(Check::Topology, i, format!(
+                            "{}/{} knots; expected {}/{} (count + order)",
+                            s.u_knots.len(), s.v_knots.len(), nu + s.u_order.max(0) as usize, nv + s.v_order.max(0) as usize
+                        ));
+                    }
+                }
+                Err(e) => r.error(Check::Read, i, e.to_string()),
+            }
+        }
+    }
+
+    for child in obj.getChildren() {
+        validate_object(&child, out);
+    }
+}
+
+/// Unknown schemas are a warning (custom schemas are legal); an unknown
+/// version of a schema this library reads is an error, since readers match
+/// the exact title and would silently skip the object.
+fn check_schema(obj: &IObject, r: &mut ObjectReport) {
+    let Some(schema) = obj.getMetaData().schema() else { return };
+    if schema.is_empty() || SchemaInfo::for_schema(schema).is_some() {
+        return;
+    }
+    let family = schema_family(schema);
+    match SchemaInfo::ALL.iter().find(|info| schema_family(info.schema) == family) {
+        Some(known) => r.report(Severity::Error, Check::Schema, "", None, format!(
+            "unsupported schema version '{}' (supported: {})", schema, known.schema
+        )),
+        None => r.report(Severity::Warning, Check::Schema, "", None, format!("unknown schema '{}'", schema)),
+    }
+}
+
+/// Properties from newer writers with POD codes this library can't
+/// interpret. They read (and copy) fine as raw bytes, so this is a warning.
+fn check_unknown_pods(props: &ICompoundProperty, prefix: &str, r: &mut ObjectReport) {
+    for i in 0..props.getNumProperties() {
+        let Some(prop) = props.getProperty(i) else { continue };
+        let path = format!("{}{}", prefix, prop.getName());
+        if let Some(compound) = prop.asCompound() {
+            check_unknown_pods(&compound, &format!("{}/", path), r);
+            continue;
+        }
+        let header = prop.getHeader();
+        if let Some(code) = header.unknown_pod {
+            r.report(Severity::Warning, Check::UnknownPod, &path, None, format!(
+                "property '{}' has unknown POD type {} (extent {}), kept as raw bytes",
+                path, code, header.data_type.extent
+            ));
+        }
+    }
+}
+
+/// "AbcGeom_Xform_v3" -> "AbcGeom_Xform".
+fn schema_family(schema: &str) -> &str {
+    match schema.rsplit_once("_v") {
+        Some((family, version)) if !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()) => family,
+        _ => schema,
+    }
+}
+
+fn check_positions(r: &mut ObjectReport, sample: usize, positions: &[glam::Vec3]) {
+    if let Some(idx) = positions.iter().position(|p| !p.is_finite()) {
+        r.error(Check::Nan, sample, format!("non-finite position at point {}", idx));
+    }
+}
+
+fn check_faces(r: &mut ObjectReport, sample: usize, num_points: usize, counts: &[i32], indices: &[i32]) {
+    if let Some(&c) = counts.iter().find(|&&c| c < 0) {
+        r.error(Check::Topology, sample, format!("negative face count {}", c));
+        return;
+    }
+    let total: usize = counts.iter().map(|&c| c as usize).sum();
+    if total != indices.len() {
+        r.error(Check::Topology, sample, format!("face counts sum to {} but there are {} face indices", total, indices.len()));
+    }
+    if let Some(&bad) = indices.iter().find(|&&i| i < 0 || i as usize >= num_points) {
+        r.error(Check::Topology, sample, format!("face index {} out of range (0..{})", bad, num_points));
+    }
+    let degenerate = counts.iter().filter(|&&c| c < 3).count();
+    if degenerate > 0 {
+        r.report(Severity::Warning, Check::Topology, "degenerate", Some(sample),
+            format!("{} faces with fewer than 3 vertices", degenerate));
+    }
+}
+
+/// Winding consistency, skipped while the face indices repeat the last
+/// checked sample's (`wound`).
+fn check_winding(r: &mut ObjectReport, sample: usize, counts: &[i32], indices: &[i32], wound: &mut Vec<i32>) {
+    if sample > 0 && indices == wound.as_slice() {
+        return;
+    }
+    wound.clear();
+    wound.extend_from_slice(indices);
+    let report = analyze_winding(counts, indices);
+    if !report.flipped_faces.is_empty() {
+        r.report(Severity::Warning, Check::Winding, "", Some(sample), format!(
+            "{} of {} faces wound against their neighbours (flipped normals)",
+            report.flipped_faces.len(), report.faces
+        ));
+    }
+    if report.non_orientable_components > 0 {
+        r.report(Severity::Warning, Check::Winding, "non_orientable", Some(sample), format!(
+            "{} non-orientable components (winding can't be made consistent)", report.non_orientable_components
+        ));
+    }
+}
+
+/// Element counts a GeomParam scope can map onto.
+struct MeshCounts {
+    points: usize,
+    faces: usize,
+    face_vertices: usize,
+}
+
+impl MeshCounts {
+    fn new(points: usize, face_counts: &[i32]) -> Self {
+        let face_vertices = face_counts.iter().map(|&c| c.max(0) as usize).sum();
+        Self { points, faces: face_counts.len(), face_vertices }
+    }
+
+    fn expected(&self, scope: GeometryScope) -> Option<usize> {
+        match scope {
+            GeometryScope::Constant => Some(1),
+            GeometryScope::Uniform => Some(self.faces),
+            GeometryScope::Varying | GeometryScope::Vertex => Some(self.points),
+            GeometryScope::FaceVarying => Some(self.face_vertices),
+            GeometryScope::Unknown => None,
+        }
+    }
+}
+
+/// Check `uv`, `N` and every `.arbGeomParams` entry of a mesh-like object.
+fn check_geom_params(obj: &IObject, r: &mut ObjectReport, sample: usize, counts: &MeshCounts) {
+    let props = obj.getProperties();
+    let Some(geom_prop) = props.getPropertyByName(".geom") else { return };
+    let Some(geom) = geom_prop.asCompound() else { return };
+    for name in ["uv", "N"] {
+        if let Some(param) = IGeomParam::new(&geom, name) {
+            check_geom_param(&param, r, sample, counts);
+        }
+    }
+    let Some(arb_prop) = geom.getPropertyByName(".arbGeomParams") else { return };
+    let Some(arb) = arb_prop.asCompound() else { return };
+    for name in arb.getPropertyNames() {
+        if let Some(param) = IGeomParam::new(&arb, &name) {
+            check_geom_param(&param, r, sample, counts);
+        }
+    }
+}
+
+fn check_geom_param(param: &IGeomParam, r: &mut ObjectReport, sample: usize, counts: &MeshCounts) {
+    let name = param.getName().to_string();
+    let last = param.getNumSamples().saturating_sub(1);
+    let s = match param.getSample(sample.min(last)) {
+        Ok(s) => s,
+        Err(e) => {
+            r.report(Severity::Error, Check::Read, &name, Some(sample), format!("{}: {}", name, e));
+            return;
+        }
+    };
+    let extent = param.array_extent().max(1);
+    let num_values = s.num_values() / extent;
+    if let Some(indices) = &s.indices {
+        if let Some(&bad) = indices.iter().find(|&&i| i as usize >= num_values) {
+            r.report(Severity::Error, Check::GeomParam, &name, Some(sample),
+                format!("{} index {} out of range (0..{})", name, bad, num_values));
+        }
+    }
+    let len = s.indices.as_ref().map_or(num_values, |i| i.len());
+    let scope = param.scope();
+    // Constant params may legally hold an array; only flag an empty one
+    let bad = match counts.expected(scope) {
+        Some(_) if scope == GeometryScope::Constant => len == 0,
+        Some(expected) => len != expected,
+        None => false,
+    };
+    if bad {
+        r.report(Severity::Error, Check::GeomParam, &name, Some(sample), format!(
+            "{} has {} {} values, expected {}",
+            name, len, scope.as_str(), counts.expected(scope).unwrap_or(0)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_family() {
+        assert_eq!(schema_family("AbcGeom_Xform_v3"), "AbcGeom_Xform");
+        assert_eq!(schema_family("AbcGeom_Xform_v12"), "AbcGeom_Xform");
+        assert_eq!(schema_family("MyStudio_Rig"), "MyStudio_Rig");
+        assert_eq!(schema_family("MyStudio_vault"), "MyStudio_vault");
+    }
+
+    #[test]
+    fn test_diagnostic_display() {
+        let d = Diagnostic {
+            severity: Severity::Error,
+            check: Check::Topology,
+            object: "/geo/mesh".into(),
+            sample: Some(3),
+            message: "face index 9 out of range (0..4)".into(),
+        };
+        assert_eq!(d.to_string(), "error[topology] /geo/mesh sample 3: face index 9 out of range (0..4)");
+    }
+
+    #[test]
+    fn test_mesh_counts_expected() {
+        let counts = MeshCounts::new(5, &[3, 4]);
+        assert_eq!(counts.expected(GeometryScope::FaceVarying), Some(7));
+        assert_eq!(counts.expected(GeometryScope::Uniform), Some(2));
+        assert_eq!(counts.expected(GeometryScope::Vertex), Some(5));
+        assert_eq!(counts.expected(GeometryScope::Unknown), None);
+    }
+}
@@ -1,230 +0,0 @@
-//! Persistent application settings
-
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-
-/// Hover highlight mode
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-pub enum HoverMode {
-    #[default]
-    None,
-    Outline,
-    Tint,
-    Both,
-}
-
-impl HoverMode {
-    pub const ALL: [HoverMode; 4] = [
-        HoverMode::None,
-        HoverMode::Outline,
-        HoverMode::Tint,
-        HoverMode::Both,
-    ];
-    
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            HoverMode::None => "None",
-            HoverMode::Outline => "Outline",
-            HoverMode::Tint => "Tint",
-            HoverMode::Both => "Both",
-        }
-    }
-}
-
-/// Application settings that persist between sessions
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct Settings {
-    // Display
-    pub show_grid: bool,
-    pub show_wireframe: bool,
-    pub flat_shading: bool,
-    pub show_shadows: bool,
-    pub ssao: bool,
-    pub ssao_strength: f32,
-    pub ssao_radius: f32,
-    pub xray_alpha: f32,
-    pub double_sided: bool,
-    pub auto_normals: bool,
-    pub smooth_normals: bool,
-    pub smooth_angle: f32,  // 0-180 degrees
-    pub show_floor: bool,
-    pub background_color: [f32; 4],
-    
-    // Window
-    pub window_width: f32,
-    pub window_height: f32,
-    pub window_x: Option<f32>,
-    pub window_y: Option<f32>,
-    
-    // Camera
-    pub camera_distance: f32,
-    pub camera_yaw: f32,
-    pub camera_pitch: f32,
-    
-    // Last opened file
-    pub last_file: Option<PathBuf>,
-    
-    // Recent files (most recent first, max 10)
-    pub recent_files: Vec<PathBuf>,
-    
-    // Environment
-    pub hdr_enabled: bool,
-    pub hdr_visible: bool,
-    pub hdr_exposure: f32,
-    pub last_hdr_file: Option<PathBuf>,
-    
-    // Anti-aliasing (requires restart)
-    pub antialiasing: u8,
-    
-    // UI layout
-    pub hierarchy_panel_width: f32,
-    pub side_panel_width: f32,
-    
-    // Playback
-    pub playback_fps: f32,
-    
-    // Lighting
-    pub use_scene_lights: bool,
-    
-    // Path tracing
-    pub path_tracing: bool,
-    pub pt_max_bounces: u32,
-    pub pt_max_samples: u32,
-    pub pt_samples_per_update: u32,
-    pub pt_target_fps: f32,       // Target FPS for auto SPP mode
-    pub pt_auto_spp: bool,        // Auto-adjust samples per frame to hit target FPS
-    pub pt_camera_snap: bool,     // Snap camera at target FPS intervals (prevents ghost samples)
-    pub pt_max_transmission_depth: u32,
-    pub pt_dof_enabled: bool,
-    pub pt_aperture: f32,       // Aperture radius in world units
-    pub pt_focus_distance: f32, // Focus distance in world units
-    pub pt_global_opacity: f32, // Global opacity multiplier (0-1)
-
-    // Material randomization
-    pub materialize_missing: bool,
-    
-    // Hover highlighting
-    pub hover_mode: HoverMode,
-    pub hover_outline_thickness: f32,
-    pub hover_outline_alpha: f32,
-
-    // Auto turntable
-    pub turntable_enabled: bool,
-    pub turntable_speed: f32,  // degrees per second
-}
-
-impl Default for Settings {
-    fn default() -> Self {
-        Self {
-            show_grid: true,
-            show_wireframe: false,
-            flat_shading: false,
-            show_shadows: true,
-            ssao: false,
-            ssao_strength: 0.5,
-            ssao_radius: 0.015,
-            xray_alpha: 1.0,
-            double_sided: true,
-            auto_normals: true,  // Auto-flip inverted normals by default
-            smooth_normals: true,
-            smooth_angle: 62.0,
-            show_floor: true,
-            background_color: [0.1, 0.1, 0.12, 1.0],
-            window_width: 1280.0,
-            window_height: 720.0,
-            window_x: None,
-            window_y: None,
-            camera_distance: 5.0,
-            camera_yaw: 0.0,
-            camera_pitch: 0.0,
-            last_file: None,
-            recent_files: Vec::new(),
-            hdr_enabled: true,
-            hdr_visible: true,
-            hdr_exposure: 1.0,
-            last_hdr_file: None,
-            antialiasing: 4,
-            hierarchy_panel_width: 200.0,
-            side_panel_width: 200.0,
-            playback_fps: 24.0,
-            use_scene_lights: false,
-            path_tracing: false,
-            pt_max_bounces: 4,
-            pt_max_samples: 512,
-            pt_samples_per_update: 1,
-            pt_target_fps: 30.0,
-            pt_auto_spp: false,
-            pt_camera_snap: true,  // On by default to prevent ghost samples
-            pt_max_transmission_depth: 8,
-            pt_dof_enabled: false,
-            pt_aperture: 0.1,
-            pt_focus_distance: 10.0,
-            pt_global_opacity: 1.0,
-            materialize_missing: false,
-            hover_mode: HoverMode::Outline,  // Default to outline hover
-            hover_outline_thickness: 2.0,
-            hover_outline_alpha: 1.0,
-            turntable_enabled: false,
-            turntable_speed: 15.0,  // 15 deg/sec = 24 sec per revolution
-        }
-    }
-}
-
-const MAX_RECENT_FILES: usize = 10;
-
-impl Settings {
-    /// Get settings file path
-    fn path() -> Option<PathBuf> {
-        dirs::config_dir().map(|mut p| {
-            p.push("alembic-viewer");
-            std::fs::create_dir_all(&p).ok();
-            p.push("settings.json");
-            p
-        })
-    }
-
-    /// Load settings from file
-    pub fn load() -> Self {
-        let mut settings: Self = Self::path()
-            .and_then(|p| std::fs::read_to_string(&p).ok())
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or_default();
-        
-        // Validate antialiasing values (8x requires TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
-        if !matches!(settings.antialiasing, 1 | 2 | 4 | 8) {
-            settings.antialiasing = 4;
-        }
-        
-        settings
-    }
-
-    /// Save settings to file
-    pub fn save(&self) {
-        if let Some(path) = Self::path() {
-            if let Ok(json) = serde_json::to_string_pretty(self) {
-                let _ = std::fs::write(path, json);
-            }
-        }
-    }
-    
-    /// Add file to recent files list (moves to top if already present)
-    pub fn add_recent(&mut self, path: PathBuf) {
-        // Remove if already in list
-        self.recent_files.retain(|p| p != &path);
-        
-        // Insert at front
-        self.recent_files.insert(0, path.clone());
-        
-        // Trim to max size
-        self.recent_files.truncate(MAX_RECENT_FILES);
-        
-        // Also update last_file
-        self.last_file = Some(path);
-    }
-    
-    /// Get recent files (filters out non-existent)
-    pub fn recent_files(&self) -> Vec<&PathBuf> {
-        self.recent_files.iter().filter(|p| p.exists()).collect()
-    }
-}
@@ -0,0 +1,266 @@
+/*
+ * Writes the legacy HDF5 (AbcCoreHDF5 layout) archives used by the `hdf5`
+ * feature tests in tests/hdf5_tests.rs:
+ *
+ *   legacy_v18.abc     HDF5 1.8 default (earliest) format: version 0
+ *                      superblock, symbol-table root group, v1 object headers
+ *                      for datasets, v1 B-tree chunk index
+ *   legacy_latest.abc  H5F_LIBVER_LATEST (v2 object headers everywhere,
+ *                      single-chunk dataset index)
+ *
+ * Build against libhdf5 and run from this directory:
+ *
+ *   cc make_fixtures.c -lhdf5 -lz -lm -o make_fixtures && ./make_fixtures
+ *
+ * The layout follows AbcCoreHDF5: objects are groups with creation order
+ * tracking, an object's properties live in its `.prop` group, each property
+ * is described by `<name>.info` / `<name>.meta` attributes on its parent
+ * compound, scalar samples are attributes and array samples datasets
+ * (`<name>.smp0` next to the header, later ones in the `<name>.smpi` group).
+ */
+#include <hdf5.h>
+#include <float.h>
+#include <stdint.h>
+#include <string.h>
+
+static hid_t creation_order_plist(void)
+{
+    hid_t gcpl = H5Pcreate(H5P_GROUP_CREATE);
+    H5Pset_link_creation_order(gcpl, H5P_CRT_ORDER_TRACKED | H5P_CRT_ORDER_INDEXED);
+    H5Pset_attr_creation_order(gcpl, H5P_CRT_ORDER_TRACKED | H5P_CRT_ORDER_INDEXED);
+    return gcpl;
+}
+
+static hid_t group(hid_t parent, const char *name)
+{
+    hid_t gcpl = creation_order_plist();
+    hid_t g = H5Gcreate2(parent, name, H5P_DEFAULT, gcpl, H5P_DEFAULT);
+    H5Pclose(gcpl);
+    return g;
+}
+
+static void attr_array(hid_t parent, const char *name, hid_t file_type, hid_t mem_type, hsize_t n, const void *data)
+{
+    hid_t space = H5Screate_simple(1, &n, NULL);
+    hid_t a = H5Acreate2(parent, name, file_type, space, H5P_DEFAULT, H5P_DEFAULT);
+    H5Awrite(a, mem_type, data);
+    H5Aclose(a);
+    H5Sclose(space);
+}
+
+static void attr_scalar(hid_t parent, const char *name, hid_t file_type, hid_t mem_type, const void *data)
+{
+    hid_t space = H5Screate(H5S_SCALAR);
+    hid_t a = H5Acreate2(parent, name, file_type, space, H5P_DEFAULT, H5P_DEFAULT);
+    H5Awrite(a, mem_type, data);
+    H5Aclose(a);
+    H5Sclose(space);
+}
+
+/* Fixed-length string attribute, as written by WriteString */
+static void attr_string(hid_t parent, const char *name, const char *value)
+{
+    hid_t type = H5Tcopy(H5T_C_S1);
+    H5Tset_size(type, strlen(value));
+    attr_scalar(parent, name, type, type, value);
+    H5Tclose(type);
+}
+
+/* `<name>.info` and `<name>.meta` */
+static void header(hid_t parent, const char *name, const uint32_t *info, size_t fields, const char *meta)
+{
+    char attr[256];
+    snprintf(attr, sizeof attr, "%s.info", name);
+    attr_array(parent, attr, H5T_STD_U32LE, H5T_NATIVE_UINT32, fields, info);
+    if (meta && *meta) {
+        snprintf(attr, sizeof attr, "%s.meta", name);
+        attr_string(parent, attr, meta);
+    }
+}
+
+static uint32_t info0(uint32_t ptype, uint32_t pod, uint32_t extent, int has_ts, int no_repeats)
+{
+    return ptype | (pod << 2) | (has_ts ? 0x40 : 0) | (no_repeats ? 0x80 : 0) | (extent << 8);
+}
+
+static hid_t dataset(hid_t parent, const char *name, hid_t file_type, hid_t mem_type, hsize_t n, const void *data,
+                     int deflate)
+{
+    hid_t space = n ? H5Screate_simple(1, &n, NULL) : H5Screate(H5S_NULL);
+    hid_t dcpl = H5Pcreate(H5P_DATASET_CREATE);
+    if (deflate) {
+        H5Pset_chunk(dcpl, 1, &n);
+        H5Pset_shuffle(dcpl);
+        H5Pset_deflate(dcpl, 6);
+    }
+    hid_t d = H5Dcreate2(parent, name, file_type, space, H5P_DEFAULT, dcpl, H5P_DEFAULT);
+    if (n)
+        H5Dwrite(d, mem_type, H5S_ALL, H5S_ALL, H5P_DEFAULT, data);
+    H5Pclose(dcpl);
+    H5Sclose(space);
+    return d;
+}
+
+static void array_sample(hid_t parent, const char *name, hid_t file_type, hid_t mem_type, hsize_t n,
+                         const void *data, int deflate)
+{
+    H5Dclose(dataset(parent, name, file_type, mem_type, n, data, deflate));
+}
+
+enum { POD_BOOL = 0, POD_UINT8 = 1, POD_INT32 = 6, POD_FLOAT32 = 10, POD_FLOAT64 = 11, POD_STRING = 12 };
+
+static void write_mesh(hid_t parent, const char *name, int shared_topology, hid_t first_mesh_geom)
+{
+    hid_t obj = group(parent, name);
+    uint32_t compound = 0;
+    header(obj, ".prop", &compound, 1, "schema=AbcGeom_PolyMesh_v1");
+    hid_t props = group(obj, ".prop");
+    header(props, ".geom", &compound, 1, "schema=AbcGeom_PolyMesh_v1");
+    hid_t geom = group(props, ".geom");
+
+    /* P: three samples on time sampling 1, the last one a repeat */
+    float p0[12] = {0, 0, 0, 1, 0, 0, 1, 1, 0, 0, 1, 0};
+    float p1[12] = {0, 0, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1};
+    uint32_t p_info[5] = {info0(2, POD_FLOAT32, 3, 1, 0), 3, 1, 1, 1};
+    header(geom, "P", p_info, 5, "geoScope=var;interpretation=point");
+    array_sample(geom, "P.smp0", H5T_IEEE_F32LE, H5T_NATIVE_FLOAT, 12, p0, 0);
+    hid_t smpi = group(geom, "P.smpi");
+    array_sample(smpi, "P.smp1", H5T_IEEE_F32LE, H5T_NATIVE_FLOAT, 12, p1, 0);
+    H5Gclose(smpi);
+
+    /* Constant topology over three samples: only sample 0 is stored */
+    int32_t indices[4] = {0, 1, 2, 3};
+    int32_t counts[1] = {4};
+    uint32_t topo_info[5] = {info0(2, POD_INT32, 1, 1, 0), 3, 0, 0, 1};
+    header(geom, ".faceIndices", topo_info, 5, NULL);
+    if (shared_topology)
+        H5Lcreate_hard(first_mesh_geom, ".faceIndices.smp0", geom, ".faceIndices.smp0", H5P_DEFAULT, H5P_DEFAULT);
+    else
+        array_sample(geom, ".faceIndices.smp0", H5T_STD_I32LE, H5T_NATIVE_INT32, 4, indices, 0);
+    header(geom, ".faceCounts", topo_info, 5, NULL);
+    array_sample(geom, ".faceCounts.smp0", H5T_STD_I32LE, H5T_NATIVE_INT32, 1, counts, 0);
+    uint32_t dims = 1;
+    attr_array(geom, ".faceCounts.smp0.dims", H5T_STD_U32LE, H5T_NATIVE_UINT32, 1, &dims);
+
+    /* Self bounds: scalar samples are attributes */
+    double b0[6] = {0, 0, 0, 1, 1, 0};
+    double b1[6] = {0, 0, 1, 1, 1, 1};
+    uint32_t bnds_info[3] = {info0(1, POD_FLOAT64, 6, 1, 1), 3, 1};
+    header(geom, ".selfBnds", bnds_info, 3, "interpretation=box");
+    attr_array(geom, ".selfBnds.smp0", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 6, b0);
+    smpi = group(geom, ".selfBnds.smpi");
+    attr_array(smpi, ".selfBnds.smp1", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 6, b1);
+    attr_array(smpi, ".selfBnds.smp2", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 6, b1);
+    H5Gclose(smpi);
+
+    /* Compressed normals (chunked, shuffle + deflate) */
+    float n[12] = {0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1};
+    uint32_t n_info[4] = {info0(2, POD_FLOAT32, 3, 0, 0), 1, 0, 0};
+    header(geom, "N", n_info, 4, "geoScope=fvr;interpretation=normal");
+    array_sample(geom, "N.smp0", H5T_IEEE_F32LE, H5T_NATIVE_FLOAT, 12, n, 1);
+
+    /* Strings: NUL-separated chars, with the string count in `.dims` */
+    header(props, ".userProperties", &compound, 1, NULL);
+    hid_t user = group(props, ".userProperties");
+    uint32_t tag_info[4] = {info0(2, POD_STRING, 1, 0, 0), 1, 0, 0};
+    header(user, "tags", tag_info, 4, NULL);
+    const char tags[] = "hero\0wet\0";
+    array_sample(user, "tags.smp0", H5T_STD_I8LE, H5T_NATIVE_SCHAR, sizeof tags - 1, tags, 0);
+    uint32_t tag_dims = 2;
+    attr_array(user, "tags.smp0.dims", H5T_STD_U32LE, H5T_NATIVE_UINT32, 1, &tag_dims);
+    uint32_t note_info[4] = {info0(1, POD_STRING, 1, 0, 0), 1, 0, 0};
+    header(user, "note", note_info, 4, NULL);
+    const char note[] = "legacy";
+    attr_array(user, "note.smp0", H5T_STD_I8LE, H5T_NATIVE_SCHAR, sizeof note, note);
+    uint32_t flag_info[4] = {info0(1, POD_BOOL, 1, 0, 0), 1, 0, 0};
+    header(user, "visible", flag_info, 4, NULL);
+    uint8_t visible = 1;
+    attr_scalar(user, "visible.smp0", H5T_STD_U8LE, H5T_NATIVE_UINT8, &visible);
+    uint32_t empty_info[4] = {info0(2, POD_INT32, 1, 0, 0), 1, 0, 0};
+    header(user, "empty", empty_info, 4, NULL);
+    array_sample(user, "empty.smp0", H5T_STD_I32LE, H5T_NATIVE_INT32, 0, NULL, 0);
+    H5Gclose(user);
+
+    H5Gclose(geom);
+    H5Gclose(props);
+    H5Gclose(obj);
+}
+
+static void write_archive(const char *path, int latest)
+{
+    hid_t fapl = H5Pcreate(H5P_FILE_ACCESS);
+    H5Pset_libver_bounds(fapl, latest ? H5F_LIBVER_LATEST : H5F_LIBVER_EARLIEST, H5F_LIBVER_LATEST);
+    hid_t file = H5Fcreate(path, H5F_ACC_TRUNC, H5P_DEFAULT, fapl);
+    H5Pclose(fapl);
+
+    int32_t version = 0, release = 10001;
+    attr_array(file, "abc_version", H5T_STD_I32LE, H5T_NATIVE_INT32, 1, &version);
+    attr_array(file, "abc_release_version", H5T_STD_I32LE, H5T_NATIVE_INT32, 1, &release);
+
+    hid_t top = group(file, "ABC");
+
+    /* 1: uniform 24fps from frame 1, 2: three samples per frame, 3: acyclic */
+    uint32_t spc = 1;
+    double tpc = 1.0 / 24.0, start = 1.0 / 24.0;
+    attr_scalar(top, "1.tspc", H5T_STD_U32LE, H5T_NATIVE_UINT32, &spc);
+    attr_scalar(top, "1.ttpc", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, &tpc);
+    attr_array(top, "1.time", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 1, &start);
+    double cyclic[3] = {0.9 / 24.0, 1.0 / 24.0, 1.1 / 24.0};
+    spc = 3;
+    attr_scalar(top, "2.tspc", H5T_STD_U32LE, H5T_NATIVE_UINT32, &spc);
+    attr_scalar(top, "2.ttpc", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, &tpc);
+    attr_array(top, "2.time", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 3, cyclic);
+    double acyclic[4] = {0.0, 0.5, 0.75, 2.0};
+    double acyclic_tpc = DBL_MAX / 32.0;
+    spc = 4;
+    attr_scalar(top, "3.tspc", H5T_STD_U32LE, H5T_NATIVE_UINT32, &spc);
+    attr_scalar(top, "3.ttpc", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, &acyclic_tpc);
+    attr_array(top, "3.time", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 4, acyclic);
+
+    uint32_t compound = 0;
+    header(top, ".prop", &compound, 1, "_ai_Application=Maya 2011;_ai_AlembicVersion=Alembic 1.0.1");
+
+    /* Transform with an animated translate and a mesh under it */
+    hid_t xform = group(top, "zeta_xform");
+    header(xform, ".prop", &compound, 1, "schema=AbcGeom_Xform_v3");
+    hid_t props = group(xform, ".prop");
+    header(props, ".xform", &compound, 1, "schema=AbcGeom_Xform_v3");
+    hid_t xprops = group(props, ".xform");
+    uint32_t ops_info[4] = {info0(1, POD_UINT8, 1, 0, 0), 1, 0, 0};
+    header(xprops, ".ops", ops_info, 4, NULL);
+    uint8_t ops = 0x10; /* kTranslateOperation << 4 */
+    attr_scalar(xprops, ".ops.smp0", H5T_STD_U8LE, H5T_NATIVE_UINT8, &ops);
+    uint32_t vals_info[3] = {info0(1, POD_FLOAT64, 3, 1, 1), 2, 1};
+    header(xprops, ".vals", vals_info, 3, NULL);
+    double t0[3] = {1, 2, 3}, t1[3] = {4, 5, 6};
+    attr_array(xprops, ".vals.smp0", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 3, t0);
+    hid_t smpi = group(xprops, ".vals.smpi");
+    attr_array(smpi, ".vals.smp1", H5T_IEEE_F64LE, H5T_NATIVE_DOUBLE, 3, t1);
+    H5Gclose(smpi);
+    H5Gclose(xprops);
+    H5Gclose(props);
+    write_mesh(xform, "mesh", 0, -1);
+    H5Gclose(xform);
+
+    /* Second mesh whose topology dataset is a hard link to the first one's */
+    hid_t first_geom = H5Gopen2(top, "zeta_xform/mesh/.prop/.geom", H5P_DEFAULT);
+    write_mesh(top, "alpha_mesh", 1, first_geom);
+    H5Gclose(first_geom);
+
+    /* Enough siblings to move ABC's links into dense storage */
+    for (int i = 9; i >= 0; --i) {
+        char name[16];
+        snprintf(name, sizeof name, "loc%d", i);
+        H5Gclose(group(top, name));
+    }
+
+    H5Gclose(top);
+    H5Fclose(file);
+}
+
+int main(void)
+{
+    write_archive("legacy_v18.abc", 0);
+    write_archive("legacy_latest.abc", 1);
+    return 0;
+}
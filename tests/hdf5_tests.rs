@@ -0,0 +1,161 @@
+//! Reading legacy HDF5 archives (`hdf5` feature).
+//!
+//! The fixtures in tests/hdf5 are written by tests/hdf5/make_fixtures.c in
+//! the HDF5 1.8 default format and in the "latest" format; both hold the
+//! same scene.
+#![cfg(feature = "hdf5")]
+
+use alembic::abc::{IArchive, IObject};
+use alembic::copy::read_sample;
+use alembic::geom::{IPolyMesh, IXform};
+use alembic::Error;
+
+const FIXTURES: [&str; 2] = ["tests/hdf5/legacy_v18.abc", "tests/hdf5/legacy_latest.abc"];
+
+/// Sample `index` of the property `name` in the compound `compound` of `obj`.
+fn property_sample(obj: &IObject, compound: &str, name: &str, index: usize) -> Vec<u8> {
+    let props = obj.getProperties();
+    let parent = props.getPropertyByName(compound).unwrap();
+    let parent = parent.asCompound().unwrap();
+    let prop = parent.getPropertyByName(name).unwrap();
+    read_sample(&prop, prop.getHeader().data_type, index).unwrap().data
+}
+
+#[test]
+fn test_hierarchy_in_creation_order() {
+    for path in FIXTURES {
+        let archive = IArchive::open(path).unwrap();
+        let top = archive.getTop();
+        let names: Vec<String> = top.getChildren().map(|c| c.getName().to_string()).collect();
+        let mut expected = vec!["zeta_xform".to_string(), "alpha_mesh".to_string()];
+        expected.extend((0..10).rev().map(|i| format!("loc{}", i)));
+        assert_eq!(names, expected, "{}", path);
+
+        let xform = top.getChildByName("zeta_xform").unwrap();
+        assert_eq!(xform.getMetaData().get("schema"), Some("AbcGeom_Xform_v3"));
+        let mesh = xform.getChildByName("mesh").unwrap();
+        assert_eq!(mesh.getMetaData().get("schema"), Some("AbcGeom_PolyMesh_v1"));
+        assert_eq!(top.getChildByName("loc3").unwrap().getNumChildren(), 0);
+
+        let meta = archive.getArchiveMetaData();
+        assert_eq!(meta.get("_ai_Application"), Some("Maya 2011"));
+    }
+}
+
+#[test]
+fn test_time_samplings() {
+    for path in FIXTURES {
+        let archive = IArchive::open(path).unwrap();
+        assert_eq!(archive.getNumTimeSamplings(), 4, "{}", path);
+
+        let uniform = archive.getTimeSampling(1).unwrap();
+        assert!(uniform.is_uniform());
+        assert!((uniform.time_per_cycle() - 1.0 / 24.0).abs() < 1e-12);
+        assert!((uniform.sample_time(0, 3) - 1.0 / 24.0).abs() < 1e-12);
+
+        let cyclic = archive.getTimeSampling(2).unwrap();
+        assert!(cyclic.is_cyclic());
+        assert_eq!(cyclic.samples_per_cycle(), 3);
+
+        let acyclic = archive.getTimeSampling(3).unwrap();
+        assert!(acyclic.is_acyclic());
+        assert_eq!(acyclic.stored_times(), vec![0.0, 0.5, 0.75, 2.0]);
+    }
+}
+
+#[test]
+fn test_xform_samples() {
+    for path in FIXTURES {
+        let archive = IArchive::open(path).unwrap();
+        let top = archive.getTop();
+        let obj = top.getChildByName("zeta_xform").unwrap();
+        let xform = IXform::new(&obj).unwrap();
+        assert_eq!(xform.getNumSamples(), 2, "{}", path);
+        assert_eq!(xform.getSample(0usize).unwrap().translation(), glam::Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(xform.getSample(1usize).unwrap().translation(), glam::Vec3::new(4.0, 5.0, 6.0));
+    }
+}
+
+#[test]
+fn test_mesh_samples() {
+    for path in FIXTURES {
+        let archive = IArchive::open(path).unwrap();
+        let top = archive.getTop();
+        let xform = top.getChildByName("zeta_xform").unwrap();
+        let obj = xform.getChildByName("mesh").unwrap();
+        let mesh = IPolyMesh::new(&obj).unwrap();
+        assert_eq!(mesh.getNumSamples(), 3, "{}", path);
+
+        let first = mesh.getSample(0usize).unwrap();
+        assert_eq!(first.face_counts, vec![4]);
+        assert_eq!(first.face_indices, vec![0, 1, 2, 3]);
+        assert_eq!(first.positions[2], glam::Vec3::new(1.0, 1.0, 0.0));
+        assert_eq!(first.normals.as_deref(), Some(&[glam::Vec3::Z; 4][..]));
+
+        // Sample 2 repeats sample 1; topology is stored once for all three
+        for index in [1usize, 2] {
+            let sample = mesh.getSample(index).unwrap();
+            assert_eq!(sample.positions[2], glam::Vec3::new(1.0, 1.0, 1.0));
+            assert_eq!(sample.face_indices, vec![0, 1, 2, 3]);
+            let bounds = sample.self_bounds.unwrap();
+            assert_eq!(bounds.max.z, 1.0);
+        }
+
+        let props = obj.getProperties();
+        let geom = props.getPropertyByName(".geom").unwrap();
+        let geom = geom.asCompound().unwrap();
+        let positions = geom.getPropertyByName("P").unwrap();
+        assert_eq!(positions.getHeader().time_sampling_index, 1);
+        assert_eq!(positions.getHeader().meta_data.get("interpretation"), Some("point"));
+    }
+}
+
+#[test]
+fn test_user_properties() {
+    for path in FIXTURES {
+        let archive = IArchive::open(path).unwrap();
+        let top = archive.getTop();
+        let xform = top.getChildByName("zeta_xform").unwrap();
+        let mesh = xform.getChildByName("mesh").unwrap();
+        let prop = |name: &str| property_sample(&mesh, ".userProperties", name, 0);
+
+        assert_eq!(prop("tags"), b"hero\0wet\0", "{}", path);
+        assert_eq!(prop("note"), b"legacy");
+        assert_eq!(prop("visible"), [1]);
+        assert!(prop("empty").is_empty());
+    }
+}
+
+#[test]
+fn test_hard_linked_sample_shared() {
+    for path in FIXTURES {
+        let archive = IArchive::open(path).unwrap();
+        let top = archive.getTop();
+        let obj = top.getChildByName("alpha_mesh").unwrap();
+        let mesh = IPolyMesh::new(&obj).unwrap();
+        assert_eq!(mesh.getSample(2usize).unwrap().face_indices, vec![0, 1, 2, 3], "{}", path);
+    }
+}
+
+#[test]
+fn test_from_bytes_and_convert() {
+    let data = std::fs::read(FIXTURES[1]).unwrap();
+    assert!(alembic::hdf5::is_hdf5(&data));
+    let archive = IArchive::from_bytes("legacy", data.clone()).unwrap();
+    assert_eq!(archive.getTop().getNumChildren(), 12);
+
+    let ogawa = alembic::hdf5::convert("legacy", &data).unwrap();
+    assert!(!alembic::hdf5::is_hdf5(&ogawa));
+    let reopened = IArchive::from_bytes("converted", ogawa).unwrap();
+    assert_eq!(reopened.getTop().getNumChildren(), 12);
+}
+
+#[test]
+fn test_truncated_file_is_an_error() {
+    let data = std::fs::read(FIXTURES[0]).unwrap();
+    for len in [16, 200, data.len() / 2] {
+        let result = IArchive::from_bytes("truncated", data[..len].to_vec());
+        assert!(matches!(result, Err(Error::Corrupt { .. } | Error::InvalidStructure(_))), "{}", len);
+    }
+}
+
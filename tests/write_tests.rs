@@ -5,7 +5,7 @@ use alembic::geom::{
     IXform, IPolyMesh, ICurves, IPoints, ISubD, ICamera, ILight, INuPatch, IFaceSet,
     XFORM_SCHEMA, POLYMESH_SCHEMA, CURVES_SCHEMA, POINTS_SCHEMA, SUBD_SCHEMA,
     CAMERA_SCHEMA, LIGHT_SCHEMA, NUPATCH_SCHEMA, FACESET_SCHEMA,
-    CurveType, CurvePeriodicity, BasisType, CameraSample,
+    CurveType, CurvePeriodicity, BasisType, CameraSample, FilmBackXformOp, XformOp, XformOpType,
 };
 use alembic::ogawa::writer::{
     OArchive, OObject, OPolyMesh, OPolyMeshSample, OXform, OXformSample,
@@ -62,6 +62,490 @@ fn test_roundtrip_simple_hierarchy() {
     assert!(nested_names.contains(&"nested".to_string()));
 }
 
+#[test]
+fn test_open_from_bytes() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut parent = OObject::new("parent");
+        parent.add_child(OObject::new("nested"));
+        root.add_child(parent);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    
+    let bytes = std::fs::read(path).expect("Failed to read archive bytes");
+    let archive = IArchive::from_bytes("memory.abc", bytes).expect("Failed to open from bytes");
+    assert_eq!(archive.getName(), "memory.abc");
+    assert!(archive.findObject("/parent/nested").is_some());
+    
+    assert!(IArchive::from_bytes("bad", vec![0u8; 8]).is_err());
+}
+
+#[test]
+fn test_in_memory_archive_round_trip() {
+    let mut archive = OArchive::create_in_memory("memory.abc").expect("Failed to create in-memory archive");
+    assert_eq!(archive.getName(), "memory.abc");
+    let ts = archive.addTimeSampling(alembic::core::TimeSampling::uniform(1.0 / 24.0, 0.0));
+    let mut mesh = OPolyMesh::new("tri");
+    mesh.set_time_sampling(ts);
+    for frame in 0..3 {
+        let y = frame as f32;
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::vec3(0.0, y, 0.0), glam::vec3(1.0, y, 0.0), glam::vec3(0.0, y, 1.0)],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+    }
+    let mut root = OObject::new("");
+    root.add_child(mesh.build());
+    archive.write_archive(&root).expect("Failed to write archive");
+    let bytes = archive.into_bytes().expect("Failed to finish archive");
+
+    // Borrowed slices are accepted too
+    let reread = IArchive::from_bytes("memory.abc", bytes.as_slice()).expect("Failed to open from bytes");
+    let tri = reread.findObject("/tri").expect("Missing /tri");
+    let sample = IPolyMesh::new(&tri).expect("Not a PolyMesh").getSample(2).expect("Failed to read sample");
+    assert_eq!(sample.positions[0].y, 2.0);
+
+    // Same bytes as writing to disk
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let mut on_disk = OArchive::create(temp.path()).expect("Failed to create archive");
+    on_disk.addTimeSampling(alembic::core::TimeSampling::uniform(1.0 / 24.0, 0.0));
+    on_disk.write_archive(&root).expect("Failed to write archive");
+    on_disk.close().expect("Failed to close archive");
+    assert_eq!(std::fs::read(temp.path()).unwrap(), bytes);
+
+    // Never written: an empty archive
+    let empty = OArchive::create_in_memory("empty.abc").unwrap().into_bytes().unwrap();
+    assert_eq!(IArchive::from_bytes("empty.abc", empty).unwrap().getTop().getNumChildren(), 0);
+    let on_disk = OArchive::create(temp.path()).unwrap();
+    assert!(on_disk.into_bytes().is_err());
+}
+
+#[test]
+fn test_child_lookup_by_name() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    
+    // Names deliberately not in sorted order
+    let names: Vec<String> = (0..200).rev().map(|i| format!("child_{:03}", i)).collect();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut parent = OObject::new("parent");
+        for name in &names {
+            parent.add_child(OObject::new(name));
+        }
+        root.add_child(parent);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    assert_eq!(top.child_index("parent"), Some(0));
+    let parent = top.child_by_name("parent").expect("parent");
+    
+    // Children keep file order; index and name lookups agree
+    let read: Vec<String> = parent.getChildren().map(|c| c.getName().to_string()).collect();
+    assert_eq!(read, names);
+    for (i, name) in names.iter().enumerate() {
+        assert_eq!(parent.child_index(name), Some(i));
+        let child = parent.child_by_name(name).expect("child");
+        assert_eq!(child.getFullName(), format!("/parent/{}", name));
+    }
+    assert!(parent.child_by_name("missing").is_none());
+    assert_eq!(parent.child_index("missing"), None);
+}
+
+#[test]
+fn test_sparse_samples_constant_after_first() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    let quad = |y: f32| vec![
+        glam::Vec3::new(0.0, y, 0.0),
+        glam::Vec3::new(1.0, y, 0.0),
+        glam::Vec3::new(1.0, y, 1.0),
+        glam::Vec3::new(0.0, y, 1.0),
+    ];
+    let uvs = vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::ONE, glam::Vec2::Y];
+    let vels = |v: f32| Some(vec![glam::Vec3::splat(v); 4]);
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(alembic::core::TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let mut root = OObject::new("");
+        let mut mesh = OPolyMesh::new("mesh");
+        mesh.set_time_sampling(ts);
+
+        // Topology and UVs only on the first sample, velocities on 0 and 2
+        let mut first = OPolyMeshSample::new(quad(0.0), vec![4], vec![0, 1, 2, 3]);
+        first.uvs = Some(uvs.clone());
+        first.velocities = vels(0.0);
+        mesh.add_sample(&first);
+        for frame in 1..4 {
+            let mut sample = OPolyMeshSample::deformed(quad(frame as f32));
+            if frame == 2 {
+                sample.velocities = vels(2.0);
+            }
+            mesh.add_sample(&sample);
+        }
+        let mut obj = mesh.build();
+
+        // A user property with a single sample next to 4-sample geometry
+        let mut user = OProperty::compound(".userProperties");
+        let mut tag = OProperty::scalar("tag", alembic::util::DataType::new(PlainOldDataType::Int32, 1));
+        tag.time_sampling_index = ts;
+        tag.add_scalar_pod(&7i32);
+        user.add_child(tag);
+        obj.properties.push(user);
+
+        root.add_child(obj);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChild(0).expect("mesh");
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+
+    let p_prop = geom.getPropertyByName("P").expect("P");
+    let p = p_prop.asArray().expect("array");
+    assert_eq!(p.getNumSamples(), 4);
+    assert!(!p.isConstant());
+
+    // Unchanged topology is not written again
+    let fi_prop = geom.getPropertyByName(".faceIndices").expect(".faceIndices");
+    let fi = fi_prop.asArray().expect("array");
+    assert_eq!(fi.getNumSamples(), 1);
+    assert!(fi.isConstant());
+
+    // Held between two written samples: repeated so frame 2 stays at index
+    // 2; held at the end: not written
+    let v_prop = geom.getPropertyByName(".velocities").expect(".velocities");
+    assert_eq!(v_prop.asArray().expect("array").getNumSamples(), 3);
+
+    let mesh = IPolyMesh::new(&obj).expect("IPolyMesh");
+    for frame in 0..4 {
+        let sample = mesh.getSample(frame).expect("sample");
+        assert_eq!(sample.positions[0].y, frame as f32);
+        assert_eq!(sample.face_counts, vec![4]);
+        assert_eq!(sample.face_indices, vec![0, 1, 2, 3]);
+        let v = if frame < 2 { 0.0 } else { 2.0 };
+        assert_eq!(sample.velocities, vels(v));
+        assert_eq!(alembic::geom::util::read_uv_set(&obj, "uv", frame), Some(uvs.clone()));
+    }
+
+    // UVs only written for the first sample
+    let arb_prop = geom.getPropertyByName(".arbGeomParams").expect(".arbGeomParams");
+    let arb = arb_prop.asCompound().expect("compound");
+    let uv_prop = arb.getPropertyByName("uv").expect("uv");
+    let uv = uv_prop.asCompound().expect("compound");
+    let vals_prop = uv.getPropertyByName(".vals").expect(".vals");
+    let vals = vals_prop.asArray().expect("array");
+    assert_eq!(vals.getNumSamples(), 1);
+
+    // Short property: one sample, readable at any object frame
+    let user_prop = props.getPropertyByName(".userProperties").expect("user props");
+    let user = user_prop.asCompound().expect("compound");
+    let tag_prop = user.getPropertyByName("tag").expect("tag");
+    let tag = tag_prop.asScalar().expect("scalar");
+    assert_eq!(tag.getNumSamples(), 1);
+    assert!(tag.isConstant());
+    let mut buf = [0u8; 4];
+    // The raw reader rejects the index; selectors hold the last sample
+    assert!(tag.getSample(3, &mut buf).is_err());
+    let typed = alembic::abc::ITypedScalarProperty::<i32>::new(tag).expect("int32");
+    assert_eq!(typed.get(3).expect("held sample"), 7);
+    assert_eq!(tag.getSampleIndex(3.into()), 0);
+}
+
+#[test]
+fn test_per_property_time_sampling() {
+    use alembic::core::TimeSampling;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts24 = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let ts48 = archive.addTimeSampling(TimeSampling::uniform(1.0 / 48.0, 0.0));
+        let mut root = OObject::new("");
+
+        // Animated P at 24fps, static UVs
+        let mut mesh = OPolyMesh::new("mesh");
+        mesh.set_time_sampling(ts24);
+        mesh.set_property_time_sampling("uv", 0);
+        let tri = |x: f32| vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::X, glam::Vec3::Y];
+        let mut first = OPolyMeshSample::new(tri(0.0), vec![3], vec![0, 1, 2]);
+        first.uvs = Some(vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::Y]);
+        mesh.add_sample(&first);
+        mesh.add_sample(&OPolyMeshSample::deformed(tri(0.5)));
+        root.add_child(mesh.build());
+
+        // Points at 24fps with widths at 48fps (two width samples per frame)
+        let mut points = OPoints::new("points");
+        points.set_time_sampling(ts24);
+        points.set_property_time_sampling(".widths", ts48);
+        for frame in 0..2 {
+            let mut sample = OPointsSample::new(vec![glam::Vec3::ZERO; 2], Vec::new());
+            sample.widths = Some(vec![frame as f32; 2]);
+            points.add_sample(&sample);
+            let widths = points.geom_property_mut(".widths").expect(".widths");
+            widths.add_array_pod(&[frame as f32 + 0.5; 2]);
+        }
+        root.add_child(points.build());
+
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+
+    let mesh = top.getChildByName("mesh").expect("mesh");
+    let props = mesh.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    assert_eq!(geom.getPropertyByName("P").expect("P").getHeader().time_sampling_index, 1);
+    let arb_prop = geom.getPropertyByName(".arbGeomParams").expect(".arbGeomParams");
+    let arb = arb_prop.asCompound().expect("compound");
+    let uv_prop = arb.getPropertyByName("uv").expect("uv");
+    let uv = uv_prop.asCompound().expect("compound");
+    assert_eq!(uv.getPropertyByName(".vals").expect(".vals").getHeader().time_sampling_index, 0);
+
+    let points = top.getChildByName("points").expect("points");
+    let props = points.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    let p = geom.getPropertyByName("P").expect("P");
+    assert_eq!(p.getHeader().time_sampling_index, 1);
+    assert_eq!(p.asArray().expect("array").getNumSamples(), 2);
+    let widths = geom.getPropertyByName(".widths").expect(".widths");
+    assert_eq!(widths.getHeader().time_sampling_index, 2);
+    let widths = widths.asArray().expect("array");
+    assert_eq!(widths.getNumSamples(), 4);
+    let data = widths.getSampleVec(3).expect("widths sample");
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&data), &[1.5, 1.5]);
+}
+
+#[test]
+fn test_time_based_sample_selection() {
+    use alembic::abc::IArrayProperty;
+    use alembic::core::{SampleSelector, TimeSampling};
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        // 24fps starting at frame 24 (t = 1s); widths at 48fps from 0
+        let ts24 = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1.0));
+        let ts48 = archive.addTimeSampling(TimeSampling::uniform(1.0 / 48.0, 0.0));
+        let mut root = OObject::new("");
+
+        let mut mesh = OPolyMesh::new("mesh");
+        mesh.set_time_sampling(ts24);
+        let tri = |x: f32| vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::X, glam::Vec3::Y];
+        for frame in 0..3 {
+            mesh.add_sample(&OPolyMeshSample::new(tri(frame as f32), vec![3], vec![0, 1, 2]));
+        }
+        root.add_child(mesh.build());
+
+        let mut points = OPoints::new("points");
+        points.set_property_time_sampling(".widths", ts48);
+        let mut sample = OPointsSample::new(vec![glam::Vec3::ZERO; 2], Vec::new());
+        sample.widths = Some(vec![0.0; 2]);
+        points.add_sample(&sample);
+        let widths = points.geom_property_mut(".widths").expect(".widths");
+        for i in 1..4 {
+            widths.add_array_pod(&[i as f32; 2]);
+        }
+        root.add_child(points.build());
+
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+
+    let mesh_obj = top.getChildByName("mesh").expect("mesh");
+    let mesh = IPolyMesh::new(&mesh_obj).expect("IPolyMesh");
+    let x = |sel: SampleSelector| mesh.getSample(sel).expect("sample").positions[0].x;
+    let t = |frame: f64| frame / 24.0;
+    assert_eq!(x(SampleSelector::TimeFloor(t(25.5))), 1.0);
+    assert_eq!(x(SampleSelector::TimeCeil(t(25.5))), 2.0);
+    assert_eq!(x(SampleSelector::TimeNear(t(25.4))), 1.0);
+    assert_eq!(x(SampleSelector::TimeNear(t(25.6))), 2.0);
+    // Out of range times and indices clamp to the first/last sample
+    assert_eq!(x(SampleSelector::TimeFloor(0.0)), 0.0);
+    assert_eq!(x(SampleSelector::TimeCeil(t(100.0))), 2.0);
+    assert_eq!(x(SampleSelector::Index(7)), 2.0);
+    assert_eq!(mesh.getSample(1).expect("sample").positions[0].x, 1.0);
+
+    let props = mesh_obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    let p_prop = geom.getPropertyByName("P").expect("P");
+    let p = IArrayProperty::new(p_prop.asArray().expect("array"));
+    assert_eq!(p.getTimeSampling().expect("time sampling").sample_time(2, 3), t(26.0));
+    let data = p.read_sample_vec(SampleSelector::TimeNear(t(24.9))).expect("P sample");
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&data)[0], 1.0);
+
+    // A property on its own time sampling resolves against that, not the schema's
+    let points = top.getChildByName("points").expect("points");
+    let props = points.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    let widths_prop = geom.getPropertyByName(".widths").expect(".widths");
+    let widths = IArrayProperty::new(widths_prop.asArray().expect("array"));
+    let data = widths.read_sample_vec(SampleSelector::TimeFloor(2.5 / 48.0)).expect("widths sample");
+    assert_eq!(bytemuck::cast_slice::<u8, f32>(&data), &[2.0, 2.0]);
+
+    // ...and so does the schema sample: P holds its only sample, widths
+    // follow their own 48fps sampling
+    let ipoints = IPoints::new(&points).expect("IPoints");
+    let sample = ipoints.getSample(SampleSelector::TimeFloor(2.5 / 48.0)).expect("points sample");
+    assert_eq!(sample.positions.len(), 2);
+    assert_eq!(sample.widths, vec![2.0, 2.0]);
+    let sample = ipoints.getSample(SampleSelector::TimeNear(1.0)).expect("points sample");
+    assert_eq!(sample.widths, vec![3.0, 3.0]);
+    assert_eq!(ipoints.getSample(1).expect("points sample").widths, vec![1.0, 1.0]);
+}
+
+#[test]
+fn test_roundtrip_skin_data() {
+    use alembic::geom::SkinSample;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    let positions = vec![
+        glam::Vec3::new(0.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 1.0, 0.0),
+        glam::Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let skin = SkinSample {
+        joint_indices: vec![0, 1, 0, 1, 1, 0, 1, 0],
+        joint_weights: vec![1.0, 0.0, 0.5, 0.5, 0.8, 0.2, 1.0, 0.0],
+        influences: 2,
+        rest_positions: Some(positions.clone()),
+        bind_matrices: vec![
+            glam::Mat4::IDENTITY,
+            glam::Mat4::from_translation(glam::Vec3::new(0.0, -1.0, 0.0)),
+        ],
+        geom_bind_transform: Some(glam::Mat4::from_scale(glam::Vec3::splat(2.0))),
+    };
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut mesh = OPolyMesh::new("skinned");
+        let mut sample = OPolyMeshSample::new(positions.clone(), vec![4], vec![0, 1, 2, 3]);
+        sample.uvs = Some(vec![glam::Vec2::ZERO; 4]);
+        mesh.add_sample(&sample);
+        for param in skin.arb_geom_params() {
+            mesh.add_arb_geom_param(&param);
+        }
+        for prop in skin.user_properties() {
+            mesh.add_user_property(prop);
+        }
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let check = |path: &std::path::Path| {
+        let archive = IArchive::open(path).expect("Failed to open archive");
+        let top = archive.getTop();
+        let obj = top.getChildByName("skinned").expect("mesh");
+        let mesh = IPolyMesh::new(&obj).expect("IPolyMesh");
+        let names = mesh.arb_geom_param_names();
+        for name in ["uv", "jointIndices", "jointWeights", "Pref"] {
+            assert!(names.iter().any(|n| n == name), "missing {name} in {names:?}");
+        }
+        assert_eq!(mesh.skin_sample(0).expect("skin"), skin);
+        assert_eq!(mesh.rest_positions(0).expect("Pref"), positions);
+    };
+    check(path);
+}
+
+#[test]
+fn test_roundtrip_sampled_arb_geom_params() {
+    use alembic::core::GeometryScope;
+    use alembic::geom::GeomParamSample;
+    use alembic::util::DataType;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    let positions = vec![
+        glam::Vec3::new(0.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        glam::Vec3::new(1.0, 1.0, 0.0),
+        glam::Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let colors = |t: f32| vec![[t, 0.0, 0.0]; 4];
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut mesh = OPolyMesh::new("attrs");
+        for frame in 0..3 {
+            let mut sample = if frame == 0 {
+                OPolyMeshSample::new(positions.clone(), vec![4], vec![0, 1, 2, 3])
+            } else {
+                OPolyMeshSample::deformed(positions.clone())
+            };
+            // Cd changes every sample; st and temperature only on the first
+            sample.set_arb_geom_param(
+                "Cd",
+                GeomParamSample::from_values(DataType::VEC3F, &colors(frame as f32), None, GeometryScope::Vertex),
+            );
+            if frame == 0 {
+                sample.set_arb_geom_param(
+                    "st",
+                    GeomParamSample::from_values(
+                        DataType::VEC2F,
+                        &[[0.0f32, 0.0], [1.0, 1.0]],
+                        Some(vec![0, 1, 1, 0]),
+                        GeometryScope::FaceVarying,
+                    ),
+                );
+                sample.set_arb_geom_param(
+                    "temperature",
+                    GeomParamSample::from_values(DataType::FLOAT32, &[36.6f32], None, GeometryScope::Uniform),
+                );
+            }
+            mesh.add_sample(&sample);
+        }
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let check = |path: &std::path::Path| {
+        let archive = IArchive::open(path).expect("Failed to open archive");
+        let top = archive.getTop();
+        let obj = top.getChildByName("attrs").expect("mesh");
+        let mesh = IPolyMesh::new(&obj).expect("IPolyMesh");
+        assert_eq!(mesh.getNumSamples(), 3);
+        for frame in 0..3 {
+            let cd = mesh.arb_geom_param("Cd", frame).expect("Cd");
+            assert_eq!(cd.scope, GeometryScope::Vertex);
+            assert!(!cd.is_indexed);
+            assert_eq!(cd.values_as_f32(), colors(frame as f32).concat().as_slice());
+
+            let st = mesh.arb_geom_param("st", frame).expect("st");
+            assert_eq!(st.scope, GeometryScope::FaceVarying);
+            assert_eq!(st.indices.as_deref(), Some(&[0u32, 1, 1, 0][..]));
+            assert_eq!(st.expand_vec2()[1], glam::Vec2::ONE);
+
+            let temperature = mesh.arb_geom_param("temperature", frame).expect("temperature");
+            assert_eq!(temperature.scope, GeometryScope::Uniform);
+            assert_eq!(temperature.values_as_f32(), &[36.6]);
+        }
+    };
+    check(path);
+}
+
 #[test]
 fn test_roundtrip_polymesh_triangle() {
     let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -278,6 +762,189 @@ fn test_roundtrip_xform() {
     }
 }
 
+#[test]
+fn test_identical_samples_read_back_constant() {
+    use alembic::core::{TimeSampling, TopologyVariance};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    let tri = |y: f32| vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::new(0.0, y, 0.0)];
+
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+
+        let mut xform = OXform::new("xform");
+        xform.set_time_sampling(ts);
+        let mut still = OPolyMesh::new("still");
+        still.set_time_sampling(ts);
+        let mut moving = OPolyMesh::new("moving");
+        moving.set_time_sampling(ts);
+        let mut points = OPoints::new("points");
+        points.set_time_sampling(ts);
+        // Same positions every frame, but the particle ids change
+        let mut respawn = OPoints::new("respawn");
+        respawn.set_time_sampling(ts);
+        for frame in 0..10 {
+            xform.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::X), true));
+            still.add_sample(&OPolyMeshSample::new(tri(1.0), vec![3], vec![0, 1, 2]));
+            moving.add_sample(&OPolyMeshSample::new(tri(1.0 + frame as f32), vec![3], vec![0, 1, 2]));
+            points.add_sample(&OPointsSample::new(vec![glam::Vec3::ZERO; 2], vec![0, 1]));
+            let ids = if frame == 0 { vec![0, 1] } else { vec![5, 9] };
+            respawn.add_sample(&OPointsSample::new(vec![glam::Vec3::ZERO; 2], ids));
+        }
+        assert!(xform.isConstant());
+        assert!(still.isConstant());
+        assert!(!moving.isConstant());
+        assert!(points.isConstant());
+        assert!(!respawn.isConstant());
+
+        xform.add_child(still.build());
+        xform.add_child(moving.build());
+        xform.add_child(points.build());
+        xform.add_child(respawn.build());
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    // Only one stored sample per constant property, and the time sampling
+    // records the animated mesh
+    assert_eq!(archive.getMaxNumSamplesForTimeSamplingIndex(1), Some(10));
+
+    let root = archive.getTop();
+    let xform_obj = root.getChildByName("xform").unwrap();
+    let xform = IXform::new(&xform_obj).unwrap();
+    // Sample count is kept (C++ nextSampleIndex); every index reads the stored value
+    assert_eq!(xform.getNumSamples(), 10);
+    assert!(xform.isConstant());
+    assert_eq!(xform.getSample(9).unwrap().translation(), glam::Vec3::X);
+
+    let still_obj = xform_obj.getChildByName("still").unwrap();
+    let still = IPolyMesh::new(&still_obj).unwrap();
+    assert_eq!(still.getNumSamples(), 10);
+    assert!(still.isConstant());
+    assert_eq!(still.topology_variance(), TopologyVariance::Static);
+    assert_eq!(still.getSample(7).unwrap().positions, tri(1.0));
+
+    let moving_obj = xform_obj.getChildByName("moving").unwrap();
+    let moving = IPolyMesh::new(&moving_obj).unwrap();
+    assert!(!moving.isConstant());
+    assert_eq!(moving.topology_variance(), TopologyVariance::Homogeneous);
+
+    let points_obj = xform_obj.getChildByName("points").unwrap();
+    let points = IPoints::new(&points_obj).unwrap();
+    assert!(points.isConstant());
+    assert_eq!(points.topology_variance(), TopologyVariance::Static);
+
+    let respawn_obj = xform_obj.getChildByName("respawn").unwrap();
+    let respawn = IPoints::new(&respawn_obj).unwrap();
+    assert!(!respawn.isConstant());
+    assert_eq!(respawn.topology_variance(), TopologyVariance::Heterogeneous);
+    assert_eq!(respawn.getSample(3).unwrap().ids, vec![5, 9]);
+}
+
+#[test]
+fn test_roundtrip_xform_op_stack() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+
+    // Maya-style stack: translate, rotate pivot, rotateY, inverse rotate pivot, scale
+    let ops = |angle: f64| vec![
+        XformOp::translate(1.0, 2.0, 3.0),
+        XformOp::translate(0.5, 0.0, 0.0).with_hint(XformOp::ROTATE_PIVOT_POINT_HINT),
+        XformOp::rotate_y(angle),
+        XformOp::translate(-0.5, 0.0, 0.0).with_hint(XformOp::ROTATE_PIVOT_POINT_HINT),
+        XformOp::scale(2.0, 2.0, 2.0),
+    ];
+
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut xform = OXform::new("transform");
+        xform.add_sample(OXformSample::from_ops(ops(0.0), true));
+        xform.add_sample(OXformSample::from_ops(ops(90.0), true));
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let root = archive.getTop();
+    let obj = root.getChildByName("transform").expect("Should find transform");
+    let xform = IXform::new(&obj).expect("Should be IXform");
+    assert_eq!(xform.getNumSamples(), 2);
+
+    let sample = xform.getSample(1).expect("Should read sample");
+    let types: Vec<XformOpType> = sample.ops.iter().map(|op| op.op_type).collect();
+    assert_eq!(types, vec![
+        XformOpType::Translate, XformOpType::Translate, XformOpType::RotateY,
+        XformOpType::Translate, XformOpType::Scale,
+    ]);
+    let hints: Vec<u8> = sample.ops.iter().map(|op| op.hint).collect();
+    assert_eq!(hints, vec![0, 3, 0, 3, 0]);
+    assert_eq!(sample.ops[1].channel_name(0), "rotatePivotX");
+    assert_eq!(sample.ops[2].channel_name(0), "rotateY");
+    assert_eq!(sample.ops[2].values, vec![90.0]);
+
+    // Only the rotateY channel (index 6 in the flattened values) animates
+    assert_eq!(xform.animated_channels(), vec![6]);
+
+    // Re-writing from the read samples keeps the stack
+    let rewritten = OXformSample::from_sample(&sample);
+    assert_eq!(rewritten.ops.len(), 5);
+    assert_eq!(rewritten.ops[3].hint, XformOp::ROTATE_PIVOT_POINT_HINT);
+}
+
+#[test]
+fn test_object_and_property_hashes() {
+    fn write(path: &std::path::Path, y: f32) {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::new(0.0, y, 0.0)],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+        let mut xform = OXform::new("geo");
+        xform.add_sample(OXformSample::identity());
+        // No .childBnds, so the Xform's own properties don't depend on the mesh
+        xform.set_compute_child_bounds(false);
+        xform.add_child(mesh.build());
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let (a, b, c) = (NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap(), NamedTempFile::new().unwrap());
+    write(a.path(), 1.0);
+    write(b.path(), 1.0);
+    write(c.path(), 2.0);
+    let (a, b, c) = (
+        IArchive::open(a.path()).unwrap(),
+        IArchive::open(b.path()).unwrap(),
+        IArchive::open(c.path()).unwrap(),
+    );
+    let (top_a, top_b, top_c) = (a.getTop(), b.getTop(), c.getTop());
+    assert!(top_a.children_hash().is_some());
+    assert_eq!(top_a.children_hash(), top_b.children_hash());
+    assert_ne!(top_a.children_hash(), top_c.children_hash());
+
+    // The Xform itself is unchanged; only the mesh below it differs
+    let (geo_a, geo_c) = (top_a.getChildByName("geo").unwrap(), top_c.getChildByName("geo").unwrap());
+    assert!(geo_a.properties_hash().is_some());
+    assert_eq!(geo_a.properties_hash(), geo_c.properties_hash());
+    assert_ne!(geo_a.children_hash(), geo_c.children_hash());
+
+    let (tri_a, tri_c) = (geo_a.getChildByName("tri").unwrap(), geo_c.getChildByName("tri").unwrap());
+    assert_ne!(tri_a.properties_hash(), tri_c.properties_hash());
+    let (props_a, props_c) = (tri_a.getProperties(), tri_c.getProperties());
+    let (geom_a, geom_c) = (props_a.getPropertyByName(".geom").unwrap(), props_c.getPropertyByName(".geom").unwrap());
+    let (geom_a, geom_c) = (geom_a.asCompound().unwrap(), geom_c.asCompound().unwrap());
+    let digest = |g: &ICompoundProperty, name: &str| g.getPropertyByName(name).unwrap().digest().unwrap();
+    assert_ne!(digest(&geom_a, "P"), digest(&geom_c, "P"));
+    assert_eq!(digest(&geom_a, ".faceIndices"), digest(&geom_c, ".faceIndices"));
+}
+
 #[test]
 fn test_roundtrip_animated_mesh() {
     let temp = NamedTempFile::new().expect("Failed to create temp file");
@@ -711,6 +1378,7 @@ fn convert_object(
                         ids: sample.ids.iter().map(|&id| id as i64).collect(),
                         velocities: vec_to_opt(&sample.velocities),
                         widths: vec_to_opt(&sample.widths),
+                        arb_geom_params: Vec::new(),
                     };
                     opoints.add_sample(&out_sample);
                 }
@@ -733,17 +1401,12 @@ fn convert_object(
             
             for i in 0..num_samples {
                 if let Ok(sample) = subd.getSample(i) {
-                    // Convert SubDScheme enum to string
-                    let scheme_str = match sample.scheme {
-                        alembic::geom::SubDScheme::CatmullClark => "catmullClark",
-                        alembic::geom::SubDScheme::Loop => "loop",
-                        alembic::geom::SubDScheme::Bilinear => "bilinear",
-                    };
                     let out_sample = OSubDSample {
                         positions: sample.positions.clone(),
                         face_counts: sample.face_counts.clone(),
                         face_indices: sample.face_indices.clone(),
-                        subdivision_scheme: scheme_str.to_string(),
+                        keep_topology: false,
+                        subdivision_scheme: sample.scheme.as_str().to_string(),
                         velocities: sample.velocities.clone(),
                         uvs: sample.uvs.clone(),
                         uv_indices: sample.uv_indices.clone(),
@@ -755,6 +1418,9 @@ fn convert_object(
                         holes: vec_to_opt(&sample.holes),
                         normals: sample.normals.clone(),
                         normal_indices: sample.normal_indices.clone(),
+                        interp_boundary: Some(sample.interp_boundary),
+                        fv_interp_boundary: Some(sample.fv_interp_boundary),
+                        fv_propagate_corners: Some(sample.fv_propagate_corners),
                     };
                     osubd.add_sample(&out_sample);
                 }
@@ -1054,6 +1720,9 @@ fn test_deduplication() {
         
         archive.write_archive(&root).expect("Failed to write");
         println!("Dedup count: {}", archive.dedup_count());
+        // mesh1..mesh4 reuse all three blocks written for mesh0
+        assert_eq!(archive.dedup_hits(), 4 * 3);
+        assert!(archive.dedup_bytes_saved() > 0);
     }
     
     // Write with deduplication disabled
@@ -1082,6 +1751,7 @@ fn test_deduplication() {
         }
         
         archive.write_archive(&root).expect("Failed to write");
+        assert_eq!(archive.dedup_hits(), 0);
     }
     
     // Compare file sizes - deduplicated should be smaller
@@ -1112,6 +1782,36 @@ fn test_deduplication() {
     println!("Deduplication test PASSED!");
 }
 
+#[test]
+fn test_static_mesh_samples_do_not_grow_file() {
+    use std::fs;
+
+    let write = |num_samples: usize| -> u64 {
+        let temp = NamedTempFile::new().expect("Failed to create temp file");
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create");
+        let mut mesh = OPolyMesh::new("static");
+        let positions: Vec<glam::Vec3> = (0..1000).map(|i| glam::Vec3::splat(i as f32)).collect();
+        let indices: Vec<i32> = (0..999).flat_map(|i| [0, i, i + 1]).collect();
+        // Alternate between two poses so repeats are not merely consecutive
+        for frame in 0..num_samples {
+            let mut pts = positions.clone();
+            if frame % 2 == 1 {
+                pts[0] = glam::Vec3::ONE;
+            }
+            mesh.add_sample(&OPolyMeshSample::new(pts, vec![3; 999], indices.clone()));
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write");
+        fs::metadata(temp.path()).unwrap().len()
+    };
+
+    let two = write(2);
+    let many = write(100);
+    // Only the per-sample offsets and the sample hash grow; no payload is rewritten
+    assert!(many - two < 100 * 64, "2 samples: {} bytes, 100 samples: {} bytes", two, many);
+}
+
 #[test]
 fn test_cyclic_time_sampling() {
     use std::fs;
@@ -1956,24 +2656,98 @@ fn test_roundtrip_camera_custom() {
 }
 
 #[test]
-fn test_roundtrip_mixed_scene() {
-    // Test a scene with multiple geometry types under an xform hierarchy
+fn test_roundtrip_camera_film_back() {
     let tmp = NamedTempFile::new().unwrap();
     let path = tmp.path();
 
+    let sample_at = |t: f64| {
+        let mut sample = CameraSample {
+            focal_length: 50.0,
+            horizontal_aperture: 3.6,
+            horizontal_film_offset: 0.12,
+            vertical_aperture: 2.0,
+            vertical_film_offset: -0.05,
+            lens_squeeze_ratio: 2.0,
+            overscan_left: 0.1,
+            overscan_right: 0.2,
+            overscan_top: 0.3,
+            overscan_bottom: 0.4,
+            f_stop: 2.8,
+            focus_distance: 7.5 + t,
+            near_clipping_plane: 0.5,
+            far_clipping_plane: 5000.0,
+            ..Default::default()
+        };
+        sample.add_op(FilmBackXformOp::translate(t, -t).with_hint("filmTranslate"));
+        sample.add_op(FilmBackXformOp::scale(1.0 + t, 1.0).with_hint("preScale"));
+        sample
+    };
+
     {
         let mut archive = OArchive::create(path).unwrap();
         let mut root = OObject::new("");
-
-        // Camera at top level
-        let mut camera = OCamera::new("camera1");
-        camera.add_sample(CameraSample::default());
+        let mut camera = OCamera::new("shotcam");
+        camera.add_sample(sample_at(0.0));
+        camera.add_sample(sample_at(0.5));
         root.add_child(camera.build());
+        archive.write_archive(&root).unwrap();
+    }
 
-        // Xform > PolyMesh
-        let mut xform = OXform::new("group1");
-        let xsample = OXformSample::from_matrix(
-            glam::Mat4::from_translation(glam::Vec3::new(1.0, 2.0, 3.0)),
+    let archive = IArchive::open(path).unwrap();
+    let root = archive.getTop();
+    let child = root.getChild(0).unwrap();
+    let camera = ICamera::new(&child).unwrap();
+    assert_eq!(camera.getNumSamples(), 2);
+    for (index, t) in [(0, 0.0), (1, 0.5)] {
+        let sample = camera.getSample(index).unwrap();
+        let expected = sample_at(t);
+        assert_eq!(sample.horizontal_aperture, 3.6);
+        assert_eq!(sample.horizontal_film_offset, 0.12);
+        assert_eq!(sample.vertical_aperture, 2.0);
+        assert_eq!(sample.vertical_film_offset, -0.05);
+        assert_eq!(sample.lens_squeeze_ratio, 2.0);
+        assert_eq!(
+            [sample.overscan_left, sample.overscan_right, sample.overscan_top, sample.overscan_bottom],
+            [0.1, 0.2, 0.3, 0.4]
+        );
+        assert_eq!(sample.f_stop, 2.8);
+        assert_eq!(sample.focus_distance, 7.5 + t);
+        assert_eq!(sample.near_clipping_plane, 0.5);
+        assert_eq!(sample.far_clipping_plane, 5000.0);
+        assert!((sample.field_of_view() - expected.field_of_view()).abs() < 1e-12);
+
+        assert_eq!(sample.num_ops(), 2);
+        let translate = sample.get_op(0).unwrap();
+        assert!(translate.is_translate_op());
+        assert_eq!(translate.hint(), "filmTranslate");
+        assert_eq!(translate.translate_value(), (t, -t));
+        let scale = sample.get_op(1).unwrap();
+        assert!(scale.is_scale_op());
+        assert_eq!(scale.hint(), "preScale");
+        assert_eq!(scale.scale_value(), (1.0 + t, 1.0));
+        assert_eq!(sample.screen_window(), expected.screen_window());
+    }
+}
+
+#[test]
+fn test_roundtrip_mixed_scene() {
+    // Test a scene with multiple geometry types under an xform hierarchy
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path();
+
+    {
+        let mut archive = OArchive::create(path).unwrap();
+        let mut root = OObject::new("");
+
+        // Camera at top level
+        let mut camera = OCamera::new("camera1");
+        camera.add_sample(CameraSample::default());
+        root.add_child(camera.build());
+
+        // Xform > PolyMesh
+        let mut xform = OXform::new("group1");
+        let xsample = OXformSample::from_matrix(
+            glam::Mat4::from_translation(glam::Vec3::new(1.0, 2.0, 3.0)),
             true,
         );
         xform.add_sample(xsample);
@@ -2386,6 +3160,7 @@ fn test_roundtrip_points_varying_widths() {
             ids: vec![0, 1, 2, 3, 4],
             velocities: Some(velocities_0.clone()),
             widths: Some(widths_0.clone()),
+            arb_geom_params: Vec::new(),
         });
         
         // Add frame 1
@@ -2394,6 +3169,7 @@ fn test_roundtrip_points_varying_widths() {
             ids: vec![0, 1, 2, 3, 4],
             velocities: Some(velocities_1.clone()),
             widths: Some(widths_1.clone()),
+            arb_geom_params: Vec::new(),
         });
         
         let mut root = OObject::new("");
@@ -2662,3 +3438,2427 @@ fn test_roundtrip_all_pod_types() {
         }
     }
 }
+
+#[test]
+fn test_export_gltf() {
+    use alembic::core::GeometryScope;
+    use alembic::export::{GltfDocument, GltfOptions};
+    use alembic::geom::OGeomParam;
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("quad");
+        let mut sample = OPolyMeshSample::new(
+            vec![
+                glam::Vec3::new(0.0, 0.0, 0.0),
+                glam::Vec3::new(1.0, 0.0, 0.0),
+                glam::Vec3::new(1.0, 1.0, 0.0),
+                glam::Vec3::new(0.0, 1.0, 0.0),
+            ],
+            vec![4],
+            vec![0, 1, 2, 3],
+        );
+        sample.uvs = Some(vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::ONE, glam::Vec2::Y]);
+        mesh.add_sample(&sample);
+        let mut st2 = OGeomParam::vec2f("st2", GeometryScope::FaceVarying);
+        st2.add_values(&[glam::Vec2::splat(0.5); 4]);
+        mesh.add_arb_geom_param(&st2);
+
+        let mut xform = OXform::new("group");
+        xform.add_sample(OXformSample::from_matrix(
+            glam::Mat4::from_translation(glam::Vec3::new(0.0, 2.0, 0.0)),
+            true,
+        ));
+        xform.add_child(mesh.build());
+
+        let mut camera = OCamera::new("cam");
+        camera.add_sample(CameraSample::default());
+
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        root.add_child(camera.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let doc = GltfDocument::build(&archive, &GltfOptions::default());
+    assert_eq!(doc.stats.nodes, 3);
+    assert_eq!(doc.stats.meshes, 1);
+    assert_eq!(doc.stats.cameras, 1);
+    assert_eq!(doc.stats.triangles, 2);
+    assert_eq!(doc.stats.max_uv_sets, 2);
+
+    let json = &doc.json;
+    let group = &json["nodes"][0];
+    assert_eq!(group["name"], "group");
+    assert_eq!(group["matrix"][13], 2.0);
+    let quad = &json["nodes"][group["children"][0].as_u64().unwrap() as usize];
+    assert_eq!(quad["extras"]["path"], "/group/quad");
+    let primitive = &json["meshes"][quad["mesh"].as_u64().unwrap() as usize]["primitives"][0];
+    for attr in ["POSITION", "NORMAL", "TEXCOORD_0", "TEXCOORD_1"] {
+        assert!(primitive["attributes"][attr].is_u64(), "missing {attr}");
+    }
+    let positions = &json["accessors"][primitive["attributes"]["POSITION"].as_u64().unwrap() as usize];
+    assert_eq!(positions["count"], 4);
+    assert_eq!(positions["max"][1], 1.0);
+    assert_eq!(json["accessors"][primitive["indices"].as_u64().unwrap() as usize]["count"], 6);
+    assert!(json["cameras"][0]["perspective"]["yfov"].as_f64().unwrap() > 0.0);
+}
+
+#[test]
+fn test_export_obj_sequence() {
+    use alembic::core::TimeSampling;
+    use alembic::export::{export_obj_sequence, ObjOptions};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        // 24 fps starting at frame 1001
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1001.0 / 24.0));
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.set_time_sampling(ts);
+        for frame in 0..3 {
+            let x = frame as f32;
+            let mut sample = OPolyMeshSample::new(
+                vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::new(x + 1.0, 0.0, 0.0), glam::Vec3::new(x, 1.0, 0.0)],
+                vec![3],
+                vec![0, 1, 2],
+            );
+            sample.uvs = Some(vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::Y]);
+            sample.normals = Some(vec![glam::Vec3::Z; 3]);
+            mesh.add_sample(&sample);
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let dir = tempfile::tempdir().expect("temp dir");
+    let options = ObjOptions { frames: Some(1002..=1003), ..Default::default() };
+    let stats = export_obj_sequence(&archive, dir.path().join("shot.####.obj"), &options)
+        .expect("export obj");
+    let names: Vec<_> = stats.files.iter()
+        .map(|f| f.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, ["shot.1002.obj", "shot.1003.obj"]);
+    assert_eq!(stats.meshes, 2);
+
+    let text = std::fs::read_to_string(&stats.files[1]).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.contains(&"o /tri"));
+    assert!(lines.contains(&"v 2 0 0"), "{text}");
+    assert_eq!(lines.iter().filter(|l| l.starts_with("vt ")).count(), 3);
+    assert!(lines.contains(&"vn 0 0 1"));
+    assert!(lines.contains(&"f 3/3/1 2/2/1 1/1/1"), "{text}");
+
+    // Out-of-range frames are an error
+    let empty = ObjOptions { frames: Some(1..=10), ..Default::default() };
+    assert!(export_obj_sequence(&archive, dir.path().join("x.obj"), &empty).is_err());
+}
+
+#[test]
+fn test_export_usda() {
+    use alembic::core::TimeSampling;
+    use alembic::export::{write_usda, UsdOptions};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1.0 / 24.0));
+
+        let mut mesh = OPolyMesh::new("body");
+        let mut sample = OPolyMeshSample::new(
+            vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y],
+            vec![3],
+            vec![0, 1, 2],
+        );
+        sample.uvs = Some(vec![glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::Y]);
+        sample.normals = Some(vec![glam::Vec3::Z; 3]);
+        mesh.add_sample(&sample);
+
+        let mut xform = OXform::new("rig");
+        xform.set_time_sampling(ts);
+        for frame in 0..2 {
+            xform.add_sample(OXformSample::from_matrix(
+                glam::Mat4::from_translation(glam::Vec3::new(frame as f32, 0.0, 0.0)),
+                true,
+            ));
+        }
+        xform.add_child(mesh.build());
+
+        let mut camera = OCamera::new("cam");
+        camera.add_sample(CameraSample { focal_length: 50.0, horizontal_aperture: 3.6, ..Default::default() });
+
+        let mut points = OPoints::new("1 fx");
+        let mut fx = OPointsSample::new(vec![glam::Vec3::ZERO, glam::Vec3::ONE], vec![7, 9]);
+        fx.widths = Some(vec![0.5, 0.5]);
+        points.add_sample(&fx);
+
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        root.add_child(camera.build());
+        root.add_child(points.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let (text, stats) = write_usda(&archive, &UsdOptions::default());
+    assert_eq!((stats.prims, stats.xforms, stats.meshes, stats.cameras, stats.points), (4, 1, 1, 1, 1));
+    assert_eq!(stats.fps, 24.0);
+    assert_eq!(stats.time_range, Some((1.0, 2.0)));
+
+    assert!(text.starts_with("#usda 1.0\n"));
+    assert!(text.contains("startTimeCode = 1\n"), "{text}");
+    assert!(text.contains("timeCodesPerSecond = 24\n"));
+    assert!(!text.contains("defaultPrim"));
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    for expected in [
+        "def Xform \"rig\"",
+        "matrix4d xformOp:transform.timeSamples = {",
+        "2: ( (1, 0, 0, 0), (0, 1, 0, 0), (0, 0, 1, 0), (1, 0, 0, 1) ),",
+        "uniform token[] xformOpOrder = [\"xformOp:transform\"]",
+        "def Mesh \"body\"",
+        "uniform token orientation = \"leftHanded\"",
+        "int[] faceVertexIndices = [0, 1, 2]",
+        "point3f[] points = [(0, 0, 0), (1, 0, 0), (0, 1, 0)]",
+        "texCoord2f[] primvars:st = [(0, 0), (1, 0), (0, 1)] (interpolation = \"faceVarying\")",
+        "def Camera \"cam\"",
+        "float focalLength = 50",
+        "float horizontalAperture = 36",
+        "def Points \"_1_fx\" (",
+        "customData = { string abcPath = \"/1 fx\" }",
+        "float[] widths = [0.5, 0.5] (interpolation = \"vertex\")",
+        "int64[] ids = [7, 9]",
+    ] {
+        assert!(lines.contains(&expected), "missing `{expected}` in:\n{text}");
+    }
+    assert!(lines.iter().any(|l| l.starts_with("normal3f[] normals = ")));
+    // Mesh is nested under the xform
+    let rig = text.find("def Xform \"rig\"").unwrap();
+    let body = text.find("def Mesh \"body\"").unwrap();
+    let cam = text.find("def Camera").unwrap();
+    assert!(rig < body && body < cam);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_roundtrip_derive_abc_struct() {
+    use alembic::geom::{read_arb, read_user, AbcStruct};
+
+    #[derive(AbcStruct, Clone, Debug, Default, PartialEq)]
+    struct Pipeline {
+        department: String,
+        approved: bool,
+    }
+
+    #[derive(AbcStruct, Clone, Debug, Default, PartialEq)]
+    struct AssetInfo {
+        #[abc(rename = "assetName")]
+        name: String,
+        version: i32,
+        pivot: glam::Vec3,
+        bind: glam::Mat4,
+        tags: Vec<String>,
+        lod: Option<u8>,
+        pipeline: Pipeline,
+        #[abc(skip)]
+        cached: usize,
+    }
+
+    #[derive(AbcStruct, Debug, PartialEq)]
+    struct Mask {
+        #[abc(scope = "vtx")]
+        mask: Vec<f32>,
+        id: i32,
+    }
+
+    let frames = [
+        AssetInfo {
+            name: "crate_a".into(),
+            version: 3,
+            pivot: glam::Vec3::new(0.0, 0.5, 0.0),
+            bind: glam::Mat4::from_scale(glam::Vec3::splat(2.0)),
+            tags: vec!["prop".into(), "hero".into()],
+            lod: Some(1),
+            pipeline: Pipeline { department: "model".into(), approved: true },
+            cached: 42,
+        },
+        AssetInfo {
+            name: "crate_a".into(),
+            version: 4,
+            lod: None,
+            ..Default::default()
+        },
+    ];
+    let mask = Mask { mask: vec![0.0, 0.25, 0.5, 1.0], id: 7 };
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("asset");
+        let positions = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::ONE, glam::Vec3::Y];
+        for info in &frames {
+            mesh.add_sample(&OPolyMeshSample::new(positions.clone(), vec![4], vec![0, 1, 2, 3]));
+            mesh.write_user(info);
+        }
+        mesh.write_arb(&mask);
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("asset").expect("asset");
+
+    let first: AssetInfo = read_user(&obj, 0).expect("read frame 0");
+    assert_eq!(first, AssetInfo { cached: 0, ..frames[0].clone() });
+    let second: AssetInfo = read_user(&obj, 1).expect("read frame 1");
+    assert_eq!(second.version, 4);
+    assert!(second.tags.is_empty(), "{:?}", second.tags);
+    // lod was None on frame 1: the previous sample is repeated
+    assert_eq!(second.lod, Some(1));
+
+    let read_mask: Mask = read_arb(&obj, 0).expect("read arb");
+    assert_eq!(read_mask, mask);
+    let mesh = IPolyMesh::new(&obj).expect("IPolyMesh");
+    let param = mesh.arb_geom_param("mask", 0).expect("mask is a geom param");
+    assert_eq!(param.scope, alembic::core::GeometryScope::Vertex);
+
+    // Type mismatches are reported, not reinterpreted
+    #[derive(AbcStruct, Debug)]
+    struct Wrong {
+        #[allow(dead_code)]
+        version: f32,
+    }
+    assert!(read_user::<Wrong>(&obj, 0).is_err());
+}
+
+#[test]
+fn test_sample_cache_interpolated_scrub() {
+    use alembic::abc::IArrayProperty;
+    use alembic::core::{SampleSelector, TimeSampling};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0, 0.0));
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.set_time_sampling(ts);
+        for frame in 0..4 {
+            let x = frame as f32 * 2.0;
+            mesh.add_sample(&OPolyMeshSample::new(
+                vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::new(x + 1.0, 0.0, 0.0), glam::Vec3::new(x, 1.0, 0.0)],
+                vec![3],
+                vec![0, 1, 2],
+            ));
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    archive.set_cache_limits(1 << 20, 2);
+    archive.clear_cache();
+    let top = archive.getTop();
+    let tri = top.getChildByName("tri").expect("tri");
+    let props = tri.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect(".geom is a compound");
+    let p_prop = geom.getPropertyByName("P").expect("P");
+    let p = IArrayProperty::new(p_prop.asArray().expect("P is an array"));
+    let ts = archive.getTimeSampling(p.getTimeSamplingIndex() as usize).expect("ts").clone();
+
+    let values = p.read_interpolated_f32(SampleSelector::TimeFloor(1.25), &ts).expect("interpolate");
+    assert!((values[0] - 2.5).abs() < 1e-5, "{values:?}");
+    let misses = archive.cache_stats().expect("ogawa caches").misses;
+
+    // Scrubbing back and forth inside the same interval is served from cache
+    for t in [1.5, 1.75, 1.1, 1.9] {
+        let values = p.read_interpolated_f32(SampleSelector::TimeFloor(t), &ts).expect("interpolate");
+        assert!((values[0] - t as f32 * 2.0).abs() < 1e-5);
+    }
+    let stats = archive.cache_stats().unwrap();
+    assert_eq!(stats.misses, misses);
+    assert!(stats.hits >= 8);
+    assert_eq!(stats.samples_per_property, 2);
+
+    // Moving on keeps only the last two samples of P
+    p.read_interpolated_f32(SampleSelector::TimeFloor(2.5), &ts).unwrap();
+    assert!(archive.cache_stats().unwrap().evictions >= 1);
+    assert!(p.read_interpolated_f32(SampleSelector::Index(0), &ts).is_ok());
+}
+
+#[test]
+fn test_open_mmap_streaming() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        for i in 0..3 {
+            let mut mesh = OPolyMesh::new(&format!("mesh{i}"));
+            for frame in 0..5 {
+                let y = (i * 10 + frame) as f32;
+                mesh.add_sample(&OPolyMeshSample::new(
+                    vec![glam::Vec3::new(0.0, y, 0.0), glam::Vec3::X, glam::Vec3::Z],
+                    vec![3],
+                    vec![0, 1, 2],
+                ));
+            }
+            root.add_child(mesh.build());
+        }
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let eager = IArchive::open(path).expect("open");
+    let lazy = IArchive::open_mmap(path, 4096).expect("open_mmap");
+    assert_eq!(lazy.getNumTimeSamplings(), eager.getNumTimeSamplings());
+    let (top_a, top_b) = (eager.getTop(), lazy.getTop());
+    assert_eq!(top_b.getNumChildren(), 3);
+    for (a, b) in top_a.getChildren().zip(top_b.getChildren()) {
+        assert_eq!(a.getFullName(), b.getFullName());
+        let (ma, mb) = (IPolyMesh::new(&a).expect("mesh"), IPolyMesh::new(&b).expect("mesh"));
+        assert_eq!(mb.getNumSamples(), 5);
+        for index in [4, 0, 2] {
+            let (sa, sb) = (ma.getSample(index).unwrap(), mb.getSample(index).unwrap());
+            assert_eq!(sa.positions, sb.positions);
+            assert_eq!(sa.face_indices, sb.face_indices);
+        }
+    }
+}
+
+#[test]
+fn test_frame_mapping_houdini_archive() {
+    use alembic::core::{FrameConvention, TimeSampling};
+    use alembic::export::obj::sequence_frames;
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        archive.setAppName("Houdini 20.5.332");
+        archive.setDccFps(25.0);
+        // Houdini frame 1 is time 0
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 25.0, 0.0));
+        let mut xform = OXform::new("xf");
+        xform.set_time_sampling(ts);
+        for frame in 0..3 {
+            xform.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::X * frame as f32), true));
+        }
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let mapping = archive.frame_mapping();
+    assert_eq!(mapping.fps, 25.0);
+    assert_eq!(mapping.convention, FrameConvention::Houdini);
+    assert_eq!(mapping.frame_to_time(1.0), 0.0);
+
+    let frames: Vec<i64> = sequence_frames(&archive).iter().map(|f| f.frame).collect();
+    assert_eq!(frames, [1, 2, 3]);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_reads_match_sequential() {
+    use alembic::abc::IArrayProperty;
+    use alembic::geom::IPolyMesh;
+    use rayon::iter::ParallelIterator;
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        for g in 0..4 {
+            let mut group = OObject::new(&format!("grp{g}"));
+            for m in 0..3 {
+                let mut mesh = OPolyMesh::new(&format!("mesh{m}"));
+                for frame in 0..6 {
+                    let y = (g * 100 + m * 10 + frame) as f32;
+                    mesh.add_sample(&OPolyMeshSample::new(
+                        vec![glam::Vec3::new(0.0, y, 0.0), glam::Vec3::X, glam::Vec3::Z],
+                        vec![3],
+                        vec![0, 1, 2],
+                    ));
+                }
+                group.add_child(mesh.build());
+            }
+            root.add_child(group);
+        }
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    assert_eq!(top.children_par().count(), 4);
+
+    // Hierarchy order is preserved
+    fn walk(obj: &alembic::abc::IObject, out: &mut Vec<String>) {
+        out.push(obj.getFullName().to_string());
+        for child in obj.getChildren() {
+            walk(&child, out);
+        }
+    }
+    let mut sequential = Vec::new();
+    walk(&top, &mut sequential);
+    let parallel = top.collect_par(&|obj| Some(obj.getFullName().to_string()));
+    assert_eq!(parallel, sequential);
+
+    let visited = std::sync::atomic::AtomicUsize::new(0);
+    top.visit_par(&|_| {
+        visited.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    });
+    assert_eq!(visited.into_inner(), sequential.len());
+
+    let grp = top.getChildByName("grp2").expect("grp2");
+    let obj = grp.getChildByName("mesh1").expect("mesh1");
+    let mesh = IPolyMesh::new(&obj).expect("mesh");
+    let samples = mesh.get_samples_par(1..100).expect("samples");
+    assert_eq!(samples.len(), 5);
+    for (i, sample) in samples.iter().enumerate() {
+        assert_eq!(sample.positions, mesh.getSample(i + 1).unwrap().positions);
+    }
+
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    let p_prop = geom.getPropertyByName("P").expect("P");
+    let p = IArrayProperty::new(p_prop.asArray().expect("array"));
+    let raw = p.get_samples_par(0..6).expect("raw samples");
+    assert_eq!(raw.len(), 6);
+    assert_eq!(raw[3], p.read_sample_vec(3usize).unwrap());
+}
+
+#[test]
+fn test_typed_geom_param_expand() {
+    use alembic::core::GeometryScope;
+    use alembic::geom::{GeomParamSample, IGeomParam, IV2fGeomParam, IC3fGeomParam, IFloatGeomParam};
+    use alembic::util::DataType;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    let counts = vec![4, 3];
+    let indices = vec![0, 1, 2, 3, 1, 4, 2];
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("mesh");
+        let positions = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::ONE, glam::Vec3::Y, glam::Vec3::Z];
+        let mut sample = OPolyMeshSample::new(positions, counts.clone(), indices.clone());
+        sample.set_arb_geom_param("Cd", GeomParamSample::from_values(
+            DataType::VEC3F,
+            &(0..5).map(|i| [i as f32, 0.0, 0.0]).collect::<Vec<_>>(),
+            None,
+            GeometryScope::Vertex,
+        ));
+        sample.set_arb_geom_param("st", GeomParamSample::from_values(
+            DataType::VEC2F,
+            &[[0.0f32, 0.0], [1.0, 1.0]],
+            Some(vec![0, 1, 0, 1, 1, 1, 0]),
+            GeometryScope::FaceVarying,
+        ));
+        sample.set_arb_geom_param("temperature", GeomParamSample::from_values(
+            DataType::FLOAT32,
+            &[10.0f32, 20.0],
+            None,
+            GeometryScope::Uniform,
+        ));
+        mesh.add_sample(&sample);
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("mesh").expect("mesh");
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    let arb_prop = geom.getPropertyByName(".arbGeomParams").expect(".arbGeomParams");
+    let arb = arb_prop.asCompound().expect("compound");
+
+    let cd = IC3fGeomParam::new_typed(&arb, "Cd").expect("type").expect("Cd");
+    assert_eq!(cd.scope(), GeometryScope::Vertex);
+    assert_eq!(cd.get_values(0).unwrap().len(), 5);
+    let cd_fv = cd.expand(0, &counts, &indices).unwrap();
+    assert_eq!(cd_fv.iter().map(|c| c.x).collect::<Vec<_>>(), vec![0.0, 1.0, 2.0, 3.0, 1.0, 4.0, 2.0]);
+
+    let st = IV2fGeomParam::new_typed(&arb, "st").expect("type").expect("st");
+    assert!(st.is_indexed());
+    assert_eq!(st.get_values(0).unwrap()[1], glam::Vec2::ONE);
+    assert_eq!(st.expand(0, &counts, &indices).unwrap().len(), 7);
+
+    let temperature = IFloatGeomParam::new_typed(&arb, "temperature").expect("type").expect("temperature");
+    assert_eq!(temperature.expand(0, &counts, &indices).unwrap(), vec![10.0, 10.0, 10.0, 10.0, 20.0, 20.0, 20.0]);
+
+    // Wrong element type is an error, missing params are None
+    assert!(IV2fGeomParam::new_typed(&arb, "Cd").is_err());
+    assert!(IFloatGeomParam::new_typed(&arb, "missing").unwrap().is_none());
+    assert!(IGeomParam::new(&arb, "Cd").unwrap().into_typed::<glam::Vec3>().is_ok());
+}
+
+#[test]
+fn test_roundtrip_multi_uv_sets() {
+    use alembic::geom::IS_UV_KEY;
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    let quad_uvs = [glam::Vec2::ZERO, glam::Vec2::X, glam::Vec2::ONE, glam::Vec2::Y];
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("quad");
+        for frame in 0..2 {
+            let mut sample = OPolyMeshSample::new(
+                vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::new(1.0, 1.0, 0.0), glam::Vec3::Y],
+                vec![4],
+                vec![0, 1, 2, 3],
+            );
+            sample.uvs = Some(quad_uvs.to_vec());
+            // "lightmap" is indexed and only written once; "detail" changes per frame
+            if frame == 0 {
+                sample.set_uv_set("lightmap", &[glam::Vec2::splat(0.25), glam::Vec2::splat(0.75)], Some(vec![0, 1, 1, 0]));
+            }
+            let scale = 2.0 + frame as f32;
+            sample.set_uv_set("detail", &quad_uvs.map(|uv| uv * scale), None);
+            mesh.add_sample(&sample);
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("quad").expect("quad");
+    let mesh = IPolyMesh::new(&obj).expect("polymesh");
+    assert_eq!(mesh.uv_set_names(), vec!["uv", "lightmap", "detail"]);
+    assert_eq!(mesh.get_uv_set("uv", 0).expect("uv"), quad_uvs.to_vec());
+    let lightmap = mesh.get_uv_set("lightmap", 1).expect("lightmap");
+    assert_eq!(lightmap, vec![glam::Vec2::splat(0.25), glam::Vec2::splat(0.75), glam::Vec2::splat(0.75), glam::Vec2::splat(0.25)]);
+    assert_eq!(mesh.get_uv_set("detail", 1).expect("detail")[2], glam::Vec2::splat(3.0));
+    assert!(mesh.get_uv_set("missing", 0).is_none());
+
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").expect(".geom");
+    let geom = geom_prop.asCompound().expect("compound");
+    let arb_prop = geom.getPropertyByName(".arbGeomParams").expect(".arbGeomParams");
+    let arb = arb_prop.asCompound().expect("compound");
+    let detail = arb.getPropertyByName("detail").expect("detail");
+    assert_eq!(detail.getHeader().meta_data.get(IS_UV_KEY), Some("1"));
+
+    // glTF export keeps every set
+    let doc = alembic::export::GltfDocument::build(&archive, &alembic::export::GltfOptions::default());
+    assert_eq!(doc.stats.max_uv_sets, 3);
+}
+
+#[test]
+fn test_split_mesh_by_face_sets() {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("strip");
+        // Three quads in a row, animated; face sets are static
+        for frame in 0..2 {
+            let positions = (0..8).map(|i| glam::Vec3::new((i / 2) as f32, (i % 2) as f32, frame as f32)).collect();
+            mesh.add_sample(&OPolyMeshSample::new(positions, vec![4, 4, 4], vec![0, 2, 3, 1, 2, 4, 5, 3, 4, 6, 7, 5]));
+        }
+        let mut mesh_obj = mesh.build();
+        for (name, faces) in [("left", vec![0]), ("right", vec![2])] {
+            let mut fs = OFaceSet::new(name);
+            fs.add_sample(&OFaceSetSample { faces });
+            mesh_obj.add_child(fs.build());
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh_obj);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(path).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("strip").expect("strip");
+    let mesh = IPolyMesh::new(&obj).expect("polymesh");
+    assert_eq!(mesh.faces_for_faceset("right", 1), Some(vec![2]));
+    assert!(mesh.faces_for_faceset("missing", 0).is_none());
+
+    let parts = mesh.split_by_face_sets(1).expect("split");
+    let names: Vec<Option<&str>> = parts.iter().map(|p| p.name.as_deref()).collect();
+    assert_eq!(names, vec![Some("left"), Some("right"), None]);
+    assert_eq!(parts[2].faces, vec![1]);
+    for part in &parts {
+        assert_eq!(part.sample.num_faces(), 1);
+        assert_eq!(part.sample.num_vertices(), 4);
+        // Sample 1 positions sit at z = 1
+        assert!(part.sample.positions.iter().all(|p| p.z == 1.0));
+    }
+    assert_eq!(parts[1].sample.compute_bounds().0.x, 2.0);
+}
+
+#[test]
+fn test_compare_archives() {
+    use alembic::compare::{compare_archives, CompareOptions, Difference};
+
+    // `offset` nudges P of sample 1, `extra` adds a sibling object, `frames` sets sample count
+    let write = |path: &std::path::Path, offset: f32, extra: bool, frames: usize| {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let mut mesh = OPolyMesh::new("tri");
+        for frame in 0..frames {
+            let dy = if frame == 1 { offset } else { 0.0 };
+            mesh.add_sample(&OPolyMeshSample::new(
+                vec![glam::Vec3::ZERO, glam::Vec3::new(1.0, dy, 0.0), glam::Vec3::Y],
+                vec![3],
+                vec![0, 1, 2],
+            ));
+        }
+        let mut xform = OXform::new("group");
+        xform.add_sample(OXformSample::from_matrix(glam::Mat4::IDENTITY, true));
+        xform.add_child(mesh.build());
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        if extra {
+            let mut other = OXform::new("extra");
+            other.add_sample(OXformSample::from_matrix(glam::Mat4::IDENTITY, true));
+            root.add_child(other.build());
+        }
+        archive.write_archive(&root).expect("Failed to write archive");
+    };
+    let open = |f: &NamedTempFile| IArchive::open(f.path()).expect("Failed to open archive");
+
+    let base = NamedTempFile::new().expect("Failed to create temp file");
+    write(base.path(), 0.0, false, 3);
+    let same = NamedTempFile::new().expect("Failed to create temp file");
+    write(same.path(), 0.0, false, 3);
+    let report = compare_archives(&open(&base), &open(&same), &CompareOptions::default()).unwrap();
+    assert!(report.is_identical(), "{:?}", report.differences);
+    assert!(report.samples_compared > 0);
+
+    // Small delta: reported exactly, accepted with a tolerance
+    let nudged = NamedTempFile::new().expect("Failed to create temp file");
+    write(nudged.path(), 1e-4, false, 3);
+    let report = compare_archives(&open(&base), &open(&nudged), &CompareOptions::default()).unwrap();
+    assert_eq!(report.differences.len(), 1, "{:?}", report.differences);
+    match &report.differences[0] {
+        Difference::SampleMismatch { path, property, sample, count, element, .. } => {
+            assert_eq!(path, "/group/tri");
+            assert_eq!(property, ".geom/P");
+            assert_eq!((*sample, *count, *element), (1, 1, 4));
+        }
+        other => panic!("unexpected difference {other:?}"),
+    }
+    let report = compare_archives(&open(&base), &open(&nudged), &CompareOptions::with_tolerance(1e-3)).unwrap();
+    assert!(report.is_identical());
+    assert!(report.max_delta > 0.0);
+
+    // Added object and different sample count
+    let changed = NamedTempFile::new().expect("Failed to create temp file");
+    write(changed.path(), 0.0, true, 2);
+    let report = compare_archives(&open(&base), &open(&changed), &CompareOptions::default()).unwrap();
+    assert!(report.differences.contains(&Difference::ObjectAdded { path: "/extra".into() }));
+    assert!(report.differences.contains(&Difference::SampleCountMismatch {
+        path: "/group/tri".into(),
+        property: ".geom/P".into(),
+        a: 3,
+        b: 2,
+    }));
+    let report = compare_archives(&open(&changed), &open(&base), &CompareOptions::default()).unwrap();
+    assert!(report.differences.contains(&Difference::ObjectRemoved { path: "/extra".into() }));
+}
+
+#[test]
+fn test_resample_archive() {
+    use alembic::core::TimeSampling;
+    use alembic::resample::{resample_archive, ResampleMode, ResampleOptions};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let path = temp.path();
+    {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        archive.setDccFps(24.0);
+        // Frames 1001..=1008 at 24 fps; x follows the frame number
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1001.0 / 24.0));
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.set_time_sampling(ts);
+        let mut xform = OXform::new("rig");
+        xform.set_time_sampling(ts);
+        for frame in 0..8 {
+            let x = frame as f32;
+            mesh.add_sample(&OPolyMeshSample::new(
+                vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::new(x + 1.0, 0.0, 0.0), glam::Vec3::new(x, 1.0, 0.0)],
+                vec![3],
+                vec![0, 1, 2],
+            ));
+            xform.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::X * x), true));
+        }
+        xform.add_child(mesh.build());
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let archive = IArchive::open(path).expect("Failed to open archive");
+
+    // 24 -> 30 fps over source frames 1002-1006: 30 fps frames 1253..=1257
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+    let options = ResampleOptions { frames: Some(1002..=1006), ..ResampleOptions::new(30.0) };
+    let stats = resample_archive(&archive, out.path(), &options).expect("resample");
+    assert_eq!(stats.frames, 5);
+    assert!(stats.resampled >= 2, "{stats:?}");
+    let (first, last) = stats.time_range.unwrap();
+    assert!((first - 1253.0 / 30.0).abs() < 1e-9 && (last - 1257.0 / 30.0).abs() < 1e-9);
+
+    let resampled = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(resampled.frame_mapping().fps, 30.0);
+    let top = resampled.getTop();
+    let rig_obj = top.getChildByName("rig").expect("rig");
+    let rig = IXform::new(&rig_obj).expect("xform");
+    let mesh_obj = rig_obj.getChildByName("tri").expect("tri");
+    let mesh = IPolyMesh::new(&mesh_obj).expect("mesh");
+    assert_eq!(rig.getNumSamples(), 5);
+    assert_eq!(mesh.getNumSamples(), 5);
+    // Output frames 1253..=1257 are source frames 1002.4..=1005.6: positions and matrices interpolate
+    let tx = rig.getSample(0).expect("xform sample").matrix().w_axis.x;
+    assert!((tx - 1.4).abs() < 1e-4, "{tx}");
+    let p = mesh.getSample(4).expect("mesh sample").positions;
+    assert!((p[0].x - 4.6).abs() < 1e-4 && (p[1].x - 5.6).abs() < 1e-4, "{p:?}");
+    let ts = resampled.getTimeSampling(mesh.getTimeSamplingIndex() as usize).unwrap();
+    assert!((ts.sample_time(1, 5) - 1254.0 / 30.0).abs() < 1e-9);
+
+    // Nearest holds source frame 1002
+    let options = ResampleOptions { mode: ResampleMode::Nearest, ..options };
+    resample_archive(&archive, out.path(), &options).expect("resample");
+    let resampled = IArchive::open(out.path()).expect("Failed to open archive");
+    let top = resampled.getTop();
+    let rig_obj = top.getChildByName("rig").expect("rig");
+    let tx = IXform::new(&rig_obj).expect("xform").getSample(0).expect("xform sample").matrix().w_axis.x;
+    assert_eq!(tx, 1.0);
+
+    // Empty frame ranges and bad rates are errors
+    let (start, end) = (1006, 1002);
+    let bad = ResampleOptions { frames: Some(start..=end), ..ResampleOptions::new(30.0) };
+    assert!(resample_archive(&archive, out.path(), &bad).is_err());
+    assert!(resample_archive(&archive, out.path(), &ResampleOptions::new(0.0)).is_err());
+}
+
+#[test]
+fn test_make_and_apply_patch() {
+    use alembic::core::TimeSampling;
+    use alembic::patch::{apply_patch, archive_fingerprint, make_patch, ArchivePatch};
+
+    // Triangle moving along X; `edit` moves frames 5.. up in Y
+    let write = |path: &std::path::Path, frames: usize, edit: bool, extra: bool| {
+        let mut archive = OArchive::create(path).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1.0 / 24.0));
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.set_time_sampling(ts);
+        for frame in 0..frames {
+            let x = frame as f32;
+            let y = if edit && frame >= 5 { 1.0 } else { 0.0 };
+            mesh.add_sample(&OPolyMeshSample::new(
+                vec![glam::Vec3::new(x, y, 0.0), glam::Vec3::new(x + 1.0, y, 0.0), glam::Vec3::new(x, y + 1.0, 0.0)],
+                vec![3],
+                vec![0, 1, 2],
+            ));
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        if extra {
+            let mut xform = OXform::new("extra");
+            xform.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::Vec3::Z), true));
+            root.add_child(xform.build());
+        }
+        archive.write_archive(&root).expect("Failed to write archive");
+    };
+    let v1 = NamedTempFile::new().expect("Failed to create temp file");
+    let v2 = NamedTempFile::new().expect("Failed to create temp file");
+    write(v1.path(), 8, false, false);
+    write(v2.path(), 10, true, true);
+    let base = IArchive::open(v1.path()).expect("Failed to open archive");
+    let target = IArchive::open(v2.path()).expect("Failed to open archive");
+
+    let patch = make_patch(&base, &target).expect("make patch");
+    // Frames 5-7 changed, 8-9 added; frames 0-4 come from the base
+    assert!(patch.stats.changed >= 3 && patch.stats.added >= 2 && patch.stats.reused >= 5, "{:?}", patch.stats);
+    assert_eq!(patch.stats.removed, 0);
+    let bytes = patch.to_bytes();
+    assert!((bytes.len() as u64) < std::fs::metadata(v2.path()).unwrap().len());
+    let patch = ArchivePatch::from_bytes(&bytes).expect("decode patch");
+
+    let out = NamedTempFile::new().expect("Failed to create temp file");
+    apply_patch(&base, &patch, out.path()).expect("apply patch");
+    let rebuilt = IArchive::open(out.path()).expect("Failed to open archive");
+    assert_eq!(archive_fingerprint(&rebuilt).unwrap(), archive_fingerprint(&target).unwrap());
+    let top = rebuilt.getTop();
+    assert!(top.getChildByName("extra").is_some());
+    let mesh_obj = top.getChildByName("tri").expect("tri");
+    let mesh = IPolyMesh::new(&mesh_obj).expect("mesh");
+    assert_eq!(mesh.getNumSamples(), 10);
+    let p = mesh.getSample(9).expect("mesh sample").positions;
+    assert_eq!((p[0].x, p[0].y), (9.0, 1.0));
+    assert_eq!(mesh.getSample(2).expect("mesh sample").positions[0].y, 0.0);
+
+    // A patch only applies to its own base
+    let other = IArchive::open(v2.path()).expect("Failed to open archive");
+    assert!(apply_patch(&other, &patch, out.path()).is_err());
+}
+
+#[test]
+fn test_copy_object_with_transform() {
+    use alembic::copy::{copy_object, copy_samples, CopySamples, CopyTransform};
+    use alembic::core::TimeSampling;
+    use alembic::ogawa::writer::OProperty;
+    use alembic::patch::archive_fingerprint;
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let mut mesh = OPolyMesh::new("tri");
+        mesh.set_time_sampling(ts);
+        for frame in 0..3 {
+            let x = frame as f32;
+            let mut sample = OPolyMeshSample::new(
+                vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::X, glam::Vec3::Y],
+                vec![3],
+                vec![0, 1, 2],
+            );
+            sample.velocities = Some(vec![glam::Vec3::X; 3]);
+            mesh.add_sample(&sample);
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let archive = IArchive::open(temp.path()).expect("Failed to open archive");
+    let write = |root: &OObject| {
+        let out = NamedTempFile::new().expect("Failed to create temp file");
+        let mut out_archive = OArchive::create(out.path()).expect("Failed to create archive");
+        for i in 1..archive.getNumTimeSamplings() {
+            out_archive.addTimeSampling(archive.getTimeSampling(i).cloned().unwrap_or_default());
+        }
+        out_archive.write_archive(root).expect("Failed to write archive");
+        out
+    };
+
+    // The default transform is a faithful copy
+    let copy = write(&copy_object(&archive.getTop(), &mut CopySamples).expect("copy"));
+    let copied = IArchive::open(copy.path()).expect("Failed to open archive");
+    assert_eq!(archive_fingerprint(&copied).unwrap(), archive_fingerprint(&archive).unwrap());
+
+    // A transform sees every scalar/array property and can drop or rewrite it
+    struct DropVelocities(Vec<String>);
+    impl CopyTransform for DropVelocities {
+        fn copy_samples(&mut self, prop: &IProperty<'_>, out: &mut OProperty) -> alembic::Result<bool> {
+            self.0.push(out.name.clone());
+            if out.name == ".velocities" {
+                return Ok(false);
+            }
+            copy_samples(prop, out)?;
+            Ok(true)
+        }
+    }
+    let mut transform = DropVelocities(Vec::new());
+    let copy = write(&copy_object(&archive.getTop(), &mut transform).expect("copy"));
+    assert!(transform.0.iter().any(|n| n == "P") && transform.0.iter().any(|n| n == ".velocities"));
+    let copied = IArchive::open(copy.path()).expect("Failed to open archive");
+    let top = copied.getTop();
+    let mesh_obj = top.getChildByName("tri").expect("tri");
+    let mesh = IPolyMesh::new(&mesh_obj).expect("mesh");
+    assert_eq!(mesh.getNumSamples(), 3);
+    let sample = mesh.getSample(2).expect("mesh sample");
+    assert_eq!(sample.positions[0].x, 2.0);
+    assert!(sample.velocities.is_none());
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn test_pack_and_unpack_compressed_archive() {
+    use alembic::core::TimeSampling;
+    use alembic::ogawa::{BlockCodec, BLOCK_COMPRESSION_KEY};
+    use alembic::pack::{pack_archive, unpack_archive};
+    use alembic::patch::archive_fingerprint;
+
+    // Dense grid so the positions compress
+    let src = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(src.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1.0 / 24.0));
+        let mut mesh = OPolyMesh::new("grid");
+        mesh.set_time_sampling(ts);
+        for frame in 0..4 {
+            let positions: Vec<glam::Vec3> =
+                (0..2000).map(|i| glam::Vec3::new((i % 40) as f32, frame as f32, (i / 40) as f32)).collect();
+            let counts = vec![3; 600];
+            let indices: Vec<i32> = (0..1800).map(|i| i % 2000).collect();
+            mesh.add_sample(&OPolyMeshSample::new(positions, counts, indices));
+        }
+        let mut root = OObject::new("");
+        root.add_child(mesh.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    let source = IArchive::open(src.path()).expect("Failed to open archive");
+
+    for codec in [BlockCodec::Lz4, BlockCodec::Zlib] {
+        let packed = NamedTempFile::new().expect("Failed to create temp file");
+        let stats = pack_archive(&source, packed.path(), codec).expect("pack");
+        assert!(stats.output_bytes < stats.input_bytes, "{:?}", stats);
+
+        let archive = IArchive::open(packed.path()).expect("Failed to open packed archive");
+        assert_eq!(archive.getArchiveMetaData().get(BLOCK_COMPRESSION_KEY), Some(codec.name()));
+        let top = archive.getTop();
+        let grid = top.getChildByName("grid").expect("grid");
+        let mesh = IPolyMesh::new(&grid).expect("mesh");
+        let sample = mesh.getSample(3).expect("mesh sample");
+        assert_eq!(sample.positions.len(), 2000);
+        assert_eq!(sample.positions[41], glam::Vec3::new(1.0, 3.0, 1.0));
+        assert_eq!(sample.face_indices.len(), 1800);
+
+        let restored = NamedTempFile::new().expect("Failed to create temp file");
+        unpack_archive(&archive, restored.path()).expect("unpack");
+        let restored = IArchive::open(restored.path()).expect("Failed to open unpacked archive");
+        assert!(restored.getArchiveMetaData().get(BLOCK_COMPRESSION_KEY).is_none());
+        assert_eq!(archive_fingerprint(&restored).unwrap(), archive_fingerprint(&source).unwrap());
+    }
+}
+
+#[test]
+fn test_unknown_pod_properties_read_as_raw() {
+    use alembic::ogawa::writer::OProperty;
+    use alembic::util::DataType;
+    use alembic::validate::{validate, Check, Severity};
+
+    // POD codes 14 and 15 are unused by current Alembic, as if from a newer writer
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut obj = OObject::new("thing");
+        let mut scalar = OProperty::scalar("future", DataType::new(PlainOldDataType::Unknown, 3));
+        scalar.unknown_pod = Some(14);
+        scalar.add_scalar_sample(&[1, 2, 3, 4, 5, 6]);
+        scalar.add_scalar_sample(&[7, 8, 9, 10, 11, 12]);
+        obj.properties.push(scalar);
+        let mut array = OProperty::array("futureArray", DataType::new(PlainOldDataType::Unknown, 1));
+        array.unknown_pod = Some(15);
+        array.add_array_sample(&[0xAB; 10], &[10]);
+        obj.properties.push(array);
+        let mut root = OObject::new("");
+        root.add_child(obj);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(temp.path()).expect("Failed to open archive");
+    let top = archive.getTop();
+    let obj = top.getChildByName("thing").expect("thing");
+    let props = obj.getProperties();
+
+    let prop = props.getPropertyByName("future").expect("future");
+    assert_eq!(prop.getHeader().data_type.pod, PlainOldDataType::Unknown);
+    let raw = prop.asRaw().expect("raw scalar");
+    assert!(!raw.is_known());
+    assert!(!raw.isArray());
+    assert_eq!((raw.pod_code(), raw.extent()), (14, 3));
+    assert_eq!(raw.getNumSamples(), 2);
+    assert_eq!(raw.getSample(1).unwrap(), vec![7, 8, 9, 10, 11, 12]);
+    assert_eq!(raw.sample_size(0).unwrap(), 6);
+
+    let prop = props.getPropertyByName("futureArray").expect("futureArray");
+    let raw = prop.asRaw().expect("raw array");
+    assert_eq!((raw.pod_code(), raw.extent()), (15, 1));
+    assert_eq!(raw.getSample(0).unwrap(), vec![0xAB; 10]);
+    assert_eq!(raw.getDimensions(0).unwrap(), vec![10]);
+
+    let diagnostics = validate(&archive);
+    let unknown: Vec<_> = diagnostics.iter().filter(|d| d.check == Check::UnknownPod).collect();
+    assert_eq!(unknown.len(), 2, "{:?}", diagnostics);
+    assert!(unknown.iter().all(|d| d.severity == Severity::Warning && d.object == "/thing"));
+    assert!(unknown[0].message.contains("unknown POD type 14"), "{}", unknown[0].message);
+}
+
+/// Archive with one object per schema, as bytes.
+fn schema_archive_bytes() -> Vec<u8> {
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        for (_, obj) in build_schema_objects() {
+            root.add_child(obj);
+        }
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+    std::fs::read(temp.path()).expect("Failed to read archive")
+}
+
+/// Read everything reachable: every property sample as raw bytes, and every
+/// schema sample through the validator.
+fn read_everything(archive: &IArchive) {
+    fn props(compound: &ICompoundProperty<'_>) {
+        for i in 0..compound.getNumProperties() {
+            let Some(prop) = compound.getProperty(i) else { continue };
+            if let Some(child) = prop.asCompound() {
+                props(&child);
+            } else if let Some(raw) = prop.asRaw() {
+                for s in 0..raw.getNumSamples().min(8) {
+                    let _ = raw.getSample(s);
+                    let _ = raw.getDimensions(s);
+                }
+            }
+        }
+    }
+    fn objects(obj: &alembic::abc::IObject<'_>) {
+        props(&obj.getProperties());
+        for child in obj.getChildren() {
+            objects(&child);
+        }
+    }
+    objects(&archive.getTop());
+    let _ = alembic::validate::validate(archive);
+}
+
+#[test]
+fn test_open_modes_on_corrupt_group() {
+    use alembic::abc::OpenMode;
+    use alembic::Error;
+
+    let mut bytes = schema_archive_bytes();
+    let clean = IArchive::from_bytes_with_mode("clean", bytes.clone(), OpenMode::Strict).expect("clean archive");
+    assert!(clean.skipped_subtrees().is_empty());
+
+    // Child count of the first object's group -> absurd
+    let (pos, name) = {
+        let ogawa = alembic::ogawa::IArchive::from_bytes(bytes.clone()).expect("ogawa");
+        let top = ogawa.root().group(2).expect("top group");
+        let pos = top.group(1).expect("first child").pos();
+        (pos, clean.getTop().getChild(0).expect("child").getName().to_string())
+    };
+    bytes[pos as usize..pos as usize + 8].copy_from_slice(&(u64::MAX / 2).to_le_bytes());
+
+    match IArchive::from_bytes_with_mode("strict", bytes.clone(), OpenMode::Strict) {
+        Err(Error::Corrupt { offset, .. }) => assert_eq!(offset, pos),
+        other => panic!("expected Corrupt, got {:?}", other.map(|_| ())),
+    }
+    let tolerant = IArchive::from_bytes_with_mode("tolerant", bytes.clone(), OpenMode::Tolerant).expect("tolerant");
+    let skipped = tolerant.skipped_subtrees();
+    assert_eq!(skipped.len(), 1, "{:?}", skipped);
+    assert_eq!(skipped[0].path, format!("/{}", name));
+    assert!(matches!(skipped[0].error, Error::Corrupt { .. }));
+    let names: Vec<String> = tolerant.getTop().getChildren().map(|c| c.getName().to_string()).collect();
+    assert!(!names.contains(&name));
+    assert_eq!(names.len(), clean.getTop().getNumChildren() - 1);
+    read_everything(&tolerant);
+
+    let diagnostics = alembic::validate::validate(&tolerant);
+    assert!(diagnostics.iter().any(|d| d.check == alembic::validate::Check::Read && d.object == skipped[0].path));
+}
+
+#[test]
+fn test_malformed_archives_do_not_panic() {
+    use alembic::abc::OpenMode;
+
+    let bytes = schema_archive_bytes();
+    let mut mutants: Vec<(String, Vec<u8>)> = Vec::new();
+    for len in (0..bytes.len()).step_by((bytes.len() / 150).max(1)) {
+        mutants.push((format!("truncated to {}", len), bytes[..len].to_vec()));
+    }
+    // Deterministic LCG so failures reproduce
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        state >> 33
+    };
+    for i in 0..400 {
+        let mut m = bytes.clone();
+        for _ in 0..1 + next() % 4 {
+            let at = next() as usize % m.len();
+            m[at] = next() as u8;
+        }
+        mutants.push((format!("byte flips #{}", i), m));
+    }
+    let smash = [u64::MAX, u64::MAX / 2, 1 << 40, 0x8000_0000_0000_0010, bytes.len() as u64 + 3, 7];
+    for _ in 0..400 {
+        let mut m = bytes.clone();
+        let at = (next() as usize % (m.len() / 8)) * 8;
+        let value = smash[next() as usize % smash.len()];
+        m[at..at + 8].copy_from_slice(&value.to_le_bytes());
+        mutants.push((format!("u64 {:#x} at {}", value, at), m));
+    }
+
+    for (what, data) in mutants {
+        let result = std::panic::catch_unwind(|| {
+            if let Ok(archive) = IArchive::from_bytes("fuzz", data.clone()) {
+                read_everything(&archive);
+            }
+            for mode in [OpenMode::Strict, OpenMode::Tolerant] {
+                if let Ok(archive) = IArchive::from_bytes_with_mode("fuzz", data.clone(), mode) {
+                    read_everything(&archive);
+                }
+            }
+        });
+        assert!(result.is_ok(), "reader panicked on {}", what);
+    }
+}
+
+/// In-memory [`alembic::ogawa::OgawaSource`] that counts reads.
+struct CountingSource {
+    bytes: Vec<u8>,
+    reads: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl alembic::ogawa::OgawaSource for CountingSource {
+    fn size(&self) -> u64 {
+        self.bytes.len() as u64
+    }
+
+    fn read_at(&self, pos: u64, buf: &mut [u8]) -> alembic::Result<()> {
+        self.reads.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        buf.copy_from_slice(&self.bytes[pos as usize..pos as usize + buf.len()]);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_archive_from_custom_source() {
+    use alembic::patch::archive_fingerprint;
+
+    let bytes = schema_archive_bytes();
+    let reads = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let source = CountingSource { bytes: bytes.clone(), reads: reads.clone() };
+    let archive = IArchive::from_source("custom", Box::new(source)).expect("open from source");
+    let local = IArchive::from_bytes("local", bytes).expect("open from bytes");
+    assert_eq!(archive.getTop().getNumChildren(), local.getTop().getNumChildren());
+    assert_eq!(archive_fingerprint(&archive).unwrap(), archive_fingerprint(&local).unwrap());
+    assert!(reads.load(std::sync::atomic::Ordering::Relaxed) > 0);
+    read_everything(&archive);
+
+    // A source shorter than its header
+    let empty = CountingSource { bytes: vec![0; 4], reads };
+    assert!(IArchive::from_source("empty", Box::new(empty)).is_err());
+}
+
+/// Serve `bytes` over HTTP/1.1 range requests on localhost; returns the URL.
+/// `ranges` counts the requests answered.
+#[cfg(feature = "remote")]
+fn serve_ranges(bytes: Vec<u8>, ranges: std::sync::Arc<std::sync::atomic::AtomicUsize>) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!("http://{}/shot.abc", listener.local_addr().unwrap());
+    let bytes = std::sync::Arc::new(bytes);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let (bytes, ranges) = (bytes.clone(), ranges.clone());
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut stream = stream;
+                stream.set_nodelay(true).unwrap();
+                loop {
+                    let mut range = None;
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                        return;
+                    }
+                    loop {
+                        line.clear();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        if let Some(spec) = line.trim().strip_prefix("Range: bytes=") {
+                            let (a, b) = spec.split_once('-').unwrap();
+                            range = Some((a.parse::<usize>().unwrap(), b.parse::<usize>().unwrap()));
+                        }
+                    }
+                    let (a, b) = range.expect("range request");
+                    let b = b.min(bytes.len() - 1);
+                    ranges.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let head = format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\n\r\n",
+                        a, b, bytes.len(), b - a + 1
+                    );
+                    stream.write_all(head.as_bytes()).unwrap();
+                    stream.write_all(&bytes[a..=b]).unwrap();
+                }
+            });
+        }
+    });
+    url
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn test_remote_archive_over_http_ranges() {
+    use alembic::ogawa::remote::{HttpSource, RemoteOptions};
+    use alembic::ogawa::OgawaSource;
+    use alembic::patch::archive_fingerprint;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let bytes = schema_archive_bytes();
+    let served = Arc::new(AtomicUsize::new(0));
+    let url = serve_ranges(bytes.clone(), served.clone());
+
+    let remote = IArchive::open_url(&url).expect("open_url");
+    let local = IArchive::from_bytes("local", bytes.clone()).expect("local");
+    assert_eq!(archive_fingerprint(&remote).unwrap(), archive_fingerprint(&local).unwrap());
+    read_everything(&remote);
+    // The whole (small) archive fits in the first chunk
+    assert_eq!(served.load(Ordering::Relaxed), 1);
+
+    // Small chunks: a long read coalesces into one request, repeats hit the cache
+    let options = RemoteOptions { chunk_size: 64, ..Default::default() };
+    let source = HttpSource::with_options(&url, options).expect("source");
+    assert_eq!(source.size(), bytes.len() as u64);
+    let mut buf = vec![0u8; 1000];
+    source.read_at(100, &mut buf).unwrap();
+    assert_eq!(buf, bytes[100..1100]);
+    assert_eq!(source.stats().requests, 2);
+    source.read_at(130, &mut buf[..500]).unwrap();
+    assert_eq!(buf[..500], bytes[130..630]);
+    assert_eq!(source.stats().requests, 2);
+    let tail = bytes.len() as u64 - 10;
+    source.read_at(tail, &mut buf[..10]).unwrap();
+    assert_eq!(buf[..10], bytes[tail as usize..]);
+    assert_eq!(source.stats().requests, 3);
+
+    source.prefetch(&[(2000, 200), (3000, 200), (2050, 10)]).unwrap();
+    assert_eq!(source.stats().requests, 5);
+    source.read_at(3000, &mut buf[..200]).unwrap();
+    assert_eq!(buf[..200], bytes[3000..3200]);
+    assert_eq!(source.stats().requests, 5);
+
+    let archive = IArchive::from_source(&url, Box::new(source)).expect("archive from source");
+    assert_eq!(archive_fingerprint(&archive).unwrap(), archive_fingerprint(&local).unwrap());
+
+    assert!(IArchive::open_url("https://example.com/shot.abc").is_err());
+}
+
+/// Minimal executor: poll `future` on this thread, parking until woken.
+#[cfg(feature = "remote")]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    struct Unpark(std::thread::Thread);
+    impl std::task::Wake for Unpark {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    let waker = std::task::Waker::from(std::sync::Arc::new(Unpark(std::thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => return output,
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn test_remote_async_api() {
+    use alembic::ogawa::remote::{HttpSource, RemoteOptions};
+    use alembic::patch::archive_fingerprint;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    let bytes = schema_archive_bytes();
+    let url = serve_ranges(bytes.clone(), Arc::new(AtomicUsize::new(0)));
+    let local = IArchive::from_bytes("local", bytes.clone()).expect("local");
+
+    let remote = block_on(IArchive::open_url_async(&url)).expect("open_url_async");
+    assert_eq!(archive_fingerprint(&remote).unwrap(), archive_fingerprint(&local).unwrap());
+
+    let options = RemoteOptions { chunk_size: 64, ..Default::default() };
+    let source = Arc::new(block_on(HttpSource::with_options_async(&url, options)).expect("source"));
+    assert_eq!(block_on(source.read_async(100, 300)).unwrap(), bytes[100..400]);
+    block_on(source.prefetch_async(vec![(2000, 200)])).unwrap();
+    let requests = source.stats().requests;
+    assert_eq!(block_on(source.read_async(2000, 200)).unwrap(), bytes[2000..2200]);
+    assert_eq!(source.stats().requests, requests);
+    assert!(block_on(source.read_async(bytes.len() as u64 - 4, 8)).is_err());
+
+    // More calls in flight than pool workers queue up and all complete
+    let reads: Vec<_> = (0..alembic::ogawa::remote::ASYNC_WORKERS * 4)
+        .map(|i| (i as u64 * 16, source.read_async(i as u64 * 16, 16)))
+        .collect();
+    for (offset, read) in reads {
+        let offset = offset as usize;
+        assert_eq!(block_on(read).unwrap(), bytes[offset..offset + 16]);
+    }
+
+    // The prefetched source keeps serving the archive
+    let archive = IArchive::from_source(&url, Box::new(source.clone())).expect("archive from source");
+    assert_eq!(archive_fingerprint(&archive).unwrap(), archive_fingerprint(&local).unwrap());
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn test_remote_rejects_mismatched_range_response() {
+    use alembic::ogawa::remote::HttpSource;
+    use std::io::{BufRead, BufReader, Write};
+
+    // Answers every request with `head` and no body
+    let serve = |head: &'static str| {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}/shot.abc", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 2 {
+                    line.clear();
+                }
+                let _ = stream.write_all(head.as_bytes());
+            }
+        });
+        url
+    };
+
+    // A huge Content-Length must fail before anything is allocated
+    let url = serve("HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 0-99/100\r\nContent-Length: 1099511627776\r\n\r\n");
+    let err = HttpSource::open(&url).err().expect("oversized body rejected");
+    assert!(err.to_string().contains("Content-Length 1099511627776"), "{}", err);
+    // So must a range other than the one asked for
+    let url = serve("HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 10-19/100\r\nContent-Length: 10\r\n\r\n");
+    assert!(HttpSource::open(&url).is_err());
+}
+
+#[test]
+fn test_decode_arena_reuses_buffers() {
+    let mut archive = OArchive::create_in_memory("arena.abc").unwrap();
+    let ts = archive.addTimeSampling(alembic::core::TimeSampling::uniform(1.0 / 24.0, 0.0));
+    let mut xform = OXform::new("xform");
+    xform.set_time_sampling(ts);
+    let mut mesh = OPolyMesh::new("tri");
+    mesh.set_time_sampling(ts);
+    for frame in 0..8 {
+        let y = frame as f32;
+        xform.add_sample(OXformSample::from_matrix(glam::Mat4::from_translation(glam::vec3(0.0, y, 0.0)), true));
+        mesh.add_sample(&OPolyMeshSample::new(
+            vec![glam::vec3(0.0, y, 0.0), glam::vec3(1.0, y, 0.0), glam::vec3(0.0, y, 1.0)],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+    }
+    xform.add_child(mesh.build());
+    let mut root = OObject::new("");
+    root.add_child(xform.build());
+    archive.write_archive(&root).unwrap();
+    let bytes = archive.into_bytes().unwrap();
+
+    let read_frames = |archive: &IArchive| -> Vec<(glam::Vec3, Vec<glam::Vec3>)> {
+        let xform_obj = archive.findObject("/xform").unwrap();
+        let mesh_obj = archive.findObject("/xform/tri").unwrap();
+        let xform = IXform::new(&xform_obj).unwrap();
+        let mesh = IPolyMesh::new(&mesh_obj).unwrap();
+        (0..8).map(|frame| {
+            let values = (xform.getSample(frame).unwrap().translation(), mesh.getSample(frame).unwrap().positions);
+            archive.reset_decode_arena();
+            values
+        }).collect()
+    };
+
+    let plain = IArchive::from_bytes("arena.abc", bytes.as_slice()).unwrap();
+    let expected = read_frames(&plain);
+    let stats = plain.decode_arena_stats().unwrap();
+    assert_eq!((stats.reuses, stats.pooled), (0, 0));
+
+    let pooled = IArchive::from_bytes("arena.abc", bytes).unwrap();
+    pooled.set_decode_arena(1 << 20);
+    assert_eq!(read_frames(&pooled), expected);
+    assert_eq!(expected[5].0.y, 5.0);
+    let stats = pooled.decode_arena_stats().unwrap();
+    // Several scalar and dimension reads per frame share the pooled buffers
+    assert!(stats.reuses > 0 && stats.reuses < stats.takes, "{:?}", stats);
+    assert_eq!(stats.pooled_bytes, 0);
+}
+
+#[test]
+fn test_verify_hashes_detects_corrupt_sample() {
+    use alembic::Error;
+
+    let mut archive = OArchive::create_in_memory("verify.abc").unwrap();
+    let mut mesh = OPolyMesh::new("tri");
+    let positions = vec![glam::vec3(0.25, 1.5, -3.75), glam::vec3(1.0, 0.0, 0.0), glam::vec3(0.0, 0.0, 1.0)];
+    mesh.add_sample(&OPolyMeshSample::new(positions.clone(), vec![3], vec![0, 1, 2]));
+    let mut root = OObject::new("");
+    root.add_child(mesh.build());
+    archive.write_archive(&root).unwrap();
+    let mut bytes = archive.into_bytes().unwrap();
+
+    // Straight from the property: the schema readers treat failed reads as missing data
+    let read_positions = |archive: &IArchive| {
+        let obj = archive.findObject("/tri").unwrap();
+        let props = obj.getProperties();
+        let geom = props.getPropertyByName(".geom").unwrap();
+        let geom = geom.asCompound().unwrap();
+        let p = geom.getPropertyByName("P").unwrap();
+        let mut out = Vec::new();
+        p.asArray().unwrap().withSample(0, &mut |bytes| out = bytemuck::cast_slice::<u8, glam::Vec3>(bytes).to_vec())?;
+        Ok::<_, Error>(out)
+    };
+
+    // Intact archive: every sample passes verification
+    let intact = IArchive::from_bytes("verify.abc", bytes.as_slice()).unwrap();
+    assert!(!intact.verifies_hashes());
+    assert!(intact.set_verify_hashes(true));
+    assert_eq!(read_positions(&intact).unwrap(), positions);
+
+    // Flip one bit of the first position in the payload
+    let needle: &[u8] = bytemuck::cast_slice(&positions[..1]);
+    let at = bytes.windows(needle.len()).position(|w| w == needle).unwrap();
+    bytes[at] ^= 1;
+
+    let unchecked = IArchive::from_bytes("verify.abc", bytes.as_slice()).unwrap();
+    assert_ne!(read_positions(&unchecked).unwrap(), positions);
+
+    let checked = IArchive::from_bytes("verify.abc", bytes).unwrap();
+    checked.set_verify_hashes(true);
+    match read_positions(&checked) {
+        Err(Error::Corrupt { context, .. }) => assert!(context.contains("digest mismatch"), "{}", context),
+        other => panic!("expected a digest mismatch, got {:?}", other.map(|p| p.len())),
+    }
+}
+
+#[test]
+fn test_unfinished_archive_is_removed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| dir.path().join(name);
+    let mut root = OObject::new("");
+    root.add_child(OObject::new("child"));
+
+    // Early return before write_archive: the partial file goes away
+    let result = (|| -> alembic::Result<()> {
+        let _archive = OArchive::create(path("dropped.abc"))?;
+        Err(alembic::Error::invalid("exporter failed"))
+    })();
+    assert!(result.is_err());
+    assert!(!path("dropped.abc").exists());
+
+    // Written archives are kept, with or without close()
+    {
+        let mut archive = OArchive::create(path("written.abc")).unwrap();
+        archive.write_archive(&root).unwrap();
+    }
+    OArchive::create(path("closed.abc")).unwrap().close().unwrap();
+    for name in ["written.abc", "closed.abc"] {
+        assert!(IArchive::open(path(name)).is_ok(), "{}", name);
+    }
+
+    // abort() discards an unfinished archive, but not one already published
+    OArchive::create(path("aborted.abc")).unwrap().abort();
+    assert!(!path("aborted.abc").exists());
+    let mut archive = OArchive::create(path("published.abc")).unwrap();
+    archive.write_archive(&root).unwrap();
+    archive.abort();
+    assert!(IArchive::open(path("published.abc")).is_ok());
+    std::fs::remove_file(path("published.abc")).unwrap();
+
+    // Two archives for the same target in one process get their own temp files
+    let first = OArchive::create(path("twice.abc")).unwrap();
+    let mut second = OArchive::create(path("twice.abc")).unwrap();
+    second.write_archive(&root).unwrap();
+    drop(first);
+    assert_eq!(IArchive::open(path("twice.abc")).unwrap().getTop().getNumChildren(), 1);
+    std::fs::remove_file(path("twice.abc")).unwrap();
+
+    // In-memory archives have nothing to clean up
+    OArchive::create_in_memory("memory.abc").unwrap().abort();
+
+    // Re-exporting over a good cache: the old file survives a failed or
+    // aborted write, and no temp file is left behind
+    {
+        let mut archive = OArchive::create(path("cache.abc")).unwrap();
+        archive.write_archive(&root).unwrap();
+    }
+    let good = std::fs::read(path("cache.abc")).unwrap();
+    let archive = OArchive::create(path("cache.abc")).unwrap();
+    assert_eq!(std::fs::read(path("cache.abc")).unwrap(), good);
+    archive.abort();
+    drop(OArchive::create(path("cache.abc")).unwrap());
+    assert_eq!(std::fs::read(path("cache.abc")).unwrap(), good);
+    let mut names: Vec<String> = std::fs::read_dir(dir.path()).unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["cache.abc", "closed.abc", "written.abc"]);
+
+    // A finished re-export replaces it
+    let mut archive = OArchive::create(path("cache.abc")).unwrap();
+    archive.write_archive(&OObject::new("")).unwrap();
+    assert_eq!(IArchive::open(path("cache.abc")).unwrap().getTop().getNumChildren(), 0);
+}
+
+/// Build one object per written schema, with a sample so the schema compound exists.
+fn build_schema_objects() -> Vec<(alembic::geom::SchemaInfo, OObject)> {
+    use alembic::geom::SchemaInfo;
+    use alembic::ogawa::writer::{OCollections, OMaterial};
+
+    let tri = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y];
+    let mut xform = OXform::new("xform");
+    xform.add_sample(OXformSample::from_matrix(glam::Mat4::IDENTITY, true));
+    let mut mesh = OPolyMesh::new("mesh");
+    mesh.add_sample(&OPolyMeshSample::new(tri.clone(), vec![3], vec![0, 1, 2]));
+    let mut subd = OSubD::new("subd");
+    subd.add_sample(&OSubDSample::new(tri.clone(), vec![3], vec![0, 1, 2]));
+    let mut curves = OCurves::new("curves");
+    curves.add_sample(&OCurvesSample::new(tri.clone(), vec![3]));
+    let mut points = OPoints::new("points");
+    points.add_sample(&OPointsSample::new(tri.clone(), vec![0, 1, 2]));
+    let mut nupatch = ONuPatch::new("nupatch");
+    let grid = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y, glam::Vec3::ONE];
+    nupatch.add_sample(&ONuPatchSample::new(grid, 2, 2, 2, 2, vec![0.0, 0.0, 1.0, 1.0], vec![0.0, 0.0, 1.0, 1.0]));
+    let mut faceset = OFaceSet::new("faceset");
+    faceset.add_sample(&OFaceSetSample { faces: vec![0] });
+    let mut camera = OCamera::new("camera");
+    camera.add_sample(CameraSample::default());
+    let mut light = OLight::new("light");
+    light.add_camera_sample(CameraSample::default());
+    let mut material = OMaterial::new("material");
+    material.add_shader("arnold", "surface", "standard_surface");
+    let mut collections = OCollections::new("collections");
+    collections.add_collection("set", vec!["/mesh".to_string()]);
+
+    vec![
+        (SchemaInfo::XFORM, xform.build()),
+        (SchemaInfo::POLYMESH, mesh.build()),
+        (SchemaInfo::SUBD, subd.build()),
+        (SchemaInfo::CURVES, curves.build()),
+        (SchemaInfo::POINTS, points.build()),
+        (SchemaInfo::NUPATCH, nupatch.build()),
+        (SchemaInfo::FACESET, faceset.build()),
+        (SchemaInfo::CAMERA, camera.build()),
+        (SchemaInfo::LIGHT, light.build()),
+        (SchemaInfo::MATERIAL, material.build()),
+        (SchemaInfo::COLLECTIONS, collections.build()),
+    ]
+}
+
+#[test]
+fn test_written_schema_metadata_matches_cpp() {
+    // Object and schema-compound metadata exactly as AbcGeom 1.8 writes them
+    let temp = NamedTempFile::new().unwrap();
+    let objects = build_schema_objects();
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let mut root = OObject::new("");
+        for (_, obj) in &objects {
+            root.add_child(obj.clone());
+        }
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    for (info, obj) in &objects {
+        let child = top.getChildByName(&obj.name).unwrap();
+        assert_eq!(
+            child.getMetaData().serialize(),
+            info.object_metadata().serialize(),
+            "{} object metadata", info.schema
+        );
+        assert_eq!(
+            child.getMetaData().get("schemaObjTitle"),
+            Some(format!("{}:{}", info.schema, info.property).as_str())
+        );
+        let props = child.getProperties();
+        let compound = props.getPropertyByName(info.property)
+            .unwrap_or_else(|| panic!("{} compound missing", info.property));
+        assert_eq!(
+            compound.getHeader().meta_data.serialize(),
+            info.compound_metadata().serialize(),
+            "{} compound metadata", info.schema
+        );
+    }
+    assert!(IXform::new(&top.getChildByName("xform").unwrap()).is_some());
+    assert!(IPolyMesh::new(&top.getChildByName("mesh").unwrap()).is_some());
+    assert!(ILight::new(&top.getChildByName("light").unwrap()).is_some());
+}
+
+#[test]
+fn test_schema_version_override() {
+    let temp = NamedTempFile::new().unwrap();
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        archive.set_schema_version_override(XFORM_SCHEMA, "AbcGeom_Xform_v2");
+        archive.set_schema_version_override("AbcGeom_GeomBase_v1", "AbcGeom_GeomBase_v0");
+        assert_eq!(archive.schema_version_overrides().len(), 2);
+
+        let mut root = OObject::new("");
+        for (_, obj) in build_schema_objects().into_iter().take(2) {
+            root.add_child(obj);
+        }
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let xform = top.getChildByName("xform").unwrap();
+    assert_eq!(xform.getMetaData().get("schema"), Some("AbcGeom_Xform_v2"));
+    assert_eq!(xform.getMetaData().get("schemaObjTitle"), Some("AbcGeom_Xform_v2:.xform"));
+    let props = xform.getProperties();
+    let compound = props.getPropertyByName(".xform").unwrap();
+    assert_eq!(compound.getHeader().meta_data.get("schema"), Some("AbcGeom_Xform_v2"));
+
+    // Only the named schema is rewritten; readers stay strict like C++
+    let mesh = top.getChildByName("mesh").unwrap();
+    assert_eq!(mesh.getMetaData().get("schema"), Some(POLYMESH_SCHEMA));
+    assert_eq!(mesh.getMetaData().get("schemaBaseType"), Some("AbcGeom_GeomBase_v0"));
+    assert_eq!(mesh.getMetaData().get("schemaObjTitle"), Some("AbcGeom_PolyMesh_v1:.geom"));
+    assert!(IXform::new(&xform).is_none());
+}
+
+#[test]
+fn test_get_interpolated_sample() {
+    use alembic::core::TimeSampling;
+
+    let temp = NamedTempFile::new().unwrap();
+    let quarter_turn = glam::Mat4::from_scale_rotation_translation(
+        glam::Vec3::splat(2.0),
+        glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+        glam::vec3(10.0, 0.0, 0.0),
+    );
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+
+        let mut xform = OXform::new("xform");
+        xform.set_time_sampling(ts);
+        xform.add_sample(OXformSample::from_matrix(glam::Mat4::IDENTITY, true));
+        xform.add_sample(OXformSample::from_matrix(quarter_turn, true));
+
+        let mut mesh = OPolyMesh::new("mesh");
+        mesh.set_time_sampling(ts);
+        for x in [0.0, 4.0] {
+            let p = vec![glam::vec3(x, 0.0, 0.0), glam::vec3(x + 1.0, 0.0, 0.0), glam::vec3(x, 1.0, 0.0)];
+            mesh.add_sample(&OPolyMeshSample::new(p, vec![3], vec![0, 1, 2]));
+        }
+
+        // Point 7 dies and point 9 is born between the samples; ids reorder
+        let mut points = OPoints::new("points");
+        points.set_time_sampling(ts);
+        points.add_sample(&OPointsSample::new(vec![glam::Vec3::ZERO, glam::Vec3::X], vec![5, 7]));
+        points.add_sample(&OPointsSample::new(vec![glam::Vec3::Y, glam::vec3(2.0, 0.0, 0.0)], vec![9, 5]));
+
+        let mut camera = OCamera::new("camera");
+        camera.set_time_sampling(ts);
+        camera.add_sample(CameraSample { focal_length: 20.0, ..CameraSample::default() });
+        camera.add_sample(CameraSample { focal_length: 60.0, ..CameraSample::default() });
+
+        let mut root = OObject::new("");
+        root.add_child(xform.build());
+        root.add_child(mesh.build());
+        root.add_child(points.build());
+        root.add_child(camera.build());
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let mid = 0.25 / 24.0;
+
+    let obj = top.getChildByName("xform").unwrap();
+    let xform = IXform::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(xform.getTimeSamplingIndex() as usize).unwrap();
+    let m = xform.get_interpolated_sample(mid, ts).unwrap().matrix();
+    let (s, r, t) = m.to_scale_rotation_translation();
+    assert!((s - glam::Vec3::splat(1.25)).length() < 1e-4, "scale {:?}", s);
+    assert!((t - glam::vec3(2.5, 0.0, 0.0)).length() < 1e-4, "translation {:?}", t);
+    let expected = glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_8);
+    assert!(r.angle_between(expected) < 1e-3, "rotation {:?}", r);
+    // Exact sample times return the stored sample untouched
+    assert_eq!(xform.get_interpolated_sample(1.0 / 24.0, ts).unwrap().matrix(), xform.getSample(1).unwrap().matrix());
+
+    let obj = top.getChildByName("mesh").unwrap();
+    let mesh = IPolyMesh::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(mesh.getTimeSamplingIndex() as usize).unwrap();
+    let s = mesh.get_interpolated_sample(mid, ts).unwrap();
+    assert_eq!(s.positions[0], glam::vec3(1.0, 0.0, 0.0));
+    assert_eq!(s.face_indices, vec![0, 1, 2]);
+
+    let obj = top.getChildByName("points").unwrap();
+    let points = IPoints::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(points.getTimeSamplingIndex() as usize).unwrap();
+    let s = points.get_interpolated_sample(mid, ts).unwrap();
+    assert_eq!(s.ids, vec![5, 7]);
+    assert_eq!(s.positions[0], glam::vec3(0.5, 0.0, 0.0));
+    assert_eq!(s.positions[1], glam::Vec3::X);
+
+    let obj = top.getChildByName("camera").unwrap();
+    let camera = ICamera::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(camera.getTimeSamplingIndex() as usize).unwrap();
+    let s = camera.get_interpolated_sample(mid, ts).unwrap();
+    assert!((s.focal_length - 30.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_mesh_motion_positions() {
+    use alembic::core::TimeSampling;
+    use alembic::geom::{mesh_motion_positions, MotionSource};
+
+    let temp = NamedTempFile::new().unwrap();
+    let tri = |x: f32| vec![glam::vec3(x, 0.0, 0.0), glam::vec3(x + 1.0, 0.0, 0.0), glam::vec3(x, 1.0, 0.0)];
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+
+        // Moves +24 units/s in x, no stored velocities
+        let mut moving = OPolyMesh::new("moving");
+        moving.set_time_sampling(ts);
+        for frame in 0..3 {
+            moving.add_sample(&OPolyMeshSample::new(tri(frame as f32), vec![3], vec![0, 1, 2]));
+        }
+
+        // Single sample with stored velocities of 2 units/s in y
+        let mut sample = OPolyMeshSample::new(tri(0.0), vec![3], vec![0, 1, 2]);
+        sample.velocities = Some(vec![glam::vec3(0.0, 2.0, 0.0); 3]);
+        let mut with_v = OPolyMesh::new("with_v");
+        with_v.add_sample(&sample);
+
+        let mut still = OPolyMesh::new("still");
+        still.add_sample(&OPolyMeshSample::new(tri(0.0), vec![3], vec![0, 1, 2]));
+
+        let mut root = OObject::new("");
+        root.add_child(moving.build());
+        root.add_child(with_v.build());
+        root.add_child(still.build());
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let shutter = (-0.25 / 24.0, 0.25 / 24.0);
+
+    let obj = top.getChildByName("moving").unwrap();
+    let mesh = IPolyMesh::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(mesh.getTimeSamplingIndex() as usize).unwrap();
+    let m = mesh_motion_positions(&mesh, ts, 1.0 / 24.0, shutter, 3).unwrap();
+    assert_eq!(m.source, MotionSource::CentralDifference);
+    assert_eq!(m.positions.len(), 3);
+    assert!((m.positions[0][0].x - 0.75).abs() < 1e-5);
+    assert!((m.positions[2][0].x - 1.25).abs() < 1e-5);
+    // One-sided difference at the last sample
+    let m = mesh_motion_positions(&mesh, ts, 2.0 / 24.0, shutter, 2).unwrap();
+    assert!((m.positions[1][0].x - 2.25).abs() < 1e-5);
+
+    let obj = top.getChildByName("with_v").unwrap();
+    let mesh = IPolyMesh::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(mesh.getTimeSamplingIndex() as usize).unwrap();
+    let m = mesh_motion_positions(&mesh, ts, 0.0, (0.0, 0.5), 2).unwrap();
+    assert_eq!(m.source, MotionSource::Velocities);
+    assert_eq!(m.times, vec![0.0, 0.5]);
+    assert_eq!(m.positions[1][2], glam::vec3(0.0, 2.0, 0.0));
+
+    let obj = top.getChildByName("still").unwrap();
+    let mesh = IPolyMesh::new(&obj).unwrap();
+    let ts = archive.getTimeSampling(mesh.getTimeSamplingIndex() as usize).unwrap();
+    let m = mesh_motion_positions(&mesh, ts, 0.0, shutter, 2).unwrap();
+    assert_eq!(m.source, MotionSource::Static);
+    assert_eq!(m.positions[0], m.sample.positions);
+}
+
+#[test]
+fn test_validate_archive() {
+    use alembic::core::{MetaData, TimeSampling};
+    use alembic::validate::{validate_archive, Check, Severity};
+
+    let temp = NamedTempFile::new().unwrap();
+    let tri = vec![glam::vec3(0.0, 0.0, 0.0), glam::vec3(1.0, 0.0, 0.0), glam::vec3(0.0, 1.0, 0.0)];
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let bad_ts = archive.addTimeSampling(TimeSampling::acyclic(vec![0.0, 2.0, 1.0]));
+
+        let mut good = OPolyMesh::new("good");
+        good.add_sample(&OPolyMeshSample::new(tri.clone(), vec![3], vec![0, 1, 2]));
+
+        // Out-of-range index, NaN point, degenerate face and too few UVs,
+        // written on both samples but reported once each
+        let mut points = tri.clone();
+        points[1].y = f32::NAN;
+        let mut sample = OPolyMeshSample::new(points, vec![3, 2], vec![0, 1, 5, 0, 1]);
+        sample.uvs = Some(vec![glam::Vec2::ZERO; 2]);
+        let mut bad = OPolyMesh::new("bad");
+        bad.set_time_sampling(bad_ts);
+        bad.add_sample(&sample);
+        bad.add_sample(&sample);
+
+        let mut meta = MetaData::new();
+        meta.set_schema("AbcGeom_PolyMesh_v9");
+        let future = OObject::new("future").with_meta_data(meta);
+
+        let mut root = OObject::new("");
+        root.add_child(good.build());
+        root.add_child(bad.build());
+        root.add_child(future);
+        archive.write_archive(&root).unwrap();
+    }
+
+    let diagnostics = validate_archive(temp.path());
+    let find = |object: &str, check: Check| {
+        diagnostics.iter().filter(|d| d.object == object && d.check == check).collect::<Vec<_>>()
+    };
+    assert!(diagnostics.iter().all(|d| d.object != "/good"), "{:?}", diagnostics);
+    assert_eq!(find("/", Check::TimeSampling).len(), 1);
+    assert_eq!(find("/bad", Check::Nan).len(), 1);
+    assert_eq!(find("/bad", Check::GeomParam).len(), 1);
+    let topology = find("/bad", Check::Topology);
+    assert_eq!(topology.len(), 2);
+    assert!(topology.iter().any(|d| d.severity == Severity::Error && d.message.contains("face index 5")));
+    assert!(topology.iter().any(|d| d.severity == Severity::Warning && d.sample == Some(0)));
+    let schema = find("/future", Check::Schema);
+    assert_eq!(schema.len(), 1);
+    assert_eq!(schema[0].severity, Severity::Error);
+
+    let missing = validate_archive(temp.path().with_extension("missing"));
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].check, Check::Open);
+}
+
+#[test]
+fn test_roundtrip_subd_scheme_and_boundary_rules() {
+    use alembic::geom::{FaceVaryingInterpolation, InterpolateBoundary, SubDScheme};
+
+    let temp = NamedTempFile::new().unwrap();
+    let quad = vec![glam::vec3(0.0, 0.0, 0.0), glam::vec3(1.0, 0.0, 0.0), glam::vec3(1.0, 1.0, 0.0), glam::vec3(0.0, 1.0, 0.0)];
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let mut subd = OSubD::new("quad");
+        // Rules first set on sample 1: sample 0 is backfilled with the defaults
+        let sample = OSubDSample::new(quad.clone(), vec![4], vec![0, 1, 2, 3]).with_scheme("loop");
+        subd.add_sample(&sample);
+        let mut sample = OSubDSample::new(quad.clone(), vec![4], vec![0, 1, 2, 3]).with_scheme("loop");
+        sample.interp_boundary = Some(InterpolateBoundary::EdgeOnly);
+        sample.fv_interp_boundary = Some(FaceVaryingInterpolation::Boundaries);
+        sample.holes = Some(vec![0]);
+        subd.add_sample(&sample);
+        // Unset rules keep the previous sample
+        subd.add_sample(&OSubDSample::new(quad, vec![4], vec![0, 1, 2, 3]).with_scheme("loop"));
+        let mut root = OObject::new("");
+        root.add_child(subd.build());
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let obj = top.getChildByName("quad").unwrap();
+    let subd = ISubD::new(&obj).unwrap();
+    let s0 = subd.getSample(0).unwrap();
+    assert_eq!(s0.scheme, SubDScheme::Loop);
+    assert_eq!(s0.interp_boundary, InterpolateBoundary::None);
+    assert_eq!(s0.fv_interp_boundary, FaceVaryingInterpolation::All);
+    assert!(s0.holes.is_empty());
+    for i in 1..3 {
+        let s = subd.getSample(i).unwrap();
+        assert_eq!(s.scheme, SubDScheme::Loop);
+        assert_eq!(s.interp_boundary, InterpolateBoundary::EdgeOnly);
+        assert_eq!(s.fv_interp_boundary, FaceVaryingInterpolation::Boundaries);
+    }
+    assert_eq!(subd.getSample(1).unwrap().holes, vec![0]);
+}
+
+#[test]
+fn test_auto_child_bounds() {
+    use alembic::core::TimeSampling;
+    use alembic::geom::XformOp;
+
+    let temp = NamedTempFile::new().unwrap();
+    let tri = |x: f32| vec![glam::vec3(x, 0.0, 0.0), glam::vec3(x + 1.0, 0.0, 0.0), glam::vec3(x, 1.0, 0.0)];
+    let build = |name: &str, auto: bool, ts: u32| {
+        // grp (translate 10) -> inner (scale 2) -> mesh moving +1 in x per sample
+        let mut mesh = OPolyMesh::new("mesh");
+        mesh.set_time_sampling(ts);
+        for frame in 0..2 {
+            mesh.add_sample(&OPolyMeshSample::new(tri(frame as f32), vec![3], vec![0, 1, 2]));
+        }
+        let mut inner = OXform::new("inner");
+        inner.add_sample(OXformSample::from_ops(vec![XformOp::scale(2.0, 2.0, 2.0)], true));
+        inner.add_child(mesh.build());
+        let mut grp = OXform::new(name);
+        grp.set_compute_child_bounds(auto);
+        grp.add_sample(OXformSample::from_ops(vec![XformOp::translate(10.0, 0.0, 0.0)], true));
+        grp.add_child(inner.build());
+        grp.build()
+    };
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let mut root = OObject::new("");
+        root.add_child(build("auto", true, ts));
+        root.add_child(build("manual", false, ts));
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let auto = top.getChildByName("auto").unwrap();
+    let xform = IXform::new(&auto).unwrap();
+    assert_eq!(xform.child_bounds_num_samples(), 2);
+    assert_eq!(xform.child_bounds_time_sampling_index(), 1);
+    // Child scale applied, own translate not
+    let b = xform.child_bounds(1).unwrap();
+    assert_eq!((b.min, b.max), (glam::dvec3(2.0, 0.0, 0.0), glam::dvec3(4.0, 2.0, 0.0)));
+
+    let manual = top.getChildByName("manual").unwrap();
+    assert!(!IXform::new(&manual).unwrap().has_child_bounds());
+    // Computed on demand when not stored
+    for obj in [&auto, &manual] {
+        let b = obj.bounds_at(&archive, 1.0 / 24.0).unwrap();
+        assert_eq!((b.min, b.max), (glam::dvec3(2.0, 0.0, 0.0), glam::dvec3(4.0, 2.0, 0.0)));
+    }
+    let b = top.bounds_at(&archive, 0.0).unwrap();
+    assert_eq!((b.min, b.max), (glam::dvec3(10.0, 0.0, 0.0), glam::dvec3(12.0, 2.0, 0.0)));
+}
+
+#[test]
+fn test_instancing_roundtrip() {
+    let temp = NamedTempFile::new().unwrap();
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let mut tree = OPolyMesh::new("tree");
+        tree.add_sample(&OPolyMeshSample::new(
+            vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y],
+            vec![3],
+            vec![0, 1, 2],
+        ));
+        let mut proto = OXform::new("proto");
+        proto.add_sample(OXformSample::identity());
+        proto.add_child(tree.build());
+
+        let mut forest = OObject::new("forest");
+        forest.add_instance_named("tree_0", "/proto/tree");
+        forest.add_instance("/proto/tree");
+        forest.add_instance_named("grove", "/proto");
+        // Instancing an ancestor would recurse forever; read as a plain object
+        forest.add_instance_named("loop", "/forest");
+        assert!(forest.children[0].is_instance());
+
+        let mut root = OObject::new("");
+        root.add_child(proto.build());
+        root.add_child(forest);
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let forest = top.getChildByName("forest").unwrap();
+    assert_eq!(forest.getNumChildren(), 4);
+    assert!(forest.isChildInstance(0));
+    assert!(forest.isChildInstanceByName("tree"));
+
+    let source = archive.findObject("/proto/tree").unwrap();
+    assert!(!source.is_instance());
+    assert_eq!(source.instance_source_path(), None);
+
+    let inst = forest.getChildByName("tree_0").unwrap();
+    assert!(inst.is_instance());
+    assert!(inst.isInstanceDescendant());
+    assert_eq!(inst.instance_source_path(), Some("/proto/tree"));
+    assert_eq!(inst.getFullName(), "/forest/tree_0");
+    assert!(inst.getProperties().getPropertyByName(".instanceSource").is_none());
+    let mesh = IPolyMesh::new(&inst).expect("instance reads as its source schema");
+    assert_eq!(mesh.getSample(0).unwrap().positions[1], glam::Vec3::X);
+
+    let grove = archive.findObject("/forest/grove").unwrap();
+    assert_eq!(grove.instance_source_path(), Some("/proto"));
+    assert!(IXform::new(&grove).is_some());
+    let child = archive.findObject("/forest/grove/tree").unwrap();
+    assert_eq!(child.getFullName(), "/forest/grove/tree");
+    assert!(!child.is_instance());
+    assert!(child.isInstanceDescendant());
+    assert!(IPolyMesh::new(&child).is_some());
+
+    let looped = forest.getChildByName("loop").unwrap();
+    assert!(!looped.is_instance());
+    assert_eq!(looped.getNumChildren(), 0);
+}
+
+#[test]
+fn test_points_attributes_roundtrip() {
+    use alembic::util::{DataType, Error};
+
+    let temp = NamedTempFile::new().unwrap();
+    let positions = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y];
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let mut points = OPoints::new("particles");
+
+        let mut s0 = OPointsSample::new(positions.clone(), vec![0, 1, 2]);
+        s0.set_attribute("Cd", &[glam::Vec3::X, glam::Vec3::Y, glam::Vec3::Z]);
+        s0.set_attribute("orient", &[glam::Quat::IDENTITY, glam::Quat::from_rotation_z(1.0), glam::Quat::IDENTITY]);
+        s0.set_attribute("pscale", &[0.5f32, 1.0, 2.0]);
+        s0.set_attribute("age", &[0.0f32, 0.1, 0.2]);
+        s0.set_attribute("id2", &[7i32, 8, 9]);
+        points.add_sample(&s0);
+
+        // `Cd`, `orient`, `pscale` and `id2` keep their previous sample
+        let mut s1 = OPointsSample::new(positions.clone(), vec![0, 1, 2]);
+        s1.set_attribute("age", &[1.0f32, 1.1, 1.2]);
+        points.add_sample(&s1);
+
+        let mut root = OObject::new("");
+        root.add_child(points.build());
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let obj = top.getChildByName("particles").unwrap();
+    let points = IPoints::new(&obj).unwrap();
+    let attrs = points.attributes();
+
+    assert_eq!(attrs.names(), vec!["Cd", "orient", "pscale", "age", "id2"]);
+    assert!(attrs.has("orient"));
+    assert!(!attrs.has("v"));
+    assert_eq!(attrs.data_type("orient"), Some(DataType::VEC4F));
+    assert_eq!(attrs.num_samples("age"), 2);
+    assert_eq!(attrs.num_samples("Cd"), 1);
+
+    assert_eq!(attrs.colors(1).unwrap(), vec![glam::Vec3::X, glam::Vec3::Y, glam::Vec3::Z]);
+    let orient = attrs.orientations(0).unwrap();
+    assert!(orient[1].abs_diff_eq(glam::Quat::from_rotation_z(1.0), 1e-6));
+    assert_eq!(attrs.scales(0).unwrap()[2], glam::Vec3::splat(2.0));
+    assert_eq!(attrs.ages(0).unwrap(), vec![0.0, 0.1, 0.2]);
+    assert_eq!(attrs.ages(1).unwrap(), vec![1.0, 1.1, 1.2]);
+    assert_eq!(attrs.get::<i32>("id2", 1).unwrap(), Some(vec![7, 8, 9]));
+    assert_eq!(attrs.get::<f32>("missing", 0).unwrap(), None);
+    assert!(matches!(attrs.get::<f32>("Cd", 0), Err(Error::TypeMismatch { .. })));
+
+    // Core schema data is unaffected
+    assert_eq!(points.getSample(1).unwrap().positions, positions);
+}
+
+#[test]
+fn test_roundtrip_curves_variable_order_periodic() {
+    let temp = NamedTempFile::new().unwrap();
+    // A closed cubic (Maya style: first 3 CVs repeated) and an open quadratic
+    let square = [glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::new(1.0, 1.0, 0.0), glam::Vec3::Y];
+    let mut positions: Vec<glam::Vec3> = square.iter().chain(&square[..3]).copied().collect();
+    positions.extend([glam::Vec3::ZERO, glam::Vec3::Z, glam::Vec3::new(0.0, 1.0, 1.0)]);
+    let mut knots: Vec<f32> = (0..11).map(|k| k as f32).collect();
+    knots.extend([0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    {
+        let mut archive = OArchive::create(temp.path()).unwrap();
+        let mut curves = OCurves::new("groom");
+        let sample = OCurvesSample::new(positions.clone(), vec![7, 3])
+            .with_wrap(CurvePeriodicity::Periodic)
+            .with_nurbs(vec![4, 3], knots.clone());
+        curves.try_add_sample(&sample).unwrap();
+
+        // Knot counts must match nVertices + order
+        let bad = OCurvesSample::new(positions.clone(), vec![7, 3]).with_nurbs(vec![4, 3], knots[..16].to_vec());
+        assert!(curves.try_add_sample(&bad).unwrap_err().to_string().contains("16 knots; expected 17"));
+
+        let mut root = OObject::new("");
+        root.add_child(curves.build());
+        archive.write_archive(&root).unwrap();
+    }
+
+    let archive = IArchive::open(temp.path()).unwrap();
+    let top = archive.getTop();
+    let obj = top.getChildByName("groom").unwrap();
+    // Orders are a uchar array, as in C++
+    let props = obj.getProperties();
+    let geom_prop = props.getPropertyByName(".geom").unwrap();
+    let geom = geom_prop.asCompound().unwrap();
+    let orders_prop = geom.getPropertyByName(".orders").unwrap();
+    assert_eq!(orders_prop.getHeader().data_type.pod, PlainOldDataType::Uint8);
+
+    let s = ICurves::new(&obj).unwrap().getSample(0).unwrap();
+    assert_eq!(s.curve_type, CurveType::VariableOrder);
+    assert_eq!(s.wrap, CurvePeriodicity::Periodic);
+    assert_eq!(s.orders, vec![4, 3]);
+    assert_eq!(s.knots, knots);
+    assert!(s.validate().is_ok());
+
+    let strands: Vec<_> = s.strands().collect();
+    assert_eq!(strands[1].order, 3);
+    assert_eq!(strands[1].knots, &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    let closed = strands[0].nurbs_points(8);
+    assert!(closed[0].abs_diff_eq(*closed.last().unwrap(), 1e-5));
+}
+
+#[test]
+fn test_layered_archive() {
+    use alembic::core::TimeSampling;
+    use alembic::ogawa::{set_layer_prune, set_layer_replace};
+    use alembic::util::DataType;
+    let base = NamedTempFile::new().expect("Failed to create temp file");
+    let layer = NamedTempFile::new().expect("Failed to create temp file");
+    let tri = |x: f32| vec![glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::X, glam::Vec3::Y];
+
+    // Base: animated mesh, a camera rig and an object the layer prunes
+    {
+        let mut archive = OArchive::create(base.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 1.0));
+        let mut root = OObject::new("");
+        let mut mesh = OPolyMesh::new("mesh");
+        mesh.set_time_sampling(ts);
+        for frame in 0..3 {
+            mesh.add_sample(&OPolyMeshSample::new(tri(frame as f32), vec![3], vec![0, 1, 2]));
+        }
+        root.add_child(mesh.build());
+        let mut rig = OObject::new("rig");
+        rig.add_child(OObject::new("old_cam"));
+        root.add_child(rig);
+        root.add_child(OObject::new("gone"));
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    // Layer: overrides P, adds a property and an object, prunes and replaces
+    {
+        let mut archive = OArchive::create(layer.path()).expect("Failed to create archive");
+        let ts30 = archive.addTimeSampling(TimeSampling::uniform(1.0 / 30.0, 0.0));
+        let mut root = OObject::new("");
+
+        let mut mesh = OObject::new("mesh");
+        let mut geom = OProperty::compound(".geom");
+        let mut p = OProperty::array("P", DataType::VEC3F);
+        p.add_array_pod(&tri(10.0));
+        geom.add_child(p);
+        mesh.properties.push(geom);
+        let mut lod = OProperty::scalar("lod", DataType::new(PlainOldDataType::Int32, 1)).with_time_sampling(ts30);
+        lod.add_scalar_pod(&2i32);
+        mesh.properties.push(lod);
+        root.add_child(mesh);
+
+        let mut gone = OObject::new("gone");
+        set_layer_prune(&mut gone.meta_data, true);
+        root.add_child(gone);
+
+        let mut rig = OObject::new("rig");
+        set_layer_replace(&mut rig.meta_data, true);
+        rig.add_child(OObject::new("new_cam"));
+        root.add_child(rig);
+
+        root.add_child(OObject::new("extra"));
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open_layered(&[base.path(), layer.path()]).expect("Failed to open layers");
+    let top = archive.getTop();
+    let names: Vec<String> = top.getChildren().map(|c| c.getName().to_string()).collect();
+    assert_eq!(names, ["mesh", "rig", "extra"]);
+    let rig = top.getChildByName("rig").expect("rig");
+    assert_eq!(rig.getNumChildren(), 1);
+    assert!(rig.getChildByName("new_cam").is_some());
+
+    // Schema from the base, P from the layer, topology from the base
+    let mesh_obj = archive.findObject("/mesh").expect("mesh");
+    let mesh = IPolyMesh::new(&mesh_obj).expect("IPolyMesh");
+    let sample = mesh.getSample(0).expect("sample");
+    assert_eq!(sample.positions[0].x, 10.0);
+    assert_eq!(sample.face_indices, vec![0, 1, 2]);
+
+    // Time samplings pooled: identity, base 24fps, layer 30fps
+    assert_eq!(archive.getNumTimeSamplings(), 3);
+    let props = mesh_obj.getProperties();
+    let lod = props.getPropertyByName("lod").expect("lod");
+    assert_eq!(lod.getHeader().time_sampling_index, 2);
+    assert_eq!(lod.getTimeSampling().expect("ts").time_per_cycle(), 1.0 / 30.0);
+    assert_eq!(lod.asScalar().expect("scalar").getRawSample(0).expect("sample"), 2i32.to_le_bytes());
+}
+
+#[test]
+fn test_material_network_roundtrip() {
+    use alembic::material::{get_material_assignment, IMaterial, ShaderNode, ShaderParam, ShaderParamValue};
+    use alembic::ogawa::writer::{add_material_assignment, OMaterial, OMaterialSample};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let look = || {
+        let mut sample = OMaterialSample::new();
+        sample.add_shader("arnold", "displacement", "disp");
+        sample.add_param("arnold", "displacement", ShaderParam::new("height", ShaderParamValue::Float(0.25)));
+
+        let mut tex = ShaderNode::new("tex", "image", "arnold");
+        tex.add_param(ShaderParam::new("filename", ShaderParamValue::String("wood.tx".into())));
+        tex.add_param(ShaderParam::new("uv_xform", ShaderParamValue::Matrix(glam::Mat4::from_scale(glam::Vec3::splat(2.0)))));
+        let mut surf = ShaderNode::new("surf", "standard_surface", "arnold");
+        surf.add_param(ShaderParam::new("base_color", ShaderParamValue::Color3(glam::vec3(0.8, 0.5, 0.2))));
+        surf.connect("base_color", "tex", "out_color");
+        sample.add_network_node(tex);
+        sample.add_network_node(surf);
+        sample.set_network_terminal("arnold", "surface", "surf", "out");
+        sample.set_interface_mapping("wood_map", "tex", "filename");
+        sample.add_interface_param(ShaderParam::new("wood_map", ShaderParamValue::String("wood.tx".into())));
+        sample
+    };
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let mut root = OObject::new("");
+        let mut materials = OObject::new("materials");
+        let mut base = OMaterial::new("base");
+        base.add_shader("arnold", "volume", "fog");
+        materials.add_child(base.build());
+        let mut wood = OMaterial::new("wood");
+        let mut sample = look();
+        sample.set_inherits("/materials/base");
+        wood.set_sample(sample);
+        materials.add_child(wood.build());
+        root.add_child(materials);
+
+        let tri = vec![glam::Vec3::ZERO, glam::Vec3::X, glam::Vec3::Y];
+        let mut mesh = OPolyMesh::new("table");
+        mesh.add_sample(&OPolyMeshSample::new(tri.clone(), vec![3], vec![0, 1, 2]));
+        let mut table = mesh.build();
+        add_material_assignment(&mut table, "/materials/wood");
+        root.add_child(table);
+
+        // A material embedded straight on the geometry
+        let mut mesh = OPolyMesh::new("chair");
+        mesh.add_sample(&OPolyMeshSample::new(tri, vec![3], vec![0, 1, 2]));
+        let mut chair = mesh.build();
+        chair.properties.push(look().into_compound());
+        root.add_child(chair);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(temp.path()).expect("Failed to open archive");
+    let table = archive.findObject("/table").expect("table");
+    assert_eq!(get_material_assignment(&table).as_deref(), Some("/materials/wood"));
+    assert!(IMaterial::embedded(&table).is_none());
+
+    let wood_obj = archive.findObject("/materials/wood").expect("wood");
+    let chair = archive.findObject("/chair").expect("chair");
+    for material in [IMaterial::new(&wood_obj).expect("material"), IMaterial::embedded(&chair).expect("embedded")] {
+        assert_eq!(material.target_names(), ["arnold"]);
+        assert_eq!(material.shader("arnold", "displacement").as_deref(), Some("disp"));
+        assert_eq!(material.network_node_names(), ["tex", "surf"]);
+        assert_eq!(material.network_terminal("arnold", "surface"), Some(("surf".into(), "out".into())));
+        assert_eq!(material.interface_mappings(), [("wood_map".into(), "tex".into(), "filename".into())]);
+
+        let tex = material.network_node("tex").expect("tex");
+        assert_eq!((tex.shader_type.as_str(), tex.target.as_str()), ("image", "arnold"));
+        assert_eq!(tex.param("filename").and_then(|p| p.as_string()), Some("wood.tx"));
+        assert!(matches!(tex.param("uv_xform").map(|p| &p.value), Some(ShaderParamValue::Matrix(m)) if m.x_axis.x == 2.0));
+        let surf = material.network_node("surf").expect("surf");
+        assert!(matches!(surf.param("base_color").map(|p| &p.value), Some(ShaderParamValue::Color3(_))));
+        assert_eq!(surf.connections.get("base_color"), Some(&("tex".to_string(), "out_color".to_string())));
+
+        let flat = material.flatten();
+        let network = flat.network("arnold").expect("network");
+        assert_eq!(network.surface_shader().map(|n| n.name.as_str()), Some("surf"));
+        assert_eq!(network.displacement_shader().and_then(|n| n.param("height")).and_then(|p| p.as_float()), Some(0.25));
+        assert_eq!(flat.interface_param("wood_map").and_then(|p| p.as_string()), Some("wood.tx"));
+        assert_eq!(flat.interface_mappings.get("wood_map"), Some(&("tex".to_string(), "filename".to_string())));
+    }
+    let wood = IMaterial::new(&wood_obj).expect("material");
+    assert_eq!(wood.inherits_path().as_deref(), Some("/materials/base"));
+}
+
+#[test]
+fn test_collections_animated_membership() {
+    use alembic::collection::ICollections;
+    use alembic::core::{SampleSelector, TimeSampling};
+    use alembic::ogawa::writer::{OCollections, OCollectionsSample};
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    let paths = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let mut sets = OCollections::new("sets");
+        sets.set_time_sampling(ts);
+        sets.add_collection("static", paths(&["/set"]));
+        let frames: [&[(&str, &[&str])]; 3] = [
+            &[("fg", &["/hero"])],
+            &[("fg", &["/hero", "/prop"]), ("bg", &["/set"])],
+            &[("fg", &["/prop"])],
+        ];
+        for frame in frames {
+            let mut sample = OCollectionsSample::new();
+            for (name, members) in frame {
+                sample.add_collection(name, paths(members));
+            }
+            sets.add_sample(&sample);
+        }
+        let mut root = OObject::new("");
+        root.add_child(sets.build());
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(temp.path()).expect("Failed to open archive");
+    let obj = archive.findObject("/sets").expect("sets");
+    let sets = ICollections::new(&obj).expect("collections");
+    assert_eq!(sets.collection_names(), ["static", "fg", "bg"]);
+    assert_eq!((sets.num_samples("static"), sets.time_sampling_index("static")), (1, Some(0)));
+    assert!(sets.is_constant("static"));
+    assert_eq!((sets.num_samples("fg"), sets.time_sampling_index("fg")), (3, Some(1)));
+    assert!(!sets.is_constant("fg"));
+
+    let members = |name: &str, sel: SampleSelector| sets.get_sample(name, sel).expect("collection").paths;
+    assert_eq!(members("fg", SampleSelector::Index(1)), ["/hero", "/prop"]);
+    assert_eq!(members("fg", SampleSelector::time_floor(2.0 / 24.0 + 0.001)), ["/prop"]);
+    // Empty before it appears, held once it's missing from a sample
+    assert!(members("bg", SampleSelector::Index(0)).is_empty());
+    assert_eq!(members("bg", SampleSelector::Index(2)), ["/set"]);
+    assert_eq!(members("static", SampleSelector::time_floor(1.0)), ["/set"]);
+    assert_eq!(sets.all_at(SampleSelector::Index(2)).iter().map(|c| c.len()).collect::<Vec<_>>(), [1, 1, 1]);
+}
+
+#[test]
+fn test_light_typed_params() {
+    use alembic::core::TimeSampling;
+    use alembic::geom::{LightSample, LightType};
+    use alembic::util::DataType;
+
+    let temp = NamedTempFile::new().expect("Failed to create temp file");
+    {
+        let mut archive = OArchive::create(temp.path()).expect("Failed to create archive");
+        let ts = archive.addTimeSampling(TimeSampling::uniform(1.0 / 24.0, 0.0));
+        let mut spot = OLight::new("spot");
+        spot.set_time_sampling(ts);
+        let mut sample = LightSample::new();
+        sample.light_type = LightType::Spot;
+        sample.intensity = Some(2.5);
+        sample.color = Some(glam::vec3(1.0, 0.5, 0.25));
+        sample.cone_angle = Some(40.0);
+        spot.add_sample(&sample);
+        sample.intensity = Some(5.0);
+        sample.color = None;
+        spot.add_sample(&sample);
+
+        // A DCC-style light: parameters under exporter-specific names.
+        let mut sun = OLight::new("sun");
+        sun.add_camera_sample(CameraSample::default());
+        let mut sun = sun.build();
+        let mut user = OProperty::compound(".userProperties");
+        let mut light_type = OProperty::scalar("light_type", DataType::STRING);
+        light_type.add_scalar_string("directionalLight");
+        user.add_child(light_type);
+        let mut intensity = OProperty::scalar("light_intensity", DataType::new(PlainOldDataType::Float64, 1));
+        intensity.add_scalar_sample(bytemuck::bytes_of(&3.0f64));
+        user.add_child(intensity);
+        let geom = sun.properties.iter_mut().find(|p| p.name == ".geom").expect(".geom");
+        geom.add_child(user);
+
+        let mut root = OObject::new("");
+        root.add_child(spot.build());
+        root.add_child(sun);
+        archive.write_archive(&root).expect("Failed to write archive");
+    }
+
+    let archive = IArchive::open(temp.path()).expect("Failed to open archive");
+    let obj = archive.findObject("/spot").expect("spot");
+    let spot = ILight::new(&obj).expect("light");
+    assert_eq!(spot.getNumSamples(), 2);
+    let first = spot.getSample(0).expect("sample 0");
+    assert_eq!(first.light_type, LightType::Spot);
+    assert_eq!(first.intensity, Some(2.5));
+    assert_eq!(first.color, Some(glam::vec3(1.0, 0.5, 0.25)));
+    assert_eq!(first.cone_angle, Some(40.0));
+    let second = spot.getSample(1).expect("sample 1");
+    assert_eq!(second.intensity, Some(5.0));
+    assert_eq!(second.color, Some(glam::Vec3::ONE), "unset color is written as white");
+
+    let obj = archive.findObject("/sun").expect("sun");
+    let sun = ILight::new(&obj).expect("light").getSample(0).expect("sample");
+    assert_eq!(sun.light_type, LightType::Distant);
+    assert_eq!(sun.intensity, Some(3.0));
+    assert!(sun.color.is_none() && sun.cone_angle.is_none());
+}